@@ -54,5 +54,5 @@ mod verbosity;
 pub use context::{ContextArgs, ContextMode, merge_context_flags};
 pub use output::{OutputArgs, OutputFormat};
 pub use pagination::PaginationArgs;
-pub use show::ShowComponent;
+pub use show::{SHOW_REGISTRY, ShowComponent, ShowComponentParser, ShowSet, ShowSpec};
 pub use verbosity::Verbosity;
@@ -31,6 +31,9 @@ pub enum OutputFormat {
     Jsonl,
     /// Raw content without any formatting.
     Raw,
+    /// Markdown outline (search `--headings-only`; other output renders as text).
+    #[value(alias = "md")]
+    Markdown,
 }
 
 impl OutputFormat {
@@ -66,6 +69,7 @@ impl std::fmt::Display for OutputFormat {
             Self::Json => write!(f, "json"),
             Self::Jsonl => write!(f, "jsonl"),
             Self::Raw => write!(f, "raw"),
+            Self::Markdown => write!(f, "markdown"),
         }
     }
 }
@@ -101,7 +105,7 @@ impl std::fmt::Display for OutputFormat {
 /// ```
 #[derive(Args, Clone, Debug, Default, PartialEq, Eq)]
 pub struct OutputArgs {
-    /// Output format (text, json, jsonl, raw).
+    /// Output format (text, json, jsonl, raw, markdown).
     ///
     /// Defaults to text for terminals, json for pipes.
     #[arg(
@@ -1,7 +1,8 @@
 //! Show component argument types for CLI commands.
 //!
 //! This module provides the `ShowComponent` enum for specifying which
-//! additional columns to display in search results.
+//! additional columns to display in search results, and the registry that
+//! describes each one.
 //!
 //! # Design
 //!
@@ -13,6 +14,11 @@
 //! blz query "useEffect" --show rank,url,lines
 //! ```
 //!
+//! Every component has one [`ShowSpec`] in [`SHOW_REGISTRY`]: its CLI name,
+//! the JSON field carrying the same value on each hit, and a description.
+//! Parsing, help text, preferences, and history all go through the registry,
+//! so a new component is added in one place.
+//!
 //! # Available Components
 //!
 //! - `rank` - Include global rank prefix (1., 2., ...)
@@ -20,7 +26,15 @@
 //! - `lines` - Prefix snippet lines with line numbers
 //! - `anchor` - Show hashed section anchor above snippet
 //! - `raw-score` - Show raw BM25 scores instead of percentages
+//! - `checksum` - Show the checksum of the source version that matched
+//! - `tokens` - Show the estimated token count of the snippet
+//! - `context` - Show the line range covered by `-C`/`--block` context
+
+use std::ffi::OsStr;
+use std::str::FromStr;
 
+use clap::builder::{PossibleValue, TypedValueParser};
+use clap::error::ErrorKind;
 use serde::{Deserialize, Serialize};
 
 /// Additional columns that can be displayed in text search results.
@@ -30,7 +44,7 @@ use serde::{Deserialize, Serialize};
 /// ```bash
 /// blz query "react hooks" --show rank,url
 /// ```
-#[derive(Clone, Copy, Debug, Eq, PartialEq, clap::ValueEnum, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub enum ShowComponent {
     /// Include the global rank prefix (1., 2., ...).
@@ -42,19 +56,233 @@ pub enum ShowComponent {
     /// Show the hashed section anchor above the snippet.
     Anchor,
     /// Show raw BM25 scores instead of percentages.
-    #[value(name = "raw-score")]
     RawScore,
+    /// Show the checksum of the source version that matched.
+    Checksum,
+    /// Show the estimated token count of the snippet.
+    Tokens,
+    /// Show the line range covered by context expansion.
+    Context,
+}
+
+/// Registry entry describing one `--show` component.
+#[derive(Debug)]
+pub struct ShowSpec {
+    /// Component this entry describes.
+    pub component: ShowComponent,
+    /// Name accepted by `--show` and stored in preferences and history.
+    pub name: &'static str,
+    /// JSON field on each hit that carries the same value, if any.
+    pub field: Option<&'static str>,
+    /// One-line description used in help and error messages.
+    pub summary: &'static str,
+}
+
+/// Every `--show` component, in display order.
+pub const SHOW_REGISTRY: &[ShowSpec] = &[
+    ShowSpec {
+        component: ShowComponent::Rank,
+        name: "rank",
+        field: None,
+        summary: "global rank prefix (always shown)",
+    },
+    ShowSpec {
+        component: ShowComponent::Url,
+        name: "url",
+        field: Some("sourceUrl"),
+        summary: "source URL below each result",
+    },
+    ShowSpec {
+        component: ShowComponent::Lines,
+        name: "lines",
+        field: Some("lines"),
+        summary: "line numbers before snippet lines",
+    },
+    ShowSpec {
+        component: ShowComponent::Anchor,
+        name: "anchor",
+        field: Some("anchor"),
+        summary: "stable section anchor",
+    },
+    ShowSpec {
+        component: ShowComponent::RawScore,
+        name: "raw-score",
+        field: Some("rawScore"),
+        summary: "raw BM25 score instead of a percentage",
+    },
+    ShowSpec {
+        component: ShowComponent::Checksum,
+        name: "checksum",
+        field: Some("checksum"),
+        summary: "checksum of the source version that matched",
+    },
+    ShowSpec {
+        component: ShowComponent::Tokens,
+        name: "tokens",
+        field: Some("tokens"),
+        summary: "estimated token count of the snippet",
+    },
+    ShowSpec {
+        component: ShowComponent::Context,
+        name: "context",
+        field: Some("context"),
+        summary: "line range covered by -C/--block context",
+    },
+];
+
+impl ShowComponent {
+    /// Registry entry for this component.
+    #[must_use]
+    pub fn spec(self) -> &'static ShowSpec {
+        SHOW_REGISTRY
+            .iter()
+            .find(|spec| spec.component == self)
+            .unwrap_or(&SHOW_REGISTRY[0])
+    }
+
+    /// Name accepted by `--show`.
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        self.spec().name
+    }
+
+    const fn bit(self) -> u16 {
+        1 << self as u16
+    }
 }
 
 impl std::fmt::Display for ShowComponent {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Self::Rank => write!(f, "rank"),
-            Self::Url => write!(f, "url"),
-            Self::Lines => write!(f, "lines"),
-            Self::Anchor => write!(f, "anchor"),
-            Self::RawScore => write!(f, "raw-score"),
+        f.write_str(self.name())
+    }
+}
+
+/// Error for a `--show` value that names no registered component.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownShowComponent {
+    name: String,
+}
+
+impl std::fmt::Display for UnknownShowComponent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unknown show component '{}'", self.name)?;
+        if let Some(suggestion) = closest_name(&self.name) {
+            write!(f, " (did you mean '{suggestion}'?)")?;
+        }
+        let names: Vec<&str> = SHOW_REGISTRY.iter().map(|spec| spec.name).collect();
+        write!(f, "; available: {}", names.join(", "))
+    }
+}
+
+impl std::error::Error for UnknownShowComponent {}
+
+impl FromStr for ShowComponent {
+    type Err = UnknownShowComponent;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let wanted = s.trim().to_ascii_lowercase().replace('_', "-");
+        SHOW_REGISTRY
+            .iter()
+            .find(|spec| spec.name == wanted)
+            .map(|spec| spec.component)
+            .ok_or_else(|| UnknownShowComponent {
+                name: s.trim().to_string(),
+            })
+    }
+}
+
+/// Registered name closest to `input`, if it is a plausible typo.
+fn closest_name(input: &str) -> Option<&'static str> {
+    let input = input.to_ascii_lowercase();
+    SHOW_REGISTRY
+        .iter()
+        .map(|spec| (edit_distance(&input, spec.name), spec.name))
+        .filter(|(distance, name)| *distance <= 2.max(name.len() / 3))
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, name)| name)
+}
+
+/// Levenshtein distance between two short ASCII strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != *cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
+/// Clap value parser for `--show`, backed by [`SHOW_REGISTRY`].
+///
+/// Lists every component in help and completions, and reports unknown
+/// names with the closest match.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ShowComponentParser;
+
+impl TypedValueParser for ShowComponentParser {
+    type Value = ShowComponent;
+
+    fn parse_ref(
+        &self,
+        cmd: &clap::Command,
+        _arg: Option<&clap::Arg>,
+        value: &OsStr,
+    ) -> Result<Self::Value, clap::Error> {
+        let value = value
+            .to_str()
+            .ok_or_else(|| clap::Error::new(ErrorKind::InvalidUtf8).with_cmd(cmd))?;
+        value.parse().map_err(|err: UnknownShowComponent| {
+            clap::Error::raw(ErrorKind::InvalidValue, format!("{err}\n")).with_cmd(cmd)
+        })
+    }
+
+    fn possible_values(&self) -> Option<Box<dyn Iterator<Item = PossibleValue> + '_>> {
+        Some(Box::new(SHOW_REGISTRY.iter().map(|spec| {
+            PossibleValue::new(spec.name).help(spec.summary)
+        })))
+    }
+}
+
+/// Set of enabled `--show` components.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ShowSet(u16);
+
+impl ShowSet {
+    /// Build a set from parsed components; duplicates are ignored.
+    #[must_use]
+    pub fn from_components(components: &[ShowComponent]) -> Self {
+        let mut set = Self::default();
+        for component in components {
+            set.insert(*component);
         }
+        set
+    }
+
+    /// Enable a component.
+    pub const fn insert(&mut self, component: ShowComponent) {
+        self.0 |= component.bit();
+    }
+
+    /// Whether a component is enabled.
+    #[must_use]
+    pub const fn contains(self, component: ShowComponent) -> bool {
+        self.0 & component.bit() != 0
+    }
+
+    /// Enabled components in registry order.
+    #[must_use]
+    pub fn components(self) -> Vec<ShowComponent> {
+        SHOW_REGISTRY
+            .iter()
+            .map(|spec| spec.component)
+            .filter(|component| self.contains(*component))
+            .collect()
     }
 }
 
@@ -70,26 +298,70 @@ mod tests {
         assert_eq!(ShowComponent::Lines.to_string(), "lines");
         assert_eq!(ShowComponent::Anchor.to_string(), "anchor");
         assert_eq!(ShowComponent::RawScore.to_string(), "raw-score");
+        assert_eq!(ShowComponent::Checksum.to_string(), "checksum");
+        assert_eq!(ShowComponent::Tokens.to_string(), "tokens");
+        assert_eq!(ShowComponent::Context.to_string(), "context");
+    }
+
+    #[test]
+    fn test_registry_covers_every_component_once() {
+        for (idx, spec) in SHOW_REGISTRY.iter().enumerate() {
+            assert_eq!(spec.component.spec().name, spec.name);
+            assert_eq!(spec.name.parse::<ShowComponent>().unwrap(), spec.component);
+            assert!(
+                SHOW_REGISTRY[..idx]
+                    .iter()
+                    .all(|other| other.component != spec.component),
+                "{} registered twice",
+                spec.name
+            );
+        }
+        assert_eq!(SHOW_REGISTRY.len(), 8);
     }
 
     #[test]
-    fn test_value_enum_parsing() {
-        use clap::ValueEnum;
+    fn test_unknown_component_suggests_closest_name() {
+        let err = "chksum".parse::<ShowComponent>().unwrap_err().to_string();
+        assert!(err.contains("unknown show component 'chksum'"));
+        assert!(err.contains("did you mean 'checksum'?"));
+        assert!(err.contains("available: rank, url, lines"));
 
-        // Test that all variants can be parsed
-        let variants = ShowComponent::value_variants();
-        assert_eq!(variants.len(), 5);
+        let err = "zzzz".parse::<ShowComponent>().unwrap_err().to_string();
+        assert!(!err.contains("did you mean"));
 
-        // Test to_possible_value for kebab-case handling
         assert_eq!(
+            "RAW_SCORE".parse::<ShowComponent>().unwrap(),
             ShowComponent::RawScore
-                .to_possible_value()
-                .unwrap()
-                .get_name(),
-            "raw-score"
         );
     }
 
+    #[test]
+    fn test_show_set_membership_and_order() {
+        let set = ShowSet::from_components(&[
+            ShowComponent::Tokens,
+            ShowComponent::Url,
+            ShowComponent::Tokens,
+        ]);
+        assert!(set.contains(ShowComponent::Url));
+        assert!(set.contains(ShowComponent::Tokens));
+        assert!(!set.contains(ShowComponent::Anchor));
+        assert_eq!(
+            set.components(),
+            vec![ShowComponent::Url, ShowComponent::Tokens]
+        );
+    }
+
+    #[test]
+    fn test_value_parser_lists_registry_names() {
+        let names: Vec<String> = ShowComponentParser
+            .possible_values()
+            .unwrap()
+            .map(|value| value.get_name().to_string())
+            .collect();
+        assert_eq!(names.len(), SHOW_REGISTRY.len());
+        assert!(names.iter().any(|name| name == "raw-score"));
+    }
+
     #[test]
     fn test_clone_copy() {
         let component = ShowComponent::Rank;
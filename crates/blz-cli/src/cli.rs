@@ -60,8 +60,11 @@ use std::path::PathBuf;
 pub use crate::args::{ContextMode, ShowComponent, merge_context_flags};
 // Re-export sub-enums and Args structs from commands module
 pub use crate::commands::{
-    AddArgs, AliasCommands, AnchorCommands, CheckArgs, ClaudePluginCommands, DocsCommands,
-    FindArgs, MapArgs, QueryArgs, RegistryCommands, RmArgs, SearchArgs, SyncArgs, TocArgs,
+    AddArgs, AliasCommands, AnchorCommands, BenchArgs, CheckArgs, ClaudePluginCommands, DemoArgs,
+    DiscoverArgs, DocsCommands, ExplainArgs, ExplainSourceArgs, ExportArgs, FilterCommands,
+    FindArgs, FixturesArgs, GcArgs, GenerateArgs, ImportArgs, InitArgs, ListColumn, ListFilter,
+    ListSort, MapArgs, ProfileCommands, QueryArgs, RegistryCommands, RmArgs, SearchArgs, ServeArgs,
+    SyncArgs, TermsArgs, TocArgs, VerifyInstallArgs, WatchArgs,
 };
 
 /// Custom help template with grouped command sections
@@ -79,16 +82,37 @@ Source Management:
   add            Add a new source
   list           List all cached sources [aliases: sources]
   sync           Fetch latest documentation from sources
+  watch          Re-sync sources on an interval (JSONL change events)
+  generate       Build a source by scraping the pages in a sitemap
+  discover       Propose sources for a project's dependencies
+  diff           Show which sections changed between versions of a source
   rm             Remove a source and its cached content
+  export         Package cached sources into a bundle for offline transfer
+  import         Unpack a bundle written by export
   info           Show detailed information about a source
   check          Validate source integrity and availability
   lookup         Search registries for documentation to add
+  demo           Try blz on built-in sample docs (no network)
 
 Configuration:
   stats          Show cache statistics and overview
+  du             Show per-source disk usage
+  gc             Remove orphaned indices, stale archives, and temp files
   history        Show recent search history and defaults
   doctor         Run health checks on cache and sources
+  bench          Measure search latency and enforce budgets
+  explain        Show how each hit's score was computed
+  explain-source Brief an agent on a source: sections, size, freshness, queries
+  terms          List the most frequent indexed terms in a source
+  filter         Preview content filter rules against a cached source
+  verify-install Run an end-to-end self-test of this installation
+  serve          Serve search and retrieval over a local HTTP API
+  ps             List orphaned blz processes
+  kill           Terminate orphaned blz processes
+  debug-args     Explain how shorthand arguments are rewritten
   clear          Clear the entire cache (removes all sources)
+  profile        Manage named profiles with isolated source sets
+  init           Scaffold a project workspace and shareable source manifest
   docs           Bundled documentation hub and CLI reference
   completions    Generate shell completions
   alias          Manage aliases for a source
@@ -164,10 +188,46 @@ pub struct Cli {
     #[arg(long, global = true)]
     pub profile: bool,
 
+    /// Pass arguments to the parser exactly as given (also via `BLZ_STRICT_ARGS`)
+    ///
+    /// Disables the `blz <input>` shorthand, so a subcommand is always required.
+    #[arg(long = "no-shorthand", global = true)]
+    pub no_shorthand: bool,
+
     /// Disable all ANSI colors in output (also respects `NO_COLOR` env)
     #[arg(long = "no-color", global = true)]
     pub no_color: bool,
 
+    /// Never prompt; operations that need confirmation fail unless `--yes` is given
+    #[arg(long = "no-input", global = true, env = "BLZ_NO_INPUT")]
+    pub no_input: bool,
+
+    /// Ignore the project workspace (`.blz.toml`) and use every source. Also via `BLZ_GLOBAL`.
+    #[arg(long, global = true, env = "BLZ_GLOBAL")]
+    pub global: bool,
+
+    /// Apply output defaults from `[profiles.<NAME>]` in config. Also via `BLZ_PROFILE_OUT`.
+    ///
+    /// Explicit flags still win over the profile.
+    #[arg(
+        long = "profile-out",
+        global = true,
+        value_name = "NAME",
+        env = "BLZ_PROFILE_OUT"
+    )]
+    pub profile_out: Option<String>,
+
+    /// Use the named profile's isolated sources and history. Also via `BLZ_PROFILE`.
+    ///
+    /// `default` is the shared cache; manage profiles with `blz profile`.
+    #[arg(
+        long = "profile-name",
+        global = true,
+        value_name = "NAME",
+        env = "BLZ_PROFILE"
+    )]
+    pub profile_name: Option<String>,
+
     /// Generate CPU flamegraph (requires flamegraph feature)
     #[cfg(feature = "flamegraph")]
     #[arg(long, global = true)]
@@ -224,7 +284,8 @@ pub struct Cli {
 /// # Content access
 /// blz search "useEffect" --limit 5
 /// blz get react --lines 120-142 -C 3
-/// blz diff react --since "2024-01-01"
+/// blz diff react --since 2w
+/// blz diff react --between v1..v2
 ///
 /// # Utility
 /// blz completions bash > ~/.bash_completion.d/blz
@@ -323,6 +384,45 @@ pub enum Commands {
     #[command(display_order = 10, hide = true)]
     Sync(SyncArgs),
 
+    /// Re-sync sources on an interval, emitting JSONL change events
+    ///
+    /// Re-checks sources with conditional requests and re-indexes the ones
+    /// that changed upstream. Runs until interrupted.
+    ///
+    /// Examples:
+    ///   blz watch                       # All sources, every 15 minutes
+    ///   blz watch react bun -i 5m       # Specific sources, every 5 minutes
+    ///   blz watch --once                # One check cycle, then exit
+    #[command(display_order = 10, hide = true)]
+    Watch(WatchArgs),
+
+    /// Build a source by scraping the pages listed in a sitemap
+    ///
+    /// For sites without an llms.txt. Pages are scraped through Firecrawl
+    /// (API key or logged-in CLI) and indexed as one llms-full.txt; sync
+    /// rescrapes only pages whose sitemap lastmod changed.
+    ///
+    /// Examples:
+    ///   blz generate hono.dev                          # Probe for sitemap.xml
+    ///   blz generate <https://hono.dev/sitemap.xml> --include /docs/
+    ///   blz generate hono.dev --max-pages 50 --dry-run # Preview the page list
+    #[command(display_order = 10, hide = true)]
+    Generate(GenerateArgs),
+
+    /// Propose sources for a project's dependencies
+    ///
+    /// Reads `package.json`, `Cargo.toml`, or `pyproject.toml` and maps each
+    /// dependency to a registry entry, its docs.rs API docs (crates), or an
+    /// llms.txt found by probing domains like `{name}.dev`. Prints the plan;
+    /// `--add` installs it and `--write` saves it as a manifest.
+    ///
+    /// Examples:
+    ///   blz discover                                  # Every manifest in this directory
+    ///   blz discover --from package.json --add        # Add the planned sources
+    ///   blz discover --from Cargo.toml --write blz.sources.toml
+    #[command(display_order = 10, hide = true)]
+    Discover(DiscoverArgs),
+
     /// Remove a source and its cached content
     ///
     /// Examples:
@@ -331,6 +431,30 @@ pub enum Commands {
     #[command(display_order = 11, hide = true)]
     Rm(RmArgs),
 
+    /// Package cached sources into a bundle for offline transfer
+    ///
+    /// Writes a `.tar.zst` with each source's content, metadata, and
+    /// descriptor. Indices are rebuilt on import unless `--with-index` bundles
+    /// them. Fetch credentials are never exported.
+    ///
+    /// Examples:
+    ///   blz export --bundle docs.tar.zst               # All sources
+    ///   blz export react bun --bundle web.tar.zst      # Specific sources
+    ///   blz export --bundle docs.tar.zst --with-index  # Skip reindexing on import
+    #[command(display_order = 11, hide = true)]
+    Export(ExportArgs),
+
+    /// Unpack a bundle written by `blz export`
+    ///
+    /// Works offline. Sources that already exist are skipped unless `--force`
+    /// is given; missing indices are rebuilt from the bundled content.
+    ///
+    /// Examples:
+    ///   blz import docs.tar.zst
+    ///   blz import docs.tar.zst --force                # Replace existing sources
+    #[command(display_order = 11, hide = true)]
+    Import(ImportArgs),
+
     /// Validate source integrity and availability
     ///
     /// Check that sources are properly configured and accessible.
@@ -371,7 +495,7 @@ pub enum Commands {
     ///
     /// Examples:
     ///   blz query "react hooks"        # Preferred: use query
-    ///   blz "react hooks"              # Default command still works
+    ///   blz "react hooks"              # Default command smart-dispatches
     #[command(display_order = 2, hide = true)]
     Search(SearchArgs),
 
@@ -392,6 +516,9 @@ pub enum Commands {
         /// Clear search history before the specified date (format: YYYY-MM-DD or ISO 8601)
         #[arg(long = "clear-before", value_name = "DATE", conflicts_with = "clear")]
         clear_before: Option<String>,
+        /// Skip the confirmation prompt when clearing history
+        #[arg(short = 'y', long)]
+        yes: bool,
     },
     // Config command removed in v1.0.0-beta.1 - flavor preferences eliminated
     /// Retrieve exact lines from a source by citation
@@ -406,6 +533,8 @@ pub enum Commands {
     ///   blz get bun:120-142 -C 5        # With context
     ///   blz get bun:120-142,200-210     # Multiple ranges
     ///   blz get bun deno:5-10           # Multiple sources
+    ///   blz get bun:1-99 --grep install # Only matching lines
+    ///   blz get --next-section          # Section after the last retrieval
     #[command(display_order = 6, hide = true)]
     Get {
         /// One or more `alias[:ranges]` targets (preferred: matches search output, e.g., "bun:1-3")
@@ -501,12 +630,42 @@ pub enum Commands {
             display_order = 34
         )]
         max_lines: Option<usize>,
+        /// Only print retrieved lines matching PATTERN (a regular expression)
+        ///
+        /// Filters within the requested ranges and context, so `-C all --grep`
+        /// searches a whole section. Matching lines keep their line numbers.
+        #[arg(long, value_name = "PATTERN", display_order = 35)]
+        grep: Option<String>,
+        /// Read lines from an earlier version of the source
+        ///
+        /// Accepts a timestamp (`2025-01-31`, `3 days ago`) to read the content
+        /// that was current then, or a content SHA (hex prefix or base64) to
+        /// resolve citations made against a previous version.
+        #[arg(long, value_name = "TIMESTAMP|SHA", display_order = 36)]
+        at: Option<String>,
+        /// Retrieve the heading section after the lines the last `get` printed
+        #[arg(
+            long,
+            conflicts_with_all = ["targets", "lines", "source", "previous_section", "remote"],
+            display_order = 37
+        )]
+        next_section: bool,
+        /// Retrieve the heading section before the lines the last `get` printed
+        #[arg(
+            long,
+            conflicts_with_all = ["targets", "lines", "source", "remote"],
+            display_order = 38
+        )]
+        previous_section: bool,
         /// Output format
         #[command(flatten)]
         format: FormatArg,
         /// Copy output to clipboard using OSC 52 escape sequence
         #[arg(long)]
         copy: bool,
+        /// Retrieve from a remote `blz serve` endpoint instead of the local cache
+        #[arg(long, value_name = "URL", env = "BLZ_REMOTE")]
+        remote: Option<String>,
     },
 
     /// Show detailed information about a source
@@ -519,6 +678,25 @@ pub enum Commands {
         format: FormatArg,
     },
 
+    /// Show which source a name or alias resolves to
+    ///
+    /// Prints the canonical source name. Use `--verbose` to see each
+    /// resolution step and the aliases every source declares.
+    ///
+    /// Examples:
+    ///   blz which rx             # Canonical source for an alias
+    ///   blz which rx --verbose   # Full resolution chain
+    ///   blz which rx --json      # Resolution trace for scripts
+    #[command(display_order = 12, hide = true)]
+    Which {
+        /// Source name or alias to resolve
+        #[arg(value_name = "SOURCE")]
+        source: String,
+        /// Output format
+        #[command(flatten)]
+        format: FormatArg,
+    },
+
     /// List all cached sources
     #[command(visible_alias = "sources", display_order = 4, hide = true)]
     List {
@@ -534,6 +712,20 @@ pub enum Commands {
         /// Maximum number of sources to display
         #[arg(short = 'n', long, value_name = "COUNT")]
         limit: Option<usize>,
+        /// Sort sources by name, size, fetch time, or search count
+        #[arg(long, value_enum, value_name = "KEY")]
+        sort: Option<ListSort>,
+        /// Only show sources matching: stale, generated, or tag:<tag> (repeatable)
+        #[arg(long = "filter", value_name = "FILTER")]
+        filters: Vec<ListFilter>,
+        /// Columns to display, comma-separated (e.g. alias,size,searches)
+        #[arg(long, value_enum, value_delimiter = ',', value_name = "COLUMNS")]
+        columns: Vec<ListColumn>,
+        /// Export curated metadata (name, description, category, tags) of the
+        /// listed sources to a JSON file (`-` for stdout); apply it elsewhere
+        /// with `blz add --descriptors`
+        #[arg(long, value_name = "FILE", conflicts_with = "columns")]
+        export: Option<PathBuf>,
     },
 
     /// Show cache statistics and overview
@@ -543,8 +735,23 @@ pub enum Commands {
         #[command(flatten)]
         format: FormatArg,
         /// Maximum number of sources to display in statistics
-        #[arg(short = 'n', long, value_name = "COUNT")]
+        #[arg(short = 'n', long, value_name = "COUNT", conflicts_with = "unused")]
         limit: Option<usize>,
+        /// List sources with no search hits in the last `--days` days
+        #[arg(long)]
+        unused: bool,
+        /// Report per-source disk usage against the configured disk budget
+        #[arg(long, conflicts_with = "unused")]
+        disk: bool,
+        /// Lookback window for `--unused`, in days
+        #[arg(long, value_name = "DAYS", default_value_t = 60, requires = "unused")]
+        days: u32,
+        /// Remove the unused sources after confirmation
+        #[arg(long, requires = "unused")]
+        prune: bool,
+        /// Skip the `--prune` confirmation prompt
+        #[arg(short = 'y', long, requires = "prune")]
+        yes: bool,
     },
 
     /// Validate source integrity (deprecated: use `check` instead)
@@ -572,6 +779,220 @@ pub enum Commands {
         fix: bool,
     },
 
+    /// Benchmark index open and search latency
+    ///
+    /// Use `--assert` to enforce latency budgets; the command exits non-zero
+    /// when any budget is exceeded.
+    ///
+    /// Examples:
+    ///   blz bench                                  # Default queries, all sources
+    ///   blz bench "hooks" "routing" -s react       # Custom queries for one source
+    ///   blz bench --assert 'p95-search<10ms'       # Fail if p95 search exceeds 10ms
+    ///   blz bench rank --queries rank.toml --ranking headings.toml # Compare ranking
+    #[command(
+        display_order = 19,
+        hide = true,
+        args_conflicts_with_subcommands = true
+    )]
+    Bench(BenchArgs),
+
+    /// Show how each hit's search score was computed
+    ///
+    /// Runs a query against one source and prints, per hit, the BM25 score of
+    /// every matched term, the field boost applied to it, and the final score.
+    ///
+    /// Examples:
+    ///   blz explain "useEffect cleanup" -s react       # Term breakdown per hit
+    ///   blz explain "# routing" -s nextjs --tree       # Include the scoring tree
+    ///   blz explain "hooks" -s react -n 1 --json       # Machine-readable breakdown
+    #[command(display_order = 19, hide = true)]
+    Explain(ExplainArgs),
+
+    /// Print a compact briefing on a source for an agent's prompt
+    ///
+    /// Summarizes what the source covers, its top-level sections with anchors
+    /// and line ranges, size, version, freshness, and suggested queries. Text
+    /// output is markdown; JSON has the same fields.
+    ///
+    /// Examples:
+    ///   blz explain-source react                    # Markdown briefing
+    ///   blz explain-source react --sections 10      # Fewer sections
+    ///   blz explain-source react --json             # Machine-readable briefing
+    #[command(name = "explain-source", display_order = 19, hide = true)]
+    ExplainSource(ExplainSourceArgs),
+
+    /// List the most frequent indexed terms in a source
+    ///
+    /// Counts come from the search index, so every listed term is one a query
+    /// against that source can match. Use --heading to look at one section.
+    ///
+    /// Examples:
+    ///   blz terms react                            # Top 50 terms in react
+    ///   blz terms react --heading "Hooks" -n 20    # Terms under the Hooks sections
+    ///   blz terms bun --prefix test --json         # Terms starting with "test"
+    #[command(display_order = 19, hide = true)]
+    Terms(TermsArgs),
+
+    /// Preview and configure content filter pipelines
+    ///
+    /// A source is indexed through a filter pipeline: the language filter by
+    /// default, or the rules set with `blz filter set`. `test` runs proposed
+    /// rules over a source's cached content and shows which sections would be
+    /// dropped or rewritten, without touching the cache or the index.
+    ///
+    /// Examples:
+    ///   blz filter test react -r 'drop-heading=Changelog'       # Drop sections by heading
+    ///   blz filter test bun -r lang -r 'strip=\[edit\]' --stat  # Counts per heading only
+    ///   blz filter test react -r 'drop-content=(?i)deprecated' --json
+    ///   blz filter set react -r lang -r nav -r dedupe           # Save and re-index
+    ///   blz filter show react                                   # Current pipeline
+    ///   blz filter clear react                                  # Back to the default
+    #[command(display_order = 19, hide = true)]
+    Filter {
+        #[command(subcommand)]
+        command: FilterCommands,
+    },
+
+    /// Try blz on built-in sample docs in a temporary sandbox
+    ///
+    /// Installs a small sample source into its own data and config directory
+    /// and prints the environment variables that point blz at it, so search,
+    /// get, and map can be tried without network access. Your real cache is
+    /// never touched.
+    ///
+    /// Examples:
+    ///   blz demo                     # Create (or refresh) the sandbox
+    ///   blz demo --dir ./blz-demo    # Use a specific directory
+    ///   blz demo --clean             # Delete the sandbox
+    #[command(display_order = 19, hide = true)]
+    Demo(DemoArgs),
+
+    /// Generate synthetic fixture sources for integration tests and benches
+    ///
+    /// Writes reproducible llms.txt corpora (size, heading depth, and code
+    /// density are configurable) and indexes them into a sandbox data and
+    /// config directory, then prints the environment variables that point blz
+    /// at it. The same seed always yields the same documents.
+    ///
+    /// Examples:
+    ///   blz fixtures                           # 3 sources x 200 sections
+    ///   blz fixtures --sources 10 --sections 2000 --seed 7
+    ///   blz fixtures --code-density 0.6 --json
+    ///   blz fixtures --clean                   # Delete the sandbox
+    #[command(display_order = 19, hide = true)]
+    Fixtures(FixturesArgs),
+
+    /// Run an end-to-end self-test of this installation
+    ///
+    /// Indexes a built-in fixture in a temporary directory, searches it, and
+    /// reads the cited lines back, reporting each step with its timing. The
+    /// real cache is never touched. Exits non-zero if any step fails.
+    ///
+    /// Examples:
+    ///   blz verify-install           # Human-readable report
+    ///   blz verify-install --json    # Machine-readable report for installers
+    ///   blz verify-install --keep    # Keep the temporary cache for inspection
+    #[command(name = "verify-install", display_order = 19, hide = true)]
+    VerifyInstall(VerifyInstallArgs),
+
+    /// Serve search and retrieval over a local HTTP API
+    ///
+    /// Exposes search, get, list, and toc as JSON endpoints under `/v1` for
+    /// editor plugins and other tools, and as the backend for `--remote`.
+    ///
+    /// Examples:
+    ///   blz serve                        # <http://127.0.0.1:7777>
+    ///   blz serve --port 0               # Pick a free port
+    ///   blz serve --host 0.0.0.0         # Reachable from other machines
+    #[command(display_order = 19, hide = true)]
+    Serve(ServeArgs),
+
+    /// Show per-source disk usage (content, archives, and index)
+    ///
+    /// Examples:
+    ///   blz du                   # All sources, largest first
+    ///   blz du react bun         # Only the named sources
+    ///   blz du --bytes --json    # Exact byte counts for scripts
+    #[command(display_order = 14, hide = true)]
+    Du {
+        /// Sources to report (defaults to all)
+        #[arg(value_name = "SOURCE")]
+        aliases: Vec<String>,
+        /// Show human-readable sizes (default)
+        #[arg(long, conflicts_with = "bytes")]
+        human: bool,
+        /// Show exact byte counts instead of human-readable sizes
+        #[arg(long)]
+        bytes: bool,
+        /// Output format
+        #[command(flatten)]
+        format: FormatArg,
+    },
+
+    /// Remove cache data nothing reads anymore
+    ///
+    /// Collects orphaned source directories and indices, snapshot archives
+    /// beyond `max_archives`, unreferenced version objects, temp files left by
+    /// aborted syncs, and anchor maps that fail to parse.
+    ///
+    /// Examples:
+    ///   blz gc                   # Remove garbage and report reclaimed space
+    ///   blz gc --dry-run         # List what would be removed
+    ///   blz gc --dry-run --json  # Machine-readable listing
+    #[command(display_order = 14, hide = true)]
+    Gc(GcArgs),
+
+    /// Explain how a command line is rewritten before parsing
+    ///
+    /// Shows where the default subcommand is injected and why, plus warnings
+    /// for rewrites that may hide a mistyped or misplaced subcommand.
+    ///
+    /// Examples:
+    ///   blz debug-args -- react hooks        # Query shorthand
+    ///   blz debug-args -- serach hooks       # Warns about the likely typo
+    ///   blz debug-args --json -- -s bun test # Machine-readable report
+    #[command(name = "debug-args", display_order = 20, hide = true)]
+    DebugArgs {
+        /// Arguments as they would follow `blz`
+        #[arg(
+            value_name = "ARGS",
+            trailing_var_arg = true,
+            allow_hyphen_values = true
+        )]
+        args: Vec<String>,
+        /// Output format
+        #[command(flatten)]
+        format: FormatArg,
+    },
+
+    /// List orphaned blz processes (e.g. MCP servers left by crashed harnesses)
+    #[command(display_order = 17, hide = true)]
+    Ps {
+        /// Include processes whose parent is still running
+        #[arg(long)]
+        all: bool,
+        /// Output format
+        #[command(flatten)]
+        format: FormatArg,
+    },
+
+    /// Terminate orphaned blz processes
+    #[command(display_order = 18, hide = true)]
+    Kill {
+        /// Process IDs to terminate (must be blz processes)
+        #[arg(value_name = "PID", required_unless_present = "orphans")]
+        pids: Vec<u32>,
+        /// Terminate every orphaned blz process
+        #[arg(long, conflicts_with = "pids")]
+        orphans: bool,
+        /// Send SIGKILL instead of SIGTERM
+        #[arg(long)]
+        force: bool,
+        /// Output format
+        #[command(flatten)]
+        format: FormatArg,
+    },
+
     /// Refresh sources (deprecated: use `sync` instead)
     #[command(display_order = 110, hide = true)]
     #[deprecated(since = "1.5.0", note = "use 'sync' instead")]
@@ -597,6 +1018,9 @@ pub enum Commands {
         /// Available filters:
         ///   lang,language  - Filter non-English content
         ///
+        /// A source with a pipeline saved by `blz filter set` runs that pipeline
+        /// instead whenever filtering is enabled.
+        ///
         /// Examples:
         ///   --filter           # Enable all filters
         ///   --filter lang      # Only language filter
@@ -638,22 +1062,60 @@ pub enum Commands {
         yes: bool,
     },
 
+    /// Manage named profiles with isolated source sets
+    ///
+    /// Each profile has its own sources, descriptors, and search history;
+    /// `config.toml` is shared. Select one with `--profile-name <NAME>` or
+    /// `BLZ_PROFILE`.
+    ///
+    /// Examples:
+    ///   blz profile create work
+    ///   blz --profile-name work add react <https://react.dev/llms.txt>
+    ///   blz profile list --json
+    ///   blz profile rm work --yes
+    #[command(display_order = 17, hide = true)]
+    Profile {
+        #[command(subcommand)]
+        command: ProfileCommands,
+    },
+
+    /// Scaffold a project's documentation set
+    ///
+    /// Writes `.blz.toml` (scoping searches in the project to its sources) and
+    /// `blz.sources.toml`, a manifest teammates install with
+    /// `blz add --manifest blz.sources.toml`. Sources come from `--source`,
+    /// from the project's dependencies with `--detect`, or default to every
+    /// installed source.
+    ///
+    /// Examples:
+    ///   blz init --source react --source vite   # Pick sources explicitly
+    ///   blz init --detect                       # Suggest from package.json, Cargo.toml, pyproject.toml
+    ///   blz init --detect --dry-run             # Preview both files
+    #[command(display_order = 17, hide = true)]
+    Init(InitArgs),
+
     /// Clear the entire cache (removes all sources and their data)
     #[command(display_order = 17, hide = true)]
     Clear {
         /// Skip confirmation prompt
-        #[arg(short = 'f', long = "force")]
+        #[arg(short = 'f', long = "force", visible_alias = "yes", short_alias = 'y')]
         force: bool,
     },
 
-    /// View diffs (coming soon)
-    #[command(hide = true, display_order = 101)]
+    /// Show which sections changed between versions of a source
+    #[command(display_order = 101)]
     Diff {
         /// Source to compare
         alias: String,
-        /// Show changes since timestamp
-        #[arg(long)]
+        /// Show changes since a time or content SHA (`2w`, `"last sync"`, `2025-01-31`)
+        #[arg(long, value_name = "WHEN", conflicts_with = "between")]
         since: Option<String>,
+        /// Compare two versions (`v1..v2`, `2w..current`; v1 is the oldest archived version)
+        #[arg(long, value_name = "FROM..TO")]
+        between: Option<String>,
+        /// Output format
+        #[command(flatten)]
+        format: FormatArg,
     },
 
     #[command(name = "mcp-server", hide = true)]
@@ -671,4 +1133,20 @@ pub enum Commands {
     #[command(display_order = 105, hide = true)]
     #[deprecated(since = "1.5.0", note = "use 'query' or 'get' instead")]
     Find(FindArgs),
+
+    /// Smart dispatch for bare `blz <input>` invocations
+    ///
+    /// Inserted automatically when the first argument is not a known command:
+    /// - `alias:digits-digits` citations → retrieve mode (like get)
+    /// - `alias#anchor` → retrieve the heading section for that anchor
+    /// - `https://...` URLs → offer to add the source
+    /// - Anything else → search mode (like query)
+    ///
+    /// Examples:
+    ///   blz "react hooks"                # Search
+    ///   blz bun:120-142 -C 5             # Retrieve lines
+    ///   blz bun#installation             # Retrieve section by anchor
+    ///   blz <https://bun.sh/llms.txt>    # Offer to add source
+    #[command(name = "default", hide = true)]
+    Default(FindArgs),
 }
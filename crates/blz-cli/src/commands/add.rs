@@ -2,43 +2,139 @@
 
 use std::fs as sync_fs;
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
-use std::sync::atomic::{AtomicUsize, Ordering};
 
-use anyhow::{Result, bail};
+use anyhow::{Context, Result, bail};
 use base64::{Engine as _, engine::general_purpose::STANDARD};
+use blz_core::follow::{FollowOptions, FollowedDocument, follow_links};
 use blz_core::numeric::safe_percentage;
 use blz_core::{
-    Fetcher, LanguageFilter, MarkdownParser, ParseResult, PerformanceMetrics, SearchIndex, Source,
-    SourceDescriptor, SourceOrigin, SourceType, SourceVariant, Storage, build_llms_json,
+    Config, FetchAuth, Fetcher, FollowLinks, LanguageFilter, LocaleSet, MarkdownParser, MirrorList,
+    ParseResult, PerformanceMetrics, SearchIndex, Secret, Source, SourceDescriptor,
+    SourceIntegrity, SourceOrigin, SourceType, SourceVariant, Storage, build_llms_json,
 };
 use chrono::Utc;
-use clap::Args;
+use clap::{Args, ValueEnum};
 use colored::Colorize;
 use indicatif::{ProgressBar, ProgressStyle};
+use inquire::Confirm;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use tokio::fs as async_fs;
 use url::Url;
 
+use crate::utils::confirm;
 use crate::utils::count_headings;
+use crate::utils::lockfile::{self, Lockfile};
 use crate::utils::validation::{normalize_alias, validate_alias};
 
+mod inspect;
+
+use inspect::Inspection;
+
 /// Arguments for `blz add`.
 #[derive(Args, Clone, Debug)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct AddArgs {
     /// Source name (used as identifier).
-    #[arg(value_name = "ALIAS", required_unless_present_any = ["manifest"])]
+    #[arg(
+        value_name = "ALIAS",
+        required_unless_present_any = ["manifest", "descriptors", "crate_spec"]
+    )]
     pub alias: Option<String>,
 
-    /// URL to fetch llms.txt from.
-    #[arg(value_name = "URL", required_unless_present_any = ["manifest"], requires = "alias")]
+    /// URL to fetch llms.txt from, `crate:NAME[@VERSION]` for Rust crate docs,
+    /// or `github:OWNER/REPO[@REF]` for an llms.txt committed to a repository.
+    ///
+    /// A local file path (or `file://` URL) adds a file that sync re-reads
+    /// when its modification time changes; `-` reads the document from stdin.
+    /// PDFs (local or remote) are indexed page by page, and `.rst`/`.txt`
+    /// files get headings for their section titles.
+    ///
+    /// Crate sources are built from docs.rs rustdoc JSON. `blz add crate:serde`
+    /// (without an alias) uses the crate name as the alias, and `blz add
+    /// github:owner/repo` the repository name.
+    #[arg(
+        value_name = "URL",
+        required_unless_present_any = ["manifest", "members", "descriptors", "crate_spec", "github"],
+        requires = "alias"
+    )]
     pub url: Option<String>,
 
+    /// Index a crate's API docs from docs.rs, as `NAME` or `NAME@VERSION`.
+    ///
+    /// Same as passing `crate:NAME[@VERSION]` as the URL; the alias defaults
+    /// to the crate name.
+    ///
+    /// Examples:
+    ///   blz add --crate serde
+    ///   blz add tokio-1 --crate tokio@1.40.0
+    #[arg(
+        long = "crate",
+        value_name = "NAME[@VERSION]",
+        value_parser = parse_crate_spec,
+        conflicts_with_all = ["url", "manifest", "members", "descriptors"]
+    )]
+    pub crate_spec: Option<CrateSpec>,
+
+    /// Index a GitHub repository's README and `docs/` markdown files.
+    ///
+    /// Takes `OWNER/REPO[@REF]` in place of the URL (or alone, in which case
+    /// the alias defaults to the repository name). Files are read at the
+    /// head commit of the ref (the default branch when omitted) and merged
+    /// under their paths; sync re-reads them when the ref moves.
+    ///
+    /// Examples:
+    ///   blz add acme/widgets --github
+    ///   blz add widgets-next acme/widgets@next --github
+    #[arg(
+        long,
+        conflicts_with_all = ["crate_spec", "manifest", "members", "descriptors", "mirrors", "follow"]
+    )]
+    pub github: bool,
+
+    /// Add a collection member as `PREFIX=URL` (repeatable).
+    ///
+    /// Members are merged into one source, each nested under a top-level
+    /// heading named after its prefix. Sync refetches members individually.
+    #[arg(
+        long = "member",
+        value_name = "PREFIX=URL",
+        conflicts_with_all = ["url", "manifest"],
+        requires = "alias"
+    )]
+    pub members: Vec<blz_core::CollectionMember>,
+
+    /// Document type of `<URL>`.
+    ///
+    /// `openapi` accepts an `OpenAPI` 3.x or Swagger 2.0 spec (JSON or YAML) from a
+    /// URL or local path and renders each operation and schema as a section.
+    /// Specs are also recognized without `--type` from their `openapi` or
+    /// `swagger` version field.
+    /// `dir` merges every markdown file under a local folder into one source.
+    ///
+    /// Examples:
+    ///   blz add api ./openapi.yaml --type openapi
+    ///   blz add projectdocs ./docs --type dir
+    #[arg(
+        long = "type",
+        value_name = "TYPE",
+        value_enum,
+        default_value_t,
+        conflicts_with_all = ["members", "manifest"]
+    )]
+    pub source_type: AddSourceType,
+
     /// Path to a manifest TOML describing multiple sources.
     #[arg(long, value_name = "FILE")]
     pub manifest: Option<PathBuf>,
 
+    /// Apply curated metadata from a `blz list --export` file (`-` for stdin).
+    ///
+    /// Updates the name, description, category, and tags of sources that are
+    /// already installed; nothing is fetched or re-indexed.
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["alias", "url", "manifest", "members"])]
+    pub descriptors: Option<PathBuf>,
+
     /// Restrict manifest processing to specific aliases.
     #[arg(long = "only", value_delimiter = ',', requires = "manifest")]
     pub only: Vec<String>,
@@ -63,14 +159,114 @@ pub struct AddArgs {
     #[arg(long, value_delimiter = ',')]
     pub tags: Vec<String>,
 
+    /// Alternate URL serving the same document (repeatable).
+    ///
+    /// Refreshes track latency and failures per mirror and fetch from the
+    /// healthiest one, falling back to the others when it fails.
+    ///
+    /// Examples:
+    ///   blz add bun <https://bun.sh/llms.txt> --mirror <https://mirror.example.com/bun/llms.txt>
+    #[arg(long = "mirror", value_name = "URL", conflicts_with_all = ["members", "manifest"])]
+    pub mirrors: Vec<String>,
+
+    /// Fetch the pages an index-style llms.txt links to and index them too.
+    ///
+    /// Links allowed by `defaults.follow_links` (first-party by default) are
+    /// fetched as markdown and merged after the index, each page under its
+    /// own heading with a `Source:` line. Sync refetches every page.
+    ///
+    /// Examples:
+    ///   blz add hono <https://hono.dev/llms.txt> --follow
+    ///   blz add hono <https://hono.dev/llms.txt> --follow --follow-depth 2 --follow-max-pages 200
+    #[arg(long, conflicts_with_all = ["members", "manifest", "mirrors"])]
+    pub follow: bool,
+
+    /// Link hops to follow from the index (with `--follow`).
+    #[arg(
+        long,
+        value_name = "N",
+        default_value_t = blz_core::follow::DEFAULT_MAX_DEPTH,
+        value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..=5),
+        requires = "follow"
+    )]
+    pub follow_depth: usize,
+
+    /// Maximum number of linked pages to fetch (with `--follow`).
+    #[arg(
+        long,
+        value_name = "N",
+        default_value_t = blz_core::follow::DEFAULT_MAX_PAGES,
+        requires = "follow"
+    )]
+    pub follow_max_pages: usize,
+
+    /// Probe and follow links even where robots.txt disallows it.
+    ///
+    /// By default, discovery probes skip paths the host's robots.txt
+    /// disallows, and `--follow` skips disallowed pages and waits out the
+    /// host's `Crawl-delay` between requests. The URL you pass is always
+    /// fetched.
+    #[arg(long)]
+    pub ignore_robots: bool,
+
+    /// Environment variable holding a bearer token for this source.
+    ///
+    /// Saved as `env:<VAR>` in the source's `settings.toml`, so the token
+    /// itself is read from the environment on every add and sync.
+    ///
+    /// Examples:
+    ///   blz add internal <https://docs.corp.example/llms.txt> --auth-bearer-env `CORP_DOCS_TOKEN`
+    #[arg(long, value_name = "VAR", conflicts_with_all = ["members", "manifest"])]
+    pub auth_bearer_env: Option<String>,
+
+    /// Extra request header as `NAME=VALUE` (repeatable).
+    ///
+    /// A value of `env:<VAR>` is read from the environment at fetch time.
+    #[arg(long = "auth-header", value_name = "NAME=VALUE", conflicts_with_all = ["members", "manifest"])]
+    pub auth_headers: Vec<String>,
+
+    /// Expected SHA-256 of the document, as hex or base64.
+    ///
+    /// Saved with the source: the add, and every later sync, fails and keeps
+    /// the cached copy when the fetched document hashes differently.
+    #[arg(long, value_name = "DIGEST", conflicts_with_all = ["members", "manifest", "follow"])]
+    pub sha256: Option<String>,
+
+    /// Minisign public key that must have signed the document.
+    ///
+    /// The signature is downloaded from `<URL>.minisig` (or
+    /// `--signature-url`) and checked on add and every sync.
+    #[arg(long, value_name = "KEY", conflicts_with_all = ["members", "manifest", "follow"])]
+    pub minisign_key: Option<String>,
+
+    /// URL of the document's minisign signature.
+    #[arg(long, value_name = "URL", requires = "minisign_key")]
+    pub signature_url: Option<String>,
+
     /// Skip confirmation prompts (non-interactive mode).
     #[arg(short = 'y', long)]
     pub yes: bool,
 
+    /// Use documentation discovered outside the URL's scope without prompting.
+    ///
+    /// When `<URL>` is a site or docs page, discovery may find llms.txt on a
+    /// parent domain. Interactive runs ask first; non-interactive runs (`-y`,
+    /// pipes) fail unless this flag is set.
+    #[arg(long, requires = "url")]
+    pub accept_scope: bool,
+
     /// Analyze source without adding it (outputs JSON analysis).
     #[arg(long)]
     pub dry_run: bool,
 
+    /// Like `--dry-run`, but also report the parsed structure.
+    ///
+    /// Adds the would-be table of contents, heading counts per level, detected
+    /// languages, language-filter effects, and estimated index size to the
+    /// analysis. Nothing is written.
+    #[arg(long)]
+    pub inspect: bool,
+
     /// Disable language filtering (keep all languages).
     ///
     /// By default, BLZ filters non-English content from multilingual documentation.
@@ -80,12 +276,55 @@ pub struct AddArgs {
     ///   blz add anthropic <https://docs.anthropic.com/llms-full.txt> --no-language-filter
     #[arg(long)]
     pub no_language_filter: bool,
+
+    /// Keep only these locales from multilingual documentation (e.g. en,de).
+    ///
+    /// Sections linking to locale paths such as `/ja/` or `/zh-cn/` are
+    /// dropped unless their locale is listed. The selection is saved with the
+    /// source and applied on every sync.
+    ///
+    /// Examples:
+    ///   blz add nextjs <https://nextjs.org/llms-full.txt> --lang en,ja
+    #[arg(long, value_name = "LOCALES", conflicts_with = "no_language_filter")]
+    pub lang: Option<LocaleSet>,
+
+    /// Limit download bandwidth (e.g. 500KB/s, 2MB/s).
+    ///
+    /// Defaults to `defaults.throttle` in config or `BLZ_THROTTLE`.
+    #[arg(long, value_name = "RATE")]
+    pub throttle: Option<Bandwidth>,
+    /// Skip TLS certificate verification.
+    ///
+    /// Last resort for hosts behind TLS-inspecting proxies; prefer
+    /// `defaults.ca_bundle` / `BLZ_CA_BUNDLE` to trust the proxy's CA instead.
+    #[arg(long)]
+    pub insecure: bool,
 }
-#[cfg(test)]
-use blz_core::discovery::DiscoveryMethod;
-use blz_core::discovery::{ProbeResult, probe_domain};
+use blz_core::discovery::{
+    DiscoveryMethod, ProbeResult, ProbeTargets, probe_domain_with, probe_url_with,
+};
+use blz_core::github::{GithubClient, RepoSpec};
+use blz_core::html;
+use blz_core::openapi;
+use blz_core::rustdoc::{self, CrateSpec};
+use blz_core::throttle::Bandwidth;
 use blz_core::url_resolver;
 
+/// `<URL>` value that reads the document from standard input.
+const STDIN_LOCATION: &str = "-";
+
+/// Document types accepted by `blz add --type`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum AddSourceType {
+    /// llms.txt-style markdown (discovered from sites and docs pages)
+    #[default]
+    Llms,
+    /// `OpenAPI` 3.x or Swagger 2.0 spec, JSON or YAML
+    Openapi,
+    /// Local folder of markdown files
+    Dir,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct SourceAnalysis {
@@ -95,6 +334,8 @@ struct SourceAnalysis {
     final_url: String,
     analysis: ContentAnalysis,
     would_index: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    inspection: Option<Inspection>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -127,6 +368,8 @@ pub struct DescriptorInput {
     github_aliases: Vec<String>,
     /// Optional manifest origin metadata.
     manifest: Option<blz_core::ManifestOrigin>,
+    /// Mirror URLs serving the same document.
+    mirrors: Vec<String>,
 }
 
 struct ResolvedAddition {
@@ -204,7 +447,6 @@ impl std::fmt::Display for AddAction {
 /// 2. If only `llms_url` exists, returns `IndexOnly`
 /// 3. If only `sitemap_url` exists, returns error (requires generation)
 /// 4. If nothing found, returns error
-#[allow(dead_code)]
 pub fn determine_add_action(probe: &ProbeResult, _interactive: bool) -> Result<AddAction> {
     // Prefer native llms-full.txt when available
     if let Some(url) = &probe.llms_full_url {
@@ -219,10 +461,10 @@ pub fn determine_add_action(probe: &ProbeResult, _interactive: bool) -> Result<A
     // Sitemap only - would need generation to be useful
     if probe.sitemap_url.is_some() {
         anyhow::bail!(
-            "No documentation sources found for '{}'. \
+            "No documentation sources found for '{domain}'. \
              Found sitemap.xml but no llms.txt or llms-full.txt. \
-             Generation from sitemap requires Firecrawl.",
-            probe.domain
+             Run 'blz generate {domain}' to build a source from the sitemap's pages.",
+            domain = probe.domain
         );
     }
 
@@ -308,146 +550,6 @@ pub fn prompt_generate_action(url_count: usize, has_llms_txt: bool) -> Result<Ad
     }
 }
 
-/// Statistics from a generate operation.
-#[allow(dead_code)]
-#[derive(Debug, Clone, Default)]
-pub struct GenerateStats {
-    /// Number of successfully scraped pages.
-    pub successful: usize,
-    /// Number of failed pages.
-    pub failed: usize,
-    /// Total lines in generated content.
-    pub total_lines: usize,
-}
-
-#[allow(dead_code)]
-impl GenerateStats {
-    /// Get the total number of URLs processed.
-    #[must_use]
-    pub const fn total(&self) -> usize {
-        self.successful + self.failed
-    }
-}
-
-/// Execute the generate flow to scrape URLs and create documentation.
-///
-/// Uses the [`GenerateOrchestrator`] to scrape discovered URLs in parallel,
-/// showing progress via an indicatif progress bar.
-///
-/// # Arguments
-///
-/// * `urls` - URLs to scrape (with optional lastmod for change detection)
-/// * `concurrency` - Maximum concurrent scrape operations
-/// * `quiet` - Whether to suppress progress output
-///
-/// # Returns
-///
-/// A tuple of (assembled markdown content, [`GenerateStats`]).
-///
-/// # Errors
-///
-/// Returns an error if the scraping fails completely (partial failures are recorded in stats).
-///
-/// [`GenerateOrchestrator`]: crate::generate::GenerateOrchestrator
-#[allow(dead_code)]
-pub async fn execute_generate_flow(
-    urls: &[crate::generate::UrlWithLastmod],
-    scraper: impl crate::generate::Scraper,
-    concurrency: usize,
-    quiet: bool,
-) -> Result<(String, GenerateStats)> {
-    use crate::generate::GenerateOrchestrator;
-
-    if urls.is_empty() {
-        return Ok((String::new(), GenerateStats::default()));
-    }
-
-    let total = urls.len();
-
-    // Create progress bar
-    let pb = if quiet {
-        ProgressBar::hidden()
-    } else {
-        let pb = ProgressBar::new(total as u64);
-        pb.set_style(
-            ProgressStyle::default_bar()
-                .template("[{bar:40.cyan/blue}] {pos}/{len} {msg}")
-                .unwrap_or_else(|_| ProgressStyle::default_bar())
-                .progress_chars("=>-"),
-        );
-        pb.set_message("Scraping...");
-        pb
-    };
-
-    // Track progress with atomic counter for thread-safe updates
-    let completed = Arc::new(AtomicUsize::new(0));
-    let completed_clone = Arc::clone(&completed);
-    let pb_clone = pb.clone();
-
-    // Create orchestrator with progress callback
-    let orchestrator =
-        GenerateOrchestrator::new(scraper, concurrency).with_progress(move |done, _total| {
-            completed_clone.store(done, Ordering::SeqCst);
-            pb_clone.set_position(done as u64);
-        });
-
-    // Execute scraping
-    let results = orchestrator.scrape_all(urls).await;
-
-    pb.finish_and_clear();
-
-    // Error if all scrapes failed (but we had URLs to scrape)
-    if results.successful.is_empty() && !results.failed.is_empty() {
-        let sample_errors: Vec<_> = results
-            .failed
-            .iter()
-            .take(3)
-            .map(|f| format!("  - {}: {}", f.url, f.error))
-            .collect();
-        bail!(
-            "All {} scrape(s) failed. Sample errors:\n{}",
-            results.failed.len(),
-            sample_errors.join("\n")
-        );
-    }
-
-    // Assemble markdown from successful scrapes
-    let mut assembled = String::new();
-    let mut total_lines = 0usize;
-
-    for entry in &results.successful {
-        // Add page header with source URL
-        if !assembled.is_empty() {
-            assembled.push_str("\n\n---\n\n");
-        }
-
-        // Add title if available
-        if let Some(title) = &entry.title {
-            assembled.push_str("# ");
-            assembled.push_str(title);
-            assembled.push_str("\n\n");
-        }
-
-        // Add source attribution
-        assembled.push_str("> Source: ");
-        assembled.push_str(&entry.url);
-        assembled.push_str("\n\n");
-
-        // Add content
-        assembled.push_str(&entry.markdown);
-
-        total_lines += entry.line_count;
-    }
-
-    let stats = GenerateStats {
-        successful: results.successful.len(),
-        failed: results.failed.len(),
-        total_lines,
-    };
-
-    Ok((assembled, stats))
-}
-
 impl DescriptorInput {
     pub fn from_cli_inputs(
         aliases: &[String],
@@ -467,9 +569,22 @@ impl DescriptorInput {
             ..Self::default()
         }
     }
+
+    /// Attach mirror URLs, rejecting anything that is not http(s).
+    pub fn with_mirrors(mut self, mirrors: &[String]) -> Result<Self> {
+        for mirror in mirrors {
+            let mirror = mirror.trim();
+            if !(mirror.starts_with("http://") || mirror.starts_with("https://")) {
+                anyhow::bail!("Mirror '{mirror}' must be an http:// or https:// URL");
+            }
+            self.mirrors.push(mirror.to_string());
+        }
+        Ok(self)
+    }
 }
 
 /// Prepared request for running the add flow.
+#[allow(clippy::struct_excessive_bools)]
 pub struct AddRequest {
     /// Alias to store the source under.
     pub alias: String,
@@ -479,33 +594,113 @@ pub struct AddRequest {
     pub descriptor: DescriptorInput,
     /// Whether to skip writes and indexing.
     pub dry_run: bool,
+    /// Whether the dry-run analysis includes the parsed structure.
+    pub inspect: bool,
     /// Suppress non-essential output.
     pub quiet: bool,
     /// Performance metrics collector.
     pub metrics: PerformanceMetrics,
     /// Disable language filtering for this add.
     pub no_language_filter: bool,
+    /// Locales kept by the language filter.
+    pub locales: LocaleSet,
+    /// Download bandwidth limit.
+    pub throttle: Option<Bandwidth>,
+    /// How to handle documentation discovered outside the URL's scope.
+    pub scope: ScopeConfirmation,
+    /// Credentials to fetch with, saved for later syncs.
+    pub auth: Option<FetchAuth>,
+    /// Follow links from an index-style llms.txt.
+    pub follow: Option<FollowOptions>,
+    /// Probe paths robots.txt disallows.
+    pub ignore_robots: bool,
+    /// Checksum or signature the document must match, saved for later syncs.
+    pub integrity: Option<SourceIntegrity>,
+}
+
+/// How `blz add` treats discovery results outside the requested scope.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ScopeConfirmation {
+    /// Accept cross-scope results without asking (`--accept-scope`).
+    pub accept: bool,
+    /// Whether the user can be prompted.
+    pub interactive: bool,
+}
+
+/// Decision for a discovery result that may have left the requested scope.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ScopeDecision {
+    /// Use the discovered URL.
+    Accept,
+    /// Ask the user before using it.
+    Prompt,
+    /// Refuse; the user must opt in with `--accept-scope`.
+    Reject,
+}
+
+impl ScopeConfirmation {
+    const fn decide(self, probe: &ProbeResult) -> ScopeDecision {
+        if !probe.requires_confirmation || self.accept {
+            ScopeDecision::Accept
+        } else if self.interactive {
+            ScopeDecision::Prompt
+        } else {
+            ScopeDecision::Reject
+        }
+    }
 }
 
 /// Options controlling add flow behavior.
 #[derive(Clone, Copy, Debug, Default)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct AddFlowOptions {
     /// Whether to skip writes and indexing.
     pub dry_run: bool,
+    /// Whether the dry-run analysis includes the parsed structure.
+    pub inspect: bool,
     /// Suppress non-essential output.
     pub quiet: bool,
     /// Disable language filtering for this add.
     pub no_language_filter: bool,
+    /// Locales kept by the language filter.
+    pub locales: LocaleSet,
+    /// Download bandwidth limit.
+    pub throttle: Option<Bandwidth>,
 }
 
 impl AddFlowOptions {
     pub const fn new(dry_run: bool, quiet: bool, no_language_filter: bool) -> Self {
         Self {
             dry_run,
+            inspect: false,
             quiet,
             no_language_filter,
+            locales: LocaleSet::ENGLISH,
+            throttle: None,
         }
     }
+
+    /// Report the parsed structure; implies a dry run.
+    #[must_use]
+    pub const fn with_inspect(mut self, inspect: bool) -> Self {
+        self.inspect = inspect;
+        self.dry_run |= inspect;
+        self
+    }
+
+    /// Keep these locales when filtering languages.
+    #[must_use]
+    pub const fn with_locales(mut self, locales: LocaleSet) -> Self {
+        self.locales = locales;
+        self
+    }
+
+    /// Limit download bandwidth.
+    #[must_use]
+    pub const fn with_throttle(mut self, throttle: Option<Bandwidth>) -> Self {
+        self.throttle = throttle;
+        self
+    }
 }
 
 /// Configuration for the `finalize_add` operation.
@@ -518,6 +713,7 @@ struct FinalizeConfig<'a> {
     spinner: &'a ProgressBar,
     metrics: PerformanceMetrics,
     no_language_filter: bool,
+    locales: LocaleSet,
 }
 
 impl AddRequest {
@@ -535,72 +731,217 @@ impl AddRequest {
             url: url.into(),
             descriptor,
             dry_run,
+            inspect: false,
             quiet,
             metrics,
             no_language_filter,
+            locales: LocaleSet::ENGLISH,
+            throttle: None,
+            scope: ScopeConfirmation::default(),
+            auth: None,
+            follow: None,
+            ignore_robots: false,
+            integrity: None,
         }
     }
+
+    /// Report the parsed structure; implies a dry run.
+    #[must_use]
+    pub const fn with_inspect(mut self, inspect: bool) -> Self {
+        self.inspect = inspect;
+        self.dry_run |= inspect;
+        self
+    }
+
+    /// Keep these locales when filtering languages.
+    #[must_use]
+    pub const fn with_locales(mut self, locales: LocaleSet) -> Self {
+        self.locales = locales;
+        self
+    }
+
+    /// Limit download bandwidth.
+    #[must_use]
+    pub const fn with_throttle(mut self, throttle: Option<Bandwidth>) -> Self {
+        self.throttle = throttle;
+        self
+    }
+
+    /// Set how cross-scope discovery results are confirmed.
+    #[must_use]
+    pub const fn with_scope(mut self, scope: ScopeConfirmation) -> Self {
+        self.scope = scope;
+        self
+    }
+
+    /// Fetch with credentials and save them for later syncs.
+    #[must_use]
+    pub fn with_auth(mut self, auth: Option<FetchAuth>) -> Self {
+        self.auth = auth;
+        self
+    }
+
+    /// Follow links from the fetched index.
+    #[must_use]
+    pub fn with_follow(mut self, follow: Option<FollowOptions>) -> Self {
+        self.follow = follow;
+        self
+    }
+
+    /// Probe without consulting robots.txt.
+    #[must_use]
+    pub const fn with_ignore_robots(mut self, ignore_robots: bool) -> Self {
+        self.ignore_robots = ignore_robots;
+        self
+    }
+
+    /// Verify the document against a checksum or signature and save it for
+    /// later syncs.
+    #[must_use]
+    pub fn with_integrity(mut self, integrity: Option<SourceIntegrity>) -> Self {
+        self.integrity = integrity;
+        self
+    }
 }
 
-#[derive(Debug, Deserialize)]
+/// A batch manifest for `blz add --manifest` (also written by `blz init`).
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
-struct ManifestFile {
-    #[serde(default)]
-    version: Option<String>,
+pub(super) struct ManifestFile {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(super) version: Option<String>,
     #[serde(rename = "source", default)]
-    sources: Vec<ManifestEntry>,
+    pub(super) sources: Vec<ManifestEntry>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
-struct ManifestEntry {
-    alias: String,
-    #[serde(default)]
-    name: String,
-    #[serde(default)]
-    description: Option<String>,
-    #[serde(default)]
-    category: String,
-    #[serde(default)]
-    tags: Vec<String>,
-    #[serde(default)]
-    url: Option<String>,
-    #[serde(default)]
-    path: Option<String>,
-    #[serde(default)]
+pub(super) struct ManifestEntry {
+    pub(super) alias: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub(super) name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(super) description: Option<String>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub(super) category: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(super) tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(super) url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(super) path: Option<String>,
+    #[serde(default, skip_serializing_if = "ManifestAliases::is_empty")]
     #[serde(rename = "aliases")]
-    alias_sets: ManifestAliases,
-    #[serde(default)]
-    _notes: Option<String>,
+    pub(super) alias_sets: ManifestAliases,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(super) mirrors: Vec<String>,
+    #[serde(default, skip_serializing_if = "SourceIntegrity::is_empty")]
+    pub(super) integrity: SourceIntegrity,
+    #[serde(default, skip_serializing)]
+    pub(super) _notes: Option<String>,
 }
 
-#[derive(Debug, Default, Deserialize)]
+#[derive(Debug, Default, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
-struct ManifestAliases {
-    #[serde(default)]
-    npm: Vec<String>,
-    #[serde(default)]
-    github: Vec<String>,
+pub(super) struct ManifestAliases {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(super) npm: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(super) github: Vec<String>,
+}
+
+impl ManifestAliases {
+    fn is_empty(&self) -> bool {
+        self.npm.is_empty() && self.github.is_empty()
+    }
 }
 
 /// Add a new documentation source.
 /// Dispatch an Add command.
 ///
 /// Handles both manifest-based and single-source additions.
+#[allow(clippy::too_many_lines)]
 pub async fn dispatch(args: AddArgs, quiet: bool, metrics: PerformanceMetrics) -> Result<()> {
+    crate::utils::throttle::set_insecure(args.insecure);
+    if let Some(path) = &args.descriptors {
+        let storage = Storage::new()?;
+        let report = super::descriptors::import(&storage, &super::descriptors::read(path)?)?;
+        super::descriptors::print_report(&report, quiet);
+        return Ok(());
+    }
     if let Some(manifest) = &args.manifest {
         execute_manifest(
             manifest,
             &args.only,
             metrics,
-            AddFlowOptions::new(args.dry_run, quiet, args.no_language_filter),
+            AddFlowOptions::new(args.dry_run, quiet, args.no_language_filter)
+                .with_locales(args.lang.unwrap_or_default())
+                .with_inspect(args.inspect)
+                .with_throttle(args.throttle),
         )
         .await
+    } else if let Some(spec) = &args.crate_spec {
+        auth_from_args(&args)?;
+        integrity_from_args(&args)?;
+        if args.source_type != AddSourceType::Llms {
+            bail!("--type does not apply to --crate sources");
+        }
+        let alias = args.alias.clone().unwrap_or_else(|| spec.name.clone());
+        dispatch_crate(&args, &alias, spec, quiet, metrics).await
+    } else if args.github {
+        auth_from_args(&args)?;
+        integrity_from_args(&args)?;
+        if args.source_type != AddSourceType::Llms {
+            bail!("--type does not apply to --github sources");
+        }
+        dispatch_github_repo(&args, quiet, metrics).await
     } else {
         let alias = args
             .alias
             .as_deref()
             .ok_or_else(|| anyhow::anyhow!("alias is required when manifest is not provided"))?;
+
+        if !args.members.is_empty() {
+            let descriptor = DescriptorInput::from_cli_inputs(
+                &args.aliases,
+                args.name.as_deref(),
+                args.description.as_deref(),
+                args.category.as_deref(),
+                &args.tags,
+            );
+            return add_collection(
+                alias,
+                args.members,
+                descriptor,
+                metrics,
+                AddFlowOptions::new(args.dry_run, quiet, args.no_language_filter)
+                    .with_locales(args.lang.unwrap_or_default())
+                    .with_inspect(args.inspect)
+                    .with_throttle(args.throttle),
+            )
+            .await;
+        }
+
+        let auth = auth_from_args(&args)?;
+        let integrity = integrity_from_args(&args)?;
+        if args.follow && args.source_type != AddSourceType::Llms {
+            bail!("--follow only applies to llms.txt sources");
+        }
+        match args.source_type {
+            AddSourceType::Llms => {},
+            AddSourceType::Openapi => return dispatch_openapi(&args, alias, quiet, metrics).await,
+            AddSourceType::Dir => return dispatch_docs_dir(&args, alias, quiet, metrics),
+        }
+
+        let crate_spec = args.url.as_deref().and_then(CrateSpec::from_prefixed);
+        if let Some(spec) = crate_spec.transpose()? {
+            return dispatch_crate(&args, alias, &spec, quiet, metrics).await;
+        }
+        let repo_spec = args.url.as_deref().and_then(RepoSpec::from_prefixed);
+        if let Some(spec) = repo_spec.transpose()? {
+            return dispatch_github(&args, alias, &spec, quiet, metrics).await;
+        }
+
         let url = args
             .url
             .as_deref()
@@ -612,7 +953,19 @@ pub async fn dispatch(args: AddArgs, quiet: bool, metrics: PerformanceMetrics) -
             args.description.as_deref(),
             args.category.as_deref(),
             &args.tags,
-        );
+        )
+        .with_mirrors(&args.mirrors)?;
+
+        let force_non_interactive = std::env::var_os("BLZ_FORCE_NON_INTERACTIVE").is_some();
+        let scope = ScopeConfirmation {
+            accept: args.accept_scope,
+            interactive: !(args.yes
+                || quiet
+                || args.dry_run
+                || args.inspect
+                || force_non_interactive
+                || !confirm::is_interactive()),
+        };
 
         let request = AddRequest::new(
             alias.to_string(),
@@ -622,90 +975,508 @@ pub async fn dispatch(args: AddArgs, quiet: bool, metrics: PerformanceMetrics) -
             quiet,
             metrics,
             args.no_language_filter,
-        );
+        )
+        .with_inspect(args.inspect)
+        .with_locales(args.lang.unwrap_or_default())
+        .with_throttle(args.throttle)
+        .with_scope(scope)
+        .with_auth(auth)
+        .with_follow(follow_from_args(&args)?)
+        .with_ignore_robots(args.ignore_robots)
+        .with_integrity(integrity);
 
         execute(request).await
     }
 }
 
-///
-/// # Arguments
-/// Execute the add flow given a prepared request.
-///
-/// # Errors
-///
-/// Returns an error if validation, fetching, or indexing fails.
-pub async fn execute(request: AddRequest) -> Result<()> {
-    let AddRequest {
-        alias,
-        url,
-        descriptor,
-        dry_run,
-        quiet,
-        metrics,
-        no_language_filter,
-    } = request;
-    let options = AddFlowOptions::new(dry_run, quiet, no_language_filter);
-
-    // Normalize the alias to kebab-case lowercase
-    let normalized_alias = normalize_alias(&alias);
+/// Parse the `--crate` value.
+fn parse_crate_spec(value: &str) -> std::result::Result<CrateSpec, String> {
+    value
+        .parse()
+        .map_err(|err: blz_core::Error| err.to_string())
+}
 
-    // Show normalization if it changed
-    if normalized_alias != alias && !quiet && !dry_run {
-        println!(
-            "Normalizing alias: '{}' → '{}'",
-            alias,
-            normalized_alias.green()
-        );
+/// Add a `crate:` source from CLI arguments.
+async fn dispatch_crate(
+    args: &AddArgs,
+    alias: &str,
+    spec: &CrateSpec,
+    quiet: bool,
+    metrics: PerformanceMetrics,
+) -> Result<()> {
+    if !args.mirrors.is_empty() {
+        bail!("--mirror is not supported for crate sources");
     }
-
-    // Validate the normalized alias
-    validate_alias(&normalized_alias)?;
-
-    let fetcher = Fetcher::new()?;
-
-    if let Ok(parsed) = Url::parse(&url) {
-        match parsed.scheme() {
-            "http" | "https" => {},
-            other => {
-                if !quiet && !dry_run {
-                    eprintln!(
-                        "Warning: URL scheme '{other}' may not be supported for fetching ({url}).\n \
-                         If this is a local file, consider hosting llms.txt or using a supported HTTP URL."
-                    );
-                }
-            },
-        }
-    } else if !quiet && !dry_run {
-        eprintln!("Warning: URL appears invalid: {url}");
+    if args.follow {
+        bail!("--follow is not supported for crate sources");
     }
-
-    fetch_and_index(
-        &normalized_alias,
-        &url,
+    let descriptor = DescriptorInput::from_cli_inputs(
+        &args.aliases,
+        args.name.as_deref(),
+        args.description.as_deref(),
+        args.category.as_deref(),
+        &args.tags,
+    );
+    add_crate(
+        alias,
+        spec,
         descriptor,
-        fetcher,
         metrics,
-        options,
+        AddFlowOptions::new(args.dry_run, quiet, args.no_language_filter)
+            .with_locales(args.lang.unwrap_or_default())
+            .with_inspect(args.inspect)
+            .with_throttle(args.throttle),
     )
     .await
 }
 
-pub async fn execute_manifest(
-    manifest_path: &Path,
-    only: &[String],
+/// Add a `github:` source from CLI arguments.
+async fn dispatch_github(
+    args: &AddArgs,
+    alias: &str,
+    spec: &RepoSpec,
+    quiet: bool,
     metrics: PerformanceMetrics,
-    options: AddFlowOptions,
 ) -> Result<()> {
-    let AddFlowOptions {
-        dry_run,
+    let descriptor = DescriptorInput::from_cli_inputs(
+        &args.aliases,
+        args.name.as_deref(),
+        args.description.as_deref(),
+        args.category.as_deref(),
+        &args.tags,
+    )
+    .with_mirrors(&args.mirrors)?;
+    let announce = !quiet && !args.dry_run;
+    let (url, descriptor) = resolve_github_source(spec, descriptor, announce).await?;
+
+    let request = AddRequest::new(
+        alias.to_string(),
+        url,
+        descriptor,
+        args.dry_run,
         quiet,
-        no_language_filter,
-    } = options;
-    let manifest_text = async_fs::read_to_string(manifest_path).await?;
-    let manifest: ManifestFile = toml::from_str(&manifest_text)?;
+        metrics,
+        args.no_language_filter,
+    )
+    .with_inspect(args.inspect)
+    .with_locales(args.lang.unwrap_or_default())
+    .with_throttle(args.throttle)
+    .with_integrity(integrity_from_args(args)?);
 
-    let manifest_abs =
+    execute(request).await
+}
+
+/// Add a `--github` repository source from CLI arguments.
+///
+/// With one positional the repository takes the alias slot and the alias
+/// defaults to the repository name.
+async fn dispatch_github_repo(
+    args: &AddArgs,
+    quiet: bool,
+    metrics: PerformanceMetrics,
+) -> Result<()> {
+    let (alias, input) = match (&args.alias, &args.url) {
+        (Some(alias), Some(repo)) => (Some(alias.as_str()), repo.as_str()),
+        (Some(repo), None) => (None, repo.as_str()),
+        (None, _) => bail!("--github requires a repository as OWNER/REPO"),
+    };
+    let spec: RepoSpec = input
+        .strip_prefix(blz_core::github::GITHUB_PREFIX)
+        .unwrap_or(input)
+        .parse()?;
+    let alias = alias.map_or_else(|| spec.repo.clone(), str::to_string);
+    let descriptor = DescriptorInput::from_cli_inputs(
+        &args.aliases,
+        args.name.as_deref(),
+        args.description.as_deref(),
+        args.category.as_deref(),
+        &args.tags,
+    );
+    add_github_repo(
+        &alias,
+        &spec,
+        descriptor,
+        metrics,
+        AddFlowOptions::new(args.dry_run, quiet, args.no_language_filter)
+            .with_locales(args.lang.unwrap_or_default())
+            .with_inspect(args.inspect),
+    )
+    .await
+}
+
+/// Resolve a `github:` spec to the URL of its llms file.
+///
+/// The repository's default branch and description come from the GitHub API
+/// (cached in storage); the document itself is then added like any remote URL,
+/// so later syncs revalidate it without touching the API. The description
+/// fills in a missing one and the repository joins the GitHub aliases.
+async fn resolve_github_source(
+    spec: &RepoSpec,
+    mut descriptor: DescriptorInput,
+    announce: bool,
+) -> Result<(String, DescriptorInput)> {
+    let client = GithubClient::from_env()?;
+    let storage = Storage::new()?;
+    let repo = client
+        .repo(&storage.github_cache(), spec)
+        .await
+        .with_context(|| format!("Failed to look up GitHub repository '{}'", spec.full_name()))?;
+    let reference = spec.reference.as_deref().unwrap_or(&repo.default_branch);
+    let url = client.find_docs(spec, reference).await?;
+    if announce {
+        println!("Using {}", url.cyan());
+    }
+
+    if descriptor.description.is_none() {
+        descriptor.description = repo.description.filter(|d| !d.trim().is_empty());
+    }
+    if !descriptor.github_aliases.contains(&repo.full_name) {
+        descriptor.github_aliases.push(repo.full_name);
+    }
+    Ok((url, descriptor))
+}
+
+/// Add an `OpenAPI` spec source from CLI arguments.
+async fn dispatch_openapi(
+    args: &AddArgs,
+    alias: &str,
+    quiet: bool,
+    metrics: PerformanceMetrics,
+) -> Result<()> {
+    if !args.mirrors.is_empty() {
+        bail!("--mirror is not supported for OpenAPI sources");
+    }
+    let location = args
+        .url
+        .as_deref()
+        .ok_or_else(|| anyhow::anyhow!("--type openapi requires a spec URL or path"))?;
+    let descriptor = DescriptorInput::from_cli_inputs(
+        &args.aliases,
+        args.name.as_deref(),
+        args.description.as_deref(),
+        args.category.as_deref(),
+        &args.tags,
+    );
+    add_openapi(
+        alias,
+        location,
+        descriptor,
+        metrics,
+        AddFlowOptions::new(args.dry_run, quiet, args.no_language_filter)
+            .with_locales(args.lang.unwrap_or_default())
+            .with_inspect(args.inspect)
+            .with_throttle(args.throttle),
+    )
+    .await
+}
+
+/// Add a local documentation folder from CLI arguments.
+fn dispatch_docs_dir(
+    args: &AddArgs,
+    alias: &str,
+    quiet: bool,
+    metrics: PerformanceMetrics,
+) -> Result<()> {
+    if !args.mirrors.is_empty() {
+        bail!("--mirror is not supported for folder sources");
+    }
+    let path = args
+        .url
+        .as_deref()
+        .ok_or_else(|| anyhow::anyhow!("--type dir requires a folder path"))?;
+    let descriptor = DescriptorInput::from_cli_inputs(
+        &args.aliases,
+        args.name.as_deref(),
+        args.description.as_deref(),
+        args.category.as_deref(),
+        &args.tags,
+    );
+    add_docs_dir(
+        alias,
+        Path::new(path),
+        descriptor,
+        metrics,
+        AddFlowOptions::new(args.dry_run, quiet, args.no_language_filter)
+            .with_locales(args.lang.unwrap_or_default())
+            .with_inspect(args.inspect),
+    )
+}
+
+///
+/// # Arguments
+/// Execute the add flow given a prepared request.
+///
+/// # Errors
+///
+/// Returns an error if validation, fetching, or indexing fails.
+#[allow(clippy::too_many_lines)]
+pub async fn execute(request: AddRequest) -> Result<()> {
+    let AddRequest {
+        alias,
+        url,
+        descriptor,
+        dry_run,
+        inspect,
+        quiet,
+        metrics,
+        no_language_filter,
+        locales,
+        throttle,
+        scope,
+        auth,
+        follow,
+        ignore_robots,
+        integrity,
+    } = request;
+    let options = AddFlowOptions::new(dry_run, quiet, no_language_filter)
+        .with_locales(locales)
+        .with_inspect(inspect)
+        .with_throttle(throttle);
+
+    // Normalize the alias to kebab-case lowercase
+    let normalized_alias = normalize_alias(&alias);
+
+    // Show normalization if it changed
+    if normalized_alias != alias && !quiet && !dry_run {
+        println!(
+            "Normalizing alias: '{}' → '{}'",
+            alias,
+            normalized_alias.green()
+        );
+    }
+
+    // Validate the normalized alias
+    validate_alias(&normalized_alias)?;
+
+    let local_path = local_source_path(&url);
+    if url == STDIN_LOCATION || local_path.is_some() {
+        if !descriptor.mirrors.is_empty() {
+            bail!("--mirror is not supported for local sources");
+        }
+        if auth.is_some() {
+            bail!("Auth options are not supported for local sources");
+        }
+        if follow.is_some() {
+            bail!("--follow is not supported for local sources");
+        }
+        if integrity.is_some() {
+            bail!("Checksum and signature checks are not supported for local sources");
+        }
+    }
+    if url == STDIN_LOCATION {
+        return add_stdin_source(&normalized_alias, descriptor, metrics, options).await;
+    }
+    if let Some(path) = local_path {
+        if is_local_openapi_spec(&path) {
+            let location = path.to_string_lossy();
+            return add_openapi(&normalized_alias, &location, descriptor, metrics, options).await;
+        }
+        return add_local_source(&normalized_alias, &path, descriptor, metrics, options).await;
+    }
+
+    let mut fetcher = crate::utils::throttle::fetcher(throttle)?;
+    if let Some(auth) = &auth {
+        fetcher = fetcher.with_auth(auth)?;
+    }
+
+    let url = if should_discover(&url) {
+        match discover_source_url(&url, scope, quiet || dry_run, ignore_robots).await? {
+            Some(discovered) => discovered,
+            None => return Ok(()),
+        }
+    } else {
+        url
+    };
+
+    if let Ok(parsed) = Url::parse(&url) {
+        match parsed.scheme() {
+            "http" | "https" => {},
+            other => {
+                if !quiet && !dry_run {
+                    eprintln!(
+                        "Warning: URL scheme '{other}' may not be supported for fetching ({url}).\n \
+                         If this is a local file, consider hosting llms.txt or using a supported HTTP URL."
+                    );
+                }
+            },
+        }
+    } else if !quiet && !dry_run {
+        eprintln!("Warning: URL appears invalid: {url}");
+    }
+
+    if blz_core::pdf::is_pdf_location(&url) {
+        if follow.is_some() {
+            bail!("--follow is not supported for PDF sources");
+        }
+        if integrity.is_some() {
+            bail!("Checksum and signature checks are not supported for PDF sources");
+        }
+        add_remote_pdf(
+            &normalized_alias,
+            &url,
+            descriptor,
+            &fetcher,
+            metrics,
+            options,
+        )
+        .await?;
+    } else {
+        fetch_and_index(
+            &normalized_alias,
+            &url,
+            descriptor,
+            fetcher,
+            metrics,
+            options,
+            follow.as_ref(),
+            integrity.as_ref(),
+        )
+        .await?;
+    }
+
+    if let Some(auth) = auth.filter(|_| !dry_run) {
+        Storage::new()?.save_fetch_auth(&normalized_alias, &auth)?;
+    }
+    Ok(())
+}
+
+/// Link-following options from `--follow`, using the configured policy.
+fn follow_from_args(args: &AddArgs) -> Result<Option<FollowOptions>> {
+    if !args.follow {
+        return Ok(None);
+    }
+    let follow = Config::load()
+        .map_or_else(
+            |_| FollowOptions::default(),
+            |config| FollowOptions::from_defaults(&config.defaults),
+        )
+        .with_limits(args.follow_depth, args.follow_max_pages)
+        .ignoring_robots(args.ignore_robots);
+    if follow.policy == FollowLinks::None {
+        bail!("--follow is disabled by `defaults.follow_links = \"none\"` in config");
+    }
+    Ok(Some(follow))
+}
+
+/// Credentials from `--auth-bearer-env` and `--auth-header`, if any were given.
+///
+/// Crate, GitHub, `OpenAPI`, and folder sources fetch through their own clients,
+/// so credentials are rejected for them rather than silently dropped.
+fn auth_from_args(args: &AddArgs) -> Result<Option<FetchAuth>> {
+    let mut auth = FetchAuth {
+        bearer: args.auth_bearer_env.as_deref().map(Secret::env),
+        ..FetchAuth::default()
+    };
+    for header in &args.auth_headers {
+        let (name, value) = header
+            .split_once('=')
+            .filter(|(name, _)| !name.trim().is_empty())
+            .ok_or_else(|| {
+                anyhow::anyhow!("Invalid --auth-header '{header}': expected NAME=VALUE")
+            })?;
+        auth.headers
+            .insert(name.trim().to_string(), Secret::new(value.trim()));
+    }
+    if auth.is_empty() {
+        return Ok(None);
+    }
+
+    let prefixed = args
+        .url
+        .as_deref()
+        .is_some_and(|url| url.starts_with("crate:") || url.starts_with("github:"))
+        || args.crate_spec.is_some()
+        || args.github;
+    if args.source_type != AddSourceType::Llms || prefixed {
+        bail!("Auth options are only supported for llms.txt URLs");
+    }
+    Ok(Some(auth))
+}
+
+/// Checksum and signature from `--sha256`, `--minisign-key`, and
+/// `--signature-url`, if any were given.
+///
+/// Only llms.txt documents are checked on sync, so the options are rejected
+/// for other source kinds rather than silently dropped.
+fn integrity_from_args(args: &AddArgs) -> Result<Option<SourceIntegrity>> {
+    let integrity = SourceIntegrity {
+        sha256: args.sha256.clone(),
+        minisign_key: args.minisign_key.clone(),
+        signature_url: args.signature_url.clone(),
+    };
+    if integrity.is_empty() {
+        return Ok(None);
+    }
+    // `github:` specs resolve to an llms file and are checked like any URL
+    let prefixed = args
+        .url
+        .as_deref()
+        .is_some_and(|url| url.starts_with("crate:"))
+        || args.crate_spec.is_some()
+        || args.github;
+    if args.source_type != AddSourceType::Llms || prefixed {
+        bail!("--sha256 and --minisign-key are only supported for llms.txt URLs");
+    }
+    integrity.validate()?;
+    Ok(Some(integrity))
+}
+
+/// Add one source given as a URL or a `crate:`/`github:` spec.
+///
+/// Used for batch adds (manifests and `blz discover --add`), where entries
+/// carry no per-source flags beyond an optional checksum or signature.
+pub(super) async fn add_location(
+    alias: &str,
+    location: &str,
+    descriptor_input: DescriptorInput,
+    integrity: Option<SourceIntegrity>,
+    metrics: PerformanceMetrics,
+    options: AddFlowOptions,
+) -> Result<()> {
+    if let Some(spec) = CrateSpec::from_prefixed(location).transpose()? {
+        if integrity.is_some() {
+            bail!("Checksum and signature checks are not supported for crate sources");
+        }
+        return add_crate(alias, &spec, descriptor_input, metrics, options).await;
+    }
+    let announce = !options.quiet && !options.dry_run;
+    let (url, descriptor_input) = match RepoSpec::from_prefixed(location).transpose()? {
+        Some(spec) => resolve_github_source(&spec, descriptor_input, announce).await?,
+        None => (location.to_string(), descriptor_input),
+    };
+    let request = AddRequest::new(
+        alias.to_string(),
+        url,
+        descriptor_input,
+        options.dry_run,
+        options.quiet,
+        metrics,
+        options.no_language_filter,
+    )
+    .with_inspect(options.inspect)
+    .with_locales(options.locales)
+    .with_throttle(options.throttle)
+    .with_integrity(integrity);
+    execute(request).await
+}
+
+#[allow(clippy::too_many_lines)]
+pub async fn execute_manifest(
+    manifest_path: &Path,
+    only: &[String],
+    metrics: PerformanceMetrics,
+    options: AddFlowOptions,
+) -> Result<()> {
+    let AddFlowOptions {
+        dry_run,
+        inspect,
+        quiet,
+        ..
+    } = options;
+    let manifest_text = async_fs::read_to_string(manifest_path).await?;
+    let manifest: ManifestFile = toml::from_str(&manifest_text)?;
+
+    let manifest_abs =
         sync_fs::canonicalize(manifest_path).unwrap_or_else(|_| manifest_path.to_path_buf());
     let manifest_path_str = manifest_abs.to_string_lossy().to_string();
 
@@ -714,7 +1485,7 @@ pub async fn execute_manifest(
         filter.sort();
     }
 
-    let mut processed = 0usize;
+    let mut processed: Vec<String> = Vec::new();
     for entry in manifest.sources {
         let normalized_alias = normalize_alias(&entry.alias);
 
@@ -737,20 +1508,36 @@ pub async fn execute_manifest(
                 entry_alias: entry.alias.clone(),
                 version: manifest.version.clone(),
             }),
+            mirrors: Vec::new(),
+        }
+        .with_mirrors(&entry.mirrors)?;
+        let integrity = if entry.integrity.is_empty() {
+            None
+        } else {
+            entry
+                .integrity
+                .validate()
+                .with_context(|| format!("Manifest entry '{}'", entry.alias))?;
+            Some(entry.integrity.clone())
         };
 
         match (entry.url.as_ref(), entry.path.as_ref()) {
             (Some(url), None) => {
-                let request = AddRequest::new(
-                    normalized_alias.clone(),
-                    url.clone(),
-                    descriptor_input.clone(),
-                    dry_run,
-                    quiet,
+                add_location(
+                    &normalized_alias,
+                    url,
+                    descriptor_input,
+                    integrity,
                     metrics.clone(),
-                    no_language_filter,
+                    options,
+                )
+                .await?;
+            },
+            (None, Some(_)) if integrity.is_some() => {
+                anyhow::bail!(
+                    "Manifest entry '{}': integrity checks are not supported for local paths",
+                    entry.alias
                 );
-                execute(request).await?;
             },
             (None, Some(path)) => {
                 let base_dir = manifest_abs.parent().unwrap_or_else(|| Path::new("."));
@@ -763,10 +1550,8 @@ pub async fn execute_manifest(
                     &normalized_alias,
                     &resolved,
                     descriptor_input,
-                    dry_run,
-                    quiet,
                     metrics.clone(),
-                    no_language_filter,
+                    options,
                 )
                 .await?;
             },
@@ -784,21 +1569,969 @@ pub async fn execute_manifest(
             },
         }
 
-        processed += 1;
-    }
+        processed.push(normalized_alias);
+    }
+
+    if processed.is_empty() {
+        if !quiet {
+            eprintln!("No manifest sources matched the provided filters.");
+        }
+    } else if !dry_run && !inspect {
+        let lock_path = manifest_abs
+            .parent()
+            .unwrap_or_else(|| Path::new("."))
+            .join(lockfile::LOCKFILE_NAME);
+        let mut lock = Lockfile::load_or_default(&lock_path)?;
+        lock.record(&Storage::new()?, &processed)?;
+        lock.save(&lock_path)?;
+        if !quiet {
+            println!("Wrote {}", lock_path.display());
+        }
+    }
+
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments, clippy::too_many_lines)]
+async fn fetch_and_index(
+    alias: &str,
+    url: &str,
+    descriptor_input: DescriptorInput,
+    fetcher: Fetcher,
+    metrics: PerformanceMetrics,
+    options: AddFlowOptions,
+    follow: Option<&FollowOptions>,
+    integrity: Option<&SourceIntegrity>,
+) -> Result<()> {
+    let AddFlowOptions {
+        dry_run,
+        quiet,
+        no_language_filter,
+        ..
+    } = options;
+    // Check if source already exists (validate even in dry-run mode)
+    let storage = Storage::new()?;
+    if storage.exists(alias) {
+        anyhow::bail!(
+            "Source '{alias}' already exists. Use 'blz refresh {alias}' or choose a different alias."
+        );
+    }
+
+    let spinner = if quiet {
+        ProgressBar::hidden()
+    } else {
+        create_spinner("Resolving URL...")
+    };
+
+    // Resolve the best URL variant (llms-full.txt vs llms.txt); a checksum
+    // or signature covers the URL as given
+    spinner.set_message("Resolving URL variant...");
+    let resolved = if integrity.is_some() {
+        url_resolver::resolve_exact_url(&fetcher, url).await?
+    } else {
+        url_resolver::resolve_best_url(&fetcher, url).await?
+    };
+
+    // A full variant already has the pages the index would link to
+    let follow = follow.filter(|_| resolved.variant != SourceVariant::LlmsFull);
+
+    // Fetch from resolved URL
+    spinner.set_message("Fetching documentation...");
+    let fetch_result = fetcher
+        .fetch_with_cache(&resolved.final_url, None, None)
+        .await?;
+
+    let (content, sha256, etag, last_modified) = match fetch_result {
+        blz_core::FetchResult::Modified {
+            content,
+            sha256,
+            etag,
+            last_modified,
+        } => (content, sha256, etag, last_modified),
+        blz_core::FetchResult::NotModified { .. } => {
+            anyhow::bail!(
+                "Server returned 304 Not Modified on initial fetch. This should not happen for new sources."
+            );
+        },
+    };
+
+    let signature = match integrity {
+        Some(integrity) => {
+            spinner.set_message("Verifying integrity...");
+            integrity
+                .verify_download(&fetcher, &resolved.final_url, &content)
+                .await
+                .with_context(|| format!("Refusing to add '{alias}'"))?
+        },
+        None => None,
+    };
+
+    // Docs sites without llms.txt serve HTML pages
+    let is_html = html::looks_like_html(&content);
+    // Spec URLs added without `--type openapi`
+    let is_spec = follow.is_none() && !is_html && openapi::looks_like_spec(&content);
+    if integrity.is_some() && (is_html || is_spec) {
+        bail!(
+            "Checksum and signature checks only apply to llms.txt documents, \
+             not HTML pages or OpenAPI specs"
+        );
+    }
+
+    // Show warning if index file
+    if resolved.should_warn && follow.is_none() && !is_html && !is_spec && !quiet && !dry_run {
+        warn_index_only_file(&spinner, resolved.line_count);
+    }
+
+    let converted = match follow {
+        Some(follow) => {
+            spinner.set_message("Following links...");
+            let document = follow_links(&fetcher, &resolved.final_url, &content, follow).await?;
+            if !quiet && !dry_run {
+                report_followed(&spinner, &document);
+            }
+            Some(document.content)
+        },
+        None if is_html => {
+            spinner.set_message("Converting HTML...");
+            Some(html::to_markdown(&content)?.markdown)
+        },
+        None if is_spec => {
+            spinner.set_message("Rendering OpenAPI spec...");
+            Some(openapi::render_openapi(&content)?)
+        },
+        None => None,
+    };
+    let (content, sha256, etag, last_modified) =
+        converted.map_or((content, sha256, etag, last_modified), |markdown| {
+            let mut hasher = Sha256::new();
+            hasher.update(markdown.as_bytes());
+            let sha256 = STANDARD.encode(hasher.finalize());
+            (markdown, sha256, None, None)
+        });
+
+    // Parse the content and apply language filtering if enabled
+    spinner.set_message("Parsing markdown...");
+    let (parse_result, inspection) = parse_content(alias, &content, options)?;
+
+    // In dry-run mode, analyze content and output JSON instead of indexing
+    if dry_run {
+        output_dry_run_analysis(alias, url, &resolved, &content, &parse_result, inspection)?;
+        spinner.finish_and_clear();
+        return Ok(());
+    }
+    let mut resolved_addition =
+        build_remote_addition(content, sha256, etag, last_modified, &resolved);
+    let final_url = resolved.final_url.clone();
+    if let Some(follow) = follow {
+        resolved_addition.origin.source_type = Some(SourceType::Followed {
+            url: final_url,
+            follow: follow.clone(),
+        });
+    } else if is_html {
+        resolved_addition.origin.source_type = Some(SourceType::Html { url: final_url });
+    } else if is_spec {
+        resolved_addition.origin.source_type = Some(SourceType::OpenApi {
+            location: final_url,
+        });
+    }
+
+    let llms_json = finalize_add(FinalizeConfig {
+        storage: &storage,
+        alias,
+        resolved: resolved_addition,
+        descriptor_input,
+        parse_result: &parse_result,
+        spinner: &spinner,
+        metrics,
+        no_language_filter,
+        locales: options.locales,
+    })?;
+    if let Some(integrity) = integrity {
+        storage.save_integrity(alias, integrity)?;
+    }
+    if let Some(signature) = signature {
+        storage.save_signature(alias, &signature)?;
+    }
+
+    spinner.finish_and_clear();
+
+    if !quiet {
+        println!(
+            "{} {} ({} headings, {} lines)",
+            "✓ Added".green(),
+            alias.green(),
+            count_headings(&llms_json.toc),
+            llms_json.line_index.total_lines
+        );
+    }
+
+    Ok(())
+}
+
+/// Summarize which linked pages were merged and which were left out.
+fn report_followed(spinner: &ProgressBar, document: &FollowedDocument) {
+    spinner.suspend(|| {
+        println!("Followed {} linked page(s)", document.pages.len());
+        for skipped in &document.skipped {
+            println!(
+                "  {} {} ({})",
+                "skipped".yellow(),
+                skipped.url,
+                skipped.reason.bright_black()
+            );
+        }
+        if document.truncated > 0 {
+            println!(
+                "  {} more link(s) not fetched; raise --follow-max-pages to include them",
+                document.truncated
+            );
+        }
+    });
+}
+
+/// Output dry-run analysis as JSON for remote sources.
+fn output_dry_run_analysis(
+    alias: &str,
+    url: &str,
+    resolved: &url_resolver::ResolvedUrl,
+    content: &str,
+    parse_result: &blz_core::ParseResult,
+    inspection: Option<Inspection>,
+) -> Result<()> {
+    let char_count = content.len();
+    let header_count = parse_result.heading_blocks.len();
+    let sections = parse_result.toc.len();
+    let file_size = format_size(content.len());
+
+    let content_type = match resolved.content_type {
+        blz_core::ContentType::Full => "full",
+        blz_core::ContentType::Index => "index",
+        blz_core::ContentType::Mixed => "mixed",
+    };
+
+    let analysis = SourceAnalysis {
+        name: alias.to_string(),
+        url: url.to_string(),
+        final_url: resolved.final_url.clone(),
+        analysis: ContentAnalysis {
+            line_count: resolved.line_count,
+            char_count,
+            header_count,
+            sections,
+            file_size,
+            content_type: content_type.to_string(),
+        },
+        would_index: true,
+        inspection,
+    };
+
+    let json = serde_json::to_string_pretty(&analysis)?;
+    println!("{json}");
+    Ok(())
+}
+
+/// Output dry-run analysis as JSON for local file sources.
+fn output_local_dry_run_analysis(
+    alias: &str,
+    path: &Path,
+    content: &str,
+    parse_result: &blz_core::ParseResult,
+    inspection: Option<Inspection>,
+) -> Result<()> {
+    let analysis = SourceAnalysis {
+        name: alias.to_string(),
+        url: path.display().to_string(),
+        final_url: path.display().to_string(),
+        analysis: ContentAnalysis {
+            line_count: parse_result.line_count,
+            char_count: content.len(),
+            header_count: parse_result.heading_blocks.len(),
+            sections: parse_result.toc.len(),
+            file_size: format_size(content.len()),
+            content_type: "local".to_string(),
+        },
+        would_index: true,
+        inspection,
+    };
+    let json = serde_json::to_string_pretty(&analysis)?;
+    println!("{json}");
+    Ok(())
+}
+
+async fn add_local_source(
+    alias: &str,
+    path: &Path,
+    descriptor_input: DescriptorInput,
+    metrics: PerformanceMetrics,
+    options: AddFlowOptions,
+) -> Result<()> {
+    let metadata = async_fs::metadata(path).await.map_err(|e| {
+        anyhow::anyhow!(
+            "Failed to access local source at '{}': {}",
+            path.display(),
+            e
+        )
+    })?;
+    if !metadata.is_file() {
+        anyhow::bail!("Local source '{}' is not a file", path.display());
+    }
+
+    // The file's mtime is kept as `last_modified` so sync can skip unchanged files
+    let blz_core::FetchResult::Modified {
+        content,
+        sha256,
+        last_modified,
+        ..
+    } = blz_core::fetcher::fetch_local(path, None)
+        .with_context(|| format!("Failed to read local source '{}'", path.display()))?
+    else {
+        anyhow::bail!("Local source '{}' could not be read", path.display());
+    };
+
+    let abs_path = sync_fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let path_str = abs_path.to_string_lossy().to_string();
+    let document = LocalDocument {
+        resolved: ResolvedAddition {
+            content,
+            sha256,
+            etag: None,
+            last_modified,
+            resolved_url: path_str.clone(),
+            variant: SourceVariant::Llms,
+            origin: SourceOrigin {
+                manifest: None,
+                source_type: Some(SourceType::LocalFile { path: path_str }),
+            },
+        },
+        display: path,
+    };
+    index_local_document(alias, document, descriptor_input, metrics, options)
+}
+
+/// Whether a local file is an `OpenAPI` or Swagger spec in JSON or YAML.
+fn is_local_openapi_spec(path: &Path) -> bool {
+    let is_spec_extension = path.extension().is_some_and(|ext| {
+        ["json", "yaml", "yml"]
+            .iter()
+            .any(|candidate| ext.eq_ignore_ascii_case(candidate))
+    });
+    is_spec_extension
+        && sync_fs::read_to_string(path).is_ok_and(|text| openapi::looks_like_spec(&text))
+}
+
+/// Add a source from a document piped in on standard input.
+///
+/// There is nothing to re-read later, so sync leaves the source as is; adding
+/// it again under a new alias (or removing and re-adding) picks up changes.
+async fn add_stdin_source(
+    alias: &str,
+    descriptor_input: DescriptorInput,
+    metrics: PerformanceMetrics,
+    options: AddFlowOptions,
+) -> Result<()> {
+    use tokio::io::AsyncReadExt as _;
+
+    let mut content = String::new();
+    tokio::io::stdin()
+        .read_to_string(&mut content)
+        .await
+        .context("Failed to read documentation from stdin")?;
+    if content.trim().is_empty() {
+        anyhow::bail!("No documentation received on stdin");
+    }
+
+    let mut hasher = Sha256::new();
+    hasher.update(content.as_bytes());
+    let sha256 = STANDARD.encode(hasher.finalize());
+
+    let document = LocalDocument {
+        resolved: ResolvedAddition {
+            content,
+            sha256,
+            etag: None,
+            last_modified: None,
+            resolved_url: "stdin".to_string(),
+            variant: SourceVariant::Llms,
+            origin: SourceOrigin {
+                manifest: None,
+                source_type: Some(SourceType::Stdin),
+            },
+        },
+        display: Path::new("stdin"),
+    };
+    index_local_document(alias, document, descriptor_input, metrics, options)
+}
+
+/// Add a remote PDF, indexed page by page from its extracted text.
+///
+/// Sync refetches the file and re-indexes it when the text changed.
+async fn add_remote_pdf(
+    alias: &str,
+    url: &str,
+    descriptor_input: DescriptorInput,
+    fetcher: &Fetcher,
+    metrics: PerformanceMetrics,
+    options: AddFlowOptions,
+) -> Result<()> {
+    let spinner = if options.quiet {
+        ProgressBar::hidden()
+    } else {
+        create_spinner("Extracting PDF text...")
+    };
+    let content = blz_core::pdf::fetch_pdf_docs(fetcher, url)
+        .await
+        .with_context(|| format!("Failed to add PDF from {url}"))?;
+    spinner.finish_and_clear();
+
+    let sha256 = STANDARD.encode(Sha256::digest(content.as_bytes()));
+    let document = LocalDocument {
+        resolved: ResolvedAddition {
+            content,
+            sha256,
+            etag: None,
+            last_modified: None,
+            resolved_url: url.to_string(),
+            variant: SourceVariant::Llms,
+            origin: SourceOrigin {
+                manifest: None,
+                source_type: Some(SourceType::Pdf {
+                    url: url.to_string(),
+                }),
+            },
+        },
+        display: Path::new(url),
+    };
+    index_local_document(alias, document, descriptor_input, metrics, options)
+}
+
+/// Add a source assembled from pages scraped off a sitemap (`blz generate`).
+///
+/// Sync rescrapes it through the `generate.json` manifest written next to it.
+pub(super) fn add_generated_source(
+    alias: &str,
+    sitemap_url: &str,
+    content: String,
+    metrics: PerformanceMetrics,
+    quiet: bool,
+) -> Result<()> {
+    let sha256 = STANDARD.encode(Sha256::digest(content.as_bytes()));
+    let document = LocalDocument {
+        resolved: ResolvedAddition {
+            content,
+            sha256,
+            etag: None,
+            last_modified: None,
+            resolved_url: sitemap_url.to_string(),
+            variant: SourceVariant::LlmsFull,
+            origin: SourceOrigin {
+                manifest: None,
+                source_type: Some(SourceType::Generated {
+                    sitemap: sitemap_url.to_string(),
+                }),
+            },
+        },
+        display: Path::new(sitemap_url),
+    };
+    let options = AddFlowOptions::new(false, quiet, false);
+    index_local_document(
+        alias,
+        document,
+        DescriptorInput::default(),
+        metrics,
+        options,
+    )
+}
+
+/// Document read from disk or stdin, ready to index.
+struct LocalDocument<'a> {
+    resolved: ResolvedAddition,
+    /// Location shown in dry-run output.
+    display: &'a Path,
+}
+
+/// Parse and store a document that was read locally rather than fetched.
+fn index_local_document(
+    alias: &str,
+    document: LocalDocument<'_>,
+    descriptor_input: DescriptorInput,
+    metrics: PerformanceMetrics,
+    options: AddFlowOptions,
+) -> Result<()> {
+    let AddFlowOptions {
+        dry_run,
+        quiet,
+        no_language_filter,
+        ..
+    } = options;
+    let storage = Storage::new()?;
+    if storage.exists(alias) {
+        anyhow::bail!(
+            "Source '{alias}' already exists. Use 'blz refresh {alias}' or choose a different alias."
+        );
+    }
+
+    let spinner = if quiet {
+        ProgressBar::hidden()
+    } else {
+        create_spinner("Parsing markdown...")
+    };
+
+    // Apply language filtering for consistency with remote sources
+    let LocalDocument { resolved, display } = document;
+    let (parse_result, inspection) = parse_content(alias, &resolved.content, options)?;
+
+    if dry_run {
+        output_local_dry_run_analysis(
+            alias,
+            display,
+            &resolved.content,
+            &parse_result,
+            inspection,
+        )?;
+        spinner.finish_and_clear();
+        return Ok(());
+    }
+
+    let llms_json = finalize_add(FinalizeConfig {
+        storage: &storage,
+        alias,
+        resolved,
+        descriptor_input,
+        parse_result: &parse_result,
+        spinner: &spinner,
+        metrics,
+        no_language_filter,
+        locales: options.locales,
+    })?;
+
+    spinner.finish_and_clear();
+
+    if !quiet {
+        println!(
+            "{} {} ({} headings, {} lines)",
+            "✓ Added".green(),
+            alias.green(),
+            count_headings(&llms_json.toc),
+            llms_json.line_index.total_lines
+        );
+    }
+
+    Ok(())
+}
+
+/// Local file named by an `add` location: a `file://` URL, or a path that
+/// exists on disk.
+fn local_source_path(location: &str) -> Option<PathBuf> {
+    if let Some(path) = blz_core::fetcher::file_url_path(location) {
+        return Some(path);
+    }
+    let is_web_url =
+        Url::parse(location).is_ok_and(|parsed| matches!(parsed.scheme(), "http" | "https"));
+    let path = Path::new(location);
+    (!is_web_url && path.is_file()).then(|| path.to_path_buf())
+}
+
+/// Add a collection source merged from several member files.
+async fn add_collection(
+    alias: &str,
+    mut members: Vec<blz_core::CollectionMember>,
+    descriptor_input: DescriptorInput,
+    metrics: PerformanceMetrics,
+    options: AddFlowOptions,
+) -> Result<()> {
+    let AddFlowOptions {
+        dry_run,
+        quiet,
+        no_language_filter,
+        ..
+    } = options;
+
+    let normalized_alias = normalize_alias(alias);
+    if normalized_alias != alias && !quiet && !dry_run {
+        println!(
+            "Normalizing alias: '{}' → '{}'",
+            alias,
+            normalized_alias.green()
+        );
+    }
+    validate_alias(&normalized_alias)?;
+    let alias = normalized_alias.as_str();
+    blz_core::collection::validate_members(&members)?;
+
+    let storage = Storage::new()?;
+    if storage.exists(alias) {
+        anyhow::bail!(
+            "Source '{alias}' already exists. Use 'blz refresh {alias}' or choose a different alias."
+        );
+    }
+
+    let spinner = if quiet {
+        ProgressBar::hidden()
+    } else {
+        create_spinner("Fetching collection members...")
+    };
+
+    let contents = fetch_collection_members(&mut members, &spinner, options.throttle).await?;
+    let content = blz_core::collection::merge_members(
+        members
+            .iter()
+            .zip(&contents)
+            .map(|(member, content)| (member.prefix.as_str(), content.as_str())),
+    );
+    let mut hasher = Sha256::new();
+    hasher.update(content.as_bytes());
+    let sha256 = STANDARD.encode(hasher.finalize());
+
+    spinner.set_message("Parsing markdown...");
+    let (parse_result, inspection) = parse_content(alias, &content, options)?;
+
+    if dry_run {
+        output_collection_dry_run_analysis(alias, &members, &content, &parse_result, inspection)?;
+        spinner.finish_and_clear();
+        return Ok(());
+    }
+
+    for (member, member_content) in members.iter().zip(&contents) {
+        storage.save_member_txt(alias, &member.prefix, member_content)?;
+    }
+
+    let resolved_addition = ResolvedAddition {
+        content,
+        sha256,
+        etag: None,
+        last_modified: None,
+        resolved_url: members[0].url.clone(),
+        variant: SourceVariant::Llms,
+        origin: SourceOrigin {
+            manifest: None,
+            source_type: Some(SourceType::Collection { members }),
+        },
+    };
+
+    let llms_json = finalize_add(FinalizeConfig {
+        storage: &storage,
+        alias,
+        resolved: resolved_addition,
+        descriptor_input,
+        parse_result: &parse_result,
+        spinner: &spinner,
+        metrics,
+        no_language_filter,
+        locales: options.locales,
+    })?;
+
+    spinner.finish_and_clear();
+
+    if !quiet {
+        println!(
+            "{} {} ({} headings, {} lines)",
+            "✓ Added".green(),
+            alias.green(),
+            count_headings(&llms_json.toc),
+            llms_json.line_index.total_lines
+        );
+    }
+
+    Ok(())
+}
+
+/// Fetch every collection member, recording its validators and checksum.
+async fn fetch_collection_members(
+    members: &mut [blz_core::CollectionMember],
+    spinner: &ProgressBar,
+    throttle: Option<Bandwidth>,
+) -> Result<Vec<String>> {
+    let fetcher = crate::utils::throttle::fetcher(throttle)?;
+    let mut contents = Vec::with_capacity(members.len());
+    for member in members {
+        spinner.set_message(format!("Fetching {}...", member.prefix));
+        match fetcher.fetch_with_cache(&member.url, None, None).await? {
+            blz_core::FetchResult::Modified {
+                content,
+                sha256,
+                etag,
+                last_modified,
+            } => {
+                member.etag = etag;
+                member.last_modified = last_modified;
+                member.sha256 = sha256;
+                contents.push(content);
+            },
+            blz_core::FetchResult::NotModified { .. } => {
+                anyhow::bail!(
+                    "Server returned 304 Not Modified on initial fetch of member '{}'.",
+                    member.prefix
+                );
+            },
+        }
+    }
+    Ok(contents)
+}
+
+/// Output dry-run analysis as JSON for collection sources.
+fn output_collection_dry_run_analysis(
+    alias: &str,
+    members: &[blz_core::CollectionMember],
+    content: &str,
+    parse_result: &blz_core::ParseResult,
+    inspection: Option<Inspection>,
+) -> Result<()> {
+    let urls = members
+        .iter()
+        .map(|member| member.url.as_str())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let analysis = SourceAnalysis {
+        name: alias.to_string(),
+        url: urls.clone(),
+        final_url: urls,
+        analysis: content_analysis(content, parse_result, "collection"),
+        would_index: true,
+        inspection,
+    };
+    let json = serde_json::to_string_pretty(&analysis)?;
+    println!("{json}");
+    Ok(())
+}
+
+/// Add a Rust crate source rendered from docs.rs rustdoc JSON.
+async fn add_crate(
+    alias: &str,
+    spec: &CrateSpec,
+    descriptor_input: DescriptorInput,
+    metrics: PerformanceMetrics,
+    options: AddFlowOptions,
+) -> Result<()> {
+    let AddFlowOptions {
+        dry_run,
+        quiet,
+        no_language_filter,
+        ..
+    } = options;
+
+    let normalized_alias = normalize_alias(alias);
+    if normalized_alias != alias && !quiet && !dry_run {
+        println!(
+            "Normalizing alias: '{}' → '{}'",
+            alias,
+            normalized_alias.green()
+        );
+    }
+    validate_alias(&normalized_alias)?;
+    let alias = normalized_alias.as_str();
+
+    let storage = Storage::new()?;
+    if storage.exists(alias) {
+        anyhow::bail!(
+            "Source '{alias}' already exists. Use 'blz sync {alias}' or choose a different alias."
+        );
+    }
+
+    let spinner = if quiet {
+        ProgressBar::hidden()
+    } else {
+        create_spinner(&format!("Fetching rustdoc JSON for {}...", spec.name))
+    };
+
+    let fetcher = crate::utils::throttle::fetcher(options.throttle)?;
+    let (content, url) = rustdoc::fetch_crate_docs(&fetcher, spec)
+        .await
+        .map_err(|err| {
+            anyhow::anyhow!(
+                "Failed to load docs for crate '{}@{}': {err}",
+                spec.name,
+                spec.version
+            )
+        })?;
+    let mut hasher = Sha256::new();
+    hasher.update(content.as_bytes());
+    let sha256 = STANDARD.encode(hasher.finalize());
+
+    spinner.set_message("Parsing markdown...");
+    let (parse_result, inspection) = parse_content(alias, &content, options)?;
+
+    if dry_run {
+        spinner.finish_and_clear();
+        let analysis = SourceAnalysis {
+            name: alias.to_string(),
+            url: format!("{}{}@{}", rustdoc::CRATE_PREFIX, spec.name, spec.version),
+            final_url: url,
+            analysis: content_analysis(&content, &parse_result, "crate"),
+            would_index: true,
+            inspection,
+        };
+        println!("{}", serde_json::to_string_pretty(&analysis)?);
+        return Ok(());
+    }
+
+    let resolved_addition = ResolvedAddition {
+        content,
+        sha256,
+        etag: None,
+        last_modified: None,
+        resolved_url: url,
+        variant: SourceVariant::Custom,
+        origin: SourceOrigin {
+            manifest: None,
+            source_type: Some(SourceType::Crate {
+                name: spec.name.clone(),
+                version: spec.version.clone(),
+            }),
+        },
+    };
+
+    let llms_json = finalize_add(FinalizeConfig {
+        storage: &storage,
+        alias,
+        resolved: resolved_addition,
+        descriptor_input,
+        parse_result: &parse_result,
+        spinner: &spinner,
+        metrics,
+        no_language_filter,
+        locales: options.locales,
+    })?;
+
+    spinner.finish_and_clear();
+
+    if !quiet {
+        println!(
+            "{} {} from {} ({} headings, {} lines)",
+            "✓ Added".green(),
+            alias.green(),
+            format!("crate {}@{}", spec.name, spec.version).cyan(),
+            count_headings(&llms_json.toc),
+            llms_json.line_index.total_lines
+        );
+    }
+
+    Ok(())
+}
+
+/// Add an `OpenAPI` or Swagger spec source rendered to markdown.
+///
+/// Local paths are stored as absolute paths so sync can re-read them.
+async fn add_openapi(
+    alias: &str,
+    location: &str,
+    descriptor_input: DescriptorInput,
+    metrics: PerformanceMetrics,
+    options: AddFlowOptions,
+) -> Result<()> {
+    let AddFlowOptions {
+        dry_run,
+        quiet,
+        no_language_filter,
+        ..
+    } = options;
+
+    let normalized_alias = normalize_alias(alias);
+    if normalized_alias != alias && !quiet && !dry_run {
+        println!(
+            "Normalizing alias: '{}' → '{}'",
+            alias,
+            normalized_alias.green()
+        );
+    }
+    validate_alias(&normalized_alias)?;
+    let alias = normalized_alias.as_str();
+
+    let storage = Storage::new()?;
+    if storage.exists(alias) {
+        anyhow::bail!(
+            "Source '{alias}' already exists. Use 'blz sync {alias}' or choose a different alias."
+        );
+    }
+
+    let location = if openapi::is_remote(location) {
+        location.to_string()
+    } else {
+        let path = Path::new(location);
+        sync_fs::canonicalize(path)
+            .map_err(|err| anyhow::anyhow!("Failed to access OpenAPI spec '{location}': {err}"))?
+            .to_string_lossy()
+            .to_string()
+    };
+
+    let spinner = if quiet {
+        ProgressBar::hidden()
+    } else {
+        create_spinner("Loading OpenAPI spec...")
+    };
+
+    let fetcher = crate::utils::throttle::fetcher(options.throttle)?;
+    let content = openapi::fetch_openapi_docs(&fetcher, &location)
+        .await
+        .map_err(|err| anyhow::anyhow!("Failed to load OpenAPI spec '{location}': {err}"))?;
+    let mut hasher = Sha256::new();
+    hasher.update(content.as_bytes());
+    let sha256 = STANDARD.encode(hasher.finalize());
+
+    spinner.set_message("Parsing markdown...");
+    let (parse_result, inspection) = parse_content(alias, &content, options)?;
+
+    if dry_run {
+        spinner.finish_and_clear();
+        let analysis = SourceAnalysis {
+            name: alias.to_string(),
+            url: location.clone(),
+            final_url: location,
+            analysis: content_analysis(&content, &parse_result, "openapi"),
+            would_index: true,
+            inspection,
+        };
+        println!("{}", serde_json::to_string_pretty(&analysis)?);
+        return Ok(());
+    }
+
+    let resolved_addition = ResolvedAddition {
+        content,
+        sha256,
+        etag: None,
+        last_modified: None,
+        resolved_url: location.clone(),
+        variant: SourceVariant::Custom,
+        origin: SourceOrigin {
+            manifest: None,
+            source_type: Some(SourceType::OpenApi {
+                location: location.clone(),
+            }),
+        },
+    };
+
+    let llms_json = finalize_add(FinalizeConfig {
+        storage: &storage,
+        alias,
+        resolved: resolved_addition,
+        descriptor_input,
+        parse_result: &parse_result,
+        spinner: &spinner,
+        metrics,
+        no_language_filter,
+        locales: options.locales,
+    })?;
+
+    spinner.finish_and_clear();
 
-    if processed == 0 && !quiet {
-        eprintln!("No manifest sources matched the provided filters.");
+    if !quiet {
+        println!(
+            "{} {} from {} ({} headings, {} lines)",
+            "✓ Added".green(),
+            alias.green(),
+            location.cyan(),
+            count_headings(&llms_json.toc),
+            llms_json.line_index.total_lines
+        );
     }
 
     Ok(())
 }
 
-async fn fetch_and_index(
+/// Add a local folder of markdown files merged into one source.
+fn add_docs_dir(
     alias: &str,
-    url: &str,
+    path: &Path,
     descriptor_input: DescriptorInput,
-    fetcher: Fetcher,
     metrics: PerformanceMetrics,
     options: AddFlowOptions,
 ) -> Result<()> {
@@ -806,65 +2539,79 @@ async fn fetch_and_index(
         dry_run,
         quiet,
         no_language_filter,
+        ..
     } = options;
-    // Check if source already exists (validate even in dry-run mode)
+
+    let normalized_alias = normalize_alias(alias);
+    if normalized_alias != alias && !quiet && !dry_run {
+        println!(
+            "Normalizing alias: '{}' → '{}'",
+            alias,
+            normalized_alias.green()
+        );
+    }
+    validate_alias(&normalized_alias)?;
+    let alias = normalized_alias.as_str();
+
     let storage = Storage::new()?;
     if storage.exists(alias) {
         anyhow::bail!(
-            "Source '{alias}' already exists. Use 'blz refresh {alias}' or choose a different alias."
+            "Source '{alias}' already exists. Use 'blz sync {alias}' or choose a different alias."
         );
     }
 
+    let root = sync_fs::canonicalize(path).map_err(|err| {
+        anyhow::anyhow!(
+            "Failed to access documentation folder '{}': {err}",
+            path.display()
+        )
+    })?;
+    let root_str = root.to_string_lossy().to_string();
+
     let spinner = if quiet {
         ProgressBar::hidden()
     } else {
-        create_spinner("Resolving URL...")
+        create_spinner("Reading markdown files...")
     };
 
-    // Resolve the best URL variant (llms-full.txt vs llms.txt)
-    spinner.set_message("Resolving URL variant...");
-    let resolved = url_resolver::resolve_best_url(&fetcher, url).await?;
-
-    // Show warning if index file
-    if resolved.should_warn && !quiet && !dry_run {
-        warn_index_only_file(&spinner, resolved.line_count);
-    }
-
-    // Fetch from resolved URL
-    spinner.set_message("Fetching documentation...");
-    let fetch_result = fetcher
-        .fetch_with_cache(&resolved.final_url, None, None)
-        .await?;
-
-    let (content, sha256, etag, last_modified) = match fetch_result {
-        blz_core::FetchResult::Modified {
-            content,
-            sha256,
-            etag,
-            last_modified,
-        } => (content, sha256, etag, last_modified),
-        blz_core::FetchResult::NotModified { .. } => {
-            anyhow::bail!(
-                "Server returned 304 Not Modified on initial fetch. This should not happen for new sources."
-            );
-        },
-    };
+    let (content, files) = blz_core::docs_dir::load_docs_dir(&root)?;
+    let mut hasher = Sha256::new();
+    hasher.update(content.as_bytes());
+    let sha256 = STANDARD.encode(hasher.finalize());
 
-    // Parse the content
     spinner.set_message("Parsing markdown...");
-    let mut parser = MarkdownParser::new()?;
-    let mut parse_result = parser.parse(&content)?;
-
-    // Apply language filtering if enabled
-    apply_language_filter(&mut parse_result, no_language_filter, quiet);
+    let (parse_result, inspection) = parse_content(alias, &content, options)?;
 
-    // In dry-run mode, analyze content and output JSON instead of indexing
     if dry_run {
-        output_dry_run_analysis(alias, url, &resolved, &content, &parse_result)?;
         spinner.finish_and_clear();
+        let analysis = SourceAnalysis {
+            name: alias.to_string(),
+            url: path.display().to_string(),
+            final_url: root_str,
+            analysis: content_analysis(&content, &parse_result, "dir"),
+            would_index: true,
+            inspection,
+        };
+        println!("{}", serde_json::to_string_pretty(&analysis)?);
         return Ok(());
     }
-    let resolved_addition = build_remote_addition(content, sha256, etag, last_modified, &resolved);
+
+    let file_count = files.len();
+    let resolved_addition = ResolvedAddition {
+        content,
+        sha256,
+        etag: None,
+        last_modified: None,
+        resolved_url: root_str.clone(),
+        variant: SourceVariant::Custom,
+        origin: SourceOrigin {
+            manifest: None,
+            source_type: Some(SourceType::Directory {
+                path: root_str.clone(),
+                files,
+            }),
+        },
+    };
 
     let llms_json = finalize_add(FinalizeConfig {
         storage: &storage,
@@ -875,15 +2622,18 @@ async fn fetch_and_index(
         spinner: &spinner,
         metrics,
         no_language_filter,
+        locales: options.locales,
     })?;
 
     spinner.finish_and_clear();
 
     if !quiet {
         println!(
-            "{} {} ({} headings, {} lines)",
+            "{} {} from {} ({} files, {} headings, {} lines)",
             "✓ Added".green(),
             alias.green(),
+            root_str.cyan(),
+            file_count,
             count_headings(&llms_json.toc),
             llms_json.line_index.total_lines
         );
@@ -892,140 +2642,102 @@ async fn fetch_and_index(
     Ok(())
 }
 
-/// Output dry-run analysis as JSON for remote sources.
-fn output_dry_run_analysis(
+/// Add a GitHub repository's README and `docs/` files merged into one source.
+#[allow(clippy::too_many_lines)]
+async fn add_github_repo(
     alias: &str,
-    url: &str,
-    resolved: &url_resolver::ResolvedUrl,
-    content: &str,
-    parse_result: &blz_core::ParseResult,
+    spec: &RepoSpec,
+    mut descriptor_input: DescriptorInput,
+    metrics: PerformanceMetrics,
+    options: AddFlowOptions,
 ) -> Result<()> {
-    let char_count = content.len();
-    let header_count = parse_result.heading_blocks.len();
-    let sections = parse_result.toc.len();
-    let file_size = format_size(content.len());
-
-    let content_type = match resolved.content_type {
-        blz_core::ContentType::Full => "full",
-        blz_core::ContentType::Index => "index",
-        blz_core::ContentType::Mixed => "mixed",
-    };
-
-    let analysis = SourceAnalysis {
-        name: alias.to_string(),
-        url: url.to_string(),
-        final_url: resolved.final_url.clone(),
-        analysis: ContentAnalysis {
-            line_count: resolved.line_count,
-            char_count,
-            header_count,
-            sections,
-            file_size,
-            content_type: content_type.to_string(),
-        },
-        would_index: true,
-    };
-
-    let json = serde_json::to_string_pretty(&analysis)?;
-    println!("{json}");
-    Ok(())
-}
+    let AddFlowOptions {
+        dry_run,
+        quiet,
+        no_language_filter,
+        ..
+    } = options;
 
-/// Output dry-run analysis as JSON for local file sources.
-fn output_local_dry_run_analysis(
-    alias: &str,
-    path: &Path,
-    content: &str,
-    parse_result: &blz_core::ParseResult,
-) -> Result<()> {
-    let analysis = SourceAnalysis {
-        name: alias.to_string(),
-        url: path.display().to_string(),
-        final_url: path.display().to_string(),
-        analysis: ContentAnalysis {
-            line_count: parse_result.line_count,
-            char_count: content.len(),
-            header_count: parse_result.heading_blocks.len(),
-            sections: parse_result.toc.len(),
-            file_size: format_size(content.len()),
-            content_type: "local".to_string(),
-        },
-        would_index: true,
-    };
-    let json = serde_json::to_string_pretty(&analysis)?;
-    println!("{json}");
-    Ok(())
-}
+    let normalized_alias = normalize_alias(alias);
+    if normalized_alias != alias && !quiet && !dry_run {
+        println!(
+            "Normalizing alias: '{}' → '{}'",
+            alias,
+            normalized_alias.green()
+        );
+    }
+    validate_alias(&normalized_alias)?;
+    let alias = normalized_alias.as_str();
 
-async fn add_local_source(
-    alias: &str,
-    path: &Path,
-    descriptor_input: DescriptorInput,
-    dry_run: bool,
-    quiet: bool,
-    metrics: PerformanceMetrics,
-    no_language_filter: bool,
-) -> Result<()> {
     let storage = Storage::new()?;
     if storage.exists(alias) {
         anyhow::bail!(
-            "Source '{alias}' already exists. Use 'blz refresh {alias}' or choose a different alias."
+            "Source '{alias}' already exists. Use 'blz sync {alias}' or choose a different alias."
         );
     }
 
     let spinner = if quiet {
         ProgressBar::hidden()
     } else {
-        create_spinner("Reading local file...")
+        create_spinner(&format!("Reading {}...", spec.full_name()))
     };
 
-    let metadata = async_fs::metadata(path).await.map_err(|e| {
-        anyhow::anyhow!(
-            "Failed to access local source at '{}': {}",
-            path.display(),
-            e
-        )
-    })?;
-    if !metadata.is_file() {
-        anyhow::bail!("Local source '{}' is not a file", path.display());
-    }
-
-    spinner.set_message("Reading local file...");
-    let content = async_fs::read_to_string(path)
+    let client = GithubClient::from_env()?;
+    let repo = client
+        .repo(&storage.github_cache(), spec)
         .await
-        .map_err(|e| anyhow::anyhow!("Failed to read local source '{}': {}", path.display(), e))?;
-
+        .with_context(|| format!("Failed to look up GitHub repository '{}'", spec.full_name()))?;
+    let reference = spec
+        .reference
+        .clone()
+        .unwrap_or_else(|| repo.default_branch.clone());
+    let docs = blz_core::repo_docs::load_repo_docs(&client, spec, &reference).await?;
+    let content = docs.content;
     let mut hasher = Sha256::new();
     hasher.update(content.as_bytes());
-    // Use base64 encoding to match remote sources
     let sha256 = STANDARD.encode(hasher.finalize());
 
     spinner.set_message("Parsing markdown...");
-    let mut parser = MarkdownParser::new()?;
-    let mut parse_result = parser.parse(&content)?;
-
-    // Apply language filtering for consistency with remote sources
-    apply_language_filter(&mut parse_result, no_language_filter, quiet);
+    let (parse_result, inspection) = parse_content(alias, &content, options)?;
 
+    let location = format!("{}/tree/{reference}", repo.html_url);
     if dry_run {
-        output_local_dry_run_analysis(alias, path, &content, &parse_result)?;
         spinner.finish_and_clear();
+        let analysis = SourceAnalysis {
+            name: alias.to_string(),
+            url: spec.to_string(),
+            final_url: location,
+            analysis: content_analysis(&content, &parse_result, "github"),
+            would_index: true,
+            inspection,
+        };
+        println!("{}", serde_json::to_string_pretty(&analysis)?);
         return Ok(());
     }
 
-    let abs_path = sync_fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
-    let path_str = abs_path.to_string_lossy().to_string();
+    if descriptor_input.description.is_none() {
+        descriptor_input.description = repo.description.filter(|d| !d.trim().is_empty());
+    }
+    if !descriptor_input.github_aliases.contains(&repo.full_name) {
+        descriptor_input.github_aliases.push(repo.full_name.clone());
+    }
 
+    let file_count = docs.files.len();
     let resolved_addition = ResolvedAddition {
         content,
         sha256,
         etag: None,
         last_modified: None,
-        resolved_url: path_str.clone(),
-        variant: SourceVariant::Llms,
+        resolved_url: location.clone(),
+        variant: SourceVariant::Custom,
         origin: SourceOrigin {
             manifest: None,
-            source_type: Some(SourceType::LocalFile { path: path_str }),
+            source_type: Some(SourceType::GithubRepo {
+                repo: repo.full_name,
+                reference,
+                commit: docs.commit.clone(),
+                files: docs.files,
+            }),
         },
     };
 
@@ -1038,15 +2750,20 @@ async fn add_local_source(
         spinner: &spinner,
         metrics,
         no_language_filter,
+        locales: options.locales,
     })?;
 
     spinner.finish_and_clear();
 
     if !quiet {
+        let short_commit: String = docs.commit.chars().take(7).collect();
         println!(
-            "{} {} ({} headings, {} lines)",
+            "{} {} from {} at {} ({} files, {} headings, {} lines)",
             "✓ Added".green(),
             alias.green(),
+            location.cyan(),
+            short_commit,
+            file_count,
             count_headings(&llms_json.toc),
             llms_json.line_index.total_lines
         );
@@ -1055,6 +2772,21 @@ async fn add_local_source(
     Ok(())
 }
 
+fn content_analysis(
+    content: &str,
+    parse_result: &blz_core::ParseResult,
+    content_type: &str,
+) -> ContentAnalysis {
+    ContentAnalysis {
+        line_count: parse_result.line_count,
+        char_count: content.len(),
+        header_count: parse_result.heading_blocks.len(),
+        sections: parse_result.toc.len(),
+        file_size: format_size(content.len()),
+        content_type: content_type.to_string(),
+    }
+}
+
 fn create_spinner(message: &str) -> ProgressBar {
     let pb = ProgressBar::new_spinner();
     pb.set_style(
@@ -1118,6 +2850,7 @@ fn build_remote_addition(
     }
 }
 
+#[allow(clippy::too_many_lines)]
 fn finalize_add(config: FinalizeConfig<'_>) -> Result<blz_core::LlmsJson> {
     let FinalizeConfig {
         storage,
@@ -1128,6 +2861,7 @@ fn finalize_add(config: FinalizeConfig<'_>) -> Result<blz_core::LlmsJson> {
         spinner,
         metrics,
         no_language_filter,
+        locales,
     } = config;
 
     spinner.set_message("Saving content...");
@@ -1200,12 +2934,14 @@ fn finalize_add(config: FinalizeConfig<'_>) -> Result<blz_core::LlmsJson> {
         filter_non_english: Some(!no_language_filter),
     };
     storage.save_source_metadata(alias, &metadata)?;
+    if !no_language_filter && !locales.is_english_only() {
+        storage.save_locales(alias, Some(locales))?;
+    }
 
-    let (descriptor_url, descriptor_path) = match &origin.source_type {
-        Some(SourceType::Remote { url }) => (Some(url.clone()), None),
-        Some(SourceType::LocalFile { path }) => (None, Some(path.clone())),
-        None => (Some(resolved.resolved_url), None),
-    };
+    let (descriptor_url, descriptor_path) = origin.source_type.as_ref().map_or_else(
+        || (Some(resolved.resolved_url), None),
+        SourceType::descriptor_location,
+    );
 
     let descriptor = SourceDescriptor {
         alias: alias.to_string(),
@@ -1222,46 +2958,85 @@ fn finalize_add(config: FinalizeConfig<'_>) -> Result<blz_core::LlmsJson> {
     };
     storage.save_descriptor(&descriptor)?;
 
+    if !descriptor_input.mirrors.is_empty() {
+        storage.save_mirrors(alias, &MirrorList::new(descriptor_input.mirrors))?;
+    }
+
     spinner.set_message("Indexing content...");
     let index_path = storage.index_dir(alias)?;
-    let index = SearchIndex::create(&index_path)?.with_metrics(metrics);
-    index.index_blocks(alias, &parse_result.heading_blocks)?;
+    let blocks = &parse_result.heading_blocks;
+    let analysis = storage.load_text_analysis(alias)?;
+    SearchIndex::rebuild_with(&index_path, alias, blocks, metrics, &analysis)?;
 
     Ok(llms_json)
 }
 
+/// Parse `content` and apply the language filter.
+///
+/// With `--inspect`, also returns the structure report, which needs the
+/// result from before filtering to show what the filter removed.
+fn parse_content(
+    alias: &str,
+    content: &str,
+    options: AddFlowOptions,
+) -> Result<(ParseResult, Option<Inspection>)> {
+    let mut parser = MarkdownParser::new()?;
+    let mut parse_result = parser.parse(content)?;
+    let unfiltered = options.inspect.then(|| parse_result.clone());
+
+    // Dry runs print JSON only; inspection reports filter effects itself
+    apply_language_filter(
+        &mut parse_result,
+        options.no_language_filter,
+        options.locales,
+        options.quiet || options.dry_run,
+    );
+
+    let inspection = unfiltered
+        .map(|unfiltered| {
+            inspect::inspect(
+                alias,
+                &unfiltered,
+                &parse_result,
+                !options.no_language_filter,
+            )
+        })
+        .transpose()?;
+    Ok((parse_result, inspection))
+}
+
 /// Apply language filtering to parse results
 ///
-/// Filters out non-English heading blocks using hybrid URL-based and text-based detection.
+/// Drops heading blocks outside `locales`, judged by the locale markers of
+/// the URLs they link to and by their heading text.
 /// Prints filtering statistics if blocks were filtered and not in quiet mode.
-fn apply_language_filter(parse_result: &mut ParseResult, no_language_filter: bool, quiet: bool) {
+fn apply_language_filter(
+    parse_result: &mut ParseResult,
+    no_language_filter: bool,
+    locales: LocaleSet,
+    quiet: bool,
+) {
     if no_language_filter {
         return;
     }
 
-    let mut language_filter = LanguageFilter::new(true);
+    let language_filter = LanguageFilter::new(true).with_locales(locales);
 
-    // Filter heading blocks using both URL-based and text-based methods
     let original_count = parse_result.heading_blocks.len();
     parse_result.heading_blocks.retain(|block| {
-        // First check URLs in content (fast, catches locale-based URLs)
         let urls_in_content = extract_urls_from_content(&block.content);
-        let url_check = urls_in_content.is_empty()
-            || urls_in_content
-                .iter()
-                .all(|url| language_filter.is_english_url(url));
-
-        // Then check heading text (catches non-URL-based translations)
-        let heading_check = language_filter.is_english_heading_path(&block.path);
-
-        // Block must pass both checks to be kept
-        url_check && heading_check
+        language_filter.keeps_section(&block.path, &urls_in_content)
     });
 
     let filtered_count = original_count - parse_result.heading_blocks.len();
     if filtered_count > 0 && !quiet {
+        let kind = if locales.is_english_only() {
+            "non-English content blocks".to_string()
+        } else {
+            format!("content blocks outside {locales}")
+        };
         println!(
-            "Filtered {} non-English content blocks ({:.1}% reduction)",
+            "Filtered {} {kind} ({:.1}% reduction)",
             filtered_count,
             percentage(filtered_count, original_count)
         );
@@ -1428,10 +3203,95 @@ pub fn is_domain_only(input: &str) -> bool {
     true
 }
 
+/// Whether `url` names a site or docs page rather than a specific file.
+///
+/// Such inputs go through discovery (Link headers, path-relative, host root,
+/// docs subdomain, parent domain) before fetching.
+fn should_discover(url: &str) -> bool {
+    if is_domain_only(url) {
+        return true;
+    }
+    let Ok(parsed) = Url::parse(url) else {
+        return false;
+    };
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return false;
+    }
+    let last_segment = parsed
+        .path_segments()
+        .and_then(Iterator::last)
+        .unwrap_or_default();
+    !last_segment.contains('.')
+}
+
+/// Probe paths from `defaults.probe_paths`, or the built-in list.
+fn configured_probe_targets() -> ProbeTargets {
+    let probe_paths = blz_core::Config::load()
+        .map(|config| config.defaults.probe_paths)
+        .unwrap_or_default();
+    ProbeTargets::from_config(&probe_paths)
+}
+
+const fn describe_discovery(method: DiscoveryMethod) -> &'static str {
+    match method {
+        DiscoveryMethod::LinkHeader => "Link header",
+        DiscoveryMethod::PathRelative => "path-relative probe",
+        DiscoveryMethod::HostRoot => "host root",
+        DiscoveryMethod::DocsSubdomain => "docs subdomain",
+        DiscoveryMethod::ParentDomain => "parent domain",
+        DiscoveryMethod::NotFound => "probe",
+    }
+}
+
+/// Resolve a site or docs-page URL to the llms.txt file to fetch.
+///
+/// Results outside the original scope are confirmed according to `scope`.
+/// Returns `Ok(None)` if the user declines. URLs with nothing discoverable
+/// are returned unchanged; bare domains with nothing discoverable are an error.
+async fn discover_source_url(
+    url: &str,
+    scope: ScopeConfirmation,
+    quiet: bool,
+    ignore_robots: bool,
+) -> Result<Option<String>> {
+    let targets = configured_probe_targets().ignoring_robots(ignore_robots);
+    let probe = probe_url_with(url, &targets).await?;
+    let discovered = match determine_add_action(&probe, false) {
+        Ok(AddAction::UseNative { url } | AddAction::IndexOnly { url }) => url,
+        Err(err) if is_domain_only(url) => return Err(err),
+        Ok(AddAction::Generate { .. } | AddAction::Cancel) | Err(_) => {
+            return Ok(Some(url.to_string()));
+        },
+    };
+    let method = describe_discovery(probe.discovery_method);
+
+    match scope.decide(&probe) {
+        ScopeDecision::Accept => {},
+        ScopeDecision::Prompt => {
+            let prompt = format!(
+                "No llms.txt found under {url}. Use {discovered} from the {method} instead?"
+            );
+            if !Confirm::new(&prompt).with_default(false).prompt()? {
+                println!("Cancelled.");
+                return Ok(None);
+            }
+        },
+        ScopeDecision::Reject => anyhow::bail!(
+            "No llms.txt found under {url}, but {discovered} was found on the {method}. \
+             Re-run with --accept-scope to use it, or pass that URL directly."
+        ),
+    }
+
+    if !quiet {
+        println!("Discovered {} via {method}", discovered.green());
+    }
+    Ok(Some(discovered))
+}
+
 /// Probe a domain for documentation sources.
 ///
-/// Uses `blz_core::discovery::probe_domain` to check for llms.txt, llms-full.txt,
-/// and sitemap.xml at the given domain.
+/// Uses `blz_core::discovery::probe_domain_with` to check for llms.txt, llms-full.txt,
+/// and sitemap.xml at the given domain, honoring `defaults.probe_paths` from config.
 ///
 /// # Arguments
 ///
@@ -1455,7 +3315,9 @@ pub fn is_domain_only(input: &str) -> bool {
 /// ```
 #[allow(dead_code)]
 pub async fn discover_for_domain(domain: &str) -> Result<ProbeResult> {
-    probe_domain(domain).await.map_err(Into::into)
+    probe_domain_with(domain, &configured_probe_targets())
+        .await
+        .map_err(Into::into)
 }
 
 fn display_name_from_alias(alias: &str) -> String {
@@ -1696,6 +3558,44 @@ mod tests {
         assert!(err.to_string().contains("No documentation sources found"));
     }
 
+    #[test]
+    fn test_scope_confirmation_decisions() {
+        let mut probe = ProbeResult {
+            domain: "claude.com".to_string(),
+            llms_full_url: Some("https://claude.com/llms-full.txt".to_string()),
+            llms_url: None,
+            sitemap_url: None,
+            docs_subdomain_checked: true,
+            discovery_method: DiscoveryMethod::ParentDomain,
+            original_url: Some("https://code.claude.com/docs".to_string()),
+            requires_confirmation: true,
+        };
+        let scope = |accept, interactive| ScopeConfirmation {
+            accept,
+            interactive,
+        };
+
+        assert_eq!(scope(false, true).decide(&probe), ScopeDecision::Prompt);
+        assert_eq!(scope(false, false).decide(&probe), ScopeDecision::Reject);
+        assert_eq!(scope(true, false).decide(&probe), ScopeDecision::Accept);
+        assert_eq!(scope(true, true).decide(&probe), ScopeDecision::Accept);
+
+        // In-scope results never need confirmation
+        probe.requires_confirmation = false;
+        assert_eq!(scope(false, false).decide(&probe), ScopeDecision::Accept);
+    }
+
+    #[test]
+    fn test_should_discover() {
+        assert!(should_discover("hono.dev"));
+        assert!(should_discover("https://code.claude.com/docs"));
+        assert!(should_discover("https://example.com/"));
+        assert!(!should_discover("https://example.com/llms.txt"));
+        assert!(!should_discover("https://example.com/docs/llms-full.txt"));
+        assert!(!should_discover("file:///tmp/docs"));
+        assert!(!should_discover("react"));
+    }
+
     #[test]
     fn test_add_action_display() {
         let native = AddAction::UseNative {
@@ -0,0 +1,145 @@
+//! Parse-structure preview for `blz add --inspect`.
+//!
+//! Summarizes what adding a source would produce (outline, heading levels,
+//! language mix, filter effects, index size) so it can be vetted before
+//! anything is written. The index is built in memory and discarded.
+
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+use blz_core::{HeadingBlock, LanguageFilter, ParseResult, SearchIndex, TocEntry};
+use serde::{Deserialize, Serialize};
+
+use super::{extract_urls_from_content, format_size};
+
+/// Deepest heading level included in the outline preview.
+const MAX_OUTLINE_DEPTH: usize = 3;
+
+/// Structure report attached to the dry-run analysis.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct Inspection {
+    /// Heading count keyed by level (1 = `#`).
+    headings_by_level: BTreeMap<usize, usize>,
+    /// Content blocks per detected language, before filtering.
+    languages: BTreeMap<String, usize>,
+    filter: FilterEffects,
+    estimated_index_bytes: u64,
+    estimated_index_size: String,
+    /// Table of contents down to [`MAX_OUTLINE_DEPTH`].
+    toc: Vec<OutlineEntry>,
+}
+
+/// What the language filter removed.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FilterEffects {
+    enabled: bool,
+    blocks_before: usize,
+    blocks_after: usize,
+    blocks_removed: usize,
+    lines_removed: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct OutlineEntry {
+    title: String,
+    lines: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    children: Vec<Self>,
+}
+
+/// Build the report from the parse result before and after language filtering.
+///
+/// # Errors
+///
+/// Returns an error if the in-memory index cannot be built.
+pub(super) fn inspect(
+    alias: &str,
+    unfiltered: &ParseResult,
+    filtered: &ParseResult,
+    filter_enabled: bool,
+) -> Result<Inspection> {
+    let mut headings_by_level = BTreeMap::new();
+    count_levels(&filtered.toc, &mut headings_by_level);
+
+    let language_filter = LanguageFilter::new(true);
+    let mut languages = BTreeMap::new();
+    for block in &unfiltered.heading_blocks {
+        *languages
+            .entry(block_language(&language_filter, block))
+            .or_insert(0) += 1;
+    }
+
+    let block_lines = |blocks: &[HeadingBlock]| -> usize {
+        blocks
+            .iter()
+            .map(|block| block.end_line.saturating_sub(block.start_line) + 1)
+            .sum()
+    };
+    let filter = FilterEffects {
+        enabled: filter_enabled,
+        blocks_before: unfiltered.heading_blocks.len(),
+        blocks_after: filtered.heading_blocks.len(),
+        blocks_removed: unfiltered.heading_blocks.len() - filtered.heading_blocks.len(),
+        lines_removed: block_lines(&unfiltered.heading_blocks)
+            .saturating_sub(block_lines(&filtered.heading_blocks)),
+    };
+
+    let index = SearchIndex::create_in_ram()?;
+    index.index_blocks(alias, &filtered.heading_blocks)?;
+    let estimated_index_bytes = index.space_usage()?;
+
+    Ok(Inspection {
+        headings_by_level,
+        languages,
+        filter,
+        estimated_index_bytes,
+        estimated_index_size: format_size(
+            usize::try_from(estimated_index_bytes).unwrap_or(usize::MAX),
+        ),
+        toc: outline(&filtered.toc, 1),
+    })
+}
+
+fn count_levels(entries: &[TocEntry], counts: &mut BTreeMap<usize, usize>) {
+    for entry in entries {
+        *counts.entry(entry.heading_path.len()).or_insert(0) += 1;
+        count_levels(&entry.children, counts);
+    }
+}
+
+fn outline(entries: &[TocEntry], depth: usize) -> Vec<OutlineEntry> {
+    if depth > MAX_OUTLINE_DEPTH {
+        return Vec::new();
+    }
+    entries
+        .iter()
+        .map(|entry| OutlineEntry {
+            title: entry
+                .heading_path_display
+                .as_ref()
+                .unwrap_or(&entry.heading_path)
+                .last()
+                .cloned()
+                .unwrap_or_default(),
+            lines: entry.lines.clone(),
+            children: outline(&entry.children, depth + 1),
+        })
+        .collect()
+}
+
+/// Locale from the first localized URL in the block, else a guess from the
+/// heading text (`en` or `non-en`).
+fn block_language(filter: &LanguageFilter, block: &HeadingBlock) -> String {
+    extract_urls_from_content(&block.content)
+        .iter()
+        .find_map(|url| filter.url_locale(url))
+        .unwrap_or_else(|| {
+            if filter.is_english_heading_path(&block.path) {
+                "en".to_string()
+            } else {
+                "non-en".to_string()
+            }
+        })
+}
@@ -0,0 +1,628 @@
+//! Benchmark command implementation
+//!
+//! `blz bench` measures index open and search latency against the locally
+//! cached sources using only the shipped binary. With `--assert`, latency
+//! budgets such as `p95-search<10ms` are checked and the command exits with an
+//! error when any budget is exceeded, so performance can be gated in CI.
+//!
+//! It also measures the per-source metadata a search reads before it touches
+//! an index, comparing the cached `summary.json` against the `llms.json` and
+//! `metadata.json` reads that search used to make for every source.
+//!
+//! `blz bench rank` measures result quality instead of speed; see [`rank`].
+
+mod rank;
+
+use std::fmt;
+use std::fs;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result, bail};
+use blz_core::numeric::percentile_count;
+use blz_core::{SearchIndex, Storage};
+use clap::{Args, Subcommand};
+use colored::Colorize;
+use serde::Serialize;
+
+pub use rank::RankArgs;
+
+use crate::output::OutputFormat;
+use crate::utils::cli_args::FormatArg;
+use crate::utils::resolver::resolve_source;
+
+/// Queries used when none are provided on the command line.
+const DEFAULT_QUERIES: &[&str] = &[
+    "install",
+    "getting started",
+    "configuration",
+    "api reference",
+    "error handling",
+];
+
+/// Results requested per search, matching the default page size.
+const SEARCH_LIMIT: usize = 50;
+
+/// Arguments for `blz bench`.
+#[derive(Args, Clone, Debug)]
+pub struct BenchArgs {
+    /// Benchmark to run instead of search latency
+    #[command(subcommand)]
+    pub command: Option<BenchCommands>,
+    /// Queries to benchmark (defaults to a small built-in set)
+    #[arg(value_name = "QUERY")]
+    pub queries: Vec<String>,
+    /// Restrict to source(s) - comma-separated or repeated (-s a -s b)
+    #[arg(
+        long = "source",
+        short = 's',
+        value_name = "SOURCE",
+        value_delimiter = ','
+    )]
+    pub sources: Vec<String>,
+    /// Measured iterations per query
+    #[arg(
+        long,
+        default_value_t = 20,
+        value_parser = clap::value_parser!(u32).range(1..=10_000)
+    )]
+    pub iterations: u32,
+    /// Unmeasured warm-up iterations per query
+    #[arg(long, default_value_t = 2)]
+    pub warmup: u32,
+    /// Latency budget to enforce, e.g. `p95-search<10ms` (repeatable)
+    ///
+    /// Format: `<stat>-<metric><op><value><unit>` where stat is one of
+    /// min, mean, p50, p90, p95, p99, max; metric is `search`, `open`, or
+    /// `metadata`; op is <, <=, >, >=; and unit is us, ms, or s.
+    #[arg(long = "assert", value_name = "EXPR")]
+    pub assertions: Vec<BenchAssertion>,
+    /// Output format
+    #[command(flatten)]
+    pub format: FormatArg,
+}
+
+/// Subcommands of `blz bench`.
+#[derive(Subcommand, Clone, Debug)]
+pub enum BenchCommands {
+    /// Compare ranking configurations on recorded queries
+    ///
+    /// Examples:
+    ///   blz bench rank --queries rank.toml                       # Current settings
+    ///   blz bench rank --queries rank.toml --ranking headings.toml
+    Rank(RankArgs),
+}
+
+/// Measured operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BenchMetric {
+    /// Opening every selected index.
+    Open,
+    /// Running one query across every selected index.
+    Search,
+    /// Loading the summaries a search reads for every selected source.
+    Metadata,
+}
+
+/// Summary statistic of a latency distribution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BenchStat {
+    Min,
+    Mean,
+    Percentile(u8),
+    Max,
+}
+
+/// Comparison operator of an assertion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+/// A latency budget such as `p95-search<10ms`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BenchAssertion {
+    stat: BenchStat,
+    metric: BenchMetric,
+    comparison: Comparison,
+    threshold: Duration,
+    expression: String,
+}
+
+impl FromStr for BenchAssertion {
+    type Err = String;
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        let expression: String = raw.chars().filter(|c| !c.is_whitespace()).collect();
+        let invalid = |reason: &str| format!("invalid assertion '{raw}': {reason}");
+
+        let op_start = expression
+            .find(['<', '>'])
+            .ok_or_else(|| invalid("expected <, <=, > or >="))?;
+        let (lhs, rest) = expression.split_at(op_start);
+        let (comparison, value) = if let Some(value) = rest.strip_prefix("<=") {
+            (Comparison::Le, value)
+        } else if let Some(value) = rest.strip_prefix(">=") {
+            (Comparison::Ge, value)
+        } else if let Some(value) = rest.strip_prefix('<') {
+            (Comparison::Lt, value)
+        } else if let Some(value) = rest.strip_prefix('>') {
+            (Comparison::Gt, value)
+        } else {
+            return Err(invalid("expected <, <=, > or >="));
+        };
+
+        let (stat, metric) = lhs
+            .split_once('-')
+            .ok_or_else(|| invalid("expected <stat>-<metric>, e.g. p95-search"))?;
+        let stat = match stat.to_ascii_lowercase().as_str() {
+            "min" => BenchStat::Min,
+            "mean" | "avg" => BenchStat::Mean,
+            "max" => BenchStat::Max,
+            other => other
+                .strip_prefix('p')
+                .and_then(|p| p.parse::<u8>().ok())
+                .filter(|p| (1..=100).contains(p))
+                .map(BenchStat::Percentile)
+                .ok_or_else(|| invalid("stat must be min, mean, max, or p1-p100"))?,
+        };
+        let metric = match metric.to_ascii_lowercase().as_str() {
+            "search" => BenchMetric::Search,
+            "open" => BenchMetric::Open,
+            "metadata" => BenchMetric::Metadata,
+            _ => return Err(invalid("metric must be 'search', 'open', or 'metadata'")),
+        };
+
+        let threshold = parse_threshold(value).ok_or_else(|| {
+            invalid("threshold must be a number followed by us, ms, or s (e.g. 10ms)")
+        })?;
+
+        Ok(Self {
+            stat,
+            metric,
+            comparison,
+            threshold,
+            expression,
+        })
+    }
+}
+
+impl fmt::Display for BenchAssertion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.expression)
+    }
+}
+
+impl BenchAssertion {
+    fn holds(&self, actual: Duration) -> bool {
+        match self.comparison {
+            Comparison::Lt => actual < self.threshold,
+            Comparison::Le => actual <= self.threshold,
+            Comparison::Gt => actual > self.threshold,
+            Comparison::Ge => actual >= self.threshold,
+        }
+    }
+}
+
+fn parse_threshold(value: &str) -> Option<Duration> {
+    let split = value.find(|c: char| c.is_ascii_alphabetic())?;
+    let (number, unit) = value.split_at(split);
+    let number: f64 = number
+        .parse()
+        .ok()
+        .filter(|n: &f64| n.is_finite() && *n >= 0.0)?;
+    let seconds = match unit.to_ascii_lowercase().as_str() {
+        "us" | "µs" => number / 1_000_000.0,
+        "ms" => number / 1_000.0,
+        "s" => number,
+        _ => return None,
+    };
+    Duration::try_from_secs_f64(seconds).ok()
+}
+
+/// Latency distribution for one metric.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LatencySummary {
+    samples: usize,
+    min_ms: f64,
+    mean_ms: f64,
+    p50_ms: f64,
+    p95_ms: f64,
+    p99_ms: f64,
+    max_ms: f64,
+    #[serde(skip)]
+    sorted: Vec<Duration>,
+}
+
+impl LatencySummary {
+    fn from_samples(mut samples: Vec<Duration>) -> Self {
+        samples.sort_unstable();
+        let mut summary = Self {
+            samples: samples.len(),
+            min_ms: 0.0,
+            mean_ms: 0.0,
+            p50_ms: 0.0,
+            p95_ms: 0.0,
+            p99_ms: 0.0,
+            max_ms: 0.0,
+            sorted: samples,
+        };
+        summary.min_ms = as_ms(summary.stat(BenchStat::Min));
+        summary.mean_ms = as_ms(summary.stat(BenchStat::Mean));
+        summary.p50_ms = as_ms(summary.stat(BenchStat::Percentile(50)));
+        summary.p95_ms = as_ms(summary.stat(BenchStat::Percentile(95)));
+        summary.p99_ms = as_ms(summary.stat(BenchStat::Percentile(99)));
+        summary.max_ms = as_ms(summary.stat(BenchStat::Max));
+        summary
+    }
+
+    fn stat(&self, stat: BenchStat) -> Duration {
+        let Some(last) = self.sorted.last() else {
+            return Duration::ZERO;
+        };
+        match stat {
+            BenchStat::Min => self.sorted[0],
+            BenchStat::Max => *last,
+            BenchStat::Mean => {
+                let total: Duration = self.sorted.iter().sum();
+                total / u32::try_from(self.sorted.len()).unwrap_or(u32::MAX)
+            },
+            // Nearest-rank percentile.
+            BenchStat::Percentile(p) => {
+                self.sorted[percentile_count(self.sorted.len(), p).saturating_sub(1)]
+            },
+        }
+    }
+}
+
+/// Outcome of a checked assertion.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AssertionResult {
+    expression: String,
+    actual_ms: f64,
+    passed: bool,
+}
+
+/// Per-query metadata reads across every selected source.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MetadataReads {
+    /// Bytes read per query from `summary.json`.
+    bytes: u64,
+    latency: LatencySummary,
+    /// The same figures for the `llms.json` and `metadata.json` reads search
+    /// made before summaries: metadata for filtering, the query cache key, and
+    /// hit provenance, plus `llms.json` for the line count.
+    full_bytes: u64,
+    full_latency: LatencySummary,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BenchReport {
+    sources: Vec<String>,
+    queries: Vec<String>,
+    iterations: u32,
+    open: LatencySummary,
+    search: LatencySummary,
+    metadata: MetadataReads,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    assertions: Vec<AssertionResult>,
+}
+
+/// How often search used to read each source's `metadata.json` per query.
+const FULL_METADATA_READS: u64 = 3;
+
+/// Load what search reads per source from the summary, as search does now.
+fn read_summaries(storage: &Storage, sources: &[String]) -> Result<()> {
+    for alias in sources {
+        storage.load_source_summary(alias)?;
+    }
+    Ok(())
+}
+
+/// Load the same fields from the full files, as search did before summaries.
+fn read_full_metadata(storage: &Storage, sources: &[String]) -> Result<()> {
+    for alias in sources {
+        for _ in 0..FULL_METADATA_READS {
+            storage.load_source_metadata(alias)?;
+        }
+        storage.load_llms_json(alias)?;
+    }
+    Ok(())
+}
+
+/// Bytes read per query by [`read_summaries`] and [`read_full_metadata`].
+fn metadata_bytes(storage: &Storage, sources: &[String]) -> Result<(u64, u64)> {
+    let size = |path: std::path::PathBuf| fs::metadata(path).map_or(0, |m| m.len());
+    let mut summary = 0;
+    let mut full = 0;
+    for alias in sources {
+        summary += size(storage.summary_path(alias)?);
+        full += FULL_METADATA_READS * size(storage.metadata_path(alias)?)
+            + size(storage.llms_json_path(alias)?);
+    }
+    Ok((summary, full))
+}
+
+/// Execute `blz bench`.
+///
+/// # Errors
+///
+/// Returns an error if no sources are available, an index cannot be opened or
+/// searched, or any `--assert` budget is violated.
+#[allow(clippy::too_many_lines)]
+pub fn execute(args: BenchArgs, quiet: bool) -> Result<()> {
+    if let Some(BenchCommands::Rank(rank_args)) = &args.command {
+        return rank::execute(rank_args, quiet);
+    }
+    let format = args.format.resolve(quiet);
+    let storage = Storage::new()?;
+
+    let sources = if args.sources.is_empty() {
+        storage.list_sources()
+    } else {
+        args.sources
+            .iter()
+            .map(|requested| {
+                resolve_source(&storage, requested)?
+                    .ok_or_else(|| anyhow::anyhow!("Source '{requested}' not found"))
+            })
+            .collect::<Result<Vec<_>>>()?
+    };
+    let index_paths = sources
+        .iter()
+        .map(|alias| storage.index_dir(alias).map(|path| (alias.clone(), path)))
+        .collect::<blz_core::Result<Vec<_>>>()?
+        .into_iter()
+        .filter(|(_, path)| path.exists())
+        .collect::<Vec<_>>();
+    if index_paths.is_empty() {
+        bail!("No indexed sources to benchmark. Use 'blz add' to add sources.");
+    }
+
+    let queries: Vec<String> = if args.queries.is_empty() {
+        DEFAULT_QUERIES.iter().map(ToString::to_string).collect()
+    } else {
+        args.queries
+    };
+
+    let open_indexes = || -> Result<Vec<(String, SearchIndex)>> {
+        index_paths
+            .iter()
+            .map(|(alias, path)| {
+                SearchIndex::open(path)
+                    .with_context(|| format!("open index for source={alias}"))
+                    .map(|index| (alias.clone(), index))
+            })
+            .collect()
+    };
+
+    let mut open_samples = Vec::with_capacity(args.iterations as usize);
+    let mut search_samples = Vec::with_capacity(args.iterations as usize * queries.len());
+    let mut summary_samples = Vec::with_capacity(args.iterations as usize);
+    let mut full_samples = Vec::with_capacity(args.iterations as usize);
+    let indexes = open_indexes()?;
+    let aliases: Vec<String> = indexes.iter().map(|(alias, _)| alias.clone()).collect();
+    // Writes summaries for caches that predate them, so every run reads the same files
+    read_summaries(&storage, &aliases)?;
+
+    for iteration in 0..args.warmup.saturating_add(args.iterations) {
+        let measured = iteration >= args.warmup;
+
+        let start = Instant::now();
+        drop(open_indexes()?);
+        if measured {
+            open_samples.push(start.elapsed());
+        }
+
+        let start = Instant::now();
+        read_summaries(&storage, &aliases)?;
+        if measured {
+            summary_samples.push(start.elapsed());
+        }
+
+        let start = Instant::now();
+        read_full_metadata(&storage, &aliases)?;
+        if measured {
+            full_samples.push(start.elapsed());
+        }
+
+        for query in &queries {
+            let start = Instant::now();
+            for (alias, index) in &indexes {
+                index
+                    .search(query, Some(alias), SEARCH_LIMIT)
+                    .with_context(|| format!("search failed for source={alias}"))?;
+            }
+            if measured {
+                search_samples.push(start.elapsed());
+            }
+        }
+    }
+
+    let open = LatencySummary::from_samples(open_samples);
+    let search = LatencySummary::from_samples(search_samples);
+    let (bytes, full_bytes) = metadata_bytes(&storage, &aliases)?;
+    let metadata = MetadataReads {
+        bytes,
+        latency: LatencySummary::from_samples(summary_samples),
+        full_bytes,
+        full_latency: LatencySummary::from_samples(full_samples),
+    };
+    let assertions: Vec<AssertionResult> = args
+        .assertions
+        .iter()
+        .map(|assertion| {
+            let summary = match assertion.metric {
+                BenchMetric::Open => &open,
+                BenchMetric::Search => &search,
+                BenchMetric::Metadata => &metadata.latency,
+            };
+            let actual = summary.stat(assertion.stat);
+            AssertionResult {
+                expression: assertion.to_string(),
+                actual_ms: as_ms(actual),
+                passed: assertion.holds(actual),
+            }
+        })
+        .collect();
+
+    let report = BenchReport {
+        sources: aliases,
+        queries,
+        iterations: args.iterations,
+        open,
+        search,
+        metadata,
+        assertions,
+    };
+
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+        OutputFormat::Jsonl | OutputFormat::Raw => println!("{}", serde_json::to_string(&report)?),
+        OutputFormat::Text | OutputFormat::Markdown => print_text_report(&report),
+    }
+
+    let failed = report.assertions.iter().filter(|a| !a.passed).count();
+    if failed > 0 {
+        bail!(
+            "{failed} of {} performance assertion(s) failed",
+            report.assertions.len()
+        );
+    }
+    Ok(())
+}
+
+fn print_text_report(report: &BenchReport) {
+    println!(
+        "Benchmarked {} source(s), {} query(s), {} iteration(s)",
+        report.sources.len(),
+        report.queries.len(),
+        report.iterations
+    );
+    println!(
+        "\n{:<8} {:>8} {:>10} {:>10} {:>10} {:>10} {:>10}",
+        "metric", "samples", "min", "p50", "p95", "p99", "max"
+    );
+    for (name, summary) in [
+        ("open", &report.open),
+        ("search", &report.search),
+        ("metadata", &report.metadata.latency),
+    ] {
+        println!(
+            "{:<8} {:>8} {:>8.3}ms {:>8.3}ms {:>8.3}ms {:>8.3}ms {:>8.3}ms",
+            name,
+            summary.samples,
+            summary.min_ms,
+            summary.p50_ms,
+            summary.p95_ms,
+            summary.p99_ms,
+            summary.max_ms
+        );
+    }
+
+    let metadata = &report.metadata;
+    println!(
+        "\nPer-query metadata reads: {} bytes in {:.3}ms (p50) from summaries, \
+         was {} bytes in {:.3}ms from llms.json and metadata.json",
+        metadata.bytes, metadata.latency.p50_ms, metadata.full_bytes, metadata.full_latency.p50_ms
+    );
+
+    if !report.assertions.is_empty() {
+        println!();
+        for result in &report.assertions {
+            let status = if result.passed {
+                "✓ pass".green()
+            } else {
+                "✗ FAIL".red()
+            };
+            println!(
+                "{status} {} (actual {:.3}ms)",
+                result.expression, result.actual_ms
+            );
+        }
+    }
+}
+
+fn as_ms(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1_000.0
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_percentile_assertion() {
+        let assertion: BenchAssertion = "p95-search<10ms".parse().unwrap();
+        assert_eq!(assertion.stat, BenchStat::Percentile(95));
+        assert_eq!(assertion.metric, BenchMetric::Search);
+        assert_eq!(assertion.comparison, Comparison::Lt);
+        assert_eq!(assertion.threshold, Duration::from_millis(10));
+    }
+
+    #[test]
+    fn parses_other_stats_units_and_operators() {
+        let assertion: BenchAssertion = "max-open <= 1.5s".parse().unwrap();
+        assert_eq!(assertion.stat, BenchStat::Max);
+        assert_eq!(assertion.metric, BenchMetric::Open);
+        assert_eq!(assertion.comparison, Comparison::Le);
+        assert_eq!(assertion.threshold, Duration::from_millis(1500));
+
+        let assertion: BenchAssertion = "mean-search>=250us".parse().unwrap();
+        assert_eq!(assertion.comparison, Comparison::Ge);
+        assert_eq!(assertion.threshold, Duration::from_micros(250));
+
+        let assertion: BenchAssertion = "p50-metadata<1ms".parse().unwrap();
+        assert_eq!(assertion.metric, BenchMetric::Metadata);
+    }
+
+    #[test]
+    fn rejects_malformed_assertions() {
+        for raw in [
+            "p95-search",
+            "p95search<10ms",
+            "p0-search<10ms",
+            "p95-index<10ms",
+            "p95-search<10",
+            "p95-search<10min",
+        ] {
+            assert!(raw.parse::<BenchAssertion>().is_err(), "{raw} should fail");
+        }
+    }
+
+    #[test]
+    fn assertion_comparisons() {
+        let assertion: BenchAssertion = "p95-search<10ms".parse().unwrap();
+        assert!(assertion.holds(Duration::from_millis(9)));
+        assert!(!assertion.holds(Duration::from_millis(10)));
+    }
+
+    #[test]
+    fn nearest_rank_percentiles() {
+        let samples = (1..=100).map(Duration::from_millis).collect();
+        let summary = LatencySummary::from_samples(samples);
+        assert_eq!(
+            summary.stat(BenchStat::Percentile(95)),
+            Duration::from_millis(95)
+        );
+        assert_eq!(summary.stat(BenchStat::Min), Duration::from_millis(1));
+        assert_eq!(summary.stat(BenchStat::Max), Duration::from_millis(100));
+        assert!((summary.mean_ms - 50.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn empty_samples_summarize_to_zero() {
+        let summary = LatencySummary::from_samples(Vec::new());
+        assert_eq!(summary.stat(BenchStat::Percentile(95)), Duration::ZERO);
+        assert_eq!(summary.samples, 0);
+    }
+}
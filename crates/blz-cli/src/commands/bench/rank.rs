@@ -0,0 +1,327 @@
+//! Ranking comparison for `blz bench rank`.
+//!
+//! Replays recorded queries, each naming the section that should answer it,
+//! under every ranking configuration given and reports where that section
+//! landed: mean reciprocal rank, and how often it came first or within the
+//! top `--limit`. The sources' saved settings are always measured as the
+//! `current` baseline.
+//!
+//! Recorded queries are a TOML file:
+//!
+//! ```toml
+//! [[queries]]
+//! query = "retry failed deliveries"
+//! source = "stripe"
+//! expect = "Webhooks > Retries"   # heading path, or its last segments
+//! ```
+//!
+//! A configuration file holds `[index]` ranking keys, as in a source's
+//! `settings.toml`, so a winning candidate can be copied over as is.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use blz_core::{IndexConfig, Ranking, SearchIndex, Storage};
+use clap::Args;
+use serde::{Deserialize, Serialize};
+
+use crate::output::OutputFormat;
+use crate::utils::cli_args::FormatArg;
+use crate::utils::resolver::resolve_source;
+
+/// Label of the configuration read from each source's own settings.
+const CURRENT: &str = "current";
+
+/// Arguments for `blz bench rank`.
+#[derive(Args, Clone, Debug)]
+pub struct RankArgs {
+    /// Recorded queries with the section expected to answer each
+    #[arg(long, value_name = "FILE")]
+    pub queries: PathBuf,
+    /// Ranking configuration to compare with the current settings
+    /// (repeatable; a TOML file of `[index]` keys)
+    #[arg(long = "ranking", value_name = "FILE")]
+    pub configs: Vec<PathBuf>,
+    /// How many hits to look through for the expected section
+    #[arg(
+        long,
+        default_value_t = 10,
+        value_parser = clap::value_parser!(u16).range(1..=1_000)
+    )]
+    pub limit: u16,
+    /// Output format
+    #[command(flatten)]
+    pub format: FormatArg,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RecordedQueries {
+    queries: Vec<RecordedQuery>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RecordedQuery {
+    query: String,
+    source: String,
+    expect: String,
+}
+
+impl RecordedQuery {
+    /// Whether a hit at `heading_path` is the expected section: the path
+    /// ends with the expected segments, compared case-insensitively.
+    fn is_expected(&self, heading_path: &[String]) -> bool {
+        let segments = |path: &str| -> Vec<String> {
+            path.split('>')
+                .map(|segment| segment.trim().to_lowercase())
+                .filter(|segment| !segment.is_empty())
+                .collect()
+        };
+        let expected = segments(&self.expect);
+        let path = segments(&heading_path.join(" > "));
+        !expected.is_empty() && path.ends_with(&expected)
+    }
+}
+
+/// A named ranking configuration; `None` uses each source's own settings.
+struct Candidate {
+    name: String,
+    ranking: Option<Ranking>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ConfigScore {
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ranking: Option<Ranking>,
+    mrr: f64,
+    hit_at_1: f64,
+    hit_at_limit: f64,
+    /// 1-based rank of the expected section per query, if it was found.
+    ranks: Vec<Option<usize>>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RankReport {
+    limit: u16,
+    queries: Vec<RecordedQuery>,
+    configs: Vec<ConfigScore>,
+}
+
+/// Execute `blz bench rank`.
+///
+/// # Errors
+///
+/// Returns an error if the queries or a configuration cannot be read, a
+/// source is missing or unindexed, or a search fails.
+pub fn execute(args: &RankArgs, quiet: bool) -> Result<()> {
+    let format = args.format.resolve(quiet);
+    let storage = Storage::new()?;
+
+    let mut queries = load_queries(&args.queries)?;
+    for recorded in &mut queries {
+        recorded.source = resolve_source(&storage, &recorded.source)?
+            .ok_or_else(|| anyhow::anyhow!("Source '{}' not found", recorded.source))?;
+    }
+    let mut sources: Vec<String> = queries.iter().map(|q| q.source.clone()).collect();
+    sources.sort_unstable();
+    sources.dedup();
+
+    let mut candidates = vec![Candidate {
+        name: CURRENT.to_string(),
+        ranking: None,
+    }];
+    for path in &args.configs {
+        candidates.push(load_candidate(path)?);
+    }
+
+    let limit = usize::from(args.limit);
+    let mut configs = Vec::with_capacity(candidates.len());
+    for candidate in candidates {
+        let indexes = sources
+            .iter()
+            .map(|alias| {
+                let index = storage
+                    .open_index(alias)
+                    .with_context(|| format!("open index for source={alias}"))?;
+                let index = match &candidate.ranking {
+                    Some(ranking) => index.with_ranking(ranking.clone()),
+                    None => index,
+                };
+                Ok((alias.as_str(), index))
+            })
+            .collect::<Result<Vec<(&str, SearchIndex)>>>()?;
+
+        let mut ranks = Vec::with_capacity(queries.len());
+        for recorded in &queries {
+            let index = indexes
+                .iter()
+                .find(|(alias, _)| *alias == recorded.source)
+                .map(|(_, index)| index)
+                .ok_or_else(|| anyhow::anyhow!("No index for '{}'", recorded.source))?;
+            let hits = index
+                .search(&recorded.query, Some(&recorded.source), limit)
+                .with_context(|| format!("search failed for source={}", recorded.source))?;
+            ranks.push(
+                hits.iter()
+                    .position(|hit| recorded.is_expected(&hit.heading_path))
+                    .map(|i| i + 1),
+            );
+        }
+        configs.push(score(candidate, ranks));
+    }
+
+    let report = RankReport {
+        limit: args.limit,
+        queries,
+        configs,
+    };
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+        OutputFormat::Jsonl | OutputFormat::Raw => println!("{}", serde_json::to_string(&report)?),
+        OutputFormat::Text | OutputFormat::Markdown => print_text_report(&report, sources.len()),
+    }
+    Ok(())
+}
+
+fn load_queries(path: &Path) -> Result<Vec<RecordedQuery>> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let recorded: RecordedQueries =
+        toml::from_str(&text).with_context(|| format!("Failed to parse {}", path.display()))?;
+    if recorded.queries.is_empty() {
+        bail!("{} has no [[queries]] entries", path.display());
+    }
+    Ok(recorded.queries)
+}
+
+/// Read a configuration file's `[index]` table (or its top level, when it
+/// has none) as ranking settings named after the file.
+fn load_candidate(path: &Path) -> Result<Candidate> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let mut table: toml::Table =
+        toml::from_str(&text).with_context(|| format!("Failed to parse {}", path.display()))?;
+    let index = table.remove("index").unwrap_or(toml::Value::Table(table));
+    let config: IndexConfig = index
+        .try_into()
+        .with_context(|| format!("Invalid [index] settings in {}", path.display()))?;
+    let ranking = Ranking::from_config(&config)
+        .with_context(|| format!("Invalid ranking settings in {}", path.display()))?;
+    let name = path.file_stem().map_or_else(
+        || path.display().to_string(),
+        |stem| stem.to_string_lossy().into_owned(),
+    );
+    Ok(Candidate {
+        name,
+        ranking: Some(ranking),
+    })
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn score(candidate: Candidate, ranks: Vec<Option<usize>>) -> ConfigScore {
+    let total = ranks.len().max(1) as f64;
+    let mrr = ranks
+        .iter()
+        .flatten()
+        .map(|&rank| 1.0 / rank as f64)
+        .sum::<f64>()
+        / total;
+    let hit_at_1 = ranks.iter().filter(|rank| **rank == Some(1)).count() as f64 / total;
+    let hit_at_limit = ranks.iter().filter(|rank| rank.is_some()).count() as f64 / total;
+    ConfigScore {
+        name: candidate.name,
+        ranking: candidate.ranking,
+        mrr,
+        hit_at_1,
+        hit_at_limit,
+        ranks,
+    }
+}
+
+fn print_text_report(report: &RankReport, sources: usize) {
+    println!(
+        "Ranked {} recorded query(s) across {sources} source(s), top {}",
+        report.queries.len(),
+        report.limit
+    );
+    let width = report
+        .configs
+        .iter()
+        .map(|config| config.name.len())
+        .max()
+        .unwrap_or(0)
+        .max("config".len());
+    println!(
+        "\n{:<width$} {:>7} {:>7} {:>8}",
+        "config",
+        "MRR",
+        "hit@1",
+        format!("hit@{}", report.limit)
+    );
+    for config in &report.configs {
+        println!(
+            "{:<width$} {:>7.3} {:>6.1}% {:>7.1}%",
+            config.name,
+            config.mrr,
+            config.hit_at_1 * 100.0,
+            config.hit_at_limit * 100.0
+        );
+    }
+
+    println!(
+        "\nRank of the expected section per query (- = not in the top {}):",
+        report.limit
+    );
+    for (i, recorded) in report.queries.iter().enumerate() {
+        let ranks: Vec<String> = report
+            .configs
+            .iter()
+            .map(|config| config.ranks[i].map_or_else(|| "-".to_string(), |r| r.to_string()))
+            .collect();
+        println!(
+            "  {}  {:?} ({}) -> {}",
+            ranks.join(" / "),
+            recorded.query,
+            recorded.source,
+            recorded.expect
+        );
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn path(segments: &[&str]) -> Vec<String> {
+        segments.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn expected_section_matches_trailing_heading_segments() {
+        let recorded = RecordedQuery {
+            query: "retries".to_string(),
+            source: "stripe".to_string(),
+            expect: "webhooks > Retries".to_string(),
+        };
+        assert!(recorded.is_expected(&path(&["API", "Webhooks", "Retries"])));
+        assert!(!recorded.is_expected(&path(&["Webhooks", "Retries", "Limits"])));
+        assert!(!recorded.is_expected(&path(&["Retries"])));
+    }
+
+    #[test]
+    fn scores_reward_higher_ranks() {
+        let candidate = || Candidate {
+            name: "test".to_string(),
+            ranking: None,
+        };
+        let score = score(candidate(), vec![Some(1), Some(2), None, Some(4)]);
+        assert!((score.mrr - (1.0 + 0.5 + 0.25) / 4.0).abs() < 1e-9);
+        assert!((score.hit_at_1 - 0.25).abs() < 1e-9);
+        assert!((score.hit_at_limit - 0.75).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,216 @@
+//! Export and import commands: move the cache between machines.
+//!
+//! `blz export --bundle out.tar.zst` packages cached sources (content,
+//! metadata, descriptors, and optionally search indices) into one file;
+//! `blz import out.tar.zst` unpacks it on another machine, which needs no
+//! network access. Indices left out of the bundle are rebuilt on import from
+//! the cached content. See [`blz_core::bundle`] for the format.
+
+use std::path::PathBuf;
+
+use anyhow::{Context, Result, bail};
+use blz_core::bundle::{self, ExportOptions, ImportOptions};
+use blz_core::refresh::{DefaultRefreshIndexer, reindex_source};
+use blz_core::{PerformanceMetrics, Storage};
+use clap::Args;
+use colored::Colorize;
+use serde::Serialize;
+
+use crate::output::OutputFormat;
+use crate::utils::cli_args::FormatArg;
+use crate::utils::resolver::resolve_source;
+
+/// Arguments for `blz export`.
+#[derive(Args, Clone, Debug)]
+pub struct ExportArgs {
+    /// Sources to export (default: all cached sources)
+    #[arg(value_name = "ALIAS")]
+    pub aliases: Vec<String>,
+    /// Bundle file to write (`.tar.zst`)
+    #[arg(long, value_name = "FILE")]
+    pub bundle: PathBuf,
+    /// Include search indices so the importing machine skips reindexing
+    #[arg(long)]
+    pub with_index: bool,
+    /// zstd compression level (1-22)
+    #[arg(long, value_name = "LEVEL", value_parser = clap::value_parser!(i32).range(1..=22))]
+    pub level: Option<i32>,
+    /// Overwrite an existing bundle file
+    #[arg(long)]
+    pub force: bool,
+    /// Output format
+    #[command(flatten)]
+    pub format: FormatArg,
+}
+
+/// Arguments for `blz import`.
+#[derive(Args, Clone, Debug)]
+pub struct ImportArgs {
+    /// Bundle written by `blz export`
+    #[arg(value_name = "BUNDLE")]
+    pub bundle: PathBuf,
+    /// Replace sources that already exist
+    #[arg(long)]
+    pub force: bool,
+    /// Leave sources without an index instead of rebuilding it
+    #[arg(long)]
+    pub no_reindex: bool,
+    /// Output format
+    #[command(flatten)]
+    pub format: FormatArg,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ImportSummary {
+    bundle: PathBuf,
+    imported: Vec<String>,
+    reindexed: Vec<String>,
+    skipped: Vec<String>,
+}
+
+/// Execute `blz export`.
+///
+/// # Errors
+///
+/// Returns an error if a source is unknown, the bundle exists without
+/// `--force`, or it cannot be written.
+pub fn execute_export(args: &ExportArgs, quiet: bool) -> Result<()> {
+    let format = args.format.resolve(quiet);
+    if args.bundle.exists() && !args.force {
+        bail!(
+            "{} already exists; pass --force to overwrite it",
+            args.bundle.display()
+        );
+    }
+    let storage = Storage::new()?;
+    let aliases = if args.aliases.is_empty() {
+        storage.list_sources()
+    } else {
+        args.aliases
+            .iter()
+            .map(|alias| {
+                resolve_source(&storage, alias)?
+                    .filter(|canonical| storage.exists(canonical))
+                    .ok_or_else(|| anyhow::anyhow!("Source '{alias}' not found"))
+            })
+            .collect::<Result<Vec<_>>>()?
+    };
+    if aliases.is_empty() {
+        bail!("No sources to export. Add one with `blz add <alias> <url>`.");
+    }
+
+    let options = ExportOptions {
+        include_index: args.with_index,
+        level: args.level,
+    };
+    let manifest = bundle::export_bundle(&storage, &aliases, &args.bundle, options)
+        .with_context(|| format!("Failed to write {}", args.bundle.display()))?;
+
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&manifest)?),
+        OutputFormat::Jsonl | OutputFormat::Raw => {
+            println!("{}", serde_json::to_string(&manifest)?);
+        },
+        OutputFormat::Text | OutputFormat::Markdown => {
+            if !quiet {
+                let size = std::fs::metadata(&args.bundle).map_or(0, |m| m.len());
+                println!(
+                    "{} Exported {} source(s) to {} ({} KB{})",
+                    "✓".green(),
+                    manifest.sources.len(),
+                    args.bundle.display(),
+                    size.div_ceil(1024),
+                    if args.with_index {
+                        ", with indices"
+                    } else {
+                        ""
+                    }
+                );
+                println!(
+                    "  Import elsewhere with: blz import {}",
+                    args.bundle.display()
+                );
+            }
+        },
+    }
+    Ok(())
+}
+
+/// Execute `blz import`.
+///
+/// # Errors
+///
+/// Returns an error if the bundle cannot be read or unpacked, or an index
+/// cannot be rebuilt.
+pub fn execute_import(args: &ImportArgs, quiet: bool, metrics: &PerformanceMetrics) -> Result<()> {
+    let format = args.format.resolve(quiet);
+    let storage = Storage::new()?;
+    let options = ImportOptions {
+        overwrite: args.force,
+    };
+    let report = bundle::import_bundle(&storage, &args.bundle, options)
+        .with_context(|| format!("Failed to import {}", args.bundle.display()))?;
+
+    let mut reindexed = Vec::new();
+    if !args.no_reindex {
+        for source in report.imported.iter().filter(|source| source.needs_index) {
+            let filter_preference = storage
+                .load_source_metadata(&source.alias)?
+                .and_then(|metadata| metadata.filter_non_english)
+                .unwrap_or(true);
+            reindex_source(
+                &storage,
+                &source.alias,
+                metrics.clone(),
+                &DefaultRefreshIndexer,
+                filter_preference,
+            )
+            .with_context(|| format!("Failed to rebuild the index for '{}'", source.alias))?;
+            reindexed.push(source.alias.clone());
+        }
+    }
+
+    let summary = ImportSummary {
+        bundle: args.bundle.clone(),
+        imported: report.imported.iter().map(|s| s.alias.clone()).collect(),
+        reindexed,
+        skipped: report.skipped,
+    };
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&summary)?),
+        OutputFormat::Jsonl | OutputFormat::Raw => {
+            println!("{}", serde_json::to_string(&summary)?);
+        },
+        OutputFormat::Text | OutputFormat::Markdown => {
+            if !quiet {
+                print_import_summary(&summary);
+            }
+        },
+    }
+    Ok(())
+}
+
+fn print_import_summary(summary: &ImportSummary) {
+    println!(
+        "{} Imported {} source(s) from {}",
+        "✓".green(),
+        summary.imported.len(),
+        summary.bundle.display()
+    );
+    for alias in &summary.imported {
+        let note = if summary.reindexed.contains(alias) {
+            " (index rebuilt)"
+        } else {
+            ""
+        };
+        println!("  • {alias}{}", note.bright_black());
+    }
+    if !summary.skipped.is_empty() {
+        println!(
+            "{} Skipped existing source(s): {} (use --force to replace)",
+            "!".yellow(),
+            summary.skipped.join(", ")
+        );
+    }
+}
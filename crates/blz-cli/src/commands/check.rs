@@ -9,6 +9,7 @@
 //! blz check bun                  # Check single source
 //! blz check --all                # Check all sources
 //! blz check bun --json           # JSON output for scripting
+//! blz check --all --verify       # Also verify declared checksums/signatures
 //! ```
 
 use anyhow::Result;
@@ -27,6 +28,11 @@ pub struct CheckArgs {
     #[arg(long)]
     pub all: bool,
 
+    /// Also check each cached document against its declared SHA-256 or
+    /// minisign signature, reporting mismatches as errors
+    #[arg(long)]
+    pub verify: bool,
+
     /// Output format
     #[command(flatten)]
     pub format: FormatArg,
@@ -41,7 +47,13 @@ pub struct CheckArgs {
 ///
 /// * `alias` - Source to validate (validates all if not specified with --all)
 /// * `all` - Validate all sources
+/// * `verify` - Check cached documents against declared checksums/signatures
 /// * `format` - Output format (text, json, jsonl)
-pub async fn execute(alias: Option<String>, all: bool, format: OutputFormat) -> Result<()> {
-    super::validate::execute(alias, all, format).await
+pub async fn execute(
+    alias: Option<String>,
+    all: bool,
+    verify: bool,
+    format: OutputFormat,
+) -> Result<()> {
+    super::validate::execute(alias, all, verify, format).await
 }
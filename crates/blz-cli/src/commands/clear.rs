@@ -5,6 +5,8 @@ use blz_core::Storage;
 use colored::Colorize;
 use std::io::{self, Write};
 
+use crate::utils::confirm::{self, Risk};
+
 /// Abstraction over the storage operations needed by the clear command.
 pub trait ClearStorage {
     fn list_sources(&self) -> Result<Vec<String>>;
@@ -92,21 +94,13 @@ pub fn run(force: bool) -> Result<()> {
     let storage = Storage::new()?;
     let stdout = io::stdout();
     let mut stdout_lock = stdout.lock();
-    let mut input = String::new();
-
-    execute_clear(&storage, &mut stdout_lock, force, |_sources| {
-        let prompt_stdout = io::stdout();
-        let mut prompt_lock = prompt_stdout.lock();
-        write!(prompt_lock, "Are you sure you want to continue? [y/N] ")?;
-        prompt_lock.flush()?;
-
-        input.clear();
-        io::stdin().read_line(&mut input)?;
 
-        Ok(matches!(
-            input.trim().to_ascii_lowercase().as_str(),
-            "y" | "yes"
-        ))
+    execute_clear(&storage, &mut stdout_lock, false, |_sources| {
+        confirm::confirm(
+            "Are you sure you want to continue?",
+            Risk::Destructive,
+            force,
+        )
     })?;
 
     Ok(())
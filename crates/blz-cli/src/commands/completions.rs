@@ -32,7 +32,7 @@ pub fn list_supported(format: crate::output::OutputFormat) {
         ("elvish", "~/.elvish/lib/blz.elv"),
     ];
     match format {
-        crate::output::OutputFormat::Text => {
+        crate::output::OutputFormat::Text | crate::output::OutputFormat::Markdown => {
             println!("Supported shells:\n");
             for (name, path) in &shells {
                 println!("  - {name} (install to {path})");
@@ -1,19 +1,24 @@
 //! Registry create-source command implementation
 
 use anyhow::{Context, Result, bail};
-use blz_core::PerformanceMetrics;
+use blz_core::github::{GithubClient, RepoSpec};
+use blz_core::{PerformanceMetrics, Storage};
 use chrono::Utc;
 use clap::Subcommand;
 use colored::Colorize;
 use inquire::{Confirm, MultiSelect, Select, Text};
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tokio::process::Command;
 
 use crate::commands::{AddRequest, DescriptorInput, add_source};
+use crate::utils::confirm::{self, Risk};
 use crate::utils::validation::{normalize_alias, validate_alias};
 
+/// Directory holding one TOML file per registry source.
+const REGISTRY_SOURCES_DIR: &str = "registry/sources";
+
 /// Subcommands for `blz registry`.
 #[derive(Subcommand, Clone, Debug)]
 pub enum RegistryCommands {
@@ -36,7 +41,10 @@ pub enum RegistryCommands {
         /// NPM package names (comma-separated).
         #[arg(long, value_delimiter = ',')]
         npm: Vec<String>,
-        /// GitHub repositories (comma-separated).
+        /// GitHub repositories as `owner/repo` (comma-separated).
+        ///
+        /// Each is checked with the GitHub API (using `GITHUB_TOKEN` when set);
+        /// the first description found is used when `--description` is omitted.
         #[arg(long, value_delimiter = ',')]
         github: Vec<String>,
         /// Also add this source to your local index after creating.
@@ -195,23 +203,16 @@ pub async fn execute(config: CreateSourceConfig) -> Result<()> {
     }
 
     // Step 3: Validate content type
-    if analysis.analysis.content_type == "index" && !yes {
-        println!(
-            "\n{} This source appears to be a navigation index only ({} lines).",
-            "⚠".yellow(),
-            analysis.analysis.line_count
-        );
-        let proceed = Confirm::new("Add it to the registry anyway?")
-            .with_default(false)
-            .prompt()?;
-
-        if !proceed {
-            println!("Skipping source.");
-            return Ok(());
-        }
+    if analysis.analysis.content_type == "index" && !yes && !confirm_index_only(&analysis)? {
+        println!("Skipping source.");
+        return Ok(());
     }
 
     // Step 4: Prompt for metadata (if not provided)
+    let description = match description {
+        Some(description) => Some(description),
+        None => describe_from_github(&github_repos, quiet).await?,
+    };
     let metadata = if yes {
         SourceMetadata {
             description: description.unwrap_or_else(|| "No description provided".to_string()),
@@ -229,6 +230,10 @@ pub async fn execute(config: CreateSourceConfig) -> Result<()> {
     };
 
     // Step 5: Create TOML file
+    if !confirm_registry_write(&safe_name, yes)? {
+        println!("Skipping source.");
+        return Ok(());
+    }
     create_source_toml(&safe_name, &name, &analysis, &metadata)?;
 
     if !quiet {
@@ -386,6 +391,38 @@ fn format_content_type(content_type: &str) -> String {
     }
 }
 
+/// Check `--github` repositories against the GitHub API and return the first
+/// repository description.
+///
+/// Metadata is cached in storage, so registering many sources that share a
+/// repository costs one API request. Rate limits only skip the lookup.
+async fn describe_from_github(repos: &[String], quiet: bool) -> Result<Option<String>> {
+    if repos.is_empty() {
+        return Ok(None);
+    }
+    let client = GithubClient::from_env()?;
+    let cache = Storage::new()?.github_cache();
+    let mut description = None;
+    for repo in repos {
+        let spec: RepoSpec = repo.parse()?;
+        match client.repo(&cache, &spec).await {
+            Ok(metadata) => {
+                if description.is_none() {
+                    description = metadata.description.filter(|d| !d.trim().is_empty());
+                }
+            },
+            Err(err @ blz_core::Error::ResourceLimited(_)) => {
+                if !quiet {
+                    eprintln!("{} Skipping GitHub lookup: {err}", "⚠".yellow());
+                }
+                break;
+            },
+            Err(err) => return Err(err).context(format!("Invalid --github repository '{repo}'")),
+        }
+    }
+    Ok(description)
+}
+
 /// Parse comma-separated input into a vector of trimmed non-empty strings
 fn parse_comma_separated(input: &str) -> Vec<String> {
     input
@@ -495,13 +532,45 @@ fn prompt_metadata(
 }
 
 /// Create TOML file for the source
+/// Warn about navigation-only content and ask whether to register it anyway.
+fn confirm_index_only(analysis: &SourceAnalysis) -> Result<bool> {
+    println!(
+        "\n{} This source appears to be a navigation index only ({} lines).",
+        "⚠".yellow(),
+        analysis.analysis.line_count
+    );
+    if !confirm::is_interactive() {
+        bail!("Refusing to add an index-only source without confirmation; pass --yes");
+    }
+    Ok(Confirm::new("Add it to the registry anyway?")
+        .with_default(false)
+        .prompt()?)
+}
+
+/// Confirm writing the registry entry; replacing an existing one counts as destructive.
+fn confirm_registry_write(id: &str, yes: bool) -> Result<bool> {
+    let toml_path = Path::new(REGISTRY_SOURCES_DIR).join(format!("{id}.toml"));
+    let (prompt, risk) = if toml_path.exists() {
+        (
+            format!("Replace existing registry entry {}?", toml_path.display()),
+            Risk::Destructive,
+        )
+    } else {
+        (
+            format!("Write {} and rebuild the registry?", toml_path.display()),
+            Risk::Routine,
+        )
+    };
+    confirm::confirm(&prompt, risk, yes)
+}
+
 fn create_source_toml(
     id: &str,
     display_name: &str,
     analysis: &SourceAnalysis,
     metadata: &SourceMetadata,
 ) -> Result<()> {
-    let registry_sources_dir = PathBuf::from("registry/sources");
+    let registry_sources_dir = PathBuf::from(REGISTRY_SOURCES_DIR);
     fs::create_dir_all(&registry_sources_dir)
         .context("Failed to create registry/sources directory")?;
 
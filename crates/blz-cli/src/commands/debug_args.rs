@@ -0,0 +1,94 @@
+//! Argument preprocessing explainer (`blz debug-args`)
+//!
+//! Shows how a command line is rewritten before clap sees it (the injected
+//! default subcommand, and why) so shorthand surprises can be diagnosed
+//! without running the command.
+
+use anyhow::Result;
+use clap::CommandFactory;
+use serde::Serialize;
+
+use crate::cli::Cli;
+use crate::i18n::{self, Message};
+use crate::output::OutputFormat;
+use crate::utils::preprocess::{ArgRewrite, explain_args_from};
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DebugArgsReport {
+    #[serde(flatten)]
+    rewrite: ArgRewrite,
+    /// Subcommand clap resolves the rewritten arguments to.
+    subcommand: Option<String>,
+    /// Clap's parse error for the rewritten arguments, if any.
+    parse_error: Option<String>,
+}
+
+/// Execute `blz debug-args`.
+///
+/// `args` excludes the binary name; it is explained exactly as if it had
+/// followed `blz` on the command line.
+///
+/// # Errors
+///
+/// Returns an error if the report cannot be serialized.
+pub fn execute(args: Vec<String>, format: OutputFormat) -> Result<()> {
+    let raw = std::iter::once("blz".to_string()).chain(args).collect();
+    let rewrite = explain_args_from(raw);
+    let (subcommand, parse_error) = match Cli::command().try_get_matches_from(&rewrite.rewritten) {
+        Ok(matches) => (matches.subcommand_name().map(str::to_string), None),
+        Err(err) => (None, Some(err.kind().to_string())),
+    };
+    let report = DebugArgsReport {
+        rewrite,
+        subcommand,
+        parse_error,
+    };
+
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+        OutputFormat::Jsonl => println!("{}", serde_json::to_string(&report)?),
+        OutputFormat::Raw => println!("{}", shell_words(&report.rewrite.rewritten)),
+        OutputFormat::Text | OutputFormat::Markdown => print_text_report(&report),
+    }
+
+    Ok(())
+}
+
+fn print_text_report(report: &DebugArgsReport) {
+    let rewrite = &report.rewrite;
+    println!("original:   {}", shell_words(&rewrite.original));
+    println!("rewritten:  {}", shell_words(&rewrite.rewritten));
+    match rewrite.injected_at {
+        Some(index) => println!("injected:   'default' at position {index}"),
+        None => println!("injected:   nothing"),
+    }
+    println!("reason:     {}", rewrite.reason);
+    match (&report.subcommand, &report.parse_error) {
+        (Some(name), _) => println!("subcommand: {name}"),
+        (None, Some(error)) => println!("subcommand: none ({error})"),
+        (None, None) => println!("subcommand: none"),
+    }
+    let prefix = i18n::text(Message::WarningPrefix, &[]);
+    for warning in &rewrite.warnings {
+        println!("{prefix} {warning}");
+    }
+}
+
+/// Join arguments for display, quoting any that would not survive a shell.
+pub(super) fn shell_words(args: &[String]) -> String {
+    args.iter()
+        .map(|arg| {
+            if !arg.is_empty()
+                && arg
+                    .chars()
+                    .all(|c| c.is_alphanumeric() || "-_.:/=@,+%".contains(c))
+            {
+                arg.clone()
+            } else {
+                format!("'{}'", arg.replace('\'', r"'\''"))
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
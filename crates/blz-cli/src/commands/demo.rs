@@ -0,0 +1,223 @@
+//! Demo sandbox with a built-in sample source.
+//!
+//! `blz demo` installs a small fictional documentation set (compiled into the
+//! binary) into a throwaway data and config directory, then prints the
+//! environment variables that point `blz` at it. New users can try search,
+//! get, and map without network access and without touching their real cache.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+use anyhow::{Context, Result};
+use base64::{Engine as _, engine::general_purpose::STANDARD as B64};
+use blz_core::{PerformanceMetrics, SearchIndex, SourceVariant, Storage, TocEntry, profile};
+use clap::Args;
+use colored::Colorize;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use super::debug_args::shell_words;
+use super::docs_bundle::{EmbeddedSource, install_embedded};
+use crate::output::OutputFormat;
+use crate::utils::cli_args::FormatArg;
+use crate::utils::toc::count_headings;
+
+/// Alias the sample source is stored under.
+const DEMO_ALIAS: &str = "tern";
+
+/// Synthetic URL persisted into the sample source's metadata.
+const DEMO_URL: &str = "blz://demo/tern";
+
+/// Human-readable description stored in metadata files.
+const DEMO_DESCRIPTION: &str = "Sample docs for a fictional web framework (blz demo).";
+
+/// Query used for the suggested search and to pick the suggested citation.
+const DEMO_QUERY: &str = "useState";
+
+/// Embedded sample llms.txt.
+static DEMO_CONTENT: &str = include_str!("../../bundled-docs/demo-llms.txt");
+
+/// Pre-computed SHA-256 hash (base64) of the sample content.
+static DEMO_SHA256: LazyLock<String> =
+    LazyLock::new(|| B64.encode(Sha256::digest(DEMO_CONTENT.as_bytes())));
+
+/// Arguments for `blz demo`.
+#[derive(Args, Clone, Debug)]
+pub struct DemoArgs {
+    /// Sandbox directory (defaults to a fixed directory under the system temp dir)
+    #[arg(long, value_name = "PATH")]
+    pub dir: Option<PathBuf>,
+    /// Delete the sandbox instead of creating it
+    #[arg(long)]
+    pub clean: bool,
+    /// Output format
+    #[command(flatten)]
+    pub format: FormatArg,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DemoReport {
+    dir: PathBuf,
+    alias: &'static str,
+    headings: usize,
+    lines: usize,
+    env: BTreeMap<&'static str, PathBuf>,
+    examples: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CleanReport {
+    dir: PathBuf,
+    removed: bool,
+}
+
+/// Execute `blz demo`.
+///
+/// # Errors
+///
+/// Returns an error if the sandbox cannot be created, indexed, or removed.
+pub fn execute(args: &DemoArgs, quiet: bool, metrics: PerformanceMetrics) -> Result<()> {
+    let format = args.format.resolve(quiet);
+    let dir = args.dir.clone().unwrap_or_else(default_dir);
+
+    if args.clean {
+        let removed = dir.exists();
+        if removed {
+            std::fs::remove_dir_all(&dir)
+                .with_context(|| format!("failed to remove {}", dir.display()))?;
+        }
+        let report = CleanReport { dir, removed };
+        match format {
+            OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+            OutputFormat::Jsonl | OutputFormat::Raw => {
+                println!("{}", serde_json::to_string(&report)?);
+            },
+            OutputFormat::Text | OutputFormat::Markdown => {
+                if report.removed {
+                    println!("Removed demo sandbox {}", report.dir.display());
+                } else if !quiet {
+                    println!("No demo sandbox at {}", report.dir.display());
+                }
+            },
+        }
+        return Ok(());
+    }
+
+    let report = install(&dir, metrics)?;
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+        OutputFormat::Jsonl | OutputFormat::Raw => println!("{}", serde_json::to_string(&report)?),
+        OutputFormat::Text | OutputFormat::Markdown => {
+            print_text_report(&report, args.dir.as_deref());
+        },
+    }
+    Ok(())
+}
+
+/// Fixed sandbox location so repeated runs reuse the same directory.
+fn default_dir() -> PathBuf {
+    std::env::temp_dir().join(format!("{}-demo", profile::app_dir_slug()))
+}
+
+/// Install and index the sample source, returning what to try next.
+fn install(dir: &Path, metrics: PerformanceMetrics) -> Result<DemoReport> {
+    let data_dir = dir.join("data");
+    let config_dir = dir.join("config");
+    let storage = Storage::with_paths(data_dir.clone(), config_dir.clone())?;
+
+    install_embedded(
+        &storage,
+        &EmbeddedSource {
+            alias: DEMO_ALIAS,
+            name: "Tern",
+            url: DEMO_URL,
+            content: DEMO_CONTENT,
+            sha256: &DEMO_SHA256,
+            variant: SourceVariant::Llms,
+            aliases: &[],
+            tags: &["demo"],
+            description: DEMO_DESCRIPTION,
+            category: "demo",
+        },
+        metrics,
+    )
+    .context("failed to install the demo source")?;
+
+    let llms_json = storage.load_llms_json(DEMO_ALIAS)?;
+    let index = SearchIndex::open(&storage.index_dir(DEMO_ALIAS)?)?;
+    // Cite the whole section the suggested query lands in, not just the hit.
+    let lines = index
+        .search(DEMO_QUERY, Some(DEMO_ALIAS), 1)?
+        .into_iter()
+        .next()
+        .map_or_else(
+            || "1-20".to_string(),
+            |hit| section_lines(&llms_json.toc, &hit.heading_path).unwrap_or(hit.lines),
+        );
+
+    Ok(DemoReport {
+        dir: dir.to_path_buf(),
+        alias: DEMO_ALIAS,
+        headings: count_headings(&llms_json.toc),
+        lines: llms_json.line_index.total_lines,
+        env: BTreeMap::from([("BLZ_CONFIG_DIR", config_dir), ("BLZ_DATA_DIR", data_dir)]),
+        examples: vec![
+            format!("blz query {DEMO_QUERY}"),
+            format!("blz get {DEMO_ALIAS}:{lines}"),
+            format!("blz map {DEMO_ALIAS}"),
+        ],
+    })
+}
+
+/// Line range of the TOC entry with exactly `heading_path`.
+fn section_lines(entries: &[TocEntry], heading_path: &[String]) -> Option<String> {
+    entries.iter().find_map(|entry| {
+        if entry.heading_path == heading_path {
+            Some(entry.lines.clone())
+        } else {
+            section_lines(&entry.children, heading_path)
+        }
+    })
+}
+
+fn print_text_report(report: &DemoReport, custom_dir: Option<&Path>) {
+    println!(
+        "{} {}",
+        "Demo sandbox ready:".green().bold(),
+        report.dir.display()
+    );
+    println!(
+        "  Source '{}': {} headings, {} lines of sample docs",
+        report.alias, report.headings, report.lines
+    );
+    println!();
+    println!("Point blz at it in this shell:");
+    let assignments: Vec<String> = report
+        .env
+        .iter()
+        .map(|(key, value)| format!("{key}={}", value.display()))
+        .collect();
+    println!("  export {}", shell_words(&assignments));
+    println!();
+    println!("Then try:");
+    for example in &report.examples {
+        println!("  {example}");
+    }
+    println!();
+    let clean = custom_dir.map_or_else(
+        || "blz demo --clean".to_string(),
+        |dir| {
+            format!(
+                "blz demo --clean {}",
+                shell_words(&["--dir".to_string(), dir.display().to_string()])
+            )
+        },
+    );
+    println!(
+        "{}",
+        format!("Your real cache is untouched. Remove the sandbox with `{clean}`.").bright_black()
+    );
+}
@@ -0,0 +1,262 @@
+//! Shareable source descriptors: curated metadata without content.
+//!
+//! `blz list --export FILE` writes each source's alias, name, description,
+//! category, tags, and URL to a JSON file; `blz add --descriptors FILE`
+//! applies that metadata to sources already installed locally. Teams can
+//! share curation this way without shipping cached documents or indexes.
+
+use std::io::{self, Read};
+use std::path::Path;
+
+use anyhow::{Context, Result, bail};
+use blz_core::{SourceDescriptor, Storage};
+use chrono::Utc;
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+
+use crate::utils::resolver;
+
+/// Current version of the descriptor file format.
+const FORMAT_VERSION: u32 = 1;
+
+/// File written by `blz list --export`.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DescriptorFile {
+    /// Format version, for forward compatibility.
+    pub version: u32,
+    /// When the file was exported (RFC 3339).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exported_at: Option<String>,
+    /// One entry per source.
+    pub sources: Vec<SharedDescriptor>,
+}
+
+/// Curated metadata for one source.
+///
+/// Absent fields leave the local value untouched on import; `tags` replaces
+/// the local tags when present.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SharedDescriptor {
+    /// Source alias the metadata applies to.
+    pub alias: String,
+    /// Display name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Short description.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Category grouping similar sources.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    /// Tags describing the source.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+    /// Where the documentation comes from; informational on import.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+/// Outcome of applying a descriptor file.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ImportReport {
+    /// Aliases whose metadata changed.
+    pub updated: Vec<String>,
+    /// Aliases whose metadata already matched.
+    pub unchanged: Vec<String>,
+    /// Entries for sources that are not installed, with their URL if known.
+    pub missing: Vec<(String, Option<String>)>,
+}
+
+/// Build the shareable descriptor for an installed source.
+///
+/// # Errors
+///
+/// Returns an error if the source metadata cannot be loaded.
+pub fn shared_descriptor(storage: &Storage, alias: &str) -> Result<SharedDescriptor> {
+    let descriptor = match storage.load_descriptor(alias)? {
+        Some(descriptor) => descriptor,
+        None => SourceDescriptor::from_source(alias, &storage.load_llms_json(alias)?.metadata),
+    };
+    Ok(SharedDescriptor {
+        alias: alias.to_string(),
+        name: descriptor.name,
+        description: descriptor.description,
+        category: descriptor.category,
+        tags: Some(descriptor.tags),
+        url: descriptor.url.or(descriptor.path),
+    })
+}
+
+/// Write descriptors for `aliases` to `path` (`-` for stdout).
+///
+/// # Errors
+///
+/// Returns an error if metadata cannot be loaded or the file cannot be written.
+pub fn export(storage: &Storage, aliases: &[String], path: &Path) -> Result<usize> {
+    let sources = aliases
+        .iter()
+        .map(|alias| shared_descriptor(storage, alias))
+        .collect::<Result<Vec<_>>>()?;
+    let file = DescriptorFile {
+        version: FORMAT_VERSION,
+        exported_at: Some(Utc::now().to_rfc3339()),
+        sources,
+    };
+    let json = serde_json::to_string_pretty(&file)?;
+    if path == Path::new("-") {
+        println!("{json}");
+    } else {
+        std::fs::write(path, format!("{json}\n"))
+            .with_context(|| format!("failed to write {}", path.display()))?;
+    }
+    Ok(file.sources.len())
+}
+
+/// Read a descriptor file from `path` (`-` for stdin).
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read, is not valid JSON, or uses a
+/// newer format version.
+pub fn read(path: &Path) -> Result<DescriptorFile> {
+    let text = if path == Path::new("-") {
+        let mut text = String::new();
+        io::stdin().read_to_string(&mut text)?;
+        text
+    } else {
+        std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?
+    };
+    let file: DescriptorFile = serde_json::from_str(&text)
+        .with_context(|| format!("invalid descriptor file {}", path.display()))?;
+    if file.version > FORMAT_VERSION {
+        bail!(
+            "descriptor file version {} is newer than supported version {FORMAT_VERSION}; upgrade blz",
+            file.version
+        );
+    }
+    Ok(file)
+}
+
+/// Apply every entry of `file` to the installed sources.
+///
+/// # Errors
+///
+/// Returns an error if an installed source's metadata cannot be updated.
+pub fn import(storage: &Storage, file: &DescriptorFile) -> Result<ImportReport> {
+    let mut report = ImportReport::default();
+    for shared in &file.sources {
+        let canonical =
+            resolver::resolve_source(storage, &shared.alias)?.filter(|alias| storage.exists(alias));
+        let Some(alias) = canonical else {
+            report
+                .missing
+                .push((shared.alias.clone(), shared.url.clone()));
+            continue;
+        };
+        if apply(storage, &alias, shared)? {
+            report.updated.push(alias);
+        } else {
+            report.unchanged.push(alias);
+        }
+    }
+    Ok(report)
+}
+
+/// Copy curated fields onto the stored metadata; returns whether anything changed.
+fn apply(storage: &Storage, alias: &str, shared: &SharedDescriptor) -> Result<bool> {
+    let mut llms = storage.load_llms_json(alias)?;
+    let mut descriptor = storage
+        .load_descriptor(alias)?
+        .unwrap_or_else(|| SourceDescriptor::from_source(alias, &llms.metadata));
+
+    let mut changed = replace(&mut descriptor.name, shared.name.as_ref());
+    if let Some(description) = &shared.description {
+        changed |= replace(&mut descriptor.description, Some(description));
+        changed |= replace(&mut llms.metadata.description, Some(description));
+    }
+    if let Some(category) = &shared.category {
+        changed |= replace(&mut descriptor.category, Some(category));
+        changed |= replace(&mut llms.metadata.category, Some(category));
+    }
+    if let Some(tags) = &shared.tags {
+        changed |= descriptor.tags != *tags || llms.metadata.tags != *tags;
+        descriptor.tags.clone_from(tags);
+        llms.metadata.tags.clone_from(tags);
+    }
+    if !changed {
+        return Ok(false);
+    }
+
+    storage.save_llms_json(alias, &llms)?;
+    storage.save_source_metadata(alias, &llms.metadata)?;
+    storage.save_descriptor(&descriptor)?;
+    Ok(true)
+}
+
+/// Overwrite `field` with `value` when one is given; returns whether it changed.
+fn replace(field: &mut Option<String>, value: Option<&String>) -> bool {
+    match value {
+        Some(value) if field.as_ref() != Some(value) => {
+            *field = Some(value.clone());
+            true
+        },
+        _ => false,
+    }
+}
+
+/// Print an import summary.
+pub fn print_report(report: &ImportReport, quiet: bool) {
+    if quiet {
+        return;
+    }
+    for alias in &report.updated {
+        println!("{} {}", "✓ Updated".green(), alias.green());
+    }
+    for (alias, url) in &report.missing {
+        let hint = url.as_ref().map_or_else(
+            || "not installed".to_string(),
+            |url| format!("not installed; add it with: blz add {alias} {url}"),
+        );
+        println!("{} {alias} ({hint})", "•".dimmed());
+    }
+    println!(
+        "\nSummary: {} updated, {} unchanged, {} not installed",
+        report.updated.len().to_string().green(),
+        report.unchanged.len(),
+        report.missing.len()
+    );
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replace_only_reports_real_changes() {
+        let mut field = Some("Docs".to_string());
+        assert!(!replace(&mut field, None));
+        assert!(!replace(&mut field, Some(&"Docs".to_string())));
+        assert!(replace(&mut field, Some(&"Guides".to_string())));
+        assert_eq!(field.as_deref(), Some("Guides"));
+    }
+
+    #[test]
+    fn absent_fields_deserialize_as_untouched() {
+        let file: DescriptorFile = serde_json::from_str(
+            r#"{"version":1,"sources":[{"alias":"bun","category":"runtime"}]}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            file.sources[0],
+            SharedDescriptor {
+                alias: "bun".to_string(),
+                category: Some("runtime".to_string()),
+                ..SharedDescriptor::default()
+            }
+        );
+    }
+}
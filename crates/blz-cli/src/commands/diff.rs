@@ -1,281 +1,853 @@
 //! Diff command implementation
+//!
+//! Compares two versions of a source: by default the current cache against
+//! the version archived by the last sync. `--since` takes a time expression
+//! (`2w`, `"last sync"`, `2025-01-31`) or a content SHA, and `--between v1..v2`
+//! compares two archived versions directly.
+//!
+//! Sections are matched by anchor, so the report says which headings were
+//! added, removed, moved, or edited (with the lines that changed), and which
+//! anchors now point at different line ranges so citations can be remapped.
 
-use anyhow::{Context, Result};
-use blz_core::{LlmsJson, Storage, compute_anchor_mappings};
-use serde_json::json;
-use std::fs;
+use std::collections::{HashMap, HashSet};
 
-#[allow(clippy::too_many_lines)]
-/// Show diffs for a source between the latest archived snapshot and current
-/// state. If no archive exists, a helpful message is printed.
+use anyhow::{Context, Result, bail};
+use blz_core::{ContentVersion, MarkdownParser, Storage, TocEntry, compute_anchor_mappings};
+use chrono::{DateTime, Utc};
+use colored::Colorize;
+use serde::Serialize;
+
+use crate::output::OutputFormat;
+use crate::utils::time_expr::parse_time_expr;
+
+/// Minimum number of hex digits accepted as a content SHA prefix.
+const MIN_SHA_PREFIX: usize = 4;
+
+/// One side of a diff: an archived version (`v1` is the oldest) or the live cache.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Version {
+    Archived {
+        number: usize,
+        version: ContentVersion,
+    },
+    Current,
+}
+
+impl Version {
+    fn archived(versions: &[ContentVersion], index: usize) -> Self {
+        Self::Archived {
+            number: index + 1,
+            version: versions[index].clone(),
+        }
+    }
+
+    fn label(&self) -> String {
+        match self {
+            Self::Archived { number, .. } => format!("v{number}"),
+            Self::Current => "current".to_string(),
+        }
+    }
+
+    fn load(&self, storage: &Storage, canonical: &str) -> Result<Document> {
+        match self {
+            Self::Archived { version, .. } => {
+                let text = storage
+                    .load_version(canonical, &version.id)
+                    .with_context(|| {
+                        format!("Failed to read version {} of '{canonical}'", version.id)
+                    })?;
+                // Archived versions keep only the text; rebuild the outline from it
+                let toc = MarkdownParser::new()?.parse(&text)?.toc;
+                Ok(Document {
+                    info: VersionInfo {
+                        snapshot: self.label(),
+                        sha256: Some(version.sha256.clone()),
+                        archived_at: Some(version.archived_at),
+                    },
+                    lines: text.lines().map(str::to_string).collect(),
+                    toc,
+                })
+            },
+            Self::Current => {
+                let llms = storage.load_llms_json(canonical)?;
+                let text = storage.load_llms_txt(canonical).with_context(|| {
+                    format!("Failed to load current llms.txt for '{canonical}'")
+                })?;
+                Ok(Document {
+                    info: VersionInfo {
+                        snapshot: self.label(),
+                        sha256: Some(llms.metadata.sha256),
+                        archived_at: None,
+                    },
+                    lines: text.lines().map(str::to_string).collect(),
+                    toc: llms.toc,
+                })
+            },
+        }
+    }
+}
+
+/// A loaded version: its text and heading outline.
+struct Document {
+    info: VersionInfo,
+    lines: Vec<String>,
+    toc: Vec<TocEntry>,
+}
+
+/// Which version one side of the report describes.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct VersionInfo {
+    snapshot: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sha256: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    archived_at: Option<DateTime<Utc>>,
+}
+
+/// A heading section present on only one side.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SectionChange {
+    anchor: String,
+    heading_path: Vec<String>,
+    lines: String,
+    content: String,
+}
+
+/// A section whose position in the outline changed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SectionMove {
+    anchor: String,
+    heading_path: Vec<String>,
+    old_heading_path: Vec<String>,
+    old_lines: String,
+    new_lines: String,
+}
+
+/// A section present on both sides whose text changed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SectionEdit {
+    anchor: String,
+    heading_path: Vec<String>,
+    old_lines: String,
+    new_lines: String,
+    /// Lines that differ in the previous version (absent for pure insertions)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    changed_old_lines: Option<String>,
+    /// Lines that differ in the current version (absent for pure deletions)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    changed_new_lines: Option<String>,
+    old_content: String,
+    new_content: String,
+}
+
+/// An anchor that still exists but now covers a different line range.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AnchorRemap {
+    anchor: String,
+    heading_path: Vec<String>,
+    old_lines: String,
+    new_lines: String,
+}
+
+/// Structural differences between two versions of a source.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DiffReport {
+    source: String,
+    previous: VersionInfo,
+    current: VersionInfo,
+    added: Vec<SectionChange>,
+    removed: Vec<SectionChange>,
+    moved: Vec<SectionMove>,
+    changed: Vec<SectionEdit>,
+    remapped: Vec<AnchorRemap>,
+}
+
+impl DiffReport {
+    fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.moved.is_empty()
+            && self.changed.is_empty()
+            && self.remapped.is_empty()
+    }
+}
+
+/// The cached state of a source, captured before a sync so the headings the
+/// sync changed can be reported afterwards (`blz sync --changes-json`).
+pub(super) struct Snapshot {
+    document: Document,
+}
+
+impl Snapshot {
+    /// Capture the current cache of `canonical`.
+    pub(super) fn capture(storage: &Storage, canonical: &str) -> Result<Self> {
+        Version::Current
+            .load(storage, canonical)
+            .map(|document| Self { document })
+    }
+
+    /// Number of lines in the captured content.
+    pub(super) fn line_count(&self) -> usize {
+        self.document.lines.len()
+    }
+
+    /// Heading-level changes from the snapshot to the current cache.
+    pub(super) fn changes_since(
+        self,
+        storage: &Storage,
+        canonical: &str,
+    ) -> Result<HeadingChanges> {
+        let current = Version::Current.load(storage, canonical)?;
+        let report = compare(canonical.to_string(), self.document, current);
+        Ok(HeadingChanges::from(report))
+    }
+}
+
+/// Which headings changed, without section content.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct HeadingChanges {
+    added: Vec<HeadingRef>,
+    removed: Vec<HeadingRef>,
+    moved: Vec<HeadingRef>,
+    changed: Vec<HeadingRef>,
+}
+
+/// A heading and its line range (in the previous version for removals).
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct HeadingRef {
+    heading_path: Vec<String>,
+    lines: String,
+}
+
+impl From<DiffReport> for HeadingChanges {
+    fn from(report: DiffReport) -> Self {
+        let heading = |heading_path, lines| HeadingRef {
+            heading_path,
+            lines,
+        };
+        Self {
+            added: report
+                .added
+                .into_iter()
+                .map(|s| heading(s.heading_path, s.lines))
+                .collect(),
+            removed: report
+                .removed
+                .into_iter()
+                .map(|s| heading(s.heading_path, s.lines))
+                .collect(),
+            moved: report
+                .moved
+                .into_iter()
+                .map(|s| heading(s.heading_path, s.new_lines))
+                .collect(),
+            changed: report
+                .changed
+                .into_iter()
+                .map(|s| heading(s.heading_path, s.new_lines))
+                .collect(),
+        }
+    }
+}
+
+/// Show structural changes to a source between two versions.
+///
+/// Without `since` or `between`, compares the current cache with the version
+/// archived by the latest sync.
 ///
 /// # Errors
 ///
-/// Returns an error if storage access, file reads, or JSON parsing fails.
-pub async fn show(alias: &str, since: Option<&str>) -> Result<()> {
+/// Returns an error if the source is unknown or has no archived versions, if
+/// a time expression or range cannot be resolved, or if a version cannot be
+/// read or parsed.
+pub async fn show(
+    alias: &str,
+    since: Option<&str>,
+    between: Option<&str>,
+    format: OutputFormat,
+) -> Result<()> {
     let storage = Storage::new()?;
     let canonical = crate::utils::resolver::resolve_source(&storage, alias)?
-        .unwrap_or_else(|| alias.to_string());
+        .filter(|canonical| storage.exists(canonical))
+        .with_context(|| {
+            format!("Source '{alias}' not found. Try 'blz list' or 'blz lookup' to add one.")
+        })?;
 
-    if !storage.exists(&canonical) {
-        println!("Source '{alias}' not found. Try 'blz list' or 'blz lookup' to add one.");
-        return Ok(());
+    let versions = storage.list_versions(&canonical)?;
+    if versions.is_empty() {
+        bail!(
+            "No previous version of '{canonical}' to compare. \
+             Versions are kept when 'blz sync {canonical}' fetches changed content."
+        );
     }
 
-    let current: LlmsJson = storage.load_llms_json(&canonical)?;
-    let Some(prev_path) = find_previous_llms_json(&storage, &canonical, since)? else {
-        println!(
-            "No previous snapshot found for '{canonical}'. Run 'blz refresh {canonical}' (deprecated alias: 'blz update {canonical}') to create history."
-        );
-        return Ok(());
-    };
-    let prev_json_text = fs::read_to_string(&prev_path)?;
-    let prev: LlmsJson = serde_json::from_str(&prev_json_text)?;
-
-    // Read contents for content diffs
-    let current_text = storage
-        .load_llms_txt(&canonical)
-        .with_context(|| format!("Failed to load current llms.txt for '{canonical}'"))?;
-    let prev_txt_path =
-        prev_path.with_file_name(prev_path.file_name().and_then(|n| n.to_str()).map_or_else(
-            || "llms.txt".to_string(),
-            |n| n.replace("-llms.json", "-llms.txt"),
-        ));
-    let prev_llms_text = std::fs::read_to_string(&prev_txt_path).with_context(|| {
-        format!(
-            "Failed to read previous llms.txt at {}",
-            prev_txt_path.display()
+    let now = Utc::now();
+    let (from, to) = if let Some(range) = between {
+        resolve_between(&versions, range, now)?
+    } else if let Some(expr) = since {
+        let from = resolve_since(&versions, expr, now)?.map_or(Version::Current, |index| {
+            Version::archived(&versions, index)
+        });
+        (from, Version::Current)
+    } else {
+        (
+            Version::archived(&versions, versions.len() - 1),
+            Version::Current,
         )
-    })?;
+    };
 
-    // Build maps of anchors for added/removed detection
-    let (prev_anchors, prev_map) = collect_anchors(&prev);
-    let (curr_anchors, curr_map) = collect_anchors(&current);
+    let previous = from.load(&storage, &canonical)?;
+    let current = to.load(&storage, &canonical)?;
+    let report = compare(canonical, previous, current);
 
-    // Compute moved sections via anchor mapping
-    let moved = compute_anchor_mappings(&prev.toc, &current.toc);
-    let moved_enriched: Vec<serde_json::Value> = moved
-        .into_iter()
-        .map(|m| {
-            let oldc = slice_content(&prev_llms_text, &m.old_lines);
-            let newc = slice_content(&current_text, &m.new_lines);
-
-            let display_current = curr_map
-                .get(&m.anchor)
-                .and_then(|v| v.get("headingPath"))
-                .cloned();
-            let raw_current = curr_map
-                .get(&m.anchor)
-                .and_then(|v| v.get("rawHeadingPath"))
-                .cloned();
-            let normalized_current = curr_map
-                .get(&m.anchor)
-                .and_then(|v| v.get("headingPathNormalized"))
-                .cloned();
-
-            let display_previous = prev_map
-                .get(&m.anchor)
-                .and_then(|v| v.get("headingPath"))
-                .cloned();
-            let raw_previous = prev_map
-                .get(&m.anchor)
-                .and_then(|v| v.get("rawHeadingPath"))
-                .cloned();
-            let normalized_previous = prev_map
-                .get(&m.anchor)
-                .and_then(|v| v.get("headingPathNormalized"))
-                .cloned();
-
-            let heading_path_value = display_current
-                .or(display_previous)
-                .unwrap_or_else(|| json!(m.heading_path));
-            let raw_heading_path_value = raw_current
-                .or(raw_previous)
-                .unwrap_or_else(|| json!(m.heading_path));
-            let normalized_heading_value = normalized_current
-                .or(normalized_previous)
-                .unwrap_or(serde_json::Value::Null);
-
-            json!({
-                "anchor": m.anchor,
-                "headingPath": heading_path_value,
-                "rawHeadingPath": raw_heading_path_value,
-                "headingPathNormalized": normalized_heading_value,
-                "oldLines": m.old_lines,
-                "newLines": m.new_lines,
-                "oldContent": oldc,
-                "newContent": newc,
-            })
-        })
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+        OutputFormat::Jsonl => print_jsonl(&report)?,
+        OutputFormat::Raw => println!("{}", serde_json::to_string(&report)?),
+        OutputFormat::Text | OutputFormat::Markdown => print_text_report(&report),
+    }
+    Ok(())
+}
+
+/// Build the section-level report for `previous` → `current`.
+fn compare(source: String, previous: Document, current: Document) -> DiffReport {
+    let old_sections = flatten(&previous.toc);
+    let new_sections = flatten(&current.toc);
+    let old_by_anchor: HashMap<&str, &Section> = old_sections
+        .iter()
+        .map(|s| (s.anchor.as_str(), s))
+        .collect();
+    let new_by_anchor: HashMap<&str, &Section> = new_sections
+        .iter()
+        .map(|s| (s.anchor.as_str(), s))
         .collect();
 
-    // Added: in current not in previous
-    let added = curr_anchors
-        .difference(&prev_anchors)
-        .filter_map(|a| curr_map.get(a).cloned())
-        .map(|mut v| {
-            if let Some(obj) = v.as_object_mut() {
-                if let Some(lines) = obj.get("lines").and_then(|x| x.as_str()) {
-                    obj.insert("content".into(), json!(slice_content(&current_text, lines)));
-                }
-            }
-            v
-        })
-        .collect::<Vec<_>>();
-
-    // Removed: in previous not in current
-    let removed = prev_anchors
-        .difference(&curr_anchors)
-        .filter_map(|a| prev_map.get(a).cloned())
-        .map(|mut v| {
-            if let Some(obj) = v.as_object_mut() {
-                if let Some(lines) = obj.get("lines").and_then(|x| x.as_str()) {
-                    obj.insert(
-                        "content".into(),
-                        json!(slice_content(&prev_llms_text, lines)),
-                    );
-                }
-            }
-            v
+    let added = new_sections
+        .iter()
+        .filter(|s| !old_by_anchor.contains_key(s.anchor.as_str()))
+        .map(|s| s.change(&current.lines))
+        .collect();
+    let removed = old_sections
+        .iter()
+        .filter(|s| !new_by_anchor.contains_key(s.anchor.as_str()))
+        .map(|s| s.change(&previous.lines))
+        .collect();
+
+    // Sections on both sides, in current document order
+    let common: Vec<(&Section, &Section)> = new_sections
+        .iter()
+        .filter_map(|new| Some((*old_by_anchor.get(new.anchor.as_str())?, new)))
+        .collect();
+    let in_place = stable_positions(&common.iter().map(|(old, _)| old.order).collect::<Vec<_>>());
+    let moved = common
+        .iter()
+        .zip(&in_place)
+        .filter(|((old, new), stable)| !**stable || old.parent() != new.parent())
+        .map(|(&(old, new), _)| SectionMove {
+            anchor: new.anchor.clone(),
+            heading_path: new.heading_path.clone(),
+            old_heading_path: old.heading_path.clone(),
+            old_lines: old.lines.clone(),
+            new_lines: new.lines.clone(),
         })
-        .collect::<Vec<_>>();
+        .collect();
+    let changed = common
+        .iter()
+        .filter_map(|(old, new)| edit(old, new, &previous.lines, &current.lines))
+        .collect();
 
-    // Text output by default
-    println!(
-        "Diff for {}\n  moved: {}\n  added: {}\n  removed: {}",
-        canonical,
-        moved_enriched.len(),
-        added.len(),
-        removed.len()
-    );
+    let paths: HashMap<&str, &Vec<String>> = new_sections
+        .iter()
+        .map(|s| (s.anchor.as_str(), &s.heading_path))
+        .collect();
+    let remapped = compute_anchor_mappings(&previous.toc, &current.toc)
+        .into_iter()
+        .map(|mapping| AnchorRemap {
+            heading_path: paths
+                .get(mapping.anchor.as_str())
+                .map_or(mapping.heading_path, |path| (*path).clone()),
+            anchor: mapping.anchor,
+            old_lines: mapping.old_lines,
+            new_lines: mapping.new_lines,
+        })
+        .collect();
 
-    // Also emit JSON to stdout for tooling (pretty)
-    let payload = json!({
-        "alias": alias,
-        "source": canonical,
-        "previous": {
-            "sha256": prev.metadata.sha256,
-        },
-        "current": {
-            "sha256": current.metadata.sha256,
-        },
-        "moved": moved_enriched,
-        "added": added,
-        "removed": removed,
-    });
-    println!("\n{}", serde_json::to_string_pretty(&payload)?);
+    DiffReport {
+        source,
+        previous: previous.info,
+        current: current.info,
+        added,
+        removed,
+        moved,
+        changed,
+        remapped,
+    }
+}
 
-    Ok(())
+/// A TOC entry flattened in document order.
+#[derive(Debug)]
+struct Section {
+    anchor: String,
+    heading_path: Vec<String>,
+    lines: String,
+    span: (usize, usize),
+    order: usize,
 }
 
-fn find_previous_llms_json(
-    storage: &Storage,
-    alias: &str,
-    since: Option<&str>,
-) -> Result<Option<std::path::PathBuf>> {
-    let dir = storage.archive_dir(alias)?;
-    if !dir.exists() {
-        return Ok(None);
-    }
-
-    let mut candidates = fs::read_dir(&dir)
-        .with_context(|| format!("Failed to read archive directory: {}", dir.display()))?
-        .filter_map(Result::ok)
-        .map(|e| e.path())
-        .filter(|p| p.is_file())
-        .filter(|p| {
-            p.file_name()
-                .and_then(|n| n.to_str())
-                .is_some_and(|n| n.ends_with("-llms.json"))
-        })
-        .collect::<Vec<_>>();
-
-    // Sort descending by filename
-    candidates.sort_by(|a, b| b.file_name().cmp(&a.file_name()));
-
-    if let Some(since_ts) = since {
-        // Find the first archive whose timestamp >= since
-        if let Some(p) = candidates.iter().find(|p| {
-            let needle = format!("{since_ts}-llms.json");
-            p.file_name()
-                .and_then(|n| n.to_str())
-                .is_some_and(|n| n >= needle.as_str())
-        }) {
-            return Ok(Some(p.clone()));
+impl Section {
+    fn parent(&self) -> &[String] {
+        &self.heading_path[..self.heading_path.len().saturating_sub(1)]
+    }
+
+    fn change(&self, lines: &[String]) -> SectionChange {
+        SectionChange {
+            anchor: self.anchor.clone(),
+            heading_path: self.heading_path.clone(),
+            lines: self.lines.clone(),
+            content: slice(lines, self.span),
         }
     }
+}
 
-    Ok(candidates.into_iter().next())
-}
-
-fn collect_anchors(
-    doc: &LlmsJson,
-) -> (
-    std::collections::BTreeSet<String>,
-    std::collections::HashMap<String, serde_json::Value>,
-) {
-    fn walk(
-        set: &mut std::collections::BTreeSet<String>,
-        map: &mut std::collections::HashMap<String, serde_json::Value>,
-        list: &[blz_core::TocEntry],
-    ) {
-        for e in list {
-            if let Some(a) = e.anchor.as_ref() {
-                set.insert(a.clone());
-                let display_path = sanitized_path(e);
-                map.insert(
-                    a.clone(),
-                    json!({
-                        "anchor": a,
-                        "headingPath": display_path,
-                        "rawHeadingPath": &e.heading_path,
-                        "headingPathNormalized": &e.heading_path_normalized,
-                        "lines": &e.lines,
-                    }),
-                );
-            }
-            if !e.children.is_empty() {
-                walk(set, map, &e.children);
+/// Flatten `toc` in document order, keeping the first section per anchor.
+fn flatten(toc: &[TocEntry]) -> Vec<Section> {
+    fn walk(entries: &[TocEntry], seen: &mut HashSet<String>, out: &mut Vec<Section>) {
+        for entry in entries {
+            if let (Some(anchor), Some(span)) = (&entry.anchor, span(&entry.lines)) {
+                if seen.insert(anchor.clone()) {
+                    out.push(Section {
+                        anchor: anchor.clone(),
+                        heading_path: entry
+                            .heading_path_display
+                            .clone()
+                            .unwrap_or_else(|| entry.heading_path.clone()),
+                        lines: entry.lines.clone(),
+                        span,
+                        order: out.len(),
+                    });
+                }
             }
+            walk(&entry.children, seen, out);
         }
     }
-    let mut set = std::collections::BTreeSet::new();
-    let mut map: std::collections::HashMap<String, serde_json::Value> =
-        std::collections::HashMap::new();
-    walk(&mut set, &mut map, &doc.toc);
-    (set, map)
-}
-
-fn slice_content(all: &str, lines_spec: &str) -> String {
-    let mut parts = lines_spec.split(['-', ':']);
-    let start = parts
-        .next()
-        .and_then(|s| s.trim().parse::<usize>().ok())
-        .unwrap_or(1);
-    let end = parts
-        .next()
-        .and_then(|s| s.trim().parse::<usize>().ok())
-        .unwrap_or(start);
-    let mut out = String::new();
-    for (idx, line) in all.lines().enumerate() {
-        let n = idx + 1;
-        if n < start {
-            continue;
+    let mut out = Vec::new();
+    walk(toc, &mut HashSet::new(), &mut out);
+    out
+}
+
+/// Parse a `start-end` (or single line) range.
+fn span(lines: &str) -> Option<(usize, usize)> {
+    let (start, end) = lines.split_once('-').unwrap_or((lines, lines));
+    Some((start.trim().parse().ok()?, end.trim().parse().ok()?))
+}
+
+/// Format a 1-based inclusive range like TOC entries do.
+fn format_span((start, end): (usize, usize)) -> String {
+    if end > start {
+        format!("{start}-{end}")
+    } else {
+        format!("{start}")
+    }
+}
+
+/// The text of 1-based inclusive `span`.
+fn slice(lines: &[String], (start, end): (usize, usize)) -> String {
+    let start = start.saturating_sub(1).min(lines.len());
+    let end = end.min(lines.len()).max(start);
+    lines[start..end].join("\n")
+}
+
+/// Which entries of `positions` keep their relative order.
+///
+/// `positions` holds each common section's index in the previous version,
+/// listed in current order. The longest increasing run stays in place; every
+/// other section moved.
+fn stable_positions(positions: &[usize]) -> Vec<bool> {
+    // Patience sorting: tails[k] is the index of the smallest tail of an
+    // increasing run of length k + 1
+    let mut tails: Vec<usize> = Vec::new();
+    let mut previous: Vec<Option<usize>> = vec![None; positions.len()];
+    for (i, &position) in positions.iter().enumerate() {
+        let k = tails.partition_point(|&t| positions[t] < position);
+        previous[i] = k.checked_sub(1).map(|k| tails[k]);
+        if k == tails.len() {
+            tails.push(i);
+        } else {
+            tails[k] = i;
         }
-        if n > end {
+    }
+    let mut stable = vec![false; positions.len()];
+    let mut cursor = tails.last().copied();
+    while let Some(i) = cursor {
+        stable[i] = true;
+        cursor = previous[i];
+    }
+    stable
+}
+
+/// Compare one section's text across versions; `None` if unchanged.
+fn edit(
+    old: &Section,
+    new: &Section,
+    old_lines: &[String],
+    new_lines: &[String],
+) -> Option<SectionEdit> {
+    let before = section_lines(old_lines, old.span);
+    let after = section_lines(new_lines, new.span);
+    if before == after {
+        return None;
+    }
+    // Narrow to the differing middle by trimming the shared prefix and suffix
+    let prefix = before.iter().zip(after).take_while(|(a, b)| a == b).count();
+    let suffix = before[prefix..]
+        .iter()
+        .rev()
+        .zip(after[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let changed = |section: &Section, len: usize| {
+        let start = section.span.0 + prefix;
+        let count = len - prefix - suffix;
+        (count > 0).then(|| format_span((start, start + count - 1)))
+    };
+    Some(SectionEdit {
+        anchor: new.anchor.clone(),
+        heading_path: new.heading_path.clone(),
+        old_lines: old.lines.clone(),
+        new_lines: new.lines.clone(),
+        changed_old_lines: changed(old, before.len()),
+        changed_new_lines: changed(new, after.len()),
+        old_content: before[prefix..before.len() - suffix].join("\n"),
+        new_content: after[prefix..after.len() - suffix].join("\n"),
+    })
+}
+
+/// The lines of `span`, without the blank lines that separate it from the next heading.
+fn section_lines(lines: &[String], (start, end): (usize, usize)) -> &[String] {
+    let start = start.saturating_sub(1).min(lines.len());
+    let mut section = &lines[start..end.min(lines.len()).max(start)];
+    while let [rest @ .., last] = section {
+        if !last.trim().is_empty() {
             break;
         }
-        if !out.is_empty() {
-            out.push('\n');
+        section = rest;
+    }
+    section
+}
+
+/// One JSON object per change, tagged with its `kind`.
+fn print_jsonl(report: &DiffReport) -> Result<()> {
+    fn emit(kind: &str, item: &impl Serialize) -> Result<()> {
+        let mut value = serde_json::to_value(item)?;
+        if let Some(object) = value.as_object_mut() {
+            object.insert("kind".to_string(), kind.into());
         }
-        out.push_str(line);
+        println!("{}", serde_json::to_string(&value)?);
+        Ok(())
     }
-    out
+    for item in &report.added {
+        emit("added", item)?;
+    }
+    for item in &report.removed {
+        emit("removed", item)?;
+    }
+    for item in &report.moved {
+        emit("moved", item)?;
+    }
+    for item in &report.changed {
+        emit("changed", item)?;
+    }
+    for item in &report.remapped {
+        emit("remapped", item)?;
+    }
+    Ok(())
+}
+
+fn print_text_report(report: &DiffReport) {
+    println!(
+        "Diff for {} ({} → {})",
+        report.source.bold(),
+        report.previous.snapshot,
+        report.current.snapshot
+    );
+    if report.is_empty() {
+        println!("No changes.");
+        return;
+    }
+    for section in &report.added {
+        println!(
+            "  {} {} (lines {})",
+            "+".green(),
+            section.heading_path.join(" > "),
+            section.lines
+        );
+    }
+    for section in &report.removed {
+        println!(
+            "  {} {} (was lines {})",
+            "-".red(),
+            section.heading_path.join(" > "),
+            section.lines
+        );
+    }
+    for section in &report.moved {
+        let from = if section.old_heading_path == section.heading_path {
+            String::new()
+        } else {
+            format!(", was {}", section.old_heading_path.join(" > "))
+        };
+        println!(
+            "  {} {} moved (lines {} → {}{from})",
+            "↕".cyan(),
+            section.heading_path.join(" > "),
+            section.old_lines,
+            section.new_lines
+        );
+    }
+    for section in &report.changed {
+        let lines = section.changed_new_lines.as_deref().map_or_else(
+            || format!("deleted in {}", section.new_lines),
+            |l| format!("lines {l}"),
+        );
+        println!(
+            "  {} {} ({lines})",
+            "~".yellow(),
+            section.heading_path.join(" > ")
+        );
+    }
+    println!(
+        "\nSummary: {} added, {} removed, {} moved, {} changed, {} remapped anchors",
+        report.added.len(),
+        report.removed.len(),
+        report.moved.len(),
+        report.changed.len(),
+        report.remapped.len()
+    );
+}
+
+/// Whether `expr` names the version archived by the most recent sync.
+fn is_last_sync(expr: &str) -> bool {
+    matches!(
+        expr.trim().to_ascii_lowercase().as_str(),
+        "last sync" | "last-sync" | "last"
+    )
 }
 
-fn sanitized_path(entry: &blz_core::TocEntry) -> Vec<String> {
-    entry
-        .heading_path_display
-        .clone()
-        .unwrap_or_else(|| entry.heading_path.clone())
+/// Index of the version whose content SHA is `reference` (base64, or a hex prefix).
+fn find_by_sha(versions: &[ContentVersion], reference: &str) -> Result<Option<usize>> {
+    let hex = reference.to_ascii_lowercase();
+    let is_prefix = hex.len() >= MIN_SHA_PREFIX && hex.bytes().all(|b| b.is_ascii_hexdigit());
+    let matches: Vec<usize> = versions
+        .iter()
+        .enumerate()
+        .filter(|(_, v)| v.sha256 == reference || (is_prefix && v.id.starts_with(&hex)))
+        .map(|(i, _)| i)
+        .collect();
+    let distinct: HashSet<&str> = matches.iter().map(|&i| versions[i].id.as_str()).collect();
+    if distinct.len() > 1 {
+        bail!("content SHA '{reference}' is ambiguous; use more characters");
+    }
+    // The same content may have been archived more than once; take the latest
+    Ok(matches.last().copied())
+}
+
+/// Find the version whose content was live at the time `expr` names.
+///
+/// Each version holds the content that was current until it was archived, so
+/// this is the oldest version archived at or after that time. `Ok(None)`
+/// means nothing was archived since, i.e. the current content was already live.
+fn resolve_since(
+    versions: &[ContentVersion],
+    expr: &str,
+    now: DateTime<Utc>,
+) -> Result<Option<usize>> {
+    if is_last_sync(expr) {
+        return Ok(versions.len().checked_sub(1));
+    }
+    if let Some(index) = find_by_sha(versions, expr.trim())? {
+        return Ok(Some(index));
+    }
+    let at = parse_time_expr(expr, now).map_err(anyhow::Error::msg)?;
+    Ok(versions.iter().position(|v| v.archived_at >= at))
+}
+
+/// Resolve one side of `--between`: `vN` (1 = oldest version), `current`,
+/// `last sync`, a content SHA, or a time expression.
+fn resolve_version(
+    versions: &[ContentVersion],
+    reference: &str,
+    now: DateTime<Utc>,
+) -> Result<Version> {
+    let reference = reference.trim();
+    if reference.is_empty() || reference.eq_ignore_ascii_case("current") {
+        return Ok(Version::Current);
+    }
+    if let Some(n) = reference
+        .strip_prefix(['v', 'V'])
+        .and_then(|n| n.parse::<usize>().ok())
+    {
+        return match n.checked_sub(1).filter(|&i| i < versions.len()) {
+            Some(index) => Ok(Version::archived(versions, index)),
+            None => bail!(
+                "version '{reference}' does not exist ({} archived: v1..v{})",
+                versions.len(),
+                versions.len()
+            ),
+        };
+    }
+    Ok(resolve_since(versions, reference, now)?
+        .map_or(Version::Current, |index| Version::archived(versions, index)))
+}
+
+/// Resolve `--between FROM..TO`; an empty `TO` means the current cache.
+fn resolve_between(
+    versions: &[ContentVersion],
+    range: &str,
+    now: DateTime<Utc>,
+) -> Result<(Version, Version)> {
+    let Some((from, to)) = range.split_once("..") else {
+        bail!("invalid range '{range}' (expected FROM..TO, e.g. v1..v2 or 2w..current)");
+    };
+    if from.trim().is_empty() {
+        bail!("invalid range '{range}': FROM must name a version");
+    }
+    Ok((
+        resolve_version(versions, from, now)?,
+        resolve_version(versions, to, now)?,
+    ))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn version(hex: &str, rfc3339: &str) -> ContentVersion {
+        ContentVersion {
+            id: hex.repeat(64 / hex.len()),
+            sha256: format!("{hex}-b64"),
+            fetched_at: None,
+            archived_at: DateTime::parse_from_rfc3339(rfc3339)
+                .unwrap()
+                .with_timezone(&Utc),
+            lines: 1,
+        }
+    }
+
+    fn versions() -> Vec<ContentVersion> {
+        vec![
+            version("a1", "2025-05-01T00:00:00Z"),
+            version("b2", "2025-06-01T00:00:00Z"),
+            version("c3", "2025-06-10T00:00:00Z"),
+        ]
+    }
+
+    fn now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2025-06-15T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn document(text: &str) -> Document {
+        Document {
+            info: VersionInfo {
+                snapshot: "test".to_string(),
+                sha256: None,
+                archived_at: None,
+            },
+            lines: text.lines().map(str::to_string).collect(),
+            toc: MarkdownParser::new().unwrap().parse(text).unwrap().toc,
+        }
+    }
+
+    #[test]
+    fn since_picks_the_version_live_at_that_time() {
+        let versions = versions();
+        assert_eq!(resolve_since(&versions, "1w", now()).unwrap(), Some(2));
+        assert_eq!(resolve_since(&versions, "2w", now()).unwrap(), Some(1));
+        assert_eq!(
+            resolve_since(&versions, "50 days ago", now()).unwrap(),
+            Some(0)
+        );
+        assert_eq!(resolve_since(&versions, "2d", now()).unwrap(), None);
+    }
+
+    #[test]
+    fn since_accepts_last_sync_and_content_shas() {
+        let versions = versions();
+        assert_eq!(
+            resolve_since(&versions, "last sync", now()).unwrap(),
+            Some(2)
+        );
+        assert_eq!(resolve_since(&versions, "b2b2", now()).unwrap(), Some(1));
+        assert_eq!(resolve_since(&versions, "a1-b64", now()).unwrap(), Some(0));
+        assert!(resolve_since(&versions, "whenever", now()).is_err());
+    }
+
+    #[test]
+    fn between_resolves_numbered_versions_and_current() {
+        let versions = versions();
+        let (from, to) = resolve_between(&versions, "v1..v2", now()).unwrap();
+        assert_eq!(from, Version::archived(&versions, 0));
+        assert_eq!(to, Version::archived(&versions, 1));
+
+        let (from, to) = resolve_between(&versions, "v3..", now()).unwrap();
+        assert_eq!(from.label(), "v3");
+        assert_eq!(to, Version::Current);
+
+        let err = resolve_between(&versions, "v1..v9", now()).unwrap_err();
+        assert!(err.to_string().contains("v1..v3"));
+        assert!(resolve_between(&versions, "v1", now()).is_err());
+    }
+
+    #[test]
+    fn stable_positions_keep_the_longest_ordered_run() {
+        assert_eq!(stable_positions(&[0, 1, 2]), vec![true, true, true]);
+        assert_eq!(stable_positions(&[2, 0, 1]), vec![false, true, true]);
+        assert_eq!(stable_positions(&[]), Vec::<bool>::new());
+    }
+
+    #[test]
+    fn compare_reports_structural_changes() {
+        let previous =
+            document("# Guide\n\n## Install\nRun it.\n\n## Legacy\nOld.\n\n## Usage\nCall it.\n");
+        let current = document(
+            "# Guide\n\n## Usage\nCall it.\n\n## Install\nRun it.\nThen verify.\n\n## Plugins\nExtend.\n",
+        );
+        let report = compare("guide".to_string(), previous, current);
+
+        let titles = |sections: &[SectionChange]| -> Vec<String> {
+            sections
+                .iter()
+                .map(|s| s.heading_path.join(" > "))
+                .collect()
+        };
+        assert_eq!(titles(&report.added), vec!["Guide > Plugins"]);
+        assert_eq!(titles(&report.removed), vec!["Guide > Legacy"]);
+        assert_eq!(report.removed[0].content, "## Legacy\nOld.\n");
+
+        assert_eq!(report.moved.len(), 1);
+        assert_eq!(report.moved[0].heading_path, vec!["Guide", "Usage"]);
+
+        assert_eq!(report.changed.len(), 1);
+        let install = &report.changed[0];
+        assert_eq!(install.heading_path, vec!["Guide", "Install"]);
+        assert_eq!(install.changed_new_lines.as_deref(), Some("8"));
+        assert_eq!(install.new_content, "Then verify.");
+
+        assert!(
+            report
+                .remapped
+                .iter()
+                .any(|remap| remap.heading_path == ["Guide", "Install"])
+        );
+    }
 }
@@ -0,0 +1,343 @@
+//! Discover command: propose sources for a project's dependencies.
+//!
+//! `blz discover --from package.json` (or `Cargo.toml`, `pyproject.toml`)
+//! reads the manifest's dependencies and resolves each to a documentation
+//! source: a registry entry, the crate's API docs on docs.rs, or an llms.txt
+//! found by probing the package's likely docs domains. The result is a batch
+//! add plan, printed by default, installed with `--add`, or written with
+//! `--write` as a manifest for `blz add --manifest`.
+
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use blz_core::discovery::ProbeTargets;
+use blz_core::discovery::ecosystem::{
+    Dependency, ResolvedBy, manifest_dependencies, project_dependencies, resolve_dependency,
+};
+use blz_core::{Config, PerformanceMetrics, Registry, Storage};
+use clap::Args;
+use colored::Colorize;
+use futures::StreamExt as _;
+use indicatif::{ProgressBar, ProgressStyle};
+use serde::Serialize;
+
+use super::add::{AddFlowOptions, DescriptorInput, ManifestEntry, ManifestFile, add_location};
+use crate::output::OutputFormat;
+use crate::utils::cli_args::FormatArg;
+use crate::utils::validation::normalize_alias;
+
+/// Candidate domains probed at the same time.
+const PROBE_CONCURRENCY: usize = 8;
+/// Manifest schema version understood by `blz add --manifest`.
+const MANIFEST_VERSION: &str = "1";
+
+/// Arguments for `blz discover`.
+#[derive(Args, Clone, Debug)]
+pub struct DiscoverArgs {
+    /// Manifest to read: `package.json`, `Cargo.toml`, or `pyproject.toml`
+    /// (repeatable; defaults to each one in the current directory)
+    #[arg(long = "from", value_name = "FILE")]
+    pub from: Vec<PathBuf>,
+
+    /// Only use the registry and docs.rs; skip probing docs domains
+    #[arg(long)]
+    pub no_probe: bool,
+
+    /// Probe docs domains even where robots.txt disallows it
+    #[arg(long, conflicts_with = "no_probe")]
+    pub ignore_robots: bool,
+
+    /// Add every planned source that is not installed yet
+    #[arg(long, conflicts_with = "write")]
+    pub add: bool,
+
+    /// Write the plan as a manifest for `blz add --manifest`
+    #[arg(long, value_name = "FILE")]
+    pub write: Option<PathBuf>,
+
+    /// Output format
+    #[command(flatten)]
+    pub format: FormatArg,
+}
+
+/// One planned `blz add`.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PlannedSource {
+    alias: String,
+    location: String,
+    dependency: String,
+    manifest: &'static str,
+    resolved_by: String,
+    installed: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DiscoverReport {
+    dependencies: usize,
+    sources: Vec<PlannedSource>,
+    unresolved: Vec<String>,
+}
+
+/// Execute `blz discover`.
+///
+/// # Errors
+///
+/// Returns an error if a manifest cannot be read, no dependencies are found,
+/// the `--write` target exists, or every `--add` fails.
+pub async fn execute(args: &DiscoverArgs, quiet: bool, metrics: PerformanceMetrics) -> Result<()> {
+    let format = args.format.resolve(quiet);
+    let dependencies = read_dependencies(&args.from)?;
+    if dependencies.is_empty() {
+        bail!(
+            "No dependencies found. Pass --from with a package.json, Cargo.toml, or pyproject.toml."
+        );
+    }
+
+    let probe = (!args.no_probe).then(|| {
+        let probe_paths = Config::load()
+            .map(|config| config.defaults.probe_paths)
+            .unwrap_or_default();
+        ProbeTargets::from_config(&probe_paths).ignoring_robots(args.ignore_robots)
+    });
+    let show_progress = !quiet && matches!(format, OutputFormat::Text);
+    let report = plan(&dependencies, probe.as_ref(), show_progress).await?;
+
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+        OutputFormat::Jsonl | OutputFormat::Raw => {
+            println!("{}", serde_json::to_string(&report)?);
+        },
+        OutputFormat::Text | OutputFormat::Markdown => {
+            if !quiet {
+                print_plan(&report, args);
+            }
+        },
+    }
+
+    if let Some(path) = &args.write {
+        write_manifest(path, &report)?;
+        if !quiet {
+            println!(
+                "Wrote {}; install with: blz add --manifest {}",
+                path.display(),
+                path.display()
+            );
+        }
+    }
+    if args.add {
+        add_planned(&report, quiet, metrics).await?;
+    }
+    Ok(())
+}
+
+/// Dependencies from the `--from` manifests, or every manifest in the
+/// current directory.
+fn read_dependencies(from: &[PathBuf]) -> Result<Vec<Dependency>> {
+    let mut dependencies = if from.is_empty() {
+        let dir = std::env::current_dir().context("Failed to read the current directory")?;
+        project_dependencies(&dir)
+    } else {
+        let mut dependencies = Vec::new();
+        for path in from {
+            dependencies.extend(
+                manifest_dependencies(path)
+                    .with_context(|| format!("Failed to read {}", path.display()))?,
+            );
+        }
+        dependencies
+    };
+    dependencies.retain(|dependency| !dependency.is_typings());
+    Ok(dependencies)
+}
+
+/// Resolve every dependency, keeping the first dependency per alias.
+async fn plan(
+    dependencies: &[Dependency],
+    probe: Option<&ProbeTargets>,
+    show_progress: bool,
+) -> Result<DiscoverReport> {
+    let registry = Registry::new();
+    let storage = Storage::new()?;
+
+    let pb = if show_progress {
+        let pb = ProgressBar::new(dependencies.len() as u64);
+        pb.set_style(
+            ProgressStyle::default_bar()
+                .template("[{bar:40.cyan/blue}] {pos}/{len} {msg}")
+                .unwrap_or_else(|_| ProgressStyle::default_bar())
+                .progress_chars("=>-"),
+        );
+        pb.set_message("Resolving dependencies...");
+        pb
+    } else {
+        ProgressBar::hidden()
+    };
+    let resolved: Vec<_> = futures::stream::iter(dependencies)
+        .map(|dependency| {
+            let registry = &registry;
+            let pb = &pb;
+            async move {
+                let resolution = resolve_dependency(dependency, registry, probe).await;
+                pb.inc(1);
+                (dependency, resolution)
+            }
+        })
+        .buffered(PROBE_CONCURRENCY)
+        .collect()
+        .await;
+    pb.finish_and_clear();
+
+    let mut sources: Vec<PlannedSource> = Vec::new();
+    let mut unresolved = Vec::new();
+    for (dependency, resolution) in resolved {
+        let Some(resolution) = resolution? else {
+            unresolved.push(dependency.name.clone());
+            continue;
+        };
+        let alias = normalize_alias(&resolution.alias);
+        if sources.iter().any(|source| source.alias == alias) {
+            continue;
+        }
+        let resolved_by = match &resolution.resolved_by {
+            ResolvedBy::Registry => "registry".to_string(),
+            ResolvedBy::DocsRs => "docs.rs".to_string(),
+            ResolvedBy::Probe { domain } => format!("probed {domain}"),
+        };
+        sources.push(PlannedSource {
+            installed: storage.exists(&alias),
+            alias,
+            location: resolution.location,
+            dependency: dependency.name.clone(),
+            manifest: dependency.ecosystem.manifest_name(),
+            resolved_by,
+        });
+    }
+
+    Ok(DiscoverReport {
+        dependencies: dependencies.len(),
+        sources,
+        unresolved,
+    })
+}
+
+fn print_plan(report: &DiscoverReport, args: &DiscoverArgs) {
+    println!(
+        "Found {} source(s) for {} dependencies:",
+        report.sources.len(),
+        report.dependencies
+    );
+    let width = report
+        .sources
+        .iter()
+        .map(|source| source.alias.len())
+        .max()
+        .unwrap_or(0);
+    for source in &report.sources {
+        let installed = if source.installed {
+            format!(" {}", "[installed]".green())
+        } else {
+            String::new()
+        };
+        println!(
+            "  {:width$}  {}  {}{installed}",
+            source.alias,
+            source.location.cyan(),
+            format!(
+                "({} in {}, {})",
+                source.dependency, source.manifest, source.resolved_by
+            )
+            .bright_black(),
+        );
+    }
+    if !report.unresolved.is_empty() {
+        println!(
+            "{} without known docs: {}",
+            report.unresolved.len(),
+            report.unresolved.join(", ").bright_black()
+        );
+    }
+
+    let pending = report.sources.iter().filter(|s| !s.installed).count();
+    if pending > 0 && !args.add && args.write.is_none() {
+        let from = args.from.iter().fold(String::new(), |mut from, path| {
+            let _ = write!(from, " --from {}", path.display());
+            from
+        });
+        println!();
+        println!("Add the {pending} new source(s) with: blz discover{from} --add");
+        println!("or save them as a manifest with:    blz discover{from} --write blz.sources.toml");
+    }
+}
+
+fn write_manifest(path: &Path, report: &DiscoverReport) -> Result<()> {
+    if path.exists() {
+        bail!(
+            "{} already exists; choose another --write path",
+            path.display()
+        );
+    }
+    let manifest = ManifestFile {
+        version: Some(MANIFEST_VERSION.to_string()),
+        sources: report
+            .sources
+            .iter()
+            .map(|source| ManifestEntry {
+                alias: source.alias.clone(),
+                url: Some(source.location.clone()),
+                ..ManifestEntry::default()
+            })
+            .collect(),
+    };
+    let text = format!(
+        "# Documentation sources for this project's dependencies.\n# Install them with: blz add --manifest {}\n\n{}",
+        path.display(),
+        toml::to_string(&manifest).context("Failed to serialize manifest")?
+    );
+    std::fs::write(path, text).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Add each planned source that is not installed, continuing past failures.
+async fn add_planned(
+    report: &DiscoverReport,
+    quiet: bool,
+    metrics: PerformanceMetrics,
+) -> Result<()> {
+    let pending: Vec<&PlannedSource> = report.sources.iter().filter(|s| !s.installed).collect();
+    if pending.is_empty() {
+        if !quiet {
+            println!("Every planned source is already installed.");
+        }
+        return Ok(());
+    }
+
+    let mut failed = 0;
+    for source in &pending {
+        let result = add_location(
+            &source.alias,
+            &source.location,
+            DescriptorInput::default(),
+            None,
+            metrics.clone(),
+            AddFlowOptions::new(false, quiet, false),
+        )
+        .await;
+        if let Err(err) = result {
+            failed += 1;
+            eprintln!("{} {}: {err:#}", "✗".red(), source.alias);
+        }
+    }
+    if failed == pending.len() {
+        bail!("Every planned source failed to add");
+    }
+    if failed > 0 && !quiet {
+        println!(
+            "Added {} of {} source(s)",
+            pending.len() - failed,
+            pending.len()
+        );
+    }
+    Ok(())
+}
@@ -138,35 +138,33 @@ async fn docs_search(args: DocsSearchArgs, quiet: bool, metrics: PerformanceMetr
 
     // Check if the bundled docs contain placeholder content
     let storage = Storage::new()?;
-    if let Ok(content_path) = storage.llms_txt_path(BUNDLED_ALIAS) {
-        if let Ok(content) = std::fs::read_to_string(&content_path) {
-            if content.contains("# BLZ bundled docs (placeholder)") {
-                let error_msg = if matches!(format, OutputFormat::Json) {
-                    // JSON output: structured error message
-                    let error_json = serde_json::json!({
-                        "error": "Bundled documentation content not yet available",
-                        "reason": "The blz-docs source currently contains placeholder content",
-                        "suggestions": [
-                            "Use 'blz docs overview' for quick-start information",
-                            "Use 'blz docs export' to view CLI documentation",
-                            "Full bundled documentation will be included in a future release"
-                        ]
-                    });
-                    return Err(anyhow::anyhow!(serde_json::to_string_pretty(&error_json)?));
-                } else {
-                    // Text output: user-friendly message
-                    "Bundled documentation content not yet available.\n\
-                     \n\
-                     The blz-docs source currently contains placeholder content.\n\
-                     Full documentation will be included in a future release.\n\
-                     \n\
-                     Available alternatives:\n\
-                     • Run 'blz docs overview' for quick-start information\n\
-                     • Run 'blz docs export' to view CLI documentation\n\
-                     • Run 'blz docs cat' to view the current placeholder content"
-                };
-                anyhow::bail!("{error_msg}");
-            }
+    if let Ok(content) = storage.load_llms_txt(BUNDLED_ALIAS) {
+        if content.contains("# BLZ bundled docs (placeholder)") {
+            let error_msg = if matches!(format, OutputFormat::Json) {
+                // JSON output: structured error message
+                let error_json = serde_json::json!({
+                    "error": "Bundled documentation content not yet available",
+                    "reason": "The blz-docs source currently contains placeholder content",
+                    "suggestions": [
+                        "Use 'blz docs overview' for quick-start information",
+                        "Use 'blz docs export' to view CLI documentation",
+                        "Full bundled documentation will be included in a future release"
+                    ]
+                });
+                return Err(anyhow::anyhow!(serde_json::to_string_pretty(&error_json)?));
+            } else {
+                // Text output: user-friendly message
+                "Bundled documentation content not yet available.\n\
+                 \n\
+                 The blz-docs source currently contains placeholder content.\n\
+                 Full documentation will be included in a future release.\n\
+                 \n\
+                 Available alternatives:\n\
+                 • Run 'blz docs overview' for quick-start information\n\
+                 • Run 'blz docs export' to view CLI documentation\n\
+                 • Run 'blz docs cat' to view the current placeholder content"
+            };
+            anyhow::bail!("{error_msg}");
         }
     }
 
@@ -220,7 +218,9 @@ fn docs_sync(force: bool, quiet: bool, metrics: PerformanceMetrics) -> Result<()
     let status = sync_and_report(force, quiet, metrics)?;
     if !quiet && matches!(status, DocsSyncStatus::Installed | DocsSyncStatus::Updated) {
         let storage = Storage::new()?;
-        let llms_path = storage.llms_txt_path(BUNDLED_ALIAS)?;
+        let llms_path = storage
+            .stored_llms_txt_path(BUNDLED_ALIAS)?
+            .unwrap_or(storage.llms_txt_path(BUNDLED_ALIAS)?);
         println!("Bundled docs stored at {}", llms_path.display());
     }
     Ok(())
@@ -230,7 +230,9 @@ fn docs_overview(quiet: bool, metrics: PerformanceMetrics) -> Result<()> {
     let status = sync_and_report(false, quiet, metrics)?;
     if !quiet {
         let storage = Storage::new()?;
-        let llms_path = storage.llms_txt_path(BUNDLED_ALIAS)?;
+        let llms_path = storage
+            .stored_llms_txt_path(BUNDLED_ALIAS)?
+            .unwrap_or(storage.llms_txt_path(BUNDLED_ALIAS)?);
         println!("Bundled docs status: {status:?}");
         println!("Alias: {BUNDLED_ALIAS} (also @blz)");
         println!("Stored at: {}", llms_path.display());
@@ -94,8 +94,62 @@ pub fn sync(force: bool, metrics: PerformanceMetrics) -> Result<SyncStatus> {
 }
 
 fn install(storage: &Storage, metrics: PerformanceMetrics) -> Result<bool> {
-    let alias = BUNDLED_ALIAS;
-    let content = BUNDLED_CONTENT;
+    install_embedded(
+        storage,
+        &EmbeddedSource {
+            alias: BUNDLED_ALIAS,
+            name: "BLZ Bundled Docs",
+            url: BUNDLED_URL,
+            content: BUNDLED_CONTENT,
+            sha256: &BUNDLED_SHA256,
+            variant: SourceVariant::LlmsFull,
+            aliases: BUNDLED_ALIASES,
+            tags: BUNDLED_TAGS,
+            description: BUNDLED_DESCRIPTION,
+            category: BUNDLED_CATEGORY,
+        },
+        metrics,
+    )
+}
+
+/// Content compiled into the binary that can be installed as a source.
+pub struct EmbeddedSource<'a> {
+    /// Canonical alias the source is stored under.
+    pub alias: &'a str,
+    /// Display name recorded in the descriptor.
+    pub name: &'a str,
+    /// Synthetic URL persisted into metadata.
+    pub url: &'a str,
+    /// The llms.txt content.
+    pub content: &'a str,
+    /// SHA-256 hash (base64) of `content`.
+    pub sha256: &'a str,
+    /// Variant recorded in metadata.
+    pub variant: SourceVariant,
+    /// Additional aliases.
+    pub aliases: &'a [&'a str],
+    /// Tags applied to the source.
+    pub tags: &'a [&'a str],
+    /// Human-readable description.
+    pub description: &'a str,
+    /// Descriptor category.
+    pub category: &'a str,
+}
+
+/// Persist and index an embedded source, replacing any previous copy.
+///
+/// Returns whether the source already existed.
+///
+/// # Errors
+///
+/// Returns an error if storage access, parsing, or indexing fails.
+pub fn install_embedded(
+    storage: &Storage,
+    source: &EmbeddedSource<'_>,
+    metrics: PerformanceMetrics,
+) -> Result<bool> {
+    let alias = source.alias;
+    let content = source.content;
     let previously_exists = storage.exists(alias);
 
     let mut parser = MarkdownParser::new()?;
@@ -109,25 +163,27 @@ fn install(storage: &Storage, metrics: PerformanceMetrics) -> Result<bool> {
 
     let mut llms_json = build_llms_json(
         alias,
-        BUNDLED_URL,
+        source.url,
         "llms.txt",
-        BUNDLED_SHA256.clone(),
+        source.sha256.to_string(),
         None,
         None,
         &parse,
     );
 
-    llms_json.metadata.variant = SourceVariant::LlmsFull;
-    llms_json.metadata.aliases = BUNDLED_ALIASES
+    llms_json.metadata.variant = source.variant.clone();
+    llms_json.metadata.aliases = source
+        .aliases
         .iter()
         .map(std::string::ToString::to_string)
         .collect();
-    llms_json.metadata.tags = BUNDLED_TAGS
+    llms_json.metadata.tags = source
+        .tags
         .iter()
         .map(std::string::ToString::to_string)
         .collect();
-    llms_json.metadata.description = Some(BUNDLED_DESCRIPTION.to_string());
-    llms_json.metadata.category = Some(BUNDLED_CATEGORY.to_string());
+    llms_json.metadata.description = Some(source.description.to_string());
+    llms_json.metadata.category = Some(source.category.to_string());
     llms_json.metadata.origin = SourceOrigin {
         manifest: None,
         source_type: Some(SourceType::LocalFile {
@@ -140,12 +196,12 @@ fn install(storage: &Storage, metrics: PerformanceMetrics) -> Result<bool> {
         .context("failed to persist embedded llms.json")?;
 
     let source_metadata = Source {
-        url: BUNDLED_URL.to_string(),
+        url: source.url.to_string(),
         etag: None,
         last_modified: None,
         fetched_at: Utc::now(),
-        sha256: BUNDLED_SHA256.clone(),
-        variant: SourceVariant::LlmsFull,
+        sha256: source.sha256.to_string(),
+        variant: source.variant.clone(),
         aliases: llms_json.metadata.aliases.clone(),
         tags: llms_json.metadata.tags.clone(),
         description: llms_json.metadata.description.clone(),
@@ -162,11 +218,11 @@ fn install(storage: &Storage, metrics: PerformanceMetrics) -> Result<bool> {
 
     let descriptor = SourceDescriptor {
         alias: alias.to_string(),
-        name: Some("BLZ Bundled Docs".to_string()),
-        description: Some(BUNDLED_DESCRIPTION.to_string()),
-        category: Some(BUNDLED_CATEGORY.to_string()),
+        name: Some(source.name.to_string()),
+        description: Some(source.description.to_string()),
+        category: Some(source.category.to_string()),
         tags: llms_json.metadata.tags.clone(),
-        url: Some(BUNDLED_URL.to_string()),
+        url: Some(source.url.to_string()),
         path: Some(llms_path),
         aliases: llms_json.metadata.aliases.clone(),
         npm_aliases: Vec::new(),
@@ -1,14 +1,24 @@
 //! Health check command - comprehensive cache and source diagnostics
 
 use anyhow::Result;
-use blz_core::{CacheInfo, HealthCheck, HealthStatus, SourceHealth, SourceKind, Storage};
+use blz_core::gc;
+use blz_core::mapping::carry_section_ids;
+use blz_core::numeric::format_bytes;
+use blz_core::refresh::{DefaultRefreshIndexer, reindex_source};
+use blz_core::{
+    CacheInfo, HealthCheck, HealthStatus, MarkdownParser, SearchIndex, SourceHealth, SourceKind,
+    Storage,
+};
 use colored::Colorize;
 use serde::Serialize;
 use std::path::{Path, PathBuf};
 
+use crate::commands::gc::configured_options as gc_options;
 use crate::commands::sync::generated::{is_generated_source, load_generate_manifest};
 use crate::output::OutputFormat;
+use crate::utils::history_log;
 use crate::utils::staleness::{self, DEFAULT_STALE_AFTER_DAYS};
+use crate::utils::store::{self, FileIntegrity};
 
 // ============================================================
 // CLI-specific Health Report (uses CLI's SourceHealthEntry)
@@ -33,6 +43,43 @@ pub struct HealthReport {
     /// Per-source health entries (includes generated source details).
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub source_entries: Vec<SourceHealthEntry>,
+    /// Damaged indices and stored data that `--fix` can rebuild.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub data_issues: Vec<DataIssue>,
+}
+
+/// Damage to a source's stored data, repairable from its cached `llms.txt`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DataIssue {
+    /// Source alias.
+    pub alias: String,
+    /// What is wrong.
+    pub kind: DataIssueKind,
+    /// Human-readable details.
+    pub detail: String,
+}
+
+/// Kinds of [`DataIssue`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DataIssueKind {
+    /// The `.index` directory is missing.
+    MissingIndex,
+    /// The index fails to open, has bad checksums, or stale NFS handles.
+    CorruptedIndex,
+    /// `llms.json` records a different line count than `llms.txt` has.
+    LineCountMismatch,
+    /// `metadata.json` is missing.
+    MissingMetadata,
+    /// A sync journal was left by a sync that never finished.
+    InterruptedSync,
+}
+
+impl DataIssueKind {
+    const fn affects_index(self) -> bool {
+        matches!(self, Self::MissingIndex | Self::CorruptedIndex)
+    }
 }
 
 // ============================================================
@@ -171,7 +218,7 @@ pub async fn execute(format: OutputFormat, fix: bool) -> Result<()> {
                 println!("{json}");
             }
         },
-        OutputFormat::Text | OutputFormat::Raw => {
+        OutputFormat::Text | OutputFormat::Markdown | OutputFormat::Raw => {
             print_text_report(&report, fix);
         },
     }
@@ -206,10 +253,26 @@ fn run_health_checks(storage: &Storage) -> Result<HealthReport> {
     checks.push(sources_check);
     recommendations.extend(source_recommendations);
 
-    let (index_check, index_recommendation) = index_health_check(storage, &sources);
+    // Shared sources are read-only here, so their issues can't be fixed locally
+    let data_issues: Vec<DataIssue> = sources
+        .iter()
+        .filter(|alias| !storage.is_shared(alias))
+        .flat_map(|alias| inspect_source_data(storage, alias))
+        .collect();
+    let (index_check, index_recommendation) = index_health_check(sources.len(), &data_issues);
     checks.push(index_check);
-    if let Some(rec) = index_recommendation {
-        recommendations.push(rec);
+    recommendations.extend(index_recommendation);
+    let (consistency_check, consistency_recommendation) = consistency_check(&data_issues);
+    checks.push(consistency_check);
+    recommendations.extend(consistency_recommendation);
+
+    let (gc_check, gc_recommendation) = garbage_check(storage);
+    checks.push(gc_check);
+    recommendations.extend(gc_recommendation);
+
+    for (check, recommendation) in state_file_checks() {
+        checks.push(check);
+        recommendations.extend(recommendation);
     }
 
     // Collect individual source health entries
@@ -235,6 +298,7 @@ fn run_health_checks(storage: &Storage) -> Result<HealthReport> {
         },
         source_health,
         source_entries,
+        data_issues,
     })
 }
 
@@ -300,10 +364,8 @@ fn source_health_check(
                 stale_sources.push(alias.clone());
             }
 
-            if let Ok(llms_path) = storage.llms_txt_path(alias) {
-                if !llms_path.exists() {
-                    corrupted_count += 1;
-                }
+            if matches!(storage.stored_llms_txt_path(alias), Ok(None)) {
+                corrupted_count += 1;
             }
         }
     }
@@ -350,35 +412,197 @@ fn source_health_check(
     Ok((check, recommendations, health))
 }
 
-fn index_health_check(storage: &Storage, aliases: &[String]) -> (HealthCheck, Option<String>) {
-    let mut missing_indices = 0usize;
-    for alias in aliases {
-        if let Ok(index_dir) = storage.index_dir(alias) {
-            if !index_dir.exists() {
-                missing_indices += 1;
-            }
+/// Find damaged indices and stored data for one source.
+fn inspect_source_data(storage: &Storage, alias: &str) -> Vec<DataIssue> {
+    let mut issues = Vec::new();
+    let issue = |kind, detail: String| DataIssue {
+        alias: alias.to_string(),
+        kind,
+        detail,
+    };
+
+    if let Ok(index_dir) = storage.index_dir(alias) {
+        if !index_dir.exists() {
+            issues.push(issue(
+                DataIssueKind::MissingIndex,
+                "No search index".to_string(),
+            ));
+        } else if let Err(e) = SearchIndex::verify(&index_dir) {
+            issues.push(issue(DataIssueKind::CorruptedIndex, e.to_string()));
         }
     }
 
-    let status = if missing_indices == 0 {
-        HealthStatus::Healthy
-    } else {
+    if let (Ok(llms_json), Ok(content)) =
+        (storage.load_llms_json(alias), storage.load_llms_txt(alias))
+    {
+        let recorded = llms_json.line_index.total_lines;
+        let actual = content.lines().count();
+        if recorded != actual {
+            issues.push(issue(
+                DataIssueKind::LineCountMismatch,
+                format!("llms.json records {recorded} lines but llms.txt has {actual}"),
+            ));
+        }
+    }
+
+    if let Some(journal) = storage.interrupted_sync(alias) {
+        issues.push(issue(
+            DataIssueKind::InterruptedSync,
+            format!(
+                "Sync started {} (pid {}) never finished",
+                journal.started_at.format("%Y-%m-%d %H:%M:%S UTC"),
+                journal.pid
+            ),
+        ));
+    }
+
+    if storage
+        .metadata_path(alias)
+        .is_ok_and(|path| !path.exists())
+    {
+        issues.push(issue(
+            DataIssueKind::MissingMetadata,
+            "metadata.json is missing".to_string(),
+        ));
+    }
+    issues
+}
+
+fn index_health_check(total: usize, issues: &[DataIssue]) -> (HealthCheck, Option<String>) {
+    let count = |kind| issues.iter().filter(|issue| issue.kind == kind).count();
+    let missing_indices = count(DataIssueKind::MissingIndex);
+    let corrupted_indices = count(DataIssueKind::CorruptedIndex);
+    let damaged = missing_indices + corrupted_indices;
+
+    let status = if corrupted_indices > 0 {
+        HealthStatus::Error
+    } else if missing_indices > 0 {
         HealthStatus::Warning
+    } else {
+        HealthStatus::Healthy
     };
-    let message = if missing_indices == 0 {
-        format!("All {} sources have search indices", aliases.len())
+    let message = if damaged == 0 {
+        format!("All {total} sources have search indices")
     } else {
-        format!("{missing_indices} sources missing search indices")
+        format!("{missing_indices} sources missing search indices, {corrupted_indices} corrupted")
     };
 
-    let recommendation = (missing_indices > 0)
-        .then_some("Re-index sources with missing indices by updating them".to_string());
+    let recommendation = (damaged > 0).then(|| {
+        format!("Run `blz doctor --fix` to rebuild {damaged} search indices from cached content")
+    });
 
     let check = HealthCheck {
         name: "Search Indices".to_string(),
         status,
         message,
-        fixable: missing_indices > 0,
+        fixable: damaged > 0,
+    };
+
+    (check, recommendation)
+}
+
+/// Check that `llms.json` and `metadata.json` agree with the cached content
+/// and that no sync journal was left behind.
+fn consistency_check(issues: &[DataIssue]) -> (HealthCheck, Option<String>) {
+    let problems: Vec<&DataIssue> = issues
+        .iter()
+        .filter(|issue| !issue.kind.affects_index())
+        .collect();
+    let (status, message) = if problems.is_empty() {
+        (
+            HealthStatus::Healthy,
+            "Stored metadata matches cached content; no interrupted syncs".to_string(),
+        )
+    } else {
+        let mut aliases: Vec<&str> = problems.iter().map(|issue| issue.alias.as_str()).collect();
+        aliases.dedup();
+        (
+            HealthStatus::Warning,
+            format!(
+                "{} consistency issue(s): {}",
+                problems.len(),
+                aliases.join(", ")
+            ),
+        )
+    };
+    let recommendation = (!problems.is_empty())
+        .then(|| "Run `blz doctor --fix` to regenerate metadata and finish interrupted syncs from cached content".to_string());
+    let check = HealthCheck {
+        name: "Content Consistency".to_string(),
+        status,
+        message,
+        fixable: !problems.is_empty(),
+    };
+    (check, recommendation)
+}
+
+/// Look for orphaned indices, stale archives, and other data `blz gc` removes.
+fn garbage_check(storage: &Storage) -> (HealthCheck, Option<String>) {
+    let (status, message, recommendation) = match gc::scan(storage, gc_options()) {
+        Ok(items) if items.is_empty() => (
+            HealthStatus::Healthy,
+            "No orphaned or stale cache data".to_string(),
+            None,
+        ),
+        Ok(items) => {
+            let bytes = format_bytes(items.iter().map(|item| item.bytes).sum());
+            (
+                HealthStatus::Warning,
+                format!("{} orphaned or stale item(s) using {bytes}", items.len()),
+                Some(format!(
+                    "Run `blz gc` to reclaim {bytes} of unused cache data"
+                )),
+            )
+        },
+        Err(e) => (
+            HealthStatus::Warning,
+            format!("Could not scan for orphaned data: {e}"),
+            None,
+        ),
+    };
+    let check = HealthCheck {
+        name: "Orphaned Data".to_string(),
+        status,
+        message,
+        fixable: false,
+    };
+    (check, recommendation)
+}
+
+/// Check the preferences store and search history for corruption.
+fn state_file_checks() -> Vec<(HealthCheck, Option<String>)> {
+    vec![
+        state_file_check("Preferences Store", "preferences", store::inspect_store()),
+        state_file_check("Search History", "search history", history_log::inspect()),
+    ]
+}
+
+fn state_file_check(
+    name: &str,
+    label: &str,
+    integrity: FileIntegrity,
+) -> (HealthCheck, Option<String>) {
+    let (status, message, recommendation) = match integrity {
+        FileIntegrity::Missing => (
+            HealthStatus::Healthy,
+            format!("No {label} recorded yet"),
+            None,
+        ),
+        FileIntegrity::Healthy => (HealthStatus::Healthy, format!("{name} is valid"), None),
+        FileIntegrity::Corrupted(detail) => (
+            HealthStatus::Warning,
+            format!("{name} is corrupted: {detail}"),
+            Some(format!(
+                "Run `blz doctor --fix` to back up and regenerate the {label}"
+            )),
+        ),
+    };
+
+    let check = HealthCheck {
+        name: name.to_string(),
+        status,
+        message,
+        fixable: recommendation.is_some(),
     };
 
     (check, recommendation)
@@ -415,6 +639,11 @@ fn collect_source_health_entries(storage: &Storage, aliases: &[String]) -> Vec<S
             entry = entry.with_line_count(llms_json.line_index.total_lines);
         }
 
+        // Persist the lint score so `blz list` can show health badges.
+        if let Err(err) = storage.record_health(alias, None) {
+            tracing::debug!("failed to record health for {alias}: {err}");
+        }
+
         // For generated sources, get failed page count from manifest
         if is_generated {
             if let Ok(manifest) = load_generate_manifest(storage, alias) {
@@ -459,6 +688,7 @@ async fn apply_fixes(storage: &Storage, report: &mut HealthReport) -> Result<()>
                 false,
                 None,
                 false,
+                None,
             )
             .await
             {
@@ -468,12 +698,95 @@ async fn apply_fixes(storage: &Storage, report: &mut HealthReport) -> Result<()>
         }
     }
 
+    // Fix 2: Rebuild damaged indices and metadata from cached content
+    if !report.data_issues.is_empty() {
+        println!("  Rebuilding damaged sources from cached content...");
+        let mut aliases: Vec<&str> = report
+            .data_issues
+            .iter()
+            .map(|issue| issue.alias.as_str())
+            .collect();
+        aliases.dedup();
+        for alias in aliases {
+            let issues: Vec<&DataIssue> = report
+                .data_issues
+                .iter()
+                .filter(|issue| issue.alias == alias)
+                .collect();
+            match repair_source_data(storage, alias, &issues) {
+                Ok(()) => println!("    ✓ Repaired {alias}"),
+                Err(e) => eprintln!("    ✗ Failed to repair {alias}: {e}"),
+            }
+        }
+        storage.invalidate_query_cache();
+    }
+
+    // Fix 3: Back up and regenerate corrupted preferences/history
+    match store::repair_store() {
+        Ok(Some(backup)) => println!(
+            "    ✓ Regenerated preferences (backup: {})",
+            backup.display()
+        ),
+        Ok(None) => {},
+        Err(e) => eprintln!("    ✗ Failed to repair preferences: {e}"),
+    }
+    match history_log::repair() {
+        Ok(Some(backup)) => println!(
+            "    ✓ Regenerated search history (backup: {})",
+            backup.display()
+        ),
+        Ok(None) => {},
+        Err(e) => eprintln!("    ✗ Failed to repair search history: {e}"),
+    }
+
     // Re-run checks to update report
     *report = run_health_checks(storage)?;
 
     Ok(())
 }
 
+/// Regenerate whatever `issues` found damaged for `alias`.
+///
+/// Metadata is restored from `llms.json`; line counts and the TOC are
+/// re-derived from `llms.txt` after a mismatch or an interrupted sync; and the
+/// index is rebuilt and swapped in whenever it is damaged or its content was
+/// re-parsed. Any sync journal is cleared once everything agrees.
+fn repair_source_data(storage: &Storage, alias: &str, issues: &[&DataIssue]) -> Result<()> {
+    let has = |kind| issues.iter().any(|issue| issue.kind == kind);
+    let mut llms_json = storage.load_llms_json(alias)?;
+
+    if has(DataIssueKind::MissingMetadata) {
+        storage.save_source_metadata(alias, &llms_json.metadata)?;
+    }
+
+    let reparse = has(DataIssueKind::LineCountMismatch) || has(DataIssueKind::InterruptedSync);
+    if reparse {
+        let content = storage.load_llms_txt(alias)?;
+        let mut parsed = MarkdownParser::new()?.parse(&content)?;
+        carry_section_ids(&llms_json.toc, &mut parsed);
+        llms_json.toc = parsed.toc;
+        llms_json.line_index.total_lines = parsed.line_count;
+        llms_json.diagnostics = parsed.diagnostics;
+        storage.save_llms_json(alias, &llms_json)?;
+    }
+
+    if reparse || issues.iter().any(|issue| issue.kind.affects_index()) {
+        let filter_preference = storage
+            .load_source_metadata(alias)?
+            .and_then(|metadata| metadata.filter_non_english)
+            .unwrap_or(true);
+        reindex_source(
+            storage,
+            alias,
+            blz_core::PerformanceMetrics::default(),
+            &DefaultRefreshIndexer,
+            filter_preference,
+        )?;
+    }
+    storage.finish_sync(alias)?;
+    Ok(())
+}
+
 fn calculate_cache_size(dir: &PathBuf) -> Result<u64> {
     let mut total = 0u64;
 
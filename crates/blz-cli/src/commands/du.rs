@@ -0,0 +1,169 @@
+//! Disk usage report (`blz du`)
+//!
+//! Breaks down how much space each cached source uses on disk (content,
+//! archives, and search index) to help decide what to prune.
+
+use anyhow::Result;
+use blz_core::numeric::format_bytes;
+use blz_core::{SourceDiskUsage, Storage};
+use serde::Serialize;
+
+use crate::i18n::{self, Message};
+use crate::output::OutputFormat;
+use crate::utils::resolver::resolve_source;
+
+/// Byte counts for one source or for the totals row.
+#[derive(Debug, Default, Clone, Copy, Serialize)]
+struct UsageBytes {
+    #[serde(rename = "contentBytes")]
+    content: u64,
+    #[serde(rename = "archivesBytes")]
+    archives: u64,
+    #[serde(rename = "indexBytes")]
+    index: u64,
+    #[serde(rename = "totalBytes")]
+    total: u64,
+}
+
+impl UsageBytes {
+    const fn add(self, other: Self) -> Self {
+        Self {
+            content: self.content + other.content,
+            archives: self.archives + other.archives,
+            index: self.index + other.index,
+            total: self.total + other.total,
+        }
+    }
+}
+
+impl From<SourceDiskUsage> for UsageBytes {
+    fn from(usage: SourceDiskUsage) -> Self {
+        Self {
+            content: usage.content,
+            archives: usage.archives,
+            index: usage.index,
+            total: usage.total(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct SourceUsage {
+    alias: String,
+    #[serde(flatten)]
+    usage: UsageBytes,
+}
+
+#[derive(Debug, Serialize)]
+struct DiskUsageReport {
+    sources: Vec<SourceUsage>,
+    totals: UsageBytes,
+}
+
+/// Execute `blz du`.
+///
+/// Reports all sources (largest first) or only the requested ones. Text output
+/// uses human-readable sizes unless `bytes` is set.
+///
+/// # Errors
+///
+/// Returns an error if a requested source does not exist or a cache directory
+/// cannot be read.
+pub fn execute(aliases: &[String], bytes: bool, format: OutputFormat) -> Result<()> {
+    let storage = Storage::new()?;
+
+    let targets = if aliases.is_empty() {
+        storage.list_sources()
+    } else {
+        aliases
+            .iter()
+            .map(|requested| {
+                resolve_source(&storage, requested)?
+                    .ok_or_else(|| anyhow::anyhow!("Source '{requested}' not found"))
+            })
+            .collect::<Result<Vec<_>>>()?
+    };
+
+    let mut sources = targets
+        .into_iter()
+        .map(|alias| {
+            let usage = storage.disk_usage(&alias)?;
+            Ok(SourceUsage {
+                alias,
+                usage: usage.into(),
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    sources.sort_by(|a, b| {
+        b.usage
+            .total
+            .cmp(&a.usage.total)
+            .then_with(|| a.alias.cmp(&b.alias))
+    });
+
+    let totals = sources
+        .iter()
+        .fold(UsageBytes::default(), |totals, source| {
+            totals.add(source.usage)
+        });
+    let report = DiskUsageReport { sources, totals };
+
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+        OutputFormat::Jsonl => {
+            for source in &report.sources {
+                println!("{}", serde_json::to_string(source)?);
+            }
+        },
+        OutputFormat::Raw => {
+            for source in &report.sources {
+                println!("{}\t{}", source.usage.total, source.alias);
+            }
+        },
+        OutputFormat::Text | OutputFormat::Markdown => print_text_report(&report, bytes),
+    }
+
+    Ok(())
+}
+
+fn print_text_report(report: &DiskUsageReport, bytes: bool) {
+    if report.sources.is_empty() {
+        println!("{}", i18n::text(Message::NoSourcesFound, &[]));
+        return;
+    }
+
+    let size = |value: u64| {
+        if bytes {
+            value.to_string()
+        } else {
+            format_bytes(value)
+        }
+    };
+    let alias_width = report
+        .sources
+        .iter()
+        .map(|source| source.alias.len())
+        .max()
+        .unwrap_or(0)
+        .max("TOTAL".len());
+
+    println!(
+        "{:<alias_width$}  {:>12}  {:>12}  {:>12}  {:>12}",
+        "SOURCE", "CONTENT", "ARCHIVES", "INDEX", "TOTAL"
+    );
+    let rows = report
+        .sources
+        .iter()
+        .map(|source| (source.alias.as_str(), source.usage))
+        .chain(std::iter::once(("TOTAL", report.totals)));
+    for (label, usage) in rows {
+        println!(
+            "{:<alias_width$}  {:>12}  {:>12}  {:>12}  {:>12}",
+            label,
+            size(usage.content),
+            size(usage.archives),
+            size(usage.index),
+            size(usage.total)
+        );
+    }
+}
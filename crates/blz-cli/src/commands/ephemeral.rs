@@ -0,0 +1,154 @@
+//! Ephemeral (throwaway) sources for one-off URL searches.
+//!
+//! `blz search --url <URL> <query>` fetches a remote llms.txt and indexes it in
+//! memory. Nothing is written to the user's data directory unless `--keep`
+//! promotes the source through the regular `blz add` flow.
+
+use anyhow::Result;
+use blz_core::discovery::derive_alias;
+use blz_core::{
+    LlmsJson, MarkdownParser, PerformanceMetrics, SearchIndex, Source, SourceOrigin, SourceType,
+    build_llms_json, url_resolver,
+};
+use chrono::Utc;
+
+/// Alias used when a URL has no usable host component.
+const FALLBACK_ALIAS: &str = "docs";
+
+/// Derive a source alias from a URL's host (e.g. `https://docs.bun.sh/llms.txt` → `bun`).
+pub(super) fn alias_for_url(url: &str) -> String {
+    url::Url::parse(url)
+        .ok()
+        .and_then(|parsed| parsed.host_str().map(derive_alias))
+        .filter(|alias| !alias.is_empty())
+        .unwrap_or_else(|| FALLBACK_ALIAS.to_string())
+}
+
+/// A fetched source indexed in memory for the lifetime of a single command.
+pub(super) struct EphemeralSource {
+    alias: String,
+    url: String,
+    index: SearchIndex,
+    llms_json: LlmsJson,
+    lines: Vec<String>,
+    metadata: Source,
+}
+
+impl EphemeralSource {
+    /// Fetch `url`, parse it, and build an in-memory search index.
+    pub(super) async fn fetch(url: &str, metrics: PerformanceMetrics) -> Result<Self> {
+        let alias = alias_for_url(url);
+
+        let fetcher = crate::utils::throttle::fetcher(None)?;
+        // Resolution already downloads the winning variant; index that copy.
+        let resolved = url_resolver::resolve_best_url(&fetcher, url).await?;
+        let content = resolved.content;
+        let sha256 = resolved.sha256;
+
+        let mut parser = MarkdownParser::new()?;
+        let parse_result = parser.parse(&content)?;
+
+        let mut llms_json = build_llms_json(
+            &alias,
+            &resolved.final_url,
+            "llms.txt",
+            sha256.clone(),
+            None,
+            None,
+            &parse_result,
+        );
+        let origin = SourceOrigin {
+            manifest: None,
+            source_type: Some(SourceType::Remote {
+                url: resolved.final_url.clone(),
+            }),
+        };
+        llms_json.metadata.variant = resolved.variant.clone();
+        llms_json.metadata.origin = origin.clone();
+
+        let metadata = Source {
+            url: resolved.final_url.clone(),
+            etag: None,
+            last_modified: None,
+            fetched_at: Utc::now(),
+            sha256,
+            variant: resolved.variant,
+            aliases: Vec::new(),
+            tags: Vec::new(),
+            description: None,
+            category: None,
+            npm_aliases: Vec::new(),
+            github_aliases: Vec::new(),
+            origin,
+            filter_non_english: None,
+        };
+
+        let index = SearchIndex::create_in_ram()?.with_metrics(metrics);
+        index.index_blocks(&alias, &parse_result.heading_blocks)?;
+
+        Ok(Self {
+            alias,
+            url: url.to_string(),
+            index,
+            llms_json,
+            lines: content.lines().map(str::to_string).collect(),
+            metadata,
+        })
+    }
+
+    /// Match search terms within `distance` edits (see `--fuzzy`).
+    #[must_use]
+    pub(super) fn with_fuzzy(mut self, distance: u8) -> Self {
+        self.index = self.index.with_fuzzy(distance);
+        self
+    }
+
+    /// Alias the ephemeral source is indexed under.
+    pub(super) fn alias(&self) -> &str {
+        &self.alias
+    }
+
+    /// URL the source was fetched from (as provided by the user).
+    pub(super) fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// In-memory search index over the fetched content.
+    pub(super) const fn index(&self) -> &SearchIndex {
+        &self.index
+    }
+
+    /// Parsed document structure (TOC and line index).
+    pub(super) const fn llms_json(&self) -> &LlmsJson {
+        &self.llms_json
+    }
+
+    /// Raw content split into lines, used for context expansion.
+    pub(super) fn lines(&self) -> &[String] {
+        &self.lines
+    }
+
+    /// Fetch metadata reported alongside hits (URL, checksum, fetch time).
+    pub(super) const fn metadata(&self) -> &Source {
+        &self.metadata
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn alias_is_derived_from_host() {
+        assert_eq!(alias_for_url("https://hono.dev/llms.txt"), "hono");
+        assert_eq!(
+            alias_for_url("https://docs.example.com/llms.txt"),
+            "example"
+        );
+    }
+
+    #[test]
+    fn alias_falls_back_without_host() {
+        assert_eq!(alias_for_url("not a url"), FALLBACK_ALIAS);
+    }
+}
@@ -0,0 +1,146 @@
+//! Score explanation command.
+//!
+//! `blz explain` runs a query against one source and shows why each hit
+//! ranked where it did: the BM25 score of every matched term, the field boost
+//! applied to it, and the final score. Use it to diagnose ranking regressions
+//! instead of guessing from result order.
+
+use anyhow::{Context, Result};
+use blz_core::{ExplainedHit, ScoreExplanation, Storage};
+use clap::Args;
+use colored::Colorize;
+use serde::Serialize;
+
+use crate::output::OutputFormat;
+use crate::utils::cli_args::FormatArg;
+use crate::utils::resolver::resolve_source;
+
+/// Arguments for `blz explain`.
+#[derive(Args, Clone, Debug)]
+pub struct ExplainArgs {
+    /// Query to explain (same syntax as `blz query`)
+    #[arg(value_name = "QUERY")]
+    pub query: String,
+    /// Source to search
+    #[arg(long = "source", short = 's', value_name = "SOURCE")]
+    pub source: String,
+    /// Number of hits to explain
+    #[arg(
+        short = 'n',
+        long,
+        default_value_t = 5,
+        value_parser = clap::value_parser!(u16).range(1..=100)
+    )]
+    pub limit: u16,
+    /// Also print the full scoring tree for each hit (text output)
+    #[arg(long)]
+    pub tree: bool,
+    /// Output format
+    #[command(flatten)]
+    pub format: FormatArg,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ExplainReport<'a> {
+    query: &'a str,
+    source: &'a str,
+    hits: Vec<ExplainedHit>,
+}
+
+/// Execute `blz explain`.
+///
+/// # Errors
+///
+/// Returns an error if the source is unknown, its index cannot be opened, or
+/// the query fails.
+pub fn execute(args: &ExplainArgs, quiet: bool) -> Result<()> {
+    let format = args.format.resolve(quiet);
+    let storage = Storage::new()?;
+    let source = resolve_source(&storage, &args.source)?
+        .ok_or_else(|| anyhow::anyhow!("Source '{}' not found", args.source))?;
+    let index = storage
+        .open_index(&source)
+        .with_context(|| format!("Failed to open index for '{source}'"))?;
+    let hits = index.explain(&args.query, Some(&source), usize::from(args.limit))?;
+
+    let report = ExplainReport {
+        query: &args.query,
+        source: &source,
+        hits,
+    };
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+        OutputFormat::Jsonl => {
+            for hit in &report.hits {
+                println!("{}", serde_json::to_string(hit)?);
+            }
+        },
+        OutputFormat::Raw => println!("{}", serde_json::to_string(&report)?),
+        OutputFormat::Text | OutputFormat::Markdown => print_text_report(&report, args.tree),
+    }
+    Ok(())
+}
+
+fn print_text_report(report: &ExplainReport<'_>, tree: bool) {
+    if report.hits.is_empty() {
+        println!("No results for \"{}\" in {}", report.query, report.source);
+        return;
+    }
+
+    println!(
+        "Explaining \"{}\" in {} ({} {})",
+        report.query,
+        report.source,
+        report.hits.len(),
+        if report.hits.len() == 1 {
+            "hit"
+        } else {
+            "hits"
+        }
+    );
+    for (rank, explained) in report.hits.iter().enumerate() {
+        let hit = &explained.hit;
+        println!();
+        println!(
+            "{} {}  {}:{}  {}",
+            format!("#{}", rank + 1).bold(),
+            format!("score {:.4}", hit.score).green(),
+            hit.source,
+            hit.lines,
+            hit.heading_path.join(" > ").bright_black()
+        );
+        println!(
+            "   {:<20} {:<24} {:>9} {:>7} {:>9}",
+            "term", "field", "bm25", "boost", "score"
+        );
+        for term in &explained.terms {
+            println!(
+                "   {:<20} {:<24} {:>9.4} {:>7} {:>9.4}",
+                term.term,
+                term.field,
+                term.bm25,
+                format!("x{:.2}", term.boost),
+                term.score
+            );
+        }
+        if tree {
+            print_tree(&explained.explanation, 1);
+        }
+    }
+}
+
+fn print_tree(node: &ScoreExplanation, depth: usize) {
+    let indent = "  ".repeat(depth + 1);
+    let context = if node.context.is_empty() {
+        String::new()
+    } else {
+        format!(" [{}]", node.context.join(", "))
+            .bright_black()
+            .to_string()
+    };
+    println!("{indent}{:.4} {}{context}", node.value, node.description);
+    for detail in &node.details {
+        print_tree(detail, depth + 1);
+    }
+}
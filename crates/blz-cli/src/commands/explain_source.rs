@@ -0,0 +1,349 @@
+//! Source briefing command.
+//!
+//! `blz explain-source` condenses what an agent needs to know before using a
+//! source: what it covers, its top-level sections with anchors and line
+//! ranges, how big and how fresh it is, and a few queries that will match.
+//! Text output is markdown meant to be pasted into a prompt; JSON carries the
+//! same fields for tooling.
+
+use std::fmt::Write as _;
+
+use anyhow::{Context, Result};
+use blz_core::{Source, Storage, TermStat, TocEntry};
+use chrono::{DateTime, Utc};
+use clap::Args;
+use serde::Serialize;
+
+use crate::output::OutputFormat;
+use crate::utils::cli_args::FormatArg;
+use crate::utils::resolver::resolve_source;
+use crate::utils::staleness::{self, DEFAULT_STALE_AFTER_DAYS};
+
+/// Indexed terms considered when picking key terms; most frequent first.
+const TERM_CANDIDATES: usize = 200;
+
+/// Arguments for `blz explain-source`.
+#[derive(Args, Clone, Debug)]
+pub struct ExplainSourceArgs {
+    /// Source to brief
+    #[arg(value_name = "ALIAS")]
+    pub alias: String,
+    /// Maximum number of top-level sections to list
+    #[arg(
+        long,
+        value_name = "COUNT",
+        default_value_t = 20,
+        value_parser = clap::value_parser!(u16).range(1..=500)
+    )]
+    pub sections: u16,
+    /// Output format (text prints markdown)
+    #[command(flatten)]
+    pub format: FormatArg,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Briefing {
+    source: String,
+    url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    aliases: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+    size: Size,
+    version: Version,
+    freshness: Freshness,
+    sections: Vec<Section>,
+    /// Sections not listed because of `--sections`.
+    more_sections: usize,
+    key_terms: Vec<String>,
+    suggested_queries: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Size {
+    lines: usize,
+    headings: usize,
+    tokens: usize,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Version {
+    sha256: String,
+    variant: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    etag: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_modified: Option<String>,
+    /// Earlier versions kept in the local archive.
+    archived: usize,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Freshness {
+    fetched_at: DateTime<Utc>,
+    age_days: i64,
+    stale: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Section {
+    title: String,
+    lines: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    anchor: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<String>,
+    subsections: usize,
+}
+
+impl Section {
+    fn new(entry: &TocEntry) -> Self {
+        Self {
+            title: entry_title(entry),
+            lines: entry.lines.clone(),
+            anchor: entry.anchor.clone(),
+            id: entry.id.clone(),
+            subsections: entry.children.len(),
+        }
+    }
+}
+
+/// Execute `blz explain-source`.
+///
+/// # Errors
+///
+/// Returns an error if the source is unknown or its cached metadata cannot be
+/// read. A missing search index only leaves the briefing without key terms.
+pub fn execute(args: &ExplainSourceArgs, quiet: bool) -> Result<()> {
+    let format = args.format.resolve(quiet);
+    let storage = Storage::new()?;
+    let source = resolve_source(&storage, &args.alias)?
+        .filter(|canonical| storage.exists(canonical))
+        .ok_or_else(|| anyhow::anyhow!("Source '{}' not found", args.alias))?;
+    let briefing = build_briefing(&storage, &source, usize::from(args.sections))?;
+
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&briefing)?),
+        OutputFormat::Jsonl | OutputFormat::Raw => {
+            println!("{}", serde_json::to_string(&briefing)?);
+        },
+        OutputFormat::Text | OutputFormat::Markdown => print!("{}", render_markdown(&briefing)),
+    }
+    Ok(())
+}
+
+fn build_briefing(storage: &Storage, source: &str, max_sections: usize) -> Result<Briefing> {
+    let llms = storage
+        .load_llms_json(source)
+        .with_context(|| format!("Failed to load metadata for '{source}'"))?;
+    let metadata: Source = storage
+        .load_source_metadata(source)?
+        .unwrap_or_else(|| llms.metadata.clone());
+
+    let top_level = top_level_entries(&llms.toc);
+    let sections: Vec<Section> = top_level
+        .iter()
+        .take(max_sections)
+        .map(Section::new)
+        .collect();
+    let more_sections = top_level.len().saturating_sub(sections.len());
+
+    let content = storage.load_llms_txt(source).unwrap_or_default();
+    let key_terms = key_terms(storage, source);
+    let suggested_queries = suggested_queries(source, &sections, &key_terms);
+    let archived = storage
+        .list_versions(source)
+        .map(|versions| versions.len())
+        .unwrap_or_default();
+
+    Ok(Briefing {
+        source: source.to_string(),
+        url: metadata.url.clone(),
+        description: metadata.description.clone(),
+        aliases: metadata.aliases.clone(),
+        tags: metadata.tags.clone(),
+        size: Size {
+            lines: llms.line_index.total_lines,
+            headings: crate::utils::count_headings(&llms.toc),
+            tokens: content.len() / 4,
+        },
+        version: Version {
+            sha256: metadata.sha256.clone(),
+            variant: format!("{:?}", metadata.variant),
+            etag: metadata.etag.clone(),
+            last_modified: metadata.last_modified.clone(),
+            archived,
+        },
+        freshness: Freshness {
+            fetched_at: metadata.fetched_at,
+            age_days: staleness::days_since(metadata.fetched_at),
+            stale: staleness::is_stale(metadata.fetched_at, DEFAULT_STALE_AFTER_DAYS),
+        },
+        sections,
+        more_sections,
+        key_terms,
+        suggested_queries,
+    })
+}
+
+/// The document's top-level sections; a lone title heading is looked through.
+fn top_level_entries(toc: &[TocEntry]) -> &[TocEntry] {
+    match toc {
+        [root] if !root.children.is_empty() => &root.children,
+        _ => toc,
+    }
+}
+
+fn entry_title(entry: &TocEntry) -> String {
+    entry
+        .heading_path_display
+        .as_ref()
+        .unwrap_or(&entry.heading_path)
+        .last()
+        .cloned()
+        .unwrap_or_default()
+}
+
+/// Frequent, distinctive indexed terms: short and numeric tokens are skipped.
+fn key_terms(storage: &Storage, source: &str) -> Vec<String> {
+    term_candidates(storage, source)
+        .inspect_err(|err| tracing::debug!("no key terms for {source}: {err}"))
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|stat| {
+            stat.term.chars().count() >= 4 && !stat.term.chars().all(|c| c.is_ascii_digit())
+        })
+        .take(10)
+        .map(|stat| stat.term)
+        .collect()
+}
+
+fn term_candidates(storage: &Storage, source: &str) -> Result<Vec<TermStat>> {
+    let index = storage.open_index(source)?;
+    Ok(index.term_stats(None, None, TERM_CANDIDATES)?)
+}
+
+/// Ready-to-run commands that exercise the source.
+fn suggested_queries(source: &str, sections: &[Section], key_terms: &[String]) -> Vec<String> {
+    let mut queries: Vec<String> = key_terms
+        .iter()
+        .take(3)
+        .map(|term| format!("blz query \"{term}\" -s {source}"))
+        .collect();
+    if let Some(section) = sections.iter().find(|section| section.subsections > 0) {
+        queries.push(format!("blz query \"# {}\" -s {source}", section.title));
+    }
+    if let Some(section) = sections.first() {
+        queries.push(format!("blz get {source}:{}", section.lines));
+    }
+    queries.push(format!("blz map {source} --max-depth 2"));
+    queries
+}
+
+fn render_markdown(briefing: &Briefing) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "# Source: {}", briefing.source);
+    out.push('\n');
+    if let Some(description) = &briefing.description {
+        let _ = writeln!(out, "{description}");
+        out.push('\n');
+    }
+    let _ = writeln!(out, "- URL: {}", briefing.url);
+    if !briefing.aliases.is_empty() {
+        let _ = writeln!(out, "- Aliases: {}", briefing.aliases.join(", "));
+    }
+    if !briefing.tags.is_empty() {
+        let _ = writeln!(out, "- Tags: {}", briefing.tags.join(", "));
+    }
+    let size = &briefing.size;
+    let _ = writeln!(
+        out,
+        "- Size: {} lines, {} headings, ~{} tokens",
+        size.lines, size.headings, size.tokens
+    );
+    let version = &briefing.version;
+    let short_sha: String = version.sha256.chars().take(12).collect();
+    let _ = writeln!(
+        out,
+        "- Version: {} ({short_sha}), {} archived",
+        version.variant, version.archived
+    );
+    let freshness = &briefing.freshness;
+    let _ = writeln!(
+        out,
+        "- Fetched: {} ({} day(s) ago{})",
+        freshness.fetched_at.format("%Y-%m-%d"),
+        freshness.age_days,
+        if freshness.stale {
+            "; stale, run `blz sync`"
+        } else {
+            ""
+        }
+    );
+
+    out.push_str("\n## Sections\n\n");
+    for section in &briefing.sections {
+        let _ = write!(out, "- {} (lines {}", section.title, section.lines);
+        if let Some(anchor) = &section.anchor {
+            let _ = write!(out, ", anchor {anchor}");
+        }
+        if section.subsections > 0 {
+            let _ = write!(out, ", {} subsections", section.subsections);
+        }
+        out.push_str(")\n");
+    }
+    if briefing.more_sections > 0 {
+        let _ = writeln!(out, "- ... {} more", briefing.more_sections);
+    }
+
+    if !briefing.key_terms.is_empty() {
+        let _ = writeln!(out, "\n## Key terms\n\n{}", briefing.key_terms.join(", "));
+    }
+
+    out.push_str("\n## How to use\n\n");
+    for query in &briefing.suggested_queries {
+        let _ = writeln!(out, "- `{query}`");
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(title: &str, lines: &str, children: Vec<TocEntry>) -> TocEntry {
+        TocEntry {
+            heading_path: vec![title.to_string()],
+            heading_path_display: None,
+            heading_path_normalized: None,
+            lines: lines.to_string(),
+            anchor: None,
+            id: None,
+            children,
+        }
+    }
+
+    #[test]
+    fn looks_through_single_title_heading() {
+        let toc = vec![entry(
+            "Guide",
+            "1-40",
+            vec![
+                entry("Install", "3-10", vec![]),
+                entry("Usage", "11-40", vec![]),
+            ],
+        )];
+        let titles: Vec<String> = top_level_entries(&toc).iter().map(entry_title).collect();
+        assert_eq!(titles, ["Install", "Usage"]);
+
+        let flat = vec![entry("A", "1-2", vec![]), entry("B", "3-4", vec![])];
+        assert_eq!(top_level_entries(&flat).len(), 2);
+    }
+}
@@ -0,0 +1,345 @@
+//! Filter command: try and configure content filter pipelines.
+//!
+//! `blz filter test` runs a proposed filter pipeline over the cached copy of a
+//! source and shows what it would remove or rewrite, section by section. The
+//! cache and index are left untouched, so rules can be iterated on freely.
+//! `blz filter set` saves a pipeline to the source's `settings.toml` and
+//! re-indexes with it; `show` and `clear` inspect and reset it. `set --lang`
+//! chooses the locales a bare `lang` rule keeps.
+
+use anyhow::{Context, Result};
+use blz_core::content_filter::{SectionAction, SectionChange};
+use blz_core::{FilterPipeline, LocaleSet, MarkdownParser, PerformanceMetrics, Storage};
+use clap::{Args, Subcommand};
+use colored::Colorize;
+use serde::Serialize;
+
+use crate::output::OutputFormat;
+use crate::utils::cli_args::FormatArg;
+use crate::utils::resolver::resolve_source;
+
+/// Subcommands for `blz filter`.
+#[derive(Subcommand, Clone, Debug)]
+pub enum FilterCommands {
+    /// Preview what a filter pipeline would remove from a source
+    Test(FilterTestArgs),
+    /// Show the filter pipeline a source is indexed with
+    Show(FilterShowArgs),
+    /// Save a source's filter pipeline and re-index it
+    Set(FilterSetArgs),
+    /// Restore a source's default pipeline (the English language filter) and re-index it
+    Clear(FilterClearArgs),
+}
+
+/// Arguments for `blz filter test`.
+#[derive(Args, Clone, Debug)]
+pub struct FilterTestArgs {
+    /// Source to preview against
+    #[arg(value_name = "ALIAS")]
+    pub alias: String,
+    /// Filter rule, applied in the order given (repeatable)
+    ///
+    /// Rules: lang, lang=<locales>, nav, dedupe, html-comments,
+    /// drop-heading=<regex>, drop-content=<regex>, strip=<regex>,
+    /// replace=<regex>=><text>
+    #[arg(short = 'r', long = "rule", value_name = "RULE", required = true)]
+    pub rules: Vec<String>,
+    /// Only show counts per heading, not the line diff
+    #[arg(long)]
+    pub stat: bool,
+    /// Output format
+    #[command(flatten)]
+    pub format: FormatArg,
+}
+
+/// Arguments for `blz filter show`.
+#[derive(Args, Clone, Debug)]
+pub struct FilterShowArgs {
+    /// Source to show
+    #[arg(value_name = "ALIAS")]
+    pub alias: String,
+    /// Output format
+    #[command(flatten)]
+    pub format: FormatArg,
+}
+
+/// Arguments for `blz filter set`.
+#[derive(Args, Clone, Debug)]
+pub struct FilterSetArgs {
+    /// Source to configure
+    #[arg(value_name = "ALIAS")]
+    pub alias: String,
+    /// Filter rule, applied in the order given (repeatable)
+    ///
+    /// Rules: lang, lang=<locales>, nav, dedupe, html-comments,
+    /// drop-heading=<regex>, drop-content=<regex>, strip=<regex>,
+    /// replace=<regex>=><text>
+    #[arg(
+        short = 'r',
+        long = "rule",
+        value_name = "RULE",
+        required_unless_present = "lang"
+    )]
+    pub rules: Vec<String>,
+    /// Locales the language filter keeps (e.g. en,de)
+    #[arg(long, value_name = "LOCALES")]
+    pub lang: Option<LocaleSet>,
+}
+
+/// Arguments for `blz filter clear`.
+#[derive(Args, Clone, Debug)]
+pub struct FilterClearArgs {
+    /// Source to reset
+    #[arg(value_name = "ALIAS")]
+    pub alias: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PipelineReport {
+    source: String,
+    enabled: bool,
+    configured: bool,
+    rules: Vec<String>,
+    locales: Vec<&'static str>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct FilterReport<'a> {
+    source: &'a str,
+    rules: &'a [String],
+    sections_total: usize,
+    sections_dropped: usize,
+    sections_modified: usize,
+    lines_removed: usize,
+    lines_rewritten: usize,
+    sections: Vec<SectionChange>,
+}
+
+/// Dispatch a `blz filter` subcommand.
+///
+/// # Errors
+///
+/// Returns an error if the subcommand fails.
+pub fn dispatch(command: &FilterCommands, quiet: bool, metrics: PerformanceMetrics) -> Result<()> {
+    match command {
+        FilterCommands::Test(args) => execute_test(args, quiet),
+        FilterCommands::Show(args) => execute_show(args, quiet),
+        FilterCommands::Set(args) => execute_set(args, quiet, metrics),
+        FilterCommands::Clear(args) => execute_clear(args, quiet, metrics),
+    }
+}
+
+fn installed_source(storage: &Storage, alias: &str) -> Result<String> {
+    resolve_source(storage, alias)?
+        .filter(|canonical| storage.exists(canonical))
+        .ok_or_else(|| anyhow::anyhow!("Source '{alias}' not found"))
+}
+
+/// Execute `blz filter show`.
+///
+/// # Errors
+///
+/// Returns an error if the source is unknown or its settings cannot be read.
+pub fn execute_show(args: &FilterShowArgs, quiet: bool) -> Result<()> {
+    let storage = Storage::new()?;
+    let source = installed_source(&storage, &args.alias)?;
+    let configured = storage.load_filter_rules(&source)?;
+    let locales = storage.load_locales(&source)?;
+    let enabled = storage
+        .load_source_metadata(&source)?
+        .and_then(|metadata| metadata.filter_non_english)
+        .unwrap_or(true);
+    let report = PipelineReport {
+        enabled,
+        configured: configured.is_some() || locales.is_some(),
+        rules: configured.unwrap_or_else(|| vec!["lang".to_string()]),
+        locales: locales.unwrap_or_default().codes(),
+        source,
+    };
+
+    match args.format.resolve(quiet) {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+        OutputFormat::Jsonl | OutputFormat::Raw => {
+            println!("{}", serde_json::to_string(&report)?);
+        },
+        OutputFormat::Text | OutputFormat::Markdown => {
+            let rules = if report.rules.is_empty() {
+                "(none)".to_string()
+            } else {
+                report.rules.join(", ")
+            };
+            let origin = if report.configured {
+                "settings.toml"
+            } else {
+                "default"
+            };
+            println!(
+                "{}: {rules} {}",
+                report.source.bold(),
+                format!("({origin})").bright_black()
+            );
+            println!("Locales: {}", report.locales.join(", "));
+            if !report.enabled {
+                println!(
+                    "{}",
+                    "Filtering is disabled for this source; re-enable it with `blz refresh --reindex --filter`."
+                        .yellow()
+                );
+            }
+        },
+    }
+    Ok(())
+}
+
+/// Execute `blz filter set`: save the rules and locales given, then
+/// re-index the source from its cached content.
+///
+/// # Errors
+///
+/// Returns an error if a rule is invalid, the source is unknown, or the
+/// settings cannot be written.
+pub fn execute_set(args: &FilterSetArgs, quiet: bool, metrics: PerformanceMetrics) -> Result<()> {
+    let pipeline = FilterPipeline::parse(&args.rules)?;
+    let storage = Storage::new()?;
+    let source = installed_source(&storage, &args.alias)?;
+    if !args.rules.is_empty() {
+        let rules = pipeline.rules().iter().map(ToString::to_string).collect();
+        storage.save_filter_rules(&source, Some(rules))?;
+    }
+    if let Some(locales) = args.lang {
+        storage.save_locales(&source, (!locales.is_english_only()).then_some(locales))?;
+    }
+    super::refresh::execute_reindex(&storage, &source, metrics, quiet, None, false)
+}
+
+/// Execute `blz filter clear`: drop the source's rules and locales, then
+/// re-index it.
+///
+/// # Errors
+///
+/// Returns an error if the source is unknown or the settings cannot be
+/// written.
+pub fn execute_clear(
+    args: &FilterClearArgs,
+    quiet: bool,
+    metrics: PerformanceMetrics,
+) -> Result<()> {
+    let storage = Storage::new()?;
+    let source = installed_source(&storage, &args.alias)?;
+    storage.save_filter_rules(&source, None)?;
+    storage.save_locales(&source, None)?;
+    super::refresh::execute_reindex(&storage, &source, metrics, quiet, None, false)
+}
+
+/// Execute `blz filter test`.
+///
+/// # Errors
+///
+/// Returns an error if a rule is invalid, the source is unknown, or its cached
+/// content cannot be read or parsed.
+pub fn execute_test(args: &FilterTestArgs, quiet: bool) -> Result<()> {
+    let format = args.format.resolve(quiet);
+    let pipeline = FilterPipeline::parse(&args.rules)?;
+    let storage = Storage::new()?;
+    let source = installed_source(&storage, &args.alias)?;
+    let pipeline = pipeline.with_locales(storage.load_locales(&source)?.unwrap_or_default());
+    let content = storage
+        .load_llms_txt(&source)
+        .with_context(|| format!("Failed to read cached content for '{source}'"))?;
+    let blocks = MarkdownParser::new()?.parse(&content)?.heading_blocks;
+    let sections_total = blocks.len();
+    let mut sections = pipeline.run(blocks).changes;
+    if args.stat {
+        for section in &mut sections {
+            section.changes.clear();
+        }
+    }
+
+    let dropped = sections
+        .iter()
+        .filter(|section| section.action == SectionAction::Dropped)
+        .count();
+    let report = FilterReport {
+        source: &source,
+        rules: &args.rules,
+        sections_total,
+        sections_dropped: dropped,
+        sections_modified: sections.len() - dropped,
+        lines_removed: sections.iter().map(|s| s.lines_removed).sum(),
+        lines_rewritten: sections.iter().map(|s| s.lines_rewritten).sum(),
+        sections,
+    };
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+        OutputFormat::Jsonl => {
+            for section in &report.sections {
+                println!("{}", serde_json::to_string(section)?);
+            }
+        },
+        OutputFormat::Raw => println!("{}", serde_json::to_string(&report)?),
+        OutputFormat::Text | OutputFormat::Markdown => print_text_report(&report, args.stat),
+    }
+    Ok(())
+}
+
+fn print_text_report(report: &FilterReport<'_>, stat: bool) {
+    println!(
+        "Filter preview for {} ({} of {} sections dropped, {} modified; {} lines removed, {} rewritten)",
+        report.source.bold(),
+        report.sections_dropped,
+        report.sections_total,
+        report.sections_modified,
+        report.lines_removed,
+        report.lines_rewritten
+    );
+    if report.sections.is_empty() {
+        println!("The rules do not change any section.");
+        return;
+    }
+    println!(
+        "{}",
+        "Preview only: the cache and index are unchanged.".bright_black()
+    );
+
+    println!();
+    for section in &report.sections {
+        let action = match section.action {
+            SectionAction::Dropped => "dropped".red(),
+            SectionAction::Modified => "modified".yellow(),
+        };
+        println!(
+            "  {:<8} {:>5} lines  {}  {}",
+            action,
+            section.lines_removed + section.lines_rewritten,
+            section.heading_path.join(" > "),
+            section.rules.join(", ").bright_black()
+        );
+    }
+    if stat {
+        return;
+    }
+
+    for section in &report.sections {
+        println!();
+        println!(
+            "{}",
+            format!(
+                "@@ {} (lines {})",
+                section.heading_path.join(" > "),
+                section.lines
+            )
+            .cyan()
+        );
+        for change in &section.changes {
+            println!(
+                "{}",
+                format!("-{:>6}  {}", change.line, change.before).red()
+            );
+            if let Some(after) = &change.after {
+                println!("{}", format!("+{:>6}  {after}", change.line).green());
+            }
+        }
+    }
+}
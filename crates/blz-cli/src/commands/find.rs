@@ -5,25 +5,32 @@
 //!
 //! - Citation pattern (`alias:digits-digits`) → retrieve mode (like `get`)
 //! - Query pattern (anything else) → search mode (like `search`)
+//!
+//! The same dispatch powers the bare `blz <input>` default command, which additionally
+//! understands anchors (`alias#anchor`) and URLs (offers to add the source).
 
-use anyhow::Result;
+use anyhow::{Result, bail};
 use clap::Args;
 use colored::Colorize;
+use inquire::Confirm;
 
-use crate::args::{ContextMode, ShowComponent};
+use crate::args::{ContextMode, ShowComponent, ShowComponentParser};
 use crate::cli::{Commands, merge_context_flags};
-use crate::commands::RequestSpec;
+use crate::commands::{AddRequest, DescriptorInput, RequestSpec, add_source};
 use crate::config::{
     ContentConfig, DisplayConfig, QueryExecutionConfig, SearchConfig, SnippetConfig,
 };
 use crate::utils::cli_args::{FormatArg, deprecation_warnings_suppressed};
+use crate::utils::confirm;
 use crate::utils::heading_filter::HeadingLevelFilter;
 use crate::utils::preferences::CliPreferences;
 use blz_core::{PerformanceMetrics, ResourceMonitor};
 
+use super::ephemeral::alias_for_url;
 use super::get;
 use super::query::execute_internal as query_execute_internal;
 use super::search::{ALL_RESULTS_LIMIT, clamp_max_chars, default_search_limit};
+use super::toc::get_by_anchor;
 
 /// Arguments for the deprecated `blz find` command.
 ///
@@ -46,6 +53,38 @@ pub struct FindArgs {
     )]
     pub sources: Vec<String>,
 
+    /// Search only the K sources whose topics best match the query (0 disables).
+    ///
+    /// Ignored when sources are given explicitly.
+    #[arg(long, value_name = "K", env = "BLZ_AUTO_SOURCES")]
+    pub auto_sources: Option<usize>,
+    /// Search every installed source, ignoring the project's `.blz.toml` list.
+    #[arg(long)]
+    pub all_sources: bool,
+
+    /// Tolerate typos: match terms within N edits (`--fuzzy=0..2`, bare flag means 1).
+    ///
+    /// Quoted phrases still match exactly. Defaults to `defaults.fuzzy` in config.
+    #[arg(
+        long,
+        value_name = "N",
+        num_args = 0..=1,
+        default_missing_value = "1",
+        require_equals = true,
+        value_parser = clap::value_parser!(u8).range(0..=2)
+    )]
+    pub fuzzy: Option<u8>,
+    /// Rank by meaning instead of keywords, using a local embedding model.
+    ///
+    /// Requires blz built with the `semantic` feature. The model downloads
+    /// once, and a vector index is built beside each source's search index on
+    /// first use.
+    #[arg(long, conflicts_with_all = ["hybrid", "headings_only"])]
+    pub semantic: bool,
+    /// Blend keyword (BM25) and embedding similarity scores.
+    #[arg(long, conflicts_with = "headings_only")]
+    pub hybrid: bool,
+
     /// Maximum number of results per page (search mode only)
     #[arg(short = 'n', long, value_name = "COUNT", conflicts_with = "all")]
     pub limit: Option<usize>,
@@ -79,13 +118,22 @@ pub struct FindArgs {
     pub format: FormatArg,
 
     /// Additional columns to include in text output (search mode only)
-    #[arg(long = "show", value_enum, value_delimiter = ',', env = "BLZ_SHOW")]
+    #[arg(
+        long = "show",
+        value_parser = ShowComponentParser,
+        value_delimiter = ',',
+        env = "BLZ_SHOW"
+    )]
     pub show: Vec<ShowComponent>,
 
     /// Hide the summary/footer line (search mode only)
     #[arg(long = "no-summary")]
     pub no_summary: bool,
 
+    /// Zero timings, round scores to the display precision, and break ties stably (for snapshots)
+    #[arg(long)]
+    pub deterministic: bool,
+
     /// Number of decimal places to show for scores (0-4, search mode only)
     #[arg(
         long = "score-precision",
@@ -115,6 +163,21 @@ pub struct FindArgs {
     )]
     pub max_chars: Option<usize>,
 
+    /// Stop adding hits to a page once their estimated tokens would exceed N
+    ///
+    /// The first hit is always kept. Token counts use the same estimate as `--show tokens`.
+    #[arg(
+        long = "max-tokens",
+        value_name = "N",
+        env = "BLZ_MAX_TOKENS",
+        value_parser = clap::value_parser!(usize)
+    )]
+    pub max_tokens: Option<usize>,
+
+    /// Keep JSON/JSONL snippets as indexed instead of widening them to whole code fences
+    #[arg(long)]
+    pub no_fence_expand: bool,
+
     /// Print LINES lines of context (both before and after match). Same as -C.
     ///
     /// Use "all" to expand to the full heading section containing the match.
@@ -190,6 +253,14 @@ pub struct FindArgs {
     #[arg(long = "headings-only", display_order = 35)]
     pub headings_only: bool,
 
+    /// Restrict matches to fenced code blocks, skipping prose (search mode only)
+    #[arg(long, conflicts_with_all = ["headings_only", "semantic", "hybrid"], display_order = 36)]
+    pub code: bool,
+
+    /// Only match code in this language (e.g. `ts`, `python`); requires --code
+    #[arg(long, value_name = "LANG", requires = "code", display_order = 37)]
+    pub lang: Option<String>,
+
     /// Don't save this search to history (search mode only)
     #[arg(long = "no-history")]
     pub no_history: bool,
@@ -201,6 +272,10 @@ pub struct FindArgs {
     /// Show detailed timing breakdown for performance analysis
     #[arg(long)]
     pub timing: bool,
+
+    /// Search or retrieve from a remote `blz serve` endpoint instead of the local cache
+    #[arg(long, value_name = "URL", env = "BLZ_REMOTE")]
+    pub remote: Option<String>,
 }
 
 /// Detect if input matches citation format: `alias:digits-digits[,digits-digits]*`
@@ -249,9 +324,39 @@ fn is_citation(input: &str) -> bool {
     true
 }
 
+/// Detect if input matches anchor format: `alias#anchor`
+///
+/// Examples:
+/// - `bun#installation`
+/// - `react#use-effect`
+fn parse_anchor(input: &str) -> Option<(&str, &str)> {
+    let (alias, anchor) = input.split_once('#')?;
+
+    if alias.is_empty()
+        || !alias
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+    {
+        return None;
+    }
+
+    if anchor.is_empty() || anchor.chars().any(char::is_whitespace) {
+        return None;
+    }
+
+    Some((alias, anchor))
+}
+
+/// Detect if input is an http(s) URL (e.g., `https://bun.sh/llms.txt`).
+fn is_url(input: &str) -> bool {
+    url::Url::parse(input).is_ok_and(|url| matches!(url.scheme(), "http" | "https"))
+}
+
 #[derive(Debug)]
 enum FindMode {
     Retrieve(Vec<RequestSpec>),
+    Anchor { alias: String, anchor: String },
+    AddUrl(String),
     Search(String),
 }
 
@@ -290,6 +395,26 @@ fn classify_inputs(inputs: &[String]) -> Result<FindMode> {
     Ok(FindMode::Search(query))
 }
 
+/// Classify inputs for the bare `blz <input>` default command.
+///
+/// Extends [`classify_inputs`] with single-input anchor and URL detection.
+fn classify_default_inputs(inputs: &[String]) -> Result<FindMode> {
+    if let [input] = inputs {
+        let input = input.trim();
+        if is_url(input) {
+            return Ok(FindMode::AddUrl(input.to_string()));
+        }
+        if let Some((alias, anchor)) = parse_anchor(input) {
+            return Ok(FindMode::Anchor {
+                alias: alias.to_string(),
+                anchor: anchor.to_string(),
+            });
+        }
+    }
+
+    classify_inputs(inputs)
+}
+
 /// Execute the find command with smart pattern-based dispatch
 ///
 /// # Pattern Detection
@@ -317,7 +442,19 @@ pub async fn execute(
     metrics: PerformanceMetrics,
     resource_monitor: Option<&mut ResourceMonitor>,
 ) -> Result<()> {
-    match classify_inputs(inputs)? {
+    let mode = classify_inputs(inputs)?;
+    execute_mode(mode, sources, config, prefs, metrics, resource_monitor).await
+}
+
+async fn execute_mode(
+    mode: FindMode,
+    sources: &[String],
+    config: &QueryExecutionConfig,
+    prefs: Option<&mut CliPreferences>,
+    metrics: PerformanceMetrics,
+    resource_monitor: Option<&mut ResourceMonitor>,
+) -> Result<()> {
+    match mode {
         FindMode::Retrieve(specs) => {
             // Retrieve mode: delegate to get command logic
             // Note: heading_filter is ignored in retrieve mode
@@ -329,9 +466,23 @@ pub async fn execute(
                 config.content.max_lines,
                 config.display.format,
                 config.content.copy,
+                config.search.remote.as_deref(),
+                None,
+                None,
             )
             .await
         },
+        FindMode::Anchor { .. } if config.search.remote.is_some() => {
+            bail!("Anchor lookups are not supported with --remote; use alias:lines instead")
+        },
+        FindMode::Anchor { alias, anchor } => {
+            let context = match config.content.context {
+                Some(ContextMode::Symmetric(lines)) => Some(lines),
+                _ => None,
+            };
+            get_by_anchor(&alias, &anchor, context, config.display.format).await
+        },
+        FindMode::AddUrl(url) => offer_add_url(&url, config.display.quiet, metrics).await,
         FindMode::Search(query) => {
             // Search mode: delegate to query command's internal execution
             query_execute_internal(&query, sources, config, prefs, metrics, resource_monitor).await
@@ -339,6 +490,38 @@ pub async fn execute(
     }
 }
 
+/// Offer to add a URL passed to the default command as a new source.
+///
+/// Prompts interactively when possible; otherwise prints the equivalent `blz add`
+/// invocation so agents can run it explicitly.
+async fn offer_add_url(url: &str, quiet: bool, metrics: PerformanceMetrics) -> Result<()> {
+    let alias = alias_for_url(url);
+
+    let force_non_interactive = std::env::var_os("BLZ_FORCE_NON_INTERACTIVE").is_some();
+    let interactive = !quiet && !force_non_interactive && confirm::is_interactive();
+
+    if !interactive {
+        eprintln!("'{url}' looks like a URL. To add it as a source, run:\n  blz add {alias} {url}");
+        return Ok(());
+    }
+
+    let prompt = format!("Add {url} as source '{alias}'?");
+    if !Confirm::new(&prompt).with_default(true).prompt()? {
+        return Ok(());
+    }
+
+    let request = AddRequest::new(
+        alias,
+        url.to_string(),
+        DescriptorInput::default(),
+        false,
+        quiet,
+        metrics,
+        false,
+    );
+    add_source(request).await
+}
+
 /// Parse heading level filter from string.
 fn parse_heading_filter(filter_str: Option<&str>) -> Result<Option<HeadingLevelFilter>> {
     filter_str
@@ -354,7 +537,7 @@ fn parse_heading_filter(filter_str: Option<&str>) -> Result<Option<HeadingLevelF
 // ============================================================================
 
 /// Dispatch a Find command variant, handling destructuring internally.
-#[allow(clippy::too_many_lines, deprecated)]
+#[allow(deprecated)]
 pub async fn dispatch(
     cmd: Commands,
     quiet: bool,
@@ -372,10 +555,40 @@ pub async fn dispatch(
         );
     }
 
+    let config = build_config(&args, quiet)?;
+
+    execute(
+        &args.inputs,
+        &args.sources,
+        &config,
+        Some(prefs),
+        metrics,
+        None,
+    )
+    .await
+}
+
+/// Dispatch the bare `blz <input>` default command.
+///
+/// Unlike `find`, this is not deprecated and also recognizes anchors and URLs.
+pub async fn dispatch_default(
+    args: FindArgs,
+    quiet: bool,
+    prefs: &mut CliPreferences,
+    metrics: PerformanceMetrics,
+) -> Result<()> {
+    let config = build_config(&args, quiet)?;
+    let mode = classify_default_inputs(&args.inputs)?;
+
+    execute_mode(mode, &args.sources, &config, Some(prefs), metrics, None).await
+}
+
+/// Build the shared execution config from find-style arguments.
+fn build_config(args: &FindArgs, quiet: bool) -> Result<QueryExecutionConfig> {
     let resolved_format = args.format.resolve(quiet);
     let merged_context = merge_context_flags(
-        args.context,
-        args.context_deprecated,
+        args.context.clone(),
+        args.context_deprecated.clone(),
         args.after_context,
         args.before_context,
     );
@@ -397,19 +610,28 @@ pub async fn dispatch(
         .with_top_percentile(args.top)
         .with_heading_filter(heading_filter)
         .with_headings_only(args.headings_only)
+        .with_code(args.code, args.lang.clone())
         .with_last(false) // find command doesn't support --last flag
-        .with_no_history(args.no_history);
+        .with_no_history(args.no_history)
+        .with_remote(args.remote.clone())
+        .with_auto_sources(args.auto_sources)
+        .with_all_sources(args.all_sources)
+        .with_fuzzy(args.fuzzy)
+        .with_ranking(args.semantic, args.hybrid);
 
     let display = DisplayConfig::new(resolved_format)
-        .with_show(args.show)
+        .with_show(args.show.clone())
         .with_no_summary(args.no_summary)
+        .with_deterministic(args.deterministic)
         .with_timing(args.timing)
         .with_quiet(quiet);
 
     let snippet = SnippetConfig::new()
         .with_lines(args.snippet_lines)
         .with_max_chars(args.max_chars.map_or(200, clamp_max_chars))
-        .with_score_precision(args.score_precision);
+        .with_score_precision(args.score_precision)
+        .with_fence_expand(!args.no_fence_expand)
+        .with_max_tokens(args.max_tokens);
 
     let content = ContentConfig::new()
         .with_context(merged_context)
@@ -417,17 +639,7 @@ pub async fn dispatch(
         .with_copy(args.copy)
         .with_block(args.block);
 
-    let config = QueryExecutionConfig::new(search, display, snippet, content);
-
-    execute(
-        &args.inputs,
-        &args.sources,
-        &config,
-        Some(prefs),
-        metrics,
-        None,
-    )
-    .await
+    Ok(QueryExecutionConfig::new(search, display, snippet, content))
 }
 
 #[cfg(test)]
@@ -469,6 +681,57 @@ mod tests {
         );
     }
 
+    #[test]
+    fn classify_default_inputs_detects_anchor() {
+        let inputs = vec!["bun#installation".to_string()];
+        let mode = classify_default_inputs(&inputs).expect("should classify as anchor mode");
+        assert!(matches!(
+            mode,
+            FindMode::Anchor { ref alias, ref anchor } if alias == "bun" && anchor == "installation"
+        ));
+    }
+
+    #[test]
+    fn classify_default_inputs_detects_url() {
+        let inputs = vec!["https://bun.sh/llms.txt".to_string()];
+        let mode = classify_default_inputs(&inputs).expect("should classify as url mode");
+        assert!(matches!(mode, FindMode::AddUrl(ref url) if url == "https://bun.sh/llms.txt"));
+    }
+
+    #[test]
+    fn classify_default_inputs_falls_back_to_find_rules() {
+        let inputs = vec!["bun:1-2".to_string()];
+        let mode = classify_default_inputs(&inputs).expect("citation should retrieve");
+        assert!(matches!(mode, FindMode::Retrieve(specs) if specs.len() == 1));
+
+        let inputs = vec!["C#".to_string(), "async".to_string()];
+        let mode = classify_default_inputs(&inputs).expect("multiword should search");
+        assert!(matches!(mode, FindMode::Search(ref query) if query == "C# async"));
+    }
+
+    #[test]
+    fn test_anchor_detection() {
+        assert_eq!(parse_anchor("bun#install"), Some(("bun", "install")));
+        assert_eq!(
+            parse_anchor("vue-router#guide"),
+            Some(("vue-router", "guide"))
+        );
+        assert_eq!(parse_anchor("c#"), None);
+        assert_eq!(parse_anchor("#install"), None);
+        assert_eq!(parse_anchor("Bun#install"), None);
+        assert_eq!(parse_anchor("bun#two words"), None);
+        assert_eq!(parse_anchor("plain query"), None);
+    }
+
+    #[test]
+    fn test_url_detection() {
+        assert!(is_url("https://bun.sh/llms.txt"));
+        assert!(is_url("http://localhost:3000/llms.txt"));
+        assert!(!is_url("bun:120-142"));
+        assert!(!is_url("file:///tmp/llms.txt"));
+        assert!(!is_url("react hooks"));
+    }
+
     #[test]
     fn test_citation_detection() {
         // Valid citations
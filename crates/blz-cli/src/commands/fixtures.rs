@@ -0,0 +1,237 @@
+//! Synthetic fixture corpora for integration tests and benchmarks.
+//!
+//! `blz fixtures` generates reproducible llms.txt documents with
+//! [`blz_core::fixtures`] and indexes them into a throwaway data and config
+//! directory, like `blz demo`. Wrapper projects point `BLZ_DATA_DIR` and
+//! `BLZ_CONFIG_DIR` at the result to test against realistic corpora, and
+//! blz's own benches generate the same documents from the same seeds.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use base64::{Engine as _, engine::general_purpose::STANDARD as B64};
+use blz_core::fixtures::{self, FixtureSpec};
+use blz_core::{PerformanceMetrics, SourceVariant, Storage, profile};
+use clap::Args;
+use colored::Colorize;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use super::debug_args::shell_words;
+use super::docs_bundle::{EmbeddedSource, install_embedded};
+use crate::output::OutputFormat;
+use crate::utils::cli_args::FormatArg;
+use crate::utils::toc::count_headings;
+
+/// Arguments for `blz fixtures`.
+#[derive(Args, Clone, Debug)]
+pub struct FixturesArgs {
+    /// Sandbox directory (defaults to a fixed directory under the system temp dir)
+    #[arg(long, value_name = "PATH")]
+    pub dir: Option<PathBuf>,
+    /// Number of fixture sources to generate
+    #[arg(long, default_value_t = 3, value_name = "N")]
+    pub sources: usize,
+    /// Headed sections per source
+    #[arg(long, default_value_t = 200, value_name = "N")]
+    pub sections: usize,
+    /// Deepest heading nesting below the title (1-5)
+    #[arg(long, default_value_t = 3, value_name = "N", value_parser = clap::value_parser!(u8).range(1..=5))]
+    pub depth: u8,
+    /// Paragraphs or code blocks per section
+    #[arg(long, default_value_t = 3, value_name = "N")]
+    pub blocks: usize,
+    /// Fraction of blocks that are fenced code (0.0-1.0)
+    #[arg(long, default_value_t = 0.25, value_name = "RATIO", value_parser = parse_density)]
+    pub code_density: f64,
+    /// Seed for the first source; each further source adds one
+    #[arg(long, default_value_t = 42)]
+    pub seed: u64,
+    /// Delete the sandbox instead of creating it
+    #[arg(long)]
+    pub clean: bool,
+    /// Output format
+    #[command(flatten)]
+    pub format: FormatArg,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct FixtureSource {
+    alias: String,
+    seed: u64,
+    file: PathBuf,
+    bytes: usize,
+    headings: usize,
+    lines: usize,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct FixturesReport {
+    dir: PathBuf,
+    sources: Vec<FixtureSource>,
+    env: BTreeMap<&'static str, PathBuf>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CleanReport {
+    dir: PathBuf,
+    removed: bool,
+}
+
+fn parse_density(value: &str) -> Result<f64, String> {
+    let density: f64 = value
+        .parse()
+        .map_err(|_| format!("'{value}' is not a number"))?;
+    if (0.0..=1.0).contains(&density) {
+        Ok(density)
+    } else {
+        Err(format!(
+            "code density must be between 0.0 and 1.0, got {value}"
+        ))
+    }
+}
+
+/// Execute `blz fixtures`.
+///
+/// # Errors
+///
+/// Returns an error if the sandbox cannot be created, indexed, or removed.
+pub fn execute(args: &FixturesArgs, quiet: bool, metrics: &PerformanceMetrics) -> Result<()> {
+    let format = args.format.resolve(quiet);
+    let dir = args.dir.clone().unwrap_or_else(default_dir);
+
+    if args.clean {
+        let removed = dir.exists();
+        if removed {
+            std::fs::remove_dir_all(&dir)
+                .with_context(|| format!("failed to remove {}", dir.display()))?;
+        }
+        let report = CleanReport { dir, removed };
+        match format {
+            OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+            OutputFormat::Jsonl | OutputFormat::Raw => {
+                println!("{}", serde_json::to_string(&report)?);
+            },
+            OutputFormat::Text | OutputFormat::Markdown => {
+                if report.removed {
+                    println!("Removed fixture sandbox {}", report.dir.display());
+                } else if !quiet {
+                    println!("No fixture sandbox at {}", report.dir.display());
+                }
+            },
+        }
+        return Ok(());
+    }
+
+    let report = install(&dir, args, metrics)?;
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+        OutputFormat::Jsonl => {
+            for source in &report.sources {
+                println!("{}", serde_json::to_string(source)?);
+            }
+        },
+        OutputFormat::Raw => println!("{}", serde_json::to_string(&report)?),
+        OutputFormat::Text | OutputFormat::Markdown => print_text_report(&report),
+    }
+    Ok(())
+}
+
+/// Fixed sandbox location so repeated runs reuse the same directory.
+fn default_dir() -> PathBuf {
+    std::env::temp_dir().join(format!("{}-fixtures", profile::app_dir_slug()))
+}
+
+/// Generate, write, and index every fixture source.
+fn install(
+    dir: &Path,
+    args: &FixturesArgs,
+    metrics: &PerformanceMetrics,
+) -> Result<FixturesReport> {
+    let data_dir = dir.join("data");
+    let config_dir = dir.join("config");
+    let files_dir = dir.join("fixtures");
+    std::fs::create_dir_all(&files_dir)
+        .with_context(|| format!("failed to create {}", files_dir.display()))?;
+    let storage = Storage::with_paths(data_dir.clone(), config_dir.clone())?;
+
+    let mut sources = Vec::with_capacity(args.sources);
+    for n in 1..=args.sources {
+        let alias = format!("fixture-{n}");
+        let seed = args.seed.wrapping_add((n - 1) as u64);
+        let content = fixtures::generate(&FixtureSpec {
+            sections: args.sections,
+            depth: args.depth,
+            blocks_per_section: args.blocks,
+            code_density: args.code_density,
+            seed,
+        });
+        let file = files_dir.join(format!("{alias}.txt"));
+        std::fs::write(&file, &content)
+            .with_context(|| format!("failed to write {}", file.display()))?;
+
+        let sha256 = B64.encode(Sha256::digest(content.as_bytes()));
+        let url = format!("blz://fixtures/{alias}?seed={seed}");
+        let name = format!("Fixture {n}");
+        let description = format!("Synthetic fixture corpus (seed {seed}).");
+        install_embedded(
+            &storage,
+            &EmbeddedSource {
+                alias: &alias,
+                name: &name,
+                url: &url,
+                content: &content,
+                sha256: &sha256,
+                variant: SourceVariant::Llms,
+                aliases: &[],
+                tags: &["fixture"],
+                description: &description,
+                category: "fixture",
+            },
+            metrics.clone(),
+        )
+        .with_context(|| format!("failed to install fixture source '{alias}'"))?;
+
+        let llms_json = storage.load_llms_json(&alias)?;
+        sources.push(FixtureSource {
+            alias,
+            seed,
+            file,
+            bytes: content.len(),
+            headings: count_headings(&llms_json.toc),
+            lines: llms_json.line_index.total_lines,
+        });
+    }
+
+    Ok(FixturesReport {
+        dir: dir.to_path_buf(),
+        sources,
+        env: BTreeMap::from([("BLZ_CONFIG_DIR", config_dir), ("BLZ_DATA_DIR", data_dir)]),
+    })
+}
+
+fn print_text_report(report: &FixturesReport) {
+    println!(
+        "{} {}",
+        "Fixture sandbox ready:".green().bold(),
+        report.dir.display()
+    );
+    for source in &report.sources {
+        println!(
+            "  {} (seed {}): {} headings, {} lines",
+            source.alias, source.seed, source.headings, source.lines
+        );
+    }
+    println!();
+    println!("Point blz at it in this shell:");
+    let assignments: Vec<String> = report
+        .env
+        .iter()
+        .map(|(key, value)| format!("{key}={}", value.display()))
+        .collect();
+    println!("  export {}", shell_words(&assignments));
+}
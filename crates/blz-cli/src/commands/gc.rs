@@ -0,0 +1,113 @@
+//! Garbage collection (`blz gc`)
+//!
+//! Removes cache data no command reads anymore: orphaned source directories
+//! and their indices, snapshot archives beyond `max_archives`, unreferenced
+//! version objects, leftover temp files, and anchor maps that fail to parse.
+//! See [`blz_core::gc`] for what counts as garbage.
+
+use anyhow::Result;
+use blz_core::gc::{self, Garbage, GcOptions};
+use blz_core::numeric::format_bytes;
+use blz_core::{Config, Storage};
+use clap::Args;
+use colored::Colorize;
+use serde::Serialize;
+
+use crate::output::OutputFormat;
+use crate::utils::cli_args::FormatArg;
+
+/// Arguments for `blz gc`.
+#[derive(Args, Clone, Debug)]
+pub struct GcArgs {
+    /// List what would be removed without deleting anything
+    #[arg(long)]
+    pub dry_run: bool,
+    /// Output format
+    #[command(flatten)]
+    pub format: FormatArg,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GcReport {
+    dry_run: bool,
+    items: Vec<Garbage>,
+    reclaimed_bytes: u64,
+}
+
+/// Options matching the user's configuration (`defaults.max_archives`).
+pub fn configured_options() -> GcOptions {
+    let mut options = GcOptions::default();
+    if let Ok(config) = Config::load() {
+        options.keep_archives = config.defaults.max_archives;
+    }
+    options
+}
+
+/// Execute `blz gc`.
+///
+/// # Errors
+///
+/// Returns an error if the cache cannot be scanned or garbage cannot be
+/// removed.
+pub fn execute(args: &GcArgs, quiet: bool) -> Result<()> {
+    let format = args.format.resolve(quiet);
+    let storage = Storage::new()?;
+    let items = gc::scan(&storage, configured_options())?;
+    let reclaimed_bytes = if args.dry_run {
+        items.iter().map(|item| item.bytes).sum()
+    } else {
+        gc::remove(&storage, &items)?
+    };
+    let report = GcReport {
+        dry_run: args.dry_run,
+        items,
+        reclaimed_bytes,
+    };
+
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+        OutputFormat::Jsonl | OutputFormat::Raw => {
+            println!("{}", serde_json::to_string(&report)?);
+        },
+        OutputFormat::Text | OutputFormat::Markdown => {
+            if !quiet {
+                print_text_report(&storage, &report);
+            }
+        },
+    }
+    Ok(())
+}
+
+fn print_text_report(storage: &Storage, report: &GcReport) {
+    if report.items.is_empty() {
+        println!("{} Nothing to collect", "✓".green());
+        return;
+    }
+    for item in &report.items {
+        let path = item
+            .path
+            .strip_prefix(storage.root_dir())
+            .unwrap_or(&item.path);
+        println!(
+            "  {:<20} {} {}",
+            item.kind.label(),
+            path.display(),
+            format!("({})", format_bytes(item.bytes)).bright_black()
+        );
+    }
+    if report.dry_run {
+        println!(
+            "Would reclaim {} from {} item(s); run `blz gc` to remove them",
+            format_bytes(report.reclaimed_bytes),
+            report.items.len()
+        );
+    } else {
+        println!(
+            "{} Reclaimed {} from {} item(s)",
+            "✓".green(),
+            format_bytes(report.reclaimed_bytes),
+            report.items.len()
+        );
+    }
+}
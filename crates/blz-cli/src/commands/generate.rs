@@ -0,0 +1,300 @@
+//! Generate command: build a source from a docs site's sitemap.
+//!
+//! Sites without an llms.txt usually still publish a `sitemap.xml`. `blz
+//! generate` reads it (from a sitemap URL, or by probing a domain), keeps the
+//! URLs that pass `--include`/`--exclude`, scrapes each page to markdown
+//! through Firecrawl, and indexes the pages as one synthetic llms-full.txt.
+//!
+//! Every page is recorded in the source's `generate.json` together with its
+//! sitemap `lastmod`, so `blz sync` only rescrapes pages whose `lastmod`
+//! moved, plus the ones that failed last time.
+//!
+//! Pages the site's robots.txt disallows are left out, and scrapes of one
+//! host are spaced by its `Crawl-delay`, unless `--ignore-robots` is passed.
+
+use anyhow::{Context, Result, bail};
+use blz_core::discovery::{
+    ProbeTargets, RobotsPolicy, derive_alias, fetch_sitemap_with, probe_url_with,
+};
+use blz_core::{PerformanceMetrics, Storage};
+use clap::Args;
+use colored::Colorize;
+use indicatif::{ProgressBar, ProgressStyle};
+use url::Url;
+
+use super::add::add_generated_source;
+use super::sync::{
+    GenerateManifest, apply_scrape_results, assemble_document, drop_disallowed,
+    save_generate_manifest,
+};
+use crate::generate::{
+    FirecrawlScraper, GenerateOrchestrator, ScrapeResults, Scraper, UrlWithLastmod,
+};
+use crate::utils::shutdown;
+use crate::utils::validation::{normalize_alias, validate_alias};
+
+/// Arguments for `blz generate`.
+#[derive(Args, Clone, Debug)]
+pub struct GenerateArgs {
+    /// Domain (`hono.dev`) or sitemap URL (`https://hono.dev/sitemap.xml`)
+    #[arg(value_name = "URL")]
+    pub url: String,
+
+    /// Alias for the new source (defaults to one derived from the domain)
+    #[arg(long)]
+    pub alias: Option<String>,
+
+    /// Only scrape URLs matching a pattern (repeatable)
+    ///
+    /// Patterns without `*` match as substrings; `*` matches any run of
+    /// characters.
+    #[arg(long, value_name = "PATTERN")]
+    pub include: Vec<String>,
+
+    /// Skip URLs matching a pattern (repeatable)
+    #[arg(long, value_name = "PATTERN")]
+    pub exclude: Vec<String>,
+
+    /// Scrape at most this many pages, in sitemap order
+    #[arg(
+        long,
+        value_name = "N",
+        default_value_t = 500,
+        value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..)
+    )]
+    pub max_pages: usize,
+
+    /// Pages scraped at the same time
+    #[arg(
+        long,
+        value_name = "N",
+        default_value_t = 5,
+        value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..=20)
+    )]
+    pub concurrency: usize,
+
+    /// List the pages that would be scraped without scraping them
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Scrape pages robots.txt disallows, without waiting out `Crawl-delay`
+    ///
+    /// Remembered for later syncs of the source.
+    #[arg(long)]
+    pub ignore_robots: bool,
+}
+
+/// Execute `blz generate`.
+///
+/// # Errors
+///
+/// Returns an error if the alias is taken, no sitemap is found, no pages pass
+/// the filters, no Firecrawl backend is configured, or every scrape fails.
+pub async fn execute(args: GenerateArgs, quiet: bool, metrics: PerformanceMetrics) -> Result<()> {
+    let alias = normalize_alias(
+        &args
+            .alias
+            .clone()
+            .unwrap_or_else(|| derive_alias(&host_of(&args.url))),
+    );
+    validate_alias(&alias)?;
+    let storage = Storage::new()?;
+    if storage.exists(&alias) {
+        bail!(
+            "Source '{alias}' already exists. Use 'blz sync {alias}' to rescrape it, \
+             or pass --alias to choose a different name."
+        );
+    }
+
+    let sitemap_url = resolve_sitemap(&args.url, args.ignore_robots).await?;
+    let mut manifest = GenerateManifest::new(sitemap_url.clone());
+    manifest.include.clone_from(&args.include);
+    manifest.exclude.clone_from(&args.exclude);
+    manifest.max_pages = Some(args.max_pages);
+    manifest.ignore_robots = args.ignore_robots;
+    let robots = manifest.robots_policy()?;
+
+    if !quiet {
+        println!("Reading {}...", sitemap_url.cyan());
+    }
+    let mut entries = fetch_sitemap_with(&sitemap_url, &manifest.sitemap_options())
+        .await
+        .with_context(|| format!("Failed to read sitemap {sitemap_url}"))?;
+    if entries.is_empty() {
+        bail!("Sitemap {sitemap_url} lists no pages matching the --include/--exclude patterns");
+    }
+    let disallowed = drop_disallowed(&mut entries, &robots).await;
+    if entries.is_empty() {
+        bail!(
+            "robots.txt disallows every matching page in {sitemap_url}; \
+             pass --ignore-robots to scrape them anyway"
+        );
+    }
+    if disallowed > 0 && !quiet {
+        println!("  {disallowed} URLs skipped (disallowed by robots.txt)");
+    }
+    let listed = entries.len();
+    if let Some(max_pages) = manifest.max_pages {
+        entries.truncate(max_pages);
+    }
+    if !quiet {
+        if entries.len() < listed {
+            println!(
+                "  {listed} URLs match; scraping the first {} (--max-pages)",
+                entries.len()
+            );
+        } else {
+            println!("  {listed} URLs match");
+        }
+    }
+
+    if args.dry_run {
+        for entry in &entries {
+            println!("{}", entry.url);
+        }
+        return Ok(());
+    }
+
+    let scraper = FirecrawlScraper::resolve().await?;
+    if !quiet {
+        println!(
+            "Scraping {} pages via {}...",
+            entries.len(),
+            scraper.describe()
+        );
+    }
+    let urls: Vec<UrlWithLastmod> = entries
+        .iter()
+        .map(|entry| UrlWithLastmod::new(entry.url.clone()).with_lastmod(entry.lastmod))
+        .collect();
+    let results = scrape_with_progress(&urls, scraper, args.concurrency, robots, quiet).await?;
+    let failed = results.failed.len();
+
+    apply_scrape_results(&mut manifest, &entries, results);
+    let assembled = assemble_document(&manifest.pages);
+    manifest.total_lines = assembled.stats.total_lines;
+
+    add_generated_source(&alias, &sitemap_url, assembled.content, metrics, quiet)?;
+    save_generate_manifest(&storage, &alias, &manifest)?;
+
+    if failed > 0 && !quiet {
+        println!(
+            "  {} page(s) failed to scrape; 'blz sync {alias}' retries them",
+            failed.to_string().yellow()
+        );
+    }
+    Ok(())
+}
+
+/// Host part of a domain or URL argument.
+fn host_of(input: &str) -> String {
+    Url::parse(input)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_string))
+        .unwrap_or_else(|| input.split('/').next().unwrap_or(input).to_string())
+}
+
+/// Use a sitemap URL as given; otherwise probe the site for one.
+async fn resolve_sitemap(input: &str, ignore_robots: bool) -> Result<String> {
+    let is_sitemap =
+        Url::parse(input).is_ok_and(|url| url.path().to_ascii_lowercase().ends_with(".xml"));
+    if is_sitemap {
+        return Ok(input.to_string());
+    }
+
+    let targets = ProbeTargets::default().ignoring_robots(ignore_robots);
+    let probe = probe_url_with(input, &targets)
+        .await
+        .with_context(|| format!("Failed to probe {input}"))?;
+    probe.sitemap_url.ok_or_else(|| {
+        anyhow::anyhow!(
+            "No sitemap.xml found for '{input}'. Pass the sitemap URL directly \
+             (e.g. 'blz generate https://{}/sitemap.xml').",
+            host_of(input)
+        )
+    })
+}
+
+/// Scrape `urls` with a progress bar.
+///
+/// On SIGINT/SIGTERM, pages being scraped finish and the rest are skipped;
+/// the pages already scraped are still returned.
+///
+/// # Errors
+///
+/// Returns an error if every attempted scrape failed.
+async fn scrape_with_progress(
+    urls: &[UrlWithLastmod],
+    scraper: impl Scraper,
+    concurrency: usize,
+    robots: RobotsPolicy,
+    quiet: bool,
+) -> Result<ScrapeResults> {
+    let total = urls.len();
+    shutdown::install();
+
+    let pb = if quiet {
+        ProgressBar::hidden()
+    } else {
+        let pb = ProgressBar::new(total as u64);
+        pb.set_style(
+            ProgressStyle::default_bar()
+                .template("[{bar:40.cyan/blue}] {pos}/{len} {msg}")
+                .unwrap_or_else(|_| ProgressStyle::default_bar())
+                .progress_chars("=>-"),
+        );
+        pb.set_message("Scraping...");
+        pb
+    };
+
+    let pb_clone = pb.clone();
+    let orchestrator = GenerateOrchestrator::new(scraper, concurrency)
+        .with_robots(robots)
+        .with_progress(move |done, _total| pb_clone.set_position(done as u64));
+
+    let results = orchestrator.scrape_all(urls).await;
+    pb.finish_and_clear();
+
+    if !results.skipped.is_empty() && !quiet {
+        eprintln!(
+            "Interrupted: scraped {} of {total} page(s), {} failed, {} skipped",
+            results.successful.len(),
+            results.failed.len(),
+            results.skipped.len()
+        );
+    }
+
+    if results.successful.is_empty() {
+        if results.failed.is_empty() {
+            bail!("Interrupted before any page was scraped");
+        }
+        let sample_errors: Vec<_> = results
+            .failed
+            .iter()
+            .take(3)
+            .map(|f| format!("  - {}: {}", f.url, f.error))
+            .collect();
+        bail!(
+            "All {} scrape(s) failed. Sample errors:\n{}",
+            results.failed.len(),
+            sample_errors.join("\n")
+        );
+    }
+
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_host_of_accepts_domains_and_urls() {
+        assert_eq!(host_of("hono.dev"), "hono.dev");
+        assert_eq!(host_of("hono.dev/docs"), "hono.dev");
+        assert_eq!(
+            host_of("https://docs.hono.dev/sitemap.xml"),
+            "docs.hono.dev"
+        );
+    }
+}
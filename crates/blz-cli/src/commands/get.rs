@@ -1,15 +1,23 @@
 //! Get command implementation for retrieving specific lines from sources
 
 use anyhow::{Context, Result};
-use blz_core::Storage;
+use blz_core::{ContentVersion, MarkdownParser, Storage, TocEntry};
+use chrono::Utc;
 use colored::Colorize;
-use std::collections::BTreeSet;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
 use std::convert::TryFrom;
+use std::io::Write;
 use std::num::NonZeroUsize;
 use std::time::Instant;
 
 use crate::cli::{Commands, ContextMode, merge_context_flags};
-use crate::output::OutputFormat;
+use crate::error::CliError;
+use crate::i18n::{self, Message};
+use crate::output::{OutputFormat, PipeWriter};
+
+use super::remote::RemoteClient;
 
 pub mod json_contract;
 use self::json_contract::{
@@ -17,10 +25,13 @@ use self::json_contract::{
     SnippetRequest,
 };
 
+use crate::utils::history_log::{self, Continuation};
 use crate::utils::parsing::{LineRange, parse_line_ranges};
+use crate::utils::preferences::SectionHistoryEntry;
+use crate::utils::time_expr::parse_time_expr;
 use crate::utils::toc::{
-    BlockSlice, extract_block_slice, finalize_block_slice, find_heading_for_line,
-    heading_level_from_line,
+    BlockSlice, ORDINAL_MARKER, adjacent_section, extract_block_slice, finalize_block_slice,
+    find_entry_by_ordinal, find_heading_for_line, heading_level_from_line,
 };
 
 struct BlockResult {
@@ -30,6 +41,13 @@ struct BlockResult {
     truncated: bool,
 }
 
+/// Content a request reads: the current llms.txt or an archived version.
+struct SourceText {
+    lines: Vec<String>,
+    toc: Option<Vec<TocEntry>>,
+    checksum: Option<String>,
+}
+
 /// Parsed positional input for the `get` command.
 #[derive(Debug, Clone)]
 pub struct RequestSpec {
@@ -93,8 +111,7 @@ fn strip_heading_if_present(
 }
 
 fn compute_block_result(
-    storage: &Storage,
-    canonical: &str,
+    toc: Option<&[TocEntry]>,
     file_lines: &[String],
     ranges: &[LineRange],
     max_block_lines: Option<usize>,
@@ -104,14 +121,12 @@ fn compute_block_result(
         LineRange::Range(start, _) | LineRange::PlusCount(start, _) => *start,
     });
 
-    let llms = storage.load_llms_json(canonical).ok();
     let file_len = file_lines.len();
     let (fallback_start, user_end) = determine_fallback_bounds(ranges, target_line, file_len);
     let fallback_span = (fallback_start, file_len);
 
-    let toc_span = llms
-        .as_ref()
-        .and_then(|doc| find_heading_for_line(&doc.toc, target_line).map(|(_, span)| span));
+    let toc_span =
+        toc.and_then(|toc| find_heading_for_line(toc, target_line).map(|(_, span)| span));
     let using_toc_span = toc_span.is_some();
     let mut span = toc_span.unwrap_or(fallback_span);
     adjust_span_for_heading(file_lines, &mut span);
@@ -251,7 +266,13 @@ fn gather_requested_lines(
         .collect()
 }
 
-struct ProcessedRequest {
+/// Lines retrieved for one request spec.
+///
+/// Also the wire format for remote retrieval (`blz get --remote`), so the
+/// client can render results exactly as a local run would.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct ProcessedRequest {
     alias: String,
     canonical: String,
     heading: Option<(usize, String)>,
@@ -316,10 +337,12 @@ fn validate_and_resolve_source(storage: &Storage, alias: &str) -> Result<String>
     if !storage.exists(&canonical) {
         let available = storage.list_sources();
         if available.is_empty() {
-            anyhow::bail!(
-                "Source '{alias}' not found.\n\
-                 No sources available. Use 'blz lookup <name>' or 'blz add <alias> <url>' to add one."
-            );
+            return Err(CliError::not_found(anyhow::anyhow!(
+                "{}\n{}",
+                i18n::text(Message::SourceNotFound, &[("alias", alias)]),
+                i18n::text(Message::NoSourcesAvailable, &[])
+            ))
+            .into());
         }
         let preview = available.iter().take(8).cloned().collect::<Vec<_>>();
         let preview_str = if available.len() > preview.len() {
@@ -331,11 +354,13 @@ fn validate_and_resolve_source(storage: &Storage, alias: &str) -> Result<String>
         } else {
             preview.join(", ")
         };
-        anyhow::bail!(
-            "Source '{alias}' not found.\n\
-             Available: {preview_str}\n\
-             Hint: 'blz list' to see all, or 'blz lookup <name>' to search registries."
-        );
+        return Err(CliError::not_found(anyhow::anyhow!(
+            "{}\n{}\n{}",
+            i18n::text(Message::SourceNotFound, &[("alias", alias)]),
+            i18n::text(Message::AvailableSources, &[("sources", &preview_str)]),
+            i18n::text(Message::HintListOrLookup, &[])
+        ))
+        .into());
     }
 
     Ok(canonical)
@@ -343,14 +368,9 @@ fn validate_and_resolve_source(storage: &Storage, alias: &str) -> Result<String>
 
 /// Load source file content and return as vector of lines.
 fn load_source_file(storage: &Storage, canonical: &str) -> Result<Vec<String>> {
-    let file_path = storage.llms_txt_path(canonical)?;
-    let file_content = std::fs::read_to_string(&file_path).with_context(|| {
-        format!(
-            "Failed to read llms.txt for source '{}' at {}",
-            canonical,
-            file_path.display()
-        )
-    })?;
+    let file_content = storage
+        .load_llms_txt(canonical)
+        .with_context(|| format!("Failed to read llms.txt for source '{canonical}'"))?;
 
     Ok(file_content
         .lines()
@@ -358,6 +378,91 @@ fn load_source_file(storage: &Storage, canonical: &str) -> Result<Vec<String>> {
         .collect())
 }
 
+/// Load the content a request reads, honoring `--at`.
+fn load_source_text(storage: &Storage, canonical: &str, at: Option<&str>) -> Result<SourceText> {
+    let version = match at {
+        Some(at) => resolve_version(storage, canonical, at)?,
+        None => None,
+    };
+    let Some(version) = version else {
+        return Ok(SourceText {
+            lines: load_source_file(storage, canonical)?,
+            toc: storage.load_llms_json(canonical).ok().map(|llms| llms.toc),
+            checksum: storage
+                .load_source_metadata(canonical)
+                .ok()
+                .flatten()
+                .map(|m| m.sha256),
+        });
+    };
+
+    let content = storage
+        .load_version(canonical, &version.id)
+        .with_context(|| format!("Failed to read version {} of '{canonical}'", version.id))?;
+    // Archived versions keep only the text; rebuild the outline from it
+    let toc = MarkdownParser::new()
+        .and_then(|mut parser| parser.parse(&content))
+        .ok()
+        .map(|parsed| parsed.toc);
+    Ok(SourceText {
+        lines: content.lines().map(str::to_string).collect(),
+        toc,
+        checksum: Some(version.sha256),
+    })
+}
+
+/// Resolve `--at` to an archived version; `None` selects the current content.
+///
+/// Content SHAs are tried first, then time expressions, which select the
+/// content that was current at that moment.
+fn resolve_version(storage: &Storage, canonical: &str, at: &str) -> Result<Option<ContentVersion>> {
+    let at = at.trim();
+    let current = storage
+        .load_source_metadata(canonical)
+        .ok()
+        .flatten()
+        .map(|m| m.sha256);
+    if current.as_deref() == Some(at) {
+        return Ok(None);
+    }
+    if let Some(version) = storage.find_version(canonical, at)? {
+        return Ok(Some(version));
+    }
+    let when = parse_time_expr(at, Utc::now()).map_err(|_| {
+        CliError::not_found(anyhow::anyhow!(
+            "No version of '{canonical}' matches '{at}'. \
+             Use a timestamp (2025-01-31, 3 days ago) or a content SHA."
+        ))
+    })?;
+    Ok(storage
+        .list_versions(canonical)?
+        .into_iter()
+        .find(|version| version.archived_at > when))
+}
+
+/// Resolve a `§2.3.1` heading ordinal to that heading's line range.
+///
+/// Plain line expressions are returned unchanged.
+fn resolve_line_expression(
+    toc: Option<&[TocEntry]>,
+    canonical: &str,
+    expression: &str,
+) -> Result<String> {
+    let Some(ordinal) = expression.strip_prefix(ORDINAL_MARKER) else {
+        return Ok(expression.to_string());
+    };
+    let toc = toc.with_context(|| format!("Failed to load TOC for '{canonical}'"))?;
+    find_entry_by_ordinal(toc, ordinal.trim())
+        .map(|entry| entry.lines.clone())
+        .ok_or_else(|| {
+            CliError::not_found(anyhow::anyhow!(
+                "No heading at {ORDINAL_MARKER}{ordinal} in '{canonical}'. \
+                 Run 'blz map {canonical} --numbered' to list heading ordinals."
+            ))
+            .into()
+        })
+}
+
 /// Validate that requested line ranges are within file bounds.
 fn validate_line_ranges(ranges: &[LineRange], max_line: usize, alias: &str) -> Result<()> {
     let all_out_of_range = ranges.iter().all(|range| {
@@ -375,10 +480,11 @@ fn validate_line_ranges(ranges: &[LineRange], max_line: usize, alias: &str) -> R
             Some(LineRange::Range(s, _) | LineRange::PlusCount(s, _)) => *s,
             None => 1,
         };
-        anyhow::bail!(
+        return Err(CliError::not_found(anyhow::anyhow!(
             "Line range starts at line {first_requested}, but source '{alias}' only has {max_line} lines.\n\
              Use 'blz info {alias}' to see source details."
-        );
+        ))
+        .into());
     }
 
     Ok(())
@@ -473,20 +579,26 @@ fn process_single_request(
     after_context: usize,
     block_mode: bool,
     max_block_lines: Option<usize>,
+    at: Option<&str>,
 ) -> Result<ProcessedRequest> {
     let alias = spec.alias.trim();
     let canonical = validate_and_resolve_source(storage, alias)?;
-    let file_lines = load_source_file(storage, &canonical)?;
+    let SourceText {
+        lines: file_lines,
+        toc,
+        checksum,
+    } = load_source_text(storage, &canonical, at)?;
 
-    let ranges = parse_line_ranges(&spec.line_expression)
+    let line_expression =
+        resolve_line_expression(toc.as_deref(), &canonical, &spec.line_expression)?;
+    let ranges = parse_line_ranges(&line_expression)
         .map_err(|err| anyhow::anyhow!("Invalid line specification for '{alias}': {err}"))?;
 
     validate_line_ranges(&ranges, file_lines.len(), &canonical)?;
 
     // Compute block or line-based results
     let (heading_line, line_numbers, mut content_lines, truncated_flag) = if block_mode {
-        let result =
-            compute_block_result(storage, &canonical, &file_lines, &ranges, max_block_lines);
+        let result = compute_block_result(toc.as_deref(), &file_lines, &ranges, max_block_lines);
         (
             result.heading_line,
             result.line_numbers,
@@ -524,12 +636,6 @@ fn process_single_request(
         build_non_block_snippet_ranges(&ranges, &file_lines, before_context, after_context)?
     };
 
-    let checksum = storage
-        .load_source_metadata(&canonical)
-        .ok()
-        .flatten()
-        .map(|m| m.sha256);
-
     Ok(ProcessedRequest {
         alias: alias.to_string(),
         canonical,
@@ -556,7 +662,7 @@ const fn parse_context_mode(
 }
 
 /// Build JSON response from processed requests.
-fn build_json_response(
+pub(super) fn build_json_response(
     processed: &[ProcessedRequest],
     specs_len: usize,
     block_mode: bool,
@@ -608,17 +714,30 @@ fn build_json_response(
 }
 
 /// Output results in text format with line numbers.
-fn output_text_format(processed: &[ProcessedRequest], block_mode: bool) {
+///
+/// Stops early once the reader closes the pipe.
+fn output_text_format<W: Write>(
+    out: &mut PipeWriter<W>,
+    processed: &[ProcessedRequest],
+    block_mode: bool,
+) -> Result<()> {
     for (idx, result) in processed.iter().enumerate() {
         if idx > 0 {
-            println!();
+            out.line(format_args!(""))?;
         }
         if block_mode {
             if let Some((line_num, heading)) = &result.heading {
-                println!("{:>5} | {}", line_num.to_string().blue(), heading);
+                out.line(format_args!(
+                    "{:>5} | {}",
+                    line_num.to_string().blue(),
+                    heading
+                ))?;
             }
         }
         for (line_num, content) in &result.lines_with_content {
+            if out.is_closed() {
+                return Ok(());
+            }
             if should_skip_line(
                 *line_num,
                 result.heading.as_ref(),
@@ -627,23 +746,37 @@ fn output_text_format(processed: &[ProcessedRequest], block_mode: bool) {
             ) {
                 continue;
             }
-            println!("{:>5} | {}", line_num.to_string().blue(), content);
+            out.line(format_args!(
+                "{:>5} | {}",
+                line_num.to_string().blue(),
+                content
+            ))?;
         }
     }
+    Ok(())
 }
 
 /// Output results in raw format without line numbers.
-fn output_raw_format(processed: &[ProcessedRequest], block_mode: bool) {
+///
+/// Stops early once the reader closes the pipe.
+fn output_raw_format<W: Write>(
+    out: &mut PipeWriter<W>,
+    processed: &[ProcessedRequest],
+    block_mode: bool,
+) -> Result<()> {
     for (idx, result) in processed.iter().enumerate() {
         if idx > 0 {
-            println!();
+            out.line(format_args!(""))?;
         }
         if block_mode {
             if let Some((_, heading)) = &result.heading {
-                println!("{heading}");
+                out.line(format_args!("{heading}"))?;
             }
         }
         for (line_num, content) in &result.lines_with_content {
+            if out.is_closed() {
+                return Ok(());
+            }
             if should_skip_line(
                 *line_num,
                 result.heading.as_ref(),
@@ -652,9 +785,10 @@ fn output_raw_format(processed: &[ProcessedRequest], block_mode: bool) {
             ) {
                 continue;
             }
-            println!("{content}");
+            out.line(format_args!("{content}"))?;
         }
     }
+    Ok(())
 }
 
 /// Execute the get command to retrieve specific lines from a source
@@ -674,14 +808,25 @@ pub async fn execute(
     // Emit deprecation warning to stderr (doesn't interfere with JSON output)
     eprintln!("warning: `blz get` is deprecated, use `blz find` instead");
 
-    execute_internal(specs, context_mode, block, max_block_lines, format, copy).await
+    execute_internal(
+        specs,
+        context_mode,
+        block,
+        max_block_lines,
+        format,
+        copy,
+        None,
+        None,
+        None,
+    )
+    .await
 }
 
 /// Internal implementation of get command - called by both `get` and `find` commands
 ///
 /// This is the actual retrieval logic, separated from `execute` to allow `find` to call
 /// it without triggering the deprecation warning.
-#[allow(clippy::unused_async)] // Keep async for API consistency with execute()
+#[allow(clippy::too_many_arguments)]
 pub(super) async fn execute_internal(
     specs: &[RequestSpec],
     context_mode: Option<&crate::cli::ContextMode>,
@@ -689,6 +834,9 @@ pub(super) async fn execute_internal(
     max_block_lines: Option<usize>,
     format: OutputFormat,
     copy: bool,
+    remote: Option<&str>,
+    grep: Option<&Regex>,
+    at: Option<&str>,
 ) -> Result<()> {
     if specs.is_empty() {
         anyhow::bail!("At least one alias is required.");
@@ -696,7 +844,6 @@ pub(super) async fn execute_internal(
 
     let start = Instant::now();
     let (before_context, after_context, block_mode) = parse_context_mode(context_mode, block);
-    let storage = Storage::new()?;
 
     // Build process parameters
     let params = GetProcessParams {
@@ -704,16 +851,28 @@ pub(super) async fn execute_internal(
         after_context,
         block_mode,
         max_block_lines,
-        copy,
+        at: at.map(str::to_string),
     };
 
-    // Process all requests
-    let (processed, clipboard_segments) = process_all_requests(&storage, specs, &params)?;
+    // Process all requests, locally or on the remote server
+    let mut processed = match remote {
+        Some(remote) => RemoteClient::new(remote)?.get(specs, &params).await?,
+        None => process_all_requests(&Storage::new()?, specs, &params)?,
+    };
+    if let Some(pattern) = grep {
+        for result in &mut processed {
+            retain_matching_lines(result, pattern)?;
+        }
+    }
 
-    // Output in requested format
+    // Output in requested format, streamed so huge ranges start printing at
+    // once and a closed pipe ends the command cleanly
+    let mut out = PipeWriter::stdout();
     match format {
-        OutputFormat::Text => output_text_format(&processed, block_mode),
-        OutputFormat::Raw => output_raw_format(&processed, block_mode),
+        OutputFormat::Text | OutputFormat::Markdown => {
+            output_text_format(&mut out, &processed, block_mode)?;
+        },
+        OutputFormat::Raw => output_raw_format(&mut out, &processed, block_mode)?,
         OutputFormat::Json | OutputFormat::Jsonl => {
             let elapsed_ms = u64::try_from(start.elapsed().as_millis()).unwrap_or(0);
             let response = build_json_response(
@@ -724,68 +883,168 @@ pub(super) async fn execute_internal(
                 after_context,
                 elapsed_ms,
             );
-            if matches!(format, OutputFormat::Json) {
-                println!("{}", serde_json::to_string_pretty(&response)?);
-            } else {
-                println!("{}", serde_json::to_string(&response)?);
-            }
+            out.json(&response, matches!(format, OutputFormat::Json))?;
         },
     }
+    out.finish()?;
+
+    if remote.is_none() {
+        record_section(processed.last(), at);
+    }
 
     // Handle clipboard copy
-    if copy && !clipboard_segments.is_empty() {
-        use crate::utils::clipboard;
-        let payload = clipboard_segments.join("\n\n");
-        clipboard::copy_to_clipboard(&payload).context("Failed to copy content to clipboard")?;
+    if copy {
+        let clipboard_segments = clipboard_segments(&processed);
+        if !clipboard_segments.is_empty() {
+            use crate::utils::clipboard;
+            let payload = clipboard_segments.join("\n\n");
+            clipboard::copy_to_clipboard(&payload)
+                .context("Failed to copy content to clipboard")?;
+        }
     }
 
     Ok(())
 }
 
 /// Parameters for processing get requests.
-struct GetProcessParams {
-    before_context: usize,
-    after_context: usize,
-    block_mode: bool,
-    max_block_lines: Option<usize>,
-    copy: bool,
+pub(super) struct GetProcessParams {
+    pub(super) before_context: usize,
+    pub(super) after_context: usize,
+    pub(super) block_mode: bool,
+    pub(super) max_block_lines: Option<usize>,
+    pub(super) at: Option<String>,
 }
 
-/// Process all request specs and return processed results with optional clipboard segments.
-fn process_all_requests(
+/// Process all request specs.
+pub(super) fn process_all_requests(
     storage: &Storage,
     specs: &[RequestSpec],
     params: &GetProcessParams,
-) -> Result<(Vec<ProcessedRequest>, Vec<String>)> {
-    let mut processed = Vec::with_capacity(specs.len());
-    let mut clipboard_segments = Vec::new();
-
-    for spec in specs {
-        let result = process_single_request(
-            storage,
-            spec,
-            params.before_context,
-            params.after_context,
-            params.block_mode,
-            params.max_block_lines,
-        )?;
-
-        if params.copy {
-            let clip = result
+) -> Result<Vec<ProcessedRequest>> {
+    specs
+        .iter()
+        .map(|spec| {
+            process_single_request(
+                storage,
+                spec,
+                params.before_context,
+                params.after_context,
+                params.block_mode,
+                params.max_block_lines,
+                params.at.as_deref(),
+            )
+        })
+        .collect()
+}
+
+/// Keep only the retrieved lines matching `pattern` (`blz get --grep`).
+///
+/// Each matching line becomes its own snippet range so JSON output carries
+/// the line number of every match.
+fn retain_matching_lines(result: &mut ProcessedRequest, pattern: &Regex) -> Result<()> {
+    if result
+        .heading
+        .as_ref()
+        .is_some_and(|(_, heading)| !pattern.is_match(heading))
+    {
+        result.heading = None;
+    }
+    result
+        .lines_with_content
+        .retain(|(_, line)| pattern.is_match(line));
+
+    let matches: BTreeMap<usize, &String> = result
+        .heading
+        .iter()
+        .chain(&result.lines_with_content)
+        .map(|(line_num, line)| (*line_num, line))
+        .collect();
+    result.snippet_ranges = matches
+        .into_iter()
+        .map(|(line_num, line)| {
+            SnippetRange::try_new(nz(line_num)?, nz(line_num)?, line.as_str())
+                .map_err(anyhow::Error::from)
+        })
+        .collect::<Result<_>>()?;
+    Ok(())
+}
+
+/// Remember the span a retrieval printed so `--next-section` can continue from it.
+fn record_section(result: Option<&ProcessedRequest>, at: Option<&str>) {
+    let Some(result) = result else {
+        return;
+    };
+    let lines = result
+        .heading
+        .iter()
+        .chain(&result.lines_with_content)
+        .map(|(line_num, _)| *line_num);
+    let (Some(line_start), Some(line_end)) = (lines.clone().min(), lines.max()) else {
+        return;
+    };
+    let entry = SectionHistoryEntry {
+        timestamp: Utc::now().to_rfc3339(),
+        source: result.canonical.clone(),
+        line_start,
+        line_end,
+        at: at.map(str::to_string),
+    };
+    if let Err(err) = history_log::record(Continuation::Section(entry)) {
+        tracing::warn!("failed to save retrieval history: {err}");
+    }
+}
+
+/// Build the request for `--next-section`/`--previous-section` from the last
+/// retrieval, returning it with the version to read.
+fn section_request(forward: bool, at: Option<String>) -> Result<(RequestSpec, Option<String>)> {
+    let last = history_log::latest(|continuation| match continuation {
+        Continuation::Section(entry) => Some(entry),
+        _ => None,
+    })
+    .context("No previous retrieval found. Use 'blz get <alias:lines>' first.")?;
+    let at = at.or(last.at);
+
+    let storage = Storage::new()?;
+    let text = load_source_text(&storage, &last.source, at.as_deref())?;
+    let (start, end) = text
+        .toc
+        .as_deref()
+        .and_then(|toc| adjacent_section(toc, (last.line_start, last.line_end), forward))
+        .with_context(|| {
+            if forward {
+                format!(
+                    "No section after line {} in '{}'",
+                    last.line_end, last.source
+                )
+            } else {
+                format!(
+                    "No section before line {} in '{}'",
+                    last.line_start, last.source
+                )
+            }
+        })?;
+
+    let spec = RequestSpec {
+        alias: last.source,
+        line_expression: format!("{start}-{end}"),
+    };
+    Ok((spec, at))
+}
+
+/// Collect the retrieved text of each request for clipboard copy.
+fn clipboard_segments(processed: &[ProcessedRequest]) -> Vec<String> {
+    processed
+        .iter()
+        .map(|result| {
+            result
                 .lines_with_content
                 .iter()
                 .map(|(_, line)| line.clone())
                 .collect::<Vec<_>>()
-                .join("\n");
-            if !clip.is_empty() {
-                clipboard_segments.push(clip);
-            }
-        }
-
-        processed.push(result);
-    }
-
-    Ok((processed, clipboard_segments))
+                .join("\n")
+        })
+        .filter(|clip| !clip.is_empty())
+        .collect()
 }
 
 // ============================================================================
@@ -807,8 +1066,13 @@ pub async fn dispatch(cmd: Commands, quiet: bool) -> Result<()> {
         before_context,
         block,
         max_lines,
+        grep,
+        at,
+        next_section,
+        previous_section,
         format,
         copy,
+        remote,
     } = cmd
     else {
         unreachable!("dispatch called with non-Get command");
@@ -824,14 +1088,20 @@ pub async fn dispatch(cmd: Commands, quiet: bool) -> Result<()> {
         before_context,
         block,
         max_lines,
+        grep,
+        at,
+        next_section,
+        previous_section,
         format: format.resolve(quiet),
         copy,
+        remote,
     };
 
     handle_get(args).await
 }
 
 /// Parameters extracted from get command arguments.
+#[allow(clippy::struct_excessive_bools)]
 struct GetArgs {
     targets: Vec<String>,
     lines: Option<String>,
@@ -842,13 +1112,32 @@ struct GetArgs {
     before_context: Option<usize>,
     block: bool,
     max_lines: Option<usize>,
+    grep: Option<String>,
+    at: Option<String>,
+    next_section: bool,
+    previous_section: bool,
     format: OutputFormat,
     copy: bool,
+    remote: Option<String>,
 }
 
 /// Handle the get command after arguments are extracted.
 async fn handle_get(args: GetArgs) -> Result<()> {
-    let request_specs = parse_get_targets(&args.targets, args.lines.as_deref(), args.source)?;
+    let (request_specs, at) = if args.next_section || args.previous_section {
+        let (spec, at) = section_request(args.next_section, args.at)?;
+        (vec![spec], at)
+    } else {
+        (
+            parse_get_targets(&args.targets, args.lines.as_deref(), args.source)?,
+            args.at,
+        )
+    };
+    let grep = args
+        .grep
+        .as_deref()
+        .map(Regex::new)
+        .transpose()
+        .context("Invalid --grep pattern")?;
 
     let merged_context = merge_context_flags(
         args.context,
@@ -864,6 +1153,9 @@ async fn handle_get(args: GetArgs) -> Result<()> {
         args.max_lines,
         args.format,
         args.copy,
+        args.remote.as_deref(),
+        grep.as_ref(),
+        at.as_deref(),
     )
     .await
 }
@@ -896,7 +1188,11 @@ fn parse_get_targets(
 }
 
 /// Parse a single target string into a `RequestSpec`.
-fn parse_single_target(target: &str, idx: usize, lines: Option<&str>) -> Result<RequestSpec> {
+pub(super) fn parse_single_target(
+    target: &str,
+    idx: usize,
+    lines: Option<&str>,
+) -> Result<RequestSpec> {
     let trimmed = target.trim();
     if trimmed.is_empty() {
         anyhow::bail!("Alias at position {} cannot be empty.", idx + 1);
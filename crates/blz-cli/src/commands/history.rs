@@ -5,6 +5,7 @@ use serde_json::json;
 
 use crate::output::OutputFormat;
 use crate::utils::cli_args::FormatArg;
+use crate::utils::confirm::{self, Risk};
 use crate::utils::history_log;
 use crate::utils::preferences::{self, CliPreferences};
 
@@ -14,10 +15,18 @@ pub fn dispatch(
     format: &FormatArg,
     clear: bool,
     clear_before: Option<&str>,
+    yes: bool,
     quiet: bool,
     prefs: &CliPreferences,
 ) -> Result<()> {
-    show(prefs, limit, format.resolve(quiet), clear, clear_before)
+    show(
+        prefs,
+        limit,
+        format.resolve(quiet),
+        clear,
+        clear_before,
+        yes,
+    )
 }
 
 /// Display search history, optionally clearing entries.
@@ -27,12 +36,16 @@ pub fn show(
     format: OutputFormat,
     clear: bool,
     clear_before: Option<&str>,
+    yes: bool,
 ) -> Result<()> {
     // Handle clear operations
     if clear {
+        if !confirm::confirm("Clear all search history?", Risk::Destructive, yes)? {
+            return Ok(());
+        }
         history_log::clear_all()?;
         match format {
-            OutputFormat::Text => {
+            OutputFormat::Text | OutputFormat::Markdown => {
                 println!("{}", "All search history cleared.".green());
             },
             OutputFormat::Json | OutputFormat::Jsonl => {
@@ -47,9 +60,13 @@ pub fn show(
 
     if let Some(date_str) = clear_before {
         let cutoff_date = parse_date(date_str)?;
+        let prompt = format!("Clear search history before {}?", cutoff_date.to_rfc3339());
+        if !confirm::confirm(&prompt, Risk::Destructive, yes)? {
+            return Ok(());
+        }
         history_log::clear_before(&cutoff_date)?;
         match format {
-            OutputFormat::Text => {
+            OutputFormat::Text | OutputFormat::Markdown => {
                 println!(
                     "{}",
                     format!(
@@ -79,7 +96,7 @@ pub fn show(
     let limit = limit.max(1);
     let entries: Vec<_> = history_log::recent_for_active_scope(limit);
     match format {
-        OutputFormat::Text => {
+        OutputFormat::Text | OutputFormat::Markdown => {
             render_text(prefs, &entries);
         },
         OutputFormat::Json => {
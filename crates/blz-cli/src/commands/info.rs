@@ -6,9 +6,10 @@ use std::fs;
 use std::io;
 use std::path::PathBuf;
 
+use crate::i18n::{self, Message};
 use crate::output::OutputFormat;
 use crate::output::render::render;
-use crate::output::shapes::{FilterStatsOutput, OutputShape, SourceInfoOutput};
+use crate::output::shapes::{FilterStatsOutput, MirrorOutput, OutputShape, SourceInfoOutput};
 use crate::utils::count_headings;
 
 /// Execute the info command.
@@ -24,7 +25,11 @@ pub async fn execute_info(alias: &str, format: OutputFormat) -> Result<()> {
         .unwrap_or_else(|| alias.to_string());
 
     if !storage.exists(&canonical) {
-        anyhow::bail!("Source '{alias}' not found. Run `blz list` to see available sources.");
+        anyhow::bail!(
+            "{} {}",
+            i18n::text(Message::SourceNotFound, &[("alias", alias)]),
+            i18n::text(Message::HintListSources, &[])
+        );
     }
 
     let llms = storage
@@ -32,9 +37,11 @@ pub async fn execute_info(alias: &str, format: OutputFormat) -> Result<()> {
         .with_context(|| format!("Failed to load metadata for '{canonical}'"))?;
     let metadata = llms.metadata.clone();
 
-    let llms_file = storage.llms_txt_path(&canonical)?;
+    let llms_file = storage
+        .stored_llms_txt_path(&canonical)?
+        .unwrap_or(storage.llms_txt_path(&canonical)?);
 
-    // Read file stats
+    // Read file stats (on-disk size, which is smaller when stored compressed)
     let file_metadata = fs::metadata(&llms_file)
         .with_context(|| format!("Failed to read source file for '{canonical}'"))?;
 
@@ -49,6 +56,8 @@ pub async fn execute_info(alias: &str, format: OutputFormat) -> Result<()> {
         .display()
         .to_string();
 
+    let access = storage.load_access(&canonical);
+
     // Build the output shape
     let mut info = SourceInfoOutput::new(
         canonical,
@@ -61,6 +70,7 @@ pub async fn execute_info(alias: &str, format: OutputFormat) -> Result<()> {
     )
     .with_aliases(metadata.aliases.clone())
     .with_last_updated(metadata.fetched_at.to_rfc3339())
+    .with_access(&access)
     .with_checksum(metadata.sha256);
 
     if let Some(etag) = metadata.etag {
@@ -77,6 +87,24 @@ pub async fn execute_info(alias: &str, format: OutputFormat) -> Result<()> {
         });
     }
 
+    if let Some(mirrors) = storage.load_mirrors(&info.alias)? {
+        let candidates = mirrors.candidates(&metadata.url);
+        info = info.with_mirrors(
+            candidates
+                .into_iter()
+                .map(|url| {
+                    let health = mirrors.health.get(&url).cloned().unwrap_or_default();
+                    MirrorOutput {
+                        url,
+                        latency_ms: health.latency_ms,
+                        last_failure: health.last_failure.map(|at| at.to_rfc3339()),
+                        consecutive_failures: health.consecutive_failures,
+                    }
+                })
+                .collect(),
+        );
+    }
+
     // Render the output using unified renderer
     let shape: OutputShape = info.into();
     let mut stdout = io::stdout();
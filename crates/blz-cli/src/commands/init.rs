@@ -0,0 +1,440 @@
+//! Init command: scaffold a project's documentation set.
+//!
+//! `blz init` writes two files at the project root:
+//!
+//! - `.blz.toml`, the workspace config, listing the project's sources so
+//!   searches inside the repository are scoped to them
+//! - `blz.sources.toml`, a batch manifest with each source's URL, aliases, and
+//!   tags, so a teammate installs the whole set with
+//!   `blz add --manifest blz.sources.toml`
+//!
+//! Sources come from `--source` (installed sources or registry entries), from
+//! `--detect` (dependencies in `package.json`, `Cargo.toml`, and
+//! `pyproject.toml` matched against installed sources and the registry), or,
+//! when neither is given, from every installed source. Sources already
+//! installed are also pinned in a `blz.lock` next to the manifest, which
+//! `blz sync --frozen` verifies.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use blz_core::discovery::ecosystem::{project_dependencies, registry_match};
+use blz_core::registry::RegistryEntry;
+use blz_core::{Registry, SourceDescriptor, SourceType, Storage};
+use clap::Args;
+use colored::Colorize;
+use serde::Serialize;
+use toml_edit::{Array, DocumentMut, value};
+
+use super::add::{ManifestAliases, ManifestEntry, ManifestFile};
+use crate::output::OutputFormat;
+use crate::utils::cli_args::FormatArg;
+use crate::utils::lockfile::{self, Lockfile};
+use crate::utils::resolver::resolve_source;
+use crate::utils::workspace::PROJECT_CONFIG_FILE;
+
+/// Default manifest file name, next to `.blz.toml`.
+const DEFAULT_MANIFEST: &str = "blz.sources.toml";
+/// Manifest schema version understood by `blz add --manifest`.
+const MANIFEST_VERSION: &str = "1";
+
+/// Arguments for `blz init`.
+#[derive(Args, Clone, Debug)]
+pub struct InitArgs {
+    /// Source to include (installed alias or registry name; repeatable)
+    #[arg(
+        long = "source",
+        short = 's',
+        value_name = "SOURCE",
+        value_delimiter = ','
+    )]
+    pub sources: Vec<String>,
+    /// Suggest sources from the project's dependencies
+    #[arg(long)]
+    pub detect: bool,
+    /// Project directory (defaults to the current directory)
+    #[arg(long, value_name = "DIR")]
+    pub dir: Option<PathBuf>,
+    /// Manifest file name, relative to the project directory
+    #[arg(long, value_name = "FILE", default_value = DEFAULT_MANIFEST)]
+    pub manifest: PathBuf,
+    /// Overwrite an existing manifest
+    #[arg(long)]
+    pub force: bool,
+    /// Print what would be written without touching any files
+    #[arg(long)]
+    pub dry_run: bool,
+    /// Output format
+    #[command(flatten)]
+    pub format: FormatArg,
+}
+
+/// One source chosen for the project, and why.
+#[derive(Debug)]
+struct Candidate {
+    entry: ManifestEntry,
+    /// Dependency (and file) that suggested the source, for `--detect`.
+    detected_from: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct InitReport {
+    workspace: PathBuf,
+    manifest: PathBuf,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    lockfile: Option<PathBuf>,
+    dry_run: bool,
+    sources: Vec<ReportSource>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ReportSource {
+    alias: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    detected_from: Option<String>,
+}
+
+/// Execute `blz init`.
+///
+/// # Errors
+///
+/// Returns an error if a requested source is unknown, no sources were found,
+/// the manifest exists without `--force`, or a file cannot be written.
+pub fn execute(args: &InitArgs, quiet: bool) -> Result<()> {
+    let format = args.format.resolve(quiet);
+    let dir = match &args.dir {
+        Some(dir) => dir.clone(),
+        None => std::env::current_dir().context("Failed to read the current directory")?,
+    };
+    let workspace_path = dir.join(PROJECT_CONFIG_FILE);
+    let manifest_path = dir.join(&args.manifest);
+    // Local paths are written relative to the manifest, as `add --manifest` reads them
+    let base = manifest_path.parent().unwrap_or(&dir);
+    let storage = Storage::new()?;
+    let registry = Registry::new();
+
+    let mut candidates: BTreeMap<String, Candidate> = BTreeMap::new();
+    for name in &args.sources {
+        let candidate = explicit_candidate(&storage, &registry, name, base)?;
+        candidates.insert(candidate.entry.alias.clone(), candidate);
+    }
+    if args.detect {
+        for candidate in detected_candidates(&storage, &registry, &dir, base) {
+            candidates
+                .entry(candidate.entry.alias.clone())
+                .or_insert(candidate);
+        }
+    }
+    if args.sources.is_empty() && !args.detect {
+        for alias in storage.list_sources() {
+            if let Some(candidate) = installed_candidate(&storage, &alias, base)? {
+                candidates.insert(alias, candidate);
+            }
+        }
+    }
+    if candidates.is_empty() {
+        bail!(
+            "No sources to write. Pass --source <ALIAS>, add sources first, or use --detect in a project with dependencies."
+        );
+    }
+
+    if manifest_path.exists() && !args.force && !args.dry_run {
+        bail!(
+            "{} already exists; pass --force to overwrite it",
+            manifest_path.display()
+        );
+    }
+
+    let aliases: Vec<String> = candidates.keys().cloned().collect();
+    let installed: Vec<String> = aliases
+        .iter()
+        .filter(|alias| storage.exists(alias))
+        .cloned()
+        .collect();
+    let lock_path = base.join(lockfile::LOCKFILE_NAME);
+    let mut report = InitReport {
+        workspace: workspace_path.clone(),
+        manifest: manifest_path.clone(),
+        lockfile: None,
+        dry_run: args.dry_run,
+        sources: candidates
+            .values()
+            .map(|candidate| ReportSource {
+                alias: candidate.entry.alias.clone(),
+                url: candidate.entry.url.clone(),
+                path: candidate.entry.path.clone(),
+                detected_from: candidate.detected_from.clone(),
+            })
+            .collect(),
+    };
+    let manifest = ManifestFile {
+        version: Some(MANIFEST_VERSION.to_string()),
+        sources: candidates.into_values().map(|c| c.entry).collect(),
+    };
+    let manifest_text = format!(
+        "# Documentation sources for this project.\n# Install them with: blz add --manifest {}\n\n{}",
+        args.manifest.display(),
+        toml::to_string(&manifest).context("Failed to serialize manifest")?
+    );
+    let workspace_text = workspace_config(&workspace_path, &aliases, &args.manifest)?;
+
+    if args.dry_run {
+        if matches!(format, OutputFormat::Text | OutputFormat::Markdown) {
+            println!("# {}\n{workspace_text}", workspace_path.display());
+            println!("# {}\n{manifest_text}", manifest_path.display());
+            return Ok(());
+        }
+    } else {
+        write_file(&workspace_path, &workspace_text)?;
+        write_file(&manifest_path, &manifest_text)?;
+        if !installed.is_empty() {
+            let mut lock = Lockfile::load_or_default(&lock_path)?;
+            lock.record(&storage, &installed)?;
+            lock.save(&lock_path)?;
+            report.lockfile = Some(lock_path);
+        }
+    }
+
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+        OutputFormat::Jsonl | OutputFormat::Raw => {
+            println!("{}", serde_json::to_string(&report)?);
+        },
+        OutputFormat::Text | OutputFormat::Markdown => {
+            if !quiet {
+                print_summary(&report, &args.manifest);
+            }
+        },
+    }
+    Ok(())
+}
+
+/// Render `.blz.toml` with `sources` set, keeping any other settings.
+fn workspace_config(path: &Path, aliases: &[String], manifest: &Path) -> Result<String> {
+    let mut doc = if path.exists() {
+        fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?
+            .parse::<DocumentMut>()
+            .with_context(|| format!("Failed to parse {}", path.display()))?
+    } else {
+        format!(
+            "# blz project workspace: searches here only cover these sources.\n# Install them with: blz add --manifest {}\n",
+            manifest.display()
+        )
+        .parse::<DocumentMut>()?
+    };
+    doc["sources"] = value(aliases.iter().map(String::as_str).collect::<Array>());
+    Ok(doc.to_string())
+}
+
+fn write_file(path: &Path, contents: &str) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    fs::write(path, contents).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+fn print_summary(report: &InitReport, manifest: &Path) {
+    println!(
+        "{} Wrote {} and {} ({} source(s))",
+        "✓".green(),
+        PROJECT_CONFIG_FILE,
+        manifest.display(),
+        report.sources.len()
+    );
+    if let Some(lock) = &report.lockfile {
+        println!(
+            "{} Pinned installed sources in {}",
+            "✓".green(),
+            lock.display()
+        );
+    }
+    for source in &report.sources {
+        let location = source
+            .url
+            .as_deref()
+            .or(source.path.as_deref())
+            .unwrap_or_default();
+        let why = source
+            .detected_from
+            .as_deref()
+            .map_or_else(String::new, |from| format!(" (from {from})"));
+        println!("  • {} {}{}", source.alias, location.bright_black(), why);
+    }
+    println!();
+    println!("Teammates can install this set with:");
+    println!("  blz add --manifest {}", manifest.display());
+}
+
+/// A `--source` argument: an installed source first, then a registry entry.
+fn explicit_candidate(
+    storage: &Storage,
+    registry: &Registry,
+    name: &str,
+    dir: &Path,
+) -> Result<Candidate> {
+    if let Some(alias) = resolve_source(storage, name)?.filter(|alias| storage.exists(alias)) {
+        return installed_candidate(storage, &alias, dir)?.ok_or_else(|| {
+            anyhow::anyhow!(
+                "Source '{alias}' cannot be described in a manifest; only URL and local file sources can"
+            )
+        });
+    }
+    let needle = name.to_ascii_lowercase();
+    registry
+        .all_entries()
+        .iter()
+        .find(|entry| entry.slug == needle || entry.aliases.contains(&needle))
+        .map(|entry| Candidate {
+            entry: registry_entry(entry),
+            detected_from: None,
+        })
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "Unknown source '{name}': not installed and not in the registry (try `blz lookup {name}`)"
+            )
+        })
+}
+
+/// Manifest entry for an installed source, described by its descriptor.
+///
+/// Returns `None` for internal sources and for source types `blz add
+/// --manifest` cannot replay (stdin, directories, crates, specs, collections).
+fn installed_candidate(storage: &Storage, alias: &str, dir: &Path) -> Result<Option<Candidate>> {
+    let Some(source) = storage.load_source_metadata(alias)? else {
+        return Ok(None);
+    };
+    if source.is_internal() {
+        return Ok(None);
+    }
+    let (url, path) = match &source.origin.source_type {
+        None | Some(SourceType::Remote { .. }) => (Some(source.url.clone()), None),
+        Some(SourceType::LocalFile { path }) => (None, Some(relative_to(path, dir))),
+        Some(_) => {
+            tracing::debug!("skipping {alias}: its source type has no manifest form");
+            return Ok(None);
+        },
+    };
+    let descriptor = storage
+        .load_descriptor(alias)?
+        .unwrap_or_else(|| SourceDescriptor::from_source(alias, &source));
+    Ok(Some(Candidate {
+        entry: ManifestEntry {
+            alias: alias.to_string(),
+            name: descriptor.name.unwrap_or_default(),
+            description: descriptor.description,
+            category: descriptor.category.unwrap_or_default(),
+            tags: descriptor.tags,
+            url,
+            path,
+            alias_sets: ManifestAliases {
+                npm: descriptor.npm_aliases,
+                github: descriptor.github_aliases,
+            },
+            ..ManifestEntry::default()
+        },
+        detected_from: None,
+    }))
+}
+
+fn registry_entry(entry: &RegistryEntry) -> ManifestEntry {
+    ManifestEntry {
+        alias: entry.slug.clone(),
+        name: entry.name.clone(),
+        description: Some(entry.description.clone()),
+        url: Some(entry.llms_url.clone()),
+        integrity: entry.integrity.clone(),
+        ..ManifestEntry::default()
+    }
+}
+
+/// Local paths inside the project are written relative to `dir` so the
+/// manifest works from any checkout.
+fn relative_to(path: &str, dir: &Path) -> String {
+    let dir = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+    Path::new(path).strip_prefix(&dir).map_or_else(
+        |_| path.to_string(),
+        |rel| rel.to_string_lossy().into_owned(),
+    )
+}
+
+/// Sources suggested by the project's dependencies.
+///
+/// A dependency matches an installed source through its alias or npm aliases,
+/// or a registry entry through its slug or aliases. Scoped npm packages also
+/// match on their unscoped name.
+fn detected_candidates(
+    storage: &Storage,
+    registry: &Registry,
+    dir: &Path,
+    base: &Path,
+) -> Vec<Candidate> {
+    let mut installed: Vec<(String, Vec<String>)> = Vec::new();
+    for alias in storage.list_sources() {
+        let mut names = vec![alias.clone()];
+        if let Ok(Some(source)) = storage.load_source_metadata(&alias) {
+            names.extend(source.aliases);
+            names.extend(source.npm_aliases);
+        }
+        installed.push((alias, names));
+    }
+
+    let mut candidates = Vec::new();
+    for dependency in project_dependencies(dir) {
+        let detected_from = Some(format!(
+            "{} in {}",
+            dependency.name,
+            dependency.ecosystem.manifest_name()
+        ));
+
+        if let Some((alias, _)) = installed
+            .iter()
+            .find(|(_, names)| names.iter().any(|name| dependency.matches(name)))
+        {
+            if let Ok(Some(candidate)) = installed_candidate(storage, alias, base) {
+                candidates.push(Candidate {
+                    detected_from,
+                    ..candidate
+                });
+                continue;
+            }
+        }
+        if let Some(entry) = registry_match(registry, &dependency) {
+            candidates.push(Candidate {
+                entry: registry_entry(entry),
+                detected_from,
+            });
+        }
+    }
+    candidates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn workspace_config_keeps_other_settings() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join(PROJECT_CONFIG_FILE);
+        fs::write(
+            &path,
+            "# team docs\nsources = [\"old\"]\nformat = \"json\"\n",
+        )?;
+
+        let text = workspace_config(&path, &["react".to_string()], Path::new(DEFAULT_MANIFEST))?;
+        assert!(text.contains("# team docs"));
+        assert!(text.contains("format = \"json\""));
+        assert!(text.contains("sources = [\"react\"]"));
+        Ok(())
+    }
+}
@@ -1,14 +1,25 @@
 //! List command implementation
 
+use std::collections::HashMap;
 use std::io::{self, Write};
+use std::path::PathBuf;
+use std::str::FromStr;
 
 use anyhow::{Context, Result};
-use blz_core::{LlmsJson, Source, SourceDescriptor, Storage};
-
+use blz_core::numeric::format_bytes;
+use blz_core::{LlmsJson, Source, SourceAccess, SourceDescriptor, Storage};
+use chrono::DateTime;
+use clap::ValueEnum;
+
+use super::descriptors;
+use super::sync::is_generated_source;
+use crate::i18n::{self, Message};
 use crate::output::OutputFormat;
 use crate::output::render::{SourceListRenderOptions, render_source_list_with_options};
-use crate::output::shapes::{SourceListOutput, SourceSummary};
+use crate::output::shapes::{SourceHealthBadges, SourceListOutput, SourceStatus, SourceSummary};
 use crate::utils::count_headings;
+use crate::utils::history_log;
+use crate::utils::staleness::{self, DEFAULT_STALE_AFTER_DAYS};
 
 /// Abstraction over storage interactions required by the list command.
 pub trait ListStorage {
@@ -16,6 +27,31 @@ pub trait ListStorage {
     fn load_metadata(&self, alias: &str) -> Result<Option<Source>>;
     fn load_llms_json(&self, alias: &str) -> Result<LlmsJson>;
     fn load_descriptor(&self, alias: &str) -> Result<Option<SourceDescriptor>>;
+
+    /// Size in bytes of the cached content for `alias`.
+    fn content_size(&self, _alias: &str) -> u64 {
+        0
+    }
+
+    /// Whether `alias` was produced by `blz generate`.
+    fn is_generated(&self, _alias: &str) -> bool {
+        false
+    }
+
+    /// Recorded search counts keyed by source alias.
+    fn search_counts(&self) -> HashMap<String, usize> {
+        HashMap::new()
+    }
+
+    /// Whether a search index exists for `alias`.
+    fn has_index(&self, _alias: &str) -> bool {
+        true
+    }
+
+    /// Recorded search and fetch times for `alias`.
+    fn load_access(&self, _alias: &str) -> SourceAccess {
+        SourceAccess::default()
+    }
 }
 
 impl ListStorage for Storage {
@@ -34,6 +70,197 @@ impl ListStorage for Storage {
     fn load_descriptor(&self, alias: &str) -> Result<Option<SourceDescriptor>> {
         Self::load_descriptor(self, alias).map_err(anyhow::Error::from)
     }
+
+    fn content_size(&self, alias: &str) -> u64 {
+        self.stored_llms_txt_path(alias)
+            .ok()
+            .flatten()
+            .and_then(|path| std::fs::metadata(path).ok())
+            .map_or(0, |meta| meta.len())
+    }
+
+    fn is_generated(&self, alias: &str) -> bool {
+        is_generated_source(self, alias)
+    }
+
+    fn search_counts(&self) -> HashMap<String, usize> {
+        history_log::search_counts_by_source()
+    }
+
+    fn load_access(&self, alias: &str) -> SourceAccess {
+        Self::load_access(self, alias)
+    }
+
+    fn has_index(&self, alias: &str) -> bool {
+        self.index_dir(alias).is_ok_and(|dir| dir.exists())
+    }
+}
+
+/// Sort order for `blz list --sort`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum ListSort {
+    /// Alphabetical by alias
+    Name,
+    /// Largest cached content first
+    Size,
+    /// Most recently fetched first
+    Fetched,
+    /// Most searched first
+    Searches,
+    /// Least recently searched first (never-searched sources lead)
+    Lru,
+}
+
+/// Filter for `blz list --filter`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ListFilter {
+    /// Sources not fetched within the staleness threshold.
+    Stale,
+    /// Sources produced by `blz generate`.
+    Generated,
+    /// Sources carrying the given tag (case-insensitive).
+    Tag(String),
+}
+
+impl FromStr for ListFilter {
+    type Err = String;
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        let value = raw.trim();
+        if let Some(tag) = value.strip_prefix("tag:") {
+            let tag = tag.trim();
+            if tag.is_empty() {
+                return Err("tag filter requires a value, e.g. tag:rust".to_string());
+            }
+            return Ok(Self::Tag(tag.to_string()));
+        }
+        match value.to_ascii_lowercase().as_str() {
+            "stale" => Ok(Self::Stale),
+            "generated" => Ok(Self::Generated),
+            _ => Err(format!(
+                "unknown filter '{raw}' (expected stale, generated, or tag:<tag>)"
+            )),
+        }
+    }
+}
+
+impl ListFilter {
+    fn matches(&self, summary: &SourceSummary) -> bool {
+        match self {
+            Self::Stale => summary.status == SourceStatus::Stale,
+            Self::Generated => summary.generated,
+            Self::Tag(tag) => summary.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)),
+        }
+    }
+}
+
+/// Column for `blz list --columns`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum ListColumn {
+    Alias,
+    Url,
+    Status,
+    Lines,
+    Headings,
+    Size,
+    Fetched,
+    Searches,
+    Searched,
+    Tags,
+    Category,
+    Description,
+    Generated,
+    Health,
+}
+
+impl ListColumn {
+    /// Field name used for this column in JSON output.
+    const fn json_key(self) -> &'static str {
+        match self {
+            Self::Alias => "alias",
+            Self::Url => "url",
+            Self::Status => "status",
+            Self::Lines => "lines",
+            Self::Headings => "headings",
+            Self::Size => "sizeBytes",
+            Self::Fetched => "fetchedAt",
+            Self::Searches => "searches",
+            Self::Searched => "lastSearchedAt",
+            Self::Tags => "tags",
+            Self::Category => "category",
+            Self::Description => "description",
+            Self::Generated => "generated",
+            Self::Health => "health",
+        }
+    }
+
+    const fn header(self) -> &'static str {
+        match self {
+            Self::Alias => "ALIAS",
+            Self::Url => "URL",
+            Self::Status => "STATUS",
+            Self::Lines => "LINES",
+            Self::Headings => "HEADINGS",
+            Self::Size => "SIZE",
+            Self::Fetched => "FETCHED",
+            Self::Searches => "SEARCHES",
+            Self::Searched => "LAST SEARCHED",
+            Self::Tags => "TAGS",
+            Self::Category => "CATEGORY",
+            Self::Description => "DESCRIPTION",
+            Self::Generated => "GENERATED",
+            Self::Health => "HEALTH",
+        }
+    }
+
+    fn cell(self, summary: &SourceSummary) -> String {
+        match self {
+            Self::Alias => summary.alias.clone(),
+            Self::Url => summary.url.clone(),
+            Self::Status => summary.status.to_string(),
+            Self::Lines => summary.lines.to_string(),
+            Self::Headings => summary.headings.to_string(),
+            Self::Size => format_bytes(summary.size_bytes),
+            Self::Fetched => format_timestamp(summary.fetched_at.as_deref()),
+            Self::Searches => summary.searches.to_string(),
+            Self::Searched => format_timestamp(summary.last_searched_at.as_deref()),
+            Self::Tags => summary.tags.join(","),
+            Self::Category => summary.category.clone().unwrap_or_default(),
+            Self::Description => summary.description.clone().unwrap_or_default(),
+            Self::Generated => if summary.generated { "yes" } else { "no" }.to_string(),
+            Self::Health => summary.health.compact(),
+        }
+    }
+}
+
+/// Render an RFC3339 timestamp as `YYYY-MM-DD HH:MM`, or `-` when absent.
+fn format_timestamp(timestamp: Option<&str>) -> String {
+    timestamp
+        .and_then(|ts| DateTime::parse_from_rfc3339(ts).ok())
+        .map_or_else(
+            || "-".to_string(),
+            |ts| ts.format("%Y-%m-%d %H:%M").to_string(),
+        )
+}
+
+/// Options controlling which sources `blz list` shows and how.
+#[derive(Clone, Debug, Default)]
+pub struct ListOptions {
+    /// Include status/health information.
+    pub status: bool,
+    /// Include descriptor metadata.
+    pub details: bool,
+    /// Maximum number of sources to display.
+    pub limit: Option<usize>,
+    /// Sort order (storage order when unset).
+    pub sort: Option<ListSort>,
+    /// Filters that every listed source must match.
+    pub filters: Vec<ListFilter>,
+    /// Columns to display; empty for the default layout.
+    pub columns: Vec<ListColumn>,
+    /// Write shareable descriptors for the listed sources here instead of
+    /// rendering them (`-` for stdout).
+    pub export: Option<PathBuf>,
 }
 
 /// Gather source summaries from storage.
@@ -43,6 +270,11 @@ impl ListStorage for Storage {
 /// Returns an error if metadata or cached content cannot be loaded.
 pub fn collect_source_summaries<S: ListStorage>(storage: &S) -> Result<Vec<SourceSummary>> {
     let aliases = storage.list_sources()?;
+    let search_counts = if aliases.is_empty() {
+        HashMap::new()
+    } else {
+        storage.search_counts()
+    };
     let mut summaries = Vec::new();
 
     for alias in aliases {
@@ -64,13 +296,23 @@ pub fn collect_source_summaries<S: ListStorage>(storage: &S) -> Result<Vec<Sourc
             .or_else(|| descriptor.as_ref().and_then(|d| d.category.clone()));
 
         let summary = build_source_summary(
-            alias,
+            alias.clone(),
             &metadata,
             &llms,
             descriptor.as_ref(),
             description,
             category,
+        )
+        .with_size_bytes(storage.content_size(&alias))
+        .with_generated(storage.is_generated(&alias))
+        .with_searches(search_counts.get(&alias).copied().unwrap_or(0))
+        .with_access(&storage.load_access(&alias));
+        let health = health_badges(
+            &llms,
+            summary.status == SourceStatus::Fresh,
+            storage.has_index(&alias),
         );
+        let summary = summary.with_health(health);
 
         summaries.push(summary);
     }
@@ -78,6 +320,18 @@ pub fn collect_source_summaries<S: ListStorage>(storage: &S) -> Result<Vec<Sourc
     Ok(summaries)
 }
 
+/// Combine live checks with the health recorded by `blz check`/`blz doctor`.
+fn health_badges(llms: &LlmsJson, fresh: bool, index_ok: bool) -> SourceHealthBadges {
+    let recorded = llms.health.as_ref();
+    SourceHealthBadges {
+        fresh,
+        index_ok,
+        upstream_reachable: recorded.and_then(|record| record.upstream_reachable),
+        lint_score: recorded.map(|record| record.lint_score),
+        checked_at: recorded.map(|record| record.checked_at.to_rfc3339()),
+    }
+}
+
 /// Build a `SourceSummary` from metadata and content.
 fn build_source_summary(
     alias: String,
@@ -94,7 +348,14 @@ fn build_source_summary(
         .with_fetched_at(metadata.fetched_at.to_rfc3339())
         .with_checksum(llms.metadata.sha256.clone())
         .with_npm_aliases(metadata.npm_aliases.clone())
-        .with_github_aliases(metadata.github_aliases.clone());
+        .with_github_aliases(metadata.github_aliases.clone())
+        .with_status(
+            if staleness::is_stale(metadata.fetched_at, DEFAULT_STALE_AFTER_DAYS) {
+                SourceStatus::Stale
+            } else {
+                SourceStatus::Fresh
+            },
+        );
 
     if let Some(etag) = &metadata.etag {
         summary = summary.with_etag(etag.clone());
@@ -151,15 +412,134 @@ pub fn render_list<W: Write>(
     render_source_list_with_options(&output, format, &options, writer)
 }
 
+/// Apply filters and sort order to collected summaries.
+fn arrange_summaries(
+    mut summaries: Vec<SourceSummary>,
+    options: &ListOptions,
+) -> Vec<SourceSummary> {
+    summaries.retain(|summary| options.filters.iter().all(|filter| filter.matches(summary)));
+
+    match options.sort {
+        None => {},
+        Some(ListSort::Name) => summaries.sort_by(|a, b| a.alias.cmp(&b.alias)),
+        Some(ListSort::Size) => summaries.sort_by(|a, b| {
+            b.size_bytes
+                .cmp(&a.size_bytes)
+                .then_with(|| a.alias.cmp(&b.alias))
+        }),
+        // RFC3339 timestamps from the same serializer sort chronologically as strings.
+        Some(ListSort::Fetched) => summaries.sort_by(|a, b| {
+            b.fetched_at
+                .cmp(&a.fetched_at)
+                .then_with(|| a.alias.cmp(&b.alias))
+        }),
+        Some(ListSort::Searches) => summaries.sort_by(|a, b| {
+            b.searches
+                .cmp(&a.searches)
+                .then_with(|| a.alias.cmp(&b.alias))
+        }),
+        // `None` sorts first, so never-searched sources lead
+        Some(ListSort::Lru) => summaries.sort_by(|a, b| {
+            a.last_searched_at
+                .cmp(&b.last_searched_at)
+                .then_with(|| a.alias.cmp(&b.alias))
+        }),
+    }
+
+    summaries
+}
+
+/// Render only the selected columns.
+///
+/// Text output becomes an aligned table; JSON output keeps only the matching
+/// fields so scripts get the same shape they asked for.
+fn render_columns<W: Write>(
+    writer: &mut W,
+    sources: &[SourceSummary],
+    columns: &[ListColumn],
+    format: OutputFormat,
+) -> Result<()> {
+    match format {
+        OutputFormat::Text | OutputFormat::Markdown => {
+            if sources.is_empty() {
+                writeln!(writer, "{}", i18n::text(Message::NoSourcesConfigured, &[]))?;
+                return Ok(());
+            }
+            let rows: Vec<Vec<String>> = sources
+                .iter()
+                .map(|summary| columns.iter().map(|column| column.cell(summary)).collect())
+                .collect();
+            let widths: Vec<usize> = columns
+                .iter()
+                .enumerate()
+                .map(|(idx, column)| {
+                    rows.iter()
+                        .map(|row| row[idx].chars().count())
+                        .max()
+                        .unwrap_or(0)
+                        .max(column.header().len())
+                })
+                .collect();
+            let headers: Vec<String> = columns.iter().map(|c| c.header().to_string()).collect();
+            for row in std::iter::once(&headers).chain(rows.iter()) {
+                let line = row
+                    .iter()
+                    .zip(&widths)
+                    .map(|(cell, width)| format!("{cell:<width$}"))
+                    .collect::<Vec<_>>()
+                    .join("  ");
+                writeln!(writer, "{}", line.trim_end())?;
+            }
+        },
+        OutputFormat::Json | OutputFormat::Jsonl => {
+            let projected = sources
+                .iter()
+                .map(|summary| project_columns(summary, columns))
+                .collect::<Result<Vec<_>>>()?;
+            if format == OutputFormat::Json {
+                writeln!(writer, "{}", serde_json::to_string_pretty(&projected)?)?;
+            } else {
+                for value in projected {
+                    writeln!(writer, "{}", serde_json::to_string(&value)?)?;
+                }
+            }
+        },
+        OutputFormat::Raw => {
+            for summary in sources {
+                let line = columns
+                    .iter()
+                    .map(|column| column.cell(summary))
+                    .collect::<Vec<_>>()
+                    .join("\t");
+                writeln!(writer, "{line}")?;
+            }
+        },
+    }
+    Ok(())
+}
+
+fn project_columns(summary: &SourceSummary, columns: &[ListColumn]) -> Result<serde_json::Value> {
+    let full = serde_json::to_value(summary)?;
+    let projected = columns
+        .iter()
+        .map(|column| {
+            let key = column.json_key();
+            (
+                key.to_string(),
+                full.get(key).cloned().unwrap_or(serde_json::Value::Null),
+            )
+        })
+        .collect::<serde_json::Map<_, _>>();
+    Ok(serde_json::Value::Object(projected))
+}
+
 /// Dispatch a List command.
 pub async fn dispatch(
     format: crate::utils::cli_args::FormatArg,
-    status: bool,
-    details: bool,
-    limit: Option<usize>,
+    options: ListOptions,
     quiet: bool,
 ) -> Result<()> {
-    execute(format.resolve(quiet), status, details, limit).await
+    execute(format.resolve(quiet), &options).await
 }
 
 /// Execute the list command using production storage and stdout.
@@ -168,30 +548,58 @@ pub async fn dispatch(
 ///
 /// Returns an error if storage access or output rendering fails.
 #[allow(clippy::unused_async)]
-pub async fn execute(
+pub async fn execute(format: OutputFormat, options: &ListOptions) -> Result<()> {
+    let storage = Storage::new()?;
+    if let Some(path) = &options.export {
+        let aliases: Vec<String> = arrange_summaries(collect_source_summaries(&storage)?, options)
+            .into_iter()
+            .take(options.limit.unwrap_or(usize::MAX))
+            .map(|summary| summary.alias)
+            .collect();
+        let count = descriptors::export(&storage, &aliases, path)?;
+        if path.as_os_str() != "-" && format == OutputFormat::Text {
+            println!("Exported {count} source descriptors to {}", path.display());
+        }
+        return Ok(());
+    }
+    let stdout = io::stdout();
+    let mut handle = stdout.lock();
+    execute_with_options(&storage, &mut handle, format, options)
+}
+
+/// Test shorthand for [`execute_with_options`] without sorting, filters, or columns.
+#[cfg(test)]
+pub fn execute_with_writer<S, W>(
+    storage: &S,
+    writer: &mut W,
     format: OutputFormat,
     status: bool,
     details: bool,
     limit: Option<usize>,
-) -> Result<()> {
-    let storage = Storage::new()?;
-    let stdout = io::stdout();
-    let mut handle = stdout.lock();
-    execute_with_writer(&storage, &mut handle, format, status, details, limit)
+) -> Result<()>
+where
+    S: ListStorage,
+    W: Write,
+{
+    let options = ListOptions {
+        status,
+        details,
+        limit,
+        ..ListOptions::default()
+    };
+    execute_with_options(storage, writer, format, &options)
 }
 
-/// Testable entry point allowing storage and writer injection.
+/// Testable entry point accepting the full set of list options.
 ///
 /// # Errors
 ///
 /// Returns an error if storage access or output rendering fails.
-pub fn execute_with_writer<S, W>(
+pub fn execute_with_options<S, W>(
     storage: &S,
     writer: &mut W,
     format: OutputFormat,
-    status: bool,
-    details: bool,
-    limit: Option<usize>,
+    options: &ListOptions,
 ) -> Result<()>
 where
     S: ListStorage,
@@ -206,7 +614,27 @@ where
         return Ok(());
     }
 
-    render_list(writer, &summaries, format, status, details, limit)
+    let total = summaries.len();
+    let mut summaries = arrange_summaries(summaries, options);
+    if summaries.is_empty() && total > 0 && format == OutputFormat::Text {
+        writeln!(writer, "{}", i18n::text(Message::NoSourcesMatch, &[]))?;
+        return Ok(());
+    }
+    if !options.columns.is_empty() {
+        if let Some(limit) = options.limit {
+            summaries.truncate(limit);
+        }
+        return render_columns(writer, &summaries, &options.columns, format);
+    }
+
+    render_list(
+        writer,
+        &summaries,
+        format,
+        options.status,
+        options.details,
+        options.limit,
+    )
 }
 
 #[cfg(test)]
@@ -284,6 +712,7 @@ mod tests {
             diagnostics: vec![],
             parse_meta: None,
             filter_stats: None,
+            health: None,
         }
     }
 
@@ -295,11 +724,196 @@ mod tests {
                 heading_path_normalized: Some(vec![format!("heading {i}")]),
                 lines: "1-2".into(),
                 anchor: None,
+                id: None,
                 children: vec![],
             })
             .collect()
     }
 
+    fn summary(alias: &str, size: u64, searches: usize, fetched_at: &str) -> SourceSummary {
+        SourceSummary::new(alias, format!("https://{alias}.dev/llms.txt"), 10)
+            .with_size_bytes(size)
+            .with_searches(searches)
+            .with_fetched_at(fetched_at)
+    }
+
+    fn aliases(summaries: &[SourceSummary]) -> Vec<&str> {
+        summaries.iter().map(|s| s.alias.as_str()).collect()
+    }
+
+    #[test]
+    fn list_filter_parsing() {
+        assert_eq!("stale".parse::<ListFilter>(), Ok(ListFilter::Stale));
+        assert_eq!("Generated".parse::<ListFilter>(), Ok(ListFilter::Generated));
+        assert_eq!(
+            "tag:rust".parse::<ListFilter>(),
+            Ok(ListFilter::Tag("rust".into()))
+        );
+        assert!("tag:".parse::<ListFilter>().is_err());
+        assert!("fresh".parse::<ListFilter>().is_err());
+    }
+
+    #[test]
+    fn arrange_sorts_by_requested_key() {
+        let sources = vec![
+            summary("beta", 300, 1, "2025-10-01T00:00:00+00:00"),
+            summary("alpha", 100, 5, "2025-09-01T00:00:00+00:00"),
+            summary("gamma", 200, 5, "2025-11-01T00:00:00+00:00"),
+        ];
+        let sorted = |sort| {
+            let options = ListOptions {
+                sort: Some(sort),
+                ..ListOptions::default()
+            };
+            arrange_summaries(sources.clone(), &options)
+        };
+
+        assert_eq!(aliases(&sorted(ListSort::Name)), ["alpha", "beta", "gamma"]);
+        assert_eq!(aliases(&sorted(ListSort::Size)), ["beta", "gamma", "alpha"]);
+        assert_eq!(
+            aliases(&sorted(ListSort::Fetched)),
+            ["gamma", "beta", "alpha"]
+        );
+        assert_eq!(
+            aliases(&sorted(ListSort::Searches)),
+            ["alpha", "gamma", "beta"]
+        );
+
+        let searched = |summary: SourceSummary, at: &str| SourceSummary {
+            last_searched_at: Some(at.to_string()),
+            ..summary
+        };
+        let lru = ListOptions {
+            sort: Some(ListSort::Lru),
+            ..ListOptions::default()
+        };
+        let sources = vec![
+            searched(sources[0].clone(), "2025-12-01T00:00:00+00:00"),
+            sources[1].clone(),
+            searched(sources[2].clone(), "2025-10-01T00:00:00+00:00"),
+        ];
+        assert_eq!(
+            aliases(&arrange_summaries(sources, &lru)),
+            ["alpha", "gamma", "beta"]
+        );
+    }
+
+    #[test]
+    fn arrange_applies_all_filters() {
+        let sources = vec![
+            summary("alpha", 1, 0, "2025-10-01T00:00:00+00:00")
+                .with_tags(vec!["Rust".into()])
+                .with_status(SourceStatus::Stale),
+            summary("beta", 1, 0, "2025-10-01T00:00:00+00:00")
+                .with_tags(vec!["rust".into()])
+                .with_generated(true),
+            summary("gamma", 1, 0, "2025-10-01T00:00:00+00:00").with_status(SourceStatus::Stale),
+        ];
+        let filtered = |filters| {
+            let options = ListOptions {
+                filters,
+                ..ListOptions::default()
+            };
+            arrange_summaries(sources.clone(), &options)
+        };
+
+        assert_eq!(
+            aliases(&filtered(vec![ListFilter::Stale])),
+            ["alpha", "gamma"]
+        );
+        assert_eq!(aliases(&filtered(vec![ListFilter::Generated])), ["beta"]);
+        assert_eq!(
+            aliases(&filtered(vec![
+                ListFilter::Tag("rust".into()),
+                ListFilter::Stale
+            ])),
+            ["alpha"]
+        );
+    }
+
+    #[test]
+    fn columns_project_json_fields_and_text_table() -> Result<()> {
+        let sources = vec![summary("alpha", 2048, 3, "2025-10-01T12:30:00+00:00")];
+        let columns = [ListColumn::Alias, ListColumn::Size, ListColumn::Searches];
+
+        let mut buf = Cursor::new(Vec::new());
+        render_columns(&mut buf, &sources, &columns, OutputFormat::Json)?;
+        let value: serde_json::Value = serde_json::from_slice(&buf.into_inner())?;
+        assert_eq!(
+            value,
+            serde_json::json!([{ "alias": "alpha", "sizeBytes": 2048, "searches": 3 }])
+        );
+
+        let mut buf = Cursor::new(Vec::new());
+        render_columns(&mut buf, &sources, &columns, OutputFormat::Text)?;
+        let output = String::from_utf8(buf.into_inner())?;
+        let mut lines = output.lines();
+        assert_eq!(
+            lines
+                .next()
+                .map(str::split_whitespace)
+                .map(Iterator::collect::<Vec<_>>),
+            Some(vec!["ALIAS", "SIZE", "SEARCHES"])
+        );
+        assert!(lines.next().is_some_and(|row| row.starts_with("alpha")));
+        Ok(())
+    }
+
+    #[test]
+    fn filtered_out_sources_report_no_match() -> Result<()> {
+        let metadata = sample_source("https://example.com");
+        let storage = MockStorage {
+            aliases: vec!["alpha".into()],
+            metadata: HashMap::from([(String::from("alpha"), metadata.clone())]),
+            llms: HashMap::from([(
+                String::from("alpha"),
+                sample_llms("alpha", metadata, 100, 10),
+            )]),
+            descriptors: HashMap::new(),
+            fail_on_metadata: false,
+        };
+        let options = ListOptions {
+            filters: vec![ListFilter::Generated],
+            ..ListOptions::default()
+        };
+
+        let mut buf = Cursor::new(Vec::new());
+        execute_with_options(&storage, &mut buf, OutputFormat::Text, &options)?;
+        let output = String::from_utf8(buf.into_inner())?;
+        assert!(output.contains("No sources match"));
+        Ok(())
+    }
+
+    #[test]
+    fn health_badges_combine_live_and_recorded_checks() -> Result<()> {
+        let metadata = sample_source("https://example.com");
+        let mut llms = sample_llms("alpha", metadata.clone(), 100, 10);
+        llms.health = Some(blz_core::HealthRecord {
+            checked_at: Utc.with_ymd_and_hms(2025, 10, 2, 8, 0, 0).unwrap(),
+            upstream_reachable: Some(false),
+            lint_score: 85,
+        });
+        let storage = MockStorage {
+            aliases: vec!["alpha".into()],
+            metadata: HashMap::from([(String::from("alpha"), metadata)]),
+            llms: HashMap::from([(String::from("alpha"), llms)]),
+            descriptors: HashMap::new(),
+            fail_on_metadata: false,
+        };
+
+        let summaries = collect_source_summaries(&storage)?;
+        let health = &summaries[0].health;
+        assert!(!health.fresh);
+        assert!(health.index_ok);
+        assert_eq!(health.upstream_reachable, Some(false));
+        assert_eq!(health.lint_score, Some(85));
+        assert_eq!(
+            health.compact(),
+            "stale · index ok · upstream down · lint 85"
+        );
+        Ok(())
+    }
+
     #[test]
     fn collect_source_summaries_empty() -> Result<()> {
         let storage = MockStorage::default();
@@ -1,7 +1,7 @@
 //! Lookup command implementation for searching registries
 
 use anyhow::Result;
-use blz_core::{Fetcher, PerformanceMetrics, Registry};
+use blz_core::{PerformanceMetrics, Registry};
 use colored::Colorize;
 use inquire::{Select, Text};
 use serde_json::json;
@@ -27,7 +27,7 @@ impl fmt::Display for SelectionItem {
 
 /// Handle the case when registry is disabled
 fn handle_registry_disabled(format: OutputFormat, quiet: bool) -> Result<()> {
-    if matches!(format, OutputFormat::Text) {
+    if matches!(format, OutputFormat::Text | OutputFormat::Markdown) {
         if !quiet {
             println!("Registry lookup is coming soon.");
             println!(
@@ -52,7 +52,7 @@ fn handle_registry_disabled(format: OutputFormat, quiet: bool) -> Result<()> {
             OutputFormat::Jsonl | OutputFormat::Raw => {
                 println!("{}", serde_json::to_string(&payload)?);
             },
-            OutputFormat::Text => unreachable!(),
+            OutputFormat::Text | OutputFormat::Markdown => unreachable!(),
         }
     }
 
@@ -66,7 +66,7 @@ async fn output_results_json(
     format: OutputFormat,
     quiet: bool,
 ) -> Result<()> {
-    let fetcher = Fetcher::new()?;
+    let fetcher = crate::utils::throttle::fetcher(None)?;
     let mut out = Vec::new();
 
     for r in results {
@@ -147,6 +147,9 @@ async fn handle_interactive_add(
         quiet,
         metrics,
         false, // no_language_filter
+    )
+    .with_integrity(
+        (!selected_entry.integrity.is_empty()).then(|| selected_entry.integrity.clone()),
     );
 
     add_source(request).await?;
@@ -239,7 +242,7 @@ async fn display_results_with_health(
         if results.len() == 1 { "" } else { "es" }
     );
 
-    let fetcher = Fetcher::new()?;
+    let fetcher = crate::utils::throttle::fetcher(None)?;
     for (i, result) in results.iter().enumerate() {
         let health = if let Ok(meta) = fetcher.head_metadata(&result.entry.llms_url).await {
             let ok = (200..300).contains(&i32::from(meta.status));
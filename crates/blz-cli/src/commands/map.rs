@@ -85,6 +85,10 @@ pub struct MapArgs {
     #[arg(short = 'a', long)]
     pub show_anchors: bool,
 
+    /// Prefix headings with ordinal paths (e.g. 2.3.1) usable as `alias:§2.3.1`
+    #[arg(long)]
+    pub numbered: bool,
+
     /// Continue from previous results (next page)
     #[arg(
         long,
@@ -155,6 +159,7 @@ pub async fn dispatch(args: MapArgs, quiet: bool) -> Result<()> {
         .with_tree(args.tree)
         .with_anchors(args.anchors)
         .with_show_anchors(args.show_anchors)
+        .with_numbered(args.numbered)
         .with_quiet(quiet);
 
     let nav = TocNavigation::new()
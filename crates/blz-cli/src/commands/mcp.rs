@@ -4,9 +4,12 @@
 
 use anyhow::Result;
 
+use crate::utils::shutdown;
+
 /// Execute the MCP server command
 ///
-/// Starts the BLZ MCP server and runs it until interrupted by SIGINT/SIGTERM.
+/// Starts the BLZ MCP server and runs it until the client disconnects or a
+/// SIGINT/SIGTERM arrives; on a signal, tool calls in progress finish first.
 ///
 /// # Errors
 ///
@@ -20,7 +23,8 @@ pub async fn execute() -> Result<()> {
     tracing::debug!("initializing BLZ MCP server");
 
     let server = blz_mcp::McpServer::new()?;
-    server.serve_stdio().await?;
+    shutdown::install();
+    server.serve_stdio_until(shutdown::cancelled()).await?;
 
     Ok(())
 }
@@ -5,6 +5,8 @@
 
 mod add;
 mod alias;
+mod bench;
+mod bundle;
 mod check;
 mod clear;
 mod completions;
@@ -12,33 +14,58 @@ mod toc;
 // config module removed in v1.0.0-beta.1 - flavor preferences eliminated
 mod claude_plugin;
 mod create_source;
+mod debug_args;
+mod demo;
+mod descriptors;
 mod diff;
+mod discover;
 pub mod docs;
 pub mod docs_bundle;
 mod doctor;
+mod du;
+mod ephemeral;
+mod explain;
+mod explain_source;
+mod filter;
 mod find;
+mod fixtures;
+mod gc;
+mod generate;
 mod get;
 mod history;
 mod info;
+mod init;
 mod list;
 mod lookup;
 mod map;
 mod mcp;
+mod profile;
+mod ps;
 mod query;
 mod refresh;
+mod remote;
 mod remove;
 mod rm;
 mod search;
+mod serve;
 mod stats;
 mod sync;
+mod terms;
 #[allow(deprecated)]
 mod update;
 mod validate;
+mod verify_install;
+mod watch;
+mod which;
 
 pub use add::{
     AddArgs, AddRequest, DescriptorInput, dispatch as dispatch_add, execute as add_source,
 };
 pub use alias::{AliasCommands, dispatch as dispatch_alias};
+pub use bench::{BenchArgs, execute as run_bench};
+pub use bundle::{
+    ExportArgs, ImportArgs, execute_export as export_bundle, execute_import as import_bundle,
+};
 pub use check::{CheckArgs, execute as check_source};
 pub use clear::run as clear_cache;
 pub use completions::dispatch as dispatch_completions;
@@ -48,21 +75,34 @@ pub use toc::{AnchorCommands, TocArgs, dispatch as dispatch_toc, dispatch_anchor
 // config command removed in v1.0.0-beta.1 - flavor preferences eliminated
 pub use claude_plugin::{ClaudePluginCommands, dispatch as dispatch_claude_plugin};
 pub use create_source::{RegistryCommands, dispatch as dispatch_registry};
+pub use debug_args::execute as debug_args;
+pub use demo::{DemoArgs, execute as run_demo};
 pub use diff::show as show_diff;
+pub use discover::{DiscoverArgs, execute as discover_sources};
 pub use docs::{DocsCommands, dispatch as dispatch_docs};
 pub use docs_bundle::{
     BUNDLED_ALIAS, SyncStatus as DocsSyncStatus, print_full_content, print_overview,
     sync as sync_bundled_docs,
 };
 pub use doctor::execute as run_doctor;
-pub use find::{FindArgs, dispatch as dispatch_find};
+pub use du::execute as show_disk_usage;
+pub use explain::{ExplainArgs, execute as explain_query};
+pub use explain_source::{ExplainSourceArgs, execute as explain_source};
+pub use filter::{FilterCommands, dispatch as dispatch_filter};
+pub use find::{FindArgs, dispatch as dispatch_find, dispatch_default};
+pub use fixtures::{FixturesArgs, execute as generate_fixtures};
+pub use gc::{GcArgs, execute as collect_garbage};
+pub use generate::{GenerateArgs, execute as generate_source};
 pub use get::{RequestSpec, dispatch as dispatch_get, execute as get_lines};
 pub use history::dispatch as dispatch_history;
 pub use info::execute_info;
-pub use list::dispatch as dispatch_list;
+pub use init::{InitArgs, execute as init_project};
+pub use list::{ListColumn, ListFilter, ListOptions, ListSort, dispatch as dispatch_list};
 pub use lookup::dispatch as dispatch_lookup;
 pub use map::{MapArgs, dispatch as dispatch_map};
 pub use mcp::execute as mcp_server;
+pub use profile::{ProfileCommands, dispatch as dispatch_profile};
+pub use ps::{execute as list_processes, execute_kill as kill_processes};
 pub use query::{QueryArgs, dispatch as dispatch_query};
 #[allow(deprecated)]
 pub use refresh::{
@@ -73,9 +113,16 @@ pub use refresh::{
 pub use remove::dispatch_deprecated as dispatch_remove_deprecated;
 pub use rm::{RmArgs, execute as rm_source};
 pub use search::{DEFAULT_MAX_CHARS, SearchArgs, dispatch as dispatch_search, execute as search};
+pub use serve::{ServeArgs, execute as serve_api};
 pub use stats::execute as show_stats;
+pub use stats::execute_disk as show_disk_budget;
+pub use stats::execute_unused as show_unused_sources;
 pub use sync::{SyncArgs, dispatch as dispatch_sync};
+pub use terms::{TermsArgs, execute as list_terms};
 #[allow(deprecated)]
 pub use validate::dispatch_deprecated as dispatch_validate_deprecated;
+pub use verify_install::{VerifyInstallArgs, execute as verify_install};
+pub use watch::{WatchArgs, execute as watch_sources};
+pub use which::execute as show_resolution;
 
 // Re-export types that commands might need
@@ -0,0 +1,200 @@
+//! Profile command: manage named profiles with isolated source sets.
+//!
+//! A named profile keeps its own sources, descriptors, and search history
+//! under `profiles/<name>` in the data and config directories, so work and
+//! personal doc sets (or per-client sets) never mix. `config.toml` is shared.
+//! The profile is picked per invocation with `--profile-name` or `BLZ_PROFILE`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use blz_core::{Storage, profile};
+use clap::Subcommand;
+use colored::Colorize;
+use serde::Serialize;
+
+use crate::output::OutputFormat;
+use crate::utils::cli_args::FormatArg;
+use crate::utils::confirm::{self, Risk};
+
+/// Name shown for the shared, unnamed profile.
+const DEFAULT_PROFILE: &str = "default";
+
+/// Subcommands for `blz profile`.
+#[derive(Subcommand, Clone, Debug)]
+pub enum ProfileCommands {
+    /// List profiles and how many sources each holds
+    #[command(alias = "ls")]
+    List {
+        /// Output format
+        #[command(flatten)]
+        format: FormatArg,
+    },
+    /// Create an empty profile
+    Create {
+        /// Profile name (letters, digits, '-' or '_')
+        name: String,
+    },
+    /// Delete a profile and all of its cached sources
+    Rm {
+        /// Profile to delete
+        name: String,
+        /// Delete without prompting
+        #[arg(short = 'y', long = "yes")]
+        yes: bool,
+    },
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ProfileEntry {
+    name: String,
+    active: bool,
+    sources: usize,
+    data_dir: PathBuf,
+}
+
+/// Dispatch a `blz profile` subcommand.
+///
+/// # Errors
+///
+/// Returns an error if the subcommand fails.
+pub fn dispatch(command: &ProfileCommands, quiet: bool) -> Result<()> {
+    match command {
+        ProfileCommands::List { format } => list(format.resolve(quiet)),
+        ProfileCommands::Create { name } => create(name, quiet),
+        ProfileCommands::Rm { name, yes } => remove(name, *yes, quiet),
+    }
+}
+
+fn list(format: OutputFormat) -> Result<()> {
+    let (data_profiles, config_profiles) = Storage::profiles_dirs()?;
+    let active = profile::named();
+
+    let mut names: Vec<String> = profile_names(&data_profiles)
+        .into_iter()
+        .chain(profile_names(&config_profiles))
+        .collect();
+    names.sort();
+    names.dedup();
+
+    let default_dir = data_profiles
+        .parent()
+        .map_or_else(|| data_profiles.clone(), Path::to_path_buf);
+    let mut entries = vec![ProfileEntry {
+        name: DEFAULT_PROFILE.to_string(),
+        active: active.is_none(),
+        sources: count_sources(&default_dir),
+        data_dir: default_dir,
+    }];
+    entries.extend(names.into_iter().map(|name| {
+        let data_dir = data_profiles.join(&name);
+        ProfileEntry {
+            active: active == Some(name.as_str()),
+            sources: count_sources(&data_dir),
+            data_dir,
+            name,
+        }
+    }));
+
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&entries)?),
+        OutputFormat::Jsonl => {
+            for entry in &entries {
+                println!("{}", serde_json::to_string(entry)?);
+            }
+        },
+        OutputFormat::Raw => println!("{}", serde_json::to_string(&entries)?),
+        OutputFormat::Text | OutputFormat::Markdown => {
+            for entry in &entries {
+                let marker = if entry.active { "*" } else { " " };
+                let name = if entry.active {
+                    entry.name.green().bold().to_string()
+                } else {
+                    entry.name.clone()
+                };
+                println!("{marker} {name:<20} {} source(s)", entry.sources);
+            }
+        },
+    }
+    Ok(())
+}
+
+fn create(name: &str, quiet: bool) -> Result<()> {
+    profile::validate_name(name)?;
+    let (data_profiles, config_profiles) = Storage::profiles_dirs()?;
+    let data_dir = data_profiles.join(name);
+    if data_dir.exists() {
+        bail!("Profile '{name}' already exists");
+    }
+    Storage::with_paths(data_dir, config_profiles.join(name))?;
+    if !quiet {
+        println!("{} Created profile '{name}'", "✓".green());
+        println!("  Use it with: blz --profile-name {name} <command>  (or BLZ_PROFILE={name})");
+    }
+    Ok(())
+}
+
+fn remove(name: &str, yes: bool, quiet: bool) -> Result<()> {
+    profile::validate_name(name)?;
+    if profile::named() == Some(name) {
+        bail!("Cannot remove the active profile '{name}'; switch to another profile first");
+    }
+    let (data_profiles, config_profiles) = Storage::profiles_dirs()?;
+    let mut dirs: Vec<PathBuf> = [data_profiles.join(name), config_profiles.join(name)]
+        .into_iter()
+        .filter(|dir| dir.exists())
+        .collect();
+    // Data and config roots may be the same directory
+    dirs.dedup();
+    if dirs.is_empty() {
+        bail!("Profile '{name}' not found");
+    }
+
+    let sources = count_sources(&data_profiles.join(name));
+    let prompt = format!("Delete profile '{name}' and its {sources} cached source(s)?");
+    if !confirm::confirm(&prompt, Risk::Destructive, yes)? {
+        if !quiet {
+            println!("{} Cancelled", "✗".red());
+        }
+        return Ok(());
+    }
+
+    for dir in dirs {
+        fs::remove_dir_all(&dir).with_context(|| format!("Failed to remove {}", dir.display()))?;
+    }
+    if !quiet {
+        println!("{} Removed profile '{name}'", "✓".green());
+    }
+    Ok(())
+}
+
+/// Names of the profile directories under `dir`.
+fn profile_names(dir: &Path) -> Vec<String> {
+    fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|entry| entry.path().is_dir())
+                .filter_map(|entry| entry.file_name().to_str().map(str::to_string))
+                .filter(|name| profile::validate_name(name).is_ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Number of cached sources under a profile's data directory.
+///
+/// Counted without opening [`Storage`] so listing never creates directories.
+fn count_sources(data_dir: &Path) -> usize {
+    fs::read_dir(data_dir.join("sources")).map_or(0, |entries| {
+        entries
+            .flatten()
+            .filter(|entry| {
+                !entry.file_name().to_string_lossy().starts_with('.')
+                    && entry.path().join("llms.json").exists()
+            })
+            .count()
+    })
+}
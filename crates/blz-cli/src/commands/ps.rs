@@ -0,0 +1,336 @@
+//! Process listing and orphan cleanup (`blz ps` / `blz kill`)
+//!
+//! Crashed agent harnesses can leave `blz` processes (most often `blz mcp`)
+//! running without a parent. The parent-exit guard normally catches these, but
+//! it can be disabled or unsupported, so these commands make leftovers visible
+//! and easy to terminate.
+
+use anyhow::{Result, bail};
+use colored::Colorize;
+use serde::Serialize;
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, Signal, System, UpdateKind};
+
+use crate::output::OutputFormat;
+
+/// PID that orphaned processes are re-parented to on Unix systems, and the
+/// parent of services started by the init system.
+const INIT_PID: u32 = 1;
+
+/// A running `blz` process.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BlzProcess {
+    pid: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parent_pid: Option<u32>,
+    orphaned: bool,
+    running_secs: u64,
+    command: String,
+}
+
+/// Result of a single termination attempt.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct KillOutcome {
+    pid: u32,
+    command: String,
+    terminated: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// Execute `blz ps`.
+///
+/// Lists orphaned `blz` processes, or every other running `blz` process when
+/// `all` is set.
+///
+/// # Errors
+///
+/// Returns an error if output serialization fails.
+pub fn execute(all: bool, format: OutputFormat) -> Result<()> {
+    let (_, processes) = scan_processes();
+    let processes: Vec<BlzProcess> = processes
+        .into_iter()
+        .filter(|process| all || process.orphaned)
+        .collect();
+
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&processes)?),
+        OutputFormat::Jsonl => {
+            for process in &processes {
+                println!("{}", serde_json::to_string(process)?);
+            }
+        },
+        OutputFormat::Raw => {
+            for process in &processes {
+                println!("{}", process.pid);
+            }
+        },
+        OutputFormat::Text | OutputFormat::Markdown => print_text_processes(&processes, all),
+    }
+
+    Ok(())
+}
+
+/// Execute `blz kill`.
+///
+/// Terminates the given PIDs (which must belong to `blz` processes), or every
+/// orphaned `blz` process when `orphans` is set. Sends `SIGTERM` unless `force`
+/// is requested.
+///
+/// # Errors
+///
+/// Returns an error if a PID is not a running `blz` process or any termination fails.
+pub fn execute_kill(pids: &[u32], orphans: bool, force: bool, format: OutputFormat) -> Result<()> {
+    let (system, processes) = scan_processes();
+
+    let targets: Vec<&BlzProcess> = if orphans {
+        processes
+            .iter()
+            .filter(|process| process.orphaned)
+            .collect()
+    } else {
+        let mut targets = Vec::with_capacity(pids.len());
+        for pid in pids {
+            let Some(process) = processes.iter().find(|process| process.pid == *pid) else {
+                bail!("PID {pid} is not a running blz process (see `blz ps --all`)");
+            };
+            targets.push(process);
+        }
+        targets
+    };
+
+    let signal = if force { Signal::Kill } else { Signal::Term };
+    let outcomes: Vec<KillOutcome> = targets
+        .into_iter()
+        .map(|process| terminate(&system, process, signal))
+        .collect();
+
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&outcomes)?),
+        OutputFormat::Jsonl => {
+            for outcome in &outcomes {
+                println!("{}", serde_json::to_string(outcome)?);
+            }
+        },
+        OutputFormat::Raw => {
+            for outcome in outcomes.iter().filter(|outcome| outcome.terminated) {
+                println!("{}", outcome.pid);
+            }
+        },
+        OutputFormat::Text | OutputFormat::Markdown => print_text_outcomes(&outcomes, orphans),
+    }
+
+    let failed = outcomes
+        .iter()
+        .filter(|outcome| !outcome.terminated)
+        .count();
+    if failed > 0 {
+        bail!("failed to terminate {failed} process(es)");
+    }
+    Ok(())
+}
+
+fn terminate(system: &System, process: &BlzProcess, signal: Signal) -> KillOutcome {
+    let result = system
+        .process(Pid::from_u32(process.pid))
+        .map(|handle| {
+            handle
+                .kill_with(signal)
+                .unwrap_or_else(|| signal == Signal::Kill && handle.kill())
+        })
+        .ok_or_else(|| "process exited before it could be signalled".to_string())
+        .and_then(|sent| {
+            if sent {
+                Ok(())
+            } else {
+                Err(format!("unable to send {signal:?} signal"))
+            }
+        });
+
+    KillOutcome {
+        pid: process.pid,
+        command: process.command.clone(),
+        terminated: result.is_ok(),
+        error: result.err(),
+    }
+}
+
+/// Snapshot all running processes and extract the `blz` ones (excluding this one).
+fn scan_processes() -> (System, Vec<BlzProcess>) {
+    let mut system = System::new();
+    system.refresh_processes_specifics(
+        ProcessesToUpdate::All,
+        true,
+        ProcessRefreshKind::new().with_cmd(UpdateKind::OnlyIfNotSet),
+    );
+
+    let own_pid = std::process::id();
+    let own_name = std::env::current_exe().ok().and_then(|path| {
+        path.file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+    });
+
+    let mut processes: Vec<BlzProcess> = system
+        .processes()
+        .iter()
+        .filter(|(pid, _)| pid.as_u32() != own_pid)
+        .filter(|(_, process)| {
+            is_blz_binary(&process.name().to_string_lossy(), own_name.as_deref())
+        })
+        .map(|(pid, process)| {
+            let parent_pid = process.parent().map(Pid::as_u32);
+            let parent_alive =
+                parent_pid.is_some_and(|parent| system.process(Pid::from_u32(parent)).is_some());
+            let session_alive = process
+                .session_id()
+                .is_none_or(|session| system.process(session).is_some());
+            let command = process
+                .cmd()
+                .iter()
+                .map(|arg| arg.to_string_lossy())
+                .collect::<Vec<_>>()
+                .join(" ");
+            BlzProcess {
+                pid: pid.as_u32(),
+                parent_pid,
+                orphaned: is_orphaned(parent_pid, parent_alive, session_alive),
+                running_secs: process.run_time(),
+                command: if command.is_empty() {
+                    process.name().to_string_lossy().into_owned()
+                } else {
+                    command
+                },
+            }
+        })
+        .collect();
+
+    processes.sort_by_key(|process| process.pid);
+    (system, processes)
+}
+
+/// Whether a process name refers to the `blz` binary (or the running binary's name).
+fn is_blz_binary(name: &str, own_name: Option<&str>) -> bool {
+    let stem = name.strip_suffix(".exe").unwrap_or(name);
+    stem == "blz" || own_name.is_some_and(|own| own == stem)
+}
+
+/// A process is orphaned when its parent is gone, or when it was re-parented
+/// to init after the session it was started in ended.
+///
+/// Init is also the real parent of services run by systemd or launchd and of
+/// container entrypoints. Those lead their own session (or share init's), so a
+/// child of init only counts as orphaned once its session leader has exited.
+const fn is_orphaned(parent_pid: Option<u32>, parent_alive: bool, session_alive: bool) -> bool {
+    match parent_pid {
+        None => false,
+        Some(INIT_PID) => !session_alive,
+        Some(_) => !parent_alive,
+    }
+}
+
+fn print_text_processes(processes: &[BlzProcess], all: bool) {
+    if processes.is_empty() {
+        if all {
+            println!("No other blz processes running");
+        } else {
+            println!("No orphaned blz processes found");
+        }
+        return;
+    }
+
+    println!(
+        "{:>8}  {:>8}  {:>9}  {:<8}  COMMAND",
+        "PID", "PPID", "UPTIME", "STATUS"
+    );
+    for process in processes {
+        let parent = process
+            .parent_pid
+            .map_or_else(|| "-".to_string(), |pid| pid.to_string());
+        let status = if process.orphaned {
+            format!("{:<8}", "orphaned").yellow().to_string()
+        } else {
+            format!("{:<8}", "running")
+        };
+        println!(
+            "{:>8}  {:>8}  {:>9}  {status}  {}",
+            process.pid,
+            parent,
+            format_uptime(process.running_secs),
+            process.command
+        );
+    }
+
+    let orphaned = processes.iter().filter(|process| process.orphaned).count();
+    if orphaned > 0 {
+        println!("\nTerminate orphans with `blz kill --orphans`");
+    }
+}
+
+fn print_text_outcomes(outcomes: &[KillOutcome], orphans: bool) {
+    if outcomes.is_empty() && orphans {
+        println!("No orphaned blz processes found");
+        return;
+    }
+
+    for outcome in outcomes {
+        match &outcome.error {
+            None => println!(
+                "{} {} ({})",
+                "✓ Terminated".green(),
+                outcome.pid,
+                outcome.command
+            ),
+            Some(err) => eprintln!(
+                "{} {} ({}): {err}",
+                "✗ Failed".red(),
+                outcome.pid,
+                outcome.command
+            ),
+        }
+    }
+}
+
+fn format_uptime(secs: u64) -> String {
+    let hours = secs / 3600;
+    let minutes = (secs % 3600) / 60;
+    if hours >= 24 {
+        format!("{}d{}h", hours / 24, hours % 24)
+    } else if hours > 0 {
+        format!("{hours}h{minutes:02}m")
+    } else if minutes > 0 {
+        format!("{minutes}m{:02}s", secs % 60)
+    } else {
+        format!("{secs}s")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn orphan_detection() {
+        assert!(!is_orphaned(None, false, true));
+        assert!(!is_orphaned(Some(INIT_PID), true, true));
+        assert!(is_orphaned(Some(INIT_PID), true, false));
+        assert!(is_orphaned(Some(4242), false, true));
+        assert!(!is_orphaned(Some(4242), true, true));
+    }
+
+    #[test]
+    fn binary_name_matching() {
+        assert!(is_blz_binary("blz", None));
+        assert!(is_blz_binary("blz.exe", None));
+        assert!(is_blz_binary("blz-dev", Some("blz-dev")));
+        assert!(!is_blz_binary("blazer", Some("blz")));
+    }
+
+    #[test]
+    fn uptime_formatting() {
+        assert_eq!(format_uptime(42), "42s");
+        assert_eq!(format_uptime(125), "2m05s");
+        assert_eq!(format_uptime(3_720), "1h02m");
+        assert_eq!(format_uptime(90_000), "1d1h");
+    }
+}
@@ -12,19 +12,25 @@
 //! blz query "error handling" -H 2,3 --json
 //! ```
 
-use std::io;
+use std::collections::{HashMap, HashSet};
+use std::io::{self, Write};
 
 use anyhow::{Result, bail};
+use blz_core::context_pack::estimate_tokens;
 use blz_core::numeric::percent_to_u8;
-use blz_core::{PerformanceMetrics, ResourceMonitor, SearchHit};
+use blz_core::{PerformanceMetrics, ResourceMonitor, SearchHit, Storage, TocEntry};
 use clap::Args;
 
-use crate::args::{ContextMode, ShowComponent};
+use crate::args::{ContextMode, ShowComponent, ShowComponentParser, ShowSet};
 use crate::config::{
     ContentConfig, DisplayConfig, QueryExecutionConfig, SearchConfig, SnippetConfig,
 };
-use crate::output::shapes::{ContextInfo, SearchHitOutput, SearchOutput};
-use crate::output::{OutputFormat, SearchRenderOptions, render_search_with_options};
+use crate::output::shapes::{
+    ContextInfo, HeadingHitOutput, HeadingSearchOutput, SearchHitOutput, SearchOutput,
+};
+use crate::output::{
+    OutputFormat, SearchRenderOptions, render_heading_search, render_search_with_options,
+};
 use crate::utils::cli_args::FormatArg;
 use crate::utils::heading_filter::HeadingLevelFilter;
 use crate::utils::preferences::CliPreferences;
@@ -48,6 +54,38 @@ pub struct QueryArgs {
     )]
     pub sources: Vec<String>,
 
+    /// Search only the K sources whose topics best match the query (0 disables).
+    ///
+    /// Ignored when sources are given explicitly.
+    #[arg(long, value_name = "K", env = "BLZ_AUTO_SOURCES")]
+    pub auto_sources: Option<usize>,
+    /// Search every installed source, ignoring the project's `.blz.toml` list.
+    #[arg(long)]
+    pub all_sources: bool,
+
+    /// Tolerate typos: match terms within N edits (`--fuzzy=0..2`, bare flag means 1).
+    ///
+    /// Quoted phrases still match exactly. Defaults to `defaults.fuzzy` in config.
+    #[arg(
+        long,
+        value_name = "N",
+        num_args = 0..=1,
+        default_missing_value = "1",
+        require_equals = true,
+        value_parser = clap::value_parser!(u8).range(0..=2)
+    )]
+    pub fuzzy: Option<u8>,
+    /// Rank by meaning instead of keywords, using a local embedding model.
+    ///
+    /// Requires blz built with the `semantic` feature. The model downloads
+    /// once, and a vector index is built beside each source's search index on
+    /// first use.
+    #[arg(long, conflicts_with_all = ["hybrid", "headings_only"])]
+    pub semantic: bool,
+    /// Blend keyword (BM25) and embedding similarity scores.
+    #[arg(long, conflicts_with = "headings_only")]
+    pub hybrid: bool,
+
     /// Maximum number of results per page.
     #[arg(short = 'n', long, value_name = "COUNT", conflicts_with = "all")]
     pub limit: Option<usize>,
@@ -75,13 +113,22 @@ pub struct QueryArgs {
     pub format: FormatArg,
 
     /// Additional columns to include in text output.
-    #[arg(long = "show", value_enum, value_delimiter = ',', env = "BLZ_SHOW")]
+    #[arg(
+        long = "show",
+        value_parser = ShowComponentParser,
+        value_delimiter = ',',
+        env = "BLZ_SHOW"
+    )]
     pub show: Vec<ShowComponent>,
 
     /// Hide the summary/footer line.
     #[arg(long = "no-summary")]
     pub no_summary: bool,
 
+    /// Zero timings, round scores to the display precision, and break ties stably (for snapshots)
+    #[arg(long)]
+    pub deterministic: bool,
+
     /// Number of decimal places to show for scores (0-4).
     #[arg(
         long = "score-precision",
@@ -111,6 +158,21 @@ pub struct QueryArgs {
     )]
     pub max_chars: Option<usize>,
 
+    /// Stop adding hits to a page once their estimated tokens would exceed N
+    ///
+    /// The first hit is always kept. Token counts use the same estimate as `--show tokens`.
+    #[arg(
+        long = "max-tokens",
+        value_name = "N",
+        env = "BLZ_MAX_TOKENS",
+        value_parser = clap::value_parser!(usize)
+    )]
+    pub max_tokens: Option<usize>,
+
+    /// Keep JSON/JSONL snippets as indexed instead of widening them to whole code fences
+    #[arg(long)]
+    pub no_fence_expand: bool,
+
     /// Print LINES lines of context (both before and after match). Same as -C.
     ///
     /// Use "all" to expand to the full heading section containing the match.
@@ -186,6 +248,14 @@ pub struct QueryArgs {
     #[arg(long = "headings-only", display_order = 35)]
     pub headings_only: bool,
 
+    /// Restrict matches to fenced code blocks, skipping prose.
+    #[arg(long, conflicts_with_all = ["headings_only", "semantic", "hybrid"], display_order = 36)]
+    pub code: bool,
+
+    /// Only match code in this language (e.g. `ts`, `python`); requires --code.
+    #[arg(long, value_name = "LANG", requires = "code", display_order = 37)]
+    pub lang: Option<String>,
+
     /// Don't save this search to history.
     #[arg(long = "no-history")]
     pub no_history: bool,
@@ -197,11 +267,18 @@ pub struct QueryArgs {
     /// Show detailed timing breakdown for performance analysis.
     #[arg(long)]
     pub timing: bool,
+
+    /// Search a remote `blz serve` endpoint instead of the local cache.
+    #[arg(long, value_name = "URL", env = "BLZ_REMOTE")]
+    pub remote: Option<String>,
 }
 
+use super::ephemeral::EphemeralSource;
+use super::remote::RemoteClient;
 use super::search::{
     ALL_RESULTS_LIMIT, DEFAULT_SCORE_PRECISION, SearchOptions, SearchResults, clamp_max_chars,
-    copy_results_to_clipboard, default_search_limit, perform_search, resolve_show_components,
+    copy_results_to_clipboard, default_search_limit, make_deterministic, perform_ephemeral_search,
+    perform_search,
 };
 
 /// Detect if input looks like a citation pattern: `alias:digits-digits`
@@ -281,19 +358,28 @@ pub async fn dispatch(
         .with_top_percentile(args.top)
         .with_heading_filter(heading_filter)
         .with_headings_only(args.headings_only)
+        .with_code(args.code, args.lang)
         .with_last(false) // query command doesn't support --last flag
-        .with_no_history(args.no_history);
+        .with_no_history(args.no_history)
+        .with_remote(args.remote)
+        .with_auto_sources(args.auto_sources)
+        .with_all_sources(args.all_sources)
+        .with_fuzzy(args.fuzzy)
+        .with_ranking(args.semantic, args.hybrid);
 
     let display = DisplayConfig::new(resolved_format)
         .with_show(args.show.clone())
         .with_no_summary(args.no_summary)
+        .with_deterministic(args.deterministic)
         .with_timing(args.timing)
         .with_quiet(quiet);
 
     let snippet = SnippetConfig::new()
         .with_lines(args.snippet_lines)
         .with_max_chars(args.max_chars.map_or(200, clamp_max_chars))
-        .with_score_precision(args.score_precision);
+        .with_score_precision(args.score_precision)
+        .with_fence_expand(!args.no_fence_expand)
+        .with_max_tokens(args.max_tokens);
 
     let content = ContentConfig::new()
         .with_context(merged_context)
@@ -365,7 +451,6 @@ fn build_search_options_from_config(
     config: &QueryExecutionConfig,
 ) -> SearchOptions {
     let (before_context, after_context, block) = config.content.resolve_context();
-    let toggles = resolve_show_components(&config.display.show);
 
     SearchOptions {
         query: query.to_string(),
@@ -375,11 +460,9 @@ fn build_search_options_from_config(
         page: config.search.page,
         top_percentile: config.search.top_percentile,
         format: config.display.format,
-        show_url: toggles.url,
-        show_lines: toggles.lines,
-        show_anchor: toggles.anchor,
-        show_raw_score: toggles.raw_score,
+        show: ShowSet::from_components(&config.display.show),
         no_summary: config.display.no_summary,
+        deterministic: config.display.deterministic,
         score_precision: config.snippet.score_precision,
         snippet_lines: config.snippet.lines.max(1),
         all: config.search.limit >= ALL_RESULTS_LIMIT,
@@ -390,9 +473,17 @@ fn build_search_options_from_config(
         block,
         max_block_lines: config.content.max_lines,
         max_chars: config.snippet.max_chars,
+        auto_sources: config.search.auto_sources,
+        all_sources: config.search.all_sources,
+        fuzzy: config.search.fuzzy,
+        ranking: config.search.ranking,
+        fence_expand: config.snippet.fence_expand,
         quiet: config.display.quiet,
         headings_only: config.search.headings_only,
+        code_only: config.search.code_only,
+        code_language: config.search.code_language.clone(),
         timing: config.display.timing,
+        max_tokens: config.snippet.max_tokens,
     }
 }
 
@@ -403,6 +494,74 @@ fn apply_heading_filter(results: &mut SearchResults, heading_filter: Option<&Hea
     }
 }
 
+/// Tables of contents keyed by source alias, used to count subsections in
+/// `--headings-only` output.
+type HeadingTocs = HashMap<String, Vec<TocEntry>>;
+
+/// Collapse `--headings-only` hits to one per heading.
+///
+/// Several matches can land in the same section; only the best-scoring one
+/// (the first, since hits are ranked) is kept.
+fn dedupe_heading_hits(results: &mut SearchResults) {
+    let mut seen = HashSet::new();
+    results
+        .hits
+        .retain(|hit| seen.insert((hit.source.clone(), hit.heading_path.clone())));
+}
+
+/// Load cached tables of contents for the searched sources.
+///
+/// Missing or unreadable sources are skipped; their headings simply report no
+/// child count.
+fn load_heading_tocs(sources: &[String]) -> HeadingTocs {
+    let Ok(storage) = Storage::new() else {
+        return HeadingTocs::new();
+    };
+    sources
+        .iter()
+        .filter_map(|alias| {
+            storage
+                .load_llms_json(alias)
+                .ok()
+                .map(|json| (alias.clone(), json.toc))
+        })
+        .collect()
+}
+
+/// Find the TOC entry for a hit, preferring the anchor and falling back to the
+/// raw or display heading path.
+fn find_toc_entry<'a>(entries: &'a [TocEntry], hit: &SearchHit) -> Option<&'a TocEntry> {
+    entries.iter().find_map(|entry| {
+        let anchor_match = hit.anchor.is_some() && entry.anchor == hit.anchor;
+        let path_match = hit.raw_heading_path.as_ref() == Some(&entry.heading_path)
+            || entry.heading_path == hit.heading_path
+            || entry.heading_path_display.as_ref() == Some(&hit.heading_path);
+        if anchor_match || path_match {
+            Some(entry)
+        } else {
+            find_toc_entry(&entry.children, hit)
+        }
+    })
+}
+
+/// Stamp every source that contributed hits with the time of this search.
+///
+/// Unlike history, this is kept with `--no-history`: it records only that a
+/// source was used, which keeps unused-source reports and pruning accurate.
+fn record_source_access(results: &SearchResults) {
+    let Ok(storage) = Storage::new() else {
+        return;
+    };
+    let mut sources: Vec<&str> = results.hits.iter().map(|hit| hit.source.as_str()).collect();
+    sources.sort_unstable();
+    sources.dedup();
+    for source in sources {
+        if let Err(err) = storage.record_search(source) {
+            tracing::debug!("failed to record search time for {source}: {err}");
+        }
+    }
+}
+
 /// Record search in preferences and history.
 fn record_search_history(
     prefs: &mut CliPreferences,
@@ -411,18 +570,18 @@ fn record_search_history(
     page: usize,
     actual_limit: usize,
     total_pages: usize,
-    total_results: usize,
+    results: &SearchResults,
 ) {
     use crate::utils::{history_log, preferences};
     use tracing::warn;
 
+    let total_results = results.hits.len();
+    let mut hit_sources: Vec<String> = results.hits.iter().map(|hit| hit.source.clone()).collect();
+    hit_sources.sort();
+    hit_sources.dedup();
+
     let precision = options.score_precision.unwrap_or(DEFAULT_SCORE_PRECISION);
-    let show_components = preferences::collect_show_components_extended(
-        options.show_url,
-        options.show_lines,
-        options.show_anchor,
-        options.show_raw_score,
-    );
+    let show_components = options.show.components();
     prefs.set_default_show(&show_components);
     prefs.set_default_score_precision(precision);
     prefs.set_default_snippet_lines(options.snippet_lines);
@@ -448,6 +607,7 @@ fn record_search_history(
                 total_results: Some(total_results),
             })
             .with_headings_only(options.headings_only)
+            .with_hit_sources(hit_sources)
             .build();
 
     if !options.no_history {
@@ -466,6 +626,7 @@ fn convert_hit_to_output(
     hit: &SearchHit,
     max_score: f32,
     context_applied: usize,
+    show: ShowSet,
 ) -> SearchHitOutput {
     // Calculate percentage score
     let percent = if max_score > 0.0 {
@@ -487,24 +648,59 @@ fn convert_hit_to_output(
         }
         info
     });
+    let tokens = show
+        .contains(ShowComponent::Tokens)
+        .then(|| hit_tokens(hit));
 
     SearchHitOutput {
         alias: hit.source.clone(),
         lines: hit.lines.clone(),
         snippet: hit.snippet.clone(),
+        snippet_language: hit.snippet_language.clone(),
+        highlights: hit.highlights.clone(),
         score: score_percentage,
         raw_score: Some(hit.score),
         heading_path: hit.heading_path.clone(),
         level: hit.level,
         anchor: hit.anchor.clone(),
+        section_id: hit.section_id.clone(),
         source_url: hit.source_url.clone(),
         fetched_at: hit.fetched_at,
         is_stale: hit.is_stale,
         checksum: hit.checksum.clone(),
+        tokens,
         context,
     }
 }
 
+/// Estimated tokens in the text a hit returns: its context if expanded, else its snippet.
+fn hit_tokens(hit: &SearchHit) -> usize {
+    let text = hit
+        .context
+        .as_ref()
+        .filter(|ctx| !ctx.content.is_empty())
+        .map_or(hit.snippet.as_str(), |ctx| ctx.content.as_str());
+    estimate_tokens(text)
+}
+
+/// Leading hits of a page whose estimated tokens fit in `max_tokens`.
+///
+/// The first hit is always kept so a tight budget still answers the query.
+fn fit_token_budget(page_hits: &[SearchHit], max_tokens: Option<usize>) -> &[SearchHit] {
+    let Some(budget) = max_tokens else {
+        return page_hits;
+    };
+    let mut used = 0;
+    let fits = page_hits
+        .iter()
+        .take_while(|hit| {
+            used += hit_tokens(hit);
+            used <= budget
+        })
+        .count();
+    &page_hits[..fits.max(1).min(page_hits.len())]
+}
+
 /// Build `SearchOutput` from `SearchResults` with pagination.
 fn build_search_output(
     results: &SearchResults,
@@ -523,7 +719,7 @@ fn build_search_output(
     // Convert hits to output format
     let hit_outputs: Vec<SearchHitOutput> = page_hits
         .iter()
-        .map(|hit| convert_hit_to_output(hit, max_score, context_applied))
+        .map(|hit| convert_hit_to_output(hit, max_score, context_applied, options.show))
         .collect();
 
     SearchOutput::builder(&options.query, hit_outputs)
@@ -546,10 +742,7 @@ fn build_render_options(
 ) -> SearchRenderOptions {
     SearchRenderOptions {
         query: options.query.clone(),
-        show_url: options.show_url,
-        show_lines: options.show_lines,
-        show_anchor: options.show_anchor,
-        show_raw_score: options.show_raw_score,
+        show: options.show,
         no_summary: options.no_summary,
         score_precision: options.score_precision.unwrap_or(DEFAULT_SCORE_PRECISION),
         snippet_lines: usize::from(options.snippet_lines.max(1)),
@@ -587,14 +780,81 @@ fn calculate_pagination(results: &SearchResults, options: &SearchOptions) -> (us
     (page, actual_limit, total_pages)
 }
 
+/// Build `HeadingSearchOutput` for a page of `--headings-only` hits.
+fn build_heading_search_output(
+    results: &SearchResults,
+    options: &SearchOptions,
+    (page, limit, total_pages): (usize, usize, usize),
+    page_hits: &[SearchHit],
+    tocs: &HeadingTocs,
+) -> HeadingSearchOutput {
+    let max_score = results.hits.first().map_or(0.0, |h| h.score);
+    let hit_outputs = page_hits
+        .iter()
+        .map(|hit| {
+            let percent = if max_score > 0.0 {
+                f64::from(hit.score) / f64::from(max_score) * 100.0
+            } else {
+                0.0
+            };
+            HeadingHitOutput {
+                alias: hit.source.clone(),
+                heading: hit.heading_path.last().cloned().unwrap_or_default(),
+                heading_path: hit.heading_path.clone(),
+                level: hit.level,
+                anchor: hit.anchor.clone(),
+                section_id: hit.section_id.clone(),
+                lines: hit.lines.clone(),
+                child_count: tocs
+                    .get(&hit.source)
+                    .and_then(|entries| find_toc_entry(entries, hit))
+                    .map(|entry| entry.children.len()),
+                score: percent_to_u8(percent),
+                raw_score: Some(hit.score),
+            }
+        })
+        .collect();
+
+    HeadingSearchOutput {
+        query: options.query.clone(),
+        results: hit_outputs,
+        total_results: results.hits.len(),
+        total_lines_searched: results.total_lines_searched,
+        search_time_ms: u64::try_from(results.search_time.as_millis()).unwrap_or(u64::MAX),
+        sources: results.sources.clone(),
+        page,
+        limit,
+        total_pages,
+    }
+}
+
 /// Render search results using shape-based output.
 fn render_search_results(
     results: &SearchResults,
     options: &SearchOptions,
+    tocs: &HeadingTocs,
+    writer: &mut impl Write,
 ) -> Result<(usize, usize, usize, usize)> {
     let (page, actual_limit, total_pages) = calculate_pagination(results, options);
     let total_results = results.hits.len();
 
+    if options.headings_only {
+        let page = if total_results == 0 { 0 } else { page };
+        let start_idx = page.saturating_sub(1).saturating_mul(actual_limit);
+        let end_idx = start_idx.saturating_add(actual_limit).min(total_results);
+        let page_hits = results.hits.get(start_idx..end_idx).unwrap_or_default();
+        let output = build_heading_search_output(
+            results,
+            options,
+            (page, actual_limit, total_pages),
+            page_hits,
+            tocs,
+        );
+        let render_options = build_render_options(options, page, total_pages, actual_limit);
+        render_heading_search(&output, options.format, &render_options, writer)?;
+        return Ok((page, actual_limit, total_pages, total_results));
+    }
+
     // Handle empty results
     if total_results == 0 {
         let output = SearchOutput::builder(&options.query, vec![])
@@ -608,8 +868,7 @@ fn render_search_results(
             .build();
 
         let render_options = build_render_options(options, 0, 0, actual_limit);
-        let mut stdout = io::stdout();
-        render_search_with_options(&output, options.format, &render_options, &mut stdout)?;
+        render_search_with_options(&output, options.format, &render_options, writer)?;
         return Ok((0, actual_limit, total_pages, total_results));
     }
 
@@ -638,18 +897,16 @@ fn render_search_results(
             .build();
 
         let render_options = build_render_options(options, page, total_pages, actual_limit);
-        let mut stdout = io::stdout();
-        render_search_with_options(&output, options.format, &render_options, &mut stdout)?;
+        render_search_with_options(&output, options.format, &render_options, writer)?;
         return Ok((page, actual_limit, total_pages, total_results));
     }
 
     // Normal case: render current page
-    let page_hits = &results.hits[start_idx..end_idx];
+    let page_hits = fit_token_budget(&results.hits[start_idx..end_idx], options.max_tokens);
     let output = build_search_output(results, options, page, actual_limit, total_pages, page_hits);
 
     let render_options = build_render_options(options, page, total_pages, actual_limit);
-    let mut stdout = io::stdout();
-    render_search_with_options(&output, options.format, &render_options, &mut stdout)?;
+    render_search_with_options(&output, options.format, &render_options, writer)?;
 
     Ok((page, actual_limit, total_pages, total_results))
 }
@@ -668,18 +925,41 @@ pub(super) async fn execute_internal(
 ) -> Result<()> {
     let options = build_search_options_from_config(query, sources, config);
 
-    let mut results = perform_search(&options, metrics.clone()).await?;
+    let mut results = match config.search.remote.as_deref() {
+        Some(remote) => RemoteClient::new(remote)?.search(&options).await?,
+        None => perform_search(&options, metrics.clone()).await?,
+    };
 
     apply_heading_filter(&mut results, config.search.heading_filter.as_ref());
+    if options.headings_only {
+        dedupe_heading_hits(&mut results);
+    }
+    if options.deterministic {
+        make_deterministic(
+            &mut results,
+            options.score_precision.unwrap_or(DEFAULT_SCORE_PRECISION),
+        );
+    }
+
+    // Subsection counts come from the local cache; remote hits go without
+    let tocs = if options.headings_only && config.search.remote.is_none() {
+        load_heading_tocs(&results.sources)
+    } else {
+        HeadingTocs::new()
+    };
 
     // Use shape-based output rendering
-    let (page, actual_limit, total_pages, total_results) =
-        render_search_results(&results, &options)?;
+    let (page, actual_limit, total_pages, _) =
+        render_search_results(&results, &options, &tocs, &mut io::stdout())?;
 
     if options.copy && !results.hits.is_empty() {
         copy_results_to_clipboard(&results, page, actual_limit)?;
     }
 
+    if config.search.remote.is_none() {
+        record_source_access(&results);
+    }
+
     if let Some(prefs) = prefs {
         record_search_history(
             prefs,
@@ -688,7 +968,7 @@ pub(super) async fn execute_internal(
             page,
             actual_limit,
             total_pages,
-            total_results,
+            &results,
         );
     }
 
@@ -699,6 +979,63 @@ pub(super) async fn execute_internal(
     Ok(())
 }
 
+/// Render the page of `results` that `options` selects, exactly as `blz query`
+/// would print it.
+///
+/// Used by `blz serve` so API responses match CLI output.
+pub(super) fn render_results_page(
+    mut results: SearchResults,
+    options: &SearchOptions,
+    writer: &mut impl Write,
+) -> Result<()> {
+    if options.headings_only {
+        dedupe_heading_hits(&mut results);
+    }
+    let tocs = if options.headings_only {
+        load_heading_tocs(&results.sources)
+    } else {
+        HeadingTocs::new()
+    };
+    render_search_results(&results, options, &tocs, writer)?;
+    Ok(())
+}
+
+/// Search a single ephemeral source (see `blz search --url`).
+///
+/// Results render exactly like a regular search, but nothing is recorded in
+/// search history because the source disappears once the command exits.
+pub(super) fn execute_ephemeral(
+    query: &str,
+    source: &EphemeralSource,
+    config: &QueryExecutionConfig,
+) -> Result<()> {
+    let sources = [source.alias().to_string()];
+    let options = build_search_options_from_config(query, &sources, config);
+
+    let mut results = perform_ephemeral_search(source, &options)?;
+
+    apply_heading_filter(&mut results, config.search.heading_filter.as_ref());
+    if options.headings_only {
+        dedupe_heading_hits(&mut results);
+    }
+    if options.deterministic {
+        make_deterministic(
+            &mut results,
+            options.score_precision.unwrap_or(DEFAULT_SCORE_PRECISION),
+        );
+    }
+
+    let tocs = HeadingTocs::from([(source.alias().to_string(), source.llms_json().toc.clone())]);
+    let (page, actual_limit, _, _) =
+        render_search_results(&results, &options, &tocs, &mut io::stdout())?;
+
+    if options.copy && !results.hits.is_empty() {
+        copy_results_to_clipboard(&results, page, actual_limit)?;
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 #[allow(clippy::expect_used, clippy::unwrap_used)]
 mod tests {
@@ -770,4 +1107,40 @@ mod tests {
         assert!(!options.block);
         assert!(options.no_history);
     }
+
+    fn hit_with_snippet(snippet: &str) -> SearchHit {
+        SearchHit {
+            source: "bun".to_string(),
+            file: "llms.txt".to_string(),
+            heading_path: vec!["Guide".to_string()],
+            raw_heading_path: None,
+            level: 1,
+            lines: "1-2".to_string(),
+            line_numbers: None,
+            snippet: snippet.to_string(),
+            snippet_language: None,
+            highlights: Vec::new(),
+            score: 1.0,
+            source_url: None,
+            fetched_at: None,
+            is_stale: false,
+            checksum: String::new(),
+            anchor: None,
+            section_id: None,
+            context: None,
+        }
+    }
+
+    #[test]
+    fn test_fit_token_budget_keeps_leading_hits() {
+        let hits: Vec<SearchHit> = (0..3).map(|_| hit_with_snippet(&"x".repeat(40))).collect();
+        let per_hit = hit_tokens(&hits[0]);
+
+        assert_eq!(fit_token_budget(&hits, None).len(), 3);
+        assert_eq!(fit_token_budget(&hits, Some(per_hit * 2)).len(), 2);
+        assert_eq!(fit_token_budget(&hits, Some(per_hit * 2 - 1)).len(), 1);
+        // A budget smaller than the first hit still returns it
+        assert_eq!(fit_token_budget(&hits, Some(0)).len(), 1);
+        assert!(fit_token_budget(&[], Some(0)).is_empty());
+    }
 }
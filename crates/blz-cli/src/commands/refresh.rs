@@ -9,11 +9,13 @@ use blz_core::refresh::{
     DefaultRefreshIndexer, RefreshContext, RefreshOutcome, RefreshStorage, RefreshUrlResolution,
     refresh_source_with_metadata, reindex_source, resolve_refresh_url,
 };
-use blz_core::{Fetcher, PerformanceMetrics, Storage};
+use blz_core::throttle::Bandwidth;
+use blz_core::{PerformanceMetrics, Storage};
 use colored::Colorize;
 use indicatif::{ProgressBar, ProgressStyle};
 
 use crate::config::SyncConfig;
+use crate::i18n::{self, Message};
 use crate::utils::filter_flags;
 use crate::utils::resolver;
 
@@ -29,7 +31,7 @@ fn create_spinner(message: &str) -> ProgressBar {
 }
 
 /// Execute reindex: re-parse and re-index from cached content.
-fn execute_reindex(
+pub(super) fn execute_reindex(
     storage: &Storage,
     alias: &str,
     metrics: PerformanceMetrics,
@@ -119,6 +121,7 @@ pub async fn execute(
     reindex: bool,
     filter: Option<&String>,
     no_filter: bool,
+    throttle: Option<Bandwidth>,
 ) -> Result<()> {
     let storage = Storage::new()?;
     let canonical_alias =
@@ -148,7 +151,7 @@ pub async fn execute(
     let start = Instant::now();
     let existing_metadata = storage.load_metadata(&canonical_alias)?;
     let existing_aliases = storage.load_llms_aliases(&canonical_alias)?;
-    let fetcher = Fetcher::new()?;
+    let fetcher = crate::utils::throttle::fetcher(throttle)?;
 
     let filter_flags = filter_flags::parse_filter_flags(filter);
     let filter_preference = if no_filter {
@@ -207,10 +210,14 @@ pub async fn execute(
 #[allow(clippy::too_many_lines)]
 pub async fn execute_all(metrics: PerformanceMetrics, config: &SyncConfig) -> Result<()> {
     let storage = Storage::new()?;
-    let sources = storage.list_sources();
+    let sources: Vec<String> = storage
+        .list_sources()
+        .into_iter()
+        .filter(|alias| !storage.is_shared(alias))
+        .collect();
 
     if sources.is_empty() {
-        anyhow::bail!("No sources configured. Use 'blz add' to add sources.");
+        anyhow::bail!(i18n::text(Message::NoSourcesConfigured, &[]));
     }
 
     if config.reindex {
@@ -254,7 +261,7 @@ pub async fn execute_all(metrics: PerformanceMetrics, config: &SyncConfig) -> Re
         return Ok(());
     }
 
-    let fetcher = Fetcher::new()?;
+    let fetcher = crate::utils::throttle::fetcher(config.throttle)?;
     let mut refreshed_count = 0;
     let mut skipped_count = 0;
     let mut error_count = 0;
@@ -464,6 +471,7 @@ pub async fn handle_refresh(
             resolved_config.reindex,
             resolved_config.filter.as_ref(),
             resolved_config.no_filter,
+            resolved_config.throttle,
         )
         .await?;
     }
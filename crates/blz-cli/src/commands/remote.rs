@@ -0,0 +1,244 @@
+//! Client mode: run search and retrieval against a remote `blz serve` instance.
+//!
+//! `--remote <URL>` (or `BLZ_REMOTE`) sends the request to another machine's
+//! HTTP endpoint instead of the local cache. The server returns raw hits and
+//! retrieved lines; pagination, formatting, clipboard, and history stay on the
+//! client so every output format matches a local run.
+
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result, bail};
+use blz_core::SearchHit;
+use blz_core::embeddings::Ranking;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use super::get::{GetProcessParams, ProcessedRequest, RequestSpec};
+use super::search::{SearchOptions, SearchResults, expands_fences};
+
+/// Route for search requests, relative to the remote base URL.
+pub(super) const SEARCH_ROUTE: &str = "v1/search";
+/// Route for line retrieval requests, relative to the remote base URL.
+pub(super) const GET_ROUTE: &str = "v1/get";
+
+/// Remote requests can run a full multi-source search, so allow more time
+/// than a plain document fetch.
+const REMOTE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Search response body returned by `GET /v1/search`.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct RemoteSearchResponse {
+    pub hits: Vec<SearchHit>,
+    pub total_lines_searched: usize,
+    pub sources: Vec<String>,
+}
+
+/// Error body returned by the server for non-success responses.
+#[derive(Debug, Deserialize)]
+struct RemoteError {
+    error: String,
+}
+
+/// HTTP client for a remote `blz serve` endpoint.
+pub(super) struct RemoteClient {
+    base: Url,
+    client: reqwest::Client,
+}
+
+impl RemoteClient {
+    /// Create a client for `base` (e.g. `http://cache-box:7777`).
+    pub(super) fn new(base: &str) -> Result<Self> {
+        let mut url = Url::parse(base).with_context(|| format!("Invalid remote URL '{base}'"))?;
+        if !matches!(url.scheme(), "http" | "https") {
+            bail!("Remote URL must use http or https: {base}");
+        }
+        if !url.path().ends_with('/') {
+            let path = format!("{}/", url.path());
+            url.set_path(&path);
+        }
+
+        let client = reqwest::Client::builder()
+            .timeout(REMOTE_TIMEOUT)
+            .user_agent(concat!("blz/", env!("CARGO_PKG_VERSION")))
+            .build()?;
+
+        Ok(Self { base: url, client })
+    }
+
+    /// Run a search remotely and return hits ready for local rendering.
+    pub(super) async fn search(&self, options: &SearchOptions) -> Result<SearchResults> {
+        let start = Instant::now();
+        let response: RemoteSearchResponse =
+            self.request(SEARCH_ROUTE, &search_query(options)).await?;
+
+        Ok(SearchResults {
+            hits: response.hits,
+            total_lines_searched: response.total_lines_searched,
+            search_time: start.elapsed(),
+            sources: response.sources,
+        })
+    }
+
+    /// Retrieve line ranges remotely.
+    pub(super) async fn get(
+        &self,
+        specs: &[RequestSpec],
+        params: &GetProcessParams,
+    ) -> Result<Vec<ProcessedRequest>> {
+        self.request(GET_ROUTE, &get_query(specs, params)).await
+    }
+
+    async fn request<T: DeserializeOwned>(
+        &self,
+        route: &str,
+        query: &[(&str, String)],
+    ) -> Result<T> {
+        let url = self.base.join(route)?;
+        let response = self
+            .client
+            .get(url.clone())
+            .query(query)
+            .send()
+            .await
+            .with_context(|| format!("Failed to reach remote blz at {}", self.base))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            let message = serde_json::from_str::<RemoteError>(&body)
+                .map_or_else(|_| body.trim().to_string(), |err| err.error);
+            bail!("Remote blz at {} returned {status}: {message}", self.base);
+        }
+
+        response
+            .json()
+            .await
+            .with_context(|| format!("Invalid response from {url}"))
+    }
+}
+
+/// Query parameters for `GET /v1/search`.
+///
+/// Only parameters that change which hits come back, or what their snippets
+/// contain, are sent; display options are applied locally.
+fn search_query(options: &SearchOptions) -> Vec<(&'static str, String)> {
+    let mut query = vec![
+        ("q", options.query.clone()),
+        ("limit", options.limit.to_string()),
+        ("maxChars", options.max_chars.to_string()),
+    ];
+    query.extend(
+        options
+            .sources
+            .iter()
+            .map(|source| ("source", source.clone())),
+    );
+    if options.all {
+        query.push(("all", "true".to_string()));
+    }
+    if let Some(top) = options.top_percentile {
+        query.push(("top", top.to_string()));
+    }
+    if options.headings_only {
+        query.push(("headingsOnly", "true".to_string()));
+    }
+    if options.code_only {
+        query.push(("code", "true".to_string()));
+    }
+    if let Some(language) = &options.code_language {
+        query.push(("lang", language.clone()));
+    }
+    if options.block {
+        query.push(("block", "true".to_string()));
+    }
+    if let Some(max_lines) = options.max_block_lines {
+        query.push(("maxLines", max_lines.to_string()));
+    }
+    if options.before_context > 0 {
+        query.push(("before", options.before_context.to_string()));
+    }
+    if options.after_context > 0 {
+        query.push(("after", options.after_context.to_string()));
+    }
+    if let Some(auto_sources) = options.auto_sources {
+        query.push(("autoSources", auto_sources.to_string()));
+    }
+    if let Some(fuzzy) = options.fuzzy {
+        query.push(("fuzzy", fuzzy.to_string()));
+    }
+    match options.ranking {
+        Ranking::Lexical => {},
+        Ranking::Semantic => query.push(("semantic", "true".to_string())),
+        Ranking::Hybrid => query.push(("hybrid", "true".to_string())),
+    }
+    if expands_fences(options) {
+        query.push(("fenceExpand", "true".to_string()));
+    }
+    query
+}
+
+/// Query parameters for `GET /v1/get`; each spec is sent as `alias:ranges`.
+fn get_query(specs: &[RequestSpec], params: &GetProcessParams) -> Vec<(&'static str, String)> {
+    let mut query: Vec<_> = specs
+        .iter()
+        .map(|spec| ("target", format!("{}:{}", spec.alias, spec.line_expression)))
+        .collect();
+    if params.block_mode {
+        query.push(("block", "true".to_string()));
+    }
+    if let Some(max_lines) = params.max_block_lines {
+        query.push(("maxLines", max_lines.to_string()));
+    }
+    if params.before_context > 0 {
+        query.push(("before", params.before_context.to_string()));
+    }
+    if params.after_context > 0 {
+        query.push(("after", params.after_context.to_string()));
+    }
+    if let Some(at) = &params.at {
+        query.push(("at", at.clone()));
+    }
+    query
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base_url_gets_trailing_slash_so_routes_nest() {
+        let client = RemoteClient::new("http://cache-box:7777/blz").unwrap();
+        assert_eq!(
+            client.base.join(SEARCH_ROUTE).unwrap().as_str(),
+            "http://cache-box:7777/blz/v1/search"
+        );
+        assert!(RemoteClient::new("ftp://cache-box").is_err());
+        assert!(RemoteClient::new("not a url").is_err());
+    }
+
+    #[test]
+    fn get_query_encodes_targets_and_context() {
+        let specs = [RequestSpec {
+            alias: "bun".to_string(),
+            line_expression: "1-3,10-12".to_string(),
+        }];
+        let params = GetProcessParams {
+            before_context: 2,
+            after_context: 2,
+            block_mode: false,
+            max_block_lines: None,
+            at: None,
+        };
+        assert_eq!(
+            get_query(&specs, &params),
+            vec![
+                ("target", "bun:1-3,10-12".to_string()),
+                ("before", "2".to_string()),
+                ("after", "2".to_string()),
+            ]
+        );
+    }
+}
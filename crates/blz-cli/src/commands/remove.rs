@@ -1,14 +1,15 @@
 //! Remove command implementation
 
 use std::fs;
-use std::io::{self, IsTerminal, Write};
+use std::io::{self, Write};
 
 use anyhow::Result;
 use blz_core::{LlmsJson, Storage};
 use colored::Colorize;
-use inquire::Confirm;
 use serde::Serialize;
 
+use crate::utils::confirm::{self, Risk};
+
 /// Abstraction over the storage interactions needed by the remove command.
 pub trait RemoveStorage {
     fn exists(&self, alias: &str) -> Result<bool>;
@@ -28,10 +29,17 @@ impl RemoveStorage for Storage {
     }
 
     fn delete_source(&self, alias: &str) -> Result<()> {
+        if self.is_shared(alias) {
+            anyhow::bail!(
+                "Source '{alias}' comes from the read-only shared cache and cannot be removed here"
+            );
+        }
         let dir = self.tool_dir(alias)?;
         fs::remove_dir_all(&dir).map_err(|e| {
             anyhow::anyhow!("Failed to remove source directory '{}': {e}", dir.display())
-        })
+        })?;
+        self.invalidate_query_cache();
+        Ok(())
     }
 }
 
@@ -143,34 +151,21 @@ pub async fn execute(alias: &str, auto_yes: bool, quiet: bool) -> Result<()> {
     let canonical = crate::utils::resolver::resolve_source(&storage, alias)?
         .unwrap_or_else(|| alias.to_string());
 
-    let force_non_interactive = std::env::var_os("BLZ_FORCE_NON_INTERACTIVE").is_some();
-    let no_tty = !std::io::stdin().is_terminal();
-    let require_confirmation = !(auto_yes || force_non_interactive || no_tty);
-
     let stdout = io::stdout();
     let mut stdout_lock = stdout.lock();
     let mut sink = io::sink();
     let writer: &mut dyn Write = if quiet { &mut sink } else { &mut stdout_lock };
 
-    let outcome = execute_remove(
-        &storage,
-        &canonical,
-        writer,
-        require_confirmation,
-        |alias, _info| {
-            let prompt_stdout = io::stdout();
-            let mut prompt_lock = prompt_stdout.lock();
-            let prompt = format!("Remove source '{alias}' and all cached data?");
-            if quiet {
-                return Ok(true);
-            }
-            let confirmed = Confirm::new(&prompt).with_default(false).prompt()?;
-            if !confirmed {
-                writeln!(prompt_lock)?;
-            }
-            Ok(confirmed)
-        },
-    )?;
+    let outcome = execute_remove(&storage, &canonical, writer, true, |alias, _info| {
+        let prompt_stdout = io::stdout();
+        let mut prompt_lock = prompt_stdout.lock();
+        let prompt = format!("Remove source '{alias}' and all cached data?");
+        let confirmed = confirm::confirm(&prompt, Risk::Destructive, auto_yes)?;
+        if !confirmed {
+            writeln!(prompt_lock)?;
+        }
+        Ok(confirmed)
+    })?;
 
     // Return error for not-found to ensure proper exit code
     match outcome {
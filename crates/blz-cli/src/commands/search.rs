@@ -1,23 +1,29 @@
 //! Search command implementation
 
 use anyhow::{Context, Result};
+use blz_core::embeddings::{self, DEFAULT_HYBRID_WEIGHT, Embedder, Ranking, VectorIndex};
+use blz_core::highlight;
 use blz_core::index::{DEFAULT_SNIPPET_CHAR_LIMIT, MAX_SNIPPET_CHAR_LIMIT, MIN_SNIPPET_CHAR_LIMIT};
 use blz_core::numeric::percentile_count;
+use blz_core::query_dsl;
+use blz_core::router::{SourceProfile, select_sources};
 use blz_core::{
-    HitContext, LlmsJson, PerformanceMetrics, ResourceMonitor, SearchHit, SearchIndex, Source,
-    Storage,
+    Config, HitContext, LlmsJson, PerformanceMetrics, QueryCacheKey, ResourceMonitor, SearchHit,
+    SearchIndex, SourceSummary, Storage,
 };
 use clap::Args;
 use futures::stream::{self, StreamExt};
 use fuzzy_matcher::FuzzyMatcher;
 use fuzzy_matcher::skim::SkimMatcherV2;
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Instant;
 use tracing::warn;
 
-use crate::args::{ContextMode, ShowComponent};
+use crate::args::{ContextMode, ShowComponent, ShowComponentParser, ShowSet};
 use crate::cli::{Commands, merge_context_flags};
+use crate::i18n::{self, Message};
 use crate::output::{FormatParams, OutputFormat, SearchResultFormatter};
 use crate::utils::cli_args::{FormatArg, flag_present};
 use crate::utils::history_log;
@@ -25,8 +31,10 @@ use crate::utils::parsing::parse_line_span;
 use crate::utils::preferences::{CliPreferences, SearchHistoryEntry};
 use crate::utils::staleness::{self, DEFAULT_STALE_AFTER_DAYS};
 use crate::utils::toc::{
-    extract_block_slice, finalize_block_slice, find_heading_span, heading_level_from_line,
+    expand_to_fences, extract_block_slice, fence_spans, finalize_block_slice, find_heading_span,
+    heading_level_from_line,
 };
+use crate::utils::workspace;
 
 pub(super) const ALL_RESULTS_LIMIT: usize = 10_000;
 pub(super) const DEFAULT_SCORE_PRECISION: u8 = 1;
@@ -52,6 +60,19 @@ pub struct SearchArgs {
     /// Search query (required unless --next, --previous, or --last)
     #[arg(required_unless_present_any = ["next", "previous", "last"])]
     pub query: Option<String>,
+    /// Search a remote llms.txt without adding it as a source
+    ///
+    /// The document is fetched and indexed in a temporary location that is
+    /// discarded after the search. Combine with --keep to add it afterwards.
+    #[arg(
+        long,
+        value_name = "URL",
+        conflicts_with_all = ["sources", "next", "previous", "last"]
+    )]
+    pub url: Option<String>,
+    /// Add the --url document as a persistent source after searching
+    #[arg(long, requires = "url")]
+    pub keep: bool,
     /// Filter by source(s) - comma-separated or repeated (-s a -s b)
     #[arg(
         long = "source",
@@ -62,6 +83,36 @@ pub struct SearchArgs {
         value_delimiter = ','
     )]
     pub sources: Vec<String>,
+    /// Search only the K sources whose topics best match the query (0 disables).
+    ///
+    /// Ignored when sources are given explicitly.
+    #[arg(long, value_name = "K", env = "BLZ_AUTO_SOURCES")]
+    pub auto_sources: Option<usize>,
+    /// Search every installed source, ignoring the project's `.blz.toml` list.
+    #[arg(long)]
+    pub all_sources: bool,
+    /// Tolerate typos: match terms within N edits (`--fuzzy=0..2`, bare flag means 1).
+    ///
+    /// Quoted phrases still match exactly. Defaults to `defaults.fuzzy` in config.
+    #[arg(
+        long,
+        value_name = "N",
+        num_args = 0..=1,
+        default_missing_value = "1",
+        require_equals = true,
+        value_parser = clap::value_parser!(u8).range(0..=2)
+    )]
+    pub fuzzy: Option<u8>,
+    /// Rank by meaning instead of keywords, using a local embedding model.
+    ///
+    /// Requires blz built with the `semantic` feature. The model downloads
+    /// once, and a vector index is built beside each source's search index on
+    /// first use.
+    #[arg(long, conflicts_with_all = ["hybrid", "headings_only"])]
+    pub semantic: bool,
+    /// Blend keyword (BM25) and embedding similarity scores.
+    #[arg(long, conflicts_with = "headings_only")]
+    pub hybrid: bool,
     /// Continue from previous search (next page)
     #[arg(
         long,
@@ -128,11 +179,19 @@ pub struct SearchArgs {
     #[command(flatten)]
     pub format: FormatArg,
     /// Additional columns to include in text output
-    #[arg(long = "show", value_enum, value_delimiter = ',', env = "BLZ_SHOW")]
+    #[arg(
+        long = "show",
+        value_parser = ShowComponentParser,
+        value_delimiter = ',',
+        env = "BLZ_SHOW"
+    )]
     pub show: Vec<ShowComponent>,
     /// Hide the summary/footer line
     #[arg(long = "no-summary")]
     pub no_summary: bool,
+    /// Zero timings, round scores to the display precision, and break ties stably (for snapshots)
+    #[arg(long)]
+    pub deterministic: bool,
     /// Number of decimal places to show for scores (0-4)
     #[arg(
         long = "score-precision",
@@ -159,6 +218,20 @@ pub struct SearchArgs {
         value_parser = clap::value_parser!(usize)
     )]
     pub max_chars: Option<usize>,
+
+    /// Stop adding hits to a page once their estimated tokens would exceed N
+    ///
+    /// The first hit is always kept. Token counts use the same estimate as `--show tokens`.
+    #[arg(
+        long = "max-tokens",
+        value_name = "N",
+        env = "BLZ_MAX_TOKENS",
+        value_parser = clap::value_parser!(usize)
+    )]
+    pub max_tokens: Option<usize>,
+    /// Keep JSON/JSONL snippets as indexed instead of widening them to whole code fences
+    #[arg(long)]
+    pub no_fence_expand: bool,
     /// Print LINES lines of context (both before and after match). Same as -C.
     ///
     /// Use "all" to expand to the full heading section containing the match.
@@ -240,6 +313,12 @@ pub struct SearchArgs {
     /// Restrict matches to heading text only
     #[arg(long = "headings-only", display_order = 35)]
     pub headings_only: bool,
+    /// Restrict matches to fenced code blocks, skipping prose
+    #[arg(long, conflicts_with_all = ["headings_only", "semantic", "hybrid"], display_order = 36)]
+    pub code: bool,
+    /// Only match code in this language (e.g. `ts`, `python`); requires --code
+    #[arg(long, value_name = "LANG", requires = "code", display_order = 37)]
+    pub lang: Option<String>,
     /// Don't save this search to history
     #[arg(long = "no-history")]
     pub no_history: bool,
@@ -249,6 +328,9 @@ pub struct SearchArgs {
     /// Show detailed timing breakdown for performance analysis
     #[arg(long)]
     pub timing: bool,
+    /// Search a remote `blz serve` endpoint instead of the local cache
+    #[arg(long, value_name = "URL", env = "BLZ_REMOTE", conflicts_with = "url")]
+    pub remote: Option<String>,
 }
 
 /// Search options
@@ -262,11 +344,9 @@ pub(super) struct SearchOptions {
     pub page: usize,
     pub top_percentile: Option<u8>,
     pub format: OutputFormat,
-    pub show_url: bool,
-    pub show_lines: bool,
-    pub show_anchor: bool,
-    pub show_raw_score: bool,
+    pub show: ShowSet,
     pub no_summary: bool,
+    pub deterministic: bool,
     pub score_precision: Option<u8>,
     pub snippet_lines: u8,
     pub(crate) all: bool,
@@ -277,34 +357,17 @@ pub(super) struct SearchOptions {
     pub block: bool,
     pub max_block_lines: Option<usize>,
     pub max_chars: usize,
+    pub auto_sources: Option<usize>,
+    pub all_sources: bool,
+    pub fuzzy: Option<u8>,
+    pub ranking: Ranking,
+    pub fence_expand: bool,
     pub quiet: bool,
     pub headings_only: bool,
+    pub code_only: bool,
+    pub code_language: Option<String>,
     pub timing: bool,
-}
-
-#[derive(Default, Debug, Clone, Copy)]
-#[allow(clippy::struct_excessive_bools)]
-pub(super) struct ShowToggles {
-    pub(super) url: bool,
-    pub(super) lines: bool,
-    pub(super) anchor: bool,
-    pub(super) raw_score: bool,
-}
-
-pub(super) fn resolve_show_components(components: &[ShowComponent]) -> ShowToggles {
-    let mut toggles = ShowToggles::default();
-    for component in components {
-        match component {
-            ShowComponent::Rank => {
-                // Rank is always displayed by default; accept the modifier for compatibility.
-            },
-            ShowComponent::Url => toggles.url = true,
-            ShowComponent::Lines => toggles.lines = true,
-            ShowComponent::Anchor => toggles.anchor = true,
-            ShowComponent::RawScore => toggles.raw_score = true,
-        }
-    }
-    toggles
+    pub max_tokens: Option<usize>,
 }
 
 /// Clamp snippet character limits to the supported range.
@@ -351,6 +414,13 @@ pub(super) struct SearchResults {
     pub(super) sources: Vec<String>,
 }
 
+/// Effective fuzzy distance: the `--fuzzy` flag wins, then `defaults.fuzzy` /
+/// `BLZ_FUZZY` from config. Exact matching when neither is set.
+fn fuzzy_distance(flag: Option<u8>) -> u8 {
+    flag.or_else(|| Config::load().ok().and_then(|config| config.defaults.fuzzy))
+        .unwrap_or(0)
+}
+
 fn get_max_concurrent_searches() -> usize {
     std::thread::available_parallelism().map_or(8, |n| (n.get().saturating_mul(2)).min(16))
 }
@@ -386,8 +456,12 @@ fn resolve_requested_sources(
                     suggestions.sort_by(|a, b| b.0.cmp(&a.0));
                     suggestions.truncate(3);
 
+                    let warning = i18n::text(Message::WarningPrefix, &[]);
                     if suggestions.is_empty() {
-                        eprintln!("Warning: Source '{alias}' not found.");
+                        eprintln!(
+                            "{warning} {}",
+                            i18n::text(Message::SourceNotFound, &[("alias", alias)])
+                        );
                     } else {
                         let suggestion_list = suggestions
                             .iter()
@@ -395,10 +469,14 @@ fn resolve_requested_sources(
                             .collect::<Vec<_>>()
                             .join(", ");
                         eprintln!(
-                            "Warning: Source '{alias}' not found. Did you mean: {suggestion_list}?"
+                            "{warning} {}",
+                            i18n::text(
+                                Message::SourceNotFoundSuggestions,
+                                &[("alias", alias), ("suggestions", &suggestion_list)]
+                            )
                         );
                     }
-                    eprintln!("Run 'blz list' to see all sources.");
+                    eprintln!("{}", i18n::text(Message::HintListSources, &[]));
                 }
                 resolved.push(alias.clone());
             },
@@ -408,57 +486,103 @@ fn resolve_requested_sources(
     Ok(resolved)
 }
 
+/// Per-query summaries of the candidate sources, keyed by alias.
+///
+/// Loaded once per search so filtering, the query cache key, line counts, and
+/// provenance never parse a source's `llms.json` or `metadata.json`.
+type Summaries = HashMap<String, SourceSummary>;
+
+fn load_summaries(storage: &Storage, sources: &[String]) -> Summaries {
+    sources
+        .iter()
+        .filter_map(|alias| match storage.load_source_summary(alias) {
+            Ok(summary) => summary.map(|summary| (alias.clone(), summary)),
+            Err(e) => {
+                warn!("Failed to load summary for {alias}: {e}");
+                None
+            },
+        })
+        .collect()
+}
+
 /// Filter out sources that aren't searchable (index-only or internal).
 fn filter_searchable_sources(
-    storage: &Storage,
+    summaries: &Summaries,
     sources: Vec<String>,
     explicit_sources_requested: bool,
 ) -> Vec<String> {
     sources
         .into_iter()
         .filter(|alias| {
-            match storage.load_source_metadata(alias) {
-                Ok(Some(metadata)) => {
-                    if metadata.is_index_only() {
-                        return false;
-                    }
-                    if !explicit_sources_requested && metadata.is_internal() {
-                        return false;
-                    }
-                    true
-                },
-                Ok(None) | Err(_) => true, // Allow search when metadata missing or failed
-            }
+            // Allow search when the summary is missing or failed to load
+            summaries.get(alias).is_none_or(|summary| {
+                !summary.is_index_only() && (explicit_sources_requested || !summary.is_internal())
+            })
         })
         .collect()
 }
 
+/// Narrow an unscoped search to the active workspace's declared sources,
+/// unless `--all-sources` was given.
+fn scope_to_workspace(storage: &Storage, sources: Vec<String>, all_sources: bool) -> Vec<String> {
+    match workspace::active() {
+        Some(workspace) if !all_sources => workspace.restrict(storage, sources),
+        _ => sources,
+    }
+}
+
+/// Narrow an unscoped search to the sources whose topics best match the query.
+///
+/// Falls back to every source when routing is off or no source matches.
+fn route_sources(storage: &Storage, sources: Vec<String>, options: &SearchOptions) -> Vec<String> {
+    let Some(limit) = options.auto_sources.filter(|limit| *limit > 0) else {
+        return sources;
+    };
+    let profiles: Vec<SourceProfile> = sources
+        .iter()
+        .filter_map(|alias| SourceProfile::load(storage, alias).ok())
+        .collect();
+    let Some(selected) = select_sources(&options.query, &profiles, limit) else {
+        return sources;
+    };
+    if !options.quiet && matches!(options.format, OutputFormat::Text) {
+        eprintln!("Auto-selected sources: {}", selected.join(", "));
+    }
+    selected
+}
+
 /// Enrich search hits with source metadata (URL, checksum, staleness).
-fn enrich_hits_with_source_metadata(hits: &mut [SearchHit], storage: &Storage) {
-    let mut metadata_cache: HashMap<String, Option<Source>> = HashMap::new();
+fn enrich_hits_with_source_metadata(hits: &mut [SearchHit], summaries: &Summaries) {
     for hit in hits {
-        let entry = metadata_cache
-            .entry(hit.source.clone())
-            .or_insert_with(|| storage.load_source_metadata(&hit.source).ok().flatten());
-        if let Some(meta) = entry {
-            hit.source_url = Some(meta.url.clone());
-            hit.checksum = meta.sha256.clone();
-            hit.fetched_at = Some(meta.fetched_at);
-            hit.is_stale = staleness::is_stale(meta.fetched_at, DEFAULT_STALE_AFTER_DAYS);
-        } else {
-            hit.source_url = None;
-            hit.fetched_at = None;
-            hit.is_stale = false;
-        }
-        hit.context = None;
+        let summary = summaries.get(&hit.source);
+        apply_source_metadata(hit, summary);
     }
 }
 
+/// Attach provenance and staleness from a source's summary to a hit.
+fn apply_source_metadata(hit: &mut SearchHit, meta: Option<&SourceSummary>) {
+    if let Some(meta) = meta {
+        hit.source_url = Some(meta.url.clone());
+        hit.checksum.clone_from(&meta.sha256);
+        hit.fetched_at = Some(meta.fetched_at);
+        hit.is_stale = staleness::is_stale(meta.fetched_at, DEFAULT_STALE_AFTER_DAYS);
+    } else {
+        hit.source_url = None;
+        hit.fetched_at = None;
+        hit.is_stale = false;
+    }
+    hit.context = None;
+}
+
 pub(super) async fn perform_search(
     options: &SearchOptions,
     metrics: PerformanceMetrics,
 ) -> Result<SearchResults> {
     let start_time = Instant::now();
+    // Per-source failures are only logged, so reject malformed syntax up front
+    if query_dsl::is_structured(&options.query) {
+        query_dsl::parse(&options.query)?;
+    }
     let storage = Arc::new(Storage::new()?);
 
     // Resolve requested sources (supports metadata aliases)
@@ -466,94 +590,434 @@ pub(super) async fn perform_search(
     let sources = if explicit_sources_requested {
         resolve_requested_sources(&storage, &options.sources, options.quiet)?
     } else {
-        storage.list_sources()
+        scope_to_workspace(&storage, storage.list_sources(), options.all_sources)
     };
 
+    let summaries = load_summaries(&storage, &sources);
+
     // Filter out index-only sources (navigation-only, no searchable content)
-    let sources = filter_searchable_sources(&storage, sources, explicit_sources_requested);
+    let sources = filter_searchable_sources(&summaries, sources, explicit_sources_requested);
 
     if sources.is_empty() {
-        return Err(anyhow::anyhow!(
-            "No sources found. Use 'blz add' to add sources."
-        ));
+        return Err(anyhow::anyhow!(i18n::text(Message::NoSourcesFound, &[])));
+    }
+
+    let sources = if explicit_sources_requested {
+        sources
+    } else {
+        route_sources(&storage, sources, options)
+    };
+
+    let cache_key = query_cache_key(&storage, &summaries, &sources, options);
+    if let Some(key) = cache_key.clone() {
+        if let Some((hits, total_lines_searched, sources)) = storage.query_cache().get(key) {
+            let mut results = SearchResults {
+                hits,
+                total_lines_searched,
+                search_time: start_time.elapsed(),
+                sources,
+            };
+            refresh_staleness(&mut results.hits);
+            return Ok(results);
+        }
     }
 
     // Execute parallel searches across all sources
-    let (mut all_hits, total_lines_searched, sources_searched) =
-        execute_parallel_searches(&storage, sources, options, metrics).await?;
+    let (mut all_hits, total_lines_searched, sources_searched, complete) =
+        execute_parallel_searches(&storage, &summaries, sources, options, metrics).await?;
 
-    // Process results
-    deduplicate_hits(&mut all_hits);
-    sort_by_score(&mut all_hits);
-    apply_percentile_filter(
-        &mut all_hits,
-        options.top_percentile,
-        matches!(options.format, OutputFormat::Text),
-    );
+    rank_hits(&mut all_hits, options);
 
     // Enrich results with metadata for provenance and staleness calculations
-    enrich_hits_with_source_metadata(&mut all_hits, &storage);
+    enrich_hits_with_source_metadata(&mut all_hits, &summaries);
 
     // Enrich with context if requested
     let mut llms_cache: HashMap<String, Option<LlmsJson>> = HashMap::new();
     let mut line_cache: HashMap<String, Vec<String>> = HashMap::new();
+    enrich_hits_with_content(
+        &mut all_hits,
+        options,
+        Some(storage.as_ref()),
+        &mut llms_cache,
+        &mut line_cache,
+    );
+
+    let mut sources_searched = sources_searched;
+    sources_searched.sort();
+
+    // Partial results from a failed source must not be replayed later
+    if let Some(key) = cache_key.filter(|_| complete) {
+        let entry = (&all_hits, total_lines_searched, &sources_searched);
+        if let Err(e) = storage.query_cache().put(key, &entry) {
+            warn!("Failed to cache search results: {e}");
+        }
+    }
+
+    Ok(SearchResults {
+        hits: all_hits,
+        total_lines_searched,
+        search_time: start_time.elapsed(),
+        sources: sources_searched,
+    })
+}
+
+/// Key for caching the results of `options` over `sources`, or `None` when
+/// the query cache is disabled or bypassed.
+///
+/// Covers every option [`perform_search`] reads, plus each source's current
+/// generation; sources without a summary are never cached.
+fn query_cache_key(
+    storage: &Storage,
+    summaries: &Summaries,
+    sources: &[String],
+    options: &SearchOptions,
+) -> Option<QueryCacheKey> {
+    // Timing breakdowns only mean something when the index is actually searched
+    if options.timing || !query_cache_enabled() {
+        return None;
+    }
+    let mut key = QueryCacheKey::new(&options.query)
+        .param("limit", &effective_search_limit(options))
+        .param("top", &format!("{:?}", options.top_percentile))
+        .param("format", &format!("{:?}", options.format))
+        .param(
+            "context",
+            &format!("{}:{}", options.before_context, options.after_context),
+        )
+        .param(
+            "block",
+            &format!("{}:{:?}", options.block, options.max_block_lines),
+        )
+        .param("max_chars", &options.max_chars)
+        .param("headings_only", &options.headings_only)
+        .param("fence_expand", &options.fence_expand)
+        .param(
+            "code",
+            &format!("{}:{:?}", options.code_only, options.code_language),
+        )
+        .param("fuzzy", &fuzzy_distance(options.fuzzy))
+        .param("ranking", &format!("{:?}", options.ranking));
+    for alias in sources {
+        key = key
+            .summary(alias, summaries.get(alias)?)
+            .ranking(alias, &storage.load_ranking(alias).ok()?);
+    }
+    Some(key)
+}
+
+/// Whether search results may be cached: `defaults.query_cache` /
+/// `BLZ_QUERY_CACHE` from config, enabled when unset.
+fn query_cache_enabled() -> bool {
+    Config::load()
+        .ok()
+        .and_then(|config| config.defaults.query_cache)
+        .unwrap_or(true)
+}
+
+/// Recompute staleness for hits replayed from the query cache.
+fn refresh_staleness(hits: &mut [SearchHit]) {
+    for hit in hits {
+        hit.is_stale = hit
+            .fetched_at
+            .is_some_and(|fetched_at| staleness::is_stale(fetched_at, DEFAULT_STALE_AFTER_DAYS));
+    }
+}
+
+/// Search a single in-memory [`EphemeralSource`] (see `blz search --url`).
+pub(super) fn perform_ephemeral_search(
+    source: &EphemeralSource,
+    options: &SearchOptions,
+) -> Result<SearchResults> {
+    let start_time = Instant::now();
+    let alias = source.alias();
+    let index = source.index();
+    let limit = effective_search_limit(options);
+
+    // Ephemeral sources are searched once, so their vectors stay in memory.
+    let mut hits = SourceQuery::new(options, limit, || Ok(Storage::new()?.models_dir()))?
+        .run(index, alias, |embedder| {
+            Ok(index.build_vectors(alias, embedder, &source.llms_json().metadata.sha256)?)
+        })
+        .with_context(|| format!("search failed for {}", source.url()))?;
 
+    rank_hits(&mut hits, options);
+    let summary = SourceSummary::new(source.metadata(), source.llms_json().line_index.total_lines);
+    for hit in &mut hits {
+        apply_source_metadata(hit, Some(&summary));
+    }
+
+    // Seed the caches so context expansion reads the in-memory copy.
+    let mut llms_cache = HashMap::from([(alias.to_string(), Some(source.llms_json().clone()))]);
+    let mut line_cache = HashMap::from([(alias.to_string(), source.lines().to_vec())]);
+    enrich_hits_with_content(&mut hits, options, None, &mut llms_cache, &mut line_cache);
+
+    Ok(SearchResults {
+        hits,
+        total_lines_searched: source.llms_json().line_index.total_lines,
+        search_time: start_time.elapsed(),
+        sources: vec![alias.to_string()],
+    })
+}
+
+/// Number of hits to request per source, over-fetching for scoring stability.
+fn effective_search_limit(options: &SearchOptions) -> usize {
+    if options.all {
+        ALL_RESULTS_LIMIT
+    } else {
+        (options.limit * 3).clamp(1, 1000)
+    }
+}
+
+/// Deduplicate, order, and apply the `--top` percentile cut to raw hits.
+fn rank_hits(hits: &mut Vec<SearchHit>, options: &SearchOptions) {
+    deduplicate_hits(hits);
+    sort_by_score(hits);
+    apply_percentile_filter(
+        hits,
+        options.top_percentile,
+        matches!(options.format, OutputFormat::Text),
+    );
+}
+
+/// Expand hits with surrounding lines or full heading blocks when requested.
+fn enrich_hits_with_content(
+    hits: &mut [SearchHit],
+    options: &SearchOptions,
+    storage: Option<&Storage>,
+    llms_cache: &mut HashMap<String, Option<LlmsJson>>,
+    line_cache: &mut HashMap<String, Vec<String>>,
+) {
     if options.block {
         enrich_hits_with_blocks(
-            &mut all_hits,
+            hits,
             options.max_block_lines,
-            &storage,
-            &mut llms_cache,
-            &mut line_cache,
+            storage,
+            llms_cache,
+            line_cache,
         );
     } else if options.before_context > 0 || options.after_context > 0 {
         enrich_hits_with_context(
-            &mut all_hits,
+            hits,
             options.before_context,
             options.after_context,
-            &storage,
-            &mut line_cache,
+            storage,
+            line_cache,
         );
     }
+    if expands_fences(options) {
+        expand_snippets_to_fences(hits, &options.query, storage, line_cache);
+    }
+}
 
-    let mut sources_searched = sources_searched;
-    sources_searched.sort();
-    Ok(SearchResults {
-        hits: all_hits,
-        total_lines_searched,
-        search_time: start_time.elapsed(),
-        sources: sources_searched,
-    })
+/// Whether snippets should be widened to whole code fences.
+///
+/// Only machine formats are affected: their snippets are rendered as markdown
+/// downstream, where a fence missing its opening or closing line swallows the
+/// rest of the document.
+pub(super) const fn expands_fences(options: &SearchOptions) -> bool {
+    options.fence_expand && matches!(options.format, OutputFormat::Json | OutputFormat::Jsonl)
+}
+
+/// Replace snippets that cut through a fenced code block with the complete
+/// fence, including its language tag and closing marker.
+///
+/// Snippets that already contain the whole fence are left untouched; widened
+/// snippets get their highlights recomputed against `query`.
+fn expand_snippets_to_fences(
+    hits: &mut [SearchHit],
+    query: &str,
+    storage: Option<&Storage>,
+    line_cache: &mut HashMap<String, Vec<String>>,
+) {
+    let mut fence_cache: HashMap<String, Vec<(usize, usize)>> = HashMap::new();
+    let highlight_terms = highlight::query_terms(query);
+    for hit in hits {
+        let Some((start, end)) = parse_line_span(&hit.lines) else {
+            continue;
+        };
+        let Some(lines) = ensure_lines(line_cache, storage, &hit.source) else {
+            continue;
+        };
+        let fences = fence_cache
+            .entry(hit.source.clone())
+            .or_insert_with(|| fence_spans(lines));
+        let Some((start, end)) = expand_to_fences(fences, start, end) else {
+            continue;
+        };
+        let Some(slice) = lines.get(start - 1..end.min(lines.len())) else {
+            continue;
+        };
+        let fence_text = slice.join("\n");
+        if !hit.snippet.contains(&fence_text) {
+            hit.highlights = highlight::match_spans(&fence_text, &highlight_terms);
+            hit.snippet = fence_text;
+            hit.lines = format!("{start}-{end}");
+            hit.line_numbers = Some(vec![start, end]);
+        }
+    }
+}
+
+/// Per-source search parameters shared by cached and ephemeral searches.
+#[derive(Clone)]
+struct SourceQuery {
+    query: String,
+    limit: usize,
+    snippet_limit: usize,
+    headings_only: bool,
+    code_only: bool,
+    code_language: Option<String>,
+    show_timing: bool,
+    ranking: Ranking,
+    /// Embedding model, loaded once per search for semantic and hybrid ranking.
+    embedder: Option<Arc<dyn Embedder>>,
+}
+
+impl SourceQuery {
+    /// `models_dir` locates the embedding model and is only called for
+    /// semantic and hybrid ranking.
+    fn new(
+        options: &SearchOptions,
+        limit: usize,
+        models_dir: impl FnOnce() -> Result<PathBuf>,
+    ) -> Result<Self> {
+        let embedder = match options.ranking {
+            Ranking::Lexical => None,
+            Ranking::Semantic | Ranking::Hybrid => Some(embeddings::load_embedder(&models_dir()?)?),
+        };
+        Ok(Self {
+            query: options.query.clone(),
+            limit,
+            snippet_limit: options.max_chars,
+            headings_only: options.headings_only,
+            code_only: options.code_only,
+            code_language: options.code_language.clone(),
+            show_timing: options.timing,
+            ranking: options.ranking,
+            embedder,
+        })
+    }
+
+    /// Search `index` for hits in `alias` using the requested ranking.
+    ///
+    /// `vectors` supplies the source's vector index and is only called for
+    /// semantic and hybrid ranking.
+    fn run(
+        &self,
+        index: &SearchIndex,
+        alias: &str,
+        vectors: impl FnOnce(&dyn Embedder) -> Result<VectorIndex>,
+    ) -> Result<Vec<SearchHit>> {
+        let lexical = || {
+            if self.code_only {
+                index.search_code_with_timing(
+                    &self.query,
+                    Some(alias),
+                    self.code_language.as_deref(),
+                    self.limit,
+                    self.snippet_limit,
+                    self.show_timing,
+                )
+            } else if self.headings_only {
+                index.search_headings_only_with_timing(
+                    &self.query,
+                    Some(alias),
+                    self.limit,
+                    self.snippet_limit,
+                    self.show_timing,
+                )
+            } else {
+                index.search_with_timing(
+                    &self.query,
+                    Some(alias),
+                    self.limit,
+                    self.snippet_limit,
+                    self.show_timing,
+                )
+            }
+        };
+
+        let Some(embedder) = self.embedder.as_deref() else {
+            return Ok(lexical()?);
+        };
+
+        let vectors = vectors(embedder)?;
+        let semantic = index.semantic_search(
+            &vectors,
+            embedder,
+            &self.query,
+            alias,
+            self.limit,
+            self.snippet_limit,
+        )?;
+        if self.ranking == Ranking::Semantic {
+            return Ok(semantic);
+        }
+
+        let mut hits = embeddings::blend(lexical()?, semantic, DEFAULT_HYBRID_WEIGHT);
+        hits.truncate(self.limit);
+        Ok(hits)
+    }
+}
+
+/// Load a source's saved vector index, rebuilding it when missing or stale.
+///
+/// Vectors are keyed to the document hash, so a refresh invalidates them and
+/// the next semantic search re-embeds the source.
+fn load_or_build_vectors(
+    storage: &Storage,
+    index: &SearchIndex,
+    alias: &str,
+    embedder: &dyn Embedder,
+) -> Result<VectorIndex> {
+    let fingerprint = storage
+        .load_source_summary(alias)?
+        .ok_or_else(|| anyhow::anyhow!("source '{alias}' is not cached"))?
+        .sha256;
+    let path = storage.vectors_path(alias)?;
+    if let Some(vectors) = VectorIndex::load(&path)? {
+        if vectors.is_current(embedder, &fingerprint) {
+            return Ok(vectors);
+        }
+    }
+
+    let vectors = index.build_vectors(alias, embedder, &fingerprint)?;
+    // The shared cache is read-only; its vectors are rebuilt per search
+    if storage.is_shared(alias) {
+        return Ok(vectors);
+    }
+    if let Err(err) = vectors.save(&path) {
+        warn!("failed to save vectors for {alias}: {err}");
+    }
+    Ok(vectors)
 }
 
 /// Execute parallel searches across multiple sources.
 ///
-/// Returns a tuple of (hits, total lines searched, sources searched).
+/// Returns a tuple of (hits, total lines searched, sources searched, whether
+/// every source was searched without error).
 async fn execute_parallel_searches(
     storage: &Arc<Storage>,
+    summaries: &Summaries,
     sources: Vec<String>,
     options: &SearchOptions,
     metrics: PerformanceMetrics,
-) -> Result<(Vec<SearchHit>, usize, Vec<String>)> {
+) -> Result<(Vec<SearchHit>, usize, Vec<String>, bool)> {
     // Calculate effective limit to prevent over-fetching
-    let effective_limit = if options.all {
-        ALL_RESULTS_LIMIT
-    } else {
-        (options.limit * 3).clamp(1, 1000)
-    };
+    let effective_limit = effective_search_limit(options);
 
     let max_concurrent_searches = get_max_concurrent_searches();
-    let snippet_limit = options.max_chars;
-    let headings_only = options.headings_only;
-    let show_timing = options.timing;
+    let fuzzy = fuzzy_distance(options.fuzzy);
     let storage_for_tasks = Arc::clone(storage);
-    let query = options.query.clone();
+    let source_query = SourceQuery::new(options, effective_limit, || Ok(storage.models_dir()))?;
 
     // Create futures that spawn blocking tasks for parallel search across sources
     let search_tasks = sources.into_iter().map(move |source| {
         let storage = Arc::clone(&storage_for_tasks);
         let metrics = metrics.clone();
-        let query = query.clone();
+        let source_query = source_query.clone();
+        let total_lines = summaries
+            .get(&source)
+            .map_or(0, |summary| summary.total_lines);
 
         async move {
             tokio::task::spawn_blocking(
@@ -563,7 +1027,8 @@ async fn execute_parallel_searches(
                         return Ok((Vec::new(), 0, source));
                     }
 
-                    let index = SearchIndex::open(&index_path)
+                    let index = storage
+                        .open_index(&source)
                         .with_context(|| {
                             format!(
                                 "open index for source={} at {}",
@@ -571,32 +1036,14 @@ async fn execute_parallel_searches(
                                 index_path.display()
                             )
                         })?
-                        .with_metrics(metrics);
-
-                    let hits = if headings_only {
-                        index.search_headings_only_with_timing(
-                            &query,
-                            Some(&source),
-                            effective_limit,
-                            snippet_limit,
-                            show_timing,
-                        )
-                    } else {
-                        index.search_with_timing(
-                            &query,
-                            Some(&source),
-                            effective_limit,
-                            snippet_limit,
-                            show_timing,
-                        )
-                    }
-                    .with_context(|| format!("search failed for source={source}"))?;
+                        .with_metrics(metrics)
+                        .with_fuzzy(fuzzy);
 
-                    // Count total lines for stats
-                    let total_lines = storage
-                        .load_llms_json(&source)
-                        .ok()
-                        .map_or(0, |json| json.line_index.total_lines);
+                    let hits = source_query
+                        .run(&index, &source, |embedder| {
+                            load_or_build_vectors(&storage, &index, &source, embedder)
+                        })
+                        .with_context(|| format!("search failed for source={source}"))?;
 
                     Ok((hits, total_lines, source))
                 },
@@ -612,6 +1059,7 @@ async fn execute_parallel_searches(
     let mut all_hits = Vec::new();
     let mut total_lines_searched = 0usize;
     let mut sources_searched = Vec::new();
+    let mut complete = true;
 
     // Collect results from the stream
     while let Some(res) = search_stream.next().await {
@@ -626,11 +1074,12 @@ async fn execute_parallel_searches(
             },
             Err(e) => {
                 tracing::warn!("Search failed: {}", e);
+                complete = false;
             },
         }
     }
 
-    Ok((all_hits, total_lines_searched, sources_searched))
+    Ok((all_hits, total_lines_searched, sources_searched, complete))
 }
 
 fn deduplicate_hits(hits: &mut Vec<SearchHit>) {
@@ -657,6 +1106,21 @@ fn sort_by_score(hits: &mut [SearchHit]) {
     });
 }
 
+/// Make results reproducible for snapshot tests (`--deterministic`).
+///
+/// Zeroes the elapsed time, drops per-hit fetch timestamps, and rounds scores
+/// to `precision` decimal places. Hits are then re-sorted so scores that now
+/// tie fall back to the alias/lines/heading order.
+pub(super) fn make_deterministic(results: &mut SearchResults, precision: u8) {
+    let factor = 10f32.powi(i32::from(precision));
+    results.search_time = std::time::Duration::ZERO;
+    for hit in &mut results.hits {
+        hit.score = (hit.score * factor).round() / factor;
+        hit.fetched_at = None;
+    }
+    sort_by_score(&mut results.hits);
+}
+
 fn apply_percentile_filter(
     hits: &mut Vec<SearchHit>,
     top_percentile: Option<u8>,
@@ -680,7 +1144,7 @@ fn enrich_hits_with_context(
     hits: &mut [SearchHit],
     before_lines: usize,
     after_lines: usize,
-    storage: &Arc<Storage>,
+    storage: Option<&Storage>,
     line_cache: &mut HashMap<String, Vec<String>>,
 ) {
     for hit in hits {
@@ -714,7 +1178,7 @@ fn enrich_hits_with_context(
 fn enrich_hits_with_blocks(
     hits: &mut [SearchHit],
     max_lines: Option<usize>,
-    storage: &Arc<Storage>,
+    storage: Option<&Storage>,
     llms_cache: &mut HashMap<String, Option<LlmsJson>>,
     line_cache: &mut HashMap<String, Vec<String>>,
 ) {
@@ -775,14 +1239,12 @@ fn enrich_hits_with_blocks(
 
 fn ensure_lines<'a>(
     cache: &'a mut HashMap<String, Vec<String>>,
-    storage: &Arc<Storage>,
+    storage: Option<&Storage>,
     source: &str,
 ) -> Option<&'a Vec<String>> {
     if !cache.contains_key(source) {
         let lines = storage
-            .llms_txt_path(source)
-            .ok()
-            .and_then(|path| std::fs::read_to_string(&path).ok())
+            .and_then(|storage| storage.load_llms_txt(source).ok())
             .map(|content| {
                 content
                     .lines()
@@ -797,11 +1259,11 @@ fn ensure_lines<'a>(
 
 fn ensure_llms<'a>(
     cache: &'a mut HashMap<String, Option<LlmsJson>>,
-    storage: &Arc<Storage>,
+    storage: Option<&Storage>,
     source: &str,
 ) -> Option<&'a LlmsJson> {
     if !cache.contains_key(source) {
-        let value = storage.load_llms_json(source).ok();
+        let value = storage.and_then(|storage| storage.load_llms_json(source).ok());
         cache.insert(source.to_string(), value);
     }
     cache.get(source).and_then(|entry| entry.as_ref())
@@ -834,10 +1296,7 @@ fn ensure_llms<'a>(
 ///     page: 1,
 ///     top_percentile: None,
 ///     format: OutputFormat::Text,
-///     show_url: false,
-///     show_lines: false,
-///     show_anchor: false,
-///     show_raw_score: false,
+///     show: ShowSet::default(),
 ///     no_summary: false,
 ///     score_precision: None,
 ///     snippet_lines: 3,
@@ -917,7 +1376,9 @@ pub(super) fn format_and_display(
 
     // Normal case: format the current page
     let page_hits = &results.hits[start_idx..end_idx];
-    let need_suggest = results.hits.first().map_or(0.0, |h| h.score) < 2.0;
+    // Weak-match suggestions key off BM25 scores; embedding scores use another scale.
+    let need_suggest =
+        options.ranking == Ranking::Lexical && results.hits.first().map_or(0.0, |h| h.score) < 2.0;
     let suggestions = suggestion_resolver.resolve(need_suggest);
     let page_ctx = PageContext {
         hits: page_hits,
@@ -1007,10 +1468,10 @@ fn format_page(
         page: page_ctx.page,
         total_pages: page_ctx.total_pages,
         page_size: page_ctx.page_size,
-        show_url: options.show_url,
-        show_lines: options.show_lines,
-        show_anchor: options.show_anchor,
-        show_raw_score: options.show_raw_score,
+        show_url: options.show.contains(ShowComponent::Url),
+        show_lines: options.show.contains(ShowComponent::Lines),
+        show_anchor: options.show.contains(ShowComponent::Anchor),
+        show_raw_score: options.show.contains(ShowComponent::RawScore),
         no_summary: options.no_summary,
         score_precision: options.score_precision.unwrap_or(DEFAULT_SCORE_PRECISION),
         snippet_lines: usize::from(options.snippet_lines.max(1)),
@@ -1158,7 +1619,10 @@ use crate::config::{
 };
 use crate::utils::heading_filter::HeadingLevelFilter;
 
-const DEFAULT_SNIPPET_LINES: u8 = 3;
+use super::ephemeral::EphemeralSource;
+use super::{AddRequest, DescriptorInput, add_source};
+
+pub(super) const DEFAULT_SNIPPET_LINES: u8 = 3;
 
 /// Parse heading level filter from string.
 fn parse_heading_filter(filter_str: Option<&str>) -> Result<Option<HeadingLevelFilter>> {
@@ -1289,19 +1753,28 @@ pub async fn dispatch(
         .with_top_percentile(args.top)
         .with_heading_filter(heading_filter)
         .with_headings_only(use_headings_only)
+        .with_code(args.code, args.lang)
         .with_last(args.last)
-        .with_no_history(args.no_history);
+        .with_no_history(args.no_history)
+        .with_remote(args.remote)
+        .with_auto_sources(args.auto_sources)
+        .with_all_sources(args.all_sources)
+        .with_fuzzy(args.fuzzy)
+        .with_ranking(args.semantic, args.hybrid);
 
     let display_config = DisplayConfig::new(resolved_format)
         .with_show(args.show)
         .with_no_summary(args.no_summary)
+        .with_deterministic(args.deterministic)
         .with_timing(args.timing)
         .with_quiet(quiet);
 
     let snippet_config = SnippetConfig::new()
         .with_lines(args.snippet_lines)
         .with_max_chars(actual_max_chars)
-        .with_score_precision(args.score_precision);
+        .with_score_precision(args.score_precision)
+        .with_fence_expand(!args.no_fence_expand)
+        .with_max_tokens(args.max_tokens);
 
     let content_config = ContentConfig::new()
         .with_context(merged_context)
@@ -1316,6 +1789,10 @@ pub async fn dispatch(
         content_config,
     );
 
+    if let Some(url) = args.url {
+        return execute_url_search(&url, args.keep, &actual_query, &config, quiet, metrics).await;
+    }
+
     execute(
         &actual_query,
         &actual_sources,
@@ -1328,6 +1805,38 @@ pub async fn dispatch(
     .await
 }
 
+/// Fetch `url` into an ephemeral source, search it, and optionally keep it.
+async fn execute_url_search(
+    url: &str,
+    keep: bool,
+    query: &str,
+    config: &QueryExecutionConfig,
+    quiet: bool,
+    metrics: PerformanceMetrics,
+) -> Result<()> {
+    let source = EphemeralSource::fetch(url, metrics.clone())
+        .await
+        .with_context(|| format!("failed to fetch {url}"))?
+        .with_fuzzy(fuzzy_distance(config.search.fuzzy));
+
+    super::query::execute_ephemeral(query, &source, config)?;
+
+    if keep {
+        let request = AddRequest::new(
+            source.alias(),
+            source.url(),
+            DescriptorInput::default(),
+            false,
+            quiet,
+            metrics,
+            false,
+        );
+        add_source(request).await?;
+    }
+
+    Ok(())
+}
+
 /// Pagination adjustments computed from history and continuation flags.
 struct PaginationAdjustment {
     page: usize,
@@ -1528,12 +2037,15 @@ mod tests {
                 lines: format!("{start}-{end}", start = i * 10, end = i * 10 + 5),
                 line_numbers: Some(vec![i * 10, i * 10 + 5]),
                 snippet: format!("test content {i}"),
+                snippet_language: None,
+                highlights: Vec::new(),
                 score: (i as f32).mul_add(-0.01, 1.0),
                 source_url: Some(format!("https://example.com/test-{i}")),
                 fetched_at: Some(Utc::now()),
                 is_stale: false,
                 checksum: format!("checksum-{i}"),
                 anchor: Some("unit-test-anchor".to_string()),
+                section_id: None,
                 context: None,
             })
             .collect();
@@ -1546,6 +2058,27 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_make_deterministic_rounds_and_breaks_ties() {
+        let mut results = create_test_results(3);
+        results.hits[0].score = 1.04;
+        results.hits[0].source = "b".to_string();
+        results.hits[1].score = 0.96;
+        results.hits[1].source = "a".to_string();
+        results.hits[2].score = 0.5;
+
+        make_deterministic(&mut results, 1);
+
+        assert_eq!(results.search_time, std::time::Duration::ZERO);
+        let order: Vec<(&str, f32)> = results
+            .hits
+            .iter()
+            .map(|hit| (hit.source.as_str(), hit.score))
+            .collect();
+        assert_eq!(order, vec![("a", 1.0), ("b", 1.0), ("test-2", 0.5)]);
+        assert!(results.hits.iter().all(|hit| hit.fetched_at.is_none()));
+    }
+
     #[test]
     fn test_pagination_with_zero_hits() {
         // Test that pagination handles empty results without panic
@@ -1558,11 +2091,9 @@ mod tests {
             page: 1,
             top_percentile: None,
             format: OutputFormat::Text,
-            show_url: false,
-            show_lines: false,
-            show_anchor: false,
-            show_raw_score: false,
+            show: ShowSet::default(),
             no_summary: false,
+            deterministic: false,
             score_precision: None,
             snippet_lines: 3,
             all: false,
@@ -1573,9 +2104,17 @@ mod tests {
             block: false,
             max_block_lines: None,
             max_chars: DEFAULT_MAX_CHARS,
+            auto_sources: None,
+            all_sources: false,
+            fuzzy: None,
+            ranking: Ranking::Lexical,
+            fence_expand: true,
             quiet: false,
             headings_only: false,
+            code_only: false,
+            code_language: None,
             timing: false,
+            max_tokens: None,
         };
 
         // Should not panic even with empty results
@@ -1602,11 +2141,9 @@ mod tests {
             page: 1,
             top_percentile: None,
             format: OutputFormat::Text,
-            show_url: false,
-            show_lines: false,
-            show_anchor: false,
-            show_raw_score: false,
+            show: ShowSet::default(),
             no_summary: false,
+            deterministic: false,
             score_precision: None,
             snippet_lines: 3,
             all: false,
@@ -1617,9 +2154,17 @@ mod tests {
             block: false,
             max_block_lines: None,
             max_chars: DEFAULT_MAX_CHARS,
+            auto_sources: None,
+            all_sources: false,
+            fuzzy: None,
+            ranking: Ranking::Lexical,
+            fence_expand: true,
             quiet: false,
             headings_only: false,
+            code_only: false,
+            code_language: None,
             timing: false,
+            max_tokens: None,
         };
 
         let result = format_and_display(&results, &options);
@@ -1639,11 +2184,9 @@ mod tests {
             page: 2, // Try to access page 2 to trigger div_ceil
             top_percentile: None,
             format: OutputFormat::Text,
-            show_url: false,
-            show_lines: false,
-            show_anchor: false,
-            show_raw_score: false,
+            show: ShowSet::default(),
             no_summary: false,
+            deterministic: false,
             score_precision: None,
             snippet_lines: 3,
             all: true,
@@ -1654,9 +2197,17 @@ mod tests {
             block: false,
             max_block_lines: None,
             max_chars: DEFAULT_MAX_CHARS,
+            auto_sources: None,
+            all_sources: false,
+            fuzzy: None,
+            ranking: Ranking::Lexical,
+            fence_expand: true,
             quiet: false,
             headings_only: false,
+            code_only: false,
+            code_language: None,
             timing: false,
+            max_tokens: None,
         };
 
         // This should NOT panic even with empty results
@@ -1673,11 +2224,9 @@ mod tests {
             page: 100, // Very high page to trigger the div_ceil in the message
             top_percentile: None,
             format: OutputFormat::Text,
-            show_url: false,
-            show_lines: false,
-            show_anchor: false,
-            show_raw_score: false,
+            show: ShowSet::default(),
             no_summary: false,
+            deterministic: false,
             score_precision: None,
             snippet_lines: 3,
             all: true,
@@ -1688,9 +2237,17 @@ mod tests {
             block: false,
             max_block_lines: None,
             max_chars: DEFAULT_MAX_CHARS,
+            auto_sources: None,
+            all_sources: false,
+            fuzzy: None,
+            ranking: Ranking::Lexical,
+            fence_expand: true,
             quiet: false,
             headings_only: false,
+            code_only: false,
+            code_language: None,
             timing: false,
+            max_tokens: None,
         };
 
         let result = format_and_display(&results, &options_high_page);
@@ -1712,11 +2269,9 @@ mod tests {
             page: 100, // Way beyond available pages
             top_percentile: None,
             format: OutputFormat::Text,
-            show_url: false,
-            show_lines: false,
-            show_anchor: false,
-            show_raw_score: false,
+            show: ShowSet::default(),
             no_summary: false,
+            deterministic: false,
             score_precision: None,
             snippet_lines: 3,
             all: false,
@@ -1727,9 +2282,17 @@ mod tests {
             block: false,
             max_block_lines: None,
             max_chars: DEFAULT_MAX_CHARS,
+            auto_sources: None,
+            all_sources: false,
+            fuzzy: None,
+            ranking: Ranking::Lexical,
+            fence_expand: true,
             quiet: false,
             headings_only: false,
+            code_only: false,
+            code_language: None,
             timing: false,
+            max_tokens: None,
         };
 
         let result = format_and_display(&results, &options);
@@ -1750,11 +2313,9 @@ mod tests {
             page: 2,
             top_percentile: None,
             format: OutputFormat::Text,
-            show_url: false,
-            show_lines: false,
-            show_anchor: false,
-            show_raw_score: false,
+            show: ShowSet::default(),
             no_summary: false,
+            deterministic: false,
             score_precision: None,
             snippet_lines: 3,
             all: false,
@@ -1765,9 +2326,17 @@ mod tests {
             block: false,
             max_block_lines: None,
             max_chars: DEFAULT_MAX_CHARS,
+            auto_sources: None,
+            all_sources: false,
+            fuzzy: None,
+            ranking: Ranking::Lexical,
+            fence_expand: true,
             quiet: false,
             headings_only: false,
+            code_only: false,
+            code_language: None,
             timing: false,
+            max_tokens: None,
         };
 
         let result = format_and_display(&results, &options);
@@ -1782,11 +2351,9 @@ mod tests {
             page: 3,
             top_percentile: None,
             format: OutputFormat::Text,
-            show_url: false,
-            show_lines: false,
-            show_anchor: false,
-            show_raw_score: false,
+            show: ShowSet::default(),
             no_summary: false,
+            deterministic: false,
             score_precision: None,
             snippet_lines: 3,
             all: false,
@@ -1797,9 +2364,17 @@ mod tests {
             block: false,
             max_block_lines: None,
             max_chars: DEFAULT_MAX_CHARS,
+            auto_sources: None,
+            all_sources: false,
+            fuzzy: None,
+            ranking: Ranking::Lexical,
+            fence_expand: true,
             quiet: false,
             headings_only: false,
+            code_only: false,
+            code_language: None,
             timing: false,
+            max_tokens: None,
         };
 
         let test_results = create_test_results(10);
@@ -1821,11 +2396,9 @@ mod tests {
             page: 1,
             top_percentile: None,
             format: OutputFormat::Text,
-            show_url: false,
-            show_lines: false,
-            show_anchor: false,
-            show_raw_score: false,
+            show: ShowSet::default(),
             no_summary: false,
+            deterministic: false,
             score_precision: None,
             snippet_lines: 3,
             all: false,
@@ -1836,9 +2409,17 @@ mod tests {
             block: false,
             max_block_lines: None,
             max_chars: DEFAULT_MAX_CHARS,
+            auto_sources: None,
+            all_sources: false,
+            fuzzy: None,
+            ranking: Ranking::Lexical,
+            fence_expand: true,
             quiet: false,
             headings_only: false,
+            code_only: false,
+            code_language: None,
             timing: false,
+            max_tokens: None,
         };
 
         let results1 = create_test_results(8);
@@ -1858,11 +2439,9 @@ mod tests {
             page: 1,
             top_percentile: None,
             format: OutputFormat::Text,
-            show_url: false,
-            show_lines: false,
-            show_anchor: false,
-            show_raw_score: false,
+            show: ShowSet::default(),
             no_summary: false,
+            deterministic: false,
             score_precision: None,
             snippet_lines: 3,
             all: true,
@@ -1873,9 +2452,17 @@ mod tests {
             block: false,
             max_block_lines: None,
             max_chars: DEFAULT_MAX_CHARS,
+            auto_sources: None,
+            all_sources: false,
+            fuzzy: None,
+            ranking: Ranking::Lexical,
+            fence_expand: true,
             quiet: false,
             headings_only: false,
+            code_only: false,
+            code_language: None,
             timing: false,
+            max_tokens: None,
         };
 
         let results2 = create_test_results(0);
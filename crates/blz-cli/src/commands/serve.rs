@@ -0,0 +1,534 @@
+//! Local HTTP API: `blz serve`.
+//!
+//! Exposes search, retrieval, source listing, and tables of contents over a
+//! small read-only REST API so editor plugins and other non-MCP tools can keep
+//! one process warm instead of shelling out per query. Every route is a `GET`
+//! returning JSON:
+//!
+//! | Route         | Parameters                                         | Body                       |
+//! |---------------|----------------------------------------------------|----------------------------|
+//! | `/v1/health`  |                                                    | `{status, version}`        |
+//! | `/v1/search`  | `q`, `source`*, `limit`, `page`, `block`, ...      | hits (see below)           |
+//! | `/v1/get`     | `target`* (`alias:lines`), `before`, `after`, ...  | retrieved lines            |
+//! | `/v1/list`    | `status`, `details`                                | `blz list --json`          |
+//! | `/v1/toc`     | `source`* or `all`, `maxDepth`, `filter`           | `blz map --json`           |
+//!
+//! `/v1/search` and `/v1/get` answer in the compact wire format that
+//! `--remote` clients render locally; add `format=json` to get exactly what
+//! `blz query --json` / `blz get --json` print instead. Failures return
+//! `{"error": "..."}` with status 400 for bad parameters, 404 for an unknown
+//! source or line span, and 500 for internal errors.
+//!
+//! Search, get, and toc responses carry an `ETag` derived from the request and
+//! the generation of every cached source. A repeat request sending it back in
+//! `If-None-Match` gets `304 Not Modified` without running the search.
+//!
+//! On SIGINT/SIGTERM the server stops accepting connections, lets requests in
+//! flight finish, closes idle keep-alive connections, and exits with status
+//! 130/143.
+
+use std::convert::Infallible;
+use std::net::IpAddr;
+use std::str::FromStr;
+use std::time::Instant;
+
+use anyhow::{Context, Result, anyhow};
+use blz_core::embeddings::Ranking;
+use blz_core::http_cache::{self, EntityTag};
+use blz_core::{PerformanceMetrics, Storage};
+use clap::Args;
+use http_body_util::Full;
+use hyper::body::{Bytes, Incoming};
+use hyper::header::{CACHE_CONTROL, CONTENT_TYPE, ETAG, HeaderValue, IF_NONE_MATCH};
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::{Method, Request, Response, StatusCode};
+use hyper_util::rt::TokioIo;
+use tokio::net::TcpListener;
+use tokio::task::{JoinSet, LocalSet};
+use tracing::{debug, warn};
+
+use super::get::{
+    GetProcessParams, build_json_response, parse_single_target, process_all_requests,
+};
+use super::list::{ListOptions, execute_with_options as render_list};
+use super::query::render_results_page;
+use super::remote::{GET_ROUTE, RemoteSearchResponse, SEARCH_ROUTE};
+use super::search::{
+    ALL_RESULTS_LIMIT, DEFAULT_MAX_CHARS, DEFAULT_SNIPPET_LINES, SearchOptions, clamp_max_chars,
+    default_search_limit, perform_search,
+};
+use super::toc::toc_listing;
+use crate::args::ShowSet;
+use crate::error::{CliError, ErrorCategory};
+use crate::output::OutputFormat;
+use crate::utils::resolver::resolve_source;
+use crate::utils::shutdown;
+
+/// Arguments for `blz serve`.
+#[derive(Args, Clone, Debug)]
+pub struct ServeArgs {
+    /// Address to listen on
+    #[arg(long, default_value = "127.0.0.1")]
+    pub host: IpAddr,
+
+    /// Port to listen on (0 picks a free port)
+    #[arg(long, default_value_t = 7777)]
+    pub port: u16,
+}
+
+/// Execute `blz serve`.
+///
+/// Runs until interrupted.
+///
+/// # Errors
+///
+/// Returns an error if the address cannot be bound or accepting connections
+/// fails.
+#[allow(clippy::future_not_send)]
+pub async fn execute(args: &ServeArgs, quiet: bool, metrics: PerformanceMetrics) -> Result<()> {
+    shutdown::install();
+    let listener = TcpListener::bind((args.host, args.port))
+        .await
+        .with_context(|| format!("Failed to listen on {}:{}", args.host, args.port))?;
+    let addr = listener.local_addr()?;
+    if !args.host.is_loopback() {
+        warn!("Listening on {addr}, which other machines can reach; the API has no authentication");
+    }
+    if !quiet {
+        println!("blz serve listening on http://{addr}");
+    }
+
+    // Handlers run on one thread so the search path need not be `Send`
+    LocalSet::new()
+        .run_until(accept_loop(listener, metrics))
+        .await
+}
+
+async fn accept_loop(listener: TcpListener, metrics: PerformanceMetrics) -> Result<()> {
+    let mut connections = JoinSet::new();
+    loop {
+        // `tokio::select!` expands to a `panic!` for its all-branches-disabled arm
+        #[allow(clippy::disallowed_macros)]
+        let (stream, peer) = tokio::select! {
+            accepted = listener.accept() => accepted.context("Failed to accept connection")?,
+            () = shutdown::cancelled() => break,
+        };
+        let metrics = metrics.clone();
+        connections.spawn_local(async move {
+            let service = service_fn(move |request| handle(request, metrics.clone()));
+            let connection = http1::Builder::new().serve_connection(TokioIo::new(stream), service);
+            tokio::pin!(connection);
+            #[allow(clippy::disallowed_macros)]
+            let result = tokio::select! {
+                result = connection.as_mut() => result,
+                () = shutdown::cancelled() => {
+                    // Finish the request in flight, then close instead of keeping alive
+                    connection.as_mut().graceful_shutdown();
+                    connection.await
+                },
+            };
+            if let Err(e) = result {
+                debug!("Connection from {peer} ended with error: {e}");
+            }
+        });
+        // Reap finished connections so the set does not grow without bound
+        while connections.try_join_next().is_some() {}
+    }
+    while connections.join_next().await.is_some() {}
+    Ok(())
+}
+
+async fn handle(
+    request: Request<Incoming>,
+    metrics: PerformanceMetrics,
+) -> Result<Response<Full<Bytes>>, Infallible> {
+    if request.method() != Method::GET {
+        return Ok(error_response(
+            StatusCode::METHOD_NOT_ALLOWED,
+            "Only GET requests are supported",
+        ));
+    }
+    let params = Params::parse(request.uri().query());
+    let route = request.uri().path().trim_matches('/');
+    let etag = [SEARCH_ROUTE, GET_ROUTE, "v1/toc"]
+        .contains(&route)
+        .then(|| entity_tag(route, &params))
+        .flatten();
+    if let Some(etag) = &etag {
+        let unchanged = request
+            .headers()
+            .get(IF_NONE_MATCH)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|header| http_cache::if_none_match(header, etag));
+        if unchanged {
+            let mut response = Response::new(Full::new(Bytes::new()));
+            *response.status_mut() = StatusCode::NOT_MODIFIED;
+            set_validators(&mut response, etag);
+            return Ok(response);
+        }
+    }
+    let result = match route {
+        "v1/health" => Ok(health()),
+        SEARCH_ROUTE => search(&params, metrics).await,
+        GET_ROUTE => get(&params),
+        "v1/list" => list(&params),
+        "v1/toc" => toc(&params),
+        _ => {
+            return Ok(error_response(
+                StatusCode::NOT_FOUND,
+                &format!("Unknown route '/{route}'"),
+            ));
+        },
+    };
+    Ok(match result {
+        Ok(body) => json_response(StatusCode::OK, body, etag.as_deref()),
+        Err(e) => {
+            debug!("{route} failed: {e:#}");
+            error_response(error_status(&e), &format!("{e:#}"))
+        },
+    })
+}
+
+/// Status for a failed request: 400 for bad parameters, 404 for an unknown
+/// source or span, and 500 for anything else.
+fn error_status(error: &anyhow::Error) -> StatusCode {
+    let category = error
+        .chain()
+        .find_map(|cause| cause.downcast_ref::<CliError>())
+        .map_or(ErrorCategory::Internal, |cause| cause.category);
+    match category {
+        ErrorCategory::Usage | ErrorCategory::InvalidQuery => StatusCode::BAD_REQUEST,
+        ErrorCategory::NotFound => StatusCode::NOT_FOUND,
+        ErrorCategory::Internal
+        | ErrorCategory::Network
+        | ErrorCategory::Timeout
+        | ErrorCategory::Integrity => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+/// Fails with a not-found error unless every alias names a cached source.
+fn require_sources(storage: &Storage, aliases: &[String]) -> Result<()> {
+    for alias in aliases {
+        if resolve_source(storage, alias)?.is_none() && !storage.exists(alias) {
+            return Err(CliError::not_found(anyhow!("Source '{alias}' not found")).into());
+        }
+    }
+    Ok(())
+}
+
+fn json_response(status: StatusCode, body: Vec<u8>, etag: Option<&str>) -> Response<Full<Bytes>> {
+    let mut response = Response::new(Full::new(Bytes::from(body)));
+    *response.status_mut() = status;
+    response
+        .headers_mut()
+        .insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    if let Some(etag) = etag {
+        set_validators(&mut response, etag);
+    }
+    response
+}
+
+/// Tag for a response built from `params` and the cached sources.
+///
+/// Any source being added, refreshed, or removed, or its ranking settings
+/// being edited, changes the tag. Returns `None` when the cache cannot be
+/// read, leaving the response untagged.
+fn entity_tag(route: &str, params: &Params) -> Option<String> {
+    let storage = Storage::new().ok()?;
+    let mut tag = params
+        .0
+        .iter()
+        .fold(EntityTag::new(route), |tag, (key, value)| {
+            tag.param(key, value)
+        });
+    for alias in storage.list_sources() {
+        let summary = storage.load_source_summary(&alias).ok().flatten()?;
+        tag = tag
+            .generation(&alias, &summary.sha256, summary.fetched_at)
+            .ranking(&alias, &storage.load_ranking(&alias).ok()?);
+    }
+    Some(tag.finish())
+}
+
+fn set_validators(response: &mut Response<Full<Bytes>>, etag: &str) {
+    let headers = response.headers_mut();
+    if let Ok(value) = HeaderValue::from_str(etag) {
+        headers.insert(ETAG, value);
+    }
+    headers.insert(
+        CACHE_CONTROL,
+        HeaderValue::from_static(http_cache::CACHE_CONTROL),
+    );
+}
+
+fn error_response(status: StatusCode, message: &str) -> Response<Full<Bytes>> {
+    let body = serde_json::json!({ "error": message }).to_string();
+    json_response(status, body.into_bytes(), None)
+}
+
+fn health() -> Vec<u8> {
+    serde_json::json!({ "status": "ok", "version": env!("CARGO_PKG_VERSION") })
+        .to_string()
+        .into_bytes()
+}
+
+async fn search(params: &Params, metrics: PerformanceMetrics) -> Result<Vec<u8>> {
+    let options = search_options(params)?;
+    require_sources(&Storage::new()?, &options.sources)?;
+    let results = perform_search(&options, metrics).await?;
+    if params.cli_shape()? {
+        let mut body = Vec::new();
+        render_results_page(results, &options, &mut body)?;
+        return Ok(body);
+    }
+    Ok(serde_json::to_vec(&RemoteSearchResponse {
+        hits: results.hits,
+        total_lines_searched: results.total_lines_searched,
+        sources: results.sources,
+    })?)
+}
+
+/// Search options for a request; parameter names follow what `--remote`
+/// clients send.
+fn search_options(params: &Params) -> Result<SearchOptions> {
+    let limit = params
+        .number("limit")?
+        .unwrap_or_else(default_search_limit)
+        .max(1);
+    let ranking = if params.flag("hybrid") {
+        Ranking::Hybrid
+    } else if params.flag("semantic") {
+        Ranking::Semantic
+    } else {
+        Ranking::Lexical
+    };
+    Ok(SearchOptions {
+        query: params.required("q")?.to_string(),
+        sources: params.all("source"),
+        last: false,
+        limit,
+        page: params.number("page")?.unwrap_or(1).max(1),
+        top_percentile: params.number("top")?,
+        format: OutputFormat::Json,
+        show: ShowSet::default(),
+        no_summary: true,
+        deterministic: false,
+        score_precision: None,
+        snippet_lines: DEFAULT_SNIPPET_LINES,
+        all: params.flag("all") || limit >= ALL_RESULTS_LIMIT,
+        no_history: true,
+        copy: false,
+        before_context: params.number("before")?.unwrap_or(0),
+        after_context: params.number("after")?.unwrap_or(0),
+        block: params.flag("block"),
+        max_block_lines: params.number("maxLines")?,
+        max_chars: params
+            .number("maxChars")?
+            .map_or(DEFAULT_MAX_CHARS, clamp_max_chars),
+        auto_sources: params.number("autoSources")?,
+        all_sources: false,
+        fuzzy: params.number("fuzzy")?,
+        ranking,
+        fence_expand: params.flag("fenceExpand"),
+        quiet: true,
+        headings_only: params.flag("headingsOnly"),
+        code_only: params.flag("code") || params.get("lang").is_some(),
+        code_language: params.get("lang").map(str::to_string),
+        timing: false,
+        max_tokens: params.number("maxTokens")?,
+    })
+}
+
+fn get(params: &Params) -> Result<Vec<u8>> {
+    let start = Instant::now();
+    let targets = params.all("target");
+    if targets.is_empty() {
+        return Err(usage("Missing required parameter 'target'"));
+    }
+    let specs = targets
+        .iter()
+        .enumerate()
+        .map(|(idx, target)| parse_single_target(target, idx, None).map_err(CliError::usage))
+        .collect::<Result<Vec<_>, _>>()?;
+    let process = GetProcessParams {
+        before_context: params.number("before")?.unwrap_or(0),
+        after_context: params.number("after")?.unwrap_or(0),
+        block_mode: params.flag("block"),
+        max_block_lines: params.number("maxLines")?,
+        at: params.get("at").map(str::to_string),
+    };
+    let processed = process_all_requests(&Storage::new()?, &specs, &process)?;
+    if params.cli_shape()? {
+        let elapsed_ms = u64::try_from(start.elapsed().as_millis()).unwrap_or(u64::MAX);
+        let response = build_json_response(
+            &processed,
+            specs.len(),
+            process.block_mode,
+            process.before_context,
+            process.after_context,
+            elapsed_ms,
+        );
+        return Ok(serde_json::to_vec(&response)?);
+    }
+    Ok(serde_json::to_vec(&processed)?)
+}
+
+fn list(params: &Params) -> Result<Vec<u8>> {
+    let options = ListOptions {
+        status: params.flag("status"),
+        details: params.flag("details"),
+        ..ListOptions::default()
+    };
+    let mut body = Vec::new();
+    render_list(&Storage::new()?, &mut body, OutputFormat::Json, &options)?;
+    Ok(body)
+}
+
+fn toc(params: &Params) -> Result<Vec<u8>> {
+    let storage = Storage::new()?;
+    let sources = if params.flag("all") {
+        storage.list_sources()
+    } else {
+        params.all("source")
+    };
+    if sources.is_empty() {
+        return Err(usage(
+            "Missing required parameter 'source' (or pass all=true)",
+        ));
+    }
+    require_sources(&storage, &sources)?;
+    let listing = toc_listing(
+        &storage,
+        &sources,
+        params.number("maxDepth")?,
+        params.get("filter"),
+    )?;
+    Ok(serde_json::to_vec(&listing)?)
+}
+
+/// Error for a malformed request, answered with `400 Bad Request`.
+fn usage(message: &str) -> anyhow::Error {
+    CliError::usage(anyhow!("{message}")).into()
+}
+
+/// Decoded query string parameters.
+#[derive(Debug, Default)]
+struct Params(Vec<(String, String)>);
+
+impl Params {
+    fn parse(query: Option<&str>) -> Self {
+        Self(
+            url::form_urlencoded::parse(query.unwrap_or_default().as_bytes())
+                .into_owned()
+                .collect(),
+        )
+    }
+
+    /// Last value given for `name`.
+    fn get(&self, name: &str) -> Option<&str> {
+        self.0
+            .iter()
+            .rev()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Every value given for `name`, in order.
+    fn all(&self, name: &str) -> Vec<String> {
+        self.0
+            .iter()
+            .filter(|(key, _)| key == name)
+            .map(|(_, value)| value.clone())
+            .collect()
+    }
+
+    fn required(&self, name: &str) -> Result<&str> {
+        self.get(name)
+            .filter(|value| !value.trim().is_empty())
+            .ok_or_else(|| usage(&format!("Missing required parameter '{name}'")))
+    }
+
+    fn flag(&self, name: &str) -> bool {
+        matches!(self.get(name), Some("true" | "1"))
+    }
+
+    fn number<T: FromStr>(&self, name: &str) -> Result<Option<T>> {
+        self.get(name)
+            .map(|value| {
+                value
+                    .parse()
+                    .map_err(|_| usage(&format!("Invalid value for '{name}': {value}")))
+            })
+            .transpose()
+    }
+
+    /// Whether the caller asked for CLI-shaped output (`format=json`) instead
+    /// of the `--remote` wire format.
+    fn cli_shape(&self) -> Result<bool> {
+        match self.get("format") {
+            None | Some("wire") => Ok(false),
+            Some("json") => Ok(true),
+            Some(other) => Err(usage(&format!(
+                "Unsupported format '{other}' (expected json or wire)"
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn params_decode_repeated_values_flags_and_numbers() {
+        let params = Params::parse(Some(
+            "q=use%20effect&source=react&source=bun&block=true&limit=7&fuzzy=x",
+        ));
+        assert_eq!(params.required("q").unwrap(), "use effect");
+        assert_eq!(params.all("source"), ["react", "bun"]);
+        assert!(params.flag("block"));
+        assert!(!params.flag("all"));
+        assert_eq!(params.number::<usize>("limit").unwrap(), Some(7));
+        assert!(params.number::<u8>("fuzzy").is_err());
+        assert!(params.required("target").is_err());
+        assert!(!params.cli_shape().unwrap());
+        assert!(Params::parse(Some("format=json")).cli_shape().unwrap());
+        assert!(Params::parse(Some("format=xml")).cli_shape().is_err());
+    }
+
+    #[test]
+    fn search_options_follow_remote_client_parameters() {
+        let params = Params::parse(Some(
+            "q=hooks&source=react&limit=5&maxChars=100000&before=2&headingsOnly=true&hybrid=true&lang=ts",
+        ));
+        let options = search_options(&params).unwrap();
+        assert_eq!(options.query, "hooks");
+        assert_eq!(options.sources, ["react"]);
+        assert_eq!(options.limit, 5);
+        assert!(!options.all);
+        assert_eq!(options.max_chars, clamp_max_chars(100_000));
+        assert_eq!(options.before_context, 2);
+        assert!(options.headings_only);
+        assert!(options.code_only);
+        assert_eq!(options.code_language.as_deref(), Some("ts"));
+        assert_eq!(options.ranking, Ranking::Hybrid);
+        assert!(search_options(&Params::parse(Some("limit=5"))).is_err());
+    }
+
+    #[test]
+    fn errors_map_to_status_by_category() {
+        let missing = Params::parse(None).required("q").unwrap_err();
+        assert_eq!(error_status(&missing), StatusCode::BAD_REQUEST);
+        let unknown: anyhow::Error = CliError::not_found(anyhow!("Source 'x' not found")).into();
+        assert_eq!(
+            error_status(&unknown.context("search failed")),
+            StatusCode::NOT_FOUND
+        );
+        assert_eq!(
+            error_status(&anyhow!("index is locked")),
+            StatusCode::INTERNAL_SERVER_ERROR
+        );
+    }
+}
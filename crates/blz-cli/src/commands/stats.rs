@@ -1,12 +1,18 @@
 //! Cache statistics command implementation
 
+use std::cmp::Reverse;
+
 use anyhow::Result;
-use blz_core::Storage;
 use blz_core::numeric::u64_to_f64_lossy;
-use chrono::{DateTime, Utc};
+use blz_core::quota::select_evictions;
+use blz_core::{SourceDiskUsage, Storage};
+use chrono::{DateTime, Duration, Utc};
 use serde::Serialize;
 
+use super::remove::RemoveStorage;
 use crate::output::OutputFormat;
+use crate::utils::confirm::{self, Risk};
+use crate::utils::{history_log, quota};
 
 /// Statistics for a single source
 #[derive(Debug, Serialize)]
@@ -16,6 +22,10 @@ struct SourceStats {
     lines: usize,
     last_updated: String,
     age_hours: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_searched: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_fetched: Option<String>,
 }
 
 /// Overall cache statistics
@@ -57,8 +67,10 @@ pub fn execute(format: OutputFormat, limit: Option<usize>) -> Result<()> {
         };
 
         // Get file size
-        let llms_path = storage.llms_txt_path(alias)?;
-        let size = std::fs::metadata(&llms_path).map(|m| m.len()).unwrap_or(0);
+        let size = storage
+            .stored_llms_txt_path(alias)?
+            .and_then(|path| std::fs::metadata(path).ok())
+            .map_or(0, |m| m.len());
 
         // Get line count from metadata
         let lines = match storage.load_llms_json(alias) {
@@ -80,12 +92,15 @@ pub fn execute(format: OutputFormat, limit: Option<usize>) -> Result<()> {
             oldest = Some((alias.clone(), metadata.fetched_at));
         }
 
+        let access = storage.load_access(alias);
         source_stats.push(SourceStats {
             alias: alias.clone(),
             size_bytes: size,
             lines,
             last_updated: metadata.fetched_at.to_rfc3339(),
             age_hours,
+            last_searched: access.last_searched.map(|at| at.to_rfc3339()),
+            last_fetched: access.last_fetched.map(|at| at.to_rfc3339()),
         });
 
         total_size += size;
@@ -125,7 +140,7 @@ pub fn execute(format: OutputFormat, limit: Option<usize>) -> Result<()> {
         OutputFormat::Jsonl => {
             println!("{}", serde_json::to_string(&stats)?);
         },
-        OutputFormat::Text => {
+        OutputFormat::Text | OutputFormat::Markdown => {
             print_text_stats(&stats);
         },
         OutputFormat::Raw => {
@@ -139,6 +154,281 @@ pub fn execute(format: OutputFormat, limit: Option<usize>) -> Result<()> {
     Ok(())
 }
 
+/// A source with no search hits inside the lookback window
+#[derive(Debug, Serialize)]
+struct UnusedSource {
+    alias: String,
+    size_bytes: u64,
+    last_hit: Option<String>,
+}
+
+/// Result of `stats --unused`
+#[derive(Debug, Serialize)]
+struct UnusedReport {
+    days: u32,
+    unused: Vec<UnusedSource>,
+    removed: Vec<String>,
+}
+
+/// Execute `stats --unused`, listing sources without search hits in the last `days` days.
+///
+/// A source's last hit is the later of its recorded access time and the
+/// newest hit still in the search history log, so sources searched before
+/// access times were recorded are not reported as unused.
+/// With `prune`, the listed sources are removed after confirmation.
+///
+/// # Errors
+///
+/// Returns an error if storage cannot be read, removal fails, or pruning
+/// needs confirmation that cannot be given (see [`confirm::confirm`]).
+pub fn execute_unused(format: OutputFormat, days: u32, prune: bool, yes: bool) -> Result<()> {
+    let storage = Storage::new()?;
+    let last_hits = history_log::last_hit_by_source();
+    let cutoff = Utc::now() - Duration::days(i64::from(days));
+
+    let mut unused: Vec<UnusedSource> = storage
+        .list_sources()
+        .into_iter()
+        .filter_map(|alias| {
+            let last_hit = storage
+                .load_access(&alias)
+                .last_searched
+                .max(last_hits.get(&alias).copied());
+            if last_hit.is_some_and(|hit| hit >= cutoff) {
+                return None;
+            }
+            let size_bytes = storage
+                .stored_llms_txt_path(&alias)
+                .ok()
+                .flatten()
+                .and_then(|path| std::fs::metadata(path).ok())
+                .map_or(0, |m| m.len());
+            Some(UnusedSource {
+                alias,
+                size_bytes,
+                last_hit: last_hit.map(|hit| hit.to_rfc3339()),
+            })
+        })
+        .collect();
+    unused.sort_by_key(|source| Reverse(source.size_bytes));
+
+    let mut removed = Vec::new();
+    let prompt = format!(
+        "Remove {} unused source(s) and all cached data?",
+        unused.len()
+    );
+    if prune && !unused.is_empty() && confirm::confirm(&prompt, Risk::Destructive, yes)? {
+        for source in &unused {
+            RemoveStorage::delete_source(&storage, &source.alias)?;
+            removed.push(source.alias.clone());
+        }
+    }
+
+    let report = UnusedReport {
+        days,
+        unused,
+        removed,
+    };
+
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+        OutputFormat::Jsonl => println!("{}", serde_json::to_string(&report)?),
+        OutputFormat::Text | OutputFormat::Markdown => print_text_unused(&report),
+        OutputFormat::Raw => {
+            for source in &report.unused {
+                println!("{}", source.alias);
+            }
+        },
+    }
+
+    Ok(())
+}
+
+/// Disk usage of one source, split by category
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SourceDisk {
+    alias: String,
+    content_bytes: u64,
+    archive_bytes: u64,
+    index_bytes: u64,
+    total_bytes: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_searched: Option<String>,
+    searches: usize,
+}
+
+/// Result of `stats --disk`
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DiskReport {
+    total_bytes: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    budget_bytes: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    policy: Option<String>,
+    over_budget: bool,
+    sources: Vec<SourceDisk>,
+    /// Sources the policy removes (or would remove) to get back under budget
+    eviction_candidates: Vec<String>,
+}
+
+/// Execute `stats --disk`, reporting per-source disk usage against the budget.
+///
+/// # Errors
+///
+/// Returns an error if storage cannot be read.
+pub fn execute_disk(format: OutputFormat, limit: Option<usize>) -> Result<()> {
+    let storage = Storage::new()?;
+    let entries = quota::entries(&storage);
+    let total_bytes: u64 = entries.iter().map(|entry| entry.bytes).sum();
+    let budget = quota::configured_budget();
+    let eviction_candidates = budget
+        .map(|(budget, policy)| {
+            select_evictions(&entries, budget, policy, &quota::protected(&storage, &[]))
+                .into_iter()
+                .map(|entry| entry.alias)
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mut sources: Vec<SourceDisk> = entries
+        .into_iter()
+        .map(|entry| {
+            let usage = storage
+                .disk_usage(&entry.alias)
+                .unwrap_or_else(|_| SourceDiskUsage::default());
+            SourceDisk {
+                content_bytes: usage.content,
+                archive_bytes: usage.archives,
+                index_bytes: usage.index,
+                total_bytes: usage.total(),
+                last_searched: entry.last_searched.map(|at| at.to_rfc3339()),
+                searches: entry.searches,
+                alias: entry.alias,
+            }
+        })
+        .collect();
+    sources.sort_by_key(|source| Reverse(source.total_bytes));
+    if let Some(limit) = limit {
+        sources.truncate(limit);
+    }
+
+    let report = DiskReport {
+        total_bytes,
+        budget_bytes: budget.map(|(budget, _)| budget.bytes()),
+        policy: budget.map(|(_, policy)| policy.to_string()),
+        over_budget: budget.is_some_and(|(budget, _)| total_bytes > budget.bytes()),
+        sources,
+        eviction_candidates,
+    };
+
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+        OutputFormat::Jsonl => println!("{}", serde_json::to_string(&report)?),
+        OutputFormat::Text | OutputFormat::Markdown => print_text_disk(&report),
+        OutputFormat::Raw => {
+            for source in &report.sources {
+                println!("{}\t{}", source.alias, source.total_bytes);
+            }
+        },
+    }
+
+    Ok(())
+}
+
+fn print_text_disk(report: &DiskReport) {
+    match report.budget_bytes {
+        Some(budget) => println!(
+            "Disk usage: {} of {} budget{}",
+            format_size(report.total_bytes),
+            format_size(budget),
+            if report.over_budget {
+                " (over budget)"
+            } else {
+                ""
+            }
+        ),
+        None => println!(
+            "Disk usage: {} (no budget; set defaults.disk_budget)",
+            format_size(report.total_bytes)
+        ),
+    }
+
+    if !report.sources.is_empty() {
+        println!(
+            "\n  {:<24} {:>10} {:>10} {:>10} {:>10}  SEARCHES",
+            "SOURCE", "TOTAL", "CONTENT", "ARCHIVES", "INDEX"
+        );
+        for source in &report.sources {
+            println!(
+                "  {:<24} {:>10} {:>10} {:>10} {:>10}  {}",
+                source.alias,
+                format_size(source.total_bytes),
+                format_size(source.content_bytes),
+                format_size(source.archive_bytes),
+                format_size(source.index_bytes),
+                source.searches
+            );
+        }
+    }
+
+    if !report.eviction_candidates.is_empty() {
+        let candidates = report.eviction_candidates.join(", ");
+        match report.policy.as_deref() {
+            Some(policy) if policy != "warn" => {
+                println!("\nEvicted on the next add or sync ({policy}): {candidates}");
+            },
+            _ => println!(
+                "\nRemoving these would bring the cache under budget (least recently searched first): {candidates}"
+            ),
+        }
+    }
+}
+
+fn print_text_unused(report: &UnusedReport) {
+    if report.unused.is_empty() {
+        println!(
+            "Every source had search hits in the last {} days",
+            report.days
+        );
+        return;
+    }
+
+    println!(
+        "Sources with no search hits in the last {} days:",
+        report.days
+    );
+    for source in &report.unused {
+        let last_hit = source
+            .last_hit
+            .as_deref()
+            .and_then(|hit| DateTime::parse_from_rfc3339(hit).ok())
+            .map_or_else(
+                || "never hit".to_string(),
+                |hit| {
+                    let days = Utc::now().signed_duration_since(hit).num_days();
+                    format!("last hit {days} days ago")
+                },
+            );
+        println!(
+            "  {} ({}, {last_hit})",
+            source.alias,
+            format_size(source.size_bytes)
+        );
+    }
+
+    if report.removed.is_empty() {
+        let total: u64 = report.unused.iter().map(|source| source.size_bytes).sum();
+        println!(
+            "\n{} reclaimable; run with --prune to remove",
+            format_size(total)
+        );
+    } else {
+        println!("\nRemoved {} source(s)", report.removed.len());
+    }
+}
+
 fn print_text_stats(stats: &CacheStats) {
     println!("BLZ Cache Statistics");
     println!("====================");
@@ -161,8 +451,19 @@ fn print_text_stats(stats: &CacheStats) {
                 }
             };
 
+            let searched = source
+                .last_searched
+                .as_deref()
+                .and_then(|at| DateTime::parse_from_rfc3339(at).ok())
+                .map_or_else(
+                    || "never searched".to_string(),
+                    |at| {
+                        let days = Utc::now().signed_duration_since(at).num_days();
+                        format!("searched {days} days ago")
+                    },
+                );
             println!(
-                "  {} ({}, {} lines, updated {})",
+                "  {} ({}, {} lines, updated {}, {searched})",
                 source.alias,
                 format_size(source.size_bytes),
                 format_number(source.lines),
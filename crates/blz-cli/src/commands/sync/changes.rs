@@ -0,0 +1,142 @@
+//! Change feed for `blz sync --changes-json`.
+//!
+//! Each source's cache is captured before it syncs and compared with the
+//! result afterwards, using the same section matching as `blz diff`. The feed
+//! lists every source attempted so CI jobs can tell "unchanged" from "failed"
+//! and regenerate prompt context only for sources whose headings moved.
+
+use anyhow::Result;
+use blz_core::Storage;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use super::{SyncReport, SyncStatus};
+use crate::commands::diff::{HeadingChanges, Snapshot};
+use crate::utils::shutdown;
+
+/// A source's cache as it was before syncing.
+pub(super) struct Baseline(Option<Snapshot>);
+
+impl Baseline {
+    /// Capture the cache of `alias`; a cache that cannot be read still syncs,
+    /// it just reports no previous line count or heading changes.
+    pub(super) fn capture(storage: &Storage, alias: &str) -> Self {
+        Self(
+            Snapshot::capture(storage, alias)
+                .inspect_err(|err| tracing::debug!("no baseline for {alias}: {err}"))
+                .ok(),
+        )
+    }
+
+    /// Compare the baseline with the cache left by a sync that ended in `status`.
+    pub(super) fn compare(
+        self,
+        storage: &Storage,
+        alias: &str,
+        status: &SyncStatus,
+    ) -> SourceChanges {
+        let Some(snapshot) = self.0 else {
+            return SourceChanges::default();
+        };
+        let previous_lines = Some(snapshot.line_count());
+        let headings = match status {
+            SyncStatus::Refreshed { .. } => snapshot
+                .changes_since(storage, alias)
+                .inspect_err(|err| tracing::warn!("failed to diff {alias} after sync: {err}"))
+                .ok(),
+            _ => None,
+        };
+        SourceChanges {
+            previous_lines,
+            headings,
+        }
+    }
+}
+
+/// What one source's sync changed.
+#[derive(Debug, Default)]
+pub(super) struct SourceChanges {
+    previous_lines: Option<usize>,
+    headings: Option<HeadingChanges>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ChangeFeed<'a> {
+    synced_at: DateTime<Utc>,
+    /// Number of sources whose content was refreshed.
+    changed: usize,
+    /// Signal that stopped the sync early; remaining sources are `skipped`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    interrupted: Option<String>,
+    sources: Vec<SourceEntry<'a>>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SourceEntry<'a> {
+    alias: &'a str,
+    status: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    previous_lines: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    lines: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    headings: Option<usize>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    upgraded: bool,
+    #[serde(flatten)]
+    changes: Option<&'a HeadingChanges>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'a str>,
+}
+
+impl<'a> SourceEntry<'a> {
+    fn new(report: &'a SyncReport) -> Self {
+        let changes = report.changes.as_ref();
+        let previous_lines = changes.and_then(|c| c.previous_lines);
+        let mut entry = Self {
+            alias: &report.alias,
+            status: "unchanged",
+            previous_lines,
+            lines: previous_lines,
+            headings: None,
+            upgraded: false,
+            changes: changes.and_then(|c| c.headings.as_ref()),
+            error: None,
+        };
+        match &report.status {
+            SyncStatus::Refreshed {
+                headings,
+                lines,
+                upgraded,
+            } => {
+                entry.status = "refreshed";
+                entry.lines = Some(*lines);
+                entry.headings = Some(*headings);
+                entry.upgraded = *upgraded;
+            },
+            SyncStatus::Unchanged { upgraded } => entry.upgraded = *upgraded,
+            SyncStatus::PagesPending(_) => entry.status = "pending",
+            SyncStatus::Skipped => entry.status = "skipped",
+            SyncStatus::Failed(error) => {
+                entry.status = "failed";
+                entry.error = Some(error);
+            },
+        }
+        entry
+    }
+}
+
+/// Print the change feed for `reports` as JSON on stdout.
+pub(super) fn print_change_feed(reports: &[SyncReport]) -> Result<()> {
+    let sources: Vec<SourceEntry<'_>> = reports.iter().map(SourceEntry::new).collect();
+    let feed = ChangeFeed {
+        synced_at: Utc::now(),
+        changed: sources.iter().filter(|s| s.status == "refreshed").count(),
+        interrupted: shutdown::requested().map(|signal| signal.to_string()),
+        sources,
+    };
+    println!("{}", serde_json::to_string_pretty(&feed)?);
+    Ok(())
+}
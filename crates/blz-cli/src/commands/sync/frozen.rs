@@ -0,0 +1,189 @@
+//! `sync --frozen`: verify sources against `blz.lock` without updating it.
+//!
+//! Each locked source's upstream content is fetched from its locked URL and
+//! hashed. A hash that differs from the lock is drift and fails the run;
+//! nothing drifted is written to the cache. When upstream still matches but
+//! the cached copy does not (a fresh machine, an older cache), the source is
+//! synced and re-checked. Collections, crates, `OpenAPI` specs, and
+//! directories are assembled from several inputs, so only their cached copy is
+//! compared.
+
+use std::path::Path;
+
+use anyhow::{Result, bail};
+use blz_core::{FetchResult, Fetcher, PerformanceMetrics, SourceType, Storage};
+use colored::Colorize;
+use futures::stream::{self, StreamExt};
+
+use super::{SyncStatus, sync_source};
+use crate::config::SyncConfig;
+use crate::utils::lockfile::{self, LockedSource, Lockfile};
+use crate::utils::resolver;
+
+/// Result of checking one locked source.
+#[derive(Debug)]
+enum FrozenStatus {
+    /// Cache and upstream match the lock.
+    Matches,
+    /// Upstream matched; the cache was synced to the locked content.
+    Synced,
+    /// Content differs from the lock.
+    Drifted {
+        actual: String,
+    },
+    /// Locked but not installed.
+    Missing,
+    Failed(String),
+}
+
+impl FrozenStatus {
+    const fn is_ok(&self) -> bool {
+        matches!(self, Self::Matches | Self::Synced)
+    }
+}
+
+/// Verify `aliases` (or every locked source) against the lockfile.
+///
+/// # Errors
+///
+/// Returns an error if no lockfile is found, an alias is not locked, or any
+/// source is missing, failed to fetch, or drifted from the lock.
+pub(super) async fn execute(
+    aliases: &[String],
+    config: &SyncConfig,
+    metrics: PerformanceMetrics,
+) -> Result<()> {
+    let Some(path) = lockfile::locate(config.lockfile.as_deref()) else {
+        bail!(
+            "No {} found in this directory or its parents; `blz add --manifest <file>` or `blz init` creates one",
+            lockfile::LOCKFILE_NAME
+        );
+    };
+    let lock = Lockfile::load(&path)?;
+    let storage = Storage::new()?;
+
+    let entries: Vec<LockedSource> = if aliases.is_empty() {
+        lock.sources.clone()
+    } else {
+        aliases
+            .iter()
+            .map(|alias| {
+                let canonical =
+                    resolver::resolve_source(&storage, alias)?.unwrap_or_else(|| alias.clone());
+                lock.get(&canonical).cloned().ok_or_else(|| {
+                    anyhow::anyhow!("Source '{alias}' is not locked in {}", path.display())
+                })
+            })
+            .collect::<Result<_>>()?
+    };
+
+    let fetcher = crate::utils::throttle::fetcher(config.throttle)?;
+    let (storage, fetcher) = (&storage, &fetcher);
+    let mut results: Vec<(LockedSource, FrozenStatus)> = stream::iter(entries)
+        .map(|entry| {
+            let metrics = metrics.clone();
+            async move {
+                let status = verify(storage, fetcher, &entry, config, metrics)
+                    .await
+                    .unwrap_or_else(|e| FrozenStatus::Failed(e.to_string()));
+                (entry, status)
+            }
+        })
+        .buffer_unordered(config.jobs.max(1))
+        .collect()
+        .await;
+    results.sort_by(|a, b| a.0.alias.cmp(&b.0.alias));
+
+    if !config.quiet {
+        print_results(&results);
+    }
+    let failed = results.iter().filter(|(_, status)| !status.is_ok()).count();
+    if failed > 0 {
+        bail!(
+            "{failed} source(s) do not match {}; run `blz sync` without --frozen to update it",
+            path.display()
+        );
+    }
+    Ok(())
+}
+
+async fn verify(
+    storage: &Storage,
+    fetcher: &Fetcher,
+    entry: &LockedSource,
+    config: &SyncConfig,
+    metrics: PerformanceMetrics,
+) -> Result<FrozenStatus> {
+    let Some(cached) = storage.load_source_metadata(&entry.alias)? else {
+        return Ok(FrozenStatus::Missing);
+    };
+
+    let upstream = match &cached.origin.source_type {
+        None | Some(SourceType::Remote { .. }) => fetcher.fetch(&entry.url).await?.1,
+        Some(SourceType::LocalFile { path }) => {
+            match blz_core::fetcher::fetch_local(Path::new(path), None)? {
+                FetchResult::Modified { sha256, .. } => sha256,
+                FetchResult::NotModified { .. } => cached.sha256.clone(),
+            }
+        },
+        Some(_) => cached.sha256.clone(),
+    };
+    if upstream != entry.sha256 {
+        return Ok(FrozenStatus::Drifted { actual: upstream });
+    }
+    if cached.sha256 == entry.sha256 {
+        return Ok(FrozenStatus::Matches);
+    }
+
+    if let SyncStatus::Failed(error) =
+        sync_source(storage, fetcher, &entry.alias, config, metrics).await?
+    {
+        return Ok(FrozenStatus::Failed(error));
+    }
+    let synced = storage
+        .load_source_metadata(&entry.alias)?
+        .map(|source| source.sha256)
+        .unwrap_or_default();
+    Ok(if synced == entry.sha256 {
+        FrozenStatus::Synced
+    } else {
+        FrozenStatus::Drifted { actual: synced }
+    })
+}
+
+fn print_results(results: &[(LockedSource, FrozenStatus)]) {
+    let alias_width = results
+        .iter()
+        .map(|(entry, _)| entry.alias.chars().count())
+        .chain(std::iter::once("SOURCE".len()))
+        .max()
+        .unwrap_or(0);
+    println!(
+        "{}",
+        format!("{:<alias_width$}  {:<9}  DETAIL", "SOURCE", "STATUS").dimmed()
+    );
+    for (entry, status) in results {
+        let alias = format!("{:<alias_width$}", entry.alias);
+        let (label, detail) = match status {
+            FrozenStatus::Matches => (format!("{:<9}", "ok").green(), String::new()),
+            FrozenStatus::Synced => (
+                format!("{:<9}", "synced").green(),
+                "cache updated to the locked content".to_string(),
+            ),
+            FrozenStatus::Drifted { actual } => (
+                format!("{:<9}", "drifted").red(),
+                format!("locked {}, now {}", short(&entry.sha256), short(actual)),
+            ),
+            FrozenStatus::Missing => (
+                format!("{:<9}", "missing").red(),
+                "not installed; run `blz add --manifest <file>`".to_string(),
+            ),
+            FrozenStatus::Failed(error) => (format!("{:<9}", "failed").red(), error.clone()),
+        };
+        println!("{alias}  {label}  {detail}");
+    }
+}
+
+fn short(sha: &str) -> String {
+    sha.chars().take(12).collect()
+}
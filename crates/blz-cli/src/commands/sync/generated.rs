@@ -12,8 +12,8 @@
 //! 4. Skip unchanged pages (FREE!)
 //! 5. Scrape only new/changed pages (costs credits)
 //! 6. Retry failed pages from previous sync
-//! 7. Re-assemble with updated pages
-//! 8. Update `generate.json` manifest
+//! 7. Re-assemble with updated pages (see [`apply_scrape_results`])
+//! 8. Update `generate.json` manifest and re-index
 //!
 //! ## Example
 //!
@@ -38,17 +38,20 @@ use std::path::PathBuf;
 
 use anyhow::{Context, Result};
 use blz_core::Storage;
-use blz_core::discovery::SitemapEntry;
+use blz_core::discovery::{RobotsPolicy, SitemapEntry};
+use blz_core::generate::{AssemblyResult, ContentAssembler};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use crate::generate::ScrapeResults;
+pub use crate::generate::{FailedPage, PageCacheEntry, UrlWithLastmod};
+
 /// Manifest for generated sources, stored as `generate.json`.
 ///
 /// Tracks all scraped pages, their lastmod timestamps for change detection,
 /// and any pages that failed scraping for retry.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-#[allow(dead_code)] // Exported for future Firecrawl integration
 pub struct GenerateManifest {
     /// Version of the manifest format.
     pub version: u32,
@@ -58,6 +61,18 @@ pub struct GenerateManifest {
     pub last_sync: DateTime<Utc>,
     /// URL of the sitemap used for discovery.
     pub sitemap_url: String,
+    /// Only scrape sitemap URLs matching one of these patterns.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub include: Vec<String>,
+    /// Skip sitemap URLs matching any of these patterns.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude: Vec<String>,
+    /// Scrape at most this many sitemap URLs, in sitemap order.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_pages: Option<usize>,
+    /// Scrape pages robots.txt disallows, without `Crawl-delay` pacing.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub ignore_robots: bool,
     /// All successfully scraped pages.
     pub pages: Vec<PageCacheEntry>,
     /// Pages that failed to scrape (for retry).
@@ -67,7 +82,7 @@ pub struct GenerateManifest {
     pub total_lines: usize,
 }
 
-#[allow(dead_code)] // Exported for future Firecrawl integration
+#[allow(dead_code)] // Lookups are part of the manifest API; sync itself only walks `pages`
 impl GenerateManifest {
     /// Current manifest version.
     pub const VERSION: u32 = 1;
@@ -81,12 +96,33 @@ impl GenerateManifest {
             created_at: now,
             last_sync: now,
             sitemap_url,
+            include: Vec::new(),
+            exclude: Vec::new(),
+            max_pages: None,
+            ignore_robots: false,
             pages: Vec::new(),
             failed: Vec::new(),
             total_lines: 0,
         }
     }
 
+    /// Sitemap discovery options derived from the manifest's URL filters.
+    #[must_use]
+    pub fn sitemap_options(&self) -> blz_core::discovery::SitemapOptions {
+        blz_core::discovery::SitemapOptions::default()
+            .with_include(self.include.iter().cloned())
+            .with_exclude(self.exclude.iter().cloned())
+    }
+
+    /// robots.txt checks for scraping this source's pages.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP client cannot be constructed.
+    pub fn robots_policy(&self) -> anyhow::Result<RobotsPolicy> {
+        Ok(RobotsPolicy::from_flag(self.ignore_robots)?)
+    }
+
     /// Get a page by URL.
     #[must_use]
     pub fn get_page(&self, url: &str) -> Option<&PageCacheEntry> {
@@ -100,119 +136,24 @@ impl GenerateManifest {
     }
 }
 
-/// A cached page from web scraping.
-///
-/// Stores the scraped content along with metadata for change detection.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-#[allow(dead_code)] // Exported for future Firecrawl integration
-pub struct PageCacheEntry {
-    /// Source URL.
-    pub url: String,
-    /// Page title.
-    pub title: Option<String>,
-    /// When this page was fetched.
-    pub fetched_at: DateTime<Utc>,
-    /// Last modified date from sitemap (for change detection).
-    pub sitemap_lastmod: Option<DateTime<Utc>>,
-    /// Extracted markdown content.
-    pub markdown: String,
-    /// Number of lines in markdown.
-    pub line_count: usize,
-}
-
-#[allow(dead_code)] // Exported for future Firecrawl integration
-impl PageCacheEntry {
-    /// Create a new page cache entry.
-    #[must_use]
-    pub fn new(url: String, markdown: String) -> Self {
-        let line_count = markdown.lines().count();
-        Self {
-            url,
-            title: None,
-            fetched_at: Utc::now(),
-            sitemap_lastmod: None,
-            markdown,
-            line_count,
-        }
-    }
-
-    /// Set the title using builder pattern.
-    #[must_use]
-    pub fn with_title(mut self, title: Option<String>) -> Self {
-        self.title = title;
-        self
-    }
-
-    /// Set the lastmod using builder pattern.
-    #[must_use]
-    pub const fn with_lastmod(mut self, lastmod: Option<DateTime<Utc>>) -> Self {
-        self.sitemap_lastmod = lastmod;
-        self
-    }
-}
-
-/// A page that failed to scrape.
-///
-/// Tracked for retry on subsequent syncs.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-#[allow(dead_code)] // Exported for future Firecrawl integration
-pub struct FailedPage {
-    /// URL that failed.
-    pub url: String,
-    /// Error message.
-    pub error: String,
-    /// Number of attempts.
-    pub attempts: u32,
-    /// Last attempt timestamp.
-    pub last_attempt: DateTime<Utc>,
-}
-
-#[allow(dead_code)] // Exported for future Firecrawl integration
-impl FailedPage {
-    /// Create a new failed page entry.
-    #[must_use]
-    pub fn new(url: String, error: String) -> Self {
-        Self {
-            url,
-            error,
-            attempts: 1,
-            last_attempt: Utc::now(),
+/// Drop sitemap entries robots.txt disallows, returning how many were dropped.
+pub async fn drop_disallowed(entries: &mut Vec<SitemapEntry>, robots: &RobotsPolicy) -> usize {
+    if !robots.is_enforced() {
+        return 0;
+    }
+    let before = entries.len();
+    let mut allowed = Vec::with_capacity(before);
+    for entry in entries.drain(..) {
+        let permitted = match url::Url::parse(&entry.url) {
+            Ok(url) => robots.allows(&url).await,
+            Err(_) => true,
+        };
+        if permitted {
+            allowed.push(entry);
         }
     }
-
-    /// Increment the attempt count.
-    pub fn increment_attempts(&mut self) {
-        self.attempts += 1;
-        self.last_attempt = Utc::now();
-    }
-}
-
-/// URL with optional lastmod for change detection.
-///
-/// Used to track URLs that need to be scraped during sync.
-#[derive(Debug, Clone)]
-pub struct UrlWithLastmod {
-    /// The URL to scrape.
-    pub url: String,
-    /// Last modification date from sitemap.
-    pub lastmod: Option<DateTime<Utc>>,
-}
-
-impl UrlWithLastmod {
-    /// Create a new URL entry.
-    #[must_use]
-    pub const fn new(url: String) -> Self {
-        Self { url, lastmod: None }
-    }
-
-    /// Set the lastmod using builder pattern.
-    #[must_use]
-    pub const fn with_lastmod(mut self, lastmod: Option<DateTime<Utc>>) -> Self {
-        self.lastmod = lastmod;
-        self
-    }
+    *entries = allowed;
+    before - entries.len()
 }
 
 // ============================================================
@@ -241,12 +182,14 @@ pub fn is_generated_source(storage: &Storage, alias: &str) -> bool {
 
 /// Get the path to the generate.json manifest for a source.
 ///
+/// Resolves into the shared cache for shared sources, so only read through it.
+///
 /// # Errors
 ///
 /// Returns an error if the alias is invalid.
 pub fn generate_manifest_path(storage: &Storage, alias: &str) -> Result<PathBuf> {
-    let tool_dir = storage.tool_dir(alias).context("Invalid alias")?;
-    Ok(tool_dir.join("generate.json"))
+    let source_dir = storage.source_dir(alias).context("Invalid alias")?;
+    Ok(source_dir.join("generate.json"))
 }
 
 /// Load the generate manifest for a source.
@@ -273,19 +216,15 @@ pub fn load_generate_manifest(storage: &Storage, alias: &str) -> Result<Generate
 /// # Errors
 ///
 /// Returns an error if the manifest can't be serialized or written.
-#[allow(dead_code)] // Exported for future Firecrawl integration
 pub fn save_generate_manifest(
     storage: &Storage,
     alias: &str,
     manifest: &GenerateManifest,
 ) -> Result<()> {
-    let path = generate_manifest_path(storage, alias)?;
-
-    // Ensure directory exists
-    if let Some(parent) = path.parent() {
-        fs::create_dir_all(parent)
-            .with_context(|| format!("Failed to create directory for '{alias}'"))?;
-    }
+    let path = storage
+        .ensure_tool_dir(alias)
+        .with_context(|| format!("Failed to create directory for '{alias}'"))?
+        .join("generate.json");
 
     let content = serde_json::to_string_pretty(manifest)
         .with_context(|| format!("Failed to serialize generate.json for '{alias}'"))?;
@@ -370,7 +309,7 @@ pub fn pages_needing_update(
 /// # Errors
 ///
 /// Returns an error if the manifest can't be loaded.
-#[allow(dead_code)] // Exported for future Firecrawl integration
+#[allow(dead_code)] // Part of the module's public API; sync reads retries from the manifest
 pub fn pages_to_retry(storage: &Storage, alias: &str) -> Result<Vec<FailedPage>> {
     let manifest = load_generate_manifest(storage, alias)?;
     Ok(manifest.failed)
@@ -405,6 +344,75 @@ pub fn categorize_sync_pages(
     (unchanged, updates, retries)
 }
 
+/// Fold a sync's scrape results into the manifest.
+///
+/// Pages follow sitemap order. Pages that were not rescraped keep their cached
+/// content, and a page whose rescrape failed keeps its previous version until a
+/// later sync succeeds. Pages no longer in the sitemap are dropped. Failures
+/// carry their attempt count forward, and pages skipped on shutdown keep any
+/// earlier failure so they are retried next time.
+pub fn apply_scrape_results(
+    manifest: &mut GenerateManifest,
+    sitemap_entries: &[SitemapEntry],
+    results: ScrapeResults,
+) {
+    let mut fresh: HashMap<String, PageCacheEntry> = results
+        .successful
+        .into_iter()
+        .map(|page| (page.url.clone(), page))
+        .collect();
+    let mut cached: HashMap<String, PageCacheEntry> = std::mem::take(&mut manifest.pages)
+        .into_iter()
+        .map(|page| (page.url.clone(), page))
+        .collect();
+    manifest.pages = sitemap_entries
+        .iter()
+        .filter_map(|entry| {
+            fresh
+                .remove(&entry.url)
+                .or_else(|| cached.remove(&entry.url))
+        })
+        .collect();
+
+    let previous: HashMap<String, FailedPage> = std::mem::take(&mut manifest.failed)
+        .into_iter()
+        .map(|failed| (failed.url.clone(), failed))
+        .collect();
+    let mut failed: Vec<FailedPage> = results
+        .failed
+        .into_iter()
+        .map(|mut page| {
+            if let Some(earlier) = previous.get(&page.url) {
+                page.attempts = earlier.attempts;
+                page.increment_attempts();
+            }
+            page
+        })
+        .chain(
+            results
+                .skipped
+                .iter()
+                .filter_map(|url| previous.get(url).cloned()),
+        )
+        .collect();
+    failed.sort_by(|a, b| a.url.cmp(&b.url));
+    manifest.failed = failed;
+    manifest.last_sync = Utc::now();
+}
+
+/// Assemble cached pages into the source document, one `#` section per page.
+#[must_use]
+pub fn assemble_document(pages: &[PageCacheEntry]) -> AssemblyResult {
+    let pages: Vec<_> = pages
+        .iter()
+        .map(|page| {
+            blz_core::page_cache::PageCacheEntry::new(page.url.clone(), page.markdown.clone())
+                .with_title(page.title.clone())
+        })
+        .collect();
+    ContentAssembler::assemble(&pages)
+}
+
 // ============================================================
 // Tests
 // ============================================================
@@ -697,6 +705,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_manifest_sitemap_filters() {
+        let mut manifest = GenerateManifest::new("https://example.com/sitemap.xml".to_string());
+        let json = serde_json::to_string(&manifest).unwrap();
+        assert!(!json.contains("include"));
+
+        manifest.include = vec!["/docs/".to_string()];
+        manifest.exclude = vec!["*/v1/*".to_string()];
+        let options = manifest.sitemap_options();
+        assert!(options.allows("https://example.com/docs/intro"));
+        assert!(!options.allows("https://example.com/docs/v1/intro"));
+        assert!(!options.allows("https://example.com/blog"));
+    }
+
     #[test]
     fn test_manifest_serialization_roundtrip() {
         let mut manifest = GenerateManifest::new("https://example.com/sitemap.xml".to_string());
@@ -898,4 +920,89 @@ mod tests {
         let retries = pages_to_retry(&storage, "test-source").unwrap();
         assert_eq!(retries.len(), 2);
     }
+
+    // --------------------------------------------------------
+    // apply_scrape_results / assemble_document Tests
+    // --------------------------------------------------------
+
+    #[test]
+    fn test_apply_scrape_results_merges_in_sitemap_order() {
+        let mut manifest = GenerateManifest::new("https://example.com/sitemap.xml".to_string());
+        manifest.pages = vec![
+            create_cached_page("https://example.com/a", "2024-01-01"),
+            create_cached_page("https://example.com/b", "2024-01-01"),
+            create_cached_page("https://example.com/gone", "2024-01-01"),
+        ];
+        let mut earlier =
+            FailedPage::new("https://example.com/c".to_string(), "timeout".to_string());
+        earlier.attempts = 2;
+        manifest.failed.push(earlier);
+
+        let sitemap = vec![
+            create_sitemap_entry("https://example.com/c", "2024-02-01"),
+            create_sitemap_entry("https://example.com/b", "2024-02-01"),
+            create_sitemap_entry("https://example.com/a", "2024-01-01"),
+        ];
+        let mut results = ScrapeResults::new();
+        results.failed.push(FailedPage::new(
+            "https://example.com/b".to_string(),
+            "500".to_string(),
+        ));
+        results.failed.push(FailedPage::new(
+            "https://example.com/c".to_string(),
+            "503".to_string(),
+        ));
+
+        apply_scrape_results(&mut manifest, &sitemap, results);
+
+        let urls: Vec<_> = manifest.pages.iter().map(|p| p.url.as_str()).collect();
+        assert_eq!(urls, ["https://example.com/b", "https://example.com/a"]);
+        assert_eq!(manifest.failed.len(), 2);
+        assert_eq!(manifest.failed[0].url, "https://example.com/b");
+        assert_eq!(manifest.failed[0].attempts, 1);
+        assert_eq!(manifest.failed[1].attempts, 3);
+        assert_eq!(manifest.failed[1].error, "503");
+    }
+
+    #[test]
+    fn test_apply_scrape_results_replaces_rescraped_pages() {
+        let mut manifest = GenerateManifest::new("https://example.com/sitemap.xml".to_string());
+        manifest.pages = vec![create_cached_page("https://example.com/a", "2024-01-01")];
+        let sitemap = vec![create_sitemap_entry("https://example.com/a", "2024-02-01")];
+        let mut results = ScrapeResults::new();
+        results.successful.push(
+            PageCacheEntry::new("https://example.com/a".to_string(), "New text".to_string())
+                .with_lastmod(sitemap[0].lastmod),
+        );
+
+        apply_scrape_results(&mut manifest, &sitemap, results);
+
+        assert_eq!(manifest.pages.len(), 1);
+        assert_eq!(manifest.pages[0].markdown, "New text");
+        assert_eq!(manifest.pages[0].sitemap_lastmod, sitemap[0].lastmod);
+        assert!(manifest.failed.is_empty());
+    }
+
+    #[test]
+    fn test_assemble_document_sections_pages() {
+        let pages = vec![
+            PageCacheEntry::new("https://example.com/a".to_string(), "Alpha".to_string())
+                .with_title(Some("A".to_string())),
+            PageCacheEntry::new("https://example.com/b".to_string(), "Beta".to_string()),
+        ];
+
+        let assembled = assemble_document(&pages);
+
+        assert!(
+            assembled
+                .content
+                .starts_with("# A\nSource: https://example.com/a\n\nAlpha")
+        );
+        assert!(
+            assembled
+                .content
+                .contains("# Untitled\nSource: https://example.com/b")
+        );
+        assert_eq!(assembled.stats.total_lines, 10);
+    }
 }
@@ -21,18 +21,64 @@
 //! ```bash
 //! blz sync bun                   # Sync single source
 //! blz sync --all                 # Sync all sources
+//! blz sync --all --jobs 8        # Sync all sources, eight at a time
 //! blz sync bun react             # Sync multiple sources
+//! blz sync --all --changes-json  # JSON changelog for CI
+//! blz sync --frozen              # Fail if content drifted from blz.lock
 //! ```
+//!
+//! # Concurrency
+//!
+//! `sync --all` is mostly network wait, so sources are refreshed concurrently
+//! (`--jobs`, default 4) behind a shared progress display, followed by one
+//! summary table instead of interleaved per-source output.
+//!
+//! # Change Feed
+//!
+//! `--changes-json` replaces the progress display and summary with a JSON
+//! changelog (see [`changes`]): each source's status, its line counts before
+//! and after, and which headings the sync added, removed, moved, or edited.
+//!
+//! # Interruption
+//!
+//! SIGINT/SIGTERM during `sync --all` (or a multi-source `--changes-json`
+//! run) lets in-flight sources finish, marks the rest as skipped in the
+//! summary or change feed, and exits with status 130/143.
+//!
+//! # Lockfile
+//!
+//! When a `blz.lock` is found in the working directory or a parent (or named
+//! with `--lockfile`), a sync re-pins its entries to the content now cached.
+//! `--frozen` instead verifies sources against the lock (see [`frozen`]) and
+//! never updates it.
 
+mod changes;
+mod frozen;
 pub mod generated;
 
+use std::fmt::Write as _;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
 use anyhow::Result;
-use blz_core::{PerformanceMetrics, Storage};
+use base64::{Engine as _, engine::general_purpose::STANDARD};
+use blz_core::refresh::{
+    ApplyRefreshParams, DefaultRefreshIndexer, RefreshContext, RefreshOutcome, RefreshPayload,
+    RefreshStorage, apply_refresh, refresh_source_with_metadata, resolve_refresh_url,
+};
+use blz_core::throttle::Bandwidth;
+use blz_core::{Fetcher, PerformanceMetrics, Storage};
 use clap::Args;
 use colored::Colorize;
+use futures::stream::{self, StreamExt};
+use indicatif::{MultiProgress, ProgressBar, ProgressDrawTarget, ProgressStyle};
+use sha2::{Digest, Sha256};
 
-use crate::config::SyncConfig;
-use crate::utils::resolver;
+use crate::config::{DEFAULT_SYNC_JOBS, SyncConfig};
+use crate::generate::{FirecrawlScraper, GenerateOrchestrator};
+use crate::i18n::{self, Message};
+use crate::utils::lockfile::{self, Lockfile};
+use crate::utils::{filter_flags, resolver, shutdown};
 
 /// Arguments for `blz sync` (fetch latest docs)
 #[derive(Args, Clone, Debug)]
@@ -63,6 +109,9 @@ pub struct SyncArgs {
     /// Available filters:
     ///   lang,language  - Filter non-English content
     ///
+    /// A source with a pipeline saved by `blz filter set` runs that pipeline
+    /// instead whenever filtering is enabled.
+    ///
     /// Examples:
     ///   --filter           # Enable all filters
     ///   --filter lang      # Only language filter
@@ -73,32 +122,99 @@ pub struct SyncArgs {
     /// Disable all content filters for this sync
     #[arg(long, conflicts_with = "filter")]
     pub no_filter: bool,
+
+    /// Limit download bandwidth (e.g. 500KB/s, 2MB/s)
+    ///
+    /// Defaults to `defaults.throttle` in config or `BLZ_THROTTLE`.
+    #[arg(long, value_name = "RATE")]
+    pub throttle: Option<Bandwidth>,
+    /// Skip TLS certificate verification.
+    ///
+    /// Last resort for hosts behind TLS-inspecting proxies; prefer
+    /// `defaults.ca_bundle` / `BLZ_CA_BUNDLE` to trust the proxy's CA instead.
+    #[arg(long)]
+    pub insecure: bool,
+
+    /// Number of sources to refresh at once with --all
+    #[arg(short = 'j', long, value_name = "N", default_value_t = DEFAULT_SYNC_JOBS, value_parser = parse_jobs)]
+    pub jobs: usize,
+
+    /// Print a JSON changelog of which sources and headings changed (for CI)
+    ///
+    /// Replaces the progress display and summary table. Every source synced
+    /// is listed with its status, so unchanged and failed ones appear too.
+    #[arg(long, conflicts_with = "reindex")]
+    pub changes_json: bool,
+
+    /// Fail if any source no longer matches `blz.lock` (for CI)
+    ///
+    /// Upstream content is checked against each locked SHA-256 and the
+    /// lockfile is left untouched; cached copies that lag the lock are synced
+    /// to it. Without aliases, every locked source is checked.
+    #[arg(long, conflicts_with_all = ["reindex", "changes_json"])]
+    pub frozen: bool,
+
+    /// Lockfile to verify or update (default: nearest blz.lock)
+    #[arg(long, value_name = "FILE")]
+    pub lockfile: Option<PathBuf>,
+}
+
+fn parse_jobs(value: &str) -> Result<usize, String> {
+    match value.parse::<usize>() {
+        Ok(jobs) if (1..=32).contains(&jobs) => Ok(jobs),
+        _ => Err(format!("jobs must be between 1 and 32, got {value}")),
+    }
 }
 
 // Re-export generated source types and functions for public API.
-// Some are not yet used internally but are exported for future Firecrawl integration.
 #[allow(unused_imports)]
 pub use generated::{
-    FailedPage, GenerateManifest, PageCacheEntry, UrlWithLastmod, categorize_sync_pages,
-    is_generated_source, load_generate_manifest,
+    FailedPage, GenerateManifest, PageCacheEntry, UrlWithLastmod, apply_scrape_results,
+    assemble_document, categorize_sync_pages, drop_disallowed, is_generated_source,
+    load_generate_manifest, save_generate_manifest,
 };
 
 // These functions are available via the `generated` module for direct use:
 // - pages_needing_update: Determine which pages need re-scraping
 // - pages_to_retry: Get failed pages for retry
-// - save_generate_manifest: Persist updated manifest
 // - should_scrape: Compare cached vs sitemap lastmod
 
 /// Dispatch a Sync command from CLI args.
 pub async fn dispatch(args: SyncArgs, quiet: bool, metrics: PerformanceMetrics) -> Result<()> {
+    crate::utils::throttle::set_insecure(args.insecure);
     let config = SyncConfig::new()
         .with_yes(args.yes)
         .with_reindex(args.reindex)
         .with_filter(args.filter)
         .with_no_filter(args.no_filter)
-        .with_quiet(quiet);
+        .with_quiet(quiet || args.changes_json)
+        .with_throttle(args.throttle)
+        .with_jobs(args.jobs)
+        .with_changes_json(args.changes_json)
+        .with_frozen(args.frozen)
+        .with_lockfile(args.lockfile);
+
+    if config.frozen {
+        return frozen::execute(&args.aliases, &config, metrics).await;
+    }
+    execute(&args.aliases, args.all, &config, metrics).await?;
+    update_lockfile(&config)
+}
 
-    execute(&args.aliases, args.all, &config, metrics).await
+/// Re-pin the entries of the project's lockfile to what is now cached.
+fn update_lockfile(config: &SyncConfig) -> Result<()> {
+    let Some(path) = lockfile::locate(config.lockfile.as_deref()) else {
+        return Ok(());
+    };
+    let mut lock = Lockfile::load(&path)?;
+    let aliases: Vec<String> = lock.sources.iter().map(|e| e.alias.clone()).collect();
+    if lock.record(&Storage::new()?, &aliases)? {
+        lock.save(&path)?;
+        if !config.quiet {
+            println!("Updated {}", path.display());
+        }
+    }
+    Ok(())
 }
 
 /// Execute the sync command to fetch latest documentation
@@ -129,6 +245,22 @@ pub async fn execute(
              blz sync <alias>      # Sync specific source\n  \
              blz sync --all        # Sync all sources"
         );
+    } else if config.changes_json {
+        let storage = Storage::new()?;
+        let sources = aliases
+            .iter()
+            .map(|alias| {
+                let canonical =
+                    resolver::resolve_source(&storage, alias)?.unwrap_or_else(|| alias.clone());
+                if !storage.exists(&canonical) {
+                    anyhow::bail!("Source '{alias}' not found");
+                }
+                Ok(canonical)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        shutdown::install();
+        let reports = sync_concurrently(&storage, sources, config, &metrics).await?;
+        changes::print_change_feed(&reports)
     } else {
         // Sync specified aliases
         let storage = Storage::new()?;
@@ -139,23 +271,107 @@ pub async fn execute(
     }
 }
 
+/// Outcome of syncing one source during `sync --all`.
+#[derive(Debug)]
+enum SyncStatus {
+    Refreshed {
+        headings: usize,
+        lines: usize,
+        upgraded: bool,
+    },
+    Unchanged {
+        upgraded: bool,
+    },
+    /// Generated source with pages that failed to scrape and await a retry.
+    PagesPending(usize),
+    Failed(String),
+    /// Not started because shutdown was requested.
+    Skipped,
+}
+
+#[derive(Debug)]
+struct SyncReport {
+    alias: String,
+    status: SyncStatus,
+    elapsed: Duration,
+    /// What changed, when collecting a change feed.
+    changes: Option<changes::SourceChanges>,
+}
+
 /// Execute sync for all sources.
 async fn execute_all(config: &SyncConfig, metrics: PerformanceMetrics) -> Result<()> {
     let storage = Storage::new()?;
-    let sources = storage.list_sources();
+    // Sources from the shared cache are read-only; whoever publishes it syncs them
+    let (shared, sources): (Vec<_>, Vec<_>) = storage
+        .list_sources()
+        .into_iter()
+        .partition(|alias| storage.is_shared(alias));
 
     if sources.is_empty() {
-        anyhow::bail!("No sources configured. Use 'blz add' to add sources.");
+        if shared.is_empty() {
+            anyhow::bail!(i18n::text(Message::NoSourcesConfigured, &[]));
+        }
+        if !config.quiet {
+            println!(
+                "All {} source(s) come from the read-only shared cache; nothing to sync",
+                shared.len()
+            );
+        }
+        return Ok(());
     }
 
-    let mut refreshed_count = 0;
-    let mut skipped_count = 0;
-    let mut error_count = 0;
+    shutdown::install();
+    if config.reindex {
+        // Re-indexing is local and CPU-bound, so concurrency buys nothing
+        reindex_all(&storage, sources, config, &metrics);
+        return Ok(());
+    }
 
-    for alias in sources {
-        match execute_single(&storage, &alias, config, metrics.clone()).await {
-            Ok(true) => refreshed_count += 1,
-            Ok(false) => skipped_count += 1,
+    let reports = sync_concurrently(&storage, sources, config, &metrics).await?;
+
+    if config.changes_json {
+        return changes::print_change_feed(&reports);
+    }
+    if !config.quiet {
+        print_summary_table(&reports);
+        metrics.print_summary();
+    }
+
+    Ok(())
+}
+
+fn reindex_all(
+    storage: &Storage,
+    sources: Vec<String>,
+    config: &SyncConfig,
+    metrics: &PerformanceMetrics,
+) {
+    let mut reindexed_count = 0;
+    let mut error_count = 0;
+    let total = sources.len();
+
+    for (done, alias) in sources.into_iter().enumerate() {
+        if shutdown::requested().is_some() {
+            if !config.quiet {
+                println!(
+                    "\nInterrupted: {} source(s) not re-indexed",
+                    (total - done).to_string().yellow()
+                );
+            }
+            break;
+        }
+        if is_generated_source(storage, &alias) {
+            continue;
+        }
+        match super::refresh::execute_reindex(
+            storage,
+            &alias,
+            metrics.clone(),
+            config.quiet,
+            config.filter.as_ref(),
+            config.no_filter,
+        ) {
+            Ok(()) => reindexed_count += 1,
             Err(e) => {
                 if !config.quiet {
                     eprintln!("{}: {}", alias.red(), e);
@@ -167,19 +383,254 @@ async fn execute_all(config: &SyncConfig, metrics: PerformanceMetrics) -> Result
 
     if !config.quiet {
         println!(
-            "\nSummary: {} synced, {} unchanged, {} errors",
-            refreshed_count.to_string().green(),
-            skipped_count,
-            if error_count > 0 {
-                error_count.to_string().red()
-            } else {
-                error_count.to_string().normal()
-            }
+            "\nSummary: {} re-indexed, {} errors",
+            reindexed_count.to_string().green(),
+            error_count_colored(error_count)
         );
         metrics.print_summary();
     }
+}
 
-    Ok(())
+/// Refresh every source with at most `config.jobs` in flight.
+///
+/// Reports come back sorted by alias regardless of completion order.
+async fn sync_concurrently(
+    storage: &Storage,
+    sources: Vec<String>,
+    config: &SyncConfig,
+    metrics: &PerformanceMetrics,
+) -> Result<Vec<SyncReport>> {
+    let fetcher = crate::utils::throttle::fetcher(config.throttle)?;
+    let progress = if config.quiet {
+        MultiProgress::with_draw_target(ProgressDrawTarget::hidden())
+    } else {
+        MultiProgress::new()
+    };
+    let overall = progress.add(ProgressBar::new(sources.len() as u64));
+    overall.set_style(
+        ProgressStyle::default_bar()
+            .template("{bar:30.green/dim} {pos}/{len} sources synced")
+            .unwrap_or_else(|_| ProgressStyle::default_bar()),
+    );
+    let spinner_style = ProgressStyle::default_spinner()
+        .template("{spinner:.green} {msg}")
+        .unwrap_or_else(|_| ProgressStyle::default_spinner());
+
+    let fetcher = &fetcher;
+    let progress = &progress;
+    let overall = &overall;
+    let mut reports: Vec<SyncReport> = stream::iter(sources)
+        .map(|alias| {
+            // Built lazily, so only in-flight sources get a spinner
+            let spinner = progress.insert_before(overall, ProgressBar::new_spinner());
+            spinner.set_style(spinner_style.clone());
+            spinner.set_message(format!("Syncing {alias}..."));
+            spinner.enable_steady_tick(Duration::from_millis(100));
+            async move {
+                let start = Instant::now();
+                if shutdown::requested().is_some() {
+                    spinner.finish_and_clear();
+                    return SyncReport {
+                        alias,
+                        status: SyncStatus::Skipped,
+                        elapsed: Duration::ZERO,
+                        changes: None,
+                    };
+                }
+                let baseline = config
+                    .changes_json
+                    .then(|| changes::Baseline::capture(storage, &alias));
+                let status = sync_source(storage, fetcher, &alias, config, metrics.clone())
+                    .await
+                    .unwrap_or_else(|e| SyncStatus::Failed(e.to_string()));
+                let changes = baseline.map(|baseline| baseline.compare(storage, &alias, &status));
+                spinner.finish_and_clear();
+                overall.inc(1);
+                SyncReport {
+                    alias,
+                    status,
+                    elapsed: start.elapsed(),
+                    changes,
+                }
+            }
+        })
+        .buffer_unordered(config.jobs.max(1))
+        .collect()
+        .await;
+    overall.finish_and_clear();
+
+    reports.sort_by(|a, b| a.alias.cmp(&b.alias));
+    Ok(reports)
+}
+
+/// Sync one source without printing, for the concurrent `--all` path.
+async fn sync_source(
+    storage: &Storage,
+    fetcher: &Fetcher,
+    alias: &str,
+    config: &SyncConfig,
+    metrics: PerformanceMetrics,
+) -> Result<SyncStatus> {
+    if is_generated_source(storage, alias) {
+        let synced = sync_generated_source(storage, alias, true, metrics).await?;
+        return Ok(match synced.outcome {
+            RefreshOutcome::Refreshed {
+                headings, lines, ..
+            } => SyncStatus::Refreshed {
+                headings,
+                lines,
+                upgraded: false,
+            },
+            RefreshOutcome::Unchanged { .. } if synced.failed > 0 => {
+                SyncStatus::PagesPending(synced.failed)
+            },
+            RefreshOutcome::Unchanged { .. } => SyncStatus::Unchanged { upgraded: false },
+        });
+    }
+
+    let metadata = storage.load_metadata(alias)?;
+    let aliases = storage.load_llms_aliases(alias)?;
+
+    let flags = filter_flags::parse_filter_flags(config.filter.as_ref());
+    let filter_preference = if config.no_filter {
+        false
+    } else if flags.any_enabled() {
+        flags.language
+    } else {
+        metadata.filter_non_english.unwrap_or(true)
+    };
+
+    let resolution = resolve_refresh_url(fetcher, &metadata).await?;
+    // Sources with a declared checksum or signature keep their URL
+    let upgraded = resolution.upgraded && storage.load_integrity(alias)?.is_none();
+    let ctx = RefreshContext::new(metadata, aliases, resolution);
+    let outcome = refresh_source_with_metadata(
+        storage,
+        fetcher,
+        alias,
+        &ctx,
+        metrics,
+        &DefaultRefreshIndexer,
+        filter_preference,
+    )
+    .await?;
+
+    Ok(match outcome {
+        RefreshOutcome::Refreshed {
+            headings, lines, ..
+        } => SyncStatus::Refreshed {
+            headings,
+            lines,
+            upgraded,
+        },
+        RefreshOutcome::Unchanged { .. } => SyncStatus::Unchanged { upgraded },
+    })
+}
+
+fn print_summary_table(reports: &[SyncReport]) {
+    let alias_width = reports
+        .iter()
+        .map(|report| report.alias.chars().count())
+        .chain(std::iter::once("SOURCE".len()))
+        .max()
+        .unwrap_or(0);
+
+    println!(
+        "{}",
+        format!(
+            "{:<alias_width$}  {:<10}  {:>8}  {:>8}  DETAIL",
+            "SOURCE", "STATUS", "HEADINGS", "TIME"
+        )
+        .dimmed()
+    );
+
+    let mut refreshed_count = 0;
+    let mut unchanged_count = 0;
+    let mut pending_count = 0;
+    let mut error_count = 0;
+    let mut skipped_count = 0;
+    for report in reports {
+        let alias = format!("{:<alias_width$}", report.alias);
+        let time = format!("{:.1}s", report.elapsed.as_secs_f64());
+        let (status, headings, detail) = match &report.status {
+            SyncStatus::Refreshed {
+                headings,
+                lines,
+                upgraded,
+            } => {
+                refreshed_count += 1;
+                let mut detail = format!("{lines} lines");
+                if *upgraded {
+                    detail.push_str(", upgraded to llms-full.txt");
+                }
+                (
+                    format!("{:<10}", "refreshed").green(),
+                    headings.to_string(),
+                    detail,
+                )
+            },
+            SyncStatus::Unchanged { upgraded } => {
+                unchanged_count += 1;
+                let detail = if *upgraded {
+                    "upgraded to llms-full.txt".to_string()
+                } else {
+                    String::new()
+                };
+                (
+                    format!("{:<10}", "unchanged").normal(),
+                    "-".to_string(),
+                    detail,
+                )
+            },
+            SyncStatus::PagesPending(failed) => {
+                pending_count += 1;
+                (
+                    format!("{:<10}", "pending").yellow(),
+                    "-".to_string(),
+                    format!("{failed} page(s) failed to scrape; retried on next sync"),
+                )
+            },
+            SyncStatus::Failed(error) => {
+                error_count += 1;
+                (
+                    format!("{:<10}", "failed").red(),
+                    "-".to_string(),
+                    error.clone(),
+                )
+            },
+            SyncStatus::Skipped => {
+                skipped_count += 1;
+                (
+                    format!("{:<10}", "skipped").yellow(),
+                    "-".to_string(),
+                    "interrupted before sync started".to_string(),
+                )
+            },
+        };
+        println!("{alias}  {status}  {headings:>8}  {time:>8}  {detail}");
+    }
+
+    let mut summary = format!(
+        "\nSummary: {} synced, {} unchanged, {} errors",
+        refreshed_count.to_string().green(),
+        unchanged_count,
+        error_count_colored(error_count)
+    );
+    if pending_count > 0 {
+        let _ = write!(summary, ", {pending_count} with pending pages");
+    }
+    if skipped_count > 0 {
+        let _ = write!(summary, ", {skipped_count} skipped (interrupted)");
+    }
+    println!("{summary}");
+}
+
+fn error_count_colored(error_count: usize) -> colored::ColoredString {
+    if error_count > 0 {
+        error_count.to_string().red()
+    } else {
+        error_count.to_string().normal()
+    }
 }
 
 /// Execute sync for a single source.
@@ -201,7 +652,9 @@ async fn execute_single(
     // Check if this is a generated source
     if is_generated_source(storage, &canonical_alias) {
         // Generated source: use lastmod-based sync
-        sync_generated_source(storage, &canonical_alias, config.quiet).await
+        let synced =
+            sync_generated_source(storage, &canonical_alias, config.quiet, metrics).await?;
+        Ok(matches!(synced.outcome, RefreshOutcome::Refreshed { .. }))
     } else {
         // Standard source: use existing refresh flow
         super::refresh::execute(
@@ -211,39 +664,75 @@ async fn execute_single(
             config.reindex,
             config.filter.as_ref(),
             config.no_filter,
+            config.throttle,
         )
         .await?;
         Ok(true) // Assume updated for now
     }
 }
 
+/// Result of syncing a generated source.
+struct GeneratedSync {
+    outcome: RefreshOutcome,
+    /// Pages that failed to scrape and will be retried next sync.
+    failed: usize,
+}
+
 /// Sync a generated source using sitemap lastmod optimization.
 ///
 /// This function:
 /// 1. Loads the generate manifest
 /// 2. Fetches the sitemap
 /// 3. Compares lastmod timestamps
-/// 4. Reports which pages need updates (actual scraping deferred)
-///
-/// Returns `Ok(true)` if updates are needed, `Ok(false)` if unchanged.
-async fn sync_generated_source(storage: &Storage, alias: &str, quiet: bool) -> Result<bool> {
-    use blz_core::discovery::fetch_sitemap;
+/// 4. Scrapes new, changed, and previously failed pages via Firecrawl
+/// 5. Re-assembles the document, keeping unchanged pages from the manifest
+/// 6. Re-indexes when the document changed and saves the manifest
+#[allow(clippy::too_many_lines)]
+async fn sync_generated_source(
+    storage: &Storage,
+    alias: &str,
+    quiet: bool,
+    metrics: PerformanceMetrics,
+) -> Result<GeneratedSync> {
+    use blz_core::discovery::fetch_sitemap_with;
 
     if !quiet {
         println!("Syncing {} {}...", alias.green(), "(generated)".dimmed());
     }
 
     // Load manifest
-    let manifest = load_generate_manifest(storage, alias)?;
+    let mut manifest = load_generate_manifest(storage, alias)?;
 
     // Fetch current sitemap
     if !quiet {
         println!("  Fetching sitemap...");
     }
-    let sitemap_entries = fetch_sitemap(&manifest.sitemap_url).await?;
+    let mut sitemap_entries =
+        fetch_sitemap_with(&manifest.sitemap_url, &manifest.sitemap_options()).await?;
+    if sitemap_entries.is_empty() {
+        anyhow::bail!(
+            "Sitemap {} lists no pages; keeping the cached copy of '{alias}'",
+            manifest.sitemap_url
+        );
+    }
+    let robots = manifest.robots_policy()?;
+    let disallowed = drop_disallowed(&mut sitemap_entries, &robots).await;
+    if sitemap_entries.is_empty() {
+        anyhow::bail!(
+            "robots.txt disallows every page in {}; keeping the cached copy of '{alias}' \
+             (re-create it with 'blz generate --ignore-robots' to override)",
+            manifest.sitemap_url
+        );
+    }
+    if let Some(max_pages) = manifest.max_pages {
+        sitemap_entries.truncate(max_pages);
+    }
 
     if !quiet {
         println!("  {} URLs in sitemap", sitemap_entries.len());
+        if disallowed > 0 {
+            println!("    {disallowed} disallowed by robots.txt (skipping)");
+        }
     }
 
     // Compare with cached pages
@@ -251,6 +740,13 @@ async fn sync_generated_source(storage: &Storage, alias: &str, quiet: bool) -> R
         categorize_sync_pages(&manifest.pages, &sitemap_entries, &manifest.failed);
 
     let total_to_scrape = updates.len() + retries.len();
+    let listed: std::collections::HashSet<&str> =
+        sitemap_entries.iter().map(|e| e.url.as_str()).collect();
+    let removed = manifest
+        .pages
+        .iter()
+        .filter(|page| !listed.contains(page.url.as_str()))
+        .count();
 
     if !quiet {
         println!("  Comparing with cache...");
@@ -264,28 +760,124 @@ async fn sync_generated_source(storage: &Storage, alias: &str, quiet: bool) -> R
         if !retries.is_empty() {
             println!("    {} previously failed (retrying)", retries.len());
         }
+        if removed > 0 {
+            println!("    {removed} no longer in sitemap (dropping)");
+        }
     }
 
-    if total_to_scrape == 0 {
+    if total_to_scrape == 0 && removed == 0 {
         if !quiet {
-            println!("{} {} (unchanged)", "".green(), alias.green());
+            println!("{} {} (unchanged)", "✓".green(), alias.green());
         }
-        return Ok(false);
+        return Ok(GeneratedSync {
+            outcome: RefreshOutcome::Unchanged {
+                alias: alias.to_string(),
+            },
+            failed: 0,
+        });
     }
 
-    // Report what would be done (actual scraping requires Firecrawl integration)
+    let urls: Vec<_> = updates.into_iter().chain(retries).collect();
+    let results = if urls.is_empty() {
+        crate::generate::ScrapeResults::default()
+    } else {
+        let scraper = FirecrawlScraper::resolve().await?;
+        if !quiet {
+            println!(
+                "  Scraping {} pages via {}...",
+                urls.len(),
+                scraper.describe()
+            );
+        }
+        GenerateOrchestrator::with_default_concurrency(scraper)
+            .with_robots(robots)
+            .scrape_all(&urls)
+            .await
+    };
     if !quiet {
         println!(
-            "\n{} {} pages would be scraped",
-            "Note:".yellow(),
-            total_to_scrape
+            "    {} scraped, {} failed",
+            results.successful.len(),
+            results.failed.len()
         );
-        println!("  Full scraping requires Firecrawl CLI integration (coming soon)");
-        println!("  For now, use 'blz generate' to regenerate with updated content");
+        if !results.skipped.is_empty() {
+            println!(
+                "    {} not scraped (interrupted)",
+                results.skipped.len().to_string().yellow()
+            );
+        }
+    }
+
+    apply_scrape_results(&mut manifest, &sitemap_entries, results);
+    let assembled = assemble_document(&manifest.pages);
+    manifest.total_lines = assembled.stats.total_lines;
+
+    let outcome = reindex_generated(storage, alias, assembled.content, metrics).await?;
+    save_generate_manifest(storage, alias, &manifest)?;
+
+    if !quiet {
+        match &outcome {
+            RefreshOutcome::Refreshed {
+                headings, lines, ..
+            } => println!(
+                "{} {} ({} headings, {} lines)",
+                "✓ Refreshed".green(),
+                alias.green(),
+                headings,
+                lines
+            ),
+            RefreshOutcome::Unchanged { .. } => {
+                println!("{} {} (unchanged)", "✓".green(), alias.green());
+            },
+        }
+        if !manifest.failed.is_empty() {
+            println!(
+                "  {} {} page(s) failed to scrape; they are retried on the next sync",
+                "Note:".yellow(),
+                manifest.failed.len()
+            );
+        }
+    }
+
+    Ok(GeneratedSync {
+        outcome,
+        failed: manifest.failed.len(),
+    })
+}
+
+/// Write and re-index a generated source's re-assembled document, unless it
+/// is byte-for-byte what is already cached.
+async fn reindex_generated(
+    storage: &Storage,
+    alias: &str,
+    content: String,
+    metrics: PerformanceMetrics,
+) -> Result<RefreshOutcome> {
+    let metadata = storage.load_metadata(alias)?;
+    let sha256 = STANDARD.encode(Sha256::digest(content.as_bytes()));
+    if sha256 == metadata.sha256 {
+        return Ok(RefreshOutcome::Unchanged {
+            alias: alias.to_string(),
+        });
     }
 
-    // Return true to indicate updates are available
-    Ok(true)
+    let aliases = storage.load_llms_aliases(alias)?;
+    let payload = RefreshPayload {
+        content,
+        sha256,
+        etag: None,
+        last_modified: None,
+    };
+    let params = ApplyRefreshParams::new(metadata, aliases);
+    Ok(apply_refresh(
+        storage,
+        alias,
+        &params,
+        &payload,
+        metrics,
+        &DefaultRefreshIndexer,
+    )
+    .await?)
 }
 
 #[cfg(test)]
@@ -0,0 +1,113 @@
+//! Term statistics command.
+//!
+//! `blz terms` lists the most frequent indexed terms in one source, optionally
+//! narrowed to the sections under a heading path. Terms are shown in their
+//! indexed form (tokenized and lowercased), so every listed term is one a
+//! `blz query` against that source can match.
+
+use anyhow::{Context, Result};
+use blz_core::{Storage, TermStat};
+use clap::Args;
+use colored::Colorize;
+use serde::Serialize;
+
+use crate::output::OutputFormat;
+use crate::utils::cli_args::FormatArg;
+use crate::utils::resolver::resolve_source;
+
+/// Arguments for `blz terms`.
+#[derive(Args, Clone, Debug)]
+pub struct TermsArgs {
+    /// Source to inspect
+    #[arg(value_name = "ALIAS")]
+    pub alias: String,
+    /// Only count sections under this heading path (e.g. "API > Hooks")
+    #[arg(long, value_name = "PATH")]
+    pub heading: Option<String>,
+    /// Only list terms starting with this prefix
+    #[arg(long, value_name = "TEXT")]
+    pub prefix: Option<String>,
+    /// Number of terms to list
+    #[arg(
+        short = 'n',
+        long,
+        default_value_t = 50,
+        value_parser = clap::value_parser!(u16).range(1..=10_000)
+    )]
+    pub limit: u16,
+    /// Output format
+    #[command(flatten)]
+    pub format: FormatArg,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TermsReport<'a> {
+    source: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    heading: Option<&'a str>,
+    terms: Vec<TermStat>,
+}
+
+/// Execute `blz terms`.
+///
+/// # Errors
+///
+/// Returns an error if the source is unknown or its index cannot be read.
+pub fn execute(args: &TermsArgs, quiet: bool) -> Result<()> {
+    let format = args.format.resolve(quiet);
+    let storage = Storage::new()?;
+    let source = resolve_source(&storage, &args.alias)?
+        .ok_or_else(|| anyhow::anyhow!("Source '{}' not found", args.alias))?;
+    let index = storage
+        .open_index(&source)
+        .with_context(|| format!("Failed to open index for '{source}'"))?;
+    let terms = index.term_stats(
+        args.heading.as_deref(),
+        args.prefix.as_deref(),
+        usize::from(args.limit),
+    )?;
+
+    let report = TermsReport {
+        source: &source,
+        heading: args.heading.as_deref(),
+        terms,
+    };
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+        OutputFormat::Jsonl => {
+            for term in &report.terms {
+                println!("{}", serde_json::to_string(term)?);
+            }
+        },
+        OutputFormat::Raw => {
+            for term in &report.terms {
+                println!("{}", term.term);
+            }
+        },
+        OutputFormat::Text | OutputFormat::Markdown => print_text_report(&report),
+    }
+    Ok(())
+}
+
+fn print_text_report(report: &TermsReport<'_>) {
+    let scope = report
+        .heading
+        .map_or_else(String::new, |heading| format!(" under \"{heading}\""));
+    if report.terms.is_empty() {
+        println!("No indexed terms in {}{scope}", report.source);
+        return;
+    }
+
+    println!("Top terms in {}{scope}", report.source.bold());
+    println!();
+    println!("   {:<28} {:>11} {:>9}", "term", "occurrences", "sections");
+    for stat in &report.terms {
+        println!(
+            "   {:<28} {:>11} {:>9}",
+            stat.term,
+            stat.term_freq,
+            stat.doc_freq.to_string().bright_black()
+        );
+    }
+}
@@ -8,6 +8,7 @@ use colored::Colorize;
 
 use crate::commands::RequestSpec;
 use crate::config::{TocConfig, TocNavigation};
+use crate::i18n::{self, Message};
 use crate::output::OutputFormat;
 use crate::output::render::{
     render_toc_multi_with_options, render_toc_paginated_with_options, render_toc_with_options,
@@ -19,8 +20,10 @@ use crate::output::shapes::{
 use crate::utils::cli_args;
 use crate::utils::cli_args::FormatArg;
 use crate::utils::heading_filter::HeadingLevelFilter;
+use crate::utils::history_log::{self, Continuation};
 use crate::utils::parsing::{LineRange, parse_line_ranges};
 use crate::utils::preferences::{self, TocHistoryEntry};
+use crate::utils::toc::child_ordinal;
 
 /// Validates that limit is at least 1
 fn validate_limit(s: &str) -> Result<usize, String> {
@@ -401,7 +404,7 @@ fn handle_anchors_mode(
                 println!("{}", serde_json::to_string(&m)?);
             }
         },
-        OutputFormat::Text => {
+        OutputFormat::Text | OutputFormat::Markdown => {
             println!(
                 "Remap metadata for {} (updated {})\n",
                 canonical.green(),
@@ -499,7 +502,7 @@ fn save_toc_history_entry(config: &TocConfig, params: &TocHistoryParams<'_>) {
             .map(serialize_heading_level_filter),
     };
 
-    if let Err(err) = preferences::save_toc_history(&history_entry) {
+    if let Err(err) = history_log::record(Continuation::Map(history_entry)) {
         tracing::warn!("failed to save TOC history: {err}");
     }
 }
@@ -540,6 +543,7 @@ fn convert_to_paginated_entries(page_entries: &[serde_json::Value]) -> Vec<TocPa
                 })
                 .unwrap_or_default();
             let heading_level = u8::try_from(v["headingLevel"].as_u64().unwrap_or(1)).unwrap_or(1);
+            let ordinal = v["ordinal"].as_str().unwrap_or_default().to_string();
             let lines = v["lines"].as_str()?.to_string();
             let anchor = v["anchor"].as_str().map(String::from);
 
@@ -550,6 +554,7 @@ fn convert_to_paginated_entries(page_entries: &[serde_json::Value]) -> Vec<TocPa
                 raw_heading_path,
                 heading_path_normalized,
                 heading_level,
+                ordinal,
                 lines,
                 anchor,
             })
@@ -566,8 +571,10 @@ fn convert_to_paginated_entries(page_entries: &[serde_json::Value]) -> Vec<TocPa
 ///
 /// Returns a `Vec` because when a parent doesn't match but has matching children,
 /// those children are promoted to the parent's level (returned as multiple entries).
+/// Promoted entries keep the ordinal of their position in the unfiltered TOC.
 fn convert_core_toc_entry(
     entry: &blz_core::TocEntry,
+    ordinal: String,
     depth: usize,
     max_depth: Option<usize>,
     filter: Option<&HeadingFilter>,
@@ -587,7 +594,17 @@ fn convert_core_toc_entry(
         entry
             .children
             .iter()
-            .flat_map(|c| convert_core_toc_entry(c, depth + 1, max_depth, filter, level_filter))
+            .enumerate()
+            .flat_map(|(i, c)| {
+                convert_core_toc_entry(
+                    c,
+                    child_ordinal(&ordinal, i),
+                    depth + 1,
+                    max_depth,
+                    filter,
+                    level_filter,
+                )
+            })
             .collect()
     } else {
         Vec::new()
@@ -598,6 +615,7 @@ fn convert_core_toc_entry(
         let title = display_path.last().cloned().unwrap_or_default();
         vec![ShapeTocEntry {
             level: u8::try_from(depth + 1).unwrap_or(6), // Max heading level is 6
+            ordinal,
             title,
             lines: entry.lines.clone(),
             anchor: entry.anchor.clone(),
@@ -631,8 +649,16 @@ fn build_toc_output_for_source(
     let entries: Vec<ShapeTocEntry> = llms
         .toc
         .iter()
-        .flat_map(|e| {
-            convert_core_toc_entry(e, 0, max_depth.map(usize::from), filter, level_filter)
+        .enumerate()
+        .flat_map(|(i, e)| {
+            convert_core_toc_entry(
+                e,
+                child_ordinal("", i),
+                0,
+                max_depth.map(usize::from),
+                filter,
+                level_filter,
+            )
         })
         .collect();
 
@@ -813,11 +839,38 @@ fn collect_all_entries(
             alias: source_alias,
             canonical: &canonical,
         };
-        collect_entries(&mut all_entries, &llms.toc, 0, &ctx);
+        collect_entries(&mut all_entries, &llms.toc, "", 0, &ctx);
     }
     Ok(all_entries)
 }
 
+/// Unpaginated table of contents for `sources` in the `blz map --json` shape.
+///
+/// Used by `blz serve`.
+pub(super) fn toc_listing(
+    storage: &Storage,
+    sources: &[String],
+    max_depth: Option<u8>,
+    filter_expr: Option<&str>,
+) -> Result<TocPaginatedOutput> {
+    let (filter, level_filter) = parse_filters(filter_expr, None, max_depth)?;
+    let all_entries = collect_all_entries(
+        storage,
+        sources,
+        max_depth,
+        filter.as_ref(),
+        level_filter.as_ref(),
+    )?;
+    let (entries, page, total_pages, total_results) = calculate_pagination(all_entries, None, 1);
+    Ok(TocPaginatedOutput::new(
+        convert_to_paginated_entries(&entries),
+        page,
+        total_pages,
+        total_results,
+        None,
+    ))
+}
+
 /// Parameters for TOC output formatting.
 struct TocOutputParams<'a> {
     source_list: &'a [String],
@@ -839,6 +892,7 @@ fn format_toc_output(
     let render_options = TocRenderOptions {
         tree_mode: config.tree,
         show_anchors: config.show_anchors,
+        numbered: config.numbered,
     };
     let mut stdout = std::io::stdout();
 
@@ -967,7 +1021,10 @@ pub async fn execute(
     let is_navigating = nav.is_navigating();
 
     let last_entry = if is_navigating {
-        preferences::load_last_toc_entry()
+        history_log::latest(|continuation| match continuation {
+            Continuation::Map(entry) => Some(entry),
+            _ => None,
+        })
     } else {
         None
     };
@@ -1027,6 +1084,7 @@ pub async fn execute(
         tree: config.tree,
         anchors: config.anchors,
         show_anchors: config.show_anchors,
+        numbered: config.numbered,
         quiet: config.quiet,
     };
 
@@ -1127,13 +1185,15 @@ struct CollectEntriesContext<'a> {
 fn collect_entries(
     entries: &mut Vec<serde_json::Value>,
     list: &[blz_core::TocEntry],
+    parent_ordinal: &str,
     depth: usize,
     ctx: &CollectEntriesContext<'_>,
 ) {
-    for e in list {
+    for (i, e) in list.iter().enumerate() {
         if exceeds_depth(depth, ctx.max_depth) {
             continue;
         }
+        let ordinal = child_ordinal(parent_ordinal, i);
         let display_path = display_path(e);
         let level_matches = ctx
             .level_filter
@@ -1150,12 +1210,13 @@ fn collect_entries(
                 "rawHeadingPath": e.heading_path,
                 "headingPathNormalized": e.heading_path_normalized,
                 "headingLevel": depth + 1,
+                "ordinal": ordinal,
                 "lines": e.lines,
                 "anchor": e.anchor,
             }));
         }
         if !e.children.is_empty() && can_descend(depth, ctx.max_depth) {
-            collect_entries(entries, &e.children, depth + 1, ctx);
+            collect_entries(entries, &e.children, &ordinal, depth + 1, ctx);
         }
     }
 }
@@ -1675,13 +1736,22 @@ pub async fn get_by_anchor(
     }
 
     let Some(entry) = find(&llms.toc, anchor) else {
-        println!("Anchor not found for '{anchor}' in '{canonical}'");
-        println!("Hint: run 'blz toc {canonical}' to inspect available headings");
+        println!(
+            "{}",
+            i18n::text(
+                Message::AnchorNotFound,
+                &[("anchor", anchor), ("alias", &canonical)]
+            )
+        );
+        println!(
+            "{}",
+            i18n::text(Message::HintInspectToc, &[("alias", &canonical)])
+        );
         return Ok(());
     };
 
     match output {
-        OutputFormat::Text => {
+        OutputFormat::Text | OutputFormat::Markdown => {
             // Convert context to ContextMode
             let context_mode = context.map(crate::cli::ContextMode::Symmetric);
             let requests = vec![RequestSpec {
@@ -1700,13 +1770,9 @@ pub async fn get_by_anchor(
         },
         OutputFormat::Json | OutputFormat::Jsonl => {
             // Build content string for the range +/- context
-            let file_path = storage.llms_txt_path(&canonical)?;
-            let file_content = std::fs::read_to_string(&file_path).with_context(|| {
-                format!(
-                    "Failed to read llms.txt content from {}",
-                    file_path.display()
-                )
-            })?;
+            let file_content = storage
+                .load_llms_txt(&canonical)
+                .with_context(|| format!("Failed to read llms.txt content for '{canonical}'"))?;
             let all_lines: Vec<&str> = file_content.lines().collect();
             let (body, line_numbers) = extract_content(&entry.lines, context, &all_lines)?;
             let display_path = display_path(entry);
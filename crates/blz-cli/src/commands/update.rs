@@ -8,13 +8,14 @@ use std::time::Instant;
 
 use anyhow::{Result, anyhow};
 use blz_core::{
-    FetchResult, Fetcher, MarkdownParser, PerformanceMetrics, SearchIndex, Source, Storage,
+    FetchResult, MarkdownParser, PerformanceMetrics, SearchIndex, Source, Storage, TextAnalysis,
     build_llms_json,
 };
 use chrono::Utc;
 use colored::Colorize;
 use indicatif::{ProgressBar, ProgressStyle};
 
+use crate::i18n::{self, Message};
 use crate::utils::count_headings;
 use crate::utils::resolver;
 use blz_core::url_resolver;
@@ -72,7 +73,9 @@ pub trait UpdateIndexer {
 }
 
 #[derive(Default)]
-struct DefaultIndexer;
+struct DefaultIndexer {
+    analysis: TextAnalysis,
+}
 
 impl UpdateIndexer for DefaultIndexer {
     fn index(
@@ -82,9 +85,7 @@ impl UpdateIndexer for DefaultIndexer {
         metrics: PerformanceMetrics,
         blocks: &[blz_core::HeadingBlock],
     ) -> Result<()> {
-        let index = SearchIndex::create_or_open(index_path)?.with_metrics(metrics);
-        index
-            .index_blocks(alias, blocks)
+        SearchIndex::rebuild_with(index_path, alias, blocks, metrics, &self.analysis)
             .map_err(anyhow::Error::from)
     }
 }
@@ -190,6 +191,21 @@ where
         (Some(blz_core::SourceType::LocalFile { path }), _) => {
             Some(blz_core::SourceType::LocalFile { path: path.clone() })
         },
+        (
+            Some(
+                composite @ (blz_core::SourceType::Stdin
+                | blz_core::SourceType::Collection { .. }
+                | blz_core::SourceType::Crate { .. }
+                | blz_core::SourceType::OpenApi { .. }
+                | blz_core::SourceType::Directory { .. }
+                | blz_core::SourceType::Html { .. }
+                | blz_core::SourceType::Pdf { .. }
+                | blz_core::SourceType::Followed { .. }
+                | blz_core::SourceType::Generated { .. }
+                | blz_core::SourceType::GithubRepo { .. }),
+            ),
+            _,
+        ) => Some(composite.clone()),
         (None, Some(existing)) => Some(existing.clone()),
     };
 
@@ -264,7 +280,7 @@ pub async fn execute(alias: &str, metrics: PerformanceMetrics, quiet: bool) -> R
     let start = Instant::now();
     let existing_metadata = storage.load_metadata(&canonical_alias)?;
     let existing_aliases = storage.load_llms_aliases(&canonical_alias)?;
-    let fetcher = Fetcher::new()?;
+    let fetcher = crate::utils::throttle::fetcher(None)?;
 
     // Check for URL upgrades (llms.txt -> llms-full.txt)
     let (final_url, updated_variant) = if existing_metadata.variant == blz_core::SourceVariant::Llms
@@ -339,7 +355,9 @@ pub async fn execute(alias: &str, metrics: PerformanceMetrics, quiet: bool) -> R
                 etag,
                 last_modified,
             };
-            let indexer = DefaultIndexer;
+            let indexer = DefaultIndexer {
+                analysis: storage.load_text_analysis(&canonical_alias)?,
+            };
 
             // Update metadata with new URL and variant if upgraded
             let mut updated_metadata = existing_metadata.clone();
@@ -399,14 +417,13 @@ pub async fn execute_all(metrics: PerformanceMetrics, quiet: bool) -> Result<()>
     let sources = storage.list_sources();
 
     if sources.is_empty() {
-        anyhow::bail!("No sources configured. Use 'blz add' to add sources.");
+        anyhow::bail!(i18n::text(Message::NoSourcesConfigured, &[]));
     }
 
-    let fetcher = Fetcher::new()?;
+    let fetcher = crate::utils::throttle::fetcher(None)?;
     let mut updated_count = 0;
     let mut skipped_count = 0;
     let mut error_count = 0;
-    let indexer = DefaultIndexer;
 
     for alias in sources {
         let spinner = if quiet {
@@ -440,6 +457,9 @@ pub async fn execute_all(metrics: PerformanceMetrics, quiet: bool) -> Result<()>
                 last_modified,
             } => {
                 spinner.set_message(format!("Parsing {alias}..."));
+                let indexer = DefaultIndexer {
+                    analysis: storage.load_text_analysis(&alias)?,
+                };
                 match apply_update(
                     &storage,
                     &alias,
@@ -33,6 +33,31 @@ pub struct ValidationResult {
     pub days_since_update: i64,
     /// Human-readable issues found during validation.
     pub issues: Vec<String>,
+    /// Cached copy checked against the declared checksum or signature
+    /// (`--verify` only).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub integrity: Option<IntegrityReport>,
+}
+
+/// Outcome of `blz check --verify` for one source.
+#[derive(Debug, Serialize)]
+pub struct IntegrityReport {
+    /// Whether the cached copy matched.
+    pub status: IntegrityStatus,
+    /// What did not match, for mismatches.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+#[derive(Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum IntegrityStatus {
+    /// Cached copy matches the declared checksum and signature.
+    Verified,
+    /// Cached copy does not match.
+    Mismatch,
+    /// The source declares no checksum or signature.
+    Undeclared,
 }
 
 #[derive(Debug, Serialize, PartialEq, Eq)]
@@ -61,15 +86,23 @@ pub async fn dispatch_deprecated(
             "Warning: 'validate' is deprecated, use 'check' instead".yellow()
         );
     }
-    execute(alias, all, format.resolve(quiet)).await
+    execute(alias, all, false, format.resolve(quiet)).await
 }
 
 /// Execute the validation command for one or more sources.
 ///
+/// With `verify`, each cached document is also checked against the checksum
+/// or signature declared for its source.
+///
 /// # Errors
 ///
 /// Returns an error if storage access, network checks, or serialization fails.
-pub async fn execute(alias: Option<String>, all: bool, format: OutputFormat) -> Result<()> {
+pub async fn execute(
+    alias: Option<String>,
+    all: bool,
+    verify: bool,
+    format: OutputFormat,
+) -> Result<()> {
     let storage = Storage::new()?;
 
     // Determine which sources to validate
@@ -89,7 +122,7 @@ pub async fn execute(alias: Option<String>, all: bool, format: OutputFormat) ->
     let mut results = Vec::new();
 
     for source_alias in &sources {
-        let result = validate_source(&storage, source_alias).await?;
+        let result = validate_source(&storage, source_alias, verify).await?;
         results.push(result);
     }
 
@@ -104,7 +137,7 @@ pub async fn execute(alias: Option<String>, all: bool, format: OutputFormat) ->
                 println!("{json}");
             }
         },
-        OutputFormat::Text | OutputFormat::Raw => {
+        OutputFormat::Text | OutputFormat::Markdown | OutputFormat::Raw => {
             print_text_results(&results);
         },
     }
@@ -118,7 +151,103 @@ pub async fn execute(alias: Option<String>, all: bool, format: OutputFormat) ->
     Ok(())
 }
 
-async fn validate_source(storage: &Storage, alias: &str) -> Result<ValidationResult> {
+/// HEAD every collection member; the collection is reachable only when all are.
+async fn check_collection_members(
+    members: &[blz_core::CollectionMember],
+    issues: &mut Vec<String>,
+) -> Result<bool> {
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(10))
+        .build()?;
+
+    let mut reachable = true;
+    for member in members {
+        match client.head(&member.url).send().await {
+            Ok(response) if response.status().is_success() => {},
+            Ok(response) => {
+                reachable = false;
+                issues.push(format!(
+                    "Member '{}' returned status code {}",
+                    member.prefix,
+                    response.status()
+                ));
+            },
+            Err(e) => {
+                reachable = false;
+                issues.push(format!(
+                    "Failed to connect to member '{}': {e}",
+                    member.prefix
+                ));
+            },
+        }
+    }
+    Ok(reachable)
+}
+
+/// Check that a local source input still exists, recording an issue if not.
+fn check_local_path(path: &str, label: &str, issues: &mut Vec<String>) -> bool {
+    let exists = std::path::Path::new(path).exists();
+    if !exists {
+        issues.push(format!("{label} not found: {path}"));
+    }
+    exists
+}
+
+/// HEAD-check a remote source URL, returning the response status code.
+async fn check_remote_url(url: &str, issues: &mut Vec<String>) -> Result<Option<u16>> {
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(10))
+        .build()?;
+
+    match client.head(url).send().await {
+        Ok(response) => {
+            if !response.status().is_success() {
+                issues.push(format!("URL returned status code {}", response.status()));
+            }
+            Ok(Some(response.status().as_u16()))
+        },
+        Err(e) => {
+            issues.push(format!("Failed to connect to URL: {e}"));
+            Ok(None)
+        },
+    }
+}
+
+/// Check the cached document against the source's declared checksum and the
+/// signature cached when it was fetched.
+fn verify_integrity(storage: &Storage, alias: &str) -> Result<IntegrityReport> {
+    let Some(integrity) = storage.load_integrity(alias)? else {
+        return Ok(IntegrityReport {
+            status: IntegrityStatus::Undeclared,
+            detail: None,
+        });
+    };
+    let content = storage.load_llms_txt(alias)?;
+    let mut result = integrity.check_sha256(content.as_bytes());
+    if result.is_ok() && integrity.minisign_key.is_some() {
+        result = storage.load_signature(alias)?.map_or_else(
+            || {
+                Err(blz_core::Error::Integrity(
+                    "No verified signature is cached for this source".to_string(),
+                ))
+            },
+            |signature| integrity.check_signature(content.as_bytes(), &signature),
+        );
+    }
+    Ok(match result {
+        Ok(()) => IntegrityReport {
+            status: IntegrityStatus::Verified,
+            detail: None,
+        },
+        Err(err) => IntegrityReport {
+            status: IntegrityStatus::Mismatch,
+            detail: Some(err.to_string()),
+        },
+    })
+}
+
+#[allow(clippy::too_many_lines)]
+async fn validate_source(storage: &Storage, alias: &str, verify: bool) -> Result<ValidationResult> {
     let canonical = resolver::resolve_source(storage, alias)?.unwrap_or_else(|| alias.to_string());
 
     let metadata = storage
@@ -126,34 +255,52 @@ async fn validate_source(storage: &Storage, alias: &str) -> Result<ValidationRes
         .with_context(|| format!("Source '{alias}' not found"))?;
 
     let mut issues = Vec::new();
-    let mut url_accessible = false;
     let mut url_status_code = None;
     let mut checksum_matches = false;
     let mut actual_checksum = None;
 
     // Check if URL/file is accessible based on source type
-    match &metadata.origin.source_type {
+    let url_accessible = match &metadata.origin.source_type {
         Some(blz_core::SourceType::LocalFile { path }) => {
-            // For local files, check filesystem existence
-            if std::path::Path::new(path).exists() {
-                url_accessible = true;
-                // No HTTP status code for local files
-            } else {
-                issues.push(format!("Local file not found: {path}"));
-            }
+            // For local files, check filesystem existence (no HTTP status code)
+            check_local_path(path, "Local file", &mut issues)
+        },
+        Some(blz_core::SourceType::Collection { members }) => {
+            check_collection_members(members, &mut issues).await?
+        },
+        Some(blz_core::SourceType::OpenApi { location })
+            if !blz_core::openapi::is_remote(location) =>
+        {
+            check_local_path(location, "OpenAPI spec", &mut issues)
         },
-        Some(blz_core::SourceType::Remote { url: _ }) | None => {
+        Some(blz_core::SourceType::Directory { path, .. }) => {
+            check_local_path(path, "Documentation folder", &mut issues)
+        },
+        // Piped sources keep no origin; the cached copy is all there is
+        Some(blz_core::SourceType::Stdin) => true,
+        Some(
+            blz_core::SourceType::Remote { url: _ }
+            | blz_core::SourceType::Html { .. }
+            | blz_core::SourceType::Pdf { .. }
+            | blz_core::SourceType::Followed { .. }
+            | blz_core::SourceType::Generated { .. }
+            | blz_core::SourceType::GithubRepo { .. }
+            | blz_core::SourceType::Crate { .. }
+            | blz_core::SourceType::OpenApi { .. },
+        )
+        | None => {
             // For remote sources (or when source_type is not set), check HTTP accessibility
-            let client = reqwest::Client::builder()
-                .timeout(std::time::Duration::from_secs(10))
-                .build()?;
-
             let check_url = metadata
                 .origin
                 .source_type
                 .as_ref()
                 .and_then(|st| {
-                    if let blz_core::SourceType::Remote { url } = st {
+                    if let blz_core::SourceType::Remote { url }
+                    | blz_core::SourceType::Html { url }
+                    | blz_core::SourceType::Pdf { url }
+                    | blz_core::SourceType::Followed { url, .. }
+                    | blz_core::SourceType::Generated { sitemap: url } = st
+                    {
                         Some(url.as_str())
                     } else {
                         None
@@ -161,28 +308,16 @@ async fn validate_source(storage: &Storage, alias: &str) -> Result<ValidationRes
                 })
                 .unwrap_or(&metadata.url);
 
-            match client.head(check_url).send().await {
-                Ok(response) => {
-                    url_accessible = response.status().is_success();
-                    url_status_code = Some(response.status().as_u16());
-
-                    if !url_accessible {
-                        issues.push(format!("URL returned status code {}", response.status()));
-                    }
-                },
-                Err(e) => {
-                    issues.push(format!("Failed to connect to URL: {e}"));
-                },
-            }
+            url_status_code = check_remote_url(check_url, &mut issues).await?;
+            url_status_code.is_some_and(|code| (200..300).contains(&code))
         },
-    }
+    };
 
     // Verify SHA-256 checksum
-    let llms_path = storage.llms_txt_path(&canonical)?;
-    if llms_path.exists() {
-        let content = tokio::fs::read(&llms_path).await?;
+    if storage.stored_llms_txt_path(&canonical)?.is_some() {
+        let content = storage.load_llms_txt(&canonical)?;
         let mut hasher = Sha256::new();
-        hasher.update(&content);
+        hasher.update(content.as_bytes());
 
         // Convert to base64 to match metadata format
         let checksum = STANDARD.encode(hasher.finalize());
@@ -197,6 +332,18 @@ async fn validate_source(storage: &Storage, alias: &str) -> Result<ValidationRes
         issues.push("Local file not found".to_string());
     }
 
+    let integrity = if verify && checksum_matches {
+        Some(verify_integrity(storage, &canonical)?)
+    } else {
+        None
+    };
+    let integrity_failed = integrity
+        .as_ref()
+        .is_some_and(|report| report.status == IntegrityStatus::Mismatch);
+    if let Some(detail) = integrity.as_ref().and_then(|report| report.detail.as_ref()) {
+        issues.push(detail.clone());
+    }
+
     // Check staleness
     let days_since_update = staleness::days_since(metadata.fetched_at);
 
@@ -209,12 +356,17 @@ async fn validate_source(storage: &Storage, alias: &str) -> Result<ValidationRes
     // Determine overall status
     let status = if issues.is_empty() {
         ValidationStatus::Healthy
-    } else if url_accessible && checksum_matches {
+    } else if url_accessible && checksum_matches && !integrity_failed {
         ValidationStatus::Warning
     } else {
         ValidationStatus::Error
     };
 
+    // Persist reachability and lint score so `blz list` can show health badges.
+    if let Err(err) = storage.record_health(&canonical, Some(url_accessible)) {
+        tracing::debug!("failed to record health for {canonical}: {err}");
+    }
+
     Ok(ValidationResult {
         alias: canonical,
         status,
@@ -226,6 +378,7 @@ async fn validate_source(storage: &Storage, alias: &str) -> Result<ValidationRes
         actual_checksum,
         days_since_update,
         issues,
+        integrity,
     })
 }
 
@@ -262,6 +415,15 @@ fn print_text_results(results: &[ValidationResult]) {
             }
         }
 
+        if let Some(integrity) = &result.integrity {
+            let integrity_str = match integrity.status {
+                IntegrityStatus::Verified => "verified".green(),
+                IntegrityStatus::Mismatch => "MISMATCH".red(),
+                IntegrityStatus::Undeclared => "no checksum or signature declared".bright_black(),
+            };
+            println!("  Integrity: {integrity_str}");
+        }
+
         println!("  Last updated: {} days ago", result.days_since_update);
 
         if !result.issues.is_empty() {
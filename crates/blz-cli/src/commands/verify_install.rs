@@ -0,0 +1,276 @@
+//! Post-install smoke test.
+//!
+//! `blz verify-install` runs the whole pipeline against a fixture compiled
+//! into the binary: store and parse it, build a search index, search it, and
+//! read the cited lines back. Everything happens in a throwaway directory, so
+//! the real cache is never touched and installer scripts, Homebrew `test do`
+//! blocks, and bug reports can run it safely.
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result, bail};
+use base64::{Engine as _, engine::general_purpose::STANDARD as B64};
+use blz_core::{MarkdownParser, SearchHit, SearchIndex, Storage, build_llms_json};
+use clap::Args;
+use colored::Colorize;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::output::OutputFormat;
+use crate::utils::cli_args::FormatArg;
+use crate::utils::parsing::parse_line_span;
+
+/// Alias the fixture is stored under inside the throwaway cache.
+const FIXTURE_ALIAS: &str = "blz-verify";
+
+/// Synthetic URL recorded in the fixture's metadata.
+const FIXTURE_URL: &str = "blz://verify-install/fixture";
+
+/// Query expected to find the fixture's retrieval section.
+const FIXTURE_QUERY: &str = "kestrel lantern";
+
+/// Text the cited lines must contain.
+const FIXTURE_MARKER: &str = "kestrel-lantern-42";
+
+/// Heading of the section the query must land in.
+const FIXTURE_HEADING: &str = "Retrieval";
+
+/// Embedded llms.txt fixture.
+const FIXTURE: &str = "\
+# BLZ Install Check
+
+This document is indexed by `blz verify-install`.
+
+## Searching
+
+Sources are parsed into heading sections and indexed for full-text search.
+
+## Retrieval
+
+Search hits cite exact lines. The verification marker is kestrel-lantern-42.
+";
+
+/// Pipeline steps in the order they run; cleanup always runs last.
+const PIPELINE_STEPS: &[&str] = &["create", "index", "search", "get"];
+
+/// Arguments for `blz verify-install`.
+#[derive(Args, Clone, Debug)]
+pub struct VerifyInstallArgs {
+    /// Keep the temporary directory for inspection instead of deleting it
+    #[arg(long)]
+    pub keep: bool,
+    /// Output format
+    #[command(flatten)]
+    pub format: FormatArg,
+}
+
+/// Outcome of one step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum StepStatus {
+    Pass,
+    Fail,
+    /// Not run because an earlier step failed.
+    Skip,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct StepReport {
+    name: &'static str,
+    status: StepStatus,
+    duration_ms: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct VerifyReport {
+    version: &'static str,
+    passed: bool,
+    total_ms: f64,
+    steps: Vec<StepReport>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    kept_dir: Option<PathBuf>,
+}
+
+/// Execute `blz verify-install`.
+///
+/// # Errors
+///
+/// Returns an error when any step fails, after the report has been printed.
+pub fn execute(args: &VerifyInstallArgs, quiet: bool) -> Result<()> {
+    let format = args.format.resolve(quiet);
+    let started = Instant::now();
+    let root = scratch_dir();
+
+    let mut steps = Vec::new();
+    // Failures are recorded in `steps`; the remaining steps are marked skipped.
+    let _ = run_pipeline(&root, &mut steps);
+    for name in PIPELINE_STEPS.iter().skip(steps.len()) {
+        steps.push(StepReport {
+            name,
+            status: StepStatus::Skip,
+            duration_ms: 0.0,
+            error: None,
+        });
+    }
+
+    let kept_dir = if args.keep {
+        Some(root)
+    } else {
+        let _ = timed(&mut steps, "cleanup", || {
+            std::fs::remove_dir_all(&root)
+                .with_context(|| format!("failed to remove {}", root.display()))
+        });
+        None
+    };
+
+    let report = VerifyReport {
+        version: env!("CARGO_PKG_VERSION"),
+        passed: steps.iter().all(|step| step.status == StepStatus::Pass),
+        total_ms: as_ms(started.elapsed()),
+        steps,
+        kept_dir,
+    };
+
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+        OutputFormat::Jsonl | OutputFormat::Raw => println!("{}", serde_json::to_string(&report)?),
+        OutputFormat::Text | OutputFormat::Markdown => print_text_report(&report),
+    }
+
+    if let Some(failed) = report
+        .steps
+        .iter()
+        .find(|step| step.status == StepStatus::Fail)
+    {
+        bail!("verify-install failed at step '{}'", failed.name);
+    }
+    Ok(())
+}
+
+/// Unique directory under the system temp dir for this run.
+fn scratch_dir() -> PathBuf {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_nanos());
+    std::env::temp_dir().join(format!("blz-verify-{}-{nanos}", std::process::id()))
+}
+
+fn run_pipeline(root: &Path, steps: &mut Vec<StepReport>) -> Result<()> {
+    let storage = timed(steps, "create", || create_fixture(root))?;
+    let index = timed(steps, "index", || index_fixture(&storage))?;
+    let hit = timed(steps, "search", || search_fixture(&index))?;
+    timed(steps, "get", || get_fixture_lines(&storage, &hit))
+}
+
+/// Run `step`, recording its outcome and duration.
+fn timed<T>(
+    steps: &mut Vec<StepReport>,
+    name: &'static str,
+    step: impl FnOnce() -> Result<T>,
+) -> Result<T> {
+    let start = Instant::now();
+    let result = step();
+    steps.push(StepReport {
+        name,
+        status: if result.is_ok() {
+            StepStatus::Pass
+        } else {
+            StepStatus::Fail
+        },
+        duration_ms: as_ms(start.elapsed()),
+        error: result.as_ref().err().map(|err| format!("{err:#}")),
+    });
+    result
+}
+
+/// Store the fixture's llms.txt and llms.json in a fresh cache.
+fn create_fixture(root: &Path) -> Result<Storage> {
+    let storage = Storage::with_paths(root.join("data"), root.join("config"))?;
+    let parse = MarkdownParser::new()?.parse(FIXTURE)?;
+    storage.save_llms_txt(FIXTURE_ALIAS, FIXTURE)?;
+
+    let sha256 = B64.encode(Sha256::digest(FIXTURE.as_bytes()));
+    let llms_json = build_llms_json(
+        FIXTURE_ALIAS,
+        FIXTURE_URL,
+        "llms.txt",
+        sha256,
+        None,
+        None,
+        &parse,
+    );
+    storage.save_llms_json(FIXTURE_ALIAS, &llms_json)?;
+    Ok(storage)
+}
+
+fn index_fixture(storage: &Storage) -> Result<SearchIndex> {
+    let parse = MarkdownParser::new()?.parse(&storage.load_llms_txt(FIXTURE_ALIAS)?)?;
+    let index = SearchIndex::create(&storage.index_dir(FIXTURE_ALIAS)?)?;
+    index.index_blocks(FIXTURE_ALIAS, &parse.heading_blocks)?;
+    Ok(index)
+}
+
+fn search_fixture(index: &SearchIndex) -> Result<SearchHit> {
+    let hits = index.search(FIXTURE_QUERY, Some(FIXTURE_ALIAS), 10)?;
+    let Some(hit) = hits.into_iter().next() else {
+        bail!("no results for '{FIXTURE_QUERY}'");
+    };
+    if hit.heading_path.last().map(String::as_str) != Some(FIXTURE_HEADING) {
+        bail!(
+            "top result for '{FIXTURE_QUERY}' is '{}', expected '{FIXTURE_HEADING}'",
+            hit.heading_path.join(" > ")
+        );
+    }
+    Ok(hit)
+}
+
+fn get_fixture_lines(storage: &Storage, hit: &SearchHit) -> Result<()> {
+    let (start, end) = parse_line_span(&hit.lines)
+        .with_context(|| format!("invalid line range '{}'", hit.lines))?;
+    let content = storage.load_llms_txt(FIXTURE_ALIAS)?;
+    let lines: Vec<&str> = content
+        .lines()
+        .skip(start.saturating_sub(1))
+        .take(end.saturating_sub(start) + 1)
+        .collect();
+    if !lines.iter().any(|line| line.contains(FIXTURE_MARKER)) {
+        bail!(
+            "lines {} do not contain the expected text '{FIXTURE_MARKER}'",
+            hit.lines
+        );
+    }
+    Ok(())
+}
+
+fn print_text_report(report: &VerifyReport) {
+    println!("blz {} install check", report.version);
+    for step in &report.steps {
+        let status = match step.status {
+            StepStatus::Pass => "✓ pass".green(),
+            StepStatus::Fail => "✗ FAIL".red(),
+            StepStatus::Skip => "- skip".bright_black(),
+        };
+        println!("{status} {:<8} {:>8.2}ms", step.name, step.duration_ms);
+        if let Some(error) = &step.error {
+            println!("         {error}");
+        }
+    }
+    if let Some(dir) = &report.kept_dir {
+        println!("Kept {}", dir.display());
+    }
+    let summary = if report.passed {
+        "All checks passed".green()
+    } else {
+        "Install check failed".red()
+    };
+    println!("{summary} in {:.2}ms", report.total_ms);
+}
+
+fn as_ms(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1_000.0
+}
@@ -0,0 +1,303 @@
+//! Watch command: keep sources fresh without cron.
+//!
+//! `blz watch` re-checks sources on an interval and re-indexes the ones whose
+//! upstream content changed. Checks go through the normal refresh path, so
+//! URL sources are fetched with `If-None-Match` / `If-Modified-Since` and an
+//! unchanged source costs a single round trip. Every change is reported as a
+//! JSONL event on stdout:
+//!
+//! ```text
+//! {"event":"started","sources":["react","bun"],"intervalSecs":900,"at":"2025-01-01T00:00:00Z"}
+//! {"event":"changed","alias":"react","headings":412,"lines":9120,"upgraded":false,"at":"..."}
+//! {"event":"error","alias":"bun","error":"Network error: ...","at":"..."}
+//! {"event":"cycle","checked":2,"changed":1,"errors":1,"at":"..."}
+//! ```
+//!
+//! The watcher stops on SIGINT/SIGTERM (after finishing the source being
+//! checked and emitting a final `cycle` event for the partial pass, then
+//! exiting with status 130/143) or when the reader of stdout goes away.
+
+use std::io::{self, Write};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use blz_core::refresh::{
+    DefaultRefreshIndexer, RefreshContext, RefreshOutcome, RefreshStorage,
+    refresh_source_with_metadata, resolve_refresh_url,
+};
+use blz_core::throttle::Bandwidth;
+use blz_core::{Fetcher, PerformanceMetrics, Storage};
+use chrono::{SecondsFormat, Utc};
+use clap::Args;
+use serde::Serialize;
+use tokio::sync::Notify;
+
+use super::sync::is_generated_source;
+use crate::utils::{resolver, shutdown};
+
+/// Arguments for `blz watch`.
+#[derive(Args, Clone, Debug)]
+pub struct WatchArgs {
+    /// Sources to watch (defaults to all, re-listed every cycle)
+    #[arg(value_name = "ALIAS")]
+    pub aliases: Vec<String>,
+
+    /// Time between checks (e.g. 30s, 5m, 1h)
+    #[arg(
+        short = 'i',
+        long,
+        value_name = "INTERVAL",
+        default_value = "15m",
+        value_parser = parse_interval
+    )]
+    pub interval: Duration,
+
+    /// Run a single check cycle and exit
+    #[arg(long)]
+    pub once: bool,
+
+    /// Limit download bandwidth (e.g. 500KB/s, 2MB/s)
+    ///
+    /// Defaults to `defaults.throttle` in config or `BLZ_THROTTLE`.
+    #[arg(long, value_name = "RATE")]
+    pub throttle: Option<Bandwidth>,
+}
+
+/// One line of `blz watch` output.
+#[derive(Debug, Serialize)]
+#[serde(tag = "event", rename_all = "camelCase")]
+enum WatchEvent<'a> {
+    Started {
+        sources: &'a [String],
+        #[serde(rename = "intervalSecs")]
+        interval_secs: u64,
+        at: String,
+    },
+    Changed {
+        alias: &'a str,
+        headings: usize,
+        lines: usize,
+        upgraded: bool,
+        at: String,
+    },
+    Error {
+        alias: &'a str,
+        error: String,
+        at: String,
+    },
+    Cycle {
+        checked: usize,
+        changed: usize,
+        errors: usize,
+        at: String,
+    },
+}
+
+/// Parse an interval such as `90`, `30s`, `5m`, or `1h`.
+fn parse_interval(value: &str) -> Result<Duration, String> {
+    let value = value.trim();
+    let split = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    let (digits, unit) = value.split_at(split);
+    let invalid = || format!("invalid interval '{value}' (expected e.g. 30s, 5m, 1h)");
+    let amount: u64 = digits.parse().map_err(|_| invalid())?;
+    let multiplier = match unit {
+        "" | "s" => 1,
+        "m" => 60,
+        "h" => 3600,
+        _ => return Err(invalid()),
+    };
+    let secs = amount.checked_mul(multiplier).ok_or_else(invalid)?;
+    if secs == 0 {
+        return Err("interval must be at least 1s".to_string());
+    }
+    Ok(Duration::from_secs(secs))
+}
+
+fn now() -> String {
+    Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+/// Write one event line.
+///
+/// Returns `Ok(false)` once the reader has closed stdout.
+fn emit(event: &WatchEvent<'_>) -> Result<bool> {
+    let line = serde_json::to_string(event)?;
+    let mut stdout = io::stdout().lock();
+    match writeln!(stdout, "{line}").and_then(|()| stdout.flush()) {
+        Ok(()) => Ok(true),
+        Err(e) if e.kind() == io::ErrorKind::BrokenPipe => Ok(false),
+        Err(e) => Err(e).context("failed to write watch event"),
+    }
+}
+
+/// Execute `blz watch`.
+///
+/// # Errors
+///
+/// Returns an error if a named source does not exist, the fetcher cannot be
+/// built, or stdout fails for a reason other than a closed pipe. Failures
+/// checking individual sources are reported as events instead.
+pub async fn execute(args: &WatchArgs, metrics: PerformanceMetrics) -> Result<()> {
+    let storage = Storage::new()?;
+    let mut named = Vec::with_capacity(args.aliases.len());
+    for alias in &args.aliases {
+        let canonical = resolver::resolve_source(&storage, alias)?
+            .filter(|canonical| storage.exists(canonical))
+            .ok_or_else(|| anyhow::anyhow!("Source '{alias}' not found"))?;
+        named.push(canonical);
+    }
+    let fetcher = crate::utils::throttle::fetcher(args.throttle)?;
+
+    let stop = Arc::new(AtomicBool::new(false));
+    let wake = Arc::new(Notify::new());
+    if !args.once {
+        let (stop, wake) = (Arc::clone(&stop), Arc::clone(&wake));
+        shutdown::install();
+        tokio::spawn(async move {
+            shutdown::cancelled().await;
+            stop.store(true, Ordering::Relaxed);
+            wake.notify_one();
+        });
+    }
+
+    let sources = if named.is_empty() {
+        storage.list_sources()
+    } else {
+        named.clone()
+    };
+    if !emit(&WatchEvent::Started {
+        sources: &sources,
+        interval_secs: args.interval.as_secs(),
+        at: now(),
+    })? {
+        return Ok(());
+    }
+
+    loop {
+        // Re-list every cycle so sources added while watching are picked up
+        let sources = if named.is_empty() {
+            storage.list_sources()
+        } else {
+            named.clone()
+        };
+        if !run_cycle(&storage, &fetcher, &sources, &metrics, &stop).await? {
+            return Ok(());
+        }
+        if args.once || stop.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+        // `tokio::select!` expands to a `panic!` for its all-branches-disabled arm
+        #[allow(clippy::disallowed_macros)]
+        let () = tokio::select! {
+            () = tokio::time::sleep(args.interval) => {},
+            () = wake.notified() => return Ok(()),
+        };
+    }
+}
+
+/// Check every source once and report changes.
+///
+/// Returns `Ok(false)` when stdout was closed.
+async fn run_cycle(
+    storage: &Storage,
+    fetcher: &Fetcher,
+    sources: &[String],
+    metrics: &PerformanceMetrics,
+    stop: &AtomicBool,
+) -> Result<bool> {
+    let (mut checked, mut changed, mut errors) = (0, 0, 0);
+    for alias in sources {
+        if stop.load(Ordering::Relaxed) {
+            break;
+        }
+        // Generated sources need a scrape to update; `blz sync` reports those
+        if is_generated_source(storage, alias) {
+            continue;
+        }
+        checked += 1;
+        let event = match check_source(storage, fetcher, alias, metrics.clone()).await {
+            Ok(None) => continue,
+            Ok(Some((headings, lines, upgraded))) => {
+                changed += 1;
+                WatchEvent::Changed {
+                    alias,
+                    headings,
+                    lines,
+                    upgraded,
+                    at: now(),
+                }
+            },
+            Err(e) => {
+                errors += 1;
+                WatchEvent::Error {
+                    alias,
+                    error: format!("{e:#}"),
+                    at: now(),
+                }
+            },
+        };
+        if !emit(&event)? {
+            return Ok(false);
+        }
+    }
+    emit(&WatchEvent::Cycle {
+        checked,
+        changed,
+        errors,
+        at: now(),
+    })
+}
+
+/// Refresh one source.
+///
+/// Returns the new heading and line counts and whether the source moved to
+/// `llms-full.txt`, or `None` when upstream is unchanged.
+async fn check_source(
+    storage: &Storage,
+    fetcher: &Fetcher,
+    alias: &str,
+    metrics: PerformanceMetrics,
+) -> Result<Option<(usize, usize, bool)>> {
+    let metadata = storage.load_metadata(alias)?;
+    let aliases = storage.load_llms_aliases(alias)?;
+    let filter_preference = metadata.filter_non_english.unwrap_or(true);
+    let resolution = resolve_refresh_url(fetcher, &metadata).await?;
+    let upgraded = resolution.upgraded;
+    let ctx = RefreshContext::new(metadata, aliases, resolution);
+    let outcome = refresh_source_with_metadata(
+        storage,
+        fetcher,
+        alias,
+        &ctx,
+        metrics,
+        &DefaultRefreshIndexer,
+        filter_preference,
+    )
+    .await?;
+    Ok(match outcome {
+        RefreshOutcome::Refreshed {
+            headings, lines, ..
+        } => Some((headings, lines, upgraded)),
+        RefreshOutcome::Unchanged { .. } => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_intervals_with_units() {
+        assert_eq!(parse_interval("90"), Ok(Duration::from_secs(90)));
+        assert_eq!(parse_interval("30s"), Ok(Duration::from_secs(30)));
+        assert_eq!(parse_interval("5m"), Ok(Duration::from_secs(300)));
+        assert_eq!(parse_interval("1h"), Ok(Duration::from_secs(3600)));
+        assert!(parse_interval("0s").is_err());
+        assert!(parse_interval("5d").is_err());
+        assert!(parse_interval("m").is_err());
+    }
+}
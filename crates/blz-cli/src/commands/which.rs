@@ -0,0 +1,99 @@
+//! Source resolution report (`blz which`)
+//!
+//! Prints the canonical source a name resolves to. With `--verbose`, shows
+//! each resolution step: the canonical-name check, then the metadata and
+//! `llms.json` aliases of every source that was checked.
+
+use anyhow::Result;
+use blz_core::Storage;
+
+use crate::i18n::{self, Message};
+use crate::output::OutputFormat;
+use crate::utils::resolver::{ResolutionTrace, trace_source};
+
+/// Execute `blz which`.
+///
+/// JSON output is the full resolution trace regardless of `verbose`.
+///
+/// # Errors
+///
+/// Returns an error if the name matches no source or is ambiguous across
+/// several sources, or if serialization fails.
+pub fn execute(requested: &str, verbose: bool, format: OutputFormat) -> Result<()> {
+    let storage = Storage::new()?;
+    let trace = trace_source(&storage, requested);
+
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&trace)?),
+        OutputFormat::Jsonl => println!("{}", serde_json::to_string(&trace)?),
+        OutputFormat::Text | OutputFormat::Markdown | OutputFormat::Raw => {
+            if verbose {
+                print_trace(&trace);
+            }
+        },
+    }
+
+    let Some(canonical) = trace.resolved()? else {
+        anyhow::bail!(
+            "{} {}",
+            i18n::text(Message::SourceNotFound, &[("alias", requested)]),
+            i18n::text(Message::HintListSources, &[])
+        );
+    };
+    if !matches!(format, OutputFormat::Json | OutputFormat::Jsonl) && !verbose {
+        println!("{canonical}");
+    }
+    Ok(())
+}
+
+fn print_trace(trace: &ResolutionTrace) {
+    let requested = &trace.requested;
+    println!("Resolving '{requested}'");
+    if trace.checked.is_empty() && !trace.candidates.is_empty() {
+        println!("  1. canonical name: matched");
+    } else {
+        println!(
+            "  1. canonical name: no match among {} sources",
+            trace.known_sources
+        );
+        println!("  2. aliases:");
+        let width = trace
+            .checked
+            .iter()
+            .map(|checked| checked.source.len())
+            .max()
+            .unwrap_or(0);
+        for checked in &trace.checked {
+            let matched = trace
+                .candidates
+                .iter()
+                .find(|candidate| candidate.source == checked.source)
+                .map_or(String::new(), |candidate| {
+                    format!("  <- {}", candidate.matched_via)
+                });
+            println!(
+                "       {:<width$}  metadata: {}  llms.json: {}{matched}",
+                checked.source,
+                alias_list(&checked.metadata_aliases),
+                alias_list(&checked.llms_aliases),
+            );
+        }
+    }
+
+    match trace.candidates.as_slice() {
+        [] => println!("Result: no match"),
+        [only] => println!("Result: {} ({})", only.source, only.matched_via),
+        many => {
+            let names: Vec<&str> = many.iter().map(|c| c.source.as_str()).collect();
+            println!("Result: ambiguous ({})", names.join(", "));
+        },
+    }
+}
+
+fn alias_list(aliases: &[String]) -> String {
+    if aliases.is_empty() {
+        "-".to_string()
+    } else {
+        aliases.join(", ")
+    }
+}
@@ -21,6 +21,7 @@ use crate::output::OutputFormat;
 ///     .with_no_summary(false);
 /// ```
 #[derive(Debug, Clone)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct DisplayConfig {
     /// Output format (text, json, jsonl).
     pub format: OutputFormat,
@@ -31,6 +32,9 @@ pub struct DisplayConfig {
     /// Hide the summary/footer line.
     pub no_summary: bool,
 
+    /// Make output reproducible for snapshot tests.
+    pub deterministic: bool,
+
     /// Show detailed timing breakdown.
     pub timing: bool,
 
@@ -44,6 +48,7 @@ impl Default for DisplayConfig {
             format: OutputFormat::Text,
             show: Vec::new(),
             no_summary: false,
+            deterministic: false,
             timing: false,
             quiet: false,
         }
@@ -58,6 +63,7 @@ impl DisplayConfig {
             format,
             show: Vec::new(),
             no_summary: false,
+            deterministic: false,
             timing: false,
             quiet: false,
         }
@@ -77,6 +83,13 @@ impl DisplayConfig {
         self
     }
 
+    /// Set whether output must be reproducible.
+    #[must_use]
+    pub const fn with_deterministic(mut self, deterministic: bool) -> Self {
+        self.deterministic = deterministic;
+        self
+    }
+
     /// Set whether to show timing.
     #[must_use]
     pub const fn with_timing(mut self, timing: bool) -> Self {
@@ -108,6 +121,7 @@ mod tests {
         assert_eq!(config.format, OutputFormat::Text);
         assert!(config.show.is_empty());
         assert!(!config.no_summary);
+        assert!(!config.deterministic);
         assert!(!config.timing);
         assert!(!config.quiet);
     }
@@ -123,12 +137,14 @@ mod tests {
         let config = DisplayConfig::new(OutputFormat::Json)
             .with_show(vec![ShowComponent::Url, ShowComponent::Lines])
             .with_no_summary(true)
+            .with_deterministic(true)
             .with_timing(true)
             .with_quiet(true);
 
         assert_eq!(config.format, OutputFormat::Json);
         assert_eq!(config.show.len(), 2);
         assert!(config.no_summary);
+        assert!(config.deterministic);
         assert!(config.timing);
         assert!(config.quiet);
     }
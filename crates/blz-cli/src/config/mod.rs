@@ -80,5 +80,5 @@ pub use query::QueryExecutionConfig;
 pub use resolved::ExecutionConfig;
 pub use search::SearchConfig;
 pub use snippet::SnippetConfig;
-pub use sync::SyncConfig;
+pub use sync::{DEFAULT_SYNC_JOBS, SyncConfig};
 pub use toc::{TocConfig, TocNavigation};
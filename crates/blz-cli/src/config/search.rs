@@ -3,6 +3,8 @@
 //! This module provides [`SearchConfig`], which bundles search-specific
 //! parameters to reduce argument counts in execute functions.
 
+use blz_core::embeddings::Ranking;
+
 use crate::utils::heading_filter::HeadingLevelFilter;
 
 /// Search configuration for query and find commands.
@@ -20,6 +22,7 @@ use crate::utils::heading_filter::HeadingLevelFilter;
 ///     .with_heading_filter("<=2".parse().ok());
 /// ```
 #[derive(Debug, Clone, Default)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct SearchConfig {
     /// Maximum number of results to return per page.
     pub limit: usize,
@@ -36,11 +39,32 @@ pub struct SearchConfig {
     /// Restrict matches to heading text only.
     pub headings_only: bool,
 
+    /// Restrict matches to fenced code blocks.
+    pub code_only: bool,
+
+    /// Only match sections with code in this language (implies `code_only`).
+    pub code_language: Option<String>,
+
     /// Jump to the last page of results.
     pub last: bool,
 
     /// Don't save this search to history.
     pub no_history: bool,
+
+    /// Remote `blz serve` endpoint to run the search on instead of the local cache.
+    pub remote: Option<String>,
+
+    /// Restrict unscoped searches to the K sources whose topics best match the query.
+    pub auto_sources: Option<usize>,
+
+    /// Search every installed source even inside a project workspace.
+    pub all_sources: bool,
+
+    /// Levenshtein distance for typo-tolerant term matching (`None` uses the config default).
+    pub fuzzy: Option<u8>,
+
+    /// How hits are scored: keywords, embeddings, or a blend of both.
+    pub ranking: Ranking,
 }
 
 impl SearchConfig {
@@ -53,8 +77,15 @@ impl SearchConfig {
             top_percentile: None,
             heading_filter: None,
             headings_only: false,
+            code_only: false,
+            code_language: None,
             last: false,
             no_history: false,
+            remote: None,
+            auto_sources: None,
+            all_sources: false,
+            fuzzy: None,
+            ranking: Ranking::Lexical,
         }
     }
 
@@ -93,6 +124,14 @@ impl SearchConfig {
         self
     }
 
+    /// Set whether to match code blocks only, optionally in one language.
+    #[must_use]
+    pub fn with_code(mut self, code_only: bool, language: Option<String>) -> Self {
+        self.code_only = code_only || language.is_some();
+        self.code_language = language;
+        self
+    }
+
     /// Set whether to jump to the last page.
     #[must_use]
     pub const fn with_last(mut self, last: bool) -> Self {
@@ -106,6 +145,47 @@ impl SearchConfig {
         self.no_history = no_history;
         self
     }
+
+    /// Set the remote endpoint to search.
+    #[must_use]
+    pub fn with_remote(mut self, remote: Option<String>) -> Self {
+        self.remote = remote;
+        self
+    }
+
+    /// Set how many sources the router may select (`None` or 0 disables routing).
+    #[must_use]
+    pub const fn with_auto_sources(mut self, auto_sources: Option<usize>) -> Self {
+        self.auto_sources = auto_sources;
+        self
+    }
+
+    /// Set whether to ignore the project workspace's source list.
+    #[must_use]
+    pub const fn with_all_sources(mut self, all_sources: bool) -> Self {
+        self.all_sources = all_sources;
+        self
+    }
+
+    /// Set the fuzzy matching distance (`Some(0)` forces exact matching).
+    #[must_use]
+    pub const fn with_fuzzy(mut self, fuzzy: Option<u8>) -> Self {
+        self.fuzzy = fuzzy;
+        self
+    }
+
+    /// Set the ranking from the `--semantic` and `--hybrid` flags.
+    #[must_use]
+    pub const fn with_ranking(mut self, semantic: bool, hybrid: bool) -> Self {
+        self.ranking = if semantic {
+            Ranking::Semantic
+        } else if hybrid {
+            Ranking::Hybrid
+        } else {
+            Ranking::Lexical
+        };
+        self
+    }
 }
 
 #[cfg(test)]
@@ -120,8 +200,14 @@ mod tests {
         assert!(config.top_percentile.is_none());
         assert!(config.heading_filter.is_none());
         assert!(!config.headings_only);
+        assert!(!config.code_only);
+        assert!(config.code_language.is_none());
         assert!(!config.last);
         assert!(!config.no_history);
+        assert!(config.remote.is_none());
+        assert!(config.auto_sources.is_none());
+        assert!(config.fuzzy.is_none());
+        assert_eq!(config.ranking, Ranking::Lexical);
     }
 
     #[test]
@@ -139,15 +225,24 @@ mod tests {
             .with_page(3)
             .with_top_percentile(Some(90))
             .with_headings_only(true)
+            .with_code(false, Some("ts".to_string()))
             .with_last(true)
-            .with_no_history(true);
+            .with_no_history(true)
+            .with_auto_sources(Some(5))
+            .with_fuzzy(Some(2))
+            .with_ranking(false, true);
 
         assert_eq!(config.limit, 20);
         assert_eq!(config.page, 3);
         assert_eq!(config.top_percentile, Some(90));
         assert!(config.headings_only);
+        assert!(config.code_only);
+        assert_eq!(config.code_language.as_deref(), Some("ts"));
         assert!(config.last);
         assert!(config.no_history);
+        assert_eq!(config.auto_sources, Some(5));
+        assert_eq!(config.fuzzy, Some(2));
+        assert_eq!(config.ranking, Ranking::Hybrid);
     }
 
     #[test]
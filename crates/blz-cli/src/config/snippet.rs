@@ -26,6 +26,12 @@ pub struct SnippetConfig {
 
     /// Number of decimal places for score display (0-4).
     pub score_precision: Option<u8>,
+
+    /// Widen JSON/JSONL snippets that cut through a fenced code block to the full fence.
+    pub fence_expand: bool,
+
+    /// Estimated-token budget for each page of hits (`None` for no budget).
+    pub max_tokens: Option<usize>,
 }
 
 impl Default for SnippetConfig {
@@ -34,6 +40,8 @@ impl Default for SnippetConfig {
             lines: 3,
             max_chars: 200,
             score_precision: None,
+            fence_expand: true,
+            max_tokens: None,
         }
     }
 }
@@ -66,6 +74,20 @@ impl SnippetConfig {
         self
     }
 
+    /// Set whether machine-format snippets expand to whole code fences.
+    #[must_use]
+    pub const fn with_fence_expand(mut self, fence_expand: bool) -> Self {
+        self.fence_expand = fence_expand;
+        self
+    }
+
+    /// Set the per-page token budget.
+    #[must_use]
+    pub const fn with_max_tokens(mut self, max_tokens: Option<usize>) -> Self {
+        self.max_tokens = max_tokens;
+        self
+    }
+
     /// Get the effective score precision (default: 1).
     #[must_use]
     pub const fn effective_score_precision(&self) -> u8 {
@@ -86,6 +108,8 @@ mod tests {
         assert_eq!(config.lines, 3);
         assert_eq!(config.max_chars, 200);
         assert!(config.score_precision.is_none());
+        assert!(config.fence_expand);
+        assert!(config.max_tokens.is_none());
     }
 
     #[test]
@@ -93,11 +117,15 @@ mod tests {
         let config = SnippetConfig::new()
             .with_lines(5)
             .with_max_chars(500)
-            .with_score_precision(Some(2));
+            .with_score_precision(Some(2))
+            .with_fence_expand(false)
+            .with_max_tokens(Some(2000));
 
         assert_eq!(config.lines, 5);
         assert_eq!(config.max_chars, 500);
         assert_eq!(config.score_precision, Some(2));
+        assert!(!config.fence_expand);
+        assert_eq!(config.max_tokens, Some(2000));
     }
 
     #[test]
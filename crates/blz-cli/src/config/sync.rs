@@ -3,6 +3,10 @@
 //! This module provides [`SyncConfig`], which bundles sync/refresh parameters
 //! to reduce argument counts in execute functions.
 
+use std::path::PathBuf;
+
+use blz_core::throttle::Bandwidth;
+
 /// Sync configuration.
 ///
 /// Controls how documentation sources are synced/refreshed.
@@ -16,7 +20,7 @@
 ///     .with_reindex(true)
 ///     .with_quiet(true);
 /// ```
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone)]
 #[allow(clippy::struct_excessive_bools)]
 pub struct SyncConfig {
     /// Skip confirmation prompts.
@@ -33,6 +37,30 @@ pub struct SyncConfig {
 
     /// Suppress informational output.
     pub quiet: bool,
+
+    /// Download bandwidth limit (falls back to the config default).
+    pub throttle: Option<Bandwidth>,
+
+    /// Maximum number of sources `sync --all` refreshes at once.
+    pub jobs: usize,
+
+    /// Print a JSON changelog of what the sync changed instead of a summary.
+    pub changes_json: bool,
+
+    /// Verify sources against the lockfile instead of updating it.
+    pub frozen: bool,
+
+    /// Lockfile to use instead of the nearest `blz.lock`.
+    pub lockfile: Option<PathBuf>,
+}
+
+/// Default number of sources refreshed concurrently by `sync --all`.
+pub const DEFAULT_SYNC_JOBS: usize = 4;
+
+impl Default for SyncConfig {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl SyncConfig {
@@ -45,6 +73,11 @@ impl SyncConfig {
             filter: None,
             no_filter: false,
             quiet: false,
+            throttle: None,
+            jobs: DEFAULT_SYNC_JOBS,
+            changes_json: false,
+            frozen: false,
+            lockfile: None,
         }
     }
 
@@ -82,6 +115,41 @@ impl SyncConfig {
         self.quiet = quiet;
         self
     }
+
+    /// Set the download bandwidth limit.
+    #[must_use]
+    pub const fn with_throttle(mut self, throttle: Option<Bandwidth>) -> Self {
+        self.throttle = throttle;
+        self
+    }
+
+    /// Set how many sources `sync --all` refreshes at once (minimum 1).
+    #[must_use]
+    pub const fn with_jobs(mut self, jobs: usize) -> Self {
+        self.jobs = if jobs == 0 { 1 } else { jobs };
+        self
+    }
+
+    /// Set whether to print a JSON changelog.
+    #[must_use]
+    pub const fn with_changes_json(mut self, changes_json: bool) -> Self {
+        self.changes_json = changes_json;
+        self
+    }
+
+    /// Set whether to verify against the lockfile without updating it.
+    #[must_use]
+    pub const fn with_frozen(mut self, frozen: bool) -> Self {
+        self.frozen = frozen;
+        self
+    }
+
+    /// Set an explicit lockfile path.
+    #[must_use]
+    pub fn with_lockfile(mut self, lockfile: Option<PathBuf>) -> Self {
+        self.lockfile = lockfile;
+        self
+    }
 }
 
 #[cfg(test)]
@@ -96,6 +164,11 @@ mod tests {
         assert!(config.filter.is_none());
         assert!(!config.no_filter);
         assert!(!config.quiet);
+        assert!(config.throttle.is_none());
+        assert_eq!(config.jobs, DEFAULT_SYNC_JOBS);
+        assert!(!config.changes_json);
+        assert!(!config.frozen);
+        assert!(config.lockfile.is_none());
     }
 
     #[test]
@@ -123,6 +196,20 @@ mod tests {
         assert!(config.quiet);
     }
 
+    #[test]
+    fn test_throttle() {
+        let rate = "500KB/s".parse().ok();
+        let config = SyncConfig::new().with_throttle(rate);
+
+        assert_eq!(config.throttle, rate);
+    }
+
+    #[test]
+    fn test_jobs_are_at_least_one() {
+        assert_eq!(SyncConfig::new().with_jobs(8).jobs, 8);
+        assert_eq!(SyncConfig::new().with_jobs(0).jobs, 1);
+    }
+
     #[test]
     fn test_no_filter() {
         let config = SyncConfig::new().with_no_filter(true);
@@ -50,6 +50,9 @@ pub struct TocConfig {
     /// Show anchor slugs in normal output.
     pub show_anchors: bool,
 
+    /// Prefix headings with ordinal paths in text output.
+    pub numbered: bool,
+
     /// Suppress non-essential output.
     pub quiet: bool,
 }
@@ -85,6 +88,7 @@ impl Default for TocConfig {
             tree: false,
             anchors: false,
             show_anchors: false,
+            numbered: false,
             quiet: false,
         }
     }
@@ -104,6 +108,7 @@ impl TocConfig {
             tree: false,
             anchors: false,
             show_anchors: false,
+            numbered: false,
             quiet: false,
         }
     }
@@ -164,6 +169,13 @@ impl TocConfig {
         self
     }
 
+    /// Set whether to show ordinal paths.
+    #[must_use]
+    pub const fn with_numbered(mut self, numbered: bool) -> Self {
+        self.numbered = numbered;
+        self
+    }
+
     /// Set quiet mode.
     #[must_use]
     pub const fn with_quiet(mut self, quiet: bool) -> Self {
@@ -235,6 +247,7 @@ mod tests {
         assert!(!config.tree);
         assert!(!config.anchors);
         assert!(!config.show_anchors);
+        assert!(!config.numbered);
         assert!(!config.quiet);
     }
 
@@ -247,6 +260,7 @@ mod tests {
             .with_page(3)
             .with_anchors(true)
             .with_show_anchors(true)
+            .with_numbered(true)
             .with_quiet(true);
 
         assert_eq!(config.format, OutputFormat::Json);
@@ -256,6 +270,7 @@ mod tests {
         assert_eq!(config.page, 3);
         assert!(config.anchors);
         assert!(config.show_anchors);
+        assert!(config.numbered);
         assert!(config.quiet);
     }
 
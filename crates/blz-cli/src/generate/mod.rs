@@ -9,6 +9,7 @@
 //! - [`GenerateOrchestrator`]: Coordinates parallel scraping with adaptive concurrency
 //! - [`UrlWithLastmod`]: URL with optional lastmod for change detection
 //! - [`ScrapeResults`]: Aggregated results from scraping operations
+//! - [`FirecrawlScraper`]: Firecrawl API or CLI backend for the orchestrator
 //!
 //! ## Example
 //!
@@ -22,9 +23,9 @@
 //!     UrlWithLastmod::new("https://example.com/docs/api".to_string()),
 //! ];
 //!
-//! // Create orchestrator (would use real FirecrawlCli)
-//! // let cli = FirecrawlCli::detect().await?;
-//! // let orchestrator = GenerateOrchestrator::new(cli, 5)
+//! // Create orchestrator (would use the configured Firecrawl backend)
+//! // let scraper = FirecrawlScraper::resolve().await?;
+//! // let orchestrator = GenerateOrchestrator::new(scraper, 5)
 //! //     .with_progress(|completed, total| {
 //! //         println!("Progress: {}/{}", completed, total);
 //! //     });
@@ -37,7 +38,10 @@
 
 mod orchestrator;
 
+mod scraper;
+
 pub use orchestrator::{
-    GenerateOrchestrator, ProgressCallback, ScrapeError, ScrapeResult, ScrapeResults, Scraper,
-    UrlWithLastmod,
+    FailedPage, GenerateOrchestrator, PageCacheEntry, ProgressCallback, ScrapeError, ScrapeResult,
+    ScrapeResults, Scraper, UrlWithLastmod,
 };
+pub use scraper::FirecrawlScraper;
@@ -6,14 +6,14 @@
 use std::sync::Arc;
 use std::sync::atomic::{AtomicUsize, Ordering};
 
+use blz_core::discovery::RobotsPolicy;
 use chrono::{DateTime, Utc};
 use futures::stream::{self, StreamExt};
 use serde::{Deserialize, Serialize};
 use tokio::sync::Semaphore;
+use url::Url;
 
-// TODO: Replace these stub types with imports from blz_core when available:
-// use blz_core::firecrawl::{FirecrawlCli, ScrapeOptions, ScrapeResult};
-// use blz_core::page_cache::{PageCacheEntry, FailedPage};
+use crate::utils::shutdown;
 
 /// URL with optional lastmod for change detection.
 ///
@@ -79,6 +79,8 @@ pub struct ScrapeResults {
     pub successful: Vec<PageCacheEntry>,
     /// Pages that failed to scrape.
     pub failed: Vec<FailedPage>,
+    /// URLs not scraped because shutdown was requested.
+    pub skipped: Vec<String>,
 }
 
 impl ScrapeResults {
@@ -147,6 +149,7 @@ pub struct GenerateOrchestrator<S: Scraper> {
     concurrency: usize,
     min_concurrency: usize,
     progress_callback: Option<ProgressCallback>,
+    robots: RobotsPolicy,
 }
 
 /// Trait for scraping URLs (allows mocking in tests).
@@ -198,12 +201,12 @@ impl std::fmt::Display for ScrapeError {
 impl std::error::Error for ScrapeError {}
 
 // ============================================================
-// Stub types for compilation (replace with blz_core imports)
+// Scraped page types (stored inline in `generate.json`)
 // ============================================================
 
 /// Result of a scrape operation.
 ///
-/// TODO: Replace with `blz_core::firecrawl::ScrapeResult` when available.
+/// The subset of `blz_core::firecrawl::ScrapeResult` the orchestrator needs.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ScrapeResult {
@@ -218,7 +221,7 @@ pub struct ScrapeResult {
 
 /// A cached page from web scraping.
 ///
-/// TODO: Replace with `blz_core::page_cache::PageCacheEntry` when available.
+/// Stores the scraped content along with metadata for change detection.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PageCacheEntry {
@@ -237,6 +240,34 @@ pub struct PageCacheEntry {
 }
 
 impl PageCacheEntry {
+    /// Create a new page cache entry.
+    #[must_use]
+    pub fn new(url: String, markdown: String) -> Self {
+        let line_count = markdown.lines().count();
+        Self {
+            url,
+            title: None,
+            fetched_at: Utc::now(),
+            sitemap_lastmod: None,
+            markdown,
+            line_count,
+        }
+    }
+
+    /// Set the title using builder pattern.
+    #[must_use]
+    pub fn with_title(mut self, title: Option<String>) -> Self {
+        self.title = title;
+        self
+    }
+
+    /// Set the lastmod using builder pattern.
+    #[must_use]
+    pub const fn with_lastmod(mut self, lastmod: Option<DateTime<Utc>>) -> Self {
+        self.sitemap_lastmod = lastmod;
+        self
+    }
+
     /// Create from a scrape result.
     #[must_use]
     pub fn from_scrape_result(result: ScrapeResult, lastmod: Option<DateTime<Utc>>) -> Self {
@@ -254,7 +285,7 @@ impl PageCacheEntry {
 
 /// A page that failed to scrape.
 ///
-/// TODO: Replace with `blz_core::page_cache::FailedPage` when available.
+/// Tracked for retry on subsequent syncs.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct FailedPage {
@@ -279,6 +310,12 @@ impl FailedPage {
             last_attempt: Utc::now(),
         }
     }
+
+    /// Record another failed attempt.
+    pub fn increment_attempts(&mut self) {
+        self.attempts += 1;
+        self.last_attempt = Utc::now();
+    }
 }
 
 // ============================================================
@@ -305,6 +342,7 @@ impl<S: Scraper> GenerateOrchestrator<S> {
             concurrency: concurrency.clamp(1, 50),
             min_concurrency: Self::MIN_CONCURRENCY,
             progress_callback: None,
+            robots: RobotsPolicy::ignoring(),
         }
     }
 
@@ -326,10 +364,22 @@ impl<S: Scraper> GenerateOrchestrator<S> {
         self
     }
 
+    /// Check pages against robots.txt and space requests to each host by
+    /// its `Crawl-delay`.
+    ///
+    /// Disallowed pages are reported as failed without being scraped.
+    #[must_use]
+    pub fn with_robots(mut self, robots: RobotsPolicy) -> Self {
+        self.robots = robots;
+        self
+    }
+
     /// Scrape all URLs in parallel.
     ///
     /// Uses a semaphore to limit concurrent operations to the configured
     /// concurrency level. Reports progress through the callback if set.
+    /// Once shutdown is requested (SIGINT/SIGTERM), scrapes in flight finish
+    /// and the remaining URLs are returned in [`ScrapeResults::skipped`].
     ///
     /// # Returns
     ///
@@ -343,8 +393,8 @@ impl<S: Scraper> GenerateOrchestrator<S> {
         let completed = Arc::new(AtomicUsize::new(0));
         let semaphore = Arc::new(Semaphore::new(self.concurrency));
 
-        // Create stream of scrape futures
-        let results: Vec<Result<PageCacheEntry, FailedPage>> = stream::iter(urls)
+        // Create stream of scrape futures; `Err` carries a URL skipped on shutdown
+        let results: Vec<Result<Result<PageCacheEntry, FailedPage>, String>> = stream::iter(urls)
             .map(|url_info| {
                 let semaphore = Arc::clone(&semaphore);
                 let completed = Arc::clone(&completed);
@@ -353,9 +403,23 @@ impl<S: Scraper> GenerateOrchestrator<S> {
                 async move {
                     // Acquire semaphore permit
                     let _permit = semaphore.acquire().await;
+                    if shutdown::requested().is_some() {
+                        return Err(url_info.url.clone());
+                    }
 
-                    // Perform scrape
-                    let result = self.scrape_one(&url_info.url, url_info.lastmod).await;
+                    // Perform scrape once robots.txt allows it
+                    let admitted = match Url::parse(&url_info.url) {
+                        Ok(parsed) => self.robots.admit(&parsed).await,
+                        Err(_) => true,
+                    };
+                    let result = if admitted {
+                        self.scrape_one(&url_info.url, url_info.lastmod).await
+                    } else {
+                        Err(FailedPage::new(
+                            url_info.url.clone(),
+                            "disallowed by robots.txt".to_string(),
+                        ))
+                    };
 
                     // Update progress
                     let done = completed.fetch_add(1, Ordering::SeqCst) + 1;
@@ -363,7 +427,7 @@ impl<S: Scraper> GenerateOrchestrator<S> {
                         cb(done, total);
                     }
 
-                    result
+                    Ok(result)
                 }
             })
             .buffer_unordered(self.concurrency)
@@ -374,8 +438,9 @@ impl<S: Scraper> GenerateOrchestrator<S> {
         let mut scrape_results = ScrapeResults::default();
         for result in results {
             match result {
-                Ok(entry) => scrape_results.successful.push(entry),
-                Err(failed) => scrape_results.failed.push(failed),
+                Ok(Ok(entry)) => scrape_results.successful.push(entry),
+                Ok(Err(failed)) => scrape_results.failed.push(failed),
+                Err(url) => scrape_results.skipped.push(url),
             }
         }
 
@@ -0,0 +1,80 @@
+//! Firecrawl backends for the scrape orchestrator.
+//!
+//! The HTTP API is preferred when a key is configured (`defaults.firecrawl_api_key`
+//! or `FIRECRAWL_API_KEY`); otherwise an installed, logged-in Firecrawl CLI is used.
+
+use anyhow::{Context, Result};
+use blz_core::Config;
+use blz_core::firecrawl::{FirecrawlApi, FirecrawlCli, ScrapeOptions};
+
+use super::{ScrapeError, ScrapeResult, Scraper};
+
+/// Hint appended when no Firecrawl backend is usable.
+const SETUP_HINT: &str = "Set FIRECRAWL_API_KEY (or defaults.firecrawl_api_key in config.toml), \
+                          or install the Firecrawl CLI and run 'firecrawl login'";
+
+/// Scraper backed by the Firecrawl API or CLI.
+#[derive(Debug, Clone)]
+pub enum FirecrawlScraper {
+    /// Scrape through the HTTP API with an API key.
+    Api(FirecrawlApi),
+    /// Scrape by shelling out to the `firecrawl` CLI.
+    Cli(FirecrawlCli),
+}
+
+impl FirecrawlScraper {
+    /// Pick the configured backend: the API when a key is set, else the CLI.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no key is configured and the CLI is missing, too
+    /// old, or not logged in.
+    pub async fn resolve() -> Result<Self> {
+        let config = Config::load().unwrap_or_default();
+        if let Some(api) = FirecrawlApi::from_config(&config)? {
+            return Ok(Self::Api(api));
+        }
+
+        let cli = FirecrawlCli::detect()
+            .await
+            .with_context(|| SETUP_HINT.to_string())?;
+        if !cli.is_authenticated().await.unwrap_or(false) {
+            anyhow::bail!("Firecrawl CLI is not logged in. {SETUP_HINT}");
+        }
+        Ok(Self::Cli(cli))
+    }
+
+    /// Short description of the backend for progress output.
+    #[must_use]
+    pub const fn describe(&self) -> &'static str {
+        match self {
+            Self::Api(_) => "Firecrawl API",
+            Self::Cli(_) => "Firecrawl CLI",
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Scraper for FirecrawlScraper {
+    async fn scrape(&self, url: &str) -> Result<ScrapeResult, ScrapeError> {
+        let options = ScrapeOptions::new().with_main_content_only(true);
+        let result = match self {
+            Self::Api(api) => api.scrape(url, options).await,
+            Self::Cli(cli) => cli.scrape(url, options).await,
+        };
+        result
+            .map(|scraped| ScrapeResult {
+                markdown: scraped.markdown,
+                title: scraped.title,
+                url: url.to_string(),
+            })
+            .map_err(|err| {
+                let message = match err {
+                    blz_core::Error::FirecrawlScrapeFailed { reason, .. } => reason,
+                    other => other.to_string(),
+                };
+                let rate_limited = message.contains("429");
+                ScrapeError::new(url.to_string(), message).with_rate_limit(rate_limited)
+            })
+    }
+}
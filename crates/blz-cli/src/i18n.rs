@@ -0,0 +1,253 @@
+//! Localized user-facing messages
+//!
+//! Errors, hints, and summaries shown to people (and agents) are looked up in
+//! per-locale JSON catalogs under `src/locales/`, so teams can translate them
+//! without touching command code. English is the reference catalog; any key a
+//! locale does not translate falls back to English.
+//!
+//! The locale is selected with `BLZ_LOCALE` or `defaults.locale` in
+//! `config.toml`. The system locale (`LANG`) is intentionally not consulted so
+//! that output stays stable for scripts unless a locale is opted into.
+//!
+//! Messages may contain `{name}` placeholders that are filled from the
+//! arguments passed to [`text`].
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use blz_core::Config;
+
+const EN_CATALOG: &str = include_str!("locales/en.json");
+const ES_CATALOG: &str = include_str!("locales/es.json");
+
+/// Supported message locales.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Locale {
+    /// English (reference catalog).
+    En,
+    /// Spanish.
+    Es,
+}
+
+impl Locale {
+    /// All supported locales.
+    pub const ALL: [Self; 2] = [Self::En, Self::Es];
+
+    /// Parse a locale tag such as `es`, `es-MX`, or `es_ES.UTF-8`.
+    pub fn parse(tag: &str) -> Option<Self> {
+        let language = tag.trim().split(['-', '_', '.', '@']).next()?;
+        Self::ALL
+            .into_iter()
+            .find(|locale| locale.code().eq_ignore_ascii_case(language))
+    }
+
+    /// Short language code for this locale.
+    pub const fn code(self) -> &'static str {
+        match self {
+            Self::En => "en",
+            Self::Es => "es",
+        }
+    }
+
+    const fn source(self) -> &'static str {
+        match self {
+            Self::En => EN_CATALOG,
+            Self::Es => ES_CATALOG,
+        }
+    }
+}
+
+/// Catalog keys for user-facing messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Message {
+    NoSourcesConfigured,
+    NoSourcesFound,
+    NoSourcesAvailable,
+    NoSourcesMatch,
+    NoResults,
+    SourceNotFound,
+    SourceNotFoundSuggestions,
+    AvailableSources,
+    AnchorNotFound,
+    WarningPrefix,
+    HintListSources,
+    HintListOrLookup,
+    HintInspectToc,
+}
+
+impl Message {
+    /// Every message key, used to validate catalogs.
+    #[cfg(test)]
+    pub const ALL: &'static [Self] = &[
+        Self::NoSourcesConfigured,
+        Self::NoSourcesFound,
+        Self::NoSourcesAvailable,
+        Self::NoSourcesMatch,
+        Self::NoResults,
+        Self::SourceNotFound,
+        Self::SourceNotFoundSuggestions,
+        Self::AvailableSources,
+        Self::AnchorNotFound,
+        Self::WarningPrefix,
+        Self::HintListSources,
+        Self::HintListOrLookup,
+        Self::HintInspectToc,
+    ];
+
+    /// Key of this message in the JSON catalogs.
+    pub const fn key(self) -> &'static str {
+        match self {
+            Self::NoSourcesConfigured => "no_sources_configured",
+            Self::NoSourcesFound => "no_sources_found",
+            Self::NoSourcesAvailable => "no_sources_available",
+            Self::NoSourcesMatch => "no_sources_match",
+            Self::NoResults => "no_results",
+            Self::SourceNotFound => "source_not_found",
+            Self::SourceNotFoundSuggestions => "source_not_found_suggestions",
+            Self::AvailableSources => "available_sources",
+            Self::AnchorNotFound => "anchor_not_found",
+            Self::WarningPrefix => "warning_prefix",
+            Self::HintListSources => "hint_list_sources",
+            Self::HintListOrLookup => "hint_list_or_lookup",
+            Self::HintInspectToc => "hint_inspect_toc",
+        }
+    }
+}
+
+type Catalog = HashMap<String, String>;
+
+fn catalog(locale: Locale) -> &'static Catalog {
+    static EN: OnceLock<Catalog> = OnceLock::new();
+    static ES: OnceLock<Catalog> = OnceLock::new();
+
+    let cell = match locale {
+        Locale::En => &EN,
+        Locale::Es => &ES,
+    };
+    // Catalogs are embedded at compile time and validated by the tests below.
+    cell.get_or_init(|| serde_json::from_str(locale.source()).unwrap_or_default())
+}
+
+/// Locale used for this process, resolved once from `BLZ_LOCALE` or config.
+pub fn current_locale() -> Locale {
+    static CURRENT: OnceLock<Locale> = OnceLock::new();
+    *CURRENT.get_or_init(|| {
+        let requested = std::env::var("BLZ_LOCALE")
+            .ok()
+            .filter(|value| !value.trim().is_empty())
+            .or_else(|| {
+                Config::load()
+                    .ok()
+                    .and_then(|config| config.defaults.locale)
+            });
+        resolve_locale(requested.as_deref())
+    })
+}
+
+fn resolve_locale(requested: Option<&str>) -> Locale {
+    let Some(tag) = requested else {
+        return Locale::En;
+    };
+    Locale::parse(tag).unwrap_or_else(|| {
+        tracing::debug!("unsupported locale '{tag}', falling back to English");
+        Locale::En
+    })
+}
+
+/// Render `message` in the current locale, filling `{name}` placeholders.
+pub fn text(message: Message, args: &[(&str, &str)]) -> String {
+    text_in(current_locale(), message, args)
+}
+
+/// Render `message` in a specific locale, falling back to English.
+pub fn text_in(locale: Locale, message: Message, args: &[(&str, &str)]) -> String {
+    let template = catalog(locale)
+        .get(message.key())
+        .or_else(|| catalog(Locale::En).get(message.key()))
+        .map_or_else(|| message.key(), String::as_str);
+    interpolate(template, args)
+}
+
+fn interpolate(template: &str, args: &[(&str, &str)]) -> String {
+    args.iter()
+        .fold(template.to_string(), |rendered, (name, value)| {
+            rendered.replace(&format!("{{{name}}}"), value)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    fn placeholders(template: &str) -> BTreeSet<&str> {
+        template
+            .split('{')
+            .skip(1)
+            .filter_map(|part| part.split_once('}').map(|(name, _)| name))
+            .collect()
+    }
+
+    #[test]
+    fn catalogs_parse() {
+        for locale in Locale::ALL {
+            let parsed: Result<Catalog, _> = serde_json::from_str(locale.source());
+            assert!(
+                parsed.is_ok(),
+                "{} catalog is not valid JSON",
+                locale.code()
+            );
+        }
+    }
+
+    #[test]
+    fn english_catalog_covers_every_message() {
+        let en = catalog(Locale::En);
+        for message in Message::ALL {
+            assert!(
+                en.contains_key(message.key()),
+                "missing en key {}",
+                message.key()
+            );
+        }
+        assert_eq!(en.len(), Message::ALL.len(), "en catalog has unknown keys");
+    }
+
+    #[test]
+    fn translations_only_use_known_keys_and_placeholders() {
+        let en = catalog(Locale::En);
+        for locale in Locale::ALL {
+            for (key, template) in catalog(locale) {
+                assert_eq!(
+                    Some(placeholders(template)),
+                    en.get(key).map(|reference| placeholders(reference)),
+                    "{} has an unknown key or mismatched placeholders for {key}",
+                    locale.code()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn renders_placeholders_per_locale() {
+        assert_eq!(
+            text_in(Locale::En, Message::NoResults, &[("query", "hooks")]),
+            "No results found for 'hooks'"
+        );
+        assert_eq!(
+            text_in(Locale::Es, Message::NoResults, &[("query", "hooks")]),
+            "No se encontraron resultados para 'hooks'"
+        );
+    }
+
+    #[test]
+    fn parses_locale_tags() {
+        assert_eq!(Locale::parse("es"), Some(Locale::Es));
+        assert_eq!(Locale::parse("es-MX"), Some(Locale::Es));
+        assert_eq!(Locale::parse("ES_es.UTF-8"), Some(Locale::Es));
+        assert_eq!(Locale::parse("en_US"), Some(Locale::En));
+        assert_eq!(Locale::parse("fr"), None);
+        assert_eq!(resolve_locale(Some("fr")), Locale::En);
+        assert_eq!(resolve_locale(None), Locale::En);
+    }
+}
@@ -15,6 +15,7 @@ mod commands;
 pub mod config;
 pub mod error;
 pub mod generate;
+mod i18n;
 mod output;
 mod prompt;
 mod utils;
@@ -33,6 +34,7 @@ use cli::{Cli, Commands};
 /// # Errors
 ///
 /// Returns an error if CLI initialization, prompt emission, or command execution fails.
+#[allow(clippy::future_not_send)]
 pub async fn run() -> Result<()> {
     // Convert Broken pipe panics into a clean exit
     std::panic::set_hook(Box::new(|info| {
@@ -48,14 +50,29 @@ pub async fn run() -> Result<()> {
     // Spawn process guard as early as possible to catch orphaned processes
     utils::process_guard::spawn_parent_exit_guard();
 
-    let mut cli = Cli::parse();
+    let rewrite = utils::preprocess::explain_args();
+    let mut cli = Cli::parse_from(&rewrite.rewritten);
+    if !cli.quiet && cli.prompt.is_none() {
+        let prefix = i18n::text(i18n::Message::WarningPrefix, &[]);
+        for warning in &rewrite.warnings {
+            eprintln!("{prefix} {warning}");
+        }
+    }
 
     if let Some(target) = cli.prompt.clone() {
         prompt::emit(&target, cli.command.as_ref())?;
         return Ok(());
     }
 
+    // Select the named profile before anything resolves storage or history paths
+    if let Some(name) = cli.profile_name.as_deref() {
+        blz_core::profile::select(name)?;
+    }
+    utils::output_profile::apply(&mut cli)?;
     initialize_logging(&cli)?;
+    utils::confirm::set_no_input(cli.no_input);
+    utils::workspace::set_global(cli.global);
+    utils::workspace::apply_defaults(&mut cli);
 
     let args: Vec<String> = std::env::args().collect();
     let mut cli_preferences = preferences::load();
@@ -73,7 +90,15 @@ pub async fn run() -> Result<()> {
     #[cfg(feature = "flamegraph")]
     let profiler_guard = start_flamegraph_if_requested(&cli);
 
-    execute_command(cli.clone(), metrics.clone(), &mut cli_preferences).await?;
+    let result = execute_command(cli.clone(), metrics.clone(), &mut cli_preferences).await;
+    // Long-running commands stop early on SIGINT/SIGTERM; report it in the exit status
+    if let Some(signal) = utils::shutdown::requested() {
+        if let Err(err) = result {
+            eprintln!("Error: {err:#}");
+        }
+        std::process::exit(signal.exit_code());
+    }
+    result?;
 
     #[cfg(feature = "flamegraph")]
     stop_flamegraph_if_started(profiler_guard);
@@ -87,13 +112,14 @@ pub async fn run() -> Result<()> {
     Ok(())
 }
 
-#[allow(clippy::too_many_lines)]
+#[allow(clippy::too_many_lines, clippy::future_not_send)]
 async fn execute_command(
     cli: Cli,
     metrics: PerformanceMetrics,
     prefs: &mut CliPreferences,
 ) -> Result<()> {
     let quiet = cli.quiet;
+    let verbose = cli.verbose;
     match cli.command {
         Some(Commands::Instruct) => {
             prompt::emit("__global__", Some(&Commands::Instruct))?;
@@ -111,7 +137,11 @@ async fn execute_command(
         },
         Some(Commands::ClaudePlugin { command }) => commands::dispatch_claude_plugin(command)?,
         Some(Commands::Alias { command }) => commands::dispatch_alias(command).await?,
-        Some(Commands::Add(args)) => commands::dispatch_add(args, quiet, metrics).await?,
+        Some(Commands::Add(args)) => {
+            let added: Vec<String> = args.alias.iter().cloned().collect();
+            commands::dispatch_add(args, quiet, metrics).await?;
+            utils::quota::enforce_budget(&added, quiet)?;
+        },
         Some(Commands::Lookup {
             query,
             format,
@@ -130,12 +160,14 @@ async fn execute_command(
             format,
             clear,
             clear_before,
+            yes,
         }) => {
             commands::dispatch_history(
                 limit,
                 &format,
                 clear,
                 clear_before.as_deref(),
+                yes,
                 quiet,
                 prefs,
             )?;
@@ -145,28 +177,77 @@ async fn execute_command(
             commands::dispatch_query(args, quiet, prefs, metrics.clone()).await?;
         },
         Some(Commands::Map(args)) => commands::dispatch_map(args, quiet).await?,
-        Some(Commands::Sync(args)) => commands::dispatch_sync(args, quiet, metrics).await?,
+        Some(Commands::Sync(args)) => {
+            commands::dispatch_sync(args, quiet, metrics).await?;
+            utils::quota::enforce_budget(&[], quiet)?;
+        },
+        Some(Commands::Watch(args)) => commands::watch_sources(&args, metrics).await?,
+        Some(Commands::Generate(args)) => commands::generate_source(args, quiet, metrics).await?,
+        Some(Commands::Discover(args)) => {
+            commands::discover_sources(&args, quiet, metrics).await?;
+        },
         Some(Commands::Check(args)) => {
-            commands::check_source(args.alias, args.all, args.format.resolve(quiet)).await?;
+            commands::check_source(
+                args.alias,
+                args.all,
+                args.verify,
+                args.format.resolve(quiet),
+            )
+            .await?;
         },
         Some(Commands::Rm(args)) => commands::rm_source(vec![args.alias], args.yes).await?,
+        Some(Commands::Export(args)) => commands::export_bundle(&args, quiet)?,
+        Some(Commands::Import(args)) => commands::import_bundle(&args, quiet, &metrics)?,
         #[allow(deprecated)]
         Some(cmd @ Commands::Find { .. }) => {
             commands::dispatch_find(cmd, quiet, prefs, metrics.clone()).await?;
         },
+        Some(Commands::Default(args)) => {
+            commands::dispatch_default(args, quiet, prefs, metrics.clone()).await?;
+        },
         Some(Commands::Info { alias, format }) => {
             commands::execute_info(&alias, format.resolve(quiet)).await?;
         },
+        Some(Commands::Which { source, format }) => {
+            commands::show_resolution(&source, verbose, format.resolve(quiet))?;
+        },
         Some(Commands::List {
             format,
             status,
             details,
             limit,
+            sort,
+            filters,
+            columns,
+            export,
         }) => {
-            commands::dispatch_list(format, status, details, limit, quiet).await?;
+            let options = commands::ListOptions {
+                status,
+                details,
+                limit,
+                sort,
+                filters,
+                columns,
+                export,
+            };
+            commands::dispatch_list(format, options, quiet).await?;
         },
-        Some(Commands::Stats { format, limit }) => {
-            commands::show_stats(format.resolve(quiet), limit)?;
+        Some(Commands::Stats {
+            format,
+            limit,
+            unused,
+            disk,
+            days,
+            prune,
+            yes,
+        }) => {
+            if unused {
+                commands::show_unused_sources(format.resolve(quiet), days, prune, yes)?;
+            } else if disk {
+                commands::show_disk_budget(format.resolve(quiet), limit)?;
+            } else {
+                commands::show_stats(format.resolve(quiet), limit)?;
+            }
         },
         #[allow(deprecated)]
         Some(Commands::Validate { alias, all, format }) => {
@@ -175,6 +256,38 @@ async fn execute_command(
         Some(Commands::Doctor { format, fix }) => {
             commands::run_doctor(format.resolve(quiet), fix).await?;
         },
+        Some(Commands::Bench(args)) => commands::run_bench(args, quiet)?,
+        Some(Commands::Explain(args)) => commands::explain_query(&args, quiet)?,
+        Some(Commands::ExplainSource(args)) => commands::explain_source(&args, quiet)?,
+        Some(Commands::Terms(args)) => commands::list_terms(&args, quiet)?,
+        Some(Commands::Filter { command }) => commands::dispatch_filter(&command, quiet, metrics)?,
+        Some(Commands::Profile { command }) => commands::dispatch_profile(&command, quiet)?,
+        Some(Commands::Init(args)) => commands::init_project(&args, quiet)?,
+        Some(Commands::Demo(args)) => commands::run_demo(&args, quiet, metrics)?,
+        Some(Commands::Fixtures(args)) => commands::generate_fixtures(&args, quiet, &metrics)?,
+        Some(Commands::VerifyInstall(args)) => commands::verify_install(&args, quiet)?,
+        Some(Commands::Serve(args)) => commands::serve_api(&args, quiet, metrics).await?,
+        Some(Commands::Du {
+            aliases,
+            human: _,
+            bytes,
+            format,
+        }) => commands::show_disk_usage(&aliases, bytes, format.resolve(quiet))?,
+        Some(Commands::Gc(args)) => commands::collect_garbage(&args, quiet)?,
+        Some(Commands::DebugArgs { args, format }) => {
+            commands::debug_args(args, format.resolve(quiet))?;
+        },
+        Some(Commands::Ps { all, format }) => {
+            commands::list_processes(all, format.resolve(quiet))?;
+        },
+        Some(Commands::Kill {
+            pids,
+            orphans,
+            force,
+            format,
+        }) => {
+            commands::kill_processes(&pids, orphans, force, format.resolve(quiet))?;
+        },
         #[allow(deprecated)]
         Some(Commands::Refresh {
             aliases,
@@ -208,8 +321,19 @@ async fn execute_command(
             commands::dispatch_remove_deprecated(alias, yes, quiet).await?;
         },
         Some(Commands::Clear { force }) => commands::clear_cache(force)?,
-        Some(Commands::Diff { alias, since }) => {
-            commands::show_diff(&alias, since.as_deref()).await?;
+        Some(Commands::Diff {
+            alias,
+            since,
+            between,
+            format,
+        }) => {
+            commands::show_diff(
+                &alias,
+                since.as_deref(),
+                between.as_deref(),
+                format.resolve(quiet),
+            )
+            .await?;
         },
         Some(Commands::McpServer) => commands::mcp_server().await?,
         Some(Commands::Anchor { command }) => dispatch_anchor(command, quiet).await?,
@@ -811,6 +935,7 @@ mod tests {
             assert!(args.category.is_none());
             assert!(args.yes);
             assert!(!args.dry_run);
+            assert!(!args.inspect);
             assert!(args.manifest.is_none());
         } else {
             panic!("Expected add command");
@@ -279,7 +279,7 @@ impl SearchResultFormatter {
             OutputFormat::Jsonl => {
                 JsonFormatter::format_search_results_jsonl(params.hits)?;
             },
-            OutputFormat::Text => {
+            OutputFormat::Text | OutputFormat::Markdown => {
                 TextFormatter::format_search_results(params);
             },
             OutputFormat::Raw => {
@@ -411,7 +411,7 @@ impl SourceInfoFormatter {
                     println!("{}", serde_json::to_string(info)?);
                 }
             },
-            OutputFormat::Text => {
+            OutputFormat::Text | OutputFormat::Markdown => {
                 // Text formatting is handled in the list command
             },
             OutputFormat::Raw => {
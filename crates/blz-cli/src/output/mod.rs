@@ -84,23 +84,23 @@ pub use formatter::{FormatParams, SearchResultFormatter};
 // Some shapes await command adoption (check, get/retrieve commands not yet migrated)
 #[allow(unused_imports)]
 pub use shapes::{
-    CheckOutput, CheckResult, GenericOutput, OutputShape, RetrieveOutput, RetrievedContent,
-    SearchHitOutput, SearchOutput, SearchOutputBuilder, SourceInfoOutput, SourceListOutput,
-    SourceStatus, SourceSummary, TocEntry, TocOutput,
+    CheckOutput, CheckResult, GenericOutput, HeadingHitOutput, HeadingSearchOutput, OutputShape,
+    RetrieveOutput, RetrievedContent, SearchHitOutput, SearchOutput, SearchOutputBuilder,
+    SourceInfoOutput, SourceListOutput, SourceStatus, SourceSummary, TocEntry, TocOutput,
 };
 
 // TODO(BLZ-341): Remove allow once commands adopt streaming output.
 #[allow(unused_imports)]
 pub use stream::{
-    StreamConfig, output_stream_jsonl, output_stream_jsonl_sync, output_stream_jsonl_sync_stdout,
-    output_stream_jsonl_with_config,
+    PipeWriter, StreamConfig, output_stream_jsonl, output_stream_jsonl_sync,
+    output_stream_jsonl_sync_stdout, output_stream_jsonl_with_config,
 };
 
 // Some render functions await command adoption
 #[allow(unused_imports)]
 pub use render::{
-    SearchRenderOptions, SourceListRenderOptions, render, render_search_with_options,
-    render_source_list_with_options,
+    SearchRenderOptions, SourceListRenderOptions, render, render_heading_search,
+    render_search_with_options, render_source_list_with_options,
 };
 
 // Re-export commonly used formatters
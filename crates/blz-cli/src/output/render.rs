@@ -34,13 +34,17 @@ use std::io::Write;
 use anyhow::Result;
 use colored::Colorize;
 
+use blz_core::MatchSpan;
 use blz_core::numeric::{format_bytes, safe_percentage};
 
 use super::OutputFormat;
 use super::shapes::{
-    OutputShape, SearchHitOutput, SearchOutput, SourceInfoOutput, SourceListOutput, SourceSummary,
-    TocEntry, TocMultiOutput, TocOutput, TocPaginatedEntry, TocPaginatedOutput, TocRenderOptions,
+    HeadingHitOutput, HeadingSearchOutput, OutputShape, SearchHitOutput, SearchOutput,
+    SourceInfoOutput, SourceListOutput, SourceSummary, TocEntry, TocMultiOutput, TocOutput,
+    TocPaginatedEntry, TocPaginatedOutput, TocRenderOptions,
 };
+use crate::args::{ShowComponent, ShowSet};
+use crate::i18n::{self, Message};
 use crate::utils::formatting::{format_heading_path, get_alias_color, terminal_width};
 
 /// Render an [`OutputShape`] to the given writer in the specified format.
@@ -154,14 +158,8 @@ pub struct SourceListRenderOptions {
 pub struct SearchRenderOptions {
     /// Original search query (used for highlighting).
     pub query: String,
-    /// Whether to show source URLs.
-    pub show_url: bool,
-    /// Whether to show line numbers inline.
-    pub show_lines: bool,
-    /// Whether to show anchor slugs.
-    pub show_anchor: bool,
-    /// Whether to show raw scores instead of percentages.
-    pub show_raw_score: bool,
+    /// Extra `--show` components to display.
+    pub show: ShowSet,
     /// Whether to suppress the summary footer.
     pub no_summary: bool,
     /// Number of decimal places for raw scores (0-4).
@@ -180,10 +178,7 @@ impl Default for SearchRenderOptions {
     fn default() -> Self {
         Self {
             query: String::new(),
-            show_url: false,
-            show_lines: false,
-            show_anchor: false,
-            show_raw_score: false,
+            show: ShowSet::default(),
             no_summary: false,
             score_precision: 1,
             snippet_lines: 3,
@@ -209,13 +204,56 @@ pub fn render_search_with_options(
     writer: &mut impl Write,
 ) -> Result<()> {
     match format {
-        OutputFormat::Text => render_search_text(data, options, writer),
+        OutputFormat::Text | OutputFormat::Markdown => render_search_text(data, options, writer),
         OutputFormat::Json => render_search_json(data, writer),
         OutputFormat::Jsonl => render_search_jsonl(data, writer),
         OutputFormat::Raw => render_search_raw(data, writer),
     }
 }
 
+/// Render `--headings-only` search results.
+///
+/// Markdown output is an outline grouped by source; other formats mirror the
+/// regular search renderers without snippets.
+///
+/// # Errors
+///
+/// Returns an error if writing to the output fails or if serialization fails.
+pub fn render_heading_search(
+    data: &HeadingSearchOutput,
+    format: OutputFormat,
+    options: &SearchRenderOptions,
+    writer: &mut impl Write,
+) -> Result<()> {
+    match format {
+        OutputFormat::Text => render_heading_search_text(data, options, writer),
+        OutputFormat::Markdown => render_heading_search_markdown(data, writer),
+        OutputFormat::Json => {
+            let json = serde_json::to_string_pretty(data)?;
+            writeln!(writer, "{json}")?;
+            Ok(())
+        },
+        OutputFormat::Jsonl => {
+            for hit in &data.results {
+                writeln!(writer, "{}", serde_json::to_string(hit)?)?;
+            }
+            Ok(())
+        },
+        OutputFormat::Raw => {
+            for hit in &data.results {
+                writeln!(
+                    writer,
+                    "{}:{} {}",
+                    hit.alias,
+                    hit.lines,
+                    hit.heading_path.join(" > ")
+                )?;
+            }
+            Ok(())
+        },
+    }
+}
+
 /// Render source list with custom options.
 ///
 /// This function provides more control over rendering compared to
@@ -231,7 +269,9 @@ pub fn render_source_list_with_options(
     writer: &mut impl Write,
 ) -> Result<()> {
     match format {
-        OutputFormat::Text => render_source_list_text_with_options(data, options, writer),
+        OutputFormat::Text | OutputFormat::Markdown => {
+            render_source_list_text_with_options(data, options, writer)
+        },
         OutputFormat::Json => render_source_list_json_with_options(data, options, writer),
         OutputFormat::Jsonl => render_source_list_jsonl_with_options(data, options, writer),
         OutputFormat::Raw => render_source_list_raw(data, writer),
@@ -255,10 +295,7 @@ fn render_source_list_text_with_options(
     writer: &mut impl Write,
 ) -> Result<()> {
     if data.sources.is_empty() {
-        writeln!(
-            writer,
-            "No sources configured. Use 'blz add' to add sources."
-        )?;
+        writeln!(writer, "{}", i18n::text(Message::NoSourcesConfigured, &[]))?;
         return Ok(());
     }
 
@@ -284,6 +321,7 @@ fn render_source_text(
         "  {} lines, {} headings",
         source.lines, source.headings
     )?;
+    writeln!(writer, "  Health: {}", source.health.compact())?;
 
     if !source.tags.is_empty() {
         writeln!(writer, "  Tags: {}", source.tags.join(", "))?;
@@ -357,6 +395,7 @@ fn render_origin_text(writer: &mut impl Write, source: &SourceSummary) -> Result
                     .get("path")
                     .and_then(|v| v.as_str())
                     .map_or_else(|| "local".to_string(), |path| format!("local ({path})")),
+                Some("stdin") => "stdin".to_string(),
                 _ => "unknown".to_string(),
             };
             writeln!(writer, "  Origin: {origin_str}")?;
@@ -419,6 +458,7 @@ fn source_to_json(source: &SourceSummary, options: &SourceListRenderOptions) ->
         serde_json::json!(source.aliases.clone()),
     );
     obj.insert("status".to_string(), serde_json::json!(source.status));
+    obj.insert("health".to_string(), serde_json::json!(source.health));
 
     // Include fetched_at and checksum (always present in original list.rs output)
     if let Some(fetched_at) = &source.fetched_at {
@@ -427,6 +467,18 @@ fn source_to_json(source: &SourceSummary, options: &SourceListRenderOptions) ->
             serde_json::Value::String(fetched_at.clone()),
         );
     }
+    if let Some(last_searched_at) = &source.last_searched_at {
+        obj.insert(
+            "lastSearchedAt".to_string(),
+            serde_json::Value::String(last_searched_at.clone()),
+        );
+    }
+    if let Some(last_fetched_at) = &source.last_fetched_at {
+        obj.insert(
+            "lastFetchedAt".to_string(),
+            serde_json::Value::String(last_fetched_at.clone()),
+        );
+    }
     if let Some(checksum) = &source.checksum {
         obj.insert(
             "sha256".to_string(),
@@ -539,6 +591,16 @@ fn render_source_info_text(data: &SourceInfoOutput, writer: &mut impl Write) ->
         writeln!(writer, "Last Updated: {updated}")?;
     }
 
+    if let Some(fetched) = &data.last_fetched {
+        writeln!(writer, "Last Checked: {fetched}")?;
+    }
+
+    writeln!(
+        writer,
+        "Last Searched: {}",
+        data.last_searched.as_deref().unwrap_or("never")
+    )?;
+
     if let Some(etag) = &data.etag {
         writeln!(writer, "ETag: {etag}")?;
     }
@@ -549,6 +611,22 @@ fn render_source_info_text(data: &SourceInfoOutput, writer: &mut impl Write) ->
 
     writeln!(writer, "Cache Location: {}", data.cache_path)?;
 
+    if !data.mirrors.is_empty() {
+        writeln!(writer, "Mirrors:")?;
+        for mirror in &data.mirrors {
+            let health = if mirror.consecutive_failures > 0 {
+                format!("{} failed", mirror.consecutive_failures)
+                    .red()
+                    .to_string()
+            } else if let Some(latency) = mirror.latency_ms {
+                format!("{latency} ms")
+            } else {
+                "untested".dimmed().to_string()
+            };
+            writeln!(writer, "  {} ({health})", mirror.url)?;
+        }
+    }
+
     // Display language filtering information
     writeln!(writer)?;
     if let Some(stats) = &data.filter_stats {
@@ -619,7 +697,7 @@ pub fn render_toc_with_options(
     writer: &mut impl Write,
 ) -> Result<()> {
     match format {
-        OutputFormat::Text => render_toc_text(data, options, writer),
+        OutputFormat::Text | OutputFormat::Markdown => render_toc_text(data, options, writer),
         OutputFormat::Json => render_toc_json(data, writer),
         OutputFormat::Jsonl => render_toc_jsonl(data, writer),
         OutputFormat::Raw => render_toc_raw_error(writer),
@@ -689,7 +767,9 @@ pub fn render_toc_paginated_with_options(
     writer: &mut impl Write,
 ) -> Result<()> {
     match format {
-        OutputFormat::Text => render_toc_paginated_text(data, options, writer),
+        OutputFormat::Text | OutputFormat::Markdown => {
+            render_toc_paginated_text(data, options, writer)
+        },
         OutputFormat::Json => render_toc_paginated_json(data, writer),
         OutputFormat::Jsonl => render_toc_paginated_jsonl(data, writer),
         OutputFormat::Raw => render_toc_raw_error(writer),
@@ -764,7 +844,11 @@ fn render_paginated_entry_text(
     entry: &TocPaginatedEntry,
     options: &TocRenderOptions,
 ) -> Result<()> {
-    let name = entry.heading_path.last().map_or("", String::as_str);
+    let name = numbered_title(
+        entry.heading_path.last().map_or("", String::as_str),
+        &entry.ordinal,
+        options,
+    );
     let indent = "  ".repeat(entry.heading_level.saturating_sub(1) as usize);
     let lines_display = format!("[{}]", entry.lines).dimmed();
 
@@ -794,7 +878,7 @@ pub fn render_toc_multi_with_options(
     writer: &mut impl Write,
 ) -> Result<()> {
     match format {
-        OutputFormat::Text => render_toc_multi_text(data, options, writer),
+        OutputFormat::Text | OutputFormat::Markdown => render_toc_multi_text(data, options, writer),
         OutputFormat::Json => render_toc_multi_json(data, writer),
         OutputFormat::Jsonl => render_toc_multi_jsonl(data, writer),
         OutputFormat::Raw => render_toc_raw_error(writer),
@@ -854,6 +938,15 @@ fn render_toc_multi_jsonl(data: &TocMultiOutput, writer: &mut impl Write) -> Res
 // Tree Rendering Helpers
 // -----------------------------------------------------------------------------
 
+/// Heading title, prefixed with its ordinal path when numbering is enabled.
+fn numbered_title(title: &str, ordinal: &str, options: &TocRenderOptions) -> String {
+    if options.numbered && !ordinal.is_empty() {
+        format!("{} {title}", ordinal.cyan())
+    } else {
+        title.to_string()
+    }
+}
+
 /// State for tree rendering, tracking previous depth and H1 children.
 #[derive(Default)]
 struct TreeState {
@@ -872,7 +965,7 @@ fn render_tree_entry(
     options: &TocRenderOptions,
     state: &mut TreeState,
 ) -> Result<bool> {
-    let name = &entry.title;
+    let name = numbered_title(&entry.title, &entry.ordinal, options);
     let lines_display = format!("[{}]", entry.lines).dimmed();
 
     // Add blank line when jumping up levels (but not to H1 - H1 handles its own spacing)
@@ -973,7 +1066,7 @@ fn render_hierarchical_entry(
     depth: usize,
     options: &TocRenderOptions,
 ) -> Result<()> {
-    let name = &entry.title;
+    let name = numbered_title(&entry.title, &entry.ordinal, options);
     let indent = "  ".repeat(depth);
     let lines_display = format!("[{}]", entry.lines).dimmed();
 
@@ -1029,7 +1122,11 @@ fn render_search_text(
     writer: &mut impl Write,
 ) -> Result<()> {
     if data.results.is_empty() {
-        writeln!(writer, "No results found for '{}'", data.query)?;
+        writeln!(
+            writer,
+            "{}",
+            i18n::text(Message::NoResults, &[("query", &data.query)])
+        )?;
         return Ok(());
     }
 
@@ -1132,7 +1229,7 @@ fn render_search_group(
     let first = hits[0];
 
     // Format score display
-    let score_display = if options.show_raw_score {
+    let score_display = if options.show.contains(ShowComponent::RawScore) {
         let raw = first.raw_score.unwrap_or(0.0);
         let score_formatted = format_score_value(raw, options.score_precision);
         format!("Score {}", score_formatted.bright_blue())
@@ -1150,7 +1247,7 @@ fn render_search_group(
     ));
     block.push(format!("  {}:{}", alias_colored.bold(), first.lines));
 
-    if options.show_anchor {
+    if options.show.contains(ShowComponent::Anchor) {
         if let Some(anchor) = first.anchor.as_deref() {
             block.push(format!("  #{}", anchor.bright_black()));
         }
@@ -1166,15 +1263,40 @@ fn render_search_group(
     // Render snippet lines
     render_search_snippet_lines(&mut block, hits, options, max_score);
 
-    if options.show_url {
+    if options.show.contains(ShowComponent::Url) {
         if let Some(url) = first.source_url.as_deref() {
             block.push(format!("  {}", url.bright_black()));
         }
     }
 
+    if let Some(details) = format_hit_details(first, options.show) {
+        block.push(format!("  {}", details.bright_black()));
+    }
+
     block.join("\n")
 }
 
+/// Format the `checksum`, `tokens`, and `context` components as one line.
+///
+/// Labels match the JSON field names so text and JSON output line up.
+fn format_hit_details(hit: &SearchHitOutput, show: ShowSet) -> Option<String> {
+    let mut parts = Vec::new();
+    if show.contains(ShowComponent::Checksum) {
+        parts.push(format!("checksum {}", hit.checksum));
+    }
+    if show.contains(ShowComponent::Tokens) {
+        if let Some(tokens) = hit.tokens {
+            parts.push(format!("tokens {tokens}"));
+        }
+    }
+    if show.contains(ShowComponent::Context) {
+        if let Some(context) = &hit.context {
+            parts.push(format!("context {}", context.lines));
+        }
+    }
+    (!parts.is_empty()).then(|| parts.join(" \u{b7} "))
+}
+
 /// Parse the start line number from a line range string like "12-15".
 /// Returns 1 if parsing fails.
 fn parse_start_line(lines: &str) -> usize {
@@ -1203,12 +1325,19 @@ fn render_search_snippet_lines(
         // Parse the start line from hit.lines (e.g., "12-15" -> 12)
         let base_line = parse_start_line(&hit.lines);
 
-        // Parse snippet into lines with actual document line numbers
-        let snippet_lines: Vec<(usize, &str)> = hit
+        // Parse snippet into lines with actual document line numbers and
+        // their byte offsets, so match highlights can be painted per line
+        let mut offset = 0;
+        let snippet_lines: Vec<(usize, String)> = hit
             .snippet
-            .lines()
+            .split_inclusive('\n')
             .enumerate()
-            .map(|(idx, line)| (base_line + idx, line))
+            .map(|(idx, raw)| {
+                let line = raw.trim_end_matches(['\n', '\r']);
+                let text = paint_highlights(line, offset, &hit.highlights);
+                offset += raw.len();
+                (base_line + idx, text)
+            })
             .collect();
 
         for (line_no, line_text) in snippet_lines {
@@ -1223,7 +1352,7 @@ fn render_search_snippet_lines(
                         block.push(format!("  {gap_line}"));
                     }
                 }
-                if options.show_lines {
+                if options.show.contains(ShowComponent::Lines) {
                     let label = format!("{line_no:>6}:").bright_black();
                     block.push(format!("  {label} {line_text}"));
                 } else {
@@ -1236,6 +1365,32 @@ fn render_search_snippet_lines(
     }
 }
 
+/// Color the parts of `line` covered by `highlights`.
+///
+/// `line_start` is the byte offset of `line` within the snippet the spans
+/// refer to; spans crossing a line break are clipped to this line.
+fn paint_highlights(line: &str, line_start: usize, highlights: &[MatchSpan]) -> String {
+    let line_end = line_start + line.len();
+    let mut out = String::with_capacity(line.len());
+    let mut cursor = 0;
+    for span in highlights {
+        let start = span.start.max(line_start);
+        let end = span.end.min(line_end);
+        if start >= end {
+            continue;
+        }
+        let (start, end) = (start - line_start, end - line_start);
+        let (Some(before), Some(matched)) = (line.get(cursor..start), line.get(start..end)) else {
+            continue;
+        };
+        out.push_str(before);
+        out.push_str(&matched.red().bold().to_string());
+        cursor = end;
+    }
+    out.push_str(line.get(cursor..).unwrap_or_default());
+    out
+}
+
 /// Print the summary footer with result counts and timing.
 fn render_search_summary(
     writer: &mut impl Write,
@@ -1279,6 +1434,114 @@ fn format_score_value(score: f32, precision: u8) -> String {
     format!("{score:.prec$}")
 }
 
+/// Render heading-only search results as human-readable text.
+fn render_heading_search_text(
+    data: &HeadingSearchOutput,
+    options: &SearchRenderOptions,
+    writer: &mut impl Write,
+) -> Result<()> {
+    if data.results.is_empty() {
+        writeln!(
+            writer,
+            "{}",
+            i18n::text(Message::NoResults, &[("query", &data.query)])
+        )?;
+        return Ok(());
+    }
+
+    let offset = options
+        .page
+        .saturating_sub(1)
+        .saturating_mul(options.per_page);
+    for (idx, hit) in data.results.iter().enumerate() {
+        let rank = offset.saturating_add(idx).saturating_add(1);
+        write!(
+            writer,
+            "{rank:>3}. {} {}  {}",
+            format!("{}:{}", hit.alias, hit.lines).cyan(),
+            format_heading_path(
+                &hit.heading_path,
+                terminal_width().unwrap_or(DEFAULT_TERMINAL_WIDTH)
+            )
+            .bold(),
+            format!("{}%", hit.score).dimmed()
+        )?;
+        if let Some(label) = subsection_label(hit.child_count) {
+            write!(writer, "  {}", format!("({label})").dimmed())?;
+        }
+        if let (true, Some(anchor)) = (options.show.contains(ShowComponent::Anchor), &hit.anchor) {
+            write!(writer, "  #{anchor}")?;
+        }
+        writeln!(writer)?;
+    }
+
+    if !options.no_summary {
+        writeln!(
+            writer,
+            "\n{} {}/{} headings shown, took {}",
+            "\u{2192}".bold(), // →
+            data.results.len().to_string().green(),
+            data.total_results.to_string().green(),
+            format!("{}ms", data.search_time_ms).blue()
+        )?;
+    }
+    Ok(())
+}
+
+/// Describe a non-zero subsection count (e.g. "2 subsections").
+fn subsection_label(child_count: Option<usize>) -> Option<String> {
+    child_count
+        .filter(|count| *count > 0)
+        .map(|count| format!("{count} subsection{}", if count == 1 { "" } else { "s" }))
+}
+
+/// Render heading-only search results as a Markdown outline.
+///
+/// Headings are grouped by source and indented relative to the shallowest
+/// matching level, so the outline can be pasted straight into notes or prompts.
+fn render_heading_search_markdown(
+    data: &HeadingSearchOutput,
+    writer: &mut impl Write,
+) -> Result<()> {
+    if data.results.is_empty() {
+        writeln!(writer, "_No headings match \"{}\"._", data.query)?;
+        return Ok(());
+    }
+
+    let mut aliases: Vec<&str> = Vec::new();
+    for hit in &data.results {
+        if !aliases.contains(&hit.alias.as_str()) {
+            aliases.push(&hit.alias);
+        }
+    }
+
+    for (idx, alias) in aliases.iter().enumerate() {
+        if idx > 0 {
+            writeln!(writer)?;
+        }
+        writeln!(writer, "## {alias}\n")?;
+        let hits: Vec<&HeadingHitOutput> = data
+            .results
+            .iter()
+            .filter(|hit| hit.alias == *alias)
+            .collect();
+        let base_level = hits.iter().map(|hit| hit.level).min().unwrap_or(1);
+        for hit in hits {
+            let indent = "  ".repeat(usize::from(hit.level.saturating_sub(base_level)));
+            let title = hit.anchor.as_ref().map_or_else(
+                || hit.heading.clone(),
+                |anchor| format!("[{}](#{anchor})", hit.heading),
+            );
+            write!(writer, "{indent}- {title} — lines {}", hit.lines)?;
+            if let Some(label) = subsection_label(hit.child_count) {
+                write!(writer, " · {label}")?;
+            }
+            writeln!(writer)?;
+        }
+    }
+    Ok(())
+}
+
 /// Render search results as JSON.
 #[allow(clippy::too_many_lines)]
 fn render_search_json(data: &SearchOutput, writer: &mut impl Write) -> Result<()> {
@@ -1356,6 +1619,15 @@ fn render_search_json(data: &SearchOutput, writer: &mut impl Write) -> Result<()
                 "snippet".to_string(),
                 serde_json::Value::String(hit.snippet.clone()),
             );
+            if let Some(language) = &hit.snippet_language {
+                hit_map.insert(
+                    "snippetLanguage".to_string(),
+                    serde_json::Value::String(language.clone()),
+                );
+            }
+            if !hit.highlights.is_empty() {
+                hit_map.insert("highlights".to_string(), serde_json::json!(hit.highlights));
+            }
             hit_map.insert("score".to_string(), serde_json::json!(hit.score));
             hit_map.insert("scorePercentage".to_string(), serde_json::json!(hit.score));
             if let Some(raw) = hit.raw_score {
@@ -1374,6 +1646,12 @@ fn render_search_json(data: &SearchOutput, writer: &mut impl Write) -> Result<()
                     serde_json::Value::String(anchor.clone()),
                 );
             }
+            if let Some(section_id) = &hit.section_id {
+                hit_map.insert(
+                    "sectionId".to_string(),
+                    serde_json::Value::String(section_id.clone()),
+                );
+            }
             if let Some(url) = &hit.source_url {
                 hit_map.insert(
                     "sourceUrl".to_string(),
@@ -1391,6 +1669,9 @@ fn render_search_json(data: &SearchOutput, writer: &mut impl Write) -> Result<()
                 "checksum".to_string(),
                 serde_json::Value::String(hit.checksum.clone()),
             );
+            if let Some(tokens) = hit.tokens {
+                hit_map.insert("tokens".to_string(), serde_json::json!(tokens));
+            }
             if let Some(ctx) = &hit.context {
                 hit_map.insert("context".to_string(), serde_json::json!(ctx));
             }
@@ -1434,6 +1715,15 @@ fn render_search_jsonl(data: &SearchOutput, writer: &mut impl Write) -> Result<(
             "snippet".to_string(),
             serde_json::Value::String(hit.snippet.clone()),
         );
+        if let Some(language) = &hit.snippet_language {
+            hit_map.insert(
+                "snippetLanguage".to_string(),
+                serde_json::Value::String(language.clone()),
+            );
+        }
+        if !hit.highlights.is_empty() {
+            hit_map.insert("highlights".to_string(), serde_json::json!(hit.highlights));
+        }
         hit_map.insert("score".to_string(), serde_json::json!(hit.score));
         if let Some(raw) = hit.raw_score {
             hit_map.insert("rawScore".to_string(), serde_json::json!(raw));
@@ -1451,6 +1741,12 @@ fn render_search_jsonl(data: &SearchOutput, writer: &mut impl Write) -> Result<(
                 serde_json::Value::String(anchor.clone()),
             );
         }
+        if let Some(section_id) = &hit.section_id {
+            hit_map.insert(
+                "sectionId".to_string(),
+                serde_json::Value::String(section_id.clone()),
+            );
+        }
         if let Some(url) = &hit.source_url {
             hit_map.insert(
                 "sourceUrl".to_string(),
@@ -1468,6 +1764,9 @@ fn render_search_jsonl(data: &SearchOutput, writer: &mut impl Write) -> Result<(
             "checksum".to_string(),
             serde_json::Value::String(hit.checksum.clone()),
         );
+        if let Some(tokens) = hit.tokens {
+            hit_map.insert("tokens".to_string(), serde_json::json!(tokens));
+        }
         if let Some(ctx) = &hit.context {
             hit_map.insert("context".to_string(), serde_json::json!(ctx));
         }
@@ -1830,6 +2129,7 @@ mod tests {
     fn sample_toc_entry() -> TocEntry {
         TocEntry {
             level: 1,
+            ordinal: String::new(),
             title: "Getting Started".to_string(),
             lines: "1-50".to_string(),
             anchor: Some("getting-started".to_string()),
@@ -1837,6 +2137,7 @@ mod tests {
             children: vec![
                 TocEntry {
                     level: 2,
+                    ordinal: String::new(),
                     title: "Installation".to_string(),
                     lines: "10-30".to_string(),
                     anchor: Some("installation".to_string()),
@@ -1845,6 +2146,7 @@ mod tests {
                 },
                 TocEntry {
                     level: 2,
+                    ordinal: String::new(),
                     title: "Quick Start".to_string(),
                     lines: "31-50".to_string(),
                     anchor: None,
@@ -1877,6 +2179,7 @@ mod tests {
         let options = TocRenderOptions {
             tree_mode: true,
             show_anchors: false,
+            numbered: false,
         };
         let mut buf = Cursor::new(Vec::new());
         render_toc_text(&data, &options, &mut buf)?;
@@ -1894,6 +2197,7 @@ mod tests {
         let options = TocRenderOptions {
             tree_mode: false,
             show_anchors: true,
+            numbered: false,
         };
         let mut buf = Cursor::new(Vec::new());
         render_toc_text(&data, &options, &mut buf)?;
@@ -1904,6 +2208,30 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_render_toc_text_numbered() -> Result<()> {
+        let mut entry = sample_toc_entry();
+        entry.ordinal = "1".to_string();
+        entry.children[1].ordinal = "1.2".to_string();
+        let data = TocOutput::new("react", vec![entry]);
+        let options = TocRenderOptions {
+            numbered: true,
+            ..TocRenderOptions::default()
+        };
+        let mut buf = Cursor::new(Vec::new());
+        render_toc_text(&data, &options, &mut buf)?;
+
+        let output = String::from_utf8(buf.into_inner())?;
+        let line = output
+            .lines()
+            .find(|line| line.contains("Quick Start"))
+            .unwrap_or_default();
+        assert!(line.contains("1.2"));
+        // Entries without an ordinal render unchanged
+        assert!(output.contains("- Installation"));
+        Ok(())
+    }
+
     #[test]
     fn test_render_toc_json() -> Result<()> {
         let data = TocOutput::new("react", vec![sample_toc_entry()]);
@@ -1943,6 +2271,7 @@ mod tests {
             raw_heading_path: vec![],
             heading_path_normalized: vec![],
             heading_level: 2,
+            ordinal: String::new(),
             lines: "100-150".to_string(),
             anchor: None,
         }];
@@ -1983,6 +2312,7 @@ mod tests {
             raw_heading_path: vec![],
             heading_path_normalized: vec![],
             heading_level: 1,
+            ordinal: String::new(),
             lines: "1-100".to_string(),
             anchor: None,
         }];
@@ -2009,6 +2339,7 @@ mod tests {
             "bun",
             vec![TocEntry {
                 level: 1,
+                ordinal: String::new(),
                 title: "Installation".to_string(),
                 lines: "1-30".to_string(),
                 anchor: None,
@@ -2076,30 +2407,43 @@ mod tests {
                     alias: "react".to_string(),
                     lines: "12-15".to_string(),
                     snippet: "useEffect example\ncleanup function".to_string(),
+                    snippet_language: None,
+                    highlights: vec![MatchSpan {
+                        start: 18,
+                        end: 25,
+                        char_start: 18,
+                        char_end: 25,
+                    }],
                     score: 95,
                     raw_score: Some(14.5),
                     heading_path: vec!["Hooks".to_string(), "useEffect".to_string()],
                     level: 2,
                     anchor: Some("use-effect".to_string()),
+                    section_id: None,
                     source_url: Some("https://react.dev/llms.txt".to_string()),
                     fetched_at: None,
                     is_stale: false,
                     checksum: "abc123".to_string(),
+                    tokens: None,
                     context: None,
                 },
                 SearchHitOutput {
                     alias: "bun".to_string(),
                     lines: "100-105".to_string(),
                     snippet: "test runner usage".to_string(),
+                    snippet_language: None,
+                    highlights: Vec::new(),
                     score: 80,
                     raw_score: Some(11.2),
                     heading_path: vec!["Testing".to_string()],
                     level: 1,
                     anchor: None,
+                    section_id: None,
                     source_url: None,
                     fetched_at: None,
                     is_stale: false,
                     checksum: "def456".to_string(),
+                    tokens: None,
                     context: None,
                 },
             ],
@@ -2146,11 +2490,29 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_render_search_text_with_checksum_and_tokens() -> Result<()> {
+        let mut data = sample_search_output();
+        data.results[0].tokens = Some(42);
+        let options = SearchRenderOptions {
+            show: ShowSet::from_components(&[ShowComponent::Checksum, ShowComponent::Tokens]),
+            ..Default::default()
+        };
+        let mut buf = Cursor::new(Vec::new());
+        render_search_text(&data, &options, &mut buf)?;
+
+        let output = String::from_utf8(buf.into_inner())?;
+        assert!(output.contains("checksum abc123"));
+        assert!(output.contains("tokens 42"));
+        assert!(output.contains("checksum def456"));
+        Ok(())
+    }
+
     #[test]
     fn test_render_search_text_with_raw_score() -> Result<()> {
         let data = sample_search_output();
         let options = SearchRenderOptions {
-            show_raw_score: true,
+            show: ShowSet::from_components(&[ShowComponent::RawScore]),
             ..Default::default()
         };
         let mut buf = Cursor::new(Vec::new());
@@ -2198,10 +2560,30 @@ mod tests {
         assert_eq!(results.len(), 2);
         assert_eq!(parsed["results"][0]["alias"], "react");
         assert_eq!(parsed["results"][0]["score"], 95);
+        assert_eq!(parsed["results"][0]["highlights"][0]["start"], 18);
+        assert_eq!(parsed["results"][0]["highlights"][0]["charEnd"], 25);
         assert_eq!(parsed["results"][1]["alias"], "bun");
+        assert!(parsed["results"][1].get("highlights").is_none());
         Ok(())
     }
 
+    #[test]
+    fn test_paint_highlights_clips_spans_to_the_line() {
+        let spans = [MatchSpan {
+            start: 18,
+            end: 25,
+            char_start: 18,
+            char_end: 25,
+        }];
+        assert_eq!(
+            paint_highlights("useEffect example", 0, &spans),
+            "useEffect example"
+        );
+        let painted = paint_highlights("cleanup function", 18, &spans);
+        assert!(painted.contains("cleanup"));
+        assert!(painted.ends_with(" function"));
+    }
+
     #[test]
     fn test_render_search_jsonl() -> Result<()> {
         let data = sample_search_output();
@@ -2260,8 +2642,7 @@ mod tests {
     fn test_render_search_with_options() -> Result<()> {
         let data = sample_search_output();
         let options = SearchRenderOptions {
-            show_anchor: true,
-            show_url: true,
+            show: ShowSet::from_components(&[ShowComponent::Anchor, ShowComponent::Url]),
             ..Default::default()
         };
         let mut buf = Cursor::new(Vec::new());
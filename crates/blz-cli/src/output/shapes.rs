@@ -11,6 +11,7 @@
 //!
 //! Each shape encapsulates a specific type of CLI output:
 //! - [`SearchOutput`] - Search results with metadata
+//! - [`HeadingSearchOutput`] - Heading-only search results (no snippets)
 //! - [`RetrieveOutput`] - Retrieved content snippets
 //! - [`TocOutput`] - Table of contents / document structure
 //! - [`SourceListOutput`] - List of configured sources
@@ -38,6 +39,7 @@
 use std::collections::HashMap;
 use std::time::Duration;
 
+use blz_core::MatchSpan;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
@@ -277,6 +279,12 @@ pub struct SearchHitOutput {
     pub lines: String,
     /// Content snippet.
     pub snippet: String,
+    /// Language of the snippet when it is predominantly fenced code.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub snippet_language: Option<String>,
+    /// Where the query matched within `snippet`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub highlights: Vec<MatchSpan>,
     /// Relevance score (0-100).
     pub score: u8,
     /// Raw score value.
@@ -290,6 +298,9 @@ pub struct SearchHitOutput {
     /// Optional anchor link.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub anchor: Option<String>,
+    /// Persistent ID of the containing section, stable across syncs.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub section_id: Option<String>,
     /// Source URL if available.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub source_url: Option<String>,
@@ -300,6 +311,9 @@ pub struct SearchHitOutput {
     pub is_stale: bool,
     /// Content checksum for verification.
     pub checksum: String,
+    /// Estimated token count of the returned text (`--show tokens`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tokens: Option<usize>,
     /// Context information when `-C` or `--context` is applied.
     ///
     /// This field provides unified context representation across search and
@@ -308,6 +322,63 @@ pub struct SearchHitOutput {
     pub context: Option<ContextInfo>,
 }
 
+/// Output shape for `--headings-only` search.
+///
+/// One entry per matching heading, without snippets or provenance fields, so
+/// outlines stay compact.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HeadingSearchOutput {
+    /// Original search query.
+    pub query: String,
+    /// Matching headings for the current page.
+    pub results: Vec<HeadingHitOutput>,
+    /// Total number of matching headings.
+    pub total_results: usize,
+    /// Total lines searched across all sources.
+    pub total_lines_searched: usize,
+    /// Search execution time in milliseconds.
+    pub search_time_ms: u64,
+    /// Source aliases included in the search.
+    pub sources: Vec<String>,
+    /// Current page number (1-based).
+    pub page: usize,
+    /// Results per page.
+    pub limit: usize,
+    /// Total number of pages.
+    pub total_pages: usize,
+}
+
+/// A single heading matched by `--headings-only` search.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HeadingHitOutput {
+    /// Source alias.
+    pub alias: String,
+    /// Heading text (last element of the path).
+    pub heading: String,
+    /// Full heading path, outermost first.
+    pub heading_path: Vec<String>,
+    /// Heading level (1-6).
+    pub level: u8,
+    /// Section anchor, if known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub anchor: Option<String>,
+    /// Persistent section ID, if known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub section_id: Option<String>,
+    /// Line range of the section (e.g., "12-40").
+    pub lines: String,
+    /// Number of direct subsections, when the source's TOC is available.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub child_count: Option<usize>,
+    /// Relevance score (0-100).
+    pub score: u8,
+    /// Raw score value.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub raw_score: Option<f32>,
+}
+
 /// Output shape for retrieved content.
 ///
 /// Contains one or more retrieved snippets with context.
@@ -421,6 +492,9 @@ impl TocOutput {
 pub struct TocEntry {
     /// Heading level (1-6).
     pub level: u8,
+    /// Position in the source's full TOC (e.g., "2.3.1"), usable as `alias:§2.3.1`.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub ordinal: String,
     /// Heading text.
     pub title: String,
     /// Line range in source (e.g., "12-15").
@@ -496,6 +570,9 @@ pub struct TocPaginatedEntry {
     pub heading_path_normalized: Vec<String>,
     /// Heading level (1-6).
     pub heading_level: u8,
+    /// Position in the source's full TOC (e.g., "2.3.1"), usable as `alias:§2.3.1`.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub ordinal: String,
     /// Line range (e.g., "12-15").
     pub lines: String,
     /// Anchor link if available.
@@ -536,6 +613,8 @@ pub struct TocRenderOptions {
     pub tree_mode: bool,
     /// Show anchor slugs in output.
     pub show_anchors: bool,
+    /// Prefix headings with their ordinal path (e.g., `2.3.1`).
+    pub numbered: bool,
 }
 
 /// Output shape for source list.
@@ -611,6 +690,66 @@ pub struct SourceSummary {
     /// Optional descriptor metadata (serialized as JSON value).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub descriptor: Option<serde_json::Value>,
+    /// Size of the cached content in bytes.
+    #[serde(default)]
+    pub size_bytes: u64,
+    /// Number of recorded searches that targeted this source.
+    #[serde(default)]
+    pub searches: usize,
+    /// Last search that returned results from this source (RFC3339).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_searched_at: Option<String>,
+    /// Last check against upstream, changed or not (RFC3339).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_fetched_at: Option<String>,
+    /// Whether the source was produced by `blz generate`.
+    #[serde(default)]
+    pub generated: bool,
+    /// Compact health indicators.
+    #[serde(default)]
+    pub health: SourceHealthBadges,
+}
+
+/// Compact health indicators for a source in list view.
+///
+/// Freshness and index presence are computed on the fly; reachability and the
+/// lint score come from the last `blz check` or `blz doctor` run.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SourceHealthBadges {
+    /// Whether the source was fetched within the staleness threshold.
+    pub fresh: bool,
+    /// Whether a search index exists on disk.
+    pub index_ok: bool,
+    /// Whether the upstream was reachable at the last check.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub upstream_reachable: Option<bool>,
+    /// Lint score (0-100) from the last check.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lint_score: Option<u8>,
+    /// When reachability and lint were last recorded (RFC3339).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checked_at: Option<String>,
+}
+
+impl SourceHealthBadges {
+    /// One-line summary such as `fresh · index ok · upstream ok · lint 95`.
+    ///
+    /// Indicators that have never been checked are shown as `?`.
+    #[must_use]
+    pub fn compact(&self) -> String {
+        let freshness = if self.fresh { "fresh" } else { "stale" };
+        let index = if self.index_ok { "ok" } else { "missing" };
+        let upstream = match self.upstream_reachable {
+            Some(true) => "ok",
+            Some(false) => "down",
+            None => "?",
+        };
+        let lint = self
+            .lint_score
+            .map_or_else(|| "?".to_string(), |score| score.to_string());
+        format!("{freshness} · index {index} · upstream {upstream} · lint {lint}")
+    }
 }
 
 /// Source status indicator.
@@ -750,6 +889,47 @@ impl SourceSummary {
         self.descriptor = Some(descriptor);
         self
     }
+
+    /// Set the cached content size in bytes.
+    #[must_use]
+    pub const fn with_size_bytes(mut self, size_bytes: u64) -> Self {
+        self.size_bytes = size_bytes;
+        self
+    }
+
+    /// Set the recorded search count.
+    #[must_use]
+    pub const fn with_searches(mut self, searches: usize) -> Self {
+        self.searches = searches;
+        self
+    }
+
+    /// Set the recorded access timestamps.
+    ///
+    /// A source never checked since it was added reports its fetch time.
+    #[must_use]
+    pub fn with_access(mut self, access: &blz_core::SourceAccess) -> Self {
+        self.last_searched_at = access.last_searched.map(|at| at.to_rfc3339());
+        self.last_fetched_at = access
+            .last_fetched
+            .map(|at| at.to_rfc3339())
+            .or_else(|| self.fetched_at.clone());
+        self
+    }
+
+    /// Mark whether the source was generated.
+    #[must_use]
+    pub const fn with_generated(mut self, generated: bool) -> Self {
+        self.generated = generated;
+        self
+    }
+
+    /// Set the health indicators.
+    #[must_use]
+    pub fn with_health(mut self, health: SourceHealthBadges) -> Self {
+        self.health = health;
+        self
+    }
 }
 
 /// Detailed output for a single source.
@@ -774,6 +954,12 @@ pub struct SourceInfoOutput {
     /// Last updated timestamp (ISO 8601).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub last_updated: Option<String>,
+    /// Last search that returned results from this source (ISO 8601).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_searched: Option<String>,
+    /// Last check against upstream, changed or not (ISO 8601).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_fetched: Option<String>,
     /// HTTP `ETag` if available.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub etag: Option<String>,
@@ -785,6 +971,25 @@ pub struct SourceInfoOutput {
     /// Language filtering statistics.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub filter_stats: Option<FilterStatsOutput>,
+    /// Configured mirrors with their observed health.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mirrors: Vec<MirrorOutput>,
+}
+
+/// Mirror URL and health for source info output.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MirrorOutput {
+    /// Mirror URL.
+    pub url: String,
+    /// Smoothed fetch latency in milliseconds, once measured.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<u64>,
+    /// Last failed fetch (ISO 8601).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_failure: Option<String>,
+    /// Failures since the last success.
+    pub consecutive_failures: u32,
 }
 
 /// Language filtering statistics for source info output.
@@ -824,10 +1029,13 @@ impl SourceInfoOutput {
             headings,
             size_bytes,
             last_updated: None,
+            last_searched: None,
+            last_fetched: None,
             etag: None,
             checksum: None,
             cache_path: cache_path.into(),
             filter_stats: None,
+            mirrors: Vec::new(),
         }
     }
 
@@ -845,6 +1053,14 @@ impl SourceInfoOutput {
         self
     }
 
+    /// Set the recorded access timestamps.
+    #[must_use]
+    pub fn with_access(mut self, access: &blz_core::SourceAccess) -> Self {
+        self.last_searched = access.last_searched.map(|at| at.to_rfc3339());
+        self.last_fetched = access.last_fetched.map(|at| at.to_rfc3339());
+        self
+    }
+
     /// Set the `ETag`.
     #[must_use]
     pub fn with_etag(mut self, etag: impl Into<String>) -> Self {
@@ -865,6 +1081,13 @@ impl SourceInfoOutput {
         self.filter_stats = Some(stats);
         self
     }
+
+    /// Set mirror health.
+    #[must_use]
+    pub fn with_mirrors(mut self, mirrors: Vec<MirrorOutput>) -> Self {
+        self.mirrors = mirrors;
+        self
+    }
 }
 
 /// Output shape for validation/check results.
@@ -1066,15 +1289,19 @@ mod tests {
                 alias: "react".to_string(),
                 lines: "12-15".to_string(),
                 snippet: "useEffect example".to_string(),
+                snippet_language: None,
+                highlights: Vec::new(),
                 score: 95,
                 raw_score: Some(14.5),
                 heading_path: vec!["Hooks".to_string(), "useEffect".to_string()],
                 level: 2,
                 anchor: Some("use-effect".to_string()),
+                section_id: None,
                 source_url: None,
                 fetched_at: None,
                 is_stale: false,
                 checksum: "abc123".to_string(),
+                tokens: None,
                 context: None,
             }],
         )
@@ -1144,6 +1371,7 @@ mod tests {
             vec![
                 TocEntry {
                     level: 1,
+                    ordinal: String::new(),
                     title: "Getting Started".to_string(),
                     lines: "1-50".to_string(),
                     anchor: None,
@@ -1152,6 +1380,7 @@ mod tests {
                 },
                 TocEntry {
                     level: 2,
+                    ordinal: String::new(),
                     title: "Installation".to_string(),
                     lines: "10-30".to_string(),
                     anchor: Some("installation".to_string()),
@@ -1171,6 +1400,7 @@ mod tests {
             "docs",
             vec![TocEntry {
                 level: 1,
+                ordinal: String::new(),
                 title: "Root".to_string(),
                 lines: "1-100".to_string(),
                 anchor: None,
@@ -1178,12 +1408,14 @@ mod tests {
                 children: vec![
                     TocEntry {
                         level: 2,
+                        ordinal: String::new(),
                         title: "Child 1".to_string(),
                         lines: "10-50".to_string(),
                         anchor: None,
                         heading_path: vec!["Root".to_string(), "Child 1".to_string()],
                         children: vec![TocEntry {
                             level: 3,
+                            ordinal: String::new(),
                             title: "Grandchild".to_string(),
                             lines: "20-30".to_string(),
                             anchor: None,
@@ -1197,6 +1429,7 @@ mod tests {
                     },
                     TocEntry {
                         level: 2,
+                        ordinal: String::new(),
                         title: "Child 2".to_string(),
                         lines: "60-80".to_string(),
                         anchor: None,
@@ -1322,15 +1555,19 @@ mod tests {
             alias: "react".to_string(),
             lines: "12-15".to_string(),
             snippet: "useEffect example".to_string(),
+            snippet_language: None,
+            highlights: Vec::new(),
             score: 95,
             raw_score: None,
             heading_path: vec![],
             level: 0,
             anchor: None,
+            section_id: None,
             source_url: None,
             fetched_at: None,
             is_stale: false,
             checksum: "test123".to_string(),
+            tokens: None,
             context: Some(ContextInfo::new(5, "7-20").with_line_numbers((7..=20).collect())),
         };
 
@@ -1368,6 +1605,7 @@ mod tests {
                 raw_heading_path: vec!["hooks".to_string(), "use-effect".to_string()],
                 heading_path_normalized: vec!["hooks".to_string(), "useeffect".to_string()],
                 heading_level: 2,
+                ordinal: String::new(),
                 lines: "100-150".to_string(),
                 anchor: Some("use-effect".to_string()),
             },
@@ -1378,6 +1616,7 @@ mod tests {
                 raw_heading_path: vec![],
                 heading_path_normalized: vec![],
                 heading_level: 2,
+                ordinal: String::new(),
                 lines: "200-250".to_string(),
                 anchor: None,
             },
@@ -1403,6 +1642,7 @@ mod tests {
     fn test_toc_multi_output() {
         let react_entries = vec![TocEntry {
             level: 1,
+            ordinal: String::new(),
             title: "Getting Started".to_string(),
             lines: "1-50".to_string(),
             anchor: None,
@@ -1412,6 +1652,7 @@ mod tests {
 
         let bun_entries = vec![TocEntry {
             level: 1,
+            ordinal: String::new(),
             title: "Installation".to_string(),
             lines: "1-30".to_string(),
             anchor: Some("installation".to_string()),
@@ -31,9 +31,13 @@
 use anyhow::{Context, Result};
 use futures::{Stream, StreamExt};
 use serde::Serialize;
-use std::io::Write;
+use std::fmt;
+use std::io::{self, StdoutLock, Write};
 use tokio::io::{AsyncWriteExt, BufWriter};
 
+/// Lines [`PipeWriter`] buffers before flushing.
+pub const FLUSH_EVERY_LINES: usize = 256;
+
 /// Configuration for streaming output.
 #[derive(Clone, Debug)]
 pub struct StreamConfig {
@@ -206,6 +210,116 @@ where
     output_stream_jsonl_sync(items, writer)
 }
 
+/// Buffered, pipe-aware writer for large line-oriented output.
+///
+/// Flushes every [`FLUSH_EVERY_LINES`] lines so consumers see output while it
+/// is produced, and blocking writes give natural backpressure. A closed pipe
+/// (`blz get big:1-200000 | head -50`) ends the output instead of failing:
+/// later writes are skipped and [`Self::is_closed`] tells callers to stop.
+pub struct PipeWriter<W: Write> {
+    inner: io::BufWriter<W>,
+    pending: usize,
+    closed: bool,
+}
+
+impl PipeWriter<StdoutLock<'static>> {
+    /// Writer over locked stdout.
+    pub fn stdout() -> Self {
+        Self::new(io::stdout().lock())
+    }
+}
+
+impl<W: Write> PipeWriter<W> {
+    /// Wrap `inner` in a buffered writer.
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner: io::BufWriter::new(inner),
+            pending: 0,
+            closed: false,
+        }
+    }
+
+    /// Whether the reader has gone away.
+    pub const fn is_closed(&self) -> bool {
+        self.closed
+    }
+
+    /// Write one line, flushing periodically.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails for any reason other than a closed pipe.
+    pub fn line(&mut self, args: fmt::Arguments<'_>) -> Result<()> {
+        if self.closed {
+            return Ok(());
+        }
+        let result = self
+            .inner
+            .write_fmt(args)
+            .and_then(|()| self.inner.write_all(b"\n"));
+        self.settle(result)?;
+        self.pending += 1;
+        if self.pending >= FLUSH_EVERY_LINES {
+            self.pending = 0;
+            let result = self.inner.flush();
+            self.settle(result)?;
+        }
+        Ok(())
+    }
+
+    /// Serialize `value` as JSON followed by a newline.
+    ///
+    /// The value is written as it is serialized, so output starts before the
+    /// whole document is rendered.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization or writing fails for any reason other
+    /// than a closed pipe.
+    pub fn json<T: Serialize>(&mut self, value: &T, pretty: bool) -> Result<()> {
+        if self.closed {
+            return Ok(());
+        }
+        let written = if pretty {
+            serde_json::to_writer_pretty(&mut self.inner, value)
+        } else {
+            serde_json::to_writer(&mut self.inner, value)
+        };
+        if let Err(e) = written {
+            if e.io_error_kind() == Some(io::ErrorKind::BrokenPipe) {
+                self.closed = true;
+                return Ok(());
+            }
+            return Err(e).context("failed to write JSON to stdout");
+        }
+        self.line(format_args!(""))
+    }
+
+    /// Flush any buffered output.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if flushing fails for any reason other than a closed pipe.
+    pub fn finish(mut self) -> Result<()> {
+        if self.closed {
+            return Ok(());
+        }
+        let result = self.inner.flush();
+        self.settle(result)
+    }
+
+    /// Record a closed pipe, or surface any other write error.
+    fn settle(&mut self, result: io::Result<()>) -> Result<()> {
+        match result {
+            Err(e) if e.kind() == io::ErrorKind::BrokenPipe => {
+                self.closed = true;
+                Ok(())
+            },
+            other => other.context("failed to write to stdout"),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -272,6 +386,79 @@ mod tests {
         assert!(output.is_empty());
     }
 
+    /// Accepts a fixed number of bytes, then reports a closed pipe.
+    struct ClosingPipe {
+        accepted: Vec<u8>,
+        capacity: usize,
+    }
+
+    impl Write for ClosingPipe {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            if self.accepted.len() >= self.capacity {
+                return Err(io::ErrorKind::BrokenPipe.into());
+            }
+            let take = buf.len().min(self.capacity - self.accepted.len());
+            self.accepted.extend_from_slice(&buf[..take]);
+            Ok(take)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_pipe_writer_stops_quietly_when_reader_closes() {
+        let pipe = ClosingPipe {
+            accepted: Vec::new(),
+            capacity: 64,
+        };
+        let mut writer = PipeWriter::new(pipe);
+        let mut written = 0;
+        for n in 0..100_000 {
+            writer
+                .line(format_args!("line {n}"))
+                .expect("closed pipe is not an error");
+            if writer.is_closed() {
+                break;
+            }
+            written += 1;
+        }
+        assert!(writer.is_closed());
+        assert!(written < 100_000);
+        writer
+            .json(
+                &TestItem {
+                    id: 1,
+                    name: "late".into(),
+                },
+                false,
+            )
+            .expect("writes after close are skipped");
+        writer.finish().expect("finish after close succeeds");
+    }
+
+    #[test]
+    fn test_pipe_writer_writes_lines_and_json() {
+        let mut output = Vec::new();
+        let mut writer = PipeWriter::new(&mut output);
+        writer
+            .line(format_args!("{:>5} | {}", 1, "hello"))
+            .expect("line");
+        writer
+            .json(
+                &TestItem {
+                    id: 2,
+                    name: "json".into(),
+                },
+                false,
+            )
+            .expect("json");
+        writer.finish().expect("finish");
+        let text = String::from_utf8(output).expect("utf-8");
+        assert_eq!(text, "    1 | hello\n{\"id\":2,\"name\":\"json\"}\n");
+    }
+
     #[tokio::test]
     async fn test_async_jsonl_stream() {
         use futures::stream;
@@ -6,6 +6,7 @@ use blz_core::{SearchHit, Storage};
 use colored::Colorize;
 use std::collections::{BTreeSet, HashMap};
 
+use crate::i18n::{self, Message};
 use crate::utils::formatting::{format_heading_path, get_alias_color, terminal_width};
 
 const PATH_PREFIX_WIDTH: usize = 5; // "  in "
@@ -18,7 +19,10 @@ impl TextFormatter {
     /// Format search results in the brief, colorized layout
     pub fn format_search_results(params: &FormatParams) {
         if params.hits.is_empty() {
-            println!("No results found for '{}'", params.query);
+            println!(
+                "{}",
+                i18n::text(Message::NoResults, &[("query", params.query)])
+            );
             return;
         }
 
@@ -418,15 +422,15 @@ fn parse_line_range(s: &str) -> (usize, usize) {
 }
 
 fn load_llms_lines(storage: &Storage, alias: &str) -> Vec<String> {
-    if let Ok(path) = storage.llms_txt_path(alias) {
-        if let Ok(content) = std::fs::read_to_string(path) {
-            return content
+    storage
+        .load_llms_txt(alias)
+        .map(|content| {
+            content
                 .lines()
                 .map(std::string::ToString::to_string)
-                .collect();
-        }
-    }
-    Vec::new()
+                .collect()
+        })
+        .unwrap_or_default()
 }
 
 fn collect_candidate_indices(
@@ -102,13 +102,18 @@ fn normalize_target(target: &str, command: Option<&Commands>) -> String {
                 Commands::ClaudePlugin { .. } => "claude-plugin".into(),
                 Commands::Registry { .. } => "registry".into(),
                 Commands::Search(_) => "search".into(),
-                Commands::Instruct => "blz".into(),
+                Commands::Instruct | Commands::Default(_) => "blz".into(),
                 Commands::Add(_) => "add".into(),
                 Commands::Query(_) => "query".into(),
                 Commands::Map(_) => "map".into(),
                 Commands::Sync(_) => "sync".into(),
+                Commands::Watch(_) => "watch".into(),
+                Commands::Generate(_) => "generate".into(),
+                Commands::Discover(_) => "discover".into(),
                 Commands::Check(_) => "check".into(),
                 Commands::Rm(_) => "rm".into(),
+                Commands::Export(_) => "export".into(),
+                Commands::Import(_) => "import".into(),
                 #[allow(deprecated)]
                 Commands::Refresh { .. } | Commands::Update { .. } => "refresh".into(),
                 #[allow(deprecated)]
@@ -120,10 +125,27 @@ fn normalize_target(target: &str, command: Option<&Commands>) -> String {
                 Commands::Lookup { .. } => "lookup".into(),
                 Commands::History { .. } => "history".into(),
                 Commands::Info { .. } => "info".into(),
+                Commands::Which { .. } => "which".into(),
                 Commands::Stats { .. } => "stats".into(),
                 #[allow(deprecated)]
                 Commands::Validate { .. } => "validate".into(),
                 Commands::Doctor { .. } => "doctor".into(),
+                Commands::Bench(_) => "bench".into(),
+                Commands::Explain(_) => "explain".into(),
+                Commands::ExplainSource(_) => "explain-source".into(),
+                Commands::Terms(_) => "terms".into(),
+                Commands::Filter { .. } => "filter".into(),
+                Commands::Profile { .. } => "profile".into(),
+                Commands::Init(_) => "init".into(),
+                Commands::Demo(_) => "demo".into(),
+                Commands::Fixtures(_) => "fixtures".into(),
+                Commands::VerifyInstall(_) => "verify-install".into(),
+                Commands::Serve(_) => "serve".into(),
+                Commands::Du { .. } => "du".into(),
+                Commands::Gc(_) => "gc".into(),
+                Commands::DebugArgs { .. } => "debug-args".into(),
+                Commands::Ps { .. } => "ps".into(),
+                Commands::Kill { .. } => "kill".into(),
                 Commands::Clear { .. } => "clear".into(),
                 Commands::Diff { .. } => "diff".into(),
                 Commands::McpServer => "mcp".into(),
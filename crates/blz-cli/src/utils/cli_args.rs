@@ -77,6 +77,17 @@ pub struct FormatArg {
 }
 
 impl FormatArg {
+    /// Whether a format was chosen by flag or `BLZ_OUTPUT_FORMAT`.
+    #[must_use]
+    pub const fn is_explicit(&self) -> bool {
+        self.format.is_some()
+            || self.deprecated_output.is_some()
+            || self.json
+            || self.jsonl
+            || self.text
+            || self.raw
+    }
+
     /// Returns the effective output format, preferring the canonical flag and falling back to
     /// the deprecated alias when necessary.
     ///
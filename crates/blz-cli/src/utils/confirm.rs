@@ -0,0 +1,133 @@
+//! Shared confirmation for guarded operations.
+//!
+//! Commands that delete data or write to the registry ask through [`confirm`],
+//! so `--yes`, `--no-input`, terminal detection, and the `defaults.confirm`
+//! policy behave the same everywhere.
+
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use anyhow::Result;
+use blz_core::{Config, ConfirmPolicy};
+use inquire::Confirm;
+
+static NO_INPUT: AtomicBool = AtomicBool::new(false);
+
+/// What a guarded operation puts at stake.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Risk {
+    /// Deletes or replaces local data (sources, cache, history, registry entries).
+    Destructive,
+    /// Changes state without losing anything, such as adding a registry entry.
+    Routine,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Decision {
+    Proceed,
+    Prompt,
+    Refuse,
+}
+
+/// Record the global `--no-input` flag.
+pub fn set_no_input(no_input: bool) {
+    NO_INPUT.store(no_input, Ordering::Relaxed);
+}
+
+/// Whether prompts can be shown: stdin is a terminal and `--no-input` is off.
+pub fn is_interactive() -> bool {
+    !NO_INPUT.load(Ordering::Relaxed) && std::io::stdin().is_terminal()
+}
+
+/// Ask before running a guarded operation.
+///
+/// Returns `Ok(true)` without prompting when `yes` is set, when
+/// `BLZ_FORCE_NON_INTERACTIVE` is set, or when the configured policy does not
+/// cover `risk`. Returns `Ok(false)` when the user declines.
+///
+/// # Errors
+///
+/// Returns an error when confirmation is required but no prompt can be shown
+/// (no terminal, or `--no-input`), or when the prompt itself fails.
+pub fn confirm(prompt: &str, risk: Risk, yes: bool) -> Result<bool> {
+    let policy = Config::load()
+        .ok()
+        .and_then(|config| config.defaults.confirm)
+        .unwrap_or_default();
+    let force_non_interactive = std::env::var_os("BLZ_FORCE_NON_INTERACTIVE").is_some();
+
+    match decide(policy, risk, yes || force_non_interactive, is_interactive()) {
+        Decision::Proceed => Ok(true),
+        Decision::Prompt => Ok(Confirm::new(prompt).with_default(false).prompt()?),
+        Decision::Refuse => anyhow::bail!(
+            "Confirmation required: {prompt}\nRe-run with --yes to proceed without prompting"
+        ),
+    }
+}
+
+const fn decide(policy: ConfirmPolicy, risk: Risk, yes: bool, interactive: bool) -> Decision {
+    let required = match policy {
+        ConfirmPolicy::Always => true,
+        ConfirmPolicy::Destructive => matches!(risk, Risk::Destructive),
+        ConfirmPolicy::Never => false,
+    };
+    if yes || !required {
+        Decision::Proceed
+    } else if interactive {
+        Decision::Prompt
+    } else {
+        Decision::Refuse
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn yes_always_proceeds() {
+        for policy in [
+            ConfirmPolicy::Always,
+            ConfirmPolicy::Destructive,
+            ConfirmPolicy::Never,
+        ] {
+            assert_eq!(
+                decide(policy, Risk::Destructive, true, false),
+                Decision::Proceed
+            );
+        }
+    }
+
+    #[test]
+    fn default_policy_only_guards_destructive_operations() {
+        let policy = ConfirmPolicy::default();
+        assert_eq!(
+            decide(policy, Risk::Destructive, false, true),
+            Decision::Prompt
+        );
+        assert_eq!(
+            decide(policy, Risk::Routine, false, true),
+            Decision::Proceed
+        );
+    }
+
+    #[test]
+    fn always_and_never_policies() {
+        assert_eq!(
+            decide(ConfirmPolicy::Always, Risk::Routine, false, true),
+            Decision::Prompt
+        );
+        assert_eq!(
+            decide(ConfirmPolicy::Never, Risk::Destructive, false, false),
+            Decision::Proceed
+        );
+    }
+
+    #[test]
+    fn refuses_without_terminal() {
+        assert_eq!(
+            decide(ConfirmPolicy::Destructive, Risk::Destructive, false, false),
+            Decision::Refuse
+        );
+    }
+}
@@ -95,8 +95,25 @@ pub const RESERVED_KEYWORDS: &[&str] = &[
     "start",
     "stop",
     "status",
+    "bench",
+    "verify-install",
+    "explain",
+    "explain-source",
+    "terms",
+    "filter",
+    "profile",
+    "init",
+    "fixtures",
+    "du",
+    "gc",
+    "debug-args",
+    "ps",
+    "kill",
     // Operations
     "sync",
+    "watch",
+    "generate",
+    "discover",
     "export",
     "import",
     "backup",
@@ -2,7 +2,8 @@
 
 use colored::Colorize;
 use terminal_size::{Width, terminal_size};
-use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
 
 /// Color cycling functions for aliases
 pub const ALIAS_COLORS: &[fn(&str) -> colored::ColoredString] = &[
@@ -198,26 +199,26 @@ fn truncate_to_width<S: AsRef<str>>(segment: S, max_width: usize) -> String {
         return ELLIPSIS[..max_width].to_string();
     }
 
+    // Cut between grapheme clusters so emoji sequences and combining marks
+    // stay intact
     let ellipsis_width = ELLIPSIS.len();
-    let mut collected = Vec::new();
+    let mut end = 0usize;
     let mut current_width = 0usize;
 
-    for ch in segment_str.chars() {
-        let char_width = UnicodeWidthChar::width(ch).unwrap_or(0);
-        if current_width + char_width + ellipsis_width > max_width {
+    for (offset, grapheme) in segment_str.grapheme_indices(true) {
+        let grapheme_width = UnicodeWidthStr::width(grapheme);
+        if current_width + grapheme_width + ellipsis_width > max_width {
             break;
         }
-        collected.push(ch);
-        current_width = current_width.saturating_add(char_width);
+        end = offset + grapheme.len();
+        current_width = current_width.saturating_add(grapheme_width);
     }
 
-    if collected.is_empty() {
-        return ELLIPSIS[..max_width].to_string();
+    if end == 0 {
+        return ELLIPSIS.to_string();
     }
 
-    let mut result: String = collected.into_iter().collect();
-    result.push_str(ELLIPSIS);
-    result
+    format!("{}{ELLIPSIS}", &segment_str[..end])
 }
 
 #[cfg(test)]
@@ -225,6 +226,9 @@ mod tests {
     use super::{
         ELLIPSIS, build_components, components_width, format_heading_path, truncate_to_width,
     };
+    use proptest::prelude::*;
+    use unicode_segmentation::UnicodeSegmentation;
+    use unicode_width::UnicodeWidthStr;
 
     fn strip_ansi_codes(input: &str) -> String {
         let mut output = String::new();
@@ -313,4 +317,33 @@ mod tests {
         let pieces = build_components(&["A", "B", "C"], 1);
         assert_eq!(components_width(&pieces), "A > B > C".len());
     }
+
+    #[test]
+    fn truncate_keeps_grapheme_clusters_whole() {
+        let family = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}";
+        let text = format!("{family}{family}{family}{family}");
+        assert_eq!(truncate_to_width(&text, 7), format!("{family}{family}..."));
+        assert_eq!(truncate_to_width("cafe\u{301} bar", 7), "cafe\u{301}...");
+    }
+
+    proptest! {
+        #[test]
+        fn truncate_fits_width_and_cuts_on_grapheme_boundaries(
+            segment in "(\\PC|\u{301}|\u{200D}|\u{1F468}|\u{4E16}){0,40}",
+            max_width in 0usize..40,
+        ) {
+            let truncated = truncate_to_width(&segment, max_width);
+            prop_assert!(UnicodeWidthStr::width(truncated.as_str()) <= max_width);
+
+            if UnicodeWidthStr::width(segment.as_str()) <= max_width {
+                prop_assert_eq!(&truncated, &segment);
+            } else if let Some(kept) = truncated.strip_suffix(ELLIPSIS) {
+                prop_assert!(segment.starts_with(kept));
+                prop_assert!(
+                    kept.is_empty()
+                        || segment.grapheme_indices(true).any(|(offset, _)| offset == kept.len())
+                );
+            }
+        }
+    }
 }
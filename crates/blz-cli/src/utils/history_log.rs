@@ -3,40 +3,128 @@ use std::fs::{self, OpenOptions};
 use std::io::{BufRead, BufReader, BufWriter, Write};
 use std::path::PathBuf;
 
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use tracing::warn;
 
-use crate::utils::preferences::{SearchHistoryEntry, active_scope_key};
-use crate::utils::store;
+use crate::utils::preferences::{
+    SearchHistoryEntry, SectionHistoryEntry, TocHistoryEntry, active_scope_key,
+};
+use crate::utils::store::{self, FileIntegrity};
 
 use fs2::FileExt;
 
 const HISTORY_FILENAME: &str = "history.jsonl";
 const MAX_HISTORY_ENTRIES: usize = 50;
 
+/// Where a resumable command left off.
+///
+/// `--next`, `--previous`, and `--last` resume from the latest continuation
+/// of their command's kind in the active scope.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum Continuation {
+    /// A page of search results.
+    Search(SearchHistoryEntry),
+    /// A page of `blz map` output.
+    Map(TocHistoryEntry),
+    /// Lines printed by `blz get`, for `--next-section`/`--previous-section`.
+    Section(SectionHistoryEntry),
+}
+
+impl Continuation {
+    /// Name of the continuation kind, as stored in the log.
+    const fn kind(&self) -> &'static str {
+        match self {
+            Self::Search(_) => "search",
+            Self::Map(_) => "map",
+            Self::Section(_) => "section",
+        }
+    }
+
+    /// When the command ran (RFC 3339).
+    fn timestamp(&self) -> &str {
+        match self {
+            Self::Search(entry) => &entry.timestamp,
+            Self::Map(entry) => &entry.timestamp,
+            Self::Section(entry) => &entry.timestamp,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 struct HistoryRecord {
+    scope: String,
+    #[serde(flatten)]
+    continuation: Continuation,
+}
+
+/// Search record written before continuations were tagged with a kind.
+#[derive(Deserialize)]
+struct LegacyRecord {
     scope: String,
     #[serde(flatten)]
     entry: SearchHistoryEntry,
 }
 
+fn parse_record(raw: &str) -> serde_json::Result<HistoryRecord> {
+    serde_json::from_str::<HistoryRecord>(raw).or_else(|err| {
+        serde_json::from_str::<LegacyRecord>(raw)
+            .map(|legacy| HistoryRecord {
+                scope: legacy.scope,
+                continuation: Continuation::Search(legacy.entry),
+            })
+            .map_err(|_| err)
+    })
+}
+
 /// Append a search history entry to the scoped history log.
 ///
 /// # Errors
 ///
 /// Returns an error if the history file cannot be created, locked, or written.
 pub fn append(entry: &SearchHistoryEntry) -> std::io::Result<()> {
+    record(Continuation::Search(entry.clone()))
+}
+
+/// Record a continuation for the active scope.
+///
+/// # Errors
+///
+/// Returns an error if the history file cannot be created, locked, or written.
+pub fn record(continuation: Continuation) -> std::io::Result<()> {
     let scope = active_scope_key();
     let mut records = load_all();
     records.push(HistoryRecord {
         scope,
-        entry: entry.clone(),
+        continuation,
     });
     prune_records(&mut records);
     write_all(&records)
 }
 
+/// Return the latest continuation in the active scope accepted by `select`.
+pub fn latest<T>(select: impl Fn(Continuation) -> Option<T>) -> Option<T> {
+    let scope = active_scope_key();
+    load_all()
+        .into_iter()
+        .rev()
+        .filter(|record| record.scope == scope)
+        .find_map(|record| select(record.continuation))
+}
+
+/// Search entries of a record list, dropping other continuation kinds.
+fn searches(
+    records: Vec<HistoryRecord>,
+) -> impl DoubleEndedIterator<Item = (String, SearchHistoryEntry)> {
+    records
+        .into_iter()
+        .filter_map(|record| match record.continuation {
+            Continuation::Search(entry) => Some((record.scope, entry)),
+            _ => None,
+        })
+}
+
 /// Return recent history entries for the active scope.
 pub fn recent_for_active_scope(limit: usize) -> Vec<SearchHistoryEntry> {
     recent_for_scope(&active_scope_key(), limit)
@@ -44,16 +132,120 @@ pub fn recent_for_active_scope(limit: usize) -> Vec<SearchHistoryEntry> {
 
 /// Return recent history entries for a specific scope.
 pub fn recent_for_scope(scope: &str, limit: usize) -> Vec<SearchHistoryEntry> {
-    let records = load_all();
-    records
-        .into_iter()
-        .filter(|record| record.scope == scope)
-        .map(|record| record.entry)
+    searches(load_all())
+        .filter(|(record_scope, _)| record_scope == scope)
+        .map(|(_, entry)| entry)
         .rev()
         .take(limit)
         .collect()
 }
 
+/// Count recorded searches per source across all scopes.
+///
+/// Searches that targeted several sources count once for each of them; searches
+/// without an explicit source are not attributed. The log is bounded, so counts
+/// reflect recent activity rather than all-time usage.
+pub fn search_counts_by_source() -> HashMap<String, usize> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for (_, entry) in searches(load_all()) {
+        let Some(sources) = entry.source else {
+            continue;
+        };
+        for alias in sources.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+            *counts.entry(alias.to_string()).or_default() += 1;
+        }
+    }
+    counts
+}
+
+/// Time of the latest recorded search that returned hits from each source.
+///
+/// Entries written before hit sources were tracked fall back to their explicit
+/// `--source` when the search had results. Like [`search_counts_by_source`],
+/// this only sees the searches the bounded log still retains.
+pub fn last_hit_by_source() -> HashMap<String, DateTime<Utc>> {
+    let mut last_hits: HashMap<String, DateTime<Utc>> = HashMap::new();
+    for (_, entry) in searches(load_all()) {
+        let Ok(timestamp) = DateTime::parse_from_rfc3339(&entry.timestamp) else {
+            continue;
+        };
+        let timestamp = timestamp.with_timezone(&Utc);
+        let sources = if entry.hit_sources.is_empty() {
+            entry
+                .source
+                .filter(|_| entry.total_results.unwrap_or(0) > 0)
+                .map(|sources| {
+                    sources
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|s| !s.is_empty())
+                        .map(str::to_string)
+                        .collect()
+                })
+                .unwrap_or_default()
+        } else {
+            entry.hit_sources
+        };
+        for alias in sources {
+            let last = last_hits.entry(alias).or_insert(timestamp);
+            *last = (*last).max(timestamp);
+        }
+    }
+    last_hits
+}
+
+/// Check the history log for lines that cannot be parsed (e.g. truncated writes).
+pub fn inspect() -> FileIntegrity {
+    let path = history_path();
+    let file = match OpenOptions::new().read(true).open(&path) {
+        Ok(file) => file,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return FileIntegrity::Missing,
+        Err(err) => return FileIntegrity::Corrupted(format!("unreadable: {err}")),
+    };
+
+    let mut total = 0usize;
+    let mut invalid = 0usize;
+    for line in BufReader::new(file).lines() {
+        match line {
+            Ok(raw) if raw.trim().is_empty() => {},
+            Ok(raw) => {
+                total += 1;
+                if parse_record(&raw).is_err() {
+                    invalid += 1;
+                }
+            },
+            Err(_) => {
+                total += 1;
+                invalid += 1;
+            },
+        }
+    }
+
+    if invalid == 0 {
+        FileIntegrity::Healthy
+    } else {
+        FileIntegrity::Corrupted(format!(
+            "{invalid} of {total} lines are not valid history records"
+        ))
+    }
+}
+
+/// Back up a corrupted history log and rewrite it with only the valid records.
+///
+/// Returns the backup path when a repair was performed.
+///
+/// # Errors
+///
+/// Returns an error if the backup or rewritten log cannot be written.
+pub fn repair() -> std::io::Result<Option<PathBuf>> {
+    if !matches!(inspect(), FileIntegrity::Corrupted(_)) {
+        return Ok(None);
+    }
+    let backup = store::backup_file(&history_path())?;
+    write_all(&load_all())?;
+    Ok(Some(backup))
+}
+
 fn load_all() -> Vec<HistoryRecord> {
     let path = history_path();
     let file = match OpenOptions::new().read(true).open(&path) {
@@ -69,14 +261,12 @@ fn load_all() -> Vec<HistoryRecord> {
     reader
         .lines()
         .filter_map(|line| match line {
-            Ok(raw) if !raw.trim().is_empty() => {
-                match serde_json::from_str::<HistoryRecord>(&raw) {
-                    Ok(record) => Some(record),
-                    Err(err) => {
-                        warn!("failed to parse history record: {err}");
-                        None
-                    },
-                }
+            Ok(raw) if !raw.trim().is_empty() => match parse_record(&raw) {
+                Ok(record) => Some(record),
+                Err(err) => {
+                    warn!("failed to parse history record: {err}");
+                    None
+                },
             },
             _ => None,
         })
@@ -139,10 +329,14 @@ fn write_all(records: &[HistoryRecord]) -> std::io::Result<()> {
     Ok(())
 }
 
+/// Keep the newest `MAX_HISTORY_ENTRIES` records of each kind in each scope.
 fn prune_records(records: &mut Vec<HistoryRecord>) {
-    let mut per_scope: HashMap<String, Vec<usize>> = HashMap::new();
+    let mut per_scope: HashMap<(String, &'static str), Vec<usize>> = HashMap::new();
     for (idx, record) in records.iter().enumerate() {
-        per_scope.entry(record.scope.clone()).or_default().push(idx);
+        per_scope
+            .entry((record.scope.clone(), record.continuation.kind()))
+            .or_default()
+            .push(idx);
     }
 
     let mut indices_to_remove = Vec::new();
@@ -190,9 +384,10 @@ pub fn clear_before(cutoff: &chrono::DateTime<chrono::Utc>) -> std::io::Result<(
 
     // Filter out records before the cutoff date
     records.retain(|record| {
-        chrono::DateTime::parse_from_rfc3339(&record.entry.timestamp).map_or(true, |timestamp| {
-            timestamp.with_timezone(&chrono::Utc) >= *cutoff
-        })
+        chrono::DateTime::parse_from_rfc3339(record.continuation.timestamp())
+            .map_or(true, |timestamp| {
+                timestamp.with_timezone(&chrono::Utc) >= *cutoff
+            })
     });
 
     write_all(&records)
@@ -239,6 +434,7 @@ mod tests {
             total_pages: Some(1),
             total_results: Some(5),
             headings_only: false,
+            hit_sources: vec![],
         }
     }
 
@@ -270,4 +466,61 @@ mod tests {
             Ok(())
         })
     }
+
+    #[test]
+    fn continuations_are_typed_and_legacy_searches_still_load() -> std::io::Result<()> {
+        with_temp_history(|| {
+            // Search records written before continuations carried a kind
+            let mut legacy = serde_json::to_value(sample_entry("legacy"))?;
+            legacy["scope"] = active_scope_key().into();
+            fs::create_dir_all(history_path().parent().unwrap())?;
+            fs::write(history_path(), format!("{legacy}\n"))?;
+
+            record(Continuation::Section(SectionHistoryEntry {
+                timestamp: "1970-01-01T00:00:01Z".to_string(),
+                source: "alias".to_string(),
+                line_start: 3,
+                line_end: 9,
+                at: None,
+            }))?;
+
+            assert_eq!(inspect(), FileIntegrity::Healthy);
+            let searches = recent_for_active_scope(5);
+            assert_eq!(searches.len(), 1);
+            assert_eq!(searches[0].query, "legacy");
+            let section = latest(|c| match c {
+                Continuation::Section(entry) => Some(entry),
+                _ => None,
+            })
+            .expect("section continuation");
+            assert_eq!((section.line_start, section.line_end), (3, 9));
+            assert!(
+                latest(|c| match c {
+                    Continuation::Map(entry) => Some(entry),
+                    _ => None,
+                })
+                .is_none()
+            );
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn repair_drops_invalid_lines_and_keeps_backup() -> std::io::Result<()> {
+        with_temp_history(|| {
+            append(&sample_entry("kept"))?;
+            let path = history_path();
+            let mut contents = fs::read_to_string(&path)?;
+            contents.push_str("{\"scope\":\"global\",\"quer\n");
+            fs::write(&path, contents)?;
+
+            assert!(matches!(inspect(), FileIntegrity::Corrupted(_)));
+
+            let backup = repair()?.expect("repair should run");
+            assert!(backup.exists());
+            assert_eq!(inspect(), FileIntegrity::Healthy);
+            assert_eq!(recent_for_active_scope(5)[0].query, "kept");
+            Ok(())
+        })
+    }
 }
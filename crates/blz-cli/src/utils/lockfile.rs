@@ -0,0 +1,229 @@
+//! `blz.lock`: pinned content for a project's documentation set.
+//!
+//! The lockfile sits next to the project's source manifest and records, for
+//! each source, the resolved URL it was fetched from, the SHA-256 of the
+//! fetched content, and when it was fetched:
+//!
+//! ```toml
+//! # blz.lock
+//! version = 1
+//!
+//! [[source]]
+//! alias = "react"
+//! url = "https://react.dev/llms-full.txt"
+//! sha256 = "q1w2e3..."
+//! fetchedAt = "2026-01-05T12:00:00Z"
+//! ```
+//!
+//! `blz add --manifest` and `blz init` write it, and `blz sync` refreshes the
+//! entries of a lockfile found in the working directory or any parent.
+//! `blz sync --frozen` leaves it untouched and fails when upstream content no
+//! longer matches it.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use blz_core::{Source, Storage};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// File name of the lockfile.
+pub const LOCKFILE_NAME: &str = "blz.lock";
+
+/// Lockfile schema version written by this build.
+const LOCKFILE_VERSION: u32 = 1;
+
+const HEADER: &str = "# Generated by blz; commit this file. Update it with `blz sync`.\n\n";
+
+/// Parsed contents of a `blz.lock`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Lockfile {
+    /// Schema version.
+    pub version: u32,
+    /// Locked sources, sorted by alias.
+    #[serde(rename = "source", default)]
+    pub sources: Vec<LockedSource>,
+}
+
+/// The pinned state of one source.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LockedSource {
+    /// Canonical source alias.
+    pub alias: String,
+    /// Resolved URL (or local path) the content was fetched from.
+    pub url: String,
+    /// SHA-256 of the fetched content, as stored in source metadata.
+    pub sha256: String,
+    /// When the content was fetched.
+    pub fetched_at: DateTime<Utc>,
+}
+
+impl LockedSource {
+    /// Pin the cached state of `alias`.
+    #[must_use]
+    pub fn from_metadata(alias: &str, source: &Source) -> Self {
+        Self {
+            alias: alias.to_string(),
+            url: source.url.clone(),
+            sha256: source.sha256.clone(),
+            fetched_at: source.fetched_at,
+        }
+    }
+}
+
+impl Default for Lockfile {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Lockfile {
+    /// An empty lockfile.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            version: LOCKFILE_VERSION,
+            sources: Vec::new(),
+        }
+    }
+
+    /// Read a lockfile.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or parsed, or was written by
+    /// a newer blz.
+    pub fn load(path: &Path) -> Result<Self> {
+        let raw = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let lock: Self =
+            toml::from_str(&raw).with_context(|| format!("Failed to parse {}", path.display()))?;
+        if lock.version > LOCKFILE_VERSION {
+            bail!(
+                "{} uses lockfile version {}; this blz understands up to {LOCKFILE_VERSION}",
+                path.display(),
+                lock.version
+            );
+        }
+        Ok(lock)
+    }
+
+    /// Read `path` if it exists, or start an empty lockfile.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an existing file cannot be read or parsed.
+    pub fn load_or_default(path: &Path) -> Result<Self> {
+        if path.exists() {
+            Self::load(path)
+        } else {
+            Ok(Self::new())
+        }
+    }
+
+    /// Write the lockfile with entries sorted by alias.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be serialized or written.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let mut sorted = self.clone();
+        sorted.sources.sort_by(|a, b| a.alias.cmp(&b.alias));
+        let body = toml::to_string(&sorted).context("Failed to serialize lockfile")?;
+        fs::write(path, format!("{HEADER}{body}"))
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// The entry for `alias`, if locked.
+    #[must_use]
+    pub fn get(&self, alias: &str) -> Option<&LockedSource> {
+        self.sources.iter().find(|entry| entry.alias == alias)
+    }
+
+    /// Pin the cached state of each of `aliases`, replacing existing entries.
+    ///
+    /// Aliases without cached metadata are skipped. Returns whether any entry
+    /// was added or changed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if source metadata cannot be read.
+    pub fn record(&mut self, storage: &Storage, aliases: &[String]) -> Result<bool> {
+        let mut changed = false;
+        for alias in aliases {
+            let Some(source) = storage.load_source_metadata(alias)? else {
+                continue;
+            };
+            let entry = LockedSource::from_metadata(alias, &source);
+            match self.sources.iter_mut().find(|e| e.alias == *alias) {
+                Some(existing) if *existing == entry => {},
+                Some(existing) => {
+                    *existing = entry;
+                    changed = true;
+                },
+                None => {
+                    self.sources.push(entry);
+                    changed = true;
+                },
+            }
+        }
+        Ok(changed)
+    }
+}
+
+/// The nearest `blz.lock` in `start` or any parent directory.
+#[must_use]
+pub fn find(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .map(|dir| dir.join(LOCKFILE_NAME))
+        .find(|path| path.is_file())
+}
+
+/// The lockfile `path` names, or the nearest one to the working directory.
+#[must_use]
+pub fn locate(path: Option<&Path>) -> Option<PathBuf> {
+    path.map_or_else(
+        || std::env::current_dir().ok().and_then(|cwd| find(&cwd)),
+        |path| Some(path.to_path_buf()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_sorted_entries() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join(LOCKFILE_NAME);
+        let entry = |alias: &str| LockedSource {
+            alias: alias.to_string(),
+            url: format!("https://{alias}.dev/llms.txt"),
+            sha256: "abc123".to_string(),
+            fetched_at: DateTime::UNIX_EPOCH,
+        };
+        let mut lock = Lockfile::new();
+        lock.sources = vec![entry("vite"), entry("react")];
+        lock.save(&path)?;
+
+        let loaded = Lockfile::load(&path)?;
+        let aliases: Vec<&str> = loaded.sources.iter().map(|e| e.alias.as_str()).collect();
+        assert_eq!(aliases, ["react", "vite"]);
+        assert_eq!(loaded.get("vite"), Some(&entry("vite")));
+        assert_eq!(find(dir.path()), Some(path));
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_newer_versions() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join(LOCKFILE_NAME);
+        fs::write(&path, "version = 99\n")?;
+        assert!(Lockfile::load(&path).is_err());
+        Ok(())
+    }
+}
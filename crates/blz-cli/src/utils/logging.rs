@@ -42,13 +42,22 @@ pub fn initialize_logging(cli: &Cli) -> Result<()> {
                 | Commands::Lookup { format, .. }
                 | Commands::Get { format, .. }
                 | Commands::Info { format, .. }
-                | Commands::Completions { format, .. },
+                | Commands::Which { format, .. }
+                | Commands::Completions { format, .. }
+                | Commands::Du { format, .. }
+                | Commands::DebugArgs { format, .. }
+                | Commands::Ps { format, .. }
+                | Commands::Kill { format, .. }
+                | Commands::Diff { format, .. },
             ) => Some(format.resolve(cli.quiet)),
             Some(Commands::Search(args)) => Some(args.format.resolve(cli.quiet)),
-            Some(Commands::Find(args)) => Some(args.format.resolve(cli.quiet)),
+            Some(Commands::Find(args) | Commands::Default(args)) => {
+                Some(args.format.resolve(cli.quiet))
+            },
             Some(Commands::Toc(args)) => Some(args.format.resolve(cli.quiet)),
             Some(Commands::Query(args)) => Some(args.format.resolve(cli.quiet)),
             Some(Commands::Map(args)) => Some(args.format.resolve(cli.quiet)),
+            Some(Commands::Bench(args)) => Some(args.format.resolve(cli.quiet)),
             Some(Commands::Check(args)) => Some(args.format.resolve(cli.quiet)),
             _ => None,
         };
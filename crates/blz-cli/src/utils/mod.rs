@@ -53,22 +53,31 @@
 
 pub mod cli_args;
 pub mod clipboard;
+pub mod confirm;
 pub mod constants;
 pub mod filter_flags;
 pub mod formatting;
 pub mod heading_filter;
 pub mod history_log;
+pub mod lockfile;
 pub mod logging;
+pub mod output_profile;
 pub mod parsing;
 pub mod preferences;
+pub mod preprocess;
 pub mod process_guard;
 pub mod profiling;
+pub mod quota;
 pub mod resolver;
 pub mod settings;
+pub mod shutdown;
 pub mod staleness;
 pub mod store;
+pub mod throttle;
+pub mod time_expr;
 pub mod toc;
 pub mod validation;
+pub mod workspace;
 
 #[cfg(test)]
 pub mod test_support;
@@ -0,0 +1,231 @@
+//! Named output profiles (`--profile-out <NAME>`).
+//!
+//! A profile is a `[profiles.<NAME>]` table in config that fills in output
+//! flags an integration would otherwise repeat on every call: format, color,
+//! a token budget, page size, and the `--show` fields on search hits. Flags
+//! given explicitly always take precedence over the profile.
+
+use anyhow::{Result, anyhow};
+use blz_core::{Config, OutputProfile};
+use clap::ValueEnum;
+
+use crate::args::ShowComponent;
+use crate::cli::{Cli, Commands};
+use crate::output::OutputFormat;
+use crate::utils::cli_args::FormatArg;
+
+/// Apply the profile named by `--profile-out` (or `BLZ_PROFILE_OUT`) to `cli`.
+///
+/// # Errors
+///
+/// Returns an error if config cannot be loaded, the profile is not defined,
+/// or it names an unknown format or field.
+pub fn apply(cli: &mut Cli) -> Result<()> {
+    let Some(name) = cli.profile_out.clone() else {
+        return Ok(());
+    };
+    let config = Config::load()?;
+    let profile = config.output_profile(&name)?;
+    apply_profile(cli, profile).map_err(|err| anyhow!("Output profile '{name}': {err}"))
+}
+
+fn apply_profile(cli: &mut Cli, profile: &OutputProfile) -> Result<()> {
+    let format = profile.format.as_deref().map(parse_format).transpose()?;
+    let show = profile
+        .fields
+        .iter()
+        .map(|name| name.parse::<ShowComponent>())
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if profile.color == Some(false) {
+        cli.no_color = true;
+    }
+
+    let Some(command) = cli.command.as_mut() else {
+        return Ok(());
+    };
+    if let Some(format) = format {
+        if let Some(arg) = format_arg_mut(command).filter(|arg| !arg.is_explicit()) {
+            arg.format = Some(format);
+        }
+    }
+
+    let Some(flags) = SearchFlags::of(command) else {
+        return Ok(());
+    };
+    if flags.limit.is_none() && !flags.all {
+        *flags.limit = profile.limit;
+    }
+    if flags.max_tokens.is_none() {
+        *flags.max_tokens = profile.max_tokens;
+    }
+    if flags.show.is_empty() {
+        *flags.show = show;
+    }
+    Ok(())
+}
+
+/// Parse a format name from config.
+///
+/// # Errors
+///
+/// Returns an error naming the accepted formats if `value` is not one.
+pub fn parse_format(value: &str) -> Result<OutputFormat> {
+    OutputFormat::from_str(value, true)
+        .map_err(|_| anyhow!("unknown format '{value}' (expected text, json, jsonl, or raw)"))
+}
+
+/// Search flags that config-driven defaults may fill in.
+pub struct SearchFlags<'a> {
+    /// `--limit`.
+    pub limit: &'a mut Option<usize>,
+    /// Whether `--all` was given (a limit default must not apply).
+    pub all: bool,
+    /// `--max-tokens`.
+    pub max_tokens: &'a mut Option<usize>,
+    /// `--show`.
+    pub show: &'a mut Vec<ShowComponent>,
+    /// `--fuzzy`.
+    pub fuzzy: &'a mut Option<u8>,
+    /// `--score-precision`.
+    pub score_precision: &'a mut Option<u8>,
+}
+
+impl<'a> SearchFlags<'a> {
+    /// The search flags of `command`, if it runs a search.
+    #[allow(deprecated)]
+    pub const fn of(command: &'a mut Commands) -> Option<Self> {
+        match command {
+            Commands::Search(args) => Some(Self {
+                limit: &mut args.limit,
+                all: args.all,
+                max_tokens: &mut args.max_tokens,
+                show: &mut args.show,
+                fuzzy: &mut args.fuzzy,
+                score_precision: &mut args.score_precision,
+            }),
+            Commands::Find(args) | Commands::Default(args) => Some(Self {
+                limit: &mut args.limit,
+                all: args.all,
+                max_tokens: &mut args.max_tokens,
+                show: &mut args.show,
+                fuzzy: &mut args.fuzzy,
+                score_precision: &mut args.score_precision,
+            }),
+            Commands::Query(args) => Some(Self {
+                limit: &mut args.limit,
+                all: args.all,
+                max_tokens: &mut args.max_tokens,
+                show: &mut args.show,
+                fuzzy: &mut args.fuzzy,
+                score_precision: &mut args.score_precision,
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// The `--format` arguments of commands that print structured output.
+#[allow(deprecated)]
+pub const fn format_arg_mut(command: &mut Commands) -> Option<&mut FormatArg> {
+    match command {
+        Commands::List { format, .. }
+        | Commands::Stats { format, .. }
+        | Commands::History { format, .. }
+        | Commands::Lookup { format, .. }
+        | Commands::Get { format, .. }
+        | Commands::Info { format, .. }
+        | Commands::Which { format, .. }
+        | Commands::Completions { format, .. }
+        | Commands::Du { format, .. }
+        | Commands::DebugArgs { format, .. }
+        | Commands::Ps { format, .. }
+        | Commands::Kill { format, .. }
+        | Commands::Diff { format, .. } => Some(format),
+        Commands::Search(args) => Some(&mut args.format),
+        Commands::Find(args) | Commands::Default(args) => Some(&mut args.format),
+        Commands::Toc(args) => Some(&mut args.format),
+        Commands::Query(args) => Some(&mut args.format),
+        Commands::Map(args) => Some(&mut args.format),
+        Commands::Bench(args) => Some(&mut args.format),
+        Commands::Check(args) => Some(&mut args.format),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::panic, clippy::disallowed_macros)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    fn profile() -> OutputProfile {
+        OutputProfile {
+            format: Some("jsonl".to_string()),
+            color: Some(false),
+            max_tokens: Some(2000),
+            limit: Some(5),
+            fields: vec!["url".to_string(), "lines".to_string()],
+        }
+    }
+
+    fn query_args(cli: &Cli) -> &crate::commands::QueryArgs {
+        match cli.command.as_ref() {
+            Some(Commands::Query(args)) => args,
+            other => panic!("expected query, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn profile_fills_unset_flags() {
+        let mut cli = Cli::parse_from(["blz", "query", "hooks"]);
+        apply_profile(&mut cli, &profile()).unwrap();
+
+        assert!(cli.no_color);
+        let args = query_args(&cli);
+        assert_eq!(args.format.format, Some(OutputFormat::Jsonl));
+        assert_eq!(args.limit, Some(5));
+        assert_eq!(args.max_tokens, Some(2000));
+        assert_eq!(args.show, vec![ShowComponent::Url, ShowComponent::Lines]);
+    }
+
+    #[test]
+    fn explicit_flags_win_over_profile() {
+        let mut cli = Cli::parse_from([
+            "blz",
+            "query",
+            "hooks",
+            "--json",
+            "--all",
+            "--max-tokens",
+            "500",
+            "--show",
+            "rank",
+        ]);
+        apply_profile(&mut cli, &profile()).unwrap();
+
+        let args = query_args(&cli);
+        assert_eq!(args.format.format, None);
+        assert!(args.format.json);
+        assert_eq!(args.limit, None);
+        assert_eq!(args.max_tokens, Some(500));
+        assert_eq!(args.show, vec![ShowComponent::Rank]);
+    }
+
+    #[test]
+    fn unknown_values_are_rejected() {
+        let mut cli = Cli::parse_from(["blz", "list"]);
+        let bad_format = OutputProfile {
+            format: Some("yaml".to_string()),
+            ..OutputProfile::default()
+        };
+        assert!(apply_profile(&mut cli, &bad_format).is_err());
+
+        let bad_field = OutputProfile {
+            fields: vec!["ulr".to_string()],
+            ..OutputProfile::default()
+        };
+        let err = apply_profile(&mut cli, &bad_field).unwrap_err().to_string();
+        assert!(err.contains("did you mean 'url'"), "{err}");
+    }
+}
@@ -7,6 +7,7 @@ use tracing::warn;
 use crate::cli::ShowComponent;
 use crate::output::OutputFormat;
 use crate::utils::store::{self, BlzStore};
+use crate::utils::workspace;
 use chrono::Utc;
 
 const GLOBAL_SCOPE_KEY: &str = "global";
@@ -59,6 +60,9 @@ pub struct SearchHistoryEntry {
     /// Whether the search was restricted to headings.
     #[serde(default)]
     pub headings_only: bool,
+    /// Sources that returned at least one hit.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hit_sources: Vec<String>,
 }
 
 /// Persisted TOC invocation metadata for history outputs.
@@ -94,6 +98,22 @@ pub struct TocHistoryEntry {
     pub heading_level: Option<String>,
 }
 
+/// Persisted `get` invocation metadata for section navigation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SectionHistoryEntry {
+    /// Timestamp of the retrieval (RFC3339).
+    pub timestamp: String,
+    /// Canonical source alias.
+    pub source: String,
+    /// First line printed.
+    pub line_start: usize,
+    /// Last line printed.
+    pub line_end: usize,
+    /// Version read with `--at`, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub at: Option<String>,
+}
+
 const fn default_precision() -> u8 {
     1
 }
@@ -194,6 +214,7 @@ pub struct HistoryEntryBuilder<'a> {
     score_precision: u8,
     pagination: PaginationInfo,
     headings_only: bool,
+    hit_sources: Vec<String>,
 }
 
 /// Pagination information for search history.
@@ -225,6 +246,7 @@ impl<'a> HistoryEntryBuilder<'a> {
             score_precision: default_precision(),
             pagination: PaginationInfo::default(),
             headings_only: false,
+            hit_sources: Vec::new(),
         }
     }
 
@@ -248,6 +270,11 @@ impl<'a> HistoryEntryBuilder<'a> {
         self
     }
 
+    pub fn with_hit_sources(mut self, hit_sources: Vec<String>) -> Self {
+        self.hit_sources = hit_sources;
+        self
+    }
+
     pub fn build(self) -> SearchHistoryEntry {
         let timestamp = Utc::now().to_rfc3339();
         SearchHistoryEntry {
@@ -263,6 +290,7 @@ impl<'a> HistoryEntryBuilder<'a> {
             total_pages: self.pagination.total_pages,
             total_results: self.pagination.total_results,
             headings_only: self.headings_only,
+            hit_sources: self.hit_sources,
         }
     }
 }
@@ -274,6 +302,7 @@ pub fn format_to_string(format: OutputFormat) -> String {
         OutputFormat::Json => "json".to_string(),
         OutputFormat::Jsonl => "jsonl".to_string(),
         OutputFormat::Raw => "raw".to_string(),
+        OutputFormat::Markdown => "markdown".to_string(),
     }
 }
 
@@ -287,26 +316,16 @@ pub fn components_to_strings(components: &[ShowComponent]) -> Vec<String> {
 }
 
 /// Map a show component to its stable identifier.
-pub const fn component_to_str(component: ShowComponent) -> &'static str {
-    match component {
-        ShowComponent::Url => "url",
-        ShowComponent::Lines => "lines",
-        ShowComponent::Anchor => "anchor",
-        ShowComponent::Rank => "rank",
-        ShowComponent::RawScore => "raw-score",
-    }
+pub fn component_to_str(component: ShowComponent) -> &'static str {
+    component.name()
 }
 
 /// Parse a show component from a string identifier.
+///
+/// Names no longer in the registry are skipped, so preferences written by
+/// another version still load.
 fn component_from_str(s: &str) -> Option<ShowComponent> {
-    match s.to_ascii_lowercase().as_str() {
-        "url" => Some(ShowComponent::Url),
-        "lines" => Some(ShowComponent::Lines),
-        "anchor" => Some(ShowComponent::Anchor),
-        "rank" => Some(ShowComponent::Rank),
-        "raw-score" => Some(ShowComponent::RawScore),
-        _ => None,
-    }
+    s.parse().ok()
 }
 
 /// Format show components as a comma-separated string.
@@ -318,43 +337,18 @@ pub fn format_show_components(components: &[ShowComponent]) -> String {
         .join(", ")
 }
 
-/// Collect show components from boolean toggles.
-pub fn collect_show_components(url: bool, lines: bool, anchor: bool) -> Vec<ShowComponent> {
-    let mut components = Vec::new();
-    if url {
-        components.push(ShowComponent::Url);
-    }
-    if lines {
-        components.push(ShowComponent::Lines);
-    }
-    if anchor {
-        components.push(ShowComponent::Anchor);
-    }
-    components
-}
-
-#[allow(clippy::fn_params_excessive_bools)]
-/// Collect show components, optionally including raw scores.
-pub fn collect_show_components_extended(
-    url: bool,
-    lines: bool,
-    anchor: bool,
-    raw_score: bool,
-) -> Vec<ShowComponent> {
-    let mut components = collect_show_components(url, lines, anchor);
-    if raw_score {
-        components.push(ShowComponent::RawScore);
-    }
-    components
-}
-
 /// Build the scope chain from global to the most specific scope.
+///
+/// Inside a project workspace the workspace replaces the directory scope, so
+/// every subdirectory shares its history and defaults.
 pub fn scope_chain() -> Vec<String> {
     let mut chain = vec![GLOBAL_SCOPE_KEY.to_string()];
     if let Some(project) = project_scope_key() {
         chain.push(project);
     }
-    if let Some(local) = local_scope_key() {
+    if let Some(workspace) = workspace::active() {
+        chain.push(workspace.scope_key());
+    } else if let Some(local) = local_scope_key() {
         chain.push(local);
     }
     chain
@@ -418,50 +412,6 @@ fn clamp_precision(value: u8) -> u8 {
     value.min(4)
 }
 
-/// Load the last TOC entry from a dedicated TOC history store
-/// This uses the same scope-based storage as preferences but with a dedicated key
-pub fn load_last_toc_entry() -> Option<TocHistoryEntry> {
-    let store = store::load_store();
-    let key = active_scope_key();
-
-    store.scopes.get(&key).and_then(|record| {
-        if record.user_settings.is_null() {
-            return None;
-        }
-
-        // Look for toc_history in user_settings
-        record
-            .user_settings
-            .get("toc_last_entry")
-            .and_then(|value| serde_json::from_value::<TocHistoryEntry>(value.clone()).ok())
-    })
-}
-
-/// Save TOC history entry for pagination state persistence.
-///
-/// # Errors
-///
-/// Returns an error if the preferences store cannot be written.
-pub fn save_toc_history(entry: &TocHistoryEntry) -> std::io::Result<()> {
-    let mut store = store::load_store();
-    let key = active_scope_key();
-    let record = store.scopes.entry(key).or_default();
-
-    // Store in user_settings under toc_last_entry key
-    if record.user_settings.is_null() {
-        record.user_settings = serde_json::json!({});
-    }
-
-    if let Some(obj) = record.user_settings.as_object_mut() {
-        obj.insert(
-            "toc_last_entry".to_string(),
-            serde_json::to_value(entry).unwrap_or(serde_json::Value::Null),
-        );
-    }
-
-    store::save_store(&store)
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -494,12 +444,12 @@ mod tests {
     }
 
     #[test]
-    fn collect_show_components_extended_includes_raw_score() {
-        let components = collect_show_components_extended(true, false, false, true);
-        assert!(components.contains(&ShowComponent::Url));
-        assert!(components.contains(&ShowComponent::RawScore));
-        assert!(!components.contains(&ShowComponent::Lines));
-        assert!(!components.contains(&ShowComponent::Anchor));
+    fn unknown_stored_components_are_skipped() {
+        let prefs = CliPreferences {
+            default_show: vec!["tokens".to_string(), "retired".to_string()],
+            ..CliPreferences::default()
+        };
+        assert_eq!(prefs.default_show_components(), vec![ShowComponent::Tokens]);
     }
 
     #[test]
@@ -0,0 +1,523 @@
+//! Argument preprocessing for the bare `blz <input>` invocation.
+//!
+//! Clap requires an explicit subcommand, but agents and humans frequently call
+//! `blz "react hooks"` or `blz bun:120-142` directly. Before parsing, we inspect the
+//! raw argument vector and, when the first non-global argument is not a known
+//! subcommand, insert the hidden [`DEFAULT_COMMAND`] so the smart-dispatch handler
+//! receives the input.
+//!
+//! ```text
+//! blz "react hooks" -n 5      → blz default "react hooks" -n 5
+//! blz -q bun:120-142          → blz -q default bun:120-142
+//! blz query "react hooks"     → (unchanged)
+//! ```
+//!
+//! [`explain_args_from`] reports the same decision with its reason and any
+//! warnings about rewrites that may not be what the user meant; `blz
+//! debug-args` prints it.
+//!
+//! `blz add crate:NAME` and `blz add github:OWNER/REPO` are expanded the same
+//! way so the crate or repository name doubles as the alias:
+//!
+//! ```text
+//! blz add crate:serde -y      → blz add serde crate:serde -y
+//! blz add github:acme/kit -y  → blz add kit github:acme/kit -y
+//! ```
+//!
+//! `--no-shorthand` (or `BLZ_STRICT_ARGS`) disables rewriting entirely, for
+//! wrappers that build argv themselves and want clap's exact semantics.
+
+use blz_core::github::GITHUB_PREFIX;
+use blz_core::rustdoc::CRATE_PREFIX;
+use clap::CommandFactory;
+use serde::Serialize;
+
+use crate::cli::Cli;
+
+/// Name of the hidden subcommand that receives bare invocations.
+pub const DEFAULT_COMMAND: &str = "default";
+
+/// Global flags that short-circuit normal command dispatch and must never be rewritten.
+const PASSTHROUGH_FLAGS: &[&str] = &["-h", "--help", "-V", "--version", "--prompt"];
+
+/// Global flag that disables shorthand rewriting.
+pub const STRICT_FLAG: &str = "--no-shorthand";
+
+/// Environment variable that disables shorthand rewriting when truthy.
+pub const STRICT_ENV: &str = "BLZ_STRICT_ARGS";
+
+/// How an argument vector was (or was not) rewritten before parsing.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArgRewrite {
+    /// Arguments as received, including the binary name.
+    pub original: Vec<String>,
+    /// Arguments handed to clap.
+    pub rewritten: Vec<String>,
+    /// Position where [`DEFAULT_COMMAND`] was inserted, if it was.
+    pub injected_at: Option<usize>,
+    /// Why the arguments were or were not rewritten.
+    pub reason: String,
+    /// Rewrites that may not be what the user meant.
+    pub warnings: Vec<String>,
+}
+
+/// Explain the current process arguments (see [`explain_args_from`]).
+#[must_use]
+pub fn explain_args() -> ArgRewrite {
+    explain_args_from(std::env::args().collect())
+}
+
+#[cfg(test)]
+fn preprocess_args_from(raw: Vec<String>) -> Vec<String> {
+    explain_args_from(raw).rewritten
+}
+
+/// Insert the default subcommand when the invocation does not name one,
+/// recording why.
+///
+/// Arguments are returned unchanged when:
+/// - no arguments beyond the binary name are present
+/// - help, version, or prompt flags appear before the first positional
+/// - the first non-global argument is a known subcommand (or alias)
+#[must_use]
+pub fn explain_args_from(raw: Vec<String>) -> ArgRewrite {
+    let command = Cli::command();
+    let decision = decide(&command, &raw);
+    let warnings = ambiguity_warnings(&command, &raw, &decision);
+    let reason = decision.reason();
+
+    let mut rewritten = raw.clone();
+    let injected_at = decision.insertion_index();
+    if let Some(index) = injected_at {
+        rewritten.insert(index, DEFAULT_COMMAND.to_string());
+    }
+    if let Decision::Subcommand(index, name) = &decision {
+        if name == "add" {
+            expand_add_shorthand(&mut rewritten, *index);
+        }
+    }
+
+    ArgRewrite {
+        original: raw,
+        rewritten,
+        injected_at,
+        reason,
+        warnings,
+    }
+}
+
+/// Outcome of scanning the arguments for a subcommand.
+enum Decision {
+    NoInput,
+    Strict(&'static str),
+    Passthrough(String),
+    Subcommand(usize, String),
+    Separator(usize),
+    CommandFlag(usize, String),
+    Input(usize, String),
+}
+
+impl Decision {
+    const fn insertion_index(&self) -> Option<usize> {
+        match self {
+            Self::Separator(index) | Self::CommandFlag(index, _) | Self::Input(index, _) => {
+                Some(*index)
+            },
+            Self::NoInput | Self::Strict(_) | Self::Passthrough(_) | Self::Subcommand(..) => None,
+        }
+    }
+
+    fn reason(&self) -> String {
+        match self {
+            Self::NoInput => "no subcommand or input was given".to_string(),
+            Self::Strict(source) => format!("shorthand rewriting is disabled by {source}"),
+            Self::Passthrough(flag) => format!("'{flag}' is handled before subcommand dispatch"),
+            Self::Subcommand(_, name) => format!("'{name}' is a subcommand"),
+            Self::Separator(_) => {
+                format!("arguments after '--' are input for the '{DEFAULT_COMMAND}' command")
+            },
+            Self::CommandFlag(_, flag) => format!(
+                "'{flag}' is not a global flag, so the '{DEFAULT_COMMAND}' command receives it"
+            ),
+            Self::Input(_, arg) => format!(
+                "'{arg}' is not a subcommand, so it is input for the '{DEFAULT_COMMAND}' command"
+            ),
+        }
+    }
+}
+
+fn decide(command: &clap::Command, raw: &[String]) -> Decision {
+    if raw
+        .iter()
+        .skip(1)
+        .take_while(|arg| *arg != "--")
+        .any(|arg| arg == STRICT_FLAG)
+    {
+        return Decision::Strict(STRICT_FLAG);
+    }
+    if strict_from_env() {
+        return Decision::Strict(STRICT_ENV);
+    }
+
+    let globals = GlobalFlags::from_command(command);
+
+    let mut index = 1;
+    while index < raw.len() {
+        let arg = raw[index].as_str();
+
+        if arg == "--" {
+            // Everything after `--` is positional input for the default command.
+            return if index + 1 < raw.len() {
+                Decision::Separator(index)
+            } else {
+                Decision::NoInput
+            };
+        }
+
+        if PASSTHROUGH_FLAGS.contains(&arg) || arg.starts_with("--prompt=") {
+            return Decision::Passthrough(arg.to_string());
+        }
+
+        if arg.starts_with('-') && arg.len() > 1 {
+            match globals.classify(arg) {
+                GlobalFlag::Switch => {
+                    index += 1;
+                    continue;
+                },
+                GlobalFlag::TakesValue => {
+                    index += 2;
+                    continue;
+                },
+                // A command-specific flag before the input (e.g. `blz -s bun hooks`).
+                GlobalFlag::NotGlobal => return Decision::CommandFlag(index, arg.to_string()),
+            }
+        }
+
+        if is_known_subcommand(command, arg) {
+            return Decision::Subcommand(index, arg.to_string());
+        }
+        return Decision::Input(index, arg.to_string());
+    }
+
+    Decision::NoInput
+}
+
+/// Insert the default alias for `blz add crate:NAME[@VERSION]` (the crate
+/// name) and `blz add github:OWNER/REPO[@REF]` (the repository name).
+///
+/// Only applies when the spec directly follows `add` and no URL follows it.
+fn expand_add_shorthand(args: &mut Vec<String>, add_index: usize) {
+    let spec_index = add_index + 1;
+    let Some(name) = args
+        .get(spec_index)
+        .and_then(|spec| {
+            spec.strip_prefix(CRATE_PREFIX).or_else(|| {
+                spec.strip_prefix(GITHUB_PREFIX)
+                    .and_then(|spec| spec.split_once('/'))
+                    .map(|(_, repo)| repo)
+            })
+        })
+        .and_then(|spec| spec.split('@').next())
+        .map(|name| name.strip_suffix(".git").unwrap_or(name))
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+    else {
+        return;
+    };
+    if args
+        .get(spec_index + 1)
+        .is_some_and(|next| !next.starts_with('-'))
+    {
+        return;
+    }
+    args.insert(spec_index, name);
+}
+
+/// Whether [`STRICT_ENV`] is set to anything but an explicit false value.
+fn strict_from_env() -> bool {
+    std::env::var(STRICT_ENV).is_ok_and(|value| {
+        let normalized = value.trim().to_ascii_lowercase();
+        !normalized.is_empty() && !matches!(normalized.as_str(), "0" | "false" | "off" | "no")
+    })
+}
+
+/// Warn about rewrites that probably hide a mistyped or misplaced subcommand.
+fn ambiguity_warnings(command: &clap::Command, raw: &[String], decision: &Decision) -> Vec<String> {
+    match decision {
+        Decision::Input(_, arg) => near_subcommand(command, arg)
+            .map(|name| {
+                format!(
+                    "'{arg}' is not a subcommand and will be searched as text; did you mean 'blz {name}'?"
+                )
+            })
+            .into_iter()
+            .collect(),
+        Decision::CommandFlag(index, flag) => raw[index + 1..]
+            .iter()
+            .filter(|arg| !arg.starts_with('-') && is_known_subcommand(command, arg))
+            .map(|arg| {
+                format!(
+                    "'{arg}' follows '{flag}' and will be treated as input, not as a subcommand; \
+                     put the subcommand first"
+                )
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn is_known_subcommand(command: &clap::Command, name: &str) -> bool {
+    name == "help" || subcommand_names(command).any(|known| known == name)
+}
+
+fn subcommand_names(command: &clap::Command) -> impl Iterator<Item = &str> {
+    command
+        .get_subcommands()
+        .filter(|sub| sub.get_name() != DEFAULT_COMMAND)
+        .flat_map(|sub| std::iter::once(sub.get_name()).chain(sub.get_all_aliases()))
+}
+
+/// A subcommand that `arg` matches ignoring case or with a single typo.
+fn near_subcommand<'a>(command: &'a clap::Command, arg: &str) -> Option<&'a str> {
+    if arg.chars().count() < 3 || !arg.chars().all(|c| c.is_alphanumeric() || c == '-') {
+        return None;
+    }
+    let lowered = arg.to_lowercase();
+    subcommand_names(command).find(|name| within_one_edit(&lowered, name))
+}
+
+/// Whether `a` becomes `b` with at most one insertion, deletion,
+/// substitution, or swap of adjacent characters.
+fn within_one_edit(a: &str, b: &str) -> bool {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let prefix = a.iter().zip(&b).take_while(|(x, y)| x == y).count();
+    let (a_rest, b_rest) = (&a[prefix..], &b[prefix..]);
+    match (a_rest.len(), b_rest.len()) {
+        (0, 0) => true,
+        (x, y) if x == y => {
+            a_rest[1..] == b_rest[1..]
+                || (x >= 2
+                    && a_rest[0] == b_rest[1]
+                    && a_rest[1] == b_rest[0]
+                    && a_rest[2..] == b_rest[2..])
+        },
+        (x, y) if x == y + 1 => a_rest[1..] == *b_rest,
+        (x, y) if x + 1 == y => *a_rest == b_rest[1..],
+        _ => false,
+    }
+}
+
+enum GlobalFlag {
+    Switch,
+    TakesValue,
+    NotGlobal,
+}
+
+/// Long and short spellings of the global flags declared on [`Cli`].
+struct GlobalFlags {
+    switches: Vec<String>,
+    valued: Vec<String>,
+}
+
+impl GlobalFlags {
+    fn from_command(command: &clap::Command) -> Self {
+        let mut switches = Vec::new();
+        let mut valued = Vec::new();
+        for arg in command.get_arguments().filter(|arg| arg.is_global_set()) {
+            let target = if arg.get_action().takes_values() {
+                &mut valued
+            } else {
+                &mut switches
+            };
+            if let Some(long) = arg.get_long() {
+                target.push(format!("--{long}"));
+            }
+            if let Some(short) = arg.get_short() {
+                target.push(format!("-{short}"));
+            }
+        }
+        Self { switches, valued }
+    }
+
+    fn classify(&self, arg: &str) -> GlobalFlag {
+        if self.switches.iter().any(|flag| flag == arg) {
+            return GlobalFlag::Switch;
+        }
+        if let Some((flag, _)) = arg.split_once('=') {
+            if self.valued.iter().any(|known| known == flag) {
+                return GlobalFlag::Switch;
+            }
+        }
+        if self.valued.iter().any(|flag| flag == arg) {
+            return GlobalFlag::TakesValue;
+        }
+        GlobalFlag::NotGlobal
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn run(args: &[&str]) -> Vec<String> {
+        preprocess_args_from(args.iter().map(|s| (*s).to_string()).collect())
+    }
+
+    #[test]
+    fn add_crate_spec_gets_alias() {
+        assert_eq!(
+            run(&["blz", "add", "crate:serde@1.0", "-y"]),
+            vec!["blz", "add", "serde", "crate:serde@1.0", "-y"]
+        );
+        assert_eq!(
+            run(&["blz", "add", "sd", "crate:serde"]),
+            vec!["blz", "add", "sd", "crate:serde"]
+        );
+        assert_eq!(run(&["blz", "add", "alias"]), vec!["blz", "add", "alias"]);
+    }
+
+    #[test]
+    fn add_github_spec_gets_alias() {
+        assert_eq!(
+            run(&["blz", "add", "github:acme/kit@v2", "-y"]),
+            vec!["blz", "add", "kit", "github:acme/kit@v2", "-y"]
+        );
+        assert_eq!(
+            run(&["blz", "add", "k", "github:acme/kit"]),
+            vec!["blz", "add", "k", "github:acme/kit"]
+        );
+    }
+
+    #[test]
+    fn bare_query_gets_default_command() {
+        assert_eq!(
+            run(&["blz", "react hooks"]),
+            vec!["blz", "default", "react hooks"]
+        );
+    }
+
+    #[test]
+    fn citation_gets_default_command() {
+        assert_eq!(
+            run(&["blz", "bun:120-142", "-C", "5"]),
+            vec!["blz", "default", "bun:120-142", "-C", "5"]
+        );
+    }
+
+    #[test]
+    fn known_subcommands_are_untouched() {
+        assert_eq!(
+            run(&["blz", "query", "hooks"]),
+            vec!["blz", "query", "hooks"]
+        );
+        assert_eq!(run(&["blz", "sources"]), vec!["blz", "sources"]);
+        assert_eq!(run(&["blz", "help"]), vec!["blz", "help"]);
+    }
+
+    #[test]
+    fn global_flags_are_skipped() {
+        assert_eq!(
+            run(&["blz", "-q", "--config-dir", "/tmp/x", "hooks"]),
+            vec!["blz", "-q", "--config-dir", "/tmp/x", "default", "hooks"]
+        );
+        assert_eq!(
+            run(&["blz", "--config=/tmp/c.toml", "list"]),
+            vec!["blz", "--config=/tmp/c.toml", "list"]
+        );
+    }
+
+    #[test]
+    fn command_flags_before_input_start_default_command() {
+        assert_eq!(
+            run(&["blz", "-s", "bun", "hooks"]),
+            vec!["blz", "default", "-s", "bun", "hooks"]
+        );
+    }
+
+    #[test]
+    fn passthrough_flags_and_empty_invocations_are_untouched() {
+        assert_eq!(run(&["blz"]), vec!["blz"]);
+        assert_eq!(run(&["blz", "--help"]), vec!["blz", "--help"]);
+        assert_eq!(
+            run(&["blz", "--prompt", "query"]),
+            vec!["blz", "--prompt", "query"]
+        );
+        assert_eq!(run(&["blz", "-V"]), vec!["blz", "-V"]);
+    }
+
+    fn explain(args: &[&str]) -> ArgRewrite {
+        explain_args_from(args.iter().map(|s| (*s).to_string()).collect())
+    }
+
+    #[test]
+    fn explanation_records_injection_and_reason() {
+        let rewrite = explain(&["blz", "-q", "hooks"]);
+        assert_eq!(rewrite.injected_at, Some(2));
+        assert_eq!(rewrite.original, vec!["blz", "-q", "hooks"]);
+        assert!(rewrite.reason.contains("'hooks' is not a subcommand"));
+        assert!(rewrite.warnings.is_empty());
+
+        let rewrite = explain(&["blz", "list"]);
+        assert_eq!(rewrite.injected_at, None);
+        assert_eq!(rewrite.reason, "'list' is a subcommand");
+    }
+
+    #[test]
+    fn mistyped_subcommands_warn() {
+        for typo in ["serach", "qurey", "Query", "lst"] {
+            let rewrite = explain(&["blz", typo, "hooks"]);
+            assert_eq!(rewrite.injected_at, Some(1), "{typo}");
+            assert_eq!(rewrite.warnings.len(), 1, "{typo}");
+        }
+        assert!(explain(&["blz", "react hooks"]).warnings.is_empty());
+        assert!(explain(&["blz", "bun:1-3"]).warnings.is_empty());
+    }
+
+    #[test]
+    fn subcommand_after_command_flag_warns() {
+        let rewrite = explain(&["blz", "-s", "bun", "query", "hooks"]);
+        assert_eq!(rewrite.injected_at, Some(1));
+        assert_eq!(rewrite.warnings.len(), 1);
+        assert!(rewrite.warnings[0].contains("'query' follows '-s'"));
+    }
+
+    #[test]
+    fn strict_flag_disables_rewriting() {
+        let rewrite = explain(&["blz", "--no-shorthand", "react", "hooks"]);
+        assert_eq!(rewrite.rewritten, rewrite.original);
+        assert_eq!(rewrite.injected_at, None);
+        assert_eq!(
+            rewrite.reason,
+            "shorthand rewriting is disabled by --no-shorthand"
+        );
+
+        // After `--` the flag is input, not a switch.
+        let rewrite = explain(&["blz", "--", "--no-shorthand"]);
+        assert_eq!(rewrite.injected_at, Some(1));
+    }
+
+    #[test]
+    fn one_edit_distance() {
+        assert!(within_one_edit("search", "search"));
+        assert!(within_one_edit("serach", "search"));
+        assert!(within_one_edit("seach", "search"));
+        assert!(within_one_edit("searchh", "search"));
+        assert!(within_one_edit("sesrch", "search"));
+        assert!(!within_one_edit("sraech", "search"));
+        assert!(!within_one_edit("hooks", "docs"));
+    }
+
+    #[test]
+    fn rewritten_args_parse() {
+        use clap::Parser;
+
+        let cli = Cli::try_parse_from(run(&["blz", "react hooks", "-n", "5"])).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(crate::cli::Commands::Default(_))
+        ));
+    }
+}
@@ -9,15 +9,86 @@ use tracing::{info, warn};
 const DEFAULT_POLL_INTERVAL_MS: u64 = 500;
 const MIN_POLL_INTERVAL_MS: u64 = 100;
 const MAX_POLL_INTERVAL_MS: u64 = 10_000;
+const MAX_GRACE_PERIOD_MS: u64 = 60_000;
+
+/// Parent-exit guard settings, read from the environment.
+///
+/// | Variable | Effect |
+/// |----------|--------|
+/// | `BLZ_DISABLE_GUARD` | Any value disables the guard |
+/// | `BLZ_PARENT_GUARD` | `off`, `false`, `0`, or `disabled` disables the guard |
+/// | `BLZ_PARENT_GUARD_INTERVAL_MS` | Poll interval (100-10000ms, default 500ms) |
+/// | `BLZ_PARENT_GUARD_GRACE_MS` | Delay before exiting once the parent is gone (max 60s) |
+/// | `BLZ_PARENT_GUARD_TIMEOUT_MS` / `_SECS` | Hard deadline, primarily for test harnesses |
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GuardConfig {
+    /// Whether the watchdog thread should be spawned at all.
+    pub enabled: bool,
+    /// How often to check whether the parent is still alive.
+    pub poll_interval: Duration,
+    /// How long to keep running after the parent disappears.
+    pub grace_period: Duration,
+    /// Optional hard deadline after which the process exits regardless.
+    pub timeout: Option<Duration>,
+}
+
+impl GuardConfig {
+    /// Read the guard configuration from the process environment.
+    #[must_use]
+    pub fn from_env() -> Self {
+        Self::from_lookup(|key| std::env::var(key).ok())
+    }
+
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Self {
+        let parse_u64 = |key: &str| lookup(key).and_then(|raw| raw.trim().parse::<u64>().ok());
+
+        let disabled_by_flag = lookup("BLZ_PARENT_GUARD").is_some_and(|raw| {
+            matches!(
+                raw.trim().to_ascii_lowercase().as_str(),
+                "off" | "false" | "0" | "disabled"
+            )
+        });
+        let enabled = lookup("BLZ_DISABLE_GUARD").is_none() && !disabled_by_flag;
+
+        let poll_interval_ms = parse_u64("BLZ_PARENT_GUARD_INTERVAL_MS")
+            .filter(|value| (MIN_POLL_INTERVAL_MS..=MAX_POLL_INTERVAL_MS).contains(value))
+            .unwrap_or(DEFAULT_POLL_INTERVAL_MS);
+
+        let grace_ms = parse_u64("BLZ_PARENT_GUARD_GRACE_MS")
+            .unwrap_or(0)
+            .min(MAX_GRACE_PERIOD_MS);
+
+        let timeout = parse_u64("BLZ_PARENT_GUARD_TIMEOUT_MS")
+            .filter(|&ms| ms > 0)
+            .map(Duration::from_millis)
+            .or_else(|| {
+                parse_u64("BLZ_PARENT_GUARD_TIMEOUT_SECS")
+                    .filter(|&secs| secs > 0)
+                    .map(Duration::from_secs)
+            });
+
+        Self {
+            enabled,
+            poll_interval: Duration::from_millis(poll_interval_ms),
+            grace_period: Duration::from_millis(grace_ms),
+            timeout,
+        }
+    }
+}
 
 /// Spawn a background watchdog that terminates the CLI when its parent process
 /// goes away. This prevents orphaned `blz` processes when test harnesses or
 /// wrapper scripts die unexpectedly (e.g., Ctrl+C during `cargo test`).
+///
+/// Behavior is controlled by [`GuardConfig`]; orphans that slip through can be
+/// found with `blz ps` and cleaned up with `blz kill --orphans`.
 pub fn spawn_parent_exit_guard() {
+    let config = GuardConfig::from_env();
+
     // No parent concept on WASM targets.
     #[cfg(target_family = "wasm")]
     {
-        let _ = POLL_INTERVAL;
+        let _ = config;
         return;
     }
 
@@ -28,35 +99,15 @@ pub fn spawn_parent_exit_guard() {
             return;
         }
 
-        if std::env::var_os("BLZ_DISABLE_GUARD").is_some() {
+        if !config.enabled {
             return;
         }
 
         let current_pid = std::process::id();
 
-        let poll_interval_ms = std::env::var("BLZ_PARENT_GUARD_INTERVAL_MS")
-            .ok()
-            .and_then(|raw| raw.parse::<u64>().ok())
-            .filter(|value| (MIN_POLL_INTERVAL_MS..=MAX_POLL_INTERVAL_MS).contains(value))
-            .unwrap_or(DEFAULT_POLL_INTERVAL_MS);
-
-        let timeout_ms = std::env::var("BLZ_PARENT_GUARD_TIMEOUT_MS")
-            .ok()
-            .and_then(|raw| raw.parse::<u64>().ok())
-            .filter(|&ms| ms > 0)
-            .map(Duration::from_millis);
-
-        let timeout = timeout_ms.or_else(|| {
-            std::env::var("BLZ_PARENT_GUARD_TIMEOUT_SECS")
-                .ok()
-                .and_then(|raw| raw.parse::<u64>().ok())
-                .filter(|&secs| secs > 0)
-                .map(Duration::from_secs)
-        });
-
         if thread::Builder::new()
             .name("blz-parent-guard".into())
-            .spawn(move || monitor_parent(current_pid, poll_interval_ms, timeout))
+            .spawn(move || monitor_parent(current_pid, config))
             .is_err()
         {
             warn!("failed to spawn parent exit guard; continuing without orphan protection");
@@ -65,7 +116,7 @@ pub fn spawn_parent_exit_guard() {
 }
 
 #[cfg(not(target_family = "wasm"))]
-fn monitor_parent(current_pid_raw: u32, poll_interval_ms: u64, guard_timeout: Option<Duration>) {
+fn monitor_parent(current_pid_raw: u32, config: GuardConfig) {
     const EXIT_PARENT_LOST: i32 = 129;
     const EXIT_GUARD_TIMEOUT: i32 = 124;
 
@@ -89,7 +140,7 @@ fn monitor_parent(current_pid_raw: u32, poll_interval_ms: u64, guard_timeout: Op
     };
 
     // Optional timeout, primarily used by test harnesses when BLZ_PARENT_GUARD_TIMEOUT_{MS,SECS} is set
-    let guard_deadline = guard_timeout.map(|timeout| Instant::now() + timeout);
+    let guard_deadline = config.timeout.map(|timeout| Instant::now() + timeout);
 
     loop {
         let parent_update = [parent_pid];
@@ -103,11 +154,19 @@ fn monitor_parent(current_pid_raw: u32, poll_interval_ms: u64, guard_timeout: Op
             // If sysinfo failed to refresh processes on this platform, wait and retry instead of
             // eagerly exiting. This still detects real exits once the process truly disappears.
             tracing::debug!(parent = %parent_pid, "unable to refresh parent process state; retrying");
-            thread::sleep(Duration::from_millis(poll_interval_ms));
+            thread::sleep(config.poll_interval);
             continue;
         }
 
         if system.process(parent_pid).is_none() {
+            if !config.grace_period.is_zero() {
+                warn!(
+                    parent = %parent_pid,
+                    grace_ms = config.grace_period.as_millis(),
+                    "parent process exited; terminating after grace period"
+                );
+                thread::sleep(config.grace_period);
+            }
             warn!(parent = %parent_pid, "parent process exited; terminating orphaned blz process");
             std::process::exit(EXIT_PARENT_LOST);
         }
@@ -119,6 +178,60 @@ fn monitor_parent(current_pid_raw: u32, poll_interval_ms: u64, guard_timeout: Op
             }
         }
 
-        thread::sleep(Duration::from_millis(poll_interval_ms));
+        thread::sleep(config.poll_interval);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn config_from(pairs: &[(&str, &str)]) -> GuardConfig {
+        let env: HashMap<String, String> = pairs
+            .iter()
+            .map(|(key, value)| ((*key).to_string(), (*value).to_string()))
+            .collect();
+        GuardConfig::from_lookup(|key| env.get(key).cloned())
+    }
+
+    #[test]
+    fn defaults_enable_guard_without_grace() {
+        let config = config_from(&[]);
+        assert!(config.enabled);
+        assert_eq!(
+            config.poll_interval,
+            Duration::from_millis(DEFAULT_POLL_INTERVAL_MS)
+        );
+        assert!(config.grace_period.is_zero());
+        assert_eq!(config.timeout, None);
+    }
+
+    #[test]
+    fn guard_can_be_disabled() {
+        assert!(!config_from(&[("BLZ_DISABLE_GUARD", "1")]).enabled);
+        assert!(!config_from(&[("BLZ_PARENT_GUARD", "off")]).enabled);
+        assert!(config_from(&[("BLZ_PARENT_GUARD", "on")]).enabled);
+    }
+
+    #[test]
+    fn grace_period_is_capped() {
+        let config = config_from(&[("BLZ_PARENT_GUARD_GRACE_MS", "1500")]);
+        assert_eq!(config.grace_period, Duration::from_millis(1500));
+
+        let capped = config_from(&[("BLZ_PARENT_GUARD_GRACE_MS", "999999")]);
+        assert_eq!(
+            capped.grace_period,
+            Duration::from_millis(MAX_GRACE_PERIOD_MS)
+        );
+    }
+
+    #[test]
+    fn out_of_range_interval_falls_back_to_default() {
+        let config = config_from(&[("BLZ_PARENT_GUARD_INTERVAL_MS", "5")]);
+        assert_eq!(
+            config.poll_interval,
+            Duration::from_millis(DEFAULT_POLL_INTERVAL_MS)
+        );
     }
 }
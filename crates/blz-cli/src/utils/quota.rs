@@ -0,0 +1,116 @@
+//! Disk budget checks shared by `blz stats --disk` and commands that grow
+//! the cache.
+//!
+//! Usage comes from [`Storage::disk_usage`]; recency and popularity come from
+//! each source's access record and the search history log. See
+//! [`blz_core::quota`] for the budget and eviction policies.
+
+use anyhow::{Context, Result};
+use blz_core::quota::{ByteSize, EvictionPolicy, QuotaEntry, select_evictions};
+use blz_core::{Config, Storage};
+use colored::Colorize;
+
+use super::history_log;
+
+/// The configured budget and policy, or `None` when the cache is unbounded.
+pub fn configured_budget() -> Option<(ByteSize, EvictionPolicy)> {
+    let defaults = Config::load().ok()?.defaults;
+    defaults
+        .disk_budget
+        .map(|budget| (budget, defaults.eviction.unwrap_or_default()))
+}
+
+/// One [`QuotaEntry`] per cached source, excluding read-only shared sources
+/// since they take no local disk space.
+pub fn entries(storage: &Storage) -> Vec<QuotaEntry> {
+    let last_hits = history_log::last_hit_by_source();
+    let counts = history_log::search_counts_by_source();
+    storage
+        .list_sources()
+        .into_iter()
+        .filter(|alias| !storage.is_shared(alias))
+        .map(|alias| {
+            let bytes = storage.disk_usage(&alias).map_or(0, |usage| usage.total());
+            let last_searched = storage
+                .load_access(&alias)
+                .last_searched
+                .max(last_hits.get(&alias).copied());
+            let searches = counts.get(&alias).copied().unwrap_or(0);
+            QuotaEntry {
+                alias,
+                bytes,
+                last_searched,
+                searches,
+            }
+        })
+        .collect()
+}
+
+/// Sources that are never evicted: `keep`, plus internal sources such as the
+/// bundled docs.
+pub fn protected(storage: &Storage, keep: &[String]) -> Vec<String> {
+    let mut protected = keep.to_vec();
+    protected.extend(storage.list_sources().into_iter().filter(|alias| {
+        storage
+            .load_source_summary(alias)
+            .ok()
+            .flatten()
+            .is_some_and(|summary| summary.is_internal())
+    }));
+    protected
+}
+
+/// Apply the disk budget after the cache grew, never evicting `keep`.
+///
+/// Under the default `warn` policy this prints which sources would go;
+/// `lru` and `least_searched` remove them. Returns the evicted aliases.
+///
+/// # Errors
+///
+/// Returns an error if an evicted source cannot be removed.
+pub fn enforce_budget(keep: &[String], quiet: bool) -> Result<Vec<String>> {
+    let Some((budget, policy)) = configured_budget() else {
+        return Ok(Vec::new());
+    };
+    let storage = Storage::new()?;
+    let entries = entries(&storage);
+    let total: u64 = entries.iter().map(|entry| entry.bytes).sum();
+    if total <= budget.bytes() {
+        return Ok(Vec::new());
+    }
+    let selected = select_evictions(&entries, budget, policy, &protected(&storage, keep));
+
+    if !policy.evicts() {
+        if !quiet {
+            eprintln!(
+                "{} Cache uses {} KB, over its {budget} disk budget",
+                "⚠".yellow(),
+                total.div_ceil(1024)
+            );
+            if !selected.is_empty() {
+                let candidates: Vec<&str> = selected.iter().map(|e| e.alias.as_str()).collect();
+                eprintln!("  Least recently searched: {}", candidates.join(", "));
+            }
+            eprintln!("  Run `blz stats --disk` for details or set `defaults.eviction = \"lru\"`");
+        }
+        return Ok(Vec::new());
+    }
+
+    let mut evicted = Vec::new();
+    for entry in selected {
+        let dir = storage.tool_dir(&entry.alias)?;
+        std::fs::remove_dir_all(&dir)
+            .with_context(|| format!("Failed to evict '{}'", entry.alias))?;
+        if !quiet {
+            eprintln!(
+                "{} Evicted '{}' ({} KB) to stay within the {budget} disk budget ({policy})",
+                "↺".cyan(),
+                entry.alias,
+                entry.bytes.div_ceil(1024)
+            );
+        }
+        evicted.push(entry.alias);
+    }
+    storage.invalidate_query_cache();
+    Ok(evicted)
+}
@@ -1,47 +1,186 @@
+use std::fmt;
+
 use anyhow::Result;
 use blz_core::Storage;
+use serde::Serialize;
 
-/// Resolve a requested source identifier to its canonical alias.
+/// Where a requested identifier matched a source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum MatchKind {
+    /// The identifier is the source's canonical alias.
+    Canonical,
+    /// The identifier is listed in the source's metadata aliases.
+    MetadataAlias,
+    /// The identifier is listed in the aliases stored in the source's `llms.json`.
+    LlmsAlias,
+}
+
+impl fmt::Display for MatchKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Canonical => "canonical name",
+            Self::MetadataAlias => "metadata alias",
+            Self::LlmsAlias => "llms.json alias",
+        })
+    }
+}
+
+/// A source the requested identifier matched.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Candidate {
+    /// Canonical source alias.
+    pub source: String,
+    /// How the identifier matched.
+    pub matched_via: MatchKind,
+}
+
+/// Aliases declared by one source, as seen while resolving.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CheckedSource {
+    /// Canonical source alias.
+    pub source: String,
+    /// Aliases from the source's metadata file.
+    pub metadata_aliases: Vec<String>,
+    /// Aliases from the source's `llms.json`.
+    pub llms_aliases: Vec<String>,
+}
+
+/// Every step taken to resolve a requested source identifier.
+///
+/// Produced by [`trace_source`]; [`resolve_source`] reduces it to a single
+/// canonical alias, and `blz which --verbose` prints it.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolutionTrace {
+    /// Identifier as given by the user.
+    pub requested: String,
+    /// Number of cached sources.
+    pub known_sources: usize,
+    /// Sources whose aliases were checked (empty for canonical matches).
+    pub checked: Vec<CheckedSource>,
+    /// Sources the identifier matched.
+    pub candidates: Vec<Candidate>,
+}
+
+impl ResolutionTrace {
+    /// Reduce the trace to the canonical alias it resolves to.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AmbiguousSource`] if more than one source matched.
+    pub fn resolved(&self) -> std::result::Result<Option<&str>, AmbiguousSource> {
+        match self.candidates.as_slice() {
+            [] => Ok(None),
+            [only] => Ok(Some(only.source.as_str())),
+            _ => Err(AmbiguousSource {
+                requested: self.requested.clone(),
+                candidates: self.candidates.clone(),
+            }),
+        }
+    }
+}
+
+/// A requested identifier matched aliases of more than one source.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AmbiguousSource {
+    /// Identifier as given by the user.
+    pub requested: String,
+    /// Every source the identifier matched.
+    pub candidates: Vec<Candidate>,
+}
+
+impl fmt::Display for AmbiguousSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names: Vec<&str> = self
+            .candidates
+            .iter()
+            .map(|candidate| candidate.source.as_str())
+            .collect();
+        write!(
+            f,
+            "Alias '{}' is ambiguous across multiple sources: {} — use --source with a canonical name",
+            self.requested,
+            names.join(", ")
+        )?;
+        for candidate in &self.candidates {
+            write!(f, "\n  {} ({})", candidate.source, candidate.matched_via)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for AmbiguousSource {}
+
+/// Trace how a requested source identifier resolves.
 ///
 /// Resolution order:
 /// 1) Exact match to canonical alias
-/// 2) Unique match across metadata aliases
-/// - Returns Ok(None) if not found
-/// - Returns Err if ambiguous across multiple sources
-///
-/// # Errors
-///
-/// Returns an error if the alias maps to more than one source.
-pub fn resolve_source(storage: &Storage, requested: &str) -> Result<Option<String>> {
-    let requested_str = requested.to_string();
+/// 2) Matches across metadata aliases, then `llms.json` aliases, of every source
+#[must_use]
+pub fn trace_source(storage: &Storage, requested: &str) -> ResolutionTrace {
     let known = storage.list_sources();
-    if known.contains(&requested_str) {
-        return Ok(Some(requested_str));
+    let mut trace = ResolutionTrace {
+        requested: requested.to_string(),
+        known_sources: known.len(),
+        checked: Vec::new(),
+        candidates: Vec::new(),
+    };
+    if known.iter().any(|src| src == requested) {
+        trace.candidates.push(Candidate {
+            source: requested.to_string(),
+            matched_via: MatchKind::Canonical,
+        });
+        return trace;
     }
 
-    let mut resolved_sources: Vec<String> = Vec::new();
-    for src in &known {
-        if let Ok(Some(metadata)) = storage.load_source_metadata(src) {
-            if metadata.aliases.iter().any(|alias| alias == requested) {
-                resolved_sources.push(src.clone());
-                continue;
-            }
-        }
+    for src in known {
+        let metadata_aliases = storage
+            .load_source_metadata(&src)
+            .ok()
+            .flatten()
+            .map(|metadata| metadata.aliases)
+            .unwrap_or_default();
+        let llms_aliases = storage
+            .load_llms_json(&src)
+            .map(|llms| llms.metadata.aliases)
+            .unwrap_or_default();
 
-        if let Ok(llms) = storage.load_llms_json(src) {
-            if llms.metadata.aliases.iter().any(|alias| alias == requested) {
-                resolved_sources.push(src.clone());
-            }
+        let matched_via = if metadata_aliases.iter().any(|alias| alias == requested) {
+            Some(MatchKind::MetadataAlias)
+        } else if llms_aliases.iter().any(|alias| alias == requested) {
+            Some(MatchKind::LlmsAlias)
+        } else {
+            None
+        };
+        if let Some(matched_via) = matched_via {
+            trace.candidates.push(Candidate {
+                source: src.clone(),
+                matched_via,
+            });
         }
+        trace.checked.push(CheckedSource {
+            source: src,
+            metadata_aliases,
+            llms_aliases,
+        });
     }
+    trace
+}
 
-    match resolved_sources.len() {
-        0 => Ok(None),
-        1 => Ok(resolved_sources.into_iter().next()),
-        _ => Err(anyhow::anyhow!(
-            "Alias '{}' is ambiguous across multiple sources: {} — use --source with a canonical name",
-            requested,
-            resolved_sources.join(", ")
-        )),
-    }
+/// Resolve a requested source identifier to its canonical alias.
+///
+/// See [`trace_source`] for the resolution order.
+/// - Returns Ok(None) if not found
+/// - Returns Err([`AmbiguousSource`]) if ambiguous across multiple sources
+///
+/// # Errors
+///
+/// Returns an error if the alias maps to more than one source.
+pub fn resolve_source(storage: &Storage, requested: &str) -> Result<Option<String>> {
+    let trace = trace_source(storage, requested);
+    Ok(trace.resolved()?.map(str::to_string))
 }
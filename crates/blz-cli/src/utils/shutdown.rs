@@ -0,0 +1,142 @@
+//! Cooperative shutdown for long-running commands.
+//!
+//! `sync --all`, `watch`, `serve`, `mcp-server`, and the generate flow call
+//! [`install`], after which the first SIGINT or SIGTERM only *requests* a
+//! shutdown: work already in flight (the source being synced, the page being
+//! scraped, the request being answered) runs to completion so no index is left
+//! half-written, nothing new is started, and the command prints what it got
+//! through. The process then exits with [`Signal::exit_code`] (130 for SIGINT,
+//! 143 for SIGTERM). A second signal exits immediately.
+//!
+//! Commands that never call [`install`] keep the default behavior of dying on
+//! the first signal.
+
+use std::fmt;
+use std::sync::OnceLock;
+
+use tokio::sync::watch;
+
+/// A signal that requested shutdown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Signal {
+    /// SIGINT (Ctrl-C).
+    Interrupt,
+    /// SIGTERM.
+    Terminate,
+}
+
+impl Signal {
+    /// Conventional shell exit status for a process stopped by this signal.
+    #[must_use]
+    pub const fn exit_code(self) -> i32 {
+        match self {
+            Self::Interrupt => 130,
+            Self::Terminate => 143,
+        }
+    }
+}
+
+impl fmt::Display for Signal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Interrupt => "SIGINT",
+            Self::Terminate => "SIGTERM",
+        })
+    }
+}
+
+static STATE: OnceLock<watch::Sender<Option<Signal>>> = OnceLock::new();
+
+/// Handle SIGINT and SIGTERM cooperatively for the rest of the process.
+///
+/// Must be called from within the Tokio runtime. Handlers are registered
+/// before this returns; calling it again is a no-op.
+pub fn install() {
+    let mut first = false;
+    STATE.get_or_init(|| {
+        first = true;
+        watch::channel(None).0
+    });
+    if !first {
+        return;
+    }
+    let mut listener = Listener::register();
+    tokio::spawn(async move {
+        let signal = listener.next().await;
+        eprintln!("Received {signal}; finishing work in progress (send again to exit immediately)");
+        if let Some(state) = STATE.get() {
+            state.send_replace(Some(signal));
+        }
+        let signal = listener.next().await;
+        std::process::exit(signal.exit_code());
+    });
+}
+
+/// The signal that requested shutdown, if any.
+#[must_use]
+pub fn requested() -> Option<Signal> {
+    STATE.get().and_then(|state| *state.borrow())
+}
+
+/// Resolve once shutdown is requested; never resolves if [`install`] was not
+/// called.
+pub async fn cancelled() {
+    let Some(state) = STATE.get() else {
+        return std::future::pending().await;
+    };
+    let mut rx = state.subscribe();
+    if rx.wait_for(Option::is_some).await.is_err() {
+        std::future::pending::<()>().await;
+    }
+}
+
+/// Registered signal handlers; a handler that cannot be registered never fires.
+struct Listener {
+    #[cfg(unix)]
+    interrupt: Option<tokio::signal::unix::Signal>,
+    #[cfg(unix)]
+    terminate: Option<tokio::signal::unix::Signal>,
+}
+
+impl Listener {
+    #[cfg(unix)]
+    fn register() -> Self {
+        use tokio::signal::unix::{SignalKind, signal};
+        Self {
+            interrupt: signal(SignalKind::interrupt()).ok(),
+            terminate: signal(SignalKind::terminate()).ok(),
+        }
+    }
+
+    #[cfg(not(unix))]
+    const fn register() -> Self {
+        Self {}
+    }
+
+    // `tokio::select!` expands to a `panic!` for its all-branches-disabled arm
+    #[cfg(unix)]
+    #[allow(clippy::disallowed_macros)]
+    async fn next(&mut self) -> Signal {
+        async fn recv(signal: Option<&mut tokio::signal::unix::Signal>) {
+            match signal {
+                Some(signal) => {
+                    signal.recv().await;
+                },
+                None => std::future::pending().await,
+            }
+        }
+        tokio::select! {
+            () = recv(self.interrupt.as_mut()) => Signal::Interrupt,
+            () = recv(self.terminate.as_mut()) => Signal::Terminate,
+        }
+    }
+
+    #[cfg(not(unix))]
+    #[allow(clippy::unused_self)]
+    async fn next(&mut self) -> Signal {
+        if tokio::signal::ctrl_c().await.is_err() {
+            std::future::pending::<()>().await;
+        }
+        Signal::Interrupt
+    }
+}
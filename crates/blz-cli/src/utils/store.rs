@@ -4,10 +4,13 @@ use std::path::{Path, PathBuf};
 
 use blz_core::profile;
 
+use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use tracing::warn;
 
+use crate::utils::preferences::CliPreferences;
+
 const STORE_FILENAME: &str = "data.json";
 const CURRENT_SCHEMA_VERSION: u32 = 1;
 
@@ -64,7 +67,10 @@ pub fn load_store() -> BlzStore {
                 }
             },
             Err(err) => {
-                warn!("failed to parse blz.json at {}: {err}", path.display());
+                warn!(
+                    "failed to parse blz.json at {}: {err} (run `blz doctor --fix` to repair)",
+                    path.display()
+                );
                 BlzStore::default()
             },
         },
@@ -90,6 +96,106 @@ pub fn save_store(store: &BlzStore) -> std::io::Result<()> {
     fs::write(path, data)
 }
 
+/// Integrity of an on-disk CLI state file (preferences store or history log).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileIntegrity {
+    /// The file has not been created yet.
+    Missing,
+    /// The file parsed cleanly.
+    Healthy,
+    /// The file exists but could not be fully parsed.
+    Corrupted(String),
+}
+
+/// Check whether the preferences store can be read without losing data.
+pub fn inspect_store() -> FileIntegrity {
+    let path = store_path();
+    let bytes = match fs::read(&path) {
+        Ok(bytes) => bytes,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return FileIntegrity::Missing,
+        Err(err) => return FileIntegrity::Corrupted(format!("unreadable: {err}")),
+    };
+
+    let store = match serde_json::from_slice::<BlzStore>(&bytes) {
+        Ok(store) => store,
+        Err(err) => return FileIntegrity::Corrupted(format!("invalid JSON: {err}")),
+    };
+    if store.schema_version != CURRENT_SCHEMA_VERSION {
+        return FileIntegrity::Corrupted(format!(
+            "unsupported schema version {}",
+            store.schema_version
+        ));
+    }
+
+    let mut invalid_scopes = invalid_preference_scopes(&store);
+    if invalid_scopes.is_empty() {
+        return FileIntegrity::Healthy;
+    }
+    invalid_scopes.sort();
+    FileIntegrity::Corrupted(format!(
+        "invalid preferences for scope(s): {}",
+        invalid_scopes.join(", ")
+    ))
+}
+
+/// Back up a corrupted preferences store and regenerate it.
+///
+/// Scopes whose preferences still deserialize are kept; anything unreadable is
+/// reset to defaults. Returns the backup path when a repair was performed.
+///
+/// # Errors
+///
+/// Returns an error if the backup or regenerated store cannot be written.
+pub fn repair_store() -> std::io::Result<Option<PathBuf>> {
+    if !matches!(inspect_store(), FileIntegrity::Corrupted(_)) {
+        return Ok(None);
+    }
+
+    let path = store_path();
+    let backup = backup_file(&path)?;
+
+    let mut store = fs::read(&path)
+        .ok()
+        .and_then(|bytes| serde_json::from_slice::<BlzStore>(&bytes).ok())
+        .filter(|store| store.schema_version == CURRENT_SCHEMA_VERSION)
+        .unwrap_or_default();
+    for key in invalid_preference_scopes(&store) {
+        if let Some(record) = store.scopes.get_mut(&key) {
+            record.cli_preferences = Value::Null;
+        }
+    }
+    save_store(&store)?;
+
+    Ok(Some(backup))
+}
+
+fn invalid_preference_scopes(store: &BlzStore) -> Vec<String> {
+    store
+        .scopes
+        .iter()
+        .filter(|(_, record)| {
+            !record.cli_preferences.is_null()
+                && serde_json::from_value::<CliPreferences>(record.cli_preferences.clone()).is_err()
+        })
+        .map(|(key, _)| key.clone())
+        .collect()
+}
+
+/// Copy `path` to a timestamped `*.corrupt-<timestamp>` sibling.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be copied.
+pub fn backup_file(path: &Path) -> std::io::Result<PathBuf> {
+    let file_name = path
+        .file_name()
+        .map_or_else(|| "state".into(), |name| name.to_string_lossy());
+    let stamp = Utc::now().format("%Y%m%dT%H%M%S");
+    let backup = path.with_file_name(format!("{file_name}.corrupt-{stamp}"));
+    fs::copy(path, &backup)?;
+    Ok(backup)
+}
+
 fn store_path() -> PathBuf {
     if let Ok(file) = std::env::var("BLZ_CONFIG") {
         let trimmed = file.trim();
@@ -100,16 +206,17 @@ fn store_path() -> PathBuf {
     active_config_dir().join(STORE_FILENAME)
 }
 
-/// Resolve the active config directory (scoped or global).
+/// Resolve the active config directory (scoped or global), within the active
+/// named profile.
 pub fn active_config_dir() -> PathBuf {
     if let Ok(dir) = std::env::var("BLZ_CONFIG_DIR") {
         let trimmed = dir.trim();
         if !trimmed.is_empty() {
-            return Path::new(trimmed).to_path_buf();
+            return profile::scoped(Path::new(trimmed));
         }
     }
 
-    global_config_dir()
+    profile::scoped(&global_config_dir())
 }
 
 /// Resolve the global config directory for BLZ.
@@ -246,5 +353,48 @@ mod tests {
         })
     }
 
+    #[test]
+    fn inspect_store_flags_truncated_file() -> Result<()> {
+        with_temp_config_dir(|dir| {
+            assert_eq!(inspect_store(), FileIntegrity::Missing);
+
+            fs::write(dir.join(STORE_FILENAME), br#"{"schema_version": 1, "scop"#)?;
+            assert!(matches!(inspect_store(), FileIntegrity::Corrupted(_)));
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn repair_store_backs_up_and_keeps_valid_scopes() -> Result<()> {
+        with_temp_config_dir(|dir| {
+            let store = serde_json::json!({
+                "schema_version": CURRENT_SCHEMA_VERSION,
+                "scopes": {
+                    "global": {
+                        "cli_preferences": {"default_show": ["url"]},
+                        "user_settings": {}
+                    },
+                    "local:/tmp": {
+                        "cli_preferences": {"default_show": "not-a-list"},
+                        "user_settings": {}
+                    }
+                }
+            });
+            fs::write(dir.join(STORE_FILENAME), serde_json::to_vec(&store)?)?;
+            assert!(matches!(inspect_store(), FileIntegrity::Corrupted(_)));
+
+            let backup = repair_store()?.expect("repair should run");
+            assert!(backup.exists());
+            assert_eq!(inspect_store(), FileIntegrity::Healthy);
+
+            let repaired = load_store();
+            assert!(!repaired.scopes["global"].cli_preferences.is_null());
+            assert!(repaired.scopes["local:/tmp"].cli_preferences.is_null());
+
+            assert!(repair_store()?.is_none(), "healthy store needs no repair");
+            Ok(())
+        })
+    }
+
     // Tests for flavor overrides removed - feature eliminated in v1.0.0-beta.1
 }
@@ -0,0 +1,30 @@
+//! Fetcher settings shared by fetching commands: bandwidth limits, proxies,
+//! and TLS trust.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use anyhow::Result;
+use blz_core::throttle::Bandwidth;
+use blz_core::{Config, Fetcher, NetworkOptions};
+
+static INSECURE: AtomicBool = AtomicBool::new(false);
+
+/// Skip TLS certificate verification for fetchers built by this process
+/// (`--insecure` on `add` / `sync`).
+pub fn set_insecure(insecure: bool) {
+    INSECURE.store(insecure, Ordering::Relaxed);
+}
+
+/// Build a fetcher honoring the configured proxy and CA bundle.
+///
+/// The `--throttle` flag wins over `defaults.throttle` / `BLZ_THROTTLE`.
+pub fn fetcher(flag: Option<Bandwidth>) -> Result<Fetcher> {
+    let config = Config::load().ok();
+    let mut network = config
+        .as_ref()
+        .map(|config| NetworkOptions::from_defaults(&config.defaults))
+        .unwrap_or_default();
+    network.insecure = INSECURE.load(Ordering::Relaxed);
+    let throttle = flag.or_else(|| config.and_then(|config| config.defaults.throttle));
+    Ok(Fetcher::with_network(&network)?.with_throttle(throttle))
+}
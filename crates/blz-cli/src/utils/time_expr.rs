@@ -0,0 +1,140 @@
+//! Human-friendly time expressions (`2w`, `3 days ago`, `yesterday`,
+//! `2025-01-31`, RFC 3339) resolved to absolute UTC instants.
+
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, Utc};
+
+/// Parse a time expression relative to `now`.
+///
+/// Accepted forms:
+/// - relative: `90m`, `12h`, `3d`, `2w`, `1mo`, optionally spelled out and
+///   suffixed with `ago` (`2 weeks ago`)
+/// - `now`, `today` (midnight UTC), `yesterday` (24 hours before `now`)
+/// - dates and timestamps: `2025-01-31`, `2025-01-31T12:00:00Z`, and archive
+///   ids such as `2025-01-31T12-00-00Z`
+///
+/// # Errors
+///
+/// Returns a message describing the accepted forms if `input` matches none.
+pub fn parse_time_expr(input: &str, now: DateTime<Utc>) -> Result<DateTime<Utc>, String> {
+    let expr = input.trim().to_ascii_lowercase();
+    match expr.as_str() {
+        "now" => return Ok(now),
+        "today" => {
+            return Ok(now.date_naive().and_time(chrono::NaiveTime::MIN).and_utc());
+        },
+        "yesterday" => return Ok(now - Duration::days(1)),
+        _ => {},
+    }
+
+    if let Some(ago) = parse_relative(&expr) {
+        return now
+            .checked_sub_signed(ago)
+            .ok_or_else(|| format!("time expression '{input}' is out of range"));
+    }
+
+    let raw = input.trim();
+    if let Ok(ts) = DateTime::parse_from_rfc3339(raw) {
+        return Ok(ts.with_timezone(&Utc));
+    }
+    if let Ok(ts) = NaiveDateTime::parse_from_str(raw, "%Y-%m-%dT%H-%M-%SZ") {
+        return Ok(ts.and_utc());
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(raw, "%Y-%m-%d") {
+        return Ok(date.and_time(chrono::NaiveTime::MIN).and_utc());
+    }
+
+    Err(format!(
+        "invalid time expression '{input}' (expected e.g. 2w, 3 days ago, yesterday, 2025-01-31)"
+    ))
+}
+
+/// Parse `<amount><unit>` with an optional `ago`, e.g. `2w` or `3 days ago`.
+fn parse_relative(expr: &str) -> Option<Duration> {
+    let expr = expr.strip_suffix("ago").unwrap_or(expr).trim_end();
+    let split = expr.find(|c: char| !c.is_ascii_digit())?;
+    let (digits, unit) = expr.split_at(split);
+    let amount: i64 = digits.parse().ok()?;
+    let minutes_per_unit = match unit.trim_start() {
+        "m" | "min" | "mins" | "minute" | "minutes" => 1,
+        "h" | "hr" | "hrs" | "hour" | "hours" => 60,
+        "d" | "day" | "days" => 60 * 24,
+        "w" | "wk" | "wks" | "week" | "weeks" => 60 * 24 * 7,
+        "mo" | "month" | "months" => 60 * 24 * 30,
+        _ => return None,
+    };
+    amount
+        .checked_mul(minutes_per_unit)
+        .and_then(Duration::try_minutes)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2025-06-15T12:30:00Z")
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn at(rfc3339: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(rfc3339)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn relative_expressions() {
+        assert_eq!(parse_time_expr("2w", now()), Ok(at("2025-06-01T12:30:00Z")));
+        assert_eq!(
+            parse_time_expr("3 days ago", now()),
+            Ok(at("2025-06-12T12:30:00Z"))
+        );
+        assert_eq!(
+            parse_time_expr("90m", now()),
+            Ok(at("2025-06-15T11:00:00Z"))
+        );
+        assert_eq!(
+            parse_time_expr("1 Month", now()),
+            Ok(at("2025-05-16T12:30:00Z"))
+        );
+    }
+
+    #[test]
+    fn named_expressions() {
+        assert_eq!(parse_time_expr("now", now()), Ok(now()));
+        assert_eq!(
+            parse_time_expr("today", now()),
+            Ok(at("2025-06-15T00:00:00Z"))
+        );
+        assert_eq!(
+            parse_time_expr("Yesterday", now()),
+            Ok(at("2025-06-14T12:30:00Z"))
+        );
+    }
+
+    #[test]
+    fn absolute_expressions() {
+        assert_eq!(
+            parse_time_expr("2025-01-31", now()),
+            Ok(at("2025-01-31T00:00:00Z"))
+        );
+        assert_eq!(
+            parse_time_expr("2025-01-31T08:00:00+02:00", now()),
+            Ok(at("2025-01-31T06:00:00Z"))
+        );
+        assert_eq!(
+            parse_time_expr("2025-01-31T06-07-08Z", now()),
+            Ok(at("2025-01-31T06:07:08Z"))
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_expressions() {
+        for input in ["", "soon", "2 fortnights", "w2", "-3d"] {
+            let err = parse_time_expr(input, now()).unwrap_err();
+            assert!(err.contains("invalid time expression"), "{input}: {err}");
+        }
+    }
+}
@@ -32,6 +32,38 @@ pub fn find_heading_span(entries: &[TocEntry], heading_path: &[String]) -> Optio
     find_entry_by_path(entries, heading_path).and_then(|entry| parse_line_span(&entry.lines))
 }
 
+/// Prefix that marks a `get` target as a heading ordinal (e.g. `bun:§2.3.1`).
+pub const ORDINAL_MARKER: char = '§';
+
+/// Ordinal path for the `index`-th (0-based) child of `parent`.
+///
+/// Ordinals are 1-based and dot-separated; top-level headings pass an empty
+/// parent, so the second child of the first heading is `1.2`.
+#[must_use]
+pub fn child_ordinal(parent: &str, index: usize) -> String {
+    if parent.is_empty() {
+        (index + 1).to_string()
+    } else {
+        format!("{parent}.{}", index + 1)
+    }
+}
+
+/// Find the heading addressed by an ordinal path such as `2.3.1`.
+///
+/// Returns `None` for malformed ordinals or positions past the end of the TOC.
+#[must_use]
+pub fn find_entry_by_ordinal<'a>(entries: &'a [TocEntry], ordinal: &str) -> Option<&'a TocEntry> {
+    let mut level = entries;
+    let mut found = None;
+    for part in ordinal.split('.') {
+        let index = part.trim().parse::<usize>().ok()?.checked_sub(1)?;
+        let entry = level.get(index)?;
+        level = &entry.children;
+        found = Some(entry);
+    }
+    found
+}
+
 /// Find the most specific heading that contains the provided line number.
 #[must_use]
 pub fn find_heading_for_line(
@@ -55,6 +87,39 @@ pub fn find_heading_for_line(
     search(entries, line)
 }
 
+/// Line span of the heading section next to the lines `start..=end`.
+///
+/// Sections are visited in document order regardless of depth: going forward
+/// picks the first section starting after `end`, going back picks the last
+/// section ending before `start`.
+#[must_use]
+pub fn adjacent_section(
+    entries: &[TocEntry],
+    (start, end): (usize, usize),
+    forward: bool,
+) -> Option<(usize, usize)> {
+    fn collect(entries: &[TocEntry], spans: &mut Vec<(usize, usize)>) {
+        for entry in entries {
+            spans.extend(parse_line_span(&entry.lines));
+            collect(&entry.children, spans);
+        }
+    }
+
+    let mut spans = Vec::new();
+    collect(entries, &mut spans);
+    spans.sort_unstable();
+    if forward {
+        spans
+            .into_iter()
+            .find(|&(section_start, _)| section_start > end)
+    } else {
+        spans
+            .into_iter()
+            .rev()
+            .find(|&(_, section_end)| section_end < start)
+    }
+}
+
 #[must_use]
 pub fn heading_level_from_line(line: &str) -> Option<usize> {
     let trimmed = line.trim_start();
@@ -71,6 +136,65 @@ pub fn heading_level_from_line(line: &str) -> Option<usize> {
     }
 }
 
+/// Line spans (1-based, inclusive) of fenced code blocks in a document.
+///
+/// Fences open with three or more backticks or tildes (indented at most three
+/// spaces) and close with at least as many of the same character. An unclosed
+/// fence runs to the end of the document.
+#[must_use]
+pub fn fence_spans(lines: &[String]) -> Vec<(usize, usize)> {
+    let mut spans = Vec::new();
+    let mut open: Option<(usize, char, usize)> = None;
+
+    for (idx, line) in lines.iter().enumerate() {
+        let trimmed = line.trim_start_matches(' ');
+        if line.len() - trimmed.len() > 3 {
+            continue;
+        }
+        let Some(ch) = trimmed.chars().next().filter(|ch| *ch == '`' || *ch == '~') else {
+            continue;
+        };
+        let len = trimmed.chars().take_while(|c| *c == ch).count();
+        if len < 3 {
+            continue;
+        }
+        match open {
+            None => open = Some((idx + 1, ch, len)),
+            Some((start, open_ch, open_len)) if ch == open_ch && len >= open_len => {
+                spans.push((start, idx + 1));
+                open = None;
+            },
+            Some(_) => {},
+        }
+    }
+
+    if let Some((start, _, _)) = open {
+        spans.push((start, lines.len()));
+    }
+    spans
+}
+
+/// Grow `start..=end` so it never cuts through a fenced code block.
+///
+/// Returns `None` when the span does not overlap any fence in `fences`.
+#[must_use]
+pub fn expand_to_fences(
+    fences: &[(usize, usize)],
+    start: usize,
+    end: usize,
+) -> Option<(usize, usize)> {
+    fences
+        .iter()
+        .filter(|(fence_start, fence_end)| *fence_start <= end && *fence_end >= start)
+        .fold(
+            None,
+            |span: Option<(usize, usize)>, (fence_start, fence_end)| {
+                let (lo, hi) = span.unwrap_or((start, end));
+                Some((lo.min(*fence_start), hi.max(*fence_end)))
+            },
+        )
+}
+
 /// Raw block slice extracted from a document.
 #[derive(Debug, Clone)]
 pub struct BlockSlice {
@@ -167,23 +291,37 @@ pub fn finalize_block_slice(block: BlockSlice) -> FinalizedBlock {
 }
 
 #[cfg(test)]
+#[allow(clippy::unwrap_used)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn counts_nested_headings() {
-        let toc = vec![TocEntry {
+    fn entry(name: &str, lines: &str, children: Vec<TocEntry>) -> TocEntry {
+        TocEntry {
+            heading_path: vec![name.into()],
+            heading_path_display: None,
+            heading_path_normalized: None,
+            lines: lines.into(),
+            anchor: None,
+            id: None,
+            children,
+        }
+    }
+
+    fn nested_toc() -> TocEntry {
+        TocEntry {
             heading_path: vec!["Root".into()],
             heading_path_display: Some(vec!["Root".into()]),
             heading_path_normalized: Some(vec!["root".into()]),
             lines: "1-10".into(),
             anchor: None,
+            id: None,
             children: vec![TocEntry {
                 heading_path: vec!["Root".into(), "Child".into()],
                 heading_path_display: Some(vec!["Root".into(), "Child".into()]),
                 heading_path_normalized: Some(vec!["root".into(), "child".into()]),
                 lines: "2-5".into(),
                 anchor: None,
+                id: None,
                 children: vec![TocEntry {
                     heading_path: vec!["Root".into(), "Child".into(), "Grandchild".into()],
                     heading_path_display: Some(vec![
@@ -198,14 +336,81 @@ mod tests {
                     ]),
                     lines: "3-4".into(),
                     anchor: None,
+                    id: None,
                     children: Vec::new(),
                 }],
             }],
-        }];
+        }
+    }
+
+    #[test]
+    fn counts_nested_headings() {
+        let toc = vec![nested_toc()];
 
         assert_eq!(count_headings(&toc), 3);
     }
 
+    #[test]
+    fn ordinals_address_nested_headings() {
+        let toc = vec![entry("Intro", "1-4", Vec::new()), nested_toc()];
+
+        assert_eq!(child_ordinal("", 0), "1");
+        assert_eq!(child_ordinal("2.1", 2), "2.1.3");
+        assert_eq!(find_entry_by_ordinal(&toc, "1").unwrap().lines, "1-4");
+        assert_eq!(find_entry_by_ordinal(&toc, "2.1.1").unwrap().lines, "3-4");
+        assert!(find_entry_by_ordinal(&toc, "2.2").is_none());
+        assert!(find_entry_by_ordinal(&toc, "0").is_none());
+        assert!(find_entry_by_ordinal(&toc, "2.x").is_none());
+        assert!(find_entry_by_ordinal(&toc, "").is_none());
+    }
+
+    #[test]
+    fn adjacent_sections_follow_document_order() {
+        let toc = vec![
+            entry("Intro", "1", Vec::new()),
+            entry("Install", "2-4", vec![entry("Linux", "5-8", Vec::new())]),
+            entry("Usage", "9-12", Vec::new()),
+        ];
+
+        assert_eq!(adjacent_section(&toc, (2, 4), true), Some((5, 8)));
+        assert_eq!(adjacent_section(&toc, (6, 7), true), Some((9, 12)));
+        assert_eq!(adjacent_section(&toc, (9, 12), true), None);
+        assert_eq!(adjacent_section(&toc, (9, 12), false), Some((5, 8)));
+        assert_eq!(adjacent_section(&toc, (6, 7), false), Some((2, 4)));
+        assert_eq!(adjacent_section(&toc, (1, 1), false), None);
+    }
+
+    #[test]
+    fn fence_spans_match_markers_and_run_to_eof() {
+        let lines: Vec<String> = [
+            "Intro",
+            "```rust",
+            "let x = 1;",
+            "~~~ not a close",
+            "```",
+            "",
+            "~~~~",
+            "```",
+            "~~~",
+        ]
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+
+        assert_eq!(fence_spans(&lines), vec![(2, 5), (7, 9)]);
+        assert_eq!(fence_spans(&lines[..8]), vec![(2, 5), (7, 8)]);
+    }
+
+    #[test]
+    fn expand_to_fences_covers_partial_overlaps() {
+        let fences = [(2, 5), (7, 9)];
+
+        assert_eq!(expand_to_fences(&fences, 3, 3), Some((2, 5)));
+        assert_eq!(expand_to_fences(&fences, 1, 2), Some((1, 5)));
+        assert_eq!(expand_to_fences(&fences, 4, 8), Some((2, 9)));
+        assert_eq!(expand_to_fences(&fences, 6, 6), None);
+    }
+
     #[test]
     fn empty_toc_returns_zero() {
         let toc: Vec<TocEntry> = Vec::new();
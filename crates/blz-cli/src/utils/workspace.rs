@@ -0,0 +1,295 @@
+//! Workspace-aware implicit scoping.
+//!
+//! A `.blz.toml` in the working directory or any parent (found the way git
+//! finds `.git`) marks a project workspace. Inside one, searches without
+//! `--source` only cover the sources the file declares (unless
+//! `--all-sources` is given), the file's output format and search defaults
+//! apply, and history and stored defaults are kept per workspace instead of
+//! per directory:
+//!
+//! ```toml
+//! # .blz.toml
+//! sources = ["react", "vite"]
+//! format = "json"
+//!
+//! [search]
+//! limit = 10
+//! show = ["url", "lines"]
+//! ```
+//!
+//! Project defaults sit between flags and global config: explicit flags and
+//! `--profile-out` win over them, and they win over stored CLI preferences and
+//! `config.toml`. The global `--global` flag (or `BLZ_GLOBAL`) ignores the
+//! workspace for one invocation.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use anyhow::{Context, Result, anyhow};
+use blz_core::Storage;
+use serde::Deserialize;
+use tracing::{debug, warn};
+
+use crate::args::ShowComponent;
+use crate::cli::Cli;
+use crate::utils::output_profile::{SearchFlags, format_arg_mut, parse_format};
+use crate::utils::resolver;
+
+/// File name marking a project workspace.
+pub const PROJECT_CONFIG_FILE: &str = ".blz.toml";
+
+static GLOBAL: AtomicBool = AtomicBool::new(false);
+
+/// Contents of a `.blz.toml` project config.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ProjectConfig {
+    /// Sources searched when no `--source` is given (aliases or canonical names).
+    #[serde(default)]
+    pub sources: Vec<String>,
+    /// Default output format for commands with `--format`.
+    #[serde(default)]
+    pub format: Option<String>,
+    /// Defaults for `query`, `find`, and `search`.
+    #[serde(default)]
+    pub search: SearchDefaults,
+}
+
+/// `[search]` table of a project config; each key mirrors a search flag.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct SearchDefaults {
+    /// Results per page (`--limit`).
+    pub limit: Option<usize>,
+    /// Token budget per page (`--max-tokens`).
+    pub max_tokens: Option<usize>,
+    /// Extra fields on each hit (`--show`).
+    #[serde(default)]
+    pub show: Vec<String>,
+    /// Typo tolerance (`--fuzzy`), overriding `defaults.fuzzy` in config.
+    pub fuzzy: Option<u8>,
+    /// Score decimal places (`--score-precision`).
+    pub score_precision: Option<u8>,
+}
+
+/// A discovered project workspace.
+#[derive(Debug, Clone)]
+pub struct Workspace {
+    /// Directory containing the project config.
+    pub root: PathBuf,
+    /// Parsed project config.
+    pub config: ProjectConfig,
+}
+
+impl Workspace {
+    /// Load the workspace defined by `config_path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or parsed.
+    pub fn load(config_path: &Path) -> Result<Self> {
+        let raw = fs::read_to_string(config_path)
+            .with_context(|| format!("Failed to read {}", config_path.display()))?;
+        let config: ProjectConfig = toml::from_str(&raw)
+            .with_context(|| format!("Failed to parse {}", config_path.display()))?;
+        let root = config_path
+            .parent()
+            .map_or_else(|| PathBuf::from("."), Path::to_path_buf);
+        Ok(Self {
+            root: root.canonicalize().unwrap_or(root),
+            config,
+        })
+    }
+
+    /// Preference and history scope shared by every directory in the workspace.
+    pub fn scope_key(&self) -> String {
+        format!("workspace:{}", self.root.to_string_lossy())
+    }
+
+    /// Narrow `sources` to the ones this workspace declares.
+    ///
+    /// Declared names may be metadata aliases. Returns `sources` unchanged when
+    /// nothing is declared.
+    pub fn restrict(&self, storage: &Storage, sources: Vec<String>) -> Vec<String> {
+        if self.config.sources.is_empty() {
+            return sources;
+        }
+        let declared: Vec<String> = self
+            .config
+            .sources
+            .iter()
+            .filter_map(|name| match resolver::resolve_source(storage, name) {
+                Ok(Some(canonical)) => Some(canonical),
+                Ok(None) => {
+                    warn!(
+                        "Workspace source '{name}' is not installed (declared in {})",
+                        self.root.join(PROJECT_CONFIG_FILE).display()
+                    );
+                    None
+                },
+                Err(e) => {
+                    warn!("Ignoring workspace source '{name}': {e}");
+                    None
+                },
+            })
+            .collect();
+        sources
+            .into_iter()
+            .filter(|alias| declared.contains(alias))
+            .collect()
+    }
+}
+
+/// Fill in flags left unset on the command line from the active workspace.
+///
+/// Runs after `--profile-out` is applied and before stored preferences, so a
+/// project default only applies where neither a flag nor the output profile
+/// set a value. Like an unreadable config, an invalid value (unknown format or
+/// field, out-of-range fuzzy distance) is reported and the project defaults
+/// are skipped rather than failing the command.
+pub fn apply_defaults(cli: &mut Cli) {
+    let Some(workspace) = active() else {
+        return;
+    };
+    if let Err(e) = apply_project_config(cli, &workspace.config) {
+        warn!(
+            "Ignoring defaults in {}: {e}",
+            workspace.root.join(PROJECT_CONFIG_FILE).display()
+        );
+    }
+}
+
+fn apply_project_config(cli: &mut Cli, config: &ProjectConfig) -> Result<()> {
+    let format = config.format.as_deref().map(parse_format).transpose()?;
+    let defaults = &config.search;
+    let show = defaults
+        .show
+        .iter()
+        .map(|name| name.parse::<ShowComponent>())
+        .collect::<Result<Vec<_>, _>>()?;
+    if defaults.fuzzy.is_some_and(|distance| distance > 2) {
+        return Err(anyhow!("search.fuzzy must be between 0 and 2"));
+    }
+
+    let Some(command) = cli.command.as_mut() else {
+        return Ok(());
+    };
+    if let Some(format) = format {
+        if let Some(arg) =
+            format_arg_mut(command).filter(|arg| !arg.is_explicit() && arg.format.is_none())
+        {
+            arg.format = Some(format);
+        }
+    }
+
+    let Some(flags) = SearchFlags::of(command) else {
+        return Ok(());
+    };
+    if flags.limit.is_none() && !flags.all {
+        *flags.limit = defaults.limit;
+    }
+    if flags.max_tokens.is_none() {
+        *flags.max_tokens = defaults.max_tokens;
+    }
+    if flags.show.is_empty() {
+        *flags.show = show;
+    }
+    if flags.fuzzy.is_none() {
+        *flags.fuzzy = defaults.fuzzy;
+    }
+    if flags.score_precision.is_none() {
+        *flags.score_precision = defaults.score_precision;
+    }
+    Ok(())
+}
+
+/// Record the global `--global` flag.
+pub fn set_global(global: bool) {
+    GLOBAL.store(global, Ordering::Relaxed);
+}
+
+/// The workspace containing the working directory, unless `--global` is set.
+///
+/// Discovery runs once per process. An unreadable config is reported and
+/// treated as no workspace so it cannot break unrelated commands.
+pub fn active() -> Option<&'static Workspace> {
+    static ACTIVE: OnceLock<Option<Workspace>> = OnceLock::new();
+    if GLOBAL.load(Ordering::Relaxed) {
+        return None;
+    }
+    ACTIVE
+        .get_or_init(|| {
+            let path = std::env::current_dir()
+                .ok()
+                .and_then(|dir| find_config(&dir))?;
+            match Workspace::load(&path) {
+                Ok(workspace) => {
+                    debug!("Using workspace {}", workspace.root.display());
+                    Some(workspace)
+                },
+                Err(e) => {
+                    warn!("Ignoring project config: {e:#}");
+                    None
+                },
+            }
+        })
+        .as_ref()
+}
+
+/// Find the nearest project config at or above `start`.
+pub fn find_config(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .map(|dir| dir.join(PROJECT_CONFIG_FILE))
+        .find(|path| path.is_file())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_nearest_config_in_ancestors() -> Result<()> {
+        let temp = tempfile::tempdir()?;
+        let nested = temp.path().join("crates").join("app");
+        fs::create_dir_all(&nested)?;
+        assert_eq!(find_config(&nested), None);
+
+        fs::write(
+            temp.path().join(PROJECT_CONFIG_FILE),
+            "sources = [\"react\"]\n",
+        )?;
+        let found = find_config(&nested).expect("config in ancestor");
+        assert_eq!(found, temp.path().join(PROJECT_CONFIG_FILE));
+
+        let workspace = Workspace::load(&found)?;
+        assert_eq!(workspace.config.sources, ["react"]);
+        assert!(workspace.scope_key().starts_with("workspace:"));
+        Ok(())
+    }
+
+    fn project(raw: &str) -> ProjectConfig {
+        toml::from_str(raw).expect("valid project config")
+    }
+
+    #[test]
+    fn project_defaults_fill_unset_flags_only() -> Result<()> {
+        use crate::output::OutputFormat;
+        use clap::Parser;
+
+        let config =
+            project("format = \"jsonl\"\n[search]\nlimit = 7\nfuzzy = 1\nshow = [\"url\"]\n");
+        let mut cli = Cli::parse_from(["blz", "query", "hooks", "--limit", "3"]);
+        apply_project_config(&mut cli, &config)?;
+        let Some(crate::cli::Commands::Query(args)) = cli.command.as_ref() else {
+            anyhow::bail!("expected query");
+        };
+        assert_eq!(args.format.format, Some(OutputFormat::Jsonl));
+        assert_eq!(args.limit, Some(3));
+        assert_eq!(args.fuzzy, Some(1));
+        assert_eq!(args.show, vec![ShowComponent::Url]);
+
+        assert!(apply_project_config(&mut cli, &project("format = \"yaml\"\n")).is_err());
+        Ok(())
+    }
+}
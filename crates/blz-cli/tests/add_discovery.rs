@@ -0,0 +1,48 @@
+#![allow(missing_docs, clippy::expect_used, clippy::unwrap_used)]
+
+mod common;
+
+use common::blz_cmd;
+use serde_json::Value;
+use tempfile::tempdir;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[tokio::test]
+async fn add_discovers_llms_txt_under_docs_page() -> anyhow::Result<()> {
+    let data_dir = tempdir()?;
+    let server = MockServer::start().await;
+    Mock::given(method("HEAD"))
+        .and(path("/docs/llms.txt"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/docs/llms.txt"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string("# Guide\n\n## Install\nRun the installer.\n"),
+        )
+        .mount(&server)
+        .await;
+
+    let page = format!("{}/docs", server.uri());
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["add", "fixture", &page, "-y"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("via path-relative probe"));
+
+    let output = blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["info", "fixture", "--json"])
+        .output()?;
+    assert!(output.status.success());
+    let info: Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(
+        info["url"],
+        format!("{}/docs/llms.txt", server.uri()).as_str()
+    );
+    Ok(())
+}
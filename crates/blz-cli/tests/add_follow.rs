@@ -0,0 +1,83 @@
+#![allow(missing_docs, clippy::expect_used, clippy::unwrap_used)]
+
+mod common;
+
+use common::blz_cmd_with_dirs;
+use serde_json::Value;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+async fn mount_docs(server: &MockServer, install: &str) {
+    let index = format!(
+        "# Widgets\n\n> Widget docs.\n\n- [Install]({}/install.md): Setup guide\n",
+        server.uri()
+    );
+    Mock::given(method("HEAD"))
+        .and(path("/llms.txt"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/llms.txt"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(index))
+        .mount(server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/install.md"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(install))
+        .mount(server)
+        .await;
+}
+
+fn hits(stdout: &[u8]) -> Vec<Value> {
+    let payload: Value = serde_json::from_slice(stdout).unwrap();
+    payload["results"].as_array().unwrap().clone()
+}
+
+#[tokio::test]
+async fn follow_indexes_linked_pages_and_sync_refetches_them() -> anyhow::Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let config_dir = tempfile::tempdir()?;
+    let server = MockServer::start().await;
+    mount_docs(&server, "# Installation\n\nRun the turbine installer.\n").await;
+    let blz = || blz_cmd_with_dirs(data_dir.path(), config_dir.path());
+
+    blz()
+        .args([
+            "add",
+            "widgets",
+            &format!("{}/llms.txt", server.uri()),
+            "--follow",
+            "-y",
+        ])
+        .assert()
+        .success();
+
+    let stdout = blz()
+        .args(["query", "turbine", "--source", "widgets", "-f", "json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let results = hits(&stdout);
+    assert!(!results.is_empty());
+    assert_eq!(results[0]["headingPath"][0], "Installation");
+
+    server.reset().await;
+    mount_docs(&server, "# Installation\n\nRun the gondola installer.\n").await;
+    blz()
+        .args(["sync", "widgets", "--quiet"])
+        .assert()
+        .success();
+
+    let stdout = blz()
+        .args(["query", "gondola", "--source", "widgets", "-f", "json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    assert!(!hits(&stdout).is_empty());
+    Ok(())
+}
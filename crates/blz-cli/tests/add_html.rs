@@ -0,0 +1,97 @@
+#![allow(missing_docs, clippy::expect_used, clippy::unwrap_used)]
+
+mod common;
+
+use common::blz_cmd_with_dirs;
+use serde_json::Value;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+async fn mount_page(server: &MockServer, tool: &str) {
+    let page = format!(
+        "<!DOCTYPE html><html><head><title>Install | Widgets</title></head><body>\
+         <nav><a href=\"/\">Home</a></nav>\
+         <main><h1>Installation</h1>\
+         <p>Widgets ships as a single binary that you download from the releases page.</p>\
+         <h2>From source</h2><p>Build it with the {tool} toolchain and put the result on \
+         your PATH so every shell can find it.</p></main>\
+         <footer>Copyright Widgets</footer></body></html>"
+    );
+    Mock::given(method("HEAD"))
+        .and(path("/install.html"))
+        .respond_with(ResponseTemplate::new(200).insert_header("content-type", "text/html"))
+        .mount(server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/install.html"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(page)
+                .insert_header("content-type", "text/html"),
+        )
+        .mount(server)
+        .await;
+}
+
+fn heading_paths(stdout: &[u8]) -> Vec<Value> {
+    let payload: Value = serde_json::from_slice(stdout).unwrap();
+    payload["results"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|hit| hit["headingPath"].clone())
+        .collect()
+}
+
+#[tokio::test]
+async fn html_pages_are_converted_indexed_and_resynced() -> anyhow::Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let config_dir = tempfile::tempdir()?;
+    let server = MockServer::start().await;
+    mount_page(&server, "turbine").await;
+    let blz = || blz_cmd_with_dirs(data_dir.path(), config_dir.path());
+
+    blz()
+        .args([
+            "add",
+            "widgets",
+            &format!("{}/install.html", server.uri()),
+            "-y",
+        ])
+        .assert()
+        .success();
+
+    let cached = std::fs::read_to_string(data_dir.path().join("sources/widgets/llms.txt"))?;
+    assert!(cached.starts_with("# Installation"));
+    assert!(!cached.contains('<'));
+    assert!(!cached.contains("Copyright"));
+
+    let stdout = blz()
+        .args(["query", "turbine", "--source", "widgets", "-f", "json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    assert_eq!(
+        heading_paths(&stdout)[0],
+        serde_json::json!(["Installation", "From source"])
+    );
+
+    server.reset().await;
+    mount_page(&server, "gondola").await;
+    blz()
+        .args(["sync", "widgets", "--quiet"])
+        .assert()
+        .success();
+
+    let stdout = blz()
+        .args(["query", "gondola", "--source", "widgets", "-f", "json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    assert!(!heading_paths(&stdout).is_empty());
+    Ok(())
+}
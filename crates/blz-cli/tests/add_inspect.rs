@@ -0,0 +1,69 @@
+#![allow(missing_docs, clippy::expect_used, clippy::unwrap_used)]
+
+mod common;
+
+use common::blz_cmd;
+use serde_json::Value;
+use tempfile::tempdir;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[tokio::test]
+async fn inspect_reports_structure_without_persisting() -> anyhow::Result<()> {
+    let data_dir = tempdir()?;
+    let server = MockServer::start().await;
+    let doc = "# Usage\n\n## Install\nRun the installer.\n\n## Configuration\nEdit the config file.\n\n\
+               ## Translations\nSee https://docs.example.com/es/guia/ for Spanish.\n";
+    Mock::given(method("HEAD"))
+        .and(path("/llms.txt"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/llms.txt"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(doc))
+        .mount(&server)
+        .await;
+
+    let url = format!("{}/llms.txt", server.uri());
+    let output = blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["add", "fixture", &url, "--inspect"])
+        .output()?;
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let report: Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(report["wouldIndex"], true);
+    let inspection = &report["inspection"];
+    assert_eq!(inspection["headingsByLevel"]["1"], 1);
+    assert_eq!(inspection["headingsByLevel"]["2"], 3);
+    assert_eq!(inspection["languages"]["es"], 1);
+    assert_eq!(inspection["filter"]["enabled"], true);
+    assert_eq!(inspection["filter"]["blocksRemoved"], 1);
+    assert!(inspection["estimatedIndexBytes"].as_u64().unwrap() > 0);
+    assert_eq!(inspection["toc"][0]["title"], "Usage");
+    assert_eq!(
+        inspection["toc"][0]["children"][1]["title"],
+        "Configuration"
+    );
+
+    assert!(!data_dir.path().join("fixture").exists());
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["info", "fixture"])
+        .assert()
+        .failure();
+
+    // Plain dry runs keep their original shape
+    let output = blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["add", "fixture", &url, "--dry-run"])
+        .output()?;
+    let report: Value = serde_json::from_slice(&output.stdout)?;
+    assert!(report.get("inspection").is_none());
+    Ok(())
+}
@@ -0,0 +1,115 @@
+#![allow(missing_docs, clippy::expect_used, clippy::unwrap_used)]
+
+mod common;
+
+use common::blz_cmd;
+use serde_json::Value;
+use tempfile::tempdir;
+use wiremock::matchers::{header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+async fn mount_private_doc(server: &MockServer, body: &str) {
+    Mock::given(method("HEAD"))
+        .and(path("/llms.txt"))
+        .and(header("authorization", "Bearer s3cret-token"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/llms.txt"))
+        .and(header("authorization", "Bearer s3cret-token"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(body))
+        .mount(server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/llms.txt"))
+        .respond_with(ResponseTemplate::new(401))
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn bearer_token_is_sent_on_add_and_sync() -> anyhow::Result<()> {
+    let data_dir = tempdir()?;
+    let server = MockServer::start().await;
+    mount_private_doc(
+        &server,
+        "# Portal\n\n## Deploys\nUse the turbine pipeline.\n",
+    )
+    .await;
+    let url = format!("{}/llms.txt", server.uri());
+
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .env("CORP_DOCS_TOKEN", "s3cret-token")
+        .args([
+            "add",
+            "portal",
+            &url,
+            "--auth-bearer-env",
+            "CORP_DOCS_TOKEN",
+            "-y",
+        ])
+        .assert()
+        .success();
+
+    // Only the variable name is persisted, never the token
+    let settings = std::fs::read_to_string(data_dir.path().join("sources/portal/settings.toml"))?;
+    assert!(settings.contains("env:CORP_DOCS_TOKEN"));
+    assert!(!settings.contains("s3cret-token"));
+
+    server.reset().await;
+    mount_private_doc(
+        &server,
+        "# Portal\n\n## Deploys\nUse the gondola pipeline.\n",
+    )
+    .await;
+
+    // Without the variable, sync reports which one is missing
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .env_remove("CORP_DOCS_TOKEN")
+        .args(["refresh", "portal", "--quiet"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("CORP_DOCS_TOKEN"));
+
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .env("CORP_DOCS_TOKEN", "s3cret-token")
+        .args(["refresh", "portal", "--quiet"])
+        .assert()
+        .success();
+
+    let stdout = blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["query", "gondola", "--source", "portal", "-f", "json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let payload: Value = serde_json::from_slice(&stdout)?;
+    assert_eq!(payload["results"].as_array().unwrap().len(), 1);
+    Ok(())
+}
+
+#[test]
+fn auth_is_rejected_for_crate_sources() {
+    let data_dir = tempdir().unwrap();
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args([
+            "add",
+            "serde",
+            "crate:serde",
+            "--auth-header",
+            "X-Key=abc",
+            "-y",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "Auth options are only supported for llms.txt URLs",
+        ));
+}
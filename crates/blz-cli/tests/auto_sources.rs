@@ -0,0 +1,87 @@
+#![allow(missing_docs, clippy::expect_used, clippy::unwrap_used)]
+
+mod common;
+
+use common::blz_cmd;
+use serde_json::Value;
+use tempfile::TempDir;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+async fn add_source(data_dir: &TempDir, server: &MockServer, alias: &str, doc: &str) {
+    let route = format!("/{alias}/llms.txt");
+    Mock::given(method("HEAD"))
+        .and(path(route.as_str()))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(route.as_str()))
+        .respond_with(ResponseTemplate::new(200).set_body_string(doc))
+        .mount(server)
+        .await;
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["add", alias, &format!("{}{route}", server.uri()), "-y"])
+        .assert()
+        .success();
+}
+
+fn hit_aliases(data_dir: &TempDir, extra: &[&str]) -> Vec<String> {
+    let stdout = blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .env_remove("BLZ_AUTO_SOURCES")
+        .args(["query", "bundler", "--json"])
+        .args(extra)
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let payload: Value = serde_json::from_slice(&stdout).unwrap();
+    let mut aliases: Vec<String> = payload["results"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|hit| hit["alias"].as_str().unwrap().to_string())
+        .collect();
+    aliases.sort();
+    aliases.dedup();
+    aliases
+}
+
+#[tokio::test]
+async fn auto_sources_restricts_search_to_best_matching_topics() -> anyhow::Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let server = MockServer::start().await;
+    add_source(
+        &data_dir,
+        &server,
+        "fixture",
+        "# Toolkit\n\n## Bundler\nThe bundler emits one file per entry.\n",
+    )
+    .await;
+    add_source(
+        &data_dir,
+        &server,
+        "e2e",
+        "# Testing\n\n## Setup\nRun tests after the bundler finishes.\n",
+    )
+    .await;
+
+    assert_eq!(hit_aliases(&data_dir, &[]), ["e2e", "fixture"]);
+    assert_eq!(
+        hit_aliases(&data_dir, &["--auto-sources", "1"]),
+        ["fixture"]
+    );
+    // Explicit sources override the router, and 0 disables it.
+    assert_eq!(
+        hit_aliases(&data_dir, &["--auto-sources", "1", "-s", "e2e"]),
+        ["e2e"]
+    );
+    assert_eq!(
+        hit_aliases(&data_dir, &["--auto-sources", "0"]),
+        ["e2e", "fixture"]
+    );
+    Ok(())
+}
@@ -0,0 +1,104 @@
+#![allow(missing_docs, clippy::expect_used, clippy::unwrap_used)]
+
+mod common;
+
+use common::blz_cmd;
+use predicates::prelude::*;
+use serde_json::Value;
+use tempfile::{TempDir, tempdir};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+async fn seed_source() -> anyhow::Result<(TempDir, MockServer)> {
+    let data_dir = tempdir()?;
+    let server = MockServer::start().await;
+    let doc =
+        "# Bench\n\n## Install\nRun the installer.\n\n## Configuration\nEdit the config file.\n";
+    Mock::given(method("HEAD"))
+        .and(path("/llms.txt"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/llms.txt"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(doc))
+        .mount(&server)
+        .await;
+
+    let url = format!("{}/llms.txt", server.uri());
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["add", "fixture", &url, "-y"])
+        .assert()
+        .success();
+
+    Ok((data_dir, server))
+}
+
+#[tokio::test]
+async fn bench_reports_latency_and_passing_assertions() -> anyhow::Result<()> {
+    let (data_dir, _server) = seed_source().await?;
+
+    let stdout = blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args([
+            "bench",
+            "install",
+            "--iterations",
+            "3",
+            "--assert",
+            "p95-search<60s",
+            "--json",
+        ])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+
+    let json: Value = serde_json::from_slice(&stdout)?;
+    assert_eq!(json["sources"], serde_json::json!(["fixture"]));
+    assert_eq!(json["search"]["samples"], 3);
+    assert_eq!(json["open"]["samples"], 3);
+    assert_eq!(json["assertions"][0]["passed"], true);
+
+    // Summaries read a fraction of the bytes llms.json and metadata.json did
+    let metadata = &json["metadata"];
+    assert_eq!(metadata["latency"]["samples"], 3);
+    assert!(metadata["bytes"].as_u64().unwrap() > 0);
+    assert!(metadata["bytes"].as_u64() < metadata["fullBytes"].as_u64());
+    Ok(())
+}
+
+#[tokio::test]
+async fn bench_exits_non_zero_when_budget_is_exceeded() -> anyhow::Result<()> {
+    let (data_dir, _server) = seed_source().await?;
+
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args([
+            "bench",
+            "--iterations",
+            "2",
+            "--assert",
+            "max-search<0us",
+            "--json",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("performance assertion(s) failed"));
+    Ok(())
+}
+
+#[test]
+fn bench_rejects_malformed_assertion() -> anyhow::Result<()> {
+    let data_dir = tempdir()?;
+
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["bench", "--assert", "p95-search"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid assertion"));
+    Ok(())
+}
@@ -0,0 +1,82 @@
+#![allow(missing_docs, clippy::expect_used, clippy::unwrap_used)]
+
+mod common;
+
+use common::blz_cmd_with_dirs;
+use predicates::prelude::*;
+use serde_json::Value;
+
+#[test]
+fn export_then_import_rebuilds_missing_indices() -> anyhow::Result<()> {
+    let (source_data, source_config) = (tempfile::tempdir()?, tempfile::tempdir()?);
+    let (target_data, target_config) = (tempfile::tempdir()?, tempfile::tempdir()?);
+    let work = tempfile::tempdir()?;
+    let guide = work.path().join("guide.md");
+    std::fs::write(&guide, "# Guide\n\n## Install\n\nRun the installer.\n")?;
+    let bundle = work.path().join("docs.tar.zst");
+    let bundle_arg = bundle.to_str().unwrap();
+
+    let exporter = || blz_cmd_with_dirs(source_data.path(), source_config.path());
+    let importer = || blz_cmd_with_dirs(target_data.path(), target_config.path());
+
+    exporter()
+        .args(["add", "guide", guide.to_str().unwrap(), "-y"])
+        .assert()
+        .success();
+    exporter()
+        .args(["export", "--bundle", bundle_arg])
+        .assert()
+        .success();
+    assert!(bundle.is_file());
+    exporter()
+        .args(["export", "--bundle", bundle_arg])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--force"));
+
+    let stdout = importer()
+        .args(["import", bundle_arg, "-f", "json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let summary: Value = serde_json::from_slice(&stdout)?;
+    assert_eq!(summary["imported"], serde_json::json!(["guide"]));
+    assert_eq!(summary["reindexed"], serde_json::json!(["guide"]));
+
+    let stdout = importer()
+        .args(["query", "installer", "--source", "guide", "-f", "json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let payload: Value = serde_json::from_slice(&stdout)?;
+    assert!(!payload["results"].as_array().unwrap().is_empty());
+
+    let stdout = importer()
+        .args(["import", bundle_arg, "-f", "json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let summary: Value = serde_json::from_slice(&stdout)?;
+    assert_eq!(summary["skipped"], serde_json::json!(["guide"]));
+    Ok(())
+}
+
+#[test]
+fn import_rejects_files_that_are_not_bundles() -> anyhow::Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let config_dir = tempfile::tempdir()?;
+    let work = tempfile::tempdir()?;
+    let bogus = work.path().join("bogus.tar.zst");
+    std::fs::write(&bogus, "not a bundle")?;
+    blz_cmd_with_dirs(data_dir.path(), config_dir.path())
+        .args(["import", bogus.to_str().unwrap()])
+        .assert()
+        .failure();
+    Ok(())
+}
@@ -0,0 +1,109 @@
+#![allow(missing_docs, clippy::expect_used, clippy::unwrap_used)]
+
+mod common;
+
+use common::{blz_cmd, search_heading_paths};
+use serde_json::json;
+use wiremock::matchers::{header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+async fn mount_member(server: &MockServer, route: &str, body: &str, etag: &str) {
+    Mock::given(method("GET"))
+        .and(path(route))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("ETag", etag)
+                .set_body_string(body),
+        )
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn collection_members_are_prefixed_and_refreshed_individually() -> anyhow::Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let server = MockServer::start().await;
+    mount_member(
+        &server,
+        "/api/llms.txt",
+        "# API\n\n## Authentication\nSend a bearer token.\n",
+        "\"api-v1\"",
+    )
+    .await;
+    mount_member(
+        &server,
+        "/guides/llms.txt",
+        "# Guides\n\n## Quickstart\nInstall the client.\n",
+        "\"guides-v1\"",
+    )
+    .await;
+
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args([
+            "add",
+            "fixture",
+            "--member",
+            &format!("api={}/api/llms.txt", server.uri()),
+            "--member",
+            &format!("guides={}/guides/llms.txt", server.uri()),
+            "-y",
+        ])
+        .assert()
+        .success();
+
+    let paths = search_heading_paths(data_dir.path(), "fixture", "bearer");
+    assert_eq!(paths[0], json!(["api", "API", "Authentication"]));
+    let paths = search_heading_paths(data_dir.path(), "fixture", "client");
+    assert_eq!(paths[0], json!(["guides", "Guides", "Quickstart"]));
+
+    // Only the guides member changes; the API member must be revalidated
+    // with its own ETag and answer 304.
+    server.reset().await;
+    Mock::given(method("GET"))
+        .and(path("/api/llms.txt"))
+        .and(header("If-None-Match", "\"api-v1\""))
+        .respond_with(ResponseTemplate::new(304))
+        .expect(1)
+        .mount(&server)
+        .await;
+    mount_member(
+        &server,
+        "/guides/llms.txt",
+        "# Guides\n\n## Quickstart\nInstall the client.\n\n## Webhooks\nVerify the signature.\n",
+        "\"guides-v2\"",
+    )
+    .await;
+
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["refresh", "fixture", "--quiet"])
+        .assert()
+        .success();
+
+    let paths = search_heading_paths(data_dir.path(), "fixture", "signature");
+    assert_eq!(paths[0], json!(["guides", "Guides", "Webhooks"]));
+    let paths = search_heading_paths(data_dir.path(), "fixture", "bearer");
+    assert_eq!(paths[0], json!(["api", "API", "Authentication"]));
+
+    server.verify().await;
+    Ok(())
+}
+
+#[test]
+fn collection_rejects_duplicate_prefixes() {
+    let data_dir = tempfile::tempdir().unwrap();
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args([
+            "add",
+            "fixture",
+            "--member",
+            "api=https://example.com/a.txt",
+            "--member",
+            "API=https://example.com/b.txt",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("duplicate collection prefix"));
+}
@@ -1,6 +1,7 @@
 #![allow(clippy::expect_used, clippy::unwrap_used)]
 
 use assert_cmd::Command;
+use serde_json::Value;
 use std::path::Path;
 use std::sync::OnceLock;
 use std::time::Duration;
@@ -58,3 +59,23 @@ pub fn add_source(alias: &str, url: &str, data_dir: &Path, config_dir: &Path) {
         .assert()
         .success();
 }
+
+/// Search `source` in `data_dir` and return each hit's `headingPath`.
+#[allow(dead_code)]
+pub fn search_heading_paths(data_dir: &Path, source: &str, query: &str) -> Vec<Value> {
+    let stdout = blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir)
+        .args(["query", query, "--source", source, "-f", "json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let payload: Value = serde_json::from_slice(&stdout).unwrap();
+    payload["results"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|hit| hit["headingPath"].clone())
+        .collect()
+}
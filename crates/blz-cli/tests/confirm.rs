@@ -0,0 +1,72 @@
+#![allow(missing_docs, clippy::expect_used, clippy::unwrap_used)]
+
+mod common;
+
+use assert_cmd::Command;
+use common::blz_cmd;
+use tempfile::TempDir;
+
+/// A `blz` command with no confirmation bypass and no terminal attached.
+fn guarded_cmd(data_dir: &TempDir, config_dir: &TempDir) -> Command {
+    let mut cmd = blz_cmd();
+    cmd.env_remove("BLZ_FORCE_NON_INTERACTIVE")
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .env("BLZ_CONFIG_DIR", config_dir.path());
+    cmd
+}
+
+fn add_dir_source(data_dir: &TempDir, config_dir: &TempDir, alias: &str) -> TempDir {
+    let docs = tempfile::tempdir().unwrap();
+    std::fs::write(docs.path().join("guide.md"), "# Guide\n\nSome text.\n").unwrap();
+    guarded_cmd(data_dir, config_dir)
+        .args(["add", alias])
+        .arg(docs.path())
+        .args(["--type", "dir", "-y"])
+        .assert()
+        .success();
+    docs
+}
+
+#[test]
+fn destructive_commands_require_yes_without_a_terminal() -> anyhow::Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let config_dir = tempfile::tempdir()?;
+    let _docs = add_dir_source(&data_dir, &config_dir, "guide");
+
+    for args in [
+        vec!["rm", "guide"],
+        vec!["clear"],
+        vec!["stats", "--unused", "--prune"],
+        vec!["history", "--clear"],
+    ] {
+        guarded_cmd(&data_dir, &config_dir)
+            .args(&args)
+            .assert()
+            .failure()
+            .stderr(predicates::str::contains("--yes"));
+    }
+    assert!(data_dir.path().join("sources/guide").exists());
+
+    // --no-input never prompts, but --yes still goes through
+    guarded_cmd(&data_dir, &config_dir)
+        .args(["rm", "guide", "--yes", "--no-input"])
+        .assert()
+        .success();
+    assert!(!data_dir.path().join("sources/guide").exists());
+    Ok(())
+}
+
+#[test]
+fn confirm_policy_never_skips_prompts() -> anyhow::Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let config_dir = tempfile::tempdir()?;
+    let _docs = add_dir_source(&data_dir, &config_dir, "guide");
+
+    guarded_cmd(&data_dir, &config_dir)
+        .env("BLZ_CONFIRM", "never")
+        .args(["rm", "guide"])
+        .assert()
+        .success();
+    assert!(!data_dir.path().join("sources/guide").exists());
+    Ok(())
+}
@@ -0,0 +1,115 @@
+#![allow(missing_docs, clippy::expect_used, clippy::unwrap_used)]
+
+mod common;
+
+use common::{blz_cmd, search_heading_paths};
+use serde_json::json;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn rustdoc(method_doc: &str) -> String {
+    json!({
+        "root": 0,
+        "crate_version": "0.3.1",
+        "format_version": 39,
+        "index": {
+            "0": {"id": 0, "name": "demo", "visibility": "public", "docs": "Demo crate.",
+                  "inner": {"module": {"is_crate": true, "items": [1]}}},
+            "1": {"id": 1, "name": "Client", "visibility": "public", "docs": "An HTTP client.",
+                  "inner": {"struct": {"impls": [2]}}},
+            "2": {"id": 2, "name": null, "visibility": "default", "docs": null,
+                  "inner": {"impl": {"trait": null, "items": [3]}}},
+            "3": {"id": 3, "name": "connect", "visibility": "public", "docs": method_doc,
+                  "inner": {"function": {}}}
+        }
+    })
+    .to_string()
+}
+
+async fn mount_rustdoc(server: &MockServer, body: String) {
+    Mock::given(method("GET"))
+        .and(path("/crate/demo/latest/json.gz"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(body))
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn crate_source_is_rendered_and_synced() -> anyhow::Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let server = MockServer::start().await;
+    mount_rustdoc(&server, rustdoc("Open a connection.")).await;
+
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .env("BLZ_DOCS_RS_URL", server.uri())
+        .args(["add", "crate:demo", "-y"])
+        .assert()
+        .success();
+
+    let paths = search_heading_paths(data_dir.path(), "demo", "connection");
+    assert_eq!(
+        paths[0],
+        json!(["demo", "struct Client", "fn connect"]),
+        "methods nest under their type"
+    );
+
+    server.reset().await;
+    mount_rustdoc(&server, rustdoc("Open a pooled socket.")).await;
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .env("BLZ_DOCS_RS_URL", server.uri())
+        .args(["sync", "demo", "--quiet"])
+        .assert()
+        .success();
+
+    let paths = search_heading_paths(data_dir.path(), "demo", "pooled");
+    assert_eq!(paths[0], json!(["demo", "struct Client", "fn connect"]));
+    Ok(())
+}
+
+#[test]
+fn crate_source_rejects_invalid_names() {
+    let data_dir = tempfile::tempdir().unwrap();
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["add", "crate:not/a/crate"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("invalid crate name"));
+}
+
+#[tokio::test]
+async fn crate_flag_defaults_alias_to_crate_name() -> anyhow::Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let server = MockServer::start().await;
+    mount_rustdoc(&server, rustdoc("Open a connection.")).await;
+
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .env("BLZ_DOCS_RS_URL", server.uri())
+        .args(["add", "--crate", "demo", "-y"])
+        .assert()
+        .success();
+
+    let paths = search_heading_paths(data_dir.path(), "demo", "connection");
+    assert_eq!(paths[0], json!(["demo", "struct Client", "fn connect"]));
+    Ok(())
+}
+
+#[test]
+fn crate_flag_conflicts_with_url() {
+    let data_dir = tempfile::tempdir().unwrap();
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args([
+            "add",
+            "demo",
+            "https://example.com/llms.txt",
+            "--crate",
+            "demo",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("cannot be used with"));
+}
@@ -0,0 +1,68 @@
+#![allow(missing_docs, clippy::expect_used, clippy::unwrap_used)]
+
+mod common;
+
+use common::blz_cmd;
+use predicates::prelude::*;
+use serde_json::Value;
+use tempfile::tempdir;
+
+#[test]
+fn debug_args_reports_injected_default_command() -> anyhow::Result<()> {
+    let data_dir = tempdir()?;
+    let output = blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["debug-args", "--json", "--", "-s", "bun", "test runner"])
+        .output()?;
+    assert!(output.status.success());
+
+    let report: Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(report["original"][1], "-s");
+    assert_eq!(report["injectedAt"], 1);
+    assert_eq!(
+        report["rewritten"],
+        serde_json::json!(["blz", "default", "-s", "bun", "test runner"])
+    );
+    assert_eq!(report["subcommand"], "default");
+    assert!(report["parseError"].is_null());
+    assert_eq!(report["warnings"], serde_json::json!([]));
+    Ok(())
+}
+
+#[test]
+fn debug_args_leaves_explicit_subcommands_alone() -> anyhow::Result<()> {
+    let data_dir = tempdir()?;
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["debug-args", "--text", "--", "list", "--json"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("rewritten:  blz list --json"))
+        .stdout(predicate::str::contains("injected:   nothing"))
+        .stdout(predicate::str::contains("subcommand: list"));
+    Ok(())
+}
+
+#[test]
+fn mistyped_subcommand_warns_on_stderr() -> anyhow::Result<()> {
+    let data_dir = tempdir()?;
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["debug-args", "--text", "--", "serach", "hooks"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("did you mean 'blz search'?"));
+
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["serach", "hooks"])
+        .assert()
+        .stderr(predicate::str::contains("did you mean 'blz search'?"));
+
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["--quiet", "serach", "hooks"])
+        .assert()
+        .stderr(predicate::str::contains("did you mean").not());
+    Ok(())
+}
@@ -0,0 +1,65 @@
+#![allow(missing_docs, clippy::expect_used, clippy::unwrap_used)]
+
+mod common;
+
+use common::blz_cmd;
+use serde_json::Value;
+
+fn json(cmd: &mut assert_cmd::Command) -> Value {
+    let stdout = cmd.assert().success().get_output().stdout.clone();
+    serde_json::from_slice(&stdout).unwrap()
+}
+
+#[test]
+fn demo_sandbox_is_searchable_offline_and_cleans_up() -> anyhow::Result<()> {
+    let real = tempfile::tempdir()?;
+    let root = tempfile::tempdir()?;
+    let sandbox = root.path().join("sandbox");
+    let dir = sandbox.to_str().unwrap();
+
+    let report = json(
+        blz_cmd()
+            .env("BLZ_DATA_DIR", real.path())
+            .args(["demo", "--dir", dir, "--json"]),
+    );
+    assert_eq!(report["alias"], "tern");
+    assert!(report["headings"].as_u64().unwrap() > 10);
+    let data_dir = report["env"]["BLZ_DATA_DIR"].as_str().unwrap();
+    let config_dir = report["env"]["BLZ_CONFIG_DIR"].as_str().unwrap();
+    assert!(data_dir.starts_with(dir));
+    assert_eq!(
+        std::fs::read_dir(real.path())?.count(),
+        0,
+        "demo must not touch the active cache"
+    );
+
+    let get_example = report["examples"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(Value::as_str)
+        .find(|example| example.starts_with("blz get "))
+        .unwrap();
+    let citation = get_example.trim_start_matches("blz get ");
+
+    let results = json(
+        blz_cmd()
+            .env("BLZ_DATA_DIR", data_dir)
+            .env("BLZ_CONFIG_DIR", config_dir)
+            .args(["query", "useState", "--json"]),
+    );
+    assert_eq!(results["results"][0]["alias"], "tern");
+
+    let lines = json(
+        blz_cmd()
+            .env("BLZ_DATA_DIR", data_dir)
+            .env("BLZ_CONFIG_DIR", config_dir)
+            .args(["get", citation, "--json"]),
+    );
+    assert!(lines.to_string().contains("useState"));
+
+    let cleaned = json(blz_cmd().args(["demo", "--dir", dir, "--clean", "--json"]));
+    assert_eq!(cleaned["removed"], true);
+    assert!(!sandbox.exists());
+    Ok(())
+}
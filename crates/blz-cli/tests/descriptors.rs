@@ -0,0 +1,104 @@
+#![allow(missing_docs, clippy::expect_used, clippy::unwrap_used)]
+
+mod common;
+
+use common::blz_cmd;
+use serde_json::Value;
+use tempfile::TempDir;
+
+fn cmd(home: &TempDir) -> assert_cmd::Command {
+    let mut cmd = blz_cmd();
+    cmd.env("BLZ_DATA_DIR", home.path().join("data"))
+        .env("BLZ_CONFIG_DIR", home.path().join("config"));
+    cmd
+}
+
+fn add_handbook(home: &TempDir, extra: &[&str]) {
+    let file = home.path().join("handbook.md");
+    std::fs::write(
+        &file,
+        "# Handbook\n\n## Deploys\n\nUse the turbine pipeline.\n",
+    )
+    .unwrap();
+    cmd(home)
+        .args(["add", "handbook"])
+        .arg(&file)
+        .arg("-y")
+        .args(extra)
+        .assert()
+        .success();
+}
+
+#[test]
+fn descriptors_round_trip_between_installs() -> anyhow::Result<()> {
+    let curator = tempfile::tempdir()?;
+    add_handbook(
+        &curator,
+        &[
+            "--name",
+            "Team Handbook",
+            "--description",
+            "How we ship",
+            "--category",
+            "internal",
+            "--tags",
+            "ops,release",
+        ],
+    );
+    let export = curator.path().join("descriptors.json");
+    cmd(&curator)
+        .args(["list", "--export"])
+        .arg(&export)
+        .assert()
+        .success();
+
+    let file: Value = serde_json::from_str(&std::fs::read_to_string(&export)?)?;
+    assert_eq!(file["version"], 1);
+    let entry = &file["sources"][0];
+    assert_eq!(entry["alias"], "handbook");
+    assert_eq!(entry["name"], "Team Handbook");
+    assert_eq!(entry["tags"], serde_json::json!(["ops", "release"]));
+
+    let teammate = tempfile::tempdir()?;
+    add_handbook(&teammate, &[]);
+    let mut missing = file.clone();
+    missing["sources"]
+        .as_array_mut()
+        .unwrap()
+        .push(serde_json::json!({"alias": "absent", "url": "https://example.com/llms.txt"}));
+    let shared = teammate.path().join("shared.json");
+    std::fs::write(&shared, missing.to_string())?;
+
+    cmd(&teammate)
+        .args(["add", "--descriptors"])
+        .arg(&shared)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(
+            "1 updated, 0 unchanged, 1 not installed",
+        ))
+        .stdout(predicates::str::contains(
+            "blz add absent https://example.com/llms.txt",
+        ));
+
+    let listed = cmd(&teammate)
+        .args(["list", "--json", "--details"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let listed: Value = serde_json::from_slice(&listed)?;
+    let source = &listed[0];
+    assert_eq!(source["tags"], serde_json::json!(["ops", "release"]));
+    assert_eq!(source["descriptor"]["category"], "internal");
+    assert_eq!(source["descriptor"]["name"], "Team Handbook");
+
+    cmd(&teammate)
+        .args(["add", "--descriptors"])
+        .arg(&shared)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("0 updated, 1 unchanged"));
+    Ok(())
+}
@@ -0,0 +1,67 @@
+#![allow(missing_docs, clippy::expect_used, clippy::unwrap_used)]
+
+mod common;
+
+use common::blz_cmd;
+use serde_json::Value;
+use tempfile::{TempDir, tempdir};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+async fn seed_source() -> anyhow::Result<(TempDir, MockServer)> {
+    let data_dir = tempdir()?;
+    let server = MockServer::start().await;
+    let doc = "# Guide\n\n## Install\nInstall the runtime.\n\n## Upgrade\nUpgrade the runtime.\n";
+    Mock::given(method("HEAD"))
+        .and(path("/llms.txt"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/llms.txt"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(doc))
+        .mount(&server)
+        .await;
+
+    let url = format!("{}/llms.txt", server.uri());
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["add", "fixture", &url, "-y"])
+        .assert()
+        .success();
+
+    Ok((data_dir, server))
+}
+
+#[tokio::test]
+async fn deterministic_output_is_reproducible() -> anyhow::Result<()> {
+    let (data_dir, _server) = seed_source().await?;
+
+    let run = || -> anyhow::Result<Vec<u8>> {
+        let output = blz_cmd()
+            .env("BLZ_DATA_DIR", data_dir.path())
+            .args([
+                "query",
+                "runtime",
+                "--json",
+                "--deterministic",
+                "--no-history",
+            ])
+            .output()?;
+        assert!(output.status.success());
+        Ok(output.stdout)
+    };
+    let first = run()?;
+    assert_eq!(first, run()?);
+
+    let report: Value = serde_json::from_slice(&first)?;
+    assert_eq!(report["searchTimeMs"], 0);
+    let results = report["results"].as_array().unwrap();
+    assert_eq!(results.len(), 2);
+    for hit in results {
+        assert!(hit.get("fetchedAt").is_none());
+        let scaled = hit["score"].as_f64().unwrap() * 10.0;
+        assert!((scaled - scaled.round()).abs() < 1e-4, "{scaled}");
+    }
+    Ok(())
+}
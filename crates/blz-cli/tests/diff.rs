@@ -0,0 +1,100 @@
+#![allow(missing_docs, clippy::expect_used, clippy::unwrap_used)]
+
+mod common;
+
+use common::blz_cmd;
+use serde_json::Value;
+use tempfile::tempdir;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const OLD: &str = "# Guide\n\n## Install\nRun the installer.\n\n## Legacy\nOld notes.\n";
+const NEW: &str =
+    "# Guide\n\n## Install\nRun the installer.\nThen verify.\n\n## Plugins\nExtend the guide.\n";
+
+async fn serve(server: &MockServer, body: &str) {
+    Mock::given(method("HEAD"))
+        .and(path("/llms.txt"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/llms.txt"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(body))
+        .mount(server)
+        .await;
+}
+
+fn headings<'a>(report: &'a Value, key: &str) -> Vec<&'a str> {
+    report[key]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|item| {
+            item["headingPath"]
+                .as_array()
+                .unwrap()
+                .last()
+                .unwrap()
+                .as_str()
+                .unwrap()
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn diff_reports_sections_changed_by_sync() -> anyhow::Result<()> {
+    let tmp = tempdir()?;
+    let server = MockServer::start().await;
+    serve(&server, OLD).await;
+    blz_cmd()
+        .env("BLZ_DATA_DIR", tmp.path())
+        .args(["add", "guide", &format!("{}/llms.txt", server.uri()), "-y"])
+        .assert()
+        .success();
+
+    blz_cmd()
+        .env("BLZ_DATA_DIR", tmp.path())
+        .args(["diff", "guide"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("No previous version of 'guide'"));
+
+    server.reset().await;
+    serve(&server, NEW).await;
+    blz_cmd()
+        .env("BLZ_DATA_DIR", tmp.path())
+        .args(["sync", "guide"])
+        .assert()
+        .success();
+
+    let output = blz_cmd()
+        .env("BLZ_DATA_DIR", tmp.path())
+        .args(["diff", "guide", "--json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let report: Value = serde_json::from_slice(&output)?;
+    assert_eq!(report["previous"]["snapshot"], "v1");
+    assert_eq!(report["current"]["snapshot"], "current");
+    assert_eq!(headings(&report, "added"), vec!["Plugins"]);
+    assert_eq!(headings(&report, "removed"), vec!["Legacy"]);
+    assert_eq!(headings(&report, "changed"), vec!["Install"]);
+    assert_eq!(report["changed"][0]["changedNewLines"], "5");
+
+    blz_cmd()
+        .env("BLZ_DATA_DIR", tmp.path())
+        .args(["diff", "guide", "--between", "v1..current", "--jsonl"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(r#""kind":"added""#));
+    blz_cmd()
+        .env("BLZ_DATA_DIR", tmp.path())
+        .args(["diff", "guide", "--between", "v2..current"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("version 'v2' does not exist"));
+    Ok(())
+}
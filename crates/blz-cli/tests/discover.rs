@@ -0,0 +1,116 @@
+#![allow(missing_docs, clippy::expect_used, clippy::unwrap_used)]
+
+mod common;
+
+use common::blz_cmd_with_dirs;
+use serde_json::{Value, json};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[test]
+fn discover_maps_dependencies_to_a_plan() -> anyhow::Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let config_dir = tempfile::tempdir()?;
+    let project = tempfile::tempdir()?;
+    let package_json = project.path().join("package.json");
+    std::fs::write(
+        &package_json,
+        r#"{"dependencies": {"react": "^19", "left-pad": "^1", "ui": "workspace:*"},
+            "devDependencies": {"@types/node": "^22"}}"#,
+    )?;
+
+    let stdout = blz_cmd_with_dirs(data_dir.path(), config_dir.path())
+        .args(["discover", "--from"])
+        .arg(&package_json)
+        .args(["--no-probe", "-f", "json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let report: Value = serde_json::from_slice(&stdout)?;
+    assert_eq!(report["dependencies"], 2);
+    assert_eq!(report["sources"][0]["alias"], "react");
+    assert_eq!(report["sources"][0]["resolvedBy"], "registry");
+    assert_eq!(report["sources"][0]["installed"], false);
+    assert_eq!(report["unresolved"], json!(["left-pad"]));
+    Ok(())
+}
+
+#[test]
+fn discover_writes_a_manifest_with_crate_specs() -> anyhow::Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let config_dir = tempfile::tempdir()?;
+    let project = tempfile::tempdir()?;
+    let cargo_toml = project.path().join("Cargo.toml");
+    std::fs::write(
+        &cargo_toml,
+        "[package]\nname = \"app\"\n\n[dependencies]\ndemo = \"0.3\"\nlocal = { path = \"../local\" }\n",
+    )?;
+    let manifest = project.path().join("blz.sources.toml");
+
+    blz_cmd_with_dirs(data_dir.path(), config_dir.path())
+        .args(["discover", "--from"])
+        .arg(&cargo_toml)
+        .arg("--write")
+        .arg(&manifest)
+        .args(["--no-probe", "--quiet"])
+        .assert()
+        .success();
+
+    let written: toml::Value = toml::from_str(&std::fs::read_to_string(&manifest)?)?;
+    let sources = written["source"].as_array().unwrap();
+    assert_eq!(sources.len(), 1);
+    assert_eq!(sources[0]["alias"].as_str(), Some("demo"));
+    assert_eq!(sources[0]["url"].as_str(), Some("crate:demo"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn discover_add_installs_planned_crates() -> anyhow::Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let config_dir = tempfile::tempdir()?;
+    let project = tempfile::tempdir()?;
+    let server = MockServer::start().await;
+    let rustdoc = json!({
+        "root": 0,
+        "crate_version": "0.3.1",
+        "format_version": 39,
+        "index": {
+            "0": {"id": 0, "name": "demo", "visibility": "public", "docs": "Demo crate.",
+                  "inner": {"module": {"is_crate": true, "items": [1]}}},
+            "1": {"id": 1, "name": "connect", "visibility": "public", "docs": "Open a turbine.",
+                  "inner": {"function": {}}}
+        }
+    });
+    Mock::given(method("GET"))
+        .and(path("/crate/demo/latest/json.gz"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(rustdoc.to_string()))
+        .mount(&server)
+        .await;
+    let cargo_toml = project.path().join("Cargo.toml");
+    std::fs::write(&cargo_toml, "[dependencies]\ndemo = \"0.3\"\n")?;
+    let blz = || {
+        let mut cmd = blz_cmd_with_dirs(data_dir.path(), config_dir.path());
+        cmd.env("BLZ_DOCS_RS_URL", server.uri());
+        cmd
+    };
+
+    blz()
+        .args(["discover", "--from"])
+        .arg(&cargo_toml)
+        .args(["--no-probe", "--add", "--quiet"])
+        .assert()
+        .success();
+
+    let stdout = blz()
+        .args(["query", "turbine", "--source", "demo", "-f", "json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let payload: Value = serde_json::from_slice(&stdout)?;
+    assert_eq!(payload["results"].as_array().unwrap().len(), 1);
+    Ok(())
+}
@@ -0,0 +1,71 @@
+#![allow(missing_docs, clippy::expect_used, clippy::unwrap_used)]
+
+mod common;
+
+use common::blz_cmd_with_dirs;
+use predicates::prelude::*;
+use serde_json::Value;
+
+#[test]
+fn disk_budget_warns_reports_and_evicts_least_recently_searched() -> anyhow::Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let config_dir = tempfile::tempdir()?;
+    let docs = tempfile::tempdir()?;
+    let blz = || blz_cmd_with_dirs(data_dir.path(), config_dir.path());
+    let add = |alias: &str, body: &str| {
+        let file = docs.path().join(format!("{alias}.md"));
+        std::fs::write(&file, body).unwrap();
+        blz()
+            .env("BLZ_DISK_BUDGET", "1")
+            .args(["add", alias, file.to_str().unwrap(), "-y"])
+            .assert()
+            .success()
+    };
+
+    add("stale", "# Stale\n\n## Setup\n\nLegacy setup steps.\n");
+    add(
+        "hooks",
+        "# Hooks\n\n## Effects\n\nCall useEffect to synchronize.\n",
+    )
+    .stderr(predicate::str::contains("disk budget"));
+    blz()
+        .args(["query", "useEffect", "--source", "hooks", "-f", "json"])
+        .assert()
+        .success();
+
+    let stdout = blz()
+        .env("BLZ_DISK_BUDGET", "1")
+        .args(["stats", "--disk", "-f", "json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let report: Value = serde_json::from_slice(&stdout)?;
+    assert_eq!(report["overBudget"], true);
+    assert_eq!(report["policy"], "warn");
+    assert_eq!(report["sources"].as_array().unwrap().len(), 2);
+    assert_eq!(report["evictionCandidates"][0], "stale");
+
+    let file = docs.path().join("fresh.md");
+    std::fs::write(&file, "# Fresh\n\n## Start\n\nBrand new docs.\n")?;
+    blz()
+        .env("BLZ_DISK_BUDGET", "1")
+        .env("BLZ_EVICTION", "lru")
+        .args(["add", "fresh", file.to_str().unwrap(), "-y"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Evicted 'stale'"));
+
+    let stdout = blz()
+        .args(["list", "-f", "json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let listed = String::from_utf8(stdout)?;
+    assert!(listed.contains("fresh"));
+    assert!(!listed.contains("stale"));
+    Ok(())
+}
@@ -0,0 +1,101 @@
+#![allow(missing_docs, clippy::expect_used, clippy::unwrap_used)]
+
+mod common;
+
+use std::path::Path;
+
+use common::{blz_cmd, search_heading_paths};
+use serde_json::json;
+
+fn write_docs(root: &Path) {
+    std::fs::create_dir_all(root.join("guides")).unwrap();
+    std::fs::write(root.join("index.md"), "# Project\n\nWelcome aboard.\n").unwrap();
+    std::fs::write(
+        root.join("guides/setup.md"),
+        "# Setup\n\n## Install\n\nRun the bootstrap script.\n",
+    )
+    .unwrap();
+    std::fs::write(root.join("guides/notes.txt"), "bootstrap notes").unwrap();
+}
+
+#[test]
+fn docs_folder_is_merged_and_synced() -> anyhow::Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let docs = tempfile::tempdir()?;
+    write_docs(docs.path());
+
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["add", "projectdocs"])
+        .arg(docs.path())
+        .args(["--type", "dir", "-y"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("2 files"));
+
+    let paths = search_heading_paths(data_dir.path(), "projectdocs", "bootstrap");
+    assert_eq!(paths, [json!(["guides/setup.md", "Setup", "Install"])]);
+
+    std::fs::write(
+        docs.path().join("guides/deploy.md"),
+        "# Deploy\n\nShip with the release pipeline.\n",
+    )?;
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["sync", "projectdocs", "--quiet"])
+        .assert()
+        .success();
+
+    let paths = search_heading_paths(data_dir.path(), "projectdocs", "pipeline");
+    assert_eq!(paths[0], json!(["guides/deploy.md", "Deploy"]));
+    Ok(())
+}
+
+#[test]
+fn mdx_and_notebooks_are_indexed_as_markdown() -> anyhow::Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let docs = tempfile::tempdir()?;
+    std::fs::write(
+        docs.path().join("install.mdx"),
+        "import { Callout } from '@/components'\n\n# Install\n\n<Callout type=\"warning\">\n  Requires the turbine runtime.\n</Callout>\n",
+    )?;
+    std::fs::write(
+        docs.path().join("tour.ipynb"),
+        r##"{"cells": [
+            {"cell_type": "markdown", "source": ["# Tour\n", "Plot the turbine output."]},
+            {"cell_type": "code", "source": "plot()", "outputs": [{"text": "figure noise"}]}
+        ]}"##,
+    )?;
+
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["add", "projectdocs"])
+        .arg(docs.path())
+        .args(["--type", "dir", "-y"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("2 files"));
+
+    let paths = search_heading_paths(data_dir.path(), "projectdocs", "turbine");
+    assert!(paths.contains(&json!(["install.mdx", "Install"])));
+    assert!(paths.contains(&json!(["tour.ipynb", "Tour"])));
+    assert!(search_heading_paths(data_dir.path(), "projectdocs", "Callout").is_empty());
+    assert!(search_heading_paths(data_dir.path(), "projectdocs", "noise").is_empty());
+    Ok(())
+}
+
+#[test]
+fn docs_folder_requires_markdown_files() {
+    let data_dir = tempfile::tempdir().unwrap();
+    let docs = tempfile::tempdir().unwrap();
+    std::fs::write(docs.path().join("notes.txt"), "plain text").unwrap();
+
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["add", "projectdocs"])
+        .arg(docs.path())
+        .args(["--type", "dir", "-y"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("No markdown files found"));
+}
@@ -0,0 +1,54 @@
+#![allow(missing_docs, clippy::expect_used, clippy::unwrap_used)]
+
+mod common;
+
+use common::blz_cmd_with_dirs;
+use serde_json::Value;
+
+#[test]
+fn doctor_fix_rebuilds_corrupted_index_and_metadata() -> anyhow::Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let config_dir = tempfile::tempdir()?;
+    let work = tempfile::tempdir()?;
+    let guide = work.path().join("guide.md");
+    std::fs::write(&guide, "# Guide\n\n## Install\n\nRun the installer.\n")?;
+    let blz = || blz_cmd_with_dirs(data_dir.path(), config_dir.path());
+
+    blz()
+        .args(["add", "guide", guide.to_str().unwrap(), "-y"])
+        .assert()
+        .success();
+    let source_dir = data_dir.path().join("sources/guide");
+    std::fs::write(source_dir.join(".index/meta.json"), "{ truncated")?;
+    std::fs::remove_file(source_dir.join("metadata.json"))?;
+
+    let stdout = blz()
+        .args(["doctor", "-f", "json"])
+        .assert()
+        .failure()
+        .get_output()
+        .stdout
+        .clone();
+    let report: Value = serde_json::from_slice(&stdout)?;
+    let kinds: Vec<&str> = report["data_issues"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|issue| issue["kind"].as_str().unwrap())
+        .collect();
+    assert_eq!(kinds, ["corruptedIndex", "missingMetadata"]);
+
+    blz().args(["doctor", "--fix"]).assert().success();
+    assert!(source_dir.join("metadata.json").is_file());
+
+    let stdout = blz()
+        .args(["query", "installer", "--source", "guide", "-f", "json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let payload: Value = serde_json::from_slice(&stdout)?;
+    assert!(!payload["results"].as_array().unwrap().is_empty());
+    Ok(())
+}
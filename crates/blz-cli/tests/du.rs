@@ -0,0 +1,93 @@
+#![allow(missing_docs, clippy::expect_used, clippy::unwrap_used)]
+
+mod common;
+
+use common::blz_cmd;
+use predicates::prelude::*;
+use serde_json::Value;
+use tempfile::{TempDir, tempdir};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+async fn seed_source() -> anyhow::Result<(TempDir, MockServer)> {
+    let data_dir = tempdir()?;
+    let server = MockServer::start().await;
+    let doc =
+        "# Usage\n\n## Install\nRun the installer.\n\n## Configuration\nEdit the config file.\n";
+    Mock::given(method("HEAD"))
+        .and(path("/llms.txt"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/llms.txt"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(doc))
+        .mount(&server)
+        .await;
+
+    let url = format!("{}/llms.txt", server.uri());
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["add", "fixture", &url, "-y"])
+        .assert()
+        .success();
+
+    Ok((data_dir, server))
+}
+
+#[tokio::test]
+async fn du_reports_breakdown_and_totals_as_json() -> anyhow::Result<()> {
+    let (data_dir, _server) = seed_source().await?;
+
+    let stdout = blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["du", "--json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+
+    let json: Value = serde_json::from_slice(&stdout)?;
+    let source = &json["sources"][0];
+    assert_eq!(source["alias"], "fixture");
+    let content = source["contentBytes"].as_u64().unwrap();
+    let index = source["indexBytes"].as_u64().unwrap();
+    let archives = source["archivesBytes"].as_u64().unwrap();
+    assert!(content > 0);
+    assert!(index > 0);
+    assert_eq!(
+        source["totalBytes"].as_u64().unwrap(),
+        content + index + archives
+    );
+    assert_eq!(json["totals"]["totalBytes"], source["totalBytes"]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn du_bytes_flag_prints_exact_counts() -> anyhow::Result<()> {
+    let (data_dir, _server) = seed_source().await?;
+
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["du", "fixture", "--bytes", "--format", "text"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("fixture"))
+        .stdout(predicate::str::contains("TOTAL"))
+        .stdout(predicate::str::contains(" KB").not());
+    Ok(())
+}
+
+#[test]
+fn du_rejects_unknown_source() -> anyhow::Result<()> {
+    let data_dir = tempdir()?;
+
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["du", "missing"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("not found"));
+    Ok(())
+}
@@ -0,0 +1,69 @@
+#![allow(missing_docs, clippy::expect_used, clippy::unwrap_used)]
+
+mod common;
+
+use common::blz_cmd;
+use serde_json::Value;
+
+#[test]
+fn explain_reports_term_contributions_that_sum_to_the_score() -> anyhow::Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let docs = tempfile::tempdir()?;
+    std::fs::write(
+        docs.path().join("hooks.md"),
+        "# Hooks\n\n## State\n\nuseState adds state to components.\n\n## Effects\n\nuseEffect runs after render.\n",
+    )?;
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args([
+            "add",
+            "react",
+            docs.path().to_str().unwrap(),
+            "--type",
+            "dir",
+            "-y",
+        ])
+        .assert()
+        .success();
+
+    let stdout = blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["explain", "# state", "-s", "react", "--json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let report: Value = serde_json::from_slice(&stdout)?;
+    assert_eq!(report["source"], "react");
+
+    let top = &report["hits"][0];
+    assert_eq!(
+        top["headingPath"].as_array().unwrap().last().unwrap(),
+        "State"
+    );
+    let terms = top["terms"].as_array().unwrap();
+    assert!(terms.iter().any(|term| {
+        term["term"] == "state"
+            && term["field"] == "heading_path"
+            && term["boost"].as_f64().unwrap() > 1.0
+    }));
+    let total: f64 = terms
+        .iter()
+        .map(|term| term["score"].as_f64().unwrap())
+        .sum();
+    assert!((total - top["score"].as_f64().unwrap()).abs() < 1e-3);
+    assert!(top["explanation"]["details"].is_array());
+    Ok(())
+}
+
+#[test]
+fn explain_rejects_unknown_source() {
+    let data_dir = tempfile::tempdir().unwrap();
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["explain", "hooks", "-s", "missing"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("Source 'missing' not found"));
+}
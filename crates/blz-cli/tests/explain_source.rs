@@ -0,0 +1,68 @@
+#![allow(missing_docs, clippy::expect_used, clippy::unwrap_used)]
+
+mod common;
+
+use common::blz_cmd;
+use serde_json::Value;
+
+#[test]
+fn explain_source_briefs_sections_and_queries() -> anyhow::Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let docs = tempfile::tempdir()?;
+    std::fs::write(
+        docs.path().join("guide.md"),
+        "# Guide\n\n## Install\n\nRun the installer script.\n\n## Routing\n\n### Layouts\n\nNested layouts share routing state.\n",
+    )?;
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["add", "guide"])
+        .arg(docs.path())
+        .args(["--type", "dir", "-y"])
+        .assert()
+        .success();
+
+    let stdout = blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["explain-source", "guide", "--json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let briefing: Value = serde_json::from_slice(&stdout)?;
+    assert_eq!(briefing["source"], "guide");
+    assert!(briefing["size"]["lines"].as_u64().unwrap() > 0);
+    assert_eq!(briefing["freshness"]["stale"], false);
+    let queries = briefing["suggestedQueries"].as_array().unwrap();
+    assert!(
+        queries
+            .iter()
+            .any(|q| q.as_str().unwrap().starts_with("blz get guide:"))
+    );
+    assert!(
+        briefing["sections"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|section| section["title"].is_string())
+    );
+
+    let markdown = blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["explain-source", "guide", "--format", "text"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let markdown = String::from_utf8(markdown)?;
+    assert!(markdown.starts_with("# Source: guide"));
+    assert!(markdown.contains("## Sections"));
+
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["explain-source", "missing"])
+        .assert()
+        .failure();
+    Ok(())
+}
@@ -0,0 +1,103 @@
+#![allow(missing_docs, clippy::expect_used, clippy::unwrap_used)]
+
+mod common;
+
+use common::blz_cmd;
+use serde_json::Value;
+use tempfile::tempdir;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const DOC: &str = "# Guide\n\n## Connect\nCreate a client first.\n\n```rust\nlet client = Client::new();\nclient.handshake().await?;\nclient.subscribe(\"events\");\nclient.close();\n```\n\nThat is all.\n";
+
+async fn seed(data_dir: &tempfile::TempDir) -> anyhow::Result<MockServer> {
+    let server = MockServer::start().await;
+    Mock::given(method("HEAD"))
+        .and(path("/llms.txt"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/llms.txt"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(DOC))
+        .mount(&server)
+        .await;
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args([
+            "add",
+            "fixture",
+            &format!("{}/llms.txt", server.uri()),
+            "-y",
+        ])
+        .assert()
+        .success();
+    Ok(server)
+}
+
+fn first_hit(data_dir: &tempfile::TempDir, extra: &[&str]) -> Value {
+    let stdout = blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["query", "handshake", "-s", "fixture", "--json"])
+        .args(["--max-chars", "50"])
+        .args(extra)
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let payload: Value = serde_json::from_slice(&stdout).unwrap();
+    payload["results"][0].clone()
+}
+
+#[tokio::test]
+async fn json_snippets_include_whole_code_fence() -> anyhow::Result<()> {
+    let data_dir = tempdir()?;
+    let _server = seed(&data_dir).await?;
+
+    let hit = first_hit(&data_dir, &[]);
+    let snippet = hit["snippet"].as_str().unwrap();
+    assert!(
+        snippet.contains("```rust\n"),
+        "missing opening fence: {snippet}"
+    );
+    assert!(
+        snippet.ends_with("client.close();\n```"),
+        "missing closing fence: {snippet}"
+    );
+    assert_eq!(hit["lines"], "6-11");
+
+    let hit = first_hit(&data_dir, &["--no-fence-expand"]);
+    let snippet = hit["snippet"].as_str().unwrap();
+    assert!(
+        !snippet.ends_with("```"),
+        "fence expanded despite flag: {snippet}"
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn json_hits_report_snippet_language_for_code() -> anyhow::Result<()> {
+    let data_dir = tempdir()?;
+    let _server = seed(&data_dir).await?;
+
+    let hit = first_hit(&data_dir, &[]);
+    assert_eq!(hit["snippetLanguage"], "rust");
+
+    let stdout = blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["query", "first", "-s", "fixture", "--json"])
+        .args(["--max-chars", "50"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let payload: Value = serde_json::from_slice(&stdout)?;
+    let prose = &payload["results"][0];
+    assert!(
+        prose.get("snippetLanguage").is_none(),
+        "prose snippet tagged as code: {prose}"
+    );
+    Ok(())
+}
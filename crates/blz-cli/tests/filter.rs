@@ -0,0 +1,135 @@
+#![allow(missing_docs, clippy::expect_used, clippy::unwrap_used)]
+
+mod common;
+
+use common::blz_cmd;
+use serde_json::Value;
+
+#[test]
+fn filter_test_previews_dropped_and_rewritten_sections() -> anyhow::Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let docs = tempfile::tempdir()?;
+    std::fs::write(
+        docs.path().join("guide.md"),
+        "# Guide\n\n## Install\n\nRun the installer [edit]\n\n## Changelog\n\nv1 shipped.\n",
+    )?;
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["add", "guide"])
+        .arg(docs.path())
+        .args(["--type", "dir", "-y"])
+        .assert()
+        .success();
+    let cached = std::fs::read_dir(data_dir.path())?.count();
+
+    let stdout = blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["filter", "test", "guide", "--json"])
+        .args(["-r", "drop-heading=Changelog", "-r", r"strip=\s*\[edit\]"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let report: Value = serde_json::from_slice(&stdout)?;
+    assert_eq!(report["source"], "guide");
+    assert_eq!(report["sectionsDropped"], 1);
+    assert_eq!(report["sectionsModified"], 1);
+
+    let sections = report["sections"].as_array().unwrap();
+    let install = sections
+        .iter()
+        .find(|s| s["headingPath"].to_string().contains("Install"))
+        .unwrap();
+    assert_eq!(install["action"], "modified");
+    assert_eq!(install["changes"][0]["after"], "Run the installer");
+    let changelog = sections
+        .iter()
+        .find(|s| s["headingPath"].to_string().contains("Changelog"))
+        .unwrap();
+    assert_eq!(changelog["action"], "dropped");
+    assert_eq!(changelog["rules"][0], "drop-heading=Changelog");
+
+    // Previewing must not touch the cache; search still finds the section
+    assert_eq!(std::fs::read_dir(data_dir.path())?.count(), cached);
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["query", "shipped", "-s", "guide", "--json"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Changelog"));
+    Ok(())
+}
+
+#[test]
+fn filter_test_rejects_invalid_rules() {
+    let data_dir = tempfile::tempdir().unwrap();
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["filter", "test", "guide", "-r", "shout"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("unknown filter rule 'shout'"));
+}
+
+#[test]
+fn filter_set_reindexes_with_the_saved_pipeline() -> anyhow::Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let docs = tempfile::tempdir()?;
+    std::fs::write(
+        docs.path().join("guide.md"),
+        "# Guide\n\n## Install\n\nRun the installer.\n\n<!-- draft: mention the wombat flag -->\n\n## Changelog\n\nv1 shipped.\n",
+    )?;
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["add", "guide"])
+        .arg(docs.path())
+        .args(["--type", "dir", "-y"])
+        .assert()
+        .success();
+    let search = |query: &str| {
+        let stdout = blz_cmd()
+            .env("BLZ_DATA_DIR", data_dir.path())
+            .args(["query", query, "-s", "guide", "--json"])
+            .assert()
+            .success()
+            .get_output()
+            .stdout
+            .clone();
+        let payload: Value = serde_json::from_slice(&stdout).unwrap();
+        payload["results"].as_array().unwrap().len()
+    };
+    assert_eq!(search("wombat"), 1);
+
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["filter", "set", "guide", "-r", "html-comments"])
+        .args(["-r", "drop-heading=Changelog", "--quiet"])
+        .assert()
+        .success();
+    let settings = std::fs::read_to_string(data_dir.path().join("sources/guide/settings.toml"))?;
+    assert!(settings.contains("html-comments"));
+    assert_eq!(search("wombat"), 0);
+    assert_eq!(search("shipped"), 0);
+    assert_eq!(search("installer"), 1);
+
+    let stdout = blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["filter", "show", "guide", "--json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let report: Value = serde_json::from_slice(&stdout)?;
+    assert_eq!(report["configured"], true);
+    assert_eq!(report["rules"][1], "drop-heading=Changelog");
+
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["filter", "clear", "guide", "--quiet"])
+        .assert()
+        .success();
+    assert_eq!(search("shipped"), 1);
+    Ok(())
+}
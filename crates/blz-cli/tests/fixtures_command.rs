@@ -0,0 +1,71 @@
+#![allow(missing_docs, clippy::expect_used, clippy::unwrap_used)]
+
+mod common;
+
+use common::blz_cmd;
+use serde_json::Value;
+
+fn json(cmd: &mut assert_cmd::Command) -> Value {
+    let stdout = cmd.assert().success().get_output().stdout.clone();
+    serde_json::from_slice(&stdout).unwrap()
+}
+
+#[test]
+fn fixtures_are_reproducible_and_searchable() -> anyhow::Result<()> {
+    let root = tempfile::tempdir()?;
+    let first = root.path().join("first");
+    let second = root.path().join("second");
+    let args = |dir: &std::path::Path| {
+        vec![
+            "fixtures".to_string(),
+            "--dir".to_string(),
+            dir.display().to_string(),
+            "--sources".to_string(),
+            "2".to_string(),
+            "--sections".to_string(),
+            "40".to_string(),
+            "--json".to_string(),
+        ]
+    };
+
+    let report = json(blz_cmd().args(args(&first)));
+    let sources = report["sources"].as_array().unwrap();
+    assert_eq!(sources.len(), 2);
+    assert_eq!(sources[0]["alias"], "fixture-1");
+    assert_eq!(sources[1]["seed"], 43);
+    assert!(sources[0]["headings"].as_u64().unwrap() >= 40);
+
+    json(blz_cmd().args(args(&second)));
+    assert_eq!(
+        std::fs::read(first.join("fixtures/fixture-1.txt"))?,
+        std::fs::read(second.join("fixtures/fixture-1.txt"))?,
+        "same seed must produce identical documents"
+    );
+
+    let data_dir = report["env"]["BLZ_DATA_DIR"].as_str().unwrap();
+    let config_dir = report["env"]["BLZ_CONFIG_DIR"].as_str().unwrap();
+    let results = json(
+        blz_cmd()
+            .env("BLZ_DATA_DIR", data_dir)
+            .env("BLZ_CONFIG_DIR", config_dir)
+            .args(["query", "session", "--source", "fixture-2", "--json"]),
+    );
+    assert!(!results["results"].as_array().unwrap().is_empty());
+
+    blz_cmd()
+        .args(["fixtures", "--clean", "--dir"])
+        .arg(&first)
+        .assert()
+        .success();
+    assert!(!first.exists());
+    Ok(())
+}
+
+#[test]
+fn code_density_is_validated() {
+    blz_cmd()
+        .args(["fixtures", "--code-density", "1.5"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("between 0.0 and 1.0"));
+}
@@ -0,0 +1,57 @@
+#![allow(missing_docs, clippy::expect_used, clippy::unwrap_used)]
+
+mod common;
+
+use common::blz_cmd_with_dirs;
+use predicates::prelude::*;
+use serde_json::Value;
+
+#[test]
+fn gc_removes_invalid_anchor_maps_and_keeps_sources() -> anyhow::Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let config_dir = tempfile::tempdir()?;
+    let work = tempfile::tempdir()?;
+    let guide = work.path().join("guide.md");
+    std::fs::write(&guide, "# Guide\n\n## Install\n\nRun the installer.\n")?;
+    let blz = || blz_cmd_with_dirs(data_dir.path(), config_dir.path());
+
+    blz()
+        .args(["add", "guide", guide.to_str().unwrap(), "-y"])
+        .assert()
+        .success();
+    let anchors = data_dir.path().join("sources/guide/anchors.json");
+    std::fs::write(&anchors, "{ not json")?;
+
+    let stdout = blz()
+        .args(["gc", "--dry-run", "-f", "json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let report: Value = serde_json::from_slice(&stdout)?;
+    assert_eq!(report["dryRun"], true);
+    let items = report["items"].as_array().unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["kind"], "invalidAnchors");
+    assert_eq!(items[0]["alias"], "guide");
+    assert!(anchors.exists());
+
+    blz()
+        .args(["gc", "-f", "text"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Reclaimed"));
+    assert!(!anchors.exists());
+
+    blz()
+        .args(["gc", "-f", "text"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Nothing to collect"));
+    blz()
+        .args(["query", "installer", "--source", "guide"])
+        .assert()
+        .success();
+    Ok(())
+}
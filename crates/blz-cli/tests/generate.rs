@@ -0,0 +1,178 @@
+#![allow(missing_docs, clippy::expect_used, clippy::unwrap_used)]
+
+mod common;
+
+use common::blz_cmd_with_dirs;
+use serde_json::{Value, json};
+use wiremock::matchers::{body_partial_json, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+async fn mount_sitemap(server: &MockServer) {
+    let uri = server.uri();
+    let sitemap = format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+  <url><loc>{uri}/docs/alpha</loc><lastmod>2024-01-01T00:00:00Z</lastmod></url>
+  <url><loc>{uri}/docs/beta</loc><lastmod>2024-02-01T00:00:00Z</lastmod></url>
+  <url><loc>{uri}/blog/launch</loc><lastmod>2024-03-01T00:00:00Z</lastmod></url>
+</urlset>"#
+    );
+    Mock::given(method("GET"))
+        .and(path("/sitemap.xml"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(sitemap))
+        .mount(server)
+        .await;
+}
+
+async fn mount_page(server: &MockServer, page: &str, title: &str, markdown: &str) {
+    Mock::given(method("POST"))
+        .and(path("/v1/scrape"))
+        .and(body_partial_json(
+            json!({ "url": format!("{}{page}", server.uri()) }),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "success": true,
+            "data": { "markdown": markdown, "metadata": { "title": title } }
+        })))
+        .expect(1)
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn generate_scrapes_filtered_sitemap_pages_into_a_source() -> anyhow::Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let config_dir = tempfile::tempdir()?;
+    let server = MockServer::start().await;
+    let uri = server.uri();
+    let blz = || blz_cmd_with_dirs(data_dir.path(), config_dir.path());
+
+    mount_sitemap(&server).await;
+    mount_page(&server, "/docs/alpha", "Alpha", "Alpha uses the turbine.").await;
+    mount_page(&server, "/docs/beta", "Beta", "Beta rides the gondola.").await;
+
+    blz()
+        .env("FIRECRAWL_API_KEY", "fc-test")
+        .env("BLZ_FIRECRAWL_URL", &uri)
+        .args([
+            "generate",
+            &format!("{uri}/sitemap.xml"),
+            "--alias",
+            "gen",
+            "--include",
+            "/docs/",
+            "--quiet",
+        ])
+        .assert()
+        .success();
+
+    let stdout = blz()
+        .args(["query", "gondola", "--source", "gen", "-f", "json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let payload: Value = serde_json::from_slice(&stdout)?;
+    assert_eq!(payload["results"].as_array().unwrap().len(), 1);
+
+    let manifest: Value = serde_json::from_str(&std::fs::read_to_string(
+        data_dir.path().join("sources/gen/generate.json"),
+    )?)?;
+    assert_eq!(manifest["sitemapUrl"], format!("{uri}/sitemap.xml"));
+    assert_eq!(manifest["include"], json!(["/docs/"]));
+    assert_eq!(manifest["maxPages"], 500);
+    let pages = manifest["pages"].as_array().unwrap();
+    assert_eq!(pages.len(), 2);
+    let beta = pages
+        .iter()
+        .find(|page| page["url"] == format!("{uri}/docs/beta"))
+        .unwrap();
+    assert_eq!(beta["sitemapLastmod"], "2024-02-01T00:00:00Z");
+    Ok(())
+}
+
+#[tokio::test]
+async fn generate_dry_run_lists_pages_without_scraping() -> anyhow::Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let config_dir = tempfile::tempdir()?;
+    let server = MockServer::start().await;
+    let uri = server.uri();
+
+    mount_sitemap(&server).await;
+    Mock::given(method("POST"))
+        .and(path("/v1/scrape"))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let stdout = blz_cmd_with_dirs(data_dir.path(), config_dir.path())
+        .env("FIRECRAWL_API_KEY", "fc-test")
+        .env("BLZ_FIRECRAWL_URL", &uri)
+        .args([
+            "generate",
+            &format!("{uri}/sitemap.xml"),
+            "--alias",
+            "gen",
+            "--exclude",
+            "/blog/",
+            "--dry-run",
+            "--quiet",
+        ])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let listed = String::from_utf8(stdout)?;
+    assert_eq!(listed.lines().count(), 2, "{listed}");
+    assert!(!listed.contains("/blog/"));
+    assert!(!data_dir.path().join("sources/gen").exists());
+    Ok(())
+}
+
+#[tokio::test]
+async fn generate_skips_pages_disallowed_by_robots_txt() -> anyhow::Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let config_dir = tempfile::tempdir()?;
+    let server = MockServer::start().await;
+    let uri = server.uri();
+
+    mount_sitemap(&server).await;
+    Mock::given(method("GET"))
+        .and(path("/robots.txt"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_string("User-agent: *\nDisallow: /docs/beta\n"),
+        )
+        .mount(&server)
+        .await;
+
+    let dry_run = |ignore_robots: bool| {
+        let mut cmd = blz_cmd_with_dirs(data_dir.path(), config_dir.path());
+        cmd.env("FIRECRAWL_API_KEY", "fc-test")
+            .env("BLZ_FIRECRAWL_URL", &uri)
+            .args([
+                "generate",
+                &format!("{uri}/sitemap.xml"),
+                "--alias",
+                "gen",
+                "--include",
+                "/docs/",
+                "--dry-run",
+                "--quiet",
+            ]);
+        if ignore_robots {
+            cmd.arg("--ignore-robots");
+        }
+        String::from_utf8(cmd.assert().success().get_output().stdout.clone())
+    };
+
+    let listed = dry_run(false)?;
+    assert_eq!(
+        listed.lines().collect::<Vec<_>>(),
+        [format!("{uri}/docs/alpha")]
+    );
+    assert_eq!(dry_run(true)?.lines().count(), 2);
+    Ok(())
+}
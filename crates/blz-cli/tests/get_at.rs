@@ -0,0 +1,82 @@
+#![allow(missing_docs, clippy::expect_used, clippy::unwrap_used)]
+
+mod common;
+
+use common::blz_cmd;
+use serde_json::Value;
+use tempfile::tempdir;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const OLD: &str = "# Guide\n\n## Install\nRun the installer.\n";
+const NEW: &str = "# Guide\n\n## Setup\nUse the package manager.\n";
+
+async fn serve(server: &MockServer, body: &str) {
+    Mock::given(method("HEAD"))
+        .and(path("/llms.txt"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/llms.txt"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(body))
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn get_at_reads_lines_from_previous_versions() -> anyhow::Result<()> {
+    let tmp = tempdir()?;
+    let server = MockServer::start().await;
+    serve(&server, OLD).await;
+    blz_cmd()
+        .env("BLZ_DATA_DIR", tmp.path())
+        .args(["add", "guide", &format!("{}/llms.txt", server.uri()), "-y"])
+        .assert()
+        .success();
+
+    // An agent cites line 4 along with the content checksum
+    let output = blz_cmd()
+        .env("BLZ_DATA_DIR", tmp.path())
+        .args(["get", "guide:4", "--json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let json: Value = serde_json::from_slice(&output)?;
+    let cited = json["requests"][0]["checksum"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    server.reset().await;
+    serve(&server, NEW).await;
+    blz_cmd()
+        .env("BLZ_DATA_DIR", tmp.path())
+        .args(["sync", "guide"])
+        .assert()
+        .success();
+
+    let get = |at: &str| {
+        blz_cmd()
+            .env("BLZ_DATA_DIR", tmp.path())
+            .args(["get", "guide:4", "-f", "raw", "--at", at])
+            .assert()
+    };
+    get(&cited).success().stdout("Run the installer.\n");
+    get("2000-01-01").success().stdout("Run the installer.\n");
+    get("now").success().stdout("Use the package manager.\n");
+    get("not-a-version")
+        .failure()
+        .stderr(predicates::str::contains("No version of 'guide' matches"));
+
+    // Heading ordinals resolve against the archived outline
+    blz_cmd()
+        .env("BLZ_DATA_DIR", tmp.path())
+        .args(["get", "guide:§1.1", "-f", "raw", "--at", &cited])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Run the installer."));
+    Ok(())
+}
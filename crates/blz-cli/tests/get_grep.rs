@@ -0,0 +1,93 @@
+#![allow(missing_docs, clippy::expect_used, clippy::unwrap_used)]
+
+mod common;
+
+use common::blz_cmd;
+use serde_json::Value;
+use tempfile::tempdir;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const DOC: &str = "# Guide
+
+## Install
+Run the installer.
+Then verify the install.
+Restart your shell.
+
+## Usage
+Install plugins later.
+";
+
+#[tokio::test]
+async fn get_grep_filters_retrieved_lines() -> anyhow::Result<()> {
+    let tmp = tempdir()?;
+    let server = MockServer::start().await;
+    Mock::given(method("HEAD"))
+        .and(path("/llms.txt"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/llms.txt"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(DOC))
+        .mount(&server)
+        .await;
+    let url = format!("{}/llms.txt", server.uri());
+
+    blz_cmd()
+        .env("BLZ_DATA_DIR", tmp.path())
+        .args(["add", "guide", &url, "-y"])
+        .assert()
+        .success();
+
+    // Text output keeps the original line numbers of each match
+    blz_cmd()
+        .env("BLZ_DATA_DIR", tmp.path())
+        .args([
+            "get", "guide:3", "-C", "all", "--grep", "install", "-f", "raw",
+        ])
+        .assert()
+        .success()
+        .stdout("Run the installer.\nThen verify the install.\n");
+
+    // JSON reports one range per matching line, scoped to the requested span
+    let output = blz_cmd()
+        .env("BLZ_DATA_DIR", tmp.path())
+        .args([
+            "get",
+            "guide:1-10",
+            "--grep",
+            "(?i)^install|shell",
+            "-f",
+            "json",
+        ])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let json: Value = serde_json::from_slice(&output)?;
+    let ranges = json["requests"][0]["ranges"].as_array().expect("ranges");
+    let found: Vec<(u64, &str)> = ranges
+        .iter()
+        .map(|range| {
+            (
+                range["lineStart"].as_u64().unwrap(),
+                range["snippet"].as_str().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        found,
+        [(6, "Restart your shell."), (9, "Install plugins later.")]
+    );
+
+    blz_cmd()
+        .env("BLZ_DATA_DIR", tmp.path())
+        .args(["get", "guide:1-3", "--grep", "("])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("Invalid --grep pattern"));
+    Ok(())
+}
@@ -0,0 +1,65 @@
+#![allow(missing_docs, clippy::expect_used, clippy::unwrap_used)]
+
+mod common;
+
+use common::blz_cmd;
+use tempfile::TempDir;
+
+const DOC: &str = "# Guide\n\n## Install\nRun the installer.\n\n## Usage\nCall the API.\n";
+
+fn cmd(home: &TempDir) -> assert_cmd::Command {
+    let mut cmd = blz_cmd();
+    cmd.env("BLZ_DATA_DIR", home.path().join("data"))
+        .env("BLZ_CONFIG_DIR", home.path().join("config"));
+    cmd
+}
+
+#[test]
+fn get_steps_through_sections_after_a_retrieval() -> anyhow::Result<()> {
+    let home = tempfile::tempdir()?;
+    let file = home.path().join("guide.md");
+    std::fs::write(&file, DOC)?;
+    cmd(&home)
+        .args(["add", "guide"])
+        .arg(&file)
+        .arg("-y")
+        .assert()
+        .success();
+
+    cmd(&home)
+        .args(["get", "--next-section"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("No previous retrieval found"));
+
+    cmd(&home)
+        .args(["get", "guide:4", "-f", "raw"])
+        .assert()
+        .success()
+        .stdout("Run the installer.\n");
+    cmd(&home)
+        .args(["get", "--next-section", "-f", "raw"])
+        .assert()
+        .success()
+        .stdout("## Usage\nCall the API.\n");
+    cmd(&home)
+        .args(["get", "--next-section"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("No section after line 7"));
+    cmd(&home)
+        .args(["get", "--previous-section", "-f", "raw"])
+        .assert()
+        .success()
+        .stdout(predicates::str::starts_with(
+            "## Install\nRun the installer.\n",
+        ));
+
+    // Search history is unaffected by retrieval continuations
+    cmd(&home)
+        .args(["history", "--json"])
+        .assert()
+        .success()
+        .stdout("[]\n");
+    Ok(())
+}
@@ -0,0 +1,193 @@
+#![allow(missing_docs, clippy::expect_used, clippy::unwrap_used)]
+
+mod common;
+
+use common::blz_cmd;
+use serde_json::{Value, json};
+use tempfile::TempDir;
+use wiremock::matchers::{header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn github_cmd(data_dir: &TempDir, server: &MockServer) -> assert_cmd::Command {
+    let mut cmd = blz_cmd();
+    cmd.env("BLZ_DATA_DIR", data_dir.path())
+        .env("BLZ_GITHUB_API_URL", server.uri())
+        .env("BLZ_GITHUB_RAW_URL", server.uri())
+        .env_remove("GITHUB_TOKEN");
+    cmd
+}
+
+async fn mount_docs(server: &MockServer) {
+    Mock::given(path("/acme/widgets/trunk/llms.txt"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string("# Widgets\n\n## Gizmos\n\nGizmos spin widgets.\n"),
+        )
+        .mount(server)
+        .await;
+}
+
+fn repo_response() -> ResponseTemplate {
+    ResponseTemplate::new(200)
+        .insert_header("x-ratelimit-limit", "5000")
+        .insert_header("x-ratelimit-remaining", "4999")
+        .insert_header("x-ratelimit-reset", "4102444800")
+        .set_body_json(json!({
+            "full_name": "acme/widgets",
+            "description": "Composable widgets",
+            "default_branch": "trunk",
+            "html_url": "https://github.com/acme/widgets"
+        }))
+}
+
+#[tokio::test]
+async fn github_source_uses_default_branch_and_caches_metadata() -> anyhow::Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let server = MockServer::start().await;
+    mount_docs(&server).await;
+    Mock::given(method("GET"))
+        .and(path("/repos/acme/widgets"))
+        .and(header("authorization", "Bearer secret"))
+        .respond_with(repo_response())
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    github_cmd(&data_dir, &server)
+        .env("GITHUB_TOKEN", "secret")
+        .args(["add", "github:acme/widgets", "-y"])
+        .assert()
+        .success();
+    github_cmd(&data_dir, &server)
+        .env("GITHUB_TOKEN", "secret")
+        .args(["add", "widgets-copy", "github:acme/widgets", "-y"])
+        .assert()
+        .success();
+
+    let stdout = github_cmd(&data_dir, &server)
+        .args(["list", "--json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let sources: Value = serde_json::from_slice(&stdout)?;
+    let widgets = sources
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|source| source["alias"] == "widgets")
+        .expect("widgets source");
+    assert_eq!(widgets["description"], "Composable widgets");
+    assert_eq!(widgets["githubAliases"], json!(["acme/widgets"]));
+    assert!(
+        widgets["url"]
+            .as_str()
+            .unwrap()
+            .ends_with("/acme/widgets/trunk/llms.txt")
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn github_rate_limit_is_reported_with_token_hint() -> anyhow::Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/repos/acme/widgets"))
+        .respond_with(
+            ResponseTemplate::new(403)
+                .insert_header("x-ratelimit-limit", "60")
+                .insert_header("x-ratelimit-remaining", "0")
+                .insert_header("x-ratelimit-reset", "4102444800"),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    for _ in 0..2 {
+        github_cmd(&data_dir, &server)
+            .args(["add", "github:acme/widgets", "-y"])
+            .assert()
+            .failure()
+            .stderr(predicates::str::contains("rate limit exceeded"))
+            .stderr(predicates::str::contains("GITHUB_TOKEN"));
+    }
+    Ok(())
+}
+
+const HEAD: &str = "5f1c0e9a2b7d";
+
+async fn mount_repo_docs(server: &MockServer) {
+    Mock::given(method("GET"))
+        .and(path("/repos/acme/widgets"))
+        .respond_with(repo_response())
+        .mount(server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/repos/acme/widgets/commits/trunk"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(HEAD))
+        .mount(server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(format!("/repos/acme/widgets/git/trees/{HEAD}")))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "tree": [
+                { "path": "README.md", "type": "blob", "size": 30 },
+                { "path": "docs", "type": "tree" },
+                { "path": "docs/guide.md", "type": "blob", "size": 50 },
+                { "path": "docs/logo.png", "type": "blob", "size": 900 },
+                { "path": "src/lib.rs", "type": "blob", "size": 10 }
+            ],
+            "truncated": false
+        })))
+        .expect(1)
+        .mount(server)
+        .await;
+    Mock::given(path(format!("/acme/widgets/{HEAD}/README.md")))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_string("# Widgets\n\nComposable widgets.\n"),
+        )
+        .mount(server)
+        .await;
+    Mock::given(path(format!("/acme/widgets/{HEAD}/docs/guide.md")))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string("# Guide\n\n## Install\n\nRun the bootstrap script.\n"),
+        )
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn github_flag_indexes_readme_and_docs_at_head_commit() -> anyhow::Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let server = MockServer::start().await;
+    mount_repo_docs(&server).await;
+
+    github_cmd(&data_dir, &server)
+        .args(["add", "acme/widgets", "--github"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("2 files"));
+
+    let stdout = github_cmd(&data_dir, &server)
+        .args(["query", "bootstrap", "--source", "widgets", "-f", "json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let payload: Value = serde_json::from_slice(&stdout)?;
+    assert_eq!(
+        payload["results"][0]["headingPath"],
+        json!(["docs/guide.md", "Guide", "Install"])
+    );
+
+    // The ref still points at the indexed commit, so the tree is not listed again
+    github_cmd(&data_dir, &server)
+        .args(["sync", "widgets", "--quiet"])
+        .assert()
+        .success();
+    Ok(())
+}
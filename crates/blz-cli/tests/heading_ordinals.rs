@@ -0,0 +1,86 @@
+#![allow(missing_docs, clippy::expect_used, clippy::unwrap_used)]
+
+mod common;
+
+use common::blz_cmd;
+use predicates::prelude::*;
+use serde_json::Value;
+use tempfile::tempdir;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const DOC: &str = "# Guide\n\n## Install\nRun the installer.\n\n## Auth\nIntro.\n\n### Tokens\nSend a bearer token.\n\n# Reference\n\n## CLI\nFlags.\n";
+
+async fn seed(data_dir: &tempfile::TempDir) -> anyhow::Result<MockServer> {
+    let server = MockServer::start().await;
+    Mock::given(method("HEAD"))
+        .and(path("/llms.txt"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/llms.txt"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(DOC))
+        .mount(&server)
+        .await;
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args([
+            "add",
+            "fixture",
+            &format!("{}/llms.txt", server.uri()),
+            "-y",
+        ])
+        .assert()
+        .success();
+    Ok(server)
+}
+
+#[tokio::test]
+async fn map_json_reports_ordinals_stable_under_filters() -> anyhow::Result<()> {
+    let data_dir = tempdir()?;
+    let _server = seed(&data_dir).await?;
+
+    let stdout = blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["map", "fixture", "--filter", "Tokens", "--json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let payload: Value = serde_json::from_slice(&stdout)?;
+    let entries = payload["entries"].as_array().unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0]["ordinal"], "1.2.1");
+
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["map", "fixture", "--numbered", "--tree"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("2.1").and(predicate::str::contains("CLI")));
+    Ok(())
+}
+
+#[tokio::test]
+async fn get_resolves_ordinal_targets() -> anyhow::Result<()> {
+    let data_dir = tempdir()?;
+    let _server = seed(&data_dir).await?;
+
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["get", "fixture:§1.2.1", "--text"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Send a bearer token."))
+        .stdout(predicate::str::contains("Run the installer.").not());
+
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["get", "fixture:§3"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No heading at §3 in 'fixture'"));
+    Ok(())
+}
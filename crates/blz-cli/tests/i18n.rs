@@ -0,0 +1,55 @@
+#![allow(missing_docs, clippy::expect_used, clippy::unwrap_used)]
+
+mod common;
+
+use common::blz_cmd_with_dirs;
+use predicates::prelude::*;
+use tempfile::tempdir;
+
+#[test]
+fn blz_locale_selects_translated_messages() -> anyhow::Result<()> {
+    let data_dir = tempdir()?;
+    let config_dir = tempdir()?;
+
+    blz_cmd_with_dirs(data_dir.path(), config_dir.path())
+        .env("BLZ_LOCALE", "es")
+        .args(["list", "--format", "text"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No hay fuentes configuradas"));
+    Ok(())
+}
+
+#[test]
+fn config_locale_applies_and_unknown_locales_fall_back_to_english() -> anyhow::Result<()> {
+    let data_dir = tempdir()?;
+    let config_dir = tempdir()?;
+    std::fs::write(
+        config_dir.path().join("config.toml"),
+        r#"[defaults]
+refresh_hours = 24
+max_archives = 10
+fetch_enabled = true
+follow_links = "first_party"
+allowlist = []
+locale = "es-MX"
+
+[paths]
+root = "/tmp/blz-i18n-test"
+"#,
+    )?;
+
+    blz_cmd_with_dirs(data_dir.path(), config_dir.path())
+        .args(["list", "--format", "text"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No hay fuentes configuradas"));
+
+    blz_cmd_with_dirs(data_dir.path(), config_dir.path())
+        .env("BLZ_LOCALE", "tlh")
+        .args(["list", "--format", "text"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No sources configured"));
+    Ok(())
+}
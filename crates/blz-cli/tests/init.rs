@@ -0,0 +1,97 @@
+#![allow(missing_docs, clippy::expect_used, clippy::unwrap_used)]
+
+mod common;
+
+use common::blz_cmd_with_dirs;
+use serde_json::Value;
+
+#[test]
+fn init_writes_workspace_and_installable_manifest() -> anyhow::Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let config_dir = tempfile::tempdir()?;
+    let project = tempfile::tempdir()?;
+    let docs = project.path().join("docs");
+    std::fs::create_dir(&docs)?;
+    std::fs::write(
+        docs.join("guide.md"),
+        "# Guide\n\n## Install\n\nRun the installer.\n",
+    )?;
+
+    blz_cmd_with_dirs(data_dir.path(), config_dir.path())
+        .args(["add", "guide"])
+        .arg(docs.join("guide.md"))
+        .args(["--tags", "team", "-y"])
+        .assert()
+        .success();
+
+    let output = blz_cmd_with_dirs(data_dir.path(), config_dir.path())
+        .current_dir(project.path())
+        .args(["init", "--source", "guide", "--json"])
+        .assert()
+        .success();
+    let report: Value = serde_json::from_slice(&output.get_output().stdout)?;
+    assert_eq!(report["sources"][0]["alias"], "guide");
+    assert_eq!(report["sources"][0]["path"], "docs/guide.md");
+
+    let workspace = std::fs::read_to_string(project.path().join(".blz.toml"))?;
+    assert!(workspace.contains("sources = [\"guide\"]"), "{workspace}");
+    let manifest: toml::Value = toml::from_str(&std::fs::read_to_string(
+        project.path().join("blz.sources.toml"),
+    )?)?;
+    assert_eq!(manifest["version"].as_str(), Some("1"));
+    assert!(project.path().join("blz.lock").exists());
+    assert_eq!(
+        manifest["source"][0]["path"].as_str(),
+        Some("docs/guide.md")
+    );
+
+    // Existing manifests are kept unless --force is given
+    blz_cmd_with_dirs(data_dir.path(), config_dir.path())
+        .current_dir(project.path())
+        .args(["init", "--source", "guide"])
+        .assert()
+        .failure();
+
+    // A teammate with an empty cache installs the same set
+    let teammate_data = tempfile::tempdir()?;
+    let teammate_config = tempfile::tempdir()?;
+    blz_cmd_with_dirs(teammate_data.path(), teammate_config.path())
+        .current_dir(project.path())
+        .args(["add", "--manifest", "blz.sources.toml", "-y"])
+        .assert()
+        .success();
+    let output = blz_cmd_with_dirs(teammate_data.path(), teammate_config.path())
+        .args(["list", "--json"])
+        .assert()
+        .success();
+    let listed: Vec<Value> = serde_json::from_slice(&output.get_output().stdout)?;
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0]["alias"], "guide");
+    Ok(())
+}
+
+#[test]
+fn init_detect_suggests_sources_from_dependencies() -> anyhow::Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let config_dir = tempfile::tempdir()?;
+    let project = tempfile::tempdir()?;
+    std::fs::write(
+        project.path().join("package.json"),
+        r#"{"dependencies": {"react": "^19.0.0"}}"#,
+    )?;
+
+    let output = blz_cmd_with_dirs(data_dir.path(), config_dir.path())
+        .current_dir(project.path())
+        .args(["init", "--detect", "--dry-run", "--json"])
+        .assert()
+        .success();
+    let report: Value = serde_json::from_slice(&output.get_output().stdout)?;
+    assert_eq!(report["dryRun"], true);
+    let react = report["sources"]
+        .as_array()
+        .and_then(|sources| sources.iter().find(|s| s["alias"] == "react"))
+        .expect("react suggested from package.json");
+    assert_eq!(react["detectedFrom"], "react in package.json");
+    assert!(!project.path().join(".blz.toml").exists());
+    Ok(())
+}
@@ -493,3 +493,51 @@ async fn find_heading_level_filter_with_range_syntax() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn bare_query_routes_to_search_mode() -> anyhow::Result<()> {
+    let tmp = tempdir()?;
+    let server = MockServer::start().await;
+
+    seed_source(&tmp, &server, "docs", SAMPLE_DOC).await?;
+
+    // No subcommand: the default command should smart-dispatch to search
+    let payload = run_find_json(&tmp, &["installation", "--source", "docs", "-f", "json"])?;
+
+    assert_eq!(payload["query"].as_str().unwrap(), "installation");
+    assert!(!payload["results"].as_array().unwrap().is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn bare_citation_routes_to_retrieve_mode() -> anyhow::Result<()> {
+    let tmp = tempdir()?;
+    let server = MockServer::start().await;
+
+    seed_source(&tmp, &server, "docs", SAMPLE_DOC).await?;
+
+    let payload = run_find_json(&tmp, &["docs:1-5", "-f", "json"])?;
+
+    let requests = payload["requests"].as_array().unwrap();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0]["alias"].as_str().unwrap(), "docs");
+
+    Ok(())
+}
+
+#[test]
+fn bare_url_suggests_add_when_non_interactive() -> anyhow::Result<()> {
+    let tmp = tempdir()?;
+
+    blz_cmd()
+        .env("BLZ_DATA_DIR", tmp.path())
+        .arg("https://docs.example.com/llms.txt")
+        .assert()
+        .success()
+        .stderr(predicates::str::contains(
+            "blz add example https://docs.example.com/llms.txt",
+        ));
+
+    Ok(())
+}
@@ -0,0 +1,108 @@
+#![allow(missing_docs, clippy::expect_used, clippy::unwrap_used)]
+
+mod common;
+
+use std::fmt::Write as _;
+
+use common::blz_cmd;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use tempfile::tempdir;
+use wiremock::matchers::path;
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const PINNED: &str = "# Portal\n\n## Deploys\nUse the turbine pipeline.\n";
+
+fn sha256_hex(content: &str) -> String {
+    Sha256::digest(content.as_bytes())
+        .iter()
+        .fold(String::new(), |mut out, b| {
+            let _ = write!(out, "{b:02x}");
+            out
+        })
+}
+
+async fn serve(server: &MockServer, body: &str) {
+    server.reset().await;
+    Mock::given(path("/llms.txt"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(body))
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn pinned_checksum_is_enforced_on_add_sync_and_check() -> anyhow::Result<()> {
+    let data_dir = tempdir()?;
+    let server = MockServer::start().await;
+    serve(&server, PINNED).await;
+    let url = format!("{}/llms.txt", server.uri());
+
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["add", "portal", &url, "--sha256", &"0".repeat(64), "-y"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("SHA-256 mismatch"));
+    assert!(!data_dir.path().join("sources/portal/llms.txt").exists());
+
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["add", "portal", &url, "--sha256", &sha256_hex(PINNED), "-y"])
+        .assert()
+        .success();
+
+    // A changed document no longer matches the pin and is not indexed
+    serve(
+        &server,
+        "# Portal\n\n## Deploys\nUse the gondola pipeline.\n",
+    )
+    .await;
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["refresh", "portal", "--quiet"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("SHA-256 mismatch"));
+
+    let stdout = blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["query", "turbine", "--source", "portal", "-f", "json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let payload: Value = serde_json::from_slice(&stdout)?;
+    assert_eq!(payload["results"].as_array().unwrap().len(), 1);
+
+    let stdout = blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["check", "portal", "--verify", "-f", "json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let results: Value = serde_json::from_slice(&stdout)?;
+    assert_eq!(results[0]["integrity"]["status"], "verified");
+    Ok(())
+}
+
+#[test]
+fn integrity_is_rejected_for_local_files() {
+    let data_dir = tempdir().unwrap();
+    let doc = data_dir.path().join("llms.txt");
+    std::fs::write(&doc, PINNED).unwrap();
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args([
+            "add",
+            "local",
+            doc.to_str().unwrap(),
+            "--sha256",
+            &sha256_hex(PINNED),
+            "-y",
+        ])
+        .assert()
+        .failure();
+}
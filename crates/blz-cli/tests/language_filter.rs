@@ -104,3 +104,72 @@ Contenu français avec diacritiques.
 
     Ok(())
 }
+
+/// `--lang` keeps the listed locale variants, and `filter set --lang` changes them later.
+#[tokio::test]
+async fn test_lang_keeps_selected_locale_variants() -> anyhow::Result<()> {
+    let data_dir = tempdir()?;
+    let config_dir = tempdir()?;
+    let server = MockServer::start().await;
+
+    let doc = r"# Portal Docs
+
+## Getting Started
+Read the [guide](https://docs.example.com/en/guide) to configure the turbine.
+
+## Erste Schritte
+Lies die [Anleitung](https://docs.example.com/de/guide) zur Gondel gondola.
+
+## はじめに
+[ガイド](https://docs.example.com/ja/guide) zeppelin
+";
+    Mock::given(method("HEAD"))
+        .and(path("/llms.txt"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/llms.txt"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(doc))
+        .mount(&server)
+        .await;
+    let url = format!("{}/llms.txt", server.uri());
+
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .env("BLZ_CONFIG_DIR", config_dir.path())
+        .args(["add", "portal", &url, "--lang", "en,de", "-y"])
+        .assert()
+        .success();
+    let settings = std::fs::read_to_string(data_dir.path().join("sources/portal/settings.toml"))?;
+    assert!(settings.contains("locales"), "{settings}");
+
+    let hit_count = |query: &str| -> anyhow::Result<usize> {
+        let output = blz_cmd()
+            .env("BLZ_DATA_DIR", data_dir.path())
+            .env("BLZ_CONFIG_DIR", config_dir.path())
+            .args(["search", query, "-f", "json"])
+            .assert()
+            .success()
+            .get_output()
+            .stdout
+            .clone();
+        let value: Value = serde_json::from_slice(&output)?;
+        Ok(value["results"].as_array().map_or(0, Vec::len))
+    };
+    assert_eq!(hit_count("turbine")?, 1);
+    assert_eq!(hit_count("gondola")?, 1);
+    assert_eq!(hit_count("zeppelin")?, 0);
+
+    // Back to English only: the German variant is dropped on re-index
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .env("BLZ_CONFIG_DIR", config_dir.path())
+        .args(["filter", "set", "portal", "--lang", "en", "--quiet"])
+        .assert()
+        .success();
+    assert_eq!(hit_count("turbine")?, 1);
+    assert_eq!(hit_count("gondola")?, 0);
+
+    Ok(())
+}
@@ -56,3 +56,116 @@ async fn list_status_json_includes_source_and_keys() -> anyhow::Result<()> {
     // Note: "source" field removed in single-flavor refactor (along with flavors and searchFlavor)
     Ok(())
 }
+
+#[tokio::test]
+async fn list_columns_sort_and_filter_shape_json() -> anyhow::Result<()> {
+    let tmp = tempdir()?;
+    let server = MockServer::start().await;
+    let url = format!("{}/llms.txt", server.uri());
+
+    Mock::given(method("HEAD"))
+        .and(path("/llms.txt"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/llms.txt"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("# Title\n\n## A\nalpha\n"))
+        .mount(&server)
+        .await;
+
+    blz_cmd()
+        .env("BLZ_DATA_DIR", tmp.path())
+        .args(["add", "e2e", &url, "-y"])
+        .assert()
+        .success();
+
+    let out = blz_cmd()
+        .env("BLZ_DATA_DIR", tmp.path())
+        .args([
+            "list",
+            "--sort",
+            "size",
+            "--columns",
+            "alias,size,status,searches",
+            "--json",
+        ])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let v: Value = serde_json::from_slice(&out)?;
+    let first = &v[0];
+    assert_eq!(first["alias"], "e2e");
+    assert_eq!(first["status"], "fresh");
+    assert!(first["sizeBytes"].as_u64().is_some_and(|size| size > 0));
+    assert_eq!(first.as_object().map(serde_json::Map::len), Some(4));
+
+    let out = blz_cmd()
+        .env("BLZ_DATA_DIR", tmp.path())
+        .args(["list", "--filter", "stale", "--json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let v: Value = serde_json::from_slice(&out)?;
+    assert_eq!(v.as_array().map(Vec::len), Some(0));
+    Ok(())
+}
+
+#[tokio::test]
+async fn list_reports_health_recorded_by_check() -> anyhow::Result<()> {
+    let tmp = tempdir()?;
+    let server = MockServer::start().await;
+    let url = format!("{}/llms.txt", server.uri());
+
+    Mock::given(method("HEAD"))
+        .and(path("/llms.txt"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/llms.txt"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("# Title\n\n## A\nalpha\n"))
+        .mount(&server)
+        .await;
+
+    blz_cmd()
+        .env("BLZ_DATA_DIR", tmp.path())
+        .args(["add", "e2e", &url, "-y"])
+        .assert()
+        .success();
+
+    let list_health = || -> anyhow::Result<Value> {
+        let out = blz_cmd()
+            .env("BLZ_DATA_DIR", tmp.path())
+            .args(["list", "--json"])
+            .assert()
+            .success()
+            .get_output()
+            .stdout
+            .clone();
+        let v: Value = serde_json::from_slice(&out)?;
+        Ok(v[0]["health"].clone())
+    };
+
+    let before = list_health()?;
+    assert_eq!(before["fresh"], true);
+    assert_eq!(before["indexOk"], true);
+    assert!(before.get("upstreamReachable").is_none());
+    assert!(before.get("lintScore").is_none());
+
+    blz_cmd()
+        .env("BLZ_DATA_DIR", tmp.path())
+        .args(["check", "e2e", "--json"])
+        .assert()
+        .success();
+
+    let after = list_health()?;
+    assert_eq!(after["upstreamReachable"], true);
+    assert_eq!(after["lintScore"], 100);
+    assert!(after["checkedAt"].is_string());
+    Ok(())
+}
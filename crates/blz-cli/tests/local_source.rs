@@ -0,0 +1,139 @@
+#![allow(missing_docs, clippy::expect_used, clippy::unwrap_used)]
+
+mod common;
+
+use common::blz_cmd;
+use serde_json::Value;
+use tempfile::TempDir;
+
+fn query_count(data_dir: &TempDir, query: &str, source: &str) -> usize {
+    let stdout = blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["query", query, "--source", source, "-f", "json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let payload: Value = serde_json::from_slice(&stdout).unwrap();
+    payload["results"].as_array().unwrap().len()
+}
+
+#[test]
+fn local_file_is_added_and_resynced_when_modified() -> anyhow::Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let docs = tempfile::tempdir()?;
+    let file = docs.path().join("llms.txt");
+    std::fs::write(
+        &file,
+        "# Internal\n\n## Deploys\n\nUse the turbine pipeline.\n",
+    )?;
+
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["add", "internal"])
+        .arg(&file)
+        .arg("-y")
+        .assert()
+        .success();
+    assert_eq!(query_count(&data_dir, "turbine", "internal"), 1);
+
+    std::fs::write(
+        &file,
+        "# Internal\n\n## Deploys\n\nUse the gondola pipeline.\n",
+    )?;
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["sync", "internal", "--quiet"])
+        .assert()
+        .success();
+    assert_eq!(query_count(&data_dir, "gondola", "internal"), 1);
+    assert_eq!(query_count(&data_dir, "turbine", "internal"), 0);
+    Ok(())
+}
+
+#[test]
+fn file_url_is_treated_as_local_path() -> anyhow::Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let docs = tempfile::tempdir()?;
+    let file = docs.path().join("llms.txt");
+    std::fs::write(&file, "# Handbook\n\nOn-call rotation details.\n")?;
+    let url = url::Url::from_file_path(&file).unwrap();
+
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["add", "handbook", url.as_str(), "-y"])
+        .assert()
+        .success();
+    assert_eq!(query_count(&data_dir, "rotation", "handbook"), 1);
+    Ok(())
+}
+
+#[test]
+fn stdin_source_is_added_and_left_alone_by_sync() -> anyhow::Result<()> {
+    let data_dir = tempfile::tempdir()?;
+
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["add", "piped", "-", "-y"])
+        .write_stdin("# Piped\n\nGenerated runbook for the lighthouse service.\n")
+        .assert()
+        .success();
+    assert_eq!(query_count(&data_dir, "lighthouse", "piped"), 1);
+
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["sync", "piped", "--quiet"])
+        .assert()
+        .success();
+    assert_eq!(query_count(&data_dir, "lighthouse", "piped"), 1);
+    Ok(())
+}
+
+#[test]
+fn empty_stdin_is_rejected() {
+    let data_dir = tempfile::tempdir().unwrap();
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["add", "piped", "-", "-y"])
+        .write_stdin("")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "No documentation received on stdin",
+        ));
+}
+
+#[test]
+fn rst_file_sections_become_headings() -> anyhow::Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let docs = tempfile::tempdir()?;
+    let file = docs.path().join("protocol.rst");
+    std::fs::write(
+        &file,
+        "Protocol\n========\n\nHandshake\n---------\n\nPeers exchange a turbine token::\n\n    HELLO 1\n",
+    )?;
+
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["add", "protocol"])
+        .arg(&file)
+        .arg("-y")
+        .assert()
+        .success();
+
+    let stdout = blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["query", "turbine", "--source", "protocol", "-f", "json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let payload: Value = serde_json::from_slice(&stdout)?;
+    assert_eq!(
+        payload["results"][0]["headingPath"],
+        serde_json::json!(["Protocol", "Handshake"])
+    );
+    Ok(())
+}
@@ -0,0 +1,72 @@
+#![allow(missing_docs, clippy::expect_used, clippy::unwrap_used)]
+
+mod common;
+
+use common::blz_cmd_with_dirs;
+use predicates::prelude::*;
+
+fn locked_sha(project: &std::path::Path) -> anyhow::Result<String> {
+    let lock: toml::Value = toml::from_str(&std::fs::read_to_string(project.join("blz.lock"))?)?;
+    Ok(lock["source"][0]["sha256"]
+        .as_str()
+        .unwrap_or_default()
+        .to_string())
+}
+
+#[test]
+fn frozen_sync_fails_when_content_drifts_from_lock() -> anyhow::Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let config_dir = tempfile::tempdir()?;
+    let project = tempfile::tempdir()?;
+    let guide = project.path().join("guide.md");
+    std::fs::write(&guide, "# Guide\n\n## Install\n\nRun the installer.\n")?;
+    std::fs::write(
+        project.path().join("blz.sources.toml"),
+        "version = \"1\"\n\n[[source]]\nalias = \"guide\"\npath = \"guide.md\"\n",
+    )?;
+    let blz = || {
+        let mut cmd = blz_cmd_with_dirs(data_dir.path(), config_dir.path());
+        cmd.current_dir(project.path());
+        cmd
+    };
+
+    blz()
+        .args(["add", "--manifest", "blz.sources.toml", "-y"])
+        .assert()
+        .success();
+    let pinned = locked_sha(project.path())?;
+    assert!(!pinned.is_empty());
+
+    blz()
+        .args(["sync", "--frozen"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("ok"));
+
+    std::fs::write(&guide, "# Guide\n\n## Install\n\nRun the new installer.\n")?;
+    blz()
+        .args(["sync", "--frozen"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("drifted"));
+    assert_eq!(locked_sha(project.path())?, pinned, "--frozen never writes");
+
+    blz().args(["sync", "guide"]).assert().success();
+    assert_ne!(locked_sha(project.path())?, pinned, "sync re-pins the lock");
+    blz().args(["sync", "--frozen"]).assert().success();
+    Ok(())
+}
+
+#[test]
+fn frozen_sync_requires_a_lockfile() -> anyhow::Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let config_dir = tempfile::tempdir()?;
+    let project = tempfile::tempdir()?;
+    blz_cmd_with_dirs(data_dir.path(), config_dir.path())
+        .current_dir(project.path())
+        .args(["sync", "--frozen"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("blz.lock"));
+    Ok(())
+}
@@ -0,0 +1,107 @@
+#![allow(missing_docs, clippy::expect_used, clippy::unwrap_used)]
+
+mod common;
+
+use common::blz_cmd;
+use serde_json::Value;
+use tempfile::tempdir;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+async fn mount_doc(server: &MockServer, route: &str, status: u16, body: &str) {
+    Mock::given(method("HEAD"))
+        .and(path(route))
+        .respond_with(ResponseTemplate::new(status))
+        .mount(server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(route))
+        .respond_with(ResponseTemplate::new(status).set_body_string(body))
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn refresh_falls_back_to_healthy_mirror() -> anyhow::Result<()> {
+    let data_dir = tempdir()?;
+    let server = MockServer::start().await;
+    mount_doc(
+        &server,
+        "/llms.txt",
+        200,
+        "# Guide\n\n## Install\nOld steps.\n",
+    )
+    .await;
+
+    let url = format!("{}/llms.txt", server.uri());
+    let mirror = format!("{}/mirror/llms.txt", server.uri());
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["add", "fixture", &url, "--mirror", &mirror, "-y"])
+        .assert()
+        .success();
+
+    // The primary host goes down; the mirror serves a newer copy
+    server.reset().await;
+    mount_doc(&server, "/llms.txt", 503, "").await;
+    mount_doc(
+        &server,
+        "/mirror/llms.txt",
+        200,
+        "# Guide\n\n## Install\nMirrored steps.\n",
+    )
+    .await;
+
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["refresh", "fixture", "--quiet"])
+        .assert()
+        .success();
+
+    let output = blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["info", "fixture", "--json"])
+        .output()?;
+    assert!(output.status.success());
+    let info: Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(info["url"], url.as_str());
+
+    let mirrors = info["mirrors"].as_array().unwrap();
+    assert_eq!(mirrors.len(), 2);
+    let primary = mirrors.iter().find(|m| m["url"] == url.as_str()).unwrap();
+    assert_eq!(primary["consecutiveFailures"], 1);
+    let backup = mirrors
+        .iter()
+        .find(|m| m["url"] == mirror.as_str())
+        .unwrap();
+    assert_eq!(backup["consecutiveFailures"], 0);
+    assert!(backup["latencyMs"].is_u64());
+
+    let output = blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["query", "Mirrored", "--json", "--no-history"])
+        .output()?;
+    let report: Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(report["results"].as_array().unwrap().len(), 1);
+    Ok(())
+}
+
+#[test]
+fn add_rejects_non_http_mirror() {
+    let data_dir = tempdir().unwrap();
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args([
+            "add",
+            "fixture",
+            "https://example.com/llms.txt",
+            "--mirror",
+            "ftp://example.com/llms.txt",
+            "-y",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "must be an http:// or https:// URL",
+        ));
+}
@@ -0,0 +1,178 @@
+#![allow(missing_docs, clippy::expect_used, clippy::unwrap_used)]
+
+mod common;
+
+use std::path::Path;
+
+use common::{blz_cmd, search_heading_paths};
+use serde_json::{Value, json};
+use tempfile::TempDir;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn spec(summary: &str) -> String {
+    format!(
+        "openapi: 3.0.3
+info:
+  title: Petstore
+  version: 1.0.0
+tags:
+  - name: pets
+paths:
+  /pets/{{petId}}:
+    get:
+      tags: [pets]
+      summary: {summary}
+      responses:
+        200:
+          description: The pet.
+components:
+  schemas:
+    Pet:
+      type: object
+      properties:
+        name:
+          type: string
+"
+    )
+}
+
+fn add_spec(data_dir: &TempDir, spec_path: &Path) {
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["add", "api"])
+        .arg(spec_path)
+        .args(["--type", "openapi", "-y"])
+        .assert()
+        .success();
+}
+
+#[test]
+fn openapi_spec_is_rendered_and_synced() -> anyhow::Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let spec_dir = tempfile::tempdir()?;
+    let spec_path = spec_dir.path().join("openapi.yaml");
+    std::fs::write(&spec_path, spec("Find a pet by identifier"))?;
+
+    add_spec(&data_dir, &spec_path);
+
+    let paths = search_heading_paths(data_dir.path(), "api", "identifier");
+    assert_eq!(paths[0], json!(["Petstore", "pets", "GET /pets/{petId}"]));
+
+    std::fs::write(&spec_path, spec("Look up a pet by microchip"))?;
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["sync", "api", "--quiet"])
+        .assert()
+        .success();
+
+    let paths = search_heading_paths(data_dir.path(), "api", "microchip");
+    assert_eq!(paths[0], json!(["Petstore", "pets", "GET /pets/{petId}"]));
+    Ok(())
+}
+
+#[test]
+fn openapi_anchors_survive_description_changes() -> anyhow::Result<()> {
+    let anchor_for = |summary: &str| -> anyhow::Result<Value> {
+        let data_dir = tempfile::tempdir()?;
+        let spec_dir = tempfile::tempdir()?;
+        let spec_path = spec_dir.path().join("openapi.yaml");
+        std::fs::write(&spec_path, spec(summary))?;
+        add_spec(&data_dir, &spec_path);
+
+        let stdout = blz_cmd()
+            .env("BLZ_DATA_DIR", data_dir.path())
+            .args(["toc", "api", "-f", "json"])
+            .assert()
+            .success()
+            .get_output()
+            .stdout
+            .clone();
+        let payload: Value = serde_json::from_slice(&stdout)?;
+        Ok(find_anchor(&payload, "GET /pets/{petId}").expect("operation anchor"))
+    };
+
+    assert_eq!(anchor_for("Find a pet")?, anchor_for("Fetch one pet")?);
+    Ok(())
+}
+
+fn find_anchor(value: &Value, heading: &str) -> Option<Value> {
+    match value {
+        Value::Object(map) => {
+            let matches = map
+                .get("headingPath")
+                .and_then(Value::as_array)
+                .and_then(|path| path.last())
+                .is_some_and(|last| last == heading);
+            if matches {
+                return map.get("anchor").cloned();
+            }
+            map.values().find_map(|child| find_anchor(child, heading))
+        },
+        Value::Array(items) => items.iter().find_map(|child| find_anchor(child, heading)),
+        _ => None,
+    }
+}
+
+#[test]
+fn openapi_source_rejects_non_specs() {
+    let data_dir = tempfile::tempdir().unwrap();
+    let spec_dir = tempfile::tempdir().unwrap();
+    let spec_path = spec_dir.path().join("notes.yaml");
+    std::fs::write(&spec_path, "title: not a spec\n").unwrap();
+
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["add", "api"])
+        .arg(&spec_path)
+        .args(["--type", "openapi", "-y"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("not an OpenAPI spec"));
+}
+
+#[tokio::test]
+async fn spec_urls_are_detected_without_type() -> anyhow::Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let server = MockServer::start().await;
+    let spec = json!({
+        "openapi": "3.0.3",
+        "info": { "title": "Charges API", "version": "1.0.0" },
+        "paths": {
+            "/v1/charges": {
+                "post": {
+                    "summary": "Create a charge",
+                    "parameters": [
+                        { "name": "amount", "in": "query", "schema": { "type": "integer" } }
+                    ],
+                    "responses": { "200": { "description": "The charge." } }
+                }
+            }
+        }
+    });
+    Mock::given(method("HEAD"))
+        .and(path("/openapi.json"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/openapi.json"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(spec))
+        .mount(&server)
+        .await;
+
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args([
+            "add",
+            "api",
+            &format!("{}/openapi.json", server.uri()),
+            "-y",
+        ])
+        .assert()
+        .success();
+
+    let paths = search_heading_paths(data_dir.path(), "api", "charges");
+    assert!(paths.contains(&json!(["Charges API", "Operations", "POST /v1/charges"])));
+    Ok(())
+}
@@ -0,0 +1,96 @@
+#![allow(missing_docs, clippy::expect_used, clippy::unwrap_used)]
+
+mod common;
+
+use common::blz_cmd;
+use serde_json::Value;
+use tempfile::TempDir;
+
+const DOC: &str = "# Guide\n\n## Install\nInstall with npm.\n\n## Upgrade\nInstall the new release.\n\n## Remove\nInstall nothing further.\n";
+
+fn cmd(home: &TempDir) -> assert_cmd::Command {
+    let mut cmd = blz_cmd();
+    cmd.env("BLZ_DATA_DIR", home.path().join("data"))
+        .env("BLZ_CONFIG_DIR", home.path().join("config"));
+    cmd
+}
+
+fn setup() -> anyhow::Result<TempDir> {
+    let home = tempfile::tempdir()?;
+    std::fs::create_dir_all(home.path().join("config"))?;
+    std::fs::write(
+        home.path().join("config").join("config.toml"),
+        format!(
+            r#"[defaults]
+refresh_hours = 24
+max_archives = 10
+fetch_enabled = true
+follow_links = "first_party"
+allowlist = []
+
+[paths]
+root = '{}'
+
+[profiles.agent]
+format = "jsonl"
+color = false
+max_tokens = 1
+"#,
+            home.path().join("data").display()
+        ),
+    )?;
+    let file = home.path().join("guide.md");
+    std::fs::write(&file, DOC)?;
+    cmd(&home)
+        .args(["add", "guide"])
+        .arg(&file)
+        .arg("-y")
+        .assert()
+        .success();
+    Ok(home)
+}
+
+#[test]
+fn profile_out_sets_format_and_token_budget() -> anyhow::Result<()> {
+    let home = setup()?;
+
+    let output = cmd(&home)
+        .args(["--profile-out", "agent", "query", "install"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let lines: Vec<Value> = String::from_utf8(output)?
+        .lines()
+        .map(serde_json::from_str)
+        .collect::<Result<_, _>>()?;
+    assert_eq!(lines.len(), 1, "budget keeps only the first hit");
+    assert_eq!(lines[0]["alias"], "guide");
+
+    // Explicit flags win over the profile
+    let output = cmd(&home)
+        .env("BLZ_PROFILE_OUT", "agent")
+        .args(["query", "install", "--json", "--max-tokens", "1000"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let json: Value = serde_json::from_slice(&output)?;
+    assert_eq!(json["results"].as_array().unwrap().len(), 3);
+    Ok(())
+}
+
+#[test]
+fn unknown_profile_lists_defined_profiles() -> anyhow::Result<()> {
+    let home = setup()?;
+    cmd(&home)
+        .args(["--profile-out", "codex", "list"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "Unknown output profile 'codex' (available: agent)",
+        ));
+    Ok(())
+}
@@ -0,0 +1,42 @@
+#![allow(missing_docs, clippy::expect_used, clippy::unwrap_used)]
+
+mod common;
+
+use common::blz_cmd;
+use predicates::prelude::*;
+use serde_json::Value;
+
+#[test]
+fn ps_emits_json_array() {
+    let stdout = blz_cmd()
+        .args(["ps", "--all", "--json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+
+    let json: Value = serde_json::from_slice(&stdout).expect("valid JSON");
+    let processes = json.as_array().expect("array of processes");
+    let own_pid = std::process::id();
+    assert!(
+        processes
+            .iter()
+            .all(|process| process["pid"].as_u64() != Some(u64::from(own_pid))),
+        "test harness is not a blz process"
+    );
+}
+
+#[test]
+fn kill_rejects_non_blz_pid() {
+    blz_cmd()
+        .args(["kill", &std::process::id().to_string()])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("is not a running blz process"));
+}
+
+#[test]
+fn kill_requires_target() {
+    blz_cmd().arg("kill").assert().failure();
+}
@@ -0,0 +1,72 @@
+#![allow(missing_docs, clippy::expect_used, clippy::unwrap_used)]
+
+mod common;
+
+use common::blz_cmd_with_dirs;
+use serde_json::Value;
+
+#[test]
+fn named_profiles_isolate_sources() -> anyhow::Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let config_dir = tempfile::tempdir()?;
+    let docs = tempfile::tempdir()?;
+    std::fs::write(
+        docs.path().join("guide.md"),
+        "# Guide\n\n## Install\n\nRun it.\n",
+    )?;
+    let blz = || {
+        let mut cmd = blz_cmd_with_dirs(data_dir.path(), config_dir.path());
+        cmd.env_remove("BLZ_PROFILE");
+        cmd
+    };
+
+    blz().args(["profile", "create", "work"]).assert().success();
+    blz()
+        .env("BLZ_PROFILE", "work")
+        .args(["add", "guide"])
+        .arg(docs.path())
+        .args(["--type", "dir", "-y"])
+        .assert()
+        .success();
+
+    let listed = |profile: Option<&str>| -> anyhow::Result<Vec<Value>> {
+        let mut cmd = blz();
+        if let Some(name) = profile {
+            cmd.args(["--profile-name", name]);
+        }
+        let output = cmd.args(["list", "--json"]).assert().success();
+        Ok(serde_json::from_slice(&output.get_output().stdout)?)
+    };
+    assert!(listed(None)?.is_empty(), "default profile stays empty");
+    let work = listed(Some("work"))?;
+    assert_eq!(work.len(), 1);
+    assert_eq!(work[0]["alias"], "guide");
+
+    let output = blz()
+        .env("BLZ_PROFILE", "work")
+        .args(["profile", "list", "--json"])
+        .assert()
+        .success();
+    let profiles: Vec<Value> = serde_json::from_slice(&output.get_output().stdout)?;
+    let names: Vec<&str> = profiles.iter().filter_map(|p| p["name"].as_str()).collect();
+    assert_eq!(names, ["default", "work"]);
+    assert_eq!(profiles[0]["active"], false);
+    assert_eq!(profiles[1]["active"], true);
+    assert_eq!(profiles[1]["sources"], 1);
+
+    blz()
+        .env("BLZ_PROFILE", "work")
+        .args(["profile", "rm", "work", "--yes"])
+        .assert()
+        .failure();
+    blz()
+        .args(["profile", "rm", "work", "--yes"])
+        .assert()
+        .success();
+    assert!(!data_dir.path().join("profiles").join("work").exists());
+    blz()
+        .args(["profile", "create", "../escape"])
+        .assert()
+        .failure();
+    Ok(())
+}
@@ -0,0 +1,82 @@
+#![allow(missing_docs, clippy::expect_used, clippy::unwrap_used)]
+
+mod common;
+
+use common::blz_cmd;
+use tempfile::tempdir;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const DOC: &str = "# Corp\n\n## Deploys\nUse the turbine pipeline.\n";
+
+#[tokio::test]
+async fn add_goes_through_http_proxy_from_env() -> anyhow::Result<()> {
+    let data_dir = tempdir()?;
+    let proxy = MockServer::start().await;
+    Mock::given(method("HEAD"))
+        .and(path("/llms.txt"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&proxy)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/llms.txt"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(DOC))
+        .mount(&proxy)
+        .await;
+
+    // The host does not resolve, so the fetch only succeeds via the proxy
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .env("HTTP_PROXY", proxy.uri())
+        .env_remove("NO_PROXY")
+        .env_remove("no_proxy")
+        .args(["add", "corp", "http://docs.corp.invalid/llms.txt", "-y"])
+        .assert()
+        .success();
+
+    let requests = proxy.received_requests().await.unwrap();
+    assert!(
+        requests
+            .iter()
+            .any(|request| request.url.path() == "/llms.txt")
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn no_proxy_hosts_are_fetched_directly() -> anyhow::Result<()> {
+    let data_dir = tempdir()?;
+    let server = MockServer::start().await;
+    Mock::given(method("HEAD"))
+        .and(path("/llms.txt"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/llms.txt"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(DOC))
+        .mount(&server)
+        .await;
+    let url = format!("{}/llms.txt", server.uri());
+
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .env("HTTP_PROXY", "http://127.0.0.1:9")
+        .env("NO_PROXY", "127.0.0.1,localhost")
+        .args(["add", "direct", &url, "-y"])
+        .assert()
+        .success();
+    Ok(())
+}
+
+#[test]
+fn unreadable_ca_bundle_is_reported() {
+    let data_dir = tempdir().unwrap();
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .env("BLZ_CA_BUNDLE", data_dir.path().join("missing.pem"))
+        .args(["add", "corp", "https://docs.corp.invalid/llms.txt", "-y"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("Failed to read CA bundle"));
+}
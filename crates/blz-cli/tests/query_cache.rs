@@ -0,0 +1,149 @@
+#![allow(missing_docs, clippy::expect_used, clippy::unwrap_used)]
+
+mod common;
+
+use common::blz_cmd;
+use serde_json::Value;
+use tempfile::TempDir;
+
+fn query(data_dir: &TempDir, envs: &[(&str, &str)]) -> Value {
+    let stdout = blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .envs(envs.iter().copied())
+        .args(["query", "useState", "--json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    serde_json::from_slice(&stdout).unwrap()
+}
+
+fn cache_entries(data_dir: &TempDir) -> usize {
+    std::fs::read_dir(data_dir.path().join("query-cache")).map_or(0, Iterator::count)
+}
+
+#[test]
+fn repeated_queries_are_served_from_the_cache_until_sources_change() -> anyhow::Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let docs = tempfile::tempdir()?;
+    std::fs::write(
+        docs.path().join("hooks.md"),
+        "# Hooks\n\nuseState adds state to components.\n",
+    )?;
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args([
+            "add",
+            "react",
+            docs.path().to_str().unwrap(),
+            "--type",
+            "dir",
+            "-y",
+        ])
+        .assert()
+        .success();
+
+    let first = query(&data_dir, &[]);
+    assert_eq!(cache_entries(&data_dir), 1);
+    let second = query(&data_dir, &[]);
+    assert_eq!(first["results"], second["results"]);
+    assert_eq!(cache_entries(&data_dir), 1);
+
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["rm", "react", "-y"])
+        .assert()
+        .success();
+    assert_eq!(cache_entries(&data_dir), 0);
+    Ok(())
+}
+
+#[test]
+fn query_cache_can_be_disabled() -> anyhow::Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let docs = tempfile::tempdir()?;
+    std::fs::write(docs.path().join("hooks.md"), "# Hooks\n\nuseState.\n")?;
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args([
+            "add",
+            "react",
+            docs.path().to_str().unwrap(),
+            "--type",
+            "dir",
+            "-y",
+        ])
+        .assert()
+        .success();
+
+    let results = query(&data_dir, &[("BLZ_QUERY_CACHE", "0")]);
+    assert_eq!(results["results"][0]["alias"], "react");
+    assert_eq!(cache_entries(&data_dir), 0);
+    Ok(())
+}
+
+fn headings(data_dir: &TempDir, query: &str) -> Vec<String> {
+    let stdout = blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["query", query, "--json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let payload: Value = serde_json::from_slice(&stdout).unwrap();
+    payload["results"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|hit| hit["headingPath"][0].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[test]
+fn editing_ranking_settings_changes_cached_results() -> anyhow::Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let docs = tempfile::tempdir()?;
+    std::fs::write(
+        docs.path().join("hooks.md"),
+        "# Hooks\n\nuseState adds state to components.\n",
+    )?;
+    std::fs::write(
+        docs.path().join("forms.md"),
+        "# Forms\n\nForm components collect input.\n",
+    )?;
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args([
+            "add",
+            "react",
+            docs.path().to_str().unwrap(),
+            "--type",
+            "dir",
+            "-y",
+        ])
+        .assert()
+        .success();
+    let settings = data_dir.path().join("sources/react/settings.toml");
+
+    let query = "useState components";
+    let mut before = headings(&data_dir, query);
+    before.sort();
+    assert_eq!(before, ["forms.md", "hooks.md"]);
+
+    let write_index = |index: &str| {
+        std::fs::write(
+            &settings,
+            format!("[meta]\nname = \"react\"\n\n[fetch]\n\n[index]\n{index}"),
+        )
+    };
+    write_index("stopwords = [\"components\"]\n")?;
+    assert_eq!(headings(&data_dir, query), ["hooks.md"]);
+
+    write_index("")?;
+    let mut after = headings(&data_dir, query);
+    after.sort();
+    assert_eq!(after, before);
+    Ok(())
+}
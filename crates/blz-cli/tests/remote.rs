@@ -0,0 +1,155 @@
+#![allow(missing_docs, clippy::expect_used, clippy::unwrap_used)]
+
+mod common;
+
+use common::blz_cmd;
+use predicates::prelude::*;
+use serde_json::{Value, json};
+use tempfile::tempdir;
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const DOC: &str = "# Guide\n\n## Auth\nSend a bearer token.\n";
+
+#[tokio::test]
+async fn query_runs_on_remote_and_renders_locally() -> anyhow::Result<()> {
+    let local = tempdir()?;
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/search"))
+        .and(query_param("q", "bearer"))
+        .and(query_param("source", "fixture"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "hits": [{
+                "source": "fixture",
+                "file": "llms.txt",
+                "headingPath": ["Guide", "Auth"],
+                "level": 2,
+                "lines": "3-4",
+                "snippet": "Send a bearer token.",
+                "score": 4.5,
+                "sourceUrl": "https://example.com/llms.txt",
+                "isStale": false,
+                "checksum": "abc"
+            }],
+            "totalLinesSearched": 4,
+            "sources": ["fixture"]
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let stdout = blz_cmd()
+        .env("BLZ_DATA_DIR", local.path())
+        .env("BLZ_CONFIG_DIR", local.path())
+        .args(["query", "bearer", "-s", "fixture", "--json"])
+        .args(["--remote", &server.uri()])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+
+    let payload: Value = serde_json::from_slice(&stdout)?;
+    let results = payload["results"].as_array().unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0]["alias"], "fixture");
+    assert_eq!(results[0]["headingPath"], json!(["Guide", "Auth"]));
+    assert_eq!(results[0]["lines"], "3-4");
+    Ok(())
+}
+
+#[tokio::test]
+async fn get_via_remote_matches_local_output() -> anyhow::Result<()> {
+    // Seed a local cache to produce the reference output.
+    let local = tempdir()?;
+    let origin = MockServer::start().await;
+    Mock::given(method("HEAD"))
+        .and(path("/llms.txt"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&origin)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/llms.txt"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(DOC))
+        .mount(&origin)
+        .await;
+    blz_cmd()
+        .env("BLZ_DATA_DIR", local.path())
+        .args([
+            "add",
+            "fixture",
+            &format!("{}/llms.txt", origin.uri()),
+            "-y",
+        ])
+        .assert()
+        .success();
+    let expected = blz_cmd()
+        .env("BLZ_DATA_DIR", local.path())
+        .args(["get", "fixture:3-4", "--text"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+
+    let remote = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/get"))
+        .and(query_param("target", "fixture:3-4"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([{
+            "alias": "fixture",
+            "canonical": "fixture",
+            "heading": null,
+            "linesWithContent": [[3, "## Auth"], [4, "Send a bearer token."]],
+            "snippetRanges": [{
+                "lineStart": 3,
+                "lineEnd": 4,
+                "snippet": "## Auth\nSend a bearer token."
+            }],
+            "checksum": null,
+            "fileLen": 4,
+            "truncated": false
+        }])))
+        .expect(1)
+        .mount(&remote)
+        .await;
+
+    let empty = tempdir()?;
+    let actual = blz_cmd()
+        .env("BLZ_DATA_DIR", empty.path())
+        .env("BLZ_REMOTE", remote.uri())
+        .args(["get", "fixture:3-4", "--text"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+
+    assert_eq!(String::from_utf8(actual)?, String::from_utf8(expected)?);
+    Ok(())
+}
+
+#[tokio::test]
+async fn remote_errors_surface_server_message() -> anyhow::Result<()> {
+    let local = tempdir()?;
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/get"))
+        .respond_with(
+            ResponseTemplate::new(404).set_body_json(json!({ "error": "Source 'nope' not found" })),
+        )
+        .mount(&server)
+        .await;
+
+    blz_cmd()
+        .env("BLZ_DATA_DIR", local.path())
+        .args(["get", "nope:1-2", "--remote", &server.uri()])
+        .assert()
+        .failure()
+        .stderr(
+            predicate::str::contains("404")
+                .and(predicate::str::contains("Source 'nope' not found")),
+        );
+    Ok(())
+}
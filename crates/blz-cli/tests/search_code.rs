@@ -0,0 +1,85 @@
+#![allow(missing_docs, clippy::expect_used, clippy::unwrap_used)]
+
+mod common;
+
+use common::blz_cmd;
+use serde_json::Value;
+use tempfile::tempdir;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const DOC: &str = "# Guide\n\n## Fetching\n\nCall fetch with the request URL.\n\n## TypeScript client\n\n```ts\nconst res = await fetch(url);\n```\n\n## Python client\n\n```python\nres = fetch(url)\n```\n";
+
+async fn seed(tmp: &tempfile::TempDir, server: &MockServer) {
+    Mock::given(method("HEAD"))
+        .and(path("/llms.txt"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/llms.txt"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(DOC))
+        .mount(server)
+        .await;
+
+    let url = format!("{}/llms.txt", server.uri());
+    blz_cmd()
+        .env("BLZ_DATA_DIR", tmp.path())
+        .args(["add", "fixture", &url, "-y"])
+        .assert()
+        .success();
+}
+
+fn headings(tmp: &tempfile::TempDir, args: &[&str]) -> anyhow::Result<Vec<String>> {
+    let output = blz_cmd()
+        .env("BLZ_DATA_DIR", tmp.path())
+        .args(["search", "fetch", "--json", "--no-history"])
+        .args(args)
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let payload: Value = serde_json::from_slice(&output)?;
+    let mut headings: Vec<String> = payload["results"]
+        .as_array()
+        .expect("results array")
+        .iter()
+        .map(|hit| {
+            hit["headingPath"]
+                .as_array()
+                .and_then(|path| path.last())
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string()
+        })
+        .collect();
+    headings.sort();
+    Ok(headings)
+}
+
+#[tokio::test]
+async fn code_search_skips_prose_and_filters_by_language() -> anyhow::Result<()> {
+    let tmp = tempdir()?;
+    let server = MockServer::start().await;
+    seed(&tmp, &server).await;
+
+    assert!(headings(&tmp, &[])?.contains(&"Fetching".to_string()));
+    assert_eq!(
+        headings(&tmp, &["--code"])?,
+        ["Python client", "TypeScript client"]
+    );
+    assert_eq!(
+        headings(&tmp, &["--code", "--lang", "ts"])?,
+        ["TypeScript client"]
+    );
+    Ok(())
+}
+
+#[test]
+fn lang_requires_code() {
+    blz_cmd()
+        .args(["search", "fetch", "--lang", "ts"])
+        .assert()
+        .failure();
+}
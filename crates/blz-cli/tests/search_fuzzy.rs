@@ -0,0 +1,54 @@
+#![allow(missing_docs, clippy::expect_used, clippy::unwrap_used)]
+
+mod common;
+
+use common::blz_cmd;
+use serde_json::Value;
+use tempfile::TempDir;
+
+fn hit_count(data_dir: &TempDir, args: &[&str], env_fuzzy: Option<&str>) -> usize {
+    let mut cmd = blz_cmd();
+    cmd.env("BLZ_DATA_DIR", data_dir.path());
+    if let Some(value) = env_fuzzy {
+        cmd.env("BLZ_FUZZY", value);
+    }
+    let stdout = cmd
+        .args(["query", "useEfect", "--source", "hooks", "-f", "json"])
+        .args(args)
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let payload: Value = serde_json::from_slice(&stdout).unwrap();
+    payload["results"].as_array().unwrap().len()
+}
+
+#[test]
+fn fuzzy_flag_and_config_default_tolerate_typos() -> anyhow::Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let docs = tempfile::tempdir()?;
+    std::fs::write(
+        docs.path().join("effects.md"),
+        "# Effects\n\nCall useEffect to synchronize with external systems.\n",
+    )?;
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["add", "hooks"])
+        .arg(docs.path())
+        .args(["--type", "dir", "-y"])
+        .assert()
+        .success();
+
+    assert_eq!(hit_count(&data_dir, &[], None), 0);
+    assert_eq!(hit_count(&data_dir, &["--fuzzy"], None), 1);
+    assert_eq!(hit_count(&data_dir, &[], Some("1")), 1);
+    assert_eq!(hit_count(&data_dir, &["--fuzzy=0"], Some("1")), 0);
+
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["query", "useEfect", "--fuzzy=3"])
+        .assert()
+        .failure();
+    Ok(())
+}
@@ -0,0 +1,97 @@
+#![allow(missing_docs, clippy::expect_used, clippy::unwrap_used)]
+
+mod common;
+
+use common::blz_cmd;
+use serde_json::Value;
+use tempfile::tempdir;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const DOC: &str = "# Guide\n\n## Routing\n\nRouting basics.\n\n### Routing params\n\nRouting with params.\n\n### Nested routing\n\nMore routing.\n\n## Deploy\n\nShip it.\n";
+
+async fn seed(tmp: &tempfile::TempDir, server: &MockServer) {
+    Mock::given(method("HEAD"))
+        .and(path("/llms.txt"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/llms.txt"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(DOC))
+        .mount(server)
+        .await;
+
+    let url = format!("{}/llms.txt", server.uri());
+    blz_cmd()
+        .env("BLZ_DATA_DIR", tmp.path())
+        .args(["add", "fixture", &url, "-y"])
+        .assert()
+        .success();
+}
+
+#[tokio::test]
+async fn headings_only_json_uses_heading_shape() -> anyhow::Result<()> {
+    let tmp = tempdir()?;
+    let server = MockServer::start().await;
+    seed(&tmp, &server).await;
+
+    let output = blz_cmd()
+        .env("BLZ_DATA_DIR", tmp.path())
+        .args(["query", "routing", "--headings-only", "-f", "json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let payload: Value = serde_json::from_slice(&output)?;
+
+    let results = payload["results"].as_array().expect("results array");
+    assert!(!results.is_empty());
+    for hit in results {
+        assert!(hit.get("snippet").is_none(), "snippet should be omitted");
+        assert!(hit["heading"].is_string());
+        assert!(hit["lines"].is_string());
+        assert!(hit["score"].is_u64());
+    }
+
+    // One entry per heading
+    let mut paths: Vec<String> = results
+        .iter()
+        .map(|hit| hit["headingPath"].to_string())
+        .collect();
+    let total = paths.len();
+    paths.sort();
+    paths.dedup();
+    assert_eq!(paths.len(), total);
+
+    let routing = results
+        .iter()
+        .find(|hit| hit["heading"] == "Routing")
+        .expect("Routing heading");
+    assert_eq!(routing["childCount"], 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn headings_only_markdown_renders_outline() -> anyhow::Result<()> {
+    let tmp = tempdir()?;
+    let server = MockServer::start().await;
+    seed(&tmp, &server).await;
+
+    let output = blz_cmd()
+        .env("BLZ_DATA_DIR", tmp.path())
+        .args(["query", "routing", "--headings-only", "-f", "markdown"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let text = String::from_utf8(output)?;
+
+    assert!(text.contains("## fixture"), "{text}");
+    assert!(text.contains("- [Routing](#"), "{text}");
+    assert!(text.contains("2 subsections"), "{text}");
+    assert!(text.contains("  - [Routing params](#"), "{text}");
+    Ok(())
+}
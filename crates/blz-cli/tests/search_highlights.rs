@@ -0,0 +1,60 @@
+#![allow(missing_docs, clippy::expect_used, clippy::unwrap_used)]
+
+mod common;
+
+use common::blz_cmd;
+use serde_json::Value;
+
+#[test]
+fn json_hits_report_match_offsets_within_snippet() -> anyhow::Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let docs = tempfile::tempdir()?;
+    std::fs::write(
+        docs.path().join("effects.md"),
+        "# Effects\n\nCall useEffect to synchronize a component with external systems.\n",
+    )?;
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["add", "hooks"])
+        .arg(docs.path())
+        .args(["--type", "dir", "-y"])
+        .assert()
+        .success();
+
+    for format in ["json", "jsonl"] {
+        let stdout = blz_cmd()
+            .env("BLZ_DATA_DIR", data_dir.path())
+            .args([
+                "query",
+                "useeffect external",
+                "--source",
+                "hooks",
+                "-f",
+                format,
+            ])
+            .assert()
+            .success()
+            .get_output()
+            .stdout
+            .clone();
+        let hit: Value = if format == "json" {
+            serde_json::from_slice::<Value>(&stdout)?["results"][0].clone()
+        } else {
+            serde_json::from_str(String::from_utf8(stdout)?.lines().next().unwrap())?
+        };
+
+        let snippet = hit["snippet"].as_str().unwrap();
+        let marked: Vec<&str> = hit["highlights"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|span| {
+                let start = usize::try_from(span["start"].as_u64().unwrap()).unwrap();
+                let end = usize::try_from(span["end"].as_u64().unwrap()).unwrap();
+                &snippet[start..end]
+            })
+            .collect();
+        assert_eq!(marked, ["useEffect", "external"], "{format}");
+    }
+    Ok(())
+}
@@ -0,0 +1,101 @@
+#![allow(missing_docs, clippy::expect_used, clippy::unwrap_used)]
+
+mod common;
+
+use common::blz_cmd;
+use serde_json::{Value, json};
+use tempfile::TempDir;
+
+const GUIDE: &str = "# Hooks
+
+## Effects
+
+Use effects to sync with the outside world.
+
+```js
+useEffect(() => subscribe(), []);
+```
+
+## State
+
+Keep state close to where it is used; reach for useEffect sparingly.
+
+# Routing
+
+## Links
+
+Links navigate between pages and preserve state.
+";
+
+fn add_guide(data_dir: &TempDir, docs: &TempDir) {
+    std::fs::write(docs.path().join("guide.md"), GUIDE).unwrap();
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["add", "guide"])
+        .arg(docs.path())
+        .args(["--type", "dir", "-y"])
+        .assert()
+        .success();
+}
+
+fn headings(data_dir: &TempDir, query: &str) -> Vec<Value> {
+    let stdout = blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["query", query, "--source", "guide", "-f", "json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let payload: Value = serde_json::from_slice(&stdout).unwrap();
+    let mut paths: Vec<Value> = payload["results"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|hit| hit["headingPath"].clone())
+        .collect();
+    paths.sort_by_key(ToString::to_string);
+    paths
+}
+
+#[test]
+fn field_prefixes_and_operators_scope_matches() -> anyhow::Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let docs = tempfile::tempdir()?;
+    add_guide(&data_dir, &docs);
+
+    assert_eq!(
+        headings(&data_dir, "code:useEffect"),
+        [json!(["guide.md", "Hooks", "Effects"])]
+    );
+    assert_eq!(
+        headings(&data_dir, "heading:state"),
+        [json!(["guide.md", "Hooks", "State"])]
+    );
+    assert_eq!(
+        headings(&data_dir, "state AND path:routing"),
+        [json!(["guide.md", "Routing", "Links"])]
+    );
+    assert_eq!(
+        headings(&data_dir, "useEffect NOT code:subscribe"),
+        [json!(["guide.md", "Hooks", "State"])]
+    );
+    Ok(())
+}
+
+#[test]
+fn malformed_structured_queries_are_rejected() -> anyhow::Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let docs = tempfile::tempdir()?;
+    add_guide(&data_dir, &docs);
+
+    for query in ["NOT hooks", "(hooks AND state", "hooks OR NOT state"] {
+        blz_cmd()
+            .env("BLZ_DATA_DIR", data_dir.path())
+            .args(["query", query, "--source", "guide"])
+            .assert()
+            .failure()
+            .stderr(predicates::str::contains("Invalid query"));
+    }
+    Ok(())
+}
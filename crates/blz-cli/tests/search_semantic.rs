@@ -0,0 +1,100 @@
+#![allow(missing_docs, clippy::expect_used, clippy::unwrap_used)]
+
+mod common;
+
+use common::blz_cmd;
+use serde_json::Value;
+use tempfile::TempDir;
+
+fn add_docs(data_dir: &TempDir) -> anyhow::Result<()> {
+    let docs = tempfile::tempdir()?;
+    std::fs::write(
+        docs.path().join("hooks.md"),
+        "# Hooks\n\nuseState lets you add state to function components and update it.\n",
+    )?;
+    std::fs::write(
+        docs.path().join("routing.md"),
+        "# Routing\n\nThe App Router maps folders to URLs in Next.js.\n",
+    )?;
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["add", "react"])
+        .arg(docs.path())
+        .args(["--type", "dir", "-y"])
+        .assert()
+        .success();
+    Ok(())
+}
+
+fn top_files(data_dir: &TempDir, query: &str, args: &[&str]) -> Vec<String> {
+    let stdout = blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["query", query, "--source", "react", "-f", "json"])
+        .args(args)
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let payload: Value = serde_json::from_slice(&stdout).unwrap();
+    payload["results"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|hit| hit["headingPath"][0].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[cfg(feature = "semantic")]
+fn contains_file(dir: &std::path::Path, name: &str) -> bool {
+    std::fs::read_dir(dir).unwrap().flatten().any(|entry| {
+        let path = entry.path();
+        if path.is_dir() {
+            contains_file(&path, name)
+        } else {
+            path.file_name().is_some_and(|file| file == name)
+        }
+    })
+}
+
+#[test]
+#[cfg(feature = "semantic")]
+fn semantic_and_hybrid_rank_related_wording() -> anyhow::Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    add_docs(&data_dir)?;
+
+    let query = "updating stateful values";
+    assert!(top_files(&data_dir, query, &[]).is_empty());
+
+    let semantic = top_files(&data_dir, query, &["--semantic"]);
+    assert_eq!(semantic.first().map(String::as_str), Some("hooks.md"));
+    assert!(contains_file(data_dir.path(), "vectors.json"));
+
+    let hybrid = top_files(&data_dir, query, &["--hybrid"]);
+    assert_eq!(hybrid.first().map(String::as_str), Some("hooks.md"));
+
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["query", query, "--semantic", "--hybrid"])
+        .assert()
+        .failure();
+    Ok(())
+}
+
+#[test]
+#[cfg(not(feature = "semantic"))]
+fn semantic_without_feature_explains_how_to_enable_it() -> anyhow::Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    add_docs(&data_dir)?;
+
+    assert_eq!(top_files(&data_dir, "useState", &[]), ["hooks.md"]);
+    for flag in ["--semantic", "--hybrid"] {
+        blz_cmd()
+            .env("BLZ_DATA_DIR", data_dir.path())
+            .args(["query", "useState", "--source", "react", flag])
+            .assert()
+            .failure()
+            .stderr(predicates::str::contains("--features semantic"));
+    }
+    Ok(())
+}
@@ -0,0 +1,119 @@
+#![allow(missing_docs, clippy::expect_used, clippy::unwrap_used)]
+
+mod common;
+
+use common::blz_cmd;
+use serde_json::Value;
+use tempfile::tempdir;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const DOC: &str = "# Remote Docs\n\n## Hooks\nUse hooks to share stateful logic.\n\n## Routing\nDefine routes in a table.\n";
+
+async fn mount_doc(server: &MockServer) -> String {
+    Mock::given(method("HEAD"))
+        .and(path("/llms.txt"))
+        .respond_with(
+            ResponseTemplate::new(200).insert_header("content-length", DOC.len().to_string()),
+        )
+        .mount(server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/llms.txt"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(DOC))
+        .mount(server)
+        .await;
+
+    format!("{}/llms.txt", server.uri())
+}
+
+fn list_sources(tmp: &tempfile::TempDir) -> Vec<Value> {
+    let stdout = blz_cmd()
+        .env("BLZ_DATA_DIR", tmp.path())
+        .args(["list", "--json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    serde_json::from_slice::<Value>(&stdout)
+        .unwrap()
+        .as_array()
+        .cloned()
+        .unwrap_or_default()
+}
+
+#[tokio::test]
+async fn url_search_returns_results_without_adding_source() -> anyhow::Result<()> {
+    let tmp = tempdir()?;
+    let server = MockServer::start().await;
+    let url = mount_doc(&server).await;
+
+    let stdout = blz_cmd()
+        .env("BLZ_DATA_DIR", tmp.path())
+        .args(["search", "--url", url.as_str(), "hooks", "--json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+
+    let json: Value = serde_json::from_slice(&stdout)?;
+    let results = json["results"].as_array().expect("results array");
+    assert!(
+        !results.is_empty(),
+        "expected hits from the remote document"
+    );
+    assert!(
+        results[0]["headingPath"]
+            .as_array()
+            .is_some_and(|path| path.iter().any(|h| h == "Hooks"))
+    );
+
+    assert!(
+        list_sources(&tmp).is_empty(),
+        "ephemeral search must not add a source"
+    );
+
+    let downloads = server
+        .received_requests()
+        .await
+        .unwrap()
+        .iter()
+        .filter(|request| request.method.as_str() == "GET")
+        .count();
+    assert_eq!(downloads, 1, "the document should be downloaded once");
+    Ok(())
+}
+
+#[tokio::test]
+async fn url_search_with_keep_adds_source() -> anyhow::Result<()> {
+    let tmp = tempdir()?;
+    let server = MockServer::start().await;
+    let url = mount_doc(&server).await;
+
+    blz_cmd()
+        .env("BLZ_DATA_DIR", tmp.path())
+        .args([
+            "search",
+            "--url",
+            url.as_str(),
+            "--keep",
+            "routing",
+            "--json",
+        ])
+        .assert()
+        .success();
+
+    assert_eq!(list_sources(&tmp).len(), 1);
+    Ok(())
+}
+
+#[test]
+fn keep_requires_url() {
+    blz_cmd()
+        .args(["search", "--keep", "hooks"])
+        .assert()
+        .failure();
+}
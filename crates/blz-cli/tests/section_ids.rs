@@ -0,0 +1,68 @@
+#![allow(missing_docs, clippy::expect_used, clippy::unwrap_used)]
+
+mod common;
+
+use common::blz_cmd;
+use serde_json::Value;
+use tempfile::{TempDir, tempdir};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const OLD: &str = "# Guide\n\n## Install\nRun the installer.\n";
+const NEW: &str =
+    "# Guide\n\n## Overview\nStart here.\n\n## Install\nRun the installer, then verify.\n";
+
+async fn serve(server: &MockServer, body: &str) {
+    Mock::given(method("HEAD"))
+        .and(path("/llms.txt"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/llms.txt"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(body))
+        .mount(server)
+        .await;
+}
+
+fn installer_hit(data_dir: &TempDir) -> Value {
+    let stdout = blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["search", "installer", "--source", "guide", "--json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let payload: Value = serde_json::from_slice(&stdout).unwrap();
+    payload["results"][0].clone()
+}
+
+#[tokio::test]
+async fn section_ids_are_stable_across_syncs() -> anyhow::Result<()> {
+    let data_dir = tempdir()?;
+    let server = MockServer::start().await;
+    serve(&server, OLD).await;
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["add", "guide", &format!("{}/llms.txt", server.uri()), "-y"])
+        .assert()
+        .success();
+
+    let before = installer_hit(&data_dir);
+    let id = before["sectionId"].as_str().expect("hit has a section id");
+
+    server.reset().await;
+    serve(&server, NEW).await;
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["sync", "guide"])
+        .assert()
+        .success();
+
+    // The section was edited and pushed down, but keeps its ID
+    let after = installer_hit(&data_dir);
+    assert_ne!(after["lines"], before["lines"]);
+    assert_eq!(after["sectionId"], id);
+    Ok(())
+}
@@ -0,0 +1,207 @@
+#![allow(missing_docs, clippy::expect_used, clippy::unwrap_used)]
+
+mod common;
+
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use std::process::{Child, Command, Stdio};
+
+use common::blz_cmd;
+use serde_json::Value;
+
+/// Running `blz serve`, killed on drop.
+struct Server {
+    child: Child,
+    base: String,
+}
+
+impl Server {
+    fn start(data_dir: &Path) -> Self {
+        let mut child = Command::new(assert_cmd::cargo::cargo_bin!("blz"))
+            .env("BLZ_DATA_DIR", data_dir)
+            .env("BLZ_CONFIG_DIR", data_dir)
+            .env("BLZ_DISABLE_GUARD", "1")
+            .env("NO_COLOR", "1")
+            .args(["serve", "--port", "0"])
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        let mut line = String::new();
+        BufReader::new(child.stdout.take().unwrap())
+            .read_line(&mut line)
+            .unwrap();
+        let base = line
+            .trim()
+            .rsplit(' ')
+            .next()
+            .unwrap()
+            .trim_end_matches('/')
+            .to_string();
+        assert!(base.starts_with("http://127.0.0.1:"), "{line}");
+        Self { child, base }
+    }
+
+    async fn get(&self, route: &str) -> (u16, Value) {
+        let response = reqwest::get(format!("{}{route}", self.base)).await.unwrap();
+        let status = response.status().as_u16();
+        (status, response.json().await.unwrap())
+    }
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+#[tokio::test]
+async fn serve_answers_api_routes_and_remote_clients() -> anyhow::Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let docs = tempfile::tempdir()?;
+    std::fs::write(
+        docs.path().join("auth.md"),
+        "# Auth\n\n## Tokens\n\nSend a bearer token with every request.\n",
+    )?;
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["add", "guide"])
+        .arg(docs.path())
+        .args(["--type", "dir", "-y"])
+        .assert()
+        .success();
+
+    let server = Server::start(data_dir.path());
+
+    let (status, health) = server.get("/v1/health").await;
+    assert_eq!(status, 200);
+    assert_eq!(health["status"], "ok");
+
+    let (_, list) = server.get("/v1/list").await;
+    assert_eq!(list[0]["alias"], "guide");
+
+    let (_, toc) = server.get("/v1/toc?source=guide").await;
+    assert!(
+        toc["entries"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|entry| entry["headingPath"].to_string().contains("Tokens"))
+    );
+
+    let (_, search) = server
+        .get("/v1/search?q=bearer&source=guide&format=json")
+        .await;
+    assert_eq!(search["results"][0]["alias"], "guide");
+    let lines = search["results"][0]["lines"].as_str().unwrap().to_string();
+
+    let (_, get) = server
+        .get(&format!("/v1/get?target=guide:{lines}&format=json"))
+        .await;
+    assert!(
+        get["requests"][0]["snippet"]
+            .as_str()
+            .unwrap()
+            .contains("bearer token")
+    );
+
+    let (status, error) = server.get("/v1/search?source=guide").await;
+    assert_eq!(status, 400);
+    assert!(error["error"].as_str().unwrap().contains("'q'"));
+    let (status, _) = server.get("/v2/nothing").await;
+    assert_eq!(status, 404);
+    for route in [
+        "/v1/search?q=bearer&source=missing",
+        "/v1/get?target=missing:1-2",
+        "/v1/get?target=guide:9000-9010",
+        "/v1/toc?source=missing",
+    ] {
+        let (status, error) = server.get(route).await;
+        assert_eq!(status, 404, "{route}: {error}");
+    }
+
+    // The wire format is what `--remote` clients consume.
+    let empty = tempfile::tempdir()?;
+    let stdout = blz_cmd()
+        .env("BLZ_DATA_DIR", empty.path())
+        .env("BLZ_CONFIG_DIR", empty.path())
+        .args(["query", "bearer", "-s", "guide", "--json"])
+        .args(["--remote", &server.base])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let payload: Value = serde_json::from_slice(&stdout)?;
+    assert_eq!(payload["results"][0]["lines"], lines.as_str());
+    Ok(())
+}
+
+#[tokio::test]
+async fn serve_answers_conditional_requests_with_not_modified() -> anyhow::Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let docs = tempfile::tempdir()?;
+    std::fs::write(
+        docs.path().join("auth.md"),
+        "# Auth\n\n## Tokens\n\nSend a bearer token with every request.\n",
+    )?;
+    let add = |alias: &str| {
+        blz_cmd()
+            .env("BLZ_DATA_DIR", data_dir.path())
+            .args(["add", alias])
+            .arg(docs.path())
+            .args(["--type", "dir", "-y"])
+            .assert()
+            .success();
+    };
+    add("guide");
+
+    let server = Server::start(data_dir.path());
+    let client = reqwest::Client::new();
+    let url = format!("{}/v1/search?q=bearer", server.base);
+
+    let first = client.get(&url).send().await?;
+    assert_eq!(first.status().as_u16(), 200);
+    assert_eq!(first.headers()["cache-control"], "private, no-cache");
+    let etag = first.headers()["etag"].to_str()?.to_string();
+
+    let repeat = client
+        .get(&url)
+        .header("If-None-Match", &etag)
+        .send()
+        .await?;
+    assert_eq!(repeat.status().as_u16(), 304);
+    assert_eq!(repeat.headers()["etag"].to_str()?, etag);
+    assert!(repeat.bytes().await?.is_empty());
+
+    // A new source can change the results, so the old tag no longer matches
+    add("handbook");
+    let changed = client
+        .get(&url)
+        .header("If-None-Match", &etag)
+        .send()
+        .await?;
+    assert_eq!(changed.status().as_u16(), 200);
+    assert_ne!(changed.headers()["etag"].to_str()?, etag);
+
+    let health = client
+        .get(format!("{}/v1/health", server.base))
+        .send()
+        .await?;
+    assert!(health.headers().get("etag").is_none());
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn serve_exits_with_signal_status_on_sigterm() -> anyhow::Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let mut server = Server::start(data_dir.path());
+
+    let status = Command::new("kill")
+        .args(["-TERM", &server.child.id().to_string()])
+        .status()?;
+    assert!(status.success());
+    assert_eq!(server.child.wait()?.code(), Some(143));
+    Ok(())
+}
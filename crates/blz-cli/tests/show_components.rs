@@ -0,0 +1,82 @@
+#![allow(missing_docs, clippy::expect_used, clippy::unwrap_used)]
+
+mod common;
+
+use common::blz_cmd;
+use serde_json::Value;
+use tempfile::TempDir;
+
+fn add_handbook() -> TempDir {
+    let data_dir = tempfile::tempdir().unwrap();
+    let file = data_dir.path().join("handbook.md");
+    std::fs::write(
+        &file,
+        "# Handbook\n\n## Deploys\n\nUse the turbine pipeline for every release.\n",
+    )
+    .unwrap();
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["add", "handbook"])
+        .arg(&file)
+        .arg("-y")
+        .assert()
+        .success();
+    data_dir
+}
+
+#[test]
+fn unknown_component_suggests_closest_name() {
+    let data_dir = add_handbook();
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["query", "turbine", "--show", "chksum"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("did you mean 'checksum'"));
+}
+
+#[test]
+fn tokens_and_checksum_appear_in_json_and_text() -> anyhow::Result<()> {
+    let data_dir = add_handbook();
+
+    let stdout = blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args([
+            "query",
+            "turbine",
+            "--show",
+            "tokens,checksum",
+            "-f",
+            "json",
+        ])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let payload: Value = serde_json::from_slice(&stdout)?;
+    let hit = &payload["results"][0];
+    assert!(hit["tokens"].as_u64().unwrap() > 0);
+    assert!(hit["checksum"].is_string());
+
+    let text = blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .env("NO_COLOR", "1")
+        .args([
+            "query",
+            "turbine",
+            "--show",
+            "tokens,checksum",
+            "-f",
+            "text",
+        ])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let text = String::from_utf8(text)?;
+    assert!(text.contains("checksum "));
+    assert!(text.contains("tokens "));
+    Ok(())
+}
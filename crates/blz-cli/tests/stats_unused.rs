@@ -0,0 +1,138 @@
+#![allow(missing_docs, clippy::expect_used, clippy::unwrap_used)]
+
+mod common;
+
+use common::blz_cmd;
+use serde_json::Value;
+use tempfile::TempDir;
+
+fn add_dir_source(data_dir: &TempDir, config_dir: &TempDir, alias: &str, body: &str) -> TempDir {
+    let docs = tempfile::tempdir().unwrap();
+    std::fs::write(docs.path().join("guide.md"), body).unwrap();
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .env("BLZ_CONFIG_DIR", config_dir.path())
+        .args(["add", alias])
+        .arg(docs.path())
+        .args(["--type", "dir", "-y"])
+        .assert()
+        .success();
+    docs
+}
+
+fn unused_aliases(data_dir: &TempDir, config_dir: &TempDir) -> Vec<String> {
+    let stdout = blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .env("BLZ_CONFIG_DIR", config_dir.path())
+        .args(["stats", "--unused", "--days", "30", "-f", "json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let report: Value = serde_json::from_slice(&stdout).unwrap();
+    assert_eq!(report["days"], 30);
+    report["unused"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|source| source["alias"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[test]
+fn stats_unused_lists_and_prunes_sources_without_hits() -> anyhow::Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let config_dir = tempfile::tempdir()?;
+    let _hooks = add_dir_source(
+        &data_dir,
+        &config_dir,
+        "hooks",
+        "# Hooks\n\nCall useEffect to synchronize.\n",
+    );
+    let _legacy = add_dir_source(
+        &data_dir,
+        &config_dir,
+        "legacy",
+        "# Legacy\n\nOld class components.\n",
+    );
+
+    assert_eq!(unused_aliases(&data_dir, &config_dir), ["hooks", "legacy"]);
+
+    // A search that returns hits from `hooks` marks it as used
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .env("BLZ_CONFIG_DIR", config_dir.path())
+        .args(["query", "useEffect", "-f", "json"])
+        .assert()
+        .success();
+    assert_eq!(unused_aliases(&data_dir, &config_dir), ["legacy"]);
+
+    // Without a terminal, pruning requires --yes
+    blz_cmd()
+        .env_remove("BLZ_FORCE_NON_INTERACTIVE")
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .env("BLZ_CONFIG_DIR", config_dir.path())
+        .args(["stats", "--unused", "--prune"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("--yes"));
+
+    let stdout = blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .env("BLZ_CONFIG_DIR", config_dir.path())
+        .args(["stats", "--unused", "--prune", "--yes", "-f", "json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let report: Value = serde_json::from_slice(&stdout)?;
+    assert_eq!(report["removed"], serde_json::json!(["legacy"]));
+    assert!(unused_aliases(&data_dir, &config_dir).is_empty());
+    Ok(())
+}
+
+#[test]
+fn access_times_track_searches_outside_the_history_log() -> anyhow::Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let config_dir = tempfile::tempdir()?;
+    let _hooks = add_dir_source(
+        &data_dir,
+        &config_dir,
+        "hooks",
+        "# Hooks\n\nCall useEffect to synchronize.\n",
+    );
+    let _legacy = add_dir_source(
+        &data_dir,
+        &config_dir,
+        "legacy",
+        "# Legacy\n\nOld class components.\n",
+    );
+
+    // Not written to history, but still counts as using the source
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .env("BLZ_CONFIG_DIR", config_dir.path())
+        .args(["query", "useEffect", "--no-history", "-f", "json"])
+        .assert()
+        .success();
+    assert_eq!(unused_aliases(&data_dir, &config_dir), ["legacy"]);
+
+    let stdout = blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .env("BLZ_CONFIG_DIR", config_dir.path())
+        .args(["list", "--sort", "lru", "--json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let list: Value = serde_json::from_slice(&stdout)?;
+    assert_eq!(list[0]["alias"], "legacy");
+    assert!(list[0].get("lastSearchedAt").is_none());
+    assert_eq!(list[1]["alias"], "hooks");
+    assert!(list[1]["lastSearchedAt"].is_string());
+    assert!(list[1]["lastFetchedAt"].is_string());
+    Ok(())
+}
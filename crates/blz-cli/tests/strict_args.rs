@@ -0,0 +1,60 @@
+#![allow(missing_docs, clippy::expect_used, clippy::unwrap_used)]
+
+mod common;
+
+use common::blz_cmd;
+use predicates::prelude::*;
+use serde_json::Value;
+use tempfile::tempdir;
+
+#[test]
+fn no_shorthand_requires_a_subcommand() -> anyhow::Result<()> {
+    let data_dir = tempdir()?;
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["--no-shorthand", "react", "hooks"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("unrecognized subcommand 'react'"));
+
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["--no-shorthand", "list", "--json"])
+        .assert()
+        .success();
+    Ok(())
+}
+
+#[test]
+fn strict_env_disables_rewriting() -> anyhow::Result<()> {
+    let data_dir = tempdir()?;
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .env("BLZ_STRICT_ARGS", "1")
+        .args(["react", "hooks"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("unrecognized subcommand 'react'"));
+
+    let output = blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .env("BLZ_STRICT_ARGS", "1")
+        .args(["debug-args", "--json", "--", "react", "hooks"])
+        .output()?;
+    assert!(output.status.success());
+    let report: Value = serde_json::from_slice(&output.stdout)?;
+    assert!(report["injectedAt"].is_null());
+    assert_eq!(
+        report["reason"],
+        "shorthand rewriting is disabled by BLZ_STRICT_ARGS"
+    );
+
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .env("BLZ_STRICT_ARGS", "off")
+        .args(["debug-args", "--json", "--", "react", "hooks"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"injectedAt\": 1"));
+    Ok(())
+}
@@ -0,0 +1,95 @@
+#![allow(missing_docs, clippy::expect_used, clippy::unwrap_used)]
+
+mod common;
+
+use common::blz_cmd;
+use tempfile::tempdir;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+async fn serve(server: &MockServer, route: &str, body: &str) {
+    Mock::given(method("HEAD"))
+        .and(path(route))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(route))
+        .respond_with(ResponseTemplate::new(200).set_body_string(body))
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn sync_all_reports_every_source_in_one_table() -> anyhow::Result<()> {
+    let data_dir = tempdir()?;
+    let server = MockServer::start().await;
+    let docs = [
+        ("alpha", "# Alpha\n\n## Install\nRun the alpha installer.\n"),
+        ("beta", "# Beta\n\n## Routing\nBeta routes requests.\n"),
+        ("gamma", "# Gamma\n\n## Caching\nGamma caches responses.\n"),
+    ];
+    for (alias, body) in docs {
+        let route = format!("/{alias}/llms.txt");
+        serve(&server, &route, body).await;
+        blz_cmd()
+            .env("BLZ_DATA_DIR", data_dir.path())
+            .args(["add", alias, &format!("{}{route}", server.uri()), "-y"])
+            .assert()
+            .success();
+    }
+
+    // Beta changes upstream and gamma starts failing
+    server.reset().await;
+    serve(&server, "/alpha/llms.txt", docs[0].1).await;
+    serve(
+        &server,
+        "/beta/llms.txt",
+        "# Beta\n\n## Routing\nBeta routes requests.\n\n## Middleware\nBeta wraps handlers.\n",
+    )
+    .await;
+    Mock::given(method("GET"))
+        .and(path("/gamma/llms.txt"))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&server)
+        .await;
+
+    let output = blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["sync", "--all", "--jobs", "3"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let stdout = String::from_utf8(output)?;
+
+    let rows: Vec<&str> = stdout
+        .lines()
+        .filter(|line| {
+            ["alpha", "beta", "gamma"]
+                .iter()
+                .any(|a| line.starts_with(a))
+        })
+        .collect();
+    assert_eq!(rows.len(), 3, "one row per source, sorted: {stdout}");
+    assert!(rows[0].starts_with("alpha") && rows[0].contains("unchanged"));
+    assert!(rows[1].starts_with("beta") && rows[1].contains("refreshed"));
+    assert!(rows[2].starts_with("gamma") && rows[2].contains("failed"));
+    assert!(
+        stdout.contains("1 synced, 1 unchanged, 1 errors"),
+        "{stdout}"
+    );
+    Ok(())
+}
+
+#[test]
+fn sync_jobs_must_be_positive() {
+    let data_dir = tempdir().unwrap();
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["sync", "--all", "--jobs", "0"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("jobs must be between 1 and 32"));
+}
@@ -0,0 +1,104 @@
+#![allow(missing_docs, clippy::expect_used, clippy::unwrap_used)]
+
+mod common;
+
+use common::blz_cmd;
+use serde_json::Value;
+use tempfile::{TempDir, tempdir};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const OLD: &str = "# Guide\n\n## Install\nRun the installer.\n\n## Legacy\nOld notes.\n";
+const NEW: &str = "# Guide\n\n## Install\nRun the installer.\n\n## Plugins\nExtend the guide.\n";
+
+async fn serve(server: &MockServer, route: &str, body: &str) {
+    Mock::given(method("HEAD"))
+        .and(path(route))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(route))
+        .respond_with(ResponseTemplate::new(200).set_body_string(body))
+        .mount(server)
+        .await;
+}
+
+fn add(data_dir: &TempDir, alias: &str, url: &str) {
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["add", alias, url, "-y"])
+        .assert()
+        .success();
+}
+
+fn change_feed(data_dir: &TempDir, args: &[&str]) -> Value {
+    let stdout = blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .arg("sync")
+        .args(args)
+        .arg("--changes-json")
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    serde_json::from_slice(&stdout).expect("stdout is a single JSON document")
+}
+
+fn headings(entry: &Value, key: &str) -> Vec<String> {
+    entry[key]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|h| {
+            h["headingPath"]
+                .as_array()
+                .unwrap()
+                .last()
+                .unwrap()
+                .as_str()
+                .unwrap()
+                .to_string()
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn changes_json_reports_changed_sources_and_headings() -> anyhow::Result<()> {
+    let data_dir = tempdir()?;
+    let server = MockServer::start().await;
+    serve(&server, "/guide.txt", OLD).await;
+    serve(&server, "/stable.txt", OLD).await;
+    add(&data_dir, "guide", &format!("{}/guide.txt", server.uri()));
+    add(&data_dir, "stable", &format!("{}/stable.txt", server.uri()));
+
+    server.reset().await;
+    serve(&server, "/guide.txt", NEW).await;
+    serve(&server, "/stable.txt", OLD).await;
+
+    let feed = change_feed(&data_dir, &["--all"]);
+    assert_eq!(feed["changed"], 1);
+    let sources = feed["sources"].as_array().unwrap();
+    assert_eq!(sources.len(), 2);
+
+    let guide = &sources[0];
+    assert_eq!(guide["alias"], "guide");
+    assert_eq!(guide["status"], "refreshed");
+    assert_eq!(guide["previousLines"], 7);
+    assert_eq!(guide["lines"], 7);
+    assert_eq!(headings(guide, "added"), vec!["Plugins"]);
+    assert_eq!(headings(guide, "removed"), vec!["Legacy"]);
+    assert!(guide["changed"].as_array().unwrap().is_empty());
+
+    let stable = &sources[1];
+    assert_eq!(stable["alias"], "stable");
+    assert_eq!(stable["status"], "unchanged");
+    assert!(stable.get("added").is_none());
+
+    // Named sources get the same feed
+    let feed = change_feed(&data_dir, &["guide"]);
+    assert_eq!(feed["sources"].as_array().unwrap().len(), 1);
+    assert_eq!(feed["sources"][0]["status"], "unchanged");
+    Ok(())
+}
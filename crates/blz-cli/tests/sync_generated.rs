@@ -0,0 +1,123 @@
+#![allow(missing_docs, clippy::expect_used, clippy::unwrap_used)]
+
+mod common;
+
+use common::blz_cmd_with_dirs;
+use serde_json::{Value, json};
+use wiremock::matchers::{body_partial_json, header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn cached_page(url: &str, title: &str, markdown: &str) -> Value {
+    json!({
+        "url": url,
+        "title": title,
+        "fetchedAt": "2024-01-01T00:00:00Z",
+        "sitemapLastmod": "2024-01-01T00:00:00Z",
+        "markdown": markdown,
+        "lineCount": markdown.lines().count(),
+    })
+}
+
+fn hit_count(stdout: &[u8]) -> usize {
+    let payload: Value = serde_json::from_slice(stdout).unwrap();
+    payload["results"].as_array().unwrap().len()
+}
+
+#[tokio::test]
+async fn sync_rescrapes_changed_pages_and_keeps_the_rest() -> anyhow::Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let config_dir = tempfile::tempdir()?;
+    let server = MockServer::start().await;
+    let uri = server.uri();
+    let blz = || blz_cmd_with_dirs(data_dir.path(), config_dir.path());
+
+    Mock::given(method("HEAD"))
+        .and(path("/seed.md"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/seed.md"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string("# Alpha\n\nAlpha uses the turbine.\n\n# Beta\n\nBeta is old.\n"),
+        )
+        .mount(&server)
+        .await;
+    blz()
+        .args(["add", "gen", &format!("{uri}/seed.md"), "-y"])
+        .assert()
+        .success();
+
+    let manifest = json!({
+        "version": 1,
+        "createdAt": "2024-01-01T00:00:00Z",
+        "lastSync": "2024-01-01T00:00:00Z",
+        "sitemapUrl": format!("{uri}/sitemap.xml"),
+        "pages": [
+            cached_page(&format!("{uri}/alpha"), "Alpha", "Alpha uses the turbine."),
+            cached_page(&format!("{uri}/beta"), "Beta", "Beta is old."),
+        ],
+        "failed": [],
+        "totalLines": 10,
+    });
+    let manifest_path = data_dir.path().join("sources/gen/generate.json");
+    std::fs::write(&manifest_path, serde_json::to_string_pretty(&manifest)?)?;
+
+    let sitemap = format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+  <url><loc>{uri}/alpha</loc><lastmod>2024-01-01T00:00:00Z</lastmod></url>
+  <url><loc>{uri}/beta</loc><lastmod>2024-02-01T00:00:00Z</lastmod></url>
+</urlset>"#
+    );
+    Mock::given(method("GET"))
+        .and(path("/sitemap.xml"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(sitemap))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/scrape"))
+        .and(header("authorization", "Bearer fc-test"))
+        .and(body_partial_json(json!({ "url": format!("{uri}/beta") })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "success": true,
+            "data": {
+                "markdown": "Beta now rides the gondola.",
+                "metadata": { "title": "Beta" }
+            }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    blz()
+        .env("FIRECRAWL_API_KEY", "fc-test")
+        .env("BLZ_FIRECRAWL_URL", &uri)
+        .args(["sync", "gen", "--quiet"])
+        .assert()
+        .success();
+
+    for (term, expected) in [("gondola", 1), ("turbine", 1), ("old", 0)] {
+        let stdout = blz()
+            .args(["query", term, "--source", "gen", "-f", "json"])
+            .assert()
+            .success()
+            .get_output()
+            .stdout
+            .clone();
+        assert_eq!(hit_count(&stdout), expected, "hits for {term}");
+    }
+
+    let saved: Value = serde_json::from_str(&std::fs::read_to_string(&manifest_path)?)?;
+    let beta = saved["pages"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|page| page["url"] == format!("{uri}/beta"))
+        .unwrap();
+    assert_eq!(beta["markdown"], "Beta now rides the gondola.");
+    assert_eq!(beta["sitemapLastmod"], "2024-02-01T00:00:00Z");
+    assert_eq!(saved["failed"], json!([]));
+    Ok(())
+}
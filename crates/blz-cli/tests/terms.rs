@@ -0,0 +1,66 @@
+#![allow(missing_docs, clippy::expect_used, clippy::unwrap_used)]
+
+mod common;
+
+use common::blz_cmd;
+use serde_json::Value;
+
+#[test]
+fn terms_lists_frequent_terms_and_narrows_by_heading() -> anyhow::Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let docs = tempfile::tempdir()?;
+    std::fs::write(
+        docs.path().join("hooks.md"),
+        "# Hooks\n\n## State\n\nuseState adds state to components. State updates re-render components.\n\n## Effects\n\nuseEffect runs effects after render.\n",
+    )?;
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["add", "react"])
+        .arg(docs.path())
+        .args(["--type", "dir", "-y"])
+        .assert()
+        .success();
+
+    let terms = |extra: &[&str]| -> Value {
+        let stdout = blz_cmd()
+            .env("BLZ_DATA_DIR", data_dir.path())
+            .args(["terms", "react", "--json"])
+            .args(extra)
+            .assert()
+            .success()
+            .get_output()
+            .stdout
+            .clone();
+        serde_json::from_slice(&stdout).unwrap()
+    };
+
+    let report = terms(&[]);
+    assert_eq!(report["source"], "react");
+    let listed = report["terms"].as_array().unwrap();
+    let state = listed.iter().find(|t| t["term"] == "state").unwrap();
+    assert!(state["termFreq"].as_u64().unwrap() >= 2);
+    assert!(listed.iter().any(|t| t["term"] == "usestate"));
+
+    let effects = terms(&["--heading", "effects"]);
+    assert_eq!(effects["heading"], "effects");
+    let listed = effects["terms"].as_array().unwrap();
+    assert!(listed.iter().any(|t| t["term"] == "useeffect"));
+    assert!(!listed.iter().any(|t| t["term"] == "usestate"));
+
+    let prefixed = terms(&["--prefix", "use", "-n", "1"]);
+    let listed = prefixed["terms"].as_array().unwrap();
+    assert_eq!(listed.len(), 1);
+    assert!(listed[0]["term"].as_str().unwrap().starts_with("use"));
+    Ok(())
+}
+
+#[test]
+fn terms_rejects_unknown_source() {
+    let data_dir = tempfile::tempdir().unwrap();
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["terms", "missing"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("Source 'missing' not found"));
+}
@@ -0,0 +1,53 @@
+#![allow(missing_docs, clippy::expect_used, clippy::unwrap_used)]
+
+mod common;
+
+use std::time::{Duration, Instant};
+
+use common::blz_cmd;
+use tempfile::tempdir;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[tokio::test]
+async fn throttle_paces_downloads() -> anyhow::Result<()> {
+    let data_dir = tempdir()?;
+    let server = MockServer::start().await;
+    let mut doc = String::from("# Guide\n\n## Install\n");
+    while doc.len() < 8 * 1024 {
+        doc.push_str("Install the runtime with the package manager.\n");
+    }
+    Mock::given(method("HEAD"))
+        .and(path("/llms.txt"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/llms.txt"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(doc))
+        .mount(&server)
+        .await;
+
+    let url = format!("{}/llms.txt", server.uri());
+    let started = Instant::now();
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["add", "fixture", &url, "-y", "--throttle", "4KB/s"])
+        .assert()
+        .success();
+
+    // One second of burst, then the remaining 4KB at 4KB/s
+    assert!(started.elapsed() >= Duration::from_millis(900));
+    Ok(())
+}
+
+#[test]
+fn throttle_rejects_invalid_rate() {
+    let data_dir = tempdir().unwrap();
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["sync", "--all", "--throttle", "fast"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("Invalid bandwidth 'fast'"));
+}
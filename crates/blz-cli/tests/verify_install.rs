@@ -0,0 +1,63 @@
+#![allow(missing_docs, clippy::expect_used, clippy::unwrap_used)]
+
+mod common;
+
+use common::blz_cmd;
+use serde_json::Value;
+
+fn run(data_dir: &tempfile::TempDir, extra: &[&str]) -> Value {
+    let stdout = blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["verify-install", "--json"])
+        .args(extra)
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    serde_json::from_slice(&stdout).unwrap()
+}
+
+#[test]
+fn verify_install_passes_every_step_without_touching_the_cache() -> anyhow::Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let report = run(&data_dir, &[]);
+
+    assert_eq!(report["passed"], true);
+    let steps: Vec<(&str, &str)> = report["steps"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|step| {
+            (
+                step["name"].as_str().unwrap(),
+                step["status"].as_str().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        steps,
+        [
+            ("create", "pass"),
+            ("index", "pass"),
+            ("search", "pass"),
+            ("get", "pass"),
+            ("cleanup", "pass"),
+        ]
+    );
+    assert!(report.get("keptDir").is_none());
+    assert!(!data_dir.path().join("sources").exists());
+    Ok(())
+}
+
+#[test]
+fn verify_install_keep_leaves_the_scratch_cache() -> anyhow::Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let report = run(&data_dir, &["--keep"]);
+
+    let kept = std::path::PathBuf::from(report["keptDir"].as_str().unwrap());
+    assert!(kept.join("data").exists());
+    assert_eq!(report["steps"].as_array().unwrap().len(), 4);
+    std::fs::remove_dir_all(kept)?;
+    Ok(())
+}
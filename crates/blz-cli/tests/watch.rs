@@ -0,0 +1,80 @@
+#![allow(missing_docs, clippy::expect_used, clippy::unwrap_used)]
+
+mod common;
+
+use common::blz_cmd;
+use serde_json::Value;
+use tempfile::TempDir;
+
+fn watch_once(data_dir: &TempDir) -> Vec<Value> {
+    let stdout = blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["watch", "--once"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    String::from_utf8(stdout)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect()
+}
+
+#[test]
+fn watch_once_reports_changed_sources_as_jsonl() -> anyhow::Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let docs = tempfile::tempdir()?;
+    std::fs::write(
+        docs.path().join("index.md"),
+        "# Project\n\nWelcome aboard.\n",
+    )?;
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["add", "projectdocs"])
+        .arg(docs.path())
+        .args(["--type", "dir", "-y"])
+        .assert()
+        .success();
+
+    let events = watch_once(&data_dir);
+    assert_eq!(events[0]["event"], "started");
+    assert_eq!(events[0]["sources"][0], "projectdocs");
+    let cycle = events.last().unwrap();
+    assert_eq!(cycle["event"], "cycle");
+    assert_eq!(cycle["checked"], 1);
+    assert_eq!(cycle["changed"], 0);
+
+    std::fs::write(
+        docs.path().join("deploy.md"),
+        "# Deploy\n\nShip with the release pipeline.\n",
+    )?;
+    let events = watch_once(&data_dir);
+    let changed: Vec<&Value> = events
+        .iter()
+        .filter(|event| event["event"] == "changed")
+        .collect();
+    assert_eq!(changed.len(), 1);
+    assert_eq!(changed[0]["alias"], "projectdocs");
+    assert!(changed[0]["headings"].as_u64().unwrap() >= 2);
+    assert_eq!(events.last().unwrap()["changed"], 1);
+    Ok(())
+}
+
+#[test]
+fn watch_rejects_unknown_sources_and_bad_intervals() {
+    let data_dir = tempfile::tempdir().unwrap();
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["watch", "missing", "--once"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("Source 'missing' not found"));
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["watch", "--interval", "5d"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("invalid interval"));
+}
@@ -0,0 +1,112 @@
+#![allow(missing_docs, clippy::expect_used, clippy::unwrap_used)]
+
+mod common;
+
+use common::blz_cmd;
+use serde_json::{Value, json};
+use tempfile::TempDir;
+
+fn add_local(data_dir: &TempDir, docs: &TempDir, alias: &str) {
+    let folder = docs.path().join(alias);
+    std::fs::create_dir_all(&folder).unwrap();
+    std::fs::write(
+        folder.join("index.md"),
+        format!("# {alias}\n\nHooks and routing.\n"),
+    )
+    .unwrap();
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["add", alias])
+        .arg(&folder)
+        .args(["--type", "dir", "-y"])
+        .assert()
+        .success();
+}
+
+fn add_metadata_alias(data_dir: &TempDir, source: &str, alias: &str) {
+    let path = data_dir
+        .path()
+        .join("sources")
+        .join(source)
+        .join("metadata.json");
+    let mut metadata: Value =
+        serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    metadata["aliases"]
+        .as_array_mut()
+        .unwrap()
+        .push(Value::String(alias.to_string()));
+    std::fs::write(&path, serde_json::to_string_pretty(&metadata).unwrap()).unwrap();
+}
+
+#[test]
+fn which_reports_canonical_source_and_trace() -> anyhow::Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let docs = tempfile::tempdir()?;
+    add_local(&data_dir, &docs, "react");
+    add_local(&data_dir, &docs, "preact");
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["alias", "add", "react", "rx"])
+        .assert()
+        .success();
+
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["which", "rx", "-f", "text"])
+        .assert()
+        .success()
+        .stdout("react\n");
+
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["which", "rx", "--verbose", "-f", "text"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(
+            "canonical name: no match among 2 sources",
+        ))
+        .stdout(predicates::str::contains("Result: react (metadata alias)"));
+
+    let stdout = blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["which", "react", "--json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let trace: Value = serde_json::from_slice(&stdout)?;
+    assert_eq!(
+        trace["candidates"],
+        json!([{"source": "react", "matchedVia": "canonical"}])
+    );
+    Ok(())
+}
+
+#[test]
+fn ambiguous_aliases_list_candidates() -> anyhow::Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let docs = tempfile::tempdir()?;
+    add_local(&data_dir, &docs, "react");
+    add_local(&data_dir, &docs, "preact");
+    add_metadata_alias(&data_dir, "react", "rx");
+    add_metadata_alias(&data_dir, "preact", "rx");
+
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["which", "rx"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "Alias 'rx' is ambiguous across multiple sources: preact, react",
+        ))
+        .stderr(predicates::str::contains("preact (metadata alias)"));
+
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["query", "hooks", "--source", "rx"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("ambiguous"));
+    Ok(())
+}
@@ -0,0 +1,128 @@
+#![allow(missing_docs, clippy::expect_used, clippy::unwrap_used)]
+
+mod common;
+
+use std::path::Path;
+
+use common::blz_cmd;
+use serde_json::Value;
+use tempfile::TempDir;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+async fn add_source(data_dir: &TempDir, server: &MockServer, alias: &str, doc: &str) {
+    let route = format!("/{alias}/llms.txt");
+    Mock::given(method("HEAD"))
+        .and(path(route.as_str()))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(route.as_str()))
+        .respond_with(ResponseTemplate::new(200).set_body_string(doc))
+        .mount(server)
+        .await;
+    blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .args(["add", alias, &format!("{}{route}", server.uri()), "-y"])
+        .assert()
+        .success();
+}
+
+fn hit_aliases(data_dir: &TempDir, cwd: &Path, extra: &[&str]) -> Vec<String> {
+    let stdout = blz_cmd()
+        .env("BLZ_DATA_DIR", data_dir.path())
+        .env_remove("BLZ_GLOBAL")
+        .current_dir(cwd)
+        .args(["query", "bundler", "--json"])
+        .args(extra)
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let payload: Value = serde_json::from_slice(&stdout).unwrap();
+    let mut aliases: Vec<String> = payload["results"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|hit| hit["alias"].as_str().unwrap().to_string())
+        .collect();
+    aliases.sort();
+    aliases.dedup();
+    aliases
+}
+
+#[tokio::test]
+async fn project_config_scopes_unqualified_searches() -> anyhow::Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let server = MockServer::start().await;
+    add_source(
+        &data_dir,
+        &server,
+        "toolkit",
+        "# Toolkit\n\n## Bundler\nThe bundler emits one file per entry.\n",
+    )
+    .await;
+    add_source(
+        &data_dir,
+        &server,
+        "testing",
+        "# Testing\n\n## Setup\nRun tests after the bundler finishes.\n",
+    )
+    .await;
+
+    let project = tempfile::tempdir()?;
+    let nested = project.path().join("src").join("app");
+    std::fs::create_dir_all(&nested)?;
+    assert_eq!(hit_aliases(&data_dir, &nested, &[]), ["testing", "toolkit"]);
+
+    std::fs::write(
+        project.path().join(".blz.toml"),
+        "sources = [\"toolkit\"]\n",
+    )?;
+    assert_eq!(hit_aliases(&data_dir, &nested, &[]), ["toolkit"]);
+    // Explicit sources and --global both bypass the workspace.
+    assert_eq!(
+        hit_aliases(&data_dir, &nested, &["-s", "testing"]),
+        ["testing"]
+    );
+    assert_eq!(
+        hit_aliases(&data_dir, &nested, &["--global"]),
+        ["testing", "toolkit"]
+    );
+    assert_eq!(
+        hit_aliases(&data_dir, &nested, &["--all-sources"]),
+        ["testing", "toolkit"]
+    );
+
+    // Project defaults apply to unset flags only.
+    std::fs::write(
+        project.path().join(".blz.toml"),
+        "format = \"json\"\n\n[search]\nlimit = 1\n",
+    )?;
+    let query = |extra: &[&str]| -> anyhow::Result<Value> {
+        let stdout = blz_cmd()
+            .env("BLZ_DATA_DIR", data_dir.path())
+            .env_remove("BLZ_GLOBAL")
+            .env_remove("BLZ_OUTPUT_FORMAT")
+            .current_dir(&nested)
+            .args(["query", "bundler"])
+            .args(extra)
+            .assert()
+            .success()
+            .get_output()
+            .stdout
+            .clone();
+        Ok(serde_json::from_slice(&stdout)?)
+    };
+    assert_eq!(query(&[])?["results"].as_array().unwrap().len(), 1);
+    assert_eq!(
+        query(&["--limit", "5"])?["results"]
+            .as_array()
+            .unwrap()
+            .len(),
+        2
+    );
+    Ok(())
+}
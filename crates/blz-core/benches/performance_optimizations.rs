@@ -349,12 +349,15 @@ fn bench_caching_strategies(c: &mut Criterion) {
                 lines: format!("{}-{}", i * 10, i * 10 + 5),
                 line_numbers: None,
                 snippet: format!("This is test content for result {}", i),
+                snippet_language: None,
+                highlights: Vec::new(),
                 score: 0.95 - (i as f32 * 0.01),
                 source_url: Some(format!("https://example.com/{}", i)),
                 fetched_at: Some(Utc::now()),
                 is_stale: false,
                 checksum: format!("checksum_{}", i),
                 anchor: Some("bench-anchor".to_string()),
+                section_id: None,
                 context: None,
             })
             .collect()
@@ -5,7 +5,8 @@
 #![allow(clippy::cast_possible_truncation)] // Allow truncation in benchmark display
 #![allow(clippy::cast_sign_loss)] // Allow sign loss in benchmark calculations
 
-use blz_core::{HeadingBlock, PerformanceMetrics, SearchIndex};
+use blz_core::fixtures::{self, FixtureSpec};
+use blz_core::{HeadingBlock, MarkdownParser, PerformanceMetrics, SearchIndex};
 use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
 use std::hint::black_box;
 use std::time::Duration;
@@ -294,6 +295,35 @@ fn bench_performance_targets(c: &mut Criterion) {
     group.finish();
 }
 
+// Corpus shared with `blz fixtures`, so results line up with wrapper projects
+fn bench_fixture_corpus(c: &mut Criterion) {
+    let mut group = c.benchmark_group("fixture_corpus");
+
+    for sections in [100, 1000] {
+        let doc = fixtures::generate(&FixtureSpec {
+            sections,
+            ..FixtureSpec::default()
+        });
+        let blocks = MarkdownParser::new()
+            .expect("Failed to create parser")
+            .parse(&doc)
+            .expect("Failed to parse fixture")
+            .heading_blocks;
+        let (_temp_dir, index) = setup_index_with_blocks(&blocks);
+
+        group.throughput(Throughput::Bytes(doc.len() as u64));
+        group.bench_with_input(BenchmarkId::new("search", sections), &sections, |b, _| {
+            b.iter(|| {
+                index
+                    .search(black_box("session timeout"), Some("bench"), 10)
+                    .expect("Search failed")
+            });
+        });
+    }
+
+    group.finish();
+}
+
 criterion_group!(
     benches,
     bench_search_scaling,
@@ -302,6 +332,7 @@ criterion_group!(
     bench_content_size_impact,
     bench_index_building,
     bench_realistic_workload,
-    bench_performance_targets
+    bench_performance_targets,
+    bench_fixture_corpus
 );
 criterion_main!(benches);
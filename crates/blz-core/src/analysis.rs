@@ -0,0 +1,384 @@
+//! Per-source text analysis: tokenizer, stemming, case, and synonyms.
+//!
+//! A source's `[index]` settings choose how its text is analyzed:
+//!
+//! ```toml
+//! [index]
+//! stemmer = "english"      # match "configuring" with "configure"
+//! lowercase = true         # the default
+//! tokenizer = "auto"       # or "default", "ngram", "jieba"
+//! synonyms = [["env var", "environment variable"], ["k8s", "kubernetes"]]
+//! ```
+//!
+//! The tokenizer, stemmer, and case handling make up the [`Analyzer`], which
+//! is recorded in the index schema and applied to queries as well as content,
+//! so changing them takes a re-index (`blz sync --reindex`). Synonyms are
+//! stored with the index too and expanded at query time: a query containing
+//! one term of a group also matches the others.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use tantivy::tokenizer::{Language, LowerCaser, Stemmer, TextAnalyzer};
+
+use crate::tokenizer::{self, TokenizerKind};
+use crate::{Error, IndexConfig, Result};
+
+/// Stemming languages by the name and ISO 639-1 code used in settings.
+const STEMMERS: &[(&str, &str, Language)] = &[
+    ("arabic", "ar", Language::Arabic),
+    ("danish", "da", Language::Danish),
+    ("dutch", "nl", Language::Dutch),
+    ("english", "en", Language::English),
+    ("finnish", "fi", Language::Finnish),
+    ("french", "fr", Language::French),
+    ("german", "de", Language::German),
+    ("greek", "el", Language::Greek),
+    ("hungarian", "hu", Language::Hungarian),
+    ("italian", "it", Language::Italian),
+    ("norwegian", "no", Language::Norwegian),
+    ("portuguese", "pt", Language::Portuguese),
+    ("romanian", "ro", Language::Romanian),
+    ("russian", "ru", Language::Russian),
+    ("spanish", "es", Language::Spanish),
+    ("swedish", "sv", Language::Swedish),
+    ("tamil", "ta", Language::Tamil),
+    ("turkish", "tr", Language::Turkish),
+];
+
+/// Separates the tokenizer from the filters in an analyzer name.
+const NAME_SEPARATOR: char = '+';
+
+/// How a source's text is analyzed, as configured in its `[index]` settings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextAnalysis {
+    /// Tokenizer to use; `None` detects one from the content.
+    pub tokenizer: Option<TokenizerKind>,
+    /// Language to stem words in, if any.
+    pub stemmer: Option<Language>,
+    /// Whether text is lowercased.
+    pub lowercase: bool,
+    /// Groups of interchangeable terms.
+    pub synonyms: Synonyms,
+}
+
+impl Default for TextAnalysis {
+    fn default() -> Self {
+        Self {
+            tokenizer: None,
+            stemmer: None,
+            lowercase: true,
+            synonyms: Synonyms::default(),
+        }
+    }
+}
+
+impl TextAnalysis {
+    /// Read the analysis settings from a source's `[index]` configuration.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Config`] for an unknown stemming language or
+    /// tokenizer.
+    pub fn from_config(config: &IndexConfig) -> Result<Self> {
+        let tokenizer = match config.tokenizer.as_deref().map(str::trim) {
+            None | Some("auto") => None,
+            Some(name) => Some(name.parse()?),
+        };
+        let stemmer = config.stemmer.as_deref().map(parse_stemmer).transpose()?;
+        Ok(Self {
+            tokenizer,
+            stemmer,
+            lowercase: config.lowercase.unwrap_or(true),
+            synonyms: Synonyms::new(config.synonyms.clone().unwrap_or_default()),
+        })
+    }
+
+    /// The analyzer for a source with the given text: the configured
+    /// tokenizer, or one detected from `texts`.
+    #[must_use]
+    pub fn analyzer_for<'a>(&self, texts: impl IntoIterator<Item = &'a str>) -> Analyzer {
+        Analyzer {
+            tokenizer: self
+                .tokenizer
+                .unwrap_or_else(|| TokenizerKind::detect(texts)),
+            stemmer: self.stemmer,
+            lowercase: self.lowercase,
+        }
+    }
+}
+
+/// Tokenizer and token filters applied to an index's text fields.
+///
+/// Tantivy records only an analyzer's name in the schema, so the name
+/// encodes the whole analyzer (`default+stem_english`) and is parsed back
+/// when the index is opened. The plain default analyzer keeps Tantivy's
+/// `default` name, so older indexes read as using it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Analyzer {
+    /// How text is split into tokens.
+    pub tokenizer: TokenizerKind,
+    /// Language tokens are stemmed in, if any.
+    pub stemmer: Option<Language>,
+    /// Whether tokens are lowercased.
+    pub lowercase: bool,
+}
+
+impl Default for Analyzer {
+    fn default() -> Self {
+        Self {
+            tokenizer: TokenizerKind::Default,
+            stemmer: None,
+            lowercase: true,
+        }
+    }
+}
+
+impl Analyzer {
+    /// Name the analyzer is registered and recorded under.
+    #[must_use]
+    pub fn name(self) -> String {
+        let mut name = self.tokenizer.name().to_string();
+        if let Some(language) = self.stemmer {
+            name.push(NAME_SEPARATOR);
+            name.push_str("stem_");
+            name.push_str(stemmer_name(language));
+        }
+        if !self.lowercase {
+            name.push(NAME_SEPARATOR);
+            name.push_str("cased");
+        }
+        name
+    }
+
+    /// Read an analyzer back from its name; `None` for names blz did not
+    /// write.
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        let mut parts = name.split(NAME_SEPARATOR);
+        let mut analyzer = Self {
+            tokenizer: TokenizerKind::from_name(parts.next()?)?,
+            ..Self::default()
+        };
+        for part in parts {
+            if part == "cased" {
+                analyzer.lowercase = false;
+            } else {
+                analyzer.stemmer = Some(parse_stemmer(part.strip_prefix("stem_")?).ok()?);
+            }
+        }
+        Some(analyzer)
+    }
+
+    /// Whether this build includes the analyzer's tokenizer.
+    #[must_use]
+    pub const fn is_available(self) -> bool {
+        self.tokenizer.is_available()
+    }
+
+    /// Register the analyzer on `index` under its [name](Self::name).
+    ///
+    /// Needed for every index opened as well as created. Does nothing when
+    /// the tokenizer is not included in this build.
+    pub(crate) fn register(self, index: &tantivy::Index) {
+        if let Some(analyzer) = self.build() {
+            index.tokenizers().register(&self.name(), analyzer);
+        }
+    }
+
+    fn build(self) -> Option<TextAnalyzer> {
+        let mut builder = tokenizer::builder(self.tokenizer)?;
+        if self.lowercase {
+            builder = builder.filter_dynamic(LowerCaser);
+        }
+        if let Some(language) = self.stemmer {
+            builder = builder.filter_dynamic(Stemmer::new(language));
+        }
+        Some(builder.build())
+    }
+}
+
+impl fmt::Display for Analyzer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} tokenizer", self.tokenizer)?;
+        if let Some(language) = self.stemmer {
+            write!(f, ", {} stemming", stemmer_name(language))?;
+        }
+        if !self.lowercase {
+            f.write_str(", case-sensitive")?;
+        }
+        Ok(())
+    }
+}
+
+/// Groups of interchangeable terms, expanded when querying.
+///
+/// Terms are compared as lowercase words, so `Env-Var` in a query matches
+/// the term `env var`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Synonyms {
+    groups: Vec<Vec<String>>,
+}
+
+impl Synonyms {
+    /// Normalize `groups`, dropping empty terms and groups with fewer than
+    /// two distinct terms.
+    #[must_use]
+    pub fn new(groups: Vec<Vec<String>>) -> Self {
+        let groups = groups
+            .into_iter()
+            .map(|group| {
+                let mut terms: Vec<String> = Vec::with_capacity(group.len());
+                for term in group {
+                    let term = words(&term).join(" ");
+                    if !term.is_empty() && !terms.contains(&term) {
+                        terms.push(term);
+                    }
+                }
+                terms
+            })
+            .filter(|terms| terms.len() > 1)
+            .collect();
+        Self { groups }
+    }
+
+    /// Whether no synonyms are configured.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.groups.is_empty()
+    }
+
+    /// The configured groups, normalized.
+    #[must_use]
+    pub fn groups(&self) -> &[Vec<String>] {
+        &self.groups
+    }
+
+    /// Terms to search for alongside `query`: the other members of every
+    /// group with a term that appears in the query as whole words.
+    #[must_use]
+    pub fn expansions(&self, query: &str) -> Vec<String> {
+        let query_words = words(query);
+        let mut expansions: Vec<String> = Vec::new();
+        for group in &self.groups {
+            let present: Vec<&String> = group
+                .iter()
+                .filter(|term| contains_words(&query_words, &words(term)))
+                .collect();
+            if present.is_empty() {
+                continue;
+            }
+            for term in group {
+                if !present.contains(&term) && !expansions.contains(term) {
+                    expansions.push(term.clone());
+                }
+            }
+        }
+        expansions
+    }
+}
+
+/// Parse a stemming language name (`english`) or code (`en`).
+fn parse_stemmer(name: &str) -> Result<Language> {
+    let name = name.trim().to_ascii_lowercase();
+    STEMMERS
+        .iter()
+        .find(|(known, code, _)| *known == name || *code == name)
+        .map(|&(_, _, language)| language)
+        .ok_or_else(|| {
+            let known: Vec<&str> = STEMMERS.iter().map(|(known, _, _)| *known).collect();
+            Error::Config(format!(
+                "Unknown stemming language '{name}' (known: {})",
+                known.join(", ")
+            ))
+        })
+}
+
+fn stemmer_name(language: Language) -> &'static str {
+    STEMMERS
+        .iter()
+        .find(|(_, _, known)| *known == language)
+        .map_or("unknown", |(name, _, _)| name)
+}
+
+fn words(text: &str) -> Vec<String> {
+    text.split(|ch: char| !ch.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+fn contains_words(haystack: &[String], needle: &[String]) -> bool {
+    !needle.is_empty()
+        && haystack
+            .windows(needle.len())
+            .any(|window| window == needle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn analyzer_names_round_trip() {
+        let analyzers = [
+            Analyzer::default(),
+            Analyzer {
+                tokenizer: TokenizerKind::Ngram,
+                stemmer: Some(Language::English),
+                lowercase: true,
+            },
+            Analyzer {
+                tokenizer: TokenizerKind::Default,
+                stemmer: Some(Language::German),
+                lowercase: false,
+            },
+        ];
+        for analyzer in analyzers {
+            assert_eq!(Analyzer::from_name(&analyzer.name()), Some(analyzer));
+        }
+        assert_eq!(Analyzer::default().name(), "default");
+        assert_eq!(Analyzer::from_name("en_stem"), None);
+    }
+
+    #[test]
+    fn config_is_validated() {
+        let config = IndexConfig {
+            stemmer: Some("en".to_string()),
+            tokenizer: Some("ngram".to_string()),
+            lowercase: Some(false),
+            ..IndexConfig::default()
+        };
+        let analysis = TextAnalysis::from_config(&config).expect("valid settings");
+        assert_eq!(analysis.stemmer, Some(Language::English));
+        assert_eq!(analysis.tokenizer, Some(TokenizerKind::Ngram));
+        assert!(!analysis.lowercase);
+
+        let bad = IndexConfig {
+            stemmer: Some("klingon".to_string()),
+            ..IndexConfig::default()
+        };
+        assert!(matches!(
+            TextAnalysis::from_config(&bad),
+            Err(Error::Config(_))
+        ));
+    }
+
+    #[test]
+    fn synonyms_expand_whole_word_matches() {
+        let synonyms = Synonyms::new(vec![
+            vec!["env var".to_string(), "Environment Variable".to_string()],
+            vec!["k8s".to_string(), "kubernetes".to_string()],
+            vec!["lonely".to_string()],
+        ]);
+        assert_eq!(synonyms.groups().len(), 2);
+        assert_eq!(
+            synonyms.expansions("set an Env-Var"),
+            ["environment variable"]
+        );
+        assert_eq!(synonyms.expansions("environment variable"), ["env var"]);
+        assert_eq!(synonyms.expansions("deploy k8s"), ["kubernetes"]);
+        assert!(synonyms.expansions("environment").is_empty());
+        assert!(synonyms.expansions("k8sx").is_empty());
+    }
+}
@@ -0,0 +1,353 @@
+//! Portable cache bundles for offline transfer.
+//!
+//! A bundle is a zstd-compressed tar archive (`.tar.zst`) holding:
+//!
+//! - `bundle.json`, a [`BundleManifest`] listing the exported sources
+//! - `sources/<alias>/`, each source's cache directory (`llms.txt`,
+//!   `llms.json`, `metadata.json`, and the rest)
+//! - `descriptors/<alias>.toml`, each source's descriptor
+//!
+//! Search indices are large and can be rebuilt from the cached content, so
+//! they are only bundled on request; [`import_bundle`] reports which imported
+//! sources still need indexing. Per-machine state (version archives, access
+//! stats) and fetch credentials in `settings.toml` are never bundled.
+
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::path::{Component, Path};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{Error, Result, Storage, ToolConfig};
+
+/// Bundle format version written by this build.
+pub const BUNDLE_VERSION: u32 = 1;
+
+const MANIFEST_ENTRY: &str = "bundle.json";
+const SOURCES_DIR: &str = "sources";
+const DESCRIPTORS_DIR: &str = "descriptors";
+const INDEX_DIR: &str = ".index";
+const SETTINGS_FILE: &str = "settings.toml";
+/// Per-source entries that stay on the exporting machine.
+const LOCAL_ONLY: &[&str] = &[".archive", "access.json"];
+/// zstd level used when none is given; favors speed over ratio.
+const DEFAULT_LEVEL: i32 = 3;
+
+/// Contents of `bundle.json`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleManifest {
+    /// Bundle format version.
+    pub version: u32,
+    /// When the bundle was written.
+    pub created_at: DateTime<Utc>,
+    /// Version of blz that wrote the bundle.
+    pub blz_version: String,
+    /// Exported sources.
+    pub sources: Vec<BundledSource>,
+}
+
+/// One source in a bundle.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundledSource {
+    /// Canonical alias.
+    pub alias: String,
+    /// URL the cached content was fetched from.
+    pub url: String,
+    /// SHA-256 of the cached content.
+    pub sha256: String,
+    /// Whether the search index is included.
+    pub has_index: bool,
+}
+
+/// Options for [`export_bundle`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ExportOptions {
+    /// Include search indices so the importing machine can skip reindexing.
+    pub include_index: bool,
+    /// zstd compression level (1-22); defaults to 3.
+    pub level: Option<i32>,
+}
+
+/// Options for [`import_bundle`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ImportOptions {
+    /// Replace sources that already exist instead of skipping them.
+    pub overwrite: bool,
+}
+
+/// What [`import_bundle`] did.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportReport {
+    /// Sources written to the cache.
+    pub imported: Vec<ImportedSource>,
+    /// Sources left alone because they already exist.
+    pub skipped: Vec<String>,
+}
+
+/// A source written by [`import_bundle`].
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportedSource {
+    /// Canonical alias.
+    pub alias: String,
+    /// The bundle had no index for this source; it must be rebuilt.
+    pub needs_index: bool,
+}
+
+/// Write `aliases` from `storage` to a bundle at `out`.
+///
+/// The bundle is written to a temporary file next to `out` and renamed into
+/// place, so an interrupted export never leaves a truncated bundle behind.
+///
+/// # Errors
+///
+/// Returns an error if a source is not cached or the bundle cannot be written.
+pub fn export_bundle(
+    storage: &Storage,
+    aliases: &[String],
+    out: &Path,
+    options: ExportOptions,
+) -> Result<BundleManifest> {
+    let mut sources = Vec::with_capacity(aliases.len());
+    for alias in aliases {
+        let metadata = storage
+            .load_source_metadata(alias)?
+            .ok_or_else(|| Error::NotFound(format!("Source '{alias}' is not cached")))?;
+        sources.push(BundledSource {
+            alias: alias.clone(),
+            url: metadata.url,
+            sha256: metadata.sha256,
+            has_index: options.include_index && storage.index_dir(alias)?.is_dir(),
+        });
+    }
+    let manifest = BundleManifest {
+        version: BUNDLE_VERSION,
+        created_at: Utc::now(),
+        blz_version: env!("CARGO_PKG_VERSION").to_string(),
+        sources,
+    };
+
+    let parent = out
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or_else(|| Path::new("."));
+    fs::create_dir_all(parent)?;
+    let temp = tempfile::NamedTempFile::new_in(parent)?;
+    let encoder = zstd::Encoder::new(temp.as_file(), options.level.unwrap_or(DEFAULT_LEVEL))?;
+    let mut builder = tar::Builder::new(encoder);
+
+    append_bytes(
+        &mut builder,
+        Path::new(MANIFEST_ENTRY),
+        &serde_json::to_vec_pretty(&manifest)?,
+    )?;
+    for source in &manifest.sources {
+        let prefix = Path::new(SOURCES_DIR).join(&source.alias);
+        append_source_dir(
+            &mut builder,
+            &storage.source_dir(&source.alias)?,
+            &prefix,
+            source.has_index,
+        )?;
+        let descriptor = storage.descriptor_path(&source.alias)?;
+        if descriptor.is_file() {
+            let name = Path::new(DESCRIPTORS_DIR).join(format!("{}.toml", source.alias));
+            builder.append_path_with_name(&descriptor, name)?;
+        }
+    }
+    builder.into_inner()?.finish()?;
+    temp.persist(out).map_err(|e| Error::Io(e.error))?;
+    Ok(manifest)
+}
+
+/// Read a bundle's manifest without extracting it.
+///
+/// # Errors
+///
+/// Returns an error if the file is not a readable bundle.
+pub fn read_manifest(bundle: &Path) -> Result<BundleManifest> {
+    let mut archive = tar::Archive::new(zstd::Decoder::new(File::open(bundle)?)?);
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        if entry.path()?.as_ref() == Path::new(MANIFEST_ENTRY) {
+            return parse_manifest(&mut entry);
+        }
+    }
+    Err(missing_manifest())
+}
+
+/// Unpack a bundle into `storage`.
+///
+/// Entries are staged in a temporary directory under the cache root and each
+/// source is moved into place only once the whole bundle has been read.
+///
+/// # Errors
+///
+/// Returns an error if the bundle is unreadable, from a newer blz, contains
+/// paths outside its layout, or a source cannot be moved into the cache.
+pub fn import_bundle(
+    storage: &Storage,
+    bundle: &Path,
+    options: ImportOptions,
+) -> Result<ImportReport> {
+    let staging = tempfile::Builder::new()
+        .prefix(".import-")
+        .tempdir_in(storage.root_dir())?;
+    let mut archive = tar::Archive::new(zstd::Decoder::new(File::open(bundle)?)?);
+    let mut manifest = None;
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let path = entry.path()?.into_owned();
+        if !path
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+        {
+            return Err(Error::Storage(format!(
+                "Bundle entry '{}' is outside the bundle layout",
+                path.display()
+            )));
+        }
+        if path == Path::new(MANIFEST_ENTRY) {
+            manifest = Some(parse_manifest(&mut entry)?);
+            continue;
+        }
+        // Links and special files are never written by export
+        if matches!(
+            entry.header().entry_type(),
+            tar::EntryType::Regular | tar::EntryType::Directory
+        ) {
+            entry.unpack_in(staging.path())?;
+        }
+    }
+    let manifest = manifest.ok_or_else(missing_manifest)?;
+
+    let mut report = ImportReport::default();
+    for source in &manifest.sources {
+        let alias = &source.alias;
+        let target = storage.tool_dir(alias)?;
+        let staged = staging.path().join(SOURCES_DIR).join(alias);
+        if !staged.join("llms.json").is_file() {
+            return Err(Error::Storage(format!(
+                "Bundle lists '{alias}' but does not contain its cache"
+            )));
+        }
+        if target.exists() {
+            if !options.overwrite {
+                report.skipped.push(alias.clone());
+                continue;
+            }
+            fs::remove_dir_all(&target)?;
+        }
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::rename(&staged, &target)?;
+
+        let descriptor = staging
+            .path()
+            .join(DESCRIPTORS_DIR)
+            .join(format!("{alias}.toml"));
+        if descriptor.is_file() {
+            let dest = storage.descriptor_path(alias)?;
+            if let Some(parent) = dest.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::copy(&descriptor, &dest)?;
+        }
+        report.imported.push(ImportedSource {
+            alias: alias.clone(),
+            needs_index: !target.join(INDEX_DIR).is_dir(),
+        });
+    }
+    storage.invalidate_query_cache();
+    Ok(report)
+}
+
+fn parse_manifest(entry: &mut impl Read) -> Result<BundleManifest> {
+    let mut raw = String::new();
+    entry.read_to_string(&mut raw)?;
+    let manifest: BundleManifest = serde_json::from_str(&raw)
+        .map_err(|e| Error::Parse(format!("Invalid bundle manifest: {e}")))?;
+    if manifest.version > BUNDLE_VERSION {
+        return Err(Error::Parse(format!(
+            "Bundle format version {} is newer than this blz supports ({BUNDLE_VERSION})",
+            manifest.version
+        )));
+    }
+    Ok(manifest)
+}
+
+fn missing_manifest() -> Error {
+    Error::Parse(format!("Not a blz bundle: {MANIFEST_ENTRY} is missing"))
+}
+
+fn append_source_dir<W: Write>(
+    builder: &mut tar::Builder<W>,
+    dir: &Path,
+    prefix: &Path,
+    include_index: bool,
+) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let name = entry.file_name();
+        let name_str = name.to_string_lossy();
+        if LOCAL_ONLY.contains(&name_str.as_ref()) || (name_str == INDEX_DIR && !include_index) {
+            continue;
+        }
+        let path = entry.path();
+        let target = prefix.join(&name);
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            builder.append_dir_all(&target, &path)?;
+        } else if name_str == SETTINGS_FILE {
+            append_settings(builder, &path, &target)?;
+        } else if file_type.is_file() {
+            builder.append_path_with_name(&path, &target)?;
+        }
+    }
+    Ok(())
+}
+
+/// Bundle `settings.toml` with fetch credentials removed.
+fn append_settings<W: Write>(
+    builder: &mut tar::Builder<W>,
+    path: &Path,
+    target: &Path,
+) -> Result<()> {
+    let mut config = ToolConfig::load(path)?;
+    config.fetch.auth = None;
+    append_bytes(builder, target, toml::to_string(&config)?.as_bytes())
+}
+
+fn append_bytes<W: Write>(builder: &mut tar::Builder<W>, path: &Path, data: &[u8]) -> Result<()> {
+    let mut header = tar::Header::new_gnu();
+    header.set_size(data.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(u64::try_from(Utc::now().timestamp()).unwrap_or_default());
+    header.set_cksum();
+    builder.append_data(&mut header, path, data)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_bundles_without_a_manifest() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("empty.tar.zst");
+        let encoder = zstd::Encoder::new(File::create(&path)?, DEFAULT_LEVEL)?;
+        let mut builder = tar::Builder::new(encoder);
+        append_bytes(&mut builder, Path::new("notes.txt"), b"hello")?;
+        builder.into_inner()?.finish()?;
+
+        let err = read_manifest(&path).expect_err("no bundle.json");
+        assert!(err.to_string().contains("Not a blz bundle"));
+        Ok(())
+    }
+}
@@ -1098,12 +1098,15 @@ mod tests {
             lines: "1-10".to_string(),
             line_numbers: Some(vec![1, 10]),
             snippet: "test snippet".to_string(),
+            snippet_language: None,
+            highlights: Vec::new(),
             score: 0.95,
             source_url: Some("https://test.com".to_string()),
             fetched_at: Some(Utc::now()),
             is_stale: false,
             checksum: "abc123".to_string(),
             anchor: None,
+            section_id: None,
             context: None,
         }];
 
@@ -1164,12 +1167,15 @@ mod tests {
             lines: "1-10".to_string(),
             line_numbers: Some(vec![1, 10]),
             snippet: "test snippet".to_string(),
+            snippet_language: None,
+            highlights: Vec::new(),
             score: 0.95,
             source_url: Some("https://test.com".to_string()),
             fetched_at: Some(Utc::now()),
             is_stale: false,
             checksum: "abc123".to_string(),
             anchor: None,
+            section_id: None,
             context: None,
         }];
 
@@ -0,0 +1,346 @@
+//! Language detection for code-heavy search snippets.
+//!
+//! Search hits carry a short snippet of the matching section. When most of
+//! that snippet sits inside a fenced code block, the hit also reports the
+//! block's language so consumers can highlight or filter results without
+//! re-parsing markdown. The fence info string wins; unlabelled fences fall
+//! back to a few keyword heuristics, and anything ambiguous is left unset.
+//!
+//! [`code_blocks`] tags every fenced block of a section the same way, so the
+//! index can restrict a search to code examples in one language.
+
+use std::ops::Range;
+
+/// Language of a snippet that is predominantly fenced code.
+///
+/// `content` is the indexed section text the snippet was cut from; the
+/// snippet may carry `...` truncation markers. Returns `None` when less than
+/// half of the snippet's visible characters are code, or when an unlabelled
+/// block does not look like any known language.
+#[must_use]
+pub fn snippet_language(content: &str, snippet: &str) -> Option<String> {
+    let core = snippet.strip_prefix("...").unwrap_or(snippet);
+    let core = core.strip_suffix("...").unwrap_or(core);
+    if core.trim().is_empty() {
+        return None;
+    }
+    let start = content.find(core)?;
+    let end = start + core.len();
+
+    let mut fence: Option<Fence> = None;
+    let mut blocks: Vec<Fence> = Vec::new();
+    let mut code_chars = 0usize;
+    let mut total_chars = 0usize;
+    let mut offset = 0usize;
+
+    for line in content.split_inclusive('\n') {
+        let visible = visible_overlap(line, offset, start..end);
+        offset += line.len();
+        total_chars += visible;
+        let trimmed = line.trim_start();
+
+        let Some(current) = fence.as_mut() else {
+            if let Some(marker) = fence_marker(trimmed) {
+                code_chars += visible;
+                fence = Some(Fence {
+                    info: trimmed[marker.len()..].trim().to_string(),
+                    marker,
+                    code: String::new(),
+                    overlap: visible,
+                });
+            }
+            continue;
+        };
+        code_chars += visible;
+        current.overlap += visible;
+        if closes_fence(trimmed, &current.marker) {
+            blocks.extend(fence.take());
+        } else {
+            current.code.push_str(line);
+        }
+    }
+    blocks.extend(fence);
+
+    if code_chars * 2 <= total_chars {
+        return None;
+    }
+    let block = blocks.into_iter().max_by_key(|block| block.overlap)?;
+    info_language(&block.info).or_else(|| guess_language(&block.code).map(str::to_string))
+}
+
+/// Text inside the fenced code blocks of `content`, one block after another.
+///
+/// Fence lines themselves are left out.
+#[must_use]
+pub fn fenced_code(content: &str) -> String {
+    let mut code = String::new();
+    let mut fence: Option<String> = None;
+    for line in content.lines() {
+        let trimmed = line.trim_start();
+        match &fence {
+            None => fence = fence_marker(trimmed),
+            Some(marker) if closes_fence(trimmed, marker) => fence = None,
+            Some(_) => {
+                code.push_str(line);
+                code.push('\n');
+            },
+        }
+    }
+    code
+}
+
+/// A fenced code block, tagged with the language it is written in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeBlock {
+    /// Canonical language name from the fence info string, or guessed from
+    /// the code when the fence is unlabelled; `None` when unknown.
+    pub language: Option<String>,
+    /// The code itself; fence lines are left out.
+    pub code: String,
+}
+
+/// The fenced code blocks of `content`, in order.
+///
+/// An unclosed fence runs to the end of `content`, as in [`fenced_code`].
+#[must_use]
+pub fn code_blocks(content: &str) -> Vec<CodeBlock> {
+    let mut blocks = Vec::new();
+    let mut fence: Option<Fence> = None;
+    for line in content.lines() {
+        let trimmed = line.trim_start();
+        match fence.as_mut() {
+            None => {
+                fence = fence_marker(trimmed).map(|marker| Fence {
+                    info: trimmed[marker.len()..].trim().to_string(),
+                    marker,
+                    code: String::new(),
+                    overlap: 0,
+                });
+            },
+            Some(current) if closes_fence(trimmed, &current.marker) => {
+                blocks.extend(fence.take().map(Fence::into_code_block));
+            },
+            Some(current) => {
+                current.code.push_str(line);
+                current.code.push('\n');
+            },
+        }
+    }
+    blocks.extend(fence.map(Fence::into_code_block));
+    blocks
+}
+
+/// Canonical name for a language as written after a fence or typed by a
+/// user, with common aliases expanded (`ts` becomes `typescript`).
+#[must_use]
+pub fn canonical_language(name: &str) -> Option<String> {
+    info_language(name.trim())
+}
+
+struct Fence {
+    marker: String,
+    info: String,
+    code: String,
+    overlap: usize,
+}
+
+impl Fence {
+    fn into_code_block(self) -> CodeBlock {
+        let language =
+            info_language(&self.info).or_else(|| guess_language(&self.code).map(str::to_string));
+        CodeBlock {
+            language,
+            code: self.code,
+        }
+    }
+}
+
+/// Non-whitespace characters of `line` (starting at byte `offset`) inside `range`.
+fn visible_overlap(line: &str, offset: usize, range: Range<usize>) -> usize {
+    let from = range.start.max(offset);
+    let to = range.end.min(offset + line.len());
+    if from >= to {
+        return 0;
+    }
+    line[from - offset..to - offset]
+        .chars()
+        .filter(|ch| !ch.is_whitespace())
+        .count()
+}
+
+fn fence_marker(line: &str) -> Option<String> {
+    let ch = line.chars().next().filter(|ch| *ch == '`' || *ch == '~')?;
+    let len = line.chars().take_while(|c| *c == ch).count();
+    (len >= 3).then(|| ch.to_string().repeat(len))
+}
+
+fn closes_fence(line: &str, marker: &str) -> bool {
+    let ch = marker.chars().next().unwrap_or('`');
+    line.starts_with(marker) && line.trim_end().chars().all(|c| c == ch)
+}
+
+/// Language named by a fence info string, with common aliases expanded.
+fn info_language(info: &str) -> Option<String> {
+    let name = info
+        .split(|ch: char| ch.is_whitespace() || ch == ',' || ch == '{')
+        .next()?
+        .trim_start_matches('.')
+        .to_ascii_lowercase();
+    if name.is_empty() {
+        return None;
+    }
+    let canonical = match name.as_str() {
+        "js" | "jsx" | "mjs" | "cjs" => "javascript",
+        "ts" | "tsx" => "typescript",
+        "py" | "python3" => "python",
+        "rs" => "rust",
+        "sh" | "shell" | "zsh" | "console" | "shellsession" => "bash",
+        "yml" => "yaml",
+        "golang" => "go",
+        other => other,
+    };
+    Some(canonical.to_string())
+}
+
+/// Guess the language of unlabelled code from characteristic keywords.
+#[must_use]
+pub fn guess_language(code: &str) -> Option<&'static str> {
+    let trimmed = code.trim_start();
+    let lines: Vec<&str> = code.lines().map(str::trim_start).collect();
+    let any_line = |pred: &dyn Fn(&str) -> bool| lines.iter().any(|line| pred(line));
+
+    if (trimmed.starts_with('{') || trimmed.starts_with('[')) && code.contains("\":") {
+        return Some("json");
+    }
+    if any_line(&|line| {
+        line.starts_with("$ ")
+            || [
+                "npm ", "npx ", "pnpm ", "yarn ", "bun ", "cargo ", "pip ", "curl ", "git ",
+                "brew ", "docker ",
+            ]
+            .iter()
+            .any(|command| line.starts_with(command))
+    }) {
+        return Some("bash");
+    }
+    if any_line(&|line| {
+        line.starts_with("fn ")
+            || line.starts_with("pub fn ")
+            || line.starts_with("impl ")
+            || (line.starts_with("use ") && line.contains("::"))
+            || line.starts_with("let mut ")
+    }) {
+        return Some("rust");
+    }
+    if any_line(&|line| line.starts_with("package main") || line.starts_with("func ")) {
+        return Some("go");
+    }
+    if any_line(&|line| {
+        (line.starts_with("def ") && line.trim_end().ends_with(':'))
+            || (line.starts_with("from ") && line.contains(" import "))
+            || line.starts_with("print(")
+    }) {
+        return Some("python");
+    }
+    if any_line(&|line| {
+        line.starts_with("interface ")
+            || (line.starts_with("type ") && line.contains(" = "))
+            || line.starts_with("import type ")
+            || line.contains(": string")
+            || line.contains(": number")
+    }) {
+        return Some("typescript");
+    }
+    if any_line(&|line| {
+        line.starts_with("const ")
+            || line.starts_with("function ")
+            || line.starts_with("export ")
+            || (line.starts_with("import ") && line.contains(" from "))
+            || line.contains("=> {")
+            || line.contains("require(")
+    }) {
+        return Some("javascript");
+    }
+    if any_line(&|line| {
+        ["SELECT ", "INSERT INTO ", "CREATE TABLE ", "UPDATE "]
+            .iter()
+            .any(|keyword| line.starts_with(keyword))
+    }) {
+        return Some("sql");
+    }
+    if trimmed.starts_with('<') && code.contains("</") {
+        return Some("html");
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECTION: &str = "Install the hook first.\n\n```tsx title=\"App.tsx\"\nconst [count, setCount] = useState(0);\nsetCount(count + 1);\n```\n\nThen render it.\n";
+
+    #[test]
+    fn reports_fence_language_for_code_snippets() {
+        assert_eq!(
+            snippet_language(
+                SECTION,
+                "...const [count, setCount] = useState(0);\nsetCount(..."
+            ),
+            Some("typescript".to_string())
+        );
+        assert_eq!(snippet_language(SECTION, "Install the hook first."), None);
+        assert_eq!(snippet_language(SECTION, "not in the section"), None);
+    }
+
+    #[test]
+    fn guesses_unlabelled_fences() {
+        let section = "Run:\n\n```\nfn main() {\n    let mut total = 0;\n}\n```\n";
+        assert_eq!(
+            snippet_language(section, "fn main() {\n    let mut total = 0;"),
+            Some("rust".to_string())
+        );
+        let prose = "```\nsome words that are not code\n```\n";
+        assert_eq!(
+            snippet_language(prose, "some words that are not code"),
+            None
+        );
+    }
+
+    #[test]
+    fn collects_fenced_code() {
+        assert_eq!(
+            fenced_code(SECTION),
+            "const [count, setCount] = useState(0);\nsetCount(count + 1);\n"
+        );
+        assert_eq!(fenced_code("no code here"), "");
+    }
+
+    #[test]
+    fn tags_code_blocks_with_their_language() {
+        let section =
+            "Setup:\n\n```sh\nnpm install\n```\n\n```\nfn main() {}\n```\n\n```text\nplain\n";
+        let blocks = code_blocks(section);
+        let languages: Vec<Option<&str>> = blocks
+            .iter()
+            .map(|block| block.language.as_deref())
+            .collect();
+        assert_eq!(languages, [Some("bash"), Some("rust"), Some("text")]);
+        assert_eq!(blocks[0].code, "npm install\n");
+        assert_eq!(blocks[2].code, "plain\n");
+        assert_eq!(canonical_language(" TS "), Some("typescript".to_string()));
+        assert_eq!(canonical_language(""), None);
+    }
+
+    #[test]
+    fn guesses_common_languages() {
+        assert_eq!(guess_language("$ blz add react"), Some("bash"));
+        assert_eq!(guess_language("{\n  \"name\": \"blz\"\n}"), Some("json"));
+        assert_eq!(
+            guess_language("def greet(name):\n    print(name)"),
+            Some("python")
+        );
+        assert_eq!(guess_language("SELECT * FROM users;"), Some("sql"));
+        assert_eq!(guess_language("just prose"), None);
+    }
+}
@@ -0,0 +1,350 @@
+//! Collection sources: several llms.txt files merged under one alias.
+//!
+//! Platforms often publish separate files (for example API reference and
+//! guides). A collection keeps one cached copy and one set of HTTP validators
+//! per member so sync only refetches files that changed, then merges them into
+//! a single document. Each member is nested under a top-level heading named
+//! after its prefix, so every heading path starts with that prefix
+//! (e.g. `api > Authentication`).
+
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::fetcher::calculate_sha256;
+use crate::refresh::{
+    ApplyRefreshParams, RefreshContext, RefreshIndexer, RefreshOutcome, RefreshPayload,
+    RefreshStorage, apply_refresh,
+};
+use crate::{Error, FetchResult, Fetcher, PerformanceMetrics, Result, SourceType};
+
+/// One llms.txt file that is part of a collection source.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CollectionMember {
+    /// Section prefix used as the top-level heading for this member.
+    pub prefix: String,
+    /// URL the member is fetched from.
+    pub url: String,
+    /// `ETag` from the last successful fetch of this member.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub etag: Option<String>,
+    /// `Last-Modified` from the last successful fetch of this member.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_modified: Option<String>,
+    /// SHA-256 (base64) of the member content.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub sha256: String,
+}
+
+impl CollectionMember {
+    /// Create a member that has not been fetched yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `prefix` is not a valid member prefix.
+    pub fn new(prefix: impl Into<String>, url: impl Into<String>) -> Result<Self> {
+        let prefix = prefix.into();
+        validate_prefix(&prefix)?;
+        Ok(Self {
+            prefix,
+            url: url.into(),
+            etag: None,
+            last_modified: None,
+            sha256: String::new(),
+        })
+    }
+}
+
+impl FromStr for CollectionMember {
+    type Err = Error;
+
+    /// Parse a `PREFIX=URL` member specification.
+    fn from_str(spec: &str) -> Result<Self> {
+        let (prefix, url) = spec.split_once('=').ok_or_else(|| {
+            Error::Config(format!(
+                "invalid collection member '{spec}' (expected PREFIX=URL)"
+            ))
+        })?;
+        let url = url.trim();
+        if url.is_empty() {
+            return Err(Error::Config(format!(
+                "collection member '{spec}' is missing a URL"
+            )));
+        }
+        Self::new(prefix.trim(), url)
+    }
+}
+
+/// Check that a member prefix is usable as a heading and a file name.
+///
+/// # Errors
+///
+/// Returns an error if the prefix is empty or contains characters other than
+/// ASCII letters, digits, `-`, and `_`.
+pub fn validate_prefix(prefix: &str) -> Result<()> {
+    let valid = !prefix.is_empty()
+        && prefix
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(Error::Config(format!(
+            "invalid collection prefix '{prefix}' (use letters, digits, '-' or '_')"
+        )))
+    }
+}
+
+/// Check that a member list is non-empty and has unique prefixes.
+///
+/// # Errors
+///
+/// Returns an error if there are no members or a prefix is repeated.
+pub fn validate_members(members: &[CollectionMember]) -> Result<()> {
+    if members.is_empty() {
+        return Err(Error::Config(
+            "a collection needs at least one member".to_string(),
+        ));
+    }
+    for (idx, member) in members.iter().enumerate() {
+        if members[..idx]
+            .iter()
+            .any(|other| other.prefix.eq_ignore_ascii_case(&member.prefix))
+        {
+            return Err(Error::Config(format!(
+                "duplicate collection prefix '{}'",
+                member.prefix
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Merge member documents into one, in order.
+///
+/// Each member is placed under `# <prefix>` with its own headings demoted by
+/// one level (headings inside fenced code blocks are left alone). Level-six
+/// headings cannot be demoted further and stay at level six.
+#[must_use]
+pub fn merge_members<'a, I>(parts: I) -> String
+where
+    I: IntoIterator<Item = (&'a str, &'a str)>,
+{
+    let mut merged = String::new();
+    for (prefix, content) in parts {
+        if !merged.is_empty() {
+            merged.push('\n');
+        }
+        merged.push_str("# ");
+        merged.push_str(prefix);
+        merged.push_str("\n\n");
+        merged.push_str(&demote_headings(content));
+        if !merged.ends_with('\n') {
+            merged.push('\n');
+        }
+    }
+    merged
+}
+
+fn demote_headings(content: &str) -> String {
+    let mut fence: Option<(char, usize)> = None;
+    let mut out = String::with_capacity(content.len() + 64);
+
+    for line in content.split_inclusive('\n') {
+        let trimmed = line.trim_start_matches(' ');
+        let indent = line.len() - trimmed.len();
+
+        if indent <= 3 {
+            if let Some(marker) = fence_marker(trimmed) {
+                fence = match fence {
+                    None => Some(marker),
+                    Some((ch, len)) if marker.0 == ch && marker.1 >= len => None,
+                    open => open,
+                };
+                out.push_str(line);
+                continue;
+            }
+            if fence.is_none() && heading_level(trimmed).is_some_and(|level| level < 6) {
+                out.push_str(&line[..indent]);
+                out.push('#');
+                out.push_str(trimmed);
+                continue;
+            }
+        }
+        out.push_str(line);
+    }
+
+    out
+}
+
+fn fence_marker(line: &str) -> Option<(char, usize)> {
+    let ch = line.chars().next().filter(|ch| *ch == '`' || *ch == '~')?;
+    let len = line.chars().take_while(|c| *c == ch).count();
+    (len >= 3).then_some((ch, len))
+}
+
+fn heading_level(line: &str) -> Option<usize> {
+    let level = line.chars().take_while(|ch| *ch == '#').count();
+    let rest = &line[level..];
+    let terminated = rest.is_empty() || rest.starts_with([' ', '\t', '\n', '\r']);
+    ((1..=6).contains(&level) && terminated).then_some(level)
+}
+
+/// Refresh a collection source, refetching only members that changed.
+///
+/// Each member is fetched with its own `ETag`/`Last-Modified` validators.
+/// When no member content changed the cache is left as-is; otherwise the
+/// members are re-merged and the source is re-indexed.
+///
+/// # Errors
+///
+/// Returns an error if the source is not a collection, or if fetching,
+/// persistence, or indexing fails.
+pub async fn refresh_collection<S, I>(
+    storage: &S,
+    fetcher: &Fetcher,
+    alias: &str,
+    ctx: &RefreshContext,
+    metrics: PerformanceMetrics,
+    indexer: &I,
+    filter_preference: bool,
+) -> Result<RefreshOutcome>
+where
+    S: RefreshStorage + Sync,
+    I: RefreshIndexer + Sync,
+{
+    let Some(SourceType::Collection { members }) = &ctx.existing_metadata.origin.source_type else {
+        return Err(Error::Config(format!(
+            "Source '{alias}' is not a collection"
+        )));
+    };
+
+    let mut members = members.clone();
+    let mut contents = Vec::with_capacity(members.len());
+    let mut content_changed = false;
+    let mut validators_changed = false;
+
+    for member in &mut members {
+        let cached = storage.load_member_txt(alias, &member.prefix).ok();
+        let (etag, last_modified) = if cached.is_some() {
+            (member.etag.as_deref(), member.last_modified.as_deref())
+        } else {
+            (None, None)
+        };
+
+        match fetcher
+            .fetch_with_cache(&member.url, etag, last_modified)
+            .await?
+        {
+            FetchResult::NotModified { .. } => {
+                // Validators are only sent when a cached copy exists.
+                contents.push(cached.unwrap_or_default());
+            },
+            FetchResult::Modified {
+                content,
+                sha256,
+                etag,
+                last_modified,
+            } => {
+                if cached.is_none() || sha256 != member.sha256 {
+                    content_changed = true;
+                    storage.save_member_txt(alias, &member.prefix, &content)?;
+                }
+                validators_changed |= etag != member.etag || last_modified != member.last_modified;
+                member.etag = etag;
+                member.last_modified = last_modified;
+                member.sha256 = sha256;
+                contents.push(content);
+            },
+        }
+    }
+
+    let mut metadata = ctx.existing_metadata.clone();
+    let filter_changed = metadata.filter_non_english.unwrap_or(true) != filter_preference;
+    metadata.filter_non_english = Some(filter_preference);
+    metadata.origin.source_type = Some(SourceType::Collection {
+        members: members.clone(),
+    });
+
+    if !content_changed {
+        if validators_changed || filter_changed {
+            storage.save_metadata(alias, &metadata)?;
+        }
+        return Ok(RefreshOutcome::Unchanged {
+            alias: alias.to_string(),
+        });
+    }
+
+    let content = merge_members(
+        members
+            .iter()
+            .zip(&contents)
+            .map(|(member, content)| (member.prefix.as_str(), content.as_str())),
+    );
+    let payload = RefreshPayload {
+        sha256: calculate_sha256(&content),
+        content,
+        etag: None,
+        last_modified: None,
+    };
+    let params = ApplyRefreshParams::new(metadata, ctx.existing_aliases.clone());
+    apply_refresh(storage, alias, &params, &payload, metrics, indexer).await
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_member_specs() {
+        let member: CollectionMember = "api=https://example.com/api/llms.txt".parse().unwrap();
+        assert_eq!(member.prefix, "api");
+        assert_eq!(member.url, "https://example.com/api/llms.txt");
+
+        assert!(
+            "https://example.com/llms.txt"
+                .parse::<CollectionMember>()
+                .is_err()
+        );
+        assert!("api=".parse::<CollectionMember>().is_err());
+        assert!(
+            "a pi=https://example.com"
+                .parse::<CollectionMember>()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn rejects_duplicate_prefixes() {
+        let members = vec![
+            CollectionMember::new("api", "https://a").unwrap(),
+            CollectionMember::new("API", "https://b").unwrap(),
+        ];
+        assert!(validate_members(&members).is_err());
+        assert!(validate_members(&[]).is_err());
+        assert!(validate_members(&members[..1]).is_ok());
+    }
+
+    #[test]
+    fn merge_nests_members_under_prefix_headings() {
+        let merged = merge_members([
+            ("api", "# API\n\n## Auth\nUse a token.\n"),
+            (
+                "guides",
+                "# Guides\n```sh\n# not a heading\n```\n###### Deep\n",
+            ),
+        ]);
+        assert_eq!(
+            merged,
+            "# api\n\n## API\n\n### Auth\nUse a token.\n\n\
+             # guides\n\n## Guides\n```sh\n# not a heading\n```\n###### Deep\n"
+        );
+    }
+
+    #[test]
+    fn hashes_without_heading_markers_are_left_alone() {
+        assert_eq!(demote_headings("#hashtag\n#\n"), "#hashtag\n##\n");
+    }
+}
@@ -40,10 +40,22 @@
 //!         refresh_hours: Some(12), // Override global default
 //!         follow_links: None,      // Use global default
 //!         allowlist: None,         // Use global default
+//!         auth: None,
+//!         integrity: None,
 //!     },
 //!     index: IndexConfig {
 //!         max_heading_block_lines: Some(500),
 //!         filter_non_english: None, // Use global default
+//!         filters: None,
+//!         locales: None,
+//!         tokenizer: None,
+//!         stemmer: None,
+//!         lowercase: None,
+//!         synonyms: None,
+//!         heading_boost: None,
+//!         path_boost: None,
+//!         code_boost: None,
+//!         stopwords: None,
 //!     },
 //! };
 //!
@@ -52,8 +64,9 @@
 //! # Ok::<(), blz_core::Error>(())
 //! ```
 
-use crate::{Error, Result, profile};
+use crate::{Error, Result, SourceIntegrity, profile};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -90,6 +103,12 @@ const fn default_filter_non_english() -> bool {
 ///
 /// [paths]
 /// root = "/home/user/.outfitter/blz"
+///
+/// [profiles.claude]
+/// format = "jsonl"
+/// color = false
+/// max_tokens = 2000
+/// fields = ["url", "lines"]
 /// ```
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
@@ -97,6 +116,9 @@ pub struct Config {
     pub defaults: DefaultsConfig,
     /// File system paths configuration
     pub paths: PathsConfig,
+    /// Named output profiles, selected with `--profile-out <name>`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub profiles: BTreeMap<String, OutputProfile>,
 }
 
 /// Default settings that apply to all sources unless overridden.
@@ -141,6 +163,78 @@ pub struct DefaultsConfig {
     /// Defaults to `true` for backward compatibility.
     #[serde(default = "default_filter_non_english")]
     pub filter_non_english: bool,
+
+    /// Locale for user-facing CLI messages (e.g. `"en"`, `"es"`).
+    ///
+    /// When unset, messages are shown in English. Unsupported locales fall back
+    /// to English.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+
+    /// Download bandwidth limit for fetches (e.g. `"500KB/s"`).
+    ///
+    /// When unset, downloads are not throttled. The `--throttle` flag on
+    /// `blz add` and `blz sync` overrides this per run.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub throttle: Option<crate::throttle::Bandwidth>,
+
+    /// Paths probed when discovering docs for a bare domain (e.g. `blz add hono.dev`).
+    ///
+    /// Empty uses the built-in list (root, `/.well-known/`, `/docs/`, and
+    /// `ai.txt` variants). A non-empty list replaces it, in preference order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub probe_paths: Vec<String>,
+
+    /// Default typo tolerance for searches, as a Levenshtein distance (0-2).
+    ///
+    /// When unset, search terms must match exactly. The `--fuzzy` flag on
+    /// search commands overrides this per run.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fuzzy: Option<u8>,
+
+    /// Which operations ask for confirmation before running.
+    ///
+    /// When unset, only destructive operations prompt (see [`ConfirmPolicy`]).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confirm: Option<ConfirmPolicy>,
+
+    /// Whether search results are cached on disk (see [`crate::QueryCache`]).
+    ///
+    /// Enabled when unset. Set to `false` to always search the index.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub query_cache: Option<bool>,
+
+    /// Proxy URL for all fetches (e.g. `"http://proxy.corp:3128"`).
+    ///
+    /// When unset, `HTTPS_PROXY` / `HTTP_PROXY` are used, and hosts in
+    /// `NO_PROXY` are always reached directly.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<String>,
+
+    /// PEM bundle of extra root certificates to trust, for TLS-inspecting
+    /// corporate proxies. Also settable via `BLZ_CA_BUNDLE`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ca_bundle: Option<PathBuf>,
+
+    /// Maximum on-disk size of the cache (e.g. `"2GB"`).
+    ///
+    /// When unset, the cache is unbounded. `blz stats --disk` reports usage
+    /// against it, and `eviction` decides what happens when it is exceeded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disk_budget: Option<crate::quota::ByteSize>,
+
+    /// What to do when the cache exceeds `disk_budget`.
+    ///
+    /// When unset, only warns (see [`crate::quota::EvictionPolicy`]).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub eviction: Option<crate::quota::EvictionPolicy>,
+
+    /// Firecrawl API key used to scrape pages of generated sources.
+    ///
+    /// Accepts a literal key or `env:NAME` (see [`Secret`]). When unset,
+    /// `FIRECRAWL_API_KEY` is used, and without either the Firecrawl CLI is.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub firecrawl_api_key: Option<Secret>,
 }
 
 /// Policy for following external links in llms.txt files.
@@ -170,6 +264,25 @@ pub enum FollowLinks {
     Allowlist,
 }
 
+/// Policy for confirming operations before they run.
+///
+/// `--yes` always skips the prompt. Without a terminal (or with `--no-input`),
+/// an operation that still needs confirmation fails instead of prompting.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfirmPolicy {
+    /// Confirm every guarded operation, including registry writes.
+    Always,
+
+    /// Confirm operations that delete data, such as removing sources or
+    /// clearing the cache.
+    #[default]
+    Destructive,
+
+    /// Never ask; guarded operations run as if `--yes` was passed.
+    Never,
+}
+
 /// File system paths configuration.
 ///
 /// Defines where cached content, indices, and metadata are stored on the local filesystem.
@@ -187,7 +300,54 @@ pub struct PathsConfig {
     pub root: PathBuf,
 }
 
+/// Output defaults for one consumer, such as an agent harness.
+///
+/// Each field only applies when the matching flag is not given on the
+/// command line, so harnesses can still override a profile per call.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OutputProfile {
+    /// Output format (`text`, `json`, `jsonl`, or `raw`) for commands that
+    /// accept `--format`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+
+    /// Set to `false` to disable ANSI colors.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<bool>,
+
+    /// Estimated-token budget for each page of search results (`--max-tokens`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<usize>,
+
+    /// Results per page for searches (`--limit`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+
+    /// Extra fields on each search hit, by `--show` name (e.g. `["url", "lines"]`).
+    #[serde(default, alias = "show", skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<String>,
+}
+
 impl Config {
+    /// Look up a named output profile.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Config`] listing the defined profiles if `name` is not one of them.
+    pub fn output_profile(&self, name: &str) -> Result<&OutputProfile> {
+        self.profiles.get(name).ok_or_else(|| {
+            let known: Vec<&str> = self.profiles.keys().map(String::as_str).collect();
+            Error::Config(if known.is_empty() {
+                format!("Unknown output profile '{name}': no [profiles] are defined in config")
+            } else {
+                format!(
+                    "Unknown output profile '{name}' (available: {})",
+                    known.join(", ")
+                )
+            })
+        })
+    }
+
     /// Load configuration from the default location or create with defaults.
     ///
     /// This method attempts to load the configuration from the system config directory.
@@ -441,6 +601,66 @@ impl Config {
                 self.defaults.allowlist = list;
             }
         }
+        if let Ok(v) = std::env::var("BLZ_LOCALE") {
+            let locale = v.trim();
+            if !locale.is_empty() {
+                self.defaults.locale = Some(locale.to_string());
+            }
+        }
+        if let Ok(v) = std::env::var("BLZ_THROTTLE") {
+            if let Ok(rate) = v.parse() {
+                self.defaults.throttle = Some(rate);
+            }
+        }
+        if let Ok(v) = std::env::var("BLZ_PROBE_PATHS") {
+            let list = v
+                .split(',')
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect::<Vec<_>>();
+            if !list.is_empty() {
+                self.defaults.probe_paths = list;
+            }
+        }
+        if let Ok(v) = std::env::var("BLZ_FUZZY") {
+            if let Ok(distance) = v.trim().parse() {
+                self.defaults.fuzzy = Some(distance);
+            }
+        }
+        if let Ok(v) = std::env::var("BLZ_CONFIRM") {
+            match v.trim().to_ascii_lowercase().as_str() {
+                "always" => self.defaults.confirm = Some(ConfirmPolicy::Always),
+                "destructive" => self.defaults.confirm = Some(ConfirmPolicy::Destructive),
+                "never" => self.defaults.confirm = Some(ConfirmPolicy::Never),
+                _ => {},
+            }
+        }
+        if let Ok(v) = std::env::var("BLZ_QUERY_CACHE") {
+            let norm = v.to_ascii_lowercase();
+            self.defaults.query_cache = Some(matches!(norm.as_str(), "1" | "true" | "yes" | "on"));
+        }
+        if let Ok(v) = std::env::var("BLZ_PROXY") {
+            let proxy = v.trim();
+            if !proxy.is_empty() {
+                self.defaults.proxy = Some(proxy.to_string());
+            }
+        }
+        if let Ok(v) = std::env::var("BLZ_CA_BUNDLE") {
+            let p = PathBuf::from(v.trim());
+            if !p.as_os_str().is_empty() {
+                self.defaults.ca_bundle = Some(p);
+            }
+        }
+        if let Ok(v) = std::env::var("BLZ_DISK_BUDGET") {
+            if let Ok(budget) = v.parse() {
+                self.defaults.disk_budget = Some(budget);
+            }
+        }
+        if let Ok(v) = std::env::var("BLZ_EVICTION") {
+            if let Ok(policy) = v.parse() {
+                self.defaults.eviction = Some(policy);
+            }
+        }
         if let Ok(v) = std::env::var("BLZ_ROOT") {
             let p = PathBuf::from(v);
             if !p.as_os_str().is_empty() {
@@ -460,6 +680,17 @@ impl Default for Config {
                 follow_links: FollowLinks::FirstParty,
                 allowlist: Vec::new(),
                 filter_non_english: true,
+                locale: None,
+                throttle: None,
+                probe_paths: Vec::new(),
+                fuzzy: None,
+                confirm: None,
+                query_cache: None,
+                proxy: None,
+                ca_bundle: None,
+                disk_budget: None,
+                eviction: None,
+                firecrawl_api_key: None,
             },
             paths: PathsConfig {
                 root: directories::ProjectDirs::from("dev", "outfitter", profile::app_dir_slug())
@@ -478,6 +709,7 @@ impl Default for Config {
                         |dirs| dirs.data_dir().to_path_buf(),
                     ),
             },
+            profiles: BTreeMap::new(),
         }
     }
 }
@@ -554,7 +786,7 @@ pub struct ToolMeta {
 ///
 /// These settings override the global defaults for fetching behavior.
 /// Any `None` values will use the corresponding global default setting.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct FetchConfig {
     /// Override for refresh interval in hours.
     ///
@@ -573,13 +805,167 @@ pub struct FetchConfig {
     /// If `Some`, overrides the global `allowlist` setting for this source.
     /// If `None`, uses the global default. Only used when `follow_links` is `Allowlist`.
     pub allowlist: Option<Vec<String>>,
+
+    /// Credentials sent with every request for this source.
+    ///
+    /// Used by `add` and `sync` for documentation served behind token or
+    /// basic auth. Omitted when the source is public.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auth: Option<FetchAuth>,
+
+    /// Checksum or signature every fetched copy must match.
+    ///
+    /// Checked by `add` and `sync` before the document is stored, and by
+    /// `blz check --verify` against the cached copy.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub integrity: Option<SourceIntegrity>,
+}
+
+/// Credentials for a source served behind authentication.
+///
+/// Secret values are [`Secret`]s, so the file can point at environment
+/// variables instead of holding tokens itself:
+///
+/// ```toml
+/// [fetch.auth]
+/// bearer = "env:CORP_DOCS_TOKEN"
+///
+/// [fetch.auth.basic]
+/// username = "docs-bot"
+/// password = "env:CORP_DOCS_PASSWORD"
+///
+/// [fetch.auth.headers]
+/// X-Portal-Key = "env:PORTAL_KEY"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FetchAuth {
+    /// Token sent as `Authorization: Bearer <token>`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bearer: Option<Secret>,
+
+    /// Username and password sent as `Authorization: Basic`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub basic: Option<BasicAuth>,
+
+    /// Extra request headers, by header name.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, Secret>,
+}
+
+impl FetchAuth {
+    /// Whether no credentials are configured.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.bearer.is_none() && self.basic.is_none() && self.headers.is_empty()
+    }
+
+    /// Resolve every secret into `(header name, header value)` pairs.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a referenced environment variable is unset, or if
+    /// both bearer and basic auth are set.
+    pub fn header_pairs(&self) -> Result<Vec<(String, String)>> {
+        use base64::Engine as _;
+
+        if self.bearer.is_some() && self.basic.is_some() {
+            return Err(Error::Config(
+                "Configure either bearer or basic auth, not both".to_string(),
+            ));
+        }
+        let mut pairs = Vec::new();
+        if let Some(token) = &self.bearer {
+            pairs.push((
+                "Authorization".to_string(),
+                format!("Bearer {}", token.resolve()?),
+            ));
+        }
+        if let Some(basic) = &self.basic {
+            let credentials = format!("{}:{}", basic.username, basic.password.resolve()?);
+            let encoded = base64::engine::general_purpose::STANDARD.encode(credentials);
+            pairs.push(("Authorization".to_string(), format!("Basic {encoded}")));
+        }
+        for (name, value) in &self.headers {
+            pairs.push((name.clone(), value.resolve()?));
+        }
+        Ok(pairs)
+    }
+}
+
+/// Basic auth credentials.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BasicAuth {
+    /// Account name.
+    pub username: String,
+    /// Account password.
+    pub password: Secret,
+}
+
+/// A credential value, or a reference to where it lives.
+///
+/// `env:NAME` reads the environment variable `NAME` each time the secret is
+/// used, so tokens can rotate without touching blz. Any other string is used
+/// as-is. `Debug` output never shows literal values.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Secret(String);
+
+impl Secret {
+    /// Prefix marking an environment variable reference.
+    const ENV_PREFIX: &'static str = "env:";
+
+    /// Wrap a secret spec (`env:NAME` or a literal value).
+    #[must_use]
+    pub fn new(spec: impl Into<String>) -> Self {
+        Self(spec.into())
+    }
+
+    /// Reference the environment variable `name`.
+    #[must_use]
+    pub fn env(name: &str) -> Self {
+        Self(format!("{}{name}", Self::ENV_PREFIX))
+    }
+
+    /// Environment variable this secret reads, if it is a reference.
+    #[must_use]
+    pub fn env_var(&self) -> Option<&str> {
+        self.0.strip_prefix(Self::ENV_PREFIX)
+    }
+
+    /// Resolve the secret value.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the referenced environment variable is unset or empty.
+    pub fn resolve(&self) -> Result<String> {
+        let Some(var) = self.env_var() else {
+            return Ok(self.0.clone());
+        };
+        std::env::var(var)
+            .ok()
+            .filter(|value| !value.is_empty())
+            .ok_or_else(|| {
+                Error::Config(format!(
+                    "Credential environment variable '{var}' is not set"
+                ))
+            })
+    }
+}
+
+impl std::fmt::Debug for Secret {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.env_var() {
+            Some(var) => write!(f, "Secret(env:{var})"),
+            None => f.write_str("Secret(<redacted>)"),
+        }
+    }
 }
 
 /// Per-source indexing parameter overrides.
 ///
 /// These settings control how the documentation is processed and indexed
 /// for this specific source, overriding global defaults where specified.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct IndexConfig {
     /// Maximum lines to include in a single heading block.
     ///
@@ -596,6 +982,68 @@ pub struct IndexConfig {
     /// If `Some(false)`, all content will be retained regardless of global default.
     /// If `None`, uses the global `filter_non_english` setting.
     pub filter_non_english: Option<bool>,
+
+    /// Content filter pipeline for this source: rule specs applied in order
+    /// (see [`crate::content_filter`]).
+    ///
+    /// Replaces the default language-only pipeline when set. Filtering can
+    /// still be switched off for the source with `filter_non_english`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filters: Option<Vec<String>>,
+
+    /// Locales kept by the language filter for this source (`["en", "de"]`).
+    ///
+    /// If `None`, only English sections are kept.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locales: Option<Vec<String>>,
+
+    /// Tokenizer for this source: `auto`, `default`, `ngram`, or `jieba`
+    /// (see [`crate::tokenizer`]).
+    ///
+    /// If `None`, the tokenizer is chosen from the content's script.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tokenizer: Option<String>,
+
+    /// Language to stem words in (`english`, `german`, ...), so inflected
+    /// forms match each other.
+    ///
+    /// If `None`, words are matched as written.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stemmer: Option<String>,
+
+    /// Whether text is lowercased for matching.
+    ///
+    /// If `None`, matching is case-insensitive.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lowercase: Option<bool>,
+
+    /// Groups of interchangeable terms expanded in queries
+    /// (`[["env var", "environment variable"]]`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub synonyms: Option<Vec<Vec<String>>>,
+
+    /// Boost for query matches in a block's own heading (see
+    /// [`crate::ranking`]).
+    ///
+    /// If `None`, headings only count as part of the heading path.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub heading_boost: Option<f32>,
+
+    /// Boost for query matches in a block's heading path.
+    ///
+    /// If `None`, path matches count the same as body matches.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path_boost: Option<f32>,
+
+    /// Boost for query matches inside code blocks.
+    ///
+    /// If `None`, code only counts as part of the body text.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code_boost: Option<f32>,
+
+    /// Words dropped from unquoted queries against this source.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stopwords: Option<Vec<String>>,
 }
 
 impl ToolConfig {
@@ -676,10 +1124,22 @@ impl ToolConfig {
     ///         refresh_hours: Some(6),
     ///         follow_links: None,
     ///         allowlist: None,
+    ///         auth: None,
+    ///         integrity: None,
     ///     },
     ///     index: IndexConfig {
     ///         max_heading_block_lines: Some(300),
     ///         filter_non_english: None,
+    ///         filters: None,
+    ///         locales: None,
+    ///         tokenizer: None,
+    ///         stemmer: None,
+    ///         lowercase: None,
+    ///         synonyms: None,
+    ///         heading_boost: None,
+    ///         path_boost: None,
+    ///         code_boost: None,
+    ///         stopwords: None,
     ///     },
     /// };
     ///
@@ -719,10 +1179,22 @@ mod tests {
                 follow_links: FollowLinks::Allowlist,
                 allowlist: vec!["example.com".to_string(), "docs.rs".to_string()],
                 filter_non_english: true,
+                locale: None,
+                throttle: None,
+                probe_paths: Vec::new(),
+                fuzzy: None,
+                confirm: None,
+                query_cache: None,
+                proxy: None,
+                ca_bundle: None,
+                disk_budget: None,
+                eviction: None,
+                firecrawl_api_key: None,
             },
             paths: PathsConfig {
                 root: PathBuf::from("/tmp/test"),
             },
+            profiles: BTreeMap::new(),
         }
     }
 
@@ -738,10 +1210,22 @@ mod tests {
                 refresh_hours: Some(6),
                 follow_links: Some(FollowLinks::FirstParty),
                 allowlist: Some(vec!["allowed.com".to_string()]),
+                auth: None,
+                integrity: None,
             },
             index: IndexConfig {
                 max_heading_block_lines: Some(100),
                 filter_non_english: None,
+                filters: None,
+                locales: None,
+                tokenizer: None,
+                stemmer: None,
+                lowercase: None,
+                synonyms: None,
+                heading_boost: None,
+                path_boost: None,
+                code_boost: None,
+                stopwords: None,
             },
         }
     }
@@ -969,10 +1453,22 @@ mod tests {
                 follow_links: FollowLinks::None,
                 allowlist: vec!["a".repeat(1000)], // Very long domain
                 filter_non_english: false,
+                locale: None,
+                throttle: None,
+                probe_paths: Vec::new(),
+                fuzzy: None,
+                confirm: None,
+                query_cache: None,
+                proxy: None,
+                ca_bundle: None,
+                disk_budget: None,
+                eviction: None,
+                firecrawl_api_key: None,
             },
             paths: PathsConfig {
                 root: PathBuf::from("/".repeat(100)), // Very long path
             },
+            profiles: BTreeMap::new(),
         };
 
         // When: Serializing and deserializing
@@ -1002,10 +1498,22 @@ mod tests {
                 follow_links: FollowLinks::Allowlist,
                 allowlist: vec![], // Empty allowlist
                 filter_non_english: true,
+                locale: None,
+                throttle: None,
+                probe_paths: Vec::new(),
+                fuzzy: None,
+                confirm: None,
+                query_cache: None,
+                proxy: None,
+                ca_bundle: None,
+                disk_budget: None,
+                eviction: None,
+                firecrawl_api_key: None,
             },
             paths: PathsConfig {
                 root: PathBuf::from("/tmp"),
             },
+            profiles: BTreeMap::new(),
         };
 
         // When: Serializing and deserializing
@@ -1022,6 +1530,50 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_fetch_auth_parses_and_resolves_headers()
+    -> std::result::Result<(), Box<dyn std::error::Error>> {
+        // Given: Per-source settings with every credential kind
+        let toml_with_auth = r#"
+            [meta]
+            name = "corp"
+
+            [fetch.auth.basic]
+            username = "docs-bot"
+            password = "hunter2"
+
+            [fetch.auth.headers]
+            X-Portal-Key = "env:BLZ_TEST_UNSET_PORTAL_KEY"
+
+            [index]
+        "#;
+
+        // When: Parsing and resolving
+        let config: ToolConfig = toml::from_str(toml_with_auth)?;
+        let mut auth = config.fetch.auth.expect("auth section");
+
+        // Then: An unset variable is reported by name, literals never leak via Debug
+        let err = auth.header_pairs().unwrap_err().to_string();
+        assert!(err.contains("BLZ_TEST_UNSET_PORTAL_KEY"));
+        assert!(!format!("{auth:?}").contains("hunter2"));
+
+        auth.headers.clear();
+        assert_eq!(
+            auth.header_pairs()?,
+            vec![(
+                "Authorization".to_string(),
+                "Basic ZG9jcy1ib3Q6aHVudGVyMg==".to_string()
+            )]
+        );
+
+        // And: Bearer and basic auth cannot be combined
+        auth.bearer = Some(Secret::new("literal-token"));
+        assert!(auth.header_pairs().is_err());
+        auth.basic = None;
+        assert_eq!(auth.header_pairs()?[0].1, "Bearer literal-token");
+        Ok(())
+    }
+
     #[test]
     fn test_defaults_config_backward_compatibility_filter_non_english() -> Result<()> {
         // Given: Configuration TOML without filter_non_english field (backward compatibility)
@@ -1048,12 +1600,180 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_defaults_config_throttle() -> Result<()> {
+        // Given: Configuration TOML with a bandwidth limit
+        let toml_with_throttle = r#"
+            [defaults]
+            refresh_hours = 24
+            max_archives = 10
+            fetch_enabled = true
+            follow_links = "first_party"
+            allowlist = []
+            throttle = "500KB/s"
+
+            [paths]
+            root = "/tmp/test"
+        "#;
+
+        // When: Deserializing and re-serializing
+        let config: Config = toml::from_str(toml_with_throttle)
+            .map_err(|e| Error::Config(format!("Failed to parse: {e}")))?;
+        let serialized = toml::to_string(&config)
+            .map_err(|e| Error::Config(format!("Failed to serialize: {e}")))?;
+
+        // Then: The rate is parsed and written back in the same form
+        assert_eq!(
+            config
+                .defaults
+                .throttle
+                .map(crate::throttle::Bandwidth::bytes_per_second),
+            Some(500 * 1024)
+        );
+        assert!(serialized.contains("throttle = \"500KB/s\""));
+
+        // And: An invalid rate is rejected
+        let invalid = toml_with_throttle.replace("500KB/s", "fast");
+        assert!(toml::from_str::<Config>(&invalid).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_defaults_config_probe_paths() -> Result<()> {
+        // Given: Configuration TOML with custom probe paths
+        let toml_with_probes = r#"
+            [defaults]
+            refresh_hours = 24
+            max_archives = 10
+            fetch_enabled = true
+            follow_links = "first_party"
+            allowlist = []
+            probe_paths = ["/.well-known/llms.txt", "/reference/llms-full.txt"]
+
+            [paths]
+            root = "/tmp/test"
+        "#;
+
+        // When: Deserializing
+        let config: Config = toml::from_str(toml_with_probes)
+            .map_err(|e| Error::Config(format!("Failed to parse: {e}")))?;
+
+        // Then: The configured list replaces the defaults
+        let targets = crate::discovery::ProbeTargets::from_config(&config.defaults.probe_paths);
+        assert_eq!(
+            targets.paths(),
+            ["/.well-known/llms.txt", "/reference/llms-full.txt"]
+        );
+
+        // And: Omitting the key keeps the built-in list
+        let without = toml_with_probes.replace("probe_paths", "# probe_paths");
+        let config: Config =
+            toml::from_str(&without).map_err(|e| Error::Config(format!("Failed to parse: {e}")))?;
+        assert!(config.defaults.probe_paths.is_empty());
+        assert_eq!(
+            crate::discovery::ProbeTargets::from_config(&config.defaults.probe_paths),
+            crate::discovery::ProbeTargets::default()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_defaults_config_confirm_policy() -> Result<()> {
+        // Given: Configuration TOML with a confirmation policy
+        let toml_with_confirm = r#"
+            [defaults]
+            refresh_hours = 24
+            max_archives = 10
+            fetch_enabled = true
+            follow_links = "first_party"
+            allowlist = []
+            confirm = "never"
+
+            [paths]
+            root = "/tmp/test"
+        "#;
+
+        // When: Deserializing
+        let config: Config = toml::from_str(toml_with_confirm)
+            .map_err(|e| Error::Config(format!("Failed to parse: {e}")))?;
+
+        // Then: The policy is read, and omitting it falls back to destructive-only
+        assert_eq!(config.defaults.confirm, Some(ConfirmPolicy::Never));
+        let without = toml_with_confirm.replace("confirm", "# confirm");
+        let config: Config =
+            toml::from_str(&without).map_err(|e| Error::Config(format!("Failed to parse: {e}")))?;
+        assert_eq!(
+            config.defaults.confirm.unwrap_or_default(),
+            ConfirmPolicy::Destructive
+        );
+
+        // And: Unknown policies are rejected
+        let invalid = toml_with_confirm.replace("never", "sometimes");
+        assert!(toml::from_str::<Config>(&invalid).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_output_profiles() -> Result<()> {
+        // Given: Configuration TOML with a named output profile
+        let toml_with_profiles = r#"
+            [defaults]
+            refresh_hours = 24
+            max_archives = 10
+            fetch_enabled = true
+            follow_links = "first_party"
+            allowlist = []
+
+            [paths]
+            root = "/tmp/test"
+
+            [profiles.claude]
+            format = "jsonl"
+            color = false
+            max_tokens = 2000
+            fields = ["url", "lines"]
+        "#;
+
+        // When: Deserializing
+        let config: Config = toml::from_str(toml_with_profiles)
+            .map_err(|e| Error::Config(format!("Failed to parse: {e}")))?;
+
+        // Then: The profile is found by name with every field read
+        let profile = config.output_profile("claude")?;
+        assert_eq!(profile.format.as_deref(), Some("jsonl"));
+        assert_eq!(profile.color, Some(false));
+        assert_eq!(profile.max_tokens, Some(2000));
+        assert_eq!(profile.limit, None);
+        assert_eq!(profile.fields, vec!["url", "lines"]);
+
+        // And: Unknown names list the defined profiles
+        let err = config.output_profile("codex").unwrap_err().to_string();
+        assert!(err.contains("available: claude"), "{err}");
+
+        // And: Configs without profiles still parse
+        assert!(create_test_config().profiles.is_empty());
+        Ok(())
+    }
+
     #[test]
     fn test_index_config_backward_compatibility_filter_non_english() -> Result<()> {
         // Given: IndexConfig without filter_non_english field (backward compatibility)
         let config = IndexConfig {
             max_heading_block_lines: Some(500),
             filter_non_english: None,
+            filters: None,
+            locales: None,
+            tokenizer: None,
+            stemmer: None,
+            lowercase: None,
+            synonyms: None,
+            heading_boost: None,
+            path_boost: None,
+            code_boost: None,
+            stopwords: None,
         };
 
         // When: Serializing and deserializing
@@ -1080,10 +1800,22 @@ mod tests {
                 follow_links: FollowLinks::FirstParty,
                 allowlist: vec![],
                 filter_non_english: false,
+                locale: None,
+                throttle: None,
+                probe_paths: Vec::new(),
+                fuzzy: None,
+                confirm: None,
+                query_cache: None,
+                proxy: None,
+                ca_bundle: None,
+                disk_budget: None,
+                eviction: None,
+                firecrawl_api_key: None,
             },
             paths: PathsConfig {
                 root: PathBuf::from("/tmp"),
             },
+            profiles: BTreeMap::new(),
         };
 
         // When: Serializing and deserializing
@@ -1108,10 +1840,22 @@ mod tests {
                     follow_links: FollowLinks::FirstParty,
                     allowlist: vec![],
                     filter_non_english: true,
+                    locale: None,
+                    throttle: None,
+                    probe_paths: Vec::new(),
+                    fuzzy: None,
+                    confirm: None,
+                    query_cache: None,
+                    proxy: None,
+                    ca_bundle: None,
+                    disk_budget: None,
+                    eviction: None,
+                    firecrawl_api_key: None,
                 },
                 paths: PathsConfig {
                     root: PathBuf::from("/tmp"),
                 },
+                profiles: BTreeMap::new(),
             };
 
             let serialized = toml::to_string_pretty(&config).expect("should serialize");
@@ -1130,10 +1874,22 @@ mod tests {
                     follow_links: FollowLinks::FirstParty,
                     allowlist: vec![],
                     filter_non_english: true,
+                    locale: None,
+                    throttle: None,
+                    probe_paths: Vec::new(),
+                    fuzzy: None,
+                    confirm: None,
+                    query_cache: None,
+                    proxy: None,
+                    ca_bundle: None,
+                    disk_budget: None,
+                    eviction: None,
+                    firecrawl_api_key: None,
                 },
                 paths: PathsConfig {
                     root: PathBuf::from("/tmp"),
                 },
+                profiles: BTreeMap::new(),
             };
 
             let serialized = toml::to_string_pretty(&config).expect("should serialize");
@@ -1152,10 +1908,22 @@ mod tests {
                     follow_links: FollowLinks::Allowlist,
                     allowlist: allowlist.clone(),
                     filter_non_english: true,
+                    locale: None,
+                    throttle: None,
+                    probe_paths: Vec::new(),
+                    fuzzy: None,
+                    confirm: None,
+                    query_cache: None,
+                    proxy: None,
+                    ca_bundle: None,
+                    disk_budget: None,
+                    eviction: None,
+                    firecrawl_api_key: None,
                 },
                 paths: PathsConfig {
                     root: PathBuf::from("/tmp"),
                 },
+                profiles: BTreeMap::new(),
             };
 
             let serialized = toml::to_string_pretty(&config).expect("should serialize");
@@ -1190,6 +1958,7 @@ mod tests {
                     paths: PathsConfig {
                         root: PathBuf::from(malicious_path),
                     },
+                    profiles: BTreeMap::new(),
                 };
 
                 // Then: Should still serialize/deserialize (path validation is separate)
@@ -1222,6 +1991,7 @@ mod tests {
                     paths: PathsConfig {
                         root: PathBuf::from("/tmp"),
                     },
+                    profiles: BTreeMap::new(),
                 };
 
                 // Then: Should serialize safely (TOML library handles escaping)
@@ -1261,6 +2031,7 @@ mod tests {
                 paths: PathsConfig {
                     root: PathBuf::from("/tmp/测试"), // Chinese characters
                 },
+                profiles: BTreeMap::new(),
             };
 
             // When: Serializing and deserializing
@@ -1300,6 +2071,7 @@ mod tests {
                 paths: PathsConfig {
                     root: PathBuf::from(""), // Empty path
                 },
+                profiles: BTreeMap::new(),
             };
 
             // When: Serializing and deserializing
@@ -0,0 +1,645 @@
+//! Content filter pipelines for heading blocks.
+//!
+//! A [`FilterPipeline`] is an ordered list of [`FilterRule`]s applied to the
+//! heading blocks of a parsed source. Rules either drop whole sections (by
+//! language, heading path, content, or repetition) or rewrite individual
+//! lines. Running a pipeline returns the surviving blocks together with a
+//! [`SectionChange`] for every section it touched, so callers can preview a
+//! pipeline before re-indexing with it.
+//!
+//! Rules are written as short specs:
+//!
+//! | Spec | Effect |
+//! |------|--------|
+//! | `lang` | Drop sections outside the source's locales (English by default) |
+//! | `lang=<locales>` | Drop sections outside the listed locales (`lang=en,de`) |
+//! | `nav` | Remove navigation boilerplate lines ("Edit this page", "Next", ...) |
+//! | `dedupe` | Drop sections whose body repeats an earlier section's |
+//! | `html-comments` | Remove `<!-- ... -->` comments, including multi-line ones |
+//! | `drop-heading=<regex>` | Drop sections whose heading path matches |
+//! | `drop-content=<regex>` | Drop sections whose content matches |
+//! | `strip=<regex>` | Remove matching text from every line |
+//! | `replace=<regex>=><text>` | Replace matching text on every line |
+//!
+//! Heading paths are matched as their segments joined with `" > "`. Lines left
+//! blank by a rewriting rule are removed, and a section whose body is removed
+//! entirely is dropped. `nav` and `html-comments` leave fenced code alone.
+//!
+//! Sources are indexed with the pipeline in their `settings.toml`
+//! (`[index] filters`), or with `lang` alone when none is configured.
+
+use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::LazyLock;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::refresh::keeps_block_language;
+use crate::{Error, HeadingBlock, LanguageFilter, LocaleSet, Result};
+
+/// Lines that are site navigation rather than documentation: skip links,
+/// edit links, feedback prompts, pagination, and "last updated" stamps, alone
+/// on their line and optionally written as a link or list item.
+///
+/// SAFETY: Pattern is a compile-time constant that is known to be valid.
+#[allow(clippy::unwrap_used)]
+static NAV_LINE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?ix)^\s*(?:[-*]\s+)?\[?\s*[←→«»‹›]?\s*
+        (?:skip\ to\ (?:main\ )?content
+          |edit\ (?:this\ page|on\ github)
+          |(?:was|is)\ this\ (?:page|article)\ helpful\??
+          |on\ this\ page
+          |back\ to\ top
+          |copy\ page
+          |(?:previous|prev|next)(?:\ page|\s*:[^\]\n]*)?
+          |last\ updated\b[^\]\n]*)
+        \s*[←→«»‹›]?\s*\]?(?:\([^)]*\))?\s*$",
+    )
+    .unwrap()
+});
+
+/// One step of a [`FilterPipeline`].
+#[derive(Debug, Clone)]
+pub enum FilterRule {
+    /// Drop sections outside a set of locales; `None` uses the pipeline's
+    /// locales (see [`FilterPipeline::with_locales`]).
+    Language(Option<LocaleSet>),
+    /// Remove navigation boilerplate lines.
+    Nav,
+    /// Drop sections whose body repeats an earlier kept section's.
+    Dedupe,
+    /// Remove HTML comments.
+    HtmlComments,
+    /// Drop sections whose heading path matches the pattern.
+    DropHeading(Regex),
+    /// Drop sections whose content matches the pattern.
+    DropContent(Regex),
+    /// Replace matches on every line (`strip` is a replace with empty text).
+    Replace {
+        /// Pattern to search for.
+        pattern: Regex,
+        /// Replacement text; supports `$1`-style capture references.
+        replacement: String,
+    },
+}
+
+impl FromStr for FilterRule {
+    type Err = Error;
+
+    fn from_str(spec: &str) -> Result<Self> {
+        let spec = spec.trim();
+        let (kind, value) = spec.split_once('=').unwrap_or((spec, ""));
+        let regex = |pattern: &str| {
+            if pattern.is_empty() {
+                return Err(Error::Config(format!(
+                    "filter rule '{spec}' needs a pattern"
+                )));
+            }
+            Regex::new(pattern)
+                .map_err(|e| Error::Config(format!("invalid pattern in filter rule '{spec}': {e}")))
+        };
+        match kind {
+            "lang" | "language" if value.is_empty() => Ok(Self::Language(None)),
+            "lang" | "language" => Ok(Self::Language(Some(value.parse()?))),
+            "nav" if value.is_empty() => Ok(Self::Nav),
+            "dedupe" if value.is_empty() => Ok(Self::Dedupe),
+            "html-comments" if value.is_empty() => Ok(Self::HtmlComments),
+            "drop-heading" => Ok(Self::DropHeading(regex(value)?)),
+            "drop-content" => Ok(Self::DropContent(regex(value)?)),
+            "strip" => Ok(Self::Replace {
+                pattern: regex(value)?,
+                replacement: String::new(),
+            }),
+            "replace" => {
+                let (pattern, replacement) = value.split_once("=>").ok_or_else(|| {
+                    Error::Config(format!(
+                        "filter rule '{spec}' must look like replace=<regex>=><text>"
+                    ))
+                })?;
+                Ok(Self::Replace {
+                    pattern: regex(pattern)?,
+                    replacement: replacement.to_string(),
+                })
+            },
+            _ => Err(Error::Config(format!(
+                "unknown filter rule '{spec}' (expected lang, nav, dedupe, html-comments, drop-heading=, drop-content=, strip=, or replace=)"
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for FilterRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Language(None) => f.write_str("lang"),
+            Self::Language(Some(locales)) => write!(f, "lang={locales}"),
+            Self::Nav => f.write_str("nav"),
+            Self::Dedupe => f.write_str("dedupe"),
+            Self::HtmlComments => f.write_str("html-comments"),
+            Self::DropHeading(pattern) => write!(f, "drop-heading={pattern}"),
+            Self::DropContent(pattern) => write!(f, "drop-content={pattern}"),
+            Self::Replace {
+                pattern,
+                replacement,
+            } if replacement.is_empty() => write!(f, "strip={pattern}"),
+            Self::Replace {
+                pattern,
+                replacement,
+            } => write!(f, "replace={pattern}=>{replacement}"),
+        }
+    }
+}
+
+impl FilterRule {
+    /// Whether a hit removes the whole section rather than rewriting lines.
+    const fn drops_section(&self) -> bool {
+        matches!(
+            self,
+            Self::Language(_) | Self::Dedupe | Self::DropHeading(_) | Self::DropContent(_)
+        )
+    }
+}
+
+/// What a pipeline did to one section.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SectionAction {
+    /// The whole section was removed.
+    Dropped,
+    /// Some lines were rewritten or removed.
+    Modified,
+}
+
+/// One line removed or rewritten by a pipeline.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LineChange {
+    /// Line number in the source (1-based).
+    pub line: usize,
+    /// The original line.
+    pub before: String,
+    /// The rewritten line, or `None` when the line was removed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<String>,
+}
+
+/// A section touched by a pipeline.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SectionChange {
+    /// Heading path of the section.
+    pub heading_path: Vec<String>,
+    /// Line range of the section (`start-end`).
+    pub lines: String,
+    /// Whether the section was dropped or modified.
+    pub action: SectionAction,
+    /// Specs of the rules that changed the section, in pipeline order.
+    pub rules: Vec<String>,
+    /// Number of source lines removed, counting a dropped section in full.
+    pub lines_removed: usize,
+    /// Number of lines rewritten in place.
+    pub lines_rewritten: usize,
+    /// Per-line changes (every line of a dropped section is listed as removed).
+    pub changes: Vec<LineChange>,
+}
+
+/// Blocks kept by a pipeline and the sections it changed.
+#[derive(Debug, Clone)]
+pub struct FilterOutcome {
+    /// Heading blocks after filtering.
+    pub blocks: Vec<HeadingBlock>,
+    /// Sections dropped or modified, in source order.
+    pub changes: Vec<SectionChange>,
+}
+
+/// An ordered list of filter rules.
+#[derive(Debug, Clone, Default)]
+pub struct FilterPipeline {
+    rules: Vec<FilterRule>,
+    locales: LocaleSet,
+}
+
+impl FilterPipeline {
+    /// Create a pipeline from rules applied in order.
+    #[must_use]
+    pub const fn new(rules: Vec<FilterRule>) -> Self {
+        Self {
+            rules,
+            locales: LocaleSet::ENGLISH,
+        }
+    }
+
+    /// Locales kept by `lang` rules that do not list their own.
+    #[must_use]
+    pub const fn with_locales(mut self, locales: LocaleSet) -> Self {
+        self.locales = locales;
+        self
+    }
+
+    /// Parse a pipeline from rule specs.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the first spec that is not a valid rule.
+    pub fn parse<S: AsRef<str>>(specs: &[S]) -> Result<Self> {
+        specs
+            .iter()
+            .map(|spec| spec.as_ref().parse())
+            .collect::<Result<Vec<_>>>()
+            .map(Self::new)
+    }
+
+    /// Rules in application order.
+    #[must_use]
+    pub fn rules(&self) -> &[FilterRule] {
+        &self.rules
+    }
+
+    /// Whether the pipeline is the English-only language filter alone, the
+    /// default for sources without configured rules.
+    #[must_use]
+    pub fn is_language_only(&self) -> bool {
+        match self.rules.as_slice() {
+            [FilterRule::Language(locales)] => locales.unwrap_or(self.locales).is_english_only(),
+            _ => false,
+        }
+    }
+
+    /// Run the pipeline over heading blocks.
+    #[must_use]
+    pub fn run(&self, blocks: Vec<HeadingBlock>) -> FilterOutcome {
+        self.filter(blocks, true)
+    }
+
+    /// Run the pipeline over heading blocks, keeping only the surviving
+    /// blocks. Cheaper than [`Self::run`] when no preview is needed.
+    #[must_use]
+    pub fn apply(&self, blocks: Vec<HeadingBlock>) -> Vec<HeadingBlock> {
+        if self.rules.is_empty() {
+            return blocks;
+        }
+        self.filter(blocks, false).blocks
+    }
+
+    fn filter(&self, blocks: Vec<HeadingBlock>, record: bool) -> FilterOutcome {
+        let language_filter = LanguageFilter::new(true).with_locales(self.locales);
+        let mut seen_bodies = HashSet::new();
+        let mut kept = Vec::with_capacity(blocks.len());
+        let mut changes = Vec::new();
+
+        for block in blocks {
+            // Current text of each line; `None` once removed
+            let mut current: Vec<Option<String>> = block
+                .content
+                .lines()
+                .map(|line| Some(line.to_string()))
+                .collect();
+            let fenced = fenced_lines(&block.content);
+            let mut rules = Vec::new();
+            let mut dropped = false;
+            let mut body_key = None;
+
+            for rule in &self.rules {
+                let hit = match rule {
+                    FilterRule::Language(None) => !keeps_block_language(&language_filter, &block),
+                    FilterRule::Language(Some(locales)) => {
+                        let filter = LanguageFilter::new(true).with_locales(*locales);
+                        !keeps_block_language(&filter, &block)
+                    },
+                    FilterRule::Nav => remove_nav_lines(&mut current, &fenced),
+                    FilterRule::Dedupe => {
+                        let key = body_text(&current);
+                        let repeated = !key.is_empty() && seen_bodies.contains(&key);
+                        body_key = Some(key);
+                        repeated
+                    },
+                    FilterRule::HtmlComments => strip_html_comments(&mut current, &fenced),
+                    FilterRule::DropHeading(pattern) => pattern.is_match(&block.path.join(" > ")),
+                    FilterRule::DropContent(pattern) => {
+                        let text = current.iter().flatten().cloned().collect::<Vec<_>>();
+                        pattern.is_match(&text.join("\n"))
+                    },
+                    FilterRule::Replace {
+                        pattern,
+                        replacement,
+                    } => replace_lines(&mut current, pattern, replacement),
+                };
+                if hit {
+                    rules.push(rule.to_string());
+                    if rule.drops_section() {
+                        dropped = true;
+                        break;
+                    }
+                }
+            }
+
+            // A section reduced to nothing but its heading line is dropped too
+            if !dropped && !rules.is_empty() {
+                dropped = current
+                    .iter()
+                    .skip(1)
+                    .flatten()
+                    .all(|line| line.trim().is_empty());
+            }
+
+            if let Some(key) = body_key.filter(|key| !dropped && !key.is_empty()) {
+                seen_bodies.insert(key);
+            }
+            if rules.is_empty() {
+                kept.push(block);
+                continue;
+            }
+            if record {
+                changes.push(section_change(&block, &current, rules, dropped));
+            }
+            if !dropped {
+                let content = current.into_iter().flatten().collect::<Vec<_>>().join("\n");
+                kept.push(HeadingBlock { content, ..block });
+            }
+        }
+
+        FilterOutcome {
+            blocks: kept,
+            changes,
+        }
+    }
+}
+
+impl fmt::Display for FilterPipeline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, rule) in self.rules.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            match rule {
+                FilterRule::Language(None) if !self.locales.is_english_only() => {
+                    write!(f, "lang={}", self.locales)?;
+                },
+                rule => write!(f, "{rule}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Describe how a block changed, given the final state of its lines.
+fn section_change(
+    block: &HeadingBlock,
+    current: &[Option<String>],
+    rules: Vec<String>,
+    dropped: bool,
+) -> SectionChange {
+    let changes: Vec<LineChange> = block
+        .content
+        .lines()
+        .zip(current)
+        .enumerate()
+        .filter(|(_, (before, after))| dropped || after.as_deref() != Some(*before))
+        .map(|(offset, (before, after))| LineChange {
+            line: block.start_line + offset,
+            before: before.to_string(),
+            after: if dropped { None } else { after.clone() },
+        })
+        .collect();
+    let lines_removed = changes.iter().filter(|c| c.after.is_none()).count();
+    SectionChange {
+        heading_path: block.path.clone(),
+        lines: format!("{}-{}", block.start_line, block.end_line),
+        action: if dropped {
+            SectionAction::Dropped
+        } else {
+            SectionAction::Modified
+        },
+        rules,
+        lines_removed,
+        lines_rewritten: changes.len() - lines_removed,
+        changes,
+    }
+}
+
+/// Apply a replacement to every remaining line, removing lines it leaves
+/// blank. Returns whether any line changed.
+fn replace_lines(lines: &mut [Option<String>], pattern: &Regex, replacement: &str) -> bool {
+    let mut changed = false;
+    for slot in lines.iter_mut() {
+        let Some(line) = slot.as_deref() else {
+            continue;
+        };
+        let replaced = pattern.replace_all(line, replacement);
+        if replaced == line {
+            continue;
+        }
+        changed = true;
+        *slot = if replaced.trim().is_empty() {
+            None
+        } else {
+            Some(replaced.into_owned())
+        };
+    }
+    changed
+}
+
+/// Which lines of `content` are inside (or open/close) a fenced code block.
+fn fenced_lines(content: &str) -> Vec<bool> {
+    let mut fence: Option<&str> = None;
+    content
+        .lines()
+        .map(|line| {
+            let trimmed = line.trim_start();
+            let marker = ["```", "~~~"]
+                .into_iter()
+                .find(|marker| trimmed.starts_with(marker));
+            match (fence, marker) {
+                (None, Some(marker)) => {
+                    fence = Some(marker);
+                    true
+                },
+                (Some(open), Some(marker)) if open == marker => {
+                    fence = None;
+                    true
+                },
+                (open, _) => open.is_some(),
+            }
+        })
+        .collect()
+}
+
+/// Remove navigation boilerplate lines outside code. Returns whether any
+/// line was removed.
+fn remove_nav_lines(lines: &mut [Option<String>], fenced: &[bool]) -> bool {
+    let mut changed = false;
+    // The first line is the section heading
+    for (slot, &in_code) in lines.iter_mut().zip(fenced).skip(1) {
+        if !in_code && slot.as_deref().is_some_and(|line| NAV_LINE.is_match(line)) {
+            *slot = None;
+            changed = true;
+        }
+    }
+    changed
+}
+
+/// Remove `<!-- ... -->` comments outside code, following comments across
+/// lines. Returns whether any line changed.
+fn strip_html_comments(lines: &mut [Option<String>], fenced: &[bool]) -> bool {
+    let mut changed = false;
+    let mut in_comment = false;
+    for (slot, &in_code) in lines.iter_mut().zip(fenced) {
+        let Some(line) = slot.as_deref() else {
+            continue;
+        };
+        if in_code && !in_comment {
+            continue;
+        }
+        let mut rest = line;
+        let mut kept = String::new();
+        loop {
+            if in_comment {
+                let Some(end) = rest.find("-->") else {
+                    rest = "";
+                    break;
+                };
+                rest = &rest[end + 3..];
+                in_comment = false;
+            } else {
+                let Some(start) = rest.find("<!--") else {
+                    break;
+                };
+                kept.push_str(&rest[..start]);
+                rest = &rest[start + 4..];
+                in_comment = true;
+            }
+        }
+        kept.push_str(rest);
+        if kept == line {
+            continue;
+        }
+        changed = true;
+        *slot = if kept.trim().is_empty() {
+            None
+        } else {
+            Some(kept.trim_end().to_string())
+        };
+    }
+    changed
+}
+
+/// The non-blank body lines of a section (everything after its heading),
+/// trimmed, used to spot repeated sections.
+fn body_text(lines: &[Option<String>]) -> String {
+    lines
+        .iter()
+        .skip(1)
+        .flatten()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::panic)]
+    use super::*;
+
+    fn block(path: &[&str], content: &str, start_line: usize) -> HeadingBlock {
+        let lines = content.lines().count();
+        HeadingBlock::new(
+            path.iter().map(ToString::to_string).collect(),
+            content.to_string(),
+            start_line,
+            start_line + lines - 1,
+        )
+    }
+
+    #[test]
+    fn parses_and_displays_rule_specs() {
+        for spec in [
+            "lang",
+            "lang=en,de",
+            "nav",
+            "dedupe",
+            "html-comments",
+            "drop-heading=^Changelog",
+            "drop-content=deprecated",
+            "strip=\\[edit\\]",
+            "replace=v(\\d+)=>version $1",
+        ] {
+            let rule: FilterRule = spec.parse().expect("valid spec");
+            assert_eq!(rule.to_string(), spec);
+        }
+        assert!("strip=".parse::<FilterRule>().is_err());
+        assert!("replace=abc".parse::<FilterRule>().is_err());
+        assert!("drop-heading=(".parse::<FilterRule>().is_err());
+        assert!("shout".parse::<FilterRule>().is_err());
+    }
+
+    #[test]
+    fn drops_and_rewrites_sections_with_line_changes() {
+        let blocks = vec![
+            block(
+                &["Guide", "Install"],
+                "## Install\nRun the installer [edit]\n",
+                1,
+            ),
+            block(&["Guide", "Changelog"], "## Changelog\nv1 shipped\n", 3),
+            block(&["Guide", "Nav"], "## Nav\n[edit]\n", 5),
+        ];
+        let pipeline = FilterPipeline::parse(&["drop-heading=Changelog", "strip=\\s*\\[edit\\]"])
+            .expect("valid pipeline");
+        let outcome = pipeline.run(blocks);
+
+        assert_eq!(outcome.blocks.len(), 1);
+        assert_eq!(outcome.blocks[0].content, "## Install\nRun the installer");
+
+        assert_eq!(outcome.changes.len(), 3, "{:?}", outcome.changes);
+        let (install, changelog, nav) = (
+            &outcome.changes[0],
+            &outcome.changes[1],
+            &outcome.changes[2],
+        );
+        assert_eq!(install.action, SectionAction::Modified);
+        assert_eq!(install.lines_rewritten, 1);
+        assert_eq!(install.changes[0].line, 2);
+        assert_eq!(
+            install.changes[0].after.as_deref(),
+            Some("Run the installer")
+        );
+        assert_eq!(changelog.action, SectionAction::Dropped);
+        assert_eq!(changelog.rules, ["drop-heading=Changelog"]);
+        assert_eq!(changelog.lines_removed, 2);
+        // Stripping left only the heading line, so the section goes
+        assert_eq!(nav.action, SectionAction::Dropped);
+    }
+
+    #[test]
+    fn builtin_rules_strip_boilerplate_and_repeats() {
+        let blocks = vec![
+            block(
+                &["Guide", "Install"],
+                "## Install\n<!-- TODO: expand\nthis section -->\nRun the installer.\n[Edit this page](https://example.com/edit)\n```html\n<!-- kept in code -->\n```\n[Next: Usage →](/usage)\n",
+                1,
+            ),
+            block(&["Guide", "Help"], "## Help\nAsk in the forum.\n", 10),
+            block(&["API", "Help"], "## Help\nAsk in the forum.\n", 12),
+        ];
+        let pipeline =
+            FilterPipeline::parse(&["html-comments", "nav", "dedupe"]).expect("valid pipeline");
+        let outcome = pipeline.run(blocks.clone());
+
+        assert_eq!(
+            outcome.blocks[0].content,
+            "## Install\nRun the installer.\n```html\n<!-- kept in code -->\n```"
+        );
+        assert_eq!(outcome.blocks.len(), 2);
+        assert_eq!(outcome.blocks[1].path, ["Guide", "Help"]);
+        assert_eq!(outcome.changes[1].rules, ["dedupe"]);
+        assert_eq!(outcome.changes[1].heading_path, ["API", "Help"]);
+
+        let applied = pipeline.apply(blocks);
+        assert_eq!(applied.len(), 2);
+        assert_eq!(applied[0].content, outcome.blocks[0].content);
+    }
+}
@@ -0,0 +1,345 @@
+//! Context packing: turning search hits into one cited document.
+//!
+//! Agents usually run several searches, fetch the best sections, and paste
+//! them into a prompt. [`pack`] does that last step against a token budget:
+//! hits from every query are taken in turn (each query's best hit first), spans
+//! already included for another query are shared instead of repeated, and
+//! sections are added until the budget runs out. A section that does not fit
+//! whole is cut at a line boundary when enough budget remains to be useful.
+//!
+//! Token counts are estimates ([`estimate_tokens`]) so the packer does not
+//! depend on any particular model's tokenizer.
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::hash::BuildHasher;
+
+use serde::{Deserialize, Serialize};
+
+use crate::SearchHit;
+
+/// Characters counted as one token when estimating.
+pub const CHARS_PER_TOKEN: usize = 4;
+
+/// Smallest remaining budget worth filling with a truncated section.
+const MIN_PARTIAL_TOKENS: usize = 48;
+
+/// Estimated token count of `text`.
+#[must_use]
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(CHARS_PER_TOKEN)
+}
+
+/// A search hit considered for packing.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PackCandidate {
+    /// Query that produced the hit.
+    pub query: String,
+    /// Canonical source alias.
+    pub source: String,
+    /// Heading path of the section.
+    pub heading_path: Vec<String>,
+    /// First line (1-based, inclusive).
+    pub start: usize,
+    /// Last line (1-based, inclusive).
+    pub end: usize,
+    /// Relevance score, used to order hits within a query.
+    pub score: f32,
+}
+
+impl PackCandidate {
+    /// Candidate for `hit`, or `None` when its line range cannot be parsed.
+    #[must_use]
+    pub fn from_hit(query: &str, hit: &SearchHit) -> Option<Self> {
+        let (start, end) = if let Some([start, end]) = hit.line_numbers.as_deref() {
+            (*start, *end)
+        } else {
+            let (start, end) = hit.lines.split_once('-')?;
+            (start.trim().parse().ok()?, end.trim().parse().ok()?)
+        };
+        (start >= 1 && end >= start).then(|| Self {
+            query: query.to_string(),
+            source: hit.source.clone(),
+            heading_path: hit.heading_path.clone(),
+            start,
+            end,
+            score: hit.score,
+        })
+    }
+}
+
+/// One section of a packed document.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PackedSection {
+    /// Citation for the included lines (`source:start-end`).
+    pub citation: String,
+    /// Canonical source alias.
+    pub source: String,
+    /// First included line (1-based).
+    pub line_start: usize,
+    /// Last included line (1-based, inclusive).
+    pub line_end: usize,
+    /// Heading path of the section.
+    pub heading_path: Vec<String>,
+    /// Queries this section answers.
+    pub queries: Vec<String>,
+    /// Estimated tokens used by the section, including its header.
+    pub tokens: usize,
+    /// Whether trailing lines were dropped to fit the budget.
+    pub truncated: bool,
+}
+
+/// A cited document assembled within a token budget.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContextPack {
+    /// Markdown document with a cited header per section.
+    pub document: String,
+    /// Sections in document order.
+    pub sections: Vec<PackedSection>,
+    /// Estimated tokens in `document`.
+    pub tokens_used: usize,
+    /// Budget the document was packed into.
+    pub token_budget: usize,
+    /// Candidates left out because the budget was exhausted.
+    pub omitted: usize,
+}
+
+/// Pack `candidates` into a document of at most `token_budget` estimated tokens.
+///
+/// `lines` maps each source alias to its document lines; candidates whose
+/// source is missing or whose range lies outside the document are skipped.
+#[must_use]
+pub fn pack<S: AsRef<str>, H: BuildHasher>(
+    candidates: Vec<PackCandidate>,
+    lines: &HashMap<String, Vec<S>, H>,
+    token_budget: usize,
+) -> ContextPack {
+    let mut sections: Vec<(PackedSection, String)> = Vec::new();
+    let mut used = 0;
+    let mut omitted = 0;
+
+    for candidate in interleave(candidates) {
+        let Some(source_lines) = lines.get(&candidate.source) else {
+            continue;
+        };
+        let end = candidate.end.min(source_lines.len());
+        if candidate.start > end {
+            continue;
+        }
+        if let Some((section, _)) = sections.iter_mut().find(|(section, _)| {
+            section.source == candidate.source
+                && candidate.start.max(section.line_start) <= candidate.end.min(section.line_end)
+        }) {
+            if !section.queries.contains(&candidate.query) {
+                section.queries.push(candidate.query);
+            }
+            continue;
+        }
+
+        // Sections are separated by a blank line, budgeted as one token
+        let separator = usize::from(!sections.is_empty());
+        let remaining = token_budget.saturating_sub(used + separator);
+        let body_lines = &source_lines[candidate.start - 1..end];
+        let Some((text, line_end)) = render_section(&candidate, body_lines, remaining) else {
+            omitted += 1;
+            continue;
+        };
+        let tokens = estimate_tokens(&text);
+        used += tokens + separator;
+        sections.push((
+            PackedSection {
+                citation: format!("{}:{}-{line_end}", candidate.source, candidate.start),
+                source: candidate.source,
+                line_start: candidate.start,
+                line_end,
+                heading_path: candidate.heading_path,
+                queries: vec![candidate.query],
+                tokens,
+                truncated: line_end < end,
+            },
+            text,
+        ));
+    }
+
+    let document = sections
+        .iter()
+        .map(|(_, text)| text.as_str())
+        .collect::<Vec<_>>()
+        .join("\n");
+    ContextPack {
+        tokens_used: estimate_tokens(&document),
+        document,
+        sections: sections.into_iter().map(|(section, _)| section).collect(),
+        token_budget,
+        omitted,
+    }
+}
+
+/// Order candidates so every query contributes its best hit before any query
+/// contributes its second.
+fn interleave(candidates: Vec<PackCandidate>) -> Vec<PackCandidate> {
+    let mut queries: Vec<String> = Vec::new();
+    let mut by_query: HashMap<String, Vec<PackCandidate>> = HashMap::new();
+    for candidate in candidates {
+        if !by_query.contains_key(&candidate.query) {
+            queries.push(candidate.query.clone());
+        }
+        by_query
+            .entry(candidate.query.clone())
+            .or_default()
+            .push(candidate);
+    }
+    let mut ranked: Vec<(usize, usize, PackCandidate)> = Vec::new();
+    for (query_index, query) in queries.iter().enumerate() {
+        let mut hits = by_query.remove(query).unwrap_or_default();
+        hits.sort_by(|a, b| b.score.total_cmp(&a.score));
+        ranked.extend(
+            hits.into_iter()
+                .enumerate()
+                .map(|(rank, hit)| (rank, query_index, hit)),
+        );
+    }
+    ranked.sort_by_key(|(rank, query_index, _)| (*rank, *query_index));
+    ranked.into_iter().map(|(_, _, hit)| hit).collect()
+}
+
+/// Render a section within `budget` tokens, dropping trailing lines if needed.
+///
+/// Returns the text and the last included line, or `None` when not even a
+/// useful part of the section fits.
+fn render_section<S: AsRef<str>>(
+    candidate: &PackCandidate,
+    body: &[S],
+    budget: usize,
+) -> Option<(String, usize)> {
+    let mut header = String::from("## ");
+    if candidate.heading_path.is_empty() {
+        header.push_str(&candidate.source);
+    } else {
+        header.push_str(&candidate.heading_path.join(" > "));
+    }
+    header.push('\n');
+
+    let full_end = candidate.start + body.len() - 1;
+    let full = section_text(&header, candidate, full_end, body);
+    if estimate_tokens(&full) <= budget {
+        return Some((full, full_end));
+    }
+    if budget < MIN_PARTIAL_TOKENS {
+        return None;
+    }
+
+    // The citation only shrinks as lines are dropped, so measuring against the
+    // full citation keeps the result within budget.
+    let mut chars =
+        estimate_tokens(&section_text(&header, candidate, full_end, &body[..0])) * CHARS_PER_TOKEN;
+    let limit = budget * CHARS_PER_TOKEN;
+    let mut kept = 0;
+    for line in body {
+        let len = line.as_ref().chars().count() + 1;
+        if chars + len > limit {
+            break;
+        }
+        chars += len;
+        kept += 1;
+    }
+    if kept == 0 {
+        return None;
+    }
+    let end = candidate.start + kept - 1;
+    Some((section_text(&header, candidate, end, &body[..kept]), end))
+}
+
+fn section_text<S: AsRef<str>>(
+    header: &str,
+    candidate: &PackCandidate,
+    end: usize,
+    body: &[S],
+) -> String {
+    let mut text = header.to_string();
+    let _ = writeln!(
+        text,
+        "Source: {}:{}-{end}\n",
+        candidate.source, candidate.start
+    );
+    for line in body {
+        text.push_str(line.as_ref());
+        text.push('\n');
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(query: &str, source: &str, start: usize, end: usize, score: f32) -> PackCandidate {
+        PackCandidate {
+            query: query.to_string(),
+            source: source.to_string(),
+            heading_path: vec!["Guide".to_string(), format!("Part {start}")],
+            start,
+            end,
+            score,
+        }
+    }
+
+    fn docs() -> HashMap<String, Vec<String>> {
+        let lines = (1..=40).map(|n| format!("line {n} of the docs")).collect();
+        HashMap::from([("docs".to_string(), lines)])
+    }
+
+    #[test]
+    fn interleaves_queries_and_shares_overlapping_sections() {
+        let candidates = vec![
+            candidate("alpha", "docs", 1, 4, 9.0),
+            candidate("alpha", "docs", 10, 12, 5.0),
+            candidate("beta", "docs", 20, 22, 7.0),
+            candidate("beta", "docs", 2, 3, 1.0),
+        ];
+        let pack = pack(candidates, &docs(), 10_000);
+
+        let citations: Vec<&str> = pack.sections.iter().map(|s| s.citation.as_str()).collect();
+        assert_eq!(citations, ["docs:1-4", "docs:20-22", "docs:10-12"]);
+        assert_eq!(pack.sections[0].queries, ["alpha", "beta"]);
+        assert!(
+            pack.document
+                .contains("## Guide > Part 1\nSource: docs:1-4\n")
+        );
+        assert_eq!(pack.omitted, 0);
+        assert_eq!(pack.tokens_used, estimate_tokens(&pack.document));
+    }
+
+    #[test]
+    fn truncates_and_omits_to_respect_budget() {
+        let candidates = vec![
+            candidate("alpha", "docs", 1, 40, 9.0),
+            candidate("beta", "docs", 5, 5, 3.0),
+            candidate("gamma", "missing", 1, 2, 8.0),
+        ];
+        let pack = pack(candidates, &docs(), 100);
+
+        assert!(pack.tokens_used <= 100, "{}", pack.tokens_used);
+        assert_eq!(pack.sections.len(), 1);
+        let section = &pack.sections[0];
+        assert!(section.truncated);
+        assert!(section.line_end < 40);
+        assert_eq!(section.citation, format!("docs:1-{}", section.line_end));
+        // The overlapping hit is attributed rather than repeated.
+        assert_eq!(section.queries, ["alpha", "beta"]);
+
+        let tiny = pack_with_budget(10);
+        assert!(tiny.sections.is_empty());
+        assert_eq!(tiny.omitted, 1);
+    }
+
+    fn pack_with_budget(budget: usize) -> ContextPack {
+        pack(
+            vec![candidate("alpha", "docs", 1, 40, 1.0)],
+            &docs(),
+            budget,
+        )
+    }
+}
@@ -0,0 +1,473 @@
+//! Documentation sources for a project's dependencies.
+//!
+//! Each supported package ecosystem knows how to list the dependencies in its
+//! manifest and where its packages tend to publish docs:
+//!
+//! | Manifest         | Ecosystem | Resolved through                                  |
+//! |------------------|-----------|---------------------------------------------------|
+//! | `package.json`   | npm       | registry, then `{name}.dev`, `{name}.js.org`      |
+//! | `Cargo.toml`     | Cargo     | registry, then the crate's docs.rs rustdoc JSON   |
+//! | `pyproject.toml` | Python    | registry, then `{name}.readthedocs.io`, `{name}.dev` |
+//!
+//! Candidate domains are checked with [`probe_domain_with`], which also tries
+//! their `docs.` subdomain, and only count when they serve an llms.txt or
+//! llms-full.txt.
+//!
+//! ```rust
+//! use blz_core::discovery::ecosystem::Ecosystem;
+//!
+//! let deps = Ecosystem::Npm
+//!     .dependencies(r#"{"dependencies": {"hono": "^4"}}"#)
+//!     .unwrap();
+//! assert_eq!(deps[0].name, "hono");
+//! assert_eq!(Ecosystem::Npm.candidate_domains("hono"), ["hono.dev", "hono.js.org"]);
+//! ```
+
+use std::path::Path;
+
+use super::probe::{ProbeTargets, probe_domain_with};
+use crate::registry::{Registry, RegistryEntry};
+use crate::rustdoc::CRATE_PREFIX;
+use crate::{Error, Result};
+
+/// A package ecosystem with a dependency manifest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ecosystem {
+    /// JavaScript packages declared in `package.json`.
+    Npm,
+    /// Rust crates declared in `Cargo.toml`.
+    Cargo,
+    /// Python packages declared in `pyproject.toml`.
+    Python,
+}
+
+/// A dependency declared in a project manifest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dependency {
+    /// Package name as written in the manifest.
+    pub name: String,
+    /// Ecosystem the manifest belongs to.
+    pub ecosystem: Ecosystem,
+}
+
+/// How a dependency was matched to a documentation source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResolvedBy {
+    /// A built-in registry entry.
+    Registry,
+    /// The crate's rustdoc JSON on docs.rs.
+    DocsRs,
+    /// An llms.txt found by probing a candidate domain.
+    Probe {
+        /// Domain that was probed.
+        domain: String,
+    },
+}
+
+/// A documentation source proposed for a dependency.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Resolution {
+    /// Dependency the source documents.
+    pub dependency: Dependency,
+    /// Suggested alias.
+    pub alias: String,
+    /// URL or `crate:` spec to pass to `blz add`.
+    pub location: String,
+    /// How the source was found.
+    pub resolved_by: ResolvedBy,
+}
+
+impl Ecosystem {
+    /// Every supported ecosystem, in the order project manifests are read.
+    pub const ALL: [Self; 3] = [Self::Npm, Self::Cargo, Self::Python];
+
+    /// File name of the ecosystem's manifest.
+    #[must_use]
+    pub const fn manifest_name(self) -> &'static str {
+        match self {
+            Self::Npm => "package.json",
+            Self::Cargo => "Cargo.toml",
+            Self::Python => "pyproject.toml",
+        }
+    }
+
+    /// Ecosystem whose manifest `path` names, by file name.
+    #[must_use]
+    pub fn from_manifest(path: &Path) -> Option<Self> {
+        let file_name = path.file_name()?.to_str()?;
+        Self::ALL
+            .into_iter()
+            .find(|ecosystem| ecosystem.manifest_name() == file_name)
+    }
+
+    /// Dependencies declared in manifest `text`.
+    ///
+    /// npm reads `dependencies`, `devDependencies`, and `peerDependencies`;
+    /// Cargo the regular, dev, build, and workspace dependency tables; Python
+    /// PEP 621 `project.dependencies` and Poetry's dependency table. Names are
+    /// returned in manifest order without duplicates.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the manifest is not valid JSON or TOML.
+    pub fn dependencies(self, text: &str) -> Result<Vec<Dependency>> {
+        let names = match self {
+            Self::Npm => npm_dependencies(text)?,
+            Self::Cargo => cargo_dependencies(text)?,
+            Self::Python => python_dependencies(text)?,
+        };
+        let mut deps: Vec<Dependency> = Vec::with_capacity(names.len());
+        for name in names {
+            if !deps.iter().any(|dep| dep.name == name) {
+                deps.push(Dependency {
+                    name,
+                    ecosystem: self,
+                });
+            }
+        }
+        Ok(deps)
+    }
+
+    /// Domains worth probing for a package's docs.
+    ///
+    /// Crates are resolved through docs.rs instead, so Cargo has none.
+    #[must_use]
+    pub fn candidate_domains(self, name: &str) -> Vec<String> {
+        let name = package_stem(name);
+        if name.is_empty() {
+            return Vec::new();
+        }
+        match self {
+            Self::Npm => vec![format!("{name}.dev"), format!("{name}.js.org")],
+            Self::Cargo => Vec::new(),
+            Self::Python => vec![format!("{name}.readthedocs.io"), format!("{name}.dev")],
+        }
+    }
+}
+
+impl Dependency {
+    /// Name without an npm scope, lowercased, with Python's `_`/`.` folded to `-`.
+    #[must_use]
+    pub fn normalized_name(&self) -> String {
+        let name = self.name.rsplit('/').next().unwrap_or(&self.name);
+        let name = name.to_ascii_lowercase();
+        match self.ecosystem {
+            Ecosystem::Python => name.replace(['_', '.'], "-"),
+            Ecosystem::Npm | Ecosystem::Cargo => name,
+        }
+    }
+
+    /// Whether `candidate` (an alias or registry name) names this dependency.
+    ///
+    /// Scoped npm packages also match on their unscoped name.
+    #[must_use]
+    pub fn matches(&self, candidate: &str) -> bool {
+        candidate.eq_ignore_ascii_case(&self.name) || candidate == self.normalized_name()
+    }
+
+    /// Whether the package only carries type declarations (`@types/*`), so it
+    /// has no docs of its own.
+    #[must_use]
+    pub fn is_typings(&self) -> bool {
+        self.ecosystem == Ecosystem::Npm && self.name.starts_with("@types/")
+    }
+}
+
+/// Dependencies declared in every manifest present in `dir`.
+///
+/// Missing manifests are skipped; unreadable ones are logged and skipped.
+#[must_use]
+pub fn project_dependencies(dir: &Path) -> Vec<Dependency> {
+    let mut deps = Vec::new();
+    for ecosystem in Ecosystem::ALL {
+        let path = dir.join(ecosystem.manifest_name());
+        let Ok(text) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        match ecosystem.dependencies(&text) {
+            Ok(found) => deps.extend(found),
+            Err(err) => tracing::warn!("Skipping {}: {err}", path.display()),
+        }
+    }
+    deps
+}
+
+/// Dependencies declared in the manifest at `path`.
+///
+/// # Errors
+///
+/// Returns an error if the file is not a supported manifest, cannot be read,
+/// or cannot be parsed.
+pub fn manifest_dependencies(path: &Path) -> Result<Vec<Dependency>> {
+    let ecosystem = Ecosystem::from_manifest(path).ok_or_else(|| {
+        Error::Config(format!(
+            "Unsupported manifest '{}': expected package.json, Cargo.toml, or pyproject.toml",
+            path.display()
+        ))
+    })?;
+    let text = std::fs::read_to_string(path)?;
+    ecosystem.dependencies(&text)
+}
+
+/// Registry entry documenting `dependency`, matched on slug or aliases.
+#[must_use]
+pub fn registry_match<'a>(
+    registry: &'a Registry,
+    dependency: &Dependency,
+) -> Option<&'a RegistryEntry> {
+    registry.all_entries().iter().find(|entry| {
+        dependency.matches(&entry.slug)
+            || entry.aliases.iter().any(|alias| dependency.matches(alias))
+    })
+}
+
+/// Find a documentation source for `dependency`.
+///
+/// Tries the registry, then docs.rs for crates, then (when `probe` is given)
+/// each candidate domain in turn. Returns `None` when nothing was found.
+///
+/// # Errors
+///
+/// Returns an error if the probe HTTP client cannot be constructed.
+pub async fn resolve_dependency(
+    dependency: &Dependency,
+    registry: &Registry,
+    probe: Option<&ProbeTargets>,
+) -> Result<Option<Resolution>> {
+    let resolution = |alias: String, location: String, resolved_by: ResolvedBy| Resolution {
+        dependency: dependency.clone(),
+        alias,
+        location,
+        resolved_by,
+    };
+
+    if let Some(entry) = registry_match(registry, dependency) {
+        return Ok(Some(resolution(
+            entry.slug.clone(),
+            entry.llms_url.clone(),
+            ResolvedBy::Registry,
+        )));
+    }
+    let name = dependency.normalized_name();
+    if dependency.ecosystem == Ecosystem::Cargo {
+        return Ok(Some(resolution(
+            name.clone(),
+            format!("{CRATE_PREFIX}{name}"),
+            ResolvedBy::DocsRs,
+        )));
+    }
+
+    let Some(targets) = probe else {
+        return Ok(None);
+    };
+    for domain in dependency.ecosystem.candidate_domains(&dependency.name) {
+        let result = probe_domain_with(&domain, targets).await?;
+        // A sitemap alone needs `blz generate`, not `blz add`
+        let url = result.llms_full_url.or(result.llms_url);
+        if let Some(url) = url {
+            return Ok(Some(resolution(
+                package_stem(&dependency.name),
+                url,
+                ResolvedBy::Probe { domain },
+            )));
+        }
+    }
+    Ok(None)
+}
+
+/// Package name reduced to something usable in a domain: unscoped,
+/// lowercased, with a trailing `.js`/`js` and non-alphanumerics dropped.
+fn package_stem(name: &str) -> String {
+    let name = name.rsplit('/').next().unwrap_or(name).to_ascii_lowercase();
+    let name = name
+        .strip_suffix(".js")
+        .or_else(|| name.strip_suffix("-js"))
+        .unwrap_or(&name);
+    name.chars()
+        .filter(|ch| ch.is_ascii_alphanumeric() || *ch == '-')
+        .collect::<String>()
+        .trim_matches('-')
+        .to_string()
+}
+
+fn npm_dependencies(text: &str) -> Result<Vec<String>> {
+    let json: serde_json::Value = serde_json::from_str(text)
+        .map_err(|err| Error::Parse(format!("invalid package.json: {err}")))?;
+    // Workspace and local packages have no published docs
+    let is_local = |version: &serde_json::Value| {
+        version.as_str().is_some_and(|v| {
+            ["workspace:", "file:", "link:"]
+                .iter()
+                .any(|p| v.starts_with(p))
+        })
+    };
+    Ok(["dependencies", "devDependencies", "peerDependencies"]
+        .into_iter()
+        .filter_map(|table| json.get(table).and_then(serde_json::Value::as_object))
+        .flat_map(|map| map.iter())
+        .filter(|(_, version)| !is_local(version))
+        .map(|(name, _)| name.clone())
+        .collect())
+}
+
+fn cargo_dependencies(text: &str) -> Result<Vec<String>> {
+    let cargo: toml::Value =
+        toml::from_str(text).map_err(|err| Error::Parse(format!("invalid Cargo.toml: {err}")))?;
+    let tables = [
+        cargo.get("dependencies"),
+        cargo.get("dev-dependencies"),
+        cargo.get("build-dependencies"),
+        cargo.get("workspace").and_then(|w| w.get("dependencies")),
+    ];
+    Ok(tables
+        .into_iter()
+        .flatten()
+        .filter_map(toml::Value::as_table)
+        .flat_map(|table| table.iter())
+        // Path dependencies are the project's own crates
+        .filter(|(_, spec)| spec.get("path").is_none())
+        .map(|(name, _)| name.clone())
+        .collect())
+}
+
+fn python_dependencies(text: &str) -> Result<Vec<String>> {
+    let pyproject: toml::Value = toml::from_str(text)
+        .map_err(|err| Error::Parse(format!("invalid pyproject.toml: {err}")))?;
+    let mut names = Vec::new();
+    let requirements = pyproject
+        .get("project")
+        .and_then(|p| p.get("dependencies"))
+        .and_then(toml::Value::as_array);
+    for requirement in requirements
+        .into_iter()
+        .flatten()
+        .filter_map(toml::Value::as_str)
+    {
+        let name: String = requirement
+            .chars()
+            .take_while(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+            .collect();
+        if !name.is_empty() {
+            names.push(name);
+        }
+    }
+    let poetry = pyproject
+        .get("tool")
+        .and_then(|t| t.get("poetry"))
+        .and_then(|p| p.get("dependencies"))
+        .and_then(toml::Value::as_table);
+    if let Some(table) = poetry {
+        names.extend(
+            table
+                .keys()
+                .filter(|name| name.as_str() != "python")
+                .cloned(),
+        );
+    }
+    Ok(names)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn dep(name: &str, ecosystem: Ecosystem) -> Dependency {
+        Dependency {
+            name: name.to_string(),
+            ecosystem,
+        }
+    }
+
+    #[test]
+    fn test_reads_dependencies_from_each_ecosystem() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("package.json"),
+            r#"{"dependencies": {"react": "^19", "ui": "workspace:*"}, "devDependencies": {"@types/node": "^22"}}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("Cargo.toml"),
+            "[package]\nname = \"app\"\n\n[dependencies]\nserde = \"1\"\nutil = { path = \"../util\" }\n\n[dev-dependencies]\nserde = \"1\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("pyproject.toml"),
+            "[project]\nname = \"svc\"\ndependencies = [\"pydantic>=2\", \"openai\"]\n",
+        )
+        .unwrap();
+
+        let names: Vec<String> = project_dependencies(dir.path())
+            .into_iter()
+            .map(|dep| dep.name)
+            .collect();
+        assert_eq!(
+            names,
+            ["react", "@types/node", "serde", "pydantic", "openai"]
+        );
+    }
+
+    #[test]
+    fn test_manifest_kind_comes_from_file_name() {
+        assert_eq!(
+            Ecosystem::from_manifest(Path::new("web/package.json")),
+            Some(Ecosystem::Npm)
+        );
+        assert_eq!(
+            Ecosystem::from_manifest(Path::new("Cargo.toml")),
+            Some(Ecosystem::Cargo)
+        );
+        assert_eq!(Ecosystem::from_manifest(Path::new("Gemfile")), None);
+        assert!(manifest_dependencies(Path::new("requirements.txt")).is_err());
+    }
+
+    #[test]
+    fn test_candidate_domains_use_package_stem() {
+        assert_eq!(
+            Ecosystem::Npm.candidate_domains("@tanstack/query"),
+            ["query.dev", "query.js.org"]
+        );
+        assert_eq!(
+            Ecosystem::Npm.candidate_domains("chart.js"),
+            ["chart.dev", "chart.js.org"]
+        );
+        assert_eq!(
+            Ecosystem::Python.candidate_domains("Flask"),
+            ["flask.readthedocs.io", "flask.dev"]
+        );
+        assert!(Ecosystem::Cargo.candidate_domains("serde").is_empty());
+    }
+
+    #[test]
+    fn test_dependency_names_match_registry_aliases() {
+        assert!(dep("@scope/react", Ecosystem::Npm).matches("react"));
+        assert!(dep("typing_extensions", Ecosystem::Python).matches("typing-extensions"));
+        assert!(!dep("preact", Ecosystem::Npm).matches("react"));
+        assert!(dep("@types/node", Ecosystem::Npm).is_typings());
+    }
+
+    #[tokio::test]
+    async fn test_resolves_registry_entries_and_crates_without_probing() {
+        let registry = Registry::new();
+
+        let react = resolve_dependency(&dep("react", Ecosystem::Npm), &registry, None)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(react.alias, "react");
+        assert_eq!(react.resolved_by, ResolvedBy::Registry);
+
+        let serde = resolve_dependency(&dep("serde_json", Ecosystem::Cargo), &registry, None)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(serde.location, "crate:serde_json");
+        assert_eq!(serde.resolved_by, ResolvedBy::DocsRs);
+
+        let unknown = resolve_dependency(&dep("left-pad", Ecosystem::Npm), &registry, None)
+            .await
+            .unwrap();
+        assert!(unknown.is_none());
+    }
+}
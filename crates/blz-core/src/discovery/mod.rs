@@ -31,6 +31,19 @@
 //! # }
 //! ```
 //!
+//! ## Project Dependencies
+//!
+//! The [`ecosystem`] module reads `package.json`, `Cargo.toml`, and
+//! `pyproject.toml` and proposes a source for each dependency, from the
+//! registry, docs.rs, or by probing the package's likely docs domains.
+//!
+//! ## robots.txt
+//!
+//! Probing, link following, and sitemap scraping consult each host's
+//! robots.txt through a shared [`RobotsPolicy`] (see [`robots`]), which also
+//! spaces requests by the host's `Crawl-delay`. Probes skip disallowed paths
+//! unless the targets opt out with [`ProbeTargets::ignoring_robots`].
+//!
 //! ## Probe Order
 //!
 //! The [`probe_domain`] function checks URLs in this order:
@@ -40,6 +53,10 @@
 //! 3. `https://{domain}/sitemap.xml` - URL discovery fallback
 //! 4. `https://docs.{domain}/*` - Subdomain fallback if main domain has nothing
 //!
+//! Each step also checks `/.well-known/` and `/docs/` variants (plus `ai.txt`)
+//! in parallel; see [`DEFAULT_PROBE_PATHS`]. Pass custom [`ProbeTargets`] to
+//! [`probe_domain_with`] or [`probe_url_with`] to change the list.
+//!
 //! ## Smart URL Resolution
 //!
 //! The [`probe_url`] function provides smarter resolution when given a URL with a path:
@@ -65,15 +82,28 @@
 //! ```
 
 pub mod alias;
+pub mod ecosystem;
 pub mod extract;
 pub mod filter;
 pub mod probe;
+pub mod robots;
 pub mod sitemap;
 
 pub use alias::{
     AliasDerivation, derive_alias, derive_alias_with_collision_check, has_collision, is_valid_alias,
 };
+pub use ecosystem::{
+    Dependency, Ecosystem, Resolution, ResolvedBy, manifest_dependencies, project_dependencies,
+    resolve_dependency,
+};
 pub use extract::{DiscoveredUrl, UrlSource, extract_urls, merge_url_sources};
 pub use filter::{filter_to_docs, filter_to_domain, is_likely_docs_path};
-pub use probe::{DiscoveryMethod, ProbeResult, probe_domain, probe_url};
-pub use sitemap::{ChangeFrequency, SitemapEntry, fetch_sitemap, is_sitemap_index, parse_sitemap};
+pub use probe::{
+    DEFAULT_PROBE_PATHS, DiscoveryMethod, ProbeKind, ProbeResult, ProbeTargets, probe_domain,
+    probe_domain_with, probe_url, probe_url_with,
+};
+pub use robots::{RobotsPolicy, RobotsTxt};
+pub use sitemap::{
+    ChangeFrequency, SitemapEntry, SitemapOptions, fetch_sitemap, fetch_sitemap_with,
+    is_sitemap_index, order_entries, parse_sitemap,
+};
@@ -14,8 +14,21 @@
 //! 4. **Domain root** - Suggest checking the parent domain (requires confirmation)
 //!
 //! This ensures we find native llms.txt files before falling back to generation.
-
+//!
+//! ## Probe Targets
+//!
+//! Host-level probes check every path in [`ProbeTargets`] with parallel HEAD
+//! requests. The defaults ([`DEFAULT_PROBE_PATHS`]) cover the root, the
+//! `/.well-known/` directory, `/docs/`, and `ai.txt` variants; each path is
+//! classified by its file name (`*-full.txt`, `*.xml`, anything else as an
+//! index) and the first hit of each kind in list order wins. Paths the host's
+//! robots.txt disallows are skipped unless the targets were built with
+//! [`ProbeTargets::ignoring_robots`]; `Crawl-delay` does not apply to the
+//! handful of HEAD requests a probe makes.
+
+use super::robots::RobotsPolicy;
 use crate::{Error, Result};
+use futures::future::join_all;
 use reqwest::{Client, StatusCode};
 use std::time::Duration;
 use tracing::{debug, instrument};
@@ -24,6 +37,148 @@ use url::Url;
 /// Default timeout for probe requests (5 seconds per URL).
 const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
 
+/// Paths probed at a host by default, in preference order.
+pub const DEFAULT_PROBE_PATHS: &[&str] = &[
+    "/llms-full.txt",
+    "/.well-known/llms-full.txt",
+    "/docs/llms-full.txt",
+    "/llms.txt",
+    "/.well-known/llms.txt",
+    "/docs/llms.txt",
+    "/ai.txt",
+    "/.well-known/ai.txt",
+    "/sitemap.xml",
+    "/sitemap_index.xml",
+];
+
+/// What kind of document a probe path points at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProbeKind {
+    /// Complete documentation (`llms-full.txt` style).
+    Full,
+    /// Documentation index (`llms.txt`, `ai.txt`, ...).
+    Index,
+    /// Sitemap used for URL discovery.
+    Sitemap,
+}
+
+impl ProbeKind {
+    /// Classify a probe path by its file name.
+    #[must_use]
+    pub fn of(path: &str) -> Self {
+        let file = path.rsplit('/').next().unwrap_or(path).to_ascii_lowercase();
+        if file.ends_with("-full.txt") {
+            Self::Full
+        } else if std::path::Path::new(&file)
+            .extension()
+            .is_some_and(|ext| ext == "xml")
+        {
+            Self::Sitemap
+        } else {
+            Self::Index
+        }
+    }
+}
+
+/// Paths to probe at each host, in preference order.
+///
+/// # Examples
+///
+/// ```
+/// use blz_core::discovery::{ProbeKind, ProbeTargets};
+///
+/// let targets = ProbeTargets::new(["llms.txt", "/docs/llms-full.txt"]);
+/// assert_eq!(targets.paths(), ["/llms.txt", "/docs/llms-full.txt"]);
+/// assert_eq!(targets.of_kind(ProbeKind::Full).collect::<Vec<_>>(), ["/docs/llms-full.txt"]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProbeTargets {
+    paths: Vec<String>,
+    respect_robots: bool,
+}
+
+impl Default for ProbeTargets {
+    fn default() -> Self {
+        Self::new(DEFAULT_PROBE_PATHS.iter().copied())
+    }
+}
+
+impl ProbeTargets {
+    /// Build a target list, adding a leading `/` where missing and dropping
+    /// blanks and duplicates.
+    #[must_use]
+    pub fn new<I, S>(paths: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut normalized: Vec<String> = Vec::new();
+        for path in paths {
+            let path = path.as_ref().trim();
+            if path.is_empty() {
+                continue;
+            }
+            let path = if path.starts_with('/') {
+                path.to_string()
+            } else {
+                format!("/{path}")
+            };
+            if !normalized.contains(&path) {
+                normalized.push(path);
+            }
+        }
+        Self {
+            paths: normalized,
+            respect_robots: true,
+        }
+    }
+
+    /// Configured paths, or the defaults when `paths` is empty.
+    #[must_use]
+    pub fn from_config(paths: &[String]) -> Self {
+        if paths.is_empty() {
+            Self::default()
+        } else {
+            Self::new(paths)
+        }
+    }
+
+    /// All paths in preference order.
+    #[must_use]
+    pub fn paths(&self) -> &[String] {
+        &self.paths
+    }
+
+    /// Paths of one kind, in preference order.
+    pub fn of_kind(&self, kind: ProbeKind) -> impl Iterator<Item = &str> {
+        self.paths
+            .iter()
+            .map(String::as_str)
+            .filter(move |path| ProbeKind::of(path) == kind)
+    }
+
+    /// Probe paths even where robots.txt disallows them (`--ignore-robots`).
+    #[must_use]
+    pub const fn ignoring_robots(mut self, ignore: bool) -> Self {
+        self.respect_robots = !ignore;
+        self
+    }
+
+    /// Whether disallowed paths are skipped.
+    #[must_use]
+    pub const fn respects_robots(&self) -> bool {
+        self.respect_robots
+    }
+
+    fn robots_policy(&self, client: &Client) -> RobotsPolicy {
+        if self.respect_robots {
+            RobotsPolicy::with_client(client.clone())
+        } else {
+            RobotsPolicy::ignoring()
+        }
+    }
+}
+
 /// How the documentation source was discovered.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum DiscoveryMethod {
@@ -94,12 +249,14 @@ impl ProbeResult {
 /// Probe a domain to discover documentation sources.
 ///
 /// Checks in order:
-/// 1. `https://{domain}/llms-full.txt` (preferred - complete docs)
-/// 2. `https://{domain}/llms.txt` (fallback - index)
-/// 3. `https://{domain}/sitemap.xml` (for URL discovery)
+/// 1. `llms-full.txt` locations (preferred - complete docs)
+/// 2. `llms.txt` and `ai.txt` locations (fallback - index)
+/// 3. Sitemaps (for URL discovery)
 /// 4. `https://docs.{domain}/*` if main domain has nothing
 ///
-/// Uses HEAD requests for efficiency (doesn't download content during probe).
+/// Locations come from [`DEFAULT_PROBE_PATHS`]; use [`probe_domain_with`] to
+/// supply a custom list. Uses parallel HEAD requests for efficiency (doesn't
+/// download content during probe).
 ///
 /// # Arguments
 ///
@@ -129,18 +286,34 @@ impl ProbeResult {
 /// ```
 #[instrument(skip_all, fields(domain = %domain))]
 pub async fn probe_domain(domain: &str) -> Result<ProbeResult> {
+    probe_domain_with(domain, &ProbeTargets::default()).await
+}
+
+/// Probe a domain using a custom list of [`ProbeTargets`].
+///
+/// # Errors
+///
+/// Returns an error if the HTTP client cannot be constructed.
+#[instrument(skip_all, fields(domain = %domain))]
+pub async fn probe_domain_with(domain: &str, targets: &ProbeTargets) -> Result<ProbeResult> {
     let normalized = normalize_domain(domain);
     let client = build_probe_client()?;
+    let robots = targets.robots_policy(&client);
 
     // Probe main domain first
-    let mut result = probe_single_domain(&client, &normalized).await?;
+    let mut result = probe_single_domain(&client, &robots, &normalized, targets).await?;
 
     // If nothing found on main domain, try docs.* subdomain
     if !result.has_source() && !normalized.starts_with("docs.") {
         let docs_domain = format!("docs.{normalized}");
-        let docs_result =
-            probe_single_domain_with_method(&client, &docs_domain, DiscoveryMethod::DocsSubdomain)
-                .await?;
+        let docs_result = probe_single_domain_with_method(
+            &client,
+            &robots,
+            &docs_domain,
+            DiscoveryMethod::DocsSubdomain,
+            targets,
+        )
+        .await?;
 
         if docs_result.has_source() {
             result.llms_full_url = docs_result.llms_full_url;
@@ -195,13 +368,27 @@ pub async fn probe_domain(domain: &str) -> Result<ProbeResult> {
 /// ```
 #[instrument(skip_all, fields(url = %url))]
 pub async fn probe_url(url: &str) -> Result<ProbeResult> {
+    probe_url_with(url, &ProbeTargets::default()).await
+}
+
+/// Probe a URL with smart resolution using a custom list of [`ProbeTargets`].
+///
+/// The targets apply to host-level probes (host root, docs subdomain, parent
+/// domain); Link headers and path-relative probing are unchanged.
+///
+/// # Errors
+///
+/// Returns an error if the HTTP client cannot be constructed.
+#[instrument(skip_all, fields(url = %url))]
+pub async fn probe_url_with(url: &str, targets: &ProbeTargets) -> Result<ProbeResult> {
     let client = build_probe_client()?;
+    let robots = targets.robots_policy(&client);
 
     // Parse the URL to extract components
     let Ok(parsed) = Url::parse(url) else {
         // If it's not a valid URL, treat it as a domain
         debug!("Input is not a valid URL, treating as domain");
-        return probe_domain(url).await;
+        return probe_domain_with(url, targets).await;
     };
 
     // Get host with port (needed for mock servers and non-standard ports)
@@ -227,7 +414,7 @@ pub async fn probe_url(url: &str) -> Result<ProbeResult> {
     // Step 2: If URL has a non-trivial path, probe relative to that path
     if path.len() > 1 && path != "/" {
         let path_base = path.trim_end_matches('/');
-        if let Some(result) = probe_path_relative(&client, &parsed, path_base).await? {
+        if let Some(result) = probe_path_relative(&client, &robots, &parsed, path_base).await? {
             debug!(path = %path_base, "Found documentation at path-relative location");
             return Ok(ProbeResult {
                 original_url: Some(original_url),
@@ -237,7 +424,7 @@ pub async fn probe_url(url: &str) -> Result<ProbeResult> {
     }
 
     // Step 3: Probe host root
-    let host_result = probe_single_domain(&client, &host).await?;
+    let host_result = probe_single_domain(&client, &robots, &host, targets).await?;
     if host_result.has_source() {
         debug!("Found documentation at host root");
         return Ok(ProbeResult {
@@ -249,9 +436,14 @@ pub async fn probe_url(url: &str) -> Result<ProbeResult> {
     // Step 4: Try docs.* subdomain (use host without port for subdomain construction)
     if !host_without_port.starts_with("docs.") {
         let docs_host = format!("docs.{host_without_port}");
-        let docs_result =
-            probe_single_domain_with_method(&client, &docs_host, DiscoveryMethod::DocsSubdomain)
-                .await?;
+        let docs_result = probe_single_domain_with_method(
+            &client,
+            &robots,
+            &docs_host,
+            DiscoveryMethod::DocsSubdomain,
+            targets,
+        )
+        .await?;
         if docs_result.has_source() {
             debug!(docs_host = %docs_host, "Found documentation at docs subdomain");
             return Ok(ProbeResult {
@@ -265,9 +457,14 @@ pub async fn probe_url(url: &str) -> Result<ProbeResult> {
     // Step 5: If we're on a subdomain, suggest checking the parent domain
     // This requires user confirmation since we're leaving the original scope
     if let Some(parent_domain) = extract_parent_domain(host_without_port) {
-        let parent_result =
-            probe_single_domain_with_method(&client, &parent_domain, DiscoveryMethod::ParentDomain)
-                .await?;
+        let parent_result = probe_single_domain_with_method(
+            &client,
+            &robots,
+            &parent_domain,
+            DiscoveryMethod::ParentDomain,
+            targets,
+        )
+        .await?;
         if parent_result.has_source() {
             debug!(parent_domain = %parent_domain, "Found documentation at parent domain (requires confirmation)");
             return Ok(ProbeResult {
@@ -408,6 +605,7 @@ fn resolve_link_url(base_url: &str, link_url: &str) -> String {
 /// Returns the domain without port in the result.
 async fn probe_path_relative(
     client: &Client,
+    robots: &RobotsPolicy,
     base: &Url,
     path: &str,
 ) -> Result<Option<ProbeResult>> {
@@ -429,8 +627,8 @@ async fn probe_path_relative(
     debug!(llms_full = %llms_full_url, llms = %llms_url, "Probing path-relative locations");
 
     let (full_exists, llms_exists) = tokio::join!(
-        probe_url_exists(client, &llms_full_url),
-        probe_url_exists(client, &llms_url),
+        probe_allowed_url_exists(client, robots, &llms_full_url),
+        probe_allowed_url_exists(client, robots, &llms_url),
     );
 
     if full_exists || llms_exists {
@@ -466,15 +664,23 @@ fn extract_parent_domain(host: &str) -> Option<String> {
 }
 
 /// Probe a single domain (without subdomain fallback).
-async fn probe_single_domain(client: &Client, domain: &str) -> Result<ProbeResult> {
-    probe_single_domain_with_method(client, domain, DiscoveryMethod::HostRoot).await
+async fn probe_single_domain(
+    client: &Client,
+    robots: &RobotsPolicy,
+    domain: &str,
+    targets: &ProbeTargets,
+) -> Result<ProbeResult> {
+    probe_single_domain_with_method(client, robots, domain, DiscoveryMethod::HostRoot, targets)
+        .await
 }
 
 /// Probe a single domain with a specific discovery method.
 async fn probe_single_domain_with_method(
     client: &Client,
+    robots: &RobotsPolicy,
     domain: &str,
     method: DiscoveryMethod,
+    targets: &ProbeTargets,
 ) -> Result<ProbeResult> {
     // Use http for localhost/loopback (testing), https for everything else
     let protocol = if domain.starts_with("127.0.0.1") || domain.starts_with("localhost") {
@@ -484,30 +690,32 @@ async fn probe_single_domain_with_method(
     };
     let base_url = format!("{protocol}://{domain}");
 
-    // Probe llms-full.txt and llms.txt in parallel
-    let llms_full_url = format!("{base_url}/llms-full.txt");
-    let llms_url = format!("{base_url}/llms.txt");
-    let sitemap_url = format!("{base_url}/sitemap.xml");
-
-    let (llms_full_exists, llms_exists) = tokio::join!(
-        probe_url_exists(client, &llms_full_url),
-        probe_url_exists(client, &llms_url),
+    // Probe all llms-full and index locations in parallel
+    let (llms_full_url, llms_url) = tokio::join!(
+        first_existing(client, robots, &base_url, targets.of_kind(ProbeKind::Full)),
+        first_existing(client, robots, &base_url, targets.of_kind(ProbeKind::Index)),
     );
 
-    // Only probe sitemap if neither llms file exists
-    let sitemap_exists = if !llms_full_exists && !llms_exists {
-        probe_url_exists(client, &sitemap_url).await
+    // Only probe sitemaps if no llms file exists
+    let sitemap_url = if llms_full_url.is_none() && llms_url.is_none() {
+        first_existing(
+            client,
+            robots,
+            &base_url,
+            targets.of_kind(ProbeKind::Sitemap),
+        )
+        .await
     } else {
-        false
+        None
     };
 
-    let has_source = llms_full_exists || llms_exists || sitemap_exists;
+    let has_source = llms_full_url.is_some() || llms_url.is_some() || sitemap_url.is_some();
 
     Ok(ProbeResult {
         domain: domain.to_string(),
-        llms_full_url: llms_full_exists.then_some(llms_full_url),
-        llms_url: llms_exists.then_some(llms_url),
-        sitemap_url: sitemap_exists.then_some(sitemap_url),
+        llms_full_url,
+        llms_url,
+        sitemap_url,
         docs_subdomain_checked: false,
         discovery_method: if has_source {
             method
@@ -519,6 +727,33 @@ async fn probe_single_domain_with_method(
     })
 }
 
+/// HEAD every path in parallel and return the first one (in list order) that exists.
+async fn first_existing<'a>(
+    client: &Client,
+    robots: &RobotsPolicy,
+    base_url: &str,
+    paths: impl Iterator<Item = &'a str>,
+) -> Option<String> {
+    let urls: Vec<String> = paths.map(|path| format!("{base_url}{path}")).collect();
+    let found = join_all(
+        urls.iter()
+            .map(|url| probe_allowed_url_exists(client, robots, url)),
+    )
+    .await;
+    urls.into_iter()
+        .zip(found)
+        .find_map(|(url, exists)| exists.then_some(url))
+}
+
+/// [`probe_url_exists`] for URLs robots.txt allows; disallowed URLs count as
+/// missing.
+async fn probe_allowed_url_exists(client: &Client, robots: &RobotsPolicy, url: &str) -> bool {
+    match Url::parse(url) {
+        Ok(parsed) if !robots.allows(&parsed).await => false,
+        _ => probe_url_exists(client, url).await,
+    }
+}
+
 /// Check if a URL exists using a HEAD request.
 ///
 /// Follows redirects to determine the final status.
@@ -1158,4 +1393,121 @@ mod tests {
         assert!(result.llms_url.is_some());
         assert_eq!(result.discovery_method, DiscoveryMethod::LinkHeader);
     }
+
+    #[test]
+    fn test_probe_kind_classification() {
+        assert_eq!(ProbeKind::of("/llms-full.txt"), ProbeKind::Full);
+        assert_eq!(ProbeKind::of("/.well-known/LLMS-FULL.txt"), ProbeKind::Full);
+        assert_eq!(ProbeKind::of("/docs/llms.txt"), ProbeKind::Index);
+        assert_eq!(ProbeKind::of("/ai.txt"), ProbeKind::Index);
+        assert_eq!(ProbeKind::of("/sitemap_index.xml"), ProbeKind::Sitemap);
+    }
+
+    #[test]
+    fn test_probe_targets_normalize() {
+        let targets = ProbeTargets::new(["llms.txt", " /llms.txt ", "", "/.well-known/ai.txt"]);
+        assert_eq!(targets.paths(), ["/llms.txt", "/.well-known/ai.txt"]);
+        assert_eq!(ProbeTargets::from_config(&[]), ProbeTargets::default());
+    }
+
+    #[test]
+    fn test_default_probe_targets_prefer_root() {
+        let targets = ProbeTargets::default();
+        assert_eq!(
+            targets.of_kind(ProbeKind::Full).next(),
+            Some("/llms-full.txt")
+        );
+        assert_eq!(targets.of_kind(ProbeKind::Index).next(), Some("/llms.txt"));
+        assert_eq!(
+            targets.of_kind(ProbeKind::Sitemap).next(),
+            Some("/sitemap.xml")
+        );
+        assert!(targets.paths().iter().any(|p| p == "/.well-known/llms.txt"));
+    }
+
+    #[tokio::test]
+    async fn test_probe_skips_paths_disallowed_by_robots() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/robots.txt"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string("User-agent: *\nDisallow: /llms-full.txt\n"),
+            )
+            .mount(&mock_server)
+            .await;
+        for route in ["/llms-full.txt", "/llms.txt"] {
+            Mock::given(method("HEAD"))
+                .and(path(route))
+                .respond_with(ResponseTemplate::new(200))
+                .mount(&mock_server)
+                .await;
+        }
+
+        let uri = mock_server.uri();
+        let domain = uri.trim_start_matches("http://");
+
+        let result = probe_domain(domain).await.unwrap();
+        assert_eq!(result.llms_full_url, None);
+        assert_eq!(result.llms_url, Some(format!("{uri}/llms.txt")));
+
+        let targets = ProbeTargets::default().ignoring_robots(true);
+        let result = probe_domain_with(domain, &targets).await.unwrap();
+        assert_eq!(result.llms_full_url, Some(format!("{uri}/llms-full.txt")));
+    }
+
+    #[tokio::test]
+    async fn test_probe_finds_well_known_locations() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("HEAD"))
+            .and(path("/.well-known/llms.txt"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("HEAD"))
+            .and(path("/docs/llms-full.txt"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&mock_server)
+            .await;
+
+        let uri = mock_server.uri();
+        let domain = uri.trim_start_matches("http://");
+
+        let result = probe_domain(domain).await.unwrap();
+
+        assert_eq!(
+            result.llms_full_url,
+            Some(format!("{uri}/docs/llms-full.txt"))
+        );
+        assert_eq!(result.llms_url, Some(format!("{uri}/.well-known/llms.txt")));
+        assert_eq!(result.discovery_method, DiscoveryMethod::HostRoot);
+    }
+
+    #[tokio::test]
+    async fn test_probe_prefers_earlier_targets() {
+        let mock_server = MockServer::start().await;
+
+        for route in ["/llms.txt", "/ai.txt"] {
+            Mock::given(method("HEAD"))
+                .and(path(route))
+                .respond_with(ResponseTemplate::new(200))
+                .mount(&mock_server)
+                .await;
+        }
+
+        let uri = mock_server.uri();
+        let domain = uri.trim_start_matches("http://");
+
+        let result = probe_domain(domain).await.unwrap();
+        assert_eq!(result.llms_url, Some(format!("{uri}/llms.txt")));
+
+        // A custom list can reorder or drop locations entirely
+        let targets = ProbeTargets::new(["/ai.txt"]);
+        let result = probe_domain_with(domain, &targets).await.unwrap();
+        assert_eq!(result.llms_url, Some(format!("{uri}/ai.txt")));
+        assert!(result.llms_full_url.is_none());
+    }
 }
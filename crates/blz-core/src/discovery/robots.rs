@@ -0,0 +1,442 @@
+//! robots.txt rules and per-host crawl pacing.
+//!
+//! Every request blz makes on its own initiative — probing a domain for
+//! llms files, following links from an index, scraping sitemap pages — is
+//! checked against the host's robots.txt first. Rules are read from the group
+//! naming `outfitter-blz` (or `blz`), falling back to `*`, and matched per
+//! RFC 9309: the longest matching `Allow`/`Disallow` pattern wins, with `*`
+//! wildcards and `$` anchors. A missing robots.txt (4xx) allows everything;
+//! an unreachable one (5xx or network error) disallows everything.
+//!
+//! `Crawl-delay` is honored by [`RobotsPolicy::admit`], which spaces requests
+//! to the same host at least that far apart (capped at [`MAX_CRAWL_DELAY`]).
+//! URLs a user passes explicitly are never checked, and
+//! [`RobotsPolicy::ignoring`] (`--ignore-robots`) turns checks off.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use reqwest::Client;
+use tokio::sync::OnceCell;
+use tokio::time::Instant;
+use tracing::debug;
+use url::Url;
+
+use crate::{Error, Result};
+
+/// Product tokens whose groups apply to blz, in addition to `*`.
+pub const ROBOTS_AGENTS: [&str; 2] = ["outfitter-blz", "blz"];
+
+/// Longest `Crawl-delay` honored; larger values are clamped.
+pub const MAX_CRAWL_DELAY: Duration = Duration::from_secs(30);
+
+/// Bytes of robots.txt parsed (RFC 9309 asks for at least 500 KiB).
+const MAX_ROBOTS_BYTES: usize = 500 * 1024;
+
+/// Timeout for fetching robots.txt.
+const ROBOTS_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// One `Allow` or `Disallow` line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Rule {
+    pattern: String,
+    allow: bool,
+}
+
+/// Rules from one robots.txt that apply to blz.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RobotsTxt {
+    rules: Vec<Rule>,
+    crawl_delay: Option<Duration>,
+}
+
+impl RobotsTxt {
+    /// Rules that allow every path (no robots.txt).
+    #[must_use]
+    pub fn allow_all() -> Self {
+        Self::default()
+    }
+
+    /// Rules that disallow every path (robots.txt unreachable).
+    #[must_use]
+    pub fn disallow_all() -> Self {
+        Self {
+            rules: vec![Rule {
+                pattern: "/".to_string(),
+                allow: false,
+            }],
+            crawl_delay: None,
+        }
+    }
+
+    /// Parse robots.txt, keeping the groups for blz or, if none, for `*`.
+    #[must_use]
+    pub fn parse(text: &str) -> Self {
+        #[derive(Default)]
+        struct Group {
+            agents: Vec<String>,
+            rules: Vec<Rule>,
+            crawl_delay: Option<Duration>,
+        }
+
+        let mut groups: Vec<Group> = Vec::new();
+        let mut in_agents = false;
+        for line in text.lines() {
+            let line = line.split_once('#').map_or(line, |(before, _)| before);
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let key = key.trim().to_ascii_lowercase();
+            let value = value.trim();
+            if key == "user-agent" {
+                if !in_agents {
+                    groups.push(Group::default());
+                    in_agents = true;
+                }
+                if let Some(group) = groups.last_mut() {
+                    group.agents.push(value.to_ascii_lowercase());
+                }
+                continue;
+            }
+            // Lines before the first user-agent belong to no group
+            let Some(group) = groups.last_mut() else {
+                continue;
+            };
+            match key.as_str() {
+                "allow" | "disallow" => {
+                    in_agents = false;
+                    if !value.is_empty() {
+                        group.rules.push(Rule {
+                            pattern: value.to_string(),
+                            allow: key == "allow",
+                        });
+                    }
+                },
+                "crawl-delay" => {
+                    in_agents = false;
+                    group.crawl_delay = value
+                        .parse::<f64>()
+                        .ok()
+                        .filter(|secs| secs.is_finite() && *secs >= 0.0)
+                        .map(|secs| Duration::from_secs_f64(secs).min(MAX_CRAWL_DELAY));
+                },
+                _ => {},
+            }
+        }
+
+        let ours = |group: &&Group| {
+            group
+                .agents
+                .iter()
+                .any(|agent| ROBOTS_AGENTS.contains(&agent.as_str()))
+        };
+        let matching: Vec<&Group> = if groups.iter().any(|group| ours(&group)) {
+            groups.iter().filter(ours).collect()
+        } else {
+            groups
+                .iter()
+                .filter(|group| group.agents.iter().any(|agent| agent == "*"))
+                .collect()
+        };
+
+        Self {
+            rules: matching
+                .iter()
+                .flat_map(|group| group.rules.iter().cloned())
+                .collect(),
+            crawl_delay: matching.iter().filter_map(|group| group.crawl_delay).max(),
+        }
+    }
+
+    /// Whether `path` (path plus query) may be fetched.
+    ///
+    /// The longest matching pattern decides; `Allow` wins ties.
+    #[must_use]
+    pub fn is_allowed(&self, path: &str) -> bool {
+        if path == "/robots.txt" {
+            return true;
+        }
+        self.rules
+            .iter()
+            .filter(|rule| pattern_matches(&rule.pattern, path))
+            .max_by_key(|rule| (rule.pattern.len(), rule.allow))
+            .is_none_or(|rule| rule.allow)
+    }
+
+    /// Minimum spacing between requests to the host, if one is asked for.
+    #[must_use]
+    pub const fn crawl_delay(&self) -> Option<Duration> {
+        self.crawl_delay
+    }
+}
+
+/// Match a robots.txt path pattern (`*` wildcards, trailing `$` anchor).
+fn pattern_matches(pattern: &str, path: &str) -> bool {
+    let (pattern, anchored) = pattern
+        .strip_suffix('$')
+        .map_or((pattern, false), |pattern| (pattern, true));
+    let mut parts = pattern.split('*');
+    let Some(mut rest) = parts.next().and_then(|prefix| path.strip_prefix(prefix)) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        return !anchored || rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+    if anchored {
+        rest.ends_with(last)
+    } else {
+        rest.contains(last)
+    }
+}
+
+/// Path and query of a URL, as robots.txt patterns see it.
+fn robots_path(url: &Url) -> String {
+    url.query().map_or_else(
+        || url.path().to_string(),
+        |query| format!("{}?{query}", url.path()),
+    )
+}
+
+#[derive(Debug)]
+struct PolicyState {
+    client: Client,
+    rules: Mutex<HashMap<String, Arc<OnceCell<Arc<RobotsTxt>>>>>,
+    next_request: Mutex<HashMap<String, Instant>>,
+}
+
+/// Shared robots.txt checks for one run: each host's robots.txt is fetched
+/// once, and requests to hosts with a `Crawl-delay` are spaced out.
+///
+/// Clones share the cache and pacing state.
+#[derive(Debug, Clone)]
+pub struct RobotsPolicy {
+    state: Option<Arc<PolicyState>>,
+}
+
+impl RobotsPolicy {
+    /// A policy that fetches robots.txt with its own HTTP client.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP client cannot be constructed.
+    pub fn new() -> Result<Self> {
+        let client = Client::builder()
+            .timeout(ROBOTS_TIMEOUT)
+            .user_agent(concat!("outfitter-blz/", env!("CARGO_PKG_VERSION")))
+            .redirect(reqwest::redirect::Policy::limited(5))
+            .build()
+            .map_err(Error::Network)?;
+        Ok(Self::with_client(client))
+    }
+
+    /// A policy that fetches robots.txt with `client` (sharing its proxy and
+    /// TLS settings).
+    #[must_use]
+    pub fn with_client(client: Client) -> Self {
+        Self {
+            state: Some(Arc::new(PolicyState {
+                client,
+                rules: Mutex::new(HashMap::new()),
+                next_request: Mutex::new(HashMap::new()),
+            })),
+        }
+    }
+
+    /// A policy that allows everything without fetching robots.txt.
+    #[must_use]
+    pub const fn ignoring() -> Self {
+        Self { state: None }
+    }
+
+    /// [`Self::new`], or [`Self::ignoring`] when `ignore` is set.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP client cannot be constructed.
+    pub fn from_flag(ignore: bool) -> Result<Self> {
+        if ignore {
+            Ok(Self::ignoring())
+        } else {
+            Self::new()
+        }
+    }
+
+    /// Whether robots.txt is consulted at all.
+    #[must_use]
+    pub const fn is_enforced(&self) -> bool {
+        self.state.is_some()
+    }
+
+    /// Rules for `url`'s host, fetched on first use.
+    pub async fn rules_for(&self, url: &Url) -> Arc<RobotsTxt> {
+        let Some(state) = &self.state else {
+            return Arc::new(RobotsTxt::allow_all());
+        };
+        let origin = url.origin().ascii_serialization();
+        let cell = {
+            let mut rules = state
+                .rules
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+            Arc::clone(rules.entry(origin.clone()).or_default())
+        };
+        Arc::clone(
+            cell.get_or_init(|| async { Arc::new(fetch_robots(&state.client, &origin).await) })
+                .await,
+        )
+    }
+
+    /// Whether robots.txt allows fetching `url`.
+    pub async fn allows(&self, url: &Url) -> bool {
+        if !self.is_enforced() {
+            return true;
+        }
+        let allowed = self.rules_for(url).await.is_allowed(&robots_path(url));
+        if !allowed {
+            debug!("robots.txt disallows {url}");
+        }
+        allowed
+    }
+
+    /// Check `url` and, when allowed, wait for the host's `Crawl-delay` slot.
+    ///
+    /// Returns `false` without waiting when robots.txt disallows the URL.
+    pub async fn admit(&self, url: &Url) -> bool {
+        if !self.allows(url).await {
+            return false;
+        }
+        let Some(state) = &self.state else {
+            return true;
+        };
+        let Some(delay) = self.rules_for(url).await.crawl_delay() else {
+            return true;
+        };
+        let slot = {
+            let mut next = state
+                .next_request
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+            let now = Instant::now();
+            let host = url.host_str().unwrap_or_default().to_string();
+            let slot = next.get(&host).map_or(now, |at| (*at).max(now));
+            next.insert(host, slot + delay);
+            slot
+        };
+        tokio::time::sleep_until(slot).await;
+        true
+    }
+}
+
+/// Fetch and parse `{origin}/robots.txt`.
+async fn fetch_robots(client: &Client, origin: &str) -> RobotsTxt {
+    let url = format!("{origin}/robots.txt");
+    let response = match client.get(&url).send().await {
+        Ok(response) => response,
+        Err(err) => {
+            debug!("robots.txt unreachable at {url}: {err}");
+            return RobotsTxt::disallow_all();
+        },
+    };
+    let status = response.status();
+    if status.is_client_error() {
+        return RobotsTxt::allow_all();
+    }
+    if !status.is_success() {
+        debug!("robots.txt at {url} returned {status}");
+        return RobotsTxt::disallow_all();
+    }
+    match response.bytes().await {
+        Ok(body) => {
+            let body = &body[..body.len().min(MAX_ROBOTS_BYTES)];
+            RobotsTxt::parse(&String::from_utf8_lossy(body))
+        },
+        Err(err) => {
+            debug!("Failed to read robots.txt at {url}: {err}");
+            RobotsTxt::disallow_all()
+        },
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[test]
+    fn longest_match_wins_and_allow_breaks_ties() {
+        let robots = RobotsTxt::parse(
+            "User-agent: *\nDisallow: /docs/\nAllow: /docs/public/\nDisallow: /*.pdf$\nAllow: /same\nDisallow: /same\n",
+        );
+        assert!(robots.is_allowed("/"));
+        assert!(!robots.is_allowed("/docs/private"));
+        assert!(robots.is_allowed("/docs/public/intro"));
+        assert!(!robots.is_allowed("/guide.pdf"));
+        assert!(robots.is_allowed("/guide.pdf?download=1"));
+        assert!(robots.is_allowed("/same"));
+        assert!(robots.is_allowed("/robots.txt"));
+    }
+
+    #[test]
+    fn blz_group_replaces_the_wildcard_group() {
+        let text = "# rules\nUser-agent: *\nDisallow: /\n\nUser-agent: Googlebot\nUser-agent: outfitter-blz\nDisallow: /admin # staff only\nCrawl-delay: 2.5\n";
+        let robots = RobotsTxt::parse(text);
+        assert!(robots.is_allowed("/docs"));
+        assert!(!robots.is_allowed("/admin/users"));
+        assert_eq!(robots.crawl_delay(), Some(Duration::from_millis(2500)));
+
+        let wildcard = RobotsTxt::parse("User-agent: *\nDisallow: /\nCrawl-delay: 600\n");
+        assert!(!wildcard.is_allowed("/docs"));
+        assert_eq!(wildcard.crawl_delay(), Some(MAX_CRAWL_DELAY));
+
+        let unrelated = RobotsTxt::parse("User-agent: Googlebot\nDisallow: /\n");
+        assert!(unrelated.is_allowed("/docs"));
+    }
+
+    #[test]
+    fn patterns_support_wildcards_and_anchors() {
+        assert!(pattern_matches("/", "/anything"));
+        assert!(pattern_matches("/a*c", "/abbbc/d"));
+        assert!(!pattern_matches("/a*c$", "/abbbc/d"));
+        assert!(pattern_matches("/a*c$", "/abbbc"));
+        assert!(pattern_matches("/exact$", "/exact"));
+        assert!(!pattern_matches("/exact$", "/exactly"));
+        assert!(pattern_matches("*/private", "/team/private"));
+        assert!(!pattern_matches("/docs", "/blog"));
+    }
+
+    #[tokio::test]
+    async fn policy_fetches_once_per_host_and_treats_missing_as_allow() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/robots.txt"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_string("User-agent: *\nDisallow: /private\n"),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        let policy = RobotsPolicy::new().unwrap();
+        let base = Url::parse(&server.uri()).unwrap();
+        assert!(policy.allows(&base.join("/docs").unwrap()).await);
+        assert!(!policy.admit(&base.join("/private/x").unwrap()).await);
+        assert!(
+            RobotsPolicy::ignoring()
+                .allows(&base.join("/private/x").unwrap())
+                .await
+        );
+
+        let missing = MockServer::start().await;
+        let base = Url::parse(&missing.uri()).unwrap();
+        assert!(policy.allows(&base.join("/private/x").unwrap()).await);
+    }
+}
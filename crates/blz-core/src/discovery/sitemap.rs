@@ -38,6 +38,26 @@
 //! - **Standard sitemap**: Contains `<urlset>` with `<url>` entries
 //! - **Sitemap index**: Contains `<sitemapindex>` with `<sitemap>` entries
 //!   pointing to other sitemaps (recursively fetched)
+//!
+//! ## Filtering and Ordering
+//!
+//! [`fetch_sitemap_with`] accepts [`SitemapOptions`] to bound index recursion
+//! and keep only URLs matching include/exclude patterns. Nested indexes are
+//! followed newest-first (by `<lastmod>`), so when the child cap is hit the
+//! stalest sitemaps are the ones dropped. Results are ordered by
+//! `<priority>`, then `<lastmod>`, then URL.
+//!
+//! ```no_run
+//! use blz_core::discovery::sitemap::{SitemapOptions, fetch_sitemap_with};
+//!
+//! # async fn example() -> blz_core::Result<()> {
+//! let options = SitemapOptions::default()
+//!     .with_include(["*/docs/*"])
+//!     .with_exclude(["*/docs/v1/*"]);
+//! let entries = fetch_sitemap_with("https://example.com/sitemap.xml", &options).await?;
+//! # Ok(())
+//! # }
+//! ```
 
 use crate::{Error, Result};
 use chrono::{DateTime, NaiveDate, Utc};
@@ -45,6 +65,9 @@ use quick_xml::Reader;
 use quick_xml::events::Event;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::sync::Arc;
 use std::time::Duration;
 use tracing::instrument;
 
@@ -57,6 +80,88 @@ const MAX_INDEX_DEPTH: u8 = 2;
 /// Maximum number of child sitemaps to fetch from an index.
 const MAX_CHILD_SITEMAPS: usize = 50;
 
+/// Priority assumed for entries without `<priority>` (the sitemap protocol default).
+const DEFAULT_PRIORITY: f32 = 0.5;
+
+/// Options controlling sitemap recursion and URL filtering.
+///
+/// Patterns match against the full URL. A `*` matches any run of
+/// characters; a pattern without `*` matches as a substring.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SitemapOptions {
+    /// How many levels of nested sitemap indexes to follow.
+    pub max_depth: u8,
+    /// Maximum child sitemaps fetched from any single index.
+    pub max_child_sitemaps: usize,
+    /// Keep only URLs matching at least one pattern (all URLs when empty).
+    pub include: Vec<String>,
+    /// Drop URLs matching any pattern.
+    pub exclude: Vec<String>,
+}
+
+impl Default for SitemapOptions {
+    fn default() -> Self {
+        Self {
+            max_depth: MAX_INDEX_DEPTH,
+            max_child_sitemaps: MAX_CHILD_SITEMAPS,
+            include: Vec::new(),
+            exclude: Vec::new(),
+        }
+    }
+}
+
+impl SitemapOptions {
+    /// Set how many levels of nested indexes to follow.
+    #[must_use]
+    pub const fn with_max_depth(mut self, max_depth: u8) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Set the include patterns.
+    #[must_use]
+    pub fn with_include<I, S>(mut self, patterns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.include = patterns.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Set the exclude patterns.
+    #[must_use]
+    pub fn with_exclude<I, S>(mut self, patterns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.exclude = patterns.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Whether `url` passes the include/exclude patterns.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use blz_core::discovery::sitemap::SitemapOptions;
+    ///
+    /// let options = SitemapOptions::default()
+    ///     .with_include(["/docs/"])
+    ///     .with_exclude(["*/docs/v1/*"]);
+    /// assert!(options.allows("https://example.com/docs/intro"));
+    /// assert!(!options.allows("https://example.com/docs/v1/intro"));
+    /// assert!(!options.allows("https://example.com/blog/post"));
+    /// ```
+    #[must_use]
+    pub fn allows(&self, url: &str) -> bool {
+        let included =
+            self.include.is_empty() || self.include.iter().any(|p| pattern_matches(p, url));
+        included && !self.exclude.iter().any(|p| pattern_matches(p, url))
+    }
+}
+
 /// A single entry from a sitemap.
 ///
 /// Contains the URL and optional metadata like last modification date,
@@ -129,7 +234,6 @@ struct SitemapIndexEntry {
     /// URL to the child sitemap.
     loc: String,
     /// Last modification date of the child sitemap.
-    #[allow(dead_code)]
     lastmod: Option<DateTime<Utc>>,
 }
 
@@ -232,8 +336,70 @@ pub fn is_sitemap_index(xml: &str) -> bool {
 /// ```
 #[instrument(skip_all, fields(url = %url))]
 pub async fn fetch_sitemap(url: &str) -> Result<Vec<SitemapEntry>> {
+    fetch_sitemap_with(url, &SitemapOptions::default()).await
+}
+
+/// Fetch and parse a sitemap with explicit recursion bounds and URL filters.
+///
+/// Entries are deduplicated by URL, filtered through
+/// [`SitemapOptions::allows`], and ordered with [`order_entries`].
+///
+/// # Errors
+///
+/// Returns an error if the top-level sitemap cannot be fetched or parsed.
+/// Failing child sitemaps are logged and skipped.
+#[instrument(skip_all, fields(url = %url))]
+pub async fn fetch_sitemap_with(url: &str, options: &SitemapOptions) -> Result<Vec<SitemapEntry>> {
     let client = build_sitemap_client()?;
-    fetch_sitemap_recursive(client, url.to_string(), 0).await
+    let options = Arc::new(options.clone());
+    let entries = fetch_sitemap_recursive(client, url.to_string(), 0, Arc::clone(&options)).await?;
+
+    let mut seen = HashSet::new();
+    let mut entries: Vec<_> = entries
+        .into_iter()
+        .filter(|entry| options.allows(&entry.url) && seen.insert(entry.url.clone()))
+        .collect();
+    order_entries(&mut entries);
+    Ok(entries)
+}
+
+/// Order entries by priority (highest first), then `lastmod` (newest first),
+/// then URL.
+///
+/// Entries without `<priority>` count as `0.5`, the protocol default; entries
+/// without `<lastmod>` sort after dated ones of equal priority.
+pub fn order_entries(entries: &mut [SitemapEntry]) {
+    entries.sort_by(|a, b| {
+        let priority = |entry: &SitemapEntry| entry.priority.unwrap_or(DEFAULT_PRIORITY);
+        priority(b)
+            .partial_cmp(&priority(a))
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| b.lastmod.cmp(&a.lastmod))
+            .then_with(|| a.url.cmp(&b.url))
+    });
+}
+
+/// Match a URL against an include/exclude pattern.
+fn pattern_matches(pattern: &str, url: &str) -> bool {
+    if !pattern.contains('*') {
+        return url.contains(pattern);
+    }
+
+    let parts: Vec<&str> = pattern.split('*').collect();
+    let (first, rest) = parts.split_first().unwrap_or((&"", &[]));
+    let Some(mut remaining) = url.strip_prefix(first) else {
+        return false;
+    };
+    let Some((last, middle)) = rest.split_last() else {
+        return remaining.is_empty();
+    };
+    for part in middle {
+        match remaining.find(part) {
+            Some(index) => remaining = &remaining[index + part.len()..],
+            None => return false,
+        }
+    }
+    remaining.ends_with(last)
 }
 
 /// Internal recursive fetcher for sitemap content.
@@ -244,11 +410,13 @@ fn fetch_sitemap_recursive(
     client: Client,
     url: String,
     depth: u8,
+    options: Arc<SitemapOptions>,
 ) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<Vec<SitemapEntry>>> + Send>> {
     Box::pin(async move {
-        if depth > MAX_INDEX_DEPTH {
+        if depth > options.max_depth {
             return Err(Error::ResourceLimited(format!(
-                "Sitemap index recursion depth exceeded (max: {MAX_INDEX_DEPTH})"
+                "Sitemap index recursion depth exceeded (max: {})",
+                options.max_depth
             )));
         }
 
@@ -262,11 +430,21 @@ fn fetch_sitemap_recursive(
 
         match parse_sitemap_content(&xml)? {
             SitemapContent::Entries(entries) => Ok(entries),
-            SitemapContent::Index(index_entries) => {
-                // Fetch child sitemaps in parallel (limited count)
+            SitemapContent::Index(mut index_entries) => {
+                if depth >= options.max_depth {
+                    tracing::warn!(
+                        url = %url,
+                        max_depth = options.max_depth,
+                        "Not following nested sitemap index beyond depth limit"
+                    );
+                    return Ok(Vec::new());
+                }
+
+                // Fetch the freshest child sitemaps in parallel (limited count)
+                index_entries.sort_by_key(|entry| std::cmp::Reverse(entry.lastmod));
                 let child_urls: Vec<_> = index_entries
                     .into_iter()
-                    .take(MAX_CHILD_SITEMAPS)
+                    .take(options.max_child_sitemaps)
                     .map(|e| e.loc)
                     .collect();
 
@@ -280,8 +458,12 @@ fn fetch_sitemap_recursive(
 
                 for child_url in child_urls {
                     let client_clone = client.clone();
-                    let handle =
-                        tokio::spawn(fetch_sitemap_recursive(client_clone, child_url, depth + 1));
+                    let handle = tokio::spawn(fetch_sitemap_recursive(
+                        client_clone,
+                        child_url,
+                        depth + 1,
+                        Arc::clone(&options),
+                    ));
                     handles.push(handle);
                 }
 
@@ -998,4 +1180,169 @@ mod tests {
             "https://example.com/path/to/page%20with%20spaces"
         );
     }
+
+    #[test]
+    fn test_pattern_matching() {
+        let url = "https://example.com/docs/guide/intro";
+        assert!(pattern_matches("/docs/", url));
+        assert!(!pattern_matches("/blog/", url));
+        assert!(pattern_matches("*/docs/*", url));
+        assert!(pattern_matches("https://example.com/*/intro", url));
+        assert!(pattern_matches("*", url));
+        assert!(!pattern_matches("*/docs", url));
+        assert!(!pattern_matches("http://*", url));
+    }
+
+    #[test]
+    fn test_options_include_and_exclude() {
+        let options = SitemapOptions::default()
+            .with_include(["*/docs/*", "*/api/*"])
+            .with_exclude(["/v1/"]);
+
+        assert!(options.allows("https://example.com/docs/intro"));
+        assert!(options.allows("https://example.com/api/users"));
+        assert!(!options.allows("https://example.com/docs/v1/intro"));
+        assert!(!options.allows("https://example.com/blog/post"));
+        assert!(SitemapOptions::default().allows("https://example.com/anything"));
+    }
+
+    #[test]
+    fn test_order_entries_by_priority_then_lastmod() {
+        let entry = |url: &str, priority: Option<f32>, lastmod: Option<&str>| SitemapEntry {
+            url: url.to_string(),
+            lastmod: lastmod.and_then(parse_lastmod),
+            changefreq: None,
+            priority,
+        };
+        let mut entries = vec![
+            entry("https://example.com/undated", None, None),
+            entry("https://example.com/old", None, Some("2023-01-01")),
+            entry("https://example.com/low", Some(0.1), Some("2024-06-01")),
+            entry("https://example.com/top", Some(1.0), None),
+            entry("https://example.com/new", Some(0.5), Some("2024-01-01")),
+        ];
+
+        order_entries(&mut entries);
+
+        let urls: Vec<_> = entries.iter().map(|e| e.url.as_str()).collect();
+        assert_eq!(
+            urls,
+            [
+                "https://example.com/top",
+                "https://example.com/new",
+                "https://example.com/old",
+                "https://example.com/undated",
+                "https://example.com/low",
+            ]
+        );
+    }
+
+    async fn mount_xml(server: &MockServer, route: &str, body: String) {
+        Mock::given(method("GET"))
+            .and(path(route))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(body)
+                    .insert_header("Content-Type", "application/xml"),
+            )
+            .mount(server)
+            .await;
+    }
+
+    fn index_xml(children: &[String]) -> String {
+        let mut sitemaps = String::new();
+        for loc in children {
+            sitemaps.push_str("<sitemap><loc>");
+            sitemaps.push_str(loc);
+            sitemaps.push_str("</loc></sitemap>");
+        }
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+            <sitemapindex xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">{sitemaps}</sitemapindex>"#
+        )
+    }
+
+    #[tokio::test]
+    async fn test_fetch_sitemap_with_nested_index_and_filters() {
+        let mock_server = MockServer::start().await;
+        let base = mock_server.uri();
+
+        mount_xml(
+            &mock_server,
+            "/sitemap.xml",
+            index_xml(&[format!("{base}/sitemap-docs.xml")]),
+        )
+        .await;
+        mount_xml(
+            &mock_server,
+            "/sitemap-docs.xml",
+            index_xml(&[format!("{base}/sitemap-docs-1.xml")]),
+        )
+        .await;
+        mount_xml(
+            &mock_server,
+            "/sitemap-docs-1.xml",
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+            <urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+              <url><loc>https://example.com/docs/intro</loc><priority>0.4</priority></url>
+              <url><loc>https://example.com/docs/api</loc><priority>0.9</priority></url>
+              <url><loc>https://example.com/docs/v1/intro</loc></url>
+              <url><loc>https://example.com/blog/post</loc></url>
+              <url><loc>https://example.com/docs/api</loc></url>
+            </urlset>"#
+                .to_string(),
+        )
+        .await;
+
+        let options = SitemapOptions::default()
+            .with_include(["/docs/"])
+            .with_exclude(["*/v1/*"]);
+        let url = format!("{base}/sitemap.xml");
+        let entries = fetch_sitemap_with(&url, &options).await.unwrap();
+
+        let urls: Vec<_> = entries.iter().map(|e| e.url.as_str()).collect();
+        assert_eq!(
+            urls,
+            [
+                "https://example.com/docs/api",
+                "https://example.com/docs/intro"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fetch_sitemap_with_stops_at_depth_limit() {
+        let mock_server = MockServer::start().await;
+        let base = mock_server.uri();
+
+        mount_xml(
+            &mock_server,
+            "/sitemap.xml",
+            index_xml(&[format!("{base}/nested.xml")]),
+        )
+        .await;
+        mount_xml(
+            &mock_server,
+            "/nested.xml",
+            index_xml(&[format!("{base}/pages.xml")]),
+        )
+        .await;
+        mount_xml(
+            &mock_server,
+            "/pages.xml",
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+            <urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+              <url><loc>https://example.com/page1</loc></url>
+            </urlset>"#
+                .to_string(),
+        )
+        .await;
+
+        let url = format!("{base}/sitemap.xml");
+        let shallow = SitemapOptions::default().with_max_depth(1);
+        assert!(fetch_sitemap_with(&url, &shallow).await.unwrap().is_empty());
+
+        let entries = fetch_sitemap(&url).await.unwrap();
+        assert_eq!(entries.len(), 1);
+    }
 }
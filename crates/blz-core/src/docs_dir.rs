@@ -0,0 +1,353 @@
+//! Local documentation folder sources.
+//!
+//! `blz add projectdocs ./docs --type dir` walks a folder for markdown files
+//! and merges them into one document: a generated `# Contents` list followed
+//! by one top-level section per file, named by its path relative to the
+//! folder, so heading paths read like `guides/setup.md > Install`. The file
+//! list with modification times, sizes, and hashes is kept in the source
+//! origin: sync skips folders whose files were not touched without reading
+//! them, and re-indexes only when file contents changed. MDX files, Jupyter
+//! notebooks, reStructuredText, and PDFs are converted to plain markdown
+//! before merging (see [`crate::markup`]).
+
+use std::fmt::Write as _;
+use std::path::Path;
+
+use base64::{Engine as _, engine::general_purpose::STANDARD};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::collection::merge_members;
+use crate::fetcher::calculate_sha256;
+use crate::markup;
+use crate::refresh::{
+    ApplyRefreshParams, RefreshContext, RefreshIndexer, RefreshOutcome, RefreshPayload,
+    RefreshStorage, apply_refresh,
+};
+use crate::{Error, PerformanceMetrics, Result, SourceType};
+
+/// File extensions read from documentation folders.
+pub const DOC_EXTENSIONS: [&str; 6] = ["md", "mdx", "markdown", "ipynb", "rst", "pdf"];
+
+/// Maximum number of files merged into one folder source.
+pub const MAX_FILES: usize = 5_000;
+
+/// Heading of the generated file list.
+pub const CONTENTS_HEADING: &str = "Contents";
+
+/// One markdown file that is part of a folder source.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DocsFile {
+    /// Path relative to the folder, with `/` separators.
+    pub path: String,
+    /// Modification time seen when the file was last scanned.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub modified: Option<DateTime<Utc>>,
+    /// File size in bytes.
+    pub size: u64,
+    /// SHA-256 (base64) of the file content.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub sha256: String,
+}
+
+impl DocsFile {
+    /// Whether path, modification time, and size match an earlier scan.
+    fn same_stamp(&self, other: &Self) -> bool {
+        self.path == other.path && self.modified == other.modified && self.size == other.size
+    }
+}
+
+/// List the markdown files under `root`, sorted by relative path.
+///
+/// Hidden entries (names starting with `.`) and symlinked directories are
+/// skipped. Entries carry modification time and size; hashes are filled in by
+/// [`read_files`].
+///
+/// # Errors
+///
+/// Returns an error if `root` is not a directory, contains no markdown files,
+/// contains more than [`MAX_FILES`], or cannot be read.
+pub fn scan_files(root: &Path) -> Result<Vec<DocsFile>> {
+    if !root.is_dir() {
+        return Err(Error::NotFound(format!(
+            "Documentation folder not found: {}",
+            root.display()
+        )));
+    }
+
+    let mut files = Vec::new();
+    let mut pending = vec![root.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        for entry in std::fs::read_dir(&dir)? {
+            let entry = entry?;
+            if entry.file_name().to_string_lossy().starts_with('.') {
+                continue;
+            }
+            let path = entry.path();
+            if entry.file_type()?.is_dir() {
+                pending.push(path);
+                continue;
+            }
+            if !is_doc_file(&path) {
+                continue;
+            }
+            // Follows symlinked files; links to directories are not files
+            let metadata = std::fs::metadata(&path)?;
+            if !metadata.is_file() {
+                continue;
+            }
+            if files.len() == MAX_FILES {
+                return Err(Error::ResourceLimited(format!(
+                    "more than {MAX_FILES} markdown files under {}",
+                    root.display()
+                )));
+            }
+            files.push(DocsFile {
+                path: relative_path(root, &path),
+                modified: metadata.modified().ok().map(DateTime::<Utc>::from),
+                size: metadata.len(),
+                sha256: String::new(),
+            });
+        }
+    }
+
+    if files.is_empty() {
+        return Err(Error::NotFound(format!(
+            "No markdown files found in {}",
+            root.display()
+        )));
+    }
+    files.sort_unstable_by(|a, b| a.path.cmp(&b.path));
+    Ok(files)
+}
+
+fn is_doc_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| {
+            DOC_EXTENSIONS
+                .iter()
+                .any(|known| ext.eq_ignore_ascii_case(known))
+        })
+}
+
+fn relative_path(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Read every scanned file as markdown, recording the hash of its raw content.
+///
+/// # Errors
+///
+/// Returns an error if a file cannot be read as UTF-8 text, a notebook is
+/// not valid notebook JSON, or a PDF has no extractable text.
+pub fn read_files(root: &Path, files: &mut [DocsFile]) -> Result<Vec<String>> {
+    files
+        .iter_mut()
+        .map(|file| {
+            let content = std::fs::read(root.join(&file.path))
+                .map_err(|err| Error::Storage(format!("Failed to read {}: {err}", file.path)))?;
+            file.sha256 = STANDARD.encode(Sha256::digest(&content));
+            markup::file_to_markdown(Path::new(&file.path), &content)
+        })
+        .collect()
+}
+
+/// Merge folder files into one document.
+///
+/// A `# Contents` section lists every file with its first heading, followed
+/// by each file under `# <relative path>` with its own headings demoted by one
+/// level.
+#[must_use]
+pub fn merge_files(files: &[DocsFile], contents: &[String]) -> String {
+    let mut merged = format!("# {CONTENTS_HEADING}\n\n");
+    for (file, content) in files.iter().zip(contents) {
+        match first_heading(content) {
+            Some(title) => {
+                let _ = writeln!(merged, "- `{}`: {title}", file.path);
+            },
+            None => {
+                let _ = writeln!(merged, "- `{}`", file.path);
+            },
+        }
+    }
+    merged.push('\n');
+    merged.push_str(&merge_members(
+        files
+            .iter()
+            .zip(contents)
+            .map(|(file, content)| (file.path.as_str(), content.as_str())),
+    ));
+    merged
+}
+
+fn first_heading(content: &str) -> Option<&str> {
+    content.lines().find_map(|line| {
+        let title = line.trim_start_matches('#');
+        let level = line.len() - title.len();
+        ((1..=6).contains(&level) && title.starts_with(' ')).then(|| title.trim())
+    })
+}
+
+/// Scan, read, and merge a folder.
+///
+/// Returns the merged document and the file list with hashes.
+///
+/// # Errors
+///
+/// Returns an error if the folder cannot be scanned or a file cannot be read.
+pub fn load_docs_dir(root: &Path) -> Result<(String, Vec<DocsFile>)> {
+    let mut files = scan_files(root)?;
+    let contents = read_files(root, &mut files)?;
+    Ok((merge_files(&files, &contents), files))
+}
+
+/// Refresh a folder source.
+///
+/// When every file keeps its path, modification time, and size the folder is
+/// not read at all. Otherwise files are re-read, and the source is re-merged
+/// and re-indexed only if a file was added, removed, or its content changed.
+///
+/// # Errors
+///
+/// Returns an error if the source is not a folder, or if scanning, reading,
+/// persistence, or indexing fails.
+pub async fn refresh_docs_dir<S, I>(
+    storage: &S,
+    alias: &str,
+    ctx: &RefreshContext,
+    metrics: PerformanceMetrics,
+    indexer: &I,
+    filter_preference: bool,
+) -> Result<RefreshOutcome>
+where
+    S: RefreshStorage + Sync,
+    I: RefreshIndexer + Sync,
+{
+    let Some(SourceType::Directory {
+        path,
+        files: previous,
+    }) = &ctx.existing_metadata.origin.source_type
+    else {
+        return Err(Error::Config(format!(
+            "Source '{alias}' is not a documentation folder"
+        )));
+    };
+
+    let root = Path::new(path);
+    let mut files = scan_files(root)?;
+
+    let mut metadata = ctx.existing_metadata.clone();
+    let filter_changed = metadata.filter_non_english.unwrap_or(true) != filter_preference;
+    metadata.filter_non_english = Some(filter_preference);
+
+    let untouched = files.len() == previous.len()
+        && files
+            .iter()
+            .zip(previous)
+            .all(|(file, old)| file.same_stamp(old));
+    if untouched {
+        if filter_changed {
+            storage.save_metadata(alias, &metadata)?;
+        }
+        return Ok(RefreshOutcome::Unchanged {
+            alias: alias.to_string(),
+        });
+    }
+
+    let contents = read_files(root, &mut files)?;
+    let content_changed = files.len() != previous.len()
+        || files
+            .iter()
+            .zip(previous)
+            .any(|(file, old)| file.path != old.path || file.sha256 != old.sha256);
+    metadata.origin.source_type = Some(SourceType::Directory {
+        path: path.clone(),
+        files: files.clone(),
+    });
+
+    if !content_changed {
+        // Touched but identical files: remember the new stamps
+        storage.save_metadata(alias, &metadata)?;
+        return Ok(RefreshOutcome::Unchanged {
+            alias: alias.to_string(),
+        });
+    }
+
+    let content = merge_files(&files, &contents);
+    let payload = RefreshPayload {
+        sha256: calculate_sha256(&content),
+        content,
+        etag: None,
+        last_modified: None,
+    };
+    let params = ApplyRefreshParams::new(metadata, ctx.existing_aliases.clone());
+    apply_refresh(storage, alias, &params, &payload, metrics, indexer).await
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scans_markdown_files_in_path_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("guides")).unwrap();
+        std::fs::create_dir_all(root.join(".git")).unwrap();
+        std::fs::write(root.join("index.md"), "# Home\n").unwrap();
+        std::fs::write(root.join("guides/setup.MDX"), "# Setup\n").unwrap();
+        std::fs::write(root.join("notes.txt"), "not markdown").unwrap();
+        std::fs::write(root.join(".draft.md"), "# Hidden\n").unwrap();
+        std::fs::write(root.join(".git/HEAD.md"), "# Git\n").unwrap();
+
+        let files = scan_files(root).unwrap();
+        let paths: Vec<&str> = files.iter().map(|file| file.path.as_str()).collect();
+        assert_eq!(paths, ["guides/setup.MDX", "index.md"]);
+        assert!(files.iter().all(|file| file.sha256.is_empty()));
+
+        assert!(scan_files(&root.join("missing")).is_err());
+        std::fs::remove_file(root.join("index.md")).unwrap();
+        std::fs::remove_file(root.join("guides/setup.MDX")).unwrap();
+        assert!(
+            scan_files(root).is_err(),
+            "folders without markdown are rejected"
+        );
+    }
+
+    #[test]
+    fn merges_files_under_path_headings_with_contents() {
+        let files = vec![
+            DocsFile {
+                path: "guides/setup.md".to_string(),
+                modified: None,
+                size: 0,
+                sha256: String::new(),
+            },
+            DocsFile {
+                path: "notes.md".to_string(),
+                modified: None,
+                size: 0,
+                sha256: String::new(),
+            },
+        ];
+        let contents = vec![
+            "# Setup\n\n## Install\n\nRun it.\n".to_string(),
+            "Plain notes.\n".to_string(),
+        ];
+
+        let merged = merge_files(&files, &contents);
+        assert!(merged.starts_with(
+            "# Contents\n\n- `guides/setup.md`: Setup\n- `notes.md`\n\n# guides/setup.md\n\n## Setup\n\n### Install\n"
+        ));
+        assert!(merged.contains("# notes.md\n\nPlain notes.\n"));
+    }
+}
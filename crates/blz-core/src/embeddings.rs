@@ -0,0 +1,508 @@
+//! Local text embeddings and the vector index stored beside each search index.
+//!
+//! Semantic search ranks heading blocks by cosine similarity between query and
+//! block embeddings rather than BM25 term statistics, so a question phrased
+//! with different words can still reach the right section. Embeddings come
+//! from an [`Embedder`]; [`load_embedder`] returns a local sentence-transformer
+//! model run through ONNX Runtime, which requires the `semantic` cargo feature.
+//! The model is downloaded once and then runs offline.
+//!
+//! Vectors are built on the first semantic search for a source and saved as
+//! [`VectorIndex`] next to its Tantivy index. Each file records the embedder and
+//! a content fingerprint, so a refresh or a different model triggers a rebuild.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+use base64::{Engine, engine::general_purpose::STANDARD as B64};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use tracing::warn;
+
+use crate::{Error, Result, SearchHit};
+
+/// Weight given to BM25 scores when blending hybrid results; the rest goes to cosine similarity.
+pub const DEFAULT_HYBRID_WEIGHT: f32 = 0.5;
+
+/// How search hits are ranked.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Ranking {
+    /// BM25 keyword ranking from the Tantivy index.
+    #[default]
+    Lexical,
+    /// Cosine similarity between query and block embeddings.
+    Semantic,
+    /// BM25 and cosine scores combined with [`blend`].
+    Hybrid,
+}
+
+/// Turns text into a fixed-size vector whose cosine similarity reflects relatedness.
+pub trait Embedder: Send + Sync {
+    /// Stable identifier stored with vector indexes; changing it forces a rebuild.
+    fn id(&self) -> &str;
+
+    /// Length of every vector returned by [`Embedder::embed`].
+    fn dimensions(&self) -> usize;
+
+    /// Embeds `text` as a vector of [`Embedder::dimensions`] entries.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the model fails to encode the text.
+    fn embed(&self, text: &str) -> Result<Vec<f32>>;
+
+    /// Embeds several texts at once, in order.
+    ///
+    /// Model-backed embedders override this to encode in batches.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any text fails to encode.
+    fn embed_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        texts.iter().map(|text| self.embed(text)).collect()
+    }
+}
+
+/// Loads the local embedding model used for semantic and hybrid search.
+///
+/// Model files are downloaded into `models_dir` on first use and read from
+/// there afterwards.
+///
+/// # Errors
+///
+/// Returns an error if the model cannot be downloaded or loaded, or if blz was
+/// built without the `semantic` feature.
+#[cfg(feature = "semantic")]
+pub fn load_embedder(models_dir: &Path) -> Result<Arc<dyn Embedder>> {
+    Ok(Arc::new(FastEmbedder::new(models_dir)?))
+}
+
+/// Loads the local embedding model used for semantic and hybrid search.
+///
+/// # Errors
+///
+/// Always fails: this build has no embedding model.
+#[cfg(not(feature = "semantic"))]
+pub fn load_embedder(_models_dir: &Path) -> Result<Arc<dyn Embedder>> {
+    Err(Error::Other(
+        "semantic search is not available in this build; rebuild blz with `--features semantic`"
+            .to_string(),
+    ))
+}
+
+/// Sentence-transformer embedder backed by `fastembed` and ONNX Runtime.
+///
+/// Uses `all-MiniLM-L6-v2`, a small English model producing 384-dimensional
+/// vectors.
+#[cfg(feature = "semantic")]
+pub struct FastEmbedder {
+    id: String,
+    dimensions: usize,
+    model: fastembed::TextEmbedding,
+}
+
+#[cfg(feature = "semantic")]
+impl FastEmbedder {
+    /// Blocks encoded per model call when building a vector index.
+    const BATCH_SIZE: usize = 64;
+
+    /// Loads the model, downloading it into `models_dir` if it is not cached.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the model cannot be downloaded or initialized.
+    pub fn new(models_dir: &Path) -> Result<Self> {
+        use fastembed::{EmbeddingModel, InitOptions, TextEmbedding};
+
+        let model_name = EmbeddingModel::AllMiniLML6V2;
+        let info = TextEmbedding::get_model_info(&model_name)
+            .map_err(|e| Error::Other(format!("unknown embedding model: {e}")))?;
+        let id = format!("fastembed:{}", info.model_code);
+        let dimensions = info.dim;
+        let model = TextEmbedding::try_new(
+            InitOptions::new(model_name)
+                .with_cache_dir(models_dir.to_path_buf())
+                .with_show_download_progress(false),
+        )
+        .map_err(|e| Error::Other(format!("failed to load embedding model: {e}")))?;
+        Ok(Self {
+            id,
+            dimensions,
+            model,
+        })
+    }
+}
+
+#[cfg(feature = "semantic")]
+impl Embedder for FastEmbedder {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+
+    fn embed(&self, text: &str) -> Result<Vec<f32>> {
+        self.embed_batch(&[text.to_string()])?
+            .pop()
+            .ok_or_else(|| Error::Other("embedding model returned no vector".to_string()))
+    }
+
+    fn embed_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        self.model
+            .embed(
+                texts.iter().map(String::as_str).collect(),
+                Some(Self::BATCH_SIZE),
+            )
+            .map_err(|e| Error::Other(format!("failed to embed text: {e}")))
+    }
+}
+
+/// Words too common to carry meaning in a query or section.
+#[cfg(test)]
+const STOPWORDS: &[&str] = &[
+    "a", "an", "and", "are", "as", "at", "be", "by", "can", "do", "does", "for", "from", "how",
+    "i", "if", "in", "is", "it", "its", "my", "of", "on", "or", "so", "that", "the", "this", "to",
+    "was", "what", "when", "where", "which", "why", "with", "you", "your",
+];
+
+/// Model-free embedder based on feature hashing, used by tests.
+///
+/// Each word is lowercased, stop words are dropped, and common suffixes are
+/// stripped. The stem and its character trigrams are hashed into signed
+/// buckets, and the result is normalized. This only captures lexical overlap,
+/// so it stands in for a model when exercising the vector index plumbing.
+#[cfg(test)]
+#[derive(Debug, Clone)]
+pub struct HashingEmbedder {
+    dimensions: usize,
+}
+
+#[cfg(test)]
+impl HashingEmbedder {
+    /// Vector length used by [`HashingEmbedder::default`].
+    pub const DEFAULT_DIMENSIONS: usize = 256;
+
+    /// Creates an embedder producing vectors of `dimensions` entries (at least 1).
+    #[must_use]
+    pub fn new(dimensions: usize) -> Self {
+        Self {
+            dimensions: dimensions.max(1),
+        }
+    }
+
+    fn add_feature(&self, vector: &mut [f32], feature: &[u8], weight: f32) {
+        let hash = fnv1a(feature);
+        let buckets = u64::try_from(self.dimensions).unwrap_or(u64::MAX);
+        let bucket = usize::try_from(hash % buckets).unwrap_or_default();
+        let sign = if hash >> 63 == 1 { -1.0 } else { 1.0 };
+        vector[bucket] += sign * weight;
+    }
+}
+
+#[cfg(test)]
+impl Default for HashingEmbedder {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_DIMENSIONS)
+    }
+}
+
+#[cfg(test)]
+impl Embedder for HashingEmbedder {
+    fn id(&self) -> &'static str {
+        "hashing-v1"
+    }
+
+    fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+
+    fn embed(&self, text: &str) -> Result<Vec<f32>> {
+        let mut vector = vec![0.0; self.dimensions];
+        for word in text
+            .split(|c: char| !c.is_alphanumeric())
+            .map(str::to_lowercase)
+            .filter(|word| word.chars().count() > 1 && !STOPWORDS.contains(&word.as_str()))
+        {
+            let stem = stem(&word);
+            self.add_feature(&mut vector, stem.as_bytes(), 1.0);
+
+            let padded: Vec<char> = format!("<{stem}>").chars().collect();
+            for trigram in padded.windows(3) {
+                let trigram: String = trigram.iter().collect();
+                self.add_feature(&mut vector, trigram.as_bytes(), 0.5);
+            }
+        }
+        normalize(&mut vector);
+        Ok(vector)
+    }
+}
+
+/// Strips a few common English suffixes so inflected forms share features.
+#[cfg(test)]
+fn stem(word: &str) -> &str {
+    for suffix in ["ing", "ies", "ed", "es", "s"] {
+        if let Some(base) = word.strip_suffix(suffix) {
+            if base.chars().count() >= 3 && !base.ends_with('s') {
+                return base;
+            }
+        }
+    }
+    word
+}
+
+/// 64-bit FNV-1a, used because it is stable across Rust releases and platforms.
+#[cfg(test)]
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+fn normalize(vector: &mut [f32]) {
+    let norm = vector.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        for x in vector {
+            *x /= norm;
+        }
+    }
+}
+
+/// Cosine similarity of two vectors; 0 when either is all zeros or lengths differ.
+#[must_use]
+pub fn cosine(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() {
+        return 0.0;
+    }
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norms =
+        a.iter().map(|x| x * x).sum::<f32>().sqrt() * b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norms > 0.0 { dot / norms } else { 0.0 }
+}
+
+/// Embedding of one indexed heading block.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VectorEntry {
+    /// Line range of the block (`"start-end"`), matching the search index.
+    pub lines: String,
+    /// Block embedding, stored as base64-encoded little-endian `f32`s.
+    #[serde(serialize_with = "encode_vector", deserialize_with = "decode_vector")]
+    pub vector: Vec<f32>,
+}
+
+/// Block embeddings for one source.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VectorIndex {
+    /// [`Embedder::id`] of the embedder that produced the vectors.
+    pub embedder: String,
+    /// Vector length.
+    pub dimensions: usize,
+    /// Fingerprint of the indexed content (the source's SHA-256).
+    pub fingerprint: String,
+    /// One entry per heading block.
+    pub entries: Vec<VectorEntry>,
+}
+
+impl VectorIndex {
+    /// Whether these vectors were built by `embedder` from content matching `fingerprint`.
+    #[must_use]
+    pub fn is_current(&self, embedder: &dyn Embedder, fingerprint: &str) -> bool {
+        self.embedder == embedder.id()
+            && self.dimensions == embedder.dimensions()
+            && self.fingerprint == fingerprint
+    }
+
+    /// Entries most similar to `query`, best first, with their cosine similarity.
+    #[must_use]
+    pub fn nearest(&self, query: &[f32], limit: usize) -> Vec<(&VectorEntry, f32)> {
+        let mut scored: Vec<(&VectorEntry, f32)> = self
+            .entries
+            .iter()
+            .map(|entry| (entry, cosine(query, &entry.vector)))
+            .filter(|(_, score)| *score > 0.0)
+            .collect();
+        scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+        scored.truncate(limit);
+        scored
+    }
+
+    /// Loads vectors from `path`.
+    ///
+    /// Returns `Ok(None)` when the file is missing or unreadable as a vector
+    /// index, so callers can rebuild it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file exists but cannot be read.
+    pub fn load(path: &Path) -> Result<Option<Self>> {
+        let bytes = match fs::read(path) {
+            Ok(bytes) => bytes,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(Error::Io(err)),
+        };
+        match serde_json::from_slice(&bytes) {
+            Ok(index) => Ok(Some(index)),
+            Err(err) => {
+                warn!("Ignoring unreadable vector index {}: {err}", path.display());
+                Ok(None)
+            },
+        }
+    }
+
+    /// Writes the vectors to `path`, replacing any existing file atomically.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization or writing fails.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_vec(self)?;
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, json)?;
+        fs::rename(&tmp, path)?;
+        Ok(())
+    }
+}
+
+/// Blends lexical (BM25) and semantic hits for the same source into one ranking.
+///
+/// Each list's scores are scaled to `0..=1` by its best score, then combined as
+/// `weight * lexical + (1 - weight) * semantic`. Hits are matched by line range;
+/// a hit found by only one method scores 0 for the other.
+#[must_use]
+pub fn blend(lexical: Vec<SearchHit>, semantic: Vec<SearchHit>, weight: f32) -> Vec<SearchHit> {
+    let weight = weight.clamp(0.0, 1.0);
+    let max_score = |hits: &[SearchHit]| {
+        hits.iter()
+            .map(|hit| hit.score)
+            .fold(0.0_f32, f32::max)
+            .max(f32::EPSILON)
+    };
+    let lexical_max = max_score(&lexical);
+    let semantic_max = max_score(&semantic);
+
+    let mut blended: Vec<SearchHit> = Vec::with_capacity(lexical.len() + semantic.len());
+    let mut positions: HashMap<(String, String), usize> = HashMap::new();
+    for mut hit in lexical {
+        hit.score = weight * hit.score / lexical_max;
+        positions.insert((hit.source.clone(), hit.lines.clone()), blended.len());
+        blended.push(hit);
+    }
+    for mut hit in semantic {
+        let semantic_score = (1.0 - weight) * hit.score / semantic_max;
+        if let Some(&position) = positions.get(&(hit.source.clone(), hit.lines.clone())) {
+            blended[position].score += semantic_score;
+        } else {
+            hit.score = semantic_score;
+            blended.push(hit);
+        }
+    }
+    blended.sort_by(|a, b| b.score.total_cmp(&a.score));
+    blended
+}
+
+#[allow(clippy::ptr_arg)] // serde passes the field by reference
+fn encode_vector<S: Serializer>(
+    vector: &Vec<f32>,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    let bytes: Vec<u8> = vector.iter().flat_map(|x| x.to_le_bytes()).collect();
+    serializer.serialize_str(&B64.encode(bytes))
+}
+
+fn decode_vector<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<Vec<f32>, D::Error> {
+    let encoded = String::deserialize(deserializer)?;
+    let bytes = B64.decode(encoded).map_err(serde::de::Error::custom)?;
+    if bytes.len() % 4 != 0 {
+        return Err(serde::de::Error::custom(
+            "vector length is not a multiple of 4 bytes",
+        ));
+    }
+    Ok(bytes
+        .chunks_exact(4)
+        .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hit(lines: &str, score: f32) -> SearchHit {
+        SearchHit {
+            source: "docs".to_string(),
+            file: "llms.txt".to_string(),
+            heading_path: vec![lines.to_string()],
+            raw_heading_path: None,
+            level: 1,
+            lines: lines.to_string(),
+            line_numbers: None,
+            snippet: String::new(),
+            snippet_language: None,
+            highlights: Vec::new(),
+            score,
+            source_url: None,
+            fetched_at: None,
+            is_stale: false,
+            checksum: String::new(),
+            anchor: None,
+            section_id: None,
+            context: None,
+        }
+    }
+
+    #[test]
+    fn related_wording_scores_higher_than_unrelated_text() -> Result<()> {
+        let embedder = HashingEmbedder::default();
+        let query = embedder.embed("how do I configure caching")?;
+        let related = embedder.embed("Cache configuration: configuring the HTTP cache layer")?;
+        let unrelated = embedder.embed("Deploying containers to a Kubernetes cluster")?;
+
+        assert!(cosine(&query, &related) > cosine(&query, &unrelated));
+        assert!(embedder.embed("the of and")?.iter().all(|x| *x == 0.0));
+        Ok(())
+    }
+
+    #[test]
+    fn vector_index_roundtrips_and_tracks_staleness() -> Result<()> {
+        let embedder = HashingEmbedder::new(16);
+        let index = VectorIndex {
+            embedder: embedder.id().to_string(),
+            dimensions: embedder.dimensions(),
+            fingerprint: "abc".to_string(),
+            entries: vec![VectorEntry {
+                lines: "1-4".to_string(),
+                vector: embedder.embed("streaming responses")?,
+            }],
+        };
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("vectors.json");
+        index.save(&path)?;
+
+        let loaded = VectorIndex::load(&path)?.expect("vectors saved");
+        assert_eq!(loaded.entries[0].vector, index.entries[0].vector);
+        assert!(loaded.is_current(&embedder, "abc"));
+        assert!(!loaded.is_current(&embedder, "def"));
+        assert!(!loaded.is_current(&HashingEmbedder::new(32), "abc"));
+        assert!(VectorIndex::load(&dir.path().join("missing.json"))?.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn blend_merges_hits_found_by_both_methods() {
+        let lexical = vec![hit("1-5", 8.0), hit("10-12", 4.0)];
+        let semantic = vec![hit("20-30", 0.9), hit("10-12", 0.6)];
+
+        let blended = blend(lexical, semantic, 0.5);
+        let order: Vec<&str> = blended.iter().map(|hit| hit.lines.as_str()).collect();
+
+        // 10-12: 0.25 + 0.33, 1-5: 0.5, 20-30: 0.5 (ties keep lexical first)
+        assert_eq!(order, ["10-12", "1-5", "20-30"]);
+        assert!((blended[0].score - (0.25 + 0.5 * 0.6 / 0.9)).abs() < 1e-6);
+    }
+}
@@ -208,6 +208,19 @@ pub enum Error {
     #[error("Serialization error: {0}")]
     Serialization(String),
 
+    /// Content failed an integrity check.
+    ///
+    /// Raised when a document does not match the SHA-256 or minisign
+    /// signature declared for its source. The cached copy is left untouched.
+    ///
+    /// ## Common Causes
+    ///
+    /// - Upstream published a new version of a pinned document
+    /// - The document or its signature was tampered with in transit
+    /// - The signature was made with a different key
+    #[error("Integrity check failed: {0}")]
+    Integrity(String),
+
     /// Firecrawl CLI is not installed or not in PATH.
     ///
     /// Indicates that the `firecrawl` command cannot be found. Users need to
@@ -414,6 +427,7 @@ impl Error {
     /// - `"resource_limited"` - Resource constraints and limits
     /// - `"timeout"` - Operation timeouts
     /// - `"serialization"` - Data format conversion
+    /// - `"integrity"` - Checksum or signature mismatches
     /// - `"other"` - Uncategorized errors
     ///
     /// # Examples
@@ -469,6 +483,7 @@ impl Error {
             Self::ResourceLimited(_) => "resource_limited",
             Self::Timeout(_) => "timeout",
             Self::Serialization(_) => "serialization",
+            Self::Integrity(_) => "integrity",
             Self::FirecrawlNotInstalled
             | Self::FirecrawlVersionTooOld { .. }
             | Self::FirecrawlNotAuthenticated
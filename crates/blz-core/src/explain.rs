@@ -0,0 +1,184 @@
+//! Score explanations for diagnosing search ranking.
+//!
+//! Tantivy can explain how any document's score was computed as a tree of
+//! BM25 factors, boosts, and boolean sums. [`ScoreExplanation`] is a
+//! serializable copy of that tree, and [`ScoreExplanation::term_contributions`]
+//! flattens it into the score each matched term added to the hit.
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::SearchHit;
+
+/// One node of a score explanation tree.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScoreExplanation {
+    /// Score (or factor) computed at this node.
+    pub value: f32,
+    /// What the node computes, e.g. `TermQuery, product of...`.
+    pub description: String,
+    /// Inputs combined to produce `value`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub details: Vec<Self>,
+    /// Extra context, such as the matched term.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub context: Vec<String>,
+}
+
+/// Score one matched term added to a hit.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TermContribution {
+    /// Index field the term matched in.
+    pub field: String,
+    /// Indexed (tokenized) form of the term.
+    pub term: String,
+    /// BM25 score of the term before boosts.
+    pub bm25: f32,
+    /// Product of the field boosts applied to the term.
+    pub boost: f32,
+    /// Score the term added to the hit (`bm25 * boost`).
+    pub score: f32,
+}
+
+/// A search hit together with the breakdown of its score.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExplainedHit {
+    /// The hit as regular search returns it.
+    #[serde(flatten)]
+    pub hit: SearchHit,
+    /// Per-term contributions, largest first.
+    pub terms: Vec<TermContribution>,
+    /// Full explanation tree.
+    pub explanation: ScoreExplanation,
+}
+
+impl ScoreExplanation {
+    /// Copy a Tantivy explanation tree.
+    ///
+    /// Tantivy only exposes the tree through `Serialize`, so the copy goes via
+    /// JSON; a tree that fails to convert degrades to its root score.
+    #[must_use]
+    pub fn from_tantivy(explanation: &tantivy::query::Explanation) -> Self {
+        serde_json::to_value(explanation)
+            .and_then(serde_json::from_value)
+            .unwrap_or_else(|_| Self {
+                value: explanation.value(),
+                description: "score".to_string(),
+                details: Vec::new(),
+                context: Vec::new(),
+            })
+    }
+
+    /// Flatten the tree into the score each matched term contributed.
+    ///
+    /// `field_name` maps a Tantivy field id to its schema name. Results are
+    /// sorted by score, largest first.
+    #[must_use]
+    pub fn term_contributions(&self, field_name: impl Fn(u32) -> String) -> Vec<TermContribution> {
+        let mut terms = Vec::new();
+        self.collect_terms(1.0, &field_name, &mut terms);
+        terms.sort_by(|a, b| b.score.total_cmp(&a.score));
+        terms
+    }
+
+    fn collect_terms(
+        &self,
+        boost: f32,
+        field_name: &impl Fn(u32) -> String,
+        terms: &mut Vec<TermContribution>,
+    ) {
+        if let Some((field, term)) = self.context.iter().find_map(|c| parse_term_context(c)) {
+            terms.push(TermContribution {
+                field: field_name(field),
+                term,
+                bm25: self.value,
+                boost,
+                score: self.value * boost,
+            });
+            return;
+        }
+        let boost = boost * self.boost_factor().unwrap_or(1.0);
+        for detail in &self.details {
+            detail.collect_terms(boost, field_name, terms);
+        }
+    }
+
+    /// Factor of a `Boost x{factor} of ...` node.
+    fn boost_factor(&self) -> Option<f32> {
+        self.description
+            .strip_prefix("Boost x")?
+            .split_whitespace()
+            .next()?
+            .parse()
+            .ok()
+    }
+}
+
+/// Parse `Term=Term(field=3, type=Str, "hook")` into `(3, "hook")`.
+fn parse_term_context(context: &str) -> Option<(u32, String)> {
+    let rest = context.strip_prefix("Term=Term(field=")?;
+    let (field, rest) = rest.split_once(", type=")?;
+    let (_, value) = rest.split_once(", ")?;
+    let value = value.strip_suffix(')')?;
+    let value = value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .unwrap_or(value);
+    Some((field.parse().ok()?, value.replace("\\\"", "\"")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(value: f32, description: &str, details: Vec<ScoreExplanation>) -> ScoreExplanation {
+        ScoreExplanation {
+            value,
+            description: description.to_string(),
+            details,
+            context: Vec::new(),
+        }
+    }
+
+    fn term(value: f32, field: u32, text: &str) -> ScoreExplanation {
+        ScoreExplanation {
+            context: vec![format!("Term=Term(field={field}, type=Str, \"{text}\")")],
+            ..node(value, "TermQuery, product of...", Vec::new())
+        }
+    }
+
+    #[test]
+    fn term_contributions_apply_ancestor_boosts() {
+        let tree = node(
+            7.0,
+            "BooleanClause. sum of ...",
+            vec![
+                term(1.0, 0, "hook"),
+                node(6.0, "Boost x2 of ...", vec![term(3.0, 1, "state")]),
+            ],
+        );
+        let names = |id: u32| ["content", "headingPath"][id as usize].to_string();
+        let terms = tree.term_contributions(names);
+
+        assert_eq!(terms.len(), 2);
+        assert_eq!(
+            (terms[0].field.as_str(), terms[0].term.as_str()),
+            ("headingPath", "state")
+        );
+        assert!((terms[0].boost - 2.0).abs() < f32::EPSILON);
+        assert!((terms[0].score - 6.0).abs() < f32::EPSILON);
+        let total: f32 = terms.iter().map(|t| t.score).sum();
+        assert!((total - tree.value).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn parses_term_context() {
+        assert_eq!(
+            parse_term_context("Term=Term(field=12, type=Str, \"use\")"),
+            Some((12, "use".to_string()))
+        );
+        assert_eq!(parse_term_context("Term=garbage"), None);
+    }
+}
@@ -1,14 +1,115 @@
-use crate::{Error, Result};
+use crate::throttle::{Bandwidth, Throttled, TokenBucket};
+use crate::{DefaultsConfig, Error, FetchAuth, MirrorList, Result};
 use base64::{Engine, engine::general_purpose::STANDARD};
-use reqwest::header::{CONTENT_LENGTH, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
-use reqwest::{Client, StatusCode};
+use chrono::Utc;
+use futures::StreamExt;
+use reqwest::header::{
+    CONTENT_LENGTH, ETAG, HeaderMap, HeaderName, HeaderValue, IF_MODIFIED_SINCE, IF_NONE_MATCH,
+    LAST_MODIFIED,
+};
+use reqwest::{Certificate, Client, ClientBuilder, NoProxy, Proxy, RequestBuilder, StatusCode};
 use sha2::{Digest, Sha256};
-use std::time::Duration;
-use tracing::{debug, info};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
+
+/// Proxy and TLS settings for reaching documentation hosts.
+///
+/// Without an explicit proxy, requests go through `HTTPS_PROXY` / `HTTP_PROXY`
+/// from the environment, skipping hosts listed in `NO_PROXY`.
+#[derive(Debug, Clone, Default)]
+pub struct NetworkOptions {
+    /// Proxy URL used for every request, overriding the environment.
+    pub proxy: Option<String>,
+    /// PEM bundle of additional trusted root certificates.
+    pub ca_bundle: Option<PathBuf>,
+    /// Skip TLS certificate verification entirely.
+    pub insecure: bool,
+}
+
+impl NetworkOptions {
+    /// Network options configured under `[defaults]`.
+    #[must_use]
+    pub fn from_defaults(defaults: &DefaultsConfig) -> Self {
+        Self {
+            proxy: defaults.proxy.clone(),
+            ca_bundle: defaults.ca_bundle.clone(),
+            insecure: false,
+        }
+    }
+
+    fn apply(&self, mut builder: ClientBuilder) -> Result<ClientBuilder> {
+        for proxy in self.proxies()? {
+            builder = builder.proxy(proxy);
+        }
+        if let Some(path) = &self.ca_bundle {
+            for cert in load_ca_bundle(path)? {
+                builder = builder.add_root_certificate(cert);
+            }
+        }
+        if self.insecure {
+            warn!("TLS certificate verification is disabled");
+            builder = builder.danger_accept_invalid_certs(true);
+        }
+        Ok(builder)
+    }
+
+    fn proxies(&self) -> Result<Vec<Proxy>> {
+        let invalid =
+            |url: &str, e: reqwest::Error| Error::Config(format!("Invalid proxy URL '{url}': {e}"));
+        if let Some(url) = self.proxy.as_deref() {
+            let proxy = Proxy::all(url).map_err(|e| invalid(url, e))?;
+            return Ok(vec![proxy.no_proxy(NoProxy::from_env())]);
+        }
+
+        let mut proxies = Vec::new();
+        if let Some(url) = env_proxy("HTTPS_PROXY") {
+            let proxy = Proxy::https(&url).map_err(|e| invalid(&url, e))?;
+            proxies.push(proxy.no_proxy(NoProxy::from_env()));
+        }
+        if let Some(url) = env_proxy("HTTP_PROXY") {
+            let proxy = Proxy::http(&url).map_err(|e| invalid(&url, e))?;
+            proxies.push(proxy.no_proxy(NoProxy::from_env()));
+        }
+        Ok(proxies)
+    }
+}
+
+/// Reads a proxy variable, accepting the lowercase spelling curl also honors.
+fn env_proxy(name: &str) -> Option<String> {
+    [name.to_string(), name.to_ascii_lowercase()]
+        .iter()
+        .filter_map(|key| std::env::var(key).ok())
+        .map(|value| value.trim().to_string())
+        .find(|value| !value.is_empty())
+}
+
+fn load_ca_bundle(path: &Path) -> Result<Vec<Certificate>> {
+    let pem = std::fs::read(path).map_err(|e| {
+        Error::Config(format!(
+            "Failed to read CA bundle '{}': {e}",
+            path.display()
+        ))
+    })?;
+    let certs = Certificate::from_pem_bundle(&pem)
+        .map_err(|e| Error::Config(format!("Invalid CA bundle '{}': {e}", path.display())))?;
+    if certs.is_empty() {
+        return Err(Error::Config(format!(
+            "CA bundle '{}' contains no certificates",
+            path.display()
+        )));
+    }
+    Ok(certs)
+}
 
 /// HTTP client for fetching llms.txt documentation with conditional request support
+#[derive(Clone)]
 pub struct Fetcher {
     client: Client,
+    throttle: Option<Arc<Mutex<TokenBucket>>>,
+    /// Credential headers added to every request.
+    auth: HeaderMap,
 }
 
 impl Fetcher {
@@ -27,14 +128,95 @@ impl Fetcher {
     ///
     /// Returns an error if the HTTP client cannot be constructed.
     pub fn with_timeout(timeout: Duration) -> Result<Self> {
-        let client = Client::builder()
+        Self::with_options(timeout, &NetworkOptions::default())
+    }
+
+    /// Creates a new fetcher that routes through a proxy or trusts extra CAs.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the proxy URL is invalid, the CA bundle cannot be
+    /// read, or the HTTP client cannot be constructed.
+    pub fn with_network(network: &NetworkOptions) -> Result<Self> {
+        Self::with_options(Duration::from_secs(30), network)
+    }
+
+    fn with_options(timeout: Duration, network: &NetworkOptions) -> Result<Self> {
+        let builder = Client::builder()
             .timeout(timeout)
             .user_agent(concat!("outfitter-blz/", env!("CARGO_PKG_VERSION")))
             .gzip(true)
-            .brotli(true)
-            .build()
-            .map_err(Error::Network)?;
-        Ok(Self { client })
+            .brotli(true);
+        let client = network.apply(builder)?.build().map_err(Error::Network)?;
+        Ok(Self {
+            client,
+            throttle: None,
+            auth: HeaderMap::new(),
+        })
+    }
+
+    /// Limits download bandwidth across every fetch made by this fetcher.
+    #[must_use]
+    pub fn with_throttle(mut self, bandwidth: Option<Bandwidth>) -> Self {
+        self.throttle = bandwidth.map(|rate| Arc::new(Mutex::new(TokenBucket::new(rate))));
+        self
+    }
+
+    /// Sends a source's credentials with every request made by this fetcher.
+    ///
+    /// Header values are marked sensitive so they stay out of debug logs.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a secret cannot be resolved or is not a valid header.
+    pub fn with_auth(mut self, auth: &FetchAuth) -> Result<Self> {
+        let mut headers = HeaderMap::new();
+        for (name, value) in auth.header_pairs()? {
+            let name = HeaderName::from_bytes(name.as_bytes())
+                .map_err(|e| Error::Config(format!("Invalid auth header name '{name}': {e}")))?;
+            let mut value = HeaderValue::from_str(&value).map_err(|e| {
+                Error::Config(format!("Invalid value for auth header '{name}': {e}"))
+            })?;
+            value.set_sensitive(true);
+            headers.insert(name, value);
+        }
+        self.auth = headers;
+        Ok(self)
+    }
+
+    /// The underlying client, for requests that share its proxy and TLS
+    /// settings but not its credentials (e.g. robots.txt).
+    pub(crate) const fn http_client(&self) -> &Client {
+        &self.client
+    }
+
+    fn get(&self, url: &str) -> RequestBuilder {
+        self.client.get(url).headers(self.auth.clone())
+    }
+
+    /// Reads a response body, pacing it through the bandwidth limit if one is set.
+    async fn read_body(&self, response: reqwest::Response) -> Result<String> {
+        if self.throttle.is_none() {
+            return Ok(response.text().await?);
+        }
+
+        let body = self.read_bytes(response).await?;
+        Ok(String::from_utf8(body)
+            .unwrap_or_else(|err| String::from_utf8_lossy(err.as_bytes()).into_owned()))
+    }
+
+    /// Reads a raw response body, pacing it through the bandwidth limit if one is set.
+    async fn read_bytes(&self, response: reqwest::Response) -> Result<Vec<u8>> {
+        let Some(bucket) = &self.throttle else {
+            return Ok(response.bytes().await?.to_vec());
+        };
+
+        let mut body = Vec::new();
+        let mut stream = Throttled::new(Box::pin(response.bytes_stream()), Arc::clone(bucket));
+        while let Some(chunk) = stream.next().await {
+            body.extend_from_slice(&chunk?);
+        }
+        Ok(body)
     }
 
     /// Fetches a URL with conditional request support using `ETag` and `Last-Modified` headers.
@@ -48,7 +230,7 @@ impl Fetcher {
         etag: Option<&str>,
         last_modified: Option<&str>,
     ) -> Result<FetchResult> {
-        let mut request = self.client.get(url);
+        let mut request = self.get(url);
 
         if let Some(tag) = etag {
             debug!("Setting If-None-Match: {}", tag);
@@ -112,7 +294,7 @@ impl Fetcher {
             .and_then(|v| v.to_str().ok())
             .map(std::string::ToString::to_string);
 
-        let content = response.text().await?;
+        let content = self.read_body(response).await?;
         let sha256 = calculate_sha256(&content);
 
         info!("Fetched {} bytes from {}", content.len(), url);
@@ -125,13 +307,47 @@ impl Fetcher {
         })
     }
 
+    /// Fetches from the healthiest of several mirror URLs, falling back in rank order.
+    ///
+    /// Each attempt's latency or failure is recorded in `mirrors`, so callers
+    /// should persist the list afterwards. Returns the URL that answered along
+    /// with its result.
+    ///
+    /// # Errors
+    ///
+    /// Returns the last attempt's error if every candidate fails.
+    pub async fn fetch_with_mirrors(
+        &self,
+        candidates: &[String],
+        mirrors: &mut MirrorList,
+        etag: Option<&str>,
+        last_modified: Option<&str>,
+    ) -> Result<(String, FetchResult)> {
+        let mut last_error = None;
+        for url in mirrors.rank(candidates, Utc::now()) {
+            let started = Instant::now();
+            match self.fetch_with_cache(&url, etag, last_modified).await {
+                Ok(result) => {
+                    mirrors.record_success(&url, started.elapsed(), Utc::now());
+                    return Ok((url, result));
+                },
+                Err(err) => {
+                    warn!("Mirror {} failed: {}", url, err);
+                    mirrors.record_failure(&url, Utc::now());
+                    last_error = Some(err);
+                },
+            }
+        }
+        Err(last_error.unwrap_or_else(|| Error::Other("No mirror URLs to fetch".to_string())))
+    }
+
     /// Fetches a URL without conditional request support, returning content and `SHA256` hash.
     ///
     /// # Errors
     ///
     /// Returns an error if the request fails or the server response is unsuccessful.
     pub async fn fetch(&self, url: &str) -> Result<(String, String)> {
-        let response = self.client.get(url).send().await?;
+        let response = self.get(url).send().await?;
         let status = response.status();
 
         if !status.is_success() {
@@ -149,19 +365,43 @@ impl Fetcher {
             }
         }
 
-        let content = response.text().await?;
+        let content = self.read_body(response).await?;
         let sha256 = calculate_sha256(&content);
 
         Ok((content, sha256))
     }
 
+    /// Fetches a URL as raw bytes (for compressed or binary payloads).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or the server response is unsuccessful.
+    pub async fn fetch_bytes(&self, url: &str) -> Result<Vec<u8>> {
+        let response = self.get(url).send().await?;
+        let status = response.status();
+
+        if status == StatusCode::NOT_FOUND {
+            return Err(Error::NotFound(format!("Resource not found at '{url}'")));
+        }
+        let response = response.error_for_status().map_err(Error::Network)?;
+
+        let body = self.read_bytes(response).await?;
+        info!("Fetched {} bytes from {}", body.len(), url);
+        Ok(body)
+    }
+
     /// Perform a HEAD request to retrieve basic metadata for a URL without downloading content.
     ///
     /// # Errors
     ///
     /// Returns an error if the request fails.
     pub async fn head_metadata(&self, url: &str) -> Result<HeadInfo> {
-        let response = self.client.head(url).send().await?;
+        let response = self
+            .client
+            .head(url)
+            .headers(self.auth.clone())
+            .send()
+            .await?;
         let status = response.status();
 
         let content_length = response
@@ -226,13 +466,57 @@ pub enum FetchResult {
     },
 }
 
-fn calculate_sha256(content: &str) -> String {
+pub(crate) fn calculate_sha256(content: &str) -> String {
     let mut hasher = Sha256::new();
     hasher.update(content.as_bytes());
     let result = hasher.finalize();
     STANDARD.encode(result)
 }
 
+/// Read a local document, skipping the read when its modification time
+/// matches `last_modified`.
+///
+/// The file's mtime (RFC 3339) stands in for the `Last-Modified` header, so
+/// local sources sync through the same conditional path as remote ones. MDX,
+/// notebooks, reStructuredText, plain text, and PDFs are converted to markdown
+/// as on `blz add`.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read or converted.
+pub fn fetch_local(path: &Path, last_modified: Option<&str>) -> Result<FetchResult> {
+    let modified = std::fs::metadata(path)?
+        .modified()
+        .ok()
+        .map(|time| chrono::DateTime::<Utc>::from(time).to_rfc3339());
+    if modified.is_some() && modified.as_deref() == last_modified {
+        debug!("Local file unchanged: {}", path.display());
+        return Ok(FetchResult::NotModified {
+            etag: None,
+            last_modified: modified,
+        });
+    }
+
+    let raw = std::fs::read(path)?;
+    let content = crate::markup::file_to_markdown(path, &raw)?;
+    let sha256 = calculate_sha256(&content);
+    Ok(FetchResult::Modified {
+        content,
+        etag: None,
+        last_modified: modified,
+        sha256,
+    })
+}
+
+/// Filesystem path named by a `file://` URL, or `None` for any other location.
+#[must_use]
+pub fn file_url_path(location: &str) -> Option<PathBuf> {
+    url::Url::parse(location)
+        .ok()
+        .filter(|url| url.scheme() == "file")
+        .and_then(|url| url.to_file_path().ok())
+}
+
 // Note: Default is not implemented as Fetcher::new() can fail.
 // Use Fetcher::new() directly and handle the Result.
 
@@ -251,6 +535,55 @@ mod tests {
         matchers::{header, method, path},
     };
 
+    #[test]
+    fn test_network_options_reject_bad_proxy_and_ca_bundle() {
+        let bad_proxy = NetworkOptions {
+            proxy: Some("not a url".to_string()),
+            ..NetworkOptions::default()
+        };
+        let err = Fetcher::with_network(&bad_proxy).err().unwrap();
+        assert!(err.to_string().contains("Invalid proxy URL"));
+
+        let dir = tempfile::tempdir().unwrap();
+        let missing = NetworkOptions {
+            ca_bundle: Some(dir.path().join("missing.pem")),
+            ..NetworkOptions::default()
+        };
+        let err = Fetcher::with_network(&missing).err().unwrap();
+        assert!(err.to_string().contains("Failed to read CA bundle"));
+
+        let empty_path = dir.path().join("empty.pem");
+        std::fs::write(&empty_path, "").unwrap();
+        let empty = NetworkOptions {
+            ca_bundle: Some(empty_path),
+            ..NetworkOptions::default()
+        };
+        let err = Fetcher::with_network(&empty).err().unwrap();
+        assert!(err.to_string().contains("contains no certificates"));
+    }
+
+    #[tokio::test]
+    async fn test_explicit_proxy_receives_requests() {
+        let proxy = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/llms.txt"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("# Proxied"))
+            .expect(1)
+            .mount(&proxy)
+            .await;
+
+        let fetcher = Fetcher::with_network(&NetworkOptions {
+            proxy: Some(proxy.uri()),
+            ..NetworkOptions::default()
+        })
+        .unwrap();
+        let (body, _) = fetcher
+            .fetch("http://docs.internal.invalid/llms.txt")
+            .await
+            .unwrap();
+        assert_eq!(body, "# Proxied");
+    }
+
     #[tokio::test]
     async fn test_fetcher_creation() {
         // Test that fetcher can be created successfully
@@ -628,4 +961,79 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    #[ignore = "network: run in CI"]
+    async fn test_fetch_with_mirrors_falls_back_and_records_health() -> anyhow::Result<()> {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/primary/llms.txt"))
+            .respond_with(ResponseTemplate::new(503))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/mirror/llms.txt"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("# Mirrored"))
+            .mount(&mock_server)
+            .await;
+
+        let fetcher = Fetcher::new()?;
+        let primary = format!("{}/primary/llms.txt", mock_server.uri());
+        let mirror = format!("{}/mirror/llms.txt", mock_server.uri());
+        let mut mirrors = MirrorList::new(vec![mirror.clone()]);
+        let candidates = mirrors.candidates(&primary);
+
+        let (served_by, result) = fetcher
+            .fetch_with_mirrors(&candidates, &mut mirrors, None, None)
+            .await?;
+        assert_eq!(served_by, mirror);
+        assert!(
+            matches!(result, FetchResult::Modified { ref content, .. } if content == "# Mirrored")
+        );
+        assert_eq!(mirrors.health[&primary].consecutive_failures, 1);
+        assert!(mirrors.health[&mirror].latency_ms.is_some());
+
+        // The failed primary is now ranked behind the healthy mirror
+        assert_eq!(mirrors.rank(&candidates, Utc::now()), vec![mirror, primary]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_fetch_local_skips_unchanged_files() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let file = dir.path().join("llms.txt");
+        std::fs::write(&file, "# Internal\n\nPrivate docs.\n")?;
+
+        let FetchResult::Modified {
+            content,
+            last_modified,
+            ..
+        } = fetch_local(&file, None)?
+        else {
+            anyhow::bail!("first read should return content");
+        };
+        assert!(content.starts_with("# Internal"));
+        assert!(last_modified.is_some());
+
+        let again = fetch_local(&file, last_modified.as_deref())?;
+        assert!(matches!(again, FetchResult::NotModified { .. }));
+        assert!(matches!(
+            fetch_local(&file, Some("2000-01-01T00:00:00+00:00"))?,
+            FetchResult::Modified { .. }
+        ));
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_file_url_path() {
+        assert_eq!(
+            file_url_path("file:///srv/docs/llms.txt"),
+            Some(PathBuf::from("/srv/docs/llms.txt"))
+        );
+        assert_eq!(file_url_path("https://example.com/llms.txt"), None);
+        assert_eq!(file_url_path("./llms.txt"), None);
+    }
 }
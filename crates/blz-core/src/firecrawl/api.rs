@@ -0,0 +1,313 @@
+//! Scraping through the Firecrawl HTTP API.
+//!
+//! An alternative to the Firecrawl CLI for environments where installing a
+//! Node tool is not an option: only an API key is needed. The key comes from
+//! `defaults.firecrawl_api_key` in the config or `FIRECRAWL_API_KEY`, and
+//! `BLZ_FIRECRAWL_URL` points at a self-hosted instance.
+//!
+//! ## Usage
+//!
+//! ```rust,no_run
+//! use blz_core::firecrawl::{FirecrawlApi, ScrapeOptions};
+//!
+//! # async fn example() -> blz_core::Result<()> {
+//! let api = FirecrawlApi::new("fc-...")?;
+//! let result = api
+//!     .scrape("https://example.com/docs", ScrapeOptions::default().with_main_content_only(true))
+//!     .await?;
+//! println!("Got {} bytes of markdown", result.markdown.len());
+//! # Ok(())
+//! # }
+//! ```
+
+use std::time::Duration;
+
+use reqwest::{Client, StatusCode};
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+
+use super::ScrapeOptions;
+use super::scrape::ScrapeResult;
+use crate::{Config, Error, Result};
+
+/// Hosted Firecrawl API.
+pub const DEFAULT_API_URL: &str = "https://api.firecrawl.dev";
+
+/// Environment variable Firecrawl's own tools read the API key from.
+pub const API_KEY_ENV: &str = "FIRECRAWL_API_KEY";
+
+/// Default timeout for scrape requests (60 seconds).
+const DEFAULT_SCRAPE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Client for the Firecrawl scrape API.
+///
+/// `Debug` output never shows the API key.
+#[derive(Clone)]
+pub struct FirecrawlApi {
+    client: Client,
+    base_url: String,
+    api_key: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ScrapeRequest<'a> {
+    url: &'a str,
+    formats: [&'a str; 1],
+    only_main_content: bool,
+    timeout: u64,
+}
+
+#[derive(Deserialize)]
+struct ScrapeResponse {
+    #[serde(default)]
+    success: bool,
+    #[serde(default)]
+    data: Option<ScrapeData>,
+    #[serde(default)]
+    error: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ScrapeData {
+    #[serde(default)]
+    markdown: Option<String>,
+    #[serde(default)]
+    metadata: ScrapeMetadata,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ScrapeMetadata {
+    #[serde(default)]
+    title: Option<String>,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default, rename = "sourceURL")]
+    source_url: Option<String>,
+    #[serde(default)]
+    status_code: Option<u16>,
+}
+
+impl FirecrawlApi {
+    /// Create a client for the hosted API, or the instance at
+    /// `BLZ_FIRECRAWL_URL` when set.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the API key is empty or the HTTP client cannot be built.
+    pub fn new(api_key: impl Into<String>) -> Result<Self> {
+        let api_key = api_key.into();
+        if api_key.trim().is_empty() {
+            return Err(Error::Config("Firecrawl API key is empty".to_string()));
+        }
+        let base_url = std::env::var("BLZ_FIRECRAWL_URL")
+            .ok()
+            .filter(|value| !value.trim().is_empty())
+            .unwrap_or_else(|| DEFAULT_API_URL.to_string());
+        let client = Client::builder()
+            .user_agent(concat!("blz/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .map_err(|e| Error::Config(format!("Failed to build Firecrawl client: {e}")))?;
+        Ok(Self {
+            client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            api_key,
+        })
+    }
+
+    /// Create a client from the configured key, falling back to
+    /// `FIRECRAWL_API_KEY`.
+    ///
+    /// Returns `Ok(None)` when no key is configured.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a configured `env:` key reference is unset, or the
+    /// HTTP client cannot be built.
+    pub fn from_config(config: &Config) -> Result<Option<Self>> {
+        let key = match &config.defaults.firecrawl_api_key {
+            Some(secret) => secret.resolve()?,
+            None => match std::env::var(API_KEY_ENV) {
+                Ok(key) if !key.trim().is_empty() => key,
+                _ => return Ok(None),
+            },
+        };
+        Self::new(key.trim()).map(Some)
+    }
+
+    /// Base URL requests are sent to.
+    #[must_use]
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Scrape a URL and return markdown content.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::FirecrawlScrapeFailed`] if the request fails, times
+    /// out, is rejected by the API, or returns no markdown.
+    #[instrument(level = "debug", skip(self, options), fields(url = %url))]
+    pub async fn scrape(&self, url: &str, options: ScrapeOptions) -> Result<ScrapeResult> {
+        let failed = |reason: String| Error::FirecrawlScrapeFailed {
+            url: url.to_string(),
+            reason,
+        };
+        let timeout = options.timeout.unwrap_or(DEFAULT_SCRAPE_TIMEOUT);
+        let body = ScrapeRequest {
+            url,
+            formats: ["markdown"],
+            only_main_content: options.only_main_content,
+            timeout: u64::try_from(timeout.as_millis()).unwrap_or(u64::MAX),
+        };
+
+        let response = self
+            .client
+            .post(format!("{}/v1/scrape", self.base_url))
+            .bearer_auth(&self.api_key)
+            .timeout(timeout + Duration::from_secs(5))
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| {
+                if e.is_timeout() {
+                    failed(format!("timed out after {}s", timeout.as_secs()))
+                } else {
+                    failed(e.to_string())
+                }
+            })?;
+
+        let status = response.status();
+        let parsed: Option<ScrapeResponse> = response.json().await.ok();
+        let api_error = parsed.as_ref().and_then(|r| r.error.clone());
+        match status {
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => {
+                return Err(failed("API key was rejected".to_string()));
+            },
+            StatusCode::PAYMENT_REQUIRED => {
+                return Err(failed("out of Firecrawl credits".to_string()));
+            },
+            StatusCode::TOO_MANY_REQUESTS => {
+                return Err(failed("rate limited (429)".to_string()));
+            },
+            status if !status.is_success() => {
+                return Err(failed(
+                    api_error.unwrap_or_else(|| format!("HTTP {status}")),
+                ));
+            },
+            _ => {},
+        }
+
+        let Some(ScrapeResponse {
+            success: true,
+            data: Some(data),
+            ..
+        }) = parsed
+        else {
+            return Err(failed(
+                api_error.unwrap_or_else(|| "unexpected API response".to_string()),
+            ));
+        };
+        let markdown = data
+            .markdown
+            .filter(|markdown| !markdown.trim().is_empty())
+            .ok_or_else(|| failed("page has no markdown content".to_string()))?;
+
+        Ok(ScrapeResult {
+            markdown,
+            title: data.metadata.title.filter(|title| !title.trim().is_empty()),
+            description: data.metadata.description,
+            url: data.metadata.source_url.unwrap_or_else(|| url.to_string()),
+            status_code: data.metadata.status_code,
+        })
+    }
+}
+
+impl std::fmt::Debug for FirecrawlApi {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FirecrawlApi")
+            .field("base_url", &self.base_url)
+            .field("api_key", &"<redacted>")
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{body_partial_json, header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn api(server: &MockServer) -> FirecrawlApi {
+        let mut api = FirecrawlApi::new("fc-test").unwrap();
+        api.base_url = server.uri();
+        api
+    }
+
+    #[tokio::test]
+    async fn test_scrape_returns_markdown_and_metadata() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/scrape"))
+            .and(header("authorization", "Bearer fc-test"))
+            .and(body_partial_json(serde_json::json!({
+                "url": "https://docs.example.com/intro",
+                "formats": ["markdown"],
+                "onlyMainContent": true,
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "success": true,
+                "data": {
+                    "markdown": "# Intro\n\nHello.",
+                    "metadata": {
+                        "title": "Intro",
+                        "sourceURL": "https://docs.example.com/intro",
+                        "statusCode": 200
+                    }
+                }
+            })))
+            .mount(&server)
+            .await;
+
+        let result = api(&server)
+            .scrape(
+                "https://docs.example.com/intro",
+                ScrapeOptions::default().with_main_content_only(true),
+            )
+            .await
+            .unwrap();
+        assert_eq!(result.markdown, "# Intro\n\nHello.");
+        assert_eq!(result.title.as_deref(), Some("Intro"));
+        assert_eq!(result.status_code, Some(200));
+    }
+
+    #[tokio::test]
+    async fn test_scrape_reports_api_errors() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/scrape"))
+            .respond_with(ResponseTemplate::new(402).set_body_json(serde_json::json!({
+                "success": false,
+                "error": "Insufficient credits"
+            })))
+            .mount(&server)
+            .await;
+
+        let err = api(&server)
+            .scrape("https://docs.example.com/intro", ScrapeOptions::default())
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("out of Firecrawl credits"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_debug_redacts_key() {
+        let api = FirecrawlApi::new("fc-secret").unwrap();
+        assert!(!format!("{api:?}").contains("fc-secret"));
+    }
+}
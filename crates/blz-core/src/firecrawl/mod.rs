@@ -2,7 +2,8 @@
 //!
 //! This module provides detection and integration with the Firecrawl CLI tool,
 //! which enables BLZ to scrape web documentation when sites don't provide native
-//! llms-full.txt files.
+//! llms-full.txt files. [`FirecrawlApi`] scrapes through the HTTP API instead,
+//! for setups with an API key but no CLI.
 //!
 //! ## Usage
 //!
@@ -28,9 +29,11 @@
 //! # }
 //! ```
 
+pub mod api;
 pub mod detect;
 pub mod scrape;
 
+pub use api::FirecrawlApi;
 pub use detect::{FirecrawlCli, FirecrawlStatus, detect_firecrawl};
 pub use scrape::{MapResult, ScrapeOptions, ScrapeResult};
 
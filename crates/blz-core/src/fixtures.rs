@@ -0,0 +1,288 @@
+//! Synthetic llms.txt corpora for tests and benchmarks.
+//!
+//! [`generate`] builds a markdown document shaped like real developer docs:
+//! nested headings, prose drawn from a technical vocabulary, and fenced code
+//! blocks in a few languages. Output depends only on the [`FixtureSpec`], so
+//! the same seed always produces the same bytes on every platform.
+
+use std::fmt::Write as _;
+
+/// Shape of a generated fixture document.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FixtureSpec {
+    /// Number of headed sections below the document title.
+    pub sections: usize,
+    /// Deepest heading nesting below the title (1 = only `##` headings).
+    pub depth: u8,
+    /// Paragraphs or code blocks per section.
+    pub blocks_per_section: usize,
+    /// Fraction of blocks that are fenced code (0.0-1.0).
+    pub code_density: f64,
+    /// Seed for the deterministic generator.
+    pub seed: u64,
+}
+
+impl Default for FixtureSpec {
+    fn default() -> Self {
+        Self {
+            sections: 100,
+            depth: 3,
+            blocks_per_section: 3,
+            code_density: 0.25,
+            seed: 42,
+        }
+    }
+}
+
+const TOPICS: &[&str] = &[
+    "Routing",
+    "Middleware",
+    "Caching",
+    "Authentication",
+    "Sessions",
+    "Streaming",
+    "Configuration",
+    "Deployment",
+    "Testing",
+    "Migrations",
+    "Webhooks",
+    "Rate Limits",
+    "Error Handling",
+    "Observability",
+    "Plugins",
+    "Queues",
+];
+
+const FACETS: &[&str] = &[
+    "Overview",
+    "Quick Start",
+    "Options",
+    "Examples",
+    "Advanced Usage",
+    "Troubleshooting",
+    "API Reference",
+    "Best Practices",
+];
+
+const WORDS: &[&str] = &[
+    "request",
+    "response",
+    "handler",
+    "router",
+    "cache",
+    "token",
+    "session",
+    "schema",
+    "query",
+    "index",
+    "worker",
+    "stream",
+    "buffer",
+    "timeout",
+    "retry",
+    "callback",
+    "promise",
+    "async",
+    "await",
+    "config",
+    "runtime",
+    "server",
+    "client",
+    "header",
+    "payload",
+    "endpoint",
+    "deploy",
+    "build",
+    "bundle",
+    "module",
+    "export",
+    "import",
+    "hook",
+    "state",
+    "effect",
+    "context",
+    "provider",
+    "adapter",
+    "driver",
+    "pool",
+    "connection",
+    "transaction",
+    "migration",
+    "event",
+    "listener",
+    "signal",
+    "metric",
+    "trace",
+    "log",
+    "the",
+    "a",
+    "with",
+    "for",
+    "when",
+    "each",
+    "returns",
+    "uses",
+    "configures",
+    "handles",
+];
+
+const CODE: &[(&str, &str)] = &[
+    (
+        "ts",
+        "export async function handler(req: Request): Promise<Response> {\n  const session = await getSession(req);\n  return Response.json({ user: session.user });\n}",
+    ),
+    (
+        "rust",
+        "pub fn connect(config: &Config) -> Result<Pool> {\n    let pool = Pool::builder().max_size(config.pool_size).build()?;\n    Ok(pool)\n}",
+    ),
+    (
+        "bash",
+        "npm install @example/server\nnpx example deploy --env production",
+    ),
+    (
+        "python",
+        "def retry(fn, attempts=3):\n    for attempt in range(attempts):\n        try:\n            return fn()\n        except TimeoutError:\n            continue",
+    ),
+    (
+        "json",
+        "{\n  \"cache\": { \"ttl\": 300 },\n  \"routes\": [\"/api\", \"/health\"]\n}",
+    ),
+];
+
+/// `SplitMix64`: small, fast, and identical on every platform.
+struct Rng(u64);
+
+impl Rng {
+    const fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    const fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    const fn pick<'a>(&mut self, items: &[&'a str]) -> &'a str {
+        items[self.below(items.len())]
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    const fn chance(&mut self, probability: f64) -> bool {
+        let unit = (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64;
+        unit < probability
+    }
+}
+
+/// Generate a fixture document for `spec`.
+#[must_use]
+pub fn generate(spec: &FixtureSpec) -> String {
+    let mut rng = Rng(spec.seed);
+    let max_level = usize::from(spec.depth.clamp(1, 5)) + 1;
+    let mut out = format!("# Fixture Docs {}\n\n", spec.seed);
+    write_paragraph(&mut out, &mut rng);
+
+    let mut level = 2;
+    for section in 0..spec.sections {
+        // Step at most one level deeper so the outline stays well-formed
+        level = match rng.below(3) {
+            0 if level < max_level => level + 1,
+            1 if level > 2 => rng.below(level - 1) + 2,
+            _ => level,
+        };
+        let heading = if level == 2 {
+            rng.pick(TOPICS).to_string()
+        } else {
+            format!("{} {}", rng.pick(TOPICS), rng.pick(FACETS))
+        };
+        let _ = write!(out, "{} {heading} {}\n\n", "#".repeat(level), section + 1);
+
+        for _ in 0..spec.blocks_per_section.max(1) {
+            if rng.chance(spec.code_density) {
+                let (lang, code) = CODE[rng.below(CODE.len())];
+                let _ = write!(out, "```{lang}\n{code}\n```\n\n");
+            } else {
+                write_paragraph(&mut out, &mut rng);
+            }
+        }
+    }
+    out
+}
+
+fn write_paragraph(out: &mut String, rng: &mut Rng) {
+    for sentence in 0..rng.below(3) + 2 {
+        if sentence > 0 {
+            out.push(' ');
+        }
+        let words = rng.below(10) + 6;
+        for word in 0..words {
+            let token = rng.pick(WORDS);
+            if word == 0 {
+                let mut chars = token.chars();
+                if let Some(first) = chars.next() {
+                    out.extend(first.to_uppercase());
+                    out.push_str(chars.as_str());
+                }
+            } else {
+                out.push(' ');
+                out.push_str(token);
+            }
+        }
+        out.push('.');
+    }
+    out.push_str("\n\n");
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::MarkdownParser;
+
+    #[test]
+    fn same_seed_produces_identical_output() {
+        let spec = FixtureSpec::default();
+        assert_eq!(generate(&spec), generate(&spec));
+        let other = FixtureSpec {
+            seed: 7,
+            ..FixtureSpec::default()
+        };
+        assert_ne!(generate(&spec), generate(&other));
+    }
+
+    #[test]
+    fn headings_respect_depth_and_section_count() {
+        let spec = FixtureSpec {
+            sections: 60,
+            depth: 2,
+            ..FixtureSpec::default()
+        };
+        let doc = generate(&spec);
+        let headings: Vec<&str> = doc.lines().filter(|line| line.starts_with("##")).collect();
+        assert_eq!(headings.len(), 60);
+        assert!(headings.iter().all(|h| !h.starts_with("####")));
+        assert!(headings.iter().any(|h| h.starts_with("### ")));
+
+        let parsed = MarkdownParser::new().unwrap().parse(&doc).unwrap();
+        assert!(parsed.heading_blocks.len() >= 60);
+    }
+
+    #[test]
+    fn code_density_controls_fences() {
+        let prose = generate(&FixtureSpec {
+            code_density: 0.0,
+            ..FixtureSpec::default()
+        });
+        assert!(!prose.contains("```"));
+
+        let code = generate(&FixtureSpec {
+            code_density: 1.0,
+            ..FixtureSpec::default()
+        });
+        let fences = code.matches("```").count() / 2;
+        assert_eq!(fences, 100 * 3);
+    }
+}
@@ -0,0 +1,585 @@
+//! Link following for index-style llms.txt files.
+//!
+//! Many projects publish a thin llms.txt that only lists links to their docs
+//! pages. `blz add <alias> <url> --follow` fetches those pages (up to a
+//! bounded depth and page count), and merges them after the index into one
+//! document. Each page sits under a `# <title>` heading followed by a
+//! `Source: <url>` line, with its own headings demoted one level, so every
+//! search hit can be traced back to the page it came from. Which links are
+//! followed is decided by the [`FollowLinks`] policy.
+//!
+//! HTML pages, including an HTML index, are converted with [`crate::html`]
+//! unless the site publishes a `.md` version of the same URL, which most docs
+//! generators do. Sync refetches the index and every linked page and
+//! re-indexes only when the assembled document changed.
+//!
+//! Linked pages are checked against their host's robots.txt: disallowed pages
+//! are skipped (and listed as such), and requests are spaced by the host's
+//! `Crawl-delay` (see [`crate::discovery::robots`]). The index itself is the
+//! URL the user asked for and is always fetched.
+
+use std::collections::{HashSet, VecDeque};
+use std::net::IpAddr;
+use std::sync::LazyLock;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+use url::Url;
+
+use crate::collection::merge_members;
+use crate::discovery::RobotsPolicy;
+use crate::fetcher::calculate_sha256;
+use crate::html::{self, looks_like_html};
+use crate::refresh::{
+    ApplyRefreshParams, RefreshContext, RefreshIndexer, RefreshOutcome, RefreshPayload,
+    RefreshStorage, apply_refresh,
+};
+use crate::{DefaultsConfig, Error, Fetcher, FollowLinks, PerformanceMetrics, Result, SourceType};
+
+/// Default link depth: only pages linked directly from the index.
+pub const DEFAULT_MAX_DEPTH: usize = 1;
+
+/// Default cap on the number of pages fetched for one source.
+pub const DEFAULT_MAX_PAGES: usize = 50;
+
+/// Markdown inline links: `[text](url)` with an optional title.
+///
+/// SAFETY: Pattern is a compile-time constant that is known to be valid.
+#[allow(clippy::unwrap_used)]
+static MARKDOWN_LINK: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"\[([^\]\n]*)\]\(\s*<?([^()\s<>]+)>?(?:\s+"[^"]*")?\s*\)"#).unwrap()
+});
+
+/// Extensions of linked files that are never documentation pages.
+const BINARY_EXTENSIONS: [&str; 12] = [
+    "png", "jpg", "jpeg", "gif", "svg", "webp", "ico", "pdf", "zip", "gz", "mp4", "woff2",
+];
+
+/// How far and how wide to follow links from an index.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FollowOptions {
+    /// Which links may be followed.
+    pub policy: FollowLinks,
+    /// Domains followed under [`FollowLinks::Allowlist`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowlist: Vec<String>,
+    /// Link hops from the index (1 = pages the index links to).
+    pub max_depth: usize,
+    /// Maximum number of pages fetched, not counting the index.
+    pub max_pages: usize,
+    /// Fetch pages robots.txt disallows, without `Crawl-delay` pacing.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub ignore_robots: bool,
+}
+
+impl Default for FollowOptions {
+    fn default() -> Self {
+        Self {
+            policy: FollowLinks::FirstParty,
+            allowlist: Vec::new(),
+            max_depth: DEFAULT_MAX_DEPTH,
+            max_pages: DEFAULT_MAX_PAGES,
+            ignore_robots: false,
+        }
+    }
+}
+
+impl FollowOptions {
+    /// Options using the configured `follow_links` policy and allowlist.
+    #[must_use]
+    pub fn from_defaults(defaults: &DefaultsConfig) -> Self {
+        Self {
+            policy: defaults.follow_links.clone(),
+            allowlist: defaults.allowlist.clone(),
+            ..Self::default()
+        }
+    }
+
+    /// Sets the depth and page limits.
+    #[must_use]
+    pub const fn with_limits(mut self, max_depth: usize, max_pages: usize) -> Self {
+        self.max_depth = max_depth;
+        self.max_pages = max_pages;
+        self
+    }
+
+    /// Skips robots.txt checks when `ignore` is set (`--ignore-robots`).
+    #[must_use]
+    pub const fn ignoring_robots(mut self, ignore: bool) -> Self {
+        self.ignore_robots = ignore;
+        self
+    }
+
+    /// Whether `link`, found while following from `root`, may be fetched.
+    ///
+    /// First-party links share the root's registrable domain (approximated
+    /// by its last two labels), so `docs.example.com` may follow links to
+    /// `example.com` and `api.example.com`. Allowlisted domains match
+    /// themselves and their subdomains.
+    #[must_use]
+    pub fn allows(&self, link: &Url, root: &Url) -> bool {
+        let Some(host) = link.host_str() else {
+            return false;
+        };
+        match self.policy {
+            FollowLinks::None => false,
+            FollowLinks::FirstParty => root
+                .host_str()
+                .is_some_and(|root_host| site(root_host) == site(host)),
+            FollowLinks::Allowlist => self.allowlist.iter().any(|domain| {
+                let domain = domain.trim().trim_start_matches("*.");
+                host == domain
+                    || host
+                        .strip_suffix(domain)
+                        .is_some_and(|prefix| prefix.ends_with('.'))
+            }),
+        }
+    }
+}
+
+/// The registrable part of a host: the host itself for IPs, otherwise its
+/// last two labels.
+fn site(host: &str) -> String {
+    if host.parse::<IpAddr>().is_ok() {
+        return host.to_string();
+    }
+    let labels: Vec<&str> = host.split('.').collect();
+    labels[labels.len().saturating_sub(2)..].join(".")
+}
+
+/// A linked page that was not included, and why.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkippedLink {
+    /// Link target.
+    pub url: String,
+    /// Why the page was left out.
+    pub reason: String,
+}
+
+/// An index merged with the pages it links to.
+#[derive(Debug, Clone)]
+pub struct FollowedDocument {
+    /// The assembled markdown document.
+    pub content: String,
+    /// URLs of the pages included after the index, in document order.
+    pub pages: Vec<String>,
+    /// Followable links that could not be included.
+    pub skipped: Vec<SkippedLink>,
+    /// Followable links left out because `max_pages` was reached.
+    pub truncated: usize,
+}
+
+/// Links in `markdown` as `(text, url)` pairs, resolved against `base`.
+///
+/// Only `http(s)` links are returned, without fragments and in order of
+/// first appearance. Images and links to binary files are left out.
+#[must_use]
+pub fn extract_links(markdown: &str, base: &Url) -> Vec<(String, Url)> {
+    let mut seen = HashSet::new();
+    let mut links = Vec::new();
+    for caps in MARKDOWN_LINK.captures_iter(markdown) {
+        let (Some(whole), Some(text), Some(target)) = (caps.get(0), caps.get(1), caps.get(2))
+        else {
+            continue;
+        };
+        if markdown[..whole.start()].ends_with('!') {
+            continue;
+        }
+        let Ok(mut url) = base.join(target.as_str()) else {
+            continue;
+        };
+        if !matches!(url.scheme(), "http" | "https") || is_binary(&url) {
+            continue;
+        }
+        url.set_fragment(None);
+        if seen.insert(url.to_string()) {
+            links.push((text.as_str().trim().to_string(), url));
+        }
+    }
+    links
+}
+
+fn is_binary(url: &Url) -> bool {
+    url.path()
+        .rsplit_once('.')
+        .is_some_and(|(_, ext)| BINARY_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()))
+}
+
+/// Splits a leading `# Title` line off a page.
+fn take_title(markdown: &str) -> (Option<String>, &str) {
+    let body = markdown.trim_start();
+    let (first, rest) = body.split_once('\n').unwrap_or((body, ""));
+    first.strip_prefix("# ").map_or((None, markdown), |title| {
+        (Some(title.trim().to_string()), rest)
+    })
+}
+
+/// Fallback title for a page: the link text, or the last path segment.
+fn fallback_title(text: &str, url: &Url) -> String {
+    if !text.is_empty() {
+        return text.to_string();
+    }
+    url.path_segments()
+        .and_then(|mut segments| segments.rfind(|segment| !segment.is_empty()))
+        .map_or_else(|| url.to_string(), ToString::to_string)
+}
+
+/// Fetch a linked page as markdown.
+///
+/// HTML pages are replaced by their `.md` sibling when the site publishes
+/// one, and converted otherwise.
+async fn fetch_page(fetcher: &Fetcher, url: &Url) -> std::result::Result<String, String> {
+    let content = fetcher
+        .fetch(url.as_str())
+        .await
+        .map_err(|err| err.to_string())?
+        .0;
+    if !looks_like_html(&content) {
+        return Ok(content);
+    }
+    let path = url.path().trim_end_matches('/');
+    let is_markdown = std::path::Path::new(path)
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("md"));
+    if !path.is_empty() && !is_markdown {
+        let mut sibling = url.clone();
+        sibling.set_path(&format!("{path}.md"));
+        if let Ok((markdown, _)) = fetcher.fetch(sibling.as_str()).await {
+            if !looks_like_html(&markdown) {
+                return Ok(markdown);
+            }
+        }
+    }
+    html::to_markdown(&content)
+        .map(|document| document.markdown)
+        .map_err(|err| err.to_string())
+}
+
+/// Fetch the pages `index_content` links to and merge them after the index.
+///
+/// Links are followed breadth-first, at most `max_depth` hops from the index
+/// and `max_pages` pages in total. Pages that fail to load are recorded in
+/// [`FollowedDocument::skipped`] instead of failing the whole document.
+///
+/// # Errors
+///
+/// Returns an error if link following is disabled by the policy,
+/// `index_url` is not a valid URL, or the index is an HTML page without
+/// readable content.
+pub async fn follow_links(
+    fetcher: &Fetcher,
+    index_url: &str,
+    index_content: &str,
+    options: &FollowOptions,
+) -> Result<FollowedDocument> {
+    if options.policy == FollowLinks::None {
+        return Err(Error::Config(
+            "Link following is disabled (follow_links = \"none\")".to_string(),
+        ));
+    }
+    let root = Url::parse(index_url)
+        .map_err(|err| Error::InvalidUrl(format!("Invalid index URL '{index_url}': {err}")))?;
+    let converted;
+    let index_content = if looks_like_html(index_content) {
+        converted = html::to_markdown(index_content)?.markdown;
+        converted.as_str()
+    } else {
+        index_content
+    };
+
+    let mut visited: HashSet<String> = HashSet::from([root.to_string()]);
+    let mut queue: VecDeque<(String, Url, usize)> = VecDeque::new();
+    let mut enqueue = |queue: &mut VecDeque<_>, content: &str, base: &Url, depth: usize| {
+        for (text, link) in extract_links(content, base) {
+            if options.allows(&link, &root) && visited.insert(link.to_string()) {
+                queue.push_back((text, link, depth));
+            }
+        }
+    };
+    enqueue(&mut queue, index_content, &root, 1);
+
+    let (index_title, index_body) = take_title(index_content);
+    let mut sections = vec![(
+        index_title.unwrap_or_else(|| fallback_title("", &root)),
+        format!("Source: {root}\n\n{}", index_body.trim_start()),
+    )];
+    let mut pages = Vec::new();
+    let mut skipped = Vec::new();
+    let robots = if options.ignore_robots {
+        RobotsPolicy::ignoring()
+    } else {
+        RobotsPolicy::with_client(fetcher.http_client().clone())
+    };
+
+    while let Some((text, url, depth)) = queue.pop_front() {
+        if pages.len() >= options.max_pages {
+            queue.push_front((text, url, depth));
+            break;
+        }
+        if !robots.admit(&url).await {
+            skipped.push(SkippedLink {
+                url: url.to_string(),
+                reason: "disallowed by robots.txt".to_string(),
+            });
+            continue;
+        }
+        debug!("Following {url} (depth {depth})");
+        match fetch_page(fetcher, &url).await {
+            Ok(markdown) => {
+                if depth < options.max_depth {
+                    enqueue(&mut queue, &markdown, &url, depth + 1);
+                }
+                let (title, body) = take_title(&markdown);
+                let title = title.unwrap_or_else(|| fallback_title(&text, &url));
+                sections.push((title, format!("Source: {url}\n\n{}", body.trim_start())));
+                pages.push(url.to_string());
+            },
+            Err(reason) => skipped.push(SkippedLink {
+                url: url.to_string(),
+                reason,
+            }),
+        }
+    }
+
+    let content = merge_members(
+        sections
+            .iter()
+            .map(|(title, body)| (title.as_str(), body.as_str())),
+    );
+    Ok(FollowedDocument {
+        content,
+        pages,
+        skipped,
+        truncated: queue.len(),
+    })
+}
+
+/// Fetch an index and follow its links.
+///
+/// # Errors
+///
+/// Returns an error if the index cannot be fetched or link following fails.
+pub async fn fetch_followed(
+    fetcher: &Fetcher,
+    index_url: &str,
+    options: &FollowOptions,
+) -> Result<FollowedDocument> {
+    let (index, _) = fetcher.fetch(index_url).await?;
+    follow_links(fetcher, index_url, &index, options).await
+}
+
+/// Refresh a followed source by refetching its index and linked pages.
+///
+/// The cache is only rewritten when the assembled document changed.
+///
+/// # Errors
+///
+/// Returns an error if the source does not follow links, or if fetching,
+/// persistence, or indexing fails.
+pub async fn refresh_followed<S, I>(
+    storage: &S,
+    fetcher: &Fetcher,
+    alias: &str,
+    ctx: &RefreshContext,
+    metrics: PerformanceMetrics,
+    indexer: &I,
+    filter_preference: bool,
+) -> Result<RefreshOutcome>
+where
+    S: RefreshStorage + Sync,
+    I: RefreshIndexer + Sync,
+{
+    let Some(SourceType::Followed { url, follow }) = &ctx.existing_metadata.origin.source_type
+    else {
+        return Err(Error::Config(format!(
+            "Source '{alias}' does not follow links"
+        )));
+    };
+
+    let document = fetch_followed(fetcher, url, follow).await?;
+    let sha256 = calculate_sha256(&document.content);
+
+    let mut metadata = ctx.existing_metadata.clone();
+    let filter_changed = metadata.filter_non_english.unwrap_or(true) != filter_preference;
+    metadata.filter_non_english = Some(filter_preference);
+
+    if sha256 == metadata.sha256 {
+        if filter_changed {
+            storage.save_metadata(alias, &metadata)?;
+        }
+        return Ok(RefreshOutcome::Unchanged {
+            alias: alias.to_string(),
+        });
+    }
+
+    let payload = RefreshPayload {
+        content: document.content,
+        sha256,
+        etag: None,
+        last_modified: None,
+    };
+    let params = ApplyRefreshParams::new(metadata, ctx.existing_aliases.clone());
+    apply_refresh(storage, alias, &params, &payload, metrics, indexer).await
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn url(s: &str) -> Url {
+        Url::parse(s).unwrap()
+    }
+
+    #[test]
+    fn test_extract_links_resolves_and_skips_images() {
+        let base = url("https://docs.example.com/llms.txt");
+        let markdown = "- [Intro](/intro.md): Start here\n\
+                        - [Intro again](https://docs.example.com/intro.md#setup)\n\
+                        - ![Logo](/logo.png)\n\
+                        - [Guide](guide.md \"The guide\")\n\
+                        - [Mail](mailto:team@example.com)\n";
+        let links = extract_links(markdown, &base);
+        let urls: Vec<&str> = links.iter().map(|(_, link)| link.as_str()).collect();
+        assert_eq!(
+            urls,
+            [
+                "https://docs.example.com/intro.md",
+                "https://docs.example.com/guide.md"
+            ]
+        );
+        assert_eq!(links[0].0, "Intro");
+    }
+
+    #[test]
+    fn test_policies_decide_which_links_are_followed() {
+        let root = url("https://docs.example.com/llms.txt");
+        let first_party = FollowOptions::default();
+        assert!(first_party.allows(&url("https://api.example.com/ref"), &root));
+        assert!(first_party.allows(&url("https://example.com/guide"), &root));
+        assert!(!first_party.allows(&url("https://other.dev/docs"), &root));
+
+        let allowlist = FollowOptions {
+            policy: FollowLinks::Allowlist,
+            allowlist: vec!["other.dev".to_string()],
+            ..FollowOptions::default()
+        };
+        assert!(allowlist.allows(&url("https://docs.other.dev/a"), &root));
+        assert!(!allowlist.allows(&url("https://notother.dev/a"), &root));
+        assert!(!allowlist.allows(&url("https://api.example.com/ref"), &root));
+
+        let none = FollowOptions {
+            policy: FollowLinks::None,
+            ..FollowOptions::default()
+        };
+        assert!(!none.allows(&url("https://docs.example.com/a"), &root));
+    }
+
+    #[tokio::test]
+    async fn test_follow_links_merges_pages_with_provenance() {
+        let server = MockServer::start().await;
+        let base = server.uri();
+        let index = format!(
+            "# Example\n\n- [Install]({base}/install.md)\n- [Config]({base}/config)\n\
+             - [Missing]({base}/missing.md)\n- [Elsewhere](https://other.dev/x.md)\n"
+        );
+        Mock::given(method("GET"))
+            .and(path("/install.md"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                "# Installation\n\nRun the installer.\n\n## Windows\n\nSee [config](/config).\n",
+            ))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/config"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_string("<!DOCTYPE html><html></html>"),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/config.md"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("Set `PORT`.\n"))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/missing.md"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+
+        let fetcher = Fetcher::new().unwrap();
+        let document = follow_links(
+            &fetcher,
+            &format!("{base}/llms.txt"),
+            &index,
+            &FollowOptions::default(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            document.pages,
+            [format!("{base}/install.md"), format!("{base}/config")]
+        );
+        assert_eq!(document.skipped.len(), 1);
+        assert_eq!(document.skipped[0].url, format!("{base}/missing.md"));
+        assert!(document.content.starts_with("# Example\n\nSource: "));
+        assert!(document.content.contains(&format!(
+            "# Installation\n\nSource: {base}/install.md\n\nRun the installer.\n\n### Windows"
+        )));
+        assert!(document.content.contains("# Config\n\nSource: "));
+
+        let capped = follow_links(
+            &fetcher,
+            &format!("{base}/llms.txt"),
+            &index,
+            &FollowOptions::default().with_limits(1, 1),
+        )
+        .await
+        .unwrap();
+        assert_eq!(capped.pages.len(), 1);
+        assert_eq!(capped.truncated, 2);
+    }
+
+    #[tokio::test]
+    async fn test_follow_links_skips_pages_disallowed_by_robots() {
+        let server = MockServer::start().await;
+        let base = server.uri();
+        let index =
+            format!("# Example\n\n- [Guide]({base}/guide.md)\n- [Admin]({base}/admin/keys.md)\n");
+        Mock::given(method("GET"))
+            .and(path("/robots.txt"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_string("User-agent: *\nDisallow: /admin/\n"),
+            )
+            .mount(&server)
+            .await;
+        for page in ["/guide.md", "/admin/keys.md"] {
+            Mock::given(method("GET"))
+                .and(path(page))
+                .respond_with(ResponseTemplate::new(200).set_body_string("# Page\n\nText.\n"))
+                .mount(&server)
+                .await;
+        }
+
+        let fetcher = Fetcher::new().unwrap();
+        let index_url = format!("{base}/llms.txt");
+        let document = follow_links(&fetcher, &index_url, &index, &FollowOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(document.pages, [format!("{base}/guide.md")]);
+        assert_eq!(document.skipped[0].reason, "disallowed by robots.txt");
+
+        let ignoring = FollowOptions::default().ignoring_robots(true);
+        let document = follow_links(&fetcher, &index_url, &index, &ignoring)
+            .await
+            .unwrap();
+        assert_eq!(document.pages.len(), 2);
+    }
+}
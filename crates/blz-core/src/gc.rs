@@ -0,0 +1,391 @@
+//! Garbage collection for the cache directory.
+//!
+//! Interrupted syncs and older releases leave data behind that no command
+//! reads again: temp files from aborted writes, staging directories from
+//! `blz import`, source directories without `llms.json` (and the search
+//! index inside them), snapshot archives beyond `max_archives`, version
+//! objects no longer listed in `versions.json`, and anchor maps that no
+//! longer parse. [`scan`] finds them without touching anything and
+//! [`remove`] deletes what was found.
+//!
+//! Temp files, staging directories, and orphaned sources are only collected
+//! once they are older than [`GcOptions::min_age`], so a sync running in
+//! another process is never disturbed.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use serde::Serialize;
+
+use crate::{AnchorsMap, Error, Result, Storage, profile};
+
+/// Prefix of the staging directories `blz import` creates in the cache root.
+const IMPORT_STAGING_PREFIX: &str = ".import-";
+
+/// Default grace period before leftovers of in-flight writes are collected.
+const DEFAULT_MIN_AGE: Duration = Duration::from_secs(60 * 60);
+
+/// Why a path is garbage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum GarbageKind {
+    /// A `*.tmp` file, import staging directory, or half-swapped index left
+    /// by an aborted write.
+    TempFile,
+    /// A source directory (index included) with no `llms.json`.
+    OrphanedSource,
+    /// A timestamped snapshot beyond the `max_archives` most recent.
+    StaleArchive,
+    /// A compressed version object missing from `versions.json`.
+    UnreferencedVersion,
+    /// An `anchors.json` that cannot be parsed.
+    InvalidAnchors,
+}
+
+impl GarbageKind {
+    /// Short human-readable label.
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::TempFile => "temp file",
+            Self::OrphanedSource => "orphaned source",
+            Self::StaleArchive => "stale archive",
+            Self::UnreferencedVersion => "unreferenced version",
+            Self::InvalidAnchors => "invalid anchor map",
+        }
+    }
+}
+
+/// One removable file or directory.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Garbage {
+    /// Why it can be removed.
+    pub kind: GarbageKind,
+    /// File or directory to remove.
+    pub path: PathBuf,
+    /// Bytes freed by removing it.
+    pub bytes: u64,
+    /// Source it belongs to, when it lives in a source directory.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alias: Option<String>,
+}
+
+/// Options for [`scan`].
+#[derive(Debug, Clone, Copy)]
+pub struct GcOptions {
+    /// Snapshot archives kept per source (`defaults.max_archives`).
+    pub keep_archives: usize,
+    /// Leftovers younger than this are assumed to belong to a running write.
+    pub min_age: Duration,
+}
+
+impl Default for GcOptions {
+    fn default() -> Self {
+        Self {
+            keep_archives: 10,
+            min_age: DEFAULT_MIN_AGE,
+        }
+    }
+}
+
+/// Find garbage in `storage` without removing anything.
+///
+/// Results are sorted by kind, then path.
+///
+/// # Errors
+///
+/// Returns an error if the cache directories cannot be read.
+pub fn scan(storage: &Storage, options: GcOptions) -> Result<Vec<Garbage>> {
+    let mut found = Vec::new();
+    let root = storage.root_dir();
+    scan_temp_files(root, root, options.min_age, &mut found)?;
+
+    let sources_dir = root.join("sources");
+    if sources_dir.is_dir() {
+        for entry in read_dir(&sources_dir)? {
+            let dir = entry.path();
+            if !dir.is_dir() {
+                continue;
+            }
+            let alias = entry.file_name().to_string_lossy().into_owned();
+            if !dir.join("llms.json").is_file() {
+                if is_older_than(&dir, options.min_age) {
+                    push(&mut found, GarbageKind::OrphanedSource, dir, Some(&alias))?;
+                }
+                continue;
+            }
+            // Directories blz could not have created are not its to collect
+            if storage.tool_dir(&alias).is_ok() {
+                scan_source(storage, &alias, options, &mut found)?;
+            }
+        }
+    }
+
+    // Leftovers inside an orphaned source go with the whole directory
+    let orphans: Vec<PathBuf> = found
+        .iter()
+        .filter(|item| item.kind == GarbageKind::OrphanedSource)
+        .map(|item| item.path.clone())
+        .collect();
+    found.retain(|item| {
+        item.kind == GarbageKind::OrphanedSource
+            || !orphans.iter().any(|dir| item.path.starts_with(dir))
+    });
+    found.sort_by(|a, b| (a.kind, &a.path).cmp(&(b.kind, &b.path)));
+    Ok(found)
+}
+
+/// Remove everything in `garbage`, returning the bytes reclaimed.
+///
+/// Paths that no longer exist are skipped.
+///
+/// # Errors
+///
+/// Returns an error if a path exists but cannot be removed.
+pub fn remove(storage: &Storage, garbage: &[Garbage]) -> Result<u64> {
+    let mut reclaimed = 0;
+    for item in garbage {
+        let result = if item.path.is_dir() {
+            fs::remove_dir_all(&item.path)
+        } else {
+            fs::remove_file(&item.path)
+        };
+        match result {
+            Ok(()) => reclaimed += item.bytes,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {},
+            Err(e) => {
+                return Err(Error::Storage(format!(
+                    "Failed to remove {}: {e}",
+                    item.path.display()
+                )));
+            },
+        }
+    }
+    if garbage
+        .iter()
+        .any(|item| item.kind == GarbageKind::OrphanedSource)
+    {
+        storage.invalidate_query_cache();
+    }
+    Ok(reclaimed)
+}
+
+fn scan_source(
+    storage: &Storage,
+    alias: &str,
+    options: GcOptions,
+    found: &mut Vec<Garbage>,
+) -> Result<()> {
+    let anchors = storage.anchors_map_path(alias)?;
+    if anchors.is_file()
+        && fs::read_to_string(&anchors)
+            .ok()
+            .and_then(|json| serde_json::from_str::<AnchorsMap>(&json).ok())
+            .is_none()
+    {
+        push(found, GarbageKind::InvalidAnchors, anchors, Some(alias))?;
+    }
+
+    let snapshots = storage.list_archives(alias)?;
+    let stale = snapshots.len().saturating_sub(options.keep_archives);
+    let archive_dir = storage.archive_dir(alias)?;
+    for snapshot in snapshots.iter().take(stale) {
+        for entry in read_dir(&archive_dir)? {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            if name.starts_with(&format!("{}-", snapshot.id))
+                || name.starts_with(&format!("{}.", snapshot.id))
+            {
+                push(found, GarbageKind::StaleArchive, entry.path(), Some(alias))?;
+            }
+        }
+    }
+
+    let versions_dir = storage.versions_dir(alias)?;
+    // An unreadable manifest says nothing about which objects are live
+    if let (true, Ok(versions)) = (versions_dir.is_dir(), storage.list_versions(alias)) {
+        for entry in read_dir(&versions_dir)? {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            let Some(id) = name
+                .strip_suffix(".txt.zst")
+                .or_else(|| name.strip_suffix(".txt.gz"))
+            else {
+                continue;
+            };
+            if !versions.iter().any(|version| version.id == id) {
+                push(
+                    found,
+                    GarbageKind::UnreferencedVersion,
+                    entry.path(),
+                    Some(alias),
+                )?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Collect `*.tmp` files and import staging directories under `dir`.
+///
+/// Search indices are skipped (their files are managed by the index), as are
+/// other profiles' caches nested under the default root.
+fn scan_temp_files(
+    root: &Path,
+    dir: &Path,
+    min_age: Duration,
+    found: &mut Vec<Garbage>,
+) -> Result<()> {
+    for entry in read_dir(dir)? {
+        let path = entry.path();
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        if file_type.is_dir() {
+            if name == ".index" || (dir == root && name == profile::PROFILES_DIR) {
+                continue;
+            }
+            if name == ".index.new" || name == ".index.old" {
+                if is_older_than(&path, min_age) {
+                    let alias = source_alias(root, &path);
+                    push(found, GarbageKind::TempFile, path, alias.as_deref())?;
+                }
+                continue;
+            }
+            if dir == root && name.starts_with(IMPORT_STAGING_PREFIX) {
+                if is_older_than(&path, min_age) {
+                    push(found, GarbageKind::TempFile, path, None)?;
+                }
+                continue;
+            }
+            scan_temp_files(root, &path, min_age, found)?;
+        } else if file_type.is_file()
+            && Path::new(name.as_ref())
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("tmp"))
+            && is_older_than(&path, min_age)
+        {
+            let alias = source_alias(root, &path);
+            push(found, GarbageKind::TempFile, path, alias.as_deref())?;
+        }
+    }
+    Ok(())
+}
+
+/// The source directory `path` lives in, if any.
+fn source_alias(root: &Path, path: &Path) -> Option<String> {
+    path.strip_prefix(root.join("sources"))
+        .ok()
+        .and_then(|rel| rel.components().next())
+        .map(|c| c.as_os_str().to_string_lossy().into_owned())
+}
+
+fn push(
+    found: &mut Vec<Garbage>,
+    kind: GarbageKind,
+    path: PathBuf,
+    alias: Option<&str>,
+) -> Result<()> {
+    let bytes = Storage::path_size(&path)?;
+    found.push(Garbage {
+        kind,
+        path,
+        bytes,
+        alias: alias.map(str::to_string),
+    });
+    Ok(())
+}
+
+fn read_dir(dir: &Path) -> Result<Vec<fs::DirEntry>> {
+    fs::read_dir(dir)
+        .and_then(Iterator::collect)
+        .map_err(|e| Error::Storage(format!("Failed to read {}: {e}", dir.display())))
+}
+
+fn is_older_than(path: &Path, min_age: Duration) -> bool {
+    fs::symlink_metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+        .is_some_and(|age| age >= min_age)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn storage() -> (Storage, TempDir) {
+        let dir = TempDir::new().expect("tempdir");
+        let storage = Storage::with_root(dir.path().to_path_buf()).expect("storage");
+        (storage, dir)
+    }
+
+    const NOW: GcOptions = GcOptions {
+        keep_archives: 1,
+        min_age: Duration::ZERO,
+    };
+
+    #[test]
+    fn finds_and_removes_leftovers() -> Result<()> {
+        let (storage, _dir) = storage();
+        let live = storage.ensure_tool_dir("live")?;
+        fs::write(live.join("llms.json"), "{}")?;
+        fs::write(live.join("llms.txt.tmp"), "partial")?;
+        fs::write(live.join("anchors.json"), "not json")?;
+        let archive = storage.archive_dir("live")?;
+        fs::create_dir_all(&archive)?;
+        for name in [
+            "2024-01-01T00-00-00Z-llms.json",
+            "2024-01-01T00-00-00Z-llms.txt",
+            "2025-01-01T00-00-00Z-llms.json",
+        ] {
+            fs::write(archive.join(name), "{}")?;
+        }
+        let versions = storage.versions_dir("live")?;
+        fs::create_dir_all(&versions)?;
+        fs::write(versions.join("versions.json"), "[]")?;
+        fs::write(versions.join("abcd.txt.zst"), "zstd")?;
+
+        let orphan = storage.ensure_tool_dir("orphan")?;
+        fs::create_dir_all(orphan.join(".index"))?;
+        fs::write(orphan.join(".index").join("meta.json"), "{}")?;
+        fs::create_dir_all(storage.root_dir().join(".import-abc"))?;
+
+        let garbage = scan(&storage, NOW)?;
+        let kinds: Vec<GarbageKind> = garbage.iter().map(|g| g.kind).collect();
+        assert_eq!(
+            kinds,
+            [
+                GarbageKind::TempFile,
+                GarbageKind::TempFile,
+                GarbageKind::OrphanedSource,
+                GarbageKind::StaleArchive,
+                GarbageKind::StaleArchive,
+                GarbageKind::UnreferencedVersion,
+                GarbageKind::InvalidAnchors,
+            ]
+        );
+
+        let reclaimed = remove(&storage, &garbage)?;
+        assert_eq!(reclaimed, garbage.iter().map(|g| g.bytes).sum::<u64>());
+        assert!(scan(&storage, NOW)?.is_empty());
+        assert!(live.join("llms.json").is_file());
+        assert!(!orphan.exists());
+        assert!(archive.join("2025-01-01T00-00-00Z-llms.json").is_file());
+        Ok(())
+    }
+
+    #[test]
+    fn recent_leftovers_are_left_for_running_writes() -> Result<()> {
+        let (storage, _dir) = storage();
+        let dir = storage.ensure_tool_dir("adding")?;
+        fs::write(dir.join("llms.txt.tmp"), "partial")?;
+        assert!(scan(&storage, GcOptions::default())?.is_empty());
+        Ok(())
+    }
+}
@@ -0,0 +1,744 @@
+//! GitHub repository sources and a rate-limit-aware API client.
+//!
+//! `blz add github:OWNER/REPO` asks the GitHub API for the repository's default
+//! branch and description, then indexes the first llms file found on that
+//! branch (see [`DOC_CANDIDATES`]). The document itself is fetched from the raw
+//! content host, so only metadata lookups count against the API rate limit.
+//!
+//! Requests are authenticated with `GITHUB_TOKEN` when it is set, which raises
+//! the limit from 60 to 5,000 requests per hour and allows private
+//! repositories. Responses are cached under the storage root ([`GithubCache`])
+//! and revalidated with `If-None-Match`, so bulk operations such as manifest
+//! imports or `registry create-source` runs reuse one lookup per repository.
+//! When the limit is exhausted the client stops calling the API until it
+//! resets, serving stale cached metadata where it has some.
+
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+use chrono::{DateTime, TimeZone, Utc};
+use reqwest::header::{ACCEPT, ETAG, HeaderMap, IF_NONE_MATCH, RETRY_AFTER};
+use reqwest::{Client, StatusCode};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+use crate::{Error, Result};
+
+/// Prefix marking a repository specification (e.g. `github:owner/repo@main`).
+pub const GITHUB_PREFIX: &str = "github:";
+
+/// Default GitHub API base URL (overridable with `BLZ_GITHUB_API_URL`).
+pub const DEFAULT_API_URL: &str = "https://api.github.com";
+
+/// Default raw content base URL (overridable with `BLZ_GITHUB_RAW_URL`).
+pub const DEFAULT_RAW_URL: &str = "https://raw.githubusercontent.com";
+
+/// Environment variable holding the API token.
+pub const TOKEN_ENV: &str = "GITHUB_TOKEN";
+
+/// Repository paths checked for documentation, in preference order.
+pub const DOC_CANDIDATES: &[&str] = &[
+    "llms-full.txt",
+    "llms.txt",
+    "docs/llms-full.txt",
+    "docs/llms.txt",
+];
+
+/// How long cached repository metadata is used without revalidation.
+pub const METADATA_TTL_SECS: i64 = 60 * 60;
+
+/// Remaining-request count below which a warning is logged.
+const LOW_REMAINING: u32 = 10;
+
+/// Fallback wait when GitHub signals a limit without saying when it resets.
+const DEFAULT_RETRY_SECS: i64 = 60;
+
+/// A GitHub repository with an optional branch, tag, or commit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RepoSpec {
+    /// Account or organization owning the repository.
+    pub owner: String,
+    /// Repository name.
+    pub repo: String,
+    /// Git ref to read; the default branch when `None`.
+    pub reference: Option<String>,
+}
+
+impl RepoSpec {
+    /// Parse `input` if it carries the `github:` prefix.
+    ///
+    /// Returns `None` for anything else so callers can fall back to URL handling.
+    #[must_use]
+    pub fn from_prefixed(input: &str) -> Option<Result<Self>> {
+        input.strip_prefix(GITHUB_PREFIX).map(str::parse::<Self>)
+    }
+
+    /// `owner/repo`, as used for GitHub aliases.
+    #[must_use]
+    pub fn full_name(&self) -> String {
+        format!("{}/{}", self.owner, self.repo)
+    }
+}
+
+impl fmt::Display for RepoSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.owner, self.repo)?;
+        if let Some(reference) = &self.reference {
+            write!(f, "@{reference}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for RepoSpec {
+    type Err = Error;
+
+    /// Parse `OWNER/REPO` or `OWNER/REPO@REF`.
+    fn from_str(spec: &str) -> Result<Self> {
+        let spec = spec.trim();
+        let (path, reference) = spec
+            .split_once('@')
+            .map_or((spec, None), |(path, reference)| {
+                (path.trim(), Some(reference.trim()))
+            });
+        let invalid = || {
+            Error::Config(format!(
+                "invalid GitHub repository '{spec}' (expected e.g. github:owner/repo or github:owner/repo@main)"
+            ))
+        };
+        let (owner, repo) = path.split_once('/').ok_or_else(invalid)?;
+        let repo = repo.strip_suffix(".git").unwrap_or(repo);
+        let valid_owner = !owner.is_empty()
+            && owner.len() <= 39
+            && !owner.starts_with('-')
+            && owner
+                .chars()
+                .all(|ch| ch.is_ascii_alphanumeric() || ch == '-');
+        let valid_repo = !repo.is_empty()
+            && repo.len() <= 100
+            && repo != "."
+            && repo != ".."
+            && repo
+                .chars()
+                .all(|ch| ch.is_ascii_alphanumeric() || "-_.".contains(ch));
+        if !valid_owner || !valid_repo {
+            return Err(invalid());
+        }
+        let reference = match reference {
+            Some(reference)
+                if reference.is_empty()
+                    || reference.contains("..")
+                    || reference.chars().any(|ch| {
+                        ch.is_whitespace() || ch.is_control() || "~^:?*[\\".contains(ch)
+                    }) =>
+            {
+                return Err(Error::Config(format!(
+                    "invalid git ref '{reference}' for repository '{path}'"
+                )));
+            },
+            reference => reference.map(str::to_string),
+        };
+        Ok(Self {
+            owner: owner.to_string(),
+            repo: repo.to_string(),
+            reference,
+        })
+    }
+}
+
+/// Repository details returned by `GET /repos/{owner}/{repo}`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RepoMetadata {
+    /// Canonical `owner/repo` name.
+    pub full_name: String,
+    /// Short description, if the repository has one.
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Branch used when no ref is requested.
+    pub default_branch: String,
+    /// Web URL of the repository.
+    #[serde(default)]
+    pub html_url: String,
+    /// Project homepage, if set.
+    #[serde(default)]
+    pub homepage: Option<String>,
+    /// Repository topics.
+    #[serde(default)]
+    pub topics: Vec<String>,
+    /// Whether the repository is archived.
+    #[serde(default)]
+    pub archived: bool,
+}
+
+/// One blob in a repository tree.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct TreeEntry {
+    /// Path from the repository root, with `/` separators.
+    pub path: String,
+    /// Object kind: `blob`, `tree`, or `commit` (submodules).
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// Blob size in bytes.
+    #[serde(default)]
+    pub size: u64,
+}
+
+#[derive(Deserialize)]
+struct TreeResponse {
+    tree: Vec<TreeEntry>,
+    #[serde(default)]
+    truncated: bool,
+}
+
+/// Cached API response for one repository.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CachedRepo {
+    /// Parsed response body.
+    pub metadata: RepoMetadata,
+    /// Entity tag used to revalidate the entry.
+    #[serde(default)]
+    pub etag: Option<String>,
+    /// When the entry was last fetched or revalidated.
+    pub fetched_at: DateTime<Utc>,
+}
+
+impl CachedRepo {
+    /// Whether the entry can be used without asking the API.
+    #[must_use]
+    pub fn is_fresh(&self, now: DateTime<Utc>) -> bool {
+        now.signed_duration_since(self.fetched_at).num_seconds() < METADATA_TTL_SECS
+    }
+}
+
+/// API rate-limit state reported by GitHub.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RateLimit {
+    /// Requests allowed per window, when reported.
+    #[serde(default)]
+    pub limit: Option<u32>,
+    /// Requests left in the current window.
+    pub remaining: u32,
+    /// When the window resets.
+    pub reset_at: DateTime<Utc>,
+}
+
+impl RateLimit {
+    /// Read the `x-ratelimit-*` headers of a response.
+    #[must_use]
+    pub fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let number = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.trim().parse::<i64>().ok())
+        };
+        let remaining = u32::try_from(number("x-ratelimit-remaining")?).ok()?;
+        let reset_at = Utc
+            .timestamp_opt(number("x-ratelimit-reset")?, 0)
+            .single()?;
+        Some(Self {
+            limit: number("x-ratelimit-limit").and_then(|limit| u32::try_from(limit).ok()),
+            remaining,
+            reset_at,
+        })
+    }
+
+    /// Whether no requests may be made until [`Self::reset_at`].
+    #[must_use]
+    pub fn is_exhausted(&self, now: DateTime<Utc>) -> bool {
+        self.remaining == 0 && self.reset_at > now
+    }
+}
+
+/// On-disk cache of repository metadata and the last known rate limit.
+#[derive(Debug, Clone)]
+pub struct GithubCache {
+    dir: PathBuf,
+}
+
+impl GithubCache {
+    /// Cache stored in `dir`, which is created on first write.
+    #[must_use]
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Directory holding the entries.
+    #[must_use]
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Cached metadata for `spec`, fresh or not.
+    #[must_use]
+    pub fn load_repo(&self, spec: &RepoSpec) -> Option<CachedRepo> {
+        read_json(&self.repo_path(spec))
+    }
+
+    /// Store metadata for `spec`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the entry cannot be written.
+    pub fn save_repo(&self, spec: &RepoSpec, entry: &CachedRepo) -> Result<()> {
+        write_json(&self.repo_path(spec), entry)
+    }
+
+    /// Rate limit recorded by the most recent API response.
+    #[must_use]
+    pub fn load_rate_limit(&self) -> Option<RateLimit> {
+        read_json(&self.dir.join("rate-limit.json"))
+    }
+
+    /// Record the rate limit from an API response.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the entry cannot be written.
+    pub fn save_rate_limit(&self, limit: &RateLimit) -> Result<()> {
+        write_json(&self.dir.join("rate-limit.json"), limit)
+    }
+
+    /// GitHub names are case-insensitive, so entries are keyed in lowercase.
+    fn repo_path(&self, spec: &RepoSpec) -> PathBuf {
+        self.dir
+            .join("repos")
+            .join(spec.owner.to_ascii_lowercase())
+            .join(format!("{}.json", spec.repo.to_ascii_lowercase()))
+    }
+}
+
+fn read_json<T: serde::de::DeserializeOwned>(path: &Path) -> Option<T> {
+    let bytes = fs::read(path).ok()?;
+    serde_json::from_slice(&bytes)
+        .inspect_err(|e| debug!("Ignoring unreadable GitHub cache entry {path:?}: {e}"))
+        .ok()
+}
+
+fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| Error::Storage(format!("Failed to create GitHub cache: {e}")))?;
+    }
+    let json = serde_json::to_vec_pretty(value)
+        .map_err(|e| Error::Storage(format!("Failed to serialize GitHub cache entry: {e}")))?;
+    fs::write(path, json)
+        .map_err(|e| Error::Storage(format!("Failed to write GitHub cache entry: {e}")))
+}
+
+/// GitHub API client that honors rate limits and caches metadata.
+#[derive(Debug, Clone)]
+pub struct GithubClient {
+    client: Client,
+    api_url: String,
+    raw_url: String,
+    token: Option<String>,
+}
+
+impl GithubClient {
+    /// Client configured from `GITHUB_TOKEN`, `BLZ_GITHUB_API_URL`, and
+    /// `BLZ_GITHUB_RAW_URL`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP client cannot be constructed.
+    pub fn from_env() -> Result<Self> {
+        let env = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+        };
+        Self::new(
+            env("BLZ_GITHUB_API_URL").unwrap_or_else(|| DEFAULT_API_URL.to_string()),
+            env("BLZ_GITHUB_RAW_URL").unwrap_or_else(|| DEFAULT_RAW_URL.to_string()),
+            env(TOKEN_ENV),
+        )
+    }
+
+    /// Client for the given API and raw content hosts.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP client cannot be constructed.
+    pub fn new(
+        api_url: impl Into<String>,
+        raw_url: impl Into<String>,
+        token: Option<String>,
+    ) -> Result<Self> {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(30))
+            .user_agent(concat!("outfitter-blz/", env!("CARGO_PKG_VERSION")))
+            .build()?;
+        Ok(Self {
+            client,
+            api_url: api_url.into().trim_end_matches('/').to_string(),
+            raw_url: raw_url.into().trim_end_matches('/').to_string(),
+            token,
+        })
+    }
+
+    /// Whether requests are authenticated.
+    #[must_use]
+    pub const fn has_token(&self) -> bool {
+        self.token.is_some()
+    }
+
+    /// Raw content URL for `path` at `reference`.
+    #[must_use]
+    pub fn raw_file_url(&self, spec: &RepoSpec, reference: &str, path: &str) -> String {
+        format!(
+            "{}/{}/{}/{reference}/{path}",
+            self.raw_url, spec.owner, spec.repo
+        )
+    }
+
+    /// Repository metadata, served from `cache` while fresh.
+    ///
+    /// Stale entries are revalidated with `If-None-Match`. While the rate limit
+    /// is exhausted no request is made and stale entries are returned as-is.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::NotFound`] for unknown (or, without a token, private)
+    /// repositories and [`Error::ResourceLimited`] when the rate limit is
+    /// exhausted and nothing is cached.
+    pub async fn repo(&self, cache: &GithubCache, spec: &RepoSpec) -> Result<RepoMetadata> {
+        let now = Utc::now();
+        let cached = cache.load_repo(spec);
+        if let Some(entry) = cached.as_ref().filter(|entry| entry.is_fresh(now)) {
+            debug!("Using cached GitHub metadata for {}", spec.full_name());
+            return Ok(entry.metadata.clone());
+        }
+        if let Some(limit) = cache
+            .load_rate_limit()
+            .filter(|limit| limit.is_exhausted(now))
+        {
+            return self.stale_or_limited(spec, cached, &limit);
+        }
+
+        let url = format!("{}/repos/{}/{}", self.api_url, spec.owner, spec.repo);
+        let mut request = self
+            .client
+            .get(&url)
+            .header(ACCEPT, "application/vnd.github+json")
+            .header("X-GitHub-Api-Version", "2022-11-28");
+        if let Some(token) = &self.token {
+            request = request.bearer_auth(token);
+        }
+        if let Some(etag) = cached.as_ref().and_then(|entry| entry.etag.as_deref()) {
+            request = request.header(IF_NONE_MATCH, etag);
+        }
+        let response = request.send().await?;
+        let status = response.status();
+
+        let limit = RateLimit::from_headers(response.headers());
+        if let Some(limit) = &limit {
+            if limit.remaining < LOW_REMAINING && limit.remaining > 0 {
+                warn!(
+                    "GitHub API rate limit nearly exhausted ({} requests left until {})",
+                    limit.remaining,
+                    limit.reset_at.format("%H:%M UTC")
+                );
+            }
+            if let Err(e) = cache.save_rate_limit(limit) {
+                warn!("Failed to record GitHub rate limit: {e}");
+            }
+        }
+
+        if let Some(limit) = rate_limited(status, response.headers(), limit.as_ref(), now) {
+            if let Err(e) = cache.save_rate_limit(&limit) {
+                warn!("Failed to record GitHub rate limit: {e}");
+            }
+            return self.stale_or_limited(spec, cached, &limit);
+        }
+        if status == StatusCode::NOT_MODIFIED {
+            if let Some(mut entry) = cached {
+                entry.fetched_at = now;
+                Self::store(cache, spec, &entry);
+                return Ok(entry.metadata);
+            }
+        }
+        if status == StatusCode::NOT_FOUND {
+            let hint = if self.has_token() {
+                ""
+            } else {
+                " (set GITHUB_TOKEN to access private repositories)"
+            };
+            return Err(Error::NotFound(format!(
+                "GitHub repository '{}' not found{hint}",
+                spec.full_name()
+            )));
+        }
+
+        let response = response.error_for_status()?;
+        let etag = response
+            .headers()
+            .get(ETAG)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+        let metadata: RepoMetadata = response.json().await?;
+        Self::store(
+            cache,
+            spec,
+            &CachedRepo {
+                metadata: metadata.clone(),
+                etag,
+                fetched_at: now,
+            },
+        );
+        Ok(metadata)
+    }
+
+    /// Find the first of [`DOC_CANDIDATES`] present at `reference`.
+    ///
+    /// Probes the raw content host, which does not count against the API
+    /// rate limit.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::NotFound`] if no candidate exists.
+    pub async fn find_docs(&self, spec: &RepoSpec, reference: &str) -> Result<String> {
+        for path in DOC_CANDIDATES {
+            let url = self.raw_file_url(spec, reference, path);
+            match self.client.head(&url).send().await {
+                Ok(response) if response.status().is_success() => return Ok(url),
+                Ok(response) => debug!("{url}: {}", response.status()),
+                Err(e) => debug!("{url}: {e}"),
+            }
+        }
+        Err(Error::NotFound(format!(
+            "no llms.txt found in {}@{reference} (looked for {})",
+            spec.full_name(),
+            DOC_CANDIDATES.join(", ")
+        )))
+    }
+
+    /// Commit SHA that `reference` (a branch, tag, or SHA) points at.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::NotFound`] for unknown repositories or refs and
+    /// [`Error::ResourceLimited`] when the rate limit is exhausted.
+    pub async fn commit_sha(&self, spec: &RepoSpec, reference: &str) -> Result<String> {
+        let url = format!(
+            "{}/repos/{}/{}/commits/{reference}",
+            self.api_url, spec.owner, spec.repo
+        );
+        let response = self
+            .api_get(&url, "application/vnd.github.sha", spec, reference)
+            .await?;
+        let sha = response.text().await?.trim().to_string();
+        if sha.is_empty() || !sha.chars().all(|ch| ch.is_ascii_hexdigit()) {
+            return Err(Error::Parse(format!(
+                "unexpected commit response for {}@{reference}",
+                spec.full_name()
+            )));
+        }
+        Ok(sha)
+    }
+
+    /// Every blob in the tree at `commit`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::NotFound`] for unknown commits and
+    /// [`Error::ResourceLimited`] when the rate limit is exhausted or GitHub
+    /// truncated the listing.
+    pub async fn tree(&self, spec: &RepoSpec, commit: &str) -> Result<Vec<TreeEntry>> {
+        let url = format!(
+            "{}/repos/{}/{}/git/trees/{commit}?recursive=1",
+            self.api_url, spec.owner, spec.repo
+        );
+        let response = self
+            .api_get(&url, "application/vnd.github+json", spec, commit)
+            .await?;
+        let tree: TreeResponse = response.json().await?;
+        if tree.truncated {
+            return Err(Error::ResourceLimited(format!(
+                "{} is too large to list through the GitHub API",
+                spec.full_name()
+            )));
+        }
+        Ok(tree
+            .tree
+            .into_iter()
+            .filter(|entry| entry.kind == "blob")
+            .collect())
+    }
+
+    /// Raw bytes of `path` at `reference`, from the raw content host.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be downloaded.
+    pub async fn raw_file(&self, spec: &RepoSpec, reference: &str, path: &str) -> Result<Vec<u8>> {
+        let mut request = self.client.get(self.raw_file_url(spec, reference, path));
+        if let Some(token) = &self.token {
+            request = request.bearer_auth(token);
+        }
+        let response = request.send().await?.error_for_status()?;
+        Ok(response.bytes().await?.to_vec())
+    }
+
+    /// Send an authenticated API `GET`, mapping missing repositories and
+    /// rate limits to their errors.
+    async fn api_get(
+        &self,
+        url: &str,
+        accept: &str,
+        spec: &RepoSpec,
+        reference: &str,
+    ) -> Result<reqwest::Response> {
+        let mut request = self
+            .client
+            .get(url)
+            .header(ACCEPT, accept)
+            .header("X-GitHub-Api-Version", "2022-11-28");
+        if let Some(token) = &self.token {
+            request = request.bearer_auth(token);
+        }
+        let response = request.send().await?;
+        let status = response.status();
+        let now = Utc::now();
+        let limit = RateLimit::from_headers(response.headers());
+        if let Some(limit) = rate_limited(status, response.headers(), limit.as_ref(), now) {
+            return Err(Error::ResourceLimited(format!(
+                "GitHub API rate limit exceeded while reading {} (resets at {})",
+                spec.full_name(),
+                limit.reset_at.format("%H:%M UTC")
+            )));
+        }
+        if status == StatusCode::NOT_FOUND || status == StatusCode::UNPROCESSABLE_ENTITY {
+            return Err(Error::NotFound(format!(
+                "{}@{reference} not found on GitHub",
+                spec.full_name()
+            )));
+        }
+        Ok(response.error_for_status()?)
+    }
+
+    fn store(cache: &GithubCache, spec: &RepoSpec, entry: &CachedRepo) {
+        if let Err(e) = cache.save_repo(spec, entry) {
+            warn!(
+                "Failed to cache GitHub metadata for {}: {e}",
+                spec.full_name()
+            );
+        }
+    }
+
+    fn stale_or_limited(
+        &self,
+        spec: &RepoSpec,
+        cached: Option<CachedRepo>,
+        limit: &RateLimit,
+    ) -> Result<RepoMetadata> {
+        let resets = limit.reset_at.format("%H:%M UTC");
+        if let Some(entry) = cached {
+            warn!(
+                "GitHub API rate limit reached; using cached metadata for {} until {resets}",
+                spec.full_name()
+            );
+            return Ok(entry.metadata);
+        }
+        let hint = if self.has_token() {
+            ""
+        } else {
+            "; set GITHUB_TOKEN to raise the limit"
+        };
+        Err(Error::ResourceLimited(format!(
+            "GitHub API rate limit exceeded while looking up {} (resets at {resets}{hint})",
+            spec.full_name()
+        )))
+    }
+}
+
+/// Rate limit to record when `status` reports a primary or secondary limit.
+fn rate_limited(
+    status: StatusCode,
+    headers: &HeaderMap,
+    limit: Option<&RateLimit>,
+    now: DateTime<Utc>,
+) -> Option<RateLimit> {
+    if status != StatusCode::FORBIDDEN && status != StatusCode::TOO_MANY_REQUESTS {
+        return None;
+    }
+    let retry_after = headers
+        .get(RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<i64>().ok());
+    let exhausted = limit.is_some_and(|limit| limit.remaining == 0);
+    if status == StatusCode::FORBIDDEN && !exhausted && retry_after.is_none() {
+        return None;
+    }
+    let reset_at = match (retry_after, limit) {
+        (Some(secs), _) => now + chrono::Duration::seconds(secs.max(1)),
+        (None, Some(limit)) if limit.reset_at > now => limit.reset_at,
+        _ => now + chrono::Duration::seconds(DEFAULT_RETRY_SECS),
+    };
+    Some(RateLimit {
+        limit: limit.and_then(|limit| limit.limit),
+        remaining: 0,
+        reset_at,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn parses_repo_specs() {
+        let spec: RepoSpec = "outfitter-dev/blz@v1.2".parse().expect("valid spec");
+        assert_eq!(spec.full_name(), "outfitter-dev/blz");
+        assert_eq!(spec.reference.as_deref(), Some("v1.2"));
+        assert_eq!(spec.to_string(), "outfitter-dev/blz@v1.2");
+
+        let spec = RepoSpec::from_prefixed("github:vercel/next.js.git")
+            .expect("prefixed")
+            .expect("valid spec");
+        assert_eq!(spec.repo, "next.js");
+        assert_eq!(spec.reference, None);
+
+        for invalid in [
+            "blz", "/blz", "a/b/c", "-x/repo", "owner/..", "o/r@", "o/r@a..b",
+        ] {
+            assert!(invalid.parse::<RepoSpec>().is_err(), "{invalid}");
+        }
+        assert!(RepoSpec::from_prefixed("https://github.com/a/b").is_none());
+    }
+
+    #[test]
+    fn detects_primary_and_secondary_limits() {
+        let now = Utc::now();
+        let mut headers = HeaderMap::new();
+        headers.insert("x-ratelimit-limit", HeaderValue::from_static("60"));
+        headers.insert("x-ratelimit-remaining", HeaderValue::from_static("0"));
+        let reset = now.timestamp() + 600;
+        headers.insert(
+            "x-ratelimit-reset",
+            HeaderValue::from_str(&reset.to_string()).expect("header"),
+        );
+        let limit = RateLimit::from_headers(&headers).expect("limit");
+        assert_eq!(limit.limit, Some(60));
+        assert!(limit.is_exhausted(now));
+
+        let primary = rate_limited(StatusCode::FORBIDDEN, &headers, Some(&limit), now)
+            .expect("primary limit");
+        assert_eq!(primary.reset_at.timestamp(), reset);
+
+        let mut secondary = HeaderMap::new();
+        secondary.insert(RETRY_AFTER, HeaderValue::from_static("30"));
+        let limit = rate_limited(StatusCode::FORBIDDEN, &secondary, None, now).expect("secondary");
+        assert_eq!((limit.reset_at - now).num_seconds(), 30);
+
+        assert!(rate_limited(StatusCode::FORBIDDEN, &HeaderMap::new(), None, now).is_none());
+        assert!(rate_limited(StatusCode::OK, &headers, None, now).is_none());
+    }
+}
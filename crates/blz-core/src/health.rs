@@ -3,9 +3,19 @@
 //! These types are shared between the CLI and MCP server to provide
 //! consistent health reporting across interfaces.
 
-use serde::Serialize;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
+use crate::types::{DiagnosticSeverity, LlmsJson};
+
+/// Points deducted from the lint score per error diagnostic.
+const LINT_ERROR_PENALTY: u32 = 20;
+/// Points deducted from the lint score per warning diagnostic.
+const LINT_WARNING_PENALTY: u32 = 5;
+/// Points deducted when the document has no headings to navigate.
+const LINT_NO_HEADINGS_PENALTY: u32 = 30;
+
 /// Overall health report for the cache.
 #[derive(Debug, Clone, Serialize)]
 pub struct HealthReport {
@@ -114,3 +124,62 @@ pub struct SourceHealthEntry {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub native_available: Option<bool>,
 }
+
+/// Per-source health observations persisted in `llms.json`.
+///
+/// Written by `blz check` and `blz doctor` so that cheap views such as
+/// `blz list` can show health badges without re-probing the network.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthRecord {
+    /// When the observations were recorded.
+    pub checked_at: DateTime<Utc>,
+    /// Whether the upstream URL (or local file) was reachable, if probed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upstream_reachable: Option<bool>,
+    /// Document lint score from 0 (poor) to 100 (clean).
+    pub lint_score: u8,
+}
+
+impl HealthRecord {
+    /// Record fresh observations for `llms`.
+    ///
+    /// When `upstream_reachable` is `None` (the caller did not probe the
+    /// network), the previously recorded reachability is kept.
+    #[must_use]
+    pub fn observe(llms: &LlmsJson, upstream_reachable: Option<bool>) -> Self {
+        let previous = llms
+            .health
+            .as_ref()
+            .and_then(|record| record.upstream_reachable);
+        Self {
+            checked_at: Utc::now(),
+            upstream_reachable: upstream_reachable.or(previous),
+            lint_score: lint_score(llms),
+        }
+    }
+}
+
+/// Score the structure of a cached document from 0 to 100.
+///
+/// Starts from 100 and deducts points for parser error and warning
+/// diagnostics and for documents without any headings.
+#[must_use]
+pub fn lint_score(llms: &LlmsJson) -> u8 {
+    let diagnostic_penalty: u32 = llms
+        .diagnostics
+        .iter()
+        .map(|diagnostic| match diagnostic.severity {
+            DiagnosticSeverity::Error => LINT_ERROR_PENALTY,
+            DiagnosticSeverity::Warn => LINT_WARNING_PENALTY,
+            DiagnosticSeverity::Info => 0,
+        })
+        .sum();
+    let structure_penalty = if llms.toc.is_empty() {
+        LINT_NO_HEADINGS_PENALTY
+    } else {
+        0
+    };
+    let score = 100u32.saturating_sub(diagnostic_penalty.saturating_add(structure_penalty));
+    u8::try_from(score).unwrap_or(0)
+}
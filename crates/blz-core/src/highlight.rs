@@ -0,0 +1,138 @@
+//! Match offsets for search snippets.
+//!
+//! Each hit records where the query matched inside its snippet, so renderers
+//! can color matches and downstream tools can locate them without repeating
+//! the search. Terms match case-insensitively at the start of a word, which
+//! also marks `hook` inside `hooks` the way a stemmed search would find it.
+
+use serde::{Deserialize, Serialize};
+
+use crate::query_dsl;
+
+/// Location of one query match within a snippet.
+///
+/// Byte offsets slice the UTF-8 snippet directly; character offsets count
+/// Unicode scalar values for consumers that index by character.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchSpan {
+    /// Byte offset where the match starts.
+    pub start: usize,
+    /// Byte offset just past the end of the match.
+    pub end: usize,
+    /// Character offset where the match starts.
+    pub char_start: usize,
+    /// Character offset just past the end of the match.
+    pub char_end: usize,
+}
+
+/// Terms of `query` that can appear in a snippet, longest first.
+///
+/// Structured queries contribute every term that is not negated or scoped to
+/// headings. A fully quoted query is a single phrase; otherwise each word is a
+/// term, ignoring `AND`/`OR` and the heading-boost `# ` prefix.
+#[must_use]
+pub fn query_terms(query: &str) -> Vec<String> {
+    let trimmed = query.trim();
+    let body = trimmed.strip_prefix("# ").map_or(trimmed, str::trim);
+
+    let mut terms = if query_dsl::is_structured(body) {
+        query_dsl::parse(body)
+            .map(|expr| expr.highlight_terms())
+            .unwrap_or_default()
+    } else if body.len() >= 2 && body.starts_with('"') && body.ends_with('"') {
+        vec![body[1..body.len() - 1].to_string()]
+    } else {
+        body.split_whitespace()
+            .map(|word| word.trim_matches('"').trim_start_matches('+').to_string())
+            .filter(|word| !word.eq_ignore_ascii_case("and") && !word.eq_ignore_ascii_case("or"))
+            .collect()
+    };
+
+    terms.retain(|term| !term.trim().is_empty());
+    terms.sort_by_key(|term| std::cmp::Reverse(term.chars().count()));
+    terms.dedup_by(|a, b| a.to_lowercase() == b.to_lowercase());
+    terms
+}
+
+/// Find where `terms` occur in `text`.
+///
+/// Spans are sorted and never overlap; at each position the longest matching
+/// term wins. Terms starting with a letter or digit only match at the start
+/// of a word.
+#[must_use]
+pub fn match_spans(text: &str, terms: &[String]) -> Vec<MatchSpan> {
+    let chars: Vec<(usize, char)> = text.char_indices().collect();
+    let terms: Vec<Vec<char>> = terms
+        .iter()
+        .map(|term| term.chars().collect::<Vec<_>>())
+        .filter(|term| !term.is_empty())
+        .collect();
+
+    let mut spans = Vec::new();
+    let mut pos = 0;
+    while pos < chars.len() {
+        let at_word_start = pos == 0 || !chars[pos - 1].1.is_alphanumeric();
+        let matched = terms.iter().find_map(|term| {
+            let end = pos + term.len();
+            let fits = end <= chars.len()
+                && (at_word_start || !term[0].is_alphanumeric())
+                && chars[pos..end]
+                    .iter()
+                    .zip(term)
+                    .all(|((_, a), b)| a.to_lowercase().eq(b.to_lowercase()));
+            fits.then_some(end)
+        });
+
+        if let Some(end) = matched {
+            spans.push(MatchSpan {
+                start: chars[pos].0,
+                end: chars.get(end).map_or(text.len(), |(offset, _)| *offset),
+                char_start: pos,
+                char_end: end,
+            });
+            pos = end;
+        } else {
+            pos += 1;
+        }
+    }
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spans(text: &str, query: &str) -> Vec<(usize, usize)> {
+        match_spans(text, &query_terms(query))
+            .into_iter()
+            .map(|span| (span.start, span.end))
+            .collect()
+    }
+
+    #[test]
+    fn marks_each_term_case_insensitively_at_word_starts() {
+        let text = "Hooks let components use React hooks; rehook is unrelated.";
+        assert_eq!(spans(text, "react hook"), vec![(0, 4), (25, 30), (31, 35)]);
+    }
+
+    #[test]
+    fn phrases_and_structured_queries() {
+        let text = "Run cargo add serde, then npm install.";
+        assert_eq!(spans(text, "\"cargo add\""), vec![(4, 13)]);
+        assert_eq!(
+            spans(text, "npm OR cargo NOT serde"),
+            vec![(4, 9), (26, 29)]
+        );
+        assert!(spans(text, "heading:install").is_empty());
+    }
+
+    #[test]
+    fn reports_byte_and_char_offsets() {
+        let text = "Größe der Datei";
+        let span = match_spans(text, &query_terms("datei"))[0];
+        assert_eq!((span.start, span.end), (12, 17));
+        assert_eq!((span.char_start, span.char_end), (10, 15));
+        assert_eq!(&text[span.start..span.end], "Datei");
+    }
+}
@@ -0,0 +1,270 @@
+//! HTML pages converted to markdown.
+//!
+//! Long-tail libraries often publish no llms.txt at all, only a docs site.
+//! `blz add` accepts such pages: the main content is extracted with a small
+//! readability pass (the largest `<main>`, `<article>`, or well-known docs
+//! content container, falling back to `<body>`), converted to markdown, and
+//! indexed like any other document so hits keep their line citations.
+//! Scripts, styles, navigation, and sidebars are dropped along the way. Sync
+//! refetches the page and re-indexes only when the converted markdown changed.
+
+use std::sync::LazyLock;
+
+use htmd::HtmlToMarkdown;
+use regex::Regex;
+use scraper::{ElementRef, Html, Selector};
+
+use crate::fetcher::calculate_sha256;
+use crate::refresh::{
+    ApplyRefreshParams, RefreshContext, RefreshIndexer, RefreshOutcome, RefreshPayload,
+    RefreshStorage, apply_refresh,
+};
+use crate::{Error, Fetcher, PerformanceMetrics, Result, SourceType};
+
+/// Containers that usually hold a page's main content, in no particular order.
+const CONTENT_SELECTORS: &str = "main, article, [role=main], #content, .content, \
+                                 .markdown-body, .theme-doc-markdown, .md-content, .prose";
+
+/// Candidates with less text than this are treated as empty shells.
+const MIN_CONTENT_CHARS: usize = 200;
+
+/// Elements dropped during conversion: page chrome and non-text content.
+const SKIPPED_TAGS: [&str; 11] = [
+    "script", "style", "noscript", "nav", "aside", "footer", "form", "button", "iframe", "svg",
+    "template",
+];
+
+/// Runs of three or more newlines.
+///
+/// SAFETY: Pattern is a compile-time constant that is known to be valid.
+#[allow(clippy::unwrap_used)]
+static BLANK_RUNS: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\n{3,}").unwrap());
+
+/// Selector for [`CONTENT_SELECTORS`].
+///
+/// SAFETY: Selectors are compile-time constants that are known to be valid.
+#[allow(clippy::unwrap_used)]
+static CONTENT: LazyLock<Selector> = LazyLock::new(|| Selector::parse(CONTENT_SELECTORS).unwrap());
+
+/// SAFETY: Selectors are compile-time constants that are known to be valid.
+#[allow(clippy::unwrap_used)]
+static BODY: LazyLock<Selector> = LazyLock::new(|| Selector::parse("body").unwrap());
+
+/// SAFETY: Selectors are compile-time constants that are known to be valid.
+#[allow(clippy::unwrap_used)]
+static TITLE: LazyLock<Selector> = LazyLock::new(|| Selector::parse("title").unwrap());
+
+/// SAFETY: Selectors are compile-time constants that are known to be valid.
+#[allow(clippy::unwrap_used)]
+static H1: LazyLock<Selector> = LazyLock::new(|| Selector::parse("h1").unwrap());
+
+/// A page converted to markdown.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HtmlDocument {
+    /// Page title from its first `<h1>` or `<title>`.
+    pub title: Option<String>,
+    /// Markdown for the page's main content, starting with a `#` heading
+    /// when the page has a title.
+    pub markdown: String,
+}
+
+/// Whether fetched text is an HTML page rather than markdown.
+#[must_use]
+pub fn looks_like_html(content: &str) -> bool {
+    let head = content
+        .trim_start()
+        .chars()
+        .take(512)
+        .collect::<String>()
+        .to_ascii_lowercase();
+    head.starts_with("<!doctype html") || head.starts_with("<html") || head.contains("<head>")
+}
+
+/// Extract a page's main content and convert it to markdown.
+///
+/// # Errors
+///
+/// Returns an error if the page has no content left after extraction.
+pub fn to_markdown(html: &str) -> Result<HtmlDocument> {
+    let document = Html::parse_document(html);
+    let main = main_content(&document);
+    let title = main
+        .and_then(|element| element.select(&H1).next())
+        .or_else(|| document.select(&H1).next())
+        .or_else(|| document.select(&TITLE).next())
+        .map(|element| clean_title(&text_of(element)))
+        .filter(|title| !title.is_empty());
+
+    let fragment = main.map_or_else(|| html.to_string(), |element| element.html());
+    let converted = HtmlToMarkdown::builder()
+        .skip_tags(SKIPPED_TAGS.to_vec())
+        .build()
+        .convert(&fragment)
+        .map_err(|err| Error::Parse(format!("Failed to convert HTML to markdown: {err}")))?;
+
+    let mut markdown = BLANK_RUNS
+        .replace_all(converted.trim(), "\n\n")
+        .into_owned();
+    if markdown.is_empty() {
+        return Err(Error::Parse(
+            "HTML page has no readable content".to_string(),
+        ));
+    }
+    let has_h1 = markdown.lines().any(|line| line.starts_with("# "));
+    if let Some(title) = title.as_deref().filter(|_| !has_h1) {
+        markdown = format!("# {title}\n\n{markdown}");
+    }
+    markdown.push('\n');
+    Ok(HtmlDocument { title, markdown })
+}
+
+/// Fetch a page and convert it to markdown.
+///
+/// # Errors
+///
+/// Returns an error if the page cannot be fetched or has no readable content.
+pub async fn fetch_html_docs(fetcher: &Fetcher, url: &str) -> Result<HtmlDocument> {
+    let (html, _) = fetcher.fetch(url).await?;
+    to_markdown(&html)
+}
+
+/// Refresh an HTML source by refetching and reconverting its page.
+///
+/// The cache is only rewritten when the converted markdown changed.
+///
+/// # Errors
+///
+/// Returns an error if the source is not an HTML page, or if fetching,
+/// conversion, persistence, or indexing fails.
+pub async fn refresh_html<S, I>(
+    storage: &S,
+    fetcher: &Fetcher,
+    alias: &str,
+    ctx: &RefreshContext,
+    metrics: PerformanceMetrics,
+    indexer: &I,
+    filter_preference: bool,
+) -> Result<RefreshOutcome>
+where
+    S: RefreshStorage + Sync,
+    I: RefreshIndexer + Sync,
+{
+    let Some(SourceType::Html { url }) = &ctx.existing_metadata.origin.source_type else {
+        return Err(Error::Config(format!(
+            "Source '{alias}' is not an HTML page"
+        )));
+    };
+
+    let content = fetch_html_docs(fetcher, url).await?.markdown;
+    let sha256 = calculate_sha256(&content);
+
+    let mut metadata = ctx.existing_metadata.clone();
+    let filter_changed = metadata.filter_non_english.unwrap_or(true) != filter_preference;
+    metadata.filter_non_english = Some(filter_preference);
+
+    if sha256 == metadata.sha256 {
+        if filter_changed {
+            storage.save_metadata(alias, &metadata)?;
+        }
+        return Ok(RefreshOutcome::Unchanged {
+            alias: alias.to_string(),
+        });
+    }
+
+    let payload = RefreshPayload {
+        content,
+        sha256,
+        etag: None,
+        last_modified: None,
+    };
+    let params = ApplyRefreshParams::new(metadata, ctx.existing_aliases.clone());
+    apply_refresh(storage, alias, &params, &payload, metrics, indexer).await
+}
+
+/// The candidate container with the most text, or `<body>` when none has
+/// enough to be the main content.
+fn main_content(document: &Html) -> Option<ElementRef<'_>> {
+    document
+        .select(&CONTENT)
+        .map(|element| (text_len(element), element))
+        .filter(|(len, _)| *len >= MIN_CONTENT_CHARS)
+        .max_by_key(|(len, _)| *len)
+        .map(|(_, element)| element)
+        .or_else(|| document.select(&BODY).next())
+}
+
+fn text_len(element: ElementRef<'_>) -> usize {
+    element.text().map(|text| text.trim().len()).sum()
+}
+
+fn text_of(element: ElementRef<'_>) -> String {
+    element
+        .text()
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Drop a trailing site name (`Install | Widgets`, `Install – Widgets`).
+fn clean_title(title: &str) -> String {
+    [" | ", " – ", " — ", " · "]
+        .iter()
+        .find_map(|separator| title.split_once(separator).map(|(page, _)| page))
+        .unwrap_or(title)
+        .trim()
+        .to_string()
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<!DOCTYPE html>
+<html>
+<head><title>Install | Widgets</title><style>body { color: red; }</style></head>
+<body>
+  <nav><a href="/">Home</a> <a href="/blog">Blog</a></nav>
+  <main>
+    <h1>Installation</h1>
+    <p>Widgets ships as a single binary. Download it from the releases page and put it on
+       your <code>PATH</code>, or build it from source with the steps below.</p>
+    <h2>From source</h2>
+    <pre><code>cargo install widgets</code></pre>
+    <p>See the <a href="/config">configuration guide</a> for the options it reads.</p>
+    <script>track();</script>
+  </main>
+  <footer>Copyright Widgets</footer>
+</body>
+</html>"#;
+
+    #[test]
+    fn test_converts_main_content_to_markdown() {
+        let document = to_markdown(PAGE).unwrap();
+        assert_eq!(document.title.as_deref(), Some("Installation"));
+        assert!(document.markdown.starts_with("# Installation\n\n"));
+        assert!(document.markdown.contains("## From source"));
+        assert!(document.markdown.contains("cargo install widgets"));
+        assert!(document.markdown.contains("[configuration guide](/config)"));
+        assert!(!document.markdown.contains("Blog"));
+        assert!(!document.markdown.contains("track()"));
+        assert!(!document.markdown.contains("Copyright"));
+        assert!(!document.markdown.contains("\n\n\n"));
+    }
+
+    #[test]
+    fn test_uses_page_title_when_content_has_no_heading() {
+        let html = "<html><head><title>Widgets — Docs</title></head>\
+                    <body><p>Short page.</p></body></html>";
+        let document = to_markdown(html).unwrap();
+        assert_eq!(document.markdown, "# Widgets\n\nShort page.\n");
+    }
+
+    #[test]
+    fn test_detects_html() {
+        assert!(looks_like_html("\n<!DOCTYPE html><html></html>"));
+        assert!(looks_like_html("<html lang=\"en\">"));
+        assert!(!looks_like_html("# Title\n\n<div>inline html</div>"));
+    }
+}
@@ -0,0 +1,174 @@
+//! HTTP cache validators for responses served from the local cache.
+//!
+//! Search, get, and toc responses only change when the query changes or when a
+//! source they read is re-indexed. [`EntityTag`] hashes both into a strong
+//! `ETag`, and [`if_none_match`] decides whether a conditional request can be
+//! answered with `304 Not Modified`, so repeated identical requests cost a
+//! hash instead of a search.
+
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
+
+use crate::{Ranking, Source};
+
+/// `Cache-Control` value for cache-derived responses.
+///
+/// Clients may store responses but must revalidate them, which is cheap
+/// because unchanged responses come back as `304` with no body.
+pub const CACHE_CONTROL: &str = "private, no-cache";
+
+/// Builds a strong entity tag from request parameters and source state.
+///
+/// Every field is length-prefixed before hashing so adjacent values cannot
+/// run together (`("ab", "c")` and `("a", "bc")` hash differently).
+#[derive(Debug, Clone)]
+pub struct EntityTag {
+    hasher: Sha256,
+}
+
+impl EntityTag {
+    /// Start a tag for `route` (e.g. `v1/search`).
+    #[must_use]
+    pub fn new(route: &str) -> Self {
+        let mut tag = Self {
+            hasher: Sha256::new(),
+        };
+        tag.field(route);
+        tag
+    }
+
+    /// Include a request parameter, in the order the server received it.
+    #[must_use]
+    pub fn param(mut self, key: &str, value: &str) -> Self {
+        self.field(key);
+        self.field(value);
+        self
+    }
+
+    /// Include the current state of a source the response was built from.
+    ///
+    /// The content checksum covers content changes; the fetch time covers
+    /// re-indexing of identical content.
+    #[must_use]
+    pub fn source(self, alias: &str, source: &Source) -> Self {
+        self.generation(alias, &source.sha256, source.fetched_at)
+    }
+
+    /// Like [`EntityTag::source`], from the checksum and fetch time directly.
+    #[must_use]
+    pub fn generation(mut self, alias: &str, sha256: &str, fetched_at: DateTime<Utc>) -> Self {
+        self.field(alias);
+        self.field(sha256);
+        self.field(&fetched_at.to_rfc3339());
+        self
+    }
+
+    /// Include the ranking settings (boosts and stopwords) a source is
+    /// searched with, which change hits without re-indexing.
+    #[must_use]
+    pub fn ranking(mut self, alias: &str, ranking: &Ranking) -> Self {
+        self.field(alias);
+        self.field(&format!("{ranking:?}"));
+        self
+    }
+
+    /// Finish the tag as a quoted `ETag` header value.
+    #[must_use]
+    pub fn finish(self) -> String {
+        let digest = self.hasher.finalize();
+        format!("\"{}\"", URL_SAFE_NO_PAD.encode(&digest[..16]))
+    }
+
+    fn field(&mut self, value: &str) {
+        self.hasher.update((value.len() as u64).to_le_bytes());
+        self.hasher.update(value.as_bytes());
+    }
+}
+
+/// Whether an `If-None-Match` header value matches `etag`.
+///
+/// Handles `*`, comma-separated lists, and weak validators (`W/"..."`), using
+/// the weak comparison that RFC 9110 requires for `If-None-Match`.
+#[must_use]
+pub fn if_none_match(header: &str, etag: &str) -> bool {
+    fn opaque(tag: &str) -> &str {
+        tag.trim().trim_start_matches("W/")
+    }
+
+    let target = opaque(etag);
+    header
+        .split(',')
+        .map(str::trim)
+        .any(|candidate| candidate == "*" || opaque(candidate) == target)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    fn source(sha256: &str, fetched_secs: i64) -> Source {
+        Source {
+            url: "https://example.com/llms.txt".to_string(),
+            etag: None,
+            last_modified: None,
+            fetched_at: Utc.timestamp_opt(fetched_secs, 0).unwrap(),
+            sha256: sha256.to_string(),
+            variant: crate::SourceVariant::Llms,
+            aliases: Vec::new(),
+            tags: Vec::new(),
+            description: None,
+            category: None,
+            npm_aliases: Vec::new(),
+            github_aliases: Vec::new(),
+            origin: crate::SourceOrigin::default(),
+            filter_non_english: None,
+        }
+    }
+
+    fn search_tag(query: &str, src: &Source) -> String {
+        EntityTag::new("v1/search")
+            .param("q", query)
+            .source("bun", src)
+            .finish()
+    }
+
+    #[test]
+    fn tag_is_stable_for_identical_requests() {
+        let src = source("abc", 1_700_000_000);
+        let tag = search_tag("bundler", &src);
+        assert_eq!(tag, search_tag("bundler", &src));
+        assert!(tag.starts_with('"') && tag.ends_with('"'));
+        assert_eq!(tag.len(), 24);
+    }
+
+    #[test]
+    fn tag_changes_with_query_content_or_reindex() {
+        let src = source("abc", 1_700_000_000);
+        let base = search_tag("bundler", &src);
+        assert_ne!(base, search_tag("runtime", &src));
+        assert_ne!(base, search_tag("bundler", &source("def", 1_700_000_000)));
+        assert_ne!(base, search_tag("bundler", &source("abc", 1_700_000_060)));
+    }
+
+    #[test]
+    fn fields_are_length_prefixed() {
+        let a = EntityTag::new("r").param("ab", "c").finish();
+        let b = EntityTag::new("r").param("a", "bc").finish();
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn if_none_match_uses_weak_comparison() {
+        let etag = "\"0123\"";
+        assert!(if_none_match("\"0123\"", etag));
+        assert!(if_none_match("W/\"0123\"", etag));
+        assert!(if_none_match("\"zzzz\", \"0123\"", etag));
+        assert!(if_none_match("*", etag));
+        assert!(!if_none_match("\"zzzz\"", etag));
+        assert!(!if_none_match("", etag));
+    }
+}
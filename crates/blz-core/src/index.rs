@@ -3,16 +3,30 @@
 //! [`SearchIndex`] encapsulates schema creation, indexing, and querying for
 //! documentation sources. It provides snippet sizing helpers and integrates
 //! optional performance metrics for profiling search operations.
+use crate::analysis::{Analyzer, Synonyms, TextAnalysis};
+use crate::code_language;
+use crate::embeddings::{Embedder, VectorEntry, VectorIndex};
+use crate::explain::{ExplainedHit, ScoreExplanation};
+use crate::highlight;
 use crate::profiling::{ComponentTimings, OperationTimer, PerformanceMetrics};
+use crate::query_dsl::{self, QueryExpr, QueryField};
+use crate::ranking::Ranking;
+use crate::terms::{self, TermStat};
 use crate::{Error, HeadingBlock, HeadingLevel, Result, SearchHit, normalize_text_for_search};
 use base64::{Engine, engine::general_purpose::STANDARD as B64};
 use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
-use tantivy::collector::TopDocs;
-use tantivy::query::QueryParser;
-use tantivy::schema::{Field, STORED, STRING, Schema, TEXT, Value};
-use tantivy::{Index, IndexReader, doc};
+use tantivy::collector::{DocSetCollector, TopDocs};
+use tantivy::postings::Postings;
+use tantivy::query::{AllQuery, BooleanQuery, Occur, Query, QueryParser, TermQuery};
+use tantivy::schema::{
+    Field, FieldType, IndexRecordOption, STORED, STRING, Schema, TextFieldIndexing, TextOptions,
+    Value,
+};
+use tantivy::{DocAddress, DocSet, Index, IndexReader, TERMINATED, Term, doc};
 use tracing::{Level, debug, info};
+use unicode_segmentation::UnicodeSegmentation;
 
 /// Default number of characters returned for a search snippet (before any ellipses).
 pub const DEFAULT_SNIPPET_CHAR_LIMIT: usize = 200;
@@ -20,9 +34,13 @@ pub const DEFAULT_SNIPPET_CHAR_LIMIT: usize = 200;
 pub const MIN_SNIPPET_CHAR_LIMIT: usize = 50;
 /// Maximum number of characters permitted for a search snippet.
 pub const MAX_SNIPPET_CHAR_LIMIT: usize = 1_000;
+/// Largest Levenshtein distance supported for fuzzy term matching.
+pub const MAX_FUZZY_DISTANCE: u8 = 2;
 
 /// Boost factor applied to heading fields when query starts with `# `.
 const HEADING_PREFIX_BOOST: f32 = 3.0;
+/// File in the index directory holding the synonyms it was built with.
+const SYNONYMS_FILE: &str = "synonyms.json";
 
 pub(crate) const fn clamp_snippet_chars(chars: usize) -> usize {
     if chars < MIN_SNIPPET_CHAR_LIMIT {
@@ -35,9 +53,26 @@ pub(crate) const fn clamp_snippet_chars(chars: usize) -> usize {
 }
 
 #[derive(Clone, Copy)]
-enum SearchMode {
+enum SearchMode<'a> {
     Combined,
     HeadingsOnly,
+    /// Fenced code only, optionally in sections with code in `language`.
+    CodeOnly {
+        language: Option<&'a str>,
+    },
+}
+
+/// What [`SearchIndex::index_blocks_delta`] changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IndexDelta {
+    /// Blocks whose documents were left untouched.
+    pub kept: usize,
+    /// Blocks indexed as new documents.
+    pub added: usize,
+    /// Stale documents deleted.
+    pub removed: usize,
+    /// Whether the whole source was re-indexed instead.
+    pub full_rebuild: bool,
 }
 
 /// Tantivy-based search index for llms.txt documentation
@@ -51,8 +86,16 @@ pub struct SearchIndex {
     lines_field: Field,
     alias_field: Field,
     anchor_field: Option<Field>,
+    heading_field: Option<Field>,
+    code_field: Option<Field>,
+    code_language_field: Option<Field>,
+    block_field: Option<Field>,
+    section_id_field: Option<Field>,
     reader: IndexReader,
     metrics: Option<PerformanceMetrics>,
+    fuzzy_distance: u8,
+    synonyms: Synonyms,
+    ranking: Ranking,
 }
 
 impl SearchIndex {
@@ -63,37 +106,140 @@ impl SearchIndex {
         self
     }
 
+    /// Match unquoted terms within `distance` edits (typo tolerance).
+    ///
+    /// `0` keeps exact term matching; distances above
+    /// [`MAX_FUZZY_DISTANCE`] are clamped.
+    #[must_use]
+    pub fn with_fuzzy(mut self, distance: u8) -> Self {
+        self.fuzzy_distance = distance.min(MAX_FUZZY_DISTANCE);
+        self
+    }
+
+    /// Rank hits with `ranking`'s field boosts and stopwords.
+    #[must_use]
+    pub fn with_ranking(mut self, ranking: Ranking) -> Self {
+        self.ranking = ranking;
+        self
+    }
+
+    /// Field boosts and stopwords applied to queries.
+    #[must_use]
+    pub const fn ranking(&self) -> &Ranking {
+        &self.ranking
+    }
+
     /// Get the performance metrics instance
     #[must_use]
     pub const fn metrics(&self) -> Option<&PerformanceMetrics> {
         self.metrics.as_ref()
     }
+
+    /// Analyzer the index's text fields were built with.
+    #[must_use]
+    pub fn analyzer(&self) -> Analyzer {
+        field_analyzer(&self.index.schema(), self.content_field)
+    }
+
+    /// Synonyms expanded in queries against this index.
+    #[must_use]
+    pub const fn synonyms(&self) -> &Synonyms {
+        &self.synonyms
+    }
+
     /// Creates a new search index at the specified path.
     ///
     /// # Errors
     ///
     /// Returns an error if the index directory or schema cannot be created.
     pub fn create(index_path: &Path) -> Result<Self> {
+        Self::create_with_analyzer(index_path, Analyzer::default(), Synonyms::default())
+    }
+
+    /// Creates a new search index at the specified path whose text fields
+    /// are processed by `analyzer` and whose queries expand `synonyms`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the index directory or schema cannot be created,
+    /// or this build does not include the analyzer's tokenizer.
+    pub fn create_with_analyzer(
+        index_path: &Path,
+        analyzer: Analyzer,
+        synonyms: Synonyms,
+    ) -> Result<Self> {
+        std::fs::create_dir_all(index_path)
+            .map_err(|e| Error::Index(format!("Failed to create index directory: {e}")))?;
+
+        let mut index = Self::create_with(analyzer, |schema| {
+            Index::create_in_dir(index_path, schema)
+                .map_err(|e| Error::Index(format!("Failed to create index: {e}")))
+        })?;
+        if !synonyms.is_empty() {
+            let json = serde_json::to_vec(&synonyms)?;
+            std::fs::write(index_path.join(SYNONYMS_FILE), json)
+                .map_err(|e| Error::Index(format!("Failed to write synonyms: {e}")))?;
+        }
+        index.synonyms = synonyms;
+        Ok(index)
+    }
+
+    /// Creates a new search index held entirely in memory.
+    ///
+    /// Nothing touches the filesystem, so there is no directory to clean up
+    /// afterwards. Intended for throwaway indexes such as ephemeral URL
+    /// searches and tests; the index is lost when it is dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the index reader cannot be created.
+    pub fn create_in_ram() -> Result<Self> {
+        Self::create_with(Analyzer::default(), |schema| {
+            Ok(Index::create_in_ram(schema))
+        })
+    }
+
+    /// Builds the current schema and wraps the index produced by `make_index`.
+    fn create_with(
+        analyzer: Analyzer,
+        make_index: impl FnOnce(Schema) -> Result<Index>,
+    ) -> Result<Self> {
+        if !analyzer.is_available() {
+            return Err(Error::Index(format!(
+                "The {} tokenizer is not included in this build",
+                analyzer.tokenizer
+            )));
+        }
+        let analyzer_name = analyzer.name();
+        let text = TextOptions::default().set_indexing_options(
+            TextFieldIndexing::default()
+                .set_tokenizer(&analyzer_name)
+                .set_index_option(IndexRecordOption::WithFreqsAndPositions),
+        );
+        let stored_text = text.clone().set_stored();
+
         let mut schema_builder = Schema::builder();
 
-        let content_field = schema_builder.add_text_field("content", TEXT | STORED);
+        let content_field = schema_builder.add_text_field("content", stored_text.clone());
         let path_field = schema_builder.add_text_field("path", STRING | STORED);
-        let heading_path_field = schema_builder.add_text_field("heading_path", TEXT | STORED);
+        let heading_path_field = schema_builder.add_text_field("heading_path", stored_text.clone());
         let heading_path_display_field =
-            schema_builder.add_text_field("heading_path_display", TEXT | STORED);
+            schema_builder.add_text_field("heading_path_display", stored_text);
         let heading_path_normalized_field =
-            schema_builder.add_text_field("heading_path_normalized", TEXT);
+            schema_builder.add_text_field("heading_path_normalized", text.clone());
         let lines_field = schema_builder.add_text_field("lines", STRING | STORED);
         let alias_field = schema_builder.add_text_field("alias", STRING | STORED);
         let anchor_field = schema_builder.add_text_field("anchor", STRING | STORED);
+        let heading_field = schema_builder.add_text_field("heading", text.clone());
+        let code_field = schema_builder.add_text_field("code", text);
+        let code_language_field = schema_builder.add_text_field("code_lang", STRING);
+        let block_field = schema_builder.add_text_field("block", STRING);
+        let section_id_field = schema_builder.add_text_field("section_id", STRING | STORED);
 
         let schema = schema_builder.build();
 
-        std::fs::create_dir_all(index_path)
-            .map_err(|e| Error::Index(format!("Failed to create index directory: {e}")))?;
-
-        let index = Index::create_in_dir(index_path, schema)
-            .map_err(|e| Error::Index(format!("Failed to create index: {e}")))?;
+        let index = make_index(schema)?;
+        analyzer.register(&index);
 
         let reader = index
             .reader_builder()
@@ -112,7 +258,15 @@ impl SearchIndex {
             alias_field,
             reader,
             anchor_field: Some(anchor_field),
+            heading_field: Some(heading_field),
+            code_field: Some(code_field),
+            code_language_field: Some(code_language_field),
+            block_field: Some(block_field),
+            section_id_field: Some(section_id_field),
             metrics: None,
+            fuzzy_distance: 0,
+            synonyms: Synonyms::default(),
+            ranking: Ranking::default(),
         })
     }
 
@@ -143,6 +297,15 @@ impl SearchIndex {
         let content_field = schema
             .get_field("content")
             .map_err(|_| Error::Index("Missing content field".into()))?;
+        let analyzer = field_analyzer(&schema, content_field);
+        if !analyzer.is_available() {
+            return Err(Error::Index(format!(
+                "Index was built with the {} tokenizer, which this build does not include",
+                analyzer.tokenizer
+            )));
+        }
+        analyzer.register(&index);
+        let synonyms = read_synonyms(index_path)?;
         let path_field = schema
             .get_field("path")
             .map_err(|_| Error::Index("Missing path field".into()))?;
@@ -160,6 +323,14 @@ impl SearchIndex {
 
         // Anchor is optional for backward compatibility with older indexes
         let anchor_field = schema.get_field("anchor").ok();
+        // Field-scoped query targets; older indexes fall back to broader fields
+        let heading_field = schema.get_field("heading").ok();
+        let code_field = schema.get_field("code").ok();
+        let code_language_field = schema.get_field("code_lang").ok();
+        // Block fingerprints enable delta updates; older indexes always rebuild
+        let block_field = schema.get_field("block").ok();
+        // Persistent section IDs are absent from indexes built before they existed
+        let section_id_field = schema.get_field("section_id").ok();
 
         let reader = index
             .reader_builder()
@@ -178,10 +349,123 @@ impl SearchIndex {
             alias_field,
             reader,
             anchor_field,
+            heading_field,
+            code_field,
+            code_language_field,
+            block_field,
+            section_id_field,
             metrics: None,
+            fuzzy_distance: 0,
+            synonyms,
+            ranking: Ranking::default(),
         })
     }
 
+    /// Builds a fresh index of `blocks` and swaps it in at `index_path`,
+    /// with the default analysis settings.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the new index cannot be built or swapped in.
+    pub fn rebuild(
+        index_path: &Path,
+        alias: &str,
+        blocks: &[HeadingBlock],
+        metrics: PerformanceMetrics,
+    ) -> Result<()> {
+        Self::rebuild_with(index_path, alias, blocks, metrics, &TextAnalysis::default())
+    }
+
+    /// Builds a fresh index of `blocks` and swaps it in at `index_path`.
+    ///
+    /// Text is processed as `analysis` configures; without a configured
+    /// tokenizer, one is chosen for the blocks' script with
+    /// [`TokenizerKind::detect`](crate::TokenizerKind::detect).
+    ///
+    /// The new index is committed in a sibling `.new` directory and renamed
+    /// into place, so searches never see a half-built index and a damaged
+    /// index at `index_path` is replaced rather than opened. The previous
+    /// index is moved to a sibling `.old` directory and then deleted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the new index cannot be built or swapped in.
+    pub fn rebuild_with(
+        index_path: &Path,
+        alias: &str,
+        blocks: &[HeadingBlock],
+        metrics: PerformanceMetrics,
+        analysis: &TextAnalysis,
+    ) -> Result<()> {
+        let sibling = |suffix: &str| {
+            let mut name = index_path.file_name().unwrap_or_default().to_os_string();
+            name.push(suffix);
+            index_path.with_file_name(name)
+        };
+        let staging = sibling(".new");
+        let retired = sibling(".old");
+        let swap_error = |e: std::io::Error| Error::Index(format!("Failed to swap in index: {e}"));
+        for leftover in [&staging, &retired] {
+            if leftover.exists() {
+                std::fs::remove_dir_all(leftover).map_err(swap_error)?;
+            }
+        }
+
+        let analyzer = analysis.analyzer_for(blocks.iter().map(|block| block.content.as_str()));
+        let index = Self::create_with_analyzer(&staging, analyzer, analysis.synonyms.clone())?
+            .with_metrics(metrics);
+        index.index_blocks(alias, blocks)?;
+        drop(index);
+
+        if index_path.exists() {
+            std::fs::rename(index_path, &retired).map_err(swap_error)?;
+        }
+        std::fs::rename(&staging, index_path).map_err(swap_error)?;
+        if let Err(e) = std::fs::remove_dir_all(&retired) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                debug!("Failed to remove retired index {}: {e}", retired.display());
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks that the index at `index_path` is intact, returning its document
+    /// count.
+    ///
+    /// Beyond opening it, every segment file's checksum is validated, and
+    /// `.nfs*` placeholders (left when an index file is deleted while still
+    /// open on an NFS mount) are reported as stale handles.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Index`] describing the first problem found.
+    pub fn verify(index_path: &Path) -> Result<u64> {
+        if let Ok(entries) = std::fs::read_dir(index_path) {
+            for entry in entries.flatten() {
+                let name = entry.file_name();
+                if name.to_string_lossy().starts_with(".nfs") {
+                    return Err(Error::Index(format!(
+                        "Stale NFS file handle: {}",
+                        name.to_string_lossy()
+                    )));
+                }
+            }
+        }
+
+        let index = Self::open(index_path)?;
+        let corrupted = index
+            .index
+            .validate_checksum()
+            .map_err(|e| Error::Index(format!("Failed to validate checksums: {e}")))?;
+        if let Some(path) = corrupted.iter().min() {
+            return Err(Error::Index(format!(
+                "Checksum mismatch in {}",
+                path.display()
+            )));
+        }
+        Ok(index.reader.searcher().num_docs())
+    }
+
     /// Indexes a collection of heading blocks for a given alias.
     ///
     /// # Errors
@@ -211,32 +495,8 @@ impl SearchIndex {
         timings.time("document_creation", || {
             for block in blocks {
                 total_content_bytes += block.content.len();
-                let heading_path_str = block.path.join(" > ");
-                let display_path_str = block.display_path.join(" > ");
-                let normalized_heading_str = block.normalized_tokens.join(" ");
-                let lines_str = format!("{}-{}", block.start_line, block.end_line);
-                // Compute anchor from last heading text
-                let anchor = block.path.last().map(|h| Self::compute_anchor(h));
-
-                let mut doc = doc!(
-                    self.content_field => block.content.as_str(),  // Use &str instead of clone
-                    self.path_field => "llms.txt",  // Always llms.txt (no flavor variants)
-                    self.heading_path_field => heading_path_str,
-                    self.lines_field => lines_str,
-                    self.alias_field => alias
-                );
-                if let Some(field) = self.heading_path_display_field {
-                    doc.add_text(field, display_path_str.as_str());
-                }
-                if let Some(field) = self.heading_path_normalized_field {
-                    doc.add_text(field, normalized_heading_str.as_str());
-                }
-                if let (Some(f), Some(a)) = (self.anchor_field, anchor) {
-                    doc.add_text(f, a);
-                }
-
                 writer
-                    .add_document(doc)
+                    .add_document(self.block_document(alias, block))
                     .map_err(|e| Error::Index(format!("Failed to add document: {e}")))?;
             }
             Ok::<(), Error>(())
@@ -272,6 +532,231 @@ impl SearchIndex {
         Ok(())
     }
 
+    /// Updates the index to match `blocks`, touching only blocks that changed.
+    ///
+    /// Every block is fingerprinted by its heading path, line range, and
+    /// content. Documents whose fingerprint no longer appears are deleted and
+    /// only new fingerprints are added, so a small upstream edit costs a few
+    /// documents instead of a full rebuild. Blocks that moved keep their text
+    /// but change line ranges, so edits that add or remove lines still
+    /// re-index every block after the edit.
+    ///
+    /// Falls back to [`index_blocks`](Self::index_blocks) for indexes created
+    /// before fingerprints existed, for empty indexes, and when the indexed
+    /// documents cannot be matched to fingerprints one-to-one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the index cannot be read, written, or committed.
+    pub fn index_blocks_delta(&self, alias: &str, blocks: &[HeadingBlock]) -> Result<IndexDelta> {
+        let full_rebuild = |index: &Self| {
+            index.index_blocks(alias, blocks).map(|()| IndexDelta {
+                kept: 0,
+                added: blocks.len(),
+                removed: 0,
+                full_rebuild: true,
+            })
+        };
+        let Some(block_field) = self.block_field else {
+            return full_rebuild(self);
+        };
+
+        let Some(indexed) = self.indexed_block_ids(alias, block_field)? else {
+            return full_rebuild(self);
+        };
+
+        let wanted: HashMap<String, &HeadingBlock> = blocks
+            .iter()
+            .map(|block| (Self::block_id(alias, block), block))
+            .collect();
+        let removed: Vec<&String> = indexed
+            .iter()
+            .filter(|id| !wanted.contains_key(*id))
+            .collect();
+        let added: Vec<&HeadingBlock> = wanted
+            .iter()
+            .filter(|(id, _)| !indexed.contains(*id))
+            .map(|(_, block)| *block)
+            .collect();
+        let delta = IndexDelta {
+            kept: wanted.len() - added.len(),
+            added: added.len(),
+            removed: removed.len(),
+            full_rebuild: false,
+        };
+        if added.is_empty() && removed.is_empty() {
+            return Ok(delta);
+        }
+
+        let mut writer = self
+            .index
+            .writer(50_000_000)
+            .map_err(|e| Error::Index(format!("Failed to create writer: {e}")))?;
+        for id in removed {
+            writer.delete_term(Term::from_field_text(block_field, id));
+        }
+        for block in added {
+            writer
+                .add_document(self.block_document(alias, block))
+                .map_err(|e| Error::Index(format!("Failed to add document: {e}")))?;
+        }
+        writer
+            .commit()
+            .map_err(|e| Error::Index(format!("Failed to commit: {e}")))?;
+        self.reader
+            .reload()
+            .map_err(|e| Error::Index(format!("Failed to reload reader: {e}")))?;
+
+        info!(
+            "Delta-indexed {}: {} kept, {} added, {} removed",
+            alias, delta.kept, delta.added, delta.removed
+        );
+        Ok(delta)
+    }
+
+    /// Fingerprints of the live documents indexed for `alias`.
+    ///
+    /// Returns `None` when there is nothing to diff against: no documents, or
+    /// documents without a unique fingerprint each.
+    fn indexed_block_ids(
+        &self,
+        alias: &str,
+        block_field: Field,
+    ) -> Result<Option<HashSet<String>>> {
+        let searcher = self.reader.searcher();
+        let alias_docs = searcher
+            .search(
+                &TermQuery::new(
+                    Term::from_field_text(self.alias_field, alias),
+                    IndexRecordOption::Basic,
+                ),
+                &tantivy::collector::Count,
+            )
+            .map_err(|e| Error::Index(format!("Search failed: {e}")))?;
+        if alias_docs == 0 {
+            return Ok(None);
+        }
+
+        let mut ids = HashSet::new();
+        let mut docs_with_ids = 0usize;
+        for segment in searcher.segment_readers() {
+            let alive = segment.alive_bitset();
+            let inverted = segment
+                .inverted_index(block_field)
+                .map_err(|e| Error::Index(format!("Failed to read term dictionary: {e}")))?;
+            let mut stream = inverted
+                .terms()
+                .stream()
+                .map_err(|e| Error::Index(format!("Failed to read term dictionary: {e}")))?;
+            while stream.advance() {
+                let mut postings = inverted
+                    .read_postings_from_terminfo(stream.value(), IndexRecordOption::Basic)
+                    .map_err(|e| Error::Index(format!("Failed to read postings: {e}")))?;
+                let mut live = 0usize;
+                while postings.doc() != TERMINATED {
+                    if alive.is_none_or(|bits| bits.is_alive(postings.doc())) {
+                        live += 1;
+                    }
+                    postings.advance();
+                }
+                if live > 0 {
+                    docs_with_ids += live;
+                    ids.insert(String::from_utf8_lossy(stream.key()).into_owned());
+                }
+            }
+        }
+
+        Ok((docs_with_ids == alias_docs && ids.len() == alias_docs).then_some(ids))
+    }
+
+    /// Builds the Tantivy document for one heading block.
+    fn block_document(&self, alias: &str, block: &HeadingBlock) -> tantivy::TantivyDocument {
+        let heading_path_str = block.path.join(" > ");
+        let display_path_str = block.display_path.join(" > ");
+        let normalized_heading_str = block.normalized_tokens.join(" ");
+        let lines_str = format!("{}-{}", block.start_line, block.end_line);
+        // Compute anchor from last heading text
+        let anchor = block.path.last().map(|h| Self::compute_anchor(h));
+
+        let mut doc = doc!(
+            self.content_field => block.content.as_str(),  // Use &str instead of clone
+            self.path_field => "llms.txt",  // Always llms.txt (no flavor variants)
+            self.heading_path_field => heading_path_str,
+            self.lines_field => lines_str,
+            self.alias_field => alias
+        );
+        if let Some(field) = self.heading_path_display_field {
+            doc.add_text(field, display_path_str.as_str());
+        }
+        if let Some(field) = self.heading_path_normalized_field {
+            doc.add_text(field, normalized_heading_str.as_str());
+        }
+        if let (Some(f), Some(a)) = (self.anchor_field, anchor) {
+            doc.add_text(f, a);
+        }
+        if let Some(field) = self.heading_field {
+            let heading = block.display_path.last().or_else(|| block.path.last());
+            doc.add_text(field, heading.map_or("", String::as_str));
+        }
+        let code_blocks = block.code_blocks();
+        if let Some(field) = self.code_field {
+            let code: String = code_blocks
+                .iter()
+                .map(|code_block| code_block.code.as_str())
+                .collect();
+            doc.add_text(field, code);
+        }
+        if let Some(field) = self.code_language_field {
+            let mut languages: Vec<&str> = code_blocks
+                .iter()
+                .filter_map(|code_block| code_block.language.as_deref())
+                .collect();
+            languages.sort_unstable();
+            languages.dedup();
+            for language in languages {
+                doc.add_text(field, language);
+            }
+        }
+        if let Some(field) = self.block_field {
+            doc.add_text(field, Self::block_id(alias, block));
+        }
+        if let Some(field) = self.section_id_field {
+            doc.add_text(field, block.id.as_str());
+        }
+        doc
+    }
+
+    /// Fingerprint of a block's identity and position within its source.
+    fn block_id(alias: &str, block: &HeadingBlock) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(alias.as_bytes());
+        for segment in &block.path {
+            hasher.update([0x1f]);
+            hasher.update(segment.as_bytes());
+        }
+        hasher.update(format!("\0{}-{}\0", block.start_line, block.end_line).as_bytes());
+        hasher.update(block.content.as_bytes());
+        hasher.update([0]);
+        hasher.update(block.id.as_bytes());
+        B64.encode(hasher.finalize())
+    }
+
+    /// Returns the bytes occupied by the committed segments of this index.
+    ///
+    /// Works for in-memory indexes too, so callers can estimate how large an
+    /// index would be on disk without writing one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if segment sizes cannot be read.
+    pub fn space_usage(&self) -> Result<u64> {
+        self.reader
+            .searcher()
+            .space_usage()
+            .map(|usage| usage.total().get_bytes())
+            .map_err(|e| Error::Index(format!("Failed to compute index size: {e}")))
+    }
+
     /// Searches the index with optional alias filtering.
     ///
     /// # Errors
@@ -362,6 +847,328 @@ impl SearchIndex {
         )
     }
 
+    /// Searches only the fenced code blocks of each section.
+    ///
+    /// With `language` (`ts`, `python`, ...), only sections with a code block
+    /// in that language match.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query cannot be parsed or executed, or if a
+    /// language is given but the index predates code language tags.
+    pub fn search_code(
+        &self,
+        query_str: &str,
+        alias: Option<&str>,
+        language: Option<&str>,
+        limit: usize,
+        snippet_max_chars: usize,
+    ) -> Result<Vec<SearchHit>> {
+        self.search_code_with_timing(query_str, alias, language, limit, snippet_max_chars, false)
+    }
+
+    /// Searches only fenced code blocks with optional timing output.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query cannot be parsed or executed, or if a
+    /// language is given but the index predates code language tags.
+    pub fn search_code_with_timing(
+        &self,
+        query_str: &str,
+        alias: Option<&str>,
+        language: Option<&str>,
+        limit: usize,
+        snippet_max_chars: usize,
+        show_timing: bool,
+    ) -> Result<Vec<SearchHit>> {
+        self.search_internal(
+            query_str,
+            alias,
+            limit,
+            snippet_max_chars,
+            SearchMode::CodeOnly { language },
+            show_timing,
+        )
+    }
+
+    /// Embeds every indexed block of `alias` for semantic search.
+    ///
+    /// Each block is embedded from its heading path followed by its content.
+    /// `fingerprint` identifies the indexed content so stale vectors can be detected.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the indexed documents cannot be read or embedded.
+    pub fn build_vectors(
+        &self,
+        alias: &str,
+        embedder: &dyn Embedder,
+        fingerprint: &str,
+    ) -> Result<VectorIndex> {
+        let searcher = self.reader.searcher();
+        let query = TermQuery::new(
+            Term::from_field_text(self.alias_field, alias),
+            IndexRecordOption::Basic,
+        );
+        let mut addresses: Vec<_> = searcher
+            .search(&query, &DocSetCollector)
+            .map_err(|e| Error::Index(format!("Failed to list documents: {e}")))?
+            .into_iter()
+            .collect();
+        addresses.sort();
+
+        let mut lines = Vec::with_capacity(addresses.len());
+        let mut texts = Vec::with_capacity(addresses.len());
+        for address in addresses {
+            let doc: tantivy::TantivyDocument = searcher
+                .doc(address)
+                .map_err(|e| Error::Index(format!("Failed to retrieve doc: {e}")))?;
+            let heading_path = Self::get_field_text(&doc, self.heading_path_field)?;
+            let content = Self::get_field_text(&doc, self.content_field)?;
+            lines.push(Self::get_field_text(&doc, self.lines_field)?);
+            texts.push(format!("{heading_path}\n{content}"));
+        }
+        let entries = lines
+            .into_iter()
+            .zip(embedder.embed_batch(&texts)?)
+            .map(|(lines, vector)| VectorEntry { lines, vector })
+            .collect();
+
+        Ok(VectorIndex {
+            embedder: embedder.id().to_string(),
+            dimensions: embedder.dimensions(),
+            fingerprint: fingerprint.to_string(),
+            entries,
+        })
+    }
+
+    /// Ranks the blocks of `alias` by cosine similarity to `query_str`.
+    ///
+    /// Hit scores are cosine similarities (`0..=1`) rather than BM25 scores.
+    /// Vectors whose block is no longer in the index are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query cannot be embedded or matching documents
+    /// cannot be read.
+    pub fn semantic_search(
+        &self,
+        vectors: &VectorIndex,
+        embedder: &dyn Embedder,
+        query_str: &str,
+        alias: &str,
+        limit: usize,
+        snippet_max_chars: usize,
+    ) -> Result<Vec<SearchHit>> {
+        let searcher = self.reader.searcher();
+        let snippet_limit = clamp_snippet_chars(snippet_max_chars);
+        let query_vector = embedder.embed(query_str)?;
+        let highlight_terms = highlight::query_terms(query_str);
+
+        let mut hits = Vec::new();
+        for (entry, score) in vectors.nearest(&query_vector, limit) {
+            let block_query = BooleanQuery::new(vec![
+                (
+                    Occur::Must,
+                    Box::new(TermQuery::new(
+                        Term::from_field_text(self.alias_field, alias),
+                        IndexRecordOption::Basic,
+                    )) as Box<dyn tantivy::query::Query>,
+                ),
+                (
+                    Occur::Must,
+                    Box::new(TermQuery::new(
+                        Term::from_field_text(self.lines_field, &entry.lines),
+                        IndexRecordOption::Basic,
+                    )),
+                ),
+            ]);
+            let top = searcher
+                .search(&block_query, &TopDocs::with_limit(1))
+                .map_err(|e| Error::Index(format!("Search failed: {e}")))?;
+            let Some((_, address)) = top.first() else {
+                continue;
+            };
+            let doc = searcher
+                .doc(*address)
+                .map_err(|e| Error::Index(format!("Failed to retrieve doc: {e}")))?;
+            let (hit, _) =
+                self.process_search_doc(&doc, score, query_str, &highlight_terms, snippet_limit)?;
+            hits.push(hit);
+        }
+
+        debug!(
+            "Found {} semantic hits for query '{}' in {}",
+            hits.len(),
+            query_str,
+            alias
+        );
+        Ok(hits)
+    }
+
+    /// Search and explain how each hit's score was computed.
+    ///
+    /// Runs the same query as [`Self::search`] and returns, per hit, the BM25
+    /// contribution of every matched term (with field boosts applied) and the
+    /// full explanation tree.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query cannot be parsed or the search fails.
+    pub fn explain(
+        &self,
+        query_str: &str,
+        alias: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<ExplainedHit>> {
+        let (query_body_input, heading_boost) = Self::detect_heading_boost(query_str);
+        let query_parser = self.query_parser(SearchMode::Combined, heading_boost);
+        let (full_query_str, highlight) = self.prepare_query(query_body_input, alias)?;
+        let highlight_query = highlight.as_deref().unwrap_or(query_body_input);
+        let highlight_terms = highlight::query_terms(query_body_input);
+        let query = query_parser
+            .parse_query(&full_query_str)
+            .map_err(|e| Error::Index(format!("Failed to parse query: {e}")))?;
+
+        let searcher = self.reader.searcher();
+        let top_docs = searcher
+            .search(&query, &TopDocs::with_limit(limit))
+            .map_err(|e| Error::Index(format!("Search failed: {e}")))?;
+
+        let schema = self.index.schema();
+        let field_name = |id: u32| schema.get_field_name(Field::from_field_id(id)).to_string();
+        let snippet_limit = clamp_snippet_chars(DEFAULT_SNIPPET_CHAR_LIMIT);
+
+        top_docs
+            .into_iter()
+            .map(|(score, doc_address)| {
+                let doc = searcher
+                    .doc(doc_address)
+                    .map_err(|e| Error::Index(format!("Failed to retrieve doc: {e}")))?;
+                let (hit, _) = self.process_search_doc(
+                    &doc,
+                    score,
+                    highlight_query,
+                    &highlight_terms,
+                    snippet_limit,
+                )?;
+                let explanation = query
+                    .explain(&searcher, doc_address)
+                    .map_err(|e| Error::Index(format!("Failed to explain score: {e}")))?;
+                let explanation = ScoreExplanation::from_tantivy(&explanation);
+                Ok(ExplainedHit {
+                    hit,
+                    terms: explanation.term_contributions(field_name),
+                    explanation,
+                })
+            })
+            .collect()
+    }
+
+    /// Most frequent indexed content terms, largest first.
+    ///
+    /// Walks the content field's term dictionary in every segment and sums
+    /// each term's postings over live documents. With `heading`, only sections
+    /// whose heading path contains that path (see
+    /// [`terms::heading_path_matches`]) are counted. `prefix` keeps only terms
+    /// starting with the given (lowercased) text.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the term dictionary or postings cannot be read.
+    pub fn term_stats(
+        &self,
+        heading: Option<&str>,
+        prefix: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<TermStat>> {
+        let searcher = self.reader.searcher();
+        let allowed = heading
+            .map(|filter| self.docs_under_heading(&searcher, filter))
+            .transpose()?;
+        let prefix = prefix.map(str::to_lowercase).unwrap_or_default();
+
+        let mut totals: HashMap<String, (u64, u64)> = HashMap::new();
+        for (segment_ord, segment) in (0u32..).zip(searcher.segment_readers()) {
+            let alive = segment.alive_bitset();
+            let inverted = segment
+                .inverted_index(self.content_field)
+                .map_err(|e| Error::Index(format!("Failed to read term dictionary: {e}")))?;
+            let mut stream = inverted
+                .terms()
+                .range()
+                .ge(prefix.as_bytes())
+                .into_stream()
+                .map_err(|e| Error::Index(format!("Failed to read term dictionary: {e}")))?;
+            while stream.advance() {
+                if !stream.key().starts_with(prefix.as_bytes()) {
+                    break;
+                }
+                let Ok(term) = std::str::from_utf8(stream.key()) else {
+                    continue;
+                };
+                let mut postings = inverted
+                    .read_postings_from_terminfo(stream.value(), IndexRecordOption::WithFreqs)
+                    .map_err(|e| Error::Index(format!("Failed to read postings: {e}")))?;
+                let (mut doc_freq, mut term_freq) = (0u64, 0u64);
+                while postings.doc() != TERMINATED {
+                    let doc_id = postings.doc();
+                    let counted = alive.is_none_or(|bits| bits.is_alive(doc_id))
+                        && allowed.as_ref().is_none_or(|docs| {
+                            docs.contains(&DocAddress::new(segment_ord, doc_id))
+                        });
+                    if counted {
+                        doc_freq += 1;
+                        term_freq += u64::from(postings.term_freq());
+                    }
+                    postings.advance();
+                }
+                if doc_freq > 0 {
+                    let entry = totals.entry(term.to_string()).or_default();
+                    entry.0 += doc_freq;
+                    entry.1 += term_freq;
+                }
+            }
+        }
+
+        let mut stats: Vec<TermStat> = totals
+            .into_iter()
+            .map(|(term, (doc_freq, term_freq))| TermStat {
+                term,
+                doc_freq,
+                term_freq,
+            })
+            .collect();
+        terms::rank(&mut stats);
+        stats.truncate(limit);
+        Ok(stats)
+    }
+
+    /// Addresses of live documents whose heading path falls under `filter`.
+    fn docs_under_heading(
+        &self,
+        searcher: &tantivy::Searcher,
+        filter: &str,
+    ) -> Result<HashSet<DocAddress>> {
+        let segments = terms::heading_filter_segments(filter);
+        let all = searcher
+            .search(&AllQuery, &DocSetCollector)
+            .map_err(|e| Error::Index(format!("Search failed: {e}")))?;
+        let mut matched = HashSet::new();
+        for address in all {
+            let doc: tantivy::TantivyDocument = searcher
+                .doc(address)
+                .map_err(|e| Error::Index(format!("Failed to retrieve doc: {e}")))?;
+            let heading_path =
+                Self::get_optional_field(&doc, self.heading_path_field).unwrap_or_default();
+            if terms::heading_path_matches(&heading_path, &segments) {
+                matched.insert(address);
+            }
+        }
+        Ok(matched)
+    }
+
     /// Detect heading boost prefix (`# `) in query and return `(query_body, optional_boost)`.
     fn detect_heading_boost(query_str: &str) -> (&str, Option<f32>) {
         let trimmed_prefix = query_str.trim_start();
@@ -381,7 +1188,14 @@ impl SearchIndex {
     }
 
     /// Build the full query string with optional alias filter and normalized query.
-    fn build_query_string(query_body_input: &str, alias: Option<&str>) -> String {
+    ///
+    /// Each of `alternatives` (synonyms of the query's terms) is also matched
+    /// as a phrase.
+    fn build_query_string(
+        query_body_input: &str,
+        alias: Option<&str>,
+        alternatives: &[String],
+    ) -> String {
         let sanitized_query = Self::escape_query(query_body_input);
 
         // Check if the original query is a phrase query (quoted)
@@ -403,11 +1217,18 @@ impl SearchIndex {
         };
 
         let use_normalized = !normalized_query.is_empty() && normalized_query != sanitized_query;
-        let query_body = if use_normalized {
+        let mut query_body = if use_normalized {
             format!("({sanitized_query}) OR ({normalized_query})")
         } else {
             sanitized_query
         };
+        if !alternatives.is_empty() {
+            let phrases: Vec<String> = alternatives
+                .iter()
+                .map(|alternative| format!("\"{alternative}\""))
+                .collect();
+            query_body = format!("({query_body}) OR {}", phrases.join(" OR "));
+        }
 
         if let Some(alias) = alias {
             format!("alias:{alias} AND ({query_body})")
@@ -416,6 +1237,194 @@ impl SearchIndex {
         }
     }
 
+    /// Query parser over the fields `mode` searches, with fuzzy matching, the
+    /// source's field boosts, and the optional heading boost applied.
+    fn query_parser(&self, mode: SearchMode, heading_boost: Option<f32>) -> QueryParser {
+        let mut path_fields = vec![self.heading_path_field];
+        path_fields.extend(self.heading_path_display_field);
+        path_fields.extend(self.heading_path_normalized_field);
+
+        let prefix_boost = heading_boost.unwrap_or(1.0);
+        let path_boost = self.ranking.path_boost * prefix_boost;
+        let mut boosts: Vec<(Field, f32)> = Vec::new();
+        if heading_boost.is_some() || (path_boost - 1.0).abs() > f32::EPSILON {
+            boosts.extend(path_fields.iter().map(|&field| (field, path_boost)));
+        }
+
+        let mut fields = match mode {
+            SearchMode::Combined => vec![self.content_field],
+            SearchMode::HeadingsOnly => Vec::new(),
+            // Older indexes without a code field fall back to all content
+            SearchMode::CodeOnly { .. } => {
+                let field = self.code_field.unwrap_or(self.content_field);
+                return self.fuzzy_parser(&[field], &[]);
+            },
+        };
+        fields.extend(path_fields);
+        // The heading and code fields are only searched when boosted, so
+        // default ranking matches what it was before they existed
+        if let (Some(field), Some(boost)) = (self.heading_field, self.ranking.heading_boost) {
+            fields.push(field);
+            boosts.push((field, boost * prefix_boost));
+        }
+        if let (SearchMode::Combined, Some(field), Some(boost)) =
+            (mode, self.code_field, self.ranking.code_boost)
+        {
+            fields.push(field);
+            boosts.push((field, boost));
+        }
+
+        self.fuzzy_parser(&fields, &boosts)
+    }
+
+    /// Query parser over `fields` with fuzzy matching and `boosts` applied.
+    fn fuzzy_parser(&self, fields: &[Field], boosts: &[(Field, f32)]) -> QueryParser {
+        let mut parser = QueryParser::for_index(&self.index, fields.to_vec());
+        if self.fuzzy_distance > 0 {
+            // Phrases stay exact; only single terms become fuzzy
+            for &field in fields {
+                parser.set_field_fuzzy(field, false, self.fuzzy_distance, true);
+            }
+        }
+        for &(field, boost) in boosts {
+            parser.set_field_boost(field, boost);
+        }
+        parser
+    }
+
+    /// `query`, limited to sections with code in the language `mode` names.
+    fn restrict_code_language(
+        &self,
+        query: Box<dyn Query>,
+        mode: SearchMode,
+    ) -> Result<Box<dyn Query>> {
+        let SearchMode::CodeOnly {
+            language: Some(language),
+        } = mode
+        else {
+            return Ok(query);
+        };
+        let name = code_language::canonical_language(language)
+            .ok_or_else(|| Error::Parse(format!("Invalid code language '{language}'")))?;
+        let field = self.code_language_field.ok_or_else(|| {
+            Error::Index(
+                "Index predates code language tags; rebuild it with `blz sync --reindex`".into(),
+            )
+        })?;
+        Ok(Box::new(BooleanQuery::new(vec![
+            (Occur::Must, query),
+            (
+                Occur::Must,
+                Box::new(TermQuery::new(
+                    Term::from_field_text(field, &name),
+                    IndexRecordOption::Basic,
+                )),
+            ),
+        ])))
+    }
+
+    /// Build the Tantivy query string for plain or structured input.
+    ///
+    /// Structured input (see [`query_dsl`]) is compiled explicitly and also
+    /// yields the term used to locate snippets; plain input keeps the
+    /// bag-of-terms query built by [`Self::build_query_string`].
+    fn prepare_query(
+        &self,
+        query_body_input: &str,
+        alias: Option<&str>,
+    ) -> Result<(String, Option<String>)> {
+        if !query_dsl::is_structured(query_body_input) {
+            let query_body_input = self.ranking.strip_stopwords(query_body_input);
+            let alternatives = self.synonyms.expansions(&query_body_input);
+            return Ok((
+                Self::build_query_string(&query_body_input, alias, &alternatives),
+                None,
+            ));
+        }
+        let expr = query_dsl::parse(query_body_input)?;
+        let compiled = self.compile_query(&expr);
+        let full_query = alias.map_or_else(
+            || compiled.clone(),
+            |alias| format!("alias:{alias} AND {compiled}"),
+        );
+        Ok((full_query, expr.highlight_text()))
+    }
+
+    /// Translate a structured query into Tantivy query syntax.
+    fn compile_query(&self, expr: &QueryExpr) -> String {
+        match expr {
+            QueryExpr::Term {
+                field,
+                text,
+                phrase,
+            } => {
+                let value = if *phrase {
+                    format!("\"{}\"", text.replace('\\', "\\\\"))
+                } else {
+                    Self::escape_query(text)
+                };
+                // Synonym terms are plain words, so they need no escaping
+                let mut values = vec![value];
+                values.extend(
+                    self.synonyms
+                        .expansions(text)
+                        .into_iter()
+                        .map(|alternative| format!("\"{alternative}\"")),
+                );
+                let Some(field) = field else {
+                    return if values.len() == 1 {
+                        values.swap_remove(0)
+                    } else {
+                        format!("({})", values.join(" OR "))
+                    };
+                };
+                let schema = self.index.schema();
+                let scoped: Vec<String> = self
+                    .scoped_fields(*field)
+                    .into_iter()
+                    .flat_map(|target| {
+                        let name = schema.get_field_name(target);
+                        values.iter().map(move |value| format!("{name}:{value}"))
+                    })
+                    .collect();
+                format!("({})", scoped.join(" OR "))
+            },
+            QueryExpr::And(children) => {
+                let clauses: Vec<String> = children
+                    .iter()
+                    .map(|child| match child {
+                        QueryExpr::Not(inner) => format!("-{}", self.compile_query(inner)),
+                        other => format!("+{}", self.compile_query(other)),
+                    })
+                    .collect();
+                format!("({})", clauses.join(" "))
+            },
+            QueryExpr::Or(children) => {
+                let alternatives: Vec<String> = children
+                    .iter()
+                    .map(|child| self.compile_query(child))
+                    .collect();
+                format!("({})", alternatives.join(" OR "))
+            },
+            QueryExpr::Not(inner) => format!("-{}", self.compile_query(inner)),
+        }
+    }
+
+    /// Index fields a scoped term searches.
+    fn scoped_fields(&self, field: QueryField) -> Vec<Field> {
+        let heading_paths = || {
+            let mut fields = vec![self.heading_path_field];
+            fields.extend(self.heading_path_display_field);
+            fields.extend(self.heading_path_normalized_field);
+            fields
+        };
+        match field {
+            QueryField::Heading => self.heading_field.map_or_else(heading_paths, |f| vec![f]),
+            QueryField::Path => heading_paths(),
+            QueryField::Code => vec![self.code_field.unwrap_or(self.content_field)],
+        }
+    }
+
     /// Parse heading path segments from raw and display strings.
     fn parse_heading_paths(
         heading_path_str: &str,
@@ -447,6 +1456,7 @@ impl SearchIndex {
         doc: &tantivy::TantivyDocument,
         score: f32,
         query_body: &str,
+        highlight_terms: &[String],
         snippet_limit: usize,
     ) -> Result<(SearchHit, usize)> {
         let alias = Self::get_field_text(doc, self.alias_field)?;
@@ -462,6 +1472,9 @@ impl SearchIndex {
                 .and_then(|v| v.as_str())
                 .map(std::string::ToString::to_string)
         });
+        let section_id = self
+            .section_id_field
+            .and_then(|field| Self::get_optional_field(doc, field));
 
         let lines_in_content = content.lines().count();
 
@@ -483,6 +1496,8 @@ impl SearchIndex {
             level,
             lines: exact_lines,
             line_numbers,
+            snippet_language: code_language::snippet_language(&content, &snippet),
+            highlights: highlight::match_spans(&snippet, highlight_terms),
             snippet,
             score,
             source_url: None,
@@ -490,6 +1505,7 @@ impl SearchIndex {
             is_stale: false,
             checksum: String::new(),
             anchor,
+            section_id,
             context: None,
         };
 
@@ -518,37 +1534,20 @@ impl SearchIndex {
 
         let searcher = timings.time("searcher_creation", || self.reader.searcher());
 
-        let mut query_parser = timings.time("query_parser_creation", || {
-            let mut fields = match mode {
-                SearchMode::Combined => vec![self.content_field, self.heading_path_field],
-                SearchMode::HeadingsOnly => vec![self.heading_path_field],
-            };
-            if let Some(field) = self.heading_path_display_field {
-                fields.push(field);
-            }
-            if let Some(field) = self.heading_path_normalized_field {
-                fields.push(field);
-            }
-            QueryParser::for_index(&self.index, fields)
+        let query_parser = timings.time("query_parser_creation", || {
+            self.query_parser(mode, heading_boost)
         });
 
-        if let Some(boost) = heading_boost {
-            query_parser.set_field_boost(self.heading_path_field, boost);
-            if let Some(field) = self.heading_path_display_field {
-                query_parser.set_field_boost(field, boost);
-            }
-            if let Some(field) = self.heading_path_normalized_field {
-                query_parser.set_field_boost(field, boost);
-            }
-        }
-
-        let full_query_str = Self::build_query_string(query_body_input, alias);
+        let (full_query_str, highlight) = self.prepare_query(query_body_input, alias)?;
+        let highlight_query = highlight.as_deref().unwrap_or(query_body_input);
+        let highlight_terms = highlight::query_terms(query_body_input);
 
         let query = timings.time("query_parsing", || {
             query_parser
                 .parse_query(&full_query_str)
                 .map_err(|e| Error::Index(format!("Failed to parse query: {e}")))
         })?;
+        let query = self.restrict_code_language(query, mode)?;
 
         let top_docs = timings.time("tantivy_search", || {
             searcher
@@ -564,8 +1563,13 @@ impl SearchIndex {
                     .doc(doc_address)
                     .map_err(|e| Error::Index(format!("Failed to retrieve doc: {e}")))?;
 
-                let (hit, doc_lines) =
-                    self.process_search_doc(&doc, score, query_body_input, snippet_limit)?;
+                let (hit, doc_lines) = self.process_search_doc(
+                    &doc,
+                    score,
+                    highlight_query,
+                    &highlight_terms,
+                    snippet_limit,
+                )?;
                 lines_searched += doc_lines;
                 hits.push(hit);
             }
@@ -739,7 +1743,7 @@ impl SearchIndex {
         let mut match_char_pos = None;
 
         // Use a sliding window approach with character iteration
-        let content_chars: Vec<char> = content.chars().collect();
+        let content_chars: Vec<(usize, char)> = content.char_indices().collect();
         let query_chars: Vec<char> = query_lower.chars().collect();
 
         if !query_chars.is_empty() {
@@ -753,7 +1757,7 @@ impl SearchIndex {
                 let window_matches = content_chars[window_start..window_end]
                     .iter()
                     .zip(query_chars.iter())
-                    .all(|(c1, c2)| c1.to_lowercase().eq(c2.to_lowercase()));
+                    .all(|((_, c1), c2)| c1.to_lowercase().eq(c2.to_lowercase()));
 
                 if window_matches {
                     match_char_pos = Some(window_start);
@@ -762,57 +1766,75 @@ impl SearchIndex {
             }
         }
 
+        // Lengths are counted in grapheme clusters so a snippet never splits
+        // an emoji sequence or a base character from its combining marks.
+        let graphemes: Vec<usize> = content
+            .grapheme_indices(true)
+            .map(|(offset, _)| offset)
+            .collect();
+        let total = graphemes.len();
+        let byte_at = |grapheme: usize| graphemes.get(grapheme).copied().unwrap_or(content.len());
+
         if let Some(char_pos) = match_char_pos {
+            let match_start = content_chars[char_pos].0;
+            let match_end = content_chars
+                .get(char_pos + query_chars.len())
+                .map_or(content.len(), |(offset, _)| *offset);
+            let first = graphemes
+                .partition_point(|offset| *offset <= match_start)
+                .saturating_sub(1);
+            let qlen = graphemes.partition_point(|offset| *offset < match_end) - first;
+
             // Derive context from max_len so we don't overshoot the requested length.
-            let total_chars = content_chars.len();
-            let qlen = query_chars.len();
             let ctx_each_side = max_len.saturating_sub(qlen) / 2;
+            let start = first.saturating_sub(ctx_each_side);
+            let mut end = (first + qlen + ctx_each_side).min(total);
 
-            let start_char = char_pos.saturating_sub(ctx_each_side);
-            let mut end_char = (char_pos + qlen + ctx_each_side).min(total_chars);
-
-            // Clamp to at most max_len characters around the match.
-            let span = end_char.saturating_sub(start_char);
-            if span > max_len {
-                end_char = start_char + max_len;
+            // Clamp to at most max_len graphemes around the match.
+            if end.saturating_sub(start) > max_len {
+                end = start + max_len;
             }
 
-            let left_trunc = start_char > 0;
-            let right_trunc = end_char < total_chars;
-
-            // Build snippet
-            let mut snippet = String::with_capacity((end_char - start_char) * 4 + 6);
-            if left_trunc {
+            let mut snippet = String::with_capacity(byte_at(end) - byte_at(start) + 6);
+            if start > 0 {
                 snippet.push_str("...");
             }
-            for &ch in content_chars.iter().take(end_char).skip(start_char) {
-                snippet.push(ch);
-            }
-            if right_trunc {
+            snippet.push_str(&content[byte_at(start)..byte_at(end)]);
+            if end < total {
                 snippet.push_str("...");
             }
             return snippet;
         }
 
-        // No match found - return truncated content using character count
-        let content_chars: Vec<char> = content.chars().collect();
-        if content_chars.len() <= max_len {
+        // No match found - return truncated content using grapheme count
+        if total <= max_len {
             content.to_string()
         } else {
-            // Truncate based on character count, not byte count
-            let mut result = String::with_capacity(max_len * 4 + 3);
-            for (i, ch) in content_chars.iter().enumerate() {
-                if i >= max_len {
-                    break;
-                }
-                result.push(*ch);
-            }
-            result.push_str("...");
-            result
+            format!("{}...", &content[..byte_at(max_len)])
         }
     }
 }
 
+/// Analyzer recorded for a text field; unknown names read as the default.
+fn field_analyzer(schema: &Schema, field: Field) -> Analyzer {
+    match schema.get_field_entry(field).field_type() {
+        FieldType::Str(options) => options
+            .get_indexing_options()
+            .and_then(|indexing| Analyzer::from_name(indexing.tokenizer()))
+            .unwrap_or_default(),
+        _ => Analyzer::default(),
+    }
+}
+
+/// Synonyms stored in an index directory; none for indexes built without.
+fn read_synonyms(index_path: &Path) -> Result<Synonyms> {
+    match std::fs::read(index_path.join(SYNONYMS_FILE)) {
+        Ok(json) => Ok(serde_json::from_slice(&json)?),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Synonyms::default()),
+        Err(e) => Err(Error::Index(format!("Failed to read synonyms: {e}"))),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #![allow(clippy::panic)]
@@ -820,6 +1842,7 @@ mod tests {
     #![allow(clippy::unwrap_used)]
     use super::*;
     use crate::HeadingBlock;
+    use crate::embeddings::HashingEmbedder;
     use std::time::Instant;
     use tempfile::TempDir;
 
@@ -858,6 +1881,77 @@ mod tests {
         assert!(index_path.exists());
     }
 
+    #[test]
+    fn test_in_ram_index_search() {
+        let index = SearchIndex::create_in_ram().expect("Should create in-memory index");
+        index
+            .index_blocks("test", &create_test_blocks())
+            .expect("Should index blocks");
+
+        let hits = index
+            .search("useState", Some("test"), 10)
+            .expect("Should search");
+
+        assert!(!hits.is_empty(), "Should find results for useState");
+        assert_eq!(hits[0].source, "test");
+    }
+
+    #[test]
+    fn test_space_usage_grows_with_content() {
+        let index = SearchIndex::create_in_ram().expect("Should create in-memory index");
+        assert_eq!(index.space_usage().expect("Should compute size"), 0);
+
+        index
+            .index_blocks("test", &create_test_blocks())
+            .expect("Should index blocks");
+        assert!(index.space_usage().expect("Should compute size") > 0);
+    }
+
+    #[test]
+    fn test_delta_indexing_touches_only_changed_blocks() {
+        let index = SearchIndex::create_in_ram().expect("Should create in-memory index");
+        let blocks = create_test_blocks();
+
+        let first = index
+            .index_blocks_delta("test", &blocks)
+            .expect("Should index blocks");
+        assert!(first.full_rebuild, "Empty index should be built in full");
+
+        let mut edited = blocks;
+        edited[2].content = "Pages Router is the classic routing system in Next.js.".to_string();
+        let delta = index
+            .index_blocks_delta("test", &edited)
+            .expect("Should apply delta");
+        assert_eq!(
+            delta,
+            IndexDelta {
+                kept: 2,
+                added: 1,
+                removed: 1,
+                full_rebuild: false,
+            }
+        );
+
+        let stale = index
+            .search("\"App Router\"", Some("test"), 10)
+            .expect("Should search");
+        assert!(stale.is_empty(), "Replaced block should be gone");
+        let fresh = index
+            .search("Pages", Some("test"), 10)
+            .expect("Should search");
+        assert_eq!(fresh.len(), 1);
+        assert_eq!(fresh[0].lines, "200-200");
+        let kept = index
+            .search("useState", Some("test"), 10)
+            .expect("Should search");
+        assert_eq!(kept.len(), 1, "Unchanged blocks must not be duplicated");
+
+        let unchanged = index
+            .index_blocks_delta("test", &edited)
+            .expect("Should apply delta");
+        assert_eq!((unchanged.added, unchanged.removed), (0, 0));
+    }
+
     #[test]
     fn test_index_open_nonexistent() {
         let temp_dir = TempDir::new().expect("Failed to create temp dir");
@@ -867,6 +1961,33 @@ mod tests {
         assert!(result.is_err(), "Should fail to open non-existent index");
     }
 
+    #[test]
+    fn test_verify_detects_corrupted_segments() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let index_path = temp_dir.path().join("test_index");
+        let index = SearchIndex::create(&index_path).expect("Should create index");
+        index
+            .index_blocks("test", &create_test_blocks())
+            .expect("Should index blocks");
+        drop(index);
+        assert_eq!(SearchIndex::verify(&index_path).expect("Should verify"), 3);
+
+        let largest = std::fs::read_dir(&index_path)
+            .unwrap()
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.extension()
+                    .is_some_and(|ext| ext != "json" && ext != "lock")
+            })
+            .max_by_key(|path| std::fs::metadata(path).map_or(0, |m| m.len()))
+            .expect("segment file");
+        let mut bytes = std::fs::read(&largest).unwrap();
+        bytes[0] ^= 0xff;
+        std::fs::write(&largest, bytes).unwrap();
+        assert!(SearchIndex::verify(&index_path).is_err());
+    }
+
     #[test]
     fn test_index_and_search_basic() {
         let temp_dir = TempDir::new().expect("Failed to create temp dir");
@@ -894,6 +2015,162 @@ mod tests {
         assert_eq!(hits[0].file, "llms.txt");
     }
 
+    #[test]
+    fn test_cjk_content_is_searchable_by_word() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let index_path = temp_dir.path().join("test_index");
+        let blocks = vec![
+            HeadingBlock::new(
+                vec!["はじめに".to_string()],
+                "blzは全文検索エンジンを使ってドキュメントを検索します。".to_string(),
+                1,
+                3,
+            ),
+            HeadingBlock::new(
+                vec!["設定".to_string()],
+                "設定ファイルはホームディレクトリに保存されます。".to_string(),
+                4,
+                6,
+            ),
+        ];
+
+        SearchIndex::rebuild(&index_path, "test", &blocks, PerformanceMetrics::default())
+            .expect("Should index blocks");
+        let index = SearchIndex::open(&index_path).expect("Should open index");
+        assert_eq!(index.analyzer().tokenizer, crate::TokenizerKind::Ngram);
+
+        for (query, heading) in [
+            ("エンジン", "はじめに"),
+            ("保存", "設定"),
+            ("blz", "はじめに"),
+        ] {
+            let hits = index
+                .search(query, Some("test"), 10)
+                .expect("Should search");
+            assert_eq!(hits.len(), 1, "query {query}");
+            assert_eq!(hits[0].heading_path, [heading], "query {query}");
+        }
+    }
+
+    #[test]
+    fn test_stemming_and_synonyms_widen_matches() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let index_path = temp_dir.path().join("test_index");
+        let blocks = vec![
+            HeadingBlock::new(
+                vec!["Setup".to_string()],
+                "Configure the environment variable before deploying.".to_string(),
+                1,
+                3,
+            ),
+            HeadingBlock::new(
+                vec!["Clusters".to_string()],
+                "Kubernetes clusters are supported.".to_string(),
+                4,
+                6,
+            ),
+        ];
+        let analysis = TextAnalysis {
+            stemmer: Some(tantivy::tokenizer::Language::English),
+            synonyms: Synonyms::new(vec![
+                vec!["env var".to_string(), "environment variable".to_string()],
+                vec!["k8s".to_string(), "kubernetes".to_string()],
+            ]),
+            ..TextAnalysis::default()
+        };
+
+        SearchIndex::rebuild_with(
+            &index_path,
+            "test",
+            &blocks,
+            PerformanceMetrics::default(),
+            &analysis,
+        )
+        .expect("Should index blocks");
+        let index = SearchIndex::open(&index_path).expect("Should open index");
+        assert_eq!(
+            index.analyzer().stemmer,
+            Some(tantivy::tokenizer::Language::English)
+        );
+        assert_eq!(index.synonyms(), &analysis.synonyms);
+
+        for (query, heading) in [
+            ("configuring", "Setup"),
+            ("deploys", "Setup"),
+            ("env var", "Setup"),
+            ("k8s", "Clusters"),
+        ] {
+            let hits = index
+                .search(query, Some("test"), 10)
+                .expect("Should search");
+            assert_eq!(hits.len(), 1, "query {query}");
+            assert_eq!(hits[0].heading_path, [heading], "query {query}");
+        }
+    }
+
+    #[test]
+    fn test_heading_boost_ranks_heading_matches_first() {
+        let blocks = vec![
+            HeadingBlock::new(
+                vec!["Overview".to_string()],
+                "Webhooks notify you of events. Webhooks retry, and webhooks are signed."
+                    .to_string(),
+                1,
+                3,
+            ),
+            HeadingBlock::new(
+                vec!["Webhooks".to_string()],
+                "Events are delivered over HTTPS.".to_string(),
+                4,
+                6,
+            ),
+        ];
+        let index = SearchIndex::create_in_ram().expect("Should create in-memory index");
+        index
+            .index_blocks("test", &blocks)
+            .expect("Should index blocks");
+        let score_of = |hits: &[SearchHit], heading: &str| {
+            hits.iter()
+                .find(|hit| hit.heading_path == [heading])
+                .map(|hit| hit.score)
+                .expect("heading should match")
+        };
+        let before = index
+            .search("webhooks", Some("test"), 10)
+            .expect("Should search");
+
+        let index = index.with_ranking(Ranking {
+            heading_boost: Some(20.0),
+            ..Ranking::default()
+        });
+        let after = index
+            .search("webhooks", Some("test"), 10)
+            .expect("Should search");
+
+        assert_eq!(after[0].heading_path, ["Webhooks"]);
+        assert!(score_of(&after, "Webhooks") > score_of(&before, "Webhooks"));
+        assert!((score_of(&after, "Overview") - score_of(&before, "Overview")).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_search_hits_carry_highlight_spans() {
+        let index = SearchIndex::create_in_ram().expect("Should create in-memory index");
+        index
+            .index_blocks("test", &create_test_blocks())
+            .expect("Should index blocks");
+
+        let hits = index
+            .search("usestate hook", Some("test"), 10)
+            .expect("Should search");
+        let hit = &hits[0];
+        let marked: Vec<&str> = hit
+            .highlights
+            .iter()
+            .map(|span| &hit.snippet[span.start..span.end])
+            .collect();
+        assert_eq!(marked, ["useState", "hook"]);
+    }
+
     #[test]
     fn test_search_limit() {
         let temp_dir = TempDir::new().expect("Failed to create temp dir");
@@ -915,6 +2192,63 @@ mod tests {
         assert!(hits.len() <= 1, "Should respect limit");
     }
 
+    #[test]
+    fn test_explain_term_contributions_sum_to_score() {
+        let index = SearchIndex::create_in_ram().expect("Should create index");
+        index
+            .index_blocks("test", &create_test_blocks())
+            .expect("Should index blocks");
+
+        let explained = index
+            .explain("# hooks useState", Some("test"), 5)
+            .expect("Should explain");
+        let top = explained.first().expect("Should find a hit");
+
+        assert!(
+            top.terms
+                .iter()
+                .any(|term| term.term == "hooks" && term.boost > 1.0),
+            "heading boost should be reported: {:?}",
+            top.terms
+        );
+        let total: f32 = top.terms.iter().map(|term| term.score).sum();
+        assert!((total - top.hit.score).abs() < 1e-3);
+        assert!((top.explanation.value - top.hit.score).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_term_stats_counts_live_documents_under_heading() {
+        let index = SearchIndex::create_in_ram().expect("Should create index");
+        index
+            .index_blocks("test", &create_test_blocks())
+            .expect("Should index blocks");
+        // Re-indexing deletes the previous documents; they must not be counted
+        index
+            .index_blocks("test", &create_test_blocks())
+            .expect("Should re-index blocks");
+
+        let stats = index.term_stats(None, None, 100).expect("Should count");
+        let components = stats
+            .iter()
+            .find(|stat| stat.term == "components")
+            .expect("components is indexed");
+        assert_eq!(components.doc_freq, 2);
+        assert_eq!(components.term_freq, 4);
+        assert!(stats.windows(2).all(|w| w[0].term_freq >= w[1].term_freq));
+
+        let routing = index
+            .term_stats(Some("next.js"), Some("rout"), 10)
+            .expect("Should count under heading");
+        let terms: Vec<&str> = routing.iter().map(|stat| stat.term.as_str()).collect();
+        assert_eq!(terms, ["router", "routing"]);
+
+        let hooks = index
+            .term_stats(Some("React > Hooks"), None, 100)
+            .expect("Should count under heading");
+        assert!(hooks.iter().any(|stat| stat.term == "usestate"));
+        assert!(!hooks.iter().any(|stat| stat.term == "router"));
+    }
+
     #[test]
     fn test_search_includes_anchor() {
         let temp_dir = TempDir::new().expect("Failed to create temp dir");
@@ -942,6 +2276,7 @@ mod tests {
         // Anchor should be derived from the last heading segment
         let expected = SearchIndex::compute_anchor("Reference");
         assert_eq!(hits[0].anchor.clone().unwrap(), expected);
+        assert_eq!(hits[0].section_id.as_deref(), Some(blocks[0].id.as_str()));
     }
 
     #[test]
@@ -967,6 +2302,56 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_fuzzy_search_tolerates_typos() {
+        let index = SearchIndex::create_in_ram().expect("Should create in-memory index");
+        index
+            .index_blocks("test", &create_test_blocks())
+            .expect("Should index blocks");
+
+        let exact = index
+            .search("useStat", Some("test"), 10)
+            .expect("Should search");
+        assert!(exact.is_empty(), "Exact matching should miss the typo");
+
+        let index = index.with_fuzzy(1);
+        let hits = index
+            .search("useStat", Some("test"), 10)
+            .expect("Should search");
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].heading_path, ["React", "Hooks"]);
+
+        let phrase = index
+            .search("\"useStat is\"", Some("test"), 10)
+            .expect("Should search");
+        assert!(phrase.is_empty(), "Phrases should stay exact");
+    }
+
+    #[test]
+    fn test_semantic_search_matches_related_wording() {
+        let index = SearchIndex::create_in_ram().expect("Should create in-memory index");
+        index
+            .index_blocks("test", &create_test_blocks())
+            .expect("Should index blocks");
+        let embedder = HashingEmbedder::default();
+        let vectors = index
+            .build_vectors("test", &embedder, "sha")
+            .expect("Should embed blocks");
+        assert_eq!(vectors.entries.len(), 3);
+
+        let query = "updating stateful values";
+        let lexical = index
+            .search(query, Some("test"), 10)
+            .expect("Should search");
+        assert!(lexical.is_empty(), "Keyword search needs exact terms");
+
+        let hits = index
+            .semantic_search(&vectors, &embedder, query, "test", 2, 200)
+            .expect("Should search semantically");
+        assert_eq!(hits[0].heading_path, ["React", "Hooks"]);
+        assert!(hits[0].score > 0.0 && hits[0].score <= 1.0);
+    }
+
     #[test]
     fn test_search_performance() {
         let temp_dir = TempDir::new().expect("Failed to create temp dir");
@@ -1295,6 +2680,78 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_code_search_matches_code_blocks_by_language() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let index_path = temp_dir.path().join("code_index");
+
+        let index = SearchIndex::create(&index_path).expect("Should create index");
+
+        let blocks = vec![
+            HeadingBlock::new(
+                vec!["Fetching".to_string()],
+                "Call fetch with the request URL to load data.".to_string(),
+                1,
+                4,
+            ),
+            HeadingBlock::new(
+                vec!["TypeScript client".to_string()],
+                "```ts\nconst res = await fetch(url);\n```".to_string(),
+                5,
+                9,
+            ),
+            HeadingBlock::new(
+                vec!["Python client".to_string()],
+                "```python\nres = fetch(url)\n```".to_string(),
+                10,
+                14,
+            ),
+        ];
+        index
+            .index_blocks("test", &blocks)
+            .expect("Should index blocks");
+
+        let headings = |hits: &[SearchHit]| -> Vec<String> {
+            let mut headings: Vec<String> = hits
+                .iter()
+                .map(|hit| hit.heading_path.join(" > "))
+                .collect();
+            headings.sort();
+            headings
+        };
+
+        let code_hits = index
+            .search_code("fetch", Some("test"), None, 10, DEFAULT_SNIPPET_CHAR_LIMIT)
+            .expect("Code search should succeed");
+        assert_eq!(
+            headings(&code_hits),
+            ["Python client", "TypeScript client"],
+            "Prose mentions should be excluded from code search"
+        );
+
+        let ts_hits = index
+            .search_code(
+                "fetch",
+                Some("test"),
+                Some("TS"),
+                10,
+                DEFAULT_SNIPPET_CHAR_LIMIT,
+            )
+            .expect("Language-filtered code search should succeed");
+        assert_eq!(headings(&ts_hits), ["TypeScript client"]);
+        assert!(
+            index
+                .search_code(
+                    "fetch",
+                    Some("test"),
+                    Some(" "),
+                    10,
+                    DEFAULT_SNIPPET_CHAR_LIMIT
+                )
+                .is_err()
+        );
+    }
+
     #[test]
     fn test_heading_path_in_results() {
         let temp_dir = TempDir::new().expect("Failed to create temp dir");
@@ -1427,4 +2884,17 @@ mod tests {
         let char_count = snippet.chars().count();
         assert!(char_count > 0);
     }
+
+    #[test]
+    fn test_snippet_limit_counts_graphemes() {
+        // Family emoji (7 code points) and "e" + combining acute (2 code points)
+        let family = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}\u{200D}\u{1F466}";
+        let content = format!("{family}{family}e\u{301}e\u{301} needle tail text");
+
+        let snippet = SearchIndex::extract_snippet(&content, "zzz", 3);
+        assert_eq!(snippet, format!("{family}{family}e\u{301}..."));
+
+        let around = SearchIndex::extract_snippet(&content, "needle", 10);
+        assert_eq!(around, "...e\u{301} needle t...");
+    }
 }
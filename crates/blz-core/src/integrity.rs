@@ -0,0 +1,291 @@
+//! Expected checksums and minisign signatures for source documents.
+//!
+//! Registry entries, manifests, and `blz add` can declare what a document
+//! must hash to, or which minisign key must have signed it. The declaration
+//! is kept in the source's `settings.toml` (`[fetch.integrity]`), every add
+//! and sync checks the downloaded document against it before anything is
+//! written, and `blz check --verify` re-checks the cached copy.
+//!
+//! A SHA-256 pins one exact version: a sync that finds different content
+//! fails and keeps the cached copy. A minisign key follows a publisher
+//! instead: each version must come with a signature (by default at the
+//! document URL plus `.minisig`) made by that key. The signature accepted
+//! on the last sync is cached next to the document so the cached copy can be
+//! verified offline.
+
+use base64::{Engine as _, engine::general_purpose::STANDARD};
+use minisign_verify::{PublicKey, Signature};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{Error, FetchResult, Fetcher, Result};
+
+/// Suffix appended to a document URL to find its signature.
+pub const SIGNATURE_SUFFIX: &str = ".minisig";
+
+/// What a source document is expected to hash to or be signed by.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SourceIntegrity {
+    /// Expected SHA-256 of the document: hex, as printed by `sha256sum`, or
+    /// base64, as recorded in `blz.lock`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>,
+
+    /// Minisign public key (the `RW...` line of a `.pub` file) that must
+    /// have signed the document.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub minisign_key: Option<String>,
+
+    /// Where the signature is published; defaults to the document URL plus
+    /// [`SIGNATURE_SUFFIX`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature_url: Option<String>,
+}
+
+impl SourceIntegrity {
+    /// Whether nothing is declared.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.sha256.is_none() && self.minisign_key.is_none()
+    }
+
+    /// Check that the declared checksum and key are well-formed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Config`] for a checksum that is neither 64 hex digits
+    /// nor base64 of 32 bytes, a key minisign cannot read, or a signature URL
+    /// without a key.
+    pub fn validate(&self) -> Result<()> {
+        if let Some(sha256) = &self.sha256 {
+            parse_sha256(sha256)?;
+        }
+        if let Some(key) = &self.minisign_key {
+            parse_public_key(key)?;
+        } else if self.signature_url.is_some() {
+            return Err(Error::Config(
+                "A signature URL needs a minisign key to verify it with".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Where the signature for the document at `document_url` is published.
+    #[must_use]
+    pub fn signature_url_for(&self, document_url: &str) -> String {
+        self.signature_url
+            .clone()
+            .unwrap_or_else(|| format!("{document_url}{SIGNATURE_SUFFIX}"))
+    }
+
+    /// Compare `content` against the declared SHA-256, if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Integrity`] when the hashes differ, or
+    /// [`Error::Config`] when the declared checksum is malformed.
+    pub fn check_sha256(&self, content: &[u8]) -> Result<()> {
+        let Some(expected) = &self.sha256 else {
+            return Ok(());
+        };
+        let actual: [u8; 32] = Sha256::digest(content).into();
+        if parse_sha256(expected)? == actual {
+            Ok(())
+        } else {
+            Err(Error::Integrity(format!(
+                "SHA-256 mismatch: expected {}, got {}",
+                expected.trim(),
+                to_hex(&actual)
+            )))
+        }
+    }
+
+    /// Verify `content` against a minisign `signature` made by the declared
+    /// key. Passes when no key is declared.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Integrity`] when the signature is unreadable or was
+    /// not made over `content` by the declared key.
+    pub fn check_signature(&self, content: &[u8], signature: &str) -> Result<()> {
+        let Some(key) = &self.minisign_key else {
+            return Ok(());
+        };
+        let public_key = parse_public_key(key)?;
+        let signature = Signature::decode(signature)
+            .map_err(|err| Error::Integrity(format!("Unreadable minisign signature: {err}")))?;
+        // Legacy (non-prehashed) signatures are still Ed25519 over the content
+        public_key
+            .verify(content, &signature, true)
+            .map_err(|err| Error::Integrity(format!("Signature verification failed: {err}")))
+    }
+
+    /// Check a freshly downloaded document, fetching its signature when a
+    /// key is declared.
+    ///
+    /// Returns the verified signature so it can be cached with the document.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Integrity`] on a checksum or signature mismatch, and
+    /// a network error when the signature cannot be downloaded.
+    pub async fn verify_download(
+        &self,
+        fetcher: &Fetcher,
+        document_url: &str,
+        content: &str,
+    ) -> Result<Option<String>> {
+        self.check_sha256(content.as_bytes())?;
+        if self.minisign_key.is_none() {
+            return Ok(None);
+        }
+        let signature_url = self.signature_url_for(document_url);
+        let signature = match fetcher.fetch_with_cache(&signature_url, None, None).await {
+            Ok(FetchResult::Modified { content, .. }) => content,
+            Ok(FetchResult::NotModified { .. }) => {
+                return Err(Error::Integrity(format!(
+                    "No signature returned by {signature_url}"
+                )));
+            },
+            Err(err) => {
+                return Err(Error::Integrity(format!(
+                    "Failed to download signature {signature_url}: {err}"
+                )));
+            },
+        };
+        self.check_signature(content.as_bytes(), &signature)?;
+        Ok(Some(signature))
+    }
+}
+
+/// Decode a hex or base64 SHA-256 digest.
+fn parse_sha256(value: &str) -> Result<[u8; 32]> {
+    let value = value.trim();
+    let value = value.strip_prefix("sha256:").unwrap_or(value);
+    let bytes = if value.len() == 64 && value.bytes().all(|b| b.is_ascii_hexdigit()) {
+        (0..32)
+            .map(|i| u8::from_str_radix(&value[i * 2..i * 2 + 2], 16))
+            .collect::<std::result::Result<Vec<u8>, _>>()
+            .ok()
+    } else {
+        STANDARD.decode(value).ok()
+    };
+    bytes
+        .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+        .ok_or_else(|| {
+            Error::Config(format!(
+                "Invalid SHA-256 '{value}': expected 64 hex digits or base64"
+            ))
+        })
+}
+
+/// Read a minisign public key from its base64 line or a whole `.pub` file.
+fn parse_public_key(value: &str) -> Result<PublicKey> {
+    let value = value.trim();
+    let parsed = if value.contains('\n') {
+        PublicKey::decode(value)
+    } else {
+        PublicKey::from_base64(value)
+    };
+    parsed.map_err(|err| Error::Config(format!("Invalid minisign public key: {err}")))
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    use std::fmt::Write as _;
+
+    bytes.iter().fold(String::with_capacity(64), |mut out, b| {
+        let _ = write!(out, "{b:02x}");
+        out
+    })
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    const CONTENT: &[u8] = b"# Docs\n";
+    const PUBLIC_KEY: &str = "RWQBAgMEBQYHCAOhB7/zzhC+HXDdGOdLwJln5NYwm6UNXx3chmQSVTG4";
+    const SIGNATURE: &str = "untrusted comment: signature from minisign secret key
+RUQBAgMEBQYHCMjRebVqOUf4eSXBzIEJpv0m+/P6zTcUWnr/LDrff0cxKOw31Q+eZY0Cw28YQeFSbN8Dw2EmbNjhxAQysv983Q4=
+trusted comment: timestamp:1760000000\tfile:llms.txt
+IfV2gfAV4jc/7Ta9OqcIP6InTy21cnW2OBCB1mYC4bOpDjfWOirrWeBKqFiHJ/a5/mMNl36oswXhP086qk7YBg==
+";
+
+    fn hex_digest() -> String {
+        to_hex(&Sha256::digest(CONTENT))
+    }
+
+    #[test]
+    fn checksum_accepts_hex_and_base64() {
+        for sha256 in [
+            hex_digest(),
+            hex_digest().to_uppercase(),
+            format!("sha256:{}", hex_digest()),
+            STANDARD.encode(Sha256::digest(CONTENT)),
+        ] {
+            let integrity = SourceIntegrity {
+                sha256: Some(sha256),
+                ..SourceIntegrity::default()
+            };
+            integrity.validate().unwrap();
+            integrity.check_sha256(CONTENT).unwrap();
+        }
+    }
+
+    #[test]
+    fn checksum_mismatch_is_an_integrity_error() {
+        let integrity = SourceIntegrity {
+            sha256: Some(hex_digest()),
+            ..SourceIntegrity::default()
+        };
+        let err = integrity.check_sha256(b"# Tampered\n").unwrap_err();
+        assert!(matches!(err, Error::Integrity(_)), "{err}");
+    }
+
+    #[test]
+    fn signature_must_cover_the_content() {
+        let integrity = SourceIntegrity {
+            minisign_key: Some(PUBLIC_KEY.to_string()),
+            ..SourceIntegrity::default()
+        };
+        integrity.validate().unwrap();
+        integrity.check_signature(CONTENT, SIGNATURE).unwrap();
+        assert!(matches!(
+            integrity.check_signature(b"# Tampered\n", SIGNATURE),
+            Err(Error::Integrity(_))
+        ));
+        assert!(integrity.check_signature(CONTENT, "garbage").is_err());
+    }
+
+    #[test]
+    fn malformed_declarations_are_rejected() {
+        let bad_sha = SourceIntegrity {
+            sha256: Some("abc123".to_string()),
+            ..SourceIntegrity::default()
+        };
+        assert!(matches!(bad_sha.validate(), Err(Error::Config(_))));
+
+        let url_without_key = SourceIntegrity {
+            signature_url: Some("https://example.com/llms.txt.minisig".to_string()),
+            ..SourceIntegrity::default()
+        };
+        assert!(url_without_key.validate().is_err());
+
+        let bad_key = SourceIntegrity {
+            minisign_key: Some("not-a-key".to_string()),
+            ..SourceIntegrity::default()
+        };
+        assert!(bad_key.validate().is_err());
+    }
+
+    #[test]
+    fn signature_url_defaults_to_minisig_suffix() {
+        let integrity = SourceIntegrity::default();
+        assert_eq!(
+            integrity.signature_url_for("https://example.com/llms.txt"),
+            "https://example.com/llms.txt.minisig"
+        );
+    }
+}
@@ -42,6 +42,7 @@ pub fn build_llms_json(
             filter_non_english: None,
         },
         filter_stats: None,
+        health: None,
         toc: parse_result.toc.clone(),
         files: vec![FileInfo {
             path: file_name.to_string(),
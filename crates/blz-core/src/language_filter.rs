@@ -33,8 +33,29 @@
 //! assert!(!filter.is_english_url("https://docs.example.com/de/guide"));
 //! assert!(!filter.is_english_url("https://ja.docs.example.com/guide"));
 //! ```
+//!
+//! ## Locale selection
+//!
+//! English is kept by default; [`LanguageFilter::with_locales`] keeps any set
+//! of locales instead (`blz add --lang en,de`). A section is assigned a locale
+//! from the locale markers in the URLs it links to, and otherwise from its
+//! heading text, which can only tell English from not-English:
+//!
+//! ```rust
+//! use blz_core::LanguageFilter;
+//!
+//! let filter = LanguageFilter::new(true).with_locales("en,ja".parse().unwrap());
+//! let urls = |url: &str| vec![url.to_string()];
+//! assert!(filter.keeps_section(&["Guide".into()], &urls("https://example.com/ja/guide")));
+//! assert!(!filter.keeps_section(&["Guide".into()], &urls("https://example.com/de/guide")));
+//! assert!(filter.keeps_section(&["Guide".into()], &[]));
+//! ```
 
 use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{Error, Result};
 
 /// Non-English locale codes to filter (ISO 639-1 + variants)
 const NON_ENGLISH_LOCALES: &[&str] = &[
@@ -46,6 +67,9 @@ const NON_ENGLISH_LOCALES: &[&str] = &[
     "zh-cn", "zh-tw", "pt-br", "pt-pt", "es-mx", "es-es",
 ];
 
+// `LocaleSet` keeps one bit per known locale, English included
+const _: () = assert!(NON_ENGLISH_LOCALES.len() < 64);
+
 /// Strong non-English indicator words that rarely appear in English text.
 /// A single match from this list is enough to flag a heading as non-English.
 const STRONG_NON_ENGLISH_INDICATORS: &[&str] = &[
@@ -173,10 +197,123 @@ impl FilterStats {
     }
 }
 
+/// Locales to keep when filtering a multilingual source.
+///
+/// Holds locale codes the filter can detect in URLs (`en`, `de`, `ja`,
+/// `zh-cn`, ...). A base language also keeps its regional variants: `zh`
+/// keeps `/zh-cn/` and `/zh-tw/` pages, while `zh-tw` keeps only those.
+/// English regions (`en-us`, `en-gb`) are always folded into `en`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LocaleSet(u64);
+
+impl LocaleSet {
+    /// English only, the default.
+    pub const ENGLISH: Self = Self(1);
+
+    /// Build a set from locale codes.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Config`] for an empty list or a code the filter
+    /// cannot detect.
+    pub fn from_codes<S: AsRef<str>>(codes: &[S]) -> Result<Self> {
+        let mut bits = 0;
+        for code in codes {
+            let code = normalize_locale(code.as_ref());
+            let index = locale_index(&code).ok_or_else(|| {
+                Error::Config(format!(
+                    "Unknown locale '{code}' (known: en, {})",
+                    NON_ENGLISH_LOCALES.join(", ")
+                ))
+            })?;
+            bits |= 1 << index;
+        }
+        if bits == 0 {
+            return Err(Error::Config("No locales given".to_string()));
+        }
+        Ok(Self(bits))
+    }
+
+    /// Whether content marked with `locale` is kept.
+    #[must_use]
+    pub fn contains(self, locale: &str) -> bool {
+        let locale = normalize_locale(locale);
+        let has = |code: &str| locale_index(code).is_some_and(|i| self.0 & (1 << i) != 0);
+        has(&locale) || locale.split_once('-').is_some_and(|(base, _)| has(base))
+    }
+
+    /// Whether the set is English alone.
+    #[must_use]
+    pub const fn is_english_only(self) -> bool {
+        self.0 == Self::ENGLISH.0
+    }
+
+    /// Locale codes in the set, English first.
+    #[must_use]
+    pub fn codes(self) -> Vec<&'static str> {
+        std::iter::once("en")
+            .chain(NON_ENGLISH_LOCALES.iter().copied())
+            .enumerate()
+            .filter(|(i, _)| self.0 & (1 << i) != 0)
+            .map(|(_, code)| code)
+            .collect()
+    }
+}
+
+impl Default for LocaleSet {
+    fn default() -> Self {
+        Self::ENGLISH
+    }
+}
+
+impl FromStr for LocaleSet {
+    type Err = Error;
+
+    /// Parse a comma-separated list such as `en,de,zh-cn`.
+    fn from_str(list: &str) -> Result<Self> {
+        let codes: Vec<&str> = list
+            .split(',')
+            .map(str::trim)
+            .filter(|code| !code.is_empty())
+            .collect();
+        Self::from_codes(&codes)
+    }
+}
+
+impl fmt::Display for LocaleSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.codes().join(","))
+    }
+}
+
+/// Lowercase a locale code, use `-` between language and region, and fold
+/// English regions into `en`.
+fn normalize_locale(code: &str) -> String {
+    let code = code.trim().to_ascii_lowercase().replace('_', "-");
+    if code.starts_with("en-") {
+        "en".to_string()
+    } else {
+        code
+    }
+}
+
+/// Bit position of a known locale code in a [`LocaleSet`].
+fn locale_index(code: &str) -> Option<usize> {
+    if code == "en" {
+        return Some(0);
+    }
+    NON_ENGLISH_LOCALES
+        .iter()
+        .position(|locale| *locale == code)
+        .map(|i| i + 1)
+}
+
 /// Language filter for URL-based locale detection
 pub struct LanguageFilter {
     /// Whether filtering is enabled
     enabled: bool,
+    /// Locales whose sections are kept
+    locales: LocaleSet,
     /// Custom locales to exclude (in addition to `NON_ENGLISH_LOCALES`)
     custom_excludes: HashSet<String>,
     /// Statistics about filtering operations
@@ -199,11 +336,51 @@ impl LanguageFilter {
     pub fn new(enabled: bool) -> Self {
         Self {
             enabled,
+            locales: LocaleSet::ENGLISH,
             custom_excludes: HashSet::new(),
             stats: FilterStats::default(),
         }
     }
 
+    /// Keep sections in `locales` instead of English only.
+    ///
+    /// Affects [`Self::keeps_section`]; the English checks
+    /// ([`Self::is_english_url`], [`Self::is_english_text`]) are unchanged.
+    #[must_use]
+    pub const fn with_locales(mut self, locales: LocaleSet) -> Self {
+        self.locales = locales;
+        self
+    }
+
+    /// Locales whose sections are kept.
+    #[must_use]
+    pub const fn locales(&self) -> LocaleSet {
+        self.locales
+    }
+
+    /// Whether a section belongs to one of the kept locales.
+    ///
+    /// Every locale marker in the section's links must be kept. A section
+    /// linking to kept non-English pages is a language variant and is kept
+    /// whatever its heading reads like; otherwise the heading path decides:
+    /// English headings are kept when English is, and non-English headings
+    /// when it is not. With the default English-only set this is the
+    /// classic filter: links and headings must both look English.
+    #[must_use]
+    pub fn keeps_section(&self, heading_path: &[String], urls: &[String]) -> bool {
+        if !self.enabled {
+            return true;
+        }
+        let mut variant = false;
+        for locale in urls.iter().filter_map(|url| self.url_locale(url)) {
+            if !self.locales.contains(&locale) {
+                return false;
+            }
+            variant |= !LocaleSet::ENGLISH.contains(&locale);
+        }
+        variant || self.is_english_heading_path(heading_path) == self.locales.contains("en")
+    }
+
     /// Add a custom locale to exclude
     ///
     /// # Arguments
@@ -280,6 +457,52 @@ impl LanguageFilter {
         true
     }
 
+    /// Locale marker in a URL, if any (e.g. `"de"` for `/de/guide` or
+    /// `de.docs.example.com`).
+    ///
+    /// Explicit English markers report their own code (`"en"`, `"en-us"`,
+    /// `"en-gb"`). Unlike [`Self::is_english_url`], this ignores whether
+    /// filtering is enabled and does not update statistics.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use blz_core::LanguageFilter;
+    ///
+    /// let filter = LanguageFilter::new(true);
+    /// assert_eq!(filter.url_locale("https://docs.example.com/ja/guide").as_deref(), Some("ja"));
+    /// assert_eq!(filter.url_locale("https://docs.example.com/en-us/guide").as_deref(), Some("en-us"));
+    /// assert_eq!(filter.url_locale("https://docs.example.com/guide"), None);
+    /// ```
+    pub fn url_locale(&self, url: &str) -> Option<String> {
+        let lower_url = url.to_ascii_lowercase();
+
+        if let Some(english) = ["en", "en-us", "en-gb"]
+            .into_iter()
+            .find(|locale| lower_url.contains(&format!("/{locale}/")))
+        {
+            return Some(english.to_string());
+        }
+
+        if let Some(host) = url::Url::parse(url)
+            .ok()
+            .as_ref()
+            .and_then(url::Url::host_str)
+        {
+            let subdomain = host.split('.').next().unwrap_or("").to_ascii_lowercase();
+            if NON_ENGLISH_LOCALES.contains(&subdomain.as_str())
+                || self.custom_excludes.contains(&subdomain)
+            {
+                return Some(subdomain);
+            }
+        }
+
+        NON_ENGLISH_LOCALES
+            .iter()
+            .map(|locale| (*locale).to_string())
+            .chain(self.custom_excludes.iter().cloned())
+            .find(|locale| lower_url.contains(&format!("/{locale}/")))
+    }
+
     /// Check if URL has a non-English subdomain
     fn has_non_english_subdomain(&self, url: &str) -> bool {
         if let Ok(parsed) = url::Url::parse(url) {
@@ -540,6 +763,39 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_url_locale_detection() {
+        let mut filter = LanguageFilter::new(false);
+        filter.add_custom_exclude("xx");
+
+        assert_eq!(
+            filter
+                .url_locale("https://fr.docs.example.com/guide")
+                .as_deref(),
+            Some("fr")
+        );
+        assert_eq!(
+            filter
+                .url_locale("https://docs.example.com/zh-cn/tutorial")
+                .as_deref(),
+            Some("zh-cn")
+        );
+        assert_eq!(
+            filter
+                .url_locale("https://docs.example.com/en-gb/tutorial")
+                .as_deref(),
+            Some("en-gb")
+        );
+        assert_eq!(
+            filter
+                .url_locale("https://docs.example.com/xx/guide")
+                .as_deref(),
+            Some("xx")
+        );
+        assert_eq!(filter.url_locale("https://docs.example.com/api/auth"), None);
+        assert_eq!(filter.stats().total_processed, 0);
+    }
+
     #[test]
     fn test_english_urls_accepted() {
         let mut filter = LanguageFilter::new(true);
@@ -859,4 +1115,39 @@ mod tests {
         assert!(filter.is_english_text("Troubleshooting Common Issues"));
         assert!(filter.is_english_text("Advanced Configuration"));
     }
+
+    #[test]
+    fn test_locale_set_parsing() {
+        let set: LocaleSet = "EN-us, de ,zh".parse().expect("known locales");
+        assert_eq!(set.codes(), ["en", "de", "zh"]);
+        assert_eq!(set.to_string(), "en,de,zh");
+        assert!(set.contains("en-gb"));
+        assert!(set.contains("zh-tw"));
+        assert!(!set.contains("ja"));
+        assert!(!"zh-tw".parse::<LocaleSet>().expect("known").contains("zh"));
+        assert!(LocaleSet::default().is_english_only());
+        assert!("xx".parse::<LocaleSet>().is_err());
+        assert!(" , ".parse::<LocaleSet>().is_err());
+    }
+
+    #[test]
+    fn test_keeps_section_for_selected_locales() {
+        let urls = |url: &str| vec![url.to_string()];
+        let heading = |text: &str| vec![text.to_string()];
+
+        let english = LanguageFilter::new(true);
+        assert!(english.keeps_section(&heading("Guide"), &urls("https://x.dev/en/guide")));
+        assert!(!english.keeps_section(&heading("Guide"), &urls("https://x.dev/ja/guide")));
+        assert!(!english.keeps_section(&heading("Anleitung"), &[]));
+
+        let german = LanguageFilter::new(true).with_locales("en,de".parse().expect("known"));
+        assert!(german.keeps_section(&heading("Anleitung"), &urls("https://x.dev/de/guide")));
+        assert!(german.keeps_section(&heading("Guide"), &[]));
+        assert!(!german.keeps_section(&heading("Guide"), &urls("https://x.dev/ja/guide")));
+
+        let japanese = LanguageFilter::new(true).with_locales("ja".parse().expect("known"));
+        assert!(japanese.keeps_section(&heading("ドキュメント"), &[]));
+        assert!(!japanese.keeps_section(&heading("Guide"), &[]));
+        assert!(!japanese.keeps_section(&heading("Guide"), &urls("https://x.dev/en/guide")));
+    }
 }
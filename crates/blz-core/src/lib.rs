@@ -55,72 +55,149 @@
 //! # Ok::<(), blz_core::Error>(())
 //! ```
 
+/// Per-source stemming, case, and synonym settings for the search index
+pub mod analysis;
+/// Portable cache bundles for offline transfer
+pub mod bundle;
+/// Language detection for code-heavy search snippets
+pub mod code_language;
+/// Collection sources merged from several llms.txt files
+pub mod collection;
 /// Configuration management for global and per-source settings
 pub mod config;
+/// Content filter pipelines with previewable per-section changes
+pub mod content_filter;
+/// Token-budgeted packing of search hits into cited context documents
+pub mod context_pack;
 /// Documentation source discovery
 pub mod discovery;
+/// Local documentation folder sources
+pub mod docs_dir;
+/// Local embeddings and vector indexes for semantic search
+pub mod embeddings;
 /// Error types and result aliases
 pub mod error;
+/// Score explanations for diagnosing search ranking
+pub mod explain;
 /// HTTP fetching with conditional requests support
 pub mod fetcher;
 /// Firecrawl CLI detection and web scraping integration
 pub mod firecrawl;
+/// Synthetic llms.txt fixtures for tests and benchmarks
+pub mod fixtures;
+/// Link following for index-style llms.txt files
+pub mod follow;
+/// Garbage collection of orphaned cache data
+pub mod gc;
 /// Generation pipeline for creating llms.txt from web scraping
 pub mod generate;
+/// GitHub repository sources with a rate-limit-aware API client
+pub mod github;
 /// Heading sanitization and normalization helpers
 pub mod heading;
 /// Health check types for diagnostics and source health monitoring
 pub mod health;
+/// Match offsets for highlighting search snippets
+pub mod highlight;
+/// HTML pages converted to markdown
+pub mod html;
+/// HTTP cache validators for cache-derived responses
+pub mod http_cache;
 /// Search index implementation using Tantivy
 pub mod index;
+/// Expected checksums and minisign signatures for source documents
+pub mod integrity;
 /// JSON builder helpers for llms.json structures
 pub mod json_builder;
 /// Language filtering for multilingual llms.txt files
 pub mod language_filter;
 /// Anchor remapping utilities between versions
 pub mod mapping;
+/// Conversions from MDX, notebooks, reStructuredText, plain text, and PDF to markdown
+pub mod markup;
+/// Source mirror lists with health-based selection
+pub mod mirrors;
 /// Safe numeric conversion helpers
 pub mod numeric;
+/// `OpenAPI` and Swagger specification sources
+pub mod openapi;
 /// Page cache for scraped web content
 pub mod page_cache;
 /// Tree-sitter based markdown parser
 pub mod parser;
+/// PDF documents converted to markdown
+pub mod pdf;
 /// Application profile detection helpers
 pub mod profile;
 /// Performance profiling utilities
 pub mod profiling;
+/// Persistent cache of search results
+pub mod query_cache;
+/// Structured search query syntax (boolean operators and field prefixes)
+pub mod query_dsl;
+/// Disk budget and eviction of least-used sources
+pub mod quota;
+/// Per-source field boosts and query stopwords
+pub mod ranking;
 /// Refresh helpers shared across CLI and MCP
 pub mod refresh;
 /// Built-in registry of known documentation sources
 pub mod registry;
+/// GitHub repositories indexed from their README and docs folder
+pub mod repo_docs;
+/// Query-time source selection by topic keywords
+pub mod router;
+/// Rust crate documentation sources built from rustdoc JSON
+pub mod rustdoc;
 /// Local filesystem storage for cached documentation
 pub mod storage;
+/// Per-source term frequency statistics
+pub mod terms;
+/// Bandwidth limiting for downloads
+pub mod throttle;
+/// CJK-aware text tokenizers for the search index
+pub mod tokenizer;
 /// Core data types and structures
 pub mod types;
 /// URL resolver for llms.txt variants
 pub mod url_resolver;
 
 // Re-export commonly used types
+pub use analysis::{Analyzer, Synonyms, TextAnalysis};
+pub use collection::CollectionMember;
 pub use config::{
-    Config, DefaultsConfig, FetchConfig, FollowLinks, IndexConfig, PathsConfig, ToolConfig,
-    ToolMeta,
+    BasicAuth, Config, ConfirmPolicy, DefaultsConfig, FetchAuth, FetchConfig, FollowLinks,
+    IndexConfig, OutputProfile, PathsConfig, Secret, ToolConfig, ToolMeta,
 };
+pub use content_filter::{FilterPipeline, FilterRule};
 pub use discovery::{ProbeResult, probe_domain};
+pub use docs_dir::DocsFile;
 pub use error::{Error, Result};
-pub use fetcher::{FetchResult, Fetcher};
+pub use explain::{ExplainedHit, ScoreExplanation, TermContribution};
+pub use fetcher::{FetchResult, Fetcher, NetworkOptions};
 pub use heading::{
     HeadingPathVariants, HeadingSegmentVariants, normalize_text_for_search, path_variants,
     segment_variants,
 };
 pub use health::{
-    CacheInfo, HealthCheck, HealthReport, HealthStatus, SourceHealth, SourceHealthEntry, SourceKind,
+    CacheInfo, HealthCheck, HealthRecord, HealthReport, HealthStatus, SourceHealth,
+    SourceHealthEntry, SourceKind, lint_score,
 };
-pub use index::SearchIndex;
+pub use highlight::MatchSpan;
+pub use index::{IndexDelta, SearchIndex};
+pub use integrity::SourceIntegrity;
 pub use json_builder::build_llms_json;
-pub use language_filter::{FilterStats, LanguageFilter};
+pub use language_filter::{FilterStats, LanguageFilter, LocaleSet};
 pub use mapping::{build_anchors_map, compute_anchor_mappings};
+pub use mirrors::{MirrorHealth, MirrorList};
 pub use parser::{MarkdownParser, ParseResult};
 pub use profiling::{PerformanceMetrics, ResourceMonitor};
+pub use query_cache::{QueryCache, QueryCacheKey};
+pub use ranking::Ranking;
 pub use registry::Registry;
-pub use storage::Storage;
+pub use storage::{
+    ArchiveSnapshot, ContentVersion, LockMode, SourceDiskUsage, SourceLock, Storage, SyncJournal,
+};
+pub use terms::TermStat;
+pub use tokenizer::TokenizerKind;
 pub use types::*;
@@ -1,6 +1,7 @@
-use crate::{AnchorMapping, AnchorsMap, TocEntry};
+use crate::{AnchorMapping, AnchorsMap, ParseResult, TocEntry};
 use chrono::{DateTime, Utc};
-use std::collections::HashMap;
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, VecDeque};
 
 /// Compute anchor remapping between two TOC trees.
 ///
@@ -50,6 +51,122 @@ fn walk_new_list(
     }
 }
 
+/// Fresh persistent ID for a heading block, from its path and content.
+///
+/// Only used the first time a section is seen; afterwards
+/// [`carry_section_ids`] keeps the ID stable while the section survives.
+#[must_use]
+pub fn section_id(path: &[String], content: &str) -> String {
+    use std::fmt::Write as _;
+
+    let mut hasher = Sha256::new();
+    for segment in path {
+        hasher.update(segment.as_bytes());
+        hasher.update([0x1f]);
+    }
+    hasher.update([0]);
+    hasher.update(content.as_bytes());
+    hasher.finalize()[..8]
+        .iter()
+        .fold(String::with_capacity(16), |mut acc, b| {
+            let _ = write!(acc, "{b:02x}");
+            acc
+        })
+}
+
+/// Carry persistent section IDs from the previous outline onto a fresh parse.
+///
+/// Sections are matched by heading path first, then by anchor for sections
+/// that moved or were re-parented. Each previous ID is reused at most once;
+/// unmatched sections keep the fresh ID the parser assigned. Heading blocks
+/// take the ID of the TOC entry that starts on the same line.
+pub fn carry_section_ids(previous: &[TocEntry], result: &mut ParseResult) {
+    let mut old = Vec::new();
+    flatten(previous, &mut old);
+    old.retain(|entry| entry.id.is_some());
+    if old.is_empty() {
+        return;
+    }
+
+    let mut new = Vec::new();
+    flatten(&result.toc, &mut new);
+
+    let mut by_path = HashMap::<&[String], VecDeque<usize>>::new();
+    let mut by_anchor = HashMap::<&str, VecDeque<usize>>::new();
+    for (index, entry) in old.iter().enumerate() {
+        by_path
+            .entry(entry.heading_path.as_slice())
+            .or_default()
+            .push_back(index);
+        if let Some(anchor) = entry.anchor.as_deref() {
+            by_anchor.entry(anchor).or_default().push_back(index);
+        }
+    }
+
+    let mut used = vec![false; old.len()];
+    let mut matched = vec![None; new.len()];
+    for (slot, entry) in matched.iter_mut().zip(&new) {
+        let candidates = by_path.get_mut(entry.heading_path.as_slice());
+        *slot = candidates.and_then(|queue| take_unused(queue, &mut used));
+    }
+    for (slot, entry) in matched.iter_mut().zip(&new) {
+        if slot.is_none() {
+            let candidates = entry.anchor.as_deref().and_then(|a| by_anchor.get_mut(a));
+            *slot = candidates.and_then(|queue| take_unused(queue, &mut used));
+        }
+    }
+
+    let mut carried = matched
+        .into_iter()
+        .map(|slot| slot.and_then(|index| old[index].id.clone()));
+    assign_ids(&mut result.toc, &mut carried);
+
+    let mut by_line = HashMap::new();
+    collect_ids_by_line(&result.toc, &mut by_line);
+    for block in &mut result.heading_blocks {
+        if let Some(id) = by_line.get(&block.start_line) {
+            block.id.clone_from(id);
+        }
+    }
+}
+
+fn flatten<'a>(list: &'a [TocEntry], out: &mut Vec<&'a TocEntry>) {
+    for entry in list {
+        out.push(entry);
+        flatten(&entry.children, out);
+    }
+}
+
+fn take_unused(queue: &mut VecDeque<usize>, used: &mut [bool]) -> Option<usize> {
+    while let Some(index) = queue.pop_front() {
+        if !used[index] {
+            used[index] = true;
+            return Some(index);
+        }
+    }
+    None
+}
+
+/// Overwrite IDs in document order, matching the order of [`flatten`].
+fn assign_ids(list: &mut [TocEntry], carried: &mut impl Iterator<Item = Option<String>>) {
+    for entry in list {
+        if let Some(id) = carried.next().flatten() {
+            entry.id = Some(id);
+        }
+        assign_ids(&mut entry.children, carried);
+    }
+}
+
+fn collect_ids_by_line(list: &[TocEntry], out: &mut HashMap<usize, String>) {
+    for entry in list {
+        let start = entry.lines.split('-').next().and_then(|s| s.parse().ok());
+        if let (Some(start), Some(id)) = (start, entry.id.as_ref()) {
+            out.insert(start, id.clone());
+        }
+        collect_ids_by_line(&entry.children, out);
+    }
+}
+
 /// Convenience to build an `AnchorsMap` with a timestamp.
 #[must_use]
 pub const fn build_anchors_map(mappings: Vec<AnchorMapping>, ts: DateTime<Utc>) -> AnchorsMap {
@@ -127,4 +244,41 @@ bravo
             .expect("mapping for A");
         assert_ne!(m_a.old_lines, m_a.new_lines);
     }
+
+    fn find_id<'a>(list: &'a [TocEntry], name: &str) -> Option<&'a str> {
+        list.iter().find_map(|e| {
+            if e.heading_path.last().map(String::as_str) == Some(name) {
+                e.id.as_deref()
+            } else {
+                find_id(&e.children, name)
+            }
+        })
+    }
+
+    #[test]
+    fn section_ids_survive_edits_and_moves() {
+        let v1 = parse_toc("# Title\n\n## A\nalpha\n\n## B\nbravo\n\n### C\ncharlie\n");
+        let mut v2 = parse_toc(
+            "# Title\n\n## Intro\nnew\n\n## C\ncharlie\n\n## A\nalpha, edited\n\n## B\nbravo\n",
+        );
+        let fresh_a = find_id(&v2.toc, "A").expect("fresh id").to_string();
+        assert_ne!(Some(fresh_a.as_str()), find_id(&v1.toc, "A"));
+
+        carry_section_ids(&v1.toc, &mut v2);
+
+        for name in ["Title", "A", "B", "C"] {
+            assert_eq!(find_id(&v2.toc, name), find_id(&v1.toc, name), "{name}");
+        }
+        let intro = find_id(&v2.toc, "Intro").expect("intro id");
+        assert!(find_id(&v1.toc, "Intro").is_none());
+        assert_ne!(Some(intro), find_id(&v1.toc, "Title"));
+
+        // Blocks share the ID of the TOC entry on the same line
+        let block = v2
+            .heading_blocks
+            .iter()
+            .find(|b| b.path.last().map(String::as_str) == Some("A"))
+            .expect("block A");
+        assert_eq!(Some(block.id.as_str()), find_id(&v1.toc, "A"));
+    }
 }
@@ -0,0 +1,674 @@
+//! Conversions from MDX, Jupyter notebooks, reStructuredText, plain text, and
+//! PDF to markdown.
+//!
+//! React-style docs mix markdown with JSX components (`<Tabs>`, `<Callout>`)
+//! and ESM `import`/`export` statements, and notebooks wrap their prose in
+//! JSON cells. Indexing either as-is buries the text in markup, so local file
+//! and folder sources convert them first: MDX keeps the text inside
+//! components but drops the tags, and notebooks keep markdown cells and code
+//! cells (as fenced blocks) but drop outputs.
+//!
+//! reStructuredText and plain text have no `#` headings for the parser to
+//! split on, so underlined (and overlined) section titles become headings,
+//! levelled in the order their styles first appear, as reST does. A document
+//! with no sections at all gets one heading named after the file so its lines
+//! still have a heading path. PDFs are handled by [`crate::pdf`].
+
+use std::ops::Range;
+use std::path::Path;
+use std::sync::LazyLock;
+
+use regex::Regex;
+use serde_json::Value;
+
+use crate::{Error, Result};
+
+/// Opening, closing, or self-closing JSX component tags and fragments.
+///
+/// SAFETY: Pattern is a compile-time constant that is known to be valid.
+#[allow(clippy::unwrap_used)]
+static JSX_TAG: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"</?[A-Z][\w.]*(?:\s+(?:[^<>"'{}]|"[^"]*"|'[^']*'|\{[^{}]*\})*)?/?>|</?>"#)
+        .unwrap()
+});
+
+/// MDX comments (`{/* ... */}`).
+///
+/// SAFETY: Pattern is a compile-time constant that is known to be valid.
+#[allow(clippy::unwrap_used)]
+static MDX_COMMENT: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\{/\*.*?\*/\}").unwrap());
+
+/// reStructuredText section adornment characters.
+const RST_ADORNMENTS: &str = "=-`:'\"~^_*+#<>";
+
+/// Directives whose content is navigation rather than text.
+const SKIPPED_DIRECTIVES: [&str; 3] = ["toctree", "index", "contents"];
+
+/// Convert a document to markdown based on its file extension.
+///
+/// `.mdx` files are stripped of JSX and ESM, `.ipynb` notebooks are reduced to
+/// their cells, `.html` pages to their main content, `.rst` and `.txt` files
+/// get headings for their sections, and anything else is returned unchanged.
+///
+/// # Errors
+///
+/// Returns an error if a notebook is not valid notebook JSON or a page has
+/// no readable content.
+pub fn to_markdown(path: &Path, content: &str) -> Result<String> {
+    let extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(str::to_ascii_lowercase);
+    match extension.as_deref() {
+        Some("mdx") => Ok(strip_mdx(content)),
+        Some("ipynb") => notebook_to_markdown(content),
+        Some("html" | "htm") => crate::html::to_markdown(content).map(|page| page.markdown),
+        Some("rst" | "rest") => Ok(rst_to_markdown(content, &file_title(path))),
+        Some("txt" | "text") => Ok(text_to_markdown(content, &file_title(path))),
+        _ => Ok(content.to_string()),
+    }
+}
+
+/// Convert a file's raw bytes to markdown: PDFs by extracting their text,
+/// anything else as UTF-8 through [`to_markdown`].
+///
+/// # Errors
+///
+/// Returns an error if a PDF has no extractable text, a text file is not
+/// UTF-8, or the conversion for its extension fails.
+pub fn file_to_markdown(path: &Path, bytes: &[u8]) -> Result<String> {
+    if crate::pdf::is_pdf(bytes) {
+        return crate::pdf::to_markdown(bytes, &file_title(path));
+    }
+    let content = std::str::from_utf8(bytes).map_err(|_| {
+        Error::Parse(format!(
+            "{} is neither UTF-8 text nor a PDF",
+            path.display()
+        ))
+    })?;
+    to_markdown(path, content)
+}
+
+/// Heading for a document without sections: its file name without extension.
+fn file_title(path: &Path) -> String {
+    path.file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or("Document")
+        .to_string()
+}
+
+/// Convert reStructuredText to markdown.
+///
+/// Section titles become headings, `code-block` directives and `::` literal
+/// blocks become fenced code, other directives keep their content under a
+/// bold label, and comments, link targets, and navigation directives are
+/// dropped. Inline markup is left as is; it reads fine as text.
+#[must_use]
+pub fn rst_to_markdown(content: &str, title: &str) -> String {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut styles = Vec::new();
+    let mut out = String::with_capacity(content.len());
+    let mut idx = 0;
+    while idx < lines.len() {
+        if let Some(section) = section_title(&lines, idx) {
+            push_heading(&mut out, &mut styles, section);
+            idx += section.lines;
+            continue;
+        }
+
+        let line = lines[idx].trim_end();
+        if line == ".." || line.starts_with(".. ") {
+            let (body, next) = indented_block(&lines, idx + 1);
+            match directive(line) {
+                Some((name, _)) if SKIPPED_DIRECTIVES.contains(&name) => {},
+                Some(("code-block" | "code" | "sourcecode", language)) => {
+                    push_fence(&mut out, language, skip_options(&body));
+                },
+                Some((name, argument)) => {
+                    let mut label = name.to_string();
+                    label[..1].make_ascii_uppercase();
+                    push_line(&mut out, "");
+                    push_line(&mut out, format!("**{label}:** {argument}").trim_end());
+                    push_line(&mut out, "");
+                    for body_line in skip_options(&body) {
+                        push_line(&mut out, body_line);
+                    }
+                    push_line(&mut out, "");
+                },
+                // Comments, link targets, and substitution definitions
+                None => {},
+            }
+            idx = next.max(idx + 1);
+            continue;
+        }
+
+        if let Some(text) = line.strip_suffix("::") {
+            let (body, next) = indented_block(&lines, idx + 1);
+            if !body.is_empty() {
+                // `Example::` keeps one colon; `Example ::` and a bare `::` keep none
+                if text.ends_with(char::is_whitespace) || text.is_empty() {
+                    push_line(&mut out, text.trim_end());
+                } else {
+                    push_line(&mut out, &format!("{text}:"));
+                }
+                push_fence(&mut out, "", &body);
+                idx = next;
+                continue;
+            }
+        }
+
+        push_line(&mut out, line);
+        idx += 1;
+    }
+    with_title(&collapse_blank_lines(&out), title)
+}
+
+/// Give plain text headings for its underlined section titles.
+///
+/// Text that already has `#` headings (an llms.txt saved as `.txt`, say) is
+/// markdown and is returned unchanged.
+#[must_use]
+pub fn text_to_markdown(content: &str, title: &str) -> String {
+    if content.lines().any(is_atx_heading) {
+        return content.to_string();
+    }
+    let lines: Vec<&str> = content.lines().collect();
+    let mut styles = Vec::new();
+    let mut out = String::with_capacity(content.len());
+    let mut idx = 0;
+    while idx < lines.len() {
+        if let Some(section) = section_title(&lines, idx) {
+            push_heading(&mut out, &mut styles, section);
+            idx += section.lines;
+        } else {
+            push_line(&mut out, lines[idx].trim_end());
+            idx += 1;
+        }
+    }
+    with_title(&collapse_blank_lines(&out), title)
+}
+
+/// An underlined or overlined section title.
+#[derive(Clone, Copy)]
+struct Section<'a> {
+    /// Adornment character and whether the title is overlined too.
+    style: (char, bool),
+    title: &'a str,
+    /// Lines the title and its adornment take up.
+    lines: usize,
+}
+
+fn section_title<'a>(lines: &[&'a str], idx: usize) -> Option<Section<'a>> {
+    let line = lines[idx];
+    if let Some(ch) = adornment(line) {
+        let title = lines.get(idx + 1)?.trim();
+        let under = lines.get(idx + 2)?;
+        return (adornment(under) == Some(ch) && !title.is_empty() && adornment(title).is_none())
+            .then_some(Section {
+                style: (ch, true),
+                title,
+                lines: 3,
+            });
+    }
+    if line.trim().is_empty() || line.starts_with([' ', '\t']) {
+        return None;
+    }
+    let under = lines.get(idx + 1)?;
+    let ch = adornment(under)?;
+    (under.trim_end().chars().count() >= line.trim_end().chars().count()).then_some(Section {
+        style: (ch, false),
+        title: line.trim(),
+        lines: 2,
+    })
+}
+
+/// The character a line of at least three repeated adornment characters is made of.
+fn adornment(line: &str) -> Option<char> {
+    let line = line.trim_end();
+    let ch = line
+        .chars()
+        .next()
+        .filter(|ch| RST_ADORNMENTS.contains(*ch))?;
+    (line.chars().count() >= 3 && line.chars().all(|c| c == ch)).then_some(ch)
+}
+
+fn push_heading(out: &mut String, styles: &mut Vec<(char, bool)>, section: Section<'_>) {
+    let level = styles
+        .iter()
+        .position(|style| *style == section.style)
+        .unwrap_or_else(|| {
+            styles.push(section.style);
+            styles.len() - 1
+        });
+    push_line(out, "");
+    push_line(
+        out,
+        &format!("{} {}", "#".repeat((level + 1).min(6)), section.title),
+    );
+    push_line(out, "");
+}
+
+fn is_atx_heading(line: &str) -> bool {
+    let hashes = line.chars().take_while(|ch| *ch == '#').count();
+    (1..=6).contains(&hashes) && line[hashes..].starts_with(' ')
+}
+
+/// Prepend `# title` to a document that has no headings.
+fn with_title(markdown: &str, title: &str) -> String {
+    if markdown.lines().any(is_atx_heading) {
+        markdown.to_string()
+    } else {
+        format!("# {title}\n\n{markdown}")
+    }
+}
+
+/// Name and argument of a `.. name:: argument` directive.
+fn directive(line: &str) -> Option<(&str, &str)> {
+    let (name, argument) = line.strip_prefix(".. ")?.split_once("::")?;
+    let name = name.trim();
+    let is_name = !name.is_empty()
+        && name
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_' | ':'));
+    is_name.then(|| (name, argument.trim()))
+}
+
+/// The indented block after line `start - 1`, dedented, and the index of the
+/// first line after it. Empty when the next text line is not indented.
+fn indented_block<'a>(lines: &[&'a str], start: usize) -> (Vec<&'a str>, usize) {
+    let mut end = start;
+    let mut next = start;
+    while let Some(line) = lines.get(end) {
+        if !line.trim().is_empty() {
+            if !line.starts_with([' ', '\t']) {
+                break;
+            }
+            next = end + 1;
+        }
+        end += 1;
+    }
+    let block = &lines[start..next];
+    let indent = block
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    let body = block
+        .iter()
+        .map(|line| dedent(line.trim_end(), indent))
+        .skip_while(|line| line.is_empty())
+        .collect();
+    (body, next)
+}
+
+/// Directive body without its leading `:option: value` lines.
+fn skip_options<'a>(body: &'a [&'a str]) -> &'a [&'a str] {
+    let options = body
+        .iter()
+        .take_while(|line| {
+            line.strip_prefix(':')
+                .and_then(|rest| rest.split_once(':'))
+                .is_some_and(|(name, _)| !name.is_empty() && !name.contains(' '))
+        })
+        .count();
+    let blanks = body[options..]
+        .iter()
+        .take_while(|line| line.is_empty())
+        .count();
+    &body[options + blanks..]
+}
+
+fn push_fence(out: &mut String, language: &str, body: &[&str]) {
+    push_line(out, "");
+    push_line(out, &format!("```{language}"));
+    for line in body {
+        push_line(out, line);
+    }
+    push_line(out, "```");
+    push_line(out, "");
+}
+
+/// Strip JSX components, MDX comments, and ESM statements from MDX.
+///
+/// Text inside components is kept and dedented so nested content is not
+/// mistaken for an indented code block. Fenced code is left untouched apart
+/// from that dedent.
+#[must_use]
+pub fn strip_mdx(content: &str) -> String {
+    let mut out = String::with_capacity(content.len());
+    let mut fence: Option<(String, usize)> = None;
+    let mut in_esm = false;
+    let mut in_tag = false;
+    let mut depth = 0usize;
+
+    for line in content.lines() {
+        let trimmed = line.trim_start();
+        let indent = line.len() - trimmed.len();
+
+        if let Some((marker, fence_indent)) = &fence {
+            let closes = trimmed.starts_with(marker.as_str());
+            push_line(&mut out, dedent(line, *fence_indent));
+            if closes {
+                fence = None;
+            }
+            continue;
+        }
+        if let Some(marker) = fence_marker(trimmed) {
+            fence = Some((marker, if depth > 0 { indent } else { 0 }));
+            push_line(&mut out, if depth > 0 { trimmed } else { line });
+            continue;
+        }
+
+        // ESM blocks run until the next blank line
+        if in_esm
+            || (depth == 0 && (trimmed.starts_with("import ") || trimmed.starts_with("export ")))
+        {
+            in_esm = !trimmed.is_empty();
+            continue;
+        }
+
+        // The rest of a tag whose attributes span several lines
+        let mut rest = trimmed;
+        if in_tag {
+            let Some(end) = rest.find('>') else {
+                continue;
+            };
+            if !rest[..end].ends_with('/') {
+                depth += 1;
+            }
+            in_tag = false;
+            rest = &rest[end + 1..];
+        }
+
+        let without_comments = MDX_COMMENT.replace_all(rest, "");
+        let spans = code_spans(&without_comments);
+        let mut text = String::with_capacity(without_comments.len());
+        let mut last = 0;
+        for tag in JSX_TAG.find_iter(&without_comments) {
+            if spans.iter().any(|span| span.contains(&tag.start())) {
+                continue;
+            }
+            text.push_str(&without_comments[last..tag.start()]);
+            last = tag.end();
+            let tag = tag.as_str();
+            if tag.starts_with("</") {
+                depth = depth.saturating_sub(1);
+            } else if !tag.ends_with("/>") {
+                depth += 1;
+            }
+        }
+        let tail = &without_comments[last..];
+        if is_open_tag_start(tail) {
+            in_tag = true;
+        } else {
+            text.push_str(tail);
+        }
+
+        let had_markup = text.len() != rest.len() || in_tag;
+        if had_markup && text.trim().is_empty() {
+            continue;
+        }
+        let keep_indent = depth == 0 && !had_markup && !in_tag;
+        push_line(&mut out, if keep_indent { line } else { text.trim_start() });
+    }
+
+    collapse_blank_lines(&out)
+}
+
+/// Byte ranges of inline code spans, where tags are literal text.
+fn code_spans(line: &str) -> Vec<Range<usize>> {
+    let bytes = line.as_bytes();
+    let run_at = |start: usize| bytes[start..].iter().take_while(|b| **b == b'`').count();
+    let mut spans = Vec::new();
+    let mut idx = 0;
+    while idx < bytes.len() {
+        if bytes[idx] != b'`' {
+            idx += 1;
+            continue;
+        }
+        let open = run_at(idx);
+        let mut end = idx + open;
+        let close = loop {
+            match bytes[end..].iter().position(|b| *b == b'`') {
+                Some(offset) => {
+                    let at = end + offset;
+                    let len = run_at(at);
+                    if len == open {
+                        break Some(at + len);
+                    }
+                    end = at + len;
+                },
+                None => break None,
+            }
+        };
+        match close {
+            Some(close) => {
+                spans.push(idx..close);
+                idx = close;
+            },
+            None => idx += open,
+        }
+    }
+    spans
+}
+
+fn fence_marker(line: &str) -> Option<String> {
+    let ch = line.chars().next().filter(|ch| *ch == '`' || *ch == '~')?;
+    let len = line.chars().take_while(|c| *c == ch).count();
+    (len >= 3).then(|| ch.to_string().repeat(len))
+}
+
+/// Whether `text` starts a component tag that continues on later lines.
+fn is_open_tag_start(text: &str) -> bool {
+    let trimmed = text.trim_start();
+    trimmed
+        .strip_prefix('<')
+        .and_then(|rest| rest.chars().next())
+        .is_some_and(|ch| ch.is_ascii_uppercase())
+        && !trimmed.contains('>')
+}
+
+fn dedent(line: &str, indent: usize) -> &str {
+    let strip = line
+        .char_indices()
+        .take(indent)
+        .take_while(|(_, ch)| *ch == ' ' || *ch == '\t')
+        .count();
+    &line[strip..]
+}
+
+fn push_line(out: &mut String, line: &str) {
+    out.push_str(line);
+    out.push('\n');
+}
+
+/// Drop leading blank lines and squeeze runs of blank lines left by removed markup.
+fn collapse_blank_lines(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut blank_run = true;
+    for line in text.lines() {
+        let blank = line.trim().is_empty();
+        if blank && blank_run {
+            continue;
+        }
+        blank_run = blank;
+        push_line(&mut out, line);
+    }
+    out.truncate(out.trim_end().len());
+    out.push('\n');
+    out
+}
+
+/// Extract the markdown and code cells of a Jupyter notebook.
+///
+/// Markdown cells are copied as-is and code cells become fenced blocks tagged
+/// with the notebook language; outputs and raw cells are dropped.
+///
+/// # Errors
+///
+/// Returns an error if the content is not notebook JSON with a `cells` array.
+pub fn notebook_to_markdown(content: &str) -> Result<String> {
+    let notebook: Value = serde_json::from_str(content)
+        .map_err(|err| Error::Parse(format!("invalid notebook JSON: {err}")))?;
+    let cells = notebook
+        .get("cells")
+        .and_then(Value::as_array)
+        .ok_or_else(|| Error::Parse("notebook has no cells".to_string()))?;
+    let language = notebook
+        .pointer("/metadata/kernelspec/language")
+        .or_else(|| notebook.pointer("/metadata/language_info/name"))
+        .and_then(Value::as_str)
+        .unwrap_or("python");
+
+    let mut blocks = Vec::new();
+    for cell in cells {
+        let source = cell_source(cell);
+        if source.trim().is_empty() {
+            continue;
+        }
+        match cell.get("cell_type").and_then(Value::as_str) {
+            Some("markdown") => blocks.push(source.trim_end().to_string()),
+            Some("code") => blocks.push(format!("```{language}\n{}\n```", source.trim_end())),
+            _ => {},
+        }
+    }
+    let mut markdown = blocks.join("\n\n");
+    markdown.push('\n');
+    Ok(markdown)
+}
+
+/// Cell source, stored either as one string or as a list of lines.
+fn cell_source(cell: &Value) -> String {
+    match cell.get("source") {
+        Some(Value::String(source)) => source.clone(),
+        Some(Value::Array(lines)) => lines.iter().filter_map(Value::as_str).collect(),
+        _ => String::new(),
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strips_jsx_and_esm_from_mdx() {
+        let mdx = r#"import { Tabs, Tab } from '@/components/tabs'
+export const meta = {
+  title: 'Install',
+}
+
+# Install
+
+{/* internal note */}
+<Callout type="warning">Requires Node 18.</Callout>
+
+<Tabs>
+  <Tab
+    label="npm"
+  >
+    Run the installer:
+
+    ```bash
+    npm install <pkg>
+    ```
+  </Tab>
+</Tabs>
+
+<Image src="/diagram.png" />
+
+Keep <kbd>Ctrl</kbd> and `<Tabs>` in code.
+"#;
+        let markdown = strip_mdx(mdx);
+        assert_eq!(
+            markdown,
+            "# Install\n\nRequires Node 18.\n\nRun the installer:\n\n```bash\nnpm install <pkg>\n```\n\nKeep <kbd>Ctrl</kbd> and `<Tabs>` in code.\n"
+        );
+    }
+
+    #[test]
+    fn extracts_notebook_cells() {
+        let notebook = r##"{
+            "metadata": {"kernelspec": {"language": "python"}},
+            "cells": [
+                {"cell_type": "markdown", "source": ["# Guide\n", "\n", "Load the data."]},
+                {"cell_type": "code", "source": "df = load()", "outputs": [{"text": "noise"}]},
+                {"cell_type": "raw", "source": "skip me"},
+                {"cell_type": "markdown", "source": []}
+            ]
+        }"##;
+        let markdown = notebook_to_markdown(notebook).unwrap();
+        assert_eq!(
+            markdown,
+            "# Guide\n\nLoad the data.\n\n```python\ndf = load()\n```\n"
+        );
+        assert!(notebook_to_markdown("{}").is_err());
+    }
+
+    #[test]
+    fn converts_rst_sections_directives_and_literals() {
+        let rst = "\
+=======
+Widgets
+=======
+
+.. _install:
+
+Install
+-------
+
+Run the installer::
+
+    widgets install --all
+
+.. code-block:: python
+   :linenos:
+
+   import widgets
+
+.. note:: Requires Python 3.9.
+
+   Older versions are untested.
+
+.. toctree::
+
+   api
+
+Usage
+-----
+
+Call ``widgets.run()``.
+";
+        assert_eq!(
+            rst_to_markdown(rst, "index"),
+            "# Widgets\n\n## Install\n\nRun the installer:\n\n```\nwidgets install --all\n```\n\n\
+             ```python\nimport widgets\n```\n\n**Note:** Requires Python 3.9.\n\n\
+             Older versions are untested.\n\n## Usage\n\nCall ``widgets.run()``.\n"
+        );
+    }
+
+    #[test]
+    fn titles_plain_text_without_sections() {
+        assert_eq!(
+            text_to_markdown("Just some notes.\n", "notes"),
+            "# notes\n\nJust some notes.\n"
+        );
+        assert_eq!(
+            text_to_markdown("Intro\n=====\n\nHello.\n", "notes"),
+            "# Intro\n\nHello.\n"
+        );
+        let markdown = "# Title\n\nAlready markdown.\n";
+        assert_eq!(text_to_markdown(markdown, "llms"), markdown);
+    }
+
+    #[test]
+    fn converts_by_extension() {
+        let path = Path::new("guide.MDX");
+        assert_eq!(to_markdown(path, "<Note>Hi</Note>\n").unwrap(), "Hi\n");
+        assert_eq!(
+            to_markdown(Path::new("guide.md"), "<Note>Hi</Note>\n").unwrap(),
+            "<Note>Hi</Note>\n"
+        );
+    }
+}
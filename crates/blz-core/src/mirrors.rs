@@ -0,0 +1,198 @@
+//! Mirror lists and health tracking for sources hosted on flaky CDNs.
+//!
+//! A source may list alternate URLs that serve the same document. Each
+//! fetch attempt records latency or failure per URL, and refreshes try the
+//! healthiest candidate first so a slow or failing host stops being the
+//! default without any manual intervention.
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// How long a failure keeps a mirror behind its healthy peers.
+pub const FAILURE_COOLDOWN: Duration = Duration::from_secs(60 * 60);
+
+/// Observed health of a single mirror URL.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MirrorHealth {
+    /// Smoothed response latency in milliseconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<u64>,
+    /// Time of the most recent successful fetch.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_success: Option<DateTime<Utc>>,
+    /// Time of the most recent failed fetch.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_failure: Option<DateTime<Utc>>,
+    /// Failures since the last success.
+    #[serde(default)]
+    pub consecutive_failures: u32,
+}
+
+impl MirrorHealth {
+    /// Whether the mirror failed recently enough to be ranked down.
+    #[must_use]
+    pub fn is_cooling_down(&self, now: DateTime<Utc>) -> bool {
+        if self.consecutive_failures == 0 {
+            return false;
+        }
+        let cooldown = chrono::Duration::from_std(FAILURE_COOLDOWN).unwrap_or_default();
+        self.last_failure
+            .is_some_and(|failed_at| now.signed_duration_since(failed_at) < cooldown)
+    }
+}
+
+/// Mirror URLs configured for a source, with per-URL health.
+///
+/// Persisted next to the source metadata as `mirrors.json`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MirrorList {
+    /// Alternate URLs serving the same document as the source URL.
+    #[serde(default)]
+    pub urls: Vec<String>,
+    /// Health observations keyed by URL (including the primary URL).
+    #[serde(default)]
+    pub health: BTreeMap<String, MirrorHealth>,
+}
+
+impl MirrorList {
+    /// Create a mirror list with no recorded health.
+    #[must_use]
+    pub fn new(urls: Vec<String>) -> Self {
+        let mut deduped = Vec::with_capacity(urls.len());
+        for url in urls {
+            if !deduped.contains(&url) {
+                deduped.push(url);
+            }
+        }
+        Self {
+            urls: deduped,
+            health: BTreeMap::new(),
+        }
+    }
+
+    /// Candidate URLs for a fetch: the primary URL followed by each mirror.
+    #[must_use]
+    pub fn candidates(&self, primary: &str) -> Vec<String> {
+        let mut candidates = vec![primary.to_string()];
+        for url in &self.urls {
+            if !candidates.contains(url) {
+                candidates.push(url.clone());
+            }
+        }
+        candidates
+    }
+
+    /// Order candidates healthiest first.
+    ///
+    /// Mirrors still cooling down from a failure go last; the rest are
+    /// ordered by smoothed latency, with unmeasured mirrors after measured
+    /// ones. Ties keep the configured order, so the primary URL wins until
+    /// there is evidence against it.
+    #[must_use]
+    pub fn rank(&self, candidates: &[String], now: DateTime<Utc>) -> Vec<String> {
+        let mut ranked: Vec<(usize, &String)> = candidates.iter().enumerate().collect();
+        ranked.sort_by_key(|(position, url)| {
+            let health = self.health.get(*url);
+            let cooling = health.is_some_and(|h| h.is_cooling_down(now));
+            let latency = health.and_then(|h| h.latency_ms).unwrap_or(u64::MAX);
+            (cooling, latency, *position)
+        });
+        ranked.into_iter().map(|(_, url)| url.clone()).collect()
+    }
+
+    /// Record a successful fetch and fold its latency into the average.
+    pub fn record_success(&mut self, url: &str, latency: Duration, at: DateTime<Utc>) {
+        let sample = u64::try_from(latency.as_millis()).unwrap_or(u64::MAX);
+        let health = self.health.entry(url.to_string()).or_default();
+        health.latency_ms = Some(health.latency_ms.map_or(sample, |previous| {
+            previous.saturating_mul(3).saturating_add(sample) / 4
+        }));
+        health.last_success = Some(at);
+        health.consecutive_failures = 0;
+    }
+
+    /// Record a failed fetch.
+    pub fn record_failure(&mut self, url: &str, at: DateTime<Utc>) {
+        let health = self.health.entry(url.to_string()).or_default();
+        health.last_failure = Some(at);
+        health.consecutive_failures = health.consecutive_failures.saturating_add(1);
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    const PRIMARY: &str = "https://cdn-a.example.com/llms.txt";
+    const MIRROR: &str = "https://cdn-b.example.com/llms.txt";
+
+    fn list() -> MirrorList {
+        MirrorList::new(vec![MIRROR.to_string(), MIRROR.to_string()])
+    }
+
+    #[test]
+    fn candidates_put_primary_first_without_duplicates() {
+        let mirrors = MirrorList::new(vec![PRIMARY.to_string(), MIRROR.to_string()]);
+        assert_eq!(mirrors.candidates(PRIMARY), vec![PRIMARY, MIRROR]);
+        assert_eq!(list().urls, vec![MIRROR]);
+    }
+
+    #[test]
+    fn rank_keeps_configured_order_without_observations() {
+        let mirrors = list();
+        let candidates = mirrors.candidates(PRIMARY);
+        assert_eq!(mirrors.rank(&candidates, Utc::now()), vec![PRIMARY, MIRROR]);
+    }
+
+    #[test]
+    fn rank_prefers_lower_latency() {
+        let mut mirrors = list();
+        let now = Utc::now();
+        mirrors.record_success(PRIMARY, Duration::from_millis(900), now);
+        mirrors.record_success(MIRROR, Duration::from_millis(50), now);
+        let candidates = mirrors.candidates(PRIMARY);
+        assert_eq!(mirrors.rank(&candidates, now), vec![MIRROR, PRIMARY]);
+    }
+
+    #[test]
+    fn recent_failure_ranks_last_until_cooldown_expires() {
+        let mut mirrors = list();
+        let now = Utc::now();
+        mirrors.record_success(PRIMARY, Duration::from_millis(10), now);
+        mirrors.record_failure(PRIMARY, now);
+        let candidates = mirrors.candidates(PRIMARY);
+        assert_eq!(mirrors.rank(&candidates, now), vec![MIRROR, PRIMARY]);
+
+        let later = now + chrono::Duration::hours(2);
+        assert_eq!(mirrors.rank(&candidates, later), vec![PRIMARY, MIRROR]);
+    }
+
+    #[test]
+    fn success_resets_failures_and_smooths_latency() {
+        let mut mirrors = list();
+        let now = Utc::now();
+        mirrors.record_failure(MIRROR, now);
+        mirrors.record_success(MIRROR, Duration::from_millis(100), now);
+        mirrors.record_success(MIRROR, Duration::from_millis(500), now);
+
+        let health = mirrors.health.get(MIRROR).unwrap();
+        assert_eq!(health.consecutive_failures, 0);
+        assert_eq!(health.latency_ms, Some(200));
+        assert!(!health.is_cooling_down(now));
+    }
+
+    #[test]
+    fn round_trips_through_json() {
+        let mut mirrors = list();
+        mirrors.record_success(MIRROR, Duration::from_millis(42), Utc::now());
+        let json = serde_json::to_string(&mirrors).unwrap();
+        let back: MirrorList = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, mirrors);
+    }
+}
@@ -0,0 +1,859 @@
+//! `OpenAPI` and Swagger specification sources.
+//!
+//! `blz add api ./openapi.yaml --type openapi` renders a spec as an llms-style
+//! markdown document: operations grouped under one `##` section per tag, a
+//! `###` section per operation (`GET /pets/{petId}`), and a `## Schemas`
+//! section with one `### schema Name` entry per component schema. Headings are
+//! built only from methods, paths, and schema names, so their anchors stay
+//! stable when descriptions change. Sync reloads the spec and re-indexes only
+//! when the rendered document changed.
+
+use std::fmt::Write as _;
+
+use serde_json::Value;
+
+use crate::fetcher::calculate_sha256;
+use crate::refresh::{
+    ApplyRefreshParams, RefreshContext, RefreshIndexer, RefreshOutcome, RefreshPayload,
+    RefreshStorage, apply_refresh,
+};
+use crate::rustdoc::demote_doc_headings;
+use crate::{Error, Fetcher, PerformanceMetrics, Result, SourceType};
+
+/// HTTP methods in the order operations are rendered.
+const METHODS: [&str; 8] = [
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+/// Section heading for operations without tags.
+const UNTAGGED: &str = "Operations";
+
+/// Maximum `$ref` hops followed when resolving parameters and responses.
+const MAX_REF_DEPTH: usize = 8;
+
+/// Whether a spec location is fetched over HTTP rather than read from disk.
+#[must_use]
+pub fn is_remote(location: &str) -> bool {
+    location.starts_with("http://") || location.starts_with("https://")
+}
+
+/// Parse an `OpenAPI` 3.x or Swagger 2.0 document from JSON or YAML.
+///
+/// # Errors
+///
+/// Returns an error if the text is neither valid JSON nor YAML, or if it lacks
+/// the top-level `openapi`/`swagger` version field.
+pub fn parse_spec(text: &str) -> Result<Value> {
+    let spec = if text.trim_start().starts_with('{') {
+        serde_json::from_str(text)
+            .map_err(|err| Error::Parse(format!("invalid OpenAPI JSON: {err}")))?
+    } else {
+        let mut yaml: serde_yaml_ng::Value = serde_yaml_ng::from_str(text)
+            .map_err(|err| Error::Parse(format!("invalid OpenAPI YAML: {err}")))?;
+        yaml.apply_merge()
+            .map_err(|err| Error::Parse(format!("invalid OpenAPI YAML: {err}")))?;
+        yaml_to_json(yaml)
+    };
+    if spec.get("openapi").is_none() && spec.get("swagger").is_none() {
+        return Err(Error::Parse(
+            "not an OpenAPI spec (missing `openapi` or `swagger` version field)".to_string(),
+        ));
+    }
+    Ok(spec)
+}
+
+/// Whether fetched or local text is an `OpenAPI` or Swagger spec.
+///
+/// Used to recognize specs added without `--type openapi`. A cheap scan for
+/// the version key runs first so markdown never goes through the YAML parser,
+/// and the required `info` object guards against prose that happens to parse
+/// as YAML.
+#[must_use]
+pub fn looks_like_spec(text: &str) -> bool {
+    let has_version_key = if text.trim_start().starts_with('{') {
+        text.contains("\"openapi\"") || text.contains("\"swagger\"")
+    } else {
+        text.lines()
+            .any(|line| line.starts_with("openapi:") || line.starts_with("swagger:"))
+    };
+    has_version_key
+        && parse_spec(text).is_ok_and(|spec| spec.get("info").is_some_and(Value::is_object))
+}
+
+/// Convert YAML into JSON, stringifying non-string mapping keys such as
+/// unquoted status codes (`200:`).
+fn yaml_to_json(yaml: serde_yaml_ng::Value) -> Value {
+    use serde_yaml_ng::Value as Yaml;
+
+    match yaml {
+        Yaml::Null => Value::Null,
+        Yaml::Bool(value) => Value::Bool(value),
+        Yaml::Number(number) => number
+            .as_i64()
+            .map(Value::from)
+            .or_else(|| number.as_u64().map(Value::from))
+            .or_else(|| number.as_f64().map(Value::from))
+            .unwrap_or(Value::Null),
+        Yaml::String(value) => Value::String(value),
+        Yaml::Sequence(items) => Value::Array(items.into_iter().map(yaml_to_json).collect()),
+        Yaml::Mapping(mapping) => Value::Object(
+            mapping
+                .into_iter()
+                .map(|(key, value)| (yaml_key(key), yaml_to_json(value)))
+                .collect(),
+        ),
+        Yaml::Tagged(tagged) => yaml_to_json(tagged.value),
+    }
+}
+
+fn yaml_key(key: serde_yaml_ng::Value) -> String {
+    match yaml_to_json(key) {
+        Value::String(key) => key,
+        other => other.to_string(),
+    }
+}
+
+/// Render an `OpenAPI` or Swagger spec as an llms-style markdown document.
+///
+/// The API title becomes the `#` heading, each tag a `##` section holding its
+/// operations as `### METHOD /path` sections, and component schemas (or
+/// Swagger `definitions`) `### schema Name` sections under `## Schemas`.
+/// Operations with several tags are listed under the first one.
+///
+/// # Errors
+///
+/// Returns an error if the text is not an `OpenAPI` or Swagger spec.
+pub fn render_openapi(text: &str) -> Result<String> {
+    let spec = parse_spec(text)?;
+    let mut renderer = Renderer {
+        spec: &spec,
+        out: String::new(),
+    };
+    renderer.render();
+    Ok(renderer.out)
+}
+
+/// One operation discovered under `paths`.
+struct Endpoint<'a> {
+    method: &'a str,
+    path: &'a str,
+    path_item: &'a Value,
+    operation: &'a Value,
+}
+
+struct Renderer<'a> {
+    spec: &'a Value,
+    out: String,
+}
+
+impl<'a> Renderer<'a> {
+    fn render(&mut self) {
+        self.render_header();
+        for (tag, operations) in self.group_operations() {
+            let _ = writeln!(self.out, "## {tag}\n");
+            if let Some(description) = self.tag_description(&tag) {
+                self.push_text(description);
+            }
+            for operation in operations {
+                self.render_operation(&operation);
+            }
+        }
+        self.render_schemas();
+        self.out.truncate(self.out.trim_end().len());
+        self.out.push('\n');
+    }
+
+    fn render_header(&mut self) {
+        let info = self.spec.get("info");
+        let title = info
+            .and_then(|info| info.get("title"))
+            .and_then(Value::as_str)
+            .unwrap_or("API");
+        let _ = writeln!(self.out, "# {}\n", inline(title));
+
+        let mut facts = Vec::new();
+        if let Some(version) = info
+            .and_then(|info| info.get("version"))
+            .and_then(scalar_text)
+        {
+            facts.push(format!("Version {version}"));
+        }
+        if let Some(version) = self.spec.get("openapi").and_then(scalar_text) {
+            facts.push(format!("OpenAPI {version}"));
+        } else if let Some(version) = self.spec.get("swagger").and_then(scalar_text) {
+            facts.push(format!("Swagger {version}"));
+        }
+        let _ = writeln!(self.out, "{}\n", facts.join(" · "));
+
+        if let Some(description) = info
+            .and_then(|info| info.get("description"))
+            .and_then(Value::as_str)
+        {
+            self.push_text(description);
+        }
+
+        let servers = self.servers();
+        if !servers.is_empty() {
+            self.out.push_str("**Servers**\n\n");
+            for server in servers {
+                let _ = writeln!(self.out, "- {server}");
+            }
+            self.out.push('\n');
+        }
+    }
+
+    fn servers(&self) -> Vec<String> {
+        if let Some(servers) = self.spec.get("servers").and_then(Value::as_array) {
+            return servers
+                .iter()
+                .filter_map(|server| {
+                    let url = server.get("url").and_then(Value::as_str)?;
+                    Some(with_description(format!("`{url}`"), server))
+                })
+                .collect();
+        }
+        // Swagger 2.0 splits the base URL into host, basePath, and schemes
+        let Some(host) = self.spec.get("host").and_then(Value::as_str) else {
+            return Vec::new();
+        };
+        let base_path = self
+            .spec
+            .get("basePath")
+            .and_then(Value::as_str)
+            .unwrap_or("");
+        let schemes: Vec<&str> = self
+            .spec
+            .get("schemes")
+            .and_then(Value::as_array)
+            .map(|schemes| schemes.iter().filter_map(Value::as_str).collect())
+            .unwrap_or_default();
+        let schemes = if schemes.is_empty() {
+            vec!["https"]
+        } else {
+            schemes
+        };
+        schemes
+            .into_iter()
+            .map(|scheme| format!("`{scheme}://{host}{base_path}`"))
+            .collect()
+    }
+
+    /// Group operations by their first tag: declared tags first (in spec
+    /// order), then undeclared tags in order of appearance, then untagged.
+    fn group_operations(&self) -> Vec<(String, Vec<Endpoint<'a>>)> {
+        let mut groups: Vec<(String, Vec<Endpoint<'a>>)> = self
+            .spec
+            .get("tags")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(|tag| tag.get("name").and_then(Value::as_str))
+            .map(|name| (name.to_string(), Vec::new()))
+            .collect();
+        let mut untagged = Vec::new();
+
+        let paths = self.spec.get("paths").and_then(Value::as_object);
+        for (path, path_item) in paths.into_iter().flatten() {
+            for method in METHODS {
+                let Some(operation) = path_item.get(method) else {
+                    continue;
+                };
+                let entry = Endpoint {
+                    method,
+                    path,
+                    path_item,
+                    operation,
+                };
+                let tag = operation
+                    .get("tags")
+                    .and_then(Value::as_array)
+                    .and_then(|tags| tags.first())
+                    .and_then(Value::as_str);
+                let Some(tag) = tag else {
+                    untagged.push(entry);
+                    continue;
+                };
+                if let Some((_, group)) = groups.iter_mut().find(|(name, _)| name == tag) {
+                    group.push(entry);
+                } else {
+                    groups.push((tag.to_string(), vec![entry]));
+                }
+            }
+        }
+
+        groups.retain(|(_, operations)| !operations.is_empty());
+        if !untagged.is_empty() {
+            groups.push((UNTAGGED.to_string(), untagged));
+        }
+        groups
+    }
+
+    fn tag_description(&self, tag: &str) -> Option<&'a str> {
+        self.spec
+            .get("tags")?
+            .as_array()?
+            .iter()
+            .find(|entry| entry.get("name").and_then(Value::as_str) == Some(tag))?
+            .get("description")?
+            .as_str()
+    }
+
+    fn render_operation(&mut self, entry: &Endpoint<'a>) {
+        let operation = entry.operation;
+        let _ = writeln!(
+            self.out,
+            "### {} {}\n",
+            entry.method.to_ascii_uppercase(),
+            entry.path
+        );
+        if operation.get("deprecated").and_then(Value::as_bool) == Some(true) {
+            self.out.push_str("*Deprecated.*\n\n");
+        }
+        if let Some(summary) = operation.get("summary").and_then(Value::as_str) {
+            let _ = writeln!(self.out, "{}\n", inline(summary));
+        }
+        if let Some(description) = operation.get("description").and_then(Value::as_str) {
+            self.push_text(description);
+        }
+        if let Some(id) = operation.get("operationId").and_then(Value::as_str) {
+            let _ = writeln!(self.out, "Operation ID: `{id}`\n");
+        }
+
+        let parameters = self.parameters(entry);
+        let (body_params, parameters): (Vec<_>, Vec<_>) = parameters
+            .into_iter()
+            .partition(|param| param.get("in").and_then(Value::as_str) == Some("body"));
+        if !parameters.is_empty() {
+            self.out.push_str("**Parameters**\n\n");
+            for param in parameters {
+                self.push_parameter(param);
+            }
+            self.out.push('\n');
+        }
+
+        self.render_request_body(operation, &body_params);
+        self.render_responses(operation);
+    }
+
+    /// Path-level parameters merged with operation parameters; the operation
+    /// wins when both declare the same name and location.
+    fn parameters(&self, entry: &Endpoint<'a>) -> Vec<&'a Value> {
+        let resolved = |holder: &'a Value| -> Vec<&'a Value> {
+            holder
+                .get("parameters")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .map(|param| self.resolve(param))
+                .collect()
+        };
+        let own = resolved(entry.operation);
+        let key = |param: &Value| {
+            (
+                param.get("name").cloned(),
+                param.get("in").and_then(Value::as_str).map(str::to_string),
+            )
+        };
+        let mut merged: Vec<&Value> = resolved(entry.path_item)
+            .into_iter()
+            .filter(|shared| own.iter().all(|param| key(param) != key(shared)))
+            .collect();
+        merged.extend(own);
+        merged
+    }
+
+    fn push_parameter(&mut self, param: &Value) {
+        let name = param.get("name").and_then(Value::as_str).unwrap_or("?");
+        let mut facts = Vec::new();
+        if let Some(location) = param.get("in").and_then(Value::as_str) {
+            facts.push(location.to_string());
+        }
+        // OpenAPI 3 nests the type under `schema`; Swagger 2 inlines it
+        let schema = param.get("schema").unwrap_or(param);
+        facts.push(format!("`{}`", type_label(schema)));
+        if param.get("required").and_then(Value::as_bool) == Some(true) {
+            facts.push("required".to_string());
+        }
+        let line = with_description(format!("`{name}` ({})", facts.join(", ")), param);
+        let _ = writeln!(self.out, "- {line}");
+    }
+
+    fn render_request_body(&mut self, operation: &Value, body_params: &[&Value]) {
+        let mut lines = Vec::new();
+        let mut description = None;
+        let mut required = false;
+        if let Some(body) = operation.get("requestBody").map(|body| self.resolve(body)) {
+            description = body.get("description").and_then(Value::as_str);
+            required = body.get("required").and_then(Value::as_bool) == Some(true);
+            lines.extend(media_types(body));
+        }
+        for param in body_params {
+            description = description.or_else(|| param.get("description").and_then(Value::as_str));
+            required |= param.get("required").and_then(Value::as_bool) == Some(true);
+            if let Some(schema) = param.get("schema") {
+                lines.push(format!("- `{}`", type_label(schema)));
+            }
+        }
+        if lines.is_empty() && description.is_none() {
+            return;
+        }
+
+        self.out.push_str(if required {
+            "**Request body** (required)\n\n"
+        } else {
+            "**Request body**\n\n"
+        });
+        if let Some(description) = description {
+            self.push_text(description);
+        }
+        for line in lines {
+            let _ = writeln!(self.out, "{line}");
+        }
+        self.out.push('\n');
+    }
+
+    fn render_responses(&mut self, operation: &Value) {
+        let Some(responses) = operation.get("responses").and_then(Value::as_object) else {
+            return;
+        };
+        if responses.is_empty() {
+            return;
+        }
+        self.out.push_str("**Responses**\n\n");
+        for (status, response) in responses {
+            let response = self.resolve(response);
+            let mut line = format!("`{status}`");
+            if let Some(description) = response.get("description").and_then(Value::as_str) {
+                let _ = write!(line, ": {}", inline(description));
+            }
+            let mut bodies: Vec<String> = response
+                .get("content")
+                .and_then(Value::as_object)
+                .into_iter()
+                .flatten()
+                .map(|(media, content)| media_label(media, content))
+                .collect();
+            if let Some(schema) = response.get("schema") {
+                bodies.push(format!("`{}`", type_label(schema)));
+            }
+            if !bodies.is_empty() {
+                let _ = write!(line, " ({})", bodies.join("; "));
+            }
+            let _ = writeln!(self.out, "- {line}");
+        }
+        self.out.push('\n');
+    }
+
+    fn render_schemas(&mut self) {
+        let schemas = self
+            .spec
+            .pointer("/components/schemas")
+            .or_else(|| self.spec.get("definitions"))
+            .and_then(Value::as_object);
+        let Some(schemas) = schemas.filter(|schemas| !schemas.is_empty()) else {
+            return;
+        };
+        self.out.push_str("## Schemas\n\n");
+        for (name, schema) in schemas {
+            self.render_schema(name, schema);
+        }
+    }
+
+    fn render_schema(&mut self, name: &str, schema: &Value) {
+        let _ = writeln!(self.out, "### schema {name}\n");
+        if let Some(description) = schema.get("description").and_then(Value::as_str) {
+            self.push_text(description);
+        }
+
+        let properties = schema.get("properties").and_then(Value::as_object);
+        if properties.is_none() {
+            let _ = writeln!(self.out, "Type: `{}`\n", type_label(schema));
+        }
+        if let Some(values) = enum_values(schema) {
+            let _ = writeln!(self.out, "Values: {values}\n");
+        }
+
+        let Some(properties) = properties.filter(|properties| !properties.is_empty()) else {
+            return;
+        };
+        let required: Vec<&str> = schema
+            .get("required")
+            .and_then(Value::as_array)
+            .map(|names| names.iter().filter_map(Value::as_str).collect())
+            .unwrap_or_default();
+        self.out.push_str("**Properties**\n\n");
+        for (property, details) in properties {
+            let mut facts = vec![format!("`{}`", type_label(details))];
+            if required.contains(&property.as_str()) {
+                facts.push("required".to_string());
+            }
+            let line = with_description(format!("`{property}` ({})", facts.join(", ")), details);
+            let _ = writeln!(self.out, "- {line}");
+        }
+        self.out.push('\n');
+    }
+
+    /// Follow local `$ref` pointers (`#/components/parameters/Limit`).
+    fn resolve(&self, mut value: &'a Value) -> &'a Value {
+        for _ in 0..MAX_REF_DEPTH {
+            let Some(target) = value
+                .get("$ref")
+                .and_then(Value::as_str)
+                .and_then(|reference| reference.strip_prefix('#'))
+                .and_then(|pointer| self.spec.pointer(pointer))
+            else {
+                break;
+            };
+            value = target;
+        }
+        value
+    }
+
+    fn push_text(&mut self, text: &str) {
+        let text = text.trim();
+        if !text.is_empty() {
+            self.out.push_str(&demote_doc_headings(text));
+            self.out.push_str("\n\n");
+        }
+    }
+}
+
+/// Bullet lines for each media type of an `OpenAPI` 3 request body.
+fn media_types(body: &Value) -> Vec<String> {
+    body.get("content")
+        .and_then(Value::as_object)
+        .into_iter()
+        .flatten()
+        .map(|(media, content)| format!("- {}", media_label(media, content)))
+        .collect()
+}
+
+fn media_label(media: &str, content: &Value) -> String {
+    content.get("schema").map_or_else(
+        || format!("`{media}`"),
+        |schema| format!("`{media}`: `{}`", type_label(schema)),
+    )
+}
+
+/// Compact type description: `Pet`, `array<Pet>`, `string (date-time)`,
+/// `Cat | Dog`.
+fn type_label(schema: &Value) -> String {
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        return reference
+            .rsplit('/')
+            .next()
+            .unwrap_or(reference)
+            .to_string();
+    }
+    for (key, separator) in [("oneOf", " | "), ("anyOf", " | "), ("allOf", " & ")] {
+        if let Some(variants) = schema.get(key).and_then(Value::as_array) {
+            return variants
+                .iter()
+                .map(type_label)
+                .collect::<Vec<_>>()
+                .join(separator);
+        }
+    }
+
+    let kind = match schema.get("type") {
+        Some(Value::String(kind)) => kind.clone(),
+        Some(Value::Array(kinds)) => kinds
+            .iter()
+            .filter_map(Value::as_str)
+            .collect::<Vec<_>>()
+            .join(" | "),
+        _ if schema.get("properties").is_some() => "object".to_string(),
+        _ => "any".to_string(),
+    };
+    if kind == "array" {
+        let items = schema
+            .get("items")
+            .map_or_else(|| "any".to_string(), type_label);
+        return format!("array<{items}>");
+    }
+    match schema.get("format").and_then(Value::as_str) {
+        Some(format) => format!("{kind} ({format})"),
+        None => kind,
+    }
+}
+
+fn enum_values(schema: &Value) -> Option<String> {
+    let values = schema.get("enum")?.as_array()?;
+    let labels: Vec<String> = values
+        .iter()
+        .map(|value| {
+            format!(
+                "`{}`",
+                scalar_text(value).unwrap_or_else(|| value.to_string())
+            )
+        })
+        .collect();
+    (!labels.is_empty()).then(|| labels.join(", "))
+}
+
+fn scalar_text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(text) => Some(text.clone()),
+        Value::Number(number) => Some(number.to_string()),
+        Value::Bool(flag) => Some(flag.to_string()),
+        _ => None,
+    }
+}
+
+/// Append `: description` (collapsed to one line) when the object has one.
+fn with_description(label: String, object: &Value) -> String {
+    match object
+        .get("description")
+        .and_then(Value::as_str)
+        .map(inline)
+        .filter(|description| !description.is_empty())
+    {
+        Some(description) => format!("{label}: {description}"),
+        None => label,
+    }
+}
+
+fn inline(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Load a spec from a URL or filesystem path and render it.
+///
+/// # Errors
+///
+/// Returns an error if the spec cannot be read or is not an `OpenAPI` document.
+pub async fn fetch_openapi_docs(fetcher: &Fetcher, location: &str) -> Result<String> {
+    let text = if is_remote(location) {
+        fetcher.fetch(location).await?.0
+    } else {
+        tokio::fs::read_to_string(location).await.map_err(|err| {
+            if err.kind() == std::io::ErrorKind::NotFound {
+                Error::NotFound(format!("OpenAPI spec not found at '{location}'"))
+            } else {
+                Error::Io(err)
+            }
+        })?
+    };
+    render_openapi(&text)
+}
+
+/// Refresh an `OpenAPI` source by re-rendering its spec.
+///
+/// The cache is only rewritten when the rendered document changed.
+///
+/// # Errors
+///
+/// Returns an error if the source is not an `OpenAPI` spec, or if loading,
+/// rendering, persistence, or indexing fails.
+pub async fn refresh_openapi<S, I>(
+    storage: &S,
+    fetcher: &Fetcher,
+    alias: &str,
+    ctx: &RefreshContext,
+    metrics: PerformanceMetrics,
+    indexer: &I,
+    filter_preference: bool,
+) -> Result<RefreshOutcome>
+where
+    S: RefreshStorage + Sync,
+    I: RefreshIndexer + Sync,
+{
+    let Some(SourceType::OpenApi { location }) = &ctx.existing_metadata.origin.source_type else {
+        return Err(Error::Config(format!(
+            "Source '{alias}' is not an OpenAPI spec"
+        )));
+    };
+
+    let content = fetch_openapi_docs(fetcher, location).await?;
+    let sha256 = calculate_sha256(&content);
+
+    let mut metadata = ctx.existing_metadata.clone();
+    let filter_changed = metadata.filter_non_english.unwrap_or(true) != filter_preference;
+    metadata.filter_non_english = Some(filter_preference);
+
+    if sha256 == metadata.sha256 {
+        if filter_changed {
+            storage.save_metadata(alias, &metadata)?;
+        }
+        return Ok(RefreshOutcome::Unchanged {
+            alias: alias.to_string(),
+        });
+    }
+
+    let payload = RefreshPayload {
+        content,
+        sha256,
+        etag: None,
+        last_modified: None,
+    };
+    let params = ApplyRefreshParams::new(metadata, ctx.existing_aliases.clone());
+    apply_refresh(storage, alias, &params, &payload, metrics, indexer).await
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    const PETSTORE: &str = r"
+openapi: 3.0.3
+info:
+  title: Petstore
+  version: 1.0.0
+  description: |
+    Manage pets.
+
+    # Authentication
+
+    Use an API key.
+servers:
+  - url: https://api.example.com/v1
+    description: Production
+tags:
+  - name: pets
+    description: Everything about pets.
+paths:
+  /pets/{petId}:
+    parameters:
+      - $ref: '#/components/parameters/PetId'
+    get:
+      tags: [pets]
+      summary: Find a pet by ID
+      operationId: getPet
+      responses:
+        200:
+          description: The pet.
+          content:
+            application/json:
+              schema:
+                $ref: '#/components/schemas/Pet'
+        '404':
+          $ref: '#/components/responses/NotFound'
+  /pets:
+    post:
+      tags: [pets]
+      summary: Create a pet
+      requestBody:
+        required: true
+        content:
+          application/json:
+            schema:
+              $ref: '#/components/schemas/Pet'
+      responses:
+        '201':
+          description: Created.
+  /health:
+    get:
+      deprecated: true
+      responses:
+        '200':
+          description: OK
+components:
+  parameters:
+    PetId:
+      name: petId
+      in: path
+      required: true
+      description: Pet identifier.
+      schema:
+        type: integer
+        format: int64
+  responses:
+    NotFound:
+      description: No such pet.
+  schemas:
+    Pet:
+      type: object
+      description: A pet.
+      required: [name]
+      properties:
+        name:
+          type: string
+          description: Pet name.
+        tags:
+          type: array
+          items:
+            type: string
+    Status:
+      type: string
+      enum: [available, sold]
+";
+
+    #[test]
+    fn renders_operations_by_tag_and_schemas() {
+        let markdown = render_openapi(PETSTORE).unwrap();
+        assert!(
+            markdown.starts_with("# Petstore\n\nVersion 1.0.0 · OpenAPI 3.0.3\n\nManage pets.")
+        );
+        assert!(
+            markdown.contains("**Authentication**"),
+            "description headings are demoted"
+        );
+        assert!(markdown.contains("- `https://api.example.com/v1`: Production"));
+        assert!(markdown.contains("## pets\n\nEverything about pets."));
+        assert!(markdown.contains("### GET /pets/{petId}\n\nFind a pet by ID"));
+        assert!(markdown.contains("Operation ID: `getPet`"));
+        assert!(
+            markdown.contains("- `petId` (path, `integer (int64)`, required): Pet identifier.")
+        );
+        assert!(markdown.contains("- `200`: The pet. (`application/json`: `Pet`)"));
+        assert!(markdown.contains("- `404`: No such pet."));
+        assert!(markdown.contains("**Request body** (required)\n\n- `application/json`: `Pet`"));
+        assert!(markdown.contains("## Operations\n\n### GET /health\n\n*Deprecated.*"));
+        assert!(markdown.contains("### schema Pet\n\nA pet."));
+        assert!(markdown.contains("- `name` (`string`, required): Pet name."));
+        assert!(markdown.contains("- `tags` (`array<string>`)"));
+        assert!(markdown.contains("Values: `available`, `sold`"));
+        // Tagged groups come before untagged operations, schemas last
+        assert!(markdown.find("## pets") < markdown.find("## Operations"));
+        assert!(markdown.find("## Operations") < markdown.find("## Schemas"));
+    }
+
+    #[test]
+    fn renders_swagger_two_specs() {
+        let spec = r##"{
+            "swagger": "2.0",
+            "info": {"title": "Legacy", "version": "2"},
+            "host": "api.example.com",
+            "basePath": "/v2",
+            "paths": {
+                "/users": {
+                    "post": {
+                        "parameters": [
+                            {"name": "body", "in": "body", "description": "New user.",
+                             "schema": {"$ref": "#/definitions/User"}},
+                            {"name": "dryRun", "in": "query", "type": "boolean"}
+                        ],
+                        "responses": {"200": {"description": "OK", "schema": {"$ref": "#/definitions/User"}}}
+                    }
+                }
+            },
+            "definitions": {"User": {"properties": {"id": {"type": "string"}}}}
+        }"##;
+        let markdown = render_openapi(spec).unwrap();
+        assert!(markdown.contains("Version 2 · Swagger 2.0"));
+        assert!(markdown.contains("- `https://api.example.com/v2`"));
+        assert!(markdown.contains("- `dryRun` (query, `boolean`)"));
+        assert!(markdown.contains("**Request body**\n\nNew user.\n\n- `User`"));
+        assert!(markdown.contains("- `200`: OK (`User`)"));
+        assert!(markdown.contains("### schema User"));
+    }
+
+    #[test]
+    fn detects_specs_but_not_markdown() {
+        assert!(looks_like_spec(PETSTORE));
+        assert!(looks_like_spec(
+            r#"{"info": {"title": "T"}, "swagger": "2.0"}"#
+        ));
+        assert!(!looks_like_spec("# Guide\n\nopenapi: is covered below\n"));
+        assert!(!looks_like_spec(r#"{"name": "openapi-tools"}"#));
+        assert!(!looks_like_spec("openapi: [unclosed"));
+    }
+
+    #[test]
+    fn rejects_non_openapi_documents() {
+        assert!(render_openapi("title: not a spec").is_err());
+        assert!(render_openapi("{\"info\": {}}").is_err());
+        assert!(render_openapi(": : :").is_err());
+    }
+}
@@ -5,6 +5,8 @@
 //! and concurrent queries. This module is tuned for low-latency search and
 //! amortized index writer creation costs.
 use crate::cache::SearchCache;
+use crate::code_language;
+use crate::highlight;
 use crate::memory_pool::{MemoryPool, PooledString};
 use crate::string_pool::StringPool;
 use crate::{Error, HeadingBlock, HeadingLevel, Result, SearchHit};
@@ -361,6 +363,7 @@ impl OptimizedSearchIndex {
         // Process results using memory pool
         let mut results = Vec::with_capacity(top_docs.len());
         let mut snippet_buffer = self.memory_pool.get_string_buffer(200).await;
+        let highlight_terms = highlight::query_terms(query_str);
 
         for (score, doc_address) in top_docs {
             let doc = searcher
@@ -416,6 +419,8 @@ impl OptimizedSearchIndex {
                 level,
                 lines,
                 line_numbers,
+                snippet_language: code_language::snippet_language(&content, snippet_buffer.as_str()),
+                highlights: highlight::match_spans(snippet_buffer.as_str(), &highlight_terms),
                 snippet: snippet_buffer.as_str().to_string(),
                 score,
                 source_url: None,
@@ -423,6 +428,7 @@ impl OptimizedSearchIndex {
                 is_stale: false,
                 checksum: String::new(),
                 anchor: None,
+                section_id: None,
                 context: None,
             });
         }
@@ -138,6 +138,7 @@
 
 use crate::{
     Diagnostic, DiagnosticSeverity, Error, HeadingBlock, Result, TocEntry, heading::path_variants,
+    mapping::section_id,
 };
 use base64::{Engine, engine::general_purpose::STANDARD as B64};
 use sha2::{Digest, Sha256};
@@ -334,16 +335,12 @@ impl MarkdownParser {
             // Splits the document into fixed-size windows to improve search fidelity
             let total_lines = text.lines().count();
             if total_lines <= FALLBACK_WINDOW_LINES {
-                let path = vec!["Document".into()];
-                let variants = path_variants(&path);
-                heading_blocks.push(HeadingBlock {
-                    path,
-                    display_path: variants.display_segments,
-                    normalized_tokens: variants.tokens,
-                    content: text.to_string(),
-                    start_line: 1,
-                    end_line: total_lines,
-                });
+                heading_blocks.push(HeadingBlock::new(
+                    vec!["Document".into()],
+                    text.to_string(),
+                    1,
+                    total_lines,
+                ));
             } else {
                 let mut start = 1usize;
                 let mut current = String::new();
@@ -356,32 +353,24 @@ impl MarkdownParser {
                     count += 1;
                     if count == FALLBACK_WINDOW_LINES {
                         let end_line = start + count - 1;
-                        let path = vec!["Document".into()];
-                        let variants = path_variants(&path);
-                        heading_blocks.push(HeadingBlock {
-                            path,
-                            display_path: variants.display_segments,
-                            normalized_tokens: variants.tokens,
-                            content: std::mem::take(&mut current),
-                            start_line: start,
+                        heading_blocks.push(HeadingBlock::new(
+                            vec!["Document".into()],
+                            std::mem::take(&mut current),
+                            start,
                             end_line,
-                        });
+                        ));
                         start = end_line + 1;
                         count = 0;
                     }
                 }
                 if !current.is_empty() {
                     let end_line = start + count - 1;
-                    let path = vec!["Document".into()];
-                    let variants = path_variants(&path);
-                    heading_blocks.push(HeadingBlock {
-                        path,
-                        display_path: variants.display_segments,
-                        normalized_tokens: variants.tokens,
-                        content: current,
-                        start_line: start,
+                    heading_blocks.push(HeadingBlock::new(
+                        vec!["Document".into()],
+                        current,
+                        start,
                         end_line,
-                    });
+                    ));
                 }
             }
         }
@@ -494,6 +483,9 @@ impl MarkdownParser {
             let normalized_segments = variants.normalized_segments.clone();
             let normalized_tokens = variants.tokens.clone();
 
+            // Fresh persistent ID; sync carries earlier IDs over matched sections
+            let id = section_id(&current_path, content);
+
             // Create heading block
             blocks.push(HeadingBlock {
                 path: current_path.clone(),
@@ -502,6 +494,7 @@ impl MarkdownParser {
                 content: content.to_string(),
                 start_line,
                 end_line,
+                id: id.clone(),
             });
 
             // Compute stable content anchor for remapping across updates
@@ -518,6 +511,7 @@ impl MarkdownParser {
                     format!("{start_line}")
                 },
                 anchor,
+                id: Some(id),
                 children: Vec::new(),
             };
 
@@ -1200,6 +1194,41 @@ Different content for section B.
         Ok(())
     }
 
+    #[test]
+    fn test_code_blocks_tagged_with_language() -> Result<()> {
+        // Given: A section with a labelled and an unlabelled fence
+        let mut parser = create_test_parser();
+        let markdown = r"# Install
+
+Run this first:
+
+```sh
+npm install blz
+```
+
+```
+fn main() {}
+```
+";
+
+        // When: Parsing markdown
+        let result = parser.parse(markdown)?;
+
+        // Then: Each fenced block is tagged with its language
+        let install = result
+            .heading_blocks
+            .iter()
+            .find(|block| block.path.contains(&"Install".to_string()))
+            .expect("Install should be found");
+        let code_blocks = install.code_blocks();
+        assert_eq!(code_blocks.len(), 2);
+        assert_eq!(code_blocks[0].language.as_deref(), Some("bash"));
+        assert_eq!(code_blocks[0].code, "npm install blz\n");
+        assert_eq!(code_blocks[1].language.as_deref(), Some("rust"));
+
+        Ok(())
+    }
+
     #[test]
     fn test_line_number_tracking() -> Result<()> {
         // Given: Markdown with known line structure
@@ -0,0 +1,199 @@
+//! PDF documents converted to markdown.
+//!
+//! Plenty of vendor docs only ship as PDFs. Text is extracted page by page
+//! and each page becomes a `## Page N` section under a `#` title taken from
+//! the file name, so hits cite the page they came from. Scanned PDFs without
+//! a text layer have nothing to extract and are rejected. Remote PDFs are
+//! refetched on sync and re-indexed only when the extracted text changed.
+
+use std::fmt::Write as _;
+use std::path::Path;
+
+use crate::fetcher::calculate_sha256;
+use crate::refresh::{
+    ApplyRefreshParams, RefreshContext, RefreshIndexer, RefreshOutcome, RefreshPayload,
+    RefreshStorage, apply_refresh,
+};
+use crate::{Error, Fetcher, PerformanceMetrics, Result, SourceType};
+
+/// Whether raw bytes are a PDF file.
+#[must_use]
+pub fn is_pdf(bytes: &[u8]) -> bool {
+    bytes.starts_with(b"%PDF-")
+}
+
+/// Whether a URL or path names a `.pdf` file.
+#[must_use]
+pub fn is_pdf_location(location: &str) -> bool {
+    let path = url::Url::parse(location)
+        .map_or_else(|_| location.to_string(), |url| url.path().to_string());
+    Path::new(&path)
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("pdf"))
+}
+
+/// Document title for a PDF at `location`: its file name without extension.
+#[must_use]
+pub fn title_from_location(location: &str) -> String {
+    let path = url::Url::parse(location)
+        .map_or_else(|_| location.to_string(), |url| url.path().to_string());
+    Path::new(&path)
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .filter(|stem| !stem.trim().is_empty())
+        .unwrap_or("Document")
+        .to_string()
+}
+
+/// Extract a PDF's text as markdown, one `## Page N` section per page.
+///
+/// # Errors
+///
+/// Returns an error if the file is not a readable PDF or has no text layer.
+pub fn to_markdown(bytes: &[u8], title: &str) -> Result<String> {
+    // The extractor panics on some malformed files instead of returning an error
+    let pages = std::panic::catch_unwind(|| pdf_extract::extract_text_from_mem_by_pages(bytes))
+        .map_err(|_| Error::Parse("PDF could not be parsed".to_string()))?
+        .map_err(|err| Error::Parse(format!("Failed to extract text from PDF: {err}")))?;
+
+    let mut markdown = format!("# {title}\n");
+    let mut has_text = false;
+    for (number, text) in pages.iter().enumerate() {
+        let body = page_text(text);
+        if body.is_empty() {
+            continue;
+        }
+        has_text = true;
+        let _ = write!(markdown, "\n## Page {}\n\n{body}\n", number + 1);
+    }
+    if !has_text {
+        return Err(Error::Parse(
+            "PDF has no extractable text (scanned pages need OCR first)".to_string(),
+        ));
+    }
+    Ok(markdown)
+}
+
+/// Fetch a PDF and convert it to markdown.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be fetched, is not a PDF, or has no text.
+pub async fn fetch_pdf_docs(fetcher: &Fetcher, url: &str) -> Result<String> {
+    let bytes = fetcher.fetch_bytes(url).await?;
+    if !is_pdf(&bytes) {
+        return Err(Error::Parse(format!("{url} did not return a PDF")));
+    }
+    to_markdown(&bytes, &title_from_location(url))
+}
+
+/// Refresh a PDF source by refetching and re-extracting the file.
+///
+/// The cache is only rewritten when the extracted text changed.
+///
+/// # Errors
+///
+/// Returns an error if the source is not a PDF, or if fetching, extraction,
+/// persistence, or indexing fails.
+pub async fn refresh_pdf<S, I>(
+    storage: &S,
+    fetcher: &Fetcher,
+    alias: &str,
+    ctx: &RefreshContext,
+    metrics: PerformanceMetrics,
+    indexer: &I,
+    filter_preference: bool,
+) -> Result<RefreshOutcome>
+where
+    S: RefreshStorage + Sync,
+    I: RefreshIndexer + Sync,
+{
+    let Some(SourceType::Pdf { url }) = &ctx.existing_metadata.origin.source_type else {
+        return Err(Error::Config(format!("Source '{alias}' is not a PDF")));
+    };
+
+    let content = fetch_pdf_docs(fetcher, url).await?;
+    let sha256 = calculate_sha256(&content);
+
+    let mut metadata = ctx.existing_metadata.clone();
+    let filter_changed = metadata.filter_non_english.unwrap_or(true) != filter_preference;
+    metadata.filter_non_english = Some(filter_preference);
+
+    if sha256 == metadata.sha256 {
+        if filter_changed {
+            storage.save_metadata(alias, &metadata)?;
+        }
+        return Ok(RefreshOutcome::Unchanged {
+            alias: alias.to_string(),
+        });
+    }
+
+    let payload = RefreshPayload {
+        content,
+        sha256,
+        etag: None,
+        last_modified: None,
+    };
+    let params = ApplyRefreshParams::new(metadata, ctx.existing_aliases.clone());
+    apply_refresh(storage, alias, &params, &payload, metrics, indexer).await
+}
+
+/// Clean one page of extracted text.
+///
+/// Lines are trimmed so layout indentation does not read as code blocks,
+/// runs of blank lines are squeezed, and lines that markdown would take for
+/// headings or rules are escaped.
+fn page_text(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut blank_run = true;
+    for line in text.lines() {
+        let line = line.trim().trim_matches('\u{c}');
+        if line.is_empty() {
+            if !blank_run {
+                out.push('\n');
+            }
+            blank_run = true;
+            continue;
+        }
+        blank_run = false;
+        let is_rule = line.chars().all(|ch| matches!(ch, '-' | '=' | '_' | '*'));
+        if line.starts_with('#') || is_rule {
+            out.push('\\');
+        }
+        out.push_str(line);
+        out.push('\n');
+    }
+    out.trim_end().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_page_text_trims_and_escapes() {
+        let text = "  Setup  \n\n\n\n# of devices\n-----\nDone\n\u{c}";
+        assert_eq!(page_text(text), "Setup\n\n\\# of devices\n\\-----\nDone");
+    }
+
+    #[test]
+    fn test_detects_pdf_locations_and_titles() {
+        assert!(is_pdf(b"%PDF-1.7\n..."));
+        assert!(!is_pdf(b"# Title"));
+        assert!(is_pdf_location(
+            "https://vendor.example/docs/Guide.PDF?download=1"
+        ));
+        assert!(is_pdf_location("./manuals/guide.pdf"));
+        assert!(!is_pdf_location("https://vendor.example/llms.txt"));
+        assert_eq!(
+            title_from_location("https://vendor.example/docs/admin-guide.pdf"),
+            "admin-guide"
+        );
+        assert_eq!(title_from_location("https://vendor.example/"), "Document");
+    }
+
+    #[test]
+    fn test_rejects_non_pdf_bytes() {
+        assert!(to_markdown(b"not a pdf", "Guide").is_err());
+    }
+}
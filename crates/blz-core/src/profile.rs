@@ -1,6 +1,9 @@
 use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
 
+use crate::{Error, Result};
+
 /// Execution profile that influences default storage locations and other behavior.
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum AppProfile {
@@ -43,6 +46,87 @@ pub fn dot_dir_slug() -> &'static str {
     }
 }
 
+/// Directory under the data and config roots that holds named profiles.
+pub const PROFILES_DIR: &str = "profiles";
+
+static NAMED: OnceLock<Option<String>> = OnceLock::new();
+
+/// Select the named profile for this process (`--profile-name` / `BLZ_PROFILE`).
+///
+/// A named profile is an isolated set of sources, descriptors, and search
+/// history stored under [`PROFILES_DIR`] in the usual data and config roots;
+/// `config.toml` stays shared. `default` selects the shared root and `dev` the
+/// developer profile. Like [`set`], only the first selection takes effect.
+///
+/// # Errors
+///
+/// Returns an error if `name` is not a valid profile name.
+pub fn select(name: &str) -> Result<()> {
+    let name = name.trim();
+    if name.eq_ignore_ascii_case("dev") {
+        set(AppProfile::Dev);
+        let _ = NAMED.set(None);
+    } else if name.eq_ignore_ascii_case("default") {
+        let _ = NAMED.set(None);
+    } else {
+        validate_name(name)?;
+        let _ = NAMED.set(Some(name.to_string()));
+    }
+    Ok(())
+}
+
+/// The active named profile, or `None` for the default (or dev) profile.
+#[must_use]
+pub fn named() -> Option<&'static str> {
+    NAMED
+        .get_or_init(|| {
+            std::env::var("BLZ_PROFILE")
+                .ok()
+                .map(|value| value.trim().to_string())
+                .filter(|value| {
+                    !value.eq_ignore_ascii_case("dev")
+                        && !value.eq_ignore_ascii_case("default")
+                        && validate_name(value).is_ok()
+                })
+        })
+        .as_deref()
+}
+
+/// `dir` scoped to the active named profile.
+#[must_use]
+pub fn scoped(dir: &Path) -> PathBuf {
+    named().map_or_else(
+        || dir.to_path_buf(),
+        |name| dir.join(PROFILES_DIR).join(name),
+    )
+}
+
+/// Check that `name` can be used as a profile name.
+///
+/// Names are 1-64 ASCII letters, digits, `-`, or `_`, and may not be one of
+/// the built-in profiles (`default`, `dev`).
+///
+/// # Errors
+///
+/// Returns an error describing why the name is invalid.
+pub fn validate_name(name: &str) -> Result<()> {
+    if name.eq_ignore_ascii_case("default") || name.eq_ignore_ascii_case("dev") {
+        return Err(Error::Config(format!(
+            "'{name}' is a built-in profile and cannot be created or removed"
+        )));
+    }
+    let valid = (1..=64).contains(&name.len())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        return Err(Error::Config(format!(
+            "Invalid profile name '{name}': use 1-64 letters, digits, '-' or '_'"
+        )));
+    }
+    Ok(())
+}
+
 fn detect_profile() -> AppProfile {
     if let Ok(value) = std::env::var("BLZ_PROFILE") {
         if value.eq_ignore_ascii_case("dev") {
@@ -60,3 +144,19 @@ fn detect_profile() -> AppProfile {
 
     AppProfile::Default
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validates_profile_names() {
+        assert!(validate_name("work").is_ok());
+        assert!(validate_name("client_a-2").is_ok());
+        assert!(validate_name("").is_err());
+        assert!(validate_name("../escape").is_err());
+        assert!(validate_name("has space").is_err());
+        assert!(validate_name("Default").is_err());
+        assert!(validate_name("dev").is_err());
+    }
+}
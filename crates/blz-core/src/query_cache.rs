@@ -0,0 +1,259 @@
+//! Persistent cache of search results.
+//!
+//! Agents often repeat the exact same query. [`QueryCache`] stores the final
+//! results of a search on disk, keyed on the query, every option that shapes
+//! the results, and the generation of each searched source (its content
+//! checksum and fetch time), so a repeat is answered from one small file
+//! without opening a Tantivy index. Entries go stale on their own when a source
+//! or its ranking settings change; [`Storage`](crate::Storage) also clears the cache whenever sources
+//! are written or removed, covering re-indexes that keep the same checksum.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use tracing::debug;
+
+use crate::http_cache::EntityTag;
+use crate::{Error, Ranking, Result, Source, SourceSummary};
+
+/// Entries kept before the least recently written ones are pruned.
+pub const MAX_ENTRIES: usize = 512;
+
+/// Key identifying one cached search.
+///
+/// Built like an HTTP entity tag: every parameter and source generation is
+/// length-prefixed and hashed, so any change produces a different key.
+#[derive(Debug, Clone)]
+pub struct QueryCacheKey {
+    tag: EntityTag,
+}
+
+impl QueryCacheKey {
+    /// Start a key for `query`.
+    #[must_use]
+    pub fn new(query: &str) -> Self {
+        Self {
+            tag: EntityTag::new("query-cache/v1").param("query", query),
+        }
+    }
+
+    /// Include an option that changes the results.
+    #[must_use]
+    pub fn param(mut self, key: &str, value: &impl ToString) -> Self {
+        self.tag = self.tag.param(key, &value.to_string());
+        self
+    }
+
+    /// Include the current generation of a searched source.
+    #[must_use]
+    pub fn source(mut self, alias: &str, source: &Source) -> Self {
+        self.tag = self.tag.source(alias, source);
+        self
+    }
+
+    /// Include the current generation of a searched source from its summary.
+    #[must_use]
+    pub fn summary(mut self, alias: &str, summary: &SourceSummary) -> Self {
+        self.tag = self
+            .tag
+            .generation(alias, &summary.sha256, summary.fetched_at);
+        self
+    }
+
+    /// Include the ranking settings a searched source is queried with.
+    #[must_use]
+    pub fn ranking(mut self, alias: &str, ranking: &Ranking) -> Self {
+        self.tag = self.tag.ranking(alias, ranking);
+        self
+    }
+
+    fn file_name(self) -> String {
+        let tag = self.tag.finish();
+        format!("{}.json", tag.trim_matches('"'))
+    }
+}
+
+/// On-disk store of serialized search results.
+#[derive(Debug, Clone)]
+pub struct QueryCache {
+    dir: PathBuf,
+}
+
+impl QueryCache {
+    /// Cache stored in `dir`, which is created on first write.
+    #[must_use]
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Directory holding the entries.
+    #[must_use]
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Cached value for `key`, if present and readable.
+    ///
+    /// Unreadable or outdated entries count as misses.
+    #[must_use]
+    pub fn get<T: DeserializeOwned>(&self, key: QueryCacheKey) -> Option<T> {
+        let path = self.dir.join(key.file_name());
+        let bytes = fs::read(&path).ok()?;
+        serde_json::from_slice(&bytes)
+            .inspect_err(|e| debug!("Ignoring unreadable query cache entry {path:?}: {e}"))
+            .ok()
+    }
+
+    /// Store `value` under `key`, pruning the oldest entries beyond
+    /// [`MAX_ENTRIES`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the entry cannot be serialized or written.
+    pub fn put<T: Serialize>(&self, key: QueryCacheKey, value: &T) -> Result<()> {
+        fs::create_dir_all(&self.dir)
+            .map_err(|e| Error::Storage(format!("Failed to create query cache: {e}")))?;
+        let json = serde_json::to_vec(value)
+            .map_err(|e| Error::Storage(format!("Failed to serialize query cache entry: {e}")))?;
+
+        // Write then rename so concurrent readers never see a partial entry
+        let path = self.dir.join(key.file_name());
+        let tmp_path = path.with_extension(format!("{}.tmp", std::process::id()));
+        fs::write(&tmp_path, json)
+            .map_err(|e| Error::Storage(format!("Failed to write query cache entry: {e}")))?;
+        fs::rename(&tmp_path, &path)
+            .map_err(|e| Error::Storage(format!("Failed to commit query cache entry: {e}")))?;
+
+        self.prune(MAX_ENTRIES);
+        Ok(())
+    }
+
+    /// Number of stored entries.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries().len()
+    }
+
+    /// Whether no entries are stored.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Remove every entry.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache directory exists but cannot be removed.
+    pub fn clear(&self) -> Result<()> {
+        match fs::remove_dir_all(&self.dir) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(Error::Storage(format!("Failed to clear query cache: {e}")))
+            },
+            _ => Ok(()),
+        }
+    }
+
+    /// Delete the oldest entries until at most `max` remain.
+    fn prune(&self, max: usize) {
+        let mut entries = self.entries();
+        if entries.len() <= max {
+            return;
+        }
+        entries.sort_by_key(|(_, modified)| *modified);
+        for (path, _) in entries.iter().take(entries.len() - max) {
+            let _ = fs::remove_file(path);
+        }
+    }
+
+    fn entries(&self) -> Vec<(PathBuf, std::time::SystemTime)> {
+        let Ok(read_dir) = fs::read_dir(&self.dir) else {
+            return Vec::new();
+        };
+        read_dir
+            .flatten()
+            .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "json"))
+            .filter_map(|entry| {
+                let modified = entry.metadata().and_then(|m| m.modified()).ok()?;
+                Some((entry.path(), modified))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn source(sha256: &str) -> Source {
+        Source {
+            url: "https://example.com/llms.txt".to_string(),
+            etag: None,
+            last_modified: None,
+            fetched_at: Utc::now(),
+            sha256: sha256.to_string(),
+            variant: crate::SourceVariant::Llms,
+            aliases: Vec::new(),
+            tags: Vec::new(),
+            description: None,
+            category: None,
+            npm_aliases: Vec::new(),
+            github_aliases: Vec::new(),
+            origin: crate::SourceOrigin {
+                manifest: None,
+                source_type: None,
+            },
+            filter_non_english: None,
+        }
+    }
+
+    #[test]
+    fn round_trips_and_misses_when_a_source_changes() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let cache = QueryCache::new(dir.path().join("query-cache"));
+        let before = source("abc");
+        let key = |source: &Source| {
+            QueryCacheKey::new("hooks")
+                .param("limit", &10)
+                .source("react", source)
+        };
+
+        assert_eq!(cache.get::<Vec<String>>(key(&before)), None);
+        cache
+            .put(key(&before), &vec!["hit".to_string()])
+            .expect("put");
+        assert_eq!(
+            cache.get::<Vec<String>>(key(&before)),
+            Some(vec!["hit".to_string()])
+        );
+
+        let after = Source {
+            sha256: "def".to_string(),
+            ..before
+        };
+        assert_eq!(cache.get::<Vec<String>>(key(&after)), None);
+        assert_eq!(
+            cache.get::<Vec<String>>(QueryCacheKey::new("hooks").param("limit", &20)),
+            None
+        );
+
+        cache.clear().expect("clear");
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn prunes_oldest_entries() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let cache = QueryCache::new(dir.path());
+        for i in 0..5 {
+            cache
+                .put(QueryCacheKey::new(&format!("q{i}")), &i)
+                .expect("put");
+        }
+        cache.prune(3);
+        assert_eq!(cache.len(), 3);
+    }
+}
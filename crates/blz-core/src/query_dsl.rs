@@ -0,0 +1,426 @@
+//! Structured search query syntax.
+//!
+//! Plain queries are a bag of ranked terms. A query that uses an operator,
+//! parentheses, or a field prefix is parsed with this grammar instead:
+//!
+//! ```text
+//! query  := or
+//! or     := and ("OR" and)*
+//! and    := unary (["AND"] unary)*      terms side by side must all match
+//! unary  := ("NOT" | "-") unary | atom
+//! atom   := "(" query ")" | field ":" atom | "\"" phrase "\"" | term
+//! field  := "heading" | "path" | "code"
+//! ```
+//!
+//! `heading:` matches a section's own heading, `path:` any heading in its
+//! breadcrumb path, and `code:` only text inside fenced code blocks.
+//! Operators are case-sensitive, so lowercase `and`/`or`/`not` stay terms.
+//! Negations must exclude from something: `hooks NOT class` is valid, a bare
+//! `NOT class` or `a OR NOT b` is rejected.
+
+use crate::{Error, Result};
+
+/// Field a term is scoped to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueryField {
+    /// The section's own (last) heading.
+    Heading,
+    /// Any heading in the section's breadcrumb path.
+    Path,
+    /// Text inside fenced code blocks.
+    Code,
+}
+
+impl QueryField {
+    fn from_prefix(prefix: &str) -> Option<Self> {
+        match prefix {
+            "heading" => Some(Self::Heading),
+            "path" => Some(Self::Path),
+            "code" => Some(Self::Code),
+            _ => None,
+        }
+    }
+}
+
+/// Parsed structured query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QueryExpr {
+    /// A single word or quoted phrase, optionally scoped to a field.
+    Term {
+        /// Field the term is scoped to; `None` searches the default fields.
+        field: Option<QueryField>,
+        /// Term text without quotes.
+        text: String,
+        /// Whether the term was quoted.
+        phrase: bool,
+    },
+    /// Every child must match.
+    And(Vec<Self>),
+    /// At least one child must match.
+    Or(Vec<Self>),
+    /// The child must not match.
+    Not(Box<Self>),
+}
+
+impl QueryExpr {
+    /// Text to locate snippets and match lines with: the first term that is
+    /// not negated and not scoped to headings, quoted if it was a phrase.
+    #[must_use]
+    pub fn highlight_text(&self) -> Option<String> {
+        match self {
+            Self::Term {
+                field: None | Some(QueryField::Code),
+                text,
+                phrase,
+            } => Some(if *phrase {
+                format!("\"{text}\"")
+            } else {
+                text.clone()
+            }),
+            Self::Term { .. } | Self::Not(_) => None,
+            Self::And(children) | Self::Or(children) => {
+                children.iter().find_map(Self::highlight_text)
+            },
+        }
+    }
+
+    /// Every term that is not negated and not scoped to headings, in query
+    /// order; these are the words a content snippet can show.
+    #[must_use]
+    pub fn highlight_terms(&self) -> Vec<String> {
+        match self {
+            Self::Term {
+                field: None | Some(QueryField::Code),
+                text,
+                ..
+            } => vec![text.clone()],
+            Self::Term { .. } | Self::Not(_) => Vec::new(),
+            Self::And(children) | Self::Or(children) => {
+                children.iter().flat_map(Self::highlight_terms).collect()
+            },
+        }
+    }
+}
+
+/// Whether `input` uses the structured syntax rather than plain terms.
+///
+/// True when, outside quotes, the input has a standalone `AND`/`OR`/`NOT`,
+/// a parenthesis, or a word starting with a `heading:`, `path:`, or `code:`
+/// prefix.
+#[must_use]
+pub fn is_structured(input: &str) -> bool {
+    tokenize(input).is_ok_and(|tokens| {
+        tokens.iter().any(|token| {
+            matches!(
+                token,
+                Token::And | Token::Or | Token::Not | Token::Open | Token::Field(_)
+            )
+        })
+    })
+}
+
+/// Parse a structured query.
+///
+/// # Errors
+///
+/// Returns [`Error::Parse`] for unbalanced parentheses or quotes, dangling
+/// operators, and negations with nothing to exclude from.
+pub fn parse(input: &str) -> Result<QueryExpr> {
+    let tokens = tokenize(input)?;
+    let mut parser = Parser {
+        tokens: &tokens,
+        pos: 0,
+    };
+    let expr = parser.parse_or(None)?;
+    if let Some(token) = parser.peek() {
+        return Err(invalid(&format!("unexpected {}", token.describe())));
+    }
+    check_negations(&expr, true)?;
+    Ok(expr)
+}
+
+fn invalid(message: &str) -> Error {
+    Error::Parse(format!("Invalid query: {message}"))
+}
+
+/// Reject negations that do not narrow a positive match.
+fn check_negations(expr: &QueryExpr, top: bool) -> Result<()> {
+    match expr {
+        QueryExpr::Term { .. } => Ok(()),
+        QueryExpr::Not(_) if top => Err(invalid(
+            "NOT needs a term to exclude from (e.g. `hooks NOT class`)",
+        )),
+        QueryExpr::Not(inner) => check_negations(inner, true),
+        QueryExpr::And(children) => {
+            if children
+                .iter()
+                .all(|child| matches!(child, QueryExpr::Not(_)))
+            {
+                return Err(invalid(
+                    "NOT needs a term to exclude from (e.g. `hooks NOT class`)",
+                ));
+            }
+            children
+                .iter()
+                .try_for_each(|child| check_negations(child, false))
+        },
+        QueryExpr::Or(children) => children.iter().try_for_each(|child| {
+            if matches!(child, QueryExpr::Not(_)) {
+                Err(invalid("NOT cannot be an OR alternative"))
+            } else {
+                check_negations(child, true)
+            }
+        }),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Word(String),
+    Phrase(String),
+    Field(QueryField),
+    And,
+    Or,
+    Not,
+    /// `-` prefix: a negation that plain queries already support.
+    Exclude,
+    Open,
+    Close,
+}
+
+impl Token {
+    fn describe(&self) -> String {
+        match self {
+            Self::Word(word) => format!("'{word}'"),
+            Self::Phrase(phrase) => format!("\"{phrase}\""),
+            Self::Field(_) => "field prefix".to_string(),
+            Self::And => "AND".to_string(),
+            Self::Or => "OR".to_string(),
+            Self::Not => "NOT".to_string(),
+            Self::Exclude => "'-'".to_string(),
+            Self::Open => "'('".to_string(),
+            Self::Close => "')'".to_string(),
+        }
+    }
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut idx = 0;
+    while idx < chars.len() {
+        let ch = chars[idx];
+        if ch.is_whitespace() {
+            idx += 1;
+            continue;
+        }
+        match ch {
+            '(' => tokens.push(Token::Open),
+            ')' => tokens.push(Token::Close),
+            '"' => {
+                let end = chars[idx + 1..]
+                    .iter()
+                    .position(|c| *c == '"')
+                    .ok_or_else(|| invalid("unclosed quote"))?;
+                let phrase: String = chars[idx + 1..idx + 1 + end].iter().collect();
+                tokens.push(Token::Phrase(phrase.trim().to_string()));
+                idx += end + 2;
+                continue;
+            },
+            '-' if chars.get(idx + 1).is_some_and(|next| !next.is_whitespace()) => {
+                tokens.push(Token::Exclude);
+            },
+            _ => {
+                let start = idx;
+                while idx < chars.len()
+                    && !chars[idx].is_whitespace()
+                    && !matches!(chars[idx], '(' | ')' | '"')
+                {
+                    idx += 1;
+                }
+                let word: String = chars[start..idx].iter().collect();
+                let scopes_next = chars.get(idx).is_some_and(|c| matches!(c, '(' | '"'));
+                push_word(&mut tokens, word, scopes_next);
+                continue;
+            },
+        }
+        idx += 1;
+    }
+    Ok(tokens)
+}
+
+/// Push a bare word, splitting off a known field prefix.
+fn push_word(tokens: &mut Vec<Token>, word: String, scopes_next: bool) {
+    match word.as_str() {
+        "AND" => return tokens.push(Token::And),
+        "OR" => return tokens.push(Token::Or),
+        "NOT" => return tokens.push(Token::Not),
+        _ => {},
+    }
+    if let Some((prefix, rest)) = word.split_once(':') {
+        if let Some(field) = QueryField::from_prefix(prefix) {
+            // `code:` directly followed by a quote or parenthesis scopes that atom
+            if !rest.is_empty() || scopes_next {
+                tokens.push(Token::Field(field));
+                if !rest.is_empty() {
+                    push_word(tokens, rest.to_string(), scopes_next);
+                }
+                return;
+            }
+        }
+    }
+    tokens.push(Token::Word(word));
+}
+
+struct Parser<'a> {
+    tokens: &'a [Token],
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<&Token> {
+        let token = self.tokens.get(self.pos);
+        self.pos += 1;
+        token
+    }
+
+    fn parse_or(&mut self, field: Option<QueryField>) -> Result<QueryExpr> {
+        let mut alternatives = vec![self.parse_and(field)?];
+        while self.peek() == Some(&Token::Or) {
+            self.pos += 1;
+            alternatives.push(self.parse_and(field)?);
+        }
+        Ok(flatten(alternatives, QueryExpr::Or))
+    }
+
+    fn parse_and(&mut self, field: Option<QueryField>) -> Result<QueryExpr> {
+        let mut required = vec![self.parse_unary(field)?];
+        loop {
+            match self.peek() {
+                Some(Token::And) => {
+                    self.pos += 1;
+                    required.push(self.parse_unary(field)?);
+                },
+                Some(Token::Or | Token::Close) | None => break,
+                Some(_) => required.push(self.parse_unary(field)?),
+            }
+        }
+        Ok(flatten(required, QueryExpr::And))
+    }
+
+    fn parse_unary(&mut self, field: Option<QueryField>) -> Result<QueryExpr> {
+        if matches!(self.peek(), Some(Token::Not | Token::Exclude)) {
+            self.pos += 1;
+            return Ok(match self.parse_unary(field)? {
+                QueryExpr::Not(inner) => *inner,
+                inner => QueryExpr::Not(Box::new(inner)),
+            });
+        }
+        self.parse_atom(field)
+    }
+
+    fn parse_atom(&mut self, field: Option<QueryField>) -> Result<QueryExpr> {
+        match self.next().cloned() {
+            Some(Token::Open) => {
+                let inner = self.parse_or(field)?;
+                if self.next() != Some(&Token::Close) {
+                    return Err(invalid("missing closing parenthesis"));
+                }
+                Ok(inner)
+            },
+            Some(Token::Field(scoped)) => self.parse_atom(Some(scoped)),
+            Some(Token::Word(text)) => Ok(QueryExpr::Term {
+                field,
+                text,
+                phrase: false,
+            }),
+            Some(Token::Phrase(text)) if !text.is_empty() => Ok(QueryExpr::Term {
+                field,
+                text,
+                phrase: true,
+            }),
+            Some(Token::Phrase(_)) => Err(invalid("empty phrase")),
+            Some(token) => Err(invalid(&format!(
+                "expected a term, found {}",
+                token.describe()
+            ))),
+            None => Err(invalid("expected a term at the end of the query")),
+        }
+    }
+}
+
+fn flatten(mut children: Vec<QueryExpr>, combine: fn(Vec<QueryExpr>) -> QueryExpr) -> QueryExpr {
+    if children.len() == 1 {
+        children.remove(0)
+    } else {
+        combine(children)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn term(field: Option<QueryField>, text: &str) -> QueryExpr {
+        QueryExpr::Term {
+            field,
+            text: text.to_string(),
+            phrase: false,
+        }
+    }
+
+    #[test]
+    fn detects_structured_queries() {
+        assert!(is_structured("hooks AND state"));
+        assert!(is_structured("heading:install"));
+        assert!(is_structured("(a b)"));
+        assert!(!is_structured("react hooks"));
+        assert!(!is_structured("\"AND\" in a phrase"));
+        assert!(!is_structured("std::vec and or not"));
+        assert!(!is_structured("-deprecated"));
+    }
+
+    #[test]
+    fn parses_operators_with_precedence_and_fields() {
+        let expr = parse("heading:install npm OR code:(\"cargo add\" -serde)").unwrap();
+        assert_eq!(
+            expr,
+            QueryExpr::Or(vec![
+                QueryExpr::And(vec![
+                    term(Some(QueryField::Heading), "install"),
+                    term(None, "npm"),
+                ]),
+                QueryExpr::And(vec![
+                    QueryExpr::Term {
+                        field: Some(QueryField::Code),
+                        text: "cargo add".to_string(),
+                        phrase: true,
+                    },
+                    QueryExpr::Not(Box::new(term(Some(QueryField::Code), "serde"))),
+                ]),
+            ])
+        );
+        assert_eq!(expr.highlight_text().as_deref(), Some("npm"));
+        assert_eq!(expr.highlight_terms(), ["npm", "cargo add"]);
+    }
+
+    #[test]
+    fn rejects_malformed_queries() {
+        for bad in [
+            "(hooks",
+            "hooks AND",
+            "NOT hooks",
+            "a OR NOT b",
+            "\"open",
+            "a )",
+        ] {
+            assert!(parse(bad).is_err(), "{bad} should be rejected");
+        }
+        assert!(parse("hooks NOT class").is_ok());
+    }
+}
@@ -0,0 +1,276 @@
+//! Disk budget for the cache and which sources to evict to stay within it.
+//!
+//! The budget (`defaults.disk_budget`, e.g. `2GB`) caps the cache's on-disk
+//! size. When it is exceeded, [`EvictionPolicy`] decides what happens: the
+//! default only warns, while `lru` and `least_searched` remove sources until
+//! the cache fits again. Callers gather a [`QuotaEntry`] per source (size
+//! plus search history) and [`select_evictions`] picks the victims.
+
+use std::cmp::Reverse;
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::numeric::u64_to_f64_lossy;
+
+const KIB: u64 = 1024;
+const MIB: u64 = KIB * 1024;
+const GIB: u64 = MIB * 1024;
+const TIB: u64 = GIB * 1024;
+
+/// A non-zero size in bytes.
+///
+/// Parsed from strings like `500MB`, `2GB`, `1.5G`, or `65536`. Units are
+/// binary (`K` = 1024 bytes).
+///
+/// # Examples
+///
+/// ```
+/// use blz_core::quota::ByteSize;
+///
+/// let size: ByteSize = "2GB".parse().unwrap();
+/// assert_eq!(size.bytes(), 2 * 1024 * 1024 * 1024);
+/// assert_eq!(size.to_string(), "2GB");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct ByteSize(u64);
+
+impl ByteSize {
+    /// Create a size from a raw byte count, rejecting zero.
+    #[must_use]
+    pub const fn from_bytes(bytes: u64) -> Option<Self> {
+        if bytes == 0 { None } else { Some(Self(bytes)) }
+    }
+
+    /// The size in bytes.
+    #[must_use]
+    pub const fn bytes(self) -> u64 {
+        self.0
+    }
+}
+
+impl FromStr for ByteSize {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("Invalid size '{input}' (expected e.g. 500MB or 2GB)");
+        let size = input.trim();
+        let split = size
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(size.len());
+        let (number, unit) = size.split_at(split);
+        let multiplier = match unit.trim().to_ascii_lowercase().as_str() {
+            "" | "b" => 1,
+            "k" | "kb" | "kib" => KIB,
+            "m" | "mb" | "mib" => MIB,
+            "g" | "gb" | "gib" => GIB,
+            "t" | "tb" | "tib" => TIB,
+            _ => return Err(invalid()),
+        };
+        let value: f64 = number.parse().map_err(|_| invalid())?;
+        let bytes = value * u64_to_f64_lossy(multiplier);
+        if !bytes.is_finite() || bytes < 1.0 {
+            return Err(invalid());
+        }
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let bytes = bytes.min(u64_to_f64_lossy(u64::MAX)) as u64;
+        Self::from_bytes(bytes).ok_or_else(invalid)
+    }
+}
+
+impl fmt::Display for ByteSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            bytes if bytes % TIB == 0 => write!(f, "{}TB", bytes / TIB),
+            bytes if bytes % GIB == 0 => write!(f, "{}GB", bytes / GIB),
+            bytes if bytes % MIB == 0 => write!(f, "{}MB", bytes / MIB),
+            bytes if bytes % KIB == 0 => write!(f, "{}KB", bytes / KIB),
+            bytes => write!(f, "{bytes}B"),
+        }
+    }
+}
+
+impl TryFrom<String> for ByteSize {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<ByteSize> for String {
+    fn from(value: ByteSize) -> Self {
+        value.to_string()
+    }
+}
+
+/// What to do when the cache grows past its disk budget.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EvictionPolicy {
+    /// Print a warning naming the sources that would be evicted.
+    #[default]
+    Warn,
+
+    /// Remove the sources searched least recently (never-searched first).
+    Lru,
+
+    /// Remove the sources with the fewest recorded searches.
+    LeastSearched,
+}
+
+impl EvictionPolicy {
+    /// Whether this policy removes sources rather than only warning.
+    #[must_use]
+    pub const fn evicts(self) -> bool {
+        !matches!(self, Self::Warn)
+    }
+}
+
+impl fmt::Display for EvictionPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Warn => "warn",
+            Self::Lru => "lru",
+            Self::LeastSearched => "least_searched",
+        })
+    }
+}
+
+impl FromStr for EvictionPolicy {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input.trim().to_ascii_lowercase().replace('-', "_").as_str() {
+            "warn" => Ok(Self::Warn),
+            "lru" => Ok(Self::Lru),
+            "least_searched" => Ok(Self::LeastSearched),
+            _ => Err(format!(
+                "Invalid eviction policy '{input}' (expected warn, lru, or least_searched)"
+            )),
+        }
+    }
+}
+
+/// One cached source as seen by the eviction planner.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuotaEntry {
+    /// Canonical alias.
+    pub alias: String,
+    /// On-disk size, including archives and index.
+    pub bytes: u64,
+    /// Latest search that returned hits from this source.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_searched: Option<DateTime<Utc>>,
+    /// Searches of this source still in the history log.
+    pub searches: usize,
+}
+
+/// Pick the sources to remove so `entries` fit within `budget`.
+///
+/// Entries are ordered by `policy` (least valuable first; [`EvictionPolicy::Warn`]
+/// uses least-recently-searched order) and taken until the remaining total is
+/// within budget. `protected` aliases are never selected, so the result may
+/// still leave the cache over budget. Returns an empty list when the cache
+/// already fits.
+#[must_use]
+pub fn select_evictions(
+    entries: &[QuotaEntry],
+    budget: ByteSize,
+    policy: EvictionPolicy,
+    protected: &[String],
+) -> Vec<QuotaEntry> {
+    let mut total: u64 = entries.iter().map(|entry| entry.bytes).sum();
+    if total <= budget.bytes() {
+        return Vec::new();
+    }
+
+    let mut candidates: Vec<&QuotaEntry> = entries
+        .iter()
+        .filter(|entry| !protected.contains(&entry.alias))
+        .collect();
+    match policy {
+        EvictionPolicy::Warn | EvictionPolicy::Lru => {
+            candidates.sort_by_key(|entry| (entry.last_searched, Reverse(entry.bytes)));
+        },
+        EvictionPolicy::LeastSearched => {
+            candidates
+                .sort_by_key(|entry| (entry.searches, entry.last_searched, Reverse(entry.bytes)));
+        },
+    }
+
+    let mut selected = Vec::new();
+    for entry in candidates {
+        if total <= budget.bytes() {
+            break;
+        }
+        total = total.saturating_sub(entry.bytes);
+        selected.push(entry.clone());
+    }
+    selected
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn entry(alias: &str, mb: u64, days_ago: Option<i64>, searches: usize) -> QuotaEntry {
+        QuotaEntry {
+            alias: alias.to_string(),
+            bytes: mb * MIB,
+            last_searched: days_ago.map(|days| Utc::now() - Duration::days(days)),
+            searches,
+        }
+    }
+
+    fn aliases(entries: &[QuotaEntry]) -> Vec<&str> {
+        entries.iter().map(|entry| entry.alias.as_str()).collect()
+    }
+
+    #[test]
+    fn parses_and_formats_sizes() {
+        assert_eq!("512MB".parse::<ByteSize>().unwrap().bytes(), 512 * MIB);
+        assert_eq!("1.5g".parse::<ByteSize>().unwrap().bytes(), GIB + GIB / 2);
+        assert_eq!("4096".parse::<ByteSize>().unwrap().to_string(), "4KB");
+        assert!("0".parse::<ByteSize>().is_err());
+        assert!("ten MB".parse::<ByteSize>().is_err());
+    }
+
+    #[test]
+    fn nothing_is_evicted_within_budget() {
+        let entries = [entry("a", 10, None, 0), entry("b", 10, Some(1), 3)];
+        let budget = ByteSize::from_bytes(20 * MIB).unwrap();
+        assert!(select_evictions(&entries, budget, EvictionPolicy::Lru, &[]).is_empty());
+    }
+
+    #[test]
+    fn lru_evicts_never_and_least_recently_searched_first() {
+        let entries = [
+            entry("recent", 40, Some(1), 1),
+            entry("stale", 40, Some(90), 50),
+            entry("never", 40, None, 0),
+        ];
+        let budget = ByteSize::from_bytes(50 * MIB).unwrap();
+        let evicted = select_evictions(&entries, budget, EvictionPolicy::Lru, &[]);
+        assert_eq!(aliases(&evicted), ["never", "stale"]);
+    }
+
+    #[test]
+    fn least_searched_uses_search_counts_and_skips_protected() {
+        let entries = [
+            entry("popular", 40, Some(90), 50),
+            entry("rare", 40, Some(1), 1),
+            entry("new", 40, None, 0),
+        ];
+        let budget = ByteSize::from_bytes(90 * MIB).unwrap();
+        let protected = ["new".to_string()];
+        let evicted = select_evictions(&entries, budget, EvictionPolicy::LeastSearched, &protected);
+        assert_eq!(aliases(&evicted), ["rare"]);
+    }
+}
@@ -0,0 +1,178 @@
+//! Per-source ranking: field boosts and query stopwords.
+//!
+//! Heavily structured docs often answer a query in the section *named* after
+//! it, while the same words turn up in passing all over the body text. A
+//! source's `[index]` settings can weight the fields a query matches in, and
+//! drop filler words that only add noise to the bag-of-terms query:
+//!
+//! ```toml
+//! [index]
+//! heading_boost = 3.0      # the block's own heading
+//! path_boost = 1.5         # the full heading path, ancestors included
+//! code_boost = 0.5         # text inside code blocks
+//! stopwords = ["how", "to", "the", "a"]
+//! ```
+//!
+//! Ranking is applied at query time, so changes take effect on the next
+//! search without re-indexing. `blz bench rank` compares configurations
+//! against recorded queries before one is saved.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Error, IndexConfig, Result};
+
+/// Largest boost accepted for any field.
+pub const MAX_FIELD_BOOST: f32 = 100.0;
+
+/// How a source's search hits are ranked.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Ranking {
+    /// Boost for matches in a block's own heading; `None` leaves the heading
+    /// field out of queries.
+    pub heading_boost: Option<f32>,
+    /// Boost for matches in the block's heading path.
+    pub path_boost: f32,
+    /// Boost for matches inside code blocks; `None` leaves the code field
+    /// out of queries.
+    pub code_boost: Option<f32>,
+    /// Lowercased words dropped from unquoted queries.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stopwords: Vec<String>,
+}
+
+impl Default for Ranking {
+    fn default() -> Self {
+        Self {
+            heading_boost: None,
+            path_boost: 1.0,
+            code_boost: None,
+            stopwords: Vec::new(),
+        }
+    }
+}
+
+impl Ranking {
+    /// Read the ranking settings from a source's `[index]` configuration.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Config`] for a boost that is not a positive number
+    /// up to [`MAX_FIELD_BOOST`].
+    pub fn from_config(config: &IndexConfig) -> Result<Self> {
+        let mut stopwords: Vec<String> = config
+            .stopwords
+            .iter()
+            .flatten()
+            .map(|word| word.trim().to_lowercase())
+            .filter(|word| !word.is_empty())
+            .collect();
+        stopwords.sort_unstable();
+        stopwords.dedup();
+        Ok(Self {
+            heading_boost: config
+                .heading_boost
+                .map(|boost| check_boost("heading_boost", boost))
+                .transpose()?,
+            path_boost: config
+                .path_boost
+                .map_or(Ok(1.0), |boost| check_boost("path_boost", boost))?,
+            code_boost: config
+                .code_boost
+                .map(|boost| check_boost("code_boost", boost))
+                .transpose()?,
+            stopwords,
+        })
+    }
+
+    /// Whether every setting is the default.
+    #[must_use]
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// `query` without its stopwords.
+    ///
+    /// Quoted phrases and queries made only of stopwords are returned
+    /// unchanged, so a query never becomes empty.
+    #[must_use]
+    pub fn strip_stopwords(&self, query: &str) -> String {
+        let trimmed = query.trim();
+        if self.stopwords.is_empty() || trimmed.contains('"') {
+            return trimmed.to_string();
+        }
+        let kept: Vec<&str> = trimmed
+            .split_whitespace()
+            .filter(|word| self.stopwords.binary_search(&word.to_lowercase()).is_err())
+            .collect();
+        if kept.is_empty() {
+            trimmed.to_string()
+        } else {
+            kept.join(" ")
+        }
+    }
+}
+
+fn check_boost(key: &str, boost: f32) -> Result<f32> {
+    if boost.is_finite() && boost > 0.0 && boost <= MAX_FIELD_BOOST {
+        Ok(boost)
+    } else {
+        Err(Error::Config(format!(
+            "{key} must be greater than 0 and at most {MAX_FIELD_BOOST}, got {boost}"
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn config_sets_boosts_and_normalizes_stopwords() {
+        let config = IndexConfig {
+            heading_boost: Some(3.0),
+            code_boost: Some(0.5),
+            stopwords: Some(vec![
+                "The".to_string(),
+                " how ".to_string(),
+                "the".to_string(),
+            ]),
+            ..IndexConfig::default()
+        };
+        let ranking = Ranking::from_config(&config).expect("valid settings");
+        assert_eq!(ranking.heading_boost, Some(3.0));
+        assert!((ranking.path_boost - 1.0).abs() < f32::EPSILON);
+        assert_eq!(ranking.code_boost, Some(0.5));
+        assert_eq!(ranking.stopwords, ["how", "the"]);
+
+        for boost in [0.0, -1.0, f32::NAN, 1_000.0] {
+            let config = IndexConfig {
+                path_boost: Some(boost),
+                ..IndexConfig::default()
+            };
+            assert!(Ranking::from_config(&config).is_err(), "boost {boost}");
+        }
+        assert!(
+            Ranking::from_config(&IndexConfig::default())
+                .expect("defaults")
+                .is_default()
+        );
+    }
+
+    #[test]
+    fn stopwords_are_dropped_from_plain_queries_only() {
+        let ranking = Ranking {
+            stopwords: vec!["how".to_string(), "the".to_string(), "to".to_string()],
+            ..Ranking::default()
+        };
+        assert_eq!(
+            ranking.strip_stopwords("How to configure the proxy"),
+            "configure proxy"
+        );
+        assert_eq!(
+            ranking.strip_stopwords("\"how to\" proxy"),
+            "\"how to\" proxy"
+        );
+        assert_eq!(ranking.strip_stopwords("how to"), "how to");
+    }
+}
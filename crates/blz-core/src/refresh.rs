@@ -1,13 +1,18 @@
 //! Refresh helpers shared by CLI and MCP consumers.
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+use tracing::debug;
 
 use crate::{
-    FetchResult, Fetcher, HeadingFilterStats, LanguageFilter, MarkdownParser, ParseResult,
-    PerformanceMetrics, Result, SearchIndex, Source, SourceType, Storage, TocEntry,
+    FetchAuth, FetchResult, Fetcher, FilterPipeline, FilterRule, HeadingBlock, HeadingFilterStats,
+    LanguageFilter, LockMode, MarkdownParser, MirrorList, ParseResult, PerformanceMetrics, Result,
+    SearchIndex, Source, SourceIntegrity, SourceLock, SourceType, Storage, TocEntry,
 };
 
+use crate::analysis::TextAnalysis;
 use crate::json_builder::build_llms_json;
+use crate::mapping::carry_section_ids;
 use crate::url_resolver::resolve_best_url;
 
 /// Abstraction over storage interactions used by refresh routines.
@@ -16,6 +21,14 @@ pub trait RefreshStorage {
     fn load_metadata(&self, alias: &str) -> Result<Source>;
     /// Load alias list from the cached llms.json for a source.
     fn load_llms_aliases(&self, alias: &str) -> Result<Vec<String>>;
+    /// Load the cached outline, whose section IDs carry over to new content.
+    fn load_toc(&self, _alias: &str) -> Result<Vec<TocEntry>> {
+        Ok(Vec::new())
+    }
+    /// Keep the current llms.txt as a previous version before it is replaced.
+    fn archive_llms_txt(&self, _alias: &str) -> Result<()> {
+        Ok(())
+    }
     /// Persist the latest llms.txt content.
     fn save_llms_txt(&self, alias: &str, content: &str) -> Result<()>;
     /// Persist the computed llms.json metadata payload.
@@ -26,6 +39,67 @@ pub trait RefreshStorage {
     fn index_path(&self, alias: &str) -> Result<PathBuf>;
     /// Load cached llms.txt content for a source.
     fn load_llms_txt(&self, alias: &str) -> Result<String>;
+    /// Load the cached copy of one collection member.
+    fn load_member_txt(&self, alias: &str, prefix: &str) -> Result<String>;
+    /// Persist the latest copy of one collection member.
+    fn save_member_txt(&self, alias: &str, prefix: &str, content: &str) -> Result<()>;
+    /// Load the configured mirror list, if any.
+    fn load_mirrors(&self, alias: &str) -> Result<Option<MirrorList>>;
+    /// Persist mirror URLs and their latest health.
+    fn save_mirrors(&self, alias: &str, mirrors: &MirrorList) -> Result<()>;
+    /// Note that the source was just checked against upstream.
+    fn record_fetch(&self, _alias: &str) -> Result<()> {
+        Ok(())
+    }
+    /// Load credentials to send when fetching the source, if any.
+    fn load_fetch_auth(&self, _alias: &str) -> Result<Option<FetchAuth>> {
+        Ok(None)
+    }
+    /// Load the content filter pipeline configured for the source, if any.
+    fn load_filters(&self, _alias: &str) -> Result<Option<FilterPipeline>> {
+        Ok(None)
+    }
+    /// Load how the source's text is analyzed when indexed.
+    fn load_text_analysis(&self, _alias: &str) -> Result<TextAnalysis> {
+        Ok(TextAnalysis::default())
+    }
+    /// Load the checksum or signature fetched content must match, if any.
+    fn load_integrity(&self, _alias: &str) -> Result<Option<SourceIntegrity>> {
+        Ok(None)
+    }
+    /// Keep the signature that verified the current llms.txt.
+    fn save_signature(&self, _alias: &str, _signature: &str) -> Result<()> {
+        Ok(())
+    }
+    /// Hold the source exclusively while it is rewritten, if supported.
+    fn lock_for_write(&self, _alias: &str) -> Result<Option<SourceLock>> {
+        Ok(None)
+    }
+    /// Like [`lock_for_write`](Self::lock_for_write), for async callers.
+    ///
+    /// Implementations that wait for a busy source must not block the
+    /// runtime while they do. The default takes the lock eagerly, which only
+    /// suits implementations that never wait.
+    fn lock_for_write_async(
+        &self,
+        alias: &str,
+    ) -> impl Future<Output = Result<Option<SourceLock>>> + Send {
+        std::future::ready(self.lock_for_write(alias))
+    }
+    /// Mark the source as being rewritten.
+    fn begin_sync(&self, _alias: &str) -> Result<()> {
+        Ok(())
+    }
+    /// Clear the mark once content, metadata, and index agree again.
+    fn finish_sync(&self, _alias: &str) -> Result<()> {
+        Ok(())
+    }
+    /// Whether an earlier sync of the source stopped partway through.
+    fn interrupted_sync(&self, _alias: &str) -> bool {
+        false
+    }
+    /// Drop cached search results after an index is rebuilt in place.
+    fn invalidate_query_cache(&self) {}
 }
 
 impl RefreshStorage for Storage {
@@ -41,6 +115,18 @@ impl RefreshStorage for Storage {
         }
     }
 
+    fn load_toc(&self, alias: &str) -> Result<Vec<TocEntry>> {
+        Self::load_llms_json(self, alias).map(|llms| llms.toc)
+    }
+
+    fn archive_llms_txt(&self, alias: &str) -> Result<()> {
+        let keep = crate::Config::load()
+            .unwrap_or_default()
+            .defaults
+            .max_archives;
+        Self::archive_version(self, alias, keep).map(|_| ())
+    }
+
     fn save_llms_txt(&self, alias: &str, content: &str) -> Result<()> {
         Self::save_llms_txt(self, alias, content)
     }
@@ -60,18 +146,101 @@ impl RefreshStorage for Storage {
     fn load_llms_txt(&self, alias: &str) -> Result<String> {
         Self::load_llms_txt(self, alias)
     }
+
+    fn load_member_txt(&self, alias: &str, prefix: &str) -> Result<String> {
+        Self::load_member_txt(self, alias, prefix)
+    }
+
+    fn save_member_txt(&self, alias: &str, prefix: &str, content: &str) -> Result<()> {
+        Self::save_member_txt(self, alias, prefix, content)
+    }
+
+    fn load_mirrors(&self, alias: &str) -> Result<Option<MirrorList>> {
+        Self::load_mirrors(self, alias)
+    }
+
+    fn save_mirrors(&self, alias: &str, mirrors: &MirrorList) -> Result<()> {
+        Self::save_mirrors(self, alias, mirrors)
+    }
+
+    fn record_fetch(&self, alias: &str) -> Result<()> {
+        Self::record_fetch(self, alias)
+    }
+
+    fn load_fetch_auth(&self, alias: &str) -> Result<Option<FetchAuth>> {
+        Self::load_fetch_auth(self, alias)
+    }
+
+    fn load_filters(&self, alias: &str) -> Result<Option<FilterPipeline>> {
+        Self::load_filters(self, alias)
+    }
+
+    fn load_text_analysis(&self, alias: &str) -> Result<TextAnalysis> {
+        Self::load_text_analysis(self, alias)
+    }
+
+    fn load_integrity(&self, alias: &str) -> Result<Option<SourceIntegrity>> {
+        Self::load_integrity(self, alias)
+    }
+
+    fn save_signature(&self, alias: &str, signature: &str) -> Result<()> {
+        Self::save_signature(self, alias, signature)
+    }
+
+    fn lock_for_write(&self, alias: &str) -> Result<Option<SourceLock>> {
+        Self::lock_source(self, alias, LockMode::Exclusive).map(Some)
+    }
+
+    async fn lock_for_write_async(&self, alias: &str) -> Result<Option<SourceLock>> {
+        Self::lock_source_async(self, alias, LockMode::Exclusive)
+            .await
+            .map(Some)
+    }
+
+    fn begin_sync(&self, alias: &str) -> Result<()> {
+        Self::begin_sync(self, alias)
+    }
+
+    fn finish_sync(&self, alias: &str) -> Result<()> {
+        Self::finish_sync(self, alias)
+    }
+
+    fn interrupted_sync(&self, alias: &str) -> bool {
+        Self::interrupted_sync(self, alias).is_some()
+    }
+
+    fn invalidate_query_cache(&self) {
+        Self::invalidate_query_cache(self);
+    }
 }
 
 /// Interface for indexing refreshed content.
 pub trait RefreshIndexer {
-    /// Index a set of heading blocks for the given alias.
+    /// Index a set of heading blocks for the given alias, analyzing their
+    /// text as `analysis` configures.
     fn index(
         &self,
         alias: &str,
         index_path: &std::path::Path,
         metrics: PerformanceMetrics,
         blocks: &[crate::HeadingBlock],
+        analysis: &TextAnalysis,
     ) -> Result<()>;
+
+    /// Bring the index up to date with changed content.
+    ///
+    /// Used when a refresh fetched new content; implementations may keep
+    /// documents for unchanged blocks. Defaults to a full [`index`](Self::index).
+    fn index_changed(
+        &self,
+        alias: &str,
+        index_path: &std::path::Path,
+        metrics: PerformanceMetrics,
+        blocks: &[crate::HeadingBlock],
+        analysis: &TextAnalysis,
+    ) -> Result<()> {
+        self.index(alias, index_path, metrics, blocks, analysis)
+    }
 }
 
 /// Default indexer that writes to the Tantivy search index.
@@ -85,9 +254,37 @@ impl RefreshIndexer for DefaultRefreshIndexer {
         index_path: &std::path::Path,
         metrics: PerformanceMetrics,
         blocks: &[crate::HeadingBlock],
+        analysis: &TextAnalysis,
     ) -> Result<()> {
-        let index = SearchIndex::create_or_open(index_path)?.with_metrics(metrics);
-        index.index_blocks(alias, blocks)
+        SearchIndex::rebuild_with(index_path, alias, blocks, metrics, analysis)
+    }
+
+    fn index_changed(
+        &self,
+        alias: &str,
+        index_path: &std::path::Path,
+        metrics: PerformanceMetrics,
+        blocks: &[crate::HeadingBlock],
+        analysis: &TextAnalysis,
+    ) -> Result<()> {
+        let analyzer = analysis.analyzer_for(blocks.iter().map(|block| block.content.as_str()));
+        match SearchIndex::create_or_open(index_path) {
+            // Content that changed script, or changed analysis settings,
+            // needs its text re-analyzed
+            Ok(index) if index.analyzer() != analyzer || index.synonyms() != &analysis.synonyms => {
+                debug!("Rebuilding index for {alias} with the {analyzer}");
+                drop(index);
+                SearchIndex::rebuild_with(index_path, alias, blocks, metrics, analysis)
+            },
+            Ok(index) => index
+                .with_metrics(metrics)
+                .index_blocks_delta(alias, blocks)
+                .map(|_| ()),
+            Err(e) => {
+                debug!("Rebuilding unreadable index for {alias}: {e}");
+                SearchIndex::rebuild_with(index_path, alias, blocks, metrics, analysis)
+            },
+        }
     }
 }
 
@@ -147,6 +344,18 @@ pub struct RefreshUrlResolution {
     pub upgraded: bool,
 }
 
+impl RefreshUrlResolution {
+    /// Keep the URL and variant the source already uses.
+    #[must_use]
+    pub fn current(metadata: &Source) -> Self {
+        Self {
+            final_url: metadata.url.clone(),
+            variant: metadata.variant.clone(),
+            upgraded: false,
+        }
+    }
+}
+
 /// Context for refresh operations with preloaded metadata.
 ///
 /// This struct bundles the context needed for `refresh_source_with_metadata`,
@@ -182,12 +391,24 @@ pub async fn resolve_refresh_url(
     fetcher: &Fetcher,
     metadata: &Source,
 ) -> Result<RefreshUrlResolution> {
-    if metadata.variant != crate::SourceVariant::Llms {
-        return Ok(RefreshUrlResolution {
-            final_url: metadata.url.clone(),
-            variant: metadata.variant.clone(),
-            upgraded: false,
-        });
+    let is_composite = matches!(
+        metadata.origin.source_type,
+        Some(
+            SourceType::LocalFile { .. }
+                | SourceType::Stdin
+                | SourceType::Collection { .. }
+                | SourceType::Crate { .. }
+                | SourceType::OpenApi { .. }
+                | SourceType::Directory { .. }
+                | SourceType::Html { .. }
+                | SourceType::Pdf { .. }
+                | SourceType::Followed { .. }
+                | SourceType::Generated { .. }
+                | SourceType::GithubRepo { .. }
+        )
+    );
+    if is_composite || metadata.variant != crate::SourceVariant::Llms {
+        return Ok(RefreshUrlResolution::current(metadata));
     }
 
     match resolve_best_url(fetcher, &metadata.url).await {
@@ -198,11 +419,7 @@ pub async fn resolve_refresh_url(
                 upgraded: true,
             })
         },
-        Ok(_) | Err(_) => Ok(RefreshUrlResolution {
-            final_url: metadata.url.clone(),
-            variant: metadata.variant.clone(),
-            upgraded: false,
-        }),
+        Ok(_) | Err(_) => Ok(RefreshUrlResolution::current(metadata)),
     }
 }
 
@@ -243,6 +460,10 @@ where
 
 /// Refresh a source using preloaded metadata and URL resolution.
 ///
+/// A successful check is recorded as the source's last fetch, whether or not
+/// upstream changed. If an earlier sync of the source was interrupted, cache
+/// validators are ignored so the content is fetched and applied in full.
+///
 /// # Errors
 ///
 /// Returns an error if fetching, parsing, or indexing fails.
@@ -259,13 +480,206 @@ where
     S: RefreshStorage + Sync,
     I: RefreshIndexer + Sync,
 {
-    let fetch_result = fetcher
-        .fetch_with_cache(
-            &ctx.resolution.final_url,
-            ctx.existing_metadata.etag.as_deref(),
-            ctx.existing_metadata.last_modified.as_deref(),
+    let recovery;
+    let ctx = if storage.interrupted_sync(alias) {
+        debug!("Previous sync of {alias} was interrupted; refetching in full");
+        let mut fresh = ctx.clone();
+        fresh.existing_metadata.etag = None;
+        fresh.existing_metadata.last_modified = None;
+        fresh.existing_metadata.sha256.clear();
+        recovery = fresh;
+        &recovery
+    } else {
+        ctx
+    };
+    let outcome = refresh_by_source_type(
+        storage,
+        fetcher,
+        alias,
+        ctx,
+        metrics,
+        indexer,
+        filter_preference,
+    )
+    .await?;
+    if let Err(e) = storage.record_fetch(alias) {
+        debug!("Failed to record fetch time for {alias}: {e}");
+    }
+    Ok(outcome)
+}
+
+#[allow(clippy::too_many_lines)]
+async fn refresh_by_source_type<S, I>(
+    storage: &S,
+    fetcher: &Fetcher,
+    alias: &str,
+    ctx: &RefreshContext,
+    metrics: PerformanceMetrics,
+    indexer: &I,
+    filter_preference: bool,
+) -> Result<RefreshOutcome>
+where
+    S: RefreshStorage + Sync,
+    I: RefreshIndexer + Sync,
+{
+    let authenticated;
+    let fetcher = match storage.load_fetch_auth(alias)? {
+        Some(auth) => {
+            authenticated = fetcher.clone().with_auth(&auth)?;
+            &authenticated
+        },
+        None => fetcher,
+    };
+    if matches!(
+        ctx.existing_metadata.origin.source_type,
+        Some(SourceType::Stdin)
+    ) {
+        // Piped content has no origin to re-read; re-add it to update.
+        debug!("Skipping stdin source {}", alias);
+        return Ok(RefreshOutcome::Unchanged {
+            alias: alias.to_string(),
+        });
+    }
+    if matches!(
+        ctx.existing_metadata.origin.source_type,
+        Some(SourceType::Collection { .. })
+    ) {
+        return crate::collection::refresh_collection(
+            storage,
+            fetcher,
+            alias,
+            ctx,
+            metrics,
+            indexer,
+            filter_preference,
         )
-        .await?;
+        .await;
+    }
+    if matches!(
+        ctx.existing_metadata.origin.source_type,
+        Some(SourceType::Crate { .. })
+    ) {
+        return crate::rustdoc::refresh_crate(
+            storage,
+            fetcher,
+            alias,
+            ctx,
+            metrics,
+            indexer,
+            filter_preference,
+        )
+        .await;
+    }
+    if matches!(
+        ctx.existing_metadata.origin.source_type,
+        Some(SourceType::OpenApi { .. })
+    ) {
+        return crate::openapi::refresh_openapi(
+            storage,
+            fetcher,
+            alias,
+            ctx,
+            metrics,
+            indexer,
+            filter_preference,
+        )
+        .await;
+    }
+    if matches!(
+        ctx.existing_metadata.origin.source_type,
+        Some(SourceType::Html { .. })
+    ) {
+        return crate::html::refresh_html(
+            storage,
+            fetcher,
+            alias,
+            ctx,
+            metrics,
+            indexer,
+            filter_preference,
+        )
+        .await;
+    }
+    if matches!(
+        ctx.existing_metadata.origin.source_type,
+        Some(SourceType::Pdf { .. })
+    ) {
+        return crate::pdf::refresh_pdf(
+            storage,
+            fetcher,
+            alias,
+            ctx,
+            metrics,
+            indexer,
+            filter_preference,
+        )
+        .await;
+    }
+    if matches!(
+        ctx.existing_metadata.origin.source_type,
+        Some(SourceType::Followed { .. })
+    ) {
+        return crate::follow::refresh_followed(
+            storage,
+            fetcher,
+            alias,
+            ctx,
+            metrics,
+            indexer,
+            filter_preference,
+        )
+        .await;
+    }
+    if matches!(
+        ctx.existing_metadata.origin.source_type,
+        Some(SourceType::Generated { .. })
+    ) {
+        return Err(crate::Error::Config(format!(
+            "Source '{alias}' was generated from a sitemap; run `blz sync {alias}` to rescrape it"
+        )));
+    }
+    if matches!(
+        ctx.existing_metadata.origin.source_type,
+        Some(SourceType::Directory { .. })
+    ) {
+        return crate::docs_dir::refresh_docs_dir(
+            storage,
+            alias,
+            ctx,
+            metrics,
+            indexer,
+            filter_preference,
+        )
+        .await;
+    }
+    if matches!(
+        ctx.existing_metadata.origin.source_type,
+        Some(SourceType::GithubRepo { .. })
+    ) {
+        return crate::repo_docs::refresh_github_repo(
+            storage,
+            alias,
+            ctx,
+            metrics,
+            indexer,
+            filter_preference,
+        )
+        .await;
+    }
+
+    let integrity = storage.load_integrity(alias)?;
+    let pinned;
+    let ctx = if integrity.is_some() && ctx.resolution.upgraded {
+        // The declared checksum or signature covers the source URL, not its
+        // llms-full.txt sibling
+        let mut current = ctx.clone();
+        current.resolution = RefreshUrlResolution::current(&ctx.existing_metadata);
+        pinned = current;
+        &pinned
+    } else {
+        ctx
+    };
+    let fetch_result = fetch_source(storage, fetcher, alias, ctx).await?;
 
     match fetch_result {
         FetchResult::NotModified { .. } => {
@@ -278,12 +692,43 @@ where
                 alias: alias.to_string(),
             })
         },
+        // Servers that ignore conditional requests resend identical content
+        FetchResult::Modified {
+            sha256,
+            etag,
+            last_modified,
+            ..
+        } if sha256 == ctx.existing_metadata.sha256 && !ctx.resolution.upgraded => {
+            let existing = &ctx.existing_metadata;
+            if existing.filter_non_english.unwrap_or(true) != filter_preference
+                || existing.etag != etag
+                || existing.last_modified != last_modified
+            {
+                let mut updated_metadata = existing.clone();
+                updated_metadata.filter_non_english = Some(filter_preference);
+                updated_metadata.etag = etag;
+                updated_metadata.last_modified = last_modified;
+                storage.save_metadata(alias, &updated_metadata)?;
+            }
+            Ok(RefreshOutcome::Unchanged {
+                alias: alias.to_string(),
+            })
+        },
         FetchResult::Modified {
             content,
             sha256,
             etag,
             last_modified,
         } => {
+            // Checked before anything is written, so a mismatch keeps the cached copy
+            let signature = match &integrity {
+                Some(integrity) => {
+                    integrity
+                        .verify_download(fetcher, &ctx.resolution.final_url, &content)
+                        .await?
+                },
+                None => None,
+            };
             let payload = RefreshPayload {
                 content,
                 sha256,
@@ -298,11 +743,53 @@ where
 
             let apply_params =
                 ApplyRefreshParams::new(updated_metadata, ctx.existing_aliases.clone());
-            apply_refresh(storage, alias, &apply_params, &payload, metrics, indexer)
+            let outcome =
+                apply_refresh(storage, alias, &apply_params, &payload, metrics, indexer).await?;
+            if let Some(signature) = signature {
+                storage.save_signature(alias, &signature)?;
+            }
+            Ok(outcome)
         },
     }
 }
 
+/// Fetch a source, trying its healthiest mirror first when mirrors are configured.
+///
+/// Mirror health is persisted even when every candidate fails, so the next
+/// refresh starts from what this attempt learned.
+async fn fetch_source<S>(
+    storage: &S,
+    fetcher: &Fetcher,
+    alias: &str,
+    ctx: &RefreshContext,
+) -> Result<FetchResult>
+where
+    S: RefreshStorage + Sync,
+{
+    let etag = ctx.existing_metadata.etag.as_deref();
+    let last_modified = ctx.existing_metadata.last_modified.as_deref();
+    if let Some(SourceType::LocalFile { path }) = &ctx.existing_metadata.origin.source_type {
+        return crate::fetcher::fetch_local(Path::new(path), last_modified);
+    }
+    let Some(mut mirrors) = storage
+        .load_mirrors(alias)?
+        .filter(|mirrors| !mirrors.urls.is_empty())
+    else {
+        return fetcher
+            .fetch_with_cache(&ctx.resolution.final_url, etag, last_modified)
+            .await;
+    };
+
+    let candidates = mirrors.candidates(&ctx.resolution.final_url);
+    let outcome = fetcher
+        .fetch_with_mirrors(&candidates, &mut mirrors, etag, last_modified)
+        .await;
+    storage.save_mirrors(alias, &mirrors)?;
+    let (served_by, result) = outcome?;
+    debug!("Refreshed {} from {}", alias, served_by);
+    Ok(result)
+}
+
 /// Re-parse and re-index a source using cached content.
 ///
 /// # Errors
@@ -322,18 +809,27 @@ where
     let content = storage.load_llms_txt(alias)?;
     let mut parser = MarkdownParser::new()?;
     let mut parse_result = parser.parse(&content)?;
+    carry_section_ids(
+        &storage.load_toc(alias).unwrap_or_default(),
+        &mut parse_result,
+    );
 
     let before_count = parse_result.heading_blocks.len();
-    apply_language_filter(&mut parse_result, filter_preference);
+    let pipeline = source_pipeline(storage, alias, filter_preference)?;
+    apply_filters(&mut parse_result, &pipeline, filter_preference);
     let after_count = parse_result.heading_blocks.len();
 
+    let analysis = storage.load_text_analysis(alias)?;
+    let _lock = storage.lock_for_write(alias)?;
     let index_path = storage.index_path(alias)?;
     indexer.index(
         alias,
         index_path.as_path(),
         metrics,
         &parse_result.heading_blocks,
+        &analysis,
     )?;
+    storage.invalidate_query_cache();
 
     Ok(ReindexOutcome {
         alias: alias.to_string(),
@@ -385,6 +881,21 @@ fn resolve_origin(existing: &Source) -> crate::SourceOrigin {
         (Some(SourceType::LocalFile { path }), _) => {
             Some(SourceType::LocalFile { path: path.clone() })
         },
+        (
+            Some(
+                composite @ (SourceType::Stdin
+                | SourceType::Collection { .. }
+                | SourceType::Crate { .. }
+                | SourceType::OpenApi { .. }
+                | SourceType::Directory { .. }
+                | SourceType::Html { .. }
+                | SourceType::Pdf { .. }
+                | SourceType::Followed { .. }
+                | SourceType::Generated { .. }
+                | SourceType::GithubRepo { .. }),
+            ),
+            _,
+        ) => Some(composite.clone()),
         (None, Some(existing_type)) => Some(existing_type.clone()),
     };
     origin
@@ -436,10 +947,14 @@ impl ApplyRefreshParams {
 
 /// Apply a refresh: persist content and re-index the source.
 ///
+/// The writes are bracketed by a sync journal, so a process killed before
+/// the index commits leaves a marker the next refresh (or `blz doctor`)
+/// recovers from.
+///
 /// # Errors
 ///
 /// Returns an error if parsing, persistence, or indexing fails.
-pub fn apply_refresh<S, I>(
+pub async fn apply_refresh<S, I>(
     storage: &S,
     alias: &str,
     params: &ApplyRefreshParams,
@@ -448,15 +963,29 @@ pub fn apply_refresh<S, I>(
     indexer: &I,
 ) -> Result<RefreshOutcome>
 where
-    S: RefreshStorage,
-    I: RefreshIndexer,
+    S: RefreshStorage + Sync,
+    I: RefreshIndexer + Sync,
 {
     let mut parser = MarkdownParser::new()?;
     let mut parse_result = parser.parse(&payload.content)?;
+    carry_section_ids(
+        &storage.load_toc(alias).unwrap_or_default(),
+        &mut parse_result,
+    );
 
     let filter_enabled = params.metadata.filter_non_english.unwrap_or(true);
-    let filter_stats = Some(apply_language_filter(&mut parse_result, filter_enabled));
-
+    let pipeline = source_pipeline(storage, alias, filter_enabled)?;
+    let filter_stats = Some(apply_filters(&mut parse_result, &pipeline, filter_enabled));
+    let analysis = storage.load_text_analysis(alias)?;
+
+    let _lock = storage.lock_for_write_async(alias).await?;
+    storage.begin_sync(alias)?;
+    if storage
+        .load_llms_txt(alias)
+        .is_ok_and(|current| current != payload.content)
+    {
+        storage.archive_llms_txt(alias)?;
+    }
     storage.save_llms_txt(alias, &payload.content)?;
 
     let mut llms_json = build_llms_json(
@@ -483,12 +1012,14 @@ where
     storage.save_metadata(alias, &metadata)?;
 
     let index_path = storage.index_path(alias)?;
-    indexer.index(
+    indexer.index_changed(
         alias,
         index_path.as_path(),
         metrics,
         &parse_result.heading_blocks,
+        &analysis,
     )?;
+    storage.finish_sync(alias)?;
 
     Ok(RefreshOutcome::Refreshed {
         alias: alias.to_string(),
@@ -497,25 +1028,29 @@ where
     })
 }
 
-fn apply_language_filter(
+/// The filter pipeline a source is indexed with: the rules in its settings,
+/// or the language filter alone. Empty when filtering is disabled.
+fn source_pipeline<S: RefreshStorage>(
+    storage: &S,
+    alias: &str,
+    filter_enabled: bool,
+) -> Result<FilterPipeline> {
+    if !filter_enabled {
+        return Ok(FilterPipeline::default());
+    }
+    Ok(storage
+        .load_filters(alias)?
+        .unwrap_or_else(|| FilterPipeline::new(vec![FilterRule::Language(None)])))
+}
+
+fn apply_filters(
     parse_result: &mut ParseResult,
+    pipeline: &FilterPipeline,
     filter_enabled: bool,
 ) -> HeadingFilterStats {
     let original_count = parse_result.heading_blocks.len();
-    if filter_enabled {
-        let mut language_filter = LanguageFilter::new(true);
-        parse_result.heading_blocks.retain(|block| {
-            let urls_in_content = extract_urls_from_content(&block.content);
-            let url_check = urls_in_content.is_empty()
-                || urls_in_content
-                    .iter()
-                    .all(|url| language_filter.is_english_url(url));
-
-            let heading_check = language_filter.is_english_heading_path(&block.path);
-
-            url_check && heading_check
-        });
-    }
+    let blocks = std::mem::take(&mut parse_result.heading_blocks);
+    parse_result.heading_blocks = pipeline.apply(blocks);
 
     let accepted = parse_result.heading_blocks.len();
     let filtered_count = original_count.saturating_sub(accepted);
@@ -524,14 +1059,20 @@ fn apply_language_filter(
         headings_total: original_count,
         headings_accepted: accepted,
         headings_rejected: filtered_count,
-        reason: if filter_enabled {
-            "non-English content removed".to_string()
-        } else {
-            "filtering disabled".to_string()
+        reason: match (filter_enabled, pipeline.is_language_only()) {
+            (false, _) => "filtering disabled".to_string(),
+            (true, true) => "non-English content removed".to_string(),
+            (true, false) => format!("filters: {pipeline}"),
         },
     }
 }
 
+/// Whether a heading block survives the language filter, judged by the
+/// locale markers of the URLs it links to and its heading path.
+pub(crate) fn keeps_block_language(language_filter: &LanguageFilter, block: &HeadingBlock) -> bool {
+    language_filter.keeps_section(&block.path, &extract_urls_from_content(&block.content))
+}
+
 fn count_headings(entries: &[TocEntry]) -> usize {
     entries
         .iter()
@@ -601,19 +1142,20 @@ const fn trailing_punctuation(c: char) -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::cell::RefCell;
     use std::collections::HashMap;
+    use std::sync::Mutex;
 
     use anyhow::Result;
 
     #[derive(Default)]
     struct MockStorage {
         metadata: HashMap<String, Source>,
-        saved_txt: RefCell<Vec<String>>,
-        saved_json: RefCell<Vec<String>>,
-        saved_metadata: RefCell<Vec<Source>>,
+        saved_txt: Mutex<Vec<String>>,
+        saved_json: Mutex<Vec<String>>,
+        saved_metadata: Mutex<Vec<Source>>,
         index_paths: HashMap<String, PathBuf>,
         cached_txt: HashMap<String, String>,
+        mirrors: HashMap<String, MirrorList>,
     }
 
     impl RefreshStorage for MockStorage {
@@ -629,17 +1171,23 @@ mod tests {
         }
 
         fn save_llms_txt(&self, alias: &str, _content: &str) -> crate::Result<()> {
-            self.saved_txt.borrow_mut().push(alias.to_string());
+            self.saved_txt.lock().expect("lock").push(alias.to_string());
             Ok(())
         }
 
         fn save_llms_json(&self, alias: &str, _data: &crate::LlmsJson) -> crate::Result<()> {
-            self.saved_json.borrow_mut().push(alias.to_string());
+            self.saved_json
+                .lock()
+                .expect("lock")
+                .push(alias.to_string());
             Ok(())
         }
 
         fn save_metadata(&self, _alias: &str, metadata: &Source) -> crate::Result<()> {
-            self.saved_metadata.borrow_mut().push(metadata.clone());
+            self.saved_metadata
+                .lock()
+                .expect("lock")
+                .push(metadata.clone());
             Ok(())
         }
 
@@ -656,11 +1204,34 @@ mod tests {
                 .cloned()
                 .ok_or_else(|| crate::Error::NotFound(format!("missing llms.txt for {alias}")))
         }
+
+        fn load_member_txt(&self, alias: &str, prefix: &str) -> crate::Result<String> {
+            Err(crate::Error::NotFound(format!(
+                "missing member {prefix} for {alias}"
+            )))
+        }
+
+        fn save_member_txt(
+            &self,
+            _alias: &str,
+            _prefix: &str,
+            _content: &str,
+        ) -> crate::Result<()> {
+            Ok(())
+        }
+
+        fn load_mirrors(&self, alias: &str) -> crate::Result<Option<MirrorList>> {
+            Ok(self.mirrors.get(alias).cloned())
+        }
+
+        fn save_mirrors(&self, _alias: &str, _mirrors: &MirrorList) -> crate::Result<()> {
+            Ok(())
+        }
     }
 
     #[derive(Default)]
     struct MockIndexer {
-        indexed: RefCell<Vec<String>>,
+        indexed: Mutex<Vec<String>>,
     }
 
     impl RefreshIndexer for MockIndexer {
@@ -670,8 +1241,9 @@ mod tests {
             _index_path: &std::path::Path,
             _metrics: PerformanceMetrics,
             _blocks: &[crate::HeadingBlock],
+            _analysis: &TextAnalysis,
         ) -> crate::Result<()> {
-            self.indexed.borrow_mut().push(alias.to_string());
+            self.indexed.lock().expect("lock").push(alias.to_string());
             Ok(())
         }
     }
@@ -709,8 +1281,8 @@ mod tests {
         }
     }
 
-    #[test]
-    fn apply_refresh_persists_changes() -> Result<()> {
+    #[tokio::test]
+    async fn apply_refresh_persists_changes() -> Result<()> {
         let mut storage = MockStorage::default();
         storage.metadata.insert("test".to_string(), sample_source());
         storage
@@ -726,13 +1298,14 @@ mod tests {
             &sample_payload(),
             PerformanceMetrics::default(),
             &indexer,
-        )?;
+        )
+        .await?;
 
         assert!(matches!(outcome, RefreshOutcome::Refreshed { .. }));
-        assert_eq!(storage.saved_txt.borrow().len(), 1);
-        assert_eq!(storage.saved_json.borrow().len(), 1);
-        assert_eq!(storage.saved_metadata.borrow().len(), 1);
-        assert_eq!(indexer.indexed.borrow().len(), 1);
+        assert_eq!(storage.saved_txt.lock().expect("lock").len(), 1);
+        assert_eq!(storage.saved_json.lock().expect("lock").len(), 1);
+        assert_eq!(storage.saved_metadata.lock().expect("lock").len(), 1);
+        assert_eq!(indexer.indexed.lock().expect("lock").len(), 1);
         Ok(())
     }
 }
@@ -2,6 +2,8 @@ use fuzzy_matcher::FuzzyMatcher;
 use fuzzy_matcher::skim::SkimMatcherV2;
 use serde::{Deserialize, Serialize};
 
+use crate::SourceIntegrity;
+
 /// Registry entry representing a documented tool/package
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RegistryEntry {
@@ -15,6 +17,9 @@ pub struct RegistryEntry {
     pub description: String,
     /// URL to the llms.txt documentation file
     pub llms_url: String,
+    /// Checksum or minisign key the documentation must match
+    #[serde(default, skip_serializing_if = "SourceIntegrity::is_empty")]
+    pub integrity: SourceIntegrity,
 }
 
 impl RegistryEntry {
@@ -27,6 +32,7 @@ impl RegistryEntry {
             aliases: vec![slug.to_string()],
             description: description.to_string(),
             llms_url: llms_url.to_string(),
+            integrity: SourceIntegrity::default(),
         }
     }
 
@@ -36,6 +42,13 @@ impl RegistryEntry {
         self.aliases = aliases.iter().map(|s| (*s).to_string()).collect();
         self
     }
+
+    /// Sets the checksum or minisign key the documentation must match
+    #[must_use]
+    pub fn with_integrity(mut self, integrity: SourceIntegrity) -> Self {
+        self.integrity = integrity;
+        self
+    }
 }
 
 impl std::fmt::Display for RegistryEntry {
@@ -0,0 +1,291 @@
+//! GitHub repositories indexed from their README and docs folder.
+//!
+//! `blz add owner/repo --github` covers projects that publish no llms.txt:
+//! the repository tree is listed through the GitHub API at the head commit of
+//! a branch or tag, the root README and every markdown file under `docs/` are
+//! downloaded from the raw content host, and the files are merged like a
+//! documentation folder (see [`crate::docs_dir::merge_files`]), so heading
+//! paths read like `docs/guide.md > Install`. The commit SHA is kept in the
+//! source origin: sync costs one API request while the branch has not moved,
+//! and re-indexes only when one of the files changed.
+
+use std::path::Path;
+
+use base64::{Engine as _, engine::general_purpose::STANDARD};
+use futures::StreamExt as _;
+use sha2::{Digest, Sha256};
+
+use crate::docs_dir::{DocsFile, merge_files};
+use crate::fetcher::calculate_sha256;
+use crate::github::{GithubClient, RepoSpec, TreeEntry};
+use crate::markup;
+use crate::refresh::{
+    ApplyRefreshParams, RefreshContext, RefreshIndexer, RefreshOutcome, RefreshPayload,
+    RefreshStorage, apply_refresh,
+};
+use crate::{Error, PerformanceMetrics, Result, SourceType};
+
+/// File extensions read from a repository's `docs/` folder.
+pub const REPO_DOC_EXTENSIONS: [&str; 4] = ["md", "mdx", "markdown", "rst"];
+
+/// Maximum number of files merged into one repository source.
+pub const MAX_REPO_FILES: usize = 500;
+
+/// Folder whose markdown files are indexed next to the README.
+const DOCS_FOLDER: &str = "docs/";
+
+/// Files downloaded at the same time.
+const DOWNLOAD_CONCURRENCY: usize = 8;
+
+/// A repository's documentation as read at one commit.
+#[derive(Debug, Clone)]
+pub struct RepoDocs {
+    /// Commit the files were read from.
+    pub commit: String,
+    /// Merged document.
+    pub content: String,
+    /// Files in merge order, with sizes and content hashes.
+    pub files: Vec<DocsFile>,
+}
+
+/// Paths to index from a repository tree, in merge order.
+///
+/// The root README comes first, followed by markdown files under `docs/`
+/// sorted by path. Hidden files and folders are skipped, and the list is cut
+/// at [`MAX_REPO_FILES`].
+#[must_use]
+pub fn select_doc_paths(tree: &[TreeEntry]) -> Vec<String> {
+    let readme = tree
+        .iter()
+        .filter(|entry| !entry.path.contains('/') && is_readme(&entry.path))
+        .min_by_key(|entry| (readme_rank(&entry.path), entry.path.clone()))
+        .map(|entry| entry.path.clone());
+
+    let mut docs: Vec<String> = tree
+        .iter()
+        .filter(|entry| entry.path.starts_with(DOCS_FOLDER))
+        .filter(|entry| {
+            !entry
+                .path
+                .split('/')
+                .any(|segment| segment.starts_with('.'))
+        })
+        .filter(|entry| has_doc_extension(&entry.path))
+        .map(|entry| entry.path.clone())
+        .collect();
+    docs.sort();
+
+    readme
+        .into_iter()
+        .chain(docs)
+        .take(MAX_REPO_FILES)
+        .collect()
+}
+
+fn is_readme(name: &str) -> bool {
+    let stem = name.split_once('.').map_or(name, |(stem, _)| stem);
+    stem.eq_ignore_ascii_case("readme") && (stem == name || has_doc_extension(name))
+}
+
+/// Prefer `README.md` over other README spellings.
+fn readme_rank(name: &str) -> u8 {
+    match name {
+        "README.md" => 0,
+        _ if has_doc_extension(name) => 1,
+        _ => 2,
+    }
+}
+
+fn has_doc_extension(path: &str) -> bool {
+    Path::new(path)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| {
+            REPO_DOC_EXTENSIONS
+                .iter()
+                .any(|allowed| ext.eq_ignore_ascii_case(allowed))
+        })
+}
+
+/// Read a repository's README and `docs/` files at the head of `reference`.
+///
+/// # Errors
+///
+/// Returns an error if the repository or ref does not exist, the API rate
+/// limit is exhausted, the repository has no README or docs, or a file cannot
+/// be downloaded or converted.
+pub async fn load_repo_docs(
+    client: &GithubClient,
+    spec: &RepoSpec,
+    reference: &str,
+) -> Result<RepoDocs> {
+    let commit = client.commit_sha(spec, reference).await?;
+    load_repo_docs_at(client, spec, commit).await
+}
+
+async fn load_repo_docs_at(
+    client: &GithubClient,
+    spec: &RepoSpec,
+    commit: String,
+) -> Result<RepoDocs> {
+    let tree = client.tree(spec, &commit).await?;
+    let paths = select_doc_paths(&tree);
+    if paths.is_empty() {
+        return Err(Error::NotFound(format!(
+            "{} has no README or markdown files under docs/",
+            spec.full_name()
+        )));
+    }
+
+    let downloads: Vec<Result<(DocsFile, String)>> = futures::stream::iter(paths)
+        .map(|path| {
+            let commit = &commit;
+            async move {
+                let bytes = client.raw_file(spec, commit, &path).await?;
+                let markdown = markup::file_to_markdown(Path::new(&path), &bytes)?;
+                let file = DocsFile {
+                    size: bytes.len() as u64,
+                    sha256: STANDARD.encode(Sha256::digest(&bytes)),
+                    modified: None,
+                    path,
+                };
+                Ok((file, markdown))
+            }
+        })
+        .buffered(DOWNLOAD_CONCURRENCY)
+        .collect()
+        .await;
+    let (files, contents): (Vec<DocsFile>, Vec<String>) = downloads
+        .into_iter()
+        .collect::<Result<Vec<_>>>()?
+        .into_iter()
+        .unzip();
+
+    Ok(RepoDocs {
+        content: merge_files(&files, &contents),
+        commit,
+        files,
+    })
+}
+
+/// Refresh a repository source.
+///
+/// When the followed branch or tag still points at the indexed commit nothing
+/// is downloaded. Otherwise the files are read at the new commit, and the
+/// source is re-merged and re-indexed only if a file was added, removed, or
+/// its content changed.
+///
+/// # Errors
+///
+/// Returns an error if the source is not a repository, or if the API,
+/// downloads, persistence, or indexing fails.
+pub async fn refresh_github_repo<S, I>(
+    storage: &S,
+    alias: &str,
+    ctx: &RefreshContext,
+    metrics: PerformanceMetrics,
+    indexer: &I,
+    filter_preference: bool,
+) -> Result<RefreshOutcome>
+where
+    S: RefreshStorage + Sync,
+    I: RefreshIndexer + Sync,
+{
+    let Some(SourceType::GithubRepo {
+        repo,
+        reference,
+        commit: previous_commit,
+        files: previous,
+    }) = &ctx.existing_metadata.origin.source_type
+    else {
+        return Err(Error::Config(format!(
+            "Source '{alias}' is not a GitHub repository"
+        )));
+    };
+    let spec: RepoSpec = repo.parse()?;
+    let client = GithubClient::from_env()?;
+
+    let mut metadata = ctx.existing_metadata.clone();
+    let filter_changed = metadata.filter_non_english.unwrap_or(true) != filter_preference;
+    metadata.filter_non_english = Some(filter_preference);
+
+    let commit = client.commit_sha(&spec, reference).await?;
+    if &commit == previous_commit {
+        if filter_changed {
+            storage.save_metadata(alias, &metadata)?;
+        }
+        return Ok(RefreshOutcome::Unchanged {
+            alias: alias.to_string(),
+        });
+    }
+
+    let docs = load_repo_docs_at(&client, &spec, commit).await?;
+    let content_changed = docs.files.len() != previous.len()
+        || docs
+            .files
+            .iter()
+            .zip(previous)
+            .any(|(file, old)| file.path != old.path || file.sha256 != old.sha256);
+    metadata.origin.source_type = Some(SourceType::GithubRepo {
+        repo: repo.clone(),
+        reference: reference.clone(),
+        commit: docs.commit,
+        files: docs.files,
+    });
+
+    if !content_changed {
+        // New commit without doc changes: remember it so the next sync is cheap
+        storage.save_metadata(alias, &metadata)?;
+        return Ok(RefreshOutcome::Unchanged {
+            alias: alias.to_string(),
+        });
+    }
+
+    let payload = RefreshPayload {
+        sha256: calculate_sha256(&docs.content),
+        content: docs.content,
+        etag: None,
+        last_modified: None,
+    };
+    let params = ApplyRefreshParams::new(metadata, ctx.existing_aliases.clone());
+    apply_refresh(storage, alias, &params, &payload, metrics, indexer).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn blob(path: &str) -> TreeEntry {
+        TreeEntry {
+            path: path.to_string(),
+            kind: "blob".to_string(),
+            size: 1,
+        }
+    }
+
+    #[test]
+    fn selects_readme_then_sorted_docs() {
+        let tree = [
+            blob("src/lib.rs"),
+            blob("docs/reference/api.md"),
+            blob("README.rst"),
+            blob("README.md"),
+            blob("docs/guide.MDX"),
+            blob("docs/.drafts/wip.md"),
+            blob("docs/logo.png"),
+            blob("examples/README.md"),
+            blob("CHANGELOG.md"),
+        ];
+        assert_eq!(
+            select_doc_paths(&tree),
+            ["README.md", "docs/guide.MDX", "docs/reference/api.md"]
+        );
+    }
+
+    #[test]
+    fn docs_without_readme_are_kept() {
+        let tree = [blob("docs/index.md"), blob("readme.txt")];
+        assert_eq!(select_doc_paths(&tree), ["docs/index.md"]);
+        assert!(select_doc_paths(&[blob("src/main.rs")]).is_empty());
+    }
+}
@@ -0,0 +1,209 @@
+//! Query-time source selection.
+//!
+//! Large caches spend most of a search on sources that cannot answer the
+//! query. The router scores each source by how many query terms appear in its
+//! descriptors (alias, aliases, tags, category, description) and top-level
+//! headings, so the full-text search can be restricted to the best few.
+
+use std::collections::HashSet;
+
+use crate::{Result, Storage};
+
+/// Points for a query term found in a source's descriptors.
+const DESCRIPTOR_WEIGHT: u32 = 3;
+/// Points for a query term found only in a source's top-level headings.
+const HEADING_WEIGHT: u32 = 1;
+/// Deepest heading level considered a topic keyword.
+const MAX_HEADING_DEPTH: usize = 2;
+/// Words too common in queries and descriptions to say anything about topic.
+const STOP_WORDS: &[&str] = &[
+    "an", "and", "are", "as", "at", "be", "by", "can", "do", "for", "from", "how", "in", "is",
+    "it", "of", "on", "or", "the", "to", "use", "using", "what", "when", "with",
+];
+
+/// Topic keywords for one source.
+#[derive(Debug, Clone, Default)]
+pub struct SourceProfile {
+    alias: String,
+    descriptors: HashSet<String>,
+    headings: HashSet<String>,
+}
+
+impl SourceProfile {
+    /// Create an empty profile; the alias itself is always a descriptor.
+    #[must_use]
+    pub fn new(alias: &str) -> Self {
+        Self {
+            alias: alias.to_string(),
+            ..Self::default()
+        }
+        .with_descriptor(alias)
+    }
+
+    /// Build a profile from a cached source's metadata and table of contents.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the source metadata cannot be read. A missing or
+    /// unreadable TOC only leaves the profile without heading keywords.
+    pub fn load(storage: &Storage, alias: &str) -> Result<Self> {
+        let mut profile = Self::new(alias);
+        if let Some(source) = storage.load_source_metadata(alias)? {
+            let descriptors = source
+                .aliases
+                .iter()
+                .chain(&source.npm_aliases)
+                .chain(&source.github_aliases)
+                .chain(&source.tags)
+                .chain(source.category.iter())
+                .chain(source.description.iter());
+            for text in descriptors {
+                profile = profile.with_descriptor(text);
+            }
+        }
+        if let Ok(llms) = storage.load_llms_json(alias) {
+            let mut level = llms.toc.iter().collect::<Vec<_>>();
+            for _ in 0..MAX_HEADING_DEPTH {
+                for entry in &level {
+                    if let Some(title) = entry.heading_path.last() {
+                        profile = profile.with_heading(title);
+                    }
+                }
+                level = level.iter().flat_map(|entry| &entry.children).collect();
+            }
+        }
+        Ok(profile)
+    }
+
+    /// Add descriptor text (alias, tag, description, ...).
+    #[must_use]
+    pub fn with_descriptor(mut self, text: &str) -> Self {
+        self.descriptors.extend(terms(text));
+        self
+    }
+
+    /// Add heading text.
+    #[must_use]
+    pub fn with_heading(mut self, text: &str) -> Self {
+        self.headings.extend(terms(text));
+        self
+    }
+
+    /// Source alias this profile describes.
+    #[must_use]
+    pub fn alias(&self) -> &str {
+        &self.alias
+    }
+
+    /// Relevance of this source to `query_terms` (0 when nothing matches).
+    #[must_use]
+    pub fn score(&self, query_terms: &[String]) -> u32 {
+        query_terms
+            .iter()
+            .map(|term| {
+                if self.descriptors.contains(term) {
+                    DESCRIPTOR_WEIGHT
+                } else if self.headings.contains(term) {
+                    HEADING_WEIGHT
+                } else {
+                    0
+                }
+            })
+            .sum()
+    }
+}
+
+/// Lowercased alphanumeric terms of `text`, ignoring single characters and
+/// stop words.
+#[must_use]
+pub fn terms(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|term| term.chars().count() > 1)
+        .map(str::to_lowercase)
+        .filter(|term| !STOP_WORDS.contains(&term.as_str()))
+        .collect()
+}
+
+/// Pick the `limit` sources most relevant to `query`, best first.
+///
+/// Returns `None` when no source matches any query term, so callers can fall
+/// back to searching everything rather than searching nothing.
+#[must_use]
+pub fn select_sources(
+    query: &str,
+    profiles: &[SourceProfile],
+    limit: usize,
+) -> Option<Vec<String>> {
+    let query_terms = terms(query);
+    let mut scored: Vec<(u32, &str)> = profiles
+        .iter()
+        .map(|profile| (profile.score(&query_terms), profile.alias()))
+        .filter(|(score, _)| *score > 0)
+        .collect();
+    if scored.is_empty() {
+        return None;
+    }
+    scored.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(b.1)));
+    Some(
+        scored
+            .into_iter()
+            .take(limit)
+            .map(|(_, alias)| alias.to_string())
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profiles() -> Vec<SourceProfile> {
+        vec![
+            SourceProfile::new("react")
+                .with_descriptor("frontend")
+                .with_heading("Hooks")
+                .with_heading("Server Components"),
+            SourceProfile::new("bun")
+                .with_descriptor("JavaScript runtime and bundler")
+                .with_heading("Test runner"),
+            SourceProfile::new("deno")
+                .with_descriptor("runtime")
+                .with_heading("Hooks"),
+        ]
+    }
+
+    #[test]
+    fn descriptors_outrank_headings() {
+        let selected = select_sources("runtime hooks", &profiles(), 2);
+        // deno: runtime (3) + hooks (1); bun: runtime (3); react: hooks (1)
+        assert_eq!(selected, Some(vec!["deno".to_string(), "bun".to_string()]));
+    }
+
+    #[test]
+    fn ties_break_by_alias_and_limit_applies() {
+        let selected = select_sources("hooks", &profiles(), 5);
+        assert_eq!(
+            selected,
+            Some(vec!["deno".to_string(), "react".to_string()])
+        );
+        assert_eq!(
+            select_sources("hooks", &profiles(), 1),
+            Some(vec!["deno".to_string()])
+        );
+    }
+
+    #[test]
+    fn no_matching_source_returns_none() {
+        assert_eq!(select_sources("kubernetes ingress", &profiles(), 3), None);
+        assert_eq!(select_sources("how to use and", &profiles(), 3), None);
+        assert_eq!(select_sources("", &profiles(), 3), None);
+    }
+
+    #[test]
+    fn terms_are_lowercased_and_split_on_punctuation() {
+        assert_eq!(
+            terms("Server-Components, a \"useState\""),
+            vec!["server", "components", "usestate"]
+        );
+    }
+}
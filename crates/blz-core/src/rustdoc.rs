@@ -0,0 +1,544 @@
+//! Rust crate documentation sources built from rustdoc JSON.
+//!
+//! `blz add crate:serde` downloads the crate's rustdoc JSON from docs.rs and
+//! renders it as an llms-style markdown document: one section per public
+//! module, a subsection per item, and method sections for inherent impls and
+//! trait members. Sync re-downloads the JSON and re-indexes only when the
+//! rendered document changed.
+
+use std::collections::HashSet;
+use std::fmt::Write as _;
+use std::io::Read;
+use std::str::FromStr;
+
+use serde_json::{Map, Value};
+
+use crate::fetcher::calculate_sha256;
+use crate::refresh::{
+    ApplyRefreshParams, RefreshContext, RefreshIndexer, RefreshOutcome, RefreshPayload,
+    RefreshStorage, apply_refresh,
+};
+use crate::{Error, Fetcher, PerformanceMetrics, Result, SourceType};
+
+/// Prefix marking a crate specification (e.g. `crate:serde@1.0`).
+pub const CRATE_PREFIX: &str = "crate:";
+
+/// Default docs.rs base URL (overridable with `BLZ_DOCS_RS_URL`).
+pub const DEFAULT_DOCS_RS_URL: &str = "https://docs.rs";
+
+/// Version requested when a spec has none; docs.rs resolves it to the newest release.
+pub const LATEST_VERSION: &str = "latest";
+
+/// A crate name with an optional version requirement.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CrateSpec {
+    /// Crate name as published on crates.io.
+    pub name: String,
+    /// Version or requirement understood by docs.rs (`latest`, `1.0.200`, `~1.0`).
+    pub version: String,
+}
+
+impl CrateSpec {
+    /// Parse `input` if it carries the `crate:` prefix.
+    ///
+    /// Returns `None` for anything else so callers can fall back to URL handling.
+    #[must_use]
+    pub fn from_prefixed(input: &str) -> Option<Result<Self>> {
+        input.strip_prefix(CRATE_PREFIX).map(str::parse::<Self>)
+    }
+
+    /// docs.rs URL serving the gzip-compressed rustdoc JSON for this crate.
+    #[must_use]
+    pub fn docs_rs_json_url(&self, base: &str) -> String {
+        format!(
+            "{}/crate/{}/{}/json.gz",
+            base.trim_end_matches('/'),
+            self.name,
+            self.version
+        )
+    }
+
+    /// docs.rs URL using `BLZ_DOCS_RS_URL` when set.
+    #[must_use]
+    pub fn json_url(&self) -> String {
+        let base = std::env::var("BLZ_DOCS_RS_URL")
+            .ok()
+            .filter(|value| !value.trim().is_empty())
+            .unwrap_or_else(|| DEFAULT_DOCS_RS_URL.to_string());
+        self.docs_rs_json_url(&base)
+    }
+}
+
+impl FromStr for CrateSpec {
+    type Err = Error;
+
+    /// Parse `NAME` or `NAME@VERSION`.
+    fn from_str(spec: &str) -> Result<Self> {
+        let spec = spec.trim();
+        let (name, version) = spec
+            .split_once('@')
+            .map_or((spec, LATEST_VERSION), |(name, version)| {
+                (name.trim(), version.trim())
+            });
+        let valid_name = !name.is_empty()
+            && name.len() <= 64
+            && name.starts_with(|ch: char| ch.is_ascii_alphabetic())
+            && name
+                .chars()
+                .all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_');
+        if !valid_name {
+            return Err(Error::Config(format!(
+                "invalid crate name '{name}' (expected e.g. crate:serde or crate:serde@1.0)"
+            )));
+        }
+        let valid_version = !version.is_empty()
+            && version
+                .chars()
+                .all(|ch| ch.is_ascii_alphanumeric() || "._-+~^=<>*".contains(ch));
+        if !valid_version {
+            return Err(Error::Config(format!(
+                "invalid version '{version}' for crate '{name}'"
+            )));
+        }
+        Ok(Self {
+            name: name.to_string(),
+            version: version.to_string(),
+        })
+    }
+}
+
+/// Decode a rustdoc JSON download, inflating it when gzip-compressed.
+///
+/// # Errors
+///
+/// Returns an error if the gzip stream is corrupt or the JSON is not UTF-8.
+pub fn decode_rustdoc(bytes: &[u8]) -> Result<String> {
+    if bytes.starts_with(&[0x1f, 0x8b]) {
+        let mut json = String::new();
+        flate2::read::GzDecoder::new(bytes)
+            .read_to_string(&mut json)
+            .map_err(|err| Error::Parse(format!("invalid gzip rustdoc JSON: {err}")))?;
+        return Ok(json);
+    }
+    String::from_utf8(bytes.to_vec())
+        .map_err(|_| Error::Parse("rustdoc JSON is not valid UTF-8".to_string()))
+}
+
+/// Render rustdoc JSON as an llms-style markdown document.
+///
+/// The crate becomes the `#` heading, each public module a `##` section named
+/// by its path, each item a `###` section (`struct Foo`, `fn bar`), and methods
+/// from inherent impls and traits `####` sections. Items without a name, private
+/// items, and impl blocks for traits are skipped.
+///
+/// # Errors
+///
+/// Returns an error if the JSON is not a rustdoc crate document.
+pub fn render_rustdoc(json: &str) -> Result<String> {
+    let krate: Value = serde_json::from_str(json)
+        .map_err(|err| Error::Parse(format!("invalid rustdoc JSON: {err}")))?;
+    let index = krate
+        .get("index")
+        .and_then(Value::as_object)
+        .ok_or_else(|| Error::Parse("rustdoc JSON has no item index".to_string()))?;
+    let root = krate
+        .get("root")
+        .and_then(|id| lookup(index, id))
+        .ok_or_else(|| Error::Parse("rustdoc JSON has no root module".to_string()))?;
+    let crate_name = root.get("name").and_then(Value::as_str).unwrap_or("crate");
+
+    let mut out = format!("# {crate_name}\n\n");
+    if let Some(version) = krate.get("crate_version").and_then(Value::as_str) {
+        let _ = writeln!(out, "Version {version}\n");
+    }
+    push_docs(&mut out, root);
+
+    let mut renderer = Renderer {
+        index,
+        out,
+        visited: HashSet::new(),
+    };
+    renderer.module(crate_name, root, true);
+    Ok(renderer.out)
+}
+
+struct Renderer<'a> {
+    index: &'a Map<String, Value>,
+    out: String,
+    visited: HashSet<String>,
+}
+
+impl Renderer<'_> {
+    fn module(&mut self, path: &str, module: &Value, is_root: bool) {
+        if let Some(id) = module.get("id").map(id_key) {
+            if !self.visited.insert(id) {
+                return;
+            }
+        }
+        if !is_root {
+            let _ = writeln!(self.out, "## {path}\n");
+            push_docs(&mut self.out, module);
+        }
+
+        let mut submodules = Vec::new();
+        for child in children(self.index, module, "module", "items") {
+            if !is_public(child) {
+                continue;
+            }
+            let Some((kind, inner)) = inner_kind(child) else {
+                continue;
+            };
+            let Some(name) = child.get("name").and_then(Value::as_str) else {
+                continue;
+            };
+            match kind {
+                "module" => submodules.push((format!("{path}::{name}"), child)),
+                "use" | "impl" | "extern_crate" => {},
+                _ => self.render_item(kind, name, child, inner),
+            }
+        }
+
+        for (sub_path, submodule) in submodules {
+            self.module(&sub_path, submodule, false);
+        }
+    }
+
+    fn render_item(&mut self, kind: &str, name: &str, item: &Value, inner: &Value) {
+        let _ = writeln!(self.out, "### {} {name}\n", kind_label(kind));
+        push_docs(&mut self.out, item);
+
+        match kind {
+            "enum" => {
+                let variants: Vec<&Value> =
+                    children(self.index, item, "enum", "variants").collect();
+                if !variants.is_empty() {
+                    self.out.push_str("Variants:\n\n");
+                    for variant in variants {
+                        push_summary_line(&mut self.out, variant);
+                    }
+                    self.out.push('\n');
+                }
+            },
+            "trait" => {
+                for member in children(self.index, item, "trait", "items") {
+                    self.member(member);
+                }
+            },
+            _ => {},
+        }
+
+        for impl_id in inner
+            .get("impls")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+        {
+            let Some(block) = lookup(self.index, impl_id) else {
+                continue;
+            };
+            let is_inherent = block
+                .get("inner")
+                .and_then(|inner| inner.get("impl"))
+                .is_some_and(|imp| imp.get("trait").is_none_or(Value::is_null));
+            if is_inherent {
+                for member in children(self.index, block, "impl", "items").filter(|m| is_public(m))
+                {
+                    self.member(member);
+                }
+            }
+        }
+    }
+
+    fn member(&mut self, member: &Value) {
+        let (Some(name), Some((kind, _))) = (
+            member.get("name").and_then(Value::as_str),
+            inner_kind(member),
+        ) else {
+            return;
+        };
+        let _ = writeln!(self.out, "#### {} {name}\n", kind_label(kind));
+        push_docs(&mut self.out, member);
+    }
+}
+
+/// Canonical index key for an id (numeric in newer formats, string in older ones).
+fn id_key(id: &Value) -> String {
+    id.as_str()
+        .map_or_else(|| id.to_string(), std::string::ToString::to_string)
+}
+
+fn lookup<'a>(index: &'a Map<String, Value>, id: &Value) -> Option<&'a Value> {
+    index.get(&id_key(id))
+}
+
+/// Resolve the ids stored at `inner.<kind>.<field>` to index entries.
+fn children<'a>(
+    index: &'a Map<String, Value>,
+    parent: &'a Value,
+    kind: &str,
+    field: &str,
+) -> impl Iterator<Item = &'a Value> + 'a {
+    parent
+        .get("inner")
+        .and_then(|inner| inner.get(kind))
+        .and_then(|inner| inner.get(field))
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(move |id| lookup(index, id))
+}
+
+/// The item's kind and payload (`"inner": {"struct": {...}}`).
+fn inner_kind(item: &Value) -> Option<(&str, &Value)> {
+    match item.get("inner")? {
+        Value::Object(map) => map
+            .iter()
+            .next()
+            .map(|(kind, inner)| (kind.as_str(), inner)),
+        Value::String(kind) => Some((kind.as_str(), &Value::Null)),
+        _ => None,
+    }
+}
+
+/// Public items, plus trait members whose visibility is inherited.
+fn is_public(item: &Value) -> bool {
+    matches!(
+        item.get("visibility").and_then(Value::as_str),
+        Some("public" | "default")
+    )
+}
+
+fn kind_label(kind: &str) -> &str {
+    match kind {
+        "function" => "fn",
+        "module" => "mod",
+        "type_alias" | "assoc_type" => "type",
+        "constant" | "assoc_const" => "const",
+        "proc_macro" => "macro",
+        "struct_field" => "field",
+        other => other,
+    }
+}
+
+fn push_docs(out: &mut String, item: &Value) {
+    let docs = item
+        .get("docs")
+        .and_then(Value::as_str)
+        .map_or("", str::trim);
+    if !docs.is_empty() {
+        out.push_str(&demote_doc_headings(docs));
+        out.push_str("\n\n");
+    }
+}
+
+fn push_summary_line(out: &mut String, item: &Value) {
+    let Some(name) = item.get("name").and_then(Value::as_str) else {
+        return;
+    };
+    let summary = item
+        .get("docs")
+        .and_then(Value::as_str)
+        .and_then(|docs| docs.lines().map(str::trim).find(|line| !line.is_empty()));
+    match summary {
+        Some(summary) => {
+            let _ = writeln!(out, "- `{name}`: {summary}");
+        },
+        None => {
+            let _ = writeln!(out, "- `{name}`");
+        },
+    }
+}
+
+/// Turn headings inside doc comments into bold text so they do not break the
+/// item hierarchy (code fences are left untouched).
+pub(crate) fn demote_doc_headings(docs: &str) -> String {
+    let mut in_fence = false;
+    let mut out = String::with_capacity(docs.len());
+    for line in docs.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+        }
+        let title = trimmed.trim_start_matches('#');
+        let is_heading = !in_fence
+            && trimmed.starts_with('#')
+            && title.starts_with(' ')
+            && trimmed.len() - title.len() <= 6;
+        if is_heading {
+            let _ = writeln!(out, "**{}**", title.trim());
+        } else {
+            out.push_str(line);
+            out.push('\n');
+        }
+    }
+    out.truncate(out.trim_end().len());
+    out
+}
+
+/// Download and render a crate's documentation.
+///
+/// Returns the rendered markdown and the URL it was built from.
+///
+/// # Errors
+///
+/// Returns an error if the download fails or the payload is not rustdoc JSON.
+pub async fn fetch_crate_docs(fetcher: &Fetcher, spec: &CrateSpec) -> Result<(String, String)> {
+    let url = spec.json_url();
+    let bytes = fetcher.fetch_bytes(&url).await?;
+    let content = render_rustdoc(&decode_rustdoc(&bytes)?)?;
+    Ok((content, url))
+}
+
+/// Refresh a crate source by re-rendering its rustdoc JSON.
+///
+/// The cache is only rewritten when the rendered document changed.
+///
+/// # Errors
+///
+/// Returns an error if the source is not a crate, or if fetching, rendering,
+/// persistence, or indexing fails.
+pub async fn refresh_crate<S, I>(
+    storage: &S,
+    fetcher: &Fetcher,
+    alias: &str,
+    ctx: &RefreshContext,
+    metrics: PerformanceMetrics,
+    indexer: &I,
+    filter_preference: bool,
+) -> Result<RefreshOutcome>
+where
+    S: RefreshStorage + Sync,
+    I: RefreshIndexer + Sync,
+{
+    let Some(SourceType::Crate { name, version }) = &ctx.existing_metadata.origin.source_type
+    else {
+        return Err(Error::Config(format!("Source '{alias}' is not a crate")));
+    };
+    let spec = CrateSpec {
+        name: name.clone(),
+        version: version.clone(),
+    };
+
+    let (content, _) = fetch_crate_docs(fetcher, &spec).await?;
+    let sha256 = calculate_sha256(&content);
+
+    let mut metadata = ctx.existing_metadata.clone();
+    let filter_changed = metadata.filter_non_english.unwrap_or(true) != filter_preference;
+    metadata.filter_non_english = Some(filter_preference);
+
+    if sha256 == metadata.sha256 {
+        if filter_changed {
+            storage.save_metadata(alias, &metadata)?;
+        }
+        return Ok(RefreshOutcome::Unchanged {
+            alias: alias.to_string(),
+        });
+    }
+
+    let payload = RefreshPayload {
+        content,
+        sha256,
+        etag: None,
+        last_modified: None,
+    };
+    let params = ApplyRefreshParams::new(metadata, ctx.existing_aliases.clone());
+    apply_refresh(storage, alias, &params, &payload, metrics, indexer).await
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r#"{
+        "root": 0,
+        "crate_version": "1.2.3",
+        "format_version": 39,
+        "index": {
+            "0": {"id": 0, "name": "demo", "visibility": "public", "docs": "Demo crate.",
+                  "inner": {"module": {"is_crate": true, "items": [1, 2, 3, 9]}}},
+            "1": {"id": 1, "name": "Widget", "visibility": "public", "docs": "A widget.\n\n# Examples\n\n```rust\n# let x = 1;\n```",
+                  "inner": {"struct": {"impls": [4, 6]}}},
+            "2": {"id": 2, "name": "shapes", "visibility": "public", "docs": "Shape helpers.",
+                  "inner": {"module": {"items": [7, 10]}}},
+            "3": {"id": 3, "name": "hidden", "visibility": "crate", "docs": "Private.",
+                  "inner": {"function": {}}},
+            "4": {"id": 4, "name": null, "visibility": "default", "docs": null,
+                  "inner": {"impl": {"trait": null, "items": [5]}}},
+            "5": {"id": 5, "name": "spin", "visibility": "public", "docs": "Spin it.",
+                  "inner": {"function": {}}},
+            "6": {"id": 6, "name": null, "visibility": "default", "docs": null,
+                  "inner": {"impl": {"trait": {"path": "Clone"}, "items": [11]}}},
+            "7": {"id": 7, "name": "Shape", "visibility": "public", "docs": "Kinds of shape.",
+                  "inner": {"enum": {"variants": [8], "impls": []}}},
+            "8": {"id": 8, "name": "Circle", "visibility": "default", "docs": "Round.",
+                  "inner": {"variant": {}}},
+            "9": {"id": 9, "name": "Draw", "visibility": "public", "docs": "Drawable things.",
+                  "inner": {"trait": {"items": [12]}}},
+            "10": {"id": 10, "name": "area", "visibility": "public", "docs": "",
+                  "inner": {"function": {}}},
+            "11": {"id": 11, "name": "clone", "visibility": "default", "docs": null,
+                  "inner": {"function": {}}},
+            "12": {"id": 12, "name": "draw", "visibility": "default", "docs": "Draw it.",
+                  "inner": {"function": {}}}
+        }
+    }"#;
+
+    #[test]
+    fn parses_crate_specs() {
+        let spec: CrateSpec = "serde".parse().unwrap();
+        assert_eq!(spec.version, LATEST_VERSION);
+        let spec: CrateSpec = "serde_json@1.0.100".parse().unwrap();
+        assert_eq!(spec.name, "serde_json");
+        assert_eq!(spec.version, "1.0.100");
+        assert_eq!(
+            spec.docs_rs_json_url("https://docs.rs/"),
+            "https://docs.rs/crate/serde_json/1.0.100/json.gz"
+        );
+
+        assert!(CrateSpec::from_prefixed("https://example.com").is_none());
+        assert!(CrateSpec::from_prefixed("crate:tokio").unwrap().is_ok());
+        for invalid in ["", "1abc", "bad/name", "serde@", "serde@1 0"] {
+            assert!(invalid.parse::<CrateSpec>().is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn renders_public_items_as_sections() {
+        let markdown = render_rustdoc(SAMPLE).unwrap();
+        assert!(markdown.starts_with("# demo\n\nVersion 1.2.3\n\nDemo crate.\n"));
+        assert!(markdown.contains("### struct Widget\n\nA widget."));
+        assert!(markdown.contains("**Examples**"));
+        assert!(markdown.contains("# let x = 1;"), "fenced code is kept");
+        assert!(markdown.contains("#### fn spin\n\nSpin it."));
+        assert!(markdown.contains("### trait Draw"));
+        assert!(markdown.contains("#### fn draw\n\nDraw it."));
+        assert!(markdown.contains("## demo::shapes\n\nShape helpers."));
+        assert!(markdown.contains("### enum Shape"));
+        assert!(markdown.contains("- `Circle`: Round."));
+        assert!(markdown.contains("### fn area"));
+
+        assert!(!markdown.contains("hidden"), "private items are skipped");
+        assert!(!markdown.contains("clone"), "trait impls are skipped");
+        // Items in the root module come before submodules
+        assert!(markdown.find("### trait Draw") < markdown.find("## demo::shapes"));
+    }
+
+    #[test]
+    fn rejects_non_rustdoc_json() {
+        assert!(render_rustdoc("{}").is_err());
+        assert!(render_rustdoc("not json").is_err());
+    }
+
+    #[test]
+    fn decodes_gzip_and_plain_payloads() {
+        use std::io::Write;
+
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(SAMPLE.as_bytes()).unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        assert_eq!(decode_rustdoc(&compressed).unwrap(), SAMPLE);
+        assert_eq!(decode_rustdoc(SAMPLE.as_bytes()).unwrap(), SAMPLE);
+    }
+}
@@ -1,17 +1,252 @@
-use crate::{Error, LlmsJson, Result, Source, SourceDescriptor, profile};
-use chrono::Utc;
+use crate::analysis::TextAnalysis;
+use crate::github::GithubCache;
+use crate::ranking::Ranking;
+use crate::{
+    Error, FetchAuth, FetchConfig, FilterPipeline, HealthRecord, IndexConfig, LlmsJson, LocaleSet,
+    QueryCache, Result, SearchIndex, Source, SourceAccess, SourceDescriptor, SourceIntegrity,
+    SourceSummary, ToolConfig, ToolMeta, profile,
+};
+use base64::{Engine, engine::general_purpose::STANDARD};
+use chrono::{DateTime, NaiveDateTime, Utc};
 use directories::{BaseDirs, ProjectDirs};
+use flate2::read::GzDecoder;
+use fs2::FileExt;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::borrow::Cow;
 use std::fs;
+use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
 use tracing::{debug, info, warn};
 
 /// Maximum allowed alias length to match CLI constraints
 const MAX_ALIAS_LEN: usize = 64;
 
+/// Access timestamps closer together than this are not rewritten, so bursts of
+/// searches against one source cost a single small write.
+const ACCESS_WRITE_GRANULARITY_SECS: i64 = 60;
+
+/// Timestamp prefix of archived files; sorts chronologically as a string.
+const ARCHIVE_TIMESTAMP_FORMAT: &str = "%Y-%m-%dT%H-%M-%SZ";
+
+/// A previous version of a source, saved under `.archive` before a refresh.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchiveSnapshot {
+    /// Archive timestamp as it appears in file names (e.g. `2025-01-02T03-04-05Z`).
+    pub id: String,
+    /// When the snapshot was archived; its content was current until then.
+    pub archived_at: DateTime<Utc>,
+    /// Archived `llms.json`.
+    pub llms_json: PathBuf,
+    /// Archived `llms.txt`.
+    pub llms_txt: PathBuf,
+}
+
+/// Directory under the cache root holding per-source lock files.
+const LOCKS_DIR: &str = "locks";
+
+/// How long to wait for a busy source unless `BLZ_LOCK_TIMEOUT` overrides it.
+const DEFAULT_LOCK_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Pause between attempts to take a contended lock.
+const LOCK_RETRY_INTERVAL: Duration = Duration::from_millis(50);
+
+/// How a [`SourceLock`] shares its source with other processes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LockMode {
+    /// Any number of readers; excludes writers.
+    Shared,
+    /// A single writer; excludes readers and other writers.
+    Exclusive,
+}
+
+/// Advisory cross-process lock on one source, released when dropped.
+///
+/// Writers hold it exclusively while they replace a source's files and index;
+/// readers hold it shared while they open them, so a search never observes an
+/// index mid-swap.
+#[derive(Debug)]
+pub struct SourceLock {
+    _file: fs::File,
+}
+
+/// Journal marking a source whose sync has not finished.
+const SYNC_JOURNAL: &str = ".sync-journal.json";
+
+/// Marker a sync writes before touching a source and removes once done.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncJournal {
+    /// When the sync started.
+    pub started_at: DateTime<Utc>,
+    /// Process running the sync.
+    pub pid: u32,
+}
+
+/// Directory under `.archive` holding compressed, content-addressed llms.txt versions.
+const VERSIONS_DIR: &str = "versions";
+
+/// Manifest listing archived versions in the order they were replaced.
+const VERSIONS_MANIFEST: &str = "versions.json";
+
+/// Shortest hex prefix accepted when looking up a version.
+const MIN_VERSION_PREFIX: usize = 4;
+
+/// Extension of zstd-compressed content files.
+const ZSTD_EXT: &str = "zst";
+
+/// llms.txt content at least this large is stored zstd-compressed; smaller
+/// files stay plain so they remain cheap to read and easy to inspect.
+const COMPRESS_THRESHOLD: usize = 64 * 1024;
+
+/// zstd level for stored content; favors fast writes over the last few percent.
+const COMPRESSION_LEVEL: i32 = 3;
+
+/// A previous llms.txt of a source, stored zstd-compressed under its content hash.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContentVersion {
+    /// Hex SHA-256 of the content; names the object file and accepts prefixes.
+    pub id: String,
+    /// Base64 SHA-256 of the content, as recorded in source metadata.
+    pub sha256: String,
+    /// When this content was fetched, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fetched_at: Option<DateTime<Utc>>,
+    /// When newer content replaced it.
+    pub archived_at: DateTime<Utc>,
+    /// Number of lines in the content.
+    pub lines: usize,
+}
+
+/// Hex and base64 encodings of the SHA-256 of `content`.
+fn content_digest(content: &str) -> (String, String) {
+    use std::fmt::Write as _;
+
+    let digest = Sha256::digest(content.as_bytes());
+    let hex = digest.iter().fold(String::with_capacity(64), |mut acc, b| {
+        let _ = write!(acc, "{b:02x}");
+        acc
+    });
+    (hex, STANDARD.encode(digest))
+}
+
+/// On-disk footprint of a cached source, in bytes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SourceDiskUsage {
+    /// Cached documents and metadata (llms.txt, llms.json, page caches, ...).
+    pub content: u64,
+    /// Archived previous versions under `.archive`.
+    pub archives: u64,
+    /// Search index under `.index`.
+    pub index: u64,
+}
+
+impl SourceDiskUsage {
+    /// Total bytes across all categories.
+    #[must_use]
+    pub const fn total(&self) -> u64 {
+        self.content + self.archives + self.index
+    }
+}
+
+/// Whether stored content may be compressed, honoring `BLZ_STORAGE_COMPRESSION`.
+fn compression_from_env() -> bool {
+    let Ok(v) = std::env::var("BLZ_STORAGE_COMPRESSION") else {
+        return true;
+    };
+    !matches!(
+        v.trim().to_ascii_lowercase().as_str(),
+        "0" | "false" | "no" | "off"
+    )
+}
+
+fn compress(content: &[u8]) -> std::io::Result<Vec<u8>> {
+    zstd::encode_all(content, COMPRESSION_LEVEL)
+}
+
+/// Reads a content file, decompressing it when its name ends in `.zst`.
+fn read_content(path: &Path) -> std::io::Result<String> {
+    let bytes = fs::read(path)?;
+    let bytes = if path.extension().is_some_and(|ext| ext == ZSTD_EXT) {
+        zstd::decode_all(bytes.as_slice())?
+    } else {
+        bytes
+    };
+    String::from_utf8(bytes).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+}
+
+/// Writes `bytes` to `path` so readers and crashes see either the old file or
+/// the new one, never a partial write.
+///
+/// The bytes go to `<path>.tmp`, which is fsynced and renamed over `path`
+/// (replacing it on Windows too); on Unix the directory is fsynced as well so
+/// the rename itself survives a power loss. The temp file is removed if any
+/// step fails.
+pub(crate) fn write_atomic(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+    let mut tmp_name = path.file_name().unwrap_or_default().to_os_string();
+    tmp_name.push(".tmp");
+    let tmp_path = path.with_file_name(tmp_name);
+    let result = (|| {
+        let mut file = fs::File::create(&tmp_path)?;
+        file.write_all(bytes)?;
+        file.sync_all()?;
+        drop(file);
+        fs::rename(&tmp_path, path)?;
+        #[cfg(unix)]
+        if let Some(parent) = path.parent() {
+            fs::File::open(parent)?.sync_all()?;
+        }
+        Ok(())
+    })();
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp_path);
+    }
+    result
+}
+
+/// Read-only cache layered under the writable root, from `BLZ_SHARED_ROOT`.
+fn shared_root_from_env() -> Option<PathBuf> {
+    let dir = std::env::var("BLZ_SHARED_ROOT").ok()?;
+    let trimmed = dir.trim();
+    (!trimmed.is_empty()).then(|| PathBuf::from(trimmed))
+}
+
+/// How long to wait for busy sources, honoring `BLZ_LOCK_TIMEOUT` (seconds).
+fn lock_timeout_from_env() -> Duration {
+    std::env::var("BLZ_LOCK_TIMEOUT")
+        .ok()
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .map_or(DEFAULT_LOCK_TIMEOUT, Duration::from_secs)
+}
+
+/// Whether `pid` names a live process other than placeholder pid 0.
+fn process_is_running(pid: u32) -> bool {
+    if pid == 0 {
+        return false;
+    }
+    if pid == std::process::id() {
+        return true;
+    }
+    let pid = Pid::from_u32(pid);
+    let mut system = System::new();
+    system.refresh_processes_specifics(
+        ProcessesToUpdate::Some(&[pid]),
+        false,
+        ProcessRefreshKind::new(),
+    );
+    system.process(pid).is_some()
+}
+
 /// Local filesystem storage for cached llms.txt documentation
 pub struct Storage {
     root_dir: PathBuf,
     config_dir: PathBuf,
+    compression: bool,
+    lock_timeout: Duration,
+    shared_root: Option<PathBuf>,
 }
 
 impl Storage {
@@ -53,34 +288,57 @@ impl Storage {
 
     /// Creates a new storage instance with the default root directory.
     ///
+    /// When a named profile is active (see [`profile::select`]) both the data
+    /// and descriptor directories are scoped to that profile.
+    ///
     /// # Errors
     ///
     /// Returns an error if the data or config directories cannot be resolved or created.
     pub fn new() -> Result<Self> {
+        let (data_dir, config_dir) = Self::profile_roots()?;
+        // Test/dev override (BLZ_DATA_DIR) never migrates the legacy cache
+        if std::env::var_os("BLZ_DATA_DIR").is_none() {
+            Self::check_and_migrate_old_cache(&data_dir);
+        }
+        Self::with_paths(profile::scoped(&data_dir), profile::scoped(&config_dir))
+    }
+
+    /// Directories holding named profiles, as `(data, config)`.
+    ///
+    /// These are the unscoped roots' [`profile::PROFILES_DIR`] subdirectories;
+    /// each profile lives in a child named after it. They may not exist yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the data or config directories cannot be resolved.
+    pub fn profiles_dirs() -> Result<(PathBuf, PathBuf)> {
+        let (data_dir, config_dir) = Self::profile_roots()?;
+        Ok((
+            data_dir.join(profile::PROFILES_DIR),
+            config_dir.join(profile::PROFILES_DIR),
+        ))
+    }
+
+    /// Unscoped data and config roots, honoring overrides.
+    fn profile_roots() -> Result<(PathBuf, PathBuf)> {
+        Ok((Self::base_data_dir()?, Self::default_config_dir()?))
+    }
+
+    /// Default data directory before profile scoping
+    fn base_data_dir() -> Result<PathBuf> {
         // Test/dev override: allow BLZ_DATA_DIR to set the root directory explicitly
         if let Ok(dir) = std::env::var("BLZ_DATA_DIR") {
-            let root = PathBuf::from(dir);
-            let config_dir = Self::default_config_dir()?;
-            return Self::with_paths(root, config_dir);
+            return Ok(PathBuf::from(dir));
         }
 
         // Use XDG_DATA_HOME if explicitly set
-        let root_dir = if let Ok(xdg) = std::env::var("XDG_DATA_HOME") {
+        if let Ok(xdg) = std::env::var("XDG_DATA_HOME") {
             let trimmed = xdg.trim();
-            if trimmed.is_empty() {
-                Self::fallback_data_dir()?
-            } else {
-                PathBuf::from(trimmed).join(profile::app_dir_slug())
+            if !trimmed.is_empty() {
+                return Ok(PathBuf::from(trimmed).join(profile::app_dir_slug()));
             }
-        } else {
-            Self::fallback_data_dir()?
-        };
-
-        // Check for migration from old cache directory
-        Self::check_and_migrate_old_cache(&root_dir);
-
-        let config_dir = Self::default_config_dir()?;
-        Self::with_paths(root_dir, config_dir)
+        }
+        Self::fallback_data_dir()
     }
 
     /// Fallback data directory when `XDG_DATA_HOME` is not set
@@ -147,7 +405,63 @@ impl Storage {
         Ok(Self {
             root_dir,
             config_dir,
-        })
+            compression: compression_from_env(),
+            lock_timeout: lock_timeout_from_env(),
+            shared_root: None,
+        }
+        .with_shared_root(shared_root_from_env()))
+    }
+
+    /// Enables or disables compression of large llms.txt files.
+    ///
+    /// Compression is on unless `BLZ_STORAGE_COMPRESSION=off`. It only affects
+    /// future writes; content already stored either way stays readable.
+    #[must_use]
+    pub const fn with_compression(mut self, enabled: bool) -> Self {
+        self.compression = enabled;
+        self
+    }
+
+    /// Layers a read-only cache under this one.
+    ///
+    /// Sources present in the shared cache but not in the writable root are
+    /// read from it as is; sources in the writable root always win. `None`
+    /// (the default unless `BLZ_SHARED_ROOT` is set) disables layering.
+    #[must_use]
+    pub fn with_shared_root(mut self, shared_root: Option<PathBuf>) -> Self {
+        self.shared_root = shared_root.filter(|shared| shared != &self.root_dir);
+        self
+    }
+
+    /// Returns the shared read-only cache layered under this one, if any.
+    #[must_use]
+    pub fn shared_root(&self) -> Option<&Path> {
+        self.shared_root.as_deref()
+    }
+
+    /// Whether a source is served from the shared read-only cache.
+    ///
+    /// Shared sources cannot be synced, removed, or otherwise written; add a
+    /// source under another alias to keep a writable copy.
+    #[must_use]
+    pub fn is_shared(&self, source: &str) -> bool {
+        Self::validate_alias(source).is_ok()
+            && !self.root_dir.join("sources").join(source).exists()
+            && self.shared_tool_dir(source).is_some()
+    }
+
+    /// The shared cache's directory for `source`, when it holds that source.
+    fn shared_tool_dir(&self, source: &str) -> Option<PathBuf> {
+        let dir = self.shared_root.as_ref()?.join("sources").join(source);
+        dir.join("llms.json").is_file().then_some(dir)
+    }
+
+    /// Sets how long [`lock_source`](Self::lock_source) waits for a busy
+    /// source (`BLZ_LOCK_TIMEOUT` seconds, 30 by default).
+    #[must_use]
+    pub const fn with_lock_timeout(mut self, timeout: Duration) -> Self {
+        self.lock_timeout = timeout;
+        self
     }
 
     /// Returns the root data directory path
@@ -227,7 +541,11 @@ impl Storage {
         Ok(())
     }
 
-    /// Returns the directory path for a given alias.
+    /// Returns the writable directory path for a given alias.
+    ///
+    /// Always under the writable root, whether or not the directory exists
+    /// yet; it never points into the shared cache. Use
+    /// [`source_dir`](Self::source_dir) to locate a source for reading.
     ///
     /// # Errors
     ///
@@ -238,18 +556,76 @@ impl Storage {
         Ok(self.root_dir.join("sources").join(source))
     }
 
+    /// Returns the directory a source is read from.
+    ///
+    /// Resolves to the shared cache for sources only it holds (see
+    /// [`with_shared_root`](Self::with_shared_root)); otherwise to
+    /// [`tool_dir`](Self::tool_dir). Callers must not write through this path.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the alias is invalid.
+    pub fn source_dir(&self, source: &str) -> Result<PathBuf> {
+        let local = self.tool_dir(source)?;
+        if local.exists() {
+            return Ok(local);
+        }
+        Ok(self.shared_tool_dir(source).unwrap_or(local))
+    }
+
+    /// Cache of search results stored under the storage root.
+    #[must_use]
+    pub fn query_cache(&self) -> QueryCache {
+        QueryCache::new(self.root_dir.join("query-cache"))
+    }
+
+    /// Directory holding downloaded embedding models for semantic search.
+    #[must_use]
+    pub fn models_dir(&self) -> PathBuf {
+        self.root_dir.join("models")
+    }
+
+    /// Cache of GitHub repository metadata stored under the storage root.
+    #[must_use]
+    pub fn github_cache(&self) -> GithubCache {
+        GithubCache::new(self.root_dir.join("github"))
+    }
+
+    /// Drop every cached search result.
+    ///
+    /// Called whenever a source is written or removed. Failures are logged
+    /// rather than returned: a leftover entry is still keyed on the old
+    /// source generation, so it can only be hit if the content is unchanged.
+    pub fn invalidate_query_cache(&self) {
+        if let Err(e) = self.query_cache().clear() {
+            warn!("Failed to invalidate query cache: {e}");
+        }
+    }
+
     /// Resolve the on-disk path for a specific flavored content file.
     fn variant_file_path(&self, source: &str, file_name: &str) -> Result<PathBuf> {
         let sanitized = Self::sanitize_variant_file_name(file_name);
-        Ok(self.tool_dir(source)?.join(sanitized))
+        Ok(self.source_dir(source)?.join(sanitized))
     }
 
     /// Ensures the directory for an alias exists and returns its path.
     ///
     /// # Errors
     ///
-    /// Returns an error if the alias is invalid or the directory cannot be created.
+    /// Returns an error if the alias is invalid, the source is read-only
+    /// because it comes from the shared cache, or the directory cannot be
+    /// created.
     pub fn ensure_tool_dir(&self, source: &str) -> Result<PathBuf> {
+        if let Some(shared) = self
+            .shared_tool_dir(source)
+            .filter(|_| self.is_shared(source))
+        {
+            return Err(Error::Storage(format!(
+                "Source '{source}' comes from the read-only shared cache ({}); \
+                 add it under another alias to keep a writable copy",
+                shared.display()
+            )));
+        }
         let dir = self.tool_dir(source)?;
         fs::create_dir_all(&dir)
             .map_err(|e| Error::Storage(format!("Failed to create tool directory: {e}")))?;
@@ -334,13 +710,40 @@ impl Storage {
         self.variant_file_path(source, "llms.txt")
     }
 
+    /// Returns the path llms.txt is stored at when compressed (`llms.txt.zst`).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the alias is invalid.
+    pub fn compressed_llms_txt_path(&self, source: &str) -> Result<PathBuf> {
+        self.variant_file_path(source, "llms.txt.zst")
+    }
+
+    /// Returns the file currently holding a source's llms.txt, plain or
+    /// compressed, or `None` when neither exists.
+    ///
+    /// Use this for on-disk facts such as size or existence; read the content
+    /// itself with [`Self::load_llms_txt`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the alias is invalid.
+    pub fn stored_llms_txt_path(&self, source: &str) -> Result<Option<PathBuf>> {
+        let plain = self.llms_txt_path(source)?;
+        if plain.is_file() {
+            return Ok(Some(plain));
+        }
+        let compressed = self.compressed_llms_txt_path(source)?;
+        Ok(compressed.is_file().then_some(compressed))
+    }
+
     /// Returns the path to the llms.json file for a source.
     ///
     /// # Errors
     ///
     /// Returns an error if the alias is invalid.
     pub fn llms_json_path(&self, source: &str) -> Result<PathBuf> {
-        Ok(self.tool_dir(source)?.join("llms.json"))
+        Ok(self.source_dir(source)?.join("llms.json"))
     }
 
     /// Returns the path to the search index directory for a source.
@@ -349,7 +752,16 @@ impl Storage {
     ///
     /// Returns an error if the alias is invalid.
     pub fn index_dir(&self, source: &str) -> Result<PathBuf> {
-        Ok(self.tool_dir(source)?.join(".index"))
+        Ok(self.source_dir(source)?.join(".index"))
+    }
+
+    /// Returns the path to the semantic search vectors for a source.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the alias is invalid.
+    pub fn vectors_path(&self, source: &str) -> Result<PathBuf> {
+        Ok(self.source_dir(source)?.join("vectors.json"))
     }
 
     /// Returns the path to the archive directory for a source.
@@ -358,7 +770,7 @@ impl Storage {
     ///
     /// Returns an error if the alias is invalid.
     pub fn archive_dir(&self, source: &str) -> Result<PathBuf> {
-        Ok(self.tool_dir(source)?.join(".archive"))
+        Ok(self.source_dir(source)?.join(".archive"))
     }
 
     /// Returns the path to the metadata file for a source.
@@ -367,7 +779,16 @@ impl Storage {
     ///
     /// Returns an error if the alias is invalid.
     pub fn metadata_path(&self, source: &str) -> Result<PathBuf> {
-        Ok(self.tool_dir(source)?.join("metadata.json"))
+        Ok(self.source_dir(source)?.join("metadata.json"))
+    }
+
+    /// Returns the path to the search summary file for a source.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the alias is invalid.
+    pub fn summary_path(&self, source: &str) -> Result<PathBuf> {
+        Ok(self.source_dir(source)?.join("summary.json"))
     }
 
     /// Returns the path to the anchors mapping file for a source.
@@ -376,44 +797,590 @@ impl Storage {
     ///
     /// Returns an error if the alias is invalid.
     pub fn anchors_map_path(&self, source: &str) -> Result<PathBuf> {
-        Ok(self.tool_dir(source)?.join("anchors.json"))
+        Ok(self.source_dir(source)?.join("anchors.json"))
     }
 
-    /// Saves the llms.txt content for a source.
+    /// Returns the path to the mirror list file for a source.
     ///
     /// # Errors
     ///
-    /// Returns an error if the file cannot be written or renamed.
-    pub fn save_llms_txt(&self, source: &str, content: &str) -> Result<()> {
+    /// Returns an error if the alias is invalid.
+    pub fn mirrors_path(&self, source: &str) -> Result<PathBuf> {
+        Ok(self.source_dir(source)?.join("mirrors.json"))
+    }
+
+    /// Returns the path to the per-source settings file (`settings.toml`).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the alias is invalid.
+    pub fn settings_path(&self, source: &str) -> Result<PathBuf> {
+        Ok(self.source_dir(source)?.join("settings.toml"))
+    }
+
+    /// Loads the credentials configured for a source, if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `settings.toml` exists but cannot be parsed.
+    pub fn load_fetch_auth(&self, source: &str) -> Result<Option<FetchAuth>> {
+        let path = self.settings_path(source)?;
+        if !path.exists() {
+            return Ok(None);
+        }
+        let config = ToolConfig::load(&path)?;
+        Ok(config.fetch.auth.filter(|auth| !auth.is_empty()))
+    }
+
+    /// Stores credentials for a source in its `settings.toml`, keeping any
+    /// other settings already there.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the settings file cannot be read or written.
+    pub fn save_fetch_auth(&self, source: &str, auth: &FetchAuth) -> Result<()> {
+        let mut config = self.load_settings_or_default(source)?;
+        config.fetch.auth = Some(auth.clone());
         self.ensure_tool_dir(source)?;
-        let path = self.llms_txt_path(source)?;
+        config.save(&self.settings_path(source)?)
+    }
 
-        let tmp_path = path.with_extension("tmp");
-        fs::write(&tmp_path, content)
-            .map_err(|e| Error::Storage(format!("Failed to write llms.txt: {e}")))?;
+    /// Loads the checksum or signature declared for a source, if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `settings.toml` exists but cannot be parsed.
+    pub fn load_integrity(&self, source: &str) -> Result<Option<SourceIntegrity>> {
+        let path = self.settings_path(source)?;
+        if !path.exists() {
+            return Ok(None);
+        }
+        let config = ToolConfig::load(&path)?;
+        Ok(config
+            .fetch
+            .integrity
+            .filter(|integrity| !integrity.is_empty()))
+    }
 
-        #[cfg(target_os = "windows")]
+    /// Stores the checksum or signature declared for a source in its
+    /// `settings.toml`, keeping any other settings already there.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the settings file cannot be read or written.
+    pub fn save_integrity(&self, source: &str, integrity: &SourceIntegrity) -> Result<()> {
+        let mut config = self.load_settings_or_default(source)?;
+        config.fetch.integrity = Some(integrity.clone());
+        self.ensure_tool_dir(source)?;
+        config.save(&self.settings_path(source)?)
+    }
+
+    /// Loads the content filter rules configured for a source
+    /// (`[index] filters`), if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `settings.toml` exists but cannot be parsed.
+    pub fn load_filter_rules(&self, source: &str) -> Result<Option<Vec<String>>> {
+        let path = self.settings_path(source)?;
+        if !path.exists() {
+            return Ok(None);
+        }
+        Ok(ToolConfig::load(&path)?.index.filters)
+    }
+
+    /// Loads the content filter pipeline configured for a source, if any.
+    ///
+    /// A source with locales but no rules gets the default language-only
+    /// pipeline keeping those locales.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `settings.toml` cannot be parsed or holds an
+    /// invalid rule or locale.
+    pub fn load_filters(&self, source: &str) -> Result<Option<FilterPipeline>> {
+        let rules = self.load_filter_rules(source)?;
+        let locales = self.load_locales(source)?;
+        if rules.is_none() && locales.is_none() {
+            return Ok(None);
+        }
+        let rules = rules.unwrap_or_else(|| vec!["lang".to_string()]);
+        let pipeline = FilterPipeline::parse(&rules).map_err(|e| {
+            Error::Config(format!("Invalid filters in settings for '{source}': {e}"))
+        })?;
+        Ok(Some(pipeline.with_locales(locales.unwrap_or_default())))
+    }
+
+    /// Loads the locales kept by the language filter for a source
+    /// (`[index] locales`), if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `settings.toml` cannot be parsed or names an
+    /// unknown locale.
+    pub fn load_locales(&self, source: &str) -> Result<Option<LocaleSet>> {
+        let path = self.settings_path(source)?;
+        if !path.exists() {
+            return Ok(None);
+        }
+        ToolConfig::load(&path)?
+            .index
+            .locales
+            .map(|codes| {
+                LocaleSet::from_codes(&codes).map_err(|e| {
+                    Error::Config(format!("Invalid locales in settings for '{source}': {e}"))
+                })
+            })
+            .transpose()
+    }
+
+    /// Loads how a source's text is analyzed (`[index]` `tokenizer`,
+    /// `stemmer`, `lowercase`, and `synonyms`), defaulting when unset.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `settings.toml` cannot be parsed or names an
+    /// unknown tokenizer or stemming language.
+    pub fn load_text_analysis(&self, source: &str) -> Result<TextAnalysis> {
+        let path = self.settings_path(source)?;
+        if !path.exists() {
+            return Ok(TextAnalysis::default());
+        }
+        TextAnalysis::from_config(&ToolConfig::load(&path)?.index)
+            .map_err(|e| Error::Config(format!("Invalid analysis settings for '{source}': {e}")))
+    }
+
+    /// Loads how a source's hits are ranked (`[index]` boosts and
+    /// `stopwords`), defaulting when unset.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `settings.toml` cannot be parsed or sets an
+    /// out-of-range boost.
+    pub fn load_ranking(&self, source: &str) -> Result<Ranking> {
+        let path = self.settings_path(source)?;
+        if !path.exists() {
+            return Ok(Ranking::default());
+        }
+        Ranking::from_config(&ToolConfig::load(&path)?.index)
+            .map_err(|e| Error::Config(format!("Invalid ranking settings for '{source}': {e}")))
+    }
+
+    /// Stores the locales kept for a source in its `settings.toml`, keeping
+    /// any other settings already there. `None` restores English only.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the settings file cannot be read or written.
+    pub fn save_locales(&self, source: &str, locales: Option<LocaleSet>) -> Result<()> {
+        let mut config = self.load_settings_or_default(source)?;
+        config.index.locales =
+            locales.map(|locales| locales.codes().into_iter().map(str::to_string).collect());
+        self.ensure_tool_dir(source)?;
+        config.save(&self.settings_path(source)?)
+    }
+
+    /// Stores the content filter rules for a source in its `settings.toml`,
+    /// keeping any other settings already there. `None` restores the default
+    /// pipeline.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the settings file cannot be read or written.
+    pub fn save_filter_rules(&self, source: &str, rules: Option<Vec<String>>) -> Result<()> {
+        let mut config = self.load_settings_or_default(source)?;
+        config.index.filters = rules;
+        self.ensure_tool_dir(source)?;
+        config.save(&self.settings_path(source)?)
+    }
+
+    fn load_settings_or_default(&self, source: &str) -> Result<ToolConfig> {
+        let path = self.settings_path(source)?;
         if path.exists() {
-            fs::remove_file(&path)
-                .map_err(|e| Error::Storage(format!("Failed to remove existing llms.txt: {e}")))?;
+            return ToolConfig::load(&path);
+        }
+        Ok(ToolConfig {
+            meta: ToolMeta {
+                name: source.to_string(),
+                display_name: None,
+                homepage: None,
+                repo: None,
+            },
+            fetch: FetchConfig::default(),
+            index: IndexConfig::default(),
+        })
+    }
+
+    /// Returns the path to the minisign signature cached for a source's
+    /// llms.txt (`llms.txt.minisig`).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the alias is invalid.
+    pub fn signature_path(&self, source: &str) -> Result<PathBuf> {
+        Ok(self.source_dir(source)?.join("llms.txt.minisig"))
+    }
+
+    /// Caches the signature that verified the current llms.txt.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the signature cannot be written.
+    pub fn save_signature(&self, source: &str, signature: &str) -> Result<()> {
+        self.ensure_tool_dir(source)?;
+        write_atomic(&self.signature_path(source)?, signature.as_bytes())
+            .map_err(|e| Error::Storage(format!("Failed to write signature: {e}")))
+    }
+
+    /// Loads the cached signature for a source's llms.txt, if one was saved.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the signature exists but cannot be read.
+    pub fn load_signature(&self, source: &str) -> Result<Option<String>> {
+        let path = self.signature_path(source)?;
+        if !path.exists() {
+            return Ok(None);
+        }
+        fs::read_to_string(&path)
+            .map(Some)
+            .map_err(|e| Error::Storage(format!("Failed to read signature: {e}")))
+    }
+
+    /// Returns the path to the access timestamps file for a source.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the alias is invalid.
+    pub fn access_path(&self, source: &str) -> Result<PathBuf> {
+        Ok(self.source_dir(source)?.join("access.json"))
+    }
+
+    /// Loads the access timestamps for a source.
+    ///
+    /// Missing or unreadable records load as empty; they are advisory and
+    /// must never block reading the source itself.
+    #[must_use]
+    pub fn load_access(&self, source: &str) -> SourceAccess {
+        self.access_path(source)
+            .ok()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    /// Records that a search returned results from a source.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the access file cannot be written.
+    pub fn record_search(&self, source: &str) -> Result<()> {
+        self.touch_access(source, |access| &mut access.last_searched)
+    }
+
+    /// Records that a source was checked against upstream.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the access file cannot be written.
+    pub fn record_fetch(&self, source: &str) -> Result<()> {
+        self.touch_access(source, |access| &mut access.last_fetched)
+    }
+
+    fn touch_access(
+        &self,
+        source: &str,
+        field: impl FnOnce(&mut SourceAccess) -> &mut Option<chrono::DateTime<Utc>>,
+    ) -> Result<()> {
+        if !self.exists(source) || self.is_shared(source) {
+            return Ok(());
+        }
+        let now = Utc::now();
+        let mut access = self.load_access(source);
+        let slot = field(&mut access);
+        if slot.is_some_and(|at| (now - at).num_seconds() < ACCESS_WRITE_GRANULARITY_SECS) {
+            return Ok(());
+        }
+        *slot = Some(now);
+        let json = serde_json::to_string(&access)
+            .map_err(|e| Error::Storage(format!("Failed to serialize access record: {e}")))?;
+        fs::write(self.access_path(source)?, json)
+            .map_err(|e| Error::Storage(format!("Failed to write access record: {e}")))
+    }
+
+    /// Returns the path to the lock file guarding a source.
+    ///
+    /// Lock files live outside the source directory so locking never creates
+    /// a source directory as a side effect.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the alias is invalid.
+    pub fn lock_path(&self, source: &str) -> Result<PathBuf> {
+        self.tool_dir(source)?;
+        Ok(self.root_dir.join(LOCKS_DIR).join(format!("{source}.lock")))
+    }
+
+    /// Takes an advisory lock on a source, waiting while another process
+    /// holds a conflicting one.
+    ///
+    /// Contention is logged once as "source busy" and retried every 50 ms
+    /// until the lock timeout (see [`with_lock_timeout`](Self::with_lock_timeout))
+    /// has passed. The wait blocks the thread; async callers should use
+    /// [`lock_source_async`](Self::lock_source_async).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the lock file cannot be opened, or the source is
+    /// still busy when the timeout expires.
+    pub fn lock_source(&self, source: &str, mode: LockMode) -> Result<SourceLock> {
+        let file = self.open_lock_file(source)?;
+        let started = Instant::now();
+        let mut announced = false;
+        loop {
+            if self.try_lock(&file, source, mode, started, &mut announced)? {
+                return Ok(SourceLock { _file: file });
+            }
+            std::thread::sleep(LOCK_RETRY_INTERVAL);
+        }
+    }
+
+    /// Like [`lock_source`](Self::lock_source), but waits on the Tokio timer
+    /// instead of sleeping, so a busy source does not hold up other tasks on
+    /// the same worker thread.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the lock file cannot be opened, or the source is
+    /// still busy when the timeout expires.
+    pub async fn lock_source_async(&self, source: &str, mode: LockMode) -> Result<SourceLock> {
+        let file = self.open_lock_file(source)?;
+        let started = Instant::now();
+        let mut announced = false;
+        loop {
+            if self.try_lock(&file, source, mode, started, &mut announced)? {
+                return Ok(SourceLock { _file: file });
+            }
+            tokio::time::sleep(LOCK_RETRY_INTERVAL).await;
+        }
+    }
+
+    fn open_lock_file(&self, source: &str) -> Result<fs::File> {
+        let path = self.lock_path(source)?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| Error::Storage(format!("Failed to create locks directory: {e}")))?;
+        }
+        fs::OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .read(true)
+            .write(true)
+            .open(&path)
+            .map_err(|e| Error::Storage(format!("Failed to open lock for '{source}': {e}")))
+    }
+
+    /// One attempt at a lock: `Ok(false)` while another process holds a
+    /// conflicting one and the timeout has not yet passed.
+    fn try_lock(
+        &self,
+        file: &fs::File,
+        source: &str,
+        mode: LockMode,
+        started: Instant,
+        announced: &mut bool,
+    ) -> Result<bool> {
+        let attempt = match mode {
+            LockMode::Shared => FileExt::try_lock_shared(file),
+            LockMode::Exclusive => FileExt::try_lock_exclusive(file),
+        };
+        match attempt {
+            Ok(()) => Ok(true),
+            Err(e) if e.raw_os_error() == fs2::lock_contended_error().raw_os_error() => {
+                let timeout = self.lock_timeout;
+                if started.elapsed() >= timeout {
+                    return Err(Error::Storage(format!(
+                        "Source '{source}' is busy: another blz process is updating it \
+                         (gave up after {}s; raise BLZ_LOCK_TIMEOUT to wait longer)",
+                        timeout.as_secs()
+                    )));
+                }
+                if !*announced {
+                    warn!("Source '{source}' is busy in another blz process; retrying");
+                    *announced = true;
+                }
+                Ok(false)
+            },
+            Err(e) => Err(Error::Storage(format!(
+                "Failed to lock source '{source}': {e}"
+            ))),
+        }
+    }
+
+    /// Opens a source's search index under a shared lock, so it is never
+    /// opened while a sync is swapping it. The source's ranking settings are
+    /// applied to its queries.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the source stays busy past the lock timeout or the
+    /// index cannot be opened.
+    pub fn open_index(&self, source: &str) -> Result<SearchIndex> {
+        let ranking = self.load_ranking(source)?;
+        let _lock = self.lock_source(source, LockMode::Shared)?;
+        Ok(SearchIndex::open(&self.index_dir(source)?)?.with_ranking(ranking))
+    }
+
+    /// Returns the path to the journal marking an unfinished sync of a source.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the alias is invalid.
+    pub fn sync_journal_path(&self, source: &str) -> Result<PathBuf> {
+        Ok(self.source_dir(source)?.join(SYNC_JOURNAL))
+    }
+
+    /// Records that a sync is about to modify a source.
+    ///
+    /// The journal stays until [`finish_sync`](Self::finish_sync), so a sync
+    /// killed between writing content and committing the index leaves it
+    /// behind for [`interrupted_sync`](Self::interrupted_sync) to find.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the journal cannot be written.
+    pub fn begin_sync(&self, source: &str) -> Result<()> {
+        self.ensure_tool_dir(source)?;
+        let journal = SyncJournal {
+            started_at: Utc::now(),
+            pid: std::process::id(),
+        };
+        let json = serde_json::to_string(&journal)
+            .map_err(|e| Error::Storage(format!("Failed to serialize sync journal: {e}")))?;
+        write_atomic(&self.sync_journal_path(source)?, json.as_bytes())
+            .map_err(|e| Error::Storage(format!("Failed to write sync journal: {e}")))
+    }
+
+    /// Clears the journal once a source's content, metadata, and index agree.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the journal exists but cannot be removed.
+    pub fn finish_sync(&self, source: &str) -> Result<()> {
+        match fs::remove_file(self.tool_dir(source)?.join(SYNC_JOURNAL)) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(Error::Storage(format!("Failed to clear sync journal: {e}")))
+            },
+            _ => Ok(()),
         }
+    }
+
+    /// Returns the journal of a sync that started but never finished.
+    ///
+    /// Journals owned by a process that is still running are in progress,
+    /// not interrupted, and return `None`. An unreadable journal counts as
+    /// interrupted.
+    #[must_use]
+    pub fn interrupted_sync(&self, source: &str) -> Option<SyncJournal> {
+        let path = self.sync_journal_path(source).ok()?;
+        let json = fs::read_to_string(path).ok()?;
+        let journal = serde_json::from_str(&json).unwrap_or(SyncJournal {
+            started_at: DateTime::UNIX_EPOCH,
+            pid: 0,
+        });
+        (!process_is_running(journal.pid)).then_some(journal)
+    }
+
+    /// Saves the llms.txt content for a source.
+    ///
+    /// Content of 64 KiB or more is stored zstd-compressed as `llms.txt.zst`
+    /// unless compression is disabled; the other form is removed so exactly
+    /// one copy exists.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the content cannot be compressed, or the file
+    /// cannot be written or renamed.
+    pub fn save_llms_txt(&self, source: &str, content: &str) -> Result<()> {
+        self.ensure_tool_dir(source)?;
+        let plain = self.llms_txt_path(source)?;
+        let compressed = self.compressed_llms_txt_path(source)?;
+        let (path, stale, bytes) = if self.compression && content.len() >= COMPRESS_THRESHOLD {
+            let bytes = compress(content.as_bytes())
+                .map_err(|e| Error::Storage(format!("Failed to compress llms.txt: {e}")))?;
+            (compressed, plain, Cow::Owned(bytes))
+        } else {
+            (plain, compressed, Cow::Borrowed(content.as_bytes()))
+        };
 
-        fs::rename(&tmp_path, &path)
-            .map_err(|e| Error::Storage(format!("Failed to commit llms.txt: {e}")))?;
+        write_atomic(&path, &bytes)
+            .map_err(|e| Error::Storage(format!("Failed to write llms.txt: {e}")))?;
+        match fs::remove_file(&stale) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                return Err(Error::Storage(format!(
+                    "Failed to remove stale {}: {e}",
+                    stale.display()
+                )));
+            },
+            _ => {},
+        }
 
         debug!("Saved llms.txt for {}", source);
         Ok(())
     }
 
-    /// Loads the llms.txt content for a source.
+    /// Loads the llms.txt content for a source, decompressing it if needed.
     ///
     /// # Errors
     ///
-    /// Returns an error if the file cannot be read.
+    /// Returns an error if the file cannot be read or decompressed.
     pub fn load_llms_txt(&self, source: &str) -> Result<String> {
-        let path = self.llms_txt_path(source)?;
+        let path = self
+            .stored_llms_txt_path(source)?
+            .unwrap_or(self.llms_txt_path(source)?);
+        read_content(&path).map_err(|e| Error::Storage(format!("Failed to read llms.txt: {e}")))
+    }
+
+    /// Returns the path to a collection member's cached copy.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the alias or member prefix is invalid.
+    pub fn member_txt_path(&self, source: &str, prefix: &str) -> Result<PathBuf> {
+        crate::collection::validate_prefix(prefix)?;
+        Ok(self
+            .source_dir(source)?
+            .join("members")
+            .join(format!("{prefix}.txt")))
+    }
+
+    /// Saves the latest copy of a collection member.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    pub fn save_member_txt(&self, source: &str, prefix: &str, content: &str) -> Result<()> {
+        self.ensure_tool_dir(source)?;
+        let path = self.member_txt_path(source, prefix)?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| Error::Storage(format!("Failed to create members directory: {e}")))?;
+        }
+        write_atomic(&path, content.as_bytes())
+            .map_err(|e| Error::Storage(format!("Failed to write member '{prefix}': {e}")))?;
+        debug!("Saved member {} for {}", prefix, source);
+        Ok(())
+    }
+
+    /// Loads the cached copy of a collection member.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read.
+    pub fn load_member_txt(&self, source: &str, prefix: &str) -> Result<String> {
+        let path = self.member_txt_path(source, prefix)?;
         fs::read_to_string(&path)
-            .map_err(|e| Error::Storage(format!("Failed to read llms.txt: {e}")))
+            .map_err(|e| Error::Storage(format!("Failed to read member '{prefix}': {e}")))
     }
 
     /// Saves the parsed llms.json data for a source.
@@ -427,19 +1394,19 @@ impl Storage {
         let json = serde_json::to_string_pretty(data)
             .map_err(|e| Error::Storage(format!("Failed to serialize JSON: {e}")))?;
 
-        let tmp_path = path.with_extension("json.tmp");
-        fs::write(&tmp_path, json)
+        write_atomic(&path, json.as_bytes())
             .map_err(|e| Error::Storage(format!("Failed to write llms.json: {e}")))?;
 
-        #[cfg(target_os = "windows")]
-        if path.exists() {
-            fs::remove_file(&path)
-                .map_err(|e| Error::Storage(format!("Failed to remove existing llms.json: {e}")))?;
-        }
-        fs::rename(&tmp_path, &path)
-            .map_err(|e| Error::Storage(format!("Failed to commit llms.json: {e}")))?;
-
         debug!("Saved llms.json for {}", source);
+        let metadata = self.load_source_metadata(source).ok().flatten();
+        self.write_summary(
+            source,
+            &SourceSummary::new(
+                metadata.as_ref().unwrap_or(&data.metadata),
+                data.line_index.total_lines,
+            ),
+        );
+        self.invalidate_query_cache();
         Ok(())
     }
 
@@ -481,6 +1448,26 @@ impl Storage {
         Ok(data)
     }
 
+    /// Records fresh health observations in a source's llms.json.
+    ///
+    /// Pass `None` for `upstream_reachable` when the upstream was not probed;
+    /// the previously recorded value is kept in that case.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if llms.json cannot be loaded or written.
+    pub fn record_health(
+        &self,
+        source: &str,
+        upstream_reachable: Option<bool>,
+    ) -> Result<HealthRecord> {
+        let mut data = self.load_llms_json(source)?;
+        let record = HealthRecord::observe(&data, upstream_reachable);
+        data.health = Some(record.clone());
+        self.save_llms_json(source, &data)?;
+        Ok(record)
+    }
+
     /// Saves source metadata for a source.
     ///
     /// # Errors
@@ -492,24 +1479,103 @@ impl Storage {
         let json = serde_json::to_string_pretty(metadata)
             .map_err(|e| Error::Storage(format!("Failed to serialize metadata: {e}")))?;
 
-        // Write to a temp file first to ensure atomicity
-        let tmp_path = path.with_extension("json.tmp");
-        fs::write(&tmp_path, &json)
-            .map_err(|e| Error::Storage(format!("Failed to write temp metadata: {e}")))?;
-
-        // Atomically rename temp file to final path (handle Windows overwrite)
-        #[cfg(target_os = "windows")]
-        if path.exists() {
-            fs::remove_file(&path)
-                .map_err(|e| Error::Storage(format!("Failed to remove existing metadata: {e}")))?;
-        }
-        fs::rename(&tmp_path, &path)
+        write_atomic(&path, json.as_bytes())
             .map_err(|e| Error::Storage(format!("Failed to persist metadata: {e}")))?;
 
         debug!("Saved metadata for {}", source);
+        // Before llms.json exists there is no line count to summarize yet
+        let total_lines = match self.read_summary(source) {
+            Some(summary) => Some(summary.total_lines),
+            None => self
+                .load_llms_json(source)
+                .ok()
+                .map(|data| data.line_index.total_lines),
+        };
+        if let Some(total_lines) = total_lines {
+            self.write_summary(source, &SourceSummary::new(metadata, total_lines));
+        }
         Ok(())
     }
 
+    /// Loads the fields a search reads without parsing `llms.json`.
+    ///
+    /// Caches written before summaries existed are summarized from
+    /// `metadata.json` and `llms.json` once, and the summary is saved for
+    /// later queries. A summary older than `metadata.json` is refreshed from
+    /// it. Returns `None` for a source that is not cached.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the alias is invalid or the fallback files cannot
+    /// be parsed.
+    pub fn load_source_summary(&self, source: &str) -> Result<Option<SourceSummary>> {
+        if let Some(summary) = self.read_summary(source) {
+            if !self.summary_outdated(source) {
+                return Ok(Some(summary));
+            }
+            let Some(metadata) = self.load_source_metadata(source)? else {
+                return Ok(Some(summary));
+            };
+            let refreshed = SourceSummary::new(&metadata, summary.total_lines);
+            self.write_summary(source, &refreshed);
+            return Ok(Some(refreshed));
+        }
+        if !self.exists(source) {
+            return Ok(None);
+        }
+        let data = self.load_llms_json(source)?;
+        let metadata = self.load_source_metadata(source)?;
+        let summary = SourceSummary::new(
+            metadata.as_ref().unwrap_or(&data.metadata),
+            data.line_index.total_lines,
+        );
+        self.write_summary(source, &summary);
+        Ok(Some(summary))
+    }
+
+    /// Reads `summary.json`, treating a missing or unreadable file as absent.
+    fn read_summary(&self, source: &str) -> Option<SourceSummary> {
+        let path = self.summary_path(source).ok()?;
+        let json = fs::read_to_string(path).ok()?;
+        serde_json::from_str(&json)
+            .inspect_err(|e| warn!("Ignoring unreadable summary for {source}: {e}"))
+            .ok()
+    }
+
+    /// Whether `metadata.json` was written after `summary.json`, e.g. by hand
+    /// or by a tool that does not know about summaries.
+    fn summary_outdated(&self, source: &str) -> bool {
+        let modified = |path: Result<PathBuf>| {
+            path.ok()
+                .and_then(|path| fs::metadata(path).ok())
+                .and_then(|meta| meta.modified().ok())
+        };
+        match (
+            modified(self.summary_path(source)),
+            modified(self.metadata_path(source)),
+        ) {
+            (Some(summary), Some(metadata)) => metadata > summary,
+            _ => false,
+        }
+    }
+
+    /// Writes `summary.json`. Failures are logged rather than returned: the
+    /// summary is rebuilt from the full files on the next read.
+    fn write_summary(&self, source: &str, summary: &SourceSummary) {
+        if self.is_shared(source) {
+            return;
+        }
+        let result = self.summary_path(source).and_then(|path| {
+            let json = serde_json::to_string_pretty(summary)
+                .map_err(|e| Error::Storage(format!("Failed to serialize summary: {e}")))?;
+            write_atomic(&path, json.as_bytes())
+                .map_err(|e| Error::Storage(format!("Failed to persist summary: {e}")))
+        });
+        if let Err(e) = result {
+            warn!("Failed to save summary for {source}: {e}");
+        }
+    }
+
     /// Save anchors remap JSON for a source.
     ///
     /// # Errors
@@ -520,11 +1586,43 @@ impl Storage {
         let path = self.anchors_map_path(source)?;
         let json = serde_json::to_string_pretty(map)
             .map_err(|e| Error::Storage(format!("Failed to serialize anchors map: {e}")))?;
-        fs::write(&path, json)
+        write_atomic(&path, json.as_bytes())
             .map_err(|e| Error::Storage(format!("Failed to write anchors map: {e}")))?;
         Ok(())
     }
 
+    /// Saves the mirror list and mirror health for a source.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the mirror list cannot be serialized or written.
+    pub fn save_mirrors(&self, source: &str, mirrors: &crate::MirrorList) -> Result<()> {
+        self.ensure_tool_dir(source)?;
+        let path = self.mirrors_path(source)?;
+        let json = serde_json::to_string_pretty(mirrors)
+            .map_err(|e| Error::Storage(format!("Failed to serialize mirrors: {e}")))?;
+        write_atomic(&path, json.as_bytes())
+            .map_err(|e| Error::Storage(format!("Failed to write mirrors: {e}")))?;
+        Ok(())
+    }
+
+    /// Loads the mirror list for a source if one is configured.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the mirror list cannot be read or parsed.
+    pub fn load_mirrors(&self, source: &str) -> Result<Option<crate::MirrorList>> {
+        let path = self.mirrors_path(source)?;
+        if !path.exists() {
+            return Ok(None);
+        }
+        let json = fs::read_to_string(&path)
+            .map_err(|e| Error::Storage(format!("Failed to read mirrors: {e}")))?;
+        let mirrors = serde_json::from_str(&json)
+            .map_err(|e| Error::Storage(format!("Failed to parse mirrors: {e}")))?;
+        Ok(Some(mirrors))
+    }
+
     /// Loads source metadata for a source if it exists.
     ///
     /// # Errors
@@ -551,12 +1649,17 @@ impl Storage {
     }
 
     /// Lists all cached sources
+    ///
+    /// Includes sources from the shared cache, if one is layered underneath.
     #[must_use]
     pub fn list_sources(&self) -> Vec<String> {
         let mut sources = Vec::new();
-        let sources_dir = self.root_dir.join("sources");
+        let roots = std::iter::once(self.root_dir.as_path()).chain(self.shared_root.as_deref());
 
-        if let Ok(entries) = fs::read_dir(&sources_dir) {
+        for root in roots {
+            let Ok(entries) = fs::read_dir(root.join("sources")) else {
+                continue;
+            };
             for entry in entries.flatten() {
                 if entry.path().is_dir() {
                     if let Some(name) = entry.file_name().to_str() {
@@ -569,21 +1672,36 @@ impl Storage {
         }
 
         sources.sort();
+        sources.dedup();
         sources
     }
 
     /// Clears the entire cache directory, removing all sources and their data.
     ///
+    /// Named profiles stored under the root are left untouched.
+    ///
     /// This is a destructive operation that cannot be undone. Use with caution.
     ///
     /// # Errors
     ///
     /// Returns an error if the cache directory cannot be removed or recreated.
     pub fn clear_cache(&self) -> Result<()> {
-        // Remove the entire root directory
-        if self.root_dir.exists() {
-            fs::remove_dir_all(&self.root_dir)
-                .map_err(|e| Error::Storage(format!("Failed to remove cache directory: {e}")))?;
+        // Remove everything under the root except other profiles' data
+        if let Ok(entries) = fs::read_dir(&self.root_dir) {
+            for entry in entries.flatten() {
+                if entry.file_name() == profile::PROFILES_DIR {
+                    continue;
+                }
+                let path = entry.path();
+                let removed = if path.is_dir() {
+                    fs::remove_dir_all(&path)
+                } else {
+                    fs::remove_file(&path)
+                };
+                removed.map_err(|e| {
+                    Error::Storage(format!("Failed to remove cache directory: {e}"))
+                })?;
+            }
         }
 
         // Recreate empty root directory
@@ -599,12 +1717,13 @@ impl Storage {
     ///
     /// Returns an error if the archive directory cannot be created or files cannot be copied.
     pub fn archive(&self, source: &str) -> Result<()> {
+        self.ensure_tool_dir(source)?;
         let archive_dir = self.archive_dir(source)?;
         fs::create_dir_all(&archive_dir)
             .map_err(|e| Error::Storage(format!("Failed to create archive directory: {e}")))?;
 
         // Include seconds for uniqueness and clearer chronology
-        let timestamp = Utc::now().format("%Y-%m-%dT%H-%M-%SZ");
+        let timestamp = Utc::now().format(ARCHIVE_TIMESTAMP_FORMAT);
 
         // Archive all llms*.json and llms*.txt files
         let dir = self.tool_dir(source)?;
@@ -642,6 +1761,270 @@ impl Storage {
         Ok(())
     }
 
+    /// Lists archived snapshots of a source, oldest first.
+    ///
+    /// Only snapshots with an archived `llms.json` are returned; files that do
+    /// not follow the archive naming scheme are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the archive directory exists but cannot be read.
+    pub fn list_archives(&self, source: &str) -> Result<Vec<ArchiveSnapshot>> {
+        let dir = self.archive_dir(source)?;
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut snapshots = Vec::new();
+        for entry in fs::read_dir(&dir)
+            .map_err(|e| Error::Storage(format!("Failed to read archive directory: {e}")))?
+        {
+            let entry = entry.map_err(|e| Error::Storage(format!("Failed to read entry: {e}")))?;
+            let name = entry.file_name();
+            let Some(id) = name.to_str().and_then(|n| n.strip_suffix("-llms.json")) else {
+                continue;
+            };
+            let Ok(archived_at) = NaiveDateTime::parse_from_str(id, ARCHIVE_TIMESTAMP_FORMAT)
+            else {
+                continue;
+            };
+            snapshots.push(ArchiveSnapshot {
+                id: id.to_string(),
+                archived_at: archived_at.and_utc(),
+                llms_json: entry.path(),
+                llms_txt: dir.join(format!("{id}-llms.txt")),
+            });
+        }
+
+        snapshots.sort_by_key(|snapshot| snapshot.archived_at);
+        Ok(snapshots)
+    }
+
+    /// Returns the directory holding content-addressed llms.txt versions.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the alias is invalid.
+    pub fn versions_dir(&self, source: &str) -> Result<PathBuf> {
+        Ok(self.archive_dir(source)?.join(VERSIONS_DIR))
+    }
+
+    /// Archives the current llms.txt as a compressed, content-addressed version.
+    ///
+    /// Call this before overwriting llms.txt with new content. Identical
+    /// content is stored once however often it recurs. Only the `keep` most
+    /// recently replaced versions are retained; older ones are pruned, and
+    /// `keep == 0` disables version history.
+    ///
+    /// Returns the recorded version, or `None` when there was no llms.txt to
+    /// archive or history is disabled.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the content cannot be read, compressed, or written.
+    pub fn archive_version(&self, source: &str, keep: usize) -> Result<Option<ContentVersion>> {
+        if self.stored_llms_txt_path(source)?.is_none() {
+            return Ok(None);
+        }
+        self.ensure_tool_dir(source)?;
+        let content = self.load_llms_txt(source)?;
+
+        let mut versions = self.list_versions(source)?;
+        let version = if keep == 0 {
+            None
+        } else {
+            let (id, sha256) = content_digest(&content);
+            let dir = self.versions_dir(source)?;
+            fs::create_dir_all(&dir)
+                .map_err(|e| Error::Storage(format!("Failed to create versions directory: {e}")))?;
+            let object = dir.join(format!("{id}.txt.{ZSTD_EXT}"));
+            if !object.exists() && !dir.join(format!("{id}.txt.gz")).exists() {
+                compress(content.as_bytes())
+                    .and_then(|bytes| write_atomic(&object, &bytes))
+                    .map_err(|e| Error::Storage(format!("Failed to store version {id}: {e}")))?;
+            }
+            let version = ContentVersion {
+                id,
+                sha256,
+                fetched_at: self
+                    .load_source_metadata(source)
+                    .ok()
+                    .flatten()
+                    .map(|metadata| metadata.fetched_at),
+                archived_at: Utc::now(),
+                lines: content.lines().count(),
+            };
+            versions.push(version.clone());
+            Some(version)
+        };
+
+        let excess = versions.len().saturating_sub(keep);
+        let pruned: Vec<ContentVersion> = versions.drain(..excess).collect();
+        self.save_versions(source, &versions)?;
+        for old in pruned {
+            if !versions.iter().any(|v| v.id == old.id) {
+                let dir = self.versions_dir(source)?;
+                for ext in [ZSTD_EXT, "gz"] {
+                    match fs::remove_file(dir.join(format!("{}.txt.{ext}", old.id))) {
+                        Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                            warn!("Failed to prune version {} of {}: {}", old.id, source, e);
+                        },
+                        _ => {},
+                    }
+                }
+            }
+        }
+
+        if let Some(version) = &version {
+            debug!("Archived version {} of {}", version.id, source);
+        }
+        Ok(version)
+    }
+
+    /// Lists archived llms.txt versions of a source, oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the manifest exists but cannot be read or parsed.
+    pub fn list_versions(&self, source: &str) -> Result<Vec<ContentVersion>> {
+        let path = self.versions_dir(source)?.join(VERSIONS_MANIFEST);
+        match fs::read_to_string(&path) {
+            Ok(json) => serde_json::from_str(&json)
+                .map_err(|e| Error::Storage(format!("Invalid versions manifest: {e}"))),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+            Err(e) => Err(Error::Storage(format!(
+                "Failed to read versions manifest: {e}"
+            ))),
+        }
+    }
+
+    /// Loads the content of an archived version by its full hex id.
+    ///
+    /// Versions are stored zstd-compressed; gzip objects written by older
+    /// releases are still read.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::NotFound`] if no such version is stored, or an error if
+    /// it cannot be decompressed.
+    pub fn load_version(&self, source: &str, id: &str) -> Result<String> {
+        if id.is_empty() || !id.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(Error::NotFound(format!("Version {id} of {source}")));
+        }
+        let dir = self.versions_dir(source)?;
+        let object = dir.join(format!("{id}.txt.{ZSTD_EXT}"));
+        if object.is_file() {
+            return read_content(&object)
+                .map_err(|e| Error::Storage(format!("Failed to decompress version {id}: {e}")));
+        }
+        let file = fs::File::open(dir.join(format!("{id}.txt.gz")))
+            .map_err(|_| Error::NotFound(format!("Version {id} of {source}")))?;
+        let mut content = String::new();
+        GzDecoder::new(file)
+            .read_to_string(&mut content)
+            .map_err(|e| Error::Storage(format!("Failed to decompress version {id}: {e}")))?;
+        Ok(content)
+    }
+
+    /// Finds the archived version whose hex id starts with `prefix` (at least
+    /// four characters) or whose base64 SHA-256 equals it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the manifest cannot be read or the prefix is ambiguous.
+    pub fn find_version(&self, source: &str, prefix: &str) -> Result<Option<ContentVersion>> {
+        let needle = prefix.to_ascii_lowercase();
+        let matches: Vec<ContentVersion> = self
+            .list_versions(source)?
+            .into_iter()
+            .filter(|v| {
+                v.sha256 == prefix
+                    || (needle.len() >= MIN_VERSION_PREFIX && v.id.starts_with(&needle))
+            })
+            .collect();
+        if matches.windows(2).any(|pair| pair[0].id != pair[1].id) {
+            return Err(Error::Storage(format!(
+                "Version prefix '{prefix}' is ambiguous for {source}"
+            )));
+        }
+        Ok(matches.into_iter().next_back())
+    }
+
+    fn save_versions(&self, source: &str, versions: &[ContentVersion]) -> Result<()> {
+        self.ensure_tool_dir(source)?;
+        let dir = self.versions_dir(source)?;
+        if versions.is_empty() && !dir.exists() {
+            return Ok(());
+        }
+        fs::create_dir_all(&dir)
+            .map_err(|e| Error::Storage(format!("Failed to create versions directory: {e}")))?;
+        let json = serde_json::to_string_pretty(versions)
+            .map_err(|e| Error::Storage(format!("Failed to serialize versions: {e}")))?;
+        let path = dir.join(VERSIONS_MANIFEST);
+        write_atomic(&path, json.as_bytes())
+            .map_err(|e| Error::Storage(format!("Failed to write versions manifest: {e}")))
+    }
+
+    /// Computes the disk usage of a source, split into content, archives, and index.
+    ///
+    /// Symlinks are not followed. A missing source directory reports zero usage.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the alias is invalid or a directory cannot be read.
+    pub fn disk_usage(&self, source: &str) -> Result<SourceDiskUsage> {
+        let tool_dir = self.source_dir(source)?;
+        let archive_dir = self.archive_dir(source)?;
+        let index_dir = self.index_dir(source)?;
+        let mut usage = SourceDiskUsage::default();
+
+        let entries = match fs::read_dir(&tool_dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(usage),
+            Err(e) => {
+                return Err(Error::Storage(format!(
+                    "Failed to read {}: {e}",
+                    tool_dir.display()
+                )));
+            },
+        };
+
+        for entry in entries {
+            let path = entry
+                .map_err(|e| Error::Storage(format!("Failed to read directory entry: {e}")))?
+                .path();
+            let size = Self::path_size(&path)?;
+            if path == archive_dir {
+                usage.archives += size;
+            } else if path == index_dir {
+                usage.index += size;
+            } else {
+                usage.content += size;
+            }
+        }
+
+        Ok(usage)
+    }
+
+    /// Recursively sums file sizes under `path` without following symlinks.
+    pub(crate) fn path_size(path: &Path) -> Result<u64> {
+        let metadata = fs::symlink_metadata(path)
+            .map_err(|e| Error::Storage(format!("Failed to stat {}: {e}", path.display())))?;
+        if !metadata.is_dir() {
+            return Ok(metadata.len());
+        }
+
+        let mut total = 0;
+        let entries = fs::read_dir(path)
+            .map_err(|e| Error::Storage(format!("Failed to read {}: {e}", path.display())))?;
+        for entry in entries {
+            let entry = entry
+                .map_err(|e| Error::Storage(format!("Failed to read directory entry: {e}")))?;
+            total += Self::path_size(&entry.path())?;
+        }
+        Ok(total)
+    }
+
     /// Check for old cache directory and migrate if needed
     fn check_and_migrate_old_cache(new_root: &Path) {
         // Try to find the old cache directory
@@ -661,7 +2044,8 @@ impl Storage {
                                 return false;
                             }
                             let has_llms_json = path.join("llms.json").exists();
-                            let has_llms_txt = path.join("llms.txt").exists();
+                            let has_llms_txt = path.join("llms.txt").exists()
+                                || path.join("llms.txt.zst").exists();
                             let has_metadata = path.join("metadata.json").exists();
                             has_llms_json || has_llms_txt || has_metadata
                         })
@@ -788,6 +2172,7 @@ mod tests {
                 heading_path_normalized: Some(vec!["getting started".to_string()]),
                 lines: "1-50".to_string(),
                 anchor: None,
+                id: None,
                 children: vec![],
             }],
             files: vec![FileInfo {
@@ -801,6 +2186,7 @@ mod tests {
             diagnostics: vec![],
             parse_meta: None,
             filter_stats: None,
+            health: None,
         }
     }
 
@@ -839,6 +2225,19 @@ mod tests {
         assert!(archive_dir.ends_with("react/.archive"));
     }
 
+    #[test]
+    fn test_mirrors_round_trip() {
+        let (storage, _temp_dir) = create_test_storage();
+        assert!(storage.load_mirrors("react").unwrap().is_none());
+
+        let mut mirrors =
+            crate::MirrorList::new(vec!["https://mirror.example.com/llms.txt".to_string()]);
+        mirrors.record_failure("https://example.com/llms.txt", Utc::now());
+        storage.save_mirrors("react", &mirrors).unwrap();
+
+        assert_eq!(storage.load_mirrors("react").unwrap(), Some(mirrors));
+    }
+
     #[test]
     fn test_invalid_alias_validation() {
         let (storage, _temp_dir) = create_test_storage();
@@ -905,6 +2304,238 @@ mod tests {
         assert_eq!(content, loaded_content);
     }
 
+    #[test]
+    fn test_large_llms_txt_is_stored_compressed() {
+        let (storage, _temp_dir) = create_test_storage();
+        let large = "## Section\n\nRepeated documentation line.\n".repeat(4096);
+        let plain = storage.llms_txt_path("react").expect("path");
+        let compressed = storage.compressed_llms_txt_path("react").expect("path");
+
+        storage.save_llms_txt("react", &large).expect("Should save");
+        assert!(compressed.is_file() && !plain.exists());
+        assert!(fs::metadata(&compressed).expect("meta").len() < large.len() as u64 / 5);
+        assert_eq!(storage.load_llms_txt("react").expect("Should load"), large);
+        assert_eq!(
+            storage.stored_llms_txt_path("react").expect("path"),
+            Some(compressed.clone())
+        );
+
+        storage
+            .save_llms_txt("react", "# Small\n")
+            .expect("Should save");
+        assert!(plain.is_file() && !compressed.exists());
+
+        let storage = storage.with_compression(false);
+        storage.save_llms_txt("react", &large).expect("Should save");
+        assert!(plain.is_file() && !compressed.exists());
+        assert_eq!(storage.load_llms_txt("react").expect("Should load"), large);
+    }
+
+    #[test]
+    fn test_atomic_writes_leave_no_temp_files() {
+        let (storage, _temp_dir) = create_test_storage();
+        storage
+            .save_llms_txt("react", "# React\n")
+            .expect("Should save");
+        storage
+            .save_llms_txt("react", "# React v2\n")
+            .expect("Should overwrite");
+        let dir = storage.tool_dir("react").expect("dir");
+        let leftovers: Vec<_> = fs::read_dir(&dir)
+            .expect("read dir")
+            .flatten()
+            .filter(|entry| entry.file_name().to_string_lossy().ends_with(".tmp"))
+            .collect();
+        assert!(leftovers.is_empty());
+        assert_eq!(
+            storage.load_llms_txt("react").expect("load"),
+            "# React v2\n"
+        );
+    }
+
+    #[test]
+    fn test_source_locks_exclude_writers_from_readers() {
+        let (storage, _temp_dir) = create_test_storage();
+        let storage = storage.with_lock_timeout(Duration::from_millis(120));
+
+        let reader = storage
+            .lock_source("react", LockMode::Shared)
+            .expect("Should lock");
+        let second_reader = storage
+            .lock_source("react", LockMode::Shared)
+            .expect("Readers share the lock");
+        let err = storage
+            .lock_source("react", LockMode::Exclusive)
+            .expect_err("Writer must wait for readers");
+        assert!(err.to_string().contains("busy"));
+        assert!(
+            !storage.exists("react"),
+            "Locking must not create the source"
+        );
+
+        drop((reader, second_reader));
+        let writer = storage
+            .lock_source("react", LockMode::Exclusive)
+            .expect("Should lock once readers are gone");
+        assert!(storage.lock_source("react", LockMode::Shared).is_err());
+        drop(writer);
+    }
+
+    #[tokio::test]
+    async fn test_async_source_lock_waits_without_blocking() {
+        let (storage, _temp_dir) = create_test_storage();
+        let storage = storage.with_lock_timeout(Duration::from_secs(5));
+
+        let reader = storage
+            .lock_source("react", LockMode::Shared)
+            .expect("Should lock");
+        let release = async {
+            tokio::time::sleep(Duration::from_millis(120)).await;
+            drop(reader);
+        };
+        // Both futures share one thread, so a blocking wait would time out
+        // before the reader is dropped
+        let (writer, ()) = tokio::join!(
+            storage.lock_source_async("react", LockMode::Exclusive),
+            release
+        );
+        writer.expect("Should lock once the reader is gone");
+    }
+
+    /// Every file under `dir` with its contents, keyed by relative path.
+    fn snapshot(dir: &Path) -> Vec<(PathBuf, Vec<u8>)> {
+        let mut files = Vec::new();
+        let mut pending = vec![dir.to_path_buf()];
+        while let Some(next) = pending.pop() {
+            for entry in fs::read_dir(&next).expect("read dir") {
+                let path = entry.expect("entry").path();
+                if path.is_dir() {
+                    pending.push(path);
+                } else {
+                    let bytes = fs::read(&path).expect("read file");
+                    files.push((path.strip_prefix(dir).expect("prefix").to_path_buf(), bytes));
+                }
+            }
+        }
+        files.sort();
+        files
+    }
+
+    #[test]
+    fn test_writes_never_touch_shared_root() {
+        let shared_dir = TempDir::new().expect("Failed to create temp directory");
+        let shared = Storage::with_root(shared_dir.path().to_path_buf()).expect("storage");
+        let llms = create_test_llms_json("react");
+        shared.save_llms_txt("react", "# React\n").expect("txt");
+        shared.save_llms_json("react", &llms).expect("json");
+        shared
+            .save_source_metadata("react", &llms.metadata)
+            .expect("metadata");
+        let bundle = shared_dir.path().join("react.tar.zst");
+        crate::bundle::export_bundle(
+            &shared,
+            &["react".to_string()],
+            &bundle,
+            crate::bundle::ExportOptions::default(),
+        )
+        .expect("export");
+        let before = snapshot(shared_dir.path());
+
+        let (storage, _temp_dir) = create_test_storage();
+        let storage = storage.with_shared_root(Some(shared_dir.path().to_path_buf()));
+        assert!(storage.save_member_txt("react", "api", "# API").is_err());
+        assert!(storage.archive("react").is_err());
+        assert!(storage.archive_version("react", 3).is_err());
+        assert!(storage.record_health("react", Some(true)).is_err());
+        storage.finish_sync("react").expect("No journal to clear");
+        storage.load_source_summary("react").expect("summary");
+        assert_eq!(snapshot(shared_dir.path()), before);
+
+        let report = crate::bundle::import_bundle(
+            &storage,
+            &bundle,
+            crate::bundle::ImportOptions { overwrite: true },
+        )
+        .expect("import");
+        assert_eq!(report.imported.len(), 1);
+        assert!(!storage.is_shared("react"));
+        assert!(
+            storage
+                .tool_dir("react")
+                .expect("dir")
+                .join("llms.json")
+                .is_file()
+        );
+        assert_eq!(snapshot(shared_dir.path()), before);
+    }
+
+    #[test]
+    fn test_shared_root_layers_under_writable_root() {
+        let shared_dir = TempDir::new().expect("Failed to create temp directory");
+        let shared = Storage::with_root(shared_dir.path().to_path_buf()).expect("storage");
+        shared
+            .save_llms_json("react", &create_test_llms_json("react"))
+            .expect("Should save");
+
+        let (storage, _temp_dir) = create_test_storage();
+        let storage = storage.with_shared_root(Some(shared_dir.path().to_path_buf()));
+        storage
+            .save_llms_json("vue", &create_test_llms_json("vue"))
+            .expect("Local adds still work");
+
+        assert_eq!(storage.list_sources(), ["react", "vue"]);
+        assert!(storage.is_shared("react"));
+        assert!(!storage.is_shared("vue"));
+        assert_eq!(
+            storage.load_llms_json("react").expect("Should read").source,
+            "react"
+        );
+        assert_eq!(
+            storage.source_dir("react").expect("dir"),
+            shared_dir.path().join("sources/react")
+        );
+        assert!(
+            storage
+                .tool_dir("react")
+                .expect("dir")
+                .starts_with(storage.root_dir())
+        );
+
+        let err = storage
+            .save_llms_txt("react", "# Overwritten")
+            .expect_err("Shared sources are read-only");
+        assert!(err.to_string().contains("read-only"));
+        storage
+            .record_search("react")
+            .expect("Access is not recorded");
+        assert!(!shared_dir.path().join("sources/react/llms.txt").exists());
+    }
+
+    #[test]
+    fn test_sync_journal_marks_interrupted_syncs() {
+        let (storage, _temp_dir) = create_test_storage();
+        storage.begin_sync("react").expect("Should begin");
+        // Still running in this process, so not interrupted
+        assert!(storage.interrupted_sync("react").is_none());
+
+        let dead = SyncJournal {
+            started_at: Utc::now(),
+            pid: u32::MAX,
+        };
+        fs::write(
+            storage.sync_journal_path("react").expect("path"),
+            serde_json::to_string(&dead).expect("json"),
+        )
+        .expect("write journal");
+        assert_eq!(storage.interrupted_sync("react"), Some(dead));
+
+        storage.finish_sync("react").expect("Should finish");
+        assert!(storage.interrupted_sync("react").is_none());
+        storage
+            .finish_sync("react")
+            .expect("Finishing twice is fine");
+    }
+
     #[test]
     fn test_save_and_load_llms_json() {
         let (storage, _temp_dir) = create_test_storage();
@@ -936,6 +2567,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_source_summary_tracks_saves() {
+        let (storage, _temp_dir) = create_test_storage();
+        let llms_json = create_test_llms_json("react");
+        assert!(storage.load_source_summary("react").unwrap().is_none());
+
+        storage.save_llms_json("react", &llms_json).unwrap();
+        let summary = storage.load_source_summary("react").unwrap().unwrap();
+        assert_eq!(summary.url, llms_json.metadata.url);
+        assert_eq!(summary.total_lines, llms_json.line_index.total_lines);
+
+        // Metadata updates keep the line count from the last llms.json
+        let mut metadata = llms_json.metadata.clone();
+        metadata.tags = vec!["index".to_string()];
+        storage.save_source_metadata("react", &metadata).unwrap();
+        let summary = storage.load_source_summary("react").unwrap().unwrap();
+        assert!(summary.is_index_only());
+        assert_eq!(summary.total_lines, llms_json.line_index.total_lines);
+
+        // Caches from before summaries existed are summarized on first read
+        fs::remove_file(storage.summary_path("react").unwrap()).unwrap();
+        let rebuilt = storage.load_source_summary("react").unwrap().unwrap();
+        assert_eq!(rebuilt, summary);
+        assert!(storage.summary_path("react").unwrap().exists());
+    }
+
     #[test]
     fn test_source_exists() {
         let (storage, _temp_dir) = create_test_storage();
@@ -952,6 +2609,76 @@ mod tests {
         assert!(storage.exists("react"));
     }
 
+    #[test]
+    fn test_record_health_persists_and_keeps_unprobed_reachability() {
+        let (storage, _temp_dir) = create_test_storage();
+        storage
+            .save_llms_json("react", &create_test_llms_json("react"))
+            .expect("Should save");
+
+        let first = storage
+            .record_health("react", Some(false))
+            .expect("Should record health");
+        assert_eq!(first.upstream_reachable, Some(false));
+        assert_eq!(first.lint_score, 100);
+
+        let second = storage
+            .record_health("react", None)
+            .expect("Should record health");
+        assert_eq!(second.upstream_reachable, Some(false));
+
+        let loaded = storage.load_llms_json("react").expect("Should load");
+        assert_eq!(loaded.health, Some(second));
+    }
+
+    #[test]
+    fn test_access_timestamps_are_recorded_and_coalesced() {
+        let (storage, _temp_dir) = create_test_storage();
+        // Unknown sources are ignored rather than materialized
+        storage.record_search("react").expect("record search");
+        assert_eq!(storage.load_access("react"), SourceAccess::default());
+
+        storage
+            .save_llms_json("react", &create_test_llms_json("react"))
+            .expect("save llms.json");
+        storage.record_search("react").expect("record search");
+        let first = storage.load_access("react");
+        assert!(first.last_searched.is_some());
+        assert!(first.last_fetched.is_none());
+
+        // A second search inside the write granularity keeps the file as is
+        storage.record_search("react").expect("record search");
+        assert_eq!(storage.load_access("react"), first);
+
+        storage.record_fetch("react").expect("record fetch");
+        let access = storage.load_access("react");
+        assert_eq!(access.last_searched, first.last_searched);
+        assert_eq!(access.last_used(), access.last_fetched);
+    }
+
+    #[test]
+    fn test_lint_score_penalizes_diagnostics_and_missing_headings() {
+        use crate::types::{Diagnostic, DiagnosticSeverity};
+
+        let mut llms = create_test_llms_json("react");
+        llms.diagnostics = vec![
+            Diagnostic {
+                severity: DiagnosticSeverity::Error,
+                message: "broken".to_string(),
+                line: None,
+            },
+            Diagnostic {
+                severity: DiagnosticSeverity::Warn,
+                message: "odd".to_string(),
+                line: Some(3),
+            },
+        ];
+        assert_eq!(crate::lint_score(&llms), 75);
+
+        llms.toc.clear();
+        assert_eq!(crate::lint_score(&llms), 45);
+    }
+
     #[test]
     fn test_list_sources_empty() {
         let (storage, _temp_dir) = create_test_storage();
@@ -1024,6 +2751,31 @@ mod tests {
         assert_eq!(sources[0], "complete");
     }
 
+    #[test]
+    fn test_disk_usage_splits_content_archives_and_index() {
+        let (storage, _temp_dir) = create_test_storage();
+        assert_eq!(
+            storage.disk_usage("missing").expect("Should report usage"),
+            SourceDiskUsage::default()
+        );
+
+        let content = "# Test content";
+        storage
+            .save_llms_txt("test", content)
+            .expect("Should save txt");
+        storage.archive("test").expect("Should archive");
+
+        let index_dir = storage.index_dir("test").expect("Should get index dir");
+        fs::create_dir_all(index_dir.join("segment")).expect("Should create index dir");
+        fs::write(index_dir.join("segment").join("data"), [0u8; 100]).expect("Should write");
+
+        let usage = storage.disk_usage("test").expect("Should report usage");
+        assert_eq!(usage.content, content.len() as u64);
+        assert_eq!(usage.archives, content.len() as u64);
+        assert_eq!(usage.index, 100);
+        assert_eq!(usage.total(), 100 + 2 * content.len() as u64);
+    }
+
     #[test]
     fn test_archive_functionality() {
         let (storage, _temp_dir) = create_test_storage();
@@ -1071,6 +2823,96 @@ mod tests {
         assert!(has_json, "Should have archived llms.json");
     }
 
+    #[test]
+    fn test_list_archives_orders_snapshots_and_skips_foreign_files() {
+        let (storage, _temp_dir) = create_test_storage();
+        assert!(
+            storage
+                .list_archives("test")
+                .expect("Should list")
+                .is_empty()
+        );
+
+        let archive_dir = storage.archive_dir("test").expect("Should get archive dir");
+        fs::create_dir_all(&archive_dir).expect("Should create archive dir");
+        for name in [
+            "2025-03-01T08-00-00Z-llms.json",
+            "2025-03-01T08-00-00Z-llms.txt",
+            "2024-12-31T23-59-59Z-llms.json",
+            "notes-llms.json",
+            "2025-03-01T08-00-00Z-llms.txt.bak",
+        ] {
+            fs::write(archive_dir.join(name), "{}").expect("Should write");
+        }
+
+        let snapshots = storage.list_archives("test").expect("Should list");
+        let ids: Vec<&str> = snapshots.iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(ids, ["2024-12-31T23-59-59Z", "2025-03-01T08-00-00Z"]);
+        assert_eq!(
+            snapshots[1].archived_at.to_rfc3339(),
+            "2025-03-01T08:00:00+00:00"
+        );
+        assert!(
+            snapshots[1]
+                .llms_txt
+                .ends_with("2025-03-01T08-00-00Z-llms.txt")
+        );
+    }
+
+    #[test]
+    fn test_versions_are_compressed_deduplicated_and_pruned() {
+        let (storage, _temp_dir) = create_test_storage();
+        assert_eq!(storage.archive_version("test", 2).expect("archive"), None);
+
+        for content in ["# One\n", "# Two\n", "# One\n", "# Three\n"] {
+            storage
+                .save_llms_txt("test", content)
+                .expect("Should save txt");
+            storage.archive_version("test", 2).expect("archive");
+        }
+
+        let versions = storage.list_versions("test").expect("Should list");
+        let (one, _) = content_digest("# One\n");
+        let (three, three_b64) = content_digest("# Three\n");
+        assert_eq!(
+            versions.iter().map(|v| v.id.as_str()).collect::<Vec<_>>(),
+            [one.as_str(), three.as_str()]
+        );
+        assert_eq!(versions[1].sha256, three_b64);
+
+        // "# Two" was pruned; its object is gone while the others remain
+        let objects = fs::read_dir(storage.versions_dir("test").expect("dir"))
+            .expect("Should read versions dir")
+            .filter(|entry| {
+                entry
+                    .as_ref()
+                    .is_ok_and(|e| e.file_name().to_string_lossy().ends_with(".txt.zst"))
+            })
+            .count();
+        assert_eq!(objects, 2);
+
+        assert_eq!(
+            storage.load_version("test", &three).expect("Should load"),
+            "# Three\n"
+        );
+        let found = storage
+            .find_version("test", &three[..8].to_ascii_uppercase())
+            .expect("Should look up");
+        assert_eq!(found.map(|v| v.id), Some(three.clone()));
+        let by_b64 = storage.find_version("test", &three_b64).expect("lookup");
+        assert_eq!(by_b64.map(|v| v.id), Some(three));
+        assert_eq!(storage.find_version("test", "abc").expect("lookup"), None);
+        assert!(storage.load_version("test", "../llms").is_err());
+
+        storage.archive_version("test", 0).expect("archive");
+        assert!(
+            storage
+                .list_versions("test")
+                .expect("Should list")
+                .is_empty()
+        );
+    }
+
     #[test]
     fn test_archive_missing_files() {
         let (storage, _temp_dir) = create_test_storage();
@@ -0,0 +1,90 @@
+//! Term frequency statistics for a source's search index.
+//!
+//! [`TermStat`] reports how often an indexed (tokenized) term occurs in a
+//! source, which helps users phrase queries with words the source actually
+//! uses. The counts come from the index's term dictionary via
+//! [`crate::SearchIndex::term_stats`]; this module holds the result type and
+//! the heading-path matching used to narrow the counts to one section.
+
+use serde::{Deserialize, Serialize};
+
+/// How often one indexed term occurs.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TermStat {
+    /// Indexed (tokenized, lowercased) form of the term.
+    pub term: String,
+    /// Number of heading sections containing the term.
+    pub doc_freq: u64,
+    /// Total number of occurrences across those sections.
+    pub term_freq: u64,
+}
+
+/// Split a heading path filter such as `"API > Hooks"` into lowercase segments.
+#[must_use]
+pub fn heading_filter_segments(filter: &str) -> Vec<String> {
+    filter
+        .split('>')
+        .map(|segment| segment.trim().to_lowercase())
+        .filter(|segment| !segment.is_empty())
+        .collect()
+}
+
+/// Whether a stored heading path (segments joined with `" > "`) falls under
+/// the filter segments.
+///
+/// The filter matches when its segments appear as a contiguous run anywhere in
+/// the path, case-insensitively, so `"Hooks"` matches `React > Hooks > useState`
+/// and `"React > Hooks"` matches the same path, but `"React > useState"` does not.
+#[must_use]
+pub fn heading_path_matches(heading_path: &str, filter: &[String]) -> bool {
+    if filter.is_empty() {
+        return true;
+    }
+    let path = heading_filter_segments(heading_path);
+    path.windows(filter.len()).any(|window| window == filter)
+}
+
+/// Order statistics by total occurrences, then section count, then term.
+pub(crate) fn rank(stats: &mut [TermStat]) {
+    stats.sort_by(|a, b| {
+        b.term_freq
+            .cmp(&a.term_freq)
+            .then_with(|| b.doc_freq.cmp(&a.doc_freq))
+            .then_with(|| a.term.cmp(&b.term))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn heading_filter_matches_contiguous_segments() {
+        let filter = heading_filter_segments(" react >  HOOKS ");
+        assert_eq!(filter, ["react", "hooks"]);
+        assert!(heading_path_matches("React > Hooks > useState", &filter));
+        assert!(heading_path_matches(
+            "React > Hooks",
+            &heading_filter_segments("hooks")
+        ));
+        assert!(!heading_path_matches(
+            "React > Hooks > useState",
+            &heading_filter_segments("React > useState")
+        ));
+        assert!(heading_path_matches("Anything", &[]));
+    }
+
+    #[test]
+    fn rank_orders_by_frequency_then_term() {
+        let stat = |term: &str, doc_freq, term_freq| TermStat {
+            term: term.to_string(),
+            doc_freq,
+            term_freq,
+        };
+        let mut stats = vec![stat("b", 1, 3), stat("a", 1, 3), stat("c", 2, 5)];
+        rank(&mut stats);
+        let order: Vec<&str> = stats.iter().map(|s| s.term.as_str()).collect();
+        assert_eq!(order, ["c", "a", "b"]);
+    }
+}
@@ -0,0 +1,267 @@
+//! Bandwidth limiting for downloads.
+//!
+//! [`Bandwidth`] parses human-friendly rates such as `500KB/s`, and
+//! [`Throttled`] wraps a byte stream with a shared [`TokenBucket`] so that
+//! long `sync --all` runs stay under the configured rate.
+
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::task::{Context, Poll, ready};
+use std::time::Duration;
+
+use futures::Stream;
+use serde::{Deserialize, Serialize};
+use tokio::time::{Instant, Sleep};
+
+use crate::numeric::u64_to_f64_lossy;
+
+const KIB: u64 = 1024;
+const MIB: u64 = KIB * 1024;
+const GIB: u64 = MIB * 1024;
+
+/// A download rate limit in bytes per second.
+///
+/// Parsed from strings like `500KB/s`, `2MB/s`, `1.5M`, or `65536`.
+/// Units are binary (`K` = 1024 bytes); the `/s` suffix is optional.
+///
+/// # Examples
+///
+/// ```
+/// use blz_core::throttle::Bandwidth;
+///
+/// let rate: Bandwidth = "500KB/s".parse().unwrap();
+/// assert_eq!(rate.bytes_per_second(), 500 * 1024);
+/// assert_eq!(rate.to_string(), "500KB/s");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Bandwidth(u64);
+
+impl Bandwidth {
+    /// Create a limit from a raw byte rate, rejecting zero.
+    #[must_use]
+    pub const fn from_bytes_per_second(bytes: u64) -> Option<Self> {
+        if bytes == 0 { None } else { Some(Self(bytes)) }
+    }
+
+    /// The limit in bytes per second.
+    #[must_use]
+    pub const fn bytes_per_second(self) -> u64 {
+        self.0
+    }
+}
+
+impl FromStr for Bandwidth {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("Invalid bandwidth '{input}' (expected e.g. 500KB/s or 2MB/s)");
+        let trimmed = input.trim();
+        let rate = trimmed
+            .strip_suffix("/s")
+            .or_else(|| trimmed.strip_suffix("/S"))
+            .unwrap_or(trimmed)
+            .trim();
+        let split = rate
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(rate.len());
+        let (number, unit) = rate.split_at(split);
+        let multiplier = match unit.trim().to_ascii_lowercase().as_str() {
+            "" | "b" => 1,
+            "k" | "kb" | "kib" => KIB,
+            "m" | "mb" | "mib" => MIB,
+            "g" | "gb" | "gib" => GIB,
+            _ => return Err(invalid()),
+        };
+        let value: f64 = number.parse().map_err(|_| invalid())?;
+        let bytes = value * u64_to_f64_lossy(multiplier);
+        if !bytes.is_finite() || bytes < 1.0 {
+            return Err(invalid());
+        }
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let bytes = bytes.min(u64_to_f64_lossy(u64::MAX)) as u64;
+        Self::from_bytes_per_second(bytes).ok_or_else(invalid)
+    }
+}
+
+impl fmt::Display for Bandwidth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            bytes if bytes % GIB == 0 => write!(f, "{}GB/s", bytes / GIB),
+            bytes if bytes % MIB == 0 => write!(f, "{}MB/s", bytes / MIB),
+            bytes if bytes % KIB == 0 => write!(f, "{}KB/s", bytes / KIB),
+            bytes => write!(f, "{bytes}B/s"),
+        }
+    }
+}
+
+impl TryFrom<String> for Bandwidth {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<Bandwidth> for String {
+    fn from(value: Bandwidth) -> Self {
+        value.to_string()
+    }
+}
+
+/// Token bucket allowing one second of burst at the configured rate.
+///
+/// Consumers may overdraw the bucket; the returned delay is how long to wait
+/// before reading more so the average rate stays at the limit.
+#[derive(Debug)]
+pub struct TokenBucket {
+    rate: f64,
+    tokens: f64,
+    updated: Instant,
+}
+
+impl TokenBucket {
+    /// Create a full bucket for the given rate.
+    #[must_use]
+    pub fn new(bandwidth: Bandwidth) -> Self {
+        let rate = u64_to_f64_lossy(bandwidth.bytes_per_second());
+        Self {
+            rate,
+            tokens: rate,
+            updated: Instant::now(),
+        }
+    }
+
+    /// Spend tokens for `bytes` and return how long to pause afterwards.
+    pub fn take(&mut self, bytes: usize, now: Instant) -> Duration {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.updated = now;
+        self.tokens = elapsed.mul_add(self.rate, self.tokens).min(self.rate);
+        self.tokens -= crate::numeric::usize_to_f64_lossy(bytes);
+        if self.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-self.tokens / self.rate)
+        }
+    }
+}
+
+/// Stream wrapper that paces byte chunks through a shared [`TokenBucket`].
+pub struct Throttled<S> {
+    inner: S,
+    bucket: Arc<Mutex<TokenBucket>>,
+    delay: Option<Pin<Box<Sleep>>>,
+}
+
+impl<S> Throttled<S> {
+    /// Wrap `inner`, drawing from `bucket` for every chunk.
+    pub const fn new(inner: S, bucket: Arc<Mutex<TokenBucket>>) -> Self {
+        Self {
+            inner,
+            bucket,
+            delay: None,
+        }
+    }
+}
+
+impl<S, B, E> Stream for Throttled<S>
+where
+    S: Stream<Item = std::result::Result<B, E>> + Unpin,
+    B: AsRef<[u8]>,
+{
+    type Item = std::result::Result<B, E>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        if let Some(delay) = this.delay.as_mut() {
+            ready!(delay.as_mut().poll(cx));
+            this.delay = None;
+        }
+
+        let item = ready!(Pin::new(&mut this.inner).poll_next(cx));
+        if let Some(Ok(chunk)) = &item {
+            let wait = this
+                .bucket
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .take(chunk.as_ref().len(), Instant::now());
+            if !wait.is_zero() {
+                this.delay = Some(Box::pin(tokio::time::sleep(wait)));
+            }
+        }
+        Poll::Ready(item)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use futures::StreamExt;
+
+    #[test]
+    fn parses_common_rates() {
+        let cases = [
+            ("500KB/s", 500 * KIB),
+            ("2MB/s", 2 * MIB),
+            ("1.5M", MIB + MIB / 2),
+            ("64k", 64 * KIB),
+            ("1GiB/s", GIB),
+            ("65536", 65536),
+            (" 10 kb/s ", 10 * KIB),
+        ];
+        for (input, expected) in cases {
+            let rate: Bandwidth = input.parse().unwrap();
+            assert_eq!(rate.bytes_per_second(), expected, "{input}");
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_rates() {
+        for input in ["", "0", "0KB/s", "fast", "10XB/s", "-5K", "0.1"] {
+            assert!(input.parse::<Bandwidth>().is_err(), "{input}");
+        }
+    }
+
+    #[test]
+    fn display_round_trips() {
+        for input in ["500KB/s", "2MB/s", "1GB/s", "1000B/s"] {
+            let rate: Bandwidth = input.parse().unwrap();
+            assert_eq!(rate.to_string(), input);
+            assert_eq!(rate.to_string().parse::<Bandwidth>().unwrap(), rate);
+        }
+    }
+
+    #[test]
+    fn bucket_allows_burst_then_paces() {
+        let mut bucket = TokenBucket::new(Bandwidth::from_bytes_per_second(1000).unwrap());
+        let start = bucket.updated;
+        assert_eq!(bucket.take(1000, start), Duration::ZERO);
+        assert_eq!(bucket.take(500, start), Duration::from_millis(500));
+
+        // After the pause the debt is paid off
+        let later = start + Duration::from_millis(500);
+        assert_eq!(bucket.take(0, later), Duration::ZERO);
+    }
+
+    #[tokio::test]
+    async fn throttled_stream_limits_rate() {
+        let rate = Bandwidth::from_bytes_per_second(20 * KIB).unwrap();
+        let bucket = Arc::new(Mutex::new(TokenBucket::new(rate)));
+        let chunks = (0..3).map(|_| Ok::<_, ()>(vec![0u8; 10 * 1024]));
+        let mut stream = Throttled::new(futures::stream::iter(chunks), bucket);
+
+        let started = std::time::Instant::now();
+        let mut total = 0;
+        while let Some(chunk) = stream.next().await {
+            total += chunk.unwrap().len();
+        }
+
+        assert_eq!(total, 30 * 1024);
+        // 20KB of burst, then 10KB at 20KB/s
+        assert!(started.elapsed() >= Duration::from_millis(450));
+    }
+}
@@ -0,0 +1,402 @@
+//! Text tokenizers for the search index.
+//!
+//! Tantivy's default tokenizer splits on anything that is not alphanumeric.
+//! Chinese, Japanese, and Korean text has no spaces between words, so a whole
+//! sentence becomes one token and only an exact copy of it matches.
+//! [`TokenizerKind::detect`] looks at a source's content when its index is
+//! built and picks a tokenizer that handles its script:
+//!
+//! | Tokenizer | Used for | Splits CJK text into |
+//! |-----------|----------|----------------------|
+//! | `default` | Everything else | Whole runs |
+//! | `ngram` | Japanese, Korean, and Chinese without `jieba` | Overlapping character pairs |
+//! | `jieba` | Chinese, with the `jieba` feature | Dictionary words |
+//!
+//! Latin words are tokenized the same way by all of them, so mixed-language
+//! docs keep working. The tokenizer is recorded in the index schema, as part
+//! of the source's [`Analyzer`](crate::analysis::Analyzer), and reused for
+//! queries, so a query is split the same way as the content.
+
+use std::fmt;
+use std::str::FromStr;
+
+use tantivy::tokenizer::{
+    RemoveLongFilter, SimpleTokenizer, TextAnalyzer, TextAnalyzerBuilder, Token, TokenStream,
+    Tokenizer,
+};
+
+use crate::{Error, Result};
+
+/// Tokens longer than this many bytes are dropped, as by Tantivy's default.
+const MAX_TOKEN_LEN: usize = 40;
+
+/// Share of letters that must be CJK for a source to get a CJK tokenizer.
+const CJK_SHARE_THRESHOLD: f64 = 0.2;
+
+/// Characters sampled when detecting a source's script.
+const DETECTION_SAMPLE_CHARS: usize = 200_000;
+
+/// Tokenizer used for a source's text fields.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum TokenizerKind {
+    /// Tantivy's default: split on non-alphanumeric characters.
+    #[default]
+    Default,
+    /// Default splitting, with CJK runs broken into character bigrams.
+    Ngram,
+    /// Default splitting, with Chinese segmented into dictionary words
+    /// (requires the `jieba` feature).
+    Jieba,
+}
+
+impl TokenizerKind {
+    const ALL: [Self; 3] = [Self::Default, Self::Ngram, Self::Jieba];
+
+    /// Name the tokenizer is registered and recorded under.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Default => "default",
+            Self::Ngram => "blz_cjk_ngram",
+            Self::Jieba => "blz_jieba",
+        }
+    }
+
+    /// Look up a tokenizer by its registered name.
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.name() == name)
+    }
+
+    /// Whether this build includes the tokenizer.
+    #[must_use]
+    pub const fn is_available(self) -> bool {
+        match self {
+            Self::Default | Self::Ngram => true,
+            Self::Jieba => cfg!(feature = "jieba"),
+        }
+    }
+
+    /// Pick a tokenizer for `texts` by the script most of their letters are
+    /// written in.
+    ///
+    /// Mostly-Chinese text uses `jieba` when it is available; other CJK text,
+    /// and Chinese without `jieba`, uses character bigrams.
+    #[must_use]
+    pub fn detect<'a>(texts: impl IntoIterator<Item = &'a str>) -> Self {
+        let mut letters = 0usize;
+        let mut han = 0usize;
+        let mut kana = 0usize;
+        let mut hangul = 0usize;
+        let chars = texts.into_iter().flat_map(str::chars);
+        for ch in chars.take(DETECTION_SAMPLE_CHARS) {
+            if !ch.is_alphabetic() {
+                continue;
+            }
+            letters += 1;
+            match Script::of(ch) {
+                Script::Han => han += 1,
+                Script::Kana => kana += 1,
+                Script::Hangul => hangul += 1,
+                Script::Other => {},
+            }
+        }
+
+        let cjk = han + kana + hangul;
+        #[allow(clippy::cast_precision_loss)]
+        let share = if letters == 0 {
+            0.0
+        } else {
+            cjk as f64 / letters as f64
+        };
+        if share < CJK_SHARE_THRESHOLD {
+            return Self::Default;
+        }
+        // Japanese mixes kana into its Han text; Chinese has next to none
+        let chinese = kana * 10 < cjk && hangul * 10 < cjk;
+        if chinese && Self::Jieba.is_available() {
+            Self::Jieba
+        } else {
+            Self::Ngram
+        }
+    }
+}
+
+impl fmt::Display for TokenizerKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Default => "default",
+            Self::Ngram => "ngram",
+            Self::Jieba => "jieba",
+        })
+    }
+}
+
+impl FromStr for TokenizerKind {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        let value = value.trim().to_ascii_lowercase();
+        Self::ALL
+            .into_iter()
+            .find(|kind| kind.to_string() == value)
+            .ok_or_else(|| {
+                Error::Config(format!(
+                    "Unknown tokenizer '{value}' (known: default, ngram, jieba)"
+                ))
+            })
+    }
+}
+
+/// An analyzer builder splitting text with `kind`, with over-long tokens
+/// dropped; `None` when this build does not include the tokenizer.
+///
+/// Token filters such as lowercasing are added by the caller (see
+/// [`crate::analysis::Analyzer`]).
+pub(crate) fn builder(kind: TokenizerKind) -> Option<TextAnalyzerBuilder> {
+    let builder = match kind {
+        TokenizerKind::Default => TextAnalyzer::builder(SimpleTokenizer::default()).dynamic(),
+        TokenizerKind::Ngram => TextAnalyzer::builder(CjkTokenizer::new(bigrams)).dynamic(),
+        #[cfg(feature = "jieba")]
+        TokenizerKind::Jieba => TextAnalyzer::builder(CjkTokenizer::new(jieba::words)).dynamic(),
+        #[cfg(not(feature = "jieba"))]
+        TokenizerKind::Jieba => return None,
+    };
+    Some(builder.filter_dynamic(RemoveLongFilter::limit(MAX_TOKEN_LEN)))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Script {
+    Han,
+    Kana,
+    Hangul,
+    Other,
+}
+
+impl Script {
+    const fn of(ch: char) -> Self {
+        match ch {
+            '\u{3005}'..='\u{3007}'
+            | '\u{3400}'..='\u{4DBF}'
+            | '\u{4E00}'..='\u{9FFF}'
+            | '\u{F900}'..='\u{FAFF}'
+            | '\u{20000}'..='\u{2A6DF}' => Self::Han,
+            '\u{3040}'..='\u{30FF}' | '\u{31F0}'..='\u{31FF}' | '\u{FF66}'..='\u{FF9F}' => {
+                Self::Kana
+            },
+            '\u{1100}'..='\u{11FF}' | '\u{3130}'..='\u{318F}' | '\u{AC00}'..='\u{D7AF}' => {
+                Self::Hangul
+            },
+            _ => Self::Other,
+        }
+    }
+}
+
+/// Splits a run of CJK text into `(start, end)` byte ranges.
+type Segmenter = fn(&str, &mut Vec<(usize, usize)>);
+
+/// Overlapping character pairs; a single character stands alone.
+fn bigrams(run: &str, ranges: &mut Vec<(usize, usize)>) {
+    let bounds: Vec<usize> = run
+        .char_indices()
+        .map(|(i, _)| i)
+        .chain(std::iter::once(run.len()))
+        .collect();
+    if bounds.len() <= 3 {
+        ranges.push((0, run.len()));
+        return;
+    }
+    ranges.extend(bounds.windows(3).map(|w| (w[0], w[2])));
+}
+
+#[cfg(feature = "jieba")]
+mod jieba {
+    use std::sync::LazyLock;
+
+    use jieba_rs::Jieba;
+
+    /// Loading the dictionary takes a moment, so it is shared.
+    static JIEBA: LazyLock<Jieba> = LazyLock::new(Jieba::new);
+
+    /// Dictionary words, as segmented by jieba.
+    pub(super) fn words(run: &str, ranges: &mut Vec<(usize, usize)>) {
+        let mut start = 0;
+        for word in JIEBA.cut(run, true) {
+            ranges.push((start, start + word.len()));
+            start += word.len();
+        }
+    }
+}
+
+/// Splits text like Tantivy's simple tokenizer, except that runs of CJK
+/// characters are handed to a [`Segmenter`].
+#[derive(Clone)]
+struct CjkTokenizer {
+    segment: Segmenter,
+    tokens: Vec<Token>,
+}
+
+impl CjkTokenizer {
+    const fn new(segment: Segmenter) -> Self {
+        Self {
+            segment,
+            tokens: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, text: &str, from: usize, to: usize) {
+        self.tokens.push(Token {
+            offset_from: from,
+            offset_to: to,
+            position: self.tokens.len(),
+            text: text[from..to].to_string(),
+            position_length: 1,
+        });
+    }
+
+    fn flush(
+        &mut self,
+        text: &str,
+        run: Option<(usize, usize, bool)>,
+        ranges: &mut Vec<(usize, usize)>,
+    ) {
+        let Some((start, end, cjk)) = run else {
+            return;
+        };
+        if !cjk {
+            self.push(text, start, end);
+            return;
+        }
+        ranges.clear();
+        (self.segment)(&text[start..end], ranges);
+        for &(from, to) in ranges.iter() {
+            self.push(text, start + from, start + to);
+        }
+    }
+
+    fn tokenize(&mut self, text: &str) {
+        self.tokens.clear();
+        let mut ranges = Vec::new();
+        // (start, end, is CJK) of the run being read
+        let mut run: Option<(usize, usize, bool)> = None;
+        for (i, ch) in text.char_indices() {
+            let end = i + ch.len_utf8();
+            if !ch.is_alphanumeric() {
+                self.flush(text, run.take(), &mut ranges);
+                continue;
+            }
+            let cjk = Script::of(ch) != Script::Other;
+            match run {
+                Some((start, _, run_cjk)) if run_cjk == cjk => run = Some((start, end, cjk)),
+                _ => {
+                    self.flush(text, run.take(), &mut ranges);
+                    run = Some((i, end, cjk));
+                },
+            }
+        }
+        self.flush(text, run, &mut ranges);
+    }
+}
+
+impl Tokenizer for CjkTokenizer {
+    type TokenStream<'a> = VecTokenStream<'a>;
+
+    fn token_stream<'a>(&'a mut self, text: &'a str) -> Self::TokenStream<'a> {
+        self.tokenize(text);
+        VecTokenStream {
+            tokens: &mut self.tokens,
+            next: 0,
+        }
+    }
+}
+
+struct VecTokenStream<'a> {
+    tokens: &'a mut [Token],
+    next: usize,
+}
+
+impl TokenStream for VecTokenStream<'_> {
+    fn advance(&mut self) -> bool {
+        if self.next < self.tokens.len() {
+            self.next += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn token(&self) -> &Token {
+        &self.tokens[self.next.saturating_sub(1)]
+    }
+
+    fn token_mut(&mut self) -> &mut Token {
+        &mut self.tokens[self.next.saturating_sub(1)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tantivy::tokenizer::LowerCaser;
+
+    fn tokens(text: &str) -> Vec<String> {
+        let mut analyzer = TextAnalyzer::builder(CjkTokenizer::new(bigrams))
+            .filter(LowerCaser)
+            .build();
+        let mut stream = analyzer.token_stream(text);
+        let mut out = Vec::new();
+        while stream.advance() {
+            out.push(stream.token().text.clone());
+        }
+        out
+    }
+
+    #[test]
+    fn cjk_runs_become_bigrams_and_words_stay_whole() {
+        assert_eq!(
+            tokens("Rust の設定ガイド v2"),
+            ["rust", "の設", "設定", "定ガ", "ガイ", "イド", "v2"]
+        );
+        assert_eq!(tokens("API設定"), ["api", "設定"]);
+        assert_eq!(tokens("검색 엔진"), ["검색", "엔진"]);
+        assert_eq!(
+            tokens("Plain English, no changes."),
+            ["plain", "english", "no", "changes"]
+        );
+    }
+
+    #[test]
+    fn detection_follows_the_dominant_script() {
+        assert_eq!(
+            TokenizerKind::detect(["Install the CLI and run a search."]),
+            TokenizerKind::Default
+        );
+        assert_eq!(
+            TokenizerKind::detect(["インストールしてから検索を実行します。"]),
+            TokenizerKind::Ngram
+        );
+        assert_eq!(
+            TokenizerKind::detect(["검색을 실행하기 전에 설치하세요."]),
+            TokenizerKind::Ngram
+        );
+        let chinese = TokenizerKind::detect(["安装后运行搜索命令。"]);
+        if cfg!(feature = "jieba") {
+            assert_eq!(chinese, TokenizerKind::Jieba);
+        } else {
+            assert_eq!(chinese, TokenizerKind::Ngram);
+        }
+        // A few CJK names in English docs do not switch tokenizers
+        assert_eq!(
+            TokenizerKind::detect(["Thanks to 山田 for the translation review and testing."]),
+            TokenizerKind::Default
+        );
+    }
+
+    #[test]
+    fn names_round_trip() {
+        for kind in TokenizerKind::ALL {
+            assert_eq!(TokenizerKind::from_name(kind.name()), Some(kind));
+        }
+        assert_eq!(TokenizerKind::from_name("en_stem"), None);
+    }
+}
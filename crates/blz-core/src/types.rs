@@ -30,6 +30,7 @@
 //!     heading_path_normalized: None,
 //!     lines: "15-42".to_string(),
 //!     anchor: None,
+//!     id: None,
 //!     children: vec![],
 //! };
 //!
@@ -52,12 +53,15 @@
 //!     lines: "120-145".to_string(),
 //!     line_numbers: Some(vec![120, 145]),
 //!     snippet: "useState returns an array with two elements...".to_string(),
+//!     snippet_language: None,
+//!     highlights: Vec::new(),
 //!     score: 0.92,
 //!     source_url: Some("https://react.dev/hooks".to_string()),
 //!     fetched_at: None,
 //!     is_stale: false,
 //!     checksum: "abc123".to_string(),
 //!     anchor: Some("react-hooks-usestate".to_string()),
+//!     section_id: None,
 //!     context: None,
 //! };
 //!
@@ -70,6 +74,8 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use crate::highlight::MatchSpan;
+
 /// Which llms.txt variant was successfully resolved and used
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
 #[serde(rename_all = "kebab-case")]
@@ -314,6 +320,29 @@ pub struct HeadingFilterStats {
     pub reason: String,
 }
 
+/// When a source was last used, for least-recently-used housekeeping.
+///
+/// Stored apart from [`Source`] metadata in a small per-source file so that
+/// recording a search never rewrites the larger metadata documents.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SourceAccess {
+    /// Last time a search returned results from this source.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_searched: Option<DateTime<Utc>>,
+    /// Last time upstream was checked for changes, whether or not it changed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_fetched: Option<DateTime<Utc>>,
+}
+
+impl SourceAccess {
+    /// Most recent of the recorded timestamps.
+    #[must_use]
+    pub fn last_used(&self) -> Option<DateTime<Utc>> {
+        self.last_searched.max(self.last_fetched)
+    }
+}
+
 /// Information about a documentation source.
 ///
 /// Represents metadata about a fetched llms.txt source, including caching headers
@@ -441,6 +470,57 @@ impl Source {
     }
 }
 
+/// The few fields of a source that every search reads.
+///
+/// Persisted next to `llms.json` as `summary.json` so a multi-source query can
+/// filter sources, key the query cache, and attach provenance without parsing
+/// each source's full `llms.json` and `metadata.json`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SourceSummary {
+    /// The URL from which this content was fetched.
+    pub url: String,
+    /// SHA-256 hash of the content.
+    pub sha256: String,
+    /// Timestamp when this content was last fetched.
+    pub fetched_at: DateTime<Utc>,
+    /// Number of lines in the cached document.
+    pub total_lines: usize,
+    /// Tags copied from the source metadata.
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+impl SourceSummary {
+    /// Summarize `source` metadata for a document of `total_lines` lines.
+    #[must_use]
+    pub fn new(source: &Source, total_lines: usize) -> Self {
+        Self {
+            url: source.url.clone(),
+            sha256: source.sha256.clone(),
+            fetched_at: source.fetched_at,
+            total_lines,
+            tags: source.tags.clone(),
+        }
+    }
+
+    /// See [`Source::is_index_only`].
+    #[must_use]
+    pub fn is_index_only(&self) -> bool {
+        self.tags
+            .iter()
+            .any(|tag| tag.eq_ignore_ascii_case("index"))
+    }
+
+    /// See [`Source::is_internal`].
+    #[must_use]
+    pub fn is_internal(&self) -> bool {
+        self.tags
+            .iter()
+            .any(|tag| tag.eq_ignore_ascii_case("internal"))
+    }
+}
+
 /// Records provenance information for a source.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -483,6 +563,92 @@ pub enum SourceType {
         /// Absolute filesystem path to the source document.
         path: String,
     },
+    /// Document piped in on standard input; sync leaves it untouched.
+    Stdin,
+    /// Several remote llms.txt files merged into one source.
+    Collection {
+        /// Member files in merge order.
+        members: Vec<crate::collection::CollectionMember>,
+    },
+    /// Rust crate documentation rendered from docs.rs rustdoc JSON.
+    Crate {
+        /// Crate name as published on crates.io.
+        name: String,
+        /// Requested version (`latest` follows new releases on sync).
+        version: String,
+    },
+    /// `OpenAPI` or Swagger spec rendered to markdown.
+    OpenApi {
+        /// Spec URL or absolute filesystem path.
+        location: String,
+    },
+    /// Folder of markdown files merged into one document.
+    Directory {
+        /// Absolute path to the folder.
+        path: String,
+        /// Files from the last scan, in merge order.
+        files: Vec<crate::docs_dir::DocsFile>,
+    },
+    /// HTML docs page converted to markdown.
+    Html {
+        /// URL of the page.
+        url: String,
+    },
+    /// Remote PDF whose extracted text is indexed page by page.
+    Pdf {
+        /// URL of the PDF.
+        url: String,
+    },
+    /// Index-style llms.txt merged with the pages it links to.
+    Followed {
+        /// URL of the index.
+        url: String,
+        /// Link policy and limits used when following.
+        follow: crate::follow::FollowOptions,
+    },
+    /// Pages listed in a sitemap, scraped and assembled into one document.
+    ///
+    /// The per-page cache lives in the source's `generate.json`.
+    Generated {
+        /// URL of the sitemap the pages were taken from.
+        sitemap: String,
+    },
+    /// GitHub repository README and `docs/` files merged into one document.
+    GithubRepo {
+        /// Repository as `owner/repo`.
+        repo: String,
+        /// Branch or tag followed on sync.
+        reference: String,
+        /// Commit the indexed files were read from.
+        commit: String,
+        /// Files read at that commit, in merge order.
+        files: Vec<crate::docs_dir::DocsFile>,
+    },
+}
+
+impl SourceType {
+    /// Descriptor `url`/`path` fields for this origin.
+    ///
+    /// Sources assembled from several inputs (collections, crates, specs, folders,
+    /// repositories) and piped documents have neither; their origin carries the
+    /// details.
+    #[must_use]
+    pub fn descriptor_location(&self) -> (Option<String>, Option<String>) {
+        match self {
+            Self::Remote { url }
+            | Self::Html { url }
+            | Self::Pdf { url }
+            | Self::Followed { url, .. }
+            | Self::Generated { sitemap: url } => (Some(url.clone()), None),
+            Self::LocalFile { path } => (None, Some(path.clone())),
+            Self::Stdin
+            | Self::Collection { .. }
+            | Self::Crate { .. }
+            | Self::OpenApi { .. }
+            | Self::Directory { .. }
+            | Self::GithubRepo { .. } => (None, None),
+        }
+    }
 }
 
 /// Canonical descriptor persisted alongside configuration for each source.
@@ -537,11 +703,10 @@ impl SourceDescriptor {
     /// Create a descriptor snapshot from an existing `Source` record.
     #[must_use]
     pub fn from_source(alias: &str, source: &Source) -> Self {
-        let (url, path) = match &source.origin.source_type {
-            Some(SourceType::Remote { url }) => (Some(url.clone()), None),
-            Some(SourceType::LocalFile { path }) => (None, Some(path.clone())),
-            None => (Some(source.url.clone()), None),
-        };
+        let (url, path) = source.origin.source_type.as_ref().map_or_else(
+            || (Some(source.url.clone()), None),
+            SourceType::descriptor_location,
+        );
 
         Self {
             alias: alias.to_string(),
@@ -633,6 +798,15 @@ pub struct TocEntry {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub anchor: Option<String>,
 
+    /// Persistent identifier for this heading block.
+    ///
+    /// Derived from the heading path and content when the section first
+    /// appears, then carried forward on sync while the section can still be
+    /// matched by path or anchor, so it survives edits, moves, and line
+    /// shifts. Absent in caches written before IDs were introduced.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+
     /// Nested subsections under this heading.
     ///
     /// Each child entry represents a subsection with its own potential
@@ -792,6 +966,12 @@ pub struct LlmsJson {
     /// with sources processed before filtering was implemented.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub filter_stats: Option<HeadingFilterStats>,
+
+    /// Health observations recorded by `blz check` and `blz doctor`.
+    ///
+    /// Absent until one of those commands has inspected the source.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub health: Option<crate::health::HealthRecord>,
 }
 
 /// Metadata about how parsing/segmentation was performed.
@@ -868,6 +1048,20 @@ pub struct SearchHit {
     /// highlighted or emphasized. Length is limited for display purposes.
     pub snippet: String,
 
+    /// Language of the snippet when it is predominantly fenced code.
+    ///
+    /// Taken from the code fence info string, or guessed from keywords for
+    /// unlabelled fences. Omitted for prose snippets.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snippet_language: Option<String>,
+
+    /// Where the query matched within `snippet`, in snippet order.
+    ///
+    /// Empty when no query term appears verbatim in the snippet, as with
+    /// fuzzy or semantic matches.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub highlights: Vec<MatchSpan>,
+
     /// Relevance score for this hit.
     ///
     /// Higher scores indicate better relevance. Typically uses BM25 scoring
@@ -900,6 +1094,13 @@ pub struct SearchHit {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub anchor: Option<String>,
 
+    /// Persistent ID of the heading block containing this hit.
+    ///
+    /// Matches [`TocEntry::id`] and stays the same across syncs while the
+    /// section survives, unlike `lines` and `anchor`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub section_id: Option<String>,
+
     /// Optional expanded content context returned when `--context` or `--block` flags are used.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub context: Option<HitContext>,
@@ -1059,6 +1260,9 @@ pub struct HeadingBlock {
 
     /// Ending line number (1-based, inclusive).
     pub end_line: usize,
+
+    /// Persistent section ID, shared with the matching [`TocEntry::id`].
+    pub id: String,
 }
 
 impl HeadingBlock {
@@ -1066,6 +1270,7 @@ impl HeadingBlock {
     /// Construct a heading block while computing display and normalized variants.
     pub fn new(path: Vec<String>, content: String, start_line: usize, end_line: usize) -> Self {
         let variants = crate::path_variants(&path);
+        let id = crate::mapping::section_id(&path, &content);
         Self {
             path,
             display_path: variants.display_segments,
@@ -1073,8 +1278,15 @@ impl HeadingBlock {
             content,
             start_line,
             end_line,
+            id,
         }
     }
+
+    /// Fenced code blocks in this section, each tagged with its language.
+    #[must_use]
+    pub fn code_blocks(&self) -> Vec<crate::code_language::CodeBlock> {
+        crate::code_language::code_blocks(&self.content)
+    }
 }
 
 #[cfg(test)]
@@ -1094,12 +1306,15 @@ mod tests {
             lines: "100-120".to_string(),
             line_numbers: Some(vec![100, 120]),
             snippet: "useState is a React hook...".to_string(),
+            snippet_language: None,
+            highlights: Vec::new(),
             score: 0.95,
             source_url: Some("https://react.dev".to_string()),
             fetched_at: Some(Utc::now()),
             is_stale: false,
             checksum: "abc123".to_string(),
             anchor: Some("anchor1".to_string()),
+            section_id: None,
             context: None,
         };
 
@@ -1112,12 +1327,15 @@ mod tests {
             lines: "100-120".to_string(),
             line_numbers: Some(vec![100, 120]),
             snippet: "useState is a React hook...".to_string(),
+            snippet_language: None,
+            highlights: Vec::new(),
             score: 0.90, // Different score
             source_url: Some("https://react.dev".to_string()),
             fetched_at: Some(Utc::now()),
             is_stale: false,
             checksum: "abc123".to_string(),
             anchor: Some("anchor1".to_string()),
+            section_id: None,
             context: None,
         };
 
@@ -1172,6 +1390,7 @@ mod tests {
             ]),
             lines: "1-25".to_string(),
             anchor: None,
+            id: None,
             children: vec![],
         };
 
@@ -1248,6 +1467,7 @@ mod tests {
             diagnostics: vec![],
             parse_meta: None,
             filter_stats: None,
+            health: None,
         };
 
         assert_eq!(llms_json.source, "test");
@@ -1418,6 +1638,7 @@ mod tests {
                 headings_rejected: 36,
                 reason: "non-English content removed".to_string(),
             }),
+            health: None,
         };
 
         // Test serialization/deserialization
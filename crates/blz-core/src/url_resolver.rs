@@ -23,6 +23,10 @@ pub struct ResolvedUrl {
     pub line_count: usize,
     /// Whether to warn the user about this source.
     pub should_warn: bool,
+    /// Document fetched while resolving, so callers need not download it again.
+    pub content: String,
+    /// SHA-256 of `content`.
+    pub sha256: String,
 }
 
 /// Resolve the best URL for llms.txt documentation.
@@ -84,7 +88,7 @@ pub async fn resolve_best_url(fetcher: &Fetcher, base_url: &str) -> Result<Resol
                 continue;
             }
 
-            let (content, _sha256) = match fetcher.fetch(url).await {
+            let (content, sha256) = match fetcher.fetch(url).await {
                 Ok(result) => result,
                 Err(err) => {
                     debug!(error = %err, %url, "GET fallback failed for candidate URL");
@@ -97,15 +101,7 @@ pub async fn resolve_best_url(fetcher: &Fetcher, base_url: &str) -> Result<Resol
 
             let variant = match idx {
                 0 => SourceVariant::LlmsFull,
-                1 => {
-                    if base_url.ends_with("llms-full.txt") {
-                        SourceVariant::LlmsFull
-                    } else if base_url.ends_with("llms.txt") {
-                        SourceVariant::Llms
-                    } else {
-                        SourceVariant::Custom
-                    }
-                },
+                1 => exact_variant(base_url),
                 2 => SourceVariant::Llms,
                 _ => SourceVariant::Custom,
             };
@@ -116,6 +112,8 @@ pub async fn resolve_best_url(fetcher: &Fetcher, base_url: &str) -> Result<Resol
                 content_type,
                 line_count,
                 should_warn,
+                content,
+                sha256,
             });
         }
     }
@@ -127,6 +125,40 @@ pub async fn resolve_best_url(fetcher: &Fetcher, base_url: &str) -> Result<Resol
     )))
 }
 
+/// Resolve `url` as given, without trying its other llms.txt variants.
+///
+/// Used for sources with a declared checksum or signature, which cover one
+/// specific document.
+///
+/// # Errors
+///
+/// Returns an error if the URL cannot be fetched.
+pub async fn resolve_exact_url(fetcher: &Fetcher, url: &str) -> Result<ResolvedUrl> {
+    let (content, sha256) = fetcher.fetch(url).await?;
+    let line_count = content.lines().count();
+    let (content_type, should_warn) = classify_content(line_count);
+    Ok(ResolvedUrl {
+        final_url: url.to_string(),
+        variant: exact_variant(url),
+        content_type,
+        line_count,
+        should_warn,
+        content,
+        sha256,
+    })
+}
+
+/// Variant named by a URL's file name.
+fn exact_variant(url: &str) -> SourceVariant {
+    if url.ends_with("llms-full.txt") {
+        SourceVariant::LlmsFull
+    } else if url.ends_with("llms.txt") {
+        SourceVariant::Llms
+    } else {
+        SourceVariant::Custom
+    }
+}
+
 fn build_resolution_error_message(
     base_url: &str,
     blocked_statuses: &[u16],
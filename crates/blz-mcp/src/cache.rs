@@ -32,8 +32,7 @@ pub async fn get_or_load_index(
 
     // Slow path: load and cache
     tracing::debug!(source, "index cache miss, loading");
-    let index_path = storage.index_dir(source)?;
-    let index = SearchIndex::open(&index_path)?;
+    let index = storage.open_index(source)?;
     let index_arc = Arc::new(index);
 
     {
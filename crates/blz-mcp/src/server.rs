@@ -187,6 +187,56 @@ fn build_blz_tool_schema() -> serde_json::Map<String, serde_json::Value> {
     schema.as_object().expect("blz schema is an object").clone()
 }
 
+/// Build the JSON schema for the `pack_context` tool.
+fn build_pack_context_tool_schema() -> serde_json::Map<String, serde_json::Value> {
+    let schema = json!({
+        "type": "object",
+        "properties": {
+            "queries": {
+                "type": "array",
+                "items": {"type": "string"},
+                "minItems": 1,
+                "maxItems": 20,
+                "description": "Queries whose best sections are packed together"
+            },
+            "tokenBudget": {
+                "type": "integer",
+                "minimum": 1,
+                "maximum": 100_000,
+                "default": 4000,
+                "description": "Estimated token budget for the document (about 4 characters per token)"
+            },
+            "maxResultsPerQuery": {
+                "type": "integer",
+                "minimum": 1,
+                "maximum": 50,
+                "default": 5,
+                "description": "Hits considered per query before packing"
+            },
+            "source": {
+                "description": "Optional source filter: omit or \"all\" for every source, a string alias, or an array of aliases",
+                "oneOf": [
+                    {
+                        "type": "string"
+                    },
+                    {
+                        "type": "array",
+                        "items": {"type": "string"},
+                        "minItems": 1
+                    }
+                ]
+            }
+        },
+        "required": ["queries"]
+    });
+    // SAFETY: The json! macro above produces an object literal; as_object() cannot fail.
+    #[allow(clippy::expect_used)]
+    schema
+        .as_object()
+        .expect("pack_context schema is an object")
+        .clone()
+}
+
 /// Map a find tool error to the appropriate MCP error code.
 const fn map_find_error_code(e: &crate::error::McpError) -> ErrorCode {
     match e.error_code() {
@@ -252,6 +302,18 @@ impl McpServer {
 
     /// Serve the MCP protocol over stdio
     pub async fn serve_stdio(&self) -> McpResult<()> {
+        self.serve_stdio_until(std::future::pending()).await
+    }
+
+    /// Serve the MCP protocol over stdio until the client disconnects or
+    /// `shutdown` resolves.
+    ///
+    /// On shutdown the service is cancelled and awaited, so tool calls in
+    /// progress finish before this returns.
+    pub async fn serve_stdio_until(
+        &self,
+        shutdown: impl std::future::Future<Output = ()>,
+    ) -> McpResult<()> {
         tracing::info!("BLZ MCP server starting");
 
         let stdin = tokio::io::stdin();
@@ -264,8 +326,21 @@ impl McpServer {
                 crate::error::McpError::Protocol(e.to_string())
             })?;
 
-        // Keep the service running until it's cancelled
-        service.waiting().await.map_err(|e| {
+        // Keep the service running until the client leaves or shutdown is requested
+        let token = service.cancellation_token();
+        let waiting = service.waiting();
+        tokio::pin!(waiting);
+        // `tokio::select!` expands to a `panic!` for its all-branches-disabled arm
+        #[allow(clippy::disallowed_macros)]
+        let result = tokio::select! {
+            result = &mut waiting => result,
+            () = shutdown => {
+                tracing::info!("BLZ MCP server shutting down");
+                token.cancel();
+                waiting.await
+            },
+        };
+        result.map_err(|e| {
             tracing::error!("server runtime error: {}", e);
             crate::error::McpError::Protocol(e.to_string())
         })?;
@@ -327,6 +402,11 @@ impl ServerHandler for McpServer {
                 "Manage sources and metadata (actions: list, add, remove, refresh, info, validate, history, lookup, doctor, clearCache, addAlias, removeAlias, help)",
                 Arc::new(build_blz_tool_schema()),
             ),
+            Tool::new(
+                "pack_context",
+                "Search several queries and pack the best sections into one cited document within a token budget",
+                Arc::new(build_pack_context_tool_schema()),
+            ),
         ];
 
         Ok(ListToolsResult {
@@ -387,6 +467,27 @@ impl ServerHandler for McpServer {
 
                 build_tool_result(&output)
             },
+            "pack_context" => {
+                let params: tools::PackContextParams = serde_json::from_value(
+                    serde_json::Value::Object(request.arguments.unwrap_or_default()),
+                )
+                .map_err(|e| {
+                    ErrorData::new(
+                        ErrorCode::INVALID_PARAMS,
+                        format!("Invalid pack_context parameters: {e}"),
+                        None,
+                    )
+                })?;
+
+                let output = tools::handle_pack_context(params, &self.storage, &self.index_cache)
+                    .await
+                    .map_err(|e| {
+                        tracing::error!("pack_context tool error: {}", e);
+                        ErrorData::new(map_find_error_code(&e), e.to_string(), None)
+                    })?;
+
+                build_tool_result(&output)
+            },
             _ => Err(ErrorData::new(
                 ErrorCode::METHOD_NOT_FOUND,
                 format!("Unknown tool: {}", request.name),
@@ -431,6 +431,11 @@ fn remove_source(storage: &Storage, alias: &str) -> McpResult<RemoveOutput> {
     if !storage.exists(alias) {
         return Err(McpError::SourceNotFound(alias.to_string()));
     }
+    if storage.is_shared(alias) {
+        return Err(McpError::InvalidParams(format!(
+            "Source '{alias}' comes from the read-only shared cache and cannot be removed here"
+        )));
+    }
 
     let info = storage.load_llms_json(alias).ok().map(|llms| RemovalInfo {
         alias: alias.to_string(),
@@ -447,6 +452,7 @@ fn remove_source(storage: &Storage, alias: &str) -> McpResult<RemoveOutput> {
         ))
     })?;
     storage.remove_descriptor(alias)?;
+    storage.invalidate_query_cache();
 
     Ok(RemoveOutput {
         alias: alias.to_string(),
@@ -469,11 +475,13 @@ fn load_info(storage: &Storage, alias: &str) -> McpResult<SourceInfoOutput> {
 
     let llms = storage.load_llms_json(alias)?;
     let metadata = llms.metadata.clone();
-    let llms_path = storage.llms_txt_path(alias)?;
+    let llms_path = storage
+        .stored_llms_txt_path(alias)?
+        .unwrap_or(storage.llms_txt_path(alias)?);
     let file_metadata = fs::metadata(&llms_path).map_err(|e| {
         McpError::Internal(format!("Failed to read source file for '{alias}': {e}"))
     })?;
-    let cache_path = storage.tool_dir(alias)?.to_string_lossy().to_string();
+    let cache_path = storage.source_dir(alias)?.to_string_lossy().to_string();
 
     Ok(SourceInfoOutput {
         alias: alias.to_string(),
@@ -597,7 +605,13 @@ async fn refresh_sources(
         vec![alias]
     };
 
-    let fetcher = Fetcher::new()?;
+    let defaults = blz_core::Config::load().ok().map(|config| config.defaults);
+    let network = defaults
+        .as_ref()
+        .map(blz_core::NetworkOptions::from_defaults)
+        .unwrap_or_default();
+    let throttle = defaults.and_then(|defaults| defaults.throttle);
+    let fetcher = Fetcher::with_network(&network)?.with_throttle(throttle);
     let indexer = DefaultRefreshIndexer;
     let metrics = PerformanceMetrics::default();
 
@@ -872,7 +886,12 @@ fn analyze_source_health(storage: &Storage) -> SourceAnalysisResult {
             }
 
             // Check if generated source
-            if metadata.url.contains("firecrawl") || metadata.url.starts_with("generate://") {
+            if matches!(
+                metadata.origin.source_type,
+                Some(blz_core::SourceType::Generated { .. })
+            ) || metadata.url.contains("firecrawl")
+                || metadata.url.starts_with("generate://")
+            {
                 entry.source_type = SourceKind::Generated;
             }
         }
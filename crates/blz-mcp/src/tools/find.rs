@@ -41,7 +41,7 @@ impl SourceFilter {
     }
 
     /// Get the list of specific sources to search, or None for "all"
-    fn sources(&self) -> Option<Vec<String>> {
+    pub(crate) fn sources(&self) -> Option<Vec<String>> {
         match self {
             Self::Single(s) if s == "all" => None,
             Self::Single(s) => Some(vec![s.clone()]),
@@ -2001,6 +2001,7 @@ mod block_detection_tests {
                 heading_path_normalized: Some(vec!["introduction".to_string()]),
                 lines: "1-10".to_string(),
                 anchor: None,
+                id: None,
                 children: vec![],
             },
             TocEntry {
@@ -2009,6 +2010,7 @@ mod block_detection_tests {
                 heading_path_normalized: Some(vec!["getting started".to_string()]),
                 lines: "11-50".to_string(),
                 anchor: None,
+                id: None,
                 children: vec![
                     TocEntry {
                         heading_path: vec![
@@ -2025,6 +2027,7 @@ mod block_detection_tests {
                         ]),
                         lines: "12-25".to_string(),
                         anchor: None,
+                        id: None,
                         children: vec![],
                     },
                     TocEntry {
@@ -2042,6 +2045,7 @@ mod block_detection_tests {
                         ]),
                         lines: "26-50".to_string(),
                         anchor: None,
+                        id: None,
                         children: vec![],
                     },
                 ],
@@ -2052,6 +2056,7 @@ mod block_detection_tests {
                 heading_path_normalized: Some(vec!["api reference".to_string()]),
                 lines: "51-100".to_string(),
                 anchor: None,
+                id: None,
                 children: vec![],
             },
         ]
@@ -2142,6 +2147,7 @@ mod block_detection_tests {
                 heading_path_normalized: Some(vec!["bad entry".to_string()]),
                 lines: "invalid".to_string(), // Bad format
                 anchor: None,
+                id: None,
                 children: vec![],
             },
             TocEntry {
@@ -2150,6 +2156,7 @@ mod block_detection_tests {
                 heading_path_normalized: Some(vec!["good entry".to_string()]),
                 lines: "10-20".to_string(),
                 anchor: None,
+                id: None,
                 children: vec![],
             },
         ];
@@ -2264,6 +2271,7 @@ Line 12";
                     heading_path_normalized: Some(vec!["documentation".to_string()]),
                     lines: "1-3".to_string(),
                     anchor: None,
+                    id: None,
                     children: vec![],
                 },
                 TocEntry {
@@ -2272,6 +2280,7 @@ Line 12";
                     heading_path_normalized: Some(vec!["section a".to_string()]),
                     lines: "4-8".to_string(),
                     anchor: None,
+                    id: None,
                     children: vec![],
                 },
                 TocEntry {
@@ -2280,6 +2289,7 @@ Line 12";
                     heading_path_normalized: Some(vec!["section b".to_string()]),
                     lines: "9-12".to_string(),
                     anchor: None,
+                    id: None,
                     children: vec![],
                 },
             ],
@@ -2291,6 +2301,7 @@ Line 12";
             diagnostics: vec![],
             parse_meta: None,
             filter_stats: None,
+            health: None,
         };
 
         let json_str = serde_json::to_string(&llms_json).expect("Failed to serialize JSON");
@@ -2387,6 +2398,7 @@ Line 12";
                 heading_path_normalized: Some(vec!["overflow".to_string()]),
                 lines: "1-10".to_string(),
                 anchor: None,
+                id: None,
                 children: vec![],
             }],
             files: vec![],
@@ -2397,6 +2409,7 @@ Line 12";
             diagnostics: vec![],
             parse_meta: None,
             filter_stats: None,
+            health: None,
         };
 
         let json_str = serde_json::to_string(&llms_json).expect("Failed to serialize JSON");
@@ -2476,6 +2489,7 @@ B line 3
                     heading_path_normalized: Some(vec!["section a".to_string()]),
                     lines: "4-7".to_string(),
                     anchor: None,
+                    id: None,
                     children: vec![],
                 },
                 TocEntry {
@@ -2484,6 +2498,7 @@ B line 3
                     heading_path_normalized: Some(vec!["section b".to_string()]),
                     lines: "9-11".to_string(),
                     anchor: None,
+                    id: None,
                     children: vec![],
                 },
             ],
@@ -2495,6 +2510,7 @@ B line 3
             diagnostics: vec![],
             parse_meta: None,
             filter_stats: None,
+            health: None,
         };
 
         let json_str = serde_json::to_string(&llms_json).expect("Failed to serialize JSON");
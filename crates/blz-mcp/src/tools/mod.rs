@@ -3,8 +3,10 @@
 pub mod blz;
 pub mod find;
 mod learn_blz;
+pub mod pack_context;
 mod run_command;
 mod sources;
 
 pub use blz::{BlzOutput, BlzParams, handle_blz};
 pub use find::{FindOutput, FindParams, handle_find};
+pub use pack_context::{PackContextOutput, PackContextParams, handle_pack_context};
@@ -0,0 +1,224 @@
+//! Pack context tool: several searches assembled into one cited document
+
+use std::collections::HashMap;
+
+use blz_core::Storage;
+use blz_core::context_pack::{self, ContextPack, PackCandidate};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cache,
+    error::{McpError, McpResult},
+    tools::find::SourceFilter,
+    types::IndexCache,
+};
+
+/// Default token budget for the packed document
+const DEFAULT_TOKEN_BUDGET: usize = 4000;
+/// Largest token budget accepted
+const MAX_TOKEN_BUDGET: usize = 100_000;
+/// Default number of hits considered per query
+const DEFAULT_RESULTS_PER_QUERY: usize = 5;
+/// Largest number of hits considered per query
+const MAX_RESULTS_PER_QUERY: usize = 50;
+/// Largest number of queries accepted in one call
+const MAX_QUERIES: usize = 20;
+
+/// Parameters for the `pack_context` tool
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PackContextParams {
+    /// Queries to search for
+    pub queries: Vec<String>,
+
+    /// Estimated token budget for the document (default 4000)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token_budget: Option<usize>,
+
+    /// Hits considered per query before packing (default 5)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_results_per_query: Option<usize>,
+
+    /// Optional source filter, as for `find`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<SourceFilter>,
+}
+
+/// Output from the `pack_context` tool
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PackContextOutput {
+    /// The packed document and its sections
+    #[serde(flatten)]
+    pub pack: ContextPack,
+
+    /// Queries that produced no hits
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub unmatched_queries: Vec<String>,
+}
+
+/// Validated `pack_context` parameters.
+struct ValidatedPackParams {
+    queries: Vec<String>,
+    token_budget: usize,
+    max_results: usize,
+    sources: Vec<String>,
+}
+
+fn validate_params(params: PackContextParams, storage: &Storage) -> McpResult<ValidatedPackParams> {
+    let mut queries: Vec<String> = Vec::new();
+    for query in params.queries {
+        let query = query.trim().to_string();
+        if !query.is_empty() && !queries.contains(&query) {
+            queries.push(query);
+        }
+    }
+    if queries.is_empty() {
+        return Err(McpError::MissingParameter("queries".to_string()));
+    }
+    if queries.len() > MAX_QUERIES {
+        return Err(McpError::InvalidParams(format!(
+            "{} queries exceeds limit of {MAX_QUERIES}",
+            queries.len()
+        )));
+    }
+
+    let token_budget = params.token_budget.unwrap_or(DEFAULT_TOKEN_BUDGET);
+    if token_budget == 0 || token_budget > MAX_TOKEN_BUDGET {
+        return Err(McpError::InvalidParams(format!(
+            "token_budget must be between 1 and {MAX_TOKEN_BUDGET}"
+        )));
+    }
+    let max_results = params
+        .max_results_per_query
+        .unwrap_or(DEFAULT_RESULTS_PER_QUERY);
+    if max_results == 0 || max_results > MAX_RESULTS_PER_QUERY {
+        return Err(McpError::InvalidParams(format!(
+            "max_results_per_query must be between 1 and {MAX_RESULTS_PER_QUERY}"
+        )));
+    }
+
+    let sources = params
+        .source
+        .as_ref()
+        .and_then(SourceFilter::sources)
+        .unwrap_or_else(|| storage.list_sources());
+    if sources.is_empty() {
+        return Err(McpError::Internal(
+            "No sources available to search".to_string(),
+        ));
+    }
+
+    Ok(ValidatedPackParams {
+        queries,
+        token_budget,
+        max_results,
+        sources,
+    })
+}
+
+/// Handle the `pack_context` tool
+///
+/// Runs every query across the selected sources, then packs the best hits
+/// into a single document within the token budget. Sources that fail to load
+/// are skipped, as in `find`.
+#[tracing::instrument(skip(storage, index_cache))]
+pub async fn handle_pack_context(
+    params: PackContextParams,
+    storage: &Storage,
+    index_cache: &IndexCache,
+) -> McpResult<PackContextOutput> {
+    let validated = validate_params(params, storage)?;
+
+    let mut candidates = Vec::new();
+    let mut unmatched_queries = Vec::new();
+    for query in &validated.queries {
+        let mut hits = Vec::new();
+        for source in &validated.sources {
+            let index = match cache::get_or_load_index(index_cache, storage, source).await {
+                Ok(index) => index,
+                Err(e) => {
+                    tracing::warn!(source, error = %e, "failed to load index, skipping source");
+                    continue;
+                },
+            };
+            match index.search(query, Some(source), validated.max_results) {
+                Ok(found) => hits.extend(found),
+                Err(e) => tracing::warn!(source, error = %e, "search failed for source, skipping"),
+            }
+        }
+        hits.sort_by(|a, b| b.score.total_cmp(&a.score));
+        hits.truncate(validated.max_results);
+        if hits.is_empty() {
+            unmatched_queries.push(query.clone());
+        }
+        candidates.extend(
+            hits.iter()
+                .filter_map(|hit| PackCandidate::from_hit(query, hit)),
+        );
+    }
+
+    let mut lines: HashMap<String, Vec<String>> = HashMap::new();
+    for candidate in &candidates {
+        if lines.contains_key(&candidate.source) {
+            continue;
+        }
+        match storage.load_llms_txt(&candidate.source) {
+            Ok(content) => {
+                lines.insert(
+                    candidate.source.clone(),
+                    content.lines().map(str::to_string).collect(),
+                );
+            },
+            Err(e) => {
+                tracing::warn!(source = %candidate.source, error = %e, "failed to load source text");
+            },
+        }
+    }
+
+    Ok(PackContextOutput {
+        pack: context_pack::pack(candidates, &lines, validated.token_budget),
+        unmatched_queries,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(value: serde_json::Value) -> PackContextParams {
+        serde_json::from_value(value).expect("valid params")
+    }
+
+    #[test]
+    fn rejects_empty_queries_and_out_of_range_budgets() {
+        let storage = Storage::new().expect("storage");
+        let err = validate_params(
+            params(serde_json::json!({"queries": ["  "], "source": "docs"})),
+            &storage,
+        )
+        .err()
+        .expect("empty queries rejected");
+        assert!(matches!(err, McpError::MissingParameter(_)));
+
+        let err = validate_params(
+            params(serde_json::json!({"queries": ["hooks"], "tokenBudget": 0, "source": "docs"})),
+            &storage,
+        )
+        .err()
+        .expect("zero budget rejected");
+        assert!(matches!(err, McpError::InvalidParams(_)));
+
+        let validated = validate_params(
+            params(serde_json::json!({
+                "queries": ["hooks", "hooks", "effects"],
+                "source": ["docs", "guides"]
+            })),
+            &storage,
+        )
+        .expect("valid params");
+        assert_eq!(validated.queries, ["hooks", "effects"]);
+        assert_eq!(validated.token_budget, DEFAULT_TOKEN_BUDGET);
+        assert_eq!(validated.sources, ["docs", "guides"]);
+    }
+}
@@ -208,13 +208,13 @@ fn execute_validate(storage: &Storage, source: Option<&str>) -> McpResult<(Strin
         }
 
         // Check for required files
-        let llms_txt_path = storage.llms_txt_path(source_name)?;
+        let llms_txt = storage.stored_llms_txt_path(source_name)?;
         let llms_json_path = storage.llms_json_path(source_name)?;
         let index_dir = storage.index_dir(source_name)?;
 
         let mut issues = Vec::new();
 
-        if !llms_txt_path.exists() {
+        if llms_txt.is_none() {
             issues.push("Missing llms.txt file");
         }
 
@@ -242,11 +242,11 @@ fn execute_validate(storage: &Storage, source: Option<&str>) -> McpResult<(Strin
         let mut invalid_sources = Vec::new();
 
         for source in sources {
-            let llms_txt_path = storage.llms_txt_path(&source)?;
+            let llms_txt = storage.stored_llms_txt_path(&source)?;
             let llms_json_path = storage.llms_json_path(&source)?;
             let index_dir = storage.index_dir(&source)?;
 
-            if llms_txt_path.exists() && llms_json_path.exists() && index_dir.exists() {
+            if llms_txt.is_some() && llms_json_path.exists() && index_dir.exists() {
                 valid_count += 1;
             } else {
                 invalid_sources.push(source);
@@ -272,8 +272,10 @@ fn execute_inspect(storage: &Storage, source: Option<&str>) -> McpResult<(String
             return Err(McpError::SourceNotFound(source_name.to_string()));
         }
 
-        let tool_dir = storage.tool_dir(source_name)?;
-        let llms_txt_path = storage.llms_txt_path(source_name)?;
+        let tool_dir = storage.source_dir(source_name)?;
+        let llms_txt_path = storage
+            .stored_llms_txt_path(source_name)?
+            .unwrap_or(storage.llms_txt_path(source_name)?);
         let llms_json_path = storage.llms_json_path(source_name)?;
         let index_dir = storage.index_dir(source_name)?;
         let metadata_path = storage.metadata_path(source_name)?;
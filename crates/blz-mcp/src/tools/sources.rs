@@ -158,8 +158,15 @@ struct FetchedContent {
 
 /// Fetch source content from URL.
 async fn fetch_source_content(url: &str) -> McpResult<FetchedContent> {
-    let fetcher = blz_core::Fetcher::new()
-        .map_err(|e| McpError::Internal(format!("Failed to create fetcher: {e}")))?;
+    let defaults = blz_core::Config::load().ok().map(|config| config.defaults);
+    let network = defaults
+        .as_ref()
+        .map(blz_core::NetworkOptions::from_defaults)
+        .unwrap_or_default();
+    let throttle = defaults.and_then(|defaults| defaults.throttle);
+    let fetcher = blz_core::Fetcher::with_network(&network)
+        .map_err(|e| McpError::Internal(format!("Failed to create fetcher: {e}")))?
+        .with_throttle(throttle);
 
     let fetch_result = fetcher
         .fetch_with_cache(url, None, None)
@@ -229,6 +236,7 @@ fn build_source_metadata(
             segmentation: "structured".to_string(),
         }),
         filter_stats: None,
+        health: None,
     }
 }
 
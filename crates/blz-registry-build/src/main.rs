@@ -18,6 +18,7 @@ struct SourceToml {
     #[serde(rename = "verifiedAt")]
     verified_at: Option<String>,
     aliases: Option<HashMap<String, Vec<String>>>,
+    integrity: Option<HashMap<String, String>>,
 }
 
 #[derive(Debug, Serialize)]
@@ -39,6 +40,8 @@ struct RegistrySource {
     verified_at: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     aliases: Option<HashMap<String, Vec<String>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    integrity: Option<HashMap<String, String>>,
 }
 
 #[derive(Debug, Serialize)]
@@ -101,6 +104,7 @@ fn main() -> Result<()> {
             registered_at,
             verified_at,
             aliases: source.aliases,
+            integrity: source.integrity,
         });
     }
 